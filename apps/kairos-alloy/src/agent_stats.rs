@@ -0,0 +1,101 @@
+use std::collections::VecDeque;
+
+// Latencies kept for the percentiles; older calls drop out so p50/p95 follow the server's
+// current behaviour rather than the whole run.
+const LATENCY_WINDOW: usize = 500;
+
+#[derive(Debug, Clone)]
+pub struct AgentCallSample {
+    pub attempts: u32,
+    pub duration_ms: u64,
+    pub status: Option<u16>,
+    pub error: Option<String>,
+}
+
+// Remote-agent diagnostics for the running backtest/paper task, fed by the agent client's call
+// observer. A failed call is answered with `agent.fallback_action`, so failures count as fallbacks.
+#[derive(Debug, Clone, Default)]
+pub struct AgentStats {
+    pub calls: u64,
+    pub retries: u64,
+    pub fallbacks: u64,
+    pub last_status: Option<u16>,
+    // The latest error and the call number it happened on.
+    pub last_error: Option<(u64, String)>,
+    latencies: VecDeque<u64>,
+}
+
+impl AgentStats {
+    pub fn record(&mut self, sample: AgentCallSample) {
+        self.calls += 1;
+        self.retries += u64::from(sample.attempts.saturating_sub(1));
+        self.last_status = sample.status;
+        if let Some(error) = sample.error {
+            self.fallbacks += 1;
+            self.last_error = Some((self.calls, error));
+        }
+        self.latencies.push_back(sample.duration_ms);
+        while self.latencies.len() > LATENCY_WINDOW {
+            self.latencies.pop_front();
+        }
+    }
+
+    // Nearest-rank percentile of the recent latencies, in ms.
+    pub fn latency_percentile(&self, pct: f64) -> Option<u64> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<u64> = self.latencies.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.clamp(1, sorted.len()) - 1])
+    }
+
+    pub fn fallback_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.fallbacks as f64 / self.calls as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AgentCallSample, AgentStats};
+
+    fn sample(attempts: u32, duration_ms: u64, error: Option<&str>) -> AgentCallSample {
+        AgentCallSample {
+            attempts,
+            duration_ms,
+            status: if error.is_some() {
+                Some(503)
+            } else {
+                Some(200)
+            },
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn counts_retries_fallbacks_and_latency_percentiles() {
+        let mut stats = AgentStats::default();
+        assert_eq!(stats.latency_percentile(50.0), None);
+        for ms in 1..=18 {
+            stats.record(sample(1, ms * 10, None));
+        }
+        stats.record(sample(3, 900, None));
+        stats.record(sample(4, 1_500, Some("retries exhausted")));
+
+        assert_eq!((stats.calls, stats.retries, stats.fallbacks), (20, 5, 1));
+        assert_eq!(stats.latency_percentile(50.0), Some(100));
+        assert_eq!(stats.latency_percentile(95.0), Some(900));
+        assert_eq!(stats.latency_percentile(100.0), Some(1_500));
+        assert_eq!(
+            stats.last_error,
+            Some((20, "retries exhausted".to_string()))
+        );
+        assert_eq!(stats.last_status, Some(503));
+        assert!((stats.fallback_rate() - 0.05).abs() < 1e-12);
+    }
+}
@@ -1,30 +1,107 @@
+use crate::agent_stats::AgentStats;
+use crate::candle_chart::CandleChart;
+use crate::config_diff::ConfigDiffView;
+use crate::config_form::{ConfigForm, FormFocus};
+use crate::data_quality::DataQualityView;
+use crate::equity_view::EquityView;
+use crate::export::{leaderboard_table, runs_table, trades_table, write_export, ExportFormat};
+use crate::ingest_form::{IngestForm, IngestProgress};
+use crate::job_queue::{JobQueue, JobState};
+use crate::jobs::JobKind;
+use crate::keymap::{Action, Keymap};
+use crate::live_equity::LiveEquity;
 use crate::logging::LogStore;
+use crate::metrics_exporter;
+use crate::metrics_view::MetricsPanel;
+use crate::notify::{format_elapsed, Toast};
+use crate::onboarding::{Onboarding, Step, StepState};
+use crate::positions::PositionPanel;
+use crate::risk_status::RiskSample;
+use crate::run_browser::RunBrowser;
+use crate::schedule::{DueRun, ScheduleEntry, Scheduler};
+use crate::settings::{load_settings_from, store_settings_to, TuiSettings};
+use crate::sweep_history::SweepHistory;
+use crate::sweep_leaderboard::SweepLeaderboard;
 use crate::tasks::{
-    AgentLlmRuntime, StreamStatusSample, SweepProgressSample, TaskEvent, TaskKind, TaskRunner,
-    TradeSample,
+    AgentLlmRuntime, IngestProgressSample, StreamStatusSample, SweepProgressSample, TaskEvent,
+    TaskKind, TaskRunner, TradeSample,
 };
+use crate::theme::Theme;
+use crate::trade_list::TradeList;
+use crate::tui_state::{load_state_from, restorable_view, store_state_to, TuiState};
 use crossterm::event::{Event as CtEvent, KeyCode, KeyEvent, KeyModifiers};
+use kairos_application::paper_trading::live::LIVE_JOURNAL_FILE;
+use kairos_domain::entities::run_summary::RunSummary;
+use kairos_domain::services::strategy::hot_swap::StrategyChange;
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::net::{TcpStream, ToSocketAddrs};
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 const MAX_SERIES_POINTS: usize = 600;
 const MAX_TRADES: usize = 200;
 const DEFAULT_CONFIG_DIR: &str = "platform/ops/configs";
 const DEFAULT_AGENT_LLM_SCRIPT: &str = "apps/agents/agent-llm/agent_llm.py";
 const DEFAULT_SWEEP_CONFIG: &str = "platform/ops/configs/sweeps/sma_grid.toml";
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+const RECENT_CONFIGS_FILE: &str = "recent_configs.json";
+const SETTINGS_FILE: &str = "settings.json";
+const KEYMAP_FILE: &str = "keymap.toml";
+const STATE_FILE: &str = "tui_state.json";
+const SCHEDULE_FILE: &str = "schedule.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ViewId {
     MainMenu,
     Setup,
+    ConfigEditor,
     Backtest,
     Monitor,
     Reports,
+    Runs,
+    Chart,
+    Equity,
+    Trades,
+    ConfigDiff,
     Experiments,
+    Leaderboard,
+    SweepHistory,
+    Ingest,
+    Jobs,
+    Schedule,
+    DataQuality,
+    Metrics,
+    Onboarding,
+}
+
+// The state shown for the current (or last) task. `x` moves a running task to `Cancelling` until its
+// thread notices at the next bar/trial/window and reports back with a cancelled error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskState {
+    Idle,
+    Running,
+    Paused,
+    Cancelling,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl TaskState {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Running => "running",
+            Self::Paused => "PAUSED",
+            Self::Cancelling => "CANCELLING",
+            Self::Succeeded => "done",
+            Self::Failed => "FAILED",
+            Self::Cancelled => "CANCELLED",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -45,6 +122,7 @@ pub enum ReportsMode {
 pub struct ReportsRun {
     pub run_id: String,
     pub line: String,
+    pub notes: Vec<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -218,6 +296,11 @@ pub struct App {
     pub config_input: TextInput,
     pub setup_focus: SetupFocus,
     pub quick_edit: QuickEditState,
+    pub config_form: Option<ConfigForm>,
+    pub ingest_form: IngestForm,
+    pub job_queue: JobQueue,
+    pub scheduler: Scheduler,
+    pub onboarding: Onboarding,
     pub available_configs: Vec<PathBuf>,
     pub recent_config_count: usize,
     pub selected_config: usize,
@@ -228,13 +311,32 @@ pub struct App {
     pub require_validate_before_run: bool,
     pub last_validate_ok: Option<bool>,
     pub paper_realtime: bool,
+    // Realtime paper streams from the built-in simulator instead of the exchange.
+    pub paper_simulated: bool,
+    pub paper_resume: bool,
+    // Open while typing a strategy change for the running paper session.
+    pub strategy_prompt: Option<TextInput>,
     pub stream_status: Option<StreamStatusSample>,
+    // Position and working orders of the running (or last) paper session.
+    pub positions: PositionPanel,
 
     pub logs: Arc<parking_lot::Mutex<LogStore>>,
     pub log_scroll: usize,
 
     pub price_series: VecDeque<(f64, f64)>,
     pub equity_series: VecDeque<(f64, f64)>,
+    pub live_equity: LiveEquity,
+    pub risk_sample: Option<RiskSample>,
+    pub agent_stats: AgentStats,
+    // The last validate report, for the data-quality dashboard.
+    pub data_quality: Option<DataQualityView>,
+    pub metrics: MetricsPanel,
+    // Where `x` in Metrics starts the exporter; `a` edits it through `metrics_addr_input`.
+    pub metrics_addr: String,
+    pub metrics_addr_input: Option<TextInput>,
+    pub toast: Option<Toast>,
+    // Bell/OSC sequences for the terminal, written by the run loop after the next frame.
+    terminal_alerts: String,
     pub trades: VecDeque<TradeSample>,
     pub trade_scroll: usize,
 
@@ -253,11 +355,19 @@ pub struct App {
     pub reports_analyzer_text: Option<String>,
     pub reports_scroll: usize,
 
+    pub run_browser: RunBrowser,
+    pub candle_chart: Option<CandleChart>,
+    pub equity_view: Option<EquityView>,
+    pub trade_list: Option<TradeList>,
+    pub config_diff: Option<ConfigDiffView>,
+
     pub experiments_focus: ExperimentsFocus,
     pub experiments_sweep_path: TextInput,
     pub experiments_parallelism: TextInput,
     pub experiments_resume: bool,
     pub experiments_progress: Option<SweepProgressSample>,
+    pub sweep_leaderboard: SweepLeaderboard,
+    pub sweep_history: SweepHistory,
 
     pub dirty: bool,
     spinner: usize,
@@ -266,6 +376,12 @@ pub struct App {
     info_expires_at: Option<Instant>,
 
     managed_llm_agent: Option<Child>,
+
+    pub settings: TuiSettings,
+    pub theme: Theme,
+    pub keymap: Keymap,
+    pub keymap_path: Option<PathBuf>,
+    pub show_help: bool,
 }
 
 impl App {
@@ -288,6 +404,11 @@ impl App {
             config_input: TextInput::new(config_path_str),
             setup_focus: SetupFocus::Input,
             quick_edit: QuickEditState::new(),
+            config_form: None,
+            ingest_form: IngestForm::new(),
+            job_queue: JobQueue::new(),
+            scheduler: Scheduler::default(),
+            onboarding: Onboarding::new(),
             available_configs: Vec::new(),
             recent_config_count: 0,
             selected_config: 0,
@@ -297,11 +418,26 @@ impl App {
             require_validate_before_run: false,
             last_validate_ok: None,
             paper_realtime: false,
+            paper_simulated: false,
+            paper_resume: false,
+            strategy_prompt: None,
             stream_status: None,
+            positions: PositionPanel::default(),
             logs,
             log_scroll: 0,
             price_series: VecDeque::new(),
             equity_series: VecDeque::new(),
+            live_equity: LiveEquity::default(),
+            risk_sample: None,
+            agent_stats: AgentStats::default(),
+            data_quality: None,
+            metrics: MetricsPanel::default(),
+            metrics_addr: metrics_exporter::address()
+                .map(|addr| addr.to_string())
+                .unwrap_or_else(|| metrics_exporter::DEFAULT_ADDR.to_string()),
+            metrics_addr_input: None,
+            toast: None,
+            terminal_alerts: String::new(),
             trades: VecDeque::new(),
             trade_scroll: 0,
             status: RunStatus {
@@ -322,18 +458,203 @@ impl App {
             reports_selected_analyzer: 0,
             reports_analyzer_text: None,
             reports_scroll: 0,
+            run_browser: RunBrowser::new(PathBuf::new()),
+            candle_chart: None,
+            equity_view: None,
+            trade_list: None,
+            config_diff: None,
             experiments_focus: ExperimentsFocus::SweepPath,
             experiments_sweep_path: TextInput::new(DEFAULT_SWEEP_CONFIG.to_string()),
             experiments_parallelism: TextInput::new(String::new()),
             experiments_resume: false,
             experiments_progress: None,
+            sweep_leaderboard: SweepLeaderboard::new(),
+            sweep_history: SweepHistory::new(),
             dirty: true,
             spinner: 0,
             last_error: None,
             info_message: None,
             info_expires_at: None,
             managed_llm_agent: None,
+            settings: TuiSettings::default(),
+            theme: Theme::default(),
+            keymap: Keymap::defaults(),
+            keymap_path: None,
+            show_help: false,
+        }
+    }
+
+    // Applies the saved settings, keymap and schedule; a broken file is reported and its defaults
+    // are kept.
+    pub fn load_settings(&mut self) {
+        if let Some(path) = tui_store_path(SETTINGS_FILE) {
+            match load_settings_from(&path) {
+                Ok(settings) => {
+                    self.theme = Theme::from_name(settings.theme);
+                    self.settings = settings;
+                }
+                Err(err) => self.set_error_and_clear_info(&err),
+            }
+        }
+        if let Some(path) = tui_store_path(KEYMAP_FILE) {
+            match Keymap::load(&path) {
+                Ok(keymap) => self.keymap = keymap,
+                Err(err) => self.set_error_and_clear_info(&format!("{err} (using default keys)")),
+            }
+            self.keymap_path = Some(path);
+        }
+        if let Some(path) = tui_store_path(SCHEDULE_FILE) {
+            self.reload_schedule(&path);
+        }
+        self.dirty = true;
+    }
+
+    fn reload_schedule(&mut self, path: &Path) {
+        match Scheduler::load(path) {
+            Ok(scheduler) => self.scheduler = scheduler,
+            Err(err) => {
+                // Without a path nothing is saved, so the broken file stays for the user to fix
+                // (then `g` in Schedule reloads it).
+                self.scheduler = Scheduler::default();
+                self.set_error_and_clear_info(&format!("{err} (schedule disabled)"));
+            }
+        }
+    }
+
+    // A toast (plus the bell/OSC alerts the settings ask for) for a task or job that ran at least
+    // `notify.min_secs`. Cancelled ones are not reported: the user asked for them to stop.
+    fn notify_finished(&mut self, what: &str, failed: bool, outcome: &str, elapsed: Duration) {
+        let notify = &self.settings.notify;
+        if !notify.wants(elapsed) {
+            return;
+        }
+        let verdict = if failed { "failed" } else { "finished" };
+        let title = format!("kairos-alloy: {what} {verdict}");
+        let body = format!("after {}: {outcome}", format_elapsed(elapsed));
+        self.terminal_alerts
+            .push_str(&notify.terminal_alert(&title, &body));
+        self.toast = Some(Toast::new(format!("{what} {verdict} {body}"), failed));
+        self.dirty = true;
+    }
+
+    pub fn take_terminal_alerts(&mut self) -> String {
+        std::mem::take(&mut self.terminal_alerts)
+    }
+
+    pub fn current_state(&self) -> TuiState {
+        TuiState {
+            view: Some(self.active_view),
+            config_path: self.config_path.clone(),
+            selected_run_dir: self
+                .run_browser
+                .selected_run()
+                .map(|run| run.run_dir.clone()),
+            reports_run_id: self
+                .reports_runs
+                .get(self.reports_selected_run)
+                .map(|run| run.run_id.clone()),
+            log_scroll: self.log_scroll,
+            trade_scroll: self.trade_scroll,
+            metrics_scroll: self.metrics.scroll,
+        }
+    }
+
+    pub fn save_state(&self) {
+        let Some(path) = tui_store_path(STATE_FILE) else {
+            return;
+        };
+        if let Err(err) = store_state_to(&path, &self.current_state()) {
+            tracing::warn!(error = %err, "failed to save the TUI session state");
+        }
+    }
+
+    // Reopens the last session's view, run selections and scroll positions. Its config is picked
+    // before startup (see `last_session_config`); a config that failed to load keeps the user in
+    // Setup to see the error.
+    pub fn restore_state(&mut self) {
+        let Some(path) = tui_store_path(STATE_FILE) else {
+            return;
+        };
+        let state = match load_state_from(&path) {
+            Ok(state) => state,
+            Err(err) => {
+                self.set_error_and_clear_info(&err);
+                return;
+            }
+        };
+        self.apply_state(state);
+    }
+
+    fn apply_state(&mut self, state: TuiState) {
+        if self.last_error.is_some() {
+            return;
+        }
+        if let Some(run_dir) = &state.selected_run_dir {
+            self.refresh_run_browser();
+            self.run_browser.select_run_dir(run_dir);
+        }
+        if let Some(run_id) = &state.reports_run_id {
+            self.refresh_reports_runs();
+            if let Some(idx) = self
+                .reports_runs
+                .iter()
+                .position(|run| &run.run_id == run_id)
+            {
+                self.reports_selected_run = idx;
+            }
+        }
+        self.log_scroll = state.log_scroll;
+        self.trade_scroll = state.trade_scroll;
+        self.metrics.scroll = state.metrics_scroll;
+        // Opening Reports/Runs refreshes them, keeping the selections made above.
+        if let Some(view) = state.view.map(restorable_view) {
+            self.open_view(view);
+        }
+    }
+
+    fn toggle_bell(&mut self) {
+        self.settings.notify.bell = !self.settings.notify.bell;
+        let applied = format!(
+            "bell on finish: {}",
+            if self.settings.notify.bell {
+                "on"
+            } else {
+                "off"
+            }
+        );
+        self.save_settings(&applied);
+    }
+
+    fn cycle_desktop_notify(&mut self) {
+        self.settings.notify.desktop = self.settings.notify.desktop.next();
+        let applied = format!(
+            "desktop notifications: {}",
+            self.settings.notify.desktop.as_str()
+        );
+        self.save_settings(&applied);
+    }
+
+    fn save_settings(&mut self, applied: &str) {
+        let saved = match tui_store_path(SETTINGS_FILE) {
+            Some(path) => store_settings_to(&path, &self.settings),
+            None => Ok(()),
+        };
+        match saved {
+            Ok(()) => {
+                self.last_error = None;
+                self.info_message = Some(applied.to_string());
+                self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(3));
+            }
+            Err(err) => self.set_error_and_clear_info(&format!("{applied} (not saved: {err})")),
         }
+        self.dirty = true;
+    }
+
+    fn cycle_theme(&mut self) {
+        self.settings.theme = self.settings.theme.next();
+        self.theme = Theme::from_name(self.settings.theme);
+        let applied = format!("theme: {}", self.settings.theme.as_str());
+        self.save_settings(&applied);
     }
 
     pub fn spawn_input_reader(&self, tx: tokio::sync::mpsc::UnboundedSender<TaskEvent>) {
@@ -356,8 +677,25 @@ impl App {
             }
         }
 
+        self.run_due_schedule();
+
+        let now = Instant::now();
+        if self.metrics.due(now) && self.metrics.sample(now) && self.active_view == ViewId::Metrics
+        {
+            self.dirty = true;
+        }
+
+        if self
+            .toast
+            .as_ref()
+            .is_some_and(|toast| now >= toast.expires_at)
+        {
+            self.toast = None;
+            self.dirty = true;
+        }
+
         if let Some(until) = self.info_expires_at {
-            if Instant::now() >= until {
+            if now >= until {
                 self.info_message = None;
                 self.info_expires_at = None;
                 self.dirty = true;
@@ -371,6 +709,12 @@ impl App {
             TaskEvent::Progress(sample) => {
                 self.price_series.push_back((sample.x, sample.price));
                 self.equity_series.push_back((sample.x, sample.equity));
+                self.live_equity.push(sample.equity);
+                self.risk_sample = sample.position_qty.map(|position_qty| RiskSample {
+                    position_qty,
+                    price: sample.price,
+                    equity: sample.equity,
+                });
                 while self.price_series.len() > MAX_SERIES_POINTS {
                     self.price_series.pop_front();
                 }
@@ -386,26 +730,125 @@ impl App {
                 self.dirty = true;
                 Ok(false)
             }
-            TaskEvent::SweepProgress(progress) => {
+            TaskEvent::DataQuality(report) => {
+                self.data_quality = Some(DataQualityView::from_validate_report(&report));
+                if self.active_view == ViewId::Backtest {
+                    self.active_view = ViewId::DataQuality;
+                }
+                self.dirty = true;
+                Ok(false)
+            }
+            TaskEvent::AgentCall(sample) => {
+                self.agent_stats.record(sample);
+                self.dirty = true;
+                Ok(false)
+            }
+            TaskEvent::SweepProgress(mut progress) => {
+                if let Some(trial) = progress.last_trial.take() {
+                    self.sweep_leaderboard.record(trial);
+                }
                 self.experiments_progress = Some(progress);
                 self.dirty = true;
                 Ok(false)
             }
+            TaskEvent::IngestProgress(sample) => {
+                let progress = &mut self.ingest_form.progress;
+                match sample {
+                    IngestProgressSample::Window {
+                        first_ts,
+                        last_ts,
+                        rows,
+                        total_rows,
+                    } => progress.record_window(first_ts, last_ts, rows, total_rows),
+                    IngestProgressSample::Retry {
+                        attempt,
+                        delay_ms,
+                        reason,
+                    } => progress.record_retry(attempt, delay_ms, &reason),
+                }
+                self.dirty = true;
+                Ok(false)
+            }
+            TaskEvent::JobLog { id, line } => {
+                self.job_queue.push_log(id, line);
+                self.dirty = true;
+                Ok(false)
+            }
+            TaskEvent::JobFinished { id, result } => {
+                let failed = result.is_err();
+                self.job_queue.finish(id, result);
+                if failed {
+                    tracing::warn!(job_id = id, "queued job did not finish ok");
+                }
+                let outcome = self
+                    .job_queue
+                    .jobs
+                    .iter()
+                    .find(|job| job.id == id)
+                    .map(|job| {
+                        format!(
+                            "{}: {}",
+                            job.state.as_str(),
+                            job.summary.clone().unwrap_or_default()
+                        )
+                    });
+                if let Some(outcome) = outcome {
+                    if self.scheduler.finish_job(id, outcome) {
+                        self.save_schedule();
+                    }
+                }
+                if let Some(job) = self.job_queue.jobs.iter().find(|job| job.id == id) {
+                    if job.state != JobState::Cancelled {
+                        let elapsed = Duration::from_millis(job.elapsed_ms.unwrap_or(0));
+                        let what = format!("job #{id} {}", job.kind.as_str());
+                        let outcome = job.summary.clone().unwrap_or_default();
+                        self.notify_finished(&what, failed, &outcome, elapsed);
+                    }
+                }
+                self.start_ready_jobs();
+                self.dirty = true;
+                Ok(false)
+            }
             TaskEvent::StreamStatus(status) => {
                 self.stream_status = Some(status);
                 self.dirty = true;
                 Ok(false)
             }
+            TaskEvent::Position(sample) => {
+                self.positions.record(sample);
+                self.dirty = true;
+                Ok(false)
+            }
             TaskEvent::TaskFinished(result) => {
                 if self.status.kind == Some(TaskKind::Validate { strict: true })
                     || self.status.kind == Some(TaskKind::Validate { strict: false })
                 {
                     self.last_validate_ok = Some(result.is_ok());
                 }
+                let was_cancelling = self.cancel_requested;
+                self.strategy_prompt = None;
+                let elapsed = self
+                    .status
+                    .started_at
+                    .map(|at| at.elapsed())
+                    .unwrap_or_default();
                 self.status.running = false;
                 self.status.started_at = None;
                 self.paused = false;
                 self.cancel_requested = false;
+                if self.status.kind == Some(TaskKind::Ingest)
+                    && self.onboarding.is_running(Step::SampleIngest)
+                {
+                    self.onboarding.set_state(
+                        Step::SampleIngest,
+                        match &result {
+                            Ok(ok) => {
+                                StepState::Done(ok.lines().last().unwrap_or("done").to_string())
+                            }
+                            Err(err) => StepState::Failed(err.clone()),
+                        },
+                    );
+                }
                 self.status.last_result = Some(match result {
                     Ok(ok) => Ok(ok),
                     Err(err) => {
@@ -416,10 +859,66 @@ impl App {
                         }
                     }
                 });
+                if was_cancelling {
+                    let kind = self.status.kind.map_or("task", crate::ui::task_kind_label);
+                    self.info_message = Some(if self.task_state() == TaskState::Cancelled {
+                        format!("{kind} cancelled")
+                    } else {
+                        format!("{kind} finished before the cancel took effect")
+                    });
+                    self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(3));
+                } else if let Some(result) = &self.status.last_result {
+                    let kind = self.status.kind.map_or("task", crate::ui::task_kind_label);
+                    let (failed, outcome) = match result {
+                        Ok(ok) => (false, ok.lines().last().unwrap_or_default().to_string()),
+                        Err(err) => (true, err.clone()),
+                    };
+                    self.notify_finished(kind, failed, &outcome, elapsed);
+                }
+                // A finished backtest/replay opens its equity and underwater curves.
+                let finished_run =
+                    matches!(self.status.kind, Some(TaskKind::Backtest | TaskKind::Paper))
+                        && matches!(self.status.last_result, Some(Ok(_)))
+                        && !was_cancelling
+                        && self.active_view == ViewId::Monitor;
+                let run_dir = self
+                    .config
+                    .as_ref()
+                    .filter(|_| finished_run)
+                    .map(|cfg| Path::new(&cfg.paths.out_dir).join(&cfg.run.run_id));
+                if let Some(run_dir) = run_dir {
+                    self.open_equity_view(&run_dir, ViewId::Monitor);
+                }
                 self.stream_status = None;
                 self.dirty = true;
                 Ok(false)
             }
+            TaskEvent::OnboardingStep { step, result } => {
+                self.onboarding.set_state(
+                    step,
+                    match result {
+                        Ok(ok) => StepState::Done(ok),
+                        Err(err) => StepState::Failed(err),
+                    },
+                );
+                self.dirty = true;
+                Ok(false)
+            }
+            TaskEvent::ChartLoaded(result) => {
+                match result {
+                    Ok(chart) => {
+                        self.info_message = None;
+                        self.info_expires_at = None;
+                        self.candle_chart = Some(CandleChart::new(chart));
+                        if self.active_view == ViewId::Runs {
+                            self.active_view = ViewId::Chart;
+                        }
+                    }
+                    Err(err) => self.set_error_and_clear_info(&format!("chart: {err}")),
+                }
+                self.dirty = true;
+                Ok(false)
+            }
         }
     }
 
@@ -439,57 +938,142 @@ impl App {
             return Ok(true);
         }
 
+        // While typing, characters (and the arrows, for the cursor) go to the input untouched.
+        let typing = self.is_typing();
+        if self.keymap.matches(Action::Help, &key)
+            && !(typing && matches!(key.code, KeyCode::Char(_)))
+        {
+            self.show_help = !self.show_help;
+            self.dirty = true;
+            return Ok(false);
+        }
+        if self.show_help {
+            if self.keymap.matches(Action::Back, &key) || key.code == KeyCode::Esc {
+                self.show_help = false;
+                self.dirty = true;
+            }
+            return Ok(false);
+        }
+        let key = if typing {
+            key
+        } else {
+            match self.keymap.translate(key) {
+                Some(key) => key,
+                None => return Ok(false),
+            }
+        };
+
         match self.active_view {
             ViewId::MainMenu => self.handle_menu_keys(key),
             ViewId::Setup => self.handle_setup_keys(key),
+            ViewId::ConfigEditor => self.handle_config_editor_keys(key),
             ViewId::Backtest => self.handle_backtest_keys(key),
             ViewId::Monitor => self.handle_backtest_keys(key), // Share controls with Backtest
             ViewId::Reports => self.handle_reports_keys(key),
+            ViewId::Runs => self.handle_runs_keys(key),
+            ViewId::Chart => self.handle_chart_keys(key),
+            ViewId::Equity => self.handle_equity_keys(key),
+            ViewId::Trades => self.handle_trade_list_keys(key),
+            ViewId::ConfigDiff => self.handle_config_diff_keys(key),
             ViewId::Experiments => self.handle_experiments_keys(key),
+            ViewId::Leaderboard => self.handle_leaderboard_keys(key),
+            ViewId::SweepHistory => self.handle_sweep_history_keys(key),
+            ViewId::Ingest => self.handle_ingest_keys(key),
+            ViewId::Jobs => self.handle_jobs_keys(key),
+            ViewId::Schedule => self.handle_schedule_keys(key),
+            ViewId::DataQuality => self.handle_data_quality_keys(key),
+            ViewId::Metrics => self.handle_metrics_keys(key),
+            ViewId::Onboarding => self.handle_onboarding_keys(key),
+        }
+    }
+
+    fn is_typing(&self) -> bool {
+        match self.active_view {
+            ViewId::Setup => self.setup_focus != SetupFocus::List,
+            ViewId::ConfigEditor => self
+                .config_form
+                .as_ref()
+                .is_some_and(|form| form.focus != FormFocus::Fields),
+            ViewId::Experiments => true,
+            ViewId::Ingest => self.ingest_form.editing,
+            ViewId::Onboarding => self.onboarding.editing,
+            ViewId::SweepHistory => self.sweep_history.editing_filter,
+            ViewId::Trades => self
+                .trade_list
+                .as_ref()
+                .is_some_and(|list| list.editing_filter),
+            ViewId::Metrics => self.metrics_addr_input.is_some(),
+            ViewId::Backtest | ViewId::Monitor => self.strategy_prompt.is_some(),
+            _ => false,
         }
     }
 
     fn handle_menu_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
         match key.code {
-            KeyCode::Char('q') => return Ok(true),
+            _ if self.keymap.matches(Action::Quit, &key) => return Ok(true),
+            _ if self.keymap.matches(Action::Theme, &key) => self.cycle_theme(),
+            KeyCode::Char('b') => self.toggle_bell(),
+            KeyCode::Char('o') => self.cycle_desktop_notify(),
             KeyCode::Up => {
                 self.menu_index = self.menu_index.saturating_sub(1);
                 self.dirty = true;
             }
             KeyCode::Down => {
-                self.menu_index = (self.menu_index + 1).min(5);
+                self.menu_index = (self.menu_index + 1).min(10);
                 self.dirty = true;
             }
             KeyCode::Enter => {
-                self.active_view = match self.menu_index {
-                    0 => {
-                        self.refresh_available_configs();
-                        self.selected_config = 0;
-                        self.setup_focus = if self.available_configs.is_empty() {
-                            SetupFocus::Input
-                        } else {
-                            SetupFocus::List
-                        };
-                        ViewId::Setup
-                    }
+                let view = match self.menu_index {
+                    0 => ViewId::Setup,
                     1 => ViewId::Backtest,
                     2 => ViewId::Monitor,
-                    3 => {
-                        self.refresh_reports_runs();
-                        self.reports_mode = ReportsMode::Runs;
-                        ViewId::Reports
-                    }
-                    4 => ViewId::Experiments,
-                    5 => return Ok(true),
+                    3 => ViewId::Reports,
+                    4 => ViewId::Runs,
+                    5 => ViewId::Experiments,
+                    6 => ViewId::Ingest,
+                    7 => ViewId::Jobs,
+                    8 => ViewId::Schedule,
+                    9 => ViewId::Metrics,
+                    10 => return Ok(true),
                     _ => ViewId::MainMenu,
                 };
-                self.dirty = true;
+                self.open_view(view);
             }
             _ => {}
         }
         Ok(false)
     }
 
+    // Switches to a view from the menu (or a restored session), loading what it shows.
+    fn open_view(&mut self, view: ViewId) {
+        match view {
+            ViewId::Setup => {
+                self.refresh_available_configs();
+                self.selected_config = 0;
+                self.setup_focus = if self.available_configs.is_empty() {
+                    SetupFocus::Input
+                } else {
+                    SetupFocus::List
+                };
+            }
+            ViewId::Reports => {
+                self.refresh_reports_runs();
+                self.reports_mode = ReportsMode::Runs;
+            }
+            ViewId::Runs => self.refresh_run_browser(),
+            ViewId::Ingest => {
+                if self.ingest_form.inputs.iter().all(|i| i.value.is_empty()) {
+                    if let Some(cfg) = self.config.as_ref() {
+                        self.ingest_form.fill_from_config(cfg);
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.active_view = view;
+        self.dirty = true;
+    }
+
     fn handle_reports_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
         match key.code {
             KeyCode::Esc => {
@@ -605,71 +1189,529 @@ impl App {
         Ok(false)
     }
 
-    fn reports_out_dir(&self) -> PathBuf {
-        self.config
-            .as_ref()
-            .map(|c| PathBuf::from(&c.paths.out_dir))
-            .unwrap_or_else(|| self.default_out_dir.clone())
+    fn handle_runs_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
+        if self.run_browser.confirm_delete {
+            if key.code == KeyCode::Char('y') {
+                let result = self.run_browser.delete_selected();
+                self.report_run_browser_result(result);
+            } else {
+                self.run_browser.confirm_delete = false;
+                self.info_message = Some("delete cancelled".to_string());
+                self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(2));
+            }
+            self.dirty = true;
+            return Ok(false);
+        }
+        if let Some(format) = self.export_format(&key) {
+            self.export_view(format);
+            return Ok(false);
+        }
+        match key.code {
+            KeyCode::Esc => {
+                if self.run_browser.detail {
+                    self.run_browser.detail = false;
+                } else {
+                    self.active_view = ViewId::MainMenu;
+                }
+            }
+            KeyCode::Char('g') | KeyCode::F(5) => {
+                self.refresh_run_browser();
+                self.info_message = Some("refreshed runs list".to_string());
+                self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(2));
+            }
+            KeyCode::Up => self.run_browser.select_prev(),
+            KeyCode::Down => self.run_browser.select_next(),
+            KeyCode::Enter => {
+                self.run_browser.detail =
+                    !self.run_browser.detail && self.run_browser.selected_run().is_some();
+            }
+            KeyCode::Char('s') => self.run_browser.cycle_sort(),
+            KeyCode::Char('r') => {
+                let result = self.run_browser.regenerate_report();
+                self.report_run_browser_result(result);
+            }
+            KeyCode::Char('c') => self.load_selected_run_chart(),
+            KeyCode::Char('u') => {
+                if let Some(run) = self.run_browser.selected_run() {
+                    let run_dir = PathBuf::from(&run.run_dir);
+                    self.open_equity_view(&run_dir, ViewId::Runs);
+                }
+            }
+            KeyCode::Char('t') => {
+                if let Some(run) = self.run_browser.selected_run() {
+                    let run_dir = PathBuf::from(&run.run_dir);
+                    self.open_trade_list(&run_dir, ViewId::Runs);
+                }
+            }
+            KeyCode::Char('m') => self.toggle_diff_base(),
+            KeyCode::Char('v') => self.open_config_diff(),
+            KeyCode::Char('f') => self.follow_live_run(),
+            KeyCode::Char('d') => {
+                if let Some(run) = self.run_browser.selected_run() {
+                    self.info_message = Some(format!("delete {}? press y to confirm", run.run_dir));
+                    self.info_expires_at = None;
+                    self.run_browser.confirm_delete = true;
+                }
+            }
+            _ => return Ok(false),
+        }
+        self.dirty = true;
+        Ok(false)
     }
 
-    fn refresh_reports_runs(&mut self) {
-        let out_dir = self.reports_out_dir();
-        let mut entries: Vec<_> = std::fs::read_dir(&out_dir)
-            .ok()
-            .into_iter()
-            .flat_map(|it| it.filter_map(|e| e.ok()).collect::<Vec<_>>())
-            .collect();
-        entries.sort_by_key(|e| e.file_name());
-
-        let mut runs: Vec<ReportsRun> = Vec::new();
-        for e in entries {
-            if !e.file_type().map(|ft| ft.is_dir()).unwrap_or(false) {
-                continue;
-            }
-            let run_id = e.file_name().to_string_lossy().to_string();
-            let run_dir = out_dir.join(&run_id);
-            let summary_path = run_dir.join("summary.json");
+    // The bars come from the database of the loaded config, so charting needs one.
+    fn load_selected_run_chart(&mut self) {
+        let Some(run) = self.run_browser.selected_run() else {
+            return;
+        };
+        let Some(config) = self.config.clone() else {
+            self.set_error_and_clear_info(
+                "load a config in Setup first: the chart reads OHLCV from its database",
+            );
+            return;
+        };
+        let run_dir = PathBuf::from(&run.run_dir);
+        self.last_error = None;
+        self.info_message = Some(format!("loading chart for {}...", run.run_id));
+        self.info_expires_at = None;
+        self.task_runner.load_chart(config, run_dir);
+    }
 
-            let analyzer_count = std::fs::read_dir(run_dir.join("analyzers"))
-                .ok()
-                .map(|it| {
-                    it.filter_map(|e| e.ok())
-                        .filter(|e| {
-                            e.path()
-                                .extension()
-                                .and_then(|s| s.to_str())
-                                .is_some_and(|s| s.eq_ignore_ascii_case("json"))
-                        })
-                        .count()
-                })
-                .unwrap_or(0);
+    fn handle_chart_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
+        let Some(chart) = self.candle_chart.as_mut() else {
+            self.active_view = ViewId::Runs;
+            self.dirty = true;
+            return Ok(false);
+        };
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.active_view = ViewId::Runs,
+            KeyCode::Char('+') | KeyCode::Char('=') => chart.zoom_in(),
+            KeyCode::Char('-') => chart.zoom_out(),
+            KeyCode::Left | KeyCode::Char('h') => chart.pan(-1),
+            KeyCode::Right | KeyCode::Char('l') => chart.pan(1),
+            KeyCode::PageUp => chart.pan(-4),
+            KeyCode::PageDown => chart.pan(4),
+            KeyCode::Home => chart.pan_to_start(),
+            KeyCode::End => chart.pan_to_end(),
+            _ => return Ok(false),
+        }
+        self.dirty = true;
+        Ok(false)
+    }
 
-            let line = if summary_path.exists() {
-                match std::fs::read_to_string(&summary_path)
-                    .ok()
-                    .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
-                {
-                    Some(value) => {
-                        let summary = value.get("summary").unwrap_or(&value);
-                        let net_profit = summary.get("net_profit").and_then(|v| v.as_f64());
-                        let sharpe = summary.get("sharpe").and_then(|v| v.as_f64());
-                        let max_drawdown = summary.get("max_drawdown").and_then(|v| v.as_f64());
-                        format!(
-                            "{run_id}: net_profit={:?} sharpe={:?} max_dd={:?} analyzers={}",
-                            net_profit, sharpe, max_drawdown, analyzer_count
-                        )
-                    }
-                    None => format!("{run_id} (invalid summary.json) analyzers={analyzer_count}"),
-                }
-            } else {
-                format!("{run_id} (no summary.json) analyzers={analyzer_count}")
-            };
+    fn handle_equity_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
+        let Some(view) = self.equity_view.as_mut() else {
+            self.active_view = ViewId::Runs;
+            self.dirty = true;
+            return Ok(false);
+        };
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.active_view = view.back_to,
+            KeyCode::Left | KeyCode::Char('h') => view.move_cursor(-1),
+            KeyCode::Right | KeyCode::Char('l') => view.move_cursor(1),
+            KeyCode::PageUp => view.move_cursor(-view.page()),
+            KeyCode::PageDown => view.move_cursor(view.page()),
+            KeyCode::Home => view.cursor_to_start(),
+            KeyCode::End => view.cursor_to_end(),
+            _ => return Ok(false),
+        }
+        self.dirty = true;
+        Ok(false)
+    }
 
-            runs.push(ReportsRun { run_id, line });
+    // equity.csv is read on the UI thread, like the run browser's report regeneration.
+    fn open_equity_view(&mut self, run_dir: &Path, back_to: ViewId) {
+        match EquityView::load(run_dir, back_to) {
+            Ok(view) => {
+                self.last_error = None;
+                self.equity_view = Some(view);
+                self.active_view = ViewId::Equity;
+            }
+            Err(err) => self.set_error_and_clear_info(&format!("equity: {err}")),
         }
+        self.dirty = true;
+    }
 
-        self.reports_runs = runs;
-        let max = self.reports_runs.len().saturating_sub(1);
+    fn handle_trade_list_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
+        let Some(list) = self.trade_list.as_mut() else {
+            self.active_view = ViewId::Runs;
+            self.dirty = true;
+            return Ok(false);
+        };
+        if list.editing_filter {
+            let input = &mut list.filter_input;
+            match key.code {
+                KeyCode::Esc => list.editing_filter = false,
+                KeyCode::Enter => {
+                    list.editing_filter = false;
+                    match list.apply_filter_input() {
+                        Ok(()) => self.last_error = None,
+                        Err(err) => self.set_error_and_clear_info(&err),
+                    }
+                }
+                KeyCode::Backspace => input.backspace(),
+                KeyCode::Delete => input.delete(),
+                KeyCode::Left => input.move_left(),
+                KeyCode::Right => input.move_right(),
+                KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    input.insert_char(ch)
+                }
+                _ => return Ok(false),
+            }
+            self.dirty = true;
+            return Ok(false);
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.active_view = list.back_to,
+            KeyCode::Up => list.move_selection(-1),
+            KeyCode::Down => list.move_selection(1),
+            KeyCode::PageUp => list.move_selection(-10),
+            KeyCode::PageDown => list.move_selection(10),
+            KeyCode::Home => list.selected = 0,
+            KeyCode::End => list.select_last(),
+            KeyCode::Char('/') => list.editing_filter = true,
+            KeyCode::Char('a') => list.cycle_group_by(),
+            _ => return Ok(false),
+        }
+        self.dirty = true;
+        Ok(false)
+    }
+
+    fn open_trade_list(&mut self, run_dir: &Path, back_to: ViewId) {
+        match TradeList::load(run_dir, back_to) {
+            Ok(list) => {
+                self.last_error = None;
+                self.trade_list = Some(list);
+                self.active_view = ViewId::Trades;
+            }
+            Err(err) => self.set_error_and_clear_info(&format!("trades: {err}")),
+        }
+        self.dirty = true;
+    }
+
+    fn toggle_diff_base(&mut self) {
+        let Some(run) = self.run_browser.selected_run() else {
+            return;
+        };
+        let (run_id, run_dir) = (run.run_id.clone(), run.run_dir.clone());
+        let browser = &mut self.run_browser;
+        self.info_message = Some(if browser.diff_base.as_deref() == Some(run_dir.as_str()) {
+            browser.diff_base = None;
+            "config diff base cleared".to_string()
+        } else {
+            browser.diff_base = Some(run_dir);
+            format!("{run_id} marked: select another run and press v to diff the configs")
+        });
+        self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(3));
+    }
+
+    // The marked run against the selected one, or without a mark (or with the mark selected) the
+    // selected run against the config loaded in Setup, to see what a re-run would change.
+    fn open_config_diff(&mut self) {
+        let Some(run) = self.run_browser.selected_run() else {
+            return;
+        };
+        let selected_dir = PathBuf::from(&run.run_dir);
+        let base_dir = self
+            .run_browser
+            .diff_base
+            .as_ref()
+            .filter(|base| **base != run.run_dir)
+            .map(PathBuf::from);
+        let result =
+            ConfigDiffView::load_snapshot(&selected_dir).and_then(|(name, toml)| match base_dir {
+                Some(base_dir) => {
+                    let (base_name, base_toml) = ConfigDiffView::load_snapshot(&base_dir)?;
+                    Ok(ConfigDiffView::new(base_name, &base_toml, name, &toml))
+                }
+                None => {
+                    let loaded = self.config_toml.as_deref().ok_or_else(|| {
+                        "mark a run with m first, or load a config in Setup to diff against"
+                            .to_string()
+                    })?;
+                    let label = self.config_path.as_ref().map_or_else(
+                        || "loaded config".to_string(),
+                        |path| path.display().to_string(),
+                    );
+                    Ok(ConfigDiffView::new(name, &toml, label, loaded))
+                }
+            });
+        match result {
+            Ok(view) => {
+                self.last_error = None;
+                self.config_diff = Some(view);
+                self.active_view = ViewId::ConfigDiff;
+            }
+            Err(err) => self.set_error_and_clear_info(&format!("config diff: {err}")),
+        }
+    }
+
+    fn handle_config_diff_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
+        let Some(view) = self.config_diff.as_mut() else {
+            self.active_view = ViewId::Runs;
+            self.dirty = true;
+            return Ok(false);
+        };
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.active_view = view.back_to,
+            KeyCode::Up => view.scroll_by(-1),
+            KeyCode::Down => view.scroll_by(1),
+            KeyCode::PageUp => view.scroll_by(-20),
+            KeyCode::PageDown => view.scroll_by(20),
+            KeyCode::Home => view.scroll = 0,
+            KeyCode::End => view.scroll_to_end(),
+            KeyCode::Char('n') => view.jump_to_change(true),
+            KeyCode::Char('p') => view.jump_to_change(false),
+            _ => return Ok(false),
+        }
+        self.dirty = true;
+        Ok(false)
+    }
+
+    fn handle_leaderboard_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
+        if let Some(format) = self.export_format(&key) {
+            self.export_view(format);
+            return Ok(false);
+        }
+        match key.code {
+            KeyCode::Esc => self.active_view = ViewId::Experiments,
+            KeyCode::Up => self.sweep_leaderboard.select_prev(),
+            KeyCode::Down => self.sweep_leaderboard.select_next(),
+            KeyCode::Char('s') => self.sweep_leaderboard.cycle_sort(),
+            KeyCode::Enter => self.open_selected_trial(),
+            KeyCode::Char('b') => {
+                self.sweep_leaderboard.select_best();
+                self.open_selected_trial();
+            }
+            _ if self.keymap.matches(Action::Cancel, &key) => {
+                if self.status.kind == Some(TaskKind::Sweep) {
+                    self.request_cancel();
+                }
+            }
+            _ => return Ok(false),
+        }
+        self.dirty = true;
+        Ok(false)
+    }
+
+    fn handle_sweep_history_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
+        let history = &mut self.sweep_history;
+        if history.editing_filter {
+            let input = &mut history.filter_input;
+            match key.code {
+                KeyCode::Esc => history.editing_filter = false,
+                KeyCode::Enter => {
+                    history.editing_filter = false;
+                    match history.apply_filter_input() {
+                        Ok(()) => self.last_error = None,
+                        Err(err) => self.set_error_and_clear_info(&err),
+                    }
+                }
+                KeyCode::Backspace => input.backspace(),
+                KeyCode::Delete => input.delete(),
+                KeyCode::Left => input.move_left(),
+                KeyCode::Right => input.move_right(),
+                KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    input.insert_char(ch)
+                }
+                _ => return Ok(false),
+            }
+            self.dirty = true;
+            return Ok(false);
+        }
+        if history.opened.is_none() {
+            match key.code {
+                KeyCode::Esc => self.active_view = ViewId::Experiments,
+                KeyCode::Up => history.select_prev(),
+                KeyCode::Down => history.select_next(),
+                KeyCode::Enter => match history.open_selected() {
+                    Ok(()) => self.last_error = None,
+                    Err(err) => self.set_error_and_clear_info(&err),
+                },
+                KeyCode::Char('g') | KeyCode::F(5) => {
+                    let out_dir = self.reports_out_dir();
+                    self.sweep_history.refresh(&out_dir);
+                }
+                _ => return Ok(false),
+            }
+            self.dirty = true;
+            return Ok(false);
+        }
+        match key.code {
+            KeyCode::Esc => history.close(),
+            KeyCode::Up => history.select_prev(),
+            KeyCode::Down => history.select_next(),
+            KeyCode::Left => history.cycle_param(false),
+            KeyCode::Right => history.cycle_param(true),
+            KeyCode::Char('s') => history.cycle_sort(),
+            KeyCode::Char('/') => history.editing_filter = true,
+            KeyCode::Enter => {
+                if let Some(trial) = history.selected_trial() {
+                    let run_dir = trial.run_dir.clone();
+                    self.open_trial_run(run_dir);
+                }
+            }
+            _ => return Ok(false),
+        }
+        self.dirty = true;
+        Ok(false)
+    }
+
+    fn export_format(&self, key: &KeyEvent) -> Option<ExportFormat> {
+        if self.keymap.matches(Action::ExportCsv, key) {
+            Some(ExportFormat::Csv)
+        } else if self.keymap.matches(Action::ExportJson, key) {
+            Some(ExportFormat::Json)
+        } else {
+            None
+        }
+    }
+
+    // Writes the table of the active view (Runs, Monitor trades, Leaderboard) to
+    // `<out_dir>/exports`, in the order shown.
+    fn export_view(&mut self, format: ExportFormat) {
+        let table = match self.active_view {
+            ViewId::Runs => runs_table(&self.run_browser.runs),
+            ViewId::Monitor => trades_table(self.trades.iter()),
+            ViewId::Leaderboard => leaderboard_table(&self.sweep_leaderboard.trials),
+            _ => return,
+        };
+        self.dirty = true;
+        if table.rows.is_empty() {
+            self.set_error_and_clear_info(&format!("no {} to export", table.name));
+            return;
+        }
+        let dir = self.reports_out_dir().join("exports");
+        let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        match write_export(&dir, &table, format, &stamp) {
+            Ok(path) => {
+                self.last_error = None;
+                self.info_message = Some(format!(
+                    "exported {} {} to {}",
+                    table.rows.len(),
+                    table.name,
+                    path.display()
+                ));
+                self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(5));
+            }
+            Err(err) => self.set_error_and_clear_info(&format!("export: {err}")),
+        }
+    }
+
+    // Shows the trial's run dir in the Runs browser, scanning the sweep's out dir.
+    fn open_selected_trial(&mut self) {
+        if let Some(trial) = self.sweep_leaderboard.selected_trial() {
+            self.open_trial_run(trial.run_dir.clone());
+        }
+    }
+
+    fn open_trial_run(&mut self, run_dir: String) {
+        let out_dir = Path::new(&run_dir)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| self.reports_out_dir());
+        if let Err(err) = self.run_browser.refresh(out_dir) {
+            self.set_error_and_clear_info(&err);
+            return;
+        }
+        if self.run_browser.select_run_dir(&run_dir) {
+            self.run_browser.detail = true;
+            self.last_error = None;
+            self.active_view = ViewId::Runs;
+        } else {
+            self.set_error_and_clear_info(&format!("{run_dir} has no summary.json"));
+        }
+    }
+
+    fn refresh_run_browser(&mut self) {
+        let out_dir = self.reports_out_dir();
+        if let Err(err) = self.run_browser.refresh(out_dir) {
+            self.set_error_and_clear_info(&err);
+        }
+    }
+
+    fn report_run_browser_result(&mut self, result: Result<String, String>) {
+        match result {
+            Ok(message) => {
+                self.last_error = None;
+                self.info_message = Some(message);
+                self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(3));
+            }
+            Err(err) => self.set_error_and_clear_info(&err),
+        }
+    }
+
+    fn reports_out_dir(&self) -> PathBuf {
+        self.config
+            .as_ref()
+            .map(|c| PathBuf::from(&c.paths.out_dir))
+            .unwrap_or_else(|| self.default_out_dir.clone())
+    }
+
+    fn refresh_reports_runs(&mut self) {
+        let out_dir = self.reports_out_dir();
+        let mut entries: Vec<_> = std::fs::read_dir(&out_dir)
+            .ok()
+            .into_iter()
+            .flat_map(|it| it.filter_map(|e| e.ok()).collect::<Vec<_>>())
+            .collect();
+        entries.sort_by_key(|e| e.file_name());
+
+        let mut runs: Vec<ReportsRun> = Vec::new();
+        for e in entries {
+            if !e.file_type().map(|ft| ft.is_dir()).unwrap_or(false) {
+                continue;
+            }
+            let run_id = e.file_name().to_string_lossy().to_string();
+            let run_dir = out_dir.join(&run_id);
+            let summary_path = run_dir.join("summary.json");
+
+            let analyzer_count = std::fs::read_dir(run_dir.join("analyzers"))
+                .ok()
+                .map(|it| {
+                    it.filter_map(|e| e.ok())
+                        .filter(|e| {
+                            e.path()
+                                .extension()
+                                .and_then(|s| s.to_str())
+                                .is_some_and(|s| s.eq_ignore_ascii_case("json"))
+                        })
+                        .count()
+                })
+                .unwrap_or(0);
+
+            let line = if summary_path.exists() {
+                match std::fs::read_to_string(&summary_path)
+                    .ok()
+                    .and_then(|c| RunSummary::from_json_str(&c).ok())
+                {
+                    Some(summary) => format!(
+                        "{run_id}: net_profit={} sharpe={} max_dd={} analyzers={} schema=v{}",
+                        summary.net_profit,
+                        summary.sharpe,
+                        summary.max_drawdown,
+                        analyzer_count,
+                        summary.schema_version
+                    ),
+                    None => format!("{run_id} (invalid summary.json) analyzers={analyzer_count}"),
+                }
+            } else {
+                format!("{run_id} (no summary.json) analyzers={analyzer_count}")
+            };
+
+            let notes = kairos_application::annotations::load_notes(&run_dir)
+                .into_iter()
+                .map(|note| note.note)
+                .collect();
+            runs.push(ReportsRun {
+                run_id,
+                line,
+                notes,
+            });
+        }
+
+        self.reports_runs = runs;
+        let max = self.reports_runs.len().saturating_sub(1);
         self.reports_selected_run = self.reports_selected_run.min(max);
         self.reports_mode = ReportsMode::Runs;
         self.reports_analyzers.clear();
@@ -724,6 +1766,10 @@ impl App {
                 self.active_view = ViewId::MainMenu;
                 self.dirty = true;
             }
+            KeyCode::F(2) => {
+                self.open_config_editor();
+                self.dirty = true;
+            }
             KeyCode::Char('g') | KeyCode::F(5) => {
                 self.refresh_available_configs();
                 self.info_message = Some("refreshed config list".to_string());
@@ -750,6 +1796,17 @@ impl App {
                 self.setup_focus = SetupFocus::List;
                 self.dirty = true;
             }
+            KeyCode::Char(ch @ ('j' | 'J')) if self.setup_focus == SetupFocus::List => {
+                if let Some(path) = self.available_configs.get(self.selected_config).cloned() {
+                    let kind = if ch == 'J' {
+                        JobKind::Validate
+                    } else {
+                        JobKind::Backtest
+                    };
+                    self.enqueue_job(kind, path);
+                }
+                self.dirty = true;
+            }
             KeyCode::Enter => {
                 match self.setup_focus {
                     SetupFocus::Input => self.try_load_config(),
@@ -831,7 +1888,151 @@ impl App {
         Ok(false)
     }
 
+    // Keeps unapplied edits from an earlier visit; otherwise starts from the loaded config.
+    fn open_config_editor(&mut self) {
+        if self.config_form.as_ref().is_some_and(|form| form.modified) {
+            self.active_view = ViewId::ConfigEditor;
+            return;
+        }
+        let Some(source) = self.config_toml.clone() else {
+            self.set_error_and_clear_info("load a config first");
+            return;
+        };
+        let save_path = self
+            .config_path
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_DIR).join("edited.toml"));
+        match ConfigForm::new(&source, &save_path) {
+            Ok(form) => {
+                self.config_form = Some(form);
+                self.last_error = None;
+                self.active_view = ViewId::ConfigEditor;
+            }
+            Err(err) => self.set_error_and_clear_info(&err),
+        }
+    }
+
+    fn handle_config_editor_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
+        let Some(form) = self.config_form.as_mut() else {
+            self.active_view = ViewId::Setup;
+            self.dirty = true;
+            return Ok(false);
+        };
+        let typing = !key.modifiers.contains(KeyModifiers::CONTROL);
+        match form.focus {
+            FormFocus::Editing | FormFocus::SavePath => {
+                let input = if form.focus == FormFocus::Editing {
+                    &mut form.input
+                } else {
+                    &mut form.save_path
+                };
+                match key.code {
+                    KeyCode::Esc => form.cancel_editing(),
+                    KeyCode::Backspace => input.backspace(),
+                    KeyCode::Delete => input.delete(),
+                    KeyCode::Left => input.move_left(),
+                    KeyCode::Right => input.move_right(),
+                    KeyCode::Char(ch) if typing => input.insert_char(ch),
+                    KeyCode::Enter if form.focus == FormFocus::Editing => {
+                        match form.commit_edit() {
+                            Ok(()) => self.last_error = None,
+                            Err(err) => self.set_error_and_clear_info(&err),
+                        }
+                    }
+                    KeyCode::Enter => {
+                        form.cancel_editing();
+                        self.save_config_form();
+                    }
+                    _ => return Ok(false),
+                }
+            }
+            FormFocus::Fields => match key.code {
+                KeyCode::Esc => self.active_view = ViewId::Setup,
+                KeyCode::Up => form.select_prev(),
+                KeyCode::Down => form.select_next(),
+                KeyCode::PageUp => form.jump_group(-1),
+                KeyCode::PageDown => form.jump_group(1),
+                KeyCode::Enter => form.start_editing(),
+                KeyCode::Char('w') => form.focus = FormFocus::SavePath,
+                KeyCode::Char('a') => {
+                    if self.apply_config_form().is_ok() {
+                        self.info_message = Some("config applied (not saved)".to_string());
+                        self.info_expires_at =
+                            Some(Instant::now() + std::time::Duration::from_secs(3));
+                    }
+                }
+                _ => return Ok(false),
+            },
+        }
+        self.dirty = true;
+        Ok(false)
+    }
+
+    // Makes the edited config the session's config, as if it had been loaded.
+    fn apply_config_form(&mut self) -> Result<(), ()> {
+        let Some(form) = self.config_form.as_mut() else {
+            return Err(());
+        };
+        match form.build() {
+            Ok((config, source)) => {
+                form.modified = false;
+                self.quick_edit.sync_from_config(&config);
+                self.config = Some(Arc::new(config));
+                self.config_toml = Some(source);
+                self.last_validate_ok = None;
+                self.last_error = None;
+                Ok(())
+            }
+            Err(err) => {
+                self.set_error_and_clear_info(&err);
+                Err(())
+            }
+        }
+    }
+
+    fn save_config_form(&mut self) {
+        let Some(form) = self.config_form.as_ref() else {
+            return;
+        };
+        let raw = form.save_path.value.trim().to_string();
+        if raw.is_empty() {
+            self.set_error_and_clear_info("save path cannot be empty");
+            return;
+        }
+        let source = match form.build() {
+            Ok((_, source)) => source,
+            Err(err) => {
+                self.set_error_and_clear_info(&err);
+                return;
+            }
+        };
+        if let Err(err) = std::fs::write(&raw, &source) {
+            self.set_error_and_clear_info(&format!("failed to write {raw}: {err}"));
+            return;
+        }
+        if self.apply_config_form().is_err() {
+            return;
+        }
+        self.config_path = Some(PathBuf::from(&raw));
+        self.config_input = TextInput::new(raw.clone());
+        self.record_recent_config(&raw);
+        self.refresh_available_configs();
+        self.info_message = Some(format!("saved config to {raw}"));
+        self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(3));
+        tracing::info!(config_path = %raw, "config saved from editor");
+    }
+
     fn handle_backtest_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
+        if self.strategy_prompt.is_some() {
+            self.handle_strategy_prompt_keys(key);
+            return Ok(false);
+        }
+        if self.active_view == ViewId::Monitor {
+            if let Some(format) = self.export_format(&key) {
+                self.export_view(format);
+                return Ok(false);
+            }
+        }
         match key.code {
             KeyCode::Esc => {
                 self.active_view = ViewId::MainMenu;
@@ -857,12 +2058,29 @@ impl App {
                 if self.backtest_tab == BacktestTab::Validate {
                     self.validate_strict = !self.validate_strict;
                     self.dirty = true;
+                } else if self.backtest_tab == BacktestTab::Paper && !self.status.running {
+                    self.paper_simulated = !self.paper_simulated;
+                    self.info_message = Some(if self.paper_simulated {
+                        "realtime stream: simulator".to_string()
+                    } else {
+                        "realtime stream: exchange".to_string()
+                    });
+                    self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(2));
+                    self.dirty = true;
                 }
             }
             KeyCode::Char('v') => {
                 self.require_validate_before_run = !self.require_validate_before_run;
                 self.dirty = true;
             }
+            KeyCode::Char('d') => {
+                if self.data_quality.is_some() {
+                    self.active_view = ViewId::DataQuality;
+                } else {
+                    self.set_error_and_clear_info("no data-quality report yet (run validate)");
+                }
+                self.dirty = true;
+            }
             KeyCode::Char('t') => {
                 if self.backtest_tab == BacktestTab::Paper && !self.status.running {
                     self.paper_realtime = !self.paper_realtime;
@@ -875,38 +2093,68 @@ impl App {
                     self.dirty = true;
                 }
             }
-            KeyCode::Char('r') => {
+            KeyCode::Char('u') => {
+                if self.backtest_tab == BacktestTab::Paper && !self.status.running {
+                    self.paper_resume = !self.paper_resume;
+                    self.info_message = Some(if self.paper_resume {
+                        "paper session: resume from session.json".to_string()
+                    } else {
+                        "paper session: start flat".to_string()
+                    });
+                    self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(2));
+                    self.dirty = true;
+                }
+            }
+            _ if self.keymap.matches(Action::Run, &key) => {
                 self.start_selected_task()?;
                 self.dirty = true;
             }
-            KeyCode::Char('p') => {
-                if self.status.running {
-                    self.paused = self.task_runner.toggle_pause();
-                    if self.paused {
-                        self.pause_blink = true;
-                    }
-                    self.cancel_requested = false;
-                    self.dirty = true;
-                }
+            _ if self.keymap.matches(Action::Pause, &key) => {
+                self.toggle_pause();
+                self.dirty = true;
             }
-            KeyCode::Char('n') => {
-                if self.status.running
-                    && self.paused
-                    && self.status.kind == Some(TaskKind::Backtest)
+            _ if self.keymap.matches(Action::Step, &key) => {
+                if self.task_state() == TaskState::Paused
+                    && self.status.kind.is_some_and(TaskKind::steppable)
                 {
                     let _ = self.task_runner.step_once();
                     self.dirty = true;
                 }
             }
-            KeyCode::Char('x') => {
-                if self.status.running {
-                    self.task_runner.cancel_current();
-                    self.cancel_requested = true;
-                    self.paused = false;
-                    self.status.last_result = Some(Err("Cancelled".to_string()));
+            KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Char('-') => {
+                if self.status.running && self.status.kind == Some(TaskKind::Paper) {
+                    let factor = if key.code == KeyCode::Char('-') {
+                        0.5
+                    } else {
+                        2.0
+                    };
+                    if let Some(scale) = self.task_runner.scale_replay_speed(factor) {
+                        self.info_message = Some(if scale > 0.0 {
+                            format!("replay speed: {scale}x")
+                        } else {
+                            "replay speed: unthrottled (paper.replay_scale = 0)".to_string()
+                        });
+                        self.info_expires_at =
+                            Some(Instant::now() + std::time::Duration::from_secs(2));
+                        self.dirty = true;
+                    }
+                }
+            }
+            KeyCode::Char('h') => {
+                if self.status.running
+                    && matches!(
+                        self.status.kind,
+                        Some(TaskKind::Paper | TaskKind::PaperRealtime { .. })
+                    )
+                {
+                    self.strategy_prompt = Some(TextInput::new(String::new()));
                     self.dirty = true;
                 }
             }
+            _ if self.keymap.matches(Action::Cancel, &key) => {
+                self.request_cancel();
+                self.dirty = true;
+            }
             KeyCode::Up => {
                 if self.active_view == ViewId::Monitor {
                     let max = self.trades.len().saturating_sub(1);
@@ -933,6 +2181,44 @@ impl App {
         Ok(false)
     }
 
+    // Queues the typed change for the paper session's next bar (where it is also audited).
+    fn handle_strategy_prompt_keys(&mut self, key: KeyEvent) {
+        let Some(input) = self.strategy_prompt.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => self.strategy_prompt = None,
+            KeyCode::Enter => {
+                let raw = input.value.clone();
+                match StrategyChange::parse(&raw) {
+                    Ok(change) => {
+                        self.strategy_prompt = None;
+                        if self.task_runner.queue_strategy_change(change) {
+                            self.info_message = Some(format!(
+                                "strategy change queued for the next bar: {}",
+                                raw.trim()
+                            ));
+                            self.info_expires_at =
+                                Some(Instant::now() + std::time::Duration::from_secs(3));
+                        } else {
+                            self.set_error_and_clear_info("no paper session is running");
+                        }
+                    }
+                    Err(err) => self.set_error_and_clear_info(&err),
+                }
+            }
+            KeyCode::Backspace => input.backspace(),
+            KeyCode::Delete => input.delete(),
+            KeyCode::Left => input.move_left(),
+            KeyCode::Right => input.move_right(),
+            KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                input.insert_char(ch)
+            }
+            _ => return,
+        }
+        self.dirty = true;
+    }
+
     fn handle_experiments_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
         match key.code {
             KeyCode::Esc => {
@@ -946,22 +2232,29 @@ impl App {
                 };
                 self.dirty = true;
             }
-            KeyCode::Char('r') | KeyCode::Enter => {
+            _ if key.code == KeyCode::Enter || self.keymap.matches(Action::Run, &key) => {
                 self.start_experiments_sweep();
                 self.dirty = true;
             }
+            KeyCode::F(2) => {
+                self.active_view = ViewId::Leaderboard;
+                self.dirty = true;
+            }
+            KeyCode::F(3) => {
+                let out_dir = self.reports_out_dir();
+                self.sweep_history.refresh(&out_dir);
+                self.active_view = ViewId::SweepHistory;
+                self.dirty = true;
+            }
             KeyCode::Char('v') => {
                 if !self.status.running {
                     self.experiments_resume = !self.experiments_resume;
                     self.dirty = true;
                 }
             }
-            KeyCode::Char('x') => {
-                if self.status.running && self.status.kind == Some(TaskKind::Sweep) {
-                    self.task_runner.cancel_current();
-                    self.cancel_requested = true;
-                    self.paused = false;
-                    self.status.last_result = Some(Err("Cancelled".to_string()));
+            _ if self.keymap.matches(Action::Cancel, &key) => {
+                if self.status.kind == Some(TaskKind::Sweep) {
+                    self.request_cancel();
                     self.dirty = true;
                 }
             }
@@ -1030,13 +2323,14 @@ impl App {
                 self.quick_edit.sync_from_config(&cfg);
                 self.config = Some(Arc::new(cfg));
                 self.config_toml = Some(source);
+                self.config_form = None;
                 self.last_error = None;
                 self.record_recent_config(&raw);
                 self.refresh_available_configs();
                 tracing::info!(config_path = %raw, "config loaded");
             }
             Err(err) => {
-                self.last_error = Some(err);
+                self.last_error = Some(err.to_string());
             }
         }
     }
@@ -1138,148 +2432,672 @@ impl App {
                     Ok(())
                 }
             }
-            QuickEditField::LlmProvider | QuickEditField::LlmModel | QuickEditField::LlmApiKey => {
-                unreachable!("handled above")
+            QuickEditField::LlmProvider | QuickEditField::LlmModel | QuickEditField::LlmApiKey => {
+                unreachable!("handled above")
+            }
+            QuickEditField::LlmManagedAgent => unreachable!("handled above"),
+        };
+
+        if let Err(err) = result {
+            self.set_error_and_clear_info(&err);
+            return;
+        }
+
+        let config_toml = match kairos_application::config::to_toml_pretty(&next) {
+            Ok(s) => s,
+            Err(err) => {
+                self.set_error_and_clear_info(&err.to_string());
+                return;
+            }
+        };
+
+        self.config = Some(Arc::new(next));
+        self.config_toml = Some(config_toml);
+        self.config_form = None;
+        self.last_error = None;
+        self.info_message = Some("quick edit applied".to_string());
+        self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(2));
+    }
+
+    fn set_error_and_clear_info(&mut self, msg: &str) {
+        self.last_error = Some(msg.to_string());
+        self.info_message = None;
+        self.info_expires_at = None;
+    }
+
+    fn try_load_selected_config(&mut self) {
+        let Some(path) = self.available_configs.get(self.selected_config).cloned() else {
+            let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+            let config_dir = resolve_config_dir(cwd.as_path());
+            self.last_error = Some(format!(
+                "no configs found under {} or recents",
+                config_dir.display()
+            ));
+            return;
+        };
+        self.config_input = TextInput::new(path.display().to_string());
+        self.try_load_config();
+    }
+
+    fn refresh_available_configs(&mut self) {
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let configs_dir = resolve_config_dir(cwd.as_path());
+        let mut configs: Vec<PathBuf> = Vec::new();
+        if let Ok(entries) = std::fs::read_dir(&configs_dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path
+                    .extension()
+                    .and_then(|s| s.to_str())
+                    .map(|s| s.eq_ignore_ascii_case("toml"))
+                    .unwrap_or(false)
+                {
+                    configs.push(std::fs::canonicalize(&path).unwrap_or(path));
+                }
+            }
+        }
+        configs.sort();
+
+        let recents = load_recent_configs()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|p| p.exists())
+            .collect::<Vec<_>>();
+
+        let (merged, recent_count) = merge_recents_and_configs(&recents, &configs);
+        self.recent_config_count = recent_count;
+        self.available_configs = merged;
+        if self.selected_config >= self.available_configs.len() {
+            self.selected_config = self.available_configs.len().saturating_sub(1);
+        }
+    }
+
+    fn record_recent_config(&self, raw_path: &str) {
+        let path = PathBuf::from(raw_path);
+        let abs = std::fs::canonicalize(&path).unwrap_or(path);
+        let mut recents = load_recent_configs().unwrap_or_default();
+        recents.retain(|p| p != &abs);
+        recents.insert(0, abs);
+        recents.truncate(10);
+        let _ = store_recent_configs(&recents);
+    }
+
+    fn start_experiments_sweep(&mut self) {
+        if self.status.running {
+            return;
+        }
+
+        let sweep_raw = self.experiments_sweep_path.value.trim();
+        if sweep_raw.is_empty() {
+            self.set_error_and_clear_info("sweep config path cannot be empty");
+            return;
+        }
+
+        let sweep_path = PathBuf::from(sweep_raw);
+        if !sweep_path.exists() {
+            self.set_error_and_clear_info("sweep config not found");
+            return;
+        }
+
+        let parallelism = {
+            let raw = self.experiments_parallelism.value.trim();
+            if raw.is_empty() {
+                None
+            } else {
+                let parsed = match raw.parse::<usize>() {
+                    Ok(v) => v,
+                    Err(_) => {
+                        self.set_error_and_clear_info("parallelism must be a positive integer");
+                        return;
+                    }
+                };
+                if parsed == 0 {
+                    self.set_error_and_clear_info("parallelism must be >= 1");
+                    return;
+                }
+                Some(parsed)
+            }
+        };
+
+        self.status.running = true;
+        self.paused = false;
+        self.cancel_requested = false;
+        self.pause_blink = true;
+        self.tick_counter = 0;
+        self.status.kind = Some(TaskKind::Sweep);
+        self.status.started_at = Some(Instant::now());
+        self.status.last_result = None;
+        self.stream_status = None;
+        self.experiments_progress = None;
+        self.sweep_leaderboard.clear();
+        self.active_view = ViewId::Leaderboard;
+        self.last_error = None;
+        self.info_message = Some("sweep started".to_string());
+        self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(2));
+
+        self.task_runner
+            .start_sweep(sweep_path, parallelism, self.experiments_resume);
+    }
+
+    pub fn task_state(&self) -> TaskState {
+        if self.status.running {
+            if self.cancel_requested {
+                TaskState::Cancelling
+            } else if self.paused {
+                TaskState::Paused
+            } else {
+                TaskState::Running
+            }
+        } else {
+            match &self.status.last_result {
+                None => TaskState::Idle,
+                Some(Ok(_)) => TaskState::Succeeded,
+                Some(Err(err)) if err == "Cancelled" => TaskState::Cancelled,
+                Some(Err(_)) => TaskState::Failed,
+            }
+        }
+    }
+
+    // Pauses or resumes the running task at its next bar; only tasks with a `RunControl` loop can.
+    fn toggle_pause(&mut self) {
+        let Some(kind) = self.status.kind.filter(|_| self.status.running) else {
+            return;
+        };
+        if self.cancel_requested {
+            return;
+        }
+        if !kind.pausable() {
+            self.set_error_and_clear_info(&format!(
+                "{} cannot be paused (x cancels it)",
+                crate::ui::task_kind_label(kind)
+            ));
+            return;
+        }
+        self.paused = self.task_runner.toggle_pause();
+        if self.paused {
+            self.pause_blink = true;
+        }
+        self.info_message = Some(if self.paused { "paused" } else { "resumed" }.to_string());
+        self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(2));
+    }
+
+    // Asks the running task to stop; it stays `Cancelling` until `TaskFinished` arrives, so a slow
+    // bar or exchange call does not look like the app hanging.
+    fn request_cancel(&mut self) {
+        if !self.status.running || self.cancel_requested {
+            return;
+        }
+        self.task_runner.cancel_current();
+        self.cancel_requested = true;
+        self.paused = false;
+        self.info_message = Some("cancelling...".to_string());
+        self.info_expires_at = None;
+        tracing::info!("task cancel requested from TUI");
+    }
+
+    fn enqueue_job(&mut self, kind: JobKind, config_path: PathBuf) {
+        let id = self.job_queue.enqueue(kind, config_path.clone());
+        self.info_message = Some(format!(
+            "queued job #{id}: {} {}",
+            kind.as_str(),
+            config_path.display()
+        ));
+        self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(3));
+        self.start_ready_jobs();
+    }
+
+    fn start_ready_jobs(&mut self) {
+        for job in self.job_queue.take_ready() {
+            tracing::info!(job_id = job.id, kind = job.kind.as_str(), config_path = %job.config_path.display(), "queued job started");
+            self.task_runner
+                .start_job(job.id, job.kind, job.config_path, job.ingest);
+        }
+    }
+
+    fn handle_jobs_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
+        match key.code {
+            KeyCode::Esc => self.active_view = ViewId::MainMenu,
+            KeyCode::Up => self.job_queue.select_prev(),
+            KeyCode::Down => self.job_queue.select_next(),
+            KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Char('-') => {
+                let concurrency = if key.code == KeyCode::Char('-') {
+                    self.job_queue.concurrency.saturating_sub(1)
+                } else {
+                    self.job_queue.concurrency + 1
+                };
+                self.job_queue.set_concurrency(concurrency);
+                self.start_ready_jobs();
+            }
+            KeyCode::Char(ch @ ('b' | 'v')) => match self.config_path.clone() {
+                Some(path) => {
+                    let kind = if ch == 'v' {
+                        JobKind::Validate
+                    } else {
+                        JobKind::Backtest
+                    };
+                    self.enqueue_job(kind, path);
+                }
+                None => self.set_error_and_clear_info(
+                    "load a config first (or queue one from the Setup list with j/J)",
+                ),
+            },
+            _ if self.keymap.matches(Action::Cancel, &key) => {
+                if let Some(id) = self.job_queue.cancel_selected() {
+                    self.task_runner.cancel_job(id);
+                    self.job_queue.push_log(id, "cancel requested".to_string());
+                }
+            }
+            KeyCode::Char('r') => {
+                if self.job_queue.retry_selected().is_some() {
+                    self.start_ready_jobs();
+                }
+            }
+            KeyCode::Char('c') => self.job_queue.clear_finished(),
+            _ => return Ok(false),
+        }
+        self.dirty = true;
+        Ok(false)
+    }
+
+    // Queues the schedule entries whose daily slot has come, each as a job (after its ingest).
+    fn run_due_schedule(&mut self) {
+        let due = self.scheduler.take_due(chrono::Utc::now());
+        self.queue_scheduled(due);
+    }
+
+    fn queue_scheduled(&mut self, due: Vec<DueRun>) {
+        if due.is_empty() {
+            return;
+        }
+        for run in due {
+            let id = self
+                .job_queue
+                .enqueue_after_ingest(run.kind, run.config.clone(), run.ingest);
+            tracing::info!(job_id = id, kind = run.kind.as_str(), config_path = %run.config.display(), "scheduled job queued");
+            self.scheduler.entries[run.entry].job_id = Some(id);
+        }
+        self.save_schedule();
+        self.start_ready_jobs();
+        self.dirty = true;
+    }
+
+    fn save_schedule(&mut self) {
+        if let Err(err) = self.scheduler.save() {
+            self.set_error_and_clear_info(&err);
+        }
+    }
+
+    fn handle_schedule_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
+        let now = chrono::Utc::now();
+        match key.code {
+            KeyCode::Esc => {
+                self.active_view = ViewId::MainMenu;
+                self.dirty = true;
+                return Ok(false);
+            }
+            KeyCode::Up => self.scheduler.select_prev(),
+            KeyCode::Down => self.scheduler.select_next(),
+            KeyCode::Char('g') => {
+                if let Some(path) = tui_store_path(SCHEDULE_FILE) {
+                    self.reload_schedule(&path);
+                }
+                self.dirty = true;
+                return Ok(false);
+            }
+            KeyCode::Char('a') => {
+                let Some(path) = self.config_path.clone() else {
+                    self.set_error_and_clear_info("load a config first (Setup), then press a");
+                    return Ok(false);
+                };
+                let symbol = self.config.as_ref().map(|cfg| cfg.run.symbol.clone());
+                self.scheduler.add(ScheduleEntry::new(path, symbol, now));
+            }
+            KeyCode::Char('d') => {
+                if self.scheduler.remove_selected().is_none() {
+                    return Ok(false);
+                }
+            }
+            KeyCode::Char(' ') => {
+                let Some(entry) = self.scheduler.selected_mut() else {
+                    return Ok(false);
+                };
+                entry.enabled = !entry.enabled;
+            }
+            KeyCode::Left | KeyCode::Right => {
+                let Some(entry) = self.scheduler.selected_mut() else {
+                    return Ok(false);
+                };
+                entry.shift_at(if key.code == KeyCode::Left { -15 } else { 15 });
+            }
+            KeyCode::Char('v') => {
+                let Some(entry) = self.scheduler.selected_mut() else {
+                    return Ok(false);
+                };
+                entry.kind = if entry.kind == JobKind::Validate {
+                    JobKind::Backtest
+                } else {
+                    JobKind::Validate
+                };
+            }
+            KeyCode::Char('i') => {
+                let Some(entry) = self.scheduler.selected_mut() else {
+                    return Ok(false);
+                };
+                if entry.ingest_symbol.take().is_none() {
+                    match kairos_application::config::load_config(&entry.config) {
+                        Ok(cfg) => entry.ingest_symbol = Some(cfg.run.symbol),
+                        Err(err) => {
+                            self.set_error_and_clear_info(&err.to_string());
+                            return Ok(false);
+                        }
+                    }
+                }
+            }
+            KeyCode::Char('r') => match self.scheduler.take_selected(now) {
+                Some(run) => self.queue_scheduled(vec![run]),
+                None => {
+                    if !self.scheduler.entries.is_empty() {
+                        self.set_error_and_clear_info("this entry is already running");
+                    }
+                    return Ok(false);
+                }
+            },
+            _ => return Ok(false),
+        }
+        self.save_schedule();
+        self.dirty = true;
+        Ok(false)
+    }
+
+    fn handle_ingest_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
+        let form = &mut self.ingest_form;
+        if form.editing {
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Tab => form.editing = false,
+                KeyCode::Backspace => form.selected_input().backspace(),
+                KeyCode::Delete => form.selected_input().delete(),
+                KeyCode::Left => form.selected_input().move_left(),
+                KeyCode::Right => form.selected_input().move_right(),
+                KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    form.selected_input().insert_char(ch)
+                }
+                _ => return Ok(false),
+            }
+            self.dirty = true;
+            return Ok(false);
+        }
+        match key.code {
+            KeyCode::Esc => self.active_view = ViewId::MainMenu,
+            KeyCode::Up => form.select_prev(),
+            KeyCode::Down => form.select_next(),
+            KeyCode::Enter => form.editing = true,
+            KeyCode::Char('c') => match self.config.as_ref() {
+                Some(cfg) => form.fill_from_config(cfg),
+                None => self.set_error_and_clear_info("load a config first (Setup)"),
+            },
+            _ if self.keymap.matches(Action::Run, &key) => self.start_ingest(),
+            _ if self.keymap.matches(Action::Cancel, &key) => {
+                if self.status.kind == Some(TaskKind::Ingest) {
+                    self.request_cancel();
+                }
+            }
+            _ => return Ok(false),
+        }
+        self.dirty = true;
+        Ok(false)
+    }
+
+    // First launch (no config and no runs yet): the guided setup instead of an empty menu.
+    pub fn start_onboarding(&mut self) {
+        self.onboarding = Onboarding::new();
+        self.active_view = ViewId::Onboarding;
+        self.dirty = true;
+    }
+
+    fn handle_onboarding_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
+        let form = &mut self.onboarding;
+        if form.editing {
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Tab => form.editing = false,
+                KeyCode::Backspace => form.selected_input().backspace(),
+                KeyCode::Delete => form.selected_input().delete(),
+                KeyCode::Left => form.selected_input().move_left(),
+                KeyCode::Right => form.selected_input().move_right(),
+                KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    form.selected_input().insert_char(ch)
+                }
+                _ => return Ok(false),
             }
-            QuickEditField::LlmManagedAgent => unreachable!("handled above"),
-        };
+            self.dirty = true;
+            return Ok(false);
+        }
+        match key.code {
+            KeyCode::Esc => self.active_view = ViewId::MainMenu,
+            KeyCode::Up => form.select_prev(),
+            KeyCode::Down => form.select_next(),
+            KeyCode::Enter => form.editing = true,
+            _ if self.keymap.matches(Action::Cancel, &key) => {
+                if form.is_running(Step::SampleIngest) {
+                    self.request_cancel();
+                }
+            }
+            KeyCode::Char(ch) => match crate::onboarding::STEPS
+                .into_iter()
+                .find(|step| step.key() == ch)
+            {
+                Some(step @ (Step::Database | Step::Migrations)) => {
+                    self.run_onboarding_db_step(step)
+                }
+                Some(Step::SampleIngest) => self.start_sample_ingest(),
+                Some(Step::StarterConfig) => self.write_starter_config(),
+                None => return Ok(false),
+            },
+            _ => return Ok(false),
+        }
+        self.dirty = true;
+        Ok(false)
+    }
 
-        if let Err(err) = result {
-            self.set_error_and_clear_info(&err);
+    fn run_onboarding_db_step(&mut self, step: Step) {
+        if self.onboarding.is_running(step) {
             return;
         }
-
-        let config_toml = match kairos_application::config::to_toml_pretty(&next) {
-            Ok(s) => s,
-            Err(err) => {
-                self.set_error_and_clear_info(&err);
-                return;
+        match self.onboarding.db_url() {
+            Ok(db_url) => {
+                self.last_error = None;
+                self.onboarding.set_state(step, StepState::Running);
+                self.task_runner.run_onboarding_step(step, db_url);
             }
-        };
-
-        self.config = Some(Arc::new(next));
-        self.config_toml = Some(config_toml);
-        self.last_error = None;
-        self.info_message = Some("quick edit applied".to_string());
-        self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(2));
+            Err(err) => self.set_error_and_clear_info(&err),
+        }
     }
 
-    fn set_error_and_clear_info(&mut self, msg: &str) {
-        self.last_error = Some(msg.to_string());
-        self.info_message = None;
-        self.info_expires_at = None;
+    fn start_sample_ingest(&mut self) {
+        if self.status.running {
+            self.set_error_and_clear_info("a task is already running");
+            return;
+        }
+        match self
+            .onboarding
+            .sample_ingest(chrono::Utc::now().timestamp())
+        {
+            Ok((args, range)) => {
+                self.onboarding
+                    .set_state(Step::SampleIngest, StepState::Running);
+                self.launch_ingest(args, range);
+            }
+            Err(err) => self.set_error_and_clear_info(&err),
+        }
     }
 
-    fn try_load_selected_config(&mut self) {
-        let Some(path) = self.available_configs.get(self.selected_config).cloned() else {
-            let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-            let config_dir = resolve_config_dir(cwd.as_path());
-            self.last_error = Some(format!(
-                "no configs found under {} or recents",
-                config_dir.display()
-            ));
-            return;
+    // Writes and loads the starter config, then opens Backtest to run it.
+    fn write_starter_config(&mut self) {
+        let path = match self.onboarding.write_config() {
+            Ok(path) => path,
+            Err(err) => {
+                self.onboarding
+                    .set_state(Step::StarterConfig, StepState::Failed(err.clone()));
+                self.set_error_and_clear_info(&err);
+                return;
+            }
         };
         self.config_input = TextInput::new(path.display().to_string());
         self.try_load_config();
+        if let Some(err) = self.last_error.clone() {
+            self.onboarding
+                .set_state(Step::StarterConfig, StepState::Failed(err));
+            return;
+        }
+        self.onboarding.set_state(
+            Step::StarterConfig,
+            StepState::Done(format!("wrote {}", path.display())),
+        );
+        self.backtest_tab = BacktestTab::Backtest;
+        self.active_view = ViewId::Backtest;
+        self.info_message = Some(format!(
+            "starter config loaded; {} runs a backtest",
+            self.keymap.label(Action::Run)
+        ));
+        self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(5));
     }
 
-    fn refresh_available_configs(&mut self) {
-        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-        let configs_dir = resolve_config_dir(cwd.as_path());
-        let mut configs: Vec<PathBuf> = Vec::new();
-        if let Ok(entries) = std::fs::read_dir(&configs_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path
-                    .extension()
-                    .and_then(|s| s.to_str())
-                    .map(|s| s.eq_ignore_ascii_case("toml"))
-                    .unwrap_or(false)
-                {
-                    configs.push(std::fs::canonicalize(&path).unwrap_or(path));
+    fn handle_metrics_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
+        if let Some(input) = self.metrics_addr_input.as_mut() {
+            match key.code {
+                KeyCode::Esc => self.metrics_addr_input = None,
+                KeyCode::Enter => {
+                    let raw = input.value.trim().to_string();
+                    self.metrics_addr_input = None;
+                    self.start_metrics_exporter(&raw);
+                }
+                KeyCode::Backspace => input.backspace(),
+                KeyCode::Delete => input.delete(),
+                KeyCode::Left => input.move_left(),
+                KeyCode::Right => input.move_right(),
+                KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    input.insert_char(ch)
                 }
+                _ => return Ok(false),
             }
+            self.dirty = true;
+            return Ok(false);
         }
-        configs.sort();
-
-        let recents = load_recent_configs()
-            .unwrap_or_default()
-            .into_iter()
-            .filter(|p| p.exists())
-            .collect::<Vec<_>>();
-
-        let (merged, recent_count) = merge_recents_and_configs(&recents, &configs);
-        self.recent_config_count = recent_count;
-        self.available_configs = merged;
-        if self.selected_config >= self.available_configs.len() {
-            self.selected_config = self.available_configs.len().saturating_sub(1);
+        let scroll = &mut self.metrics.scroll;
+        match key.code {
+            KeyCode::Esc => self.active_view = ViewId::MainMenu,
+            KeyCode::Char('x') => match metrics_exporter::stop() {
+                Some(addr) => {
+                    self.last_error = None;
+                    self.info_message = Some(format!("metrics exporter on {addr} stopped"));
+                    self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(3));
+                }
+                None => {
+                    let addr = self.metrics_addr.clone();
+                    self.start_metrics_exporter(&addr);
+                }
+            },
+            KeyCode::Char('a') => {
+                self.metrics_addr_input = Some(TextInput::new(self.metrics_addr.clone()))
+            }
+            KeyCode::Up => *scroll = scroll.saturating_sub(1),
+            KeyCode::Down => *scroll = scroll.saturating_add(1),
+            KeyCode::PageUp => *scroll = scroll.saturating_sub(10),
+            KeyCode::PageDown => *scroll = scroll.saturating_add(10),
+            _ => return Ok(false),
         }
+        self.dirty = true;
+        Ok(false)
     }
 
-    fn record_recent_config(&self, raw_path: &str) {
-        let path = PathBuf::from(raw_path);
-        let abs = std::fs::canonicalize(&path).unwrap_or(path);
-        let mut recents = load_recent_configs().unwrap_or_default();
-        recents.retain(|p| p != &abs);
-        recents.insert(0, abs);
-        recents.truncate(10);
-        let _ = store_recent_configs(&recents);
+    // Starts the exporter on `raw`, or moves it there when it is running.
+    fn start_metrics_exporter(&mut self, raw: &str) {
+        let started = raw
+            .parse::<std::net::SocketAddr>()
+            .map_err(|err| format!("invalid metrics address {raw:?} (expected host:port): {err}"))
+            .and_then(metrics_exporter::start);
+        match started {
+            Ok(addr) => {
+                self.metrics_addr = raw.to_string();
+                self.last_error = None;
+                self.info_message = Some(format!("metrics exporter serving http://{addr}/metrics"));
+                self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(3));
+            }
+            Err(err) => self.set_error_and_clear_info(&err),
+        }
     }
 
-    fn start_experiments_sweep(&mut self) {
-        if self.status.running {
-            return;
+    fn handle_data_quality_keys(&mut self, key: KeyEvent) -> Result<bool, String> {
+        let Some(view) = self.data_quality.as_mut() else {
+            self.active_view = ViewId::Backtest;
+            return Ok(false);
+        };
+        match key.code {
+            KeyCode::Esc => self.active_view = ViewId::Backtest,
+            KeyCode::Up | KeyCode::Left => view.select_prev(),
+            KeyCode::Down | KeyCode::Right => view.select_next(),
+            KeyCode::Char('b') => {
+                let range = view.selected_range();
+                self.start_backfill(range);
+            }
+            KeyCode::Char('a') => {
+                let range = view.all_gaps_range();
+                self.start_backfill(range);
+            }
+            _ => return Ok(false),
         }
+        self.dirty = true;
+        Ok(false)
+    }
 
-        let sweep_raw = self.experiments_sweep_path.value.trim();
-        if sweep_raw.is_empty() {
-            self.set_error_and_clear_info("sweep config path cannot be empty");
+    // Ingests the config's market over a gap (from the bar before it to the bar after it) and
+    // switches to the Ingest view to follow it; upserts leave the bars around the gap unchanged.
+    fn start_backfill(&mut self, range: Option<(i64, i64)>) {
+        let Some((start, end)) = range else {
+            self.set_error_and_clear_info("no gaps to backfill");
             return;
-        }
+        };
+        let Some(cfg) = self.config.as_ref() else {
+            self.set_error_and_clear_info("load a config first (Setup)");
+            return;
+        };
+        self.ingest_form.fill_from_config(cfg);
+        self.ingest_form.set_range(start, end);
+        self.active_view = ViewId::Ingest;
+        self.start_ingest();
+    }
 
-        let sweep_path = PathBuf::from(sweep_raw);
-        if !sweep_path.exists() {
-            self.set_error_and_clear_info("sweep config not found");
+    fn start_ingest(&mut self) {
+        if self.status.running {
+            self.set_error_and_clear_info("a task is already running");
             return;
         }
-
-        let parallelism = {
-            let raw = self.experiments_parallelism.value.trim();
-            if raw.is_empty() {
-                None
-            } else {
-                let parsed = match raw.parse::<usize>() {
-                    Ok(v) => v,
-                    Err(_) => {
-                        self.set_error_and_clear_info("parallelism must be a positive integer");
-                        return;
-                    }
-                };
-                if parsed == 0 {
-                    self.set_error_and_clear_info("parallelism must be >= 1");
-                    return;
-                }
-                Some(parsed)
+        let db_url = self.config.as_ref().and_then(|cfg| cfg.db.url.clone());
+        let (args, range) = match self.ingest_form.job(db_url) {
+            Ok(job) => job,
+            Err(err) => {
+                self.set_error_and_clear_info(&err);
+                return;
             }
         };
+        self.launch_ingest(args, range);
+    }
 
+    fn launch_ingest(&mut self, args: crate::ingest::KucoinIngestArgs, range: (i64, i64)) {
+        let config = self.config.clone();
         self.status.running = true;
         self.paused = false;
         self.cancel_requested = false;
-        self.pause_blink = true;
         self.tick_counter = 0;
-        self.status.kind = Some(TaskKind::Sweep);
+        self.status.kind = Some(TaskKind::Ingest);
         self.status.started_at = Some(Instant::now());
         self.status.last_result = None;
-        self.stream_status = None;
-        self.experiments_progress = None;
+        self.ingest_form.progress = IngestProgress::start(range);
         self.last_error = None;
-        self.info_message = Some("sweep started".to_string());
+        self.info_message = Some("ingest started".to_string());
         self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(2));
+        tracing::info!(start = %args.start, "ingest started from TUI");
 
-        self.task_runner
-            .start_sweep(sweep_path, parallelism, self.experiments_resume);
+        self.task_runner.start_ingest(args, config);
     }
 
     fn start_selected_task(&mut self) -> Result<(), String> {
@@ -1300,7 +3118,9 @@ impl App {
             BacktestTab::Backtest => TaskKind::Backtest,
             BacktestTab::Paper => {
                 if self.paper_realtime {
-                    TaskKind::PaperRealtime
+                    TaskKind::PaperRealtime {
+                        simulated: self.paper_simulated,
+                    }
                 } else {
                     TaskKind::Paper
                 }
@@ -1310,7 +3130,7 @@ impl App {
         if self.require_validate_before_run
             && matches!(
                 kind,
-                TaskKind::Backtest | TaskKind::Paper | TaskKind::PaperRealtime
+                TaskKind::Backtest | TaskKind::Paper | TaskKind::PaperRealtime { .. }
             )
             && self.last_validate_ok != Some(true)
         {
@@ -1348,7 +3168,7 @@ impl App {
         if managed
             && matches!(
                 kind,
-                TaskKind::Backtest | TaskKind::Paper | TaskKind::PaperRealtime
+                TaskKind::Backtest | TaskKind::Paper | TaskKind::PaperRealtime { .. }
             )
         {
             if let Err(err) = self.ensure_managed_llm_agent(cfg.as_ref(), agent_llm.as_ref()) {
@@ -1365,22 +3185,77 @@ impl App {
         self.status.kind = Some(kind);
         self.status.started_at = Some(Instant::now());
         self.status.last_result = None;
+        self.reset_monitor();
+        if matches!(
+            kind,
+            TaskKind::Backtest | TaskKind::Paper | TaskKind::PaperRealtime { .. }
+        ) {
+            self.active_view = ViewId::Monitor;
+        }
+
+        self.task_runner
+            .start(kind, cfg, cfg_toml, agent_llm, self.paper_resume);
+        Ok(())
+    }
 
+    fn reset_monitor(&mut self) {
         self.price_series.clear();
         self.equity_series.clear();
+        self.live_equity = LiveEquity::default();
+        self.risk_sample = None;
+        self.agent_stats = AgentStats::default();
         self.trades.clear();
         self.trade_scroll = 0;
         self.paused = false;
         self.stream_status = None;
-        if matches!(
-            kind,
-            TaskKind::Backtest | TaskKind::Paper | TaskKind::PaperRealtime
-        ) {
-            self.active_view = ViewId::Monitor;
+        self.positions = PositionPanel::default();
+    }
+
+    // Opens a run started elsewhere (a headless paper/live session) in the Monitor, streaming
+    // its live.jsonl until the run ends; cancelling only stops following.
+    pub fn start_follow(&mut self, run_dir: PathBuf) {
+        if self.status.running {
+            self.set_error_and_clear_info("a task is already running");
+            return;
         }
+        self.status.running = true;
+        self.paused = false;
+        self.cancel_requested = false;
+        self.pause_blink = true;
+        self.tick_counter = 0;
+        self.status.kind = Some(TaskKind::Follow);
+        self.status.started_at = Some(Instant::now());
+        self.status.last_result = None;
+        self.reset_monitor();
+        self.active_view = ViewId::Monitor;
+        self.last_error = None;
+        self.info_message = Some(format!("following {}", run_dir.display()));
+        self.info_expires_at = Some(Instant::now() + std::time::Duration::from_secs(3));
+        self.task_runner.start_follow(run_dir);
+    }
 
-        self.task_runner.start(kind, cfg, cfg_toml, agent_llm);
-        Ok(())
+    // The selected run when its journal is still open, else the most recently written live run
+    // under the out dir.
+    fn follow_live_run(&mut self) {
+        let selected = self
+            .run_browser
+            .selected_run()
+            .map(|run| PathBuf::from(&run.run_dir))
+            .filter(|dir| crate::follow::journal_active(&dir.join(LIVE_JOURNAL_FILE)));
+        let out_dir = self.reports_out_dir();
+        let run_dir = selected.or_else(|| {
+            crate::follow::find_live_runs(&out_dir)
+                .into_iter()
+                .next()
+                .map(|run| run.run_dir)
+        });
+        match run_dir {
+            Some(run_dir) => self.start_follow(run_dir),
+            None => self.set_error_and_clear_info(&format!(
+                "no run in progress under {} (paper/live runs write {LIVE_JOURNAL_FILE})",
+                out_dir.display()
+            )),
+        }
     }
 
     fn ensure_managed_llm_agent(
@@ -1564,30 +3439,39 @@ fn resolve_managed_agent_script() -> PathBuf {
     PathBuf::from(DEFAULT_AGENT_LLM_SCRIPT)
 }
 
-fn recent_store_path() -> Option<PathBuf> {
+// Files the TUI keeps between sessions (recent configs, settings) live in one directory.
+// The config the last TUI session had loaded, if it still exists.
+pub fn last_session_config() -> Option<PathBuf> {
+    let state = load_state_from(&tui_store_path(STATE_FILE)?).ok()?;
+    state.config_path.filter(|path| path.is_file())
+}
+
+fn tui_store_path(file_name: &str) -> Option<PathBuf> {
     let override_dir = std::env::var("KAIROS_TUI_CONFIG_HOME").ok();
     let xdg = std::env::var("XDG_CONFIG_HOME").ok();
     let home = std::env::var("HOME").ok();
-    recent_store_path_from(override_dir.as_deref(), xdg.as_deref(), home.as_deref())
+    tui_store_path_from(
+        file_name,
+        override_dir.as_deref(),
+        xdg.as_deref(),
+        home.as_deref(),
+    )
 }
 
-fn recent_store_path_from(
+fn tui_store_path_from(
+    file_name: &str,
     config_home_override: Option<&str>,
     xdg_config_home: Option<&str>,
     home: Option<&str>,
 ) -> Option<PathBuf> {
     if let Some(dir) = config_home_override {
         if !dir.trim().is_empty() {
-            return Some(PathBuf::from(dir).join("recent_configs.json"));
+            return Some(PathBuf::from(dir).join(file_name));
         }
     }
     if let Some(xdg) = xdg_config_home {
         if !xdg.trim().is_empty() {
-            return Some(
-                PathBuf::from(xdg)
-                    .join("kairos-alloy")
-                    .join("recent_configs.json"),
-            );
+            return Some(PathBuf::from(xdg).join("kairos-alloy").join(file_name));
         }
     }
     if let Some(home) = home {
@@ -1596,7 +3480,7 @@ fn recent_store_path_from(
                 PathBuf::from(home)
                     .join(".config")
                     .join("kairos-alloy")
-                    .join("recent_configs.json"),
+                    .join(file_name),
             );
         }
     }
@@ -1604,14 +3488,14 @@ fn recent_store_path_from(
 }
 
 fn load_recent_configs() -> Result<Vec<PathBuf>, String> {
-    let Some(path) = recent_store_path() else {
+    let Some(path) = tui_store_path(RECENT_CONFIGS_FILE) else {
         return Ok(Vec::new());
     };
     load_recent_configs_from(&path)
 }
 
 fn store_recent_configs(paths: &[PathBuf]) -> Result<(), String> {
-    let Some(path) = recent_store_path() else {
+    let Some(path) = tui_store_path(RECENT_CONFIGS_FILE) else {
         return Ok(());
     };
     store_recent_configs_to(&path, paths)
@@ -1658,14 +3542,20 @@ fn merge_recents_and_configs(recents: &[PathBuf], configs: &[PathBuf]) -> (Vec<P
 #[cfg(test)]
 mod tests {
     use super::{
-        load_recent_configs_from, merge_recents_and_configs, recent_store_path_from,
-        store_recent_configs_to, App, QuickEditField, SetupFocus, TextInput,
+        load_recent_configs_from, merge_recents_and_configs, store_recent_configs_to,
+        tui_store_path_from, App, QuickEditField, SetupFocus, TaskState, TextInput, ViewId,
     };
+    use crate::config_form::FormFocus;
+    use crate::keymap::Keymap;
     use crate::logging::LogStore;
     use crate::tasks::TaskRunner;
+    use crate::tasks::{TaskEvent, TaskKind, TradeSample};
+    use crate::tui_state::TuiState;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use kairos_application::paper_trading::live::LIVE_JOURNAL_FILE;
     use std::path::PathBuf;
     use std::sync::Arc;
-    use std::time::{SystemTime, UNIX_EPOCH};
+    use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
     fn minimal_config() -> kairos_application::config::Config {
         kairos_application::config::Config {
@@ -1674,6 +3564,9 @@ mod tests {
                 symbol: "BTC-USDT".to_string(),
                 timeframe: "1min".to_string(),
                 initial_capital: 100.0,
+                start: None,
+                end: None,
+                max_bars: None,
             },
             db: kairos_application::config::DbConfig {
                 url: None,
@@ -1682,10 +3575,14 @@ mod tests {
                 market: "spot".to_string(),
                 source_timeframe: None,
                 pool_max_size: None,
+                cache_dir: None,
+                sentiment_table: None,
             },
             paths: kairos_application::config::PathsConfig {
                 sentiment_path: None,
+                sentiment_source: None,
                 out_dir: "runs/".to_string(),
+                sentiment_sources: None,
             },
             costs: kairos_application::config::CostsConfig {
                 fee_bps: 0.0,
@@ -1714,11 +3611,14 @@ mod tests {
                 fallback_action: kairos_domain::value_objects::action_type::ActionType::Hold,
                 api_version: "v1".to_string(),
                 feature_version: "v1".to_string(),
+                max_calls: None,
+                max_total_ms: None,
             },
             strategy: None,
             metrics: None,
             data_quality: None,
             paper: None,
+            live: None,
             report: None,
         }
     }
@@ -1751,15 +3651,21 @@ mod tests {
     }
 
     #[test]
-    fn recent_store_path_prefers_override_then_xdg_then_home() {
-        let path = recent_store_path_from(Some("/tmp/kairos"), Some("/xdg"), Some("/home/u"))
-            .expect("path");
+    fn tui_store_path_prefers_override_then_xdg_then_home() {
+        let path = tui_store_path_from(
+            "recent_configs.json",
+            Some("/tmp/kairos"),
+            Some("/xdg"),
+            Some("/home/u"),
+        )
+        .expect("path");
         assert_eq!(
             path,
             PathBuf::from("/tmp/kairos").join("recent_configs.json")
         );
 
-        let path = recent_store_path_from(None, Some("/xdg"), Some("/home/u")).expect("path");
+        let path = tui_store_path_from("recent_configs.json", None, Some("/xdg"), Some("/home/u"))
+            .expect("path");
         assert_eq!(
             path,
             PathBuf::from("/xdg")
@@ -1767,7 +3673,8 @@ mod tests {
                 .join("recent_configs.json")
         );
 
-        let path = recent_store_path_from(None, None, Some("/home/u")).expect("path");
+        let path =
+            tui_store_path_from("recent_configs.json", None, None, Some("/home/u")).expect("path");
         assert_eq!(
             path,
             PathBuf::from("/home/u")
@@ -1831,4 +3738,304 @@ mod tests {
         let toml = app.config_toml.as_deref().unwrap_or("");
         assert!(toml.contains("run_id = \"run_123\""));
     }
+
+    #[test]
+    fn config_editor_applies_valid_edits_to_the_session_config() {
+        let mut app = make_app();
+        let cfg = minimal_config();
+        app.config_toml = Some(kairos_application::config::to_toml_pretty(&cfg).unwrap());
+        app.config = Some(Arc::new(cfg));
+        app.active_view = ViewId::Setup;
+
+        app.handle_setup_keys(KeyEvent::from(KeyCode::F(2)))
+            .expect("open");
+        assert_eq!(app.active_view, ViewId::ConfigEditor);
+        let form = app.config_form.as_mut().expect("form");
+        assert!(
+            form.is_valid(),
+            "{:?} {:?}",
+            form.errors,
+            form.general_errors
+        );
+        form.selected = form
+            .fields
+            .iter()
+            .position(|f| f.path == "costs.fee_bps")
+            .expect("fee field");
+
+        app.handle_config_editor_keys(KeyEvent::from(KeyCode::Enter))
+            .expect("edit");
+        app.config_form.as_mut().unwrap().input = TextInput::new("-".to_string());
+        app.handle_config_editor_keys(KeyEvent::from(KeyCode::Char('3')))
+            .expect("type");
+        app.handle_config_editor_keys(KeyEvent::from(KeyCode::Enter))
+            .expect("commit");
+        let form = app.config_form.as_ref().unwrap();
+        assert_eq!(form.focus, FormFocus::Fields);
+        assert!(form.errors.contains_key("costs.fee_bps"));
+        app.handle_config_editor_keys(KeyEvent::from(KeyCode::Char('a')))
+            .expect("apply");
+        assert!(app.last_error.is_some());
+
+        app.handle_config_editor_keys(KeyEvent::from(KeyCode::Enter))
+            .expect("edit");
+        app.config_form.as_mut().unwrap().input = TextInput::new("3".to_string());
+        app.handle_config_editor_keys(KeyEvent::from(KeyCode::Enter))
+            .expect("commit");
+        app.handle_config_editor_keys(KeyEvent::from(KeyCode::Char('a')))
+            .expect("apply");
+        assert!(app.last_error.is_none());
+        assert_eq!(app.config.as_ref().unwrap().costs.fee_bps, 3.0);
+        assert!(app.config_toml.as_deref().unwrap().contains("fee_bps = 3"));
+
+        app.handle_config_editor_keys(KeyEvent::from(KeyCode::Esc))
+            .expect("back");
+        assert_eq!(app.active_view, ViewId::Setup);
+    }
+
+    #[test]
+    fn session_state_restores_view_and_scrolls() {
+        let mut app = make_app();
+        app.active_view = ViewId::Metrics;
+        app.log_scroll = 7;
+        app.metrics.scroll = 3;
+        let state = app.current_state();
+        assert_eq!(state.view, Some(ViewId::Metrics));
+
+        let mut restored = make_app();
+        restored.apply_state(state);
+        assert_eq!(restored.active_view, ViewId::Metrics);
+        assert_eq!((restored.log_scroll, restored.metrics.scroll), (7, 3));
+
+        let mut restored = make_app();
+        restored.apply_state(TuiState {
+            view: Some(ViewId::Leaderboard),
+            ..TuiState::default()
+        });
+        assert_eq!(restored.active_view, ViewId::Experiments);
+    }
+
+    #[test]
+    fn long_tasks_pop_a_toast_and_ring_the_bell_when_they_finish() {
+        let mut app = make_app();
+        app.settings.notify.bell = true;
+        app.status.running = true;
+        app.status.kind = Some(TaskKind::Backtest);
+        app.status.started_at = Some(Instant::now());
+        app.on_event(TaskEvent::TaskFinished(Ok("runs/x".to_string())))
+            .expect("finished");
+        assert!(app.toast.is_none());
+        assert_eq!(app.take_terminal_alerts(), "");
+
+        app.status.running = true;
+        app.status.started_at = Some(Instant::now() - Duration::from_secs(7_200));
+        app.on_event(TaskEvent::TaskFinished(Err("db down".to_string())))
+            .expect("failed");
+        let toast = app.toast.as_ref().expect("toast");
+        assert!(toast.failed);
+        assert_eq!(toast.text, "backtest failed after 2h 00m: db down");
+        assert_eq!(app.take_terminal_alerts(), "\x07");
+        assert_eq!(app.take_terminal_alerts(), "");
+
+        app.toast = None;
+        app.status.running = true;
+        app.status.started_at = Some(Instant::now() - Duration::from_secs(60));
+        app.request_cancel();
+        app.on_event(TaskEvent::TaskFinished(Err(
+            "backtest cancelled".to_string()
+        )))
+        .expect("cancelled");
+        assert!(app.toast.is_none());
+    }
+
+    #[test]
+    fn validate_report_opens_the_data_quality_dashboard() {
+        let mut app = make_app();
+        app.active_view = ViewId::Backtest;
+        let report = serde_json::json!({
+            "ohlcv": { "gaps": 1, "gap_ranges": [[60, 300]] },
+            "limits": { "max_gaps": 0 },
+        });
+        app.on_event(TaskEvent::DataQuality(report))
+            .expect("report");
+        assert_eq!(app.active_view, ViewId::DataQuality);
+        let view = app.data_quality.as_ref().expect("view");
+        assert!(!view.passed());
+        assert_eq!(view.selected_range(), Some((60, 300)));
+
+        app.on_key(KeyEvent::from(KeyCode::Char('b')))
+            .expect("backfill");
+        assert_eq!(
+            app.last_error.as_deref(),
+            Some("load a config first (Setup)")
+        );
+        app.on_key(KeyEvent::from(KeyCode::Esc)).expect("back");
+        assert_eq!(app.active_view, ViewId::Backtest);
+    }
+
+    #[test]
+    fn rebound_keys_drive_navigation_and_the_help_overlay() {
+        let mut app = make_app();
+        app.keymap =
+            Keymap::from_toml_str("[keys]\ndown = [\"j\"]\nquit = [\"ctrl-q\"]\n").expect("keymap");
+
+        app.on_key(KeyEvent::from(KeyCode::Char('j'))).expect("j");
+        assert_eq!(app.menu_index, 1);
+        app.on_key(KeyEvent::from(KeyCode::Down)).expect("down");
+        assert_eq!(app.menu_index, 1);
+        assert!(!app.on_key(KeyEvent::from(KeyCode::Char('q'))).expect("q"));
+
+        app.on_key(KeyEvent::from(KeyCode::Char('?')))
+            .expect("help");
+        assert!(app.show_help);
+        app.on_key(KeyEvent::from(KeyCode::Char('j')))
+            .expect("ignored");
+        assert_eq!(app.menu_index, 1);
+        app.on_key(KeyEvent::from(KeyCode::Esc)).expect("close");
+        assert!(!app.show_help);
+
+        app.active_view = ViewId::Setup;
+        app.setup_focus = SetupFocus::Input;
+        app.config_input = TextInput::new(String::new());
+        app.on_key(KeyEvent::from(KeyCode::Char('j')))
+            .expect("typed");
+        assert_eq!(app.config_input.value, "j");
+
+        app.active_view = ViewId::MainMenu;
+        let ctrl_q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        assert!(app.on_key(ctrl_q).expect("quit"));
+    }
+
+    #[test]
+    fn export_keys_write_the_monitor_trades_under_the_out_dir() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let mut app = make_app();
+        app.default_out_dir = std::env::temp_dir().join(format!("kairos_tui_export_app_{unique}"));
+        app.active_view = ViewId::Monitor;
+        app.on_key(KeyEvent::from(KeyCode::Char('e'))).expect("key");
+        assert_eq!(app.last_error.as_deref(), Some("no trades to export"));
+
+        app.trades.push_back(TradeSample {
+            bar_index: 1,
+            timestamp: 60,
+            side: kairos_domain::value_objects::side::Side::Buy,
+            quantity: 2.0,
+            price: 10.0,
+        });
+        app.on_key(KeyEvent::new(KeyCode::Char('E'), KeyModifiers::SHIFT))
+            .expect("key");
+        assert!(app.last_error.is_none());
+        let exports: Vec<PathBuf> = std::fs::read_dir(app.default_out_dir.join("exports"))
+            .expect("exports dir")
+            .flatten()
+            .map(|entry| entry.path())
+            .collect();
+        assert_eq!(exports.len(), 1);
+        assert_eq!(
+            exports[0].extension().and_then(|e| e.to_str()),
+            Some("json")
+        );
+        assert!(app
+            .info_message
+            .as_deref()
+            .is_some_and(|info| info.starts_with("exported 1 trades to ")));
+        let _ = std::fs::remove_dir_all(&app.default_out_dir);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn follow_key_streams_the_newest_unfinished_journal_until_it_ends() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut app = App::new(
+            None,
+            std::env::temp_dir().join(format!("kairos_tui_follow_app_{unique}")),
+            Arc::new(parking_lot::Mutex::new(LogStore::new(10))),
+            TaskRunner::new(tx),
+        );
+        app.active_view = ViewId::Runs;
+        app.on_key(KeyEvent::from(KeyCode::Char('f'))).expect("key");
+        assert!(app
+            .last_error
+            .as_deref()
+            .is_some_and(|err| err.starts_with("no run in progress under ")));
+
+        let run_dir = app.default_out_dir.join("paper_1");
+        std::fs::create_dir_all(&run_dir).expect("mkdir");
+        let journal = run_dir.join(LIVE_JOURNAL_FILE);
+        std::fs::write(
+            &journal,
+            concat!(
+                "{\"type\":\"start\",\"run_id\":\"p1\",\"symbol\":\"BTC-USDT\",\"mode\":\"replay\"}\n",
+                "{\"type\":\"equity\",\"timestamp_utc\":60,\"bar_index\":1,\"close\":10.0,\"equity\":100.0}\n",
+            ),
+        )
+        .expect("write");
+        app.on_key(KeyEvent::from(KeyCode::Char('f'))).expect("key");
+        assert_eq!(app.status.kind, Some(TaskKind::Follow));
+        assert_eq!(app.active_view, ViewId::Monitor);
+
+        let progress = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("follow event")
+            .expect("channel open");
+        assert!(matches!(&progress, TaskEvent::Progress(p) if p.equity == 100.0));
+        app.on_event(progress).expect("progress");
+        assert_eq!(app.equity_series.len(), 1);
+
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&journal)
+            .expect("open");
+        std::io::Write::write_all(&mut file, b"{\"type\":\"end\",\"status\":\"completed\"}\n")
+            .expect("append");
+        let finished = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("follow event")
+            .expect("channel open");
+        assert!(
+            matches!(&finished, TaskEvent::TaskFinished(Ok(msg)) if msg.ends_with("ended: completed"))
+        );
+        app.on_event(finished).expect("finished");
+        assert!(!app.status.running);
+        let _ = std::fs::remove_dir_all(&app.default_out_dir);
+    }
+
+    #[test]
+    fn cancel_stays_pending_until_the_task_reports_back() {
+        let mut app = make_app();
+        assert_eq!(app.task_state(), TaskState::Idle);
+        app.status.running = true;
+        app.status.kind = Some(TaskKind::Sweep);
+        app.toggle_pause();
+        assert_eq!(app.task_state(), TaskState::Running);
+        assert!(app
+            .last_error
+            .as_deref()
+            .unwrap()
+            .contains("cannot be paused"));
+
+        app.status.kind = Some(TaskKind::Backtest);
+        app.handle_backtest_keys(KeyEvent::from(KeyCode::Char('x')))
+            .expect("cancel");
+        assert_eq!(app.task_state(), TaskState::Cancelling);
+        assert!(app.status.last_result.is_none());
+
+        app.on_event(TaskEvent::TaskFinished(Err(
+            "backtest cancelled".to_string()
+        )))
+        .expect("finished");
+        assert_eq!(app.task_state(), TaskState::Cancelled);
+        assert_eq!(app.info_message.as_deref(), Some("backtest cancelled"));
+
+        app.status.running = true;
+        app.on_event(TaskEvent::TaskFinished(Err("db down".to_string())))
+            .expect("finished");
+        assert_eq!(app.task_state(), TaskState::Failed);
+    }
 }
@@ -0,0 +1,359 @@
+use crate::output::CliError;
+use kairos_application::registry::read_run_record;
+use kairos_domain::repositories::run_registry::RunRecord;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::time::Instant;
+
+pub struct BatchArgs {
+    // Config paths or file-name globs (`configs/*.toml`); each match is one backtest.
+    pub configs: Vec<String>,
+    pub parallel: usize,
+    pub config_sets: Vec<String>,
+}
+
+pub struct BatchEntry {
+    pub config_path: PathBuf,
+    pub elapsed_ms: u128,
+    pub result: Result<RunRecord, String>,
+}
+
+pub struct BatchReport {
+    // In config order, independent of completion order.
+    pub entries: Vec<BatchEntry>,
+}
+
+impl BatchReport {
+    pub fn failed(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.result.is_err())
+            .count()
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let failed = self.failed();
+        serde_json::json!({
+            "status": if failed == 0 { "ok" } else { "failed" },
+            "mode": "batch",
+            "succeeded": self.entries.len() - failed,
+            "failed": failed,
+            "runs": self
+                .entries
+                .iter()
+                .map(|entry| {
+                    let mut value = match &entry.result {
+                        Ok(record) => serde_json::json!({ "status": "ok", "run": record }),
+                        Err(err) => serde_json::json!({ "status": "error", "error": err }),
+                    };
+                    value["config"] = entry.config_path.display().to_string().into();
+                    value["elapsed_ms"] = (entry.elapsed_ms as u64).into();
+                    value
+                })
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+// `kairos-alloy batch`: every config is an independent backtest (own run dir, registry entry);
+// one failing config does not stop the others.
+pub fn run_batch(args: &BatchArgs) -> Result<BatchReport, CliError> {
+    let mut config_paths = Vec::new();
+    for pattern in &args.configs {
+        config_paths.extend(expand_config_pattern(pattern).map_err(CliError::usage)?);
+    }
+    let mut unique = BTreeSet::new();
+    config_paths.retain(|path| unique.insert(path.clone()));
+    if config_paths.is_empty() {
+        return Err(CliError::usage("--configs matched no files"));
+    }
+    check_unique_run_dirs(&config_paths, &args.config_sets).map_err(CliError::config)?;
+
+    let worker_count = args.parallel.max(1).min(config_paths.len());
+    let next_index = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel::<(usize, BatchEntry)>();
+    std::thread::scope(|scope| {
+        for _ in 0..worker_count {
+            let tx = tx.clone();
+            let next_index = &next_index;
+            let config_paths = &config_paths;
+            scope.spawn(move || loop {
+                let idx = next_index.fetch_add(1, Ordering::Relaxed);
+                let Some(config_path) = config_paths.get(idx) else {
+                    break;
+                };
+                let started = Instant::now();
+                let result = run_one(config_path, &args.config_sets);
+                metrics::counter!(
+                    "kairos.batch.runs_total",
+                    "status" => if result.is_ok() { "ok" } else { "error" }
+                )
+                .increment(1);
+                let entry = BatchEntry {
+                    config_path: config_path.clone(),
+                    elapsed_ms: started.elapsed().as_millis(),
+                    result,
+                };
+                if tx.send((idx, entry)).is_err() {
+                    break;
+                }
+            });
+        }
+    });
+    drop(tx);
+
+    let mut entries: Vec<(usize, BatchEntry)> = rx.into_iter().collect();
+    entries.sort_by_key(|(idx, _)| *idx);
+    Ok(BatchReport {
+        entries: entries.into_iter().map(|(_, entry)| entry).collect(),
+    })
+}
+
+fn run_one(config_path: &Path, config_sets: &[String]) -> Result<RunRecord, String> {
+    let _span = tracing::info_span!("batch_run", config = %config_path.display()).entered();
+    let (config, config_toml) =
+        kairos_application::config::load_config_with_overrides(config_path, config_sets)
+            .map_err(|err| err.to_string())?;
+    let run_dir =
+        crate::headless::execute_backtest(&config, &config_toml).map_err(|err| err.message)?;
+    read_run_record(&run_dir)
+}
+
+// Two configs writing the same `<out_dir>/<run_id>` would overwrite each other's artifacts.
+// Configs that fail to load are left for `run_one` to report.
+fn check_unique_run_dirs(config_paths: &[PathBuf], config_sets: &[String]) -> Result<(), String> {
+    let mut seen: BTreeMap<PathBuf, &Path> = BTreeMap::new();
+    for config_path in config_paths {
+        let Ok((config, _)) =
+            kairos_application::config::load_config_with_overrides(config_path, config_sets)
+        else {
+            continue;
+        };
+        let run_dir = Path::new(&config.paths.out_dir).join(&config.run.run_id);
+        if let Some(previous) = seen.insert(run_dir.clone(), config_path) {
+            return Err(format!(
+                "{} and {} both write {} (give each config its own run.run_id)",
+                previous.display(),
+                config_path.display(),
+                run_dir.display()
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Plain paths are taken as-is; `*` and `?` are expanded in the file name only, sorted by path.
+pub fn expand_config_pattern(pattern: &str) -> Result<Vec<PathBuf>, String> {
+    let path = Path::new(pattern);
+    let Some(file_pattern) = path.file_name().and_then(|name| name.to_str()) else {
+        return Err(format!("invalid --configs pattern '{pattern}'"));
+    };
+    if !file_pattern.contains(['*', '?']) {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let dir = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    if dir.to_string_lossy().contains(['*', '?']) {
+        return Err(format!(
+            "invalid --configs pattern '{pattern}': wildcards are only supported in the file name"
+        ));
+    }
+    let entries =
+        std::fs::read_dir(dir).map_err(|err| format!("failed to read {}: {err}", dir.display()))?;
+    let mut matches: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| wildcard_match(file_pattern, name))
+        })
+        .map(|entry| {
+            if path.parent().is_some_and(|p| p.as_os_str().is_empty()) {
+                PathBuf::from(entry.file_name())
+            } else {
+                dir.join(entry.file_name())
+            }
+        })
+        .collect();
+    matches.sort();
+    Ok(matches)
+}
+
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    backtrack = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+pub fn format_report(report: &BatchReport) -> String {
+    let header = [
+        "CONFIG",
+        "STATUS",
+        "RUN_ID",
+        "TRADES",
+        "NET_PROFIT",
+        "SHARPE",
+        "MAX_DD",
+        "WIN_RATE",
+        "SECS",
+    ];
+    let rows: Vec<Vec<String>> = report
+        .entries
+        .iter()
+        .map(|entry| {
+            let config = entry.config_path.display().to_string();
+            let secs = format!("{:.1}", entry.elapsed_ms as f64 / 1000.0);
+            match &entry.result {
+                Ok(run) => vec![
+                    config,
+                    "ok".to_string(),
+                    run.run_id.clone(),
+                    run.trades.to_string(),
+                    format!("{:.2}", run.net_profit),
+                    format!("{:.3}", run.sharpe),
+                    format!("{:.2}%", run.max_drawdown * 100.0),
+                    format!("{:.1}%", run.win_rate * 100.0),
+                    secs,
+                ],
+                Err(_) => {
+                    let mut row = vec![config, "error".to_string()];
+                    row.extend(std::iter::repeat_n("-".to_string(), 6));
+                    row.push(secs);
+                    row
+                }
+            }
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = header.iter().map(|h| h.len()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let mut lines = vec![crate::runs::format_row(
+        header.iter().map(|h| h.to_string()),
+        &widths,
+    )];
+    for row in rows {
+        lines.push(crate::runs::format_row(row.into_iter(), &widths));
+    }
+
+    let failed = report.failed();
+    lines.push(format!(
+        "{} succeeded, {} failed",
+        report.entries.len() - failed,
+        failed
+    ));
+    for entry in &report.entries {
+        if let Err(err) = &entry.result {
+            lines.push(format!("error: {}: {err}", entry.config_path.display()));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand_config_pattern, format_report, wildcard_match, BatchEntry, BatchReport};
+    use kairos_domain::repositories::run_registry::RunRecord;
+    use std::path::PathBuf;
+
+    #[test]
+    fn wildcard_match_handles_star_and_question_mark() {
+        assert!(wildcard_match("*.toml", "sma.toml"));
+        assert!(wildcard_match("sma_?.toml", "sma_1.toml"));
+        assert!(wildcard_match("*_grid*.toml", "btc_grid_v2.toml"));
+        assert!(!wildcard_match("*.toml", "sma.toml.bak"));
+        assert!(!wildcard_match("sma_?.toml", "sma_10.toml"));
+    }
+
+    #[test]
+    fn expand_config_pattern_lists_matching_files_sorted() {
+        let dir = std::env::temp_dir().join(format!("kairos_batch_glob_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("dir");
+        for name in ["b.toml", "a.toml", "notes.md"] {
+            std::fs::write(dir.join(name), "").expect("write");
+        }
+        let pattern = format!("{}/*.toml", dir.display());
+        let matches = expand_config_pattern(&pattern).expect("expand");
+        assert_eq!(matches, vec![dir.join("a.toml"), dir.join("b.toml")]);
+        assert_eq!(
+            expand_config_pattern("plain.toml").expect("plain"),
+            vec![PathBuf::from("plain.toml")]
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn report_table_lists_failures_after_the_summary() {
+        let run = RunRecord {
+            run_id: "sma".to_string(),
+            run_dir: "runs/sma".to_string(),
+            config_hash: None,
+            symbol: None,
+            timeframe: None,
+            start: None,
+            end: None,
+            bars_processed: 10,
+            trades: 4,
+            win_rate: 0.5,
+            net_profit: 10.0,
+            sharpe: 1.25,
+            max_drawdown: 0.1,
+            recorded_at: 0,
+            notes: Vec::new(),
+        };
+        let report = BatchReport {
+            entries: vec![
+                BatchEntry {
+                    config_path: PathBuf::from("configs/sma.toml"),
+                    elapsed_ms: 1500,
+                    result: Ok(run),
+                },
+                BatchEntry {
+                    config_path: PathBuf::from("configs/bad.toml"),
+                    elapsed_ms: 10,
+                    result: Err("failed to query OHLCV".to_string()),
+                },
+            ],
+        };
+        let text = format_report(&report);
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].starts_with("CONFIG            STATUS  RUN_ID"));
+        assert!(lines[1].starts_with("configs/sma.toml  ok      sma"));
+        assert!(lines[1].ends_with("1.5"));
+        assert!(lines[2].starts_with("configs/bad.toml  error   -"));
+        assert_eq!(lines[3], "1 succeeded, 1 failed");
+        assert_eq!(lines[4], "error: configs/bad.toml: failed to query OHLCV");
+        assert_eq!(report.to_json()["status"], "failed");
+    }
+}
@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use kairos_application::config::{self, Config};
-use kairos_ingest::{ingest_kucoin, migrate_db, Market};
+use kairos_ingest::{ingest_kucoin, migrate_db, stderr_progress, InsertMode, Market};
 use std::env;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -22,7 +22,8 @@ struct CoverageStats {
 
 pub fn prepare_tui_startup(initial_config_path: Option<PathBuf>) -> Result<PathBuf, String> {
     let config_path = resolve_bootstrap_config_path(initial_config_path)?;
-    let (config, _config_toml) = config::load_config_with_source(config_path.as_path())?;
+    let (config, _config_toml) =
+        config::load_config_with_source(config_path.as_path()).map_err(|err| err.to_string())?;
 
     ensure_supported_exchange(&config)?;
     ensure_supported_market(&config.db.market)?;
@@ -113,7 +114,10 @@ async fn ensure_bootstrap_window(config: &Config, db_url: &str) -> Result<(), St
         "kucoin",
         DEFAULT_INGEST_SLEEP_MS,
         DEFAULT_INGEST_BATCH_SIZE,
+        InsertMode::default(),
         None,
+        false,
+        &mut stderr_progress(),
     )
     .await
     .map_err(|err| format!("bootstrap failed while ingesting OHLCV: {err}"))?;
@@ -0,0 +1,196 @@
+use kairos_application::reporting::chart::RunChart;
+use kairos_domain::value_objects::bar::Bar;
+use kairos_domain::value_objects::trade::Trade;
+
+const MIN_VISIBLE_BARS: usize = 10;
+const DEFAULT_VISIBLE_BARS: usize = 120;
+
+// The TUI candlestick view of a finished run: the loaded bars and fills, and the window of bars
+// on screen (`offset` is the first visible bar, `width` how many). Opens on the latest bars.
+pub struct CandleChart {
+    pub chart: RunChart,
+    pub offset: usize,
+    pub width: usize,
+}
+
+impl CandleChart {
+    pub fn new(chart: RunChart) -> Self {
+        let width = DEFAULT_VISIBLE_BARS.min(chart.bars.len());
+        let offset = chart.bars.len() - width;
+        Self {
+            chart,
+            offset,
+            width,
+        }
+    }
+
+    pub fn visible_bars(&self) -> &[Bar] {
+        &self.chart.bars[self.offset..self.offset + self.width]
+    }
+
+    // Fills inside the window, each with the index (within the window) of the bar it happened in.
+    pub fn visible_trades(&self) -> Vec<(usize, &Trade)> {
+        let bars = self.visible_bars();
+        let Some(first) = bars.first() else {
+            return Vec::new();
+        };
+        let next_ts = self
+            .chart
+            .bars
+            .get(self.offset + self.width)
+            .map_or(i64::MAX, |bar| bar.timestamp);
+        self.chart
+            .trades
+            .iter()
+            .filter(|trade| trade.timestamp >= first.timestamp && trade.timestamp < next_ts)
+            .map(|trade| {
+                let idx = bars.partition_point(|bar| bar.timestamp <= trade.timestamp);
+                (idx.saturating_sub(1).min(bars.len() - 1), trade)
+            })
+            .collect()
+    }
+
+    // Lowest low and highest high in the window, padded so wicks and markers stay inside.
+    pub fn price_bounds(&self) -> (f64, f64) {
+        let (min, max) = self
+            .visible_bars()
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), bar| {
+                (min.min(bar.low), max.max(bar.high))
+            });
+        if !min.is_finite() || !max.is_finite() {
+            return (0.0, 1.0);
+        }
+        if max <= min {
+            return (min - 1.0, max + 1.0);
+        }
+        let pad = (max - min) * 0.08;
+        (min - pad, max + pad)
+    }
+
+    // Halves the window around its centre.
+    pub fn zoom_in(&mut self) {
+        self.resize((self.width / 2).max(MIN_VISIBLE_BARS));
+    }
+
+    // Doubles the window around its centre, up to every bar.
+    pub fn zoom_out(&mut self) {
+        self.resize(self.width.saturating_mul(2));
+    }
+
+    fn resize(&mut self, width: usize) {
+        let len = self.chart.bars.len();
+        let width = width.min(len);
+        let centre = self.offset + self.width / 2;
+        self.width = width;
+        self.offset = centre.saturating_sub(width / 2).min(len - width);
+    }
+
+    // Moves the window by a quarter of its width; negative pans towards older bars.
+    pub fn pan(&mut self, quarters: isize) {
+        let step = (self.width / 4).max(1);
+        let max_offset = self.chart.bars.len() - self.width;
+        self.offset = self
+            .offset
+            .saturating_add_signed(quarters * step as isize)
+            .min(max_offset);
+    }
+
+    pub fn pan_to_start(&mut self) {
+        self.offset = 0;
+    }
+
+    pub fn pan_to_end(&mut self) {
+        self.offset = self.chart.bars.len() - self.width;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CandleChart;
+    use kairos_application::reporting::chart::RunChart;
+    use kairos_domain::value_objects::bar::Bar;
+    use kairos_domain::value_objects::side::Side;
+    use kairos_domain::value_objects::trade::Trade;
+
+    fn chart(bars: usize, trade_ts: &[i64]) -> CandleChart {
+        let bars = (0..bars as i64)
+            .map(|i| Bar {
+                symbol: "BTC-USDT".into(),
+                timestamp: i * 60,
+                open: 100.0 + i as f64,
+                high: 102.0 + i as f64,
+                low: 99.0 + i as f64,
+                close: 101.0 + i as f64,
+                volume: 1.0,
+            })
+            .collect();
+        let trades = trade_ts
+            .iter()
+            .map(|ts| Trade {
+                timestamp: *ts,
+                symbol: "BTC-USDT".into(),
+                side: Side::Buy,
+                quantity: 1.0,
+                price: 100.0,
+                fee: 0.0,
+                slippage: 0.0,
+                strategy_id: "test".to_string(),
+                reason: "signal".to_string(),
+            })
+            .collect();
+        CandleChart::new(RunChart {
+            run_id: "run".to_string(),
+            symbol: "BTC-USDT".to_string(),
+            timeframe: "1min".to_string(),
+            bars,
+            trades,
+        })
+    }
+
+    #[test]
+    fn zoom_and_pan_stay_inside_the_bars() {
+        let mut view = chart(300, &[]);
+        assert_eq!((view.offset, view.width), (180, 120));
+
+        view.zoom_in();
+        assert_eq!((view.offset, view.width), (210, 60));
+        view.pan(1);
+        assert_eq!(view.offset, 225);
+        view.pan(10);
+        assert_eq!(view.offset, 240);
+
+        view.zoom_out();
+        view.zoom_out();
+        assert_eq!((view.offset, view.width), (60, 240));
+        view.zoom_out();
+        assert_eq!((view.offset, view.width), (0, 300));
+
+        view.zoom_in();
+        view.pan(-10);
+        assert_eq!(view.offset, 0);
+        view.pan_to_end();
+        assert_eq!(view.offset, 150);
+    }
+
+    #[test]
+    fn trades_are_placed_on_the_bar_they_filled_in() {
+        let mut view = chart(20, &[0, 65, 600, 1_190]);
+        view.width = 10;
+        view.offset = 0;
+        let placed: Vec<(usize, i64)> = view
+            .visible_trades()
+            .into_iter()
+            .map(|(idx, trade)| (idx, trade.timestamp))
+            .collect();
+        assert_eq!(placed, [(0, 0), (1, 65)]);
+
+        view.pan_to_end();
+        let placed: Vec<usize> = view
+            .visible_trades()
+            .into_iter()
+            .map(|(idx, _)| idx)
+            .collect();
+        assert_eq!(placed, [0, 9]);
+    }
+}
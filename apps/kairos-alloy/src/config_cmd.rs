@@ -0,0 +1,29 @@
+use crate::output::CliError;
+use kairos_application::config::schema::{check_config_source, config_schema};
+use std::path::Path;
+
+// `kairos-alloy config schema`: pretty JSON Schema, to stdout or `--out`.
+pub fn write_schema(out: Option<&Path>) -> Result<Option<String>, String> {
+    let rendered = serde_json::to_string_pretty(&config_schema())
+        .map_err(|err| format!("failed to serialize schema: {err}"))?;
+    match out {
+        Some(path) => {
+            std::fs::write(path, format!("{rendered}\n"))
+                .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
+            Ok(None)
+        }
+        None => Ok(Some(rendered)),
+    }
+}
+
+// `kairos-alloy config check`: one `<file>:<line>:<col>: <field>: <message>` line per issue
+// (compiler-style, so editors and CI annotate the right line); empty when the config is valid.
+pub fn check_config(path: &Path) -> Result<Vec<String>, CliError> {
+    let source = std::fs::read_to_string(path).map_err(|err| {
+        CliError::config(format!("failed to read config {}: {err}", path.display()))
+    })?;
+    Ok(check_config_source(&source)
+        .into_iter()
+        .map(|issue| format!("{}:{issue}", path.display()))
+        .collect())
+}
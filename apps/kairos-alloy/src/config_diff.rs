@@ -0,0 +1,226 @@
+use crate::app::ViewId;
+use kairos_domain::repositories::artifacts::ArtifactReader;
+use kairos_infrastructure::artifacts::FilesystemArtifactReader;
+use std::path::Path;
+
+// Unchanged lines kept around each change; longer unchanged stretches collapse to one marker.
+const CONTEXT_LINES: usize = 2;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffLine {
+    Same(String),
+    Removed(String),
+    Added(String),
+    // Unchanged lines left out, and the `[table]` the next shown line sits in.
+    Skipped { lines: usize, table: Option<String> },
+}
+
+// A line diff of two config snapshots (the base on the left, the compared one on the right).
+pub struct ConfigDiffView {
+    pub left: String,
+    pub right: String,
+    pub lines: Vec<DiffLine>,
+    pub added: usize,
+    pub removed: usize,
+    pub scroll: usize,
+    // Where Esc goes back to.
+    pub back_to: ViewId,
+}
+
+impl ConfigDiffView {
+    pub fn new(left: String, left_toml: &str, right: String, right_toml: &str) -> Self {
+        let ops = diff_lines(&normalize(left_toml), &normalize(right_toml));
+        let added = ops
+            .iter()
+            .filter(|line| matches!(line, DiffLine::Added(_)))
+            .count();
+        let removed = ops
+            .iter()
+            .filter(|line| matches!(line, DiffLine::Removed(_)))
+            .count();
+        Self {
+            left,
+            right,
+            lines: collapse(ops, CONTEXT_LINES),
+            added,
+            removed,
+            scroll: 0,
+            back_to: ViewId::Runs,
+        }
+    }
+
+    // The config_snapshot.toml of a run dir, named by the dir.
+    pub fn load_snapshot(run_dir: &Path) -> Result<(String, String), String> {
+        let name = run_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| run_dir.display().to_string());
+        FilesystemArtifactReader::new()
+            .read_config_snapshot_toml(&run_dir.join("config_snapshot.toml"))?
+            .map(|toml| (name, toml))
+            .ok_or_else(|| format!("no config_snapshot.toml in {}", run_dir.display()))
+    }
+
+    pub fn scroll_by(&mut self, delta: isize) {
+        self.scroll = self
+            .scroll
+            .saturating_add_signed(delta)
+            .min(self.lines.len().saturating_sub(1));
+    }
+
+    pub fn scroll_to_end(&mut self) {
+        self.scroll = self.lines.len().saturating_sub(1);
+    }
+
+    // Scrolls to the next (or previous) line that starts a change.
+    pub fn jump_to_change(&mut self, forward: bool) {
+        let starts: Vec<usize> = (0..self.lines.len())
+            .filter(|&idx| {
+                is_change(&self.lines[idx]) && (idx == 0 || !is_change(&self.lines[idx - 1]))
+            })
+            .collect();
+        let next = if forward {
+            starts.into_iter().find(|&idx| idx > self.scroll)
+        } else {
+            starts.into_iter().rev().find(|&idx| idx < self.scroll)
+        };
+        if let Some(idx) = next {
+            self.scroll = idx;
+        }
+    }
+}
+
+fn is_change(line: &DiffLine) -> bool {
+    matches!(line, DiffLine::Added(_) | DiffLine::Removed(_))
+}
+
+// Snapshots are either the config file verbatim or re-serialized with overrides; re-serializing
+// both sides keeps comments and key order out of the diff. Unparsable TOML is diffed as is.
+fn normalize(raw: &str) -> String {
+    raw.parse::<toml::Table>()
+        .ok()
+        .and_then(|table| toml::to_string_pretty(&table).ok())
+        .unwrap_or_else(|| raw.to_string())
+}
+
+// Longest-common-subsequence line diff; config snapshots are a few hundred lines at most.
+fn diff_lines(left: &str, right: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = left.lines().collect();
+    let b: Vec<&str> = right.lines().collect();
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::with_capacity(a.len().max(b.len()));
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            out.push(DiffLine::Same(a[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push(DiffLine::Removed(a[i].to_string()));
+            i += 1;
+        } else {
+            out.push(DiffLine::Added(b[j].to_string()));
+            j += 1;
+        }
+    }
+    out
+}
+
+fn collapse(ops: Vec<DiffLine>, context: usize) -> Vec<DiffLine> {
+    let changes: Vec<usize> = (0..ops.len()).filter(|&idx| is_change(&ops[idx])).collect();
+    let keep = |idx: usize| {
+        changes
+            .iter()
+            .any(|&change| idx + context >= change && idx <= change + context)
+    };
+    let mut out = Vec::new();
+    let mut table: Option<String> = None;
+    let mut skipped = 0;
+    for (idx, line) in ops.into_iter().enumerate() {
+        if let DiffLine::Same(text) | DiffLine::Added(text) | DiffLine::Removed(text) = &line {
+            if text.starts_with('[') {
+                table = Some(text.clone());
+            }
+        }
+        if matches!(line, DiffLine::Same(_)) && !keep(idx) {
+            skipped += 1;
+            continue;
+        }
+        if skipped > 0 {
+            out.push(DiffLine::Skipped {
+                lines: skipped,
+                table: table
+                    .clone()
+                    .filter(|header| line != DiffLine::Same(header.clone())),
+            });
+            skipped = 0;
+        }
+        out.push(line);
+    }
+    if skipped > 0 {
+        out.push(DiffLine::Skipped {
+            lines: skipped,
+            table: None,
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConfigDiffView, DiffLine};
+
+    #[test]
+    fn snapshots_diff_by_line_with_collapsed_context() {
+        let base = "# tuned by hand\n[run]\nsymbol = \"BTCUSDT\"\ntimeframe = \"1min\"\n\n[costs]\nfee_bps = 10.0\nslippage_bps = 5.0\n\n[risk]\nmax_position_qty = 1.0\nmax_drawdown_pct = 0.2\nmax_exposure_pct = 1.0\n";
+        let edited = base
+            .replace("# tuned by hand\n", "")
+            .replace("max_drawdown_pct = 0.2", "max_drawdown_pct = 0.1")
+            .replace("fee_bps = 10.0\n", "fee_bps = 10.0\nextra = true\n");
+
+        let view = ConfigDiffView::new("a".into(), base, "b".into(), &edited);
+        assert_eq!((view.added, view.removed), (2, 1));
+        assert!(view
+            .lines
+            .contains(&DiffLine::Removed("max_drawdown_pct = 0.2".into())));
+        assert!(view
+            .lines
+            .contains(&DiffLine::Added("max_drawdown_pct = 0.1".into())));
+        // The comment is gone after normalizing, and the untouched [run] table collapses.
+        assert!(!view.lines.iter().any(|line| matches!(
+            line,
+            DiffLine::Same(text) if text.contains("symbol")
+        )));
+        assert!(matches!(view.lines.last(), Some(DiffLine::Skipped { lines, .. }) if *lines >= 2));
+
+        let mut view = view;
+        view.jump_to_change(true);
+        assert_eq!(
+            view.lines[view.scroll],
+            DiffLine::Added("extra = true".into())
+        );
+        view.jump_to_change(true);
+        assert_eq!(
+            view.lines[view.scroll],
+            DiffLine::Removed("max_drawdown_pct = 0.2".into())
+        );
+        view.jump_to_change(false);
+        assert_eq!(
+            view.lines[view.scroll],
+            DiffLine::Added("extra = true".into())
+        );
+
+        let same = ConfigDiffView::new("a".into(), base, "b".into(), base);
+        assert_eq!((same.added, same.removed), (0, 0));
+        assert!(matches!(same.lines.as_slice(), [DiffLine::Skipped { .. }]));
+    }
+}
@@ -0,0 +1,411 @@
+use crate::app::TextInput;
+use kairos_application::config::overrides::apply_set;
+use kairos_application::config::schema::{check_config_source, config_schema};
+use kairos_application::config::{load_config_from_source, Config};
+use serde_json::Value as Schema;
+use std::collections::BTreeMap;
+use std::path::Path;
+use toml::Value;
+
+// Sections shown first, in the order of a config file; the rest follow alphabetically.
+const SECTION_ORDER: [&str; 7] = ["run", "db", "paths", "costs", "risk", "features", "agent"];
+
+pub struct FormField {
+    pub group: String,
+    pub path: String,
+    // Schema type: string|number|integer|boolean|array.
+    pub kind: String,
+    // Accepted values or the schema description, shown next to the selected field.
+    pub hint: Option<String>,
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormFocus {
+    Fields,
+    Editing,
+    SavePath,
+}
+
+// The TUI config editor: one field per scalar (or scalar array) key of `config_schema()`, grouped
+// by section, editing a TOML document that is re-checked against the schema after every change.
+// Issues are attached to the field they name; the rest (and `${ENV}` errors) are listed apart.
+// Arrays of tables (`paths.sentiment_sources`) are kept as they are but not editable here.
+pub struct ConfigForm {
+    pub fields: Vec<FormField>,
+    pub selected: usize,
+    pub focus: FormFocus,
+    pub input: TextInput,
+    pub save_path: TextInput,
+    pub errors: BTreeMap<String, String>,
+    pub general_errors: Vec<String>,
+    pub modified: bool,
+    document: Value,
+}
+
+impl ConfigForm {
+    // `source` is the config as loaded (placeholders such as `${KAIROS_DB_URL}` stay unresolved,
+    // so saving does not write secrets out).
+    pub fn new(source: &str, save_path: &Path) -> Result<Self, String> {
+        let document: Value =
+            toml::from_str(source).map_err(|err| format!("failed to parse config: {err}"))?;
+        let mut form = Self {
+            fields: schema_fields(&config_schema()),
+            selected: 0,
+            focus: FormFocus::Fields,
+            input: TextInput::new(String::new()),
+            save_path: TextInput::new(save_path.display().to_string()),
+            errors: BTreeMap::new(),
+            general_errors: Vec::new(),
+            modified: false,
+            document,
+        };
+        form.validate();
+        Ok(form)
+    }
+
+    pub fn value(&self, path: &str) -> Option<&Value> {
+        path.split('.')
+            .try_fold(&self.document, |value, key| value.get(key))
+    }
+
+    pub fn display_value(&self, path: &str) -> String {
+        match self.value(path) {
+            Some(Value::String(raw)) => raw.clone(),
+            Some(other) => other.to_string(),
+            None => String::new(),
+        }
+    }
+
+    pub fn selected_field(&self) -> Option<&FormField> {
+        self.fields.get(self.selected)
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1).min(self.fields.len().saturating_sub(1));
+    }
+
+    // Jumps to the first field of the previous (`-1`) or next (`1`) group.
+    pub fn jump_group(&mut self, direction: isize) {
+        let Some(group) = self.selected_field().map(|f| f.group.clone()) else {
+            return;
+        };
+        if direction > 0 {
+            if let Some(idx) = self.fields[self.selected..]
+                .iter()
+                .position(|f| f.group != group)
+            {
+                self.selected += idx;
+            }
+        } else {
+            let start = self.fields[..self.selected]
+                .iter()
+                .rposition(|f| f.group != group);
+            if let Some(prev_end) = start {
+                let prev_group = &self.fields[prev_end].group;
+                self.selected = self.fields[..=prev_end]
+                    .iter()
+                    .position(|f| &f.group == prev_group)
+                    .unwrap_or(0);
+            }
+        }
+    }
+
+    pub fn start_editing(&mut self) {
+        if let Some(field) = self.selected_field() {
+            self.input = TextInput::new(self.display_value(&field.path));
+            self.focus = FormFocus::Editing;
+        }
+    }
+
+    // Writes the edited value into the document (an empty value removes an optional key) and
+    // re-validates. The value is kept even when it has issues, so it can be fixed in place.
+    pub fn commit_edit(&mut self) -> Result<(), String> {
+        self.focus = FormFocus::Fields;
+        let Some(field) = self.fields.get(self.selected) else {
+            return Ok(());
+        };
+        let path = field.path.clone();
+        let raw = self.input.value.trim();
+        if raw.is_empty() {
+            if field.required {
+                return Err(format!("{path} is required"));
+            }
+            remove_path(&mut self.document, &path);
+        } else {
+            let literal = if field.kind == "string" {
+                Value::String(raw.to_string()).to_string()
+            } else {
+                raw.to_string()
+            };
+            apply_set(&mut self.document, &format!("{path}={literal}"))
+                .map_err(|err| err.to_string())?;
+        }
+        self.modified = true;
+        self.validate();
+        Ok(())
+    }
+
+    pub fn cancel_editing(&mut self) {
+        self.focus = FormFocus::Fields;
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty() && self.general_errors.is_empty()
+    }
+
+    pub fn source(&self) -> Result<String, String> {
+        toml::to_string_pretty(&self.document)
+            .map_err(|err| format!("failed to serialize config: {err}"))
+    }
+
+    fn validate(&mut self) {
+        self.errors.clear();
+        self.general_errors.clear();
+        let source = match self.source() {
+            Ok(source) => source,
+            Err(err) => {
+                self.general_errors.push(err);
+                return;
+            }
+        };
+        for issue in check_config_source(&source) {
+            let field = issue.field.split('[').next().unwrap_or_default();
+            if self.fields.iter().any(|f| f.path == field) {
+                self.errors
+                    .entry(field.to_string())
+                    .or_insert(issue.message);
+            } else if issue.field.is_empty() {
+                self.general_errors.push(issue.message);
+            } else {
+                self.general_errors
+                    .push(format!("{}: {}", issue.field, issue.message));
+            }
+        }
+        if self.is_valid() {
+            if let Err(err) = load_config_from_source(&source) {
+                self.general_errors.push(err.to_string());
+            }
+        }
+    }
+
+    // The edited config and its source, when it has no issues.
+    pub fn build(&self) -> Result<(Config, String), String> {
+        if !self.is_valid() {
+            return Err(format!(
+                "config has {} issue(s); fix them before applying",
+                self.errors.len() + self.general_errors.len()
+            ));
+        }
+        let source = self.source()?;
+        let config = load_config_from_source(&source).map_err(|err| err.to_string())?;
+        Ok((config, source))
+    }
+}
+
+fn schema_fields(schema: &Schema) -> Vec<FormField> {
+    let Some(sections) = schema.get("properties").and_then(Schema::as_object) else {
+        return Vec::new();
+    };
+    let mut names: Vec<&String> = sections.keys().collect();
+    names.sort_by_key(|name| {
+        (
+            SECTION_ORDER
+                .iter()
+                .position(|s| s == name)
+                .unwrap_or(SECTION_ORDER.len()),
+            name.as_str(),
+        )
+    });
+    let mut fields = Vec::new();
+    for name in names {
+        collect_fields(name, &sections[name.as_str()], &mut fields);
+    }
+    fields
+}
+
+fn collect_fields(path: &str, schema: &Schema, fields: &mut Vec<FormField>) {
+    let Some(properties) = schema.get("properties").and_then(Schema::as_object) else {
+        return;
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Schema::as_array)
+        .map(|keys| keys.iter().filter_map(Schema::as_str).collect())
+        .unwrap_or_default();
+    for (key, property) in properties {
+        let field_path = format!("{path}.{key}");
+        let kind = property
+            .get("type")
+            .and_then(Schema::as_str)
+            .unwrap_or("string");
+        match kind {
+            "object" => collect_fields(&field_path, property, fields),
+            "array" if property["items"].get("type").and_then(Schema::as_str) == Some("object") => {
+            }
+            _ => fields.push(FormField {
+                group: path.to_string(),
+                path: field_path,
+                kind: kind.to_string(),
+                hint: hint(property),
+                required: required.contains(&key.as_str()),
+            }),
+        }
+    }
+}
+
+fn hint(property: &Schema) -> Option<String> {
+    if let Some(values) = property.get("enum").and_then(Schema::as_array) {
+        let values: Vec<String> = values
+            .iter()
+            .map(|v| v.as_str().map_or_else(|| v.to_string(), str::to_string))
+            .collect();
+        return Some(values.join("|"));
+    }
+    if let Some(description) = property.get("description").and_then(Schema::as_str) {
+        return Some(description.to_string());
+    }
+    let bound = |key: &str, op: &str| property.get(key).map(|min| format!("{op} {min}"));
+    bound("minimum", ">=").or_else(|| bound("exclusiveMinimum", ">"))
+}
+
+fn remove_path(document: &mut Value, path: &str) {
+    let (parent, key) = match path.rsplit_once('.') {
+        Some((parent, key)) => (Some(parent), key),
+        None => (None, path),
+    };
+    let table = match parent {
+        Some(parent) => parent
+            .split('.')
+            .try_fold(&mut *document, |value, key| value.get_mut(key)),
+        None => Some(document),
+    };
+    if let Some(table) = table.and_then(Value::as_table_mut) {
+        table.remove(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConfigForm, FormFocus};
+    use crate::app::TextInput;
+    use std::path::Path;
+
+    const SOURCE: &str = r#"
+[run]
+run_id = "btc"
+symbol = "BTC-USDT"
+timeframe = "1min"
+initial_capital = 1000.0
+
+[db]
+url = "postgres://kairos@localhost/kairos"
+ohlcv_table = "ohlcv_candles"
+exchange = "kucoin"
+market = "spot"
+
+[paths]
+out_dir = "runs/"
+
+[costs]
+fee_bps = 10.0
+slippage_bps = 5.0
+
+[risk]
+max_position_qty = 1.0
+max_drawdown_pct = 0.5
+max_exposure_pct = 1.0
+
+[features]
+return_mode = "log"
+sma_windows = [10, 50]
+volatility_windows = [10]
+rsi_enabled = false
+sentiment_lag = "5m"
+
+[agent]
+mode = "baseline"
+url = "http://127.0.0.1:8000"
+timeout_ms = 200
+retries = 0
+fallback_action = "HOLD"
+api_version = "v1"
+feature_version = "v1"
+"#;
+
+    fn edit(form: &mut ConfigForm, path: &str, value: &str) -> Result<(), String> {
+        form.selected = form
+            .fields
+            .iter()
+            .position(|f| f.path == path)
+            .expect("field");
+        form.start_editing();
+        form.input = TextInput::new(value.to_string());
+        form.commit_edit()
+    }
+
+    #[test]
+    fn edits_are_checked_against_the_schema_and_attached_to_their_field() {
+        let mut form = ConfigForm::new(SOURCE, Path::new("cfg.toml")).expect("form");
+        assert!(
+            form.is_valid(),
+            "{:?} {:?}",
+            form.errors,
+            form.general_errors
+        );
+        assert_eq!(form.fields[0].group, "run");
+        assert_eq!(form.display_value("features.sma_windows"), "[10, 50]");
+
+        edit(&mut form, "costs.fee_bps", "-1").expect("edit");
+        assert!(form.errors.contains_key("costs.fee_bps"));
+        assert!(form.build().is_err());
+
+        edit(&mut form, "costs.fee_bps", "7.5").expect("edit");
+        edit(&mut form, "run.symbol", "1000").expect("edit");
+        edit(&mut form, "features.sma_windows", "[5, 20]").expect("edit");
+        assert!(
+            form.is_valid(),
+            "{:?} {:?}",
+            form.errors,
+            form.general_errors
+        );
+        assert_eq!(form.focus, FormFocus::Fields);
+
+        let err = edit(&mut form, "run.run_id", "").unwrap_err();
+        assert_eq!(err, "run.run_id is required");
+        edit(&mut form, "run.max_bars", "500").expect("edit");
+        assert_eq!(form.display_value("run.max_bars"), "500");
+        edit(&mut form, "run.max_bars", "").expect("remove optional");
+        assert!(form.value("run.max_bars").is_none());
+
+        let source = form.source().expect("source");
+        assert!(!source.contains("max_bars"));
+        let (config, _) = form.build().expect("build");
+        assert_eq!(config.costs.fee_bps, 7.5);
+        assert_eq!(config.run.symbol, "1000");
+        assert_eq!(config.features.sma_windows, [5, 20]);
+    }
+
+    #[test]
+    fn group_jumps_land_on_the_first_field_of_a_section() {
+        let mut form = ConfigForm::new(SOURCE, Path::new("cfg.toml")).expect("form");
+        form.jump_group(1);
+        assert_eq!(form.selected_field().expect("field").group, "db");
+        form.select_next();
+        form.jump_group(-1);
+        assert_eq!(form.selected_field().expect("field").path, "run.end");
+        form.jump_group(1);
+        form.jump_group(1);
+        assert_eq!(form.selected_field().expect("field").group, "paths");
+        form.jump_group(-1);
+        assert_eq!(form.selected_field().expect("field").group, "db");
+        assert_eq!(
+            form.selected,
+            form.fields.iter().position(|f| f.group == "db").unwrap()
+        );
+    }
+}
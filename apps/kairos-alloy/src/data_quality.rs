@@ -0,0 +1,279 @@
+use serde_json::Value;
+
+// The validate limits as (name, count in the report, limit in `limits`), in the order validate
+// checks them; a `null` limit is not checked.
+const CHECKS: [(&str, &str, &str); 15] = [
+    ("ohlcv gaps", "/ohlcv/gaps", "max_gaps"),
+    (
+        "ohlcv missing bars",
+        "/ohlcv/missing_bars",
+        "max_missing_bars",
+    ),
+    ("ohlcv duplicates", "/ohlcv/duplicates", "max_duplicates"),
+    (
+        "ohlcv out of order",
+        "/ohlcv/out_of_order",
+        "max_out_of_order",
+    ),
+    (
+        "ohlcv invalid close",
+        "/ohlcv/invalid_close",
+        "max_invalid_close",
+    ),
+    (
+        "sentiment duplicates",
+        "/sentiment/duplicates",
+        "max_duplicates",
+    ),
+    (
+        "sentiment out of order",
+        "/sentiment/out_of_order",
+        "max_out_of_order",
+    ),
+    (
+        "sentiment missing",
+        "/sentiment/missing_values",
+        "max_sentiment_missing",
+    ),
+    (
+        "sentiment invalid",
+        "/sentiment/invalid_values",
+        "max_sentiment_invalid",
+    ),
+    (
+        "sentiment dropped",
+        "/sentiment/dropped_rows",
+        "max_sentiment_dropped",
+    ),
+    (
+        "return outliers",
+        "/ohlcv_outliers/return_outliers",
+        "max_return_outliers",
+    ),
+    (
+        "zero-volume streaks",
+        "/ohlcv_outliers/zero_volume_streaks",
+        "max_zero_volume_streaks",
+    ),
+    (
+        "flat-price streaks",
+        "/ohlcv_outliers/flat_price_streaks",
+        "max_flat_price_streaks",
+    ),
+    (
+        "zero-volume moves",
+        "/ohlcv/zero_volume_moves",
+        "max_zero_volume_moves",
+    ),
+    (
+        "turnover mismatches",
+        "/ohlcv/turnover_mismatches",
+        "max_turnover_mismatches",
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QualityCheck {
+    pub name: &'static str,
+    pub value: usize,
+    pub limit: Option<usize>,
+}
+
+impl QualityCheck {
+    pub fn passed(&self) -> bool {
+        self.limit.is_none_or(|limit| self.value <= limit)
+    }
+}
+
+// The TUI data-quality dashboard: the last validate report, reduced to what the view draws.
+#[derive(Debug, Clone, Default)]
+pub struct DataQualityView {
+    pub rows: usize,
+    pub duplicates: usize,
+    pub gaps: usize,
+    pub missing_bars: usize,
+    pub out_of_order: usize,
+    pub invalid_close: usize,
+    pub first_timestamp: Option<i64>,
+    pub last_timestamp: Option<i64>,
+    pub max_gap_seconds: Option<i64>,
+    // (last bar before, first bar after) of each gap the report listed, in time order.
+    pub gap_ranges: Vec<(i64, i64)>,
+    pub sentiment_duplicates: usize,
+    pub sentiment_missing: usize,
+    pub sentiment_invalid: usize,
+    pub sentiment_dropped: usize,
+    pub checks: Vec<QualityCheck>,
+    pub selected_gap: usize,
+}
+
+impl DataQualityView {
+    // Reads the JSON report of `kairos_application::validation::validate`.
+    pub fn from_validate_report(report: &Value) -> Self {
+        let count =
+            |pointer: &str| report.pointer(pointer).and_then(Value::as_u64).unwrap_or(0) as usize;
+        let ts = |pointer: &str| report.pointer(pointer).and_then(Value::as_i64);
+        let gap_ranges = report
+            .pointer("/ohlcv/gap_ranges")
+            .and_then(Value::as_array)
+            .map(|ranges| {
+                ranges
+                    .iter()
+                    .filter_map(|range| Some((range.get(0)?.as_i64()?, range.get(1)?.as_i64()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let checks = CHECKS
+            .iter()
+            .map(|(name, pointer, limit)| QualityCheck {
+                name,
+                value: count(pointer),
+                limit: report
+                    .pointer(&format!("/limits/{limit}"))
+                    .and_then(Value::as_u64)
+                    .map(|limit| limit as usize),
+            })
+            .collect();
+        Self {
+            rows: count("/ohlcv/rows"),
+            duplicates: count("/ohlcv/duplicates"),
+            gaps: count("/ohlcv/gaps"),
+            missing_bars: count("/ohlcv/missing_bars"),
+            out_of_order: count("/ohlcv/out_of_order"),
+            invalid_close: count("/ohlcv/invalid_close"),
+            first_timestamp: ts("/ohlcv/first_timestamp"),
+            last_timestamp: ts("/ohlcv/last_timestamp"),
+            max_gap_seconds: ts("/ohlcv/max_gap_seconds"),
+            gap_ranges,
+            sentiment_duplicates: count("/sentiment/duplicates"),
+            sentiment_missing: count("/sentiment/missing_values"),
+            sentiment_invalid: count("/sentiment/invalid_values"),
+            sentiment_dropped: count("/sentiment/dropped_rows"),
+            checks,
+            selected_gap: 0,
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(QualityCheck::passed)
+    }
+
+    pub fn failed_checks(&self) -> usize {
+        self.checks.iter().filter(|check| !check.passed()).count()
+    }
+
+    pub fn selected_range(&self) -> Option<(i64, i64)> {
+        self.gap_ranges.get(self.selected_gap).copied()
+    }
+
+    // One range spanning every listed gap, for a single backfill ingest.
+    pub fn all_gaps_range(&self) -> Option<(i64, i64)> {
+        Some((self.gap_ranges.first()?.0, self.gap_ranges.last()?.1))
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected_gap = self.selected_gap.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected_gap = (self.selected_gap + 1).min(self.gap_ranges.len().saturating_sub(1));
+    }
+
+    // The data range split into `width` columns, each holding the index of the first gap that
+    // overlaps it (a gap always covers at least one column, however short).
+    pub fn timeline(&self, width: usize) -> Vec<Option<usize>> {
+        let mut columns = vec![None; width];
+        let (Some(first), Some(last)) = (self.first_timestamp, self.last_timestamp) else {
+            return columns;
+        };
+        let span = last - first;
+        if width == 0 || span <= 0 {
+            return columns;
+        }
+        let column = |ts: i64| {
+            let offset = (ts - first).clamp(0, span) as i128;
+            ((offset * width as i128 / span as i128) as usize).min(width - 1)
+        };
+        for (idx, (start, end)) in self.gap_ranges.iter().enumerate() {
+            for slot in &mut columns[column(*start)..=column(*end)] {
+                slot.get_or_insert(idx);
+            }
+        }
+        columns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DataQualityView;
+
+    #[test]
+    fn reads_the_validate_report_into_checks_and_a_gap_timeline() {
+        let report = serde_json::json!({
+            "ohlcv": {
+                "rows": 90,
+                "duplicates": 1,
+                "gaps": 2,
+                "missing_bars": 10,
+                "out_of_order": 0,
+                "invalid_close": 0,
+                "first_timestamp": 0,
+                "last_timestamp": 1_000,
+                "max_gap_seconds": 100,
+                "zero_volume_moves": 0,
+                "turnover_mismatches": 0,
+                "gap_ranges": [[100, 150], [800, 900]],
+            },
+            "ohlcv_outliers": { "return_outliers": 3 },
+            "sentiment": { "duplicates": 0, "missing_values": 2 },
+            "limits": {
+                "max_gaps": 5,
+                "max_missing_bars": 5,
+                "max_duplicates": 0,
+                "max_out_of_order": 0,
+                "max_invalid_close": 0,
+                "max_sentiment_missing": 2,
+                "max_sentiment_invalid": 0,
+                "max_sentiment_dropped": 0,
+                "max_return_outliers": null,
+            },
+            "strict": false
+        });
+        let mut view = DataQualityView::from_validate_report(&report);
+        assert_eq!((view.rows, view.gaps, view.missing_bars), (90, 2, 10));
+        let failed: Vec<&str> = view
+            .checks
+            .iter()
+            .filter(|check| !check.passed())
+            .map(|check| check.name)
+            .collect();
+        assert_eq!(failed, ["ohlcv missing bars", "ohlcv duplicates"]);
+        assert!(!view.passed());
+        let outliers = view
+            .checks
+            .iter()
+            .find(|check| check.name == "return outliers")
+            .expect("outliers check");
+        assert_eq!((outliers.value, outliers.limit), (3, None));
+
+        assert_eq!(
+            view.timeline(10),
+            [
+                None,
+                Some(0),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(1),
+                Some(1)
+            ]
+        );
+        assert_eq!(view.all_gaps_range(), Some((100, 900)));
+        view.select_next();
+        view.select_next();
+        assert_eq!(view.selected_range(), Some((800, 900)));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::output::CliError;
+use kairos_application::diagnostics::{run_doctor, CheckStatus, DoctorReport};
+use kairos_domain::repositories::sentiment::SentimentRepository;
+use std::path::Path;
+
+// `kairos-alloy doctor`: environment diagnosis for a config; see `DoctorReport::passed`.
+pub fn doctor(config_path: &Path, config_sets: &[String]) -> Result<DoctorReport, CliError> {
+    let (config, _) =
+        kairos_application::config::load_config_with_overrides(config_path, config_sets)?;
+    let market_data = crate::headless::build_market_data_repo(&config);
+    let agent = crate::headless::build_remote_agent(&config);
+    let sentiment_repo = crate::headless::build_sentiment_repo(&config);
+
+    Ok(run_doctor(
+        &config,
+        market_data.as_deref().map_err(|err| err.clone()),
+        agent
+            .as_ref()
+            .map(|agent| agent.as_deref())
+            .map_err(|err| err.clone()),
+        sentiment_repo
+            .as_deref()
+            .map(|repo| repo as &dyn SentimentRepository)
+            .map_err(|err| err.clone()),
+    ))
+}
+
+pub fn format_checklist(config_path: &Path, report: &DoctorReport) -> String {
+    let mut lines = vec![format!("kairos-alloy doctor: {}", config_path.display())];
+    let width = report
+        .checks
+        .iter()
+        .map(|check| check.name.len())
+        .max()
+        .unwrap_or(0);
+    for check in &report.checks {
+        lines.push(format!(
+            "[{}] {:<width$}  {}",
+            check.status.as_str().to_uppercase(),
+            check.name,
+            check.detail
+        ));
+    }
+    let count = |status| {
+        report
+            .checks
+            .iter()
+            .filter(|check| check.status == status)
+            .count()
+    };
+    lines.push(format!(
+        "{} passed, {} failed, {} skipped",
+        count(CheckStatus::Pass),
+        count(CheckStatus::Fail),
+        count(CheckStatus::Skip)
+    ));
+    lines.join("\n")
+}
+
+pub fn report_json(config_path: &Path, report: &DoctorReport) -> serde_json::Value {
+    serde_json::json!({
+        "status": if report.passed() { "ok" } else { "failed" },
+        "mode": "doctor",
+        "config": config_path.display().to_string(),
+        "checks": report
+            .checks
+            .iter()
+            .map(|check| serde_json::json!({
+                "name": check.name,
+                "status": check.status.as_str(),
+                "detail": check.detail,
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_checklist;
+    use kairos_application::diagnostics::{CheckStatus, DoctorCheck, DoctorReport};
+    use std::path::Path;
+
+    #[test]
+    fn checklist_aligns_names_and_counts_statuses() {
+        let report = DoctorReport {
+            checks: vec![
+                DoctorCheck {
+                    name: "db",
+                    status: CheckStatus::Fail,
+                    detail: "missing db.url".to_string(),
+                },
+                DoctorCheck {
+                    name: "out_dir",
+                    status: CheckStatus::Pass,
+                    detail: "runs is writable".to_string(),
+                },
+                DoctorCheck {
+                    name: "agent",
+                    status: CheckStatus::Skip,
+                    detail: "agent.mode = baseline".to_string(),
+                },
+            ],
+        };
+        assert!(!report.passed());
+        assert_eq!(
+            format_checklist(Path::new("sample.toml"), &report),
+            "kairos-alloy doctor: sample.toml\n\
+             [FAIL] db       missing db.url\n\
+             [PASS] out_dir  runs is writable\n\
+             [SKIP] agent    agent.mode = baseline\n\
+             1 passed, 1 failed, 1 skipped"
+        );
+    }
+}
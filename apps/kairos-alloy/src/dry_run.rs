@@ -0,0 +1,139 @@
+use crate::output::CliError;
+use chrono::DateTime;
+use kairos_application::backtesting::dry_run::{dry_run_backtest, DryRunReport};
+use kairos_application::diagnostics::CheckStatus;
+use std::path::Path;
+
+// `kairos-alloy backtest --dry-run`: resolves the config and checks a sample of the data without
+// running the engine. Fails like the real run would (missing data, bad config); an unhealthy agent
+// is reported, not raised, so the rest of the report still shows.
+pub fn dry_run(
+    config_path: &Path,
+    config_sets: &[String],
+    ping_agent: bool,
+    sample_bars: u64,
+) -> Result<DryRunReport, CliError> {
+    let (config, _) =
+        kairos_application::config::load_config_with_overrides(config_path, config_sets)?;
+    let market_data = crate::headless::market_data_repo(&config)?;
+    let sentiment_repo = crate::headless::sentiment_repo(&config)?;
+    let agent = if ping_agent {
+        crate::headless::build_remote_agent(&config)?
+    } else {
+        None
+    };
+    dry_run_backtest(
+        &config,
+        market_data.as_ref(),
+        sentiment_repo.as_ref(),
+        agent.as_deref(),
+        sample_bars,
+    )
+    .map_err(CliError::from)
+}
+
+pub fn format_report(report: &DryRunReport) -> String {
+    let timeframe = if report.source_timeframe == report.timeframe {
+        report.timeframe.clone()
+    } else {
+        format!(
+            "{} (resampled from {})",
+            report.timeframe, report.source_timeframe
+        )
+    };
+    let sentiment = match report.sentiment_points {
+        Some(points) => format!("{points} points"),
+        None => "none".to_string(),
+    };
+    [
+        format!("dry run: {} ({})", report.run_id, report.symbol),
+        format!("timeframe  {timeframe}"),
+        format!(
+            "data       {} source bars, {} .. {}",
+            report.rows,
+            format_ts(report.first_ts),
+            format_ts(report.last_ts)
+        ),
+        format!(
+            "sample     {} bars, {} features per bar",
+            report.sample_rows, report.feature_count
+        ),
+        format!("sentiment  {sentiment}"),
+        format!("strategy   {}", report.strategy),
+        format!(
+            "agent      [{}] {}",
+            report.agent.as_str().to_uppercase(),
+            report.agent_detail
+        ),
+        "engine not run".to_string(),
+    ]
+    .join("\n")
+}
+
+pub fn report_json(report: &DryRunReport) -> serde_json::Value {
+    serde_json::json!({
+        "status": if report.agent == CheckStatus::Fail { "failed" } else { "ok" },
+        "mode": "backtest_dry_run",
+        "run_id": report.run_id,
+        "symbol": report.symbol,
+        "timeframe": report.timeframe,
+        "source_timeframe": report.source_timeframe,
+        "rows": report.rows,
+        "first_ts": report.first_ts,
+        "last_ts": report.last_ts,
+        "sample_rows": report.sample_rows,
+        "feature_count": report.feature_count,
+        "sentiment_points": report.sentiment_points,
+        "strategy": report.strategy,
+        "agent": {
+            "status": report.agent.as_str(),
+            "detail": report.agent_detail,
+        },
+    })
+}
+
+fn format_ts(ts: Option<i64>) -> String {
+    ts.and_then(|ts| DateTime::from_timestamp(ts, 0))
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_else(|| "?".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_report, report_json};
+    use kairos_application::backtesting::dry_run::DryRunReport;
+    use kairos_application::diagnostics::CheckStatus;
+
+    #[test]
+    fn report_lists_scope_sample_and_strategy() {
+        let report = DryRunReport {
+            run_id: "btc_5m".to_string(),
+            symbol: "BTC-USDT".to_string(),
+            timeframe: "5min".to_string(),
+            source_timeframe: "1min".to_string(),
+            rows: 1440,
+            first_ts: Some(1_704_067_200),
+            last_ts: Some(1_704_153_540),
+            sample_rows: 100,
+            feature_count: 5,
+            sentiment_points: None,
+            strategy: "simple_sma",
+            agent: CheckStatus::Skip,
+            agent_detail: "agent.mode = baseline".to_string(),
+        };
+        assert_eq!(
+            format_report(&report),
+            "dry run: btc_5m (BTC-USDT)\n\
+             timeframe  5min (resampled from 1min)\n\
+             data       1440 source bars, 2024-01-01T00:00:00+00:00 .. 2024-01-01T23:59:00+00:00\n\
+             sample     100 bars, 5 features per bar\n\
+             sentiment  none\n\
+             strategy   simple_sma\n\
+             agent      [SKIP] agent.mode = baseline\n\
+             engine not run"
+        );
+        let json = report_json(&report);
+        assert_eq!(json["status"], "ok");
+        assert_eq!(json["agent"]["status"], "skip");
+    }
+}
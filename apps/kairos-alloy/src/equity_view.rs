@@ -0,0 +1,174 @@
+use crate::app::ViewId;
+use crate::stage_timings::{load_stage_timings, StageTiming};
+use kairos_domain::repositories::artifacts::ArtifactReader;
+use kairos_domain::services::downsample::lttb_indices;
+use kairos_infrastructure::artifacts::FilesystemArtifactReader;
+use std::path::Path;
+
+// Points drawn per curve; the crosshair still walks every point of equity.csv.
+const MAX_DRAWN_POINTS: usize = 2_000;
+
+// The results view of a finished run: its equity curve and the underwater curve (drawdown from
+// the running peak) from equity.csv, with a crosshair on one point, and where its wall time went.
+pub struct EquityView {
+    pub run_id: String,
+    // (timestamp, equity, drawdown as a fraction below the peak), in file order.
+    pub points: Vec<(i64, f64, f64)>,
+    pub cursor: usize,
+    pub timings: Vec<StageTiming>,
+    // Where Esc goes back to.
+    pub back_to: ViewId,
+}
+
+impl EquityView {
+    pub fn load(run_dir: &Path, back_to: ViewId) -> Result<Self, String> {
+        let reader = FilesystemArtifactReader::new();
+        let path = run_dir.join("equity.csv");
+        if !reader.exists(&path) {
+            return Err(format!("no equity.csv in {}", run_dir.display()));
+        }
+        let equity = reader.read_equity_csv(&path)?;
+        if equity.is_empty() {
+            return Err(format!("{} has no points", path.display()));
+        }
+        let mut peak = f64::NEG_INFINITY;
+        let points: Vec<_> = equity
+            .iter()
+            .map(|point| {
+                peak = peak.max(point.equity);
+                let drawdown = if peak > 0.0 {
+                    (peak - point.equity) / peak
+                } else {
+                    0.0
+                };
+                (point.timestamp, point.equity, drawdown)
+            })
+            .collect();
+        let run_id = run_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| run_dir.display().to_string());
+        // Missing timings only leave their panel empty.
+        let timings = load_stage_timings(run_dir).unwrap_or_else(|err| {
+            tracing::warn!(error = %err, "stage timings unavailable");
+            Vec::new()
+        });
+        Ok(Self {
+            run_id,
+            cursor: points.len() - 1,
+            points,
+            timings,
+            back_to,
+        })
+    }
+
+    // Moves the crosshair by `delta` points, stopping at either end.
+    pub fn move_cursor(&mut self, delta: isize) {
+        let last = self.points.len() - 1;
+        self.cursor = self.cursor.saturating_add_signed(delta).min(last);
+    }
+
+    pub fn cursor_to_start(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn cursor_to_end(&mut self) {
+        self.cursor = self.points.len() - 1;
+    }
+
+    // A tenth of the run, for PgUp/PgDn.
+    pub fn page(&self) -> isize {
+        (self.points.len() / 10).max(1) as isize
+    }
+
+    pub fn selected(&self) -> (i64, f64, f64) {
+        self.points[self.cursor]
+    }
+
+    pub fn max_drawdown(&self) -> f64 {
+        self.points.iter().map(|p| p.2).fold(0.0, f64::max)
+    }
+
+    // (timestamp, equity), reduced with LTTB so long runs keep their peaks and troughs.
+    pub fn equity_curve(&self) -> Vec<(f64, f64)> {
+        downsample(self.points.iter().map(|p| (p.0 as f64, p.1)).collect())
+    }
+
+    // (timestamp, -drawdown %), so the curve hangs below zero.
+    pub fn underwater_curve(&self) -> Vec<(f64, f64)> {
+        downsample(
+            self.points
+                .iter()
+                .map(|p| (p.0 as f64, -p.2 * 100.0))
+                .collect(),
+        )
+    }
+}
+
+fn downsample(series: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+    if series.len() <= MAX_DRAWN_POINTS {
+        return series;
+    }
+    lttb_indices(&series, MAX_DRAWN_POINTS)
+        .into_iter()
+        .map(|idx| series[idx])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EquityView;
+    use crate::app::ViewId;
+    use kairos_domain::repositories::artifacts::ArtifactWriter;
+    use kairos_domain::value_objects::equity_point::EquityPoint;
+    use kairos_infrastructure::artifacts::FilesystemArtifactWriter;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn equity_view_tracks_the_underwater_curve_and_the_crosshair() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let dir = std::env::temp_dir().join(format!("kairos_tui_equity_{unique}"));
+        std::fs::create_dir_all(&dir).expect("mkdir");
+        assert!(EquityView::load(&dir, ViewId::Runs)
+            .err()
+            .expect("missing")
+            .contains("no equity.csv"));
+
+        let points: Vec<EquityPoint> = [100.0, 120.0, 90.0, 110.0, 130.0]
+            .iter()
+            .enumerate()
+            .map(|(idx, equity)| EquityPoint {
+                timestamp: 60 * idx as i64,
+                equity: *equity,
+                cash: *equity,
+                position_qty: 0.0,
+                unrealized_pnl: 0.0,
+                realized_pnl: 0.0,
+            })
+            .collect();
+        FilesystemArtifactWriter::new()
+            .write_equity_csv(&dir.join("equity.csv"), &points)
+            .expect("write");
+
+        let mut view = EquityView::load(&dir, ViewId::Runs).expect("load");
+        assert_eq!(view.cursor, 4);
+        assert!((view.max_drawdown() - 0.25).abs() < 1e-9);
+        view.move_cursor(-2);
+        assert_eq!(view.selected(), (120, 90.0, 0.25));
+        view.move_cursor(-10);
+        assert_eq!(view.cursor, 0);
+        view.move_cursor(3);
+        let (ts, equity, drawdown) = view.selected();
+        assert_eq!((ts, equity), (180, 110.0));
+        assert!((drawdown - 10.0 / 120.0).abs() < 1e-9);
+        view.cursor_to_end();
+        assert_eq!(view.selected().2, 0.0);
+
+        assert_eq!(view.equity_curve().len(), 5);
+        assert_eq!(view.underwater_curve()[2], (120.0, -25.0));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,284 @@
+use crate::tasks::TradeSample;
+use kairos_application::experiments::sweep::TrialOutcome;
+use kairos_domain::repositories::run_registry::RunRecord;
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+// A table as a TUI view shows it, in the same row order, ready to be written as CSV or JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportTable {
+    // Used in the file name: `runs`, `trades` or `leaderboard`.
+    pub name: &'static str,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+pub fn runs_table(runs: &[RunRecord]) -> ExportTable {
+    let columns = [
+        "run_id",
+        "run_dir",
+        "symbol",
+        "timeframe",
+        "start",
+        "end",
+        "bars_processed",
+        "trades",
+        "win_rate",
+        "net_profit",
+        "sharpe",
+        "max_drawdown",
+        "recorded_at",
+        "config_hash",
+    ];
+    let rows = runs
+        .iter()
+        .map(|run| {
+            vec![
+                json!(run.run_id),
+                json!(run.run_dir),
+                json!(run.symbol),
+                json!(run.timeframe),
+                json!(run.start),
+                json!(run.end),
+                json!(run.bars_processed),
+                json!(run.trades),
+                json!(run.win_rate),
+                json!(run.net_profit),
+                json!(run.sharpe),
+                json!(run.max_drawdown),
+                json!(run.recorded_at),
+                json!(run.config_hash),
+            ]
+        })
+        .collect();
+    ExportTable {
+        name: "runs",
+        columns: columns.iter().map(|c| c.to_string()).collect(),
+        rows,
+    }
+}
+
+// Oldest first, unlike the Monitor list, which shows the latest trade on top.
+pub fn trades_table<'a>(trades: impl Iterator<Item = &'a TradeSample>) -> ExportTable {
+    let columns = ["bar_index", "timestamp", "side", "quantity", "price"];
+    let rows = trades
+        .map(|trade| {
+            vec![
+                json!(trade.bar_index),
+                json!(trade.timestamp),
+                json!(format!("{:?}", trade.side).to_lowercase()),
+                json!(trade.quantity),
+                json!(trade.price),
+            ]
+        })
+        .collect();
+    ExportTable {
+        name: "trades",
+        columns: columns.iter().map(|c| c.to_string()).collect(),
+        rows,
+    }
+}
+
+// One column per sweep param, in the order the trials first assign them.
+pub fn leaderboard_table(trials: &[TrialOutcome]) -> ExportTable {
+    let mut params: Vec<&str> = Vec::new();
+    for trial in trials {
+        for (path, _) in &trial.params {
+            if !params.contains(&path.as_str()) {
+                params.push(path);
+            }
+        }
+    }
+    let mut columns: Vec<String> = ["rank", "run_id", "split_id", "status"]
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+    columns.extend(params.iter().map(|p| p.to_string()));
+    columns.extend(
+        ["sharpe", "net_profit", "max_drawdown", "run_dir"]
+            .iter()
+            .map(|c| c.to_string()),
+    );
+    let rows = trials
+        .iter()
+        .enumerate()
+        .map(|(idx, trial)| {
+            let mut row = vec![
+                json!(idx + 1),
+                json!(trial.run_id),
+                json!(trial.split_id),
+                json!(trial.status),
+            ];
+            row.extend(params.iter().map(|path| {
+                trial
+                    .params
+                    .iter()
+                    .find(|(p, _)| p == path)
+                    .map_or(Value::Null, |(_, value)| json!(value))
+            }));
+            row.extend([
+                json!(trial.sharpe),
+                json!(trial.net_profit),
+                json!(trial.max_drawdown),
+                json!(trial.run_dir),
+            ]);
+            row
+        })
+        .collect();
+    ExportTable {
+        name: "leaderboard",
+        columns,
+        rows,
+    }
+}
+
+// Writes `<dir>/<name>_<stamp>.<ext>` and returns its path. JSON is an array with one object
+// per row; CSV leaves missing values empty.
+pub fn write_export(
+    dir: &Path,
+    table: &ExportTable,
+    format: ExportFormat,
+    stamp: &str,
+) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|err| format!("failed to create {}: {err}", dir.display()))?;
+    let path = dir.join(format!("{}_{stamp}.{}", table.name, format.extension()));
+    match format {
+        ExportFormat::Csv => {
+            let mut wtr = csv::Writer::from_path(&path)
+                .map_err(|err| format!("failed to create {}: {err}", path.display()))?;
+            wtr.write_record(&table.columns)
+                .map_err(|err| format!("failed to write header: {err}"))?;
+            for row in &table.rows {
+                wtr.write_record(row.iter().map(csv_cell))
+                    .map_err(|err| format!("failed to write row: {err}"))?;
+            }
+            wtr.flush()
+                .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
+        }
+        ExportFormat::Json => {
+            let rows: Vec<Value> = table
+                .rows
+                .iter()
+                .map(|row| {
+                    let object: Map<String, Value> = table
+                        .columns
+                        .iter()
+                        .cloned()
+                        .zip(row.iter().cloned())
+                        .collect();
+                    Value::Object(object)
+                })
+                .collect();
+            let json = serde_json::to_string_pretty(&rows)
+                .map_err(|err| format!("failed to serialize {}: {err}", table.name))?;
+            std::fs::write(&path, json)
+                .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
+        }
+    }
+    Ok(path)
+}
+
+fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{leaderboard_table, trades_table, write_export, ExportFormat};
+    use crate::tasks::TradeSample;
+    use kairos_application::experiments::sweep::TrialOutcome;
+    use kairos_domain::value_objects::side::Side;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn trial(run_id: &str, params: &[(&str, &str)], sharpe: Option<f64>) -> TrialOutcome {
+        TrialOutcome {
+            run_id: run_id.to_string(),
+            split_id: "full".to_string(),
+            run_dir: format!("runs/{run_id}"),
+            params: params
+                .iter()
+                .map(|(p, v)| (p.to_string(), v.to_string()))
+                .collect(),
+            status: if sharpe.is_some() { "ok" } else { "error" }.to_string(),
+            net_profit: sharpe.map(|s| s * 100.0),
+            sharpe,
+            max_drawdown: sharpe.map(|_| 0.1),
+        }
+    }
+
+    #[test]
+    fn writes_the_leaderboard_and_trades_as_csv_and_json() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let dir = std::env::temp_dir().join(format!("kairos_tui_export_{unique}"));
+
+        let table = leaderboard_table(&[
+            trial("t1", &[("strategy.fast", "5")], Some(1.5)),
+            trial(
+                "t2",
+                &[("strategy.fast", "8"), ("strategy.slow", "30")],
+                None,
+            ),
+        ]);
+        let csv_path =
+            write_export(&dir, &table, ExportFormat::Csv, "20261016T120000Z").expect("csv");
+        assert_eq!(
+            csv_path.file_name().and_then(|n| n.to_str()),
+            Some("leaderboard_20261016T120000Z.csv")
+        );
+        let csv = std::fs::read_to_string(&csv_path).expect("read csv");
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "rank,run_id,split_id,status,strategy.fast,strategy.slow,sharpe,net_profit,max_drawdown,run_dir",
+                "1,t1,full,ok,5,,1.5,150.0,0.1,runs/t1",
+                "2,t2,full,error,8,30,,,,runs/t2",
+            ]
+        );
+
+        let trades = [TradeSample {
+            bar_index: 3,
+            timestamp: 1_700_000_000,
+            side: Side::Sell,
+            quantity: 0.5,
+            price: 101.25,
+        }];
+        let json_path = write_export(
+            &dir,
+            &trades_table(trades.iter()),
+            ExportFormat::Json,
+            "20261016T120000Z",
+        )
+        .expect("json");
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&json_path).expect("read json"))
+                .expect("parse json");
+        assert_eq!(json[0]["side"], "sell");
+        assert_eq!(json[0]["price"], 101.25);
+        assert_eq!(json[0]["bar_index"], 3);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
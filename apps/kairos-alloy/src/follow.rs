@@ -0,0 +1,241 @@
+use crate::tasks::TradeSample;
+use kairos_application::paper_trading::live::LIVE_JOURNAL_FILE;
+use kairos_domain::value_objects::side::Side;
+use serde_json::Value;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+// Enough of the journal's tail to hold its last record.
+const TAIL_BYTES: u64 = 16 * 1024;
+
+// A headless paper/live run still appending to its live.jsonl.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveRun {
+    pub run_dir: PathBuf,
+    pub modified: SystemTime,
+}
+
+// Runs under `out_dir` (and the per-symbol sleeves of multi-symbol sessions one level deeper)
+// whose journal does not end with an `end` record, most recently written first.
+pub fn find_live_runs(out_dir: &Path) -> Vec<LiveRun> {
+    let mut runs = Vec::new();
+    let mut dirs = subdirs(out_dir);
+    dirs.extend(dirs.clone().iter().flat_map(|dir| subdirs(dir)));
+    for dir in dirs {
+        let journal = dir.join(LIVE_JOURNAL_FILE);
+        if !journal_active(&journal) {
+            continue;
+        }
+        if let Some(modified) = std::fs::metadata(&journal)
+            .ok()
+            .and_then(|meta| meta.modified().ok())
+        {
+            runs.push(LiveRun {
+                run_dir: dir,
+                modified,
+            });
+        }
+    }
+    runs.sort_by(|a, b| b.modified.cmp(&a.modified));
+    runs
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect()
+        })
+        .unwrap_or_default();
+    dirs.sort();
+    dirs
+}
+
+// Whether the journal exists and its last complete record is not an `end`.
+pub fn journal_active(path: &Path) -> bool {
+    let Ok(mut file) = std::fs::File::open(path) else {
+        return false;
+    };
+    let len = file.metadata().map(|meta| meta.len()).unwrap_or(0);
+    if file
+        .seek(SeekFrom::Start(len.saturating_sub(TAIL_BYTES)))
+        .is_err()
+    {
+        return false;
+    }
+    let mut tail = String::new();
+    if file.read_to_string(&mut tail).is_err() {
+        return false;
+    }
+    let last = tail
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .next();
+    last.is_some_and(|record| record.get("type").and_then(Value::as_str) != Some("end"))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FollowUpdate {
+    Started {
+        run_id: String,
+        symbol: String,
+        mode: String,
+    },
+    Fill(TradeSample),
+    Equity {
+        bar_index: u64,
+        close: f64,
+        equity: f64,
+    },
+    Ended(String),
+    Other,
+}
+
+// Reads live.jsonl records into Monitor updates. Fills come before the equity record of their
+// bar, so they take the index of the bar after the last one seen.
+#[derive(Debug, Clone, Default)]
+pub struct JournalFollower {
+    pub last_bar: u64,
+}
+
+impl JournalFollower {
+    pub fn apply(&mut self, record: &Value) -> FollowUpdate {
+        let text = |key: &str| {
+            record
+                .get(key)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let number = |key: &str| record.get(key).and_then(Value::as_f64).unwrap_or(0.0);
+        let timestamp = record
+            .get("timestamp_utc")
+            .and_then(Value::as_i64)
+            .unwrap_or(0);
+        match record.get("type").and_then(Value::as_str) {
+            Some("start") => FollowUpdate::Started {
+                run_id: text("run_id"),
+                symbol: text("symbol"),
+                mode: text("mode"),
+            },
+            Some("fill") => FollowUpdate::Fill(TradeSample {
+                bar_index: self.last_bar + 1,
+                timestamp,
+                side: if text("side") == "SELL" {
+                    Side::Sell
+                } else {
+                    Side::Buy
+                },
+                quantity: number("qty"),
+                price: number("price"),
+            }),
+            Some("equity") => {
+                self.last_bar = record
+                    .get("bar_index")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(self.last_bar + 1);
+                FollowUpdate::Equity {
+                    bar_index: self.last_bar,
+                    close: number("close"),
+                    equity: number("equity"),
+                }
+            }
+            Some("end") => FollowUpdate::Ended(text("status")),
+            _ => FollowUpdate::Other,
+        }
+    }
+}
+
+// Restarts append a new session to the same journal; following starts at the latest one.
+pub fn latest_session(records: &[Value]) -> &[Value] {
+    let start = records
+        .iter()
+        .rposition(|record| record.get("type").and_then(Value::as_str) == Some("start"))
+        .unwrap_or(0);
+    &records[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_live_runs, latest_session, FollowUpdate, JournalFollower};
+    use kairos_domain::value_objects::side::Side;
+    use serde_json::json;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn follows_the_latest_session_and_finds_unfinished_journals() {
+        let records = vec![
+            json!({"type": "start", "run_id": "old", "symbol": "BTC-USDT", "mode": "replay"}),
+            json!({"type": "end", "status": "stopped"}),
+            json!({"type": "start", "run_id": "btc", "symbol": "BTC-USDT", "mode": "realtime"}),
+            json!({"type": "equity", "timestamp_utc": 60, "bar_index": 4, "close": 10.0, "equity": 100.0}),
+            json!({"type": "fill", "timestamp_utc": 120, "side": "SELL", "qty": 1.5, "price": 11.0}),
+            json!({"type": "equity", "timestamp_utc": 120, "bar_index": 5, "close": 11.0, "equity": 101.0}),
+            json!({"type": "end", "status": "completed"}),
+        ];
+        let session = latest_session(&records);
+        assert_eq!(session.len(), 5);
+
+        let mut follower = JournalFollower::default();
+        let updates: Vec<FollowUpdate> = session.iter().map(|r| follower.apply(r)).collect();
+        assert_eq!(
+            updates[0],
+            FollowUpdate::Started {
+                run_id: "btc".to_string(),
+                symbol: "BTC-USDT".to_string(),
+                mode: "realtime".to_string(),
+            }
+        );
+        let FollowUpdate::Fill(fill) = &updates[2] else {
+            panic!("expected a fill, got {:?}", updates[2]);
+        };
+        assert_eq!(
+            (fill.bar_index, fill.side, fill.price),
+            (5, Side::Sell, 11.0)
+        );
+        assert_eq!(
+            updates[3],
+            FollowUpdate::Equity {
+                bar_index: 5,
+                close: 11.0,
+                equity: 101.0
+            }
+        );
+        assert_eq!(updates[4], FollowUpdate::Ended("completed".to_string()));
+
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let out_dir = std::env::temp_dir().join(format!("kairos_tui_follow_{unique}"));
+        let write = |dir: &str, lines: &[serde_json::Value]| {
+            let dir = out_dir.join(dir);
+            std::fs::create_dir_all(&dir).expect("mkdir");
+            let body: String = lines.iter().map(|l| format!("{l}\n")).collect();
+            std::fs::write(dir.join("live.jsonl"), body).expect("write");
+        };
+        write("finished", &records);
+        write("running", &records[2..6]);
+        write("multi/ETH-USDT", &records[2..4]);
+        std::fs::create_dir_all(out_dir.join("backtest")).expect("mkdir");
+
+        let mut live: Vec<String> = find_live_runs(&out_dir)
+            .into_iter()
+            .map(|run| {
+                run.run_dir
+                    .strip_prefix(&out_dir)
+                    .expect("under out dir")
+                    .display()
+                    .to_string()
+            })
+            .collect();
+        live.sort();
+        assert_eq!(live, ["multi/ETH-USDT", "running"]);
+        let _ = std::fs::remove_dir_all(&out_dir);
+    }
+}
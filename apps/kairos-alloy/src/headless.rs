@@ -1,13 +1,38 @@
+use crate::ingest::{IngestCommand, KucoinIngestArgs, SeedArgs};
+use crate::jobs::{JobKind, JobSpec};
+use crate::output::CliError;
+use crate::profile::{profiled, ProfileArgs};
+use crate::progress::{ProgressArgs, ProgressReporter};
+use kairos_application::experiments::sweep::{SweepOverrides, SweepProgress};
+use kairos_application::paper_trading::clock::ReplayPacing;
+use kairos_application::paper_trading::notifications::PaperNotifications;
+use kairos_application::reporting::ReportOptions;
 use kairos_domain::repositories::agent::AgentClient as AgentPort;
+use kairos_domain::repositories::artifacts::BundleFormat;
+use kairos_domain::repositories::execution_venue::ExecutionVenue;
 use kairos_domain::repositories::market_data::MarketDataRepository;
+use kairos_domain::repositories::market_stream::MarketStream;
 use kairos_domain::repositories::sentiment::SentimentRepository;
+use kairos_domain::services::engine::backtest::{CancellationToken, NoopControl};
+use kairos_domain::services::montecarlo::MonteCarloConfig;
 use kairos_domain::services::ohlcv::{data_quality_from_bars, resample_bars};
+use kairos_domain::services::progress::ProgressEvent;
 use kairos_domain::value_objects::timeframe::Timeframe;
 use kairos_infrastructure::agents::AgentClient as InfraAgentClient;
 use kairos_infrastructure::artifacts::{FilesystemArtifactReader, FilesystemArtifactWriter};
+use kairos_infrastructure::execution::kucoin::{
+    KucoinCredentials, KucoinVenue, KUCOIN_API_URL_ENV,
+};
+use kairos_infrastructure::market_data::parquet_cache::ParquetOhlcvCache;
+use kairos_infrastructure::notifications::telegram::TelegramNotifier;
 use kairos_infrastructure::persistence::postgres_ohlcv::PostgresMarketDataRepository;
+use kairos_infrastructure::persistence::postgres_sentiment::PostgresSentimentRepository;
+use kairos_infrastructure::persistence::sqlite_run_registry::SqliteRunRegistry;
+use kairos_infrastructure::pushgateway::{Pushgateway, RunTiming};
 use kairos_infrastructure::sentiment::FilesystemSentimentRepository;
+use std::cell::RefCell;
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HeadlessMode {
@@ -17,46 +42,390 @@ pub enum HeadlessMode {
     Report,
     Sweep,
     Cpcv,
+    Compare,
+    Registry,
+    Annotate,
+    Walkforward,
+    Ingest,
+    Seed,
+    Batch,
+    Live,
+}
+
+impl HeadlessMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HeadlessMode::Validate => "validate",
+            HeadlessMode::Backtest => "backtest",
+            HeadlessMode::Paper => "paper",
+            HeadlessMode::Report => "report",
+            HeadlessMode::Sweep => "sweep",
+            HeadlessMode::Cpcv => "cpcv",
+            HeadlessMode::Compare => "compare",
+            HeadlessMode::Registry => "registry",
+            HeadlessMode::Annotate => "annotate",
+            HeadlessMode::Walkforward => "walkforward",
+            HeadlessMode::Ingest => "ingest",
+            HeadlessMode::Seed => "seed",
+            HeadlessMode::Batch => "batch",
+            HeadlessMode::Live => "live",
+        }
+    }
 }
 
 pub struct HeadlessArgs {
     pub mode: HeadlessMode,
     pub config_path: Option<PathBuf>,
     pub strict: bool,
+    // Second OHLCV source to reconcile the configured one against (validate mode only).
+    pub reconcile: ReconcileArgs,
     pub run_dir: Option<PathBuf>,
+    pub watch: bool,
+    pub watch_interval_secs: u64,
+    // Which artifacts to rewrite, and where (report mode only).
+    pub report: ReportOptions,
+    pub note: Option<String>,
     pub sweep_config: Option<PathBuf>,
-    pub cpcv_out: Option<PathBuf>,
-    pub cpcv_n_groups: usize,
-    pub cpcv_k_test: usize,
-    pub cpcv_horizon_bars: usize,
-    pub cpcv_purge_bars: usize,
-    pub cpcv_embargo_bars: usize,
-    pub cpcv_start: Option<String>,
-    pub cpcv_end: Option<String>,
+    pub cpcv: CpcvArgs,
+    pub walkforward: WalkForwardArgs,
+    pub ingest: IngestModeArgs,
+    // `config_path`/`db_url` are ignored; the mode uses `--config` like the others.
+    pub seed: SeedArgs,
+    // `jobs.toml` queue (batch mode only).
+    pub jobs: Option<PathBuf>,
+    // Overrides `[queue].concurrency`.
+    pub jobs_concurrency: Option<usize>,
+    // Live mode places real orders; it refuses to start without this acknowledgement.
+    pub confirm_live: bool,
+    // Continue the paper session saved in `<run_dir>/session.json` (paper mode only).
+    pub resume_session: bool,
+    pub compare_a: Option<PathBuf>,
+    pub compare_b: Option<PathBuf>,
+    pub compare_out: Option<PathBuf>,
+    pub registry: RegistryArgs,
+    // `--set key=value` config overrides, applied in order after load.
+    pub config_sets: Vec<String>,
+    // backtest | paper | sweep only.
+    pub profile: ProfileArgs,
+    pub progress: ProgressArgs,
+}
+
+#[derive(Debug, Clone)]
+pub struct CpcvArgs {
+    pub out: Option<PathBuf>,
+    pub n_groups: usize,
+    pub k_test: usize,
+    pub horizon_bars: usize,
+    pub purge_bars: usize,
+    pub embargo_bars: usize,
+    pub start: Option<String>,
+    pub end: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WalkForwardArgs {
+    pub train_bars: Option<usize>,
+    pub test_bars: Option<usize>,
+    // Defaults to `test_bars` (back-to-back OOS windows).
+    pub step: Option<usize>,
+    // TOML file with `[[params]]` (a sweep config works) re-optimized on every train window.
+    pub grid: Option<PathBuf>,
+    pub select: Option<String>,
+}
+
+// Unset fields keep the configured `db.*` value; reconciliation runs when any is set.
+#[derive(Debug, Clone, Default)]
+pub struct ReconcileArgs {
+    pub exchange: Option<String>,
+    pub market: Option<String>,
+    pub table: Option<String>,
+}
+
+impl ReconcileArgs {
+    fn reference_config(
+        &self,
+        config: &kairos_application::config::Config,
+    ) -> Option<kairos_application::config::Config> {
+        if self.exchange.is_none() && self.market.is_none() && self.table.is_none() {
+            return None;
+        }
+        let mut reference = config.clone();
+        if let Some(exchange) = &self.exchange {
+            reference.db.exchange = exchange.clone();
+        }
+        if let Some(market) = &self.market {
+            reference.db.market = market.clone();
+        }
+        if let Some(table) = &self.table {
+            reference.db.ohlcv_table = table.clone();
+        }
+        Some(reference)
+    }
 }
 
-pub fn run_headless(args: HeadlessArgs) -> Result<serde_json::Value, String> {
+#[derive(Debug, Clone, Default)]
+pub struct IngestModeArgs {
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub symbol: Option<String>,
+    pub timeframe: Option<String>,
+    pub base_url: Option<String>,
+    // Start after the series' ingest checkpoint when it is past `start`.
+    pub resume: bool,
+    // Apply the SQL migrations in `migrations_path` before fetching candles.
+    pub migrate: bool,
+    pub migrations_path: PathBuf,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RegistryArgs {
+    pub path: Option<PathBuf>,
+    pub reindex: bool,
+    pub symbol: Option<String>,
+    pub timeframe: Option<String>,
+    pub config_hash: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub sort: Option<String>,
+    pub ascending: bool,
+    pub limit: Option<usize>,
+}
+
+// Subcommands of the main binary (`kairos-alloy <command> ...`). They print the same single JSON
+// line as `--headless` modes.
+pub enum CliCommand {
+    Sweep {
+        sweep_config: PathBuf,
+        overrides: SweepOverrides,
+        profile: ProfileArgs,
+    },
+    Cpcv {
+        config_path: PathBuf,
+        config_sets: Vec<String>,
+        cpcv: CpcvArgs,
+    },
+    Walkforward {
+        config_path: PathBuf,
+        config_sets: Vec<String>,
+        walkforward: WalkForwardArgs,
+    },
+    Backtest {
+        config_path: PathBuf,
+        config_sets: Vec<String>,
+        profile: ProfileArgs,
+    },
+    Export {
+        input: PathBuf,
+        out: Option<PathBuf>,
+        format: BundleFormat,
+    },
+    Montecarlo {
+        input: PathBuf,
+        out: Option<PathBuf>,
+        config: MonteCarloConfig,
+    },
+    Compare {
+        a: PathBuf,
+        b: PathBuf,
+        out: Option<PathBuf>,
+    },
+    Annotate {
+        run_dir: PathBuf,
+        note: String,
+    },
+}
+
+pub fn run_headless(args: HeadlessArgs) -> Result<serde_json::Value, CliError> {
+    let reporter = ProgressReporter::new(args.progress.clone(), args.mode.as_str());
+    reporter.stage("started");
+    let result = run_headless_mode(&args, &reporter);
+    reporter.finished(&result);
+    result
+}
+
+fn run_headless_mode(
+    args: &HeadlessArgs,
+    reporter: &ProgressReporter,
+) -> Result<serde_json::Value, CliError> {
+    if args.profile.enabled()
+        && !matches!(
+            args.mode,
+            HeadlessMode::Backtest | HeadlessMode::Paper | HeadlessMode::Sweep
+        )
+    {
+        return Err(CliError::usage(
+            "--profile-svg/--profile-heap are only supported for backtest, paper and sweep",
+        ));
+    }
     match args.mode {
-        HeadlessMode::Sweep => run_sweep(args.sweep_config.as_deref()),
+        HeadlessMode::Sweep => profiled(&args.profile, || {
+            run_sweep(
+                args.sweep_config.as_deref(),
+                &SweepOverrides {
+                    config_sets: args.config_sets.clone(),
+                    ..SweepOverrides::default()
+                },
+                reporter,
+            )
+        }),
+        HeadlessMode::Compare => run_compare(args),
+        HeadlessMode::Registry => run_registry(args.config_path.as_deref(), &args.registry),
+        HeadlessMode::Annotate => run_annotate(args),
+        HeadlessMode::Ingest => run_ingest(args, reporter),
+        HeadlessMode::Seed => crate::ingest::run_seed(SeedArgs {
+            config_path: args.config_path.clone(),
+            db_url: None,
+            ..args.seed.clone()
+        })
+        .map(|report| report.to_json()),
+        HeadlessMode::Batch => run_jobs(args),
         mode => {
             let config_path = args
                 .config_path
                 .as_deref()
-                .ok_or_else(|| "--config is required for this mode".to_string())?;
-            let (config, config_toml) =
-                kairos_application::config::load_config_with_source(config_path)?;
+                .ok_or_else(|| CliError::usage("--config is required for this mode"))?;
+            let (config, config_toml) = kairos_application::config::load_config_with_overrides(
+                config_path,
+                &args.config_sets,
+            )?;
             match mode {
-                HeadlessMode::Validate => run_validate(&config, args.strict),
-                HeadlessMode::Backtest => run_backtest(&config, &config_toml),
-                HeadlessMode::Paper => run_paper(&config, &config_toml),
-                HeadlessMode::Report => run_report(&config, args.run_dir.as_deref()),
-                HeadlessMode::Sweep => unreachable!("handled above"),
-                HeadlessMode::Cpcv => run_cpcv(&config, &args),
+                HeadlessMode::Validate => {
+                    run_validate(&config, args.strict, &args.reconcile, reporter)
+                }
+                HeadlessMode::Backtest => profiled(&args.profile, || {
+                    run_backtest(&config, &config_toml, reporter)
+                }),
+                HeadlessMode::Paper => profiled(&args.profile, || {
+                    run_paper(&config, &config_toml, args.resume_session, reporter)
+                }),
+                HeadlessMode::Report => run_report(&config, args),
+                HeadlessMode::Live => run_live(&config, &config_toml, args.confirm_live, reporter),
+                HeadlessMode::Sweep
+                | HeadlessMode::Compare
+                | HeadlessMode::Registry
+                | HeadlessMode::Annotate
+                | HeadlessMode::Ingest
+                | HeadlessMode::Seed
+                | HeadlessMode::Batch => {
+                    unreachable!("handled above")
+                }
+                HeadlessMode::Cpcv => run_cpcv(&config, &args.cpcv),
+                HeadlessMode::Walkforward => {
+                    run_walkforward(&config, &config_toml, &args.walkforward)
+                }
             }
         }
     }
 }
 
+pub fn run_command(command: CliCommand) -> Result<serde_json::Value, CliError> {
+    match command {
+        CliCommand::Sweep {
+            sweep_config,
+            overrides,
+            profile,
+        } => profiled(&profile, || {
+            run_sweep(
+                Some(sweep_config.as_path()),
+                &overrides,
+                &ProgressReporter::new(ProgressArgs::default(), "sweep"),
+            )
+        }),
+        CliCommand::Backtest {
+            config_path,
+            config_sets,
+            profile,
+        } => {
+            let (config, config_toml) =
+                kairos_application::config::load_config_with_overrides(&config_path, &config_sets)?;
+            profiled(&profile, || {
+                run_backtest(
+                    &config,
+                    &config_toml,
+                    &ProgressReporter::new(ProgressArgs::default(), "backtest"),
+                )
+            })
+        }
+        CliCommand::Cpcv {
+            config_path,
+            config_sets,
+            cpcv,
+        } => {
+            let (config, _) =
+                kairos_application::config::load_config_with_overrides(&config_path, &config_sets)?;
+            run_cpcv(&config, &cpcv)
+        }
+        CliCommand::Walkforward {
+            config_path,
+            config_sets,
+            walkforward,
+        } => {
+            let (config, config_toml) =
+                kairos_application::config::load_config_with_overrides(&config_path, &config_sets)?;
+            run_walkforward(&config, &config_toml, &walkforward)
+        }
+        CliCommand::Export { input, out, format } => run_export(&input, out.as_deref(), format),
+        CliCommand::Montecarlo { input, out, config } => {
+            run_montecarlo(&input, out.as_deref(), config)
+        }
+        CliCommand::Compare { a, b, out } => compare_run_dirs(&a, &b, out),
+        CliCommand::Annotate { run_dir, note } => annotate_run_dir(&run_dir, &note),
+    }
+}
+
+// Optional migrations, then a KuCoin ingest with the same defaults as `ingest kucoin`.
+fn run_ingest(
+    args: &HeadlessArgs,
+    reporter: &ProgressReporter,
+) -> Result<serde_json::Value, CliError> {
+    let ingest = &args.ingest;
+    let start = ingest
+        .start
+        .clone()
+        .ok_or_else(|| CliError::usage("--ingest-start is required for --mode ingest"))?;
+    // Window lines on stderr, or `progress` events with `rows_written` under `--progress ndjson`.
+    let mut stderr_lines = kairos_ingest::stderr_progress();
+    let mut progress = |event: ProgressEvent<'_>| {
+        if reporter.ndjson() {
+            reporter.event(event);
+        } else {
+            stderr_lines(event);
+        }
+    };
+    if ingest.migrate {
+        crate::ingest::run_ingest(
+            IngestCommand::Migrate {
+                config_path: args.config_path.clone(),
+                db_url: None,
+                migrations_path: ingest.migrations_path.clone(),
+            },
+            &mut progress,
+        )?;
+    }
+    let mut value = crate::ingest::run_ingest(
+        IngestCommand::Kucoin(KucoinIngestArgs {
+            config_path: args.config_path.clone(),
+            db_url: None,
+            symbol: ingest.symbol.clone(),
+            market: None,
+            timeframe: ingest.timeframe.clone(),
+            start,
+            end: ingest.end.clone(),
+            exchange: None,
+            source: "kucoin".to_string(),
+            sleep_ms: 350,
+            batch_size: 500,
+            insert_mode: kairos_ingest::InsertMode::default(),
+            base_url: ingest.base_url.clone(),
+            resume: ingest.resume,
+        }),
+        &mut progress,
+    )?;
+    value["mode"] = serde_json::json!("ingest");
+    value["migrated"] = serde_json::json!(ingest.migrate);
+    Ok(value)
+}
+
 fn resolve_db_url(config: &kairos_application::config::Config) -> Result<String, String> {
     match config.db.url.as_deref() {
         Some(url) if !url.trim().is_empty() => Ok(url.to_string()),
@@ -65,23 +434,43 @@ fn resolve_db_url(config: &kairos_application::config::Config) -> Result<String,
     }
 }
 
-fn build_market_data_repo(
+pub(crate) fn build_market_data_repo(
     config: &kairos_application::config::Config,
 ) -> Result<Box<dyn MarketDataRepository>, String> {
     let db_url = resolve_db_url(config)?;
     let pool_max_size = config.db.pool_max_size.unwrap_or(8);
-    Ok(Box::new(PostgresMarketDataRepository::new(
-        db_url,
+    let postgres = PostgresMarketDataRepository::new(
+        db_url.clone(),
         config.db.ohlcv_table.to_string(),
         pool_max_size,
-    )?))
+    )?;
+    match config.db.cache_dir.as_deref() {
+        Some(dir) if !dir.trim().is_empty() => Ok(Box::new(ParquetOhlcvCache::new(
+            postgres,
+            PathBuf::from(dir),
+            format!("{db_url}|{}", config.db.ohlcv_table),
+        ))),
+        _ => Ok(Box::new(postgres)),
+    }
 }
 
-fn build_sentiment_repo() -> Box<dyn SentimentRepository + Sync> {
-    Box::new(FilesystemSentimentRepository)
+pub(crate) fn build_sentiment_repo(
+    config: &kairos_application::config::Config,
+) -> Result<Box<dyn SentimentRepository + Sync>, String> {
+    match config.paths.sentiment_source.unwrap_or_default() {
+        kairos_application::config::SentimentSourceKind::File => {
+            Ok(Box::new(FilesystemSentimentRepository))
+        }
+        kairos_application::config::SentimentSourceKind::Db => {
+            Ok(Box::new(PostgresSentimentRepository::new(
+                &resolve_db_url(config)?,
+                config.db.pool_max_size.unwrap_or(8),
+            )?))
+        }
+    }
 }
 
-fn build_remote_agent(
+pub(crate) fn build_remote_agent(
     config: &kairos_application::config::Config,
 ) -> Result<Option<Box<dyn AgentPort>>, String> {
     match config.agent.mode {
@@ -106,6 +495,22 @@ fn build_remote_agent(
     }
 }
 
+// The run commands' view of the builders above. A missing `db.url` is a config error; past that,
+// building the OHLCV repository opens its connection pool, so a failure there is a data error.
+pub(crate) fn market_data_repo(
+    config: &kairos_application::config::Config,
+) -> Result<Box<dyn MarketDataRepository>, CliError> {
+    resolve_db_url(config).map_err(CliError::config)?;
+    build_market_data_repo(config).map_err(CliError::data)
+}
+
+// The sentiment repository connects on first load; building it only checks the settings.
+pub(crate) fn sentiment_repo(
+    config: &kairos_application::config::Config,
+) -> Result<Box<dyn SentimentRepository + Sync>, CliError> {
+    build_sentiment_repo(config).map_err(CliError::config)
+}
+
 fn artifacts_for_run(run_dir: &Path) -> serde_json::Value {
     serde_json::json!({
         "run_dir": run_dir.display().to_string(),
@@ -120,18 +525,84 @@ fn artifacts_for_run(run_dir: &Path) -> serde_json::Value {
     })
 }
 
+fn run_jobs(args: &HeadlessArgs) -> Result<serde_json::Value, CliError> {
+    let jobs_path = args
+        .jobs
+        .as_deref()
+        .ok_or_else(|| CliError::usage("--jobs is required for --mode batch"))?;
+    let file = crate::jobs::load_jobs_file(jobs_path).map_err(CliError::config)?;
+    let concurrency = args
+        .jobs_concurrency
+        .or(file.queue.concurrency)
+        .unwrap_or(1)
+        .max(1);
+    let outcomes = crate::jobs::run_queue(&file.jobs, concurrency, &run_job);
+    Ok(crate::jobs::queue_json(&file.jobs, concurrency, &outcomes))
+}
+
+// One `[[jobs]]` entry of `--mode batch`; validation guarantees the path its kind needs.
+fn run_job(job: &JobSpec) -> Result<serde_json::Value, String> {
+    let reporter = ProgressReporter::new(ProgressArgs::default(), job.kind.as_str());
+    if job.kind == JobKind::Sweep {
+        return run_sweep(
+            job.sweep_config.as_deref(),
+            &SweepOverrides {
+                parallelism: job.workers,
+                config_sets: job.set.clone(),
+                ..SweepOverrides::default()
+            },
+            &reporter,
+        )
+        .map_err(|err| err.message);
+    }
+    let config_path = job
+        .config
+        .as_deref()
+        .ok_or_else(|| format!("jobs config: job '{}' needs `config`", job.id))?;
+    let (config, config_toml) =
+        kairos_application::config::load_config_with_overrides(config_path, &job.set)
+            .map_err(|err| err.to_string())?;
+    match job.kind {
+        JobKind::Validate => {
+            run_validate(&config, job.strict, &ReconcileArgs::default(), &reporter)
+        }
+        _ => run_backtest(&config, &config_toml, &reporter),
+    }
+    .map_err(|err| err.message)
+}
+
 fn run_validate(
     config: &kairos_application::config::Config,
     strict: bool,
-) -> Result<serde_json::Value, String> {
-    let market_data = build_market_data_repo(config)?;
-    let sentiment_repo = build_sentiment_repo();
-    let report = kairos_application::validation::validate(
+    reconcile: &ReconcileArgs,
+    reporter: &ProgressReporter,
+) -> Result<serde_json::Value, CliError> {
+    let market_data = market_data_repo(config)?;
+    let sentiment_repo = sentiment_repo(config)?;
+    let report = kairos_application::validation::validate_with_control(
         config,
         strict,
         market_data.as_ref(),
         sentiment_repo.as_ref(),
+        &NoopControl,
+        &mut |event: ProgressEvent<'_>| reporter.event(event),
     )?;
+    let reconciliation = match reconcile.reference_config(config) {
+        Some(reference) => {
+            let reference_data = market_data_repo(&reference)?;
+            Some(
+                kairos_application::validation::reconcile::reconcile_sources(
+                    config,
+                    &reference,
+                    market_data.as_ref(),
+                    reference_data.as_ref(),
+                    &NoopControl,
+                    &mut |event: ProgressEvent<'_>| reporter.event(event),
+                )?,
+            )
+        }
+        None => None,
+    };
     Ok(serde_json::json!({
         "status": "ok",
         "mode": "validate",
@@ -139,75 +610,347 @@ fn run_validate(
         "run_id": config.run.run_id,
         "out_dir": config.paths.out_dir,
         "report": report,
+        "reconciliation": reconciliation,
     }))
 }
 
 fn run_backtest(
     config: &kairos_application::config::Config,
     config_toml: &str,
-) -> Result<serde_json::Value, String> {
-    let market_data = build_market_data_repo(config)?;
-    let sentiment_repo = build_sentiment_repo();
+    reporter: &ProgressReporter,
+) -> Result<serde_json::Value, CliError> {
+    let run_dir = execute_backtest_reporting(config, config_toml, reporter)?;
+    Ok(serde_json::json!({
+        "status": "ok",
+        "mode": "backtest",
+        "run_id": config.run.run_id,
+        "out_dir": config.paths.out_dir,
+        "artifacts": artifacts_for_run(&run_dir),
+    }))
+}
+
+// Runs a backtest with the Postgres/filesystem adapters and records it in the registry.
+pub(crate) fn execute_backtest(
+    config: &kairos_application::config::Config,
+    config_toml: &str,
+) -> Result<PathBuf, CliError> {
+    let reporter = ProgressReporter::new(ProgressArgs::default(), "backtest");
+    execute_backtest_reporting(config, config_toml, &reporter)
+}
+
+fn execute_backtest_reporting(
+    config: &kairos_application::config::Config,
+    config_toml: &str,
+    reporter: &ProgressReporter,
+) -> Result<PathBuf, CliError> {
+    let market_data = market_data_repo(config)?;
+    let sentiment_repo = sentiment_repo(config)?;
     let artifacts = FilesystemArtifactWriter::new();
     let remote_agent = build_remote_agent(config)?;
+    let pushgateway = Pushgateway::from_env().map_err(CliError::config)?;
+    let started = Instant::now();
 
-    let run_dir = kairos_application::backtesting::run_backtest(
+    let run_dir = kairos_application::backtesting::run_backtest_streaming(
         config,
         config_toml,
         None,
-        market_data.as_ref(),
+        &reporter.market_data(market_data.as_ref()),
         sentiment_repo.as_ref(),
         &artifacts,
         remote_agent,
+        &mut |event: ProgressEvent<'_>| reporter.event(event),
     )?;
+    record_in_registry(Path::new(&config.paths.out_dir), &run_dir);
+    push_run_metrics(pushgateway.as_ref(), &run_dir, "backtest", started);
+    Ok(run_dir)
+}
+
+fn run_paper(
+    config: &kairos_application::config::Config,
+    config_toml: &str,
+    resume_session: bool,
+    reporter: &ProgressReporter,
+) -> Result<serde_json::Value, CliError> {
+    let resume = if resume_session {
+        kairos_application::paper_trading::session::load_paper_session(
+            config,
+            None,
+            &FilesystemArtifactReader::new(),
+        )?
+    } else {
+        None
+    };
+    let resumed_from_bar = resume.as_ref().map(|session| session.engine.bar_index());
+    let market_data = market_data_repo(config)?;
+    let sentiment_repo = sentiment_repo(config)?;
+    let artifacts = FilesystemArtifactWriter::new();
+    let remote_agent = build_remote_agent(config)?;
+    let telegram = TelegramNotifier::from_env().map_err(CliError::config)?;
+    let pushgateway = Pushgateway::from_env().map_err(CliError::config)?;
+    let started = Instant::now();
+    let mut notifications = telegram
+        .as_ref()
+        .map(|notifier| PaperNotifications::new(notifier, &config.run.run_id, &config.run.symbol));
+    if let Some(notifications) = notifications.as_mut() {
+        notifications.on_start("replay");
+    }
+
+    let result = kairos_application::paper_trading::run_paper_streaming_control(
+        config,
+        config_toml,
+        None,
+        &reporter.market_data(market_data.as_ref()),
+        sentiment_repo.as_ref(),
+        &artifacts,
+        remote_agent,
+        resume,
+        ReplayPacing::from_config(config),
+        &NoopControl,
+        &mut |event: ProgressEvent<'_>| {
+            reporter.event(event);
+            if let (ProgressEvent::BarProcessed(progress), Some(notifications)) =
+                (event, notifications.as_mut())
+            {
+                notifications.on_progress(progress);
+            }
+        },
+    );
+    if let Some(notifications) = notifications.as_mut() {
+        notifications.on_finish(&result);
+    }
+    let run_dir = result?;
+    record_in_registry(Path::new(&config.paths.out_dir), &run_dir);
+    push_run_metrics(pushgateway.as_ref(), &run_dir, "paper", started);
     Ok(serde_json::json!({
         "status": "ok",
-        "mode": "backtest",
+        "mode": "paper",
         "run_id": config.run.run_id,
+        "resumed_from_bar": resumed_from_bar,
         "out_dir": config.paths.out_dir,
         "artifacts": artifacts_for_run(&run_dir),
     }))
 }
 
-fn run_paper(
+fn run_live(
     config: &kairos_application::config::Config,
     config_toml: &str,
-) -> Result<serde_json::Value, String> {
-    let market_data = build_market_data_repo(config)?;
-    let sentiment_repo = build_sentiment_repo();
+    confirmed: bool,
+    reporter: &ProgressReporter,
+) -> Result<serde_json::Value, CliError> {
+    if !confirmed {
+        return Err(CliError::usage(
+            "--confirm-live is required: live mode places real orders on live.venue",
+        ));
+    }
+    let settings = kairos_application::live_trading::resolve_live_settings(config)?;
+    let venue = build_execution_venue(&settings.venue)?;
+    let sentiment_repo = sentiment_repo(config)?;
     let artifacts = FilesystemArtifactWriter::new();
     let remote_agent = build_remote_agent(config)?;
+    let telegram = TelegramNotifier::from_env().map_err(CliError::config)?;
+    let pushgateway = Pushgateway::from_env().map_err(CliError::config)?;
+    let started = Instant::now();
+    let notifications = telegram.as_ref().map(|notifier| {
+        RefCell::new(PaperNotifications::new(
+            notifier,
+            &config.run.run_id,
+            &config.run.symbol,
+        ))
+    });
+    if let Some(notifications) = &notifications {
+        notifications.borrow_mut().on_start("live");
+    }
 
-    let run_dir = kairos_application::paper_trading::run_paper(
+    let mut connect_stream = || -> Result<Box<dyn MarketStream>, String> {
+        #[cfg(feature = "realtime-kucoin")]
+        {
+            let stream =
+                kairos_infrastructure::market_stream::kucoin::KucoinPublicTickerStream::connect(
+                    config.run.symbol.clone(),
+                )?;
+            Ok(Box::new(stream))
+        }
+        #[cfg(not(feature = "realtime-kucoin"))]
+        {
+            Err("kairos-alloy was built without feature realtime-kucoin".to_string())
+        }
+    };
+    let control = stop_on_ctrl_c()?;
+    let result = kairos_application::live_trading::run_live_streaming_control(
         config,
         config_toml,
         None,
-        market_data.as_ref(),
+        &mut connect_stream,
         sentiment_repo.as_ref(),
         &artifacts,
         remote_agent,
-    )?;
+        venue.as_ref(),
+        &control,
+        &mut |event: ProgressEvent<'_>| {
+            reporter.event(event);
+            if let (ProgressEvent::BarProcessed(progress), Some(notifications)) =
+                (event, &notifications)
+            {
+                notifications.borrow_mut().on_progress(progress);
+            }
+        },
+        &mut |status| {
+            if let Some(err) = &status.last_error {
+                tracing::warn!(reconnects = status.reconnects, error = %err, "market stream disconnected");
+            }
+            if let Some(notifications) = &notifications {
+                notifications.borrow_mut().on_status(&status);
+            }
+        },
+    );
+    if let Some(notifications) = &notifications {
+        notifications.borrow_mut().on_finish(&result);
+    }
+    let run_dir = result?;
+    record_in_registry(Path::new(&config.paths.out_dir), &run_dir);
+    push_run_metrics(pushgateway.as_ref(), &run_dir, "live", started);
     Ok(serde_json::json!({
         "status": "ok",
-        "mode": "paper",
+        "mode": "live",
         "run_id": config.run.run_id,
+        "venue": venue.name(),
         "out_dir": config.paths.out_dir,
         "artifacts": artifacts_for_run(&run_dir),
     }))
 }
 
+// The first Ctrl-C stops the session after the current bar, so it still audits "stopped" and writes
+// its artifacts; a second one exits at once.
+fn stop_on_ctrl_c() -> Result<CancellationToken, CliError> {
+    let token = CancellationToken::new();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_io()
+        .build()
+        .map_err(|err| format!("failed to start the ctrl-c handler: {err}"))?;
+    let cancel = token.clone();
+    std::thread::spawn(move || {
+        runtime.block_on(async {
+            if tokio::signal::ctrl_c().await.is_err() {
+                return;
+            }
+            tracing::warn!("ctrl-c: stopping the live session after the current bar");
+            cancel.cancel();
+            if tokio::signal::ctrl_c().await.is_ok() {
+                crate::telemetry::shutdown();
+                std::process::exit(130);
+            }
+        })
+    });
+    Ok(token)
+}
+
+// `live.venue` picks the KuCoin endpoint; `KAIROS_KUCOIN_API_URL` overrides it (e.g. a mock).
+fn build_execution_venue(venue: &str) -> Result<Box<dyn ExecutionVenue>, CliError> {
+    let credentials = KucoinCredentials::from_env().map_err(CliError::config)?;
+    let base_url = std::env::var(KUCOIN_API_URL_ENV)
+        .ok()
+        .filter(|v| !v.trim().is_empty());
+    let venue = match (venue, base_url) {
+        (_, Some(base_url)) => KucoinVenue::with_base_url(venue, &base_url, credentials)?,
+        ("kucoin", None) => KucoinVenue::production(credentials)?,
+        _ => KucoinVenue::sandbox(credentials)?,
+    };
+    Ok(Box::new(venue))
+}
+
+fn run_export(
+    input: &Path,
+    out: Option<&Path>,
+    format: BundleFormat,
+) -> Result<serde_json::Value, CliError> {
+    let reader = FilesystemArtifactReader::new();
+    let writer = FilesystemArtifactWriter::new();
+    let result =
+        kairos_application::reporting::export::export_run(input, out, format, &reader, &writer)?;
+    Ok(serde_json::json!({
+        "status": "ok",
+        "mode": "export",
+        "input_dir": result.input_dir.display().to_string(),
+        "format": result.format.as_str(),
+        "archive": result.archive.display().to_string(),
+        "files": result.entries,
+    }))
+}
+
+fn run_montecarlo(
+    input: &Path,
+    out: Option<&Path>,
+    config: MonteCarloConfig,
+) -> Result<serde_json::Value, CliError> {
+    use kairos_application::reporting::montecarlo::distribution_json;
+
+    let reader = FilesystemArtifactReader::new();
+    let writer = FilesystemArtifactWriter::new();
+    let result = kairos_application::reporting::montecarlo::run_montecarlo(
+        input, out, config, &reader, &writer,
+    )?;
+    let headline = |r: &kairos_domain::services::montecarlo::ResamplingResult| {
+        serde_json::json!({
+            "observations": r.observations,
+            "prob_loss": r.prob_loss,
+            "total_return": distribution_json(&r.total_return),
+            "max_drawdown": distribution_json(&r.max_drawdown),
+        })
+    };
+    Ok(serde_json::json!({
+        "status": "ok",
+        "mode": "montecarlo",
+        "input_dir": result.input_dir.display().to_string(),
+        "output": result.output.display().to_string(),
+        "samples": result.config.samples,
+        "block": result.config.block,
+        "seed": result.config.seed,
+        "returns": headline(&result.returns),
+        "trades": result.trades.as_ref().map(headline),
+    }))
+}
+
 fn run_report(
     config: &kairos_application::config::Config,
-    run_dir: Option<&Path>,
-) -> Result<serde_json::Value, String> {
-    let input_dir = run_dir
-        .map(|p| p.to_path_buf())
-        .ok_or_else(|| "--run-dir is required for --mode report".to_string())?;
+    args: &HeadlessArgs,
+) -> Result<serde_json::Value, CliError> {
+    let input_dir = args
+        .run_dir
+        .clone()
+        .ok_or_else(|| CliError::usage("--run-dir is required for --mode report"))?;
 
     let reader = FilesystemArtifactReader::new();
     let writer = FilesystemArtifactWriter::new();
-    let result =
-        kairos_application::reporting::generate_report(input_dir.as_path(), &reader, &writer)?;
+    let result = if args.watch {
+        if args.watch_interval_secs == 0 {
+            return Err(CliError::usage("--watch-interval-secs must be > 0"));
+        }
+        kairos_application::reporting::watch::watch_report(
+            input_dir.as_path(),
+            &args.report,
+            &reader,
+            &writer,
+            std::time::Duration::from_secs(args.watch_interval_secs),
+            &|| false,
+            &mut |tick| {
+                tracing::info!(
+                    tick = tick.tick,
+                    new_records = tick.new_records,
+                    bars = tick.bars,
+                    trades = tick.trades,
+                    net_profit = tick.summary.net_profit,
+                    "report refreshed from live journal"
+                );
+            },
+        )?
+    } else {
+        kairos_application::reporting::generate_report_with(
+            input_dir.as_path(),
+            &args.report,
+            &reader,
+            &writer,
+        )?
+    };
 
     Ok(serde_json::json!({
         "status": "ok",
@@ -215,7 +958,10 @@ fn run_report(
         "run_id": result.run_id,
         "out_dir": config.paths.out_dir,
         "input_dir": result.input_dir.display().to_string(),
+        "report_dir": result.out_dir.display().to_string(),
+        "outputs": result.outputs.iter().map(|output| output.as_str()).collect::<Vec<_>>(),
         "wrote_html": result.wrote_html,
+        "watch": args.watch,
         "summary": {
             "bars_processed": result.summary.bars_processed,
             "trades": result.summary.trades,
@@ -227,19 +973,232 @@ fn run_report(
     }))
 }
 
-fn run_sweep(sweep_config: Option<&Path>) -> Result<serde_json::Value, String> {
+fn run_registry(
+    config_path: Option<&Path>,
+    args: &RegistryArgs,
+) -> Result<serde_json::Value, CliError> {
+    use kairos_domain::repositories::run_registry::{RunQuery, RunSortKey};
+
+    let out_dir = match config_path {
+        Some(path) => {
+            let config = kairos_application::config::load_config(path)?;
+            PathBuf::from(config.paths.out_dir)
+        }
+        None => PathBuf::from("runs"),
+    };
+    let registry_path = args
+        .path
+        .clone()
+        .unwrap_or_else(|| kairos_application::registry::registry_path(&out_dir));
+    let registry = SqliteRunRegistry::open(&registry_path)?;
+
+    let reindexed = if args.reindex {
+        Some(kairos_application::registry::reindex(&out_dir, &registry)?)
+    } else {
+        None
+    };
+
+    let sort_by = args
+        .sort
+        .as_deref()
+        .map(RunSortKey::parse)
+        .transpose()
+        .map_err(|err| CliError::usage(format!("--registry-sort: {err}")))?
+        .unwrap_or(RunSortKey::RecordedAt);
+    let query = RunQuery {
+        symbol: args.symbol.clone(),
+        timeframe: args.timeframe.clone(),
+        config_hash: args.config_hash.clone(),
+        start_from: args
+            .from
+            .as_deref()
+            .map(parse_timestamp_seconds)
+            .transpose()?,
+        end_to: args
+            .to
+            .as_deref()
+            .map(parse_timestamp_seconds)
+            .transpose()?,
+        sort_by,
+        descending: !args.ascending,
+        limit: args.limit,
+    };
+    let runs = kairos_application::registry::list_runs(&registry, &query)?;
+
+    Ok(serde_json::json!({
+        "status": "ok",
+        "mode": "registry",
+        "registry_path": registry.path().display().to_string(),
+        "reindexed": reindexed,
+        "count": runs.len(),
+        "runs": runs,
+    }))
+}
+
+fn run_annotate(args: &HeadlessArgs) -> Result<serde_json::Value, CliError> {
+    let run_dir = args
+        .run_dir
+        .as_deref()
+        .ok_or_else(|| CliError::usage("--run-dir is required for --mode annotate"))?;
+    let note = args
+        .note
+        .as_deref()
+        .ok_or_else(|| CliError::usage("--note is required for --mode annotate"))?;
+    annotate_run_dir(run_dir, note)
+}
+
+fn annotate_run_dir(run_dir: &Path, note: &str) -> Result<serde_json::Value, CliError> {
+    let reader = FilesystemArtifactReader::new();
+    let writer = FilesystemArtifactWriter::new();
+    let recorded = kairos_application::annotations::annotate_run(run_dir, note, &reader, &writer)?;
+    let notes = kairos_application::annotations::read_notes(run_dir, &reader)?;
+
+    // Run dirs live directly under `paths.out_dir`; refresh the registry row only when one exists
+    // there, so annotating never creates a registry as a side effect.
+    let out_dir = run_dir
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let registry_updated = kairos_application::registry::registry_path(out_dir).is_file()
+        && run_dir.join("summary.json").is_file();
+    if registry_updated {
+        record_in_registry(out_dir, run_dir);
+    }
+
+    Ok(serde_json::json!({
+        "status": "ok",
+        "mode": "annotate",
+        "run_dir": run_dir.display().to_string(),
+        "notes_path": kairos_application::annotations::notes_path(run_dir).display().to_string(),
+        "note": recorded,
+        "notes_count": notes.len(),
+        "registry_updated": registry_updated,
+    }))
+}
+
+pub(crate) fn record_in_registry(out_dir: &Path, run_dir: &Path) {
+    let registry_path = kairos_application::registry::registry_path(out_dir);
+    let result = SqliteRunRegistry::open(&registry_path)
+        .and_then(|registry| kairos_application::registry::record_run(run_dir, &registry));
+    if let Err(err) = result {
+        tracing::warn!(
+            run_dir = %run_dir.display(),
+            registry = %registry_path.display(),
+            error = %err,
+            "failed to record run in registry"
+        );
+    }
+}
+
+// Like the registry, best effort: an unreachable Pushgateway does not fail a finished run.
+fn push_run_metrics(gateway: Option<&Pushgateway>, run_dir: &Path, mode: &str, started: Instant) {
+    let Some(gateway) = gateway else {
+        return;
+    };
+    let timing = RunTiming {
+        mode,
+        duration_ms: started.elapsed().as_millis() as u64,
+    };
+    let result = kairos_application::registry::read_run_record(run_dir)
+        .and_then(|run| gateway.push_run(&run, timing));
+    if let Err(err) = result {
+        tracing::warn!(
+            run_dir = %run_dir.display(),
+            error = %err,
+            "failed to push run metrics to the pushgateway"
+        );
+    }
+}
+
+fn run_compare(args: &HeadlessArgs) -> Result<serde_json::Value, CliError> {
+    let run_a = args
+        .compare_a
+        .as_deref()
+        .ok_or_else(|| CliError::usage("--compare-a is required for --mode compare"))?;
+    let run_b = args
+        .compare_b
+        .as_deref()
+        .ok_or_else(|| CliError::usage("--compare-b is required for --mode compare"))?;
+    compare_run_dirs(run_a, run_b, args.compare_out.clone())
+}
+
+fn compare_run_dirs(
+    run_a: &Path,
+    run_b: &Path,
+    out_dir: Option<PathBuf>,
+) -> Result<serde_json::Value, CliError> {
+    let out_dir = out_dir.unwrap_or_else(|| default_compare_out(run_a, run_b));
+
+    let reader = FilesystemArtifactReader::new();
+    let writer = FilesystemArtifactWriter::new();
+    let result = kairos_application::reporting::compare::compare_runs(
+        run_a, run_b, &out_dir, &reader, &writer,
+    )?;
+
+    Ok(serde_json::json!({
+        "status": "ok",
+        "mode": "compare",
+        "run_a": result.run_a,
+        "run_b": result.run_b,
+        "out_dir": result.out_dir.display().to_string(),
+        "metrics": result
+            .metrics
+            .iter()
+            .map(|m| serde_json::json!({
+                "name": m.name,
+                "a": m.a,
+                "b": m.b,
+                "delta": m.delta(),
+            }))
+            .collect::<Vec<_>>(),
+        "config_diff_keys": result
+            .config_diff
+            .iter()
+            .map(|entry| entry.key.clone())
+            .collect::<Vec<_>>(),
+        "artifacts": {
+            "compare_json": result.out_dir.join("compare.json").display().to_string(),
+            "compare_html": result.out_dir.join("compare.html").display().to_string(),
+        },
+    }))
+}
+
+fn default_compare_out(run_a: &Path, run_b: &Path) -> PathBuf {
+    let name = |p: &Path| {
+        p.file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("run")
+            .to_string()
+    };
+    run_a
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("compare")
+        .join(format!("{}__vs__{}", name(run_a), name(run_b)))
+}
+
+fn run_sweep(
+    sweep_config: Option<&Path>,
+    overrides: &SweepOverrides,
+    reporter: &ProgressReporter,
+) -> Result<serde_json::Value, CliError> {
     let sweep_path = sweep_config
         .map(|p| p.to_path_buf())
-        .ok_or_else(|| "--sweep-config is required for --mode sweep".to_string())?;
+        .ok_or_else(|| CliError::usage("--sweep-config is required for --mode sweep"))?;
 
     let raw = std::fs::read_to_string(&sweep_path).map_err(|err| {
-        format!(
+        CliError::config(format!(
             "failed to read sweep config {}: {err}",
             sweep_path.display()
-        )
+        ))
     })?;
     let sweep_file: kairos_application::experiments::sweep::SweepFile = toml::from_str(&raw)
-        .map_err(|err| format!("failed to parse sweep TOML {}: {err}", sweep_path.display()))?;
+        .map_err(|err| {
+            CliError::config(format!(
+                "failed to parse sweep TOML {}: {err}",
+                sweep_path.display()
+            ))
+        })?;
 
     let base_config_path = {
         let p = PathBuf::from(&sweep_file.base.config);
@@ -252,11 +1211,13 @@ fn run_sweep(sweep_config: Option<&Path>) -> Result<serde_json::Value, String> {
                 .join(p)
         }
     };
-    let (base_config, _toml) =
-        kairos_application::config::load_config_with_source(base_config_path.as_path())?;
+    let (base_config, _toml) = kairos_application::config::load_config_with_overrides(
+        base_config_path.as_path(),
+        &overrides.config_sets,
+    )?;
 
-    let market_data = build_market_data_repo(&base_config)?;
-    let sentiment_repo = build_sentiment_repo();
+    let market_data = market_data_repo(&base_config)?;
+    let sentiment_repo = sentiment_repo(&base_config)?;
     let artifacts = FilesystemArtifactWriter::new();
 
     let agent_factory =
@@ -264,13 +1225,36 @@ fn run_sweep(sweep_config: Option<&Path>) -> Result<serde_json::Value, String> {
             build_remote_agent(cfg)
         };
 
-    let result = kairos_application::experiments::sweep::run_sweep(
+    // One stderr line per finished trial, or a `progress` event with `--progress ndjson`.
+    let mut on_progress = |event: ProgressEvent<'_>| match event {
+        ProgressEvent::TrialCompleted(progress) if !reporter.ndjson() => {
+            if let Some(line) = format_sweep_progress(progress) {
+                eprintln!("{line}");
+            }
+        }
+        event => reporter.event(event),
+    };
+    let result = kairos_application::experiments::sweep::run_sweep_with_hooks(
         sweep_path.as_path(),
+        overrides,
         &agent_factory,
-        market_data.as_ref(),
+        &reporter.market_data(market_data.as_ref()),
         sentiment_repo.as_ref(),
         &artifacts,
+        Some(&mut on_progress),
+        None,
     )?;
+    let out_dir = overrides
+        .out_dir
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(&base_config.paths.out_dir));
+    for entry in result
+        .runs
+        .iter()
+        .filter(|entry| matches!(entry.status.as_str(), "ok" | "skipped"))
+    {
+        record_in_registry(&out_dir, &out_dir.join(&entry.run_id));
+    }
 
     Ok(serde_json::json!({
         "status": "ok",
@@ -280,17 +1264,44 @@ fn run_sweep(sweep_config: Option<&Path>) -> Result<serde_json::Value, String> {
         "manifest_json": result.sweep_dir.join("manifest.json").display().to_string(),
         "results_csv": result.sweep_dir.join("results.csv").display().to_string(),
         "leaderboard_csv": result.sweep_dir.join("leaderboard.csv").display().to_string(),
+        "index_html": result.sweep_dir.join("index.html").display().to_string(),
+        "out_dir": out_dir.display().to_string(),
         "runs_total": result.runs.len(),
+        "runs_ok": result.runs.iter().filter(|entry| entry.status == "ok").count(),
+        "runs_error": result.runs.iter().filter(|entry| entry.status == "error").count(),
+        "runs_pruned": result.runs.iter().filter(|entry| entry.status == "pruned").count(),
     }))
 }
 
+fn format_sweep_progress(progress: &SweepProgress) -> Option<String> {
+    let run_id = progress.last_run_id.as_deref()?;
+    let status = progress.last_status.as_deref().unwrap_or("ok");
+    let secs = |ms: u64| format!("{:.1}s", ms as f64 / 1000.0);
+    let mut line = format!(
+        "sweep [{}/{}] {status} {run_id}",
+        progress.completed_runs, progress.total_runs
+    );
+    if let Some(trial_ms) = progress.last_trial_ms {
+        line.push_str(&format!(" in {}", secs(trial_ms)));
+    }
+    line.push_str(&format!(
+        " | workers={} elapsed={}",
+        progress.workers,
+        secs(progress.elapsed_ms)
+    ));
+    if let Some(eta_ms) = progress.eta_ms {
+        line.push_str(&format!(" eta={}", secs(eta_ms)));
+    }
+    Some(line)
+}
+
 fn run_cpcv(
     config: &kairos_application::config::Config,
-    args: &HeadlessArgs,
-) -> Result<serde_json::Value, String> {
-    let market_data = build_market_data_repo(config)?;
+    args: &CpcvArgs,
+) -> Result<serde_json::Value, CliError> {
+    let market_data = market_data_repo(config)?;
 
-    let timeframe = Timeframe::parse_or_seconds(&config.run.timeframe)?;
+    let timeframe = Timeframe::parse_or_seconds(&config.run.timeframe).map_err(CliError::config)?;
     let expected_step = timeframe.step_seconds;
     let timeframe_label = timeframe.label;
 
@@ -299,25 +1310,24 @@ fn run_cpcv(
         .source_timeframe
         .as_deref()
         .unwrap_or(&timeframe_label);
-    let source_timeframe = Timeframe::parse_or_seconds(source_label)?;
+    let source_timeframe = Timeframe::parse_or_seconds(source_label).map_err(CliError::config)?;
     let source_step = source_timeframe.step_seconds;
     let source_timeframe_label = source_timeframe.label;
 
-    let (source_bars, _source_report) =
-        market_data.load_ohlcv(&kairos_domain::repositories::market_data::OhlcvQuery {
-            exchange: config.db.exchange.to_lowercase(),
-            market: config.db.market.to_lowercase(),
-            symbol: config.run.symbol.clone(),
-            timeframe: source_timeframe_label.clone(),
-            expected_step_seconds: Some(source_step),
-        })?;
+    let (source_bars, _source_report) = market_data
+        .load_ohlcv(&kairos_application::config::build_ohlcv_query(
+            config,
+            &source_timeframe_label,
+            source_step,
+        )?)
+        .map_err(CliError::data)?;
 
     let bars = if source_timeframe_label != timeframe_label {
         if source_step > expected_step {
-            return Err(format!(
+            return Err(CliError::config(format!(
                 "cannot resample OHLCV: source timeframe ({}) is larger than run timeframe ({})",
                 source_timeframe_label, timeframe_label
-            ));
+            )));
         }
         resample_bars(&source_bars, expected_step)?
     } else {
@@ -329,12 +1339,12 @@ fn run_cpcv(
     bars.dedup_by_key(|b| b.timestamp);
 
     let start = args
-        .cpcv_start
+        .start
         .as_deref()
         .map(parse_timestamp_seconds)
         .transpose()?;
     let end = args
-        .cpcv_end
+        .end
         .as_deref()
         .map(parse_timestamp_seconds)
         .transpose()?;
@@ -345,15 +1355,15 @@ fn run_cpcv(
         .collect();
 
     let cfg = kairos_application::experiments::cpcv::CpcvConfig {
-        n_groups: args.cpcv_n_groups,
-        k_test: args.cpcv_k_test,
-        horizon_bars: args.cpcv_horizon_bars,
-        purge_bars: args.cpcv_purge_bars,
-        embargo_bars: args.cpcv_embargo_bars,
+        n_groups: args.n_groups,
+        k_test: args.k_test,
+        horizon_bars: args.horizon_bars,
+        purge_bars: args.purge_bars,
+        embargo_bars: args.embargo_bars,
     };
     let cpcv = kairos_application::experiments::cpcv::generate_cpcv(&bars, cfg)?;
 
-    let out_path = args.cpcv_out.clone().unwrap_or_else(|| {
+    let out_path = args.out.clone().unwrap_or_else(|| {
         PathBuf::from(&config.paths.out_dir)
             .join("cpcv")
             .join(format!("{}__cpcv.csv", config.run.run_id))
@@ -388,15 +1398,88 @@ fn run_cpcv(
     }))
 }
 
-fn parse_timestamp_seconds(raw: &str) -> Result<i64, String> {
+fn run_walkforward(
+    config: &kairos_application::config::Config,
+    config_toml: &str,
+    args: &WalkForwardArgs,
+) -> Result<serde_json::Value, CliError> {
+    use kairos_application::experiments::walkforward::{WalkForwardConfig, WalkForwardRequest};
+    use kairos_domain::repositories::run_registry::RunSortKey;
+
+    let train_bars = args
+        .train_bars
+        .ok_or_else(|| CliError::usage("--train-bars is required for walkforward"))?;
+    let test_bars = args
+        .test_bars
+        .ok_or_else(|| CliError::usage("--test-bars is required for walkforward"))?;
+    let params = match &args.grid {
+        Some(path) => kairos_application::experiments::walkforward::load_grid_params(path)
+            .map_err(CliError::config)?,
+        None => Vec::new(),
+    };
+    let select_by = RunSortKey::parse(args.select.as_deref().unwrap_or("sharpe"))
+        .map_err(|err| CliError::usage(format!("--select: {err}")))?;
+    let request = WalkForwardRequest {
+        config,
+        config_toml,
+        wf: WalkForwardConfig {
+            train_bars,
+            test_bars,
+            step_bars: args.step.unwrap_or(test_bars),
+        },
+        params: &params,
+        select_by,
+    };
+
+    let market_data = market_data_repo(config)?;
+    let sentiment_repo = sentiment_repo(config)?;
+    let reader = FilesystemArtifactReader::new();
+    let writer = FilesystemArtifactWriter::new();
+    let agent_factory =
+        |cfg: &kairos_application::config::Config| -> Result<Option<Box<dyn AgentPort>>, String> {
+            build_remote_agent(cfg)
+        };
+    let result = kairos_application::experiments::walkforward::run_walkforward(
+        &request,
+        &agent_factory,
+        market_data.as_ref(),
+        sentiment_repo.as_ref(),
+        &reader,
+        &writer,
+    )?;
+
+    let summary = &result.oos_summary;
+    Ok(serde_json::json!({
+        "status": "ok",
+        "mode": "walkforward",
+        "run_id": config.run.run_id,
+        "wf_dir": result.wf_dir.display().to_string(),
+        "windows_csv": result.wf_dir.join("windows.csv").display().to_string(),
+        "windows": result.windows.len(),
+        "select_by": args.select.as_deref().unwrap_or("sharpe"),
+        "oos_summary": {
+            "bars_processed": summary.bars_processed,
+            "trades": summary.trades,
+            "win_rate": summary.win_rate,
+            "net_profit": summary.net_profit,
+            "sharpe": summary.sharpe,
+            "max_drawdown": summary.max_drawdown,
+        },
+    }))
+}
+
+fn parse_timestamp_seconds(raw: &str) -> Result<i64, CliError> {
     let trimmed = raw.trim();
     if trimmed.is_empty() {
-        return Err("timestamp cannot be empty".to_string());
+        return Err(CliError::usage("timestamp cannot be empty"));
     }
     if let Ok(v) = trimmed.parse::<i64>() {
         return Ok(v);
     }
-    let dt = chrono::DateTime::parse_from_rfc3339(trimmed)
-        .map_err(|err| format!("invalid timestamp (expected epoch seconds or RFC3339): {err}"))?;
+    let dt = chrono::DateTime::parse_from_rfc3339(trimmed).map_err(|err| {
+        CliError::usage(format!(
+            "invalid timestamp (expected epoch seconds or RFC3339): {err}"
+        ))
+    })?;
     Ok(dt.timestamp())
 }
@@ -0,0 +1,368 @@
+use crate::app::ViewId;
+use crate::keymap::{Action, Keymap};
+
+// A row of the help overlay: a keymap action (labelled with its current bindings, so a remap in
+// keymap.toml shows up here) or a key the view handles itself.
+#[derive(Debug, Clone, Copy)]
+pub enum HelpKey {
+    Action(Action, &'static str),
+    Fixed(&'static str, &'static str),
+}
+
+use HelpKey::{Action as Act, Fixed};
+
+#[derive(Debug, Clone, Copy)]
+pub struct ViewHelp {
+    pub title: &'static str,
+    pub about: &'static str,
+    pub keys: &'static [HelpKey],
+}
+
+// Available everywhere, listed after the view's own keys.
+const GLOBAL_KEYS: &[HelpKey] = &[
+    Act(Action::Help, "show/hide this help"),
+    Fixed("Ctrl-c", "quit from any view"),
+];
+
+pub fn view_help(view: ViewId) -> ViewHelp {
+    let (title, about, keys): (&str, &str, &[HelpKey]) = match view {
+        ViewId::MainMenu => (
+            "Menu",
+            "Pick a view. Alerts and the theme are saved in settings.json.",
+            &[
+                Act(Action::Up, "previous item"),
+                Act(Action::Down, "next item"),
+                Act(Action::Select, "open the view"),
+                Act(Action::Theme, "cycle the color theme"),
+                Fixed("b", "terminal bell on/off when long tasks finish"),
+                Fixed("o", "cycle desktop notifications (off/osc9/osc777)"),
+                Act(Action::Quit, "quit"),
+            ],
+        ),
+        ViewId::Setup => (
+            "Setup",
+            "Load a config by path or from the list (recent first) and quick-edit its common fields.",
+            &[
+                Fixed("Tab", "cycle path / list / quick edit"),
+                Fixed("i/l/e", "focus path / list / quick edit"),
+                Act(Action::Up, "previous config or field"),
+                Act(Action::Down, "next config or field"),
+                Act(Action::Select, "load the config / apply the field"),
+                Fixed("j/J", "queue a backtest/validate of the selected config (list)"),
+                Fixed("g/F5", "refresh the config list"),
+                Fixed("F2", "open the full config editor"),
+                Act(Action::Back, "back to the menu"),
+            ],
+        ),
+        ViewId::ConfigEditor => (
+            "Config editor",
+            "Every config field, validated as you type; apply to the session or save to a file.",
+            &[
+                Act(Action::Up, "previous field"),
+                Act(Action::Down, "next field"),
+                Act(Action::PageUp, "previous section"),
+                Act(Action::PageDown, "next section"),
+                Act(Action::Select, "edit the field (Enter again commits)"),
+                Fixed("a", "apply to the session without saving"),
+                Fixed("w", "save to a path"),
+                Act(Action::Back, "cancel the edit / back to Setup"),
+            ],
+        ),
+        ViewId::Backtest => (
+            "Backtest",
+            "Validate, backtest or paper-trade the loaded config; runs open the Monitor.",
+            &[
+                Act(Action::Left, "previous tab (Validate/Backtest/Paper)"),
+                Act(Action::Right, "next tab"),
+                Act(Action::Run, "run the selected tab"),
+                Fixed("s", "strict limits (Validate)"),
+                Fixed("v", "require a passing validate before runs"),
+                Fixed("d", "data-quality report of the last validate"),
+                Fixed("t", "realtime / replay (Paper)"),
+                Fixed("s", "realtime stream: exchange / simulator (Paper)"),
+                Fixed("u", "resume the saved session.json (Paper)"),
+                Act(Action::Pause, "pause/resume the run"),
+                Act(Action::Step, "one bar while paused"),
+                Fixed("+/-", "replay speed x2 / x0.5 (paper replay)"),
+                Fixed("h", "change the strategy at the next bar (running paper session)"),
+                Act(Action::Cancel, "cancel the running task"),
+                Act(Action::PageUp, "scroll the log back"),
+                Act(Action::PageDown, "scroll the log forward"),
+                Act(Action::Back, "back to the menu"),
+            ],
+        ),
+        ViewId::Monitor => (
+            "Monitor",
+            "Price/equity charts, risk limit gauges, trades, position and log of the running task or followed run.",
+            &[
+                Act(Action::Up, "scroll the trades back"),
+                Act(Action::Down, "scroll the trades forward"),
+                Act(Action::Pause, "pause/resume the run"),
+                Act(Action::Step, "one bar while paused"),
+                Fixed("+/-", "replay speed x2 / x0.5 (paper replay)"),
+                Fixed("h", "change the strategy at the next bar (running paper session)"),
+                Act(Action::Cancel, "cancel the task (or stop following)"),
+                Act(Action::Run, "run the Backtest tab again"),
+                Act(Action::ExportCsv, "export the trades to CSV"),
+                Act(Action::ExportJson, "export the trades to JSON"),
+                Act(Action::PageUp, "scroll the log back"),
+                Act(Action::PageDown, "scroll the log forward"),
+                Act(Action::Back, "back to the menu"),
+            ],
+        ),
+        ViewId::Reports => (
+            "Reports",
+            "Analyzer reports of finished runs: run, then analyzer, then its text.",
+            &[
+                Act(Action::Up, "previous run/analyzer, or scroll"),
+                Act(Action::Down, "next run/analyzer, or scroll"),
+                Act(Action::Select, "open the run's analyzers / the analyzer"),
+                Act(Action::PageUp, "scroll back"),
+                Act(Action::PageDown, "scroll forward"),
+                Fixed("g/F5", "refresh the runs"),
+                Act(Action::Back, "back one level"),
+            ],
+        ),
+        ViewId::Runs => (
+            "Runs",
+            "Every run in the registry with its metrics; open, chart, follow, export or delete one.",
+            &[
+                Act(Action::Up, "previous run"),
+                Act(Action::Down, "next run"),
+                Act(Action::Select, "show/hide the details"),
+                Fixed("s", "cycle the sort"),
+                Fixed("c", "candlestick chart with the trades"),
+                Fixed("u", "equity and underwater curves"),
+                Fixed("t", "trade list with filters and aggregations"),
+                Fixed("m", "mark the run as the base of a config diff"),
+                Fixed("v", "diff the config with the marked run (or the loaded config)"),
+                Fixed("f", "follow a paper/live run in progress"),
+                Fixed("r", "regenerate the report"),
+                Fixed("d", "delete the run (y confirms)"),
+                Fixed("g/F5", "refresh the list"),
+                Act(Action::ExportCsv, "export the table to CSV"),
+                Act(Action::ExportJson, "export the table to JSON"),
+                Act(Action::Back, "close the details / back to the menu"),
+            ],
+        ),
+        ViewId::Chart => (
+            "Chart",
+            "Candlesticks of the selected run with its entries and exits.",
+            &[
+                Fixed("+/-", "zoom in / out"),
+                Act(Action::Left, "pan left (also h)"),
+                Act(Action::Right, "pan right (also l)"),
+                Act(Action::PageUp, "pan left faster"),
+                Act(Action::PageDown, "pan right faster"),
+                Fixed("Home/End", "first / last bars"),
+                Act(Action::Back, "back to Runs (also q)"),
+            ],
+        ),
+        ViewId::Equity => (
+            "Equity",
+            "Equity and underwater (drawdown) curves of a finished run, with a crosshair, and its stage timings.",
+            &[
+                Act(Action::Left, "crosshair one point back (also h)"),
+                Act(Action::Right, "crosshair one point forward (also l)"),
+                Act(Action::PageUp, "a tenth of the run back"),
+                Act(Action::PageDown, "a tenth of the run forward"),
+                Fixed("Home/End", "first / last point"),
+                Act(Action::Back, "back to where it opened from (also q)"),
+            ],
+        ),
+        ViewId::Trades => (
+            "Trades",
+            "The trades of a run with their realized PnL, filtered by side, time range and minimum PnL, and grouped by hour, reason or strategy.",
+            &[
+                Act(Action::Up, "previous trade"),
+                Act(Action::Down, "next trade"),
+                Act(Action::PageUp, "ten trades up"),
+                Act(Action::PageDown, "ten trades down"),
+                Fixed("Home/End", "first / last trade"),
+                Fixed("/", "edit the filter (side=sell, from=2024-01-01, to=..., min_pnl=0)"),
+                Fixed("a", "group by none / hour / reason / strategy_id"),
+                Act(Action::Back, "back to Runs (also q)"),
+            ],
+        ),
+        ViewId::ConfigDiff => (
+            "Config diff",
+            "Line diff of two config snapshots (normalized TOML), to tie metric changes to parameter edits.",
+            &[
+                Act(Action::Up, "scroll up"),
+                Act(Action::Down, "scroll down"),
+                Act(Action::PageUp, "page up"),
+                Act(Action::PageDown, "page down"),
+                Fixed("Home/End", "top / bottom"),
+                Fixed("n/p", "next / previous change"),
+                Act(Action::Back, "back to Runs (also q)"),
+            ],
+        ),
+        ViewId::Experiments => (
+            "Experiments",
+            "Parameter sweeps from a sweep TOML, trials run in parallel.",
+            &[
+                Fixed("Tab", "sweep path / parallelism"),
+                Act(Action::Run, "start the sweep (also Enter)"),
+                Fixed("v", "resume the previous sweep"),
+                Fixed("F2", "open the leaderboard"),
+                Fixed("F3", "explore past sweeps"),
+                Act(Action::Cancel, "cancel the sweep"),
+                Act(Action::Back, "back to the menu"),
+            ],
+        ),
+        ViewId::Leaderboard => (
+            "Leaderboard",
+            "Sweep trials ranked by the selected metric.",
+            &[
+                Act(Action::Up, "previous trial"),
+                Act(Action::Down, "next trial"),
+                Fixed("s", "cycle the ranking metric"),
+                Act(Action::Select, "open the trial's run"),
+                Fixed("b", "open the best trial"),
+                Act(Action::Cancel, "cancel the sweep"),
+                Act(Action::ExportCsv, "export the table to CSV"),
+                Act(Action::ExportJson, "export the table to JSON"),
+                Act(Action::Back, "back to Experiments"),
+            ],
+        ),
+        ViewId::SweepHistory => (
+            "Past sweeps",
+            "Finished sweeps of the out dir: filter trials by parameter and plot a metric against one.",
+            &[
+                Act(Action::Up, "previous sweep/trial"),
+                Act(Action::Down, "next sweep/trial"),
+                Act(Action::Select, "open the sweep / the trial's run"),
+                Act(Action::Left, "previous parameter on the x axis"),
+                Act(Action::Right, "next parameter on the x axis"),
+                Fixed("s", "cycle the metric (sharpe/net profit/drawdown)"),
+                Fixed("/", "edit the filters, e.g. sma_fast>=10 (Enter applies)"),
+                Fixed("g/F5", "refresh the sweeps"),
+                Act(Action::Back, "back to the sweeps / Experiments"),
+            ],
+        ),
+        ViewId::Ingest => (
+            "Ingest",
+            "Download KuCoin candles into the db.url of the loaded config.",
+            &[
+                Act(Action::Up, "previous field"),
+                Act(Action::Down, "next field"),
+                Act(Action::Select, "edit the field (Enter/Tab/Esc finish)"),
+                Fixed("c", "fill the fields from the loaded config"),
+                Act(Action::Run, "start the ingest"),
+                Act(Action::Cancel, "cancel it (written windows stay)"),
+                Act(Action::Back, "back to the menu"),
+            ],
+        ),
+        ViewId::Jobs => (
+            "Jobs",
+            "A validate/backtest queue of any config, running next to the Backtest/Paper task.",
+            &[
+                Act(Action::Up, "previous job"),
+                Act(Action::Down, "next job"),
+                Fixed("b/v", "queue a backtest/validate of the loaded config"),
+                Fixed("+/-", "jobs running at once (1-4)"),
+                Act(Action::Cancel, "cancel the selected job"),
+                Fixed("r", "queue the selected job again"),
+                Fixed("c", "clear finished jobs"),
+                Act(Action::Back, "back to the menu"),
+            ],
+        ),
+        ViewId::Schedule => (
+            "Schedule",
+            "Daily runs (UTC) queued as jobs while the TUI is open, saved in schedule.toml.",
+            &[
+                Act(Action::Up, "previous entry"),
+                Act(Action::Down, "next entry"),
+                Fixed("a", "schedule the loaded config"),
+                Act(Action::Left, "15 minutes earlier"),
+                Act(Action::Right, "15 minutes later"),
+                Fixed("v", "validate / backtest"),
+                Fixed("i", "ingest the config's symbol first"),
+                Fixed("space", "enable/disable the entry"),
+                Fixed("r", "run it now"),
+                Fixed("d", "delete the entry"),
+                Fixed("g", "reload schedule.toml"),
+                Act(Action::Back, "back to the menu"),
+            ],
+        ),
+        ViewId::DataQuality => (
+            "Data quality",
+            "OHLCV gaps and data counts from the last validate, with its PASS/FAIL badge.",
+            &[
+                Act(Action::Up, "previous gap"),
+                Act(Action::Down, "next gap"),
+                Fixed("b", "backfill the selected gap (Ingest)"),
+                Fixed("a", "backfill every gap"),
+                Act(Action::Back, "back to Backtest"),
+            ],
+        ),
+        ViewId::Metrics => (
+            "Metrics",
+            "The process metrics /metrics exports, sampled every second, and the exporter's listener.",
+            &[
+                Act(Action::Up, "scroll up"),
+                Act(Action::Down, "scroll down"),
+                Act(Action::PageUp, "scroll up a page"),
+                Act(Action::PageDown, "scroll down a page"),
+                Fixed("x", "start / stop the HTTP exporter"),
+                Fixed("a", "edit the bind address (Enter starts or moves the exporter)"),
+                Act(Action::Back, "back to the menu"),
+            ],
+        ),
+        ViewId::Onboarding => (
+            "Welcome",
+            "First launch: check the database, migrate, ingest a sample and write a starter config.",
+            &[
+                Act(Action::Up, "previous field"),
+                Act(Action::Down, "next field"),
+                Act(Action::Select, "edit the field (Enter/Tab/Esc finish)"),
+                Fixed("c", "check the database connection"),
+                Fixed("m", "run the migrations"),
+                Fixed("s", "ingest the last 7 days of the symbol"),
+                Act(Action::Cancel, "cancel the sample ingest"),
+                Fixed("w", "write the starter config, load it and open Backtest"),
+                Act(Action::Back, "skip to the menu"),
+            ],
+        ),
+    };
+    ViewHelp { title, about, keys }
+}
+
+// (keys, description) for the overlay: the view's keys, then the global ones.
+pub fn help_rows(keymap: &Keymap, view: ViewId) -> Vec<(String, &'static str)> {
+    view_help(view)
+        .keys
+        .iter()
+        .chain(GLOBAL_KEYS)
+        .map(|key| match *key {
+            HelpKey::Action(action, help) => (keymap.label(action), help),
+            HelpKey::Fixed(keys, help) => (keys.to_string(), help),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::help_rows;
+    use crate::app::ViewId;
+    use crate::keymap::Keymap;
+
+    #[test]
+    fn rows_follow_the_view_and_the_active_keymap() {
+        let keymap = Keymap::from_toml_str("[keys]\ncancel = [\"ctrl-x\"]\n").expect("keymap");
+        let jobs = help_rows(&keymap, ViewId::Jobs);
+        assert!(jobs.contains(&("Ctrl-x".to_string(), "cancel the selected job")));
+        assert!(jobs.contains(&(
+            "b/v".to_string(),
+            "queue a backtest/validate of the loaded config"
+        )));
+        assert_eq!(
+            jobs.last(),
+            Some(&("Ctrl-c".to_string(), "quit from any view"))
+        );
+
+        let metrics = help_rows(&Keymap::defaults(), ViewId::Metrics);
+        assert!(metrics.iter().all(|(_, help)| !help.contains("cancel")));
+        assert!(metrics.contains(&("?/F1".to_string(), "show/hide this help")));
+    }
+}
@@ -0,0 +1,504 @@
+use crate::output::CliError;
+use kairos_application::config::Config;
+use kairos_domain::services::progress::ProgressSink;
+use kairos_domain::services::synthetic::{generate_bars, Regime, SyntheticSpec};
+use kairos_domain::value_objects::timeframe::Timeframe;
+use kairos_ingest::{ingest_kucoin, migrate_db, upsert_bars, InsertMode, Market};
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_MIGRATIONS_PATH: &str = "platform/ops/migrations";
+pub const DEFAULT_SEED_SYMBOL: &str = "TEST";
+const CANCEL_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(200);
+
+// `kairos-alloy ingest ...`: the kairos-ingest commands, with connection/market defaults taken from a
+// backtest config (`--config` or KAIROS_CONFIG) so the same TOML drives ingestion and runs.
+pub enum IngestCommand {
+    Migrate {
+        config_path: Option<PathBuf>,
+        db_url: Option<String>,
+        migrations_path: PathBuf,
+    },
+    Kucoin(KucoinIngestArgs),
+}
+
+#[derive(Debug, Clone)]
+pub struct KucoinIngestArgs {
+    pub config_path: Option<PathBuf>,
+    pub db_url: Option<String>,
+    pub symbol: Option<String>,
+    pub market: Option<Market>,
+    pub timeframe: Option<String>,
+    pub start: String,
+    pub end: Option<String>,
+    pub exchange: Option<String>,
+    pub source: String,
+    pub sleep_ms: u64,
+    pub batch_size: usize,
+    pub insert_mode: InsertMode,
+    pub base_url: Option<String>,
+    // Continue after the series' `ingest_checkpoints` entry (see kairos-ingest `--resume`).
+    pub resume: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct SeedArgs {
+    pub config_path: Option<PathBuf>,
+    pub db_url: Option<String>,
+    pub symbol: Option<String>,
+    pub market: Option<Market>,
+    pub timeframe: Option<String>,
+    pub exchange: Option<String>,
+    pub bars: usize,
+    pub regime: Regime,
+    // Epoch seconds or RFC3339.
+    pub start: String,
+    pub start_price: f64,
+    pub seed: u64,
+    // Defaults to the regime's gap rate (non-zero only for `gappy`).
+    pub gap_rate: Option<f64>,
+    pub duplicate_rate: f64,
+    pub source: String,
+    pub batch_size: usize,
+    pub insert_mode: InsertMode,
+}
+
+#[derive(Debug, Clone)]
+pub struct SeedReport {
+    pub symbol: String,
+    pub exchange: String,
+    pub market: Market,
+    pub timeframe: String,
+    pub regime: Regime,
+    pub seed: u64,
+    pub bars: usize,
+    pub gaps: usize,
+    pub duplicates: usize,
+    pub rows_written: u64,
+    pub first_ts: Option<i64>,
+    pub last_ts: Option<i64>,
+}
+
+impl SeedReport {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "status": "ok",
+            "mode": "seed",
+            "symbol": self.symbol,
+            "exchange": self.exchange,
+            "market": market_label(&self.market),
+            "timeframe": self.timeframe,
+            "regime": self.regime.as_str(),
+            "seed": self.seed,
+            "bars": self.bars,
+            "gaps": self.gaps,
+            "duplicates": self.duplicates,
+            "rows_written": self.rows_written,
+            "first_ts": self.first_ts,
+            "last_ts": self.last_ts,
+        })
+    }
+
+    pub fn to_text(&self) -> String {
+        format!(
+            "seeded {} rows for {} {} ({}/{}), regime={} seed={}: {} bars, {} gaps, {} duplicates",
+            self.rows_written,
+            self.symbol,
+            self.timeframe,
+            self.exchange,
+            market_label(&self.market),
+            self.regime.as_str(),
+            self.seed,
+            self.bars,
+            self.gaps,
+            self.duplicates
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct KucoinIngestPlan {
+    db_url: String,
+    symbol: String,
+    market: Market,
+    timeframe: String,
+    exchange: String,
+}
+
+// Returns the result JSON (`mode` is `ingest_migrate` or `ingest_kucoin`).
+pub fn run_ingest(
+    command: IngestCommand,
+    progress: &mut dyn ProgressSink,
+) -> Result<serde_json::Value, CliError> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(|err| format!("unable to start async runtime: {err}"))?;
+
+    match command {
+        IngestCommand::Migrate {
+            config_path,
+            db_url,
+            migrations_path,
+        } => {
+            let config = load_optional_config(config_path.as_deref())?;
+            let db_url = resolve_db_url(db_url, config.as_ref())?;
+            runtime.block_on(migrate_db(&db_url, migrations_path.as_path()))?;
+            Ok(serde_json::json!({
+                "status": "ok",
+                "mode": "ingest_migrate",
+                "migrations_path": migrations_path.display().to_string(),
+            }))
+        }
+        IngestCommand::Kucoin(args) => {
+            let config = load_optional_config(args.config_path.as_deref())?;
+            run_kucoin_ingest(&runtime, &args, config.as_ref(), progress, None)
+        }
+    }
+}
+
+// A KuCoin ingest driven by an in-memory config (the TUI's loaded one) that stops at the next
+// await once `should_cancel` returns true; windows already upserted are kept.
+pub fn run_kucoin_ingest_with_cancel(
+    args: &KucoinIngestArgs,
+    config: Option<&Config>,
+    progress: &mut dyn ProgressSink,
+    should_cancel: &dyn Fn() -> bool,
+) -> Result<serde_json::Value, CliError> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(|err| format!("unable to start async runtime: {err}"))?;
+    run_kucoin_ingest(&runtime, args, config, progress, Some(should_cancel))
+}
+
+fn run_kucoin_ingest(
+    runtime: &tokio::runtime::Runtime,
+    args: &KucoinIngestArgs,
+    config: Option<&Config>,
+    progress: &mut dyn ProgressSink,
+    should_cancel: Option<&dyn Fn() -> bool>,
+) -> Result<serde_json::Value, CliError> {
+    let plan = plan_kucoin_ingest(args, config)?;
+    let ingest = ingest_kucoin(
+        &plan.db_url,
+        &plan.symbol,
+        plan.market.clone(),
+        &plan.timeframe,
+        &args.start,
+        args.end.as_deref(),
+        &plan.exchange,
+        &args.source,
+        args.sleep_ms,
+        args.batch_size,
+        args.insert_mode,
+        args.base_url.as_deref(),
+        args.resume,
+        progress,
+    );
+    match should_cancel {
+        Some(should_cancel) => runtime.block_on(async {
+            tokio::pin!(ingest);
+            let mut poll = tokio::time::interval(CANCEL_POLL_INTERVAL);
+            loop {
+                tokio::select! {
+                    result = &mut ingest => break result.map_err(CliError::from),
+                    _ = poll.tick() => {
+                        if should_cancel() {
+                            break Err(CliError::from("ingest cancelled"));
+                        }
+                    }
+                }
+            }
+        })?,
+        None => runtime.block_on(ingest)?,
+    }
+    Ok(serde_json::json!({
+        "status": "ok",
+        "mode": "ingest_kucoin",
+        "symbol": plan.symbol,
+        "exchange": plan.exchange,
+        "market": market_label(&plan.market),
+        "timeframe": plan.timeframe,
+        "start": args.start,
+        "end": args.end,
+    }))
+}
+
+// `kairos-alloy seed`: deterministic synthetic candles written like ingested ones, so tests and
+// demos run without exchange access. Duplicates hit the table's upsert key, so the later row wins.
+pub fn run_seed(args: SeedArgs) -> Result<SeedReport, CliError> {
+    let config = load_optional_config(args.config_path.as_deref())?;
+    let plan = plan_kucoin_ingest(
+        &KucoinIngestArgs {
+            config_path: None,
+            db_url: args.db_url.clone(),
+            symbol: args
+                .symbol
+                .clone()
+                .or_else(|| config.is_none().then(|| DEFAULT_SEED_SYMBOL.to_string())),
+            market: args.market.clone(),
+            timeframe: args.timeframe.clone(),
+            start: args.start.clone(),
+            end: None,
+            exchange: args.exchange.clone(),
+            source: args.source.clone(),
+            sleep_ms: 0,
+            batch_size: args.batch_size,
+            insert_mode: args.insert_mode,
+            base_url: None,
+            resume: false,
+        },
+        config.as_ref(),
+    )?;
+    let timeframe = Timeframe::parse(&plan.timeframe).map_err(CliError::config)?;
+    let spec = SyntheticSpec {
+        symbol: plan.symbol.clone(),
+        bars: args.bars,
+        start_ts: parse_start(&args.start).map_err(CliError::usage)?,
+        step_seconds: timeframe.step_seconds,
+        start_price: args.start_price,
+        regime: args.regime,
+        seed: args.seed,
+        gap_rate: args
+            .gap_rate
+            .unwrap_or_else(|| args.regime.default_gap_rate()),
+        duplicate_rate: args.duplicate_rate,
+    };
+    let series = generate_bars(&spec)
+        .map_err(|err| CliError::usage(format!("--{}", err.replace('_', "-"))))?;
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(|err| format!("unable to start async runtime: {err}"))?;
+    let rows_written = runtime.block_on(upsert_bars(
+        &plan.db_url,
+        &plan.exchange,
+        plan.market.clone(),
+        &plan.symbol,
+        &timeframe.label,
+        &args.source,
+        &series.bars,
+        args.batch_size,
+        args.insert_mode,
+    ))?;
+    metrics::counter!("kairos.seed.rows_total").increment(rows_written);
+
+    Ok(SeedReport {
+        symbol: plan.symbol,
+        exchange: plan.exchange,
+        market: plan.market,
+        timeframe: timeframe.label,
+        regime: args.regime,
+        seed: args.seed,
+        bars: args.bars,
+        gaps: series.gaps,
+        duplicates: series.duplicates,
+        rows_written,
+        first_ts: series.bars.first().map(|bar| bar.timestamp),
+        last_ts: series.bars.last().map(|bar| bar.timestamp),
+    })
+}
+
+pub(crate) fn parse_start(raw: &str) -> Result<i64, String> {
+    let raw = raw.trim();
+    if let Ok(ts) = raw.parse::<i64>() {
+        return Ok(ts);
+    }
+    chrono::DateTime::parse_from_rfc3339(raw)
+        .map(|dt| dt.timestamp())
+        .map_err(|err| format!("invalid --start '{raw}' (epoch seconds or RFC3339): {err}"))
+}
+
+fn market_label(market: &Market) -> &'static str {
+    match market {
+        Market::Spot => "spot",
+        Market::Futures => "futures",
+    }
+}
+
+fn load_optional_config(path: Option<&Path>) -> Result<Option<Config>, CliError> {
+    path.map(kairos_application::config::load_config)
+        .transpose()
+        .map_err(CliError::from)
+}
+
+// Flag, then `db.url`, then env KAIROS_DB_URL (same precedence as backtest/paper).
+fn resolve_db_url(flag: Option<String>, config: Option<&Config>) -> Result<String, CliError> {
+    flag.filter(|url| !url.trim().is_empty())
+        .or_else(|| {
+            config
+                .and_then(|c| c.db.url.clone())
+                .filter(|url| !url.trim().is_empty())
+        })
+        .or_else(|| {
+            std::env::var("KAIROS_DB_URL")
+                .ok()
+                .filter(|url| !url.trim().is_empty())
+        })
+        .ok_or_else(|| {
+            CliError::usage("missing --db-url (or db.url in --config, or env KAIROS_DB_URL)")
+        })
+}
+
+pub(crate) fn parse_market(market: &str) -> Result<Market, String> {
+    match market.trim().to_lowercase().as_str() {
+        "spot" => Ok(Market::Spot),
+        "futures" => Ok(Market::Futures),
+        other => Err(format!(
+            "unsupported db.market='{other}'; expected 'spot' or 'futures'"
+        )),
+    }
+}
+
+// Explicit flags win; otherwise the config's run/db sections fill in. The timeframe defaults to
+// `db.source_timeframe` (what backtests load and resample from), then `run.timeframe`.
+fn plan_kucoin_ingest(
+    args: &KucoinIngestArgs,
+    config: Option<&Config>,
+) -> Result<KucoinIngestPlan, CliError> {
+    let db_url = resolve_db_url(args.db_url.clone(), config)?;
+    let symbol = args
+        .symbol
+        .clone()
+        .or_else(|| config.map(|c| c.run.symbol.clone()))
+        .ok_or_else(|| CliError::usage("missing --symbol (or run.symbol in --config)"))?;
+    let market = match (&args.market, config) {
+        (Some(market), _) => market.clone(),
+        (None, Some(config)) => parse_market(&config.db.market).map_err(CliError::config)?,
+        (None, None) => Market::Spot,
+    };
+    let timeframe = args
+        .timeframe
+        .clone()
+        .or_else(|| {
+            config.map(|c| {
+                c.db.source_timeframe
+                    .clone()
+                    .unwrap_or_else(|| c.run.timeframe.clone())
+            })
+        })
+        .unwrap_or_else(|| "1min".to_string());
+    let exchange = args
+        .exchange
+        .clone()
+        .or_else(|| config.map(|c| c.db.exchange.clone()))
+        .unwrap_or_else(|| "kucoin".to_string());
+
+    Ok(KucoinIngestPlan {
+        db_url,
+        symbol,
+        market,
+        timeframe,
+        exchange,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{plan_kucoin_ingest, KucoinIngestArgs};
+    use crate::output::ErrorKind;
+    use kairos_application::config::Config;
+    use kairos_ingest::{InsertMode, Market};
+
+    fn args() -> KucoinIngestArgs {
+        KucoinIngestArgs {
+            config_path: None,
+            db_url: None,
+            symbol: None,
+            market: None,
+            timeframe: None,
+            start: "2024-01-01T00:00:00Z".to_string(),
+            end: None,
+            exchange: None,
+            source: "kucoin".to_string(),
+            sleep_ms: 0,
+            batch_size: 500,
+            insert_mode: InsertMode::default(),
+            base_url: None,
+            resume: false,
+        }
+    }
+
+    fn config() -> Config {
+        toml::from_str(
+            r#"
+[run]
+run_id = "ingest_cfg"
+symbol = "ETH-USDT"
+timeframe = "1hour"
+initial_capital = 1000.0
+
+[db]
+url = "postgres://from-config"
+ohlcv_table = "ohlcv_candles"
+exchange = "kucoin"
+market = "futures"
+source_timeframe = "1min"
+
+[paths]
+out_dir = "runs/"
+
+[costs]
+fee_bps = 0.0
+slippage_bps = 0.0
+
+[risk]
+max_position_qty = 1.0
+max_drawdown_pct = 1.0
+max_exposure_pct = 1.0
+
+[features]
+return_mode = "pct"
+sma_windows = [2]
+rsi_enabled = false
+sentiment_lag = "0s"
+
+[agent]
+mode = "baseline"
+url = "http://127.0.0.1:8000"
+timeout_ms = 100
+retries = 0
+fallback_action = "HOLD"
+api_version = "v1"
+feature_version = "v1"
+"#,
+        )
+        .expect("config")
+    }
+
+    #[test]
+    fn plan_fills_defaults_from_config_and_flags_win() {
+        let config = config();
+        let plan = plan_kucoin_ingest(&args(), Some(&config)).expect("plan");
+        assert_eq!(plan.db_url, "postgres://from-config");
+        assert_eq!(plan.symbol, "ETH-USDT");
+        assert!(matches!(plan.market, Market::Futures));
+        assert_eq!(plan.timeframe, "1min");
+        assert_eq!(plan.exchange, "kucoin");
+
+        let explicit = KucoinIngestArgs {
+            db_url: Some("postgres://flag".to_string()),
+            symbol: Some("BTC-USDT".to_string()),
+            market: Some(Market::Spot),
+            timeframe: Some("5min".to_string()),
+            ..args()
+        };
+        let plan = plan_kucoin_ingest(&explicit, Some(&config)).expect("plan");
+        assert_eq!(plan.db_url, "postgres://flag");
+        assert_eq!(plan.symbol, "BTC-USDT");
+        assert!(matches!(plan.market, Market::Spot));
+        assert_eq!(plan.timeframe, "5min");
+    }
+
+    #[test]
+    fn plan_without_config_requires_symbol() {
+        let no_symbol = KucoinIngestArgs {
+            db_url: Some("postgres://flag".to_string()),
+            ..args()
+        };
+        let err = plan_kucoin_ingest(&no_symbol, None).expect_err("symbol required");
+        assert_eq!(err.kind, ErrorKind::Usage);
+        assert!(err.message.contains("--symbol"));
+    }
+}
@@ -0,0 +1,234 @@
+use crate::app::TextInput;
+use crate::ingest::{parse_market, parse_start, KucoinIngestArgs};
+use kairos_application::config::Config;
+use std::collections::VecDeque;
+
+pub const FIELD_LABELS: [&str; 6] = ["exchange", "symbol", "market", "timeframe", "start", "end"];
+const MAX_LOG_LINES: usize = 200;
+
+// The TUI ingest view: a KuCoin ingest job to launch (`kairos-alloy ingest kucoin` flags, with the
+// loaded config filling what is left empty) and the progress of the one running or last run.
+pub struct IngestForm {
+    // One input per `FIELD_LABELS` entry.
+    pub inputs: Vec<TextInput>,
+    pub selected: usize,
+    pub editing: bool,
+    pub progress: IngestProgress,
+}
+
+impl IngestForm {
+    pub fn new() -> Self {
+        Self {
+            inputs: FIELD_LABELS
+                .iter()
+                .map(|_| TextInput::new(String::new()))
+                .collect(),
+            selected: 0,
+            editing: false,
+            progress: IngestProgress::default(),
+        }
+    }
+
+    // Prefills every field from the config, as `plan_kucoin_ingest` would default them.
+    pub fn fill_from_config(&mut self, config: &Config) {
+        let values = [
+            config.db.exchange.clone(),
+            config.run.symbol.clone(),
+            config.db.market.clone(),
+            config
+                .db
+                .source_timeframe
+                .clone()
+                .unwrap_or_else(|| config.run.timeframe.clone()),
+            config.run.start.clone().unwrap_or_default(),
+            config.run.end.clone().unwrap_or_default(),
+        ];
+        for (input, value) in self.inputs.iter_mut().zip(values) {
+            *input = TextInput::new(value);
+        }
+    }
+
+    // Sets start/end to a range in epoch seconds, e.g. a gap to backfill.
+    pub fn set_range(&mut self, start: i64, end: i64) {
+        for (label, value) in [("start", start), ("end", end)] {
+            if let Some(idx) = FIELD_LABELS.iter().position(|l| *l == label) {
+                self.inputs[idx] = TextInput::new(value.to_string());
+            }
+        }
+    }
+
+    pub fn selected_input(&mut self) -> &mut TextInput {
+        &mut self.inputs[self.selected]
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1).min(FIELD_LABELS.len() - 1);
+    }
+
+    fn value(&self, label: &str) -> Option<String> {
+        let idx = FIELD_LABELS.iter().position(|l| *l == label)?;
+        let value = self.inputs[idx].value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
+    // The job for `run_kucoin_ingest_with_cancel`, plus its range in epoch seconds for the
+    // progress bar (an empty end means "now", like the CLI).
+    pub fn job(&self, db_url: Option<String>) -> Result<(KucoinIngestArgs, (i64, i64)), String> {
+        let start = self
+            .value("start")
+            .ok_or_else(|| "start is required (epoch seconds or RFC3339)".to_string())?;
+        let end = self.value("end");
+        let range_start = parse_start(&start)
+            .map_err(|_| format!("invalid start '{start}' (epoch seconds or RFC3339)"))?;
+        let range_end = match end.as_deref() {
+            Some(end) => parse_start(end)
+                .map_err(|_| format!("invalid end '{end}' (epoch seconds or RFC3339)"))?,
+            None => chrono::Utc::now().timestamp(),
+        };
+        if range_end <= range_start {
+            return Err("end must be after start".to_string());
+        }
+        let market = self
+            .value("market")
+            .as_deref()
+            .map(parse_market)
+            .transpose()?;
+        let args = KucoinIngestArgs {
+            config_path: None,
+            db_url,
+            symbol: self.value("symbol"),
+            market,
+            timeframe: self.value("timeframe"),
+            start,
+            end,
+            exchange: self.value("exchange"),
+            source: "kucoin".to_string(),
+            sleep_ms: 350,
+            batch_size: 500,
+            insert_mode: kairos_ingest::InsertMode::default(),
+            base_url: None,
+            resume: false,
+        };
+        Ok((args, (range_start, range_end)))
+    }
+}
+
+// What the running ingest reported so far: windows written, rows upserted and rate-limit retries,
+// with a log of recent windows/retries (newest last).
+#[derive(Debug, Clone, Default)]
+pub struct IngestProgress {
+    pub range: Option<(i64, i64)>,
+    pub windows: u64,
+    pub last_window: Option<(i64, i64, u64)>,
+    pub total_rows: u64,
+    pub retries: u64,
+    pub log: VecDeque<String>,
+}
+
+impl IngestProgress {
+    pub fn start(range: (i64, i64)) -> Self {
+        Self {
+            range: Some(range),
+            ..Self::default()
+        }
+    }
+
+    pub fn record_window(&mut self, first_ts: i64, last_ts: i64, rows: u64, total_rows: u64) {
+        self.windows += 1;
+        self.last_window = Some((first_ts, last_ts, rows));
+        self.total_rows = total_rows;
+        self.push_log(format!(
+            "window {} {}..{} candles={} total_upserts={}",
+            self.windows,
+            format_ts(first_ts),
+            format_ts(last_ts),
+            rows,
+            total_rows
+        ));
+    }
+
+    pub fn record_retry(&mut self, attempt: u32, delay_ms: u64, reason: &str) {
+        self.retries += 1;
+        self.push_log(format!(
+            "retry {attempt} of window {} in {delay_ms}ms: {reason}",
+            self.windows + 1
+        ));
+    }
+
+    fn push_log(&mut self, line: String) {
+        self.log.push_back(line);
+        while self.log.len() > MAX_LOG_LINES {
+            self.log.pop_front();
+        }
+    }
+
+    // Share of the range covered by the windows written so far (0.0..=1.0).
+    pub fn fraction(&self) -> f64 {
+        match (self.range, self.last_window) {
+            (Some((start, end)), Some((_, last_ts, _))) if end > start => {
+                ((last_ts - start) as f64 / (end - start) as f64).clamp(0.0, 1.0)
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+pub fn format_ts(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%SZ").to_string())
+        .unwrap_or_else(|| ts.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IngestForm, IngestProgress};
+    use crate::app::TextInput;
+    use kairos_ingest::Market;
+
+    #[test]
+    fn job_keeps_empty_fields_for_the_config_and_checks_the_range() {
+        let mut form = IngestForm::new();
+        assert!(form.job(None).unwrap_err().contains("start is required"));
+
+        form.inputs[1] = TextInput::new("ETH-USDT".to_string());
+        form.inputs[2] = TextInput::new("Futures".to_string());
+        form.inputs[4] = TextInput::new("2024-01-01T00:00:00Z".to_string());
+        form.inputs[5] = TextInput::new("1704067200".to_string());
+        assert_eq!(form.job(None).unwrap_err(), "end must be after start");
+
+        form.inputs[5] = TextInput::new("2024-01-02T00:00:00Z".to_string());
+        let (args, range) = form.job(Some("postgres://db".to_string())).expect("job");
+        assert_eq!(range, (1_704_067_200, 1_704_153_600));
+        assert_eq!(args.symbol.as_deref(), Some("ETH-USDT"));
+        assert!(matches!(args.market, Some(Market::Futures)));
+        assert_eq!(args.exchange, None);
+        assert_eq!(args.timeframe, None);
+        assert_eq!(args.db_url.as_deref(), Some("postgres://db"));
+
+        form.inputs[2] = TextInput::new("margin".to_string());
+        assert!(form.job(None).unwrap_err().contains("margin"));
+    }
+
+    #[test]
+    fn progress_tracks_windows_retries_and_coverage() {
+        let mut progress = IngestProgress::start((0, 1_000));
+        progress.record_retry(1, 500, "rate limited (HTTP 429)");
+        progress.record_window(0, 250, 10, 10);
+        progress.record_window(260, 500, 8, 18);
+        assert_eq!((progress.windows, progress.retries), (2, 1));
+        assert_eq!(progress.total_rows, 18);
+        assert_eq!(progress.fraction(), 0.5);
+        let log: Vec<&str> = progress.log.iter().map(String::as_str).collect();
+        assert_eq!(
+            log[..2],
+            [
+                "retry 1 of window 1 in 500ms: rate limited (HTTP 429)",
+                "window 1 1970-01-01 00:00:00Z..1970-01-01 00:04:10Z candles=10 total_upserts=10",
+            ]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use kairos_application::config::init::{parse_agent_mode, render_config, InitAnswers};
+use kairos_application::config::AgentMode;
+use kairos_domain::value_objects::timeframe::Timeframe;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+pub struct InitOptions {
+    pub out: PathBuf,
+    pub force: bool,
+    // Skip the questions and write the defaults.
+    pub defaults: bool,
+}
+
+// `kairos-alloy init`: asks a handful of questions and writes a commented config TOML.
+// Empty answers keep the default shown in brackets; invalid answers are asked again.
+pub fn run_init(
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+    opts: &InitOptions,
+) -> Result<PathBuf, String> {
+    if opts.out.exists() && !opts.force {
+        return Err(format!(
+            "{} already exists (use --force to overwrite)",
+            opts.out.display()
+        ));
+    }
+
+    let answers = if opts.defaults {
+        InitAnswers::default()
+    } else {
+        ask_answers(input, output)?
+    };
+    let rendered = render_config(&answers)?;
+    write_config(&opts.out, &rendered)?;
+    writeln!(
+        output,
+        "wrote {}\nnext: kairos-alloy --headless --mode validate --config {}",
+        opts.out.display(),
+        opts.out.display()
+    )
+    .map_err(|err| format!("failed to write prompt: {err}"))?;
+    Ok(opts.out.clone())
+}
+
+fn ask_answers(input: &mut dyn BufRead, output: &mut dyn Write) -> Result<InitAnswers, String> {
+    let defaults = InitAnswers::default();
+
+    let symbol = ask(input, output, "Symbol", &defaults.symbol, |raw| {
+        let symbol = raw.trim().to_uppercase();
+        if symbol.contains('-') {
+            Ok(symbol)
+        } else {
+            Err("expected BASE-QUOTE, e.g. BTC-USDT".to_string())
+        }
+    })?;
+    let timeframe = ask(
+        input,
+        output,
+        "Timeframe (1min|5min|15min|1hour|4hour|1day)",
+        &defaults.timeframe,
+        |raw| Timeframe::parse(raw).map(|tf| tf.label),
+    )?;
+    let db_url = ask(
+        input,
+        output,
+        "DB URL (empty = use env KAIROS_DB_URL)",
+        "",
+        |raw| Ok(raw.trim().to_string()),
+    )?;
+    let agent_mode = ask(
+        input,
+        output,
+        "Agent mode (baseline|remote|hold)",
+        "baseline",
+        parse_agent_mode,
+    )?;
+    let agent_url = if matches!(agent_mode, AgentMode::Remote) {
+        ask(input, output, "Agent URL", &defaults.agent_url, |raw| {
+            let url = raw.trim();
+            if url.starts_with("http://") || url.starts_with("https://") {
+                Ok(url.to_string())
+            } else {
+                Err("expected an http(s) URL".to_string())
+            }
+        })?
+    } else {
+        defaults.agent_url.clone()
+    };
+
+    Ok(InitAnswers {
+        symbol,
+        timeframe,
+        db_url: (!db_url.is_empty()).then_some(db_url),
+        agent_mode,
+        agent_url,
+    })
+}
+
+// End of input (e.g. piped stdin) accepts the default, so `init < /dev/null` still works.
+fn ask<T>(
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+    question: &str,
+    default: &str,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<T, String> {
+    loop {
+        if default.is_empty() {
+            write!(output, "{question}: ")
+        } else {
+            write!(output, "{question} [{default}]: ")
+        }
+        .and_then(|_| output.flush())
+        .map_err(|err| format!("failed to write prompt: {err}"))?;
+
+        let mut line = String::new();
+        let read = input
+            .read_line(&mut line)
+            .map_err(|err| format!("failed to read answer: {err}"))?;
+        let answer = line.trim();
+        let answer = if answer.is_empty() { default } else { answer };
+        match parse(answer) {
+            Ok(value) => return Ok(value),
+            Err(err) if read == 0 => return Err(err),
+            Err(err) => writeln!(output, "  {err}")
+                .map_err(|err| format!("failed to write prompt: {err}"))?,
+        }
+    }
+}
+
+fn write_config(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("failed to create {}: {err}", parent.display()))?;
+    }
+    std::fs::write(path, contents)
+        .map_err(|err| format!("failed to write {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_init, InitOptions};
+    use kairos_application::config::{load_config, AgentMode};
+
+    #[test]
+    fn init_reasks_invalid_answers_and_writes_loadable_config() {
+        let dir = std::env::temp_dir().join(format!("kairos_init_{}", std::process::id()));
+        let out = dir.join("kairos.toml");
+        let opts = InitOptions {
+            out: out.clone(),
+            force: false,
+            defaults: false,
+        };
+
+        let mut input = "eth-usdt\n7min\n1h\n\nremote\nftp://x\nhttp://agent:9000\n".as_bytes();
+        let mut output = Vec::new();
+        run_init(&mut input, &mut output, &opts).expect("init");
+
+        let config = load_config(&out).expect("generated config loads");
+        assert_eq!(config.run.symbol, "ETH-USDT");
+        assert_eq!(config.run.timeframe, "1hour");
+        assert!(config.db.url.is_none());
+        assert!(matches!(config.agent.mode, AgentMode::Remote));
+        assert_eq!(config.agent.url, "http://agent:9000");
+        let prompts = String::from_utf8(output).unwrap();
+        assert!(prompts.contains("unsupported timeframe: 7min"));
+        assert!(prompts.contains("expected an http(s) URL"));
+
+        let err = run_init(&mut "".as_bytes(), &mut Vec::new(), &opts).expect_err("exists");
+        assert!(err.contains("--force"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::ingest::KucoinIngestArgs;
+use crate::jobs::JobKind;
+use std::path::PathBuf;
+use std::time::Instant;
+
+pub const MAX_CONCURRENCY: usize = 4;
+const MAX_JOB_LOG_LINES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobState {
+    Queued,
+    Running,
+    Done,
+    Failed,
+    Cancelled,
+}
+
+impl JobState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            JobState::Queued => "queued",
+            JobState::Running => "running",
+            JobState::Done => "done",
+            JobState::Failed => "failed",
+            JobState::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn is_finished(self) -> bool {
+        matches!(
+            self,
+            JobState::Done | JobState::Failed | JobState::Cancelled
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct QueuedJob {
+    pub id: usize,
+    pub kind: JobKind,
+    pub config_path: PathBuf,
+    // Ingested before the run (scheduled jobs).
+    pub ingest: Option<KucoinIngestArgs>,
+    pub state: JobState,
+    pub started_at: Option<Instant>,
+    pub elapsed_ms: Option<u64>,
+    // Last line of the result (run dir or error).
+    pub summary: Option<String>,
+    pub log: Vec<String>,
+}
+
+// The TUI jobs panel: validate/backtest runs of any config, started in queue order with at most
+// `concurrency` running at once. Jobs run next to the Backtest/Paper task, not through its slot.
+pub struct JobQueue {
+    pub jobs: Vec<QueuedJob>,
+    pub concurrency: usize,
+    pub selected: usize,
+    next_id: usize,
+}
+
+impl JobQueue {
+    pub fn new() -> Self {
+        Self {
+            jobs: Vec::new(),
+            concurrency: 1,
+            selected: 0,
+            next_id: 1,
+        }
+    }
+
+    pub fn enqueue(&mut self, kind: JobKind, config_path: PathBuf) -> usize {
+        self.enqueue_after_ingest(kind, config_path, None)
+    }
+
+    pub fn enqueue_after_ingest(
+        &mut self,
+        kind: JobKind,
+        config_path: PathBuf,
+        ingest: Option<KucoinIngestArgs>,
+    ) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.jobs.push(QueuedJob {
+            id,
+            kind,
+            config_path,
+            ingest,
+            state: JobState::Queued,
+            started_at: None,
+            elapsed_ms: None,
+            summary: None,
+            log: Vec::new(),
+        });
+        id
+    }
+
+    pub fn running(&self) -> usize {
+        self.count(JobState::Running)
+    }
+
+    pub fn count(&self, state: JobState) -> usize {
+        self.jobs.iter().filter(|job| job.state == state).count()
+    }
+
+    // Marks the next queued jobs as running, up to `concurrency`, and returns what to start.
+    pub fn take_ready(&mut self) -> Vec<QueuedJob> {
+        let free = self.concurrency.saturating_sub(self.running());
+        let mut ready = Vec::new();
+        for job in self
+            .jobs
+            .iter_mut()
+            .filter(|job| job.state == JobState::Queued)
+            .take(free)
+        {
+            job.state = JobState::Running;
+            job.started_at = Some(Instant::now());
+            ready.push(job.clone());
+        }
+        ready
+    }
+
+    pub fn push_log(&mut self, id: usize, line: String) {
+        if let Some(job) = self.job_mut(id) {
+            job.log.push(line);
+            if job.log.len() > MAX_JOB_LOG_LINES {
+                job.log.remove(0);
+            }
+        }
+    }
+
+    pub fn finish(&mut self, id: usize, result: Result<String, String>) {
+        let Some(job) = self.job_mut(id) else {
+            return;
+        };
+        job.elapsed_ms = job.started_at.map(|at| at.elapsed().as_millis() as u64);
+        let (state, text) = match result {
+            Ok(text) => (JobState::Done, text),
+            Err(err) if err.to_lowercase().contains("cancelled") => (JobState::Cancelled, err),
+            Err(err) => (JobState::Failed, err),
+        };
+        job.state = state;
+        job.summary = text.lines().last().map(str::to_string);
+        job.log.push(format!("{}: {text}", state.as_str()));
+    }
+
+    // A queued job is dropped from the queue; a running one has to be stopped by its runner, so
+    // its id is returned and it stays `running` until it reports back.
+    pub fn cancel_selected(&mut self) -> Option<usize> {
+        let job = self.jobs.get_mut(self.selected)?;
+        match job.state {
+            JobState::Queued => {
+                job.state = JobState::Cancelled;
+                job.log.push("cancelled before it started".to_string());
+                None
+            }
+            JobState::Running => Some(job.id),
+            _ => None,
+        }
+    }
+
+    // Queues a finished job again with the same kind, config and ingest.
+    pub fn retry_selected(&mut self) -> Option<usize> {
+        let job = self.jobs.get(self.selected)?;
+        if !job.state.is_finished() {
+            return None;
+        }
+        let (kind, config_path, ingest) = (job.kind, job.config_path.clone(), job.ingest.clone());
+        Some(self.enqueue_after_ingest(kind, config_path, ingest))
+    }
+
+    pub fn clear_finished(&mut self) {
+        self.jobs.retain(|job| !job.state.is_finished());
+        self.selected = self.selected.min(self.jobs.len().saturating_sub(1));
+    }
+
+    pub fn set_concurrency(&mut self, concurrency: usize) {
+        self.concurrency = concurrency.clamp(1, MAX_CONCURRENCY);
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1).min(self.jobs.len().saturating_sub(1));
+    }
+
+    pub fn selected_job(&self) -> Option<&QueuedJob> {
+        self.jobs.get(self.selected)
+    }
+
+    fn job_mut(&mut self, id: usize) -> Option<&mut QueuedJob> {
+        self.jobs.iter_mut().find(|job| job.id == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JobQueue, JobState};
+    use crate::jobs::JobKind;
+    use std::path::PathBuf;
+
+    fn states(queue: &JobQueue) -> Vec<JobState> {
+        queue.jobs.iter().map(|job| job.state).collect()
+    }
+
+    #[test]
+    fn jobs_start_in_order_within_the_concurrency_limit() {
+        let mut queue = JobQueue::new();
+        for name in ["a", "b", "c", "d"] {
+            queue.enqueue(JobKind::Backtest, PathBuf::from(format!("{name}.toml")));
+        }
+        queue.set_concurrency(2);
+        let started: Vec<usize> = queue.take_ready().iter().map(|job| job.id).collect();
+        assert_eq!(started, [1, 2]);
+        assert!(queue.take_ready().is_empty());
+
+        queue.push_log(1, "stage run_engine started".to_string());
+        queue.finish(1, Ok("backtest complete: runs/a".to_string()));
+        queue.finish(2, Err("db down".to_string()));
+        assert_eq!(
+            queue.jobs[0].summary.as_deref(),
+            Some("backtest complete: runs/a")
+        );
+        assert_eq!(queue.jobs[0].log.len(), 2);
+
+        queue.selected = 3;
+        assert_eq!(queue.cancel_selected(), None);
+        let started: Vec<usize> = queue.take_ready().iter().map(|job| job.id).collect();
+        assert_eq!(started, [3]);
+        queue.selected = 2;
+        assert_eq!(queue.cancel_selected(), Some(3));
+        queue.finish(3, Err("backtest cancelled".to_string()));
+        assert_eq!(
+            states(&queue),
+            [
+                JobState::Done,
+                JobState::Failed,
+                JobState::Cancelled,
+                JobState::Cancelled
+            ]
+        );
+
+        queue.selected = 1;
+        assert_eq!(queue.retry_selected(), Some(5));
+        queue.clear_finished();
+        assert_eq!(states(&queue), [JobState::Queued]);
+        assert_eq!(queue.jobs[0].config_path, PathBuf::from("b.toml"));
+    }
+}
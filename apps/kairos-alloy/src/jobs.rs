@@ -0,0 +1,390 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobKind {
+    Validate,
+    Backtest,
+    Sweep,
+}
+
+impl JobKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            JobKind::Validate => "validate",
+            JobKind::Backtest => "backtest",
+            JobKind::Sweep => "sweep",
+        }
+    }
+}
+
+// `[[jobs]]`: relative paths are resolved against the jobs file's directory.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JobSpec {
+    pub id: String,
+    pub kind: JobKind,
+    // validate | backtest.
+    pub config: Option<PathBuf>,
+    // sweep.
+    pub sweep_config: Option<PathBuf>,
+    // `--set`-style overrides (the sweep's base config for sweeps).
+    #[serde(default)]
+    pub set: Vec<String>,
+    // validate only.
+    #[serde(default)]
+    pub strict: bool,
+    // sweep only.
+    pub workers: Option<usize>,
+    // Jobs that must finish `ok` first; if one fails this job is skipped.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QueueConfig {
+    // Jobs running at once (default 1).
+    pub concurrency: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JobsFile {
+    #[serde(default)]
+    pub queue: QueueConfig,
+    pub jobs: Vec<JobSpec>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobStatus {
+    Ok,
+    Error,
+    // A dependency did not finish `ok`.
+    Skipped,
+}
+
+impl JobStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Ok => "ok",
+            JobStatus::Error => "error",
+            JobStatus::Skipped => "skipped",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct JobOutcome {
+    pub status: JobStatus,
+    pub elapsed_ms: u64,
+    pub result: Result<serde_json::Value, String>,
+}
+
+pub fn load_jobs_file(path: &Path) -> Result<JobsFile, String> {
+    let raw = std::fs::read_to_string(path)
+        .map_err(|err| format!("failed to read jobs config {}: {err}", path.display()))?;
+    let mut file: JobsFile = toml::from_str(&raw)
+        .map_err(|err| format!("failed to parse jobs config {}: {err}", path.display()))?;
+    let base = path.parent().unwrap_or_else(|| Path::new("."));
+    for job in &mut file.jobs {
+        for p in [&mut job.config, &mut job.sweep_config]
+            .into_iter()
+            .flatten()
+        {
+            if p.is_relative() {
+                *p = base.join(&*p);
+            }
+        }
+    }
+    validate_jobs(&file.jobs)?;
+    Ok(file)
+}
+
+fn validate_jobs(jobs: &[JobSpec]) -> Result<(), String> {
+    if jobs.is_empty() {
+        return Err("jobs config has no [[jobs]]".to_string());
+    }
+    let mut ids = BTreeSet::new();
+    for job in jobs {
+        if job.id.trim().is_empty() || !ids.insert(job.id.as_str()) {
+            return Err(format!(
+                "jobs config: job id '{}' is empty or duplicated",
+                job.id
+            ));
+        }
+        let missing = match job.kind {
+            JobKind::Validate | JobKind::Backtest => job.config.is_none().then_some("config"),
+            JobKind::Sweep => job.sweep_config.is_none().then_some("sweep_config"),
+        };
+        if let Some(field) = missing {
+            return Err(format!(
+                "jobs config: {} job '{}' needs `{field}`",
+                job.kind.as_str(),
+                job.id
+            ));
+        }
+    }
+    for job in jobs {
+        if let Some(dep) = job
+            .depends_on
+            .iter()
+            .find(|dep| !ids.contains(dep.as_str()))
+        {
+            return Err(format!(
+                "jobs config: job '{}' depends on unknown job '{dep}'",
+                job.id
+            ));
+        }
+    }
+    // Kahn's algorithm: anything left over sits on a cycle.
+    let mut done: BTreeSet<&str> = BTreeSet::new();
+    loop {
+        let ready: Vec<&str> = jobs
+            .iter()
+            .filter(|job| !done.contains(job.id.as_str()))
+            .filter(|job| job.depends_on.iter().all(|dep| done.contains(dep.as_str())))
+            .map(|job| job.id.as_str())
+            .collect();
+        if ready.is_empty() {
+            break;
+        }
+        done.extend(ready);
+    }
+    if done.len() < jobs.len() {
+        let cycle: Vec<&str> = jobs
+            .iter()
+            .map(|job| job.id.as_str())
+            .filter(|id| !done.contains(id))
+            .collect();
+        return Err(format!(
+            "jobs config: dependency cycle between {}",
+            cycle.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+// Runs every job once its dependencies finished `ok`, at most `concurrency` at a time. Ready jobs
+// start in file order. Outcomes are returned in file order.
+pub fn run_queue(
+    jobs: &[JobSpec],
+    concurrency: usize,
+    run_job: &(dyn Fn(&JobSpec) -> Result<serde_json::Value, String> + Sync),
+) -> Vec<JobOutcome> {
+    let index: BTreeMap<&str, usize> = jobs
+        .iter()
+        .enumerate()
+        .map(|(idx, job)| (job.id.as_str(), idx))
+        .collect();
+    let mut outcomes: Vec<Option<JobOutcome>> = vec![None; jobs.len()];
+    let mut started = vec![false; jobs.len()];
+    let (tx, rx) = mpsc::channel::<(usize, JobOutcome)>();
+
+    std::thread::scope(|scope| {
+        let mut running = 0;
+        loop {
+            // Skips cascade: a job whose dependency failed or was skipped is skipped as well.
+            let mut changed = true;
+            while changed {
+                changed = false;
+                for (idx, job) in jobs.iter().enumerate() {
+                    if started[idx] {
+                        continue;
+                    }
+                    let failed_dep = job.depends_on.iter().find(|dep| {
+                        outcomes[index[dep.as_str()]]
+                            .as_ref()
+                            .is_some_and(|outcome| outcome.status != JobStatus::Ok)
+                    });
+                    if let Some(dep) = failed_dep {
+                        started[idx] = true;
+                        changed = true;
+                        outcomes[idx] = Some(JobOutcome {
+                            status: JobStatus::Skipped,
+                            elapsed_ms: 0,
+                            result: Err(format!("dependency '{dep}' did not finish ok")),
+                        });
+                    }
+                }
+            }
+
+            for (idx, job) in jobs.iter().enumerate() {
+                if running >= concurrency.max(1) {
+                    break;
+                }
+                let ready = !started[idx]
+                    && job.depends_on.iter().all(|dep| {
+                        outcomes[index[dep.as_str()]]
+                            .as_ref()
+                            .is_some_and(|outcome| outcome.status == JobStatus::Ok)
+                    });
+                if !ready {
+                    continue;
+                }
+                started[idx] = true;
+                running += 1;
+                let tx = tx.clone();
+                scope.spawn(move || {
+                    let _span = tracing::info_span!("job", id = %job.id).entered();
+                    let clock = Instant::now();
+                    let result = run_job(job);
+                    metrics::counter!(
+                        "kairos.jobs.runs_total",
+                        "status" => if result.is_ok() { "ok" } else { "error" }
+                    )
+                    .increment(1);
+                    let outcome = JobOutcome {
+                        status: if result.is_ok() {
+                            JobStatus::Ok
+                        } else {
+                            JobStatus::Error
+                        },
+                        elapsed_ms: clock.elapsed().as_millis() as u64,
+                        result,
+                    };
+                    let _ = tx.send((idx, outcome));
+                });
+            }
+
+            if running == 0 {
+                break;
+            }
+            let Ok((idx, outcome)) = rx.recv() else {
+                break;
+            };
+            running -= 1;
+            outcomes[idx] = Some(outcome);
+        }
+    });
+
+    outcomes
+        .into_iter()
+        .map(|outcome| {
+            outcome.unwrap_or_else(|| JobOutcome {
+                status: JobStatus::Skipped,
+                elapsed_ms: 0,
+                result: Err("job was never started".to_string()),
+            })
+        })
+        .collect()
+}
+
+pub fn queue_json(
+    jobs: &[JobSpec],
+    concurrency: usize,
+    outcomes: &[JobOutcome],
+) -> serde_json::Value {
+    let count = |status: JobStatus| outcomes.iter().filter(|o| o.status == status).count();
+    let failed = count(JobStatus::Error) + count(JobStatus::Skipped);
+    serde_json::json!({
+        "status": if failed == 0 { "ok" } else { "failed" },
+        "mode": "batch",
+        "concurrency": concurrency,
+        "succeeded": count(JobStatus::Ok),
+        "failed": count(JobStatus::Error),
+        "skipped": count(JobStatus::Skipped),
+        "jobs": jobs
+            .iter()
+            .zip(outcomes)
+            .map(|(job, outcome)| {
+                let mut value = serde_json::json!({
+                    "id": job.id,
+                    "kind": job.kind.as_str(),
+                    "status": outcome.status.as_str(),
+                    "depends_on": job.depends_on,
+                    "elapsed_ms": outcome.elapsed_ms,
+                });
+                match &outcome.result {
+                    Ok(result) => value["result"] = result.clone(),
+                    Err(err) => value["error"] = err.clone().into(),
+                }
+                value
+            })
+            .collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_queue, validate_jobs, JobKind, JobSpec, JobStatus};
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    fn job(id: &str, depends_on: &[&str]) -> JobSpec {
+        JobSpec {
+            id: id.to_string(),
+            kind: JobKind::Backtest,
+            config: Some(PathBuf::from(format!("{id}.toml"))),
+            sweep_config: None,
+            set: Vec::new(),
+            strict: false,
+            workers: None,
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn validation_rejects_unknown_dependencies_and_cycles() {
+        assert!(validate_jobs(&[job("a", &[]), job("b", &["a"])]).is_ok());
+        let err = validate_jobs(&[job("a", &["x"])]).unwrap_err();
+        assert!(err.contains("unknown job 'x'"), "{err}");
+        let err = validate_jobs(&[job("a", &["b"]), job("b", &["a"]), job("c", &[])]).unwrap_err();
+        assert!(err.ends_with("cycle between a, b"), "{err}");
+        let mut sweep = job("s", &[]);
+        sweep.kind = JobKind::Sweep;
+        assert!(validate_jobs(&[sweep])
+            .unwrap_err()
+            .contains("sweep_config"));
+    }
+
+    #[test]
+    fn queue_respects_dependencies_concurrency_and_skips_after_failures() {
+        let jobs = vec![
+            job("a", &[]),
+            job("b", &[]),
+            job("c", &["a", "b"]),
+            job("bad", &[]),
+            job("after_bad", &["bad"]),
+            job("after_after", &["after_bad"]),
+        ];
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let order = Mutex::new(Vec::new());
+        let outcomes = run_queue(&jobs, 2, &|job: &JobSpec| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            order.lock().expect("order").push(job.id.clone());
+            running.fetch_sub(1, Ordering::SeqCst);
+            if job.id == "bad" {
+                Err("boom".to_string())
+            } else {
+                Ok(serde_json::json!({ "id": job.id }))
+            }
+        });
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        let order = order.into_inner().expect("order");
+        let position = |id: &str| order.iter().position(|o| o == id).expect("ran");
+        assert!(position("c") > position("a") && position("c") > position("b"));
+        let statuses: Vec<JobStatus> = outcomes.iter().map(|o| o.status.clone()).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                JobStatus::Ok,
+                JobStatus::Ok,
+                JobStatus::Ok,
+                JobStatus::Error,
+                JobStatus::Skipped,
+                JobStatus::Skipped
+            ]
+        );
+        assert!(!order.contains(&"after_bad".to_string()));
+    }
+}
@@ -0,0 +1,337 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+// TUI actions that can be rebound. Navigation actions are translated back to their built-in key
+// before the views see them; the rest are checked by the views that offer them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Help,
+    Quit,
+    Back,
+    Select,
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Run,
+    Cancel,
+    Pause,
+    Step,
+    Theme,
+    ExportCsv,
+    ExportJson,
+}
+
+// (action, name in keymap.toml, default keys); what each one does in a view is in `help.rs`.
+const ACTIONS: [(Action, &str, &[&str]); 17] = [
+    (Action::Help, "help", &["?", "f1"]),
+    (Action::Quit, "quit", &["q"]),
+    (Action::Back, "back", &["esc"]),
+    (Action::Select, "select", &["enter"]),
+    (Action::Up, "up", &["up"]),
+    (Action::Down, "down", &["down"]),
+    (Action::Left, "left", &["left"]),
+    (Action::Right, "right", &["right"]),
+    (Action::PageUp, "page_up", &["pgup"]),
+    (Action::PageDown, "page_down", &["pgdn"]),
+    (Action::Run, "run", &["r"]),
+    (Action::Cancel, "cancel", &["x"]),
+    (Action::Pause, "pause", &["p"]),
+    (Action::Step, "step", &["n"]),
+    (Action::Theme, "theme", &["t"]),
+    (Action::ExportCsv, "export_csv", &["e"]),
+    (Action::ExportJson, "export_json", &["E"]),
+];
+
+// What the navigation actions stand for in the views.
+const NAV_KEYS: [(Action, KeyCode); 8] = [
+    (Action::Back, KeyCode::Esc),
+    (Action::Select, KeyCode::Enter),
+    (Action::Up, KeyCode::Up),
+    (Action::Down, KeyCode::Down),
+    (Action::Left, KeyCode::Left),
+    (Action::Right, KeyCode::Right),
+    (Action::PageUp, KeyCode::PageUp),
+    (Action::PageDown, KeyCode::PageDown),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    // Accepts a character ("k", "?"), a named key ("esc", "pgdn", "f5", "space") and `ctrl-`/`alt-`
+    // prefixes ("ctrl-n").
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = raw.trim();
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            if lower.len() > 5 && lower.starts_with("ctrl-") {
+                modifiers |= KeyModifiers::CONTROL;
+                rest = &rest[5..];
+            } else if lower.len() > 4 && lower.starts_with("alt-") {
+                modifiers |= KeyModifiers::ALT;
+                rest = &rest[4..];
+            } else {
+                break;
+            }
+        }
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(ch), None) => KeyCode::Char(ch),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "esc" | "escape" => KeyCode::Esc,
+                "enter" | "return" => KeyCode::Enter,
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pgup" | "pageup" => KeyCode::PageUp,
+                "pgdn" | "pagedown" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                other => match other.strip_prefix('f').map(str::parse::<u8>) {
+                    Some(Ok(n)) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key '{raw}'")),
+                },
+            },
+        };
+        Ok(Self { code, modifiers })
+    }
+
+    // Shift is part of the character itself ('?' or 'J'), so it is ignored for characters.
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let relevant = KeyModifiers::CONTROL | KeyModifiers::ALT;
+        let modifiers = if matches!(key.code, KeyCode::Char(_)) {
+            key.modifiers & relevant
+        } else {
+            key.modifiers & (relevant | KeyModifiers::SHIFT)
+        };
+        self.code == key.code && self.modifiers == modifiers
+    }
+
+    pub fn label(&self) -> String {
+        let key = match self.code {
+            KeyCode::Char(' ') => "space".to_string(),
+            KeyCode::Char(ch) => ch.to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::Backspace => "Backspace".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::F(n) => format!("F{n}"),
+            other => format!("{other:?}"),
+        };
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl-");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt-");
+        }
+        label + &key
+    }
+}
+
+// The active bindings: the defaults, with the actions listed in `keymap.toml` replaced, e.g.
+//
+//   [keys]
+//   up = ["up", "k"]
+//   down = ["down", "j"]
+//   cancel = ["ctrl-x"]
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<Vec<KeyBinding>>,
+}
+
+impl Keymap {
+    pub fn defaults() -> Self {
+        let bindings = ACTIONS
+            .iter()
+            .map(|(_, _, keys)| {
+                keys.iter()
+                    .map(|key| KeyBinding::parse(key).expect("default key binding"))
+                    .collect()
+            })
+            .collect();
+        Self { bindings }
+    }
+
+    pub fn from_toml_str(contents: &str) -> Result<Self, String> {
+        #[derive(serde::Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct KeymapFile {
+            #[serde(default)]
+            keys: BTreeMap<String, Vec<String>>,
+        }
+
+        let file: KeymapFile = toml::from_str(contents).map_err(|err| err.to_string())?;
+        let mut keymap = Self::defaults();
+        for (name, keys) in file.keys {
+            let idx = ACTIONS
+                .iter()
+                .position(|(_, action_name, _)| *action_name == name)
+                .ok_or_else(|| format!("unknown action '{name}'"))?;
+            if keys.is_empty() {
+                return Err(format!("action '{name}' needs at least one key"));
+            }
+            keymap.bindings[idx] = keys
+                .iter()
+                .map(|key| KeyBinding::parse(key))
+                .collect::<Result<_, _>>()
+                .map_err(|err| format!("{name}: {err}"))?;
+        }
+        keymap.check_conflicts()?;
+        Ok(keymap)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::defaults());
+        }
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        Self::from_toml_str(&contents)
+            .map_err(|err| format!("invalid keymap {}: {err}", path.display()))
+    }
+
+    fn check_conflicts(&self) -> Result<(), String> {
+        for (idx, keys) in self.bindings.iter().enumerate() {
+            for key in keys {
+                let other = self.bindings[idx + 1..]
+                    .iter()
+                    .position(|later| later.contains(key));
+                if let Some(offset) = other {
+                    return Err(format!(
+                        "key '{}' is bound to both '{}' and '{}'",
+                        key.label(),
+                        ACTIONS[idx].1,
+                        ACTIONS[idx + 1 + offset].1
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn keys(&self, action: Action) -> &[KeyBinding] {
+        let idx = ACTIONS
+            .iter()
+            .position(|(a, _, _)| *a == action)
+            .expect("every action is listed");
+        &self.bindings[idx]
+    }
+
+    pub fn matches(&self, action: Action, key: &KeyEvent) -> bool {
+        self.keys(action).iter().any(|binding| binding.matches(key))
+    }
+
+    // Maps a bound navigation key to the key the views handle (so `k` can act as ↑); a built-in
+    // navigation key that was rebound away is dropped (None). Other keys pass through.
+    pub fn translate(&self, key: KeyEvent) -> Option<KeyEvent> {
+        for (action, code) in NAV_KEYS {
+            if self.matches(action, &key) {
+                return Some(KeyEvent::new(code, KeyModifiers::NONE));
+            }
+        }
+        let bound = ACTIONS
+            .iter()
+            .any(|(action, ..)| self.matches(*action, &key));
+        if !bound && key.modifiers.is_empty() && NAV_KEYS.iter().any(|(_, code)| *code == key.code)
+        {
+            return None;
+        }
+        Some(key)
+    }
+
+    pub fn label(&self, action: Action) -> String {
+        self.keys(action)
+            .iter()
+            .map(KeyBinding::label)
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Action, KeyBinding, Keymap};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn keymap_file_replaces_the_listed_actions_only() {
+        let keymap = Keymap::from_toml_str(
+            r#"
+            [keys]
+            up = ["up", "k"]
+            down = ["j"]
+            cancel = ["ctrl-x"]
+            "#,
+        )
+        .expect("keymap");
+
+        assert_eq!(
+            keymap.translate(key(KeyCode::Char('k'))),
+            Some(key(KeyCode::Up))
+        );
+        assert_eq!(keymap.translate(key(KeyCode::Up)), Some(key(KeyCode::Up)));
+        assert_eq!(
+            keymap.translate(key(KeyCode::Char('j'))),
+            Some(key(KeyCode::Down))
+        );
+        assert_eq!(keymap.translate(key(KeyCode::Down)), None);
+        assert_eq!(
+            keymap.translate(key(KeyCode::Char('r'))),
+            Some(key(KeyCode::Char('r')))
+        );
+
+        let ctrl_x = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL);
+        assert!(keymap.matches(Action::Cancel, &ctrl_x));
+        assert!(!keymap.matches(Action::Cancel, &key(KeyCode::Char('x'))));
+        assert!(keymap.matches(Action::Run, &key(KeyCode::Char('r'))));
+        let question = KeyEvent::new(KeyCode::Char('?'), KeyModifiers::SHIFT);
+        assert!(keymap.matches(Action::Help, &question));
+
+        assert_eq!(keymap.label(Action::Up), "↑/k");
+        assert_eq!(keymap.label(Action::Cancel), "Ctrl-x");
+    }
+
+    #[test]
+    fn keymap_file_errors_are_explicit() {
+        let err = Keymap::from_toml_str("[keys]\nzoom = [\"z\"]\n").unwrap_err();
+        assert!(err.contains("unknown action 'zoom'"));
+        let err = Keymap::from_toml_str("[keys]\nrun = [\"hyper-r\"]\n").unwrap_err();
+        assert!(err.contains("run: unknown key 'hyper-r'"));
+        let err = Keymap::from_toml_str("[keys]\nstep = [\"r\"]\n").unwrap_err();
+        assert!(err.contains("bound to both 'run' and 'step'"));
+        assert_eq!(
+            KeyBinding::parse("F5").expect("f5"),
+            KeyBinding {
+                code: KeyCode::F(5),
+                modifiers: KeyModifiers::NONE
+            }
+        );
+    }
+}
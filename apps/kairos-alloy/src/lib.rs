@@ -1,9 +1,51 @@
+mod agent_stats;
 mod app;
+pub mod batch;
 pub mod bootstrap;
+mod candle_chart;
+pub mod config_cmd;
+mod config_diff;
+mod config_form;
+mod data_quality;
+pub mod doctor;
+pub mod dry_run;
+mod equity_view;
+mod export;
+mod follow;
 pub mod headless;
+mod help;
+pub mod ingest;
+mod ingest_form;
+pub mod init;
+mod job_queue;
+pub mod jobs;
+mod keymap;
+mod live_equity;
 pub mod logging;
+pub mod metrics_exporter;
+pub mod metrics_view;
+mod notify;
+mod onboarding;
+pub mod output;
+mod positions;
+pub mod profile;
+pub mod progress;
+mod risk_status;
+mod run_browser;
+pub mod runs;
+mod schedule;
+pub mod server;
+mod settings;
+mod stage_timings;
+mod sweep_history;
+mod sweep_leaderboard;
 mod tasks;
+pub mod telemetry;
+mod theme;
+mod trade_list;
+mod tui_state;
 mod ui;
+pub mod watch;
 
 use crate::app::{App, ViewId};
 use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
@@ -23,6 +65,22 @@ pub struct TuiOpts {
     pub initial_config_path: Option<PathBuf>,
     pub log_store: Arc<parking_lot::Mutex<logging::LogStore>>,
     pub default_out_dir: PathBuf,
+    // A run dir to follow in the Monitor right away (`--follow`).
+    pub follow: Option<PathBuf>,
+    // Open the first-run setup view (see `is_first_run`).
+    pub onboarding: bool,
+}
+
+// The config the last TUI session had loaded, when it still exists: the TUI starts from it unless
+// `--config`/`KAIROS_CONFIG` names another.
+pub fn last_session_config() -> Option<PathBuf> {
+    app::last_session_config()
+}
+
+// No config named anywhere and nothing in the runs dir yet: the TUI starts with the guided setup
+// instead of the bootstrap.
+pub fn is_first_run(config_path: Option<&std::path::Path>, runs_dir: &std::path::Path) -> bool {
+    onboarding::is_first_run(config_path, runs_dir)
 }
 
 pub fn run(opts: TuiOpts) -> Result<(), String> {
@@ -74,11 +132,19 @@ async fn run_loop<B: ratatui::backend::Backend>(
         opts.log_store,
         task_runner,
     );
+    app.load_settings();
 
     if app.config_path.is_some() {
         app.try_load_config();
         app.active_view = ViewId::Setup;
     }
+    app.restore_state();
+    if opts.onboarding {
+        app.start_onboarding();
+    }
+    if let Some(run_dir) = opts.follow {
+        app.start_follow(run_dir);
+    }
 
     app.spawn_input_reader(event_tx);
 
@@ -91,6 +157,14 @@ async fn run_loop<B: ratatui::backend::Backend>(
                 .map_err(|err| format!("terminal draw failed: {err}"))?;
             app.dirty = false;
         }
+        // Bell/OSC notifications go straight to the terminal, between frames.
+        let alerts = app.take_terminal_alerts();
+        if !alerts.is_empty() {
+            use std::io::Write;
+            let mut stdout = io::stdout();
+            let _ = stdout.write_all(alerts.as_bytes());
+            let _ = stdout.flush();
+        }
 
         tokio::select! {
             _ = tick.tick() => {
@@ -98,7 +172,10 @@ async fn run_loop<B: ratatui::backend::Backend>(
             }
             maybe_event = event_rx.recv() => {
                 let Some(event) = maybe_event else { return Ok(()); };
-                if app.on_event(event)? { return Ok(()); }
+                if app.on_event(event)? {
+                    app.save_state();
+                    return Ok(());
+                }
             }
         }
     }
@@ -0,0 +1,109 @@
+// Points kept for the whole-run drawdown sparkline; past this, neighbours are merged in pairs.
+const MAX_POINTS: usize = 1024;
+
+// Equity of the running backtest/paper task as it streams in: start, peak, latest value and the
+// drawdown from the peak, over every sample of the run (the Monitor line charts only keep a
+// window of recent points).
+#[derive(Debug, Clone, Default)]
+pub struct LiveEquity {
+    pub start: Option<f64>,
+    pub last: f64,
+    pub peak: f64,
+    pub max_drawdown: f64,
+    // Drawdown from the running peak, one point per `stride` samples (the deepest of them).
+    drawdowns: Vec<f64>,
+    stride: usize,
+    pending: usize,
+}
+
+impl LiveEquity {
+    pub fn push(&mut self, equity: f64) {
+        if !equity.is_finite() {
+            return;
+        }
+        if self.start.is_none() {
+            self.start = Some(equity);
+            self.peak = equity;
+            self.stride = 1;
+        }
+        self.last = equity;
+        self.peak = self.peak.max(equity);
+        let drawdown = self.drawdown();
+        self.max_drawdown = self.max_drawdown.max(drawdown);
+
+        if self.pending == 0 {
+            self.drawdowns.push(drawdown);
+        } else if let Some(deepest) = self.drawdowns.last_mut() {
+            *deepest = deepest.max(drawdown);
+        }
+        self.pending = (self.pending + 1) % self.stride;
+        if self.pending == 0 && self.drawdowns.len() >= MAX_POINTS {
+            self.drawdowns = self
+                .drawdowns
+                .chunks(2)
+                .map(|pair| pair.iter().copied().fold(0.0, f64::max))
+                .collect();
+            self.stride *= 2;
+        }
+    }
+
+    // Fraction below the peak, e.g. 0.05 for 5% under water.
+    pub fn drawdown(&self) -> f64 {
+        if self.peak > 0.0 {
+            (self.peak - self.last) / self.peak
+        } else {
+            0.0
+        }
+    }
+
+    pub fn total_return(&self) -> Option<f64> {
+        self.start
+            .filter(|start| *start > 0.0)
+            .map(|start| self.last / start - 1.0)
+    }
+
+    // The drawdown curve squeezed into `width` columns (the deepest point of each), in basis
+    // points so it can feed a sparkline.
+    pub fn drawdown_bars(&self, width: usize) -> Vec<u64> {
+        if width == 0 || self.drawdowns.is_empty() {
+            return Vec::new();
+        }
+        let per_column = self.drawdowns.len().div_ceil(width);
+        self.drawdowns
+            .chunks(per_column)
+            .map(|chunk| {
+                let deepest = chunk.iter().copied().fold(0.0, f64::max);
+                (deepest * 10_000.0).round() as u64
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LiveEquity, MAX_POINTS};
+
+    #[test]
+    fn tracks_peak_and_drawdowns_across_the_whole_run() {
+        let mut live = LiveEquity::default();
+        for equity in [100.0, 110.0, 99.0, 105.0, 120.0, 114.0] {
+            live.push(equity);
+        }
+        assert_eq!(live.peak, 120.0);
+        assert!((live.drawdown() - 0.05).abs() < 1e-12);
+        assert!((live.max_drawdown - 0.1).abs() < 1e-12);
+        assert!((live.total_return().expect("return") - 0.14).abs() < 1e-12);
+        assert_eq!(live.drawdown_bars(3), [0, 1000, 500]);
+    }
+
+    #[test]
+    fn long_runs_are_merged_without_losing_the_deepest_drawdown() {
+        let mut live = LiveEquity::default();
+        for i in 0..(MAX_POINTS * 4) {
+            live.push(if i == 1_500 { 50.0 } else { 100.0 });
+        }
+        assert!(live.drawdowns.len() <= MAX_POINTS);
+        assert_eq!(live.drawdown_bars(1), [5_000]);
+        assert!((live.max_drawdown - 0.5).abs() < 1e-12);
+    }
+}
@@ -1,6 +1,22 @@
-use clap::{Parser, ValueEnum};
-use kairos_alloy::headless::{HeadlessArgs, HeadlessMode};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use kairos_alloy::headless::{
+    CliCommand, CpcvArgs, HeadlessArgs, HeadlessMode, IngestModeArgs, ReconcileArgs, RegistryArgs,
+    WalkForwardArgs,
+};
+use kairos_alloy::ingest::{IngestCommand, KucoinIngestArgs, SeedArgs};
+use kairos_alloy::output::{error_json, CliError, ErrorKind, EXIT_CONFIG, EXIT_OK};
+use kairos_alloy::profile::ProfileArgs;
+use kairos_alloy::progress::{ProgressArgs, ProgressFormat};
+use kairos_alloy::runs::RunsCommand;
 use kairos_alloy::{logging, TuiOpts};
+use kairos_application::backtesting::dry_run::DEFAULT_DRY_RUN_SAMPLE_BARS;
+use kairos_application::diagnostics::CheckStatus;
+use kairos_application::experiments::sweep::SweepOverrides;
+use kairos_application::reporting::{ReportOptions, ReportOutput};
+use kairos_domain::repositories::artifacts::BundleFormat;
+use kairos_domain::services::montecarlo::MonteCarloConfig;
+use kairos_domain::services::synthetic::Regime;
+use kairos_ingest::{InsertMode, Market};
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -9,11 +25,18 @@ use std::sync::Arc;
 #[command(name = "kairos-alloy")]
 #[command(about = "Kairos Alloy TUI + optional headless runner.", version)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Print exactly one JSON object on stdout (errors included) instead of human-readable text.
+    #[arg(long, global = true)]
+    json: bool,
+
     /// Run without TUI and exit after the selected mode completes.
     #[arg(long)]
     headless: bool,
 
-    /// Headless mode: validate | backtest | paper | report | sweep | cpcv
+    /// Headless mode: validate | backtest | paper | live | report | sweep | cpcv | compare | registry | annotate | walkforward | ingest | seed | batch
     #[arg(long)]
     mode: Option<Mode>,
 
@@ -21,51 +44,761 @@ struct Cli {
     #[arg(long)]
     config: Option<PathBuf>,
 
+    /// Config override applied after load, e.g. `--set features.rsi_enabled=true` (repeatable; headless only).
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    set: Vec<String>,
+
     /// Enable strict validation limits (validate mode only).
     #[arg(long)]
     strict: bool,
 
-    /// Input run directory for report regeneration (report mode only).
+    #[command(flatten)]
+    reconcile: ReconcileFlags,
+
+    /// Run directory: input for report regeneration (report mode) or the run to annotate (annotate mode).
     #[arg(long)]
     run_dir: Option<PathBuf>,
 
+    /// Keep regenerating the report from `<run-dir>/live.jsonl` while a paper run is in progress (report mode only).
+    #[arg(long)]
+    watch: bool,
+
+    /// Open the TUI Monitor on a paper/live run started elsewhere, streaming `<RUN_DIR>/live.jsonl` (TUI only).
+    #[arg(long, value_name = "RUN_DIR")]
+    follow: Option<PathBuf>,
+
+    /// Seconds between report refreshes with --watch (report mode only).
+    #[arg(long, default_value_t = 5)]
+    watch_interval_secs: u64,
+
+    /// Artifacts to regenerate, comma-separated (report mode only). Defaults to summary.json, plus
+    /// summary.html/dashboard.html when `report.html` is set.
+    #[arg(long, value_enum, value_delimiter = ',')]
+    report_outputs: Vec<ReportOutputArg>,
+
+    /// Write the regenerated artifacts to this directory instead of over `--run-dir` (report mode only).
+    #[arg(long, value_name = "DIR")]
+    report_out: Option<PathBuf>,
+
+    /// Free-form note appended to `<run-dir>/notes.jsonl` (annotate mode only).
+    #[arg(long)]
+    note: Option<String>,
+
     /// Sweep config file (sweep mode only).
     #[arg(long)]
     sweep_config: Option<PathBuf>,
 
-    /// Output path for CPCV folds CSV (cpcv mode only).
+    /// Job queue file with `[[jobs]]` validate/backtest/sweep entries (batch mode only).
+    #[arg(long)]
+    jobs: Option<PathBuf>,
+
+    /// Jobs running at once; overrides `[queue].concurrency` (batch mode only).
+    #[arg(long)]
+    jobs_concurrency: Option<usize>,
+
+    /// Acknowledge that live mode sends real orders to `[live].venue` (live mode only).
+    #[arg(long)]
+    confirm_live: bool,
+
+    /// Continue the session saved in `<run dir>/session.json` instead of starting flat (paper mode only).
+    #[arg(long)]
+    resume_session: bool,
+
+    #[command(flatten)]
+    cpcv: CpcvFlags,
+
+    #[command(flatten)]
+    walkforward: WalkForwardFlags,
+
+    #[command(flatten)]
+    ingest: IngestFlags,
+
+    #[command(flatten)]
+    seed: SeedFlags,
+
+    #[command(flatten)]
+    profile: ProfileFlags,
+
+    /// Headless progress output: text (sweep lines on stderr) | ndjson (one JSON event per stage and every N bars or sweep trial).
+    #[arg(long, value_enum, default_value = "text")]
+    progress: ProgressMode,
+
+    /// Bars between ndjson `progress` events (backtest and paper).
+    #[arg(long, default_value_t = kairos_alloy::progress::DEFAULT_PROGRESS_EVERY_BARS)]
+    progress_every: u64,
+
+    /// Write ndjson progress events to stdout, before the result line, instead of stderr.
+    #[arg(long)]
+    progress_stdout: bool,
+
+    /// Baseline run directory "A" (compare mode only).
+    #[arg(long)]
+    compare_a: Option<PathBuf>,
+
+    /// Candidate run directory "B" (compare mode only).
+    #[arg(long)]
+    compare_b: Option<PathBuf>,
+
+    /// Output directory for compare.json/compare.html (compare mode only).
+    /// Defaults to `<parent of A>/compare/<A>__vs__<B>`.
+    #[arg(long)]
+    compare_out: Option<PathBuf>,
+
+    /// Run registry SQLite file (registry mode only). Defaults to `<paths.out_dir>/registry.sqlite`.
+    #[arg(long)]
+    registry_path: Option<PathBuf>,
+
+    /// Scan `paths.out_dir` and (re)record every run directory before querying (registry mode only).
+    #[arg(long)]
+    registry_reindex: bool,
+
+    /// Filter by symbol (registry mode only).
+    #[arg(long)]
+    registry_symbol: Option<String>,
+
+    /// Filter by timeframe (registry mode only).
+    #[arg(long)]
+    registry_timeframe: Option<String>,
+
+    /// Filter by config snapshot SHA-256 (registry mode only).
+    #[arg(long)]
+    registry_config_hash: Option<String>,
+
+    /// Only runs whose data starts at/after this timestamp (epoch seconds or RFC3339) (registry mode only).
+    #[arg(long)]
+    registry_from: Option<String>,
+
+    /// Only runs whose data ends at/before this timestamp (epoch seconds or RFC3339) (registry mode only).
+    #[arg(long)]
+    registry_to: Option<String>,
+
+    /// Sort key: recorded_at | net_profit | sharpe | max_drawdown | win_rate (registry mode only).
+    #[arg(long)]
+    registry_sort: Option<String>,
+
+    /// Sort ascending instead of descending (registry mode only).
+    #[arg(long)]
+    registry_asc: bool,
+
+    /// Max number of runs to return (registry mode only).
+    #[arg(long)]
+    registry_limit: Option<usize>,
+}
+
+// Shared by `--headless --mode walkforward` and the `walkforward` subcommand.
+#[derive(Args, Debug)]
+struct WalkForwardFlags {
+    /// Bars (run timeframe) in each in-sample window (walkforward only).
+    #[arg(long)]
+    train_bars: Option<usize>,
+
+    /// Bars in each out-of-sample window (walkforward only).
+    #[arg(long)]
+    test_bars: Option<usize>,
+
+    /// Bars between window starts; must be >= --test-bars. Defaults to --test-bars (walkforward only).
+    #[arg(long)]
+    step: Option<usize>,
+
+    /// TOML with `[[params]]` (e.g. a sweep config) re-optimized on every train window (walkforward only).
+    #[arg(long)]
+    grid: Option<PathBuf>,
+
+    /// Metric used to pick the best in-sample params: net_profit | sharpe | max_drawdown | win_rate (walkforward only).
+    #[arg(long)]
+    select: Option<String>,
+}
+
+// Shared by `--headless --mode backtest|paper|sweep` and the `backtest`/`sweep` subcommands.
+#[derive(Args, Debug)]
+struct ProfileFlags {
+    /// Capture a CPU flamegraph of the whole command; defaults to `<run dir>/profile.svg` (requires feature `pprof`).
+    #[arg(long, num_args = 0..=1, value_name = "PATH")]
+    profile_svg: Option<Option<PathBuf>>,
+
+    /// Record allocation counts and peak live bytes into `<run dir>/heap_profile.json` (requires feature `heap-profile`).
+    #[arg(long)]
+    profile_heap: bool,
+}
+
+// `--headless --mode ingest`: KuCoin ingestion with defaults from `--config` (like `ingest kucoin`).
+#[derive(Args, Debug)]
+struct IngestFlags {
+    /// First candle to fetch (epoch seconds or RFC3339) (ingest only, required).
+    #[arg(long)]
+    ingest_start: Option<String>,
+
+    /// Last candle to fetch; defaults to now (ingest only).
+    #[arg(long)]
+    ingest_end: Option<String>,
+
+    /// Defaults to `run.symbol` (ingest only).
+    #[arg(long)]
+    ingest_symbol: Option<String>,
+
+    /// Defaults to `db.source_timeframe`, then `run.timeframe` (ingest only).
+    #[arg(long)]
+    ingest_timeframe: Option<String>,
+
+    /// Override KuCoin base URL (ingest only).
+    #[arg(long)]
+    ingest_base_url: Option<String>,
+
+    /// Continue after the last window a previous ingest of the series completed (ingest only).
+    #[arg(long)]
+    ingest_resume: bool,
+
+    /// Apply SQL migrations before ingesting (ingest only).
+    #[arg(long)]
+    ingest_migrate: bool,
+
+    /// Migrations directory for --ingest-migrate (ingest only).
+    #[arg(long, env = "KAIROS_MIGRATIONS_DIR", default_value = kairos_alloy::ingest::DEFAULT_MIGRATIONS_PATH)]
+    ingest_migrations_path: PathBuf,
+}
+
+// `--headless --mode validate`: a second OHLCV source (same symbol and timeframe) to compare the
+// configured one against.
+#[derive(Args, Debug)]
+struct ReconcileFlags {
+    /// Reference exchange; defaults to `db.exchange` (validate only).
+    #[arg(long)]
+    reconcile_exchange: Option<String>,
+
+    /// Reference market; defaults to `db.market` (validate only).
+    #[arg(long)]
+    reconcile_market: Option<String>,
+
+    /// Reference OHLCV table; defaults to `db.ohlcv_table` (validate only).
+    #[arg(long)]
+    reconcile_table: Option<String>,
+}
+
+// `--headless --mode seed`: the `seed` subcommand's generator, with `--seed-` prefixed flags.
+#[derive(Args, Debug)]
+struct SeedFlags {
+    /// Defaults to `run.symbol` with a config, `TEST` without one (seed only).
+    #[arg(long)]
+    seed_symbol: Option<String>,
+
+    /// Defaults to `db.source_timeframe`, then `run.timeframe` (seed only).
+    #[arg(long)]
+    seed_timeframe: Option<String>,
+
+    /// Number of bars to generate (seed only).
+    #[arg(long, default_value_t = 100_000)]
+    seed_bars: usize,
+
+    /// Price process: trending | choppy | gappy (seed only).
+    #[arg(long, value_enum, default_value = "trending")]
+    seed_regime: SeedRegime,
+
+    /// First bar timestamp (epoch seconds or RFC3339) (seed only).
+    #[arg(long, default_value = "2024-01-01T00:00:00Z")]
+    seed_start: String,
+
+    /// Price of the first bar (seed only).
+    #[arg(long, default_value_t = 100.0)]
+    seed_start_price: f64,
+
+    /// RNG seed (seed only).
+    #[arg(long, default_value_t = 42)]
+    seed_rng: u64,
+
+    /// Share of bars left out, in [0, 1). Defaults to 0.01 for `gappy`, 0 otherwise (seed only).
+    #[arg(long)]
+    seed_gap_rate: Option<f64>,
+
+    /// Share of bars sent twice, in [0, 1) (seed only).
+    #[arg(long, default_value_t = 0.0)]
+    seed_duplicate_rate: f64,
+}
+
+// Shared by `--headless --mode cpcv` and the `cpcv` subcommand.
+#[derive(Args, Debug)]
+struct CpcvFlags {
+    /// Output path for CPCV folds CSV (cpcv only).
     #[arg(long)]
     cpcv_out: Option<PathBuf>,
 
-    /// Number of contiguous groups to split the time series into (cpcv mode only).
+    /// Number of contiguous groups to split the time series into (cpcv only).
     #[arg(long, default_value_t = 6)]
     cpcv_n_groups: usize,
 
-    /// Number of groups held out for testing in each fold (cpcv mode only).
+    /// Number of groups held out for testing in each fold (cpcv only).
     #[arg(long, default_value_t = 2)]
     cpcv_k_test: usize,
 
-    /// Label/lookahead horizon (in bars) used for purge calculations (cpcv mode only).
+    /// Label/lookahead horizon (in bars) used for purge calculations (cpcv only).
     #[arg(long, default_value_t = 1)]
     cpcv_horizon_bars: usize,
 
-    /// Extra purge bars before each test segment (cpcv mode only).
+    /// Extra purge bars before each test segment (cpcv only).
     #[arg(long, default_value_t = 0)]
     cpcv_purge_bars: usize,
 
-    /// Embargo bars after each test segment (cpcv mode only).
+    /// Embargo bars after each test segment (cpcv only).
     #[arg(long, default_value_t = 0)]
     cpcv_embargo_bars: usize,
 
-    /// Optional start timestamp filter (epoch seconds or RFC3339, inclusive) (cpcv mode only).
+    /// Optional start timestamp filter (epoch seconds or RFC3339, inclusive) (cpcv only).
     #[arg(long)]
     cpcv_start: Option<String>,
 
-    /// Optional end timestamp filter (epoch seconds or RFC3339, inclusive) (cpcv mode only).
+    /// Optional end timestamp filter (epoch seconds or RFC3339, inclusive) (cpcv only).
     #[arg(long)]
     cpcv_end: Option<String>,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run a backtest (one JSON line), or keep rerunning it on config changes with --watch.
+    Backtest(BacktestCmd),
+    /// Backtest every config matching `--configs` (independent runs) and print a consolidated table.
+    Batch(BatchCmd),
+    /// Run a parameter sweep and write results.csv/leaderboard.csv/manifest.json/index.html.
+    Sweep(SweepCmd),
+    /// Generate CPCV folds (purged/embargoed train/test splits) for the config's market data.
+    Cpcv(CpcvCmd),
+    /// Walk-forward analysis: rolling train/test windows, per-window results and a stitched OOS summary.
+    Walkforward(WalkforwardCmd),
+    /// Database migrations and OHLCV ingestion (same as the kairos-ingest binary).
+    #[command(subcommand)]
+    Ingest(IngestCmd),
+    /// Write deterministic synthetic OHLCV candles into Postgres (no exchange access needed).
+    Seed(SeedCmd),
+    /// Interactive wizard that writes a commented config TOML.
+    Init(InitCmd),
+    /// Config JSON Schema export and validation.
+    #[command(subcommand)]
+    Config(ConfigCmd),
+    /// Browse and prune run directories (metrics come from each run's summary.json).
+    Runs(RunsCmd),
+    /// Bundle a run directory into a single zip archive (optionally with CSVs converted to Parquet).
+    Export(ExportCmd),
+    /// Monte Carlo block bootstrap of an existing run's equity returns and trade PnLs.
+    Montecarlo(MontecarloCmd),
+    /// Side-by-side report of two runs: metric deltas, config diff and overlaid equity curves.
+    Compare(CompareCmd),
+    /// Append a free-form note to a run's notes.jsonl (and its registry row, when there is one).
+    Annotate(AnnotateCmd),
+    /// Environment checks for a config: DB rows, agent health, out_dir, sentiment file.
+    Doctor(DoctorCmd),
+    /// REST API: submit backtest/paper jobs, poll status/progress and fetch run summaries.
+    Serve(ServeCmd),
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigCmd {
+    /// Print the config JSON Schema (for editor autocompletion / CI).
+    Schema {
+        /// Write to a file instead of stdout.
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Validate a config TOML, reporting each issue as `<file>:<line>:<col>: <field>: <message>`.
+    Check {
+        /// Config file path (TOML). If omitted, uses --config or env KAIROS_CONFIG.
+        path: Option<PathBuf>,
+        #[arg(long)]
+        config: Option<PathBuf>,
+    },
+}
+
+#[derive(Args, Debug)]
+struct InitCmd {
+    /// Where to write the config.
+    #[arg(long, default_value = "kairos.toml")]
+    out: PathBuf,
+
+    /// Overwrite an existing file.
+    #[arg(long)]
+    force: bool,
+
+    /// Skip the questions and write the defaults.
+    #[arg(long)]
+    yes: bool,
+}
+
+#[derive(Args, Debug)]
+struct RunsCmd {
+    /// Config file path (TOML) used for `paths.out_dir`. If omitted, uses env KAIROS_CONFIG.
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// Runs directory. Overrides `paths.out_dir` (defaults to `runs`).
+    #[arg(long, global = true)]
+    out_dir: Option<PathBuf>,
+
+    #[command(subcommand)]
+    action: RunsAction,
+}
+
+#[derive(Subcommand, Debug)]
+enum RunsAction {
+    /// Table of runs with their headline metrics.
+    List {
+        /// Sort key, best first: recorded_at|net_profit|sharpe|max_drawdown|win_rate.
+        #[arg(long)]
+        sort: Option<String>,
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+    /// Details for one run (directory name, path or run_id), including notes and files.
+    Show { id: String },
+    /// Delete run directories, keeping recent and/or best runs.
+    Clean {
+        /// Remove runs recorded before this age (e.g. 30d, 12h, 2w).
+        #[arg(long)]
+        older_than: Option<String>,
+        /// Never remove the best N runs by a metric (e.g. sharpe:10).
+        #[arg(long)]
+        keep_best: Option<String>,
+        /// Only print what would be removed.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum IngestCmd {
+    /// Apply SQL migrations to the target database.
+    Migrate {
+        /// Config file path (TOML) used for `db.url`. If omitted, uses env KAIROS_CONFIG.
+        #[arg(long)]
+        config: Option<PathBuf>,
+        /// Overrides `db.url` / env KAIROS_DB_URL.
+        #[arg(long)]
+        db_url: Option<String>,
+        #[arg(long, env = "KAIROS_MIGRATIONS_DIR", default_value = kairos_alloy::ingest::DEFAULT_MIGRATIONS_PATH)]
+        migrations_path: PathBuf,
+    },
+    /// Ingest KuCoin OHLCV into PostgreSQL. Unset flags default to the config's run/db settings.
+    Kucoin {
+        /// Config file path (TOML). If omitted, uses env KAIROS_CONFIG.
+        #[arg(long)]
+        config: Option<PathBuf>,
+        /// Overrides `db.url` / env KAIROS_DB_URL.
+        #[arg(long)]
+        db_url: Option<String>,
+        /// Defaults to `run.symbol`.
+        #[arg(long)]
+        symbol: Option<String>,
+        /// Defaults to `db.market` (spot without a config).
+        #[arg(long)]
+        market: Option<Market>,
+        /// Defaults to `db.source_timeframe`, then `run.timeframe` (1min without a config).
+        #[arg(long)]
+        timeframe: Option<String>,
+        #[arg(long)]
+        start: String,
+        #[arg(long)]
+        end: Option<String>,
+        /// Defaults to `db.exchange` (kucoin without a config).
+        #[arg(long)]
+        exchange: Option<String>,
+        #[arg(long, default_value = "kucoin")]
+        source: String,
+        #[arg(long, default_value_t = 350)]
+        sleep_ms: u64,
+        #[arg(long, default_value_t = 500)]
+        batch_size: usize,
+        /// copy (binary COPY plus one merge per window) or rows (one INSERT per candle).
+        #[arg(long, value_enum, default_value = "copy")]
+        insert_mode: InsertMode,
+        /// Override KuCoin base URL (useful for tests; defaults to real KuCoin endpoints).
+        #[arg(long)]
+        base_url: Option<String>,
+        /// Continue after the last window a previous ingest of the series completed.
+        #[arg(long)]
+        resume: bool,
+    },
+}
+
+#[derive(Args, Debug)]
+struct SeedCmd {
+    /// Config file path (TOML) for db/market defaults. If omitted, uses env KAIROS_CONFIG.
+    #[arg(long)]
+    config: Option<PathBuf>,
+    /// Overrides `db.url` / env KAIROS_DB_URL.
+    #[arg(long)]
+    db_url: Option<String>,
+    /// Defaults to `run.symbol` with a config, `TEST` without one.
+    #[arg(long)]
+    symbol: Option<String>,
+    /// Defaults to `db.market` (spot without a config).
+    #[arg(long)]
+    market: Option<Market>,
+    /// Defaults to `db.source_timeframe`, then `run.timeframe` (1min without a config).
+    #[arg(long)]
+    timeframe: Option<String>,
+    /// Defaults to `db.exchange` (kucoin without a config).
+    #[arg(long)]
+    exchange: Option<String>,
+    /// Number of bars to generate (before gaps/duplicates).
+    #[arg(long, default_value_t = 100_000)]
+    bars: usize,
+    /// Price process: trending | choppy | gappy.
+    #[arg(long, value_enum, default_value = "trending")]
+    regime: SeedRegime,
+    /// First bar timestamp (epoch seconds or RFC3339).
+    #[arg(long, default_value = "2024-01-01T00:00:00Z")]
+    start: String,
+    #[arg(long, default_value_t = 100.0)]
+    start_price: f64,
+    /// RNG seed; the same seed and flags write the same candles.
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+    /// Share of bars left out, in [0, 1). Defaults to 0.01 for `gappy`, 0 otherwise.
+    #[arg(long)]
+    gap_rate: Option<f64>,
+    /// Share of bars sent twice with a slightly different close, in [0, 1).
+    #[arg(long, default_value_t = 0.0)]
+    duplicate_rate: f64,
+    #[arg(long, default_value = "synthetic")]
+    source: String,
+    #[arg(long, default_value_t = 5000)]
+    batch_size: usize,
+    /// copy (binary COPY plus one merge per batch) or rows (one INSERT per candle).
+    #[arg(long, value_enum, default_value = "copy")]
+    insert_mode: InsertMode,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum ReportOutputArg {
+    /// summary.json
+    Summary,
+    /// summary.html
+    Html,
+    /// dashboard.html
+    Dashboard,
+    /// summary.md
+    Markdown,
+    /// trades.parquet + equity.parquet
+    Parquet,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum SeedRegime {
+    Trending,
+    Choppy,
+    Gappy,
+}
+
+#[derive(Args, Debug)]
+struct BacktestCmd {
+    /// Config file path (TOML). If omitted, uses env KAIROS_CONFIG.
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Config override applied after load, e.g. `--set features.rsi_enabled=true` (repeatable).
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    set: Vec<String>,
+
+    /// Rerun whenever the config or its sentiment file changes, printing metric deltas (Ctrl-C to stop).
+    #[arg(long)]
+    watch: bool,
+
+    /// How often to check for changes in watch mode.
+    #[arg(long, default_value_t = 1000)]
+    watch_interval_ms: u64,
+
+    /// Resolve the config, load a sample of bars and build features, then report what the run
+    /// would do without running the engine.
+    #[arg(long, conflicts_with = "watch")]
+    dry_run: bool,
+
+    /// With --dry-run, also health-check the remote agent (agent.mode = remote).
+    #[arg(long, requires = "dry_run")]
+    ping_agent: bool,
+
+    /// Bars (at the run timeframe) loaded by --dry-run.
+    #[arg(long, default_value_t = DEFAULT_DRY_RUN_SAMPLE_BARS, requires = "dry_run")]
+    sample_bars: u64,
+
+    #[command(flatten)]
+    profile: ProfileFlags,
+}
+
+#[derive(Args, Debug)]
+struct BatchCmd {
+    /// Config paths or file-name globs, e.g. `--configs 'configs/*.toml'` (repeatable).
+    #[arg(long, num_args = 1.., required = true)]
+    configs: Vec<String>,
+
+    /// Backtests executed in parallel.
+    #[arg(long, default_value_t = 1)]
+    parallel: usize,
+
+    /// Override applied to every config, e.g. `--set costs.fee_bps=5.0` (repeatable).
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    set: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+struct SweepCmd {
+    /// Sweep config file (TOML).
+    #[arg(long)]
+    sweep_config: PathBuf,
+
+    /// Output directory for trial runs and `sweeps/<id>/`. Defaults to the base config's `paths.out_dir`.
+    #[arg(long)]
+    out: Option<PathBuf>,
+
+    /// Worker threads running trials in parallel. Defaults to `[sweep].parallelism` (1 when unset).
+    #[arg(long, visible_alias = "parallelism")]
+    workers: Option<usize>,
+
+    /// Base config override, e.g. `--set features.rsi_enabled=true` (repeatable).
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    set: Vec<String>,
+
+    /// Resume an interrupted sweep: skip completed trials and retry failed ones.
+    #[arg(long)]
+    resume: bool,
+
+    #[command(flatten)]
+    profile: ProfileFlags,
+}
+
+#[derive(Args, Debug)]
+struct CpcvCmd {
+    /// Config file path (TOML). If omitted, uses env KAIROS_CONFIG.
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Config override applied after load, e.g. `--set features.rsi_enabled=true` (repeatable).
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    set: Vec<String>,
+
+    #[command(flatten)]
+    cpcv: CpcvFlags,
+}
+
+#[derive(Args, Debug)]
+struct ExportCmd {
+    /// Run directory to export (e.g. runs/<run_id>).
+    #[arg(long)]
+    input: PathBuf,
+
+    /// Archive layout: `zip` (artifacts as-is) or `parquet` (trades/equity CSVs converted to Parquet).
+    #[arg(long, value_enum, default_value = "zip")]
+    format: ExportFormat,
+
+    /// Archive path. Defaults to `<input>.zip` (`<input>.parquet.zip` for `--format parquet`).
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum ExportFormat {
+    Zip,
+    Parquet,
+}
+
+#[derive(Args, Debug)]
+struct ServeCmd {
+    /// Listen address.
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    addr: SocketAddr,
+
+    /// Jobs executed concurrently; further submissions wait as `queued`.
+    #[arg(long, default_value_t = 1)]
+    workers: usize,
+
+    /// Runs directory served by `GET /v1/runs/{run_id}/summary`; every job writes its run here.
+    #[arg(long, default_value = "runs")]
+    out_dir: PathBuf,
+
+    /// Agent endpoint for jobs with `agent.mode = "remote"` (their own `agent.url` is ignored).
+    #[arg(long, default_value = "http://127.0.0.1:8000")]
+    agent_url: String,
+
+    /// Bearer token required on `/v1/*`; mandatory when `--addr` is not a loopback address.
+    #[arg(long, env = "KAIROS_API_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct DoctorCmd {
+    /// Config file path (TOML). If omitted, uses env KAIROS_CONFIG.
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Config override applied after load, e.g. `--set features.rsi_enabled=true` (repeatable).
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    set: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+struct MontecarloCmd {
+    /// Run directory to resample (e.g. runs/<run_id>).
+    #[arg(long)]
+    input: PathBuf,
+
+    /// Number of resampled paths.
+    #[arg(long, default_value_t = 1000)]
+    samples: usize,
+
+    /// Block length in observations (bars for returns, closed trades for PnLs); 1 = i.i.d.
+    #[arg(long, default_value_t = 20)]
+    block: usize,
+
+    /// RNG seed; the same seed and inputs reproduce the same report.
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    /// Report path. Defaults to `<input>/analyzers/montecarlo.json` (the run manifest is refreshed).
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct CompareCmd {
+    /// Baseline run directory "A".
+    #[arg(long)]
+    a: PathBuf,
+
+    /// Candidate run directory "B".
+    #[arg(long)]
+    b: PathBuf,
+
+    /// Output directory for compare.json/compare.html. Defaults to `<parent of A>/compare/<A>__vs__<B>`.
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct AnnotateCmd {
+    /// Run directory to annotate (e.g. runs/<run_id>).
+    #[arg(long)]
+    run_dir: PathBuf,
+
+    /// Note text, appended to `<run-dir>/notes.jsonl`.
+    #[arg(long)]
+    note: String,
+}
+
+#[derive(Args, Debug)]
+struct WalkforwardCmd {
+    /// Config file path (TOML). If omitted, uses env KAIROS_CONFIG.
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Config override applied after load, e.g. `--set features.rsi_enabled=true` (repeatable).
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    set: Vec<String>,
+
+    #[command(flatten)]
+    walkforward: WalkForwardFlags,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum ProgressMode {
+    Text,
+    Ndjson,
+}
+
 #[derive(ValueEnum, Debug, Clone, Copy)]
 enum Mode {
     Validate,
@@ -74,28 +807,306 @@ enum Mode {
     Report,
     Sweep,
     Cpcv,
+    Compare,
+    Registry,
+    Annotate,
+    Walkforward,
+    Ingest,
+    Seed,
+    Batch,
+    Live,
 }
 
+#[cfg(feature = "heap-profile")]
+#[global_allocator]
+static ALLOCATOR: kairos_infrastructure::profiling::TrackingAllocator =
+    kairos_infrastructure::profiling::TrackingAllocator;
+
 fn main() {
-    let cli = Cli::parse();
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        // --help/--version keep clap's output and exit 0; usage errors get their own exit code.
+        Err(err) if !err.use_stderr() => err.exit(),
+        Err(err) => {
+            if std::env::args().any(|arg| arg == "--json") {
+                exit_with_error(true, ErrorKind::Usage, err.to_string().trim());
+            }
+            let _ = err.print();
+            exit(ErrorKind::Usage.exit_code());
+        }
+    };
+    let json = cli.json;
 
     let log_store = Arc::new(parking_lot::Mutex::new(logging::LogStore::new(5000)));
     if let Err(err) = init_tracing(log_store.clone()) {
-        eprintln!("error: {err}");
-        std::process::exit(1);
+        fail(json, err);
     }
-    if let Err(err) = init_metrics() {
-        eprintln!("error: {err}");
-        std::process::exit(1);
+    let tui = cli.command.is_none() && !cli.headless;
+    if let Err(err) = init_metrics(tui) {
+        exit_with_error(json, ErrorKind::Config, &err);
+    }
+
+    if let Some(command) = cli.command {
+        let command = match command {
+            Command::Ingest(cmd) => match kairos_alloy::ingest::run_ingest(
+                ingest_command(cmd),
+                &mut kairos_ingest::stderr_progress(),
+            ) {
+                Ok(value) => exit_ok(json, None, value),
+                Err(err) => fail(json, err),
+            },
+            Command::Seed(cmd) => match kairos_alloy::ingest::run_seed(seed_args(cmd)) {
+                Ok(report) => exit_ok(json, Some(report.to_text()), report.to_json()),
+                Err(err) => fail(json, err),
+            },
+            Command::Init(cmd) => {
+                let opts = kairos_alloy::init::InitOptions {
+                    out: cmd.out,
+                    force: cmd.force,
+                    defaults: cmd.yes,
+                };
+                let stdin = std::io::stdin();
+                // With --json the questions go to stderr so stdout only carries the result.
+                let result = if json {
+                    kairos_alloy::init::run_init(&mut stdin.lock(), &mut std::io::stderr(), &opts)
+                } else {
+                    kairos_alloy::init::run_init(&mut stdin.lock(), &mut std::io::stdout(), &opts)
+                };
+                match result {
+                    Ok(path) => exit_ok(
+                        json,
+                        None,
+                        serde_json::json!({
+                            "status": "ok",
+                            "mode": "init",
+                            "path": path.display().to_string(),
+                        }),
+                    ),
+                    Err(err) => fail(json, err),
+                }
+            }
+            Command::Config(ConfigCmd::Schema { out }) => {
+                match kairos_alloy::config_cmd::write_schema(out.as_deref()) {
+                    Ok(Some(rendered)) if json => match serde_json::from_str(&rendered) {
+                        Ok(schema) => exit_ok(json, None, schema),
+                        Err(err) => fail(json, format!("failed to serialize schema: {err}")),
+                    },
+                    Ok(rendered) => exit_ok(
+                        json,
+                        rendered,
+                        serde_json::json!({
+                            "status": "ok",
+                            "mode": "config_schema",
+                            "out": out.map(|path| path.display().to_string()),
+                        }),
+                    ),
+                    Err(err) => fail(json, err),
+                }
+            }
+            Command::Config(ConfigCmd::Check { path, config }) => {
+                let path = require_config_path(json, path.or(config));
+                match kairos_alloy::config_cmd::check_config(&path) {
+                    Ok(issues) if issues.is_empty() => exit_ok(
+                        json,
+                        Some(format!("{}: ok", path.display())),
+                        serde_json::json!({
+                            "status": "ok",
+                            "mode": "config_check",
+                            "path": path.display().to_string(),
+                            "issues": issues,
+                        }),
+                    ),
+                    Ok(issues) => {
+                        if json {
+                            let mut value = error_json(
+                                ErrorKind::Config,
+                                &format!("{} issue(s) in {}", issues.len(), path.display()),
+                            );
+                            value["mode"] = "config_check".into();
+                            value["path"] = path.display().to_string().into();
+                            value["issues"] = issues.into();
+                            println!("{value}");
+                        } else {
+                            for issue in &issues {
+                                eprintln!("{issue}");
+                            }
+                        }
+                        exit(EXIT_CONFIG);
+                    }
+                    Err(err) => fail(json, err),
+                }
+            }
+            Command::Runs(cmd) => match runs_command(cmd)
+                .and_then(|command| kairos_alloy::runs::run_runs(command).map_err(CliError::from))
+            {
+                Ok(output) => exit_ok(json, Some(output.to_text()), output.to_json()),
+                Err(err) => fail(json, err),
+            },
+            Command::Serve(cmd) => {
+                let opts = kairos_alloy::server::ServeOpts {
+                    addr: cmd.addr,
+                    workers: cmd.workers,
+                    out_dir: cmd.out_dir,
+                    agent_url: cmd.agent_url,
+                    token: cmd.token.filter(|token| !token.is_empty()),
+                };
+                let addr = opts.addr.to_string();
+                match kairos_alloy::server::serve(opts) {
+                    Ok(()) => exit_ok(
+                        json,
+                        None,
+                        serde_json::json!({"status": "ok", "mode": "serve", "addr": addr}),
+                    ),
+                    Err(err) => fail(json, err),
+                }
+            }
+            Command::Doctor(cmd) => {
+                let config_path = require_config_path(json, cmd.config);
+                match kairos_alloy::doctor::doctor(&config_path, &cmd.set) {
+                    Ok(report) => {
+                        let text = kairos_alloy::doctor::format_checklist(&config_path, &report);
+                        let value = kairos_alloy::doctor::report_json(&config_path, &report);
+                        if json {
+                            println!("{value}");
+                        } else {
+                            println!("{text}");
+                        }
+                        exit(if report.passed() { EXIT_OK } else { 1 });
+                    }
+                    Err(err) => fail(json, err),
+                }
+            }
+            Command::Batch(cmd) => {
+                let args = kairos_alloy::batch::BatchArgs {
+                    configs: cmd.configs,
+                    parallel: cmd.parallel,
+                    config_sets: cmd.set,
+                };
+                match kairos_alloy::batch::run_batch(&args) {
+                    Ok(report) => {
+                        if json {
+                            println!("{}", report.to_json());
+                        } else {
+                            println!("{}", kairos_alloy::batch::format_report(&report));
+                        }
+                        exit(if report.failed() == 0 { EXIT_OK } else { 1 });
+                    }
+                    Err(err) => fail(json, err),
+                }
+            }
+            Command::Backtest(cmd) if cmd.dry_run => {
+                let config_path = require_config_path(json, cmd.config);
+                match kairos_alloy::dry_run::dry_run(
+                    &config_path,
+                    &cmd.set,
+                    cmd.ping_agent,
+                    cmd.sample_bars,
+                ) {
+                    Ok(report) => {
+                        let text = kairos_alloy::dry_run::format_report(&report);
+                        let value = kairos_alloy::dry_run::report_json(&report);
+                        if json {
+                            println!("{value}");
+                        } else {
+                            println!("{text}");
+                        }
+                        exit(if report.agent == CheckStatus::Fail {
+                            1
+                        } else {
+                            EXIT_OK
+                        });
+                    }
+                    Err(err) => fail(json, err),
+                }
+            }
+            Command::Backtest(cmd) if cmd.watch => {
+                if profile_args(cmd.profile).enabled() {
+                    exit_with_error(
+                        json,
+                        ErrorKind::Usage,
+                        "--profile-svg/--profile-heap are not supported with --watch",
+                    );
+                }
+                let args = kairos_alloy::watch::WatchBacktestArgs {
+                    config_path: require_config_path(json, cmd.config),
+                    config_sets: cmd.set,
+                    interval: std::time::Duration::from_millis(cmd.watch_interval_ms.max(50)),
+                    json,
+                };
+                let result =
+                    kairos_alloy::watch::watch_backtest(&args, &|| false, &mut std::io::stdout());
+                if let Err(err) = result {
+                    fail(json, err);
+                }
+                exit(EXIT_OK);
+            }
+            Command::Backtest(cmd) => CliCommand::Backtest {
+                config_path: require_config_path(json, cmd.config),
+                config_sets: cmd.set,
+                profile: profile_args(cmd.profile),
+            },
+            Command::Sweep(cmd) => CliCommand::Sweep {
+                sweep_config: cmd.sweep_config,
+                overrides: SweepOverrides {
+                    out_dir: cmd.out,
+                    parallelism: cmd.workers,
+                    config_sets: cmd.set,
+                    resume: cmd.resume,
+                },
+                profile: profile_args(cmd.profile),
+            },
+            Command::Cpcv(cmd) => CliCommand::Cpcv {
+                config_path: require_config_path(json, cmd.config),
+                config_sets: cmd.set,
+                cpcv: cpcv_args(cmd.cpcv),
+            },
+            Command::Walkforward(cmd) => CliCommand::Walkforward {
+                config_path: require_config_path(json, cmd.config),
+                config_sets: cmd.set,
+                walkforward: walkforward_args(cmd.walkforward),
+            },
+            Command::Export(cmd) => CliCommand::Export {
+                input: cmd.input,
+                out: cmd.out,
+                format: match cmd.format {
+                    ExportFormat::Zip => BundleFormat::Zip,
+                    ExportFormat::Parquet => BundleFormat::Parquet,
+                },
+            },
+            Command::Montecarlo(cmd) => CliCommand::Montecarlo {
+                input: cmd.input,
+                out: cmd.out,
+                config: MonteCarloConfig {
+                    samples: cmd.samples,
+                    block: cmd.block,
+                    seed: cmd.seed,
+                },
+            },
+            Command::Compare(cmd) => CliCommand::Compare {
+                a: cmd.a,
+                b: cmd.b,
+                out: cmd.out,
+            },
+            Command::Annotate(cmd) => CliCommand::Annotate {
+                run_dir: cmd.run_dir,
+                note: cmd.note,
+            },
+        };
+        exit_with_result(json, kairos_alloy::headless::run_command(command));
+    }
+
+    if cli.headless && cli.follow.is_some() {
+        exit_with_error(
+            json,
+            ErrorKind::Usage,
+            "--follow opens the TUI; use --mode report --watch to follow a run headless",
+        );
     }
 
     if cli.headless {
         let mode = match cli.mode {
             Some(m) => m,
-            None => {
-                eprintln!("error: --mode is required with --headless");
-                std::process::exit(1);
-            }
+            None => exit_with_error(json, ErrorKind::Usage, "--mode is required with --headless"),
         };
 
         let mode = match mode {
@@ -105,93 +1116,368 @@ fn main() {
             Mode::Report => HeadlessMode::Report,
             Mode::Sweep => HeadlessMode::Sweep,
             Mode::Cpcv => HeadlessMode::Cpcv,
+            Mode::Compare => HeadlessMode::Compare,
+            Mode::Registry => HeadlessMode::Registry,
+            Mode::Annotate => HeadlessMode::Annotate,
+            Mode::Walkforward => HeadlessMode::Walkforward,
+            Mode::Ingest => HeadlessMode::Ingest,
+            Mode::Seed => HeadlessMode::Seed,
+            Mode::Batch => HeadlessMode::Batch,
+            Mode::Live => HeadlessMode::Live,
         };
+        if cli.progress_every == 0 {
+            exit_with_error(json, ErrorKind::Usage, "--progress-every must be > 0");
+        }
+        if cli.jobs_concurrency == Some(0) {
+            exit_with_error(json, ErrorKind::Usage, "--jobs-concurrency must be > 0");
+        }
 
         let config_path = match mode {
-            HeadlessMode::Sweep => cli.config.or_else(|| {
-                std::env::var("KAIROS_CONFIG")
-                    .ok()
-                    .filter(|v| !v.trim().is_empty())
-                    .map(PathBuf::from)
-            }),
-            _ => Some(
-                cli.config
-                    .or_else(|| {
-                        std::env::var("KAIROS_CONFIG")
-                            .ok()
-                            .filter(|v| !v.trim().is_empty())
-                            .map(PathBuf::from)
-                    })
-                    .unwrap_or_else(|| {
-                        eprintln!("error: missing --config and env KAIROS_CONFIG is not set");
-                        std::process::exit(1);
-                    }),
-            ),
+            HeadlessMode::Sweep
+            | HeadlessMode::Compare
+            | HeadlessMode::Registry
+            | HeadlessMode::Annotate
+            | HeadlessMode::Ingest
+            | HeadlessMode::Seed
+            | HeadlessMode::Batch => config_path_or_env(cli.config),
+            _ => Some(require_config_path(json, cli.config)),
         };
 
         let result = kairos_alloy::headless::run_headless(HeadlessArgs {
             mode,
             config_path,
             strict: cli.strict,
+            reconcile: ReconcileArgs {
+                exchange: cli.reconcile.reconcile_exchange,
+                market: cli.reconcile.reconcile_market,
+                table: cli.reconcile.reconcile_table,
+            },
             run_dir: cli.run_dir,
+            watch: cli.watch,
+            watch_interval_secs: cli.watch_interval_secs,
+            report: ReportOptions {
+                outputs: (!cli.report_outputs.is_empty())
+                    .then(|| cli.report_outputs.into_iter().map(report_output).collect()),
+                out_dir: cli.report_out,
+            },
+            note: cli.note,
             sweep_config: cli.sweep_config,
-            cpcv_out: cli.cpcv_out,
-            cpcv_n_groups: cli.cpcv_n_groups,
-            cpcv_k_test: cli.cpcv_k_test,
-            cpcv_horizon_bars: cli.cpcv_horizon_bars,
-            cpcv_purge_bars: cli.cpcv_purge_bars,
-            cpcv_embargo_bars: cli.cpcv_embargo_bars,
-            cpcv_start: cli.cpcv_start,
-            cpcv_end: cli.cpcv_end,
+            cpcv: cpcv_args(cli.cpcv),
+            walkforward: walkforward_args(cli.walkforward),
+            ingest: IngestModeArgs {
+                start: cli.ingest.ingest_start,
+                end: cli.ingest.ingest_end,
+                symbol: cli.ingest.ingest_symbol,
+                timeframe: cli.ingest.ingest_timeframe,
+                base_url: cli.ingest.ingest_base_url,
+                resume: cli.ingest.ingest_resume,
+                migrate: cli.ingest.ingest_migrate,
+                migrations_path: cli.ingest.ingest_migrations_path,
+            },
+            seed: SeedArgs {
+                config_path: None,
+                db_url: None,
+                symbol: cli.seed.seed_symbol,
+                market: None,
+                timeframe: cli.seed.seed_timeframe,
+                exchange: None,
+                bars: cli.seed.seed_bars,
+                regime: regime(cli.seed.seed_regime),
+                start: cli.seed.seed_start,
+                start_price: cli.seed.seed_start_price,
+                seed: cli.seed.seed_rng,
+                gap_rate: cli.seed.seed_gap_rate,
+                duplicate_rate: cli.seed.seed_duplicate_rate,
+                source: "synthetic".to_string(),
+                batch_size: 5_000,
+                insert_mode: InsertMode::default(),
+            },
+            jobs: cli.jobs,
+            jobs_concurrency: cli.jobs_concurrency,
+            confirm_live: cli.confirm_live,
+            resume_session: cli.resume_session,
+            compare_a: cli.compare_a,
+            compare_b: cli.compare_b,
+            compare_out: cli.compare_out,
+            registry: RegistryArgs {
+                path: cli.registry_path,
+                reindex: cli.registry_reindex,
+                symbol: cli.registry_symbol,
+                timeframe: cli.registry_timeframe,
+                config_hash: cli.registry_config_hash,
+                from: cli.registry_from,
+                to: cli.registry_to,
+                sort: cli.registry_sort,
+                ascending: cli.registry_asc,
+                limit: cli.registry_limit,
+            },
+            config_sets: cli.set,
+            profile: profile_args(cli.profile),
+            progress: ProgressArgs {
+                format: match cli.progress {
+                    ProgressMode::Text => ProgressFormat::Text,
+                    ProgressMode::Ndjson => ProgressFormat::Ndjson,
+                },
+                every_bars: cli.progress_every,
+                stdout: cli.progress_stdout,
+            },
         });
 
-        match result {
-            Ok(json) => {
-                println!(
-                    "{}",
-                    serde_json::to_string(&json)
-                        .unwrap_or_else(|_| "{\"status\":\"error\",\"error\":\"json\"}".to_string())
-                );
-                std::process::exit(0);
-            }
-            Err(err) => {
-                let lower = err.to_lowercase();
-                let code = if lower.contains("strict validation failed") {
-                    2
-                } else {
-                    1
-                };
-                eprintln!("error: {err}");
-                std::process::exit(code);
+        // Like the `batch` subcommand: the report is printed, but a failed job exits 1.
+        if mode == HeadlessMode::Batch {
+            if let Ok(report) = &result {
+                if report["status"] == "failed" {
+                    println!("{report}");
+                    exit(1);
+                }
             }
         }
+        exit_with_result(json, result);
     }
 
-    let initial_config_path = cli.config.or_else(|| {
-        std::env::var("KAIROS_CONFIG")
-            .ok()
-            .filter(|v| !v.trim().is_empty())
-            .map(PathBuf::from)
-    });
+    if json {
+        exit_with_error(
+            json,
+            ErrorKind::Usage,
+            "--json needs a subcommand or --headless (the TUI has no JSON output)",
+        );
+    }
+    if !cli.set.is_empty() {
+        exit_with_error(
+            json,
+            ErrorKind::Usage,
+            "--set is only supported with --headless (edit the config for the TUI)",
+        );
+    }
+
+    let default_out_dir = PathBuf::from("runs");
     let initial_config_path =
+        config_path_or_env(cli.config).or_else(kairos_alloy::last_session_config);
+    // A first launch gets the onboarding view (db check, migrations, sample ingest, starter
+    // config) rather than the bootstrap of the sample config.
+    let onboarding = kairos_alloy::is_first_run(initial_config_path.as_deref(), &default_out_dir);
+    let initial_config_path = if onboarding {
+        None
+    } else {
         match kairos_alloy::bootstrap::prepare_tui_startup(initial_config_path) {
             Ok(path) => Some(path),
-            Err(err) => {
-                eprintln!("error: {err}");
-                std::process::exit(1);
-            }
-        };
+            Err(err) => fail(json, err),
+        }
+    };
 
     let opts = TuiOpts {
         initial_config_path,
         log_store,
-        default_out_dir: PathBuf::from("runs"),
+        default_out_dir,
+        follow: cli.follow,
+        onboarding,
     };
 
     if let Err(err) = kairos_alloy::run(opts) {
+        fail(json, err);
+    }
+    kairos_alloy::telemetry::shutdown();
+}
+
+fn config_path_or_env(config: Option<PathBuf>) -> Option<PathBuf> {
+    config.or_else(|| {
+        std::env::var("KAIROS_CONFIG")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .map(PathBuf::from)
+    })
+}
+
+fn require_config_path(json: bool, config: Option<PathBuf>) -> PathBuf {
+    config_path_or_env(config).unwrap_or_else(|| {
+        exit_with_error(
+            json,
+            ErrorKind::Config,
+            "missing --config and env KAIROS_CONFIG is not set",
+        )
+    })
+}
+
+fn ingest_command(cmd: IngestCmd) -> IngestCommand {
+    match cmd {
+        IngestCmd::Migrate {
+            config,
+            db_url,
+            migrations_path,
+        } => IngestCommand::Migrate {
+            config_path: config_path_or_env(config),
+            db_url,
+            migrations_path,
+        },
+        IngestCmd::Kucoin {
+            config,
+            db_url,
+            symbol,
+            market,
+            timeframe,
+            start,
+            end,
+            exchange,
+            source,
+            sleep_ms,
+            batch_size,
+            insert_mode,
+            base_url,
+            resume,
+        } => IngestCommand::Kucoin(KucoinIngestArgs {
+            config_path: config_path_or_env(config),
+            db_url,
+            symbol,
+            market,
+            timeframe,
+            start,
+            end,
+            exchange,
+            source,
+            sleep_ms,
+            batch_size,
+            insert_mode,
+            base_url,
+            resume,
+        }),
+    }
+}
+
+fn seed_args(cmd: SeedCmd) -> SeedArgs {
+    SeedArgs {
+        config_path: config_path_or_env(cmd.config),
+        db_url: cmd.db_url,
+        symbol: cmd.symbol,
+        market: cmd.market,
+        timeframe: cmd.timeframe,
+        exchange: cmd.exchange,
+        bars: cmd.bars,
+        regime: regime(cmd.regime),
+        start: cmd.start,
+        start_price: cmd.start_price,
+        seed: cmd.seed,
+        gap_rate: cmd.gap_rate,
+        duplicate_rate: cmd.duplicate_rate,
+        source: cmd.source,
+        batch_size: cmd.batch_size,
+        insert_mode: cmd.insert_mode,
+    }
+}
+
+fn report_output(output: ReportOutputArg) -> ReportOutput {
+    match output {
+        ReportOutputArg::Summary => ReportOutput::Summary,
+        ReportOutputArg::Html => ReportOutput::Html,
+        ReportOutputArg::Dashboard => ReportOutput::Dashboard,
+        ReportOutputArg::Markdown => ReportOutput::Markdown,
+        ReportOutputArg::Parquet => ReportOutput::Parquet,
+    }
+}
+
+fn regime(regime: SeedRegime) -> Regime {
+    match regime {
+        SeedRegime::Trending => Regime::Trending,
+        SeedRegime::Choppy => Regime::Choppy,
+        SeedRegime::Gappy => Regime::Gappy,
+    }
+}
+
+fn runs_command(cmd: RunsCmd) -> Result<RunsCommand, CliError> {
+    // Only fails when the config cannot be loaded.
+    let out_dir =
+        kairos_alloy::runs::resolve_out_dir(cmd.out_dir, config_path_or_env(cmd.config).as_deref())
+            .map_err(CliError::config)?;
+    Ok(match cmd.action {
+        RunsAction::List { sort, limit } => RunsCommand::List {
+            out_dir,
+            sort,
+            limit,
+        },
+        RunsAction::Show { id } => RunsCommand::Show { out_dir, id },
+        RunsAction::Clean {
+            older_than,
+            keep_best,
+            dry_run,
+        } => RunsCommand::Clean {
+            out_dir,
+            older_than,
+            keep_best,
+            dry_run,
+        },
+    })
+}
+
+fn cpcv_args(flags: CpcvFlags) -> CpcvArgs {
+    CpcvArgs {
+        out: flags.cpcv_out,
+        n_groups: flags.cpcv_n_groups,
+        k_test: flags.cpcv_k_test,
+        horizon_bars: flags.cpcv_horizon_bars,
+        purge_bars: flags.cpcv_purge_bars,
+        embargo_bars: flags.cpcv_embargo_bars,
+        start: flags.cpcv_start,
+        end: flags.cpcv_end,
+    }
+}
+
+fn profile_args(flags: ProfileFlags) -> ProfileArgs {
+    ProfileArgs {
+        svg: flags.profile_svg,
+        heap: flags.profile_heap,
+    }
+}
+
+fn walkforward_args(flags: WalkForwardFlags) -> WalkForwardArgs {
+    WalkForwardArgs {
+        train_bars: flags.train_bars,
+        test_bars: flags.test_bars,
+        step: flags.step,
+        grid: flags.grid,
+        select: flags.select,
+    }
+}
+
+// Prints the single JSON result line (or the error) and exits with the code for the error kind.
+fn exit_with_result(json: bool, result: Result<serde_json::Value, CliError>) -> ! {
+    match result {
+        Ok(value) => {
+            let line = serde_json::to_string(&value)
+                .unwrap_or_else(|_| "{\"status\":\"error\",\"error\":\"json\"}".to_string());
+            exit_ok(json, Some(line), value)
+        }
+        Err(err) => fail(json, err),
+    }
+}
+
+// Success: `value` as one line with --json, otherwise `text` (if any).
+fn exit_ok(json: bool, text: Option<String>, value: serde_json::Value) -> ! {
+    if json {
+        println!("{value}");
+    } else if let Some(text) = text {
+        println!("{text}");
+    }
+    exit(EXIT_OK);
+}
+
+// Flushes pending OTLP spans before leaving (`std::process::exit` skips destructors).
+fn exit(code: i32) -> ! {
+    kairos_alloy::telemetry::shutdown();
+    std::process::exit(code)
+}
+
+fn fail(json: bool, err: impl Into<CliError>) -> ! {
+    let err = err.into();
+    exit_with_error(json, err.kind, &err.message)
+}
+
+// `error: ...` on stderr, or the error object on stdout with --json.
+fn exit_with_error(json: bool, kind: ErrorKind, err: &str) -> ! {
+    if json {
+        println!("{}", error_json(kind, err));
+    } else {
         eprintln!("error: {err}");
-        std::process::exit(1);
     }
+    exit(kind.exit_code());
 }
 
 fn init_tracing(log_store: Arc<parking_lot::Mutex<logging::LogStore>>) -> Result<(), String> {
@@ -199,39 +1485,53 @@ fn init_tracing(log_store: Arc<parking_lot::Mutex<logging::LogStore>>) -> Result
     let env_filter = tracing_subscriber::EnvFilter::try_new(filter)
         .map_err(|err| format!("invalid log filter: {err}"))?;
 
-    tracing_subscriber::fmt()
-        .with_env_filter(env_filter)
-        .with_writer(logging::LogMakeWriter::new(log_store))
-        .init();
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+
+    let registry = tracing_subscriber::registry()
+        .with(env_filter)
+        .with(tracing_subscriber::fmt::layer().with_writer(logging::LogMakeWriter::new(log_store)));
+    let otel = kairos_alloy::telemetry::otel_layer()?;
+    let exporting = otel.is_some();
+    registry.with(otel).init();
+    if exporting {
+        tracing::info!("OTLP trace export enabled");
+    }
 
     Ok(())
 }
 
+// The TUI always gets a recorder, for its Metrics view; other commands only record with
+// `KAIROS_METRICS_ADDR`.
 #[cfg(feature = "prometheus")]
-fn init_metrics() -> Result<Option<SocketAddr>, String> {
+fn init_metrics(tui: bool) -> Result<Option<SocketAddr>, String> {
     use metrics_exporter_prometheus::PrometheusBuilder;
 
-    let Some(raw) = std::env::var("KAIROS_METRICS_ADDR").ok() else {
+    let raw = std::env::var("KAIROS_METRICS_ADDR")
+        .ok()
+        .filter(|raw| !raw.trim().is_empty());
+    let Some(raw) = raw else {
+        if tui {
+            let handle = PrometheusBuilder::new()
+                .install_recorder()
+                .map_err(|err| format!("failed to install metrics recorder: {err}"))?;
+            kairos_alloy::metrics_view::set_handle(handle);
+        }
         return Ok(None);
     };
-    if raw.trim().is_empty() {
-        return Ok(None);
-    }
 
     let addr: SocketAddr = raw
         .parse()
         .map_err(|err| format!("invalid KAIROS_METRICS_ADDR (expected host:port): {err}"))?;
-
-    PrometheusBuilder::new()
-        .with_http_listener(addr)
-        .install()
+    let handle = PrometheusBuilder::new()
+        .install_recorder()
         .map_err(|err| format!("failed to install prometheus exporter: {err}"))?;
-
-    tracing::info!(metrics_addr = %addr, "prometheus metrics exporter enabled");
-    Ok(Some(addr))
+    kairos_alloy::metrics_view::set_handle(handle);
+    // The listener is separate from the recorder, so the TUI can stop or move it later.
+    kairos_alloy::metrics_exporter::start(addr).map(Some)
 }
 
 #[cfg(not(feature = "prometheus"))]
-fn init_metrics() -> Result<Option<SocketAddr>, String> {
+fn init_metrics(_tui: bool) -> Result<Option<SocketAddr>, String> {
     Ok(None)
 }
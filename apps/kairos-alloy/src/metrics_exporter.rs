@@ -0,0 +1,160 @@
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+use tokio::sync::oneshot;
+
+// Where the exporter listens when nothing else names an address.
+pub const DEFAULT_ADDR: &str = "127.0.0.1:9898";
+
+// The HTTP listener serving the installed recorder in the Prometheus text format, on any path like
+// `metrics-exporter-prometheus` does. `KAIROS_METRICS_ADDR` starts it at launch and the TUI's
+// Metrics view starts, moves and stops it afterwards.
+struct Exporter {
+    addr: SocketAddr,
+    shutdown: oneshot::Sender<()>,
+    thread: JoinHandle<()>,
+}
+
+impl Exporter {
+    fn stop(self) {
+        let _ = self.shutdown.send(());
+        let _ = self.thread.join();
+    }
+}
+
+static EXPORTER: Mutex<Option<Exporter>> = Mutex::new(None);
+
+// The address the exporter is listening on, if it is running.
+pub fn address() -> Option<SocketAddr> {
+    EXPORTER
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .as_ref()
+        .map(|exporter| exporter.addr)
+}
+
+// Starts the exporter on `addr`, or moves it there when it already runs elsewhere; returns the
+// bound address (port 0 picks a free one). A failed bind on another port leaves a running
+// exporter where it was.
+pub fn start(addr: SocketAddr) -> Result<SocketAddr, String> {
+    if crate::metrics_view::render().is_none() {
+        return Err(
+            "no metrics recorder in this build (enable the `prometheus` feature)".to_string(),
+        );
+    }
+    let mut slot = EXPORTER.lock().unwrap_or_else(|err| err.into_inner());
+    if let Some(current) = slot.as_ref() {
+        if current.addr == addr {
+            return Ok(addr);
+        }
+        // Same port on another host form (`0.0.0.0` vs `127.0.0.1`) would not bind next to it.
+        if current.addr.port() == addr.port() {
+            if let Some(current) = slot.take() {
+                current.stop();
+            }
+        }
+    }
+
+    let listener = std::net::TcpListener::bind(addr)
+        .and_then(|listener| listener.set_nonblocking(true).map(|()| listener))
+        .map_err(|err| format!("failed to bind the metrics exporter to {addr}: {err}"))?;
+    let bound = listener
+        .local_addr()
+        .map_err(|err| format!("failed to read the metrics exporter address: {err}"))?;
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|err| format!("failed to start the metrics runtime: {err}"))?;
+    let (shutdown, stopped) = oneshot::channel();
+    // Dropping the runtime on shutdown closes the listener and any open scrape connections, so the
+    // port is free again once `stop` returns.
+    let thread = std::thread::Builder::new()
+        .name("metrics-exporter".to_string())
+        .spawn(move || {
+            runtime.block_on(async move {
+                let listener = match tokio::net::TcpListener::from_std(listener) {
+                    Ok(listener) => listener,
+                    Err(err) => {
+                        tracing::warn!(error = %err, "metrics exporter listener failed");
+                        return;
+                    }
+                };
+                let serve = async { axum::serve(listener, Router::new().fallback(scrape)).await };
+                tokio::select! {
+                    result = serve => {
+                        if let Err(err) = result {
+                            tracing::warn!(error = %err, "metrics exporter stopped");
+                        }
+                    }
+                    _ = stopped => {}
+                }
+            });
+        })
+        .map_err(|err| format!("failed to start the metrics exporter: {err}"))?;
+
+    if let Some(previous) = slot.replace(Exporter {
+        addr: bound,
+        shutdown,
+        thread,
+    }) {
+        previous.stop();
+    }
+    tracing::info!(metrics_addr = %bound, "prometheus metrics exporter enabled");
+    Ok(bound)
+}
+
+// Stops the exporter; returns where it was listening, or `None` when it was not running.
+pub fn stop() -> Option<SocketAddr> {
+    let exporter = EXPORTER
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .take()?;
+    let addr = exporter.addr;
+    exporter.stop();
+    tracing::info!(metrics_addr = %addr, "prometheus metrics exporter disabled");
+    Some(addr)
+}
+
+async fn scrape() -> Response {
+    match crate::metrics_view::render() {
+        Some(text) => ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], text).into_response(),
+        None => (StatusCode::SERVICE_UNAVAILABLE, "no metrics recorder").into_response(),
+    }
+}
+
+#[cfg(all(test, feature = "prometheus"))]
+mod tests {
+    use super::{address, start, stop};
+    use std::io::{Read, Write};
+    use std::net::{SocketAddr, TcpStream};
+
+    fn scrape(addr: SocketAddr) -> std::io::Result<String> {
+        let mut stream = TcpStream::connect(addr)?;
+        stream.write_all(b"GET /metrics HTTP/1.1\r\nHost: kairos\r\nConnection: close\r\n\r\n")?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        Ok(response)
+    }
+
+    #[test]
+    fn exporter_starts_moves_and_stops() {
+        let recorder = metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+        crate::metrics_view::set_handle(recorder.handle());
+
+        let first = start("127.0.0.1:0".parse().unwrap()).expect("start");
+        assert_eq!(address(), Some(first));
+        assert!(scrape(first).expect("scrape").starts_with("HTTP/1.1 200"));
+
+        let moved = start("127.0.0.1:0".parse().unwrap()).expect("move");
+        assert_ne!(moved, first);
+        assert!(scrape(first).is_err());
+        assert!(scrape(moved).expect("scrape").contains("text/plain"));
+
+        assert_eq!(stop(), Some(moved));
+        assert_eq!((address(), stop()), (None, None));
+        assert!(scrape(moved).is_err());
+    }
+}
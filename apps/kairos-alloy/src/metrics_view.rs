@@ -0,0 +1,284 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+// Samples kept per metric for the sparklines, one per `SAMPLE_EVERY`.
+const HISTORY_LEN: usize = 60;
+const SAMPLE_EVERY: Duration = Duration::from_secs(1);
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+// The recorder behind `KAIROS_METRICS_ADDR` (or the TUI's own, without it), read by the Metrics
+// view; set once by the binary at startup.
+#[cfg(feature = "prometheus")]
+static HANDLE: std::sync::OnceLock<metrics_exporter_prometheus::PrometheusHandle> =
+    std::sync::OnceLock::new();
+
+#[cfg(feature = "prometheus")]
+pub fn set_handle(handle: metrics_exporter_prometheus::PrometheusHandle) {
+    let _ = HANDLE.set(handle);
+}
+
+// The current process metrics in the Prometheus text format, or `None` when no recorder is
+// installed (a build without the `prometheus` feature).
+pub fn render() -> Option<String> {
+    #[cfg(feature = "prometheus")]
+    if let Some(handle) = HANDLE.get() {
+        handle.run_upkeep();
+        return Some(handle.render());
+    }
+    None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Counter,
+    Gauge,
+    // `metrics` histograms, which the exporter renders as summaries.
+    Summary,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricSeries {
+    // The metric name plus its labels, e.g. `kairos_infra_agent_requests_total{status="200"}`.
+    pub name: String,
+    pub kind: MetricKind,
+    // The counter/gauge value, or the observation count of a summary.
+    pub value: f64,
+    pub sum: Option<f64>,
+    pub quantiles: Vec<(f64, f64)>,
+}
+
+impl MetricSeries {
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        self.quantiles
+            .iter()
+            .find(|(quantile, _)| (quantile - q).abs() < 1e-9)
+            .map(|(_, value)| *value)
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        self.sum
+            .filter(|_| self.value > 0.0)
+            .map(|sum| sum / self.value)
+    }
+}
+
+// Reads the Prometheus text format as rendered by `metrics-exporter-prometheus`: one series per
+// metric and label set, in the order they appear. Untyped samples are read as gauges.
+pub fn parse_exposition(text: &str) -> Vec<MetricSeries> {
+    let mut kinds: HashMap<&str, MetricKind> = HashMap::new();
+    let mut series: Vec<MetricSeries> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix("# TYPE ") {
+            let mut parts = rest.split_whitespace();
+            let kind = match parts.clone().nth(1) {
+                Some("counter") => MetricKind::Counter,
+                Some("gauge") => MetricKind::Gauge,
+                Some("summary") | Some("histogram") => MetricKind::Summary,
+                _ => continue,
+            };
+            if let Some(name) = parts.next() {
+                kinds.insert(name, kind);
+            }
+            continue;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((sample, value)) = line.rsplit_once(' ') else {
+            continue;
+        };
+        let Ok(value) = value.parse::<f64>() else {
+            continue;
+        };
+        let (name, labels) = match sample.split_once('{') {
+            Some((name, labels)) => (name, labels.trim_end_matches('}')),
+            None => (sample, ""),
+        };
+        let mut quantile = None;
+        let mut other_labels = Vec::new();
+        for label in labels.split(',').filter(|label| !label.is_empty()) {
+            match label.strip_prefix("quantile=") {
+                Some(q) => quantile = q.trim_matches('"').parse::<f64>().ok(),
+                None => other_labels.push(label),
+            }
+        }
+
+        let family_of = |suffix: &str| {
+            name.strip_suffix(suffix)
+                .filter(|family| kinds.get(family) == Some(&MetricKind::Summary))
+        };
+        let (family, part) = if kinds.contains_key(name) {
+            (name, "")
+        } else if let Some(family) = family_of("_sum") {
+            (family, "sum")
+        } else if let Some(family) = family_of("_count") {
+            (family, "count")
+        } else if family_of("_bucket").is_some() {
+            continue;
+        } else {
+            (name, "")
+        };
+        let kind = kinds.get(family).copied().unwrap_or(MetricKind::Gauge);
+        let key = if other_labels.is_empty() {
+            family.to_string()
+        } else {
+            format!("{family}{{{}}}", other_labels.join(","))
+        };
+        let idx = match series.iter().position(|s| s.name == key) {
+            Some(idx) => idx,
+            None => {
+                series.push(MetricSeries {
+                    name: key,
+                    kind,
+                    value: 0.0,
+                    sum: None,
+                    quantiles: Vec::new(),
+                });
+                series.len() - 1
+            }
+        };
+        let entry = &mut series[idx];
+        match (kind, part, quantile) {
+            (MetricKind::Summary, "", Some(q)) => entry.quantiles.push((q, value)),
+            (MetricKind::Summary, "sum", _) => entry.sum = Some(value),
+            (MetricKind::Summary, "", None) => {}
+            _ => entry.value = value,
+        }
+    }
+    series
+}
+
+// The Metrics view: the latest snapshot plus, per series, what changed at each sample (counter and
+// summary-count increases, gauge values) for the sparklines.
+#[derive(Debug, Default)]
+pub struct MetricsPanel {
+    pub series: Vec<MetricSeries>,
+    pub sampled_at: Option<Instant>,
+    pub available: bool,
+    pub scroll: usize,
+    history: HashMap<String, VecDeque<u64>>,
+    last_values: HashMap<String, f64>,
+}
+
+impl MetricsPanel {
+    pub fn due(&self, now: Instant) -> bool {
+        self.sampled_at
+            .is_none_or(|at| now.duration_since(at) >= SAMPLE_EVERY)
+    }
+
+    // Takes a snapshot from the installed recorder; returns whether there was one.
+    pub fn sample(&mut self, now: Instant) -> bool {
+        self.sampled_at = Some(now);
+        let Some(text) = render() else {
+            return false;
+        };
+        self.available = true;
+        self.record(parse_exposition(&text));
+        true
+    }
+
+    pub fn record(&mut self, series: Vec<MetricSeries>) {
+        for s in &series {
+            let point = match s.kind {
+                MetricKind::Gauge => s.value,
+                MetricKind::Counter | MetricKind::Summary => {
+                    let last = self.last_values.insert(s.name.clone(), s.value);
+                    // The first sample only sets the baseline.
+                    match last {
+                        Some(last) => s.value - last,
+                        None => 0.0,
+                    }
+                }
+            };
+            let history = self.history.entry(s.name.clone()).or_default();
+            history.push_back(point.max(0.0).round() as u64);
+            while history.len() > HISTORY_LEN {
+                history.pop_front();
+            }
+        }
+        self.series = series;
+    }
+
+    pub fn sparkline(&self, name: &str) -> String {
+        self.history
+            .get(name)
+            .map(|values| sparkline_text(values.iter().copied()))
+            .unwrap_or_default()
+    }
+}
+
+// Bar characters scaled to the largest value; all-zero input stays flat.
+pub fn sparkline_text(values: impl Iterator<Item = u64> + Clone) -> String {
+    let max = values.clone().max().unwrap_or(0);
+    values
+        .map(|value| {
+            if max == 0 {
+                SPARK_CHARS[0]
+            } else {
+                let level = (value as f64 / max as f64 * (SPARK_CHARS.len() - 1) as f64).round();
+                SPARK_CHARS[level as usize]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_exposition, sparkline_text, MetricKind, MetricsPanel};
+
+    const EXPOSITION: &str = r#"# TYPE kairos_infra_agent_requests_total counter
+kairos_infra_agent_requests_total{status="200"} 40
+kairos_infra_agent_requests_total{status="503"} 2
+
+# TYPE kairos_backtest_trades gauge
+kairos_backtest_trades 7
+
+# TYPE kairos_backtest_engine_ms summary
+kairos_backtest_engine_ms{quantile="0"} 10
+kairos_backtest_engine_ms{quantile="0.5"} 20
+kairos_backtest_engine_ms{quantile="0.99"} 90
+kairos_backtest_engine_ms_sum 300
+kairos_backtest_engine_ms_count 10
+"#;
+
+    #[test]
+    fn parses_counters_gauges_and_summaries_by_label_set() {
+        let series = parse_exposition(EXPOSITION);
+        let names: Vec<&str> = series.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "kairos_infra_agent_requests_total{status=\"200\"}",
+                "kairos_infra_agent_requests_total{status=\"503\"}",
+                "kairos_backtest_trades",
+                "kairos_backtest_engine_ms",
+            ]
+        );
+        assert_eq!(series[1].kind, MetricKind::Counter);
+        assert_eq!(series[1].value, 2.0);
+        assert_eq!(series[2].kind, MetricKind::Gauge);
+        let engine = &series[3];
+        assert_eq!(engine.kind, MetricKind::Summary);
+        assert_eq!((engine.value, engine.sum), (10.0, Some(300.0)));
+        assert_eq!(engine.quantile(0.99), Some(90.0));
+        assert_eq!(engine.mean(), Some(30.0));
+    }
+
+    #[test]
+    fn sparklines_follow_counter_increases_per_sample() {
+        let mut panel = MetricsPanel::default();
+        for total in [40, 44, 44, 52] {
+            let text = EXPOSITION.replace(
+                "{status=\"200\"} 40",
+                &format!("{{status=\"200\"}} {total}"),
+            );
+            panel.record(parse_exposition(&text));
+        }
+        assert_eq!(
+            panel.sparkline("kairos_infra_agent_requests_total{status=\"200\"}"),
+            "▁▅▁█"
+        );
+        assert_eq!(panel.sparkline("kairos_backtest_trades"), "████");
+        assert_eq!(sparkline_text([0, 0].into_iter()), "▁▁");
+    }
+}
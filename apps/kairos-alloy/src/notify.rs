@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+const TOAST_SECS: u64 = 8;
+
+// How to raise a desktop notification from inside the terminal: OSC 9 (iTerm2, Windows Terminal,
+// kitty, WezTerm) or OSC 777 (urxvt, foot, VTE terminals such as GNOME Terminal).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DesktopNotify {
+    #[default]
+    Off,
+    Osc9,
+    Osc777,
+}
+
+impl DesktopNotify {
+    pub const ALL: [DesktopNotify; 3] = [
+        DesktopNotify::Off,
+        DesktopNotify::Osc9,
+        DesktopNotify::Osc777,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DesktopNotify::Off => "off",
+            DesktopNotify::Osc9 => "osc9",
+            DesktopNotify::Osc777 => "osc777",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|mode| *mode == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+// When a finished task or job is worth a toast, and how loud it gets. Tasks shorter than
+// `min_secs` finish while the user is still looking, so they stay quiet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotifySettings {
+    pub bell: bool,
+    pub desktop: DesktopNotify,
+    pub min_secs: u64,
+}
+
+impl Default for NotifySettings {
+    fn default() -> Self {
+        Self {
+            bell: false,
+            desktop: DesktopNotify::Off,
+            min_secs: 10,
+        }
+    }
+}
+
+impl NotifySettings {
+    pub fn wants(&self, elapsed: Duration) -> bool {
+        elapsed.as_secs() >= self.min_secs
+    }
+
+    // The bytes to write to the terminal for a notification: BEL and/or the OSC sequence.
+    pub fn terminal_alert(&self, title: &str, body: &str) -> String {
+        let mut out = String::new();
+        if self.bell {
+            out.push('\x07');
+        }
+        match self.desktop {
+            DesktopNotify::Off => {}
+            DesktopNotify::Osc9 => {
+                out.push_str(&format!("\x1b]9;{}: {}\x07", clean(title), clean(body)));
+            }
+            DesktopNotify::Osc777 => {
+                // `;` separates the title from the body here.
+                out.push_str(&format!(
+                    "\x1b]777;notify;{};{}\x07",
+                    clean(title).replace(';', ","),
+                    clean(body)
+                ));
+            }
+        }
+        out
+    }
+}
+
+// Control characters (an ESC or BEL in an error message) would end the sequence early.
+fn clean(text: &str) -> String {
+    text.chars()
+        .map(|ch| if ch.is_control() { ' ' } else { ch })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub text: String,
+    pub failed: bool,
+    pub expires_at: Instant,
+}
+
+impl Toast {
+    pub fn new(text: String, failed: bool) -> Self {
+        Self {
+            text,
+            failed,
+            expires_at: Instant::now() + Duration::from_secs(TOAST_SECS),
+        }
+    }
+}
+
+// "1h 02m", "3m 07s" or "42s".
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    match (secs / 3600, secs % 3600 / 60, secs % 60) {
+        (0, 0, s) => format!("{s}s"),
+        (0, m, s) => format!("{m}m {s:02}s"),
+        (h, m, _) => format!("{h}h {m:02}m"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_elapsed, DesktopNotify, NotifySettings};
+    use std::time::Duration;
+
+    #[test]
+    fn alerts_follow_the_settings_and_stay_inside_the_sequence() {
+        let mut settings = NotifySettings::default();
+        assert!(!settings.wants(Duration::from_secs(9)));
+        assert!(settings.wants(Duration::from_secs(10)));
+        assert_eq!(settings.terminal_alert("sweep", "done"), "");
+
+        settings.bell = true;
+        settings.desktop = DesktopNotify::Osc777;
+        assert_eq!(
+            settings.terminal_alert("sweep; 2h", "failed:\x1b[31m db down"),
+            "\x07\x1b]777;notify;sweep, 2h;failed: [31m db down\x07"
+        );
+        settings.bell = false;
+        settings.desktop = settings.desktop.next().next();
+        assert_eq!(
+            settings.terminal_alert("ingest", "done"),
+            "\x1b]9;ingest: done\x07"
+        );
+
+        assert_eq!(format_elapsed(Duration::from_secs(42)), "42s");
+        assert_eq!(format_elapsed(Duration::from_secs(187)), "3m 07s");
+        assert_eq!(format_elapsed(Duration::from_secs(7_380)), "2h 03m");
+    }
+}
@@ -0,0 +1,329 @@
+use crate::app::TextInput;
+use crate::ingest::{KucoinIngestArgs, DEFAULT_MIGRATIONS_PATH};
+use kairos_application::config::init::{render_config, InitAnswers};
+use kairos_domain::value_objects::timeframe::Timeframe;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio_postgres::NoTls;
+
+pub const FIELD_LABELS: [&str; 4] = ["symbol", "timeframe", "db url", "config path"];
+pub const STARTER_CONFIG_PATH: &str = "kairos.toml";
+// Enough bars for a first backtest without a long download (the bootstrap ingests years).
+const SAMPLE_INGEST_DAYS: i64 = 7;
+const DB_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Database,
+    Migrations,
+    SampleIngest,
+    StarterConfig,
+}
+
+pub const STEPS: [Step; 4] = [
+    Step::Database,
+    Step::Migrations,
+    Step::SampleIngest,
+    Step::StarterConfig,
+];
+
+impl Step {
+    pub fn label(self) -> &'static str {
+        match self {
+            Step::Database => "check the database connection",
+            Step::Migrations => "run the migrations",
+            Step::SampleIngest => "ingest a sample (last 7 days from KuCoin)",
+            Step::StarterConfig => "write the starter config and load it",
+        }
+    }
+
+    pub fn key(self) -> char {
+        match self {
+            Step::Database => 'c',
+            Step::Migrations => 'm',
+            Step::SampleIngest => 's',
+            Step::StarterConfig => 'w',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepState {
+    Pending,
+    Running,
+    Done(String),
+    Failed(String),
+}
+
+// Whether the TUI should open on the onboarding view: nothing names a config and no run was ever
+// written (a missing runs dir counts as empty).
+pub fn is_first_run(config_path: Option<&Path>, runs_dir: &Path) -> bool {
+    config_path.is_none()
+        && std::fs::read_dir(runs_dir)
+            .map(|mut entries| entries.next().is_none())
+            .unwrap_or(true)
+}
+
+// The first-run view: the answers of `kairos-alloy init` plus the state of each setup step.
+pub struct Onboarding {
+    // One input per `FIELD_LABELS` entry.
+    pub inputs: Vec<TextInput>,
+    pub selected: usize,
+    pub editing: bool,
+    // One per `STEPS` entry.
+    pub states: Vec<StepState>,
+}
+
+impl Onboarding {
+    // An empty db url falls back to KAIROS_DB_URL, like the generated config.
+    pub fn new() -> Self {
+        let defaults = InitAnswers::default();
+        let values = [
+            defaults.symbol,
+            defaults.timeframe,
+            String::new(),
+            STARTER_CONFIG_PATH.to_string(),
+        ];
+        Self {
+            inputs: values.into_iter().map(TextInput::new).collect(),
+            selected: 0,
+            editing: false,
+            states: STEPS.iter().map(|_| StepState::Pending).collect(),
+        }
+    }
+
+    pub fn selected_input(&mut self) -> &mut TextInput {
+        &mut self.inputs[self.selected]
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1).min(FIELD_LABELS.len() - 1);
+    }
+
+    pub fn state(&self, step: Step) -> &StepState {
+        &self.states[step_index(step)]
+    }
+
+    pub fn set_state(&mut self, step: Step, state: StepState) {
+        self.states[step_index(step)] = state;
+    }
+
+    pub fn is_running(&self, step: Step) -> bool {
+        *self.state(step) == StepState::Running
+    }
+
+    // The first step not done yet, highlighted as the next thing to do.
+    pub fn next_step(&self) -> Option<Step> {
+        STEPS
+            .into_iter()
+            .find(|step| !matches!(self.state(*step), StepState::Done(_)))
+    }
+
+    fn value(&self, label: &str) -> Option<String> {
+        let idx = FIELD_LABELS.iter().position(|l| *l == label)?;
+        let value = self.inputs[idx].value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
+    pub fn db_url(&self) -> Result<String, String> {
+        self.value("db url")
+            .or_else(|| {
+                std::env::var("KAIROS_DB_URL")
+                    .ok()
+                    .filter(|url| !url.trim().is_empty())
+            })
+            .ok_or_else(|| "set the db url (or export KAIROS_DB_URL)".to_string())
+    }
+
+    pub fn config_path(&self) -> PathBuf {
+        PathBuf::from(
+            self.value("config path")
+                .unwrap_or_else(|| STARTER_CONFIG_PATH.to_string()),
+        )
+    }
+
+    fn answers(&self) -> Result<InitAnswers, String> {
+        let symbol = self.value("symbol").unwrap_or_default().to_uppercase();
+        if !symbol.contains('-') {
+            return Err("symbol: expected BASE-QUOTE, e.g. BTC-USDT".to_string());
+        }
+        let timeframe = Timeframe::parse(&self.value("timeframe").unwrap_or_default())
+            .map_err(|err| format!("timeframe: {err}"))?
+            .label;
+        Ok(InitAnswers {
+            symbol,
+            timeframe,
+            db_url: self.value("db url"),
+            ..InitAnswers::default()
+        })
+    }
+
+    // Writes the config `kairos-alloy init` would with these answers; an existing file is kept.
+    pub fn write_config(&self) -> Result<PathBuf, String> {
+        let path = self.config_path();
+        if path.exists() {
+            return Err(format!(
+                "{} already exists (pick another config path)",
+                path.display()
+            ));
+        }
+        let rendered = render_config(&self.answers()?)?;
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|err| format!("failed to create {}: {err}", parent.display()))?;
+        }
+        std::fs::write(&path, rendered)
+            .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
+        Ok(path)
+    }
+
+    // The sample ingest of the symbol at the config's timeframe, with its range for the progress
+    // bar.
+    pub fn sample_ingest(&self, now: i64) -> Result<(KucoinIngestArgs, (i64, i64)), String> {
+        let answers = self.answers()?;
+        let start = now - SAMPLE_INGEST_DAYS * 24 * 3600;
+        let args = KucoinIngestArgs {
+            config_path: None,
+            db_url: Some(self.db_url()?),
+            symbol: Some(answers.symbol),
+            market: None,
+            timeframe: Some(answers.timeframe),
+            start: start.to_string(),
+            end: None,
+            exchange: None,
+            source: "kucoin".to_string(),
+            sleep_ms: 350,
+            batch_size: 500,
+            insert_mode: kairos_ingest::InsertMode::default(),
+            base_url: None,
+            resume: false,
+        };
+        Ok((args, (start, now)))
+    }
+}
+
+fn step_index(step: Step) -> usize {
+    STEPS
+        .iter()
+        .position(|s| *s == step)
+        .expect("every step is listed")
+}
+
+// Runs the database steps off the UI thread.
+pub fn run_db_step(step: Step, db_url: &str) -> Result<String, String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|err| format!("unable to start async runtime: {err}"))?;
+    match step {
+        Step::Database => runtime.block_on(check_database(db_url)),
+        Step::Migrations => {
+            let path = find_migrations_dir().ok_or_else(|| {
+                format!("{DEFAULT_MIGRATIONS_PATH} not found above the current directory")
+            })?;
+            runtime
+                .block_on(kairos_ingest::migrate_db(db_url, &path))
+                .map_err(|err| err.to_string())?;
+            Ok(format!("applied {}", path.display()))
+        }
+        Step::SampleIngest | Step::StarterConfig => {
+            Err(format!("'{}' is not a database step", step.label()))
+        }
+    }
+}
+
+async fn check_database(db_url: &str) -> Result<String, String> {
+    let (client, connection) =
+        tokio::time::timeout(DB_CONNECT_TIMEOUT, tokio_postgres::connect(db_url, NoTls))
+            .await
+            .map_err(|_| {
+                format!(
+                    "no answer after {}s (is postgres up? docker compose up -d db)",
+                    DB_CONNECT_TIMEOUT.as_secs()
+                )
+            })?
+            .map_err(|err| format!("unable to connect: {err}"))?;
+    tokio::spawn(async move {
+        let _ = connection.await;
+    });
+    let row = client
+        .query_one(
+            "SELECT current_setting('server_version'), \
+             to_regclass('ohlcv_candles') IS NOT NULL",
+            &[],
+        )
+        .await
+        .map_err(|err| format!("connected, but the check query failed: {err}"))?;
+    let version: String = row.get(0);
+    let migrated: bool = row.get(1);
+    Ok(if migrated {
+        format!("postgres {version}, ohlcv_candles present")
+    } else {
+        format!("postgres {version}, ohlcv_candles missing (run the migrations)")
+    })
+}
+
+fn find_migrations_dir() -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok()?;
+    cwd.ancestors()
+        .map(|dir| dir.join(DEFAULT_MIGRATIONS_PATH))
+        .find(|path| path.is_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_first_run, Onboarding, Step, StepState};
+    use crate::app::TextInput;
+    use kairos_application::config::load_config;
+    use std::path::Path;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn first_run_writes_a_loadable_starter_config_and_plans_a_short_ingest() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let dir = std::env::temp_dir().join(format!("kairos_tui_onboarding_{unique}"));
+        let runs = dir.join("runs");
+        assert!(is_first_run(None, &runs));
+        std::fs::create_dir_all(runs.join("btc_usdt_1min")).expect("mkdir");
+        assert!(!is_first_run(None, &runs));
+        assert!(!is_first_run(Some(Path::new("a.toml")), &dir.join("none")));
+
+        let mut onboarding = Onboarding::new();
+        assert_eq!(onboarding.next_step(), Some(Step::Database));
+        onboarding.set_state(Step::Database, StepState::Done("ok".to_string()));
+        assert_eq!(onboarding.next_step(), Some(Step::Migrations));
+
+        onboarding.inputs[1] = TextInput::new("7min".to_string());
+        assert!(onboarding
+            .write_config()
+            .expect_err("bad timeframe")
+            .contains("timeframe"));
+        onboarding.inputs[0] = TextInput::new("eth-usdt".to_string());
+        onboarding.inputs[1] = TextInput::new("1h".to_string());
+        onboarding.inputs[2] = TextInput::new("postgres://k:k@localhost/k".to_string());
+        onboarding.inputs[3] = TextInput::new(dir.join("kairos.toml").display().to_string());
+
+        let path = onboarding.write_config().expect("write");
+        let config = load_config(&path).expect("starter config loads");
+        assert_eq!(config.run.symbol, "ETH-USDT");
+        assert_eq!(config.run.timeframe, "1hour");
+        assert_eq!(config.db.url.as_deref(), Some("postgres://k:k@localhost/k"));
+        assert!(onboarding
+            .write_config()
+            .expect_err("kept")
+            .contains("already exists"));
+
+        let (args, range) = onboarding.sample_ingest(1_000_000).expect("ingest");
+        assert_eq!(args.symbol.as_deref(), Some("ETH-USDT"));
+        assert_eq!(args.timeframe.as_deref(), Some("1hour"));
+        assert_eq!(range, (1_000_000 - 7 * 24 * 3600, 1_000_000));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,233 @@
+use kairos_application::config::ConfigError;
+use kairos_application::error::RunError;
+use kairos_domain::errors::EngineError;
+use kairos_ingest::IngestError;
+use serde_json::json;
+use std::fmt;
+
+// Process exit codes, shared by the text and `--json` output modes.
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_RUNTIME: i32 = 1;
+pub const EXIT_STRICT_VALIDATION: i32 = 2;
+pub const EXIT_CONFIG: i32 = 3;
+pub const EXIT_DATA: i32 = 4;
+pub const EXIT_USAGE: i32 = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Usage,
+    Config,
+    Data,
+    StrictValidation,
+    Runtime,
+}
+
+impl ErrorKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorKind::Usage => "usage",
+            ErrorKind::Config => "config",
+            ErrorKind::Data => "data",
+            ErrorKind::StrictValidation => "strict_validation",
+            ErrorKind::Runtime => "runtime",
+        }
+    }
+
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::Usage => EXIT_USAGE,
+            ErrorKind::Config => EXIT_CONFIG,
+            ErrorKind::Data => EXIT_DATA,
+            ErrorKind::StrictValidation => EXIT_STRICT_VALIDATION,
+            ErrorKind::Runtime => EXIT_RUNTIME,
+        }
+    }
+}
+
+// A failed command: the message printed on stderr (or in the `--json` error object) and the kind
+// that picks the exit code. The kind comes from where the error is raised, through the
+// application's error enums or an explicit constructor; plain strings are runtime errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliError {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl CliError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        CliError {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    pub fn usage(message: impl Into<String>) -> Self {
+        CliError::new(ErrorKind::Usage, message)
+    }
+
+    pub fn config(message: impl Into<String>) -> Self {
+        CliError::new(ErrorKind::Config, message)
+    }
+
+    pub fn data(message: impl Into<String>) -> Self {
+        CliError::new(ErrorKind::Data, message)
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<String> for CliError {
+    fn from(message: String) -> Self {
+        CliError::new(ErrorKind::Runtime, message)
+    }
+}
+
+impl From<&str> for CliError {
+    fn from(message: &str) -> Self {
+        CliError::new(ErrorKind::Runtime, message)
+    }
+}
+
+impl From<ConfigError> for CliError {
+    fn from(err: ConfigError) -> Self {
+        CliError::config(err.to_string())
+    }
+}
+
+impl From<EngineError> for CliError {
+    fn from(err: EngineError) -> Self {
+        CliError::new(engine_kind(&err), err.to_string())
+    }
+}
+
+// Bad steps and schedules come from the config; the rest happen while running.
+fn engine_kind(err: &EngineError) -> ErrorKind {
+    match err {
+        EngineError::NonPositiveStep { .. }
+        | EngineError::InvalidSchedule(_)
+        | EngineError::InvalidTimeOfDay(_) => ErrorKind::Config,
+        EngineError::Cancelled | EngineError::AggregatorMismatch { .. } => ErrorKind::Runtime,
+    }
+}
+
+impl From<RunError> for CliError {
+    fn from(err: RunError) -> Self {
+        let kind = match &err {
+            RunError::Config(_) => ErrorKind::Config,
+            RunError::Engine(err) => engine_kind(err),
+            RunError::Data(_) => ErrorKind::Data,
+            RunError::StrictValidation(_) => ErrorKind::StrictValidation,
+            RunError::Usage(_) => ErrorKind::Usage,
+            RunError::Agent(_)
+            | RunError::Artifacts(_)
+            | RunError::Cancelled(_)
+            | RunError::Runtime(_) => ErrorKind::Runtime,
+        };
+        CliError::new(kind, err.to_string())
+    }
+}
+
+impl From<IngestError> for CliError {
+    fn from(err: IngestError) -> Self {
+        let kind = match &err {
+            IngestError::Connect(_)
+            | IngestError::Database { .. }
+            | IngestError::InvalidInput(_) => ErrorKind::Data,
+            IngestError::MissingMigrations(_) => ErrorKind::Usage,
+            IngestError::Io { .. }
+            | IngestError::HttpClient(_)
+            | IngestError::Request { .. }
+            | IngestError::Status { .. }
+            | IngestError::Decode { .. }
+            | IngestError::Api { .. } => ErrorKind::Runtime,
+        };
+        CliError::new(kind, err.to_string())
+    }
+}
+
+// The `--json` error object: `{"status":"error","kind":...,"exit_code":...,"error":...}`.
+pub fn error_json(kind: ErrorKind, err: &str) -> serde_json::Value {
+    json!({
+        "status": "error",
+        "kind": kind.as_str(),
+        "exit_code": kind.exit_code(),
+        "error": err,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{error_json, CliError, ErrorKind, EXIT_CONFIG, EXIT_DATA, EXIT_RUNTIME};
+    use kairos_application::config::ConfigError;
+    use kairos_application::error::RunError;
+    use kairos_domain::errors::EngineError;
+
+    #[test]
+    fn kind_follows_the_error_variant_not_the_message() {
+        let write_failed: CliError =
+            RunError::Artifacts("failed to write summary.json: permission denied".to_string())
+                .into();
+        assert_eq!(write_failed.kind.exit_code(), EXIT_RUNTIME);
+        assert_eq!(
+            write_failed.message,
+            "failed to write summary.json: permission denied"
+        );
+
+        let missing: CliError =
+            RunError::Data("missing trades.csv or equity.csv in runs/x".to_string()).into();
+        assert_eq!(missing.kind.exit_code(), EXIT_DATA);
+
+        let cases = [
+            (
+                RunError::StrictValidation("3 gaps".to_string()),
+                ErrorKind::StrictValidation,
+            ),
+            (
+                RunError::Config(ConfigError::Invalid("postgres timeframe".to_string())),
+                ErrorKind::Config,
+            ),
+            (
+                RunError::Engine(EngineError::InvalidSchedule("paper.session_start")),
+                ErrorKind::Config,
+            ),
+            (RunError::Cancelled("backtest"), ErrorKind::Runtime),
+            (
+                RunError::Usage("--out must be outside the run dir".to_string()),
+                ErrorKind::Usage,
+            ),
+            (
+                RunError::Runtime("ohlcv stream closed".to_string()),
+                ErrorKind::Runtime,
+            ),
+        ];
+        for (err, expected) in cases {
+            let message = err.to_string();
+            let err = CliError::from(err);
+            assert_eq!(err.kind, expected, "{message}");
+            assert_eq!(err.message, message);
+        }
+    }
+
+    #[test]
+    fn plain_strings_are_runtime_errors() {
+        let err = CliError::from("unable to start async runtime: oops");
+        assert_eq!(err.kind, ErrorKind::Runtime);
+        let err = CliError::from(ConfigError::InvalidOverride(
+            "invalid --set 'x'".to_string(),
+        ));
+        assert_eq!(err.kind.exit_code(), EXIT_CONFIG);
+    }
+
+    #[test]
+    fn error_json_carries_kind_and_exit_code() {
+        let value = error_json(ErrorKind::Data, "no bars");
+        assert_eq!(value["status"], "error");
+        assert_eq!(value["kind"], "data");
+        assert_eq!(value["exit_code"], 4);
+        assert_eq!(value["error"], "no bars");
+    }
+}
@@ -0,0 +1,99 @@
+use crate::tasks::PositionSample;
+use kairos_domain::services::engine::backtest::OpenOrder;
+
+// The Monitor position panel of a paper session: the latest sample of each symbol, in the order
+// they first reported.
+#[derive(Debug, Clone, Default)]
+pub struct PositionPanel {
+    pub symbols: Vec<PositionSample>,
+}
+
+impl PositionPanel {
+    pub fn record(&mut self, sample: PositionSample) {
+        match self.symbols.iter_mut().find(|s| s.symbol == sample.symbol) {
+            Some(slot) => *slot = sample,
+            None => self.symbols.push(sample),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    pub fn unrealized_pnl(&self) -> f64 {
+        self.symbols.iter().map(unrealized_pnl).sum()
+    }
+
+    pub fn open_orders(&self) -> impl Iterator<Item = (&str, &OpenOrder)> {
+        self.symbols.iter().flat_map(|sample| {
+            sample
+                .open_orders
+                .iter()
+                .map(move |order| (sample.symbol.as_str(), order))
+        })
+    }
+}
+
+// Same rule as `Portfolio::unrealized_pnl`: long-only, so a flat sleeve has none.
+pub fn unrealized_pnl(sample: &PositionSample) -> f64 {
+    if sample.quantity <= 0.0 {
+        return 0.0;
+    }
+    (sample.price - sample.avg_price) * sample.quantity
+}
+
+// Unrealized PnL as a fraction of the position's cost.
+pub fn unrealized_return(sample: &PositionSample) -> Option<f64> {
+    let cost = sample.avg_price * sample.quantity;
+    (sample.quantity > 0.0 && cost > 0.0).then(|| unrealized_pnl(sample) / cost)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{unrealized_return, PositionPanel};
+    use crate::tasks::PositionSample;
+    use kairos_domain::services::engine::backtest::OpenOrder;
+    use kairos_domain::services::engine::execution::OrderKind;
+    use kairos_domain::value_objects::side::Side;
+
+    fn sample(symbol: &str, price: f64, quantity: f64, avg_price: f64) -> PositionSample {
+        PositionSample {
+            symbol: symbol.to_string(),
+            timestamp: 1_700_000_000,
+            price,
+            quantity,
+            avg_price,
+            cash: 1_000.0,
+            equity: 1_000.0 + price * quantity,
+            open_orders: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn keeps_the_latest_sample_per_symbol_and_sums_unrealized_pnl() {
+        let mut panel = PositionPanel::default();
+        panel.record(sample("BTC-USDT", 100.0, 2.0, 90.0));
+        let mut eth = sample("ETH-USDT", 10.0, 0.0, 0.0);
+        eth.open_orders.push(OpenOrder {
+            id: 3,
+            side: Side::Buy,
+            kind: OrderKind::Limit,
+            remaining_qty: 1.5,
+            limit_price: Some(9.5),
+            stop_price: None,
+            submitted_bar_index: 7,
+        });
+        panel.record(eth);
+        panel.record(sample("BTC-USDT", 110.0, 2.0, 90.0));
+
+        assert_eq!(panel.symbols.len(), 2);
+        assert_eq!(panel.unrealized_pnl(), 40.0);
+        assert_eq!(
+            unrealized_return(&panel.symbols[0]).map(|r| (r * 1e6).round() / 1e6),
+            Some(0.222222)
+        );
+        assert_eq!(unrealized_return(&panel.symbols[1]), None);
+        let orders: Vec<(&str, u64)> = panel.open_orders().map(|(s, o)| (s, o.id)).collect();
+        assert_eq!(orders, [("ETH-USDT", 3)]);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::output::CliError;
+use kairos_domain::repositories::artifacts::ArtifactWriter;
+use kairos_infrastructure::artifacts::FilesystemArtifactWriter;
+use kairos_infrastructure::profiling::HeapStats;
+use std::path::PathBuf;
+
+pub const FLAMEGRAPH_FILE_NAME: &str = "profile.svg";
+pub const HEAP_PROFILE_FILE_NAME: &str = "heap_profile.json";
+
+#[derive(Debug, Clone, Default)]
+pub struct ProfileArgs {
+    // CPU flamegraph of the whole command; `Some(None)` writes `<run dir>/profile.svg`.
+    pub svg: Option<Option<PathBuf>>,
+    // Allocation counts and peak live bytes, written to `<run dir>/heap_profile.json`.
+    pub heap: bool,
+}
+
+impl ProfileArgs {
+    pub fn enabled(&self) -> bool {
+        self.svg.is_some() || self.heap
+    }
+}
+
+#[cfg(feature = "pprof")]
+type CpuProfiler = kairos_infrastructure::profiling::CpuProfiler;
+
+// Stand-in so the call sites stay the same when built without pprof; `start` always fails.
+#[cfg(not(feature = "pprof"))]
+struct CpuProfiler;
+
+#[cfg(not(feature = "pprof"))]
+impl CpuProfiler {
+    fn start(_frequency_hz: i32) -> Result<Self, String> {
+        Err("--profile-svg requires kairos-alloy feature `pprof`".to_string())
+    }
+
+    fn write_flamegraph(self, _path: &std::path::Path) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+// Runs `command` under the requested profilers and writes the results next to its artifacts: the
+// run dir for backtest/paper (whose manifest is then refreshed), the sweep dir for sweeps. The
+// written paths are added to the result JSON under `profile`.
+pub fn profiled(
+    args: &ProfileArgs,
+    command: impl FnOnce() -> Result<serde_json::Value, CliError>,
+) -> Result<serde_json::Value, CliError> {
+    if !args.enabled() {
+        return command();
+    }
+    if args.svg.is_some() && !cfg!(feature = "pprof") {
+        return Err(CliError::usage(
+            "--profile-svg requires kairos-alloy feature `pprof`",
+        ));
+    }
+    if args.heap && !cfg!(feature = "heap-profile") {
+        return Err(CliError::usage(
+            "--profile-heap requires kairos-alloy feature `heap-profile`",
+        ));
+    }
+    let cpu = args
+        .svg
+        .as_ref()
+        .map(|_| CpuProfiler::start(100))
+        .transpose()?;
+    let heap_start = args.heap.then(HeapStats::start_window);
+
+    let mut result = command()?;
+    let heap_end = HeapStats::snapshot();
+
+    let (target_dir, is_run_dir) = output_dir(&result)?;
+    let mut written = serde_json::Map::new();
+    if let (Some(cpu), Some(svg)) = (cpu, args.svg.as_ref()) {
+        let path = svg
+            .clone()
+            .unwrap_or_else(|| target_dir.join(FLAMEGRAPH_FILE_NAME));
+        cpu.write_flamegraph(&path)?;
+        written.insert("flamegraph_svg".into(), path.display().to_string().into());
+    }
+    if let Some(heap_start) = heap_start {
+        let path = target_dir.join(HEAP_PROFILE_FILE_NAME);
+        let raw = serde_json::to_string_pretty(&heap_end.to_json_since(&heap_start))
+            .map_err(|err| format!("failed to serialize heap profile: {err}"))?;
+        std::fs::write(&path, format!("{raw}\n"))
+            .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
+        written.insert("heap_json".into(), path.display().to_string().into());
+    }
+    if is_run_dir {
+        FilesystemArtifactWriter::new().write_manifest(&target_dir)?;
+    }
+    tracing::info!(dir = %target_dir.display(), "wrote profile");
+    result["profile"] = written.into();
+    Ok(result)
+}
+
+fn output_dir(result: &serde_json::Value) -> Result<(PathBuf, bool), CliError> {
+    if let Some(run_dir) = result["artifacts"]["run_dir"].as_str() {
+        return Ok((PathBuf::from(run_dir), true));
+    }
+    if let Some(sweep_dir) = result["sweep_dir"].as_str() {
+        return Ok((PathBuf::from(sweep_dir), false));
+    }
+    Err(CliError::usage(
+        "profiling is only supported for backtest, paper and sweep",
+    ))
+}
+
+#[cfg(all(test, feature = "pprof"))]
+mod tests {
+    use super::{profiled, ProfileArgs, FLAMEGRAPH_FILE_NAME};
+
+    #[test]
+    fn profiled_writes_flamegraph_into_run_dir_and_refreshes_manifest() {
+        let run_dir =
+            std::env::temp_dir().join(format!("kairos_profile_test_{}", std::process::id()));
+        std::fs::create_dir_all(&run_dir).expect("run dir");
+        std::fs::write(run_dir.join("summary.json"), "{}").expect("summary");
+        let args = ProfileArgs {
+            svg: Some(None),
+            heap: false,
+        };
+
+        let result = profiled(&args, || {
+            let mut acc = 0_u64;
+            for i in 0..2_000_000_u64 {
+                acc = acc.wrapping_mul(31).wrapping_add(i);
+            }
+            Ok(serde_json::json!({
+                "status": "ok",
+                "acc": acc,
+                "artifacts": { "run_dir": run_dir.display().to_string() },
+            }))
+        })
+        .expect("profiled");
+
+        let svg = run_dir.join(FLAMEGRAPH_FILE_NAME);
+        assert_eq!(
+            result["profile"]["flamegraph_svg"],
+            svg.display().to_string()
+        );
+        assert!(svg.is_file());
+        let manifest = std::fs::read_to_string(run_dir.join("manifest.json")).expect("manifest");
+        assert!(manifest.contains(FLAMEGRAPH_FILE_NAME), "{manifest}");
+        let _ = std::fs::remove_dir_all(&run_dir);
+    }
+}
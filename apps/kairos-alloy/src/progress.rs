@@ -0,0 +1,256 @@
+use crate::output::CliError;
+use kairos_domain::repositories::market_data::{MarketDataRepository, OhlcvCoverage, OhlcvQuery};
+use kairos_domain::services::engine::backtest::{BarProgress, RunControl};
+use kairos_domain::services::ohlcv::DataQualityReport;
+use kairos_domain::services::progress::{ProgressEvent, SweepProgress};
+use kairos_domain::value_objects::bar::Bar;
+use serde_json::json;
+use std::sync::Mutex;
+use std::time::Instant;
+
+pub const DEFAULT_PROGRESS_EVERY_BARS: u64 = 1_000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProgressFormat {
+    // Human-readable sweep lines on stderr; nothing for backtest/paper.
+    #[default]
+    Text,
+    // One JSON event per line: `stage` events plus `progress` every N bars (or per sweep trial).
+    Ndjson,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProgressArgs {
+    pub format: ProgressFormat,
+    pub every_bars: u64,
+    // Stream events on stdout (before the result line) instead of stderr.
+    pub stdout: bool,
+}
+
+impl Default for ProgressArgs {
+    fn default() -> Self {
+        Self {
+            format: ProgressFormat::Text,
+            every_bars: DEFAULT_PROGRESS_EVERY_BARS,
+            stdout: false,
+        }
+    }
+}
+
+// Emits the `--progress ndjson` events of one headless command. Every event carries `event`,
+// `mode` and `elapsed_ms`.
+pub struct ProgressReporter {
+    args: ProgressArgs,
+    mode: &'static str,
+    started: Instant,
+    // First/last timestamp of the loaded bars, used for the bar ETA.
+    range: Mutex<Option<(i64, i64)>>,
+}
+
+impl ProgressReporter {
+    pub fn new(args: ProgressArgs, mode: &'static str) -> Self {
+        Self {
+            args,
+            mode,
+            started: Instant::now(),
+            range: Mutex::new(None),
+        }
+    }
+
+    pub fn ndjson(&self) -> bool {
+        self.args.format == ProgressFormat::Ndjson
+    }
+
+    fn elapsed_ms(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
+    }
+
+    fn emit(&self, event: &str, fields: serde_json::Value) {
+        if !self.ndjson() {
+            return;
+        }
+        let mut value = json!({
+            "event": event,
+            "mode": self.mode,
+            "elapsed_ms": self.elapsed_ms(),
+        });
+        if let (Some(target), serde_json::Value::Object(fields)) = (value.as_object_mut(), fields) {
+            target.extend(fields);
+        }
+        if self.args.stdout {
+            println!("{value}");
+        } else {
+            eprintln!("{value}");
+        }
+    }
+
+    pub fn stage(&self, stage: &str) {
+        self.emit("stage", json!({ "stage": stage }));
+    }
+
+    // Pipeline stages of the use case carry a `state` (`started`/`finished`, with `duration_ms`),
+    // which keeps them apart from the `started`/`finished` stages of the command itself.
+    pub fn event(&self, event: ProgressEvent<'_>) {
+        match event {
+            ProgressEvent::StageStarted { stage } => {
+                self.emit("stage", json!({ "stage": stage, "state": "started" }))
+            }
+            ProgressEvent::StageFinished { stage, duration_ms } => self.emit(
+                "stage",
+                json!({ "stage": stage, "state": "finished", "duration_ms": duration_ms }),
+            ),
+            ProgressEvent::BarProcessed(progress) => self.bar(progress),
+            ProgressEvent::TrialCompleted(progress) => self.sweep(progress),
+            ProgressEvent::RowsWritten {
+                first_ts,
+                last_ts,
+                rows,
+                total_rows,
+            } => self.emit(
+                "progress",
+                json!({
+                    "rows_written": rows,
+                    "total_rows": total_rows,
+                    "first_ts": first_ts,
+                    "last_ts": last_ts,
+                }),
+            ),
+            ProgressEvent::RequestRetried {
+                attempt,
+                delay_ms,
+                reason,
+            } => self.emit(
+                "retry",
+                json!({ "attempt": attempt, "delay_ms": delay_ms, "reason": reason }),
+            ),
+        }
+    }
+
+    pub fn finished(&self, result: &Result<serde_json::Value, CliError>) {
+        match result {
+            Ok(_) => self.emit("stage", json!({ "stage": "finished", "status": "ok" })),
+            Err(err) => self.emit(
+                "stage",
+                json!({ "stage": "finished", "status": "error", "error": err.message }),
+            ),
+        }
+    }
+
+    fn bar(&self, progress: &BarProgress) {
+        let bars_processed = progress.bar_index + 1;
+        if !bars_processed.is_multiple_of(self.args.every_bars.max(1)) {
+            return;
+        }
+        let range = self.range.lock().ok().and_then(|range| *range);
+        let eta_ms = range.and_then(|(first_ts, last_ts)| {
+            bar_eta_ms(self.elapsed_ms(), first_ts, last_ts, progress.timestamp)
+        });
+        self.emit(
+            "progress",
+            json!({
+                "bars_processed": bars_processed,
+                "timestamp": progress.timestamp,
+                "equity": progress.equity,
+                "eta_ms": eta_ms,
+            }),
+        );
+    }
+
+    fn sweep(&self, progress: &SweepProgress) {
+        self.emit(
+            "progress",
+            json!({
+                "trials_completed": progress.completed_runs,
+                "trials_total": progress.total_runs,
+                "ok": progress.ok_runs,
+                "skipped": progress.skipped_runs,
+                "error": progress.error_runs,
+                "pruned": progress.pruned_runs,
+                "last_run_id": progress.last_run_id,
+                "last_status": progress.last_status,
+                "last_trial_ms": progress.last_trial_ms,
+                "workers": progress.workers,
+                "eta_ms": progress.eta_ms,
+            }),
+        );
+    }
+
+    // Wraps the OHLCV source so loading the bars emits a `data_loaded` stage and sets the time
+    // range the bar ETA is measured against.
+    pub fn market_data<'a>(&'a self, inner: &'a dyn MarketDataRepository) -> TrackedMarketData<'a> {
+        TrackedMarketData {
+            inner,
+            reporter: self,
+        }
+    }
+}
+
+pub struct TrackedMarketData<'a> {
+    inner: &'a dyn MarketDataRepository,
+    reporter: &'a ProgressReporter,
+}
+
+impl TrackedMarketData<'_> {
+    fn loaded(
+        &self,
+        (bars, report): (Vec<Bar>, DataQualityReport),
+    ) -> (Vec<Bar>, DataQualityReport) {
+        let first_ts = bars.first().map(|bar| bar.timestamp);
+        let last_ts = bars.last().map(|bar| bar.timestamp);
+        if let (Some(first), Some(last), Ok(mut range)) =
+            (first_ts, last_ts, self.reporter.range.lock())
+        {
+            *range = Some((first, last));
+        }
+        self.reporter.emit(
+            "stage",
+            json!({
+                "stage": "data_loaded",
+                "bars": bars.len(),
+                "first_ts": first_ts,
+                "last_ts": last_ts,
+            }),
+        );
+        (bars, report)
+    }
+}
+
+impl MarketDataRepository for TrackedMarketData<'_> {
+    fn load_ohlcv(&self, query: &OhlcvQuery) -> Result<(Vec<Bar>, DataQualityReport), String> {
+        Ok(self.loaded(self.inner.load_ohlcv(query)?))
+    }
+
+    fn load_ohlcv_with_control(
+        &self,
+        query: &OhlcvQuery,
+        control: &dyn RunControl,
+    ) -> Result<(Vec<Bar>, DataQualityReport), String> {
+        Ok(self.loaded(self.inner.load_ohlcv_with_control(query, control)?))
+    }
+
+    fn ohlcv_coverage(&self, query: &OhlcvQuery) -> Result<OhlcvCoverage, String> {
+        self.inner.ohlcv_coverage(query)
+    }
+}
+
+// Share of the time range covered so far, extrapolated from the elapsed time.
+fn bar_eta_ms(elapsed_ms: u64, first_ts: i64, last_ts: i64, ts: i64) -> Option<u64> {
+    if last_ts <= first_ts || ts <= first_ts {
+        return None;
+    }
+    let done = ((ts - first_ts) as f64 / (last_ts - first_ts) as f64).min(1.0);
+    Some((elapsed_ms as f64 * (1.0 - done) / done).round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bar_eta_ms;
+
+    #[test]
+    fn bar_eta_extrapolates_from_the_covered_time_range() {
+        assert_eq!(bar_eta_ms(1_000, 0, 100, 25), Some(3_000));
+        assert_eq!(bar_eta_ms(1_000, 0, 100, 100), Some(0));
+        assert_eq!(bar_eta_ms(1_000, 0, 100, 0), None);
+        assert_eq!(bar_eta_ms(1_000, 50, 50, 50), None);
+    }
+}
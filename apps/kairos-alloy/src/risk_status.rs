@@ -0,0 +1,142 @@
+use crate::live_equity::LiveEquity;
+use kairos_application::config::RiskConfig;
+
+// Utilization at which a gauge turns from ok to warn, and then to error before the limit itself.
+const WARN_AT: f64 = 0.6;
+const ALERT_AT: f64 = 0.85;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Ok,
+    Warn,
+    Alert,
+}
+
+// One `[risk]` limit against its current value, for the Monitor risk panel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskGauge {
+    pub label: &'static str,
+    pub value: f64,
+    pub limit: f64,
+    // Shown as percentages (drawdown, exposure) rather than quantities.
+    pub percent: bool,
+}
+
+impl RiskGauge {
+    // Share of the limit in use, 1.0 and above once it is hit.
+    pub fn utilization(&self) -> f64 {
+        (self.value / self.limit).max(0.0)
+    }
+
+    pub fn level(&self) -> Level {
+        match self.utilization() {
+            u if u >= ALERT_AT => Level::Alert,
+            u if u >= WARN_AT => Level::Warn,
+            _ => Level::Ok,
+        }
+    }
+
+    pub fn text(&self) -> String {
+        if self.percent {
+            format!(
+                "{} {:.2}% / {:.2}%",
+                self.label,
+                self.value * 100.0,
+                self.limit * 100.0
+            )
+        } else {
+            format!("{} {:.4} / {:.4}", self.label, self.value, self.limit)
+        }
+    }
+}
+
+// The position of the running task after its latest streamed bar.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RiskSample {
+    pub position_qty: f64,
+    pub price: f64,
+    pub equity: f64,
+}
+
+// The gauges of the limits the engine enforces (a limit <= 0 is off, as in `RiskLimits`). Without
+// a position sample (a followed run) only the drawdown is known.
+pub fn risk_gauges(
+    limits: &RiskConfig,
+    equity: &LiveEquity,
+    sample: Option<RiskSample>,
+) -> Vec<RiskGauge> {
+    let mut gauges = Vec::new();
+    if limits.max_drawdown_pct > 0.0 && equity.start.is_some() {
+        gauges.push(RiskGauge {
+            label: "drawdown",
+            value: equity.drawdown(),
+            limit: limits.max_drawdown_pct,
+            percent: true,
+        });
+    }
+    let Some(sample) = sample else {
+        return gauges;
+    };
+    if limits.max_exposure_pct > 0.0 && sample.equity > 0.0 {
+        gauges.push(RiskGauge {
+            label: "exposure",
+            value: sample.position_qty * sample.price / sample.equity,
+            limit: limits.max_exposure_pct,
+            percent: true,
+        });
+    }
+    if limits.max_position_qty > 0.0 {
+        gauges.push(RiskGauge {
+            label: "position",
+            value: sample.position_qty,
+            limit: limits.max_position_qty,
+            percent: false,
+        });
+    }
+    gauges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{risk_gauges, Level, RiskSample};
+    use crate::live_equity::LiveEquity;
+    use kairos_application::config::RiskConfig;
+
+    #[test]
+    fn gauges_follow_the_enabled_limits_and_escalate_near_them() {
+        let limits = RiskConfig {
+            max_position_qty: 2.0,
+            max_drawdown_pct: 0.2,
+            max_exposure_pct: 0.0,
+        };
+        let mut equity = LiveEquity::default();
+        equity.push(1_000.0);
+        equity.push(900.0);
+
+        let followed = risk_gauges(&limits, &equity, None);
+        assert_eq!(followed.len(), 1);
+        assert_eq!(followed[0].label, "drawdown");
+        assert!((followed[0].utilization() - 0.5).abs() < 1e-9);
+        assert_eq!(followed[0].level(), Level::Ok);
+        assert_eq!(followed[0].text(), "drawdown 10.00% / 20.00%");
+
+        let sample = RiskSample {
+            position_qty: 1.8,
+            price: 100.0,
+            equity: 900.0,
+        };
+        let gauges = risk_gauges(&limits, &equity, Some(sample));
+        let labels: Vec<_> = gauges.iter().map(|g| g.label).collect();
+        assert_eq!(labels, ["drawdown", "position"]);
+        assert_eq!(gauges[1].level(), Level::Alert);
+
+        let limits = RiskConfig {
+            max_exposure_pct: 0.25,
+            ..limits
+        };
+        let exposure = &risk_gauges(&limits, &equity, Some(sample))[1];
+        assert_eq!(exposure.label, "exposure");
+        assert!((exposure.value - 0.2).abs() < 1e-9);
+        assert_eq!(exposure.level(), Level::Warn);
+    }
+}
@@ -0,0 +1,212 @@
+use kairos_application::registry;
+use kairos_domain::repositories::run_registry::{RunRecord, RunRegistry, RunSortKey};
+use kairos_infrastructure::artifacts::{FilesystemArtifactReader, FilesystemArtifactWriter};
+use kairos_infrastructure::persistence::sqlite_run_registry::SqliteRunRegistry;
+use std::path::{Path, PathBuf};
+
+// Sort orders the browser cycles through with `s`, best first.
+const SORT_KEYS: [RunSortKey; 3] = [
+    RunSortKey::RecordedAt,
+    RunSortKey::Sharpe,
+    RunSortKey::NetProfit,
+];
+
+// The TUI run browser: every run dir under the out dir (read from its summary.json, like
+// `runs list`), the sort order, the selection and whether the detail pane or a delete
+// confirmation is showing.
+pub struct RunBrowser {
+    pub out_dir: PathBuf,
+    pub runs: Vec<RunRecord>,
+    pub sort: RunSortKey,
+    pub selected: usize,
+    pub detail: bool,
+    pub confirm_delete: bool,
+    // Run dir marked with `m` as the left side of a config diff.
+    pub diff_base: Option<String>,
+}
+
+impl RunBrowser {
+    pub fn new(out_dir: PathBuf) -> Self {
+        Self {
+            out_dir,
+            runs: Vec::new(),
+            sort: RunSortKey::RecordedAt,
+            selected: 0,
+            detail: false,
+            confirm_delete: false,
+            diff_base: None,
+        }
+    }
+
+    // Rescans `out_dir`, keeping the selection on the same run when it is still there.
+    pub fn refresh(&mut self, out_dir: PathBuf) -> Result<(), String> {
+        let selected_dir = self.selected_run().map(|run| run.run_dir.clone());
+        self.out_dir = out_dir;
+        self.runs = registry::scan_runs(&self.out_dir)?;
+        self.confirm_delete = false;
+        self.sort_runs(selected_dir.as_deref());
+        Ok(())
+    }
+
+    pub fn cycle_sort(&mut self) {
+        let idx = SORT_KEYS.iter().position(|key| *key == self.sort);
+        self.sort = SORT_KEYS[idx.map_or(0, |idx| (idx + 1) % SORT_KEYS.len())];
+        let selected_dir = self.selected_run().map(|run| run.run_dir.clone());
+        self.sort_runs(selected_dir.as_deref());
+    }
+
+    fn sort_runs(&mut self, keep: Option<&str>) {
+        registry::sort_best_first(&mut self.runs, self.sort);
+        self.selected = keep
+            .and_then(|dir| self.runs.iter().position(|run| run.run_dir == dir))
+            .unwrap_or(self.selected)
+            .min(self.runs.len().saturating_sub(1));
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+        self.confirm_delete = false;
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1).min(self.runs.len().saturating_sub(1));
+        self.confirm_delete = false;
+    }
+
+    pub fn select_run_dir(&mut self, run_dir: &str) -> bool {
+        match self.runs.iter().position(|run| run.run_dir == run_dir) {
+            Some(idx) => {
+                self.selected = idx;
+                self.confirm_delete = false;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn selected_run(&self) -> Option<&RunRecord> {
+        self.runs.get(self.selected)
+    }
+
+    // The detail pane: what `runs show` prints for the selected run.
+    pub fn detail_text(&self) -> Option<String> {
+        let run = self.selected_run()?;
+        let files = crate::runs::run_files(Path::new(&run.run_dir));
+        Some(crate::runs::format_run(run, &files))
+    }
+
+    // Rebuilds summary.json (and report.html when enabled) from the run's trades/equity CSVs.
+    pub fn regenerate_report(&mut self) -> Result<String, String> {
+        let run_dir = self
+            .selected_run()
+            .map(|run| PathBuf::from(&run.run_dir))
+            .ok_or_else(|| "no run selected".to_string())?;
+        let result = kairos_application::reporting::generate_report(
+            &run_dir,
+            &FilesystemArtifactReader::new(),
+            &FilesystemArtifactWriter::new(),
+        )
+        .map_err(|err| err.to_string())?;
+        self.refresh(self.out_dir.clone())?;
+        Ok(if result.wrote_html {
+            format!(
+                "regenerated summary.json and report.html for {}",
+                result.run_id
+            )
+        } else {
+            format!("regenerated summary.json for {}", result.run_id)
+        })
+    }
+
+    // Removes the selected run dir, and its registry.sqlite row when the registry exists.
+    pub fn delete_selected(&mut self) -> Result<String, String> {
+        self.confirm_delete = false;
+        let run = self
+            .selected_run()
+            .cloned()
+            .ok_or_else(|| "no run selected".to_string())?;
+        std::fs::remove_dir_all(&run.run_dir)
+            .map_err(|err| format!("failed to remove {}: {err}", run.run_dir))?;
+        let registry_path = registry::registry_path(&self.out_dir);
+        if registry_path.is_file() {
+            SqliteRunRegistry::open(&registry_path)?.remove_run(&run.run_dir)?;
+        }
+        self.detail = false;
+        self.refresh(self.out_dir.clone())?;
+        Ok(format!("deleted {}", run.run_dir))
+    }
+}
+
+pub fn sort_label(key: RunSortKey) -> &'static str {
+    match key {
+        RunSortKey::RecordedAt => "date",
+        RunSortKey::Sharpe => "sharpe",
+        RunSortKey::NetProfit => "net profit",
+        RunSortKey::MaxDrawdown => "max drawdown",
+        RunSortKey::WinRate => "win rate",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RunBrowser;
+    use kairos_domain::repositories::run_registry::RunSortKey;
+    use std::path::PathBuf;
+
+    fn write_run(out_dir: &std::path::Path, name: &str, sharpe: f64, net_profit: f64) {
+        let run_dir = out_dir.join(name);
+        std::fs::create_dir_all(&run_dir).expect("run dir");
+        std::fs::write(
+            run_dir.join("summary.json"),
+            format!(r#"{{"sharpe": {sharpe}, "net_profit": {net_profit}}}"#),
+        )
+        .expect("summary");
+    }
+
+    fn out_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("kairos_run_browser_{name}"));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("out dir");
+        dir
+    }
+
+    #[test]
+    fn sorting_keeps_the_selected_run() {
+        let dir = out_dir("sort");
+        write_run(&dir, "a", 0.5, 30.0);
+        write_run(&dir, "b", 2.0, 10.0);
+        write_run(&dir, "c", 1.0, 20.0);
+
+        let mut browser = RunBrowser::new(dir.clone());
+        browser.refresh(dir.clone()).expect("refresh");
+        browser.cycle_sort();
+        assert_eq!(browser.sort, RunSortKey::Sharpe);
+        let order: Vec<&str> = browser.runs.iter().map(|r| r.run_id.as_str()).collect();
+        assert_eq!(order, ["b", "c", "a"]);
+
+        browser.selected = 1;
+        browser.cycle_sort();
+        assert_eq!(browser.sort, RunSortKey::NetProfit);
+        assert_eq!(browser.selected_run().expect("selected").run_id, "c");
+        browser.cycle_sort();
+        assert_eq!(browser.sort, RunSortKey::RecordedAt);
+    }
+
+    #[test]
+    fn delete_removes_the_run_dir_and_rescans() {
+        let dir = out_dir("delete");
+        write_run(&dir, "keep", 1.0, 1.0);
+        write_run(&dir, "drop", 2.0, 2.0);
+
+        let mut browser = RunBrowser::new(dir.clone());
+        browser.sort = RunSortKey::Sharpe;
+        browser.refresh(dir.clone()).expect("refresh");
+        assert_eq!(browser.selected_run().expect("selected").run_id, "drop");
+        browser.delete_selected().expect("delete");
+
+        assert!(!dir.join("drop").exists());
+        assert_eq!(browser.runs.len(), 1);
+        assert_eq!(browser.selected_run().expect("selected").run_id, "keep");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,326 @@
+use chrono::{DateTime, Utc};
+use kairos_application::registry::{self, CleanPolicy};
+use kairos_domain::repositories::run_registry::{RunRecord, RunRegistry, RunSortKey};
+use kairos_domain::value_objects::timeframe::parse_duration_like_seconds;
+use kairos_infrastructure::persistence::sqlite_run_registry::SqliteRunRegistry;
+use std::path::{Path, PathBuf};
+
+// `kairos-alloy runs ...`: browse and prune run directories straight from disk (summary.json),
+// so it works without a registry; `clean` also drops the removed runs from registry.sqlite.
+pub enum RunsCommand {
+    List {
+        out_dir: PathBuf,
+        sort: Option<String>,
+        limit: Option<usize>,
+    },
+    Show {
+        out_dir: PathBuf,
+        id: String,
+    },
+    Clean {
+        out_dir: PathBuf,
+        older_than: Option<String>,
+        keep_best: Option<String>,
+        dry_run: bool,
+    },
+}
+
+// `--out-dir`, then the config's `paths.out_dir`, then `runs`.
+pub fn resolve_out_dir(
+    out_dir: Option<PathBuf>,
+    config_path: Option<&Path>,
+) -> Result<PathBuf, String> {
+    if let Some(out_dir) = out_dir {
+        return Ok(out_dir);
+    }
+    match config_path {
+        Some(path) => {
+            let config =
+                kairos_application::config::load_config(path).map_err(|err| err.to_string())?;
+            Ok(PathBuf::from(config.paths.out_dir))
+        }
+        None => Ok(PathBuf::from("runs")),
+    }
+}
+
+pub enum RunsOutput {
+    List {
+        out_dir: PathBuf,
+        runs: Vec<RunRecord>,
+    },
+    Show {
+        run: Box<RunRecord>,
+        files: Vec<(String, u64)>,
+    },
+    Clean {
+        dry_run: bool,
+        removed: Vec<RunRecord>,
+    },
+}
+
+impl RunsOutput {
+    pub fn to_text(&self) -> String {
+        match self {
+            RunsOutput::List { out_dir, runs } if runs.is_empty() => {
+                format!("no runs found under {}", out_dir.display())
+            }
+            RunsOutput::List { runs, .. } => format_table(runs),
+            RunsOutput::Show { run, files } => format_run(run, files),
+            RunsOutput::Clean { dry_run, removed } => {
+                let verb = if *dry_run { "would remove" } else { "removed" };
+                let mut out = format!("{verb} {} run(s)", removed.len());
+                for run in removed {
+                    out.push_str(&format!("\n  {}", run.run_dir));
+                }
+                out
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            RunsOutput::List { out_dir, runs } => serde_json::json!({
+                "status": "ok",
+                "mode": "runs_list",
+                "out_dir": out_dir.display().to_string(),
+                "runs": runs,
+            }),
+            RunsOutput::Show { run, files } => serde_json::json!({
+                "status": "ok",
+                "mode": "runs_show",
+                "run": run,
+                "files": files
+                    .iter()
+                    .map(|(name, len)| serde_json::json!({ "name": name, "bytes": len }))
+                    .collect::<Vec<_>>(),
+            }),
+            RunsOutput::Clean { dry_run, removed } => serde_json::json!({
+                "status": "ok",
+                "mode": "runs_clean",
+                "dry_run": dry_run,
+                "removed": removed.iter().map(|run| run.run_dir.as_str()).collect::<Vec<_>>(),
+            }),
+        }
+    }
+}
+
+pub fn run_runs(command: RunsCommand) -> Result<RunsOutput, String> {
+    match command {
+        RunsCommand::List {
+            out_dir,
+            sort,
+            limit,
+        } => {
+            let key = sort
+                .as_deref()
+                .map(RunSortKey::parse)
+                .transpose()?
+                .unwrap_or(RunSortKey::RecordedAt);
+            let mut runs = registry::scan_runs(&out_dir)?;
+            registry::sort_best_first(&mut runs, key);
+            if let Some(limit) = limit {
+                runs.truncate(limit);
+            }
+            Ok(RunsOutput::List { out_dir, runs })
+        }
+        RunsCommand::Show { out_dir, id } => {
+            let run = Box::new(registry::find_run(&out_dir, &id)?);
+            let files = run_files(Path::new(&run.run_dir));
+            Ok(RunsOutput::Show { run, files })
+        }
+        RunsCommand::Clean {
+            out_dir,
+            older_than,
+            keep_best,
+            dry_run,
+        } => {
+            let policy = CleanPolicy {
+                older_than_secs: older_than
+                    .as_deref()
+                    .map(|raw| {
+                        parse_duration_like_seconds(raw)
+                            .map_err(|err| format!("invalid --older-than '{raw}': {err}"))
+                    })
+                    .transpose()?,
+                keep_best: keep_best
+                    .as_deref()
+                    .map(CleanPolicy::parse_keep_best)
+                    .transpose()?,
+            };
+            let registry_path = registry::registry_path(&out_dir);
+            let sqlite = if registry_path.is_file() && !dry_run {
+                Some(SqliteRunRegistry::open(&registry_path)?)
+            } else {
+                None
+            };
+            let removed = registry::clean_runs(
+                &out_dir,
+                &policy,
+                Utc::now().timestamp(),
+                dry_run,
+                sqlite.as_ref().map(|r| r as &dyn RunRegistry),
+            )?;
+            Ok(RunsOutput::Clean { dry_run, removed })
+        }
+    }
+}
+
+fn format_table(runs: &[RunRecord]) -> String {
+    let header = [
+        "RUN_ID",
+        "SYMBOL",
+        "TF",
+        "TRADES",
+        "NET_PROFIT",
+        "SHARPE",
+        "MAX_DD",
+        "WIN_RATE",
+        "RECORDED_AT",
+    ];
+    let rows: Vec<[String; 9]> = runs
+        .iter()
+        .map(|run| {
+            [
+                run.run_id.clone(),
+                run.symbol.clone().unwrap_or_else(|| "-".to_string()),
+                run.timeframe.clone().unwrap_or_else(|| "-".to_string()),
+                run.trades.to_string(),
+                format!("{:.2}", run.net_profit),
+                format!("{:.3}", run.sharpe),
+                format!("{:.2}%", run.max_drawdown * 100.0),
+                format!("{:.1}%", run.win_rate * 100.0),
+                format_timestamp(run.recorded_at),
+            ]
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = header.iter().map(|h| h.len()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut lines = Vec::with_capacity(rows.len() + 1);
+    lines.push(format_row(header.iter().map(|h| h.to_string()), &widths));
+    for row in rows {
+        lines.push(format_row(row.into_iter(), &widths));
+    }
+    lines.join("\n")
+}
+
+pub(crate) fn format_row(cells: impl Iterator<Item = String>, widths: &[usize]) -> String {
+    cells
+        .zip(widths.iter())
+        .map(|(cell, width)| format!("{cell:<width$}"))
+        .collect::<Vec<_>>()
+        .join("  ")
+        .trim_end()
+        .to_string()
+}
+
+pub(crate) fn format_run(run: &RunRecord, files: &[(String, u64)]) -> String {
+    let mut lines = vec![
+        format!("run_id:       {}", run.run_id),
+        format!("run_dir:      {}", run.run_dir),
+        format!("symbol:       {}", run.symbol.as_deref().unwrap_or("-")),
+        format!("timeframe:    {}", run.timeframe.as_deref().unwrap_or("-")),
+        format!(
+            "period:       {} .. {}",
+            run.start
+                .map(format_timestamp)
+                .unwrap_or_else(|| "-".to_string()),
+            run.end
+                .map(format_timestamp)
+                .unwrap_or_else(|| "-".to_string())
+        ),
+        format!("bars:         {}", run.bars_processed),
+        format!("trades:       {}", run.trades),
+        format!("net_profit:   {:.4}", run.net_profit),
+        format!("sharpe:       {:.4}", run.sharpe),
+        format!("max_drawdown: {:.4}", run.max_drawdown),
+        format!("win_rate:     {:.4}", run.win_rate),
+        format!(
+            "config_hash:  {}",
+            run.config_hash.as_deref().unwrap_or("-")
+        ),
+        format!("recorded_at:  {}", format_timestamp(run.recorded_at)),
+    ];
+    if !run.notes.is_empty() {
+        lines.push("notes:".to_string());
+        lines.extend(run.notes.iter().map(|note| format!("  - {note}")));
+    }
+
+    if !files.is_empty() {
+        lines.push("files:".to_string());
+        lines.extend(
+            files
+                .iter()
+                .map(|(name, len)| format!("  {name} ({len} bytes)")),
+        );
+    }
+    lines.join("\n")
+}
+
+pub(crate) fn run_files(run_dir: &Path) -> Vec<(String, u64)> {
+    let mut files: Vec<(String, u64)> = std::fs::read_dir(run_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| {
+                    let meta = entry.metadata().ok()?;
+                    meta.is_file()
+                        .then(|| (entry.file_name().to_string_lossy().to_string(), meta.len()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+pub(crate) fn format_timestamp(ts: i64) -> String {
+    DateTime::<Utc>::from_timestamp(ts, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| ts.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_table;
+    use kairos_domain::repositories::run_registry::RunRecord;
+
+    #[test]
+    fn table_aligns_columns_and_formats_metrics() {
+        let run = RunRecord {
+            run_id: "btc_sma".to_string(),
+            run_dir: "runs/btc_sma".to_string(),
+            config_hash: None,
+            symbol: Some("BTC-USDT".to_string()),
+            timeframe: Some("1min".to_string()),
+            start: Some(0),
+            end: Some(60),
+            bars_processed: 2,
+            trades: 3,
+            win_rate: 0.5,
+            net_profit: 12.345,
+            sharpe: 1.5,
+            max_drawdown: 0.125,
+            recorded_at: 0,
+            notes: Vec::new(),
+        };
+        let table = format_table(&[run]);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("RUN_ID   SYMBOL"));
+        assert!(lines[1].starts_with("btc_sma  BTC-USDT"));
+        assert!(lines[1].contains("12.35"));
+        assert!(lines[1].contains("12.50%"));
+        assert!(lines[1].ends_with("1970-01-01 00:00"));
+        assert_eq!(
+            lines[0].find("SHARPE"),
+            lines[1].find("1.500"),
+            "columns should line up"
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use crate::ingest::KucoinIngestArgs;
+use crate::jobs::JobKind;
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+// A new entry's slot: just after the daily candle closes.
+pub const DEFAULT_AT: &str = "00:05";
+// The ingest before a run starts this long before the previous run, so bars that were still
+// open then are written again (the upsert keeps one row per bar).
+const INGEST_OVERLAP_HOURS: i64 = 24;
+
+// One `[[schedule]]` of `schedule.toml`: a validate/backtest of `config` every day at `at` (UTC),
+// optionally after ingesting `ingest_symbol` from KuCoin with the config's db/market settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScheduleEntry {
+    pub config: PathBuf,
+    #[serde(default = "default_kind")]
+    pub kind: JobKind,
+    pub at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingest_symbol: Option<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    // Epoch seconds of the last start (or of when the entry was added), so relaunching the TUI
+    // does not run the same day's slot twice.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run_utc: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_result: Option<String>,
+    // The job queue id of the run in progress.
+    #[serde(skip)]
+    pub job_id: Option<usize>,
+}
+
+fn default_kind() -> JobKind {
+    JobKind::Backtest
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl ScheduleEntry {
+    // Added now, so its first run is the next slot rather than one already past today.
+    pub fn new(config: PathBuf, ingest_symbol: Option<String>, now: DateTime<Utc>) -> Self {
+        Self {
+            config,
+            kind: JobKind::Backtest,
+            at: DEFAULT_AT.to_string(),
+            ingest_symbol,
+            enabled: true,
+            last_run_utc: Some(now.timestamp()),
+            last_result: None,
+            job_id: None,
+        }
+    }
+
+    // The latest slot at or before `now`.
+    fn slot(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let at = parse_at(&self.at).ok()?;
+        let today = now.date_naive().and_time(at).and_utc();
+        Some(if today <= now {
+            today
+        } else {
+            today - Duration::days(1)
+        })
+    }
+
+    fn ran_since(&self, slot: DateTime<Utc>) -> bool {
+        self.last_run_utc
+            .is_some_and(|last| last >= slot.timestamp())
+    }
+
+    // Slots missed while the TUI was closed collapse into one run when it opens again.
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.enabled
+            && self.job_id.is_none()
+            && self.slot(now).is_some_and(|slot| !self.ran_since(slot))
+    }
+
+    pub fn next_run(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let slot = self.slot(now)?;
+        Some(if self.ran_since(slot) {
+            slot + Duration::days(1)
+        } else {
+            slot
+        })
+    }
+
+    // Moves the slot by `minutes`, wrapping around midnight.
+    pub fn shift_at(&mut self, minutes: i64) {
+        let at = parse_at(&self.at).unwrap_or_default();
+        let (shifted, _) = at.overflowing_add_signed(Duration::minutes(minutes));
+        self.at = shifted.format("%H:%M").to_string();
+    }
+
+    fn ingest_args(&self, now: DateTime<Utc>) -> Option<KucoinIngestArgs> {
+        let symbol = self.ingest_symbol.clone()?;
+        let since = self.last_run_utc.unwrap_or(now.timestamp()) - INGEST_OVERLAP_HOURS * 3600;
+        Some(KucoinIngestArgs {
+            config_path: Some(self.config.clone()),
+            db_url: None,
+            symbol: Some(symbol),
+            market: None,
+            timeframe: None,
+            start: since.to_string(),
+            end: None,
+            exchange: None,
+            source: "kucoin".to_string(),
+            sleep_ms: 350,
+            batch_size: 500,
+            insert_mode: kairos_ingest::InsertMode::default(),
+            base_url: None,
+            resume: false,
+        })
+    }
+}
+
+pub fn parse_at(raw: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(raw.trim(), "%H:%M")
+        .map_err(|err| format!("invalid schedule time '{raw}' (expected HH:MM, UTC): {err}"))
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScheduleFile {
+    #[serde(default)]
+    pub schedule: Vec<ScheduleEntry>,
+}
+
+// A run the scheduler wants queued; `entry` indexes `Scheduler::entries`.
+#[derive(Debug, Clone)]
+pub struct DueRun {
+    pub entry: usize,
+    pub kind: JobKind,
+    pub config: PathBuf,
+    pub ingest: Option<KucoinIngestArgs>,
+}
+
+// The TUI Schedule panel. Entries only run while the TUI is open, through the job queue; every
+// change is written back to `path` (none in tests).
+#[derive(Debug, Clone, Default)]
+pub struct Scheduler {
+    pub entries: Vec<ScheduleEntry>,
+    pub selected: usize,
+    pub path: Option<PathBuf>,
+}
+
+impl Scheduler {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let mut scheduler = Self {
+            path: Some(path.to_path_buf()),
+            ..Self::default()
+        };
+        if !path.exists() {
+            return Ok(scheduler);
+        }
+        let raw = std::fs::read_to_string(path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        let file: ScheduleFile = toml::from_str(&raw)
+            .map_err(|err| format!("failed to parse {}: {err}", path.display()))?;
+        for entry in &file.schedule {
+            parse_at(&entry.at).map_err(|err| format!("{}: {err}", path.display()))?;
+            if entry.kind == JobKind::Sweep {
+                return Err(format!(
+                    "{}: sweeps cannot be scheduled (kind = validate | backtest)",
+                    path.display()
+                ));
+            }
+        }
+        scheduler.entries = file.schedule;
+        Ok(scheduler)
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let Some(path) = self.path.as_ref() else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|err| format!("failed to create {}: {err}", parent.display()))?;
+        }
+        let file = ScheduleFile {
+            schedule: self.entries.clone(),
+        };
+        let toml = toml::to_string_pretty(&file)
+            .map_err(|err| format!("failed to serialize the schedule: {err}"))?;
+        std::fs::write(path, toml)
+            .map_err(|err| format!("failed to write {}: {err}", path.display()))
+    }
+
+    pub fn add(&mut self, entry: ScheduleEntry) {
+        self.entries.push(entry);
+        self.selected = self.entries.len() - 1;
+    }
+
+    pub fn remove_selected(&mut self) -> Option<ScheduleEntry> {
+        if self.selected >= self.entries.len() {
+            return None;
+        }
+        let entry = self.entries.remove(self.selected);
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+        Some(entry)
+    }
+
+    pub fn selected_mut(&mut self) -> Option<&mut ScheduleEntry> {
+        self.entries.get_mut(self.selected)
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1).min(self.entries.len().saturating_sub(1));
+    }
+
+    // Marks the due entries as started at `now` and returns what to queue.
+    pub fn take_due(&mut self, now: DateTime<Utc>) -> Vec<DueRun> {
+        let due: Vec<usize> = (0..self.entries.len())
+            .filter(|&idx| self.entries[idx].is_due(now))
+            .collect();
+        due.into_iter().map(|idx| self.start(idx, now)).collect()
+    }
+
+    // Starts the selected entry outside its slot (`r`); the slot itself still runs if it is later
+    // today.
+    pub fn take_selected(&mut self, now: DateTime<Utc>) -> Option<DueRun> {
+        let entry = self.entries.get(self.selected)?;
+        entry
+            .job_id
+            .is_none()
+            .then(|| self.start(self.selected, now))
+    }
+
+    fn start(&mut self, idx: usize, now: DateTime<Utc>) -> DueRun {
+        let entry = &mut self.entries[idx];
+        let run = DueRun {
+            entry: idx,
+            kind: entry.kind,
+            config: entry.config.clone(),
+            ingest: entry.ingest_args(now),
+        };
+        entry.last_run_utc = Some(now.timestamp());
+        run
+    }
+
+    // Records the outcome of a scheduled job; false when `job_id` was not started by the schedule.
+    pub fn finish_job(&mut self, job_id: usize, outcome: String) -> bool {
+        let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.job_id == Some(job_id))
+        else {
+            return false;
+        };
+        entry.job_id = None;
+        entry.last_result = Some(outcome);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ScheduleEntry, Scheduler};
+    use chrono::{TimeZone, Utc};
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn entries_run_once_per_daily_slot_and_roundtrip_through_the_file() {
+        let added = Utc.with_ymd_and_hms(2026, 10, 15, 15, 0, 0).unwrap();
+        let mut scheduler = Scheduler::default();
+        scheduler.add(ScheduleEntry::new(
+            PathBuf::from("platform/ops/configs/sample.toml"),
+            Some("BTC-USDT".to_string()),
+            added,
+        ));
+        // Added after today's 00:05: nothing until tomorrow.
+        assert!(scheduler.take_due(added).is_empty());
+        let tomorrow = Utc.with_ymd_and_hms(2026, 10, 16, 0, 5, 0).unwrap();
+        assert_eq!(scheduler.entries[0].next_run(added), Some(tomorrow));
+
+        let due = scheduler.take_due(tomorrow + chrono::Duration::seconds(30));
+        assert_eq!(due.len(), 1);
+        let ingest = due[0].ingest.as_ref().expect("ingest first");
+        assert_eq!(ingest.symbol.as_deref(), Some("BTC-USDT"));
+        assert_eq!(ingest.start, (added.timestamp() - 24 * 3600).to_string());
+        scheduler.entries[0].job_id = Some(7);
+        assert!(scheduler
+            .take_due(tomorrow + chrono::Duration::hours(2))
+            .is_empty());
+        assert!(scheduler.finish_job(7, "backtest complete: runs/btc".to_string()));
+        assert!(!scheduler.finish_job(8, "other".to_string()));
+        assert!(scheduler
+            .take_due(tomorrow + chrono::Duration::hours(2))
+            .is_empty());
+
+        scheduler.entries[0].shift_at(-10);
+        assert_eq!(scheduler.entries[0].at, "23:55");
+
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let path = std::env::temp_dir().join(format!("kairos_tui_schedule_{unique}.toml"));
+        scheduler.path = Some(path.clone());
+        scheduler.save().expect("save");
+        let loaded = Scheduler::load(&path).expect("load");
+        assert_eq!(loaded.entries, scheduler.entries);
+
+        std::fs::write(&path, "[[schedule]]\nconfig = \"a.toml\"\nat = \"25:00\"\n")
+            .expect("write");
+        assert!(Scheduler::load(&path)
+            .expect_err("bad time")
+            .contains("expected HH:MM"));
+        let _ = std::fs::remove_file(&path);
+    }
+}
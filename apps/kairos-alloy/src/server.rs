@@ -0,0 +1,873 @@
+use crate::output::CliError;
+use axum::body::Bytes;
+use axum::extract::{Path as UrlPath, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use kairos_application::config::Config;
+use kairos_application::paper_trading::clock::ReplayPacing;
+use kairos_domain::services::engine::backtest::{CancellationToken, RunControl};
+use kairos_domain::services::progress::{ProgressEvent, ProgressSink};
+use kairos_infrastructure::artifacts::FilesystemArtifactWriter;
+use parking_lot::Mutex;
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+pub struct ServeOpts {
+    pub addr: SocketAddr,
+    // Jobs running at the same time; the rest wait as `queued`.
+    pub workers: usize,
+    // Root for `GET /v1/runs/{run_id}/summary` and where every job writes its run dir.
+    pub out_dir: PathBuf,
+    // Used as `agent.url` by jobs with `agent.mode = "remote"`.
+    pub agent_url: String,
+    // Required as `Authorization: Bearer <token>` on `/v1/*` when set; must be set to listen on
+    // anything but loopback.
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    Backtest,
+    Paper,
+}
+
+impl JobKind {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        match raw.trim().to_lowercase().as_str() {
+            "backtest" => Ok(JobKind::Backtest),
+            "paper" => Ok(JobKind::Paper),
+            other => Err(format!(
+                "invalid job kind '{other}' (expected backtest|paper)"
+            )),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            JobKind::Backtest => "backtest",
+            JobKind::Paper => "paper",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct JobProgress {
+    // Pipeline stage the job is in (`load_ohlcv`, `run_engine`, `write_outputs`, ...).
+    pub stage: Option<String>,
+    pub bars_processed: u64,
+    pub last_timestamp: Option<i64>,
+    pub equity: Option<f64>,
+    pub trades: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub id: String,
+    pub kind: JobKind,
+    pub run_id: String,
+    pub status: JobStatus,
+    pub submitted_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub progress: JobProgress,
+    pub run_dir: Option<PathBuf>,
+    pub error: Option<String>,
+    // Shared with the worker running the job; `POST /v1/jobs/{id}/cancel` trips it.
+    pub cancel: CancellationToken,
+}
+
+impl Job {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "job_id": self.id,
+            "kind": self.kind.as_str(),
+            "run_id": self.run_id,
+            "status": self.status.as_str(),
+            "submitted_at": self.submitted_at,
+            "started_at": self.started_at,
+            "finished_at": self.finished_at,
+            "progress": {
+                "stage": self.progress.stage,
+                "bars_processed": self.progress.bars_processed,
+                "last_timestamp": self.progress.last_timestamp,
+                "equity": self.progress.equity,
+                "trades": self.progress.trades,
+            },
+            "run_dir": self.run_dir.as_ref().map(|dir| dir.display().to_string()),
+            "error": self.error,
+        })
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(
+            self.status,
+            JobStatus::Succeeded | JobStatus::Failed | JobStatus::Cancelled
+        )
+    }
+
+    fn record_progress(&mut self, event: ProgressEvent<'_>) {
+        match event {
+            ProgressEvent::StageStarted { stage } => self.progress.stage = Some(stage.to_string()),
+            ProgressEvent::BarProcessed(progress) => {
+                self.progress.bars_processed = progress.bar_index + 1;
+                self.progress.last_timestamp = Some(progress.timestamp);
+                self.progress.equity = Some(progress.equity);
+                self.progress.trades += progress.trades_in_bar.len() as u64;
+            }
+            _ => {}
+        }
+    }
+}
+
+// In-memory job table; jobs do not survive a restart (their run dirs do).
+#[derive(Debug, Default)]
+pub struct JobStore {
+    next_id: u64,
+    jobs: BTreeMap<String, Job>,
+}
+
+impl JobStore {
+    pub fn submit(&mut self, kind: JobKind, run_id: &str) -> Job {
+        self.next_id += 1;
+        let job = Job {
+            id: format!("job-{:06}", self.next_id),
+            kind,
+            run_id: run_id.to_string(),
+            status: JobStatus::Queued,
+            submitted_at: now(),
+            started_at: None,
+            finished_at: None,
+            progress: JobProgress::default(),
+            run_dir: None,
+            error: None,
+            cancel: CancellationToken::new(),
+        };
+        self.jobs.insert(job.id.clone(), job.clone());
+        job
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Job> {
+        self.jobs.get(id)
+    }
+
+    pub fn list(&self) -> impl Iterator<Item = &Job> {
+        self.jobs.values()
+    }
+
+    // False when the job was cancelled while queued, in which case it must not run.
+    pub fn start(&mut self, id: &str) -> bool {
+        match self.jobs.get_mut(id) {
+            Some(job) if job.status == JobStatus::Queued => {
+                job.status = JobStatus::Running;
+                job.started_at = Some(now());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Queued jobs are cancelled on the spot; running ones stop at their next cancellation check
+    // and are marked `cancelled` when the worker returns. Finished jobs are left alone and their
+    // status is returned as the error; `None` for unknown ids.
+    pub fn cancel(&mut self, id: &str) -> Option<Result<Job, JobStatus>> {
+        let job = self.jobs.get_mut(id)?;
+        if job.is_finished() {
+            return Some(Err(job.status));
+        }
+        job.cancel.cancel();
+        if job.status == JobStatus::Queued {
+            job.status = JobStatus::Cancelled;
+            job.finished_at = Some(now());
+        }
+        Some(Ok(job.clone()))
+    }
+
+    pub fn finish(&mut self, id: &str, result: Result<PathBuf, String>) {
+        if let Some(job) = self.jobs.get_mut(id) {
+            job.finished_at = Some(now());
+            match result {
+                Ok(run_dir) => {
+                    job.status = JobStatus::Succeeded;
+                    job.run_dir = Some(run_dir);
+                }
+                Err(err) => {
+                    job.status = if job.cancel.is_cancelled() {
+                        JobStatus::Cancelled
+                    } else {
+                        JobStatus::Failed
+                    };
+                    job.error = Some(err);
+                }
+            }
+        }
+    }
+
+    fn record_progress(&mut self, id: &str, event: ProgressEvent<'_>) {
+        if let Some(job) = self.jobs.get_mut(id) {
+            job.record_progress(event);
+        }
+    }
+}
+
+pub struct JobRequest {
+    pub kind: JobKind,
+    pub config: Config,
+    pub config_toml: String,
+}
+
+// Config keys that name files or endpoints on the server's side; a job cannot set them.
+const SERVER_KEYS: &[(&str, &str)] = &[
+    ("db", "url"),
+    ("db", "cache_dir"),
+    ("paths", "sentiment_path"),
+];
+
+// Body of `POST /v1/jobs`: `{"kind": "backtest"|"paper", "config": <TOML string or JSON object>,
+// "set": ["key=value", ...]}`. `${ENV}` placeholders are not expanded, so a client cannot read
+// the server's environment; `db.url` may still come from the server's KAIROS_DB_URL.
+//
+// Whoever can reach the API runs jobs as the server's user, so the config they send only picks
+// what to run, never where the server reads or writes: `db.url`, `db.cache_dir`,
+// `paths.sentiment_path` and file-based `paths.sentiment_sources` are rejected (also through
+// `set`), `paths.out_dir` is always `out_dir`, `agent.url` is always `agent_url`, and `run.run_id`
+// must be a plain directory name so the run dir stays under `out_dir`.
+pub fn parse_job_request(
+    body: &serde_json::Value,
+    out_dir: &Path,
+    agent_url: &str,
+) -> Result<JobRequest, String> {
+    let kind = JobKind::parse(
+        body.get("kind")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| "missing field 'kind'".to_string())?,
+    )?;
+    let mut merged: toml::Value = match body.get("config") {
+        Some(serde_json::Value::String(raw)) => {
+            toml::from_str(raw).map_err(|err| format!("failed to parse config TOML: {err}"))?
+        }
+        Some(value @ serde_json::Value::Object(_)) => toml::Value::try_from(value)
+            .map_err(|err| format!("failed to convert config object: {err}"))?,
+        Some(_) => return Err("'config' must be a TOML string or an object".to_string()),
+        None => return Err("missing field 'config'".to_string()),
+    };
+    let sets = match body.get("set") {
+        None | Some(serde_json::Value::Null) => Vec::new(),
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .map(|item| {
+                item.as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| "'set' entries must be strings".to_string())
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        Some(_) => return Err("'set' must be an array of key=value strings".to_string()),
+    };
+    for assignment in &sets {
+        kairos_application::config::overrides::apply_set(&mut merged, assignment)
+            .map_err(|err| err.to_string())?;
+    }
+    reject_server_keys(&merged)?;
+    pin_server_keys(&mut merged, out_dir, agent_url);
+    let config_toml = toml::to_string_pretty(&merged)
+        .map_err(|err| format!("failed to serialize config: {err}"))?;
+    let config: Config =
+        toml::from_str(&config_toml).map_err(|err| format!("invalid config: {err}"))?;
+    if !is_plain_run_id(&config.run.run_id) {
+        return Err(format!(
+            "run.run_id must be a plain directory name: {}",
+            config.run.run_id
+        ));
+    }
+    Ok(JobRequest {
+        kind,
+        config,
+        config_toml,
+    })
+}
+
+fn reject_server_keys(config: &toml::Value) -> Result<(), String> {
+    for (section, key) in SERVER_KEYS {
+        if config
+            .get(section)
+            .and_then(|table| table.get(key))
+            .is_some()
+        {
+            return Err(format!("{section}.{key} cannot be set through the API"));
+        }
+    }
+    let file_sources = config
+        .get("paths")
+        .and_then(|paths| paths.get("sentiment_sources"))
+        .and_then(|sources| sources.as_array())
+        .is_some_and(|sources| sources.iter().any(|source| source.get("path").is_some()));
+    if file_sources {
+        return Err("paths.sentiment_sources[].path cannot be set through the API".to_string());
+    }
+    Ok(())
+}
+
+// Missing sections are left for the config parse to report.
+fn pin_server_keys(config: &mut toml::Value, out_dir: &Path, agent_url: &str) {
+    let mut pin = |section: &str, key: &str, value: String| {
+        if let Some(table) = config.get_mut(section).and_then(|v| v.as_table_mut()) {
+            table.insert(key.to_string(), toml::Value::String(value));
+        }
+    };
+    pin("paths", "out_dir", out_dir.display().to_string());
+    pin("agent", "url", agent_url.to_string());
+}
+
+struct ServerState {
+    jobs: Mutex<JobStore>,
+    slots: Arc<Semaphore>,
+    out_dir: PathBuf,
+    agent_url: String,
+}
+
+pub fn serve(opts: ServeOpts) -> Result<(), CliError> {
+    if opts.workers == 0 {
+        return Err(CliError::usage("--workers must be > 0"));
+    }
+    if opts.token.is_none() && !opts.addr.ip().is_loopback() {
+        return Err(CliError::usage(format!(
+            "--token (or KAIROS_API_TOKEN) is required to listen on {}",
+            opts.addr
+        )));
+    }
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(|err| format!("failed to init tokio runtime: {err}"))?;
+    runtime
+        .block_on(async move {
+            let listener = tokio::net::TcpListener::bind(opts.addr)
+                .await
+                .map_err(|err| format!("failed to bind {}: {err}", opts.addr))?;
+            tracing::info!(addr = %opts.addr, workers = opts.workers, "api server listening");
+            eprintln!("kairos-alloy api listening on http://{}", opts.addr);
+            axum::serve(listener, router(&opts))
+                .await
+                .map_err(|err| format!("api server failed: {err}"))
+        })
+        .map_err(CliError::from)
+}
+
+fn router(opts: &ServeOpts) -> Router {
+    let state = Arc::new(ServerState {
+        jobs: Mutex::new(JobStore::default()),
+        slots: Arc::new(Semaphore::new(opts.workers)),
+        out_dir: opts.out_dir.clone(),
+        agent_url: opts.agent_url.clone(),
+    });
+    let api = Router::new()
+        .route("/v1/jobs", post(submit_job).get(list_jobs))
+        .route("/v1/jobs/{id}", get(get_job))
+        .route("/v1/jobs/{id}/cancel", post(cancel_job))
+        .route("/v1/jobs/{id}/summary", get(job_summary))
+        .route("/v1/runs/{run_id}/summary", get(run_summary))
+        .with_state(state);
+    let api = match opts.token.clone() {
+        Some(token) => api.route_layer(middleware::from_fn_with_state(
+            Arc::new(token),
+            require_token,
+        )),
+        None => api,
+    };
+    Router::new()
+        .route("/healthz", get(|| async { "OK" }))
+        .merge(api)
+}
+
+async fn require_token(State(token): State<Arc<String>>, request: Request, next: Next) -> Response {
+    let presented = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    match presented {
+        Some(presented) if tokens_match(presented, &token) => next.run(request).await,
+        _ => error_response(
+            StatusCode::UNAUTHORIZED,
+            "missing or invalid bearer token".to_string(),
+        ),
+    }
+}
+
+// Compares every byte so the response time does not reveal how much of the token matched.
+fn tokens_match(presented: &str, expected: &str) -> bool {
+    presented.len() == expected.len()
+        && presented
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0_u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+async fn submit_job(State(state): State<Arc<ServerState>>, body: Bytes) -> Response {
+    let request = serde_json::from_slice::<serde_json::Value>(&body)
+        .map_err(|err| format!("invalid JSON body: {err}"))
+        .and_then(|body| parse_job_request(&body, &state.out_dir, &state.agent_url));
+    let request = match request {
+        Ok(request) => request,
+        Err(err) => return error_response(StatusCode::BAD_REQUEST, err),
+    };
+    let job = state
+        .jobs
+        .lock()
+        .submit(request.kind, &request.config.run.run_id);
+    metrics::counter!("kairos.api.jobs_submitted_total", "kind" => request.kind.as_str())
+        .increment(1);
+
+    let id = job.id.clone();
+    let job_json = job.to_json();
+    let worker_state = state.clone();
+    tokio::spawn(async move {
+        let Ok(_permit) = worker_state.slots.clone().acquire_owned().await else {
+            return;
+        };
+        if !worker_state.jobs.lock().start(&id) {
+            return;
+        }
+        let progress_state = worker_state.clone();
+        let progress_id = id.clone();
+        let cancel = job.cancel.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            execute_job(&request, &cancel, &mut |event: ProgressEvent<'_>| {
+                progress_state
+                    .jobs
+                    .lock()
+                    .record_progress(&progress_id, event)
+            })
+        })
+        .await
+        .unwrap_or_else(|err| Err(format!("job aborted: {err}")));
+        if let Err(err) = &result {
+            tracing::warn!(job_id = %id, error = %err, "api job failed");
+        }
+        worker_state.jobs.lock().finish(&id, result);
+    });
+
+    (StatusCode::ACCEPTED, Json(job_json)).into_response()
+}
+
+async fn cancel_job(
+    State(state): State<Arc<ServerState>>,
+    UrlPath(id): UrlPath<String>,
+) -> Response {
+    let result = state.jobs.lock().cancel(&id);
+    match result {
+        None => error_response(StatusCode::NOT_FOUND, format!("unknown job: {id}")),
+        Some(Ok(job)) => {
+            metrics::counter!("kairos.api.jobs_cancelled_total", "kind" => job.kind.as_str())
+                .increment(1);
+            (StatusCode::ACCEPTED, Json(job.to_json())).into_response()
+        }
+        Some(Err(status)) => error_response(
+            StatusCode::CONFLICT,
+            format!("job {id} is {}", status.as_str()),
+        ),
+    }
+}
+
+async fn list_jobs(State(state): State<Arc<ServerState>>) -> Response {
+    let jobs: Vec<_> = state.jobs.lock().list().map(Job::to_json).collect();
+    Json(serde_json::json!({ "jobs": jobs })).into_response()
+}
+
+async fn get_job(State(state): State<Arc<ServerState>>, UrlPath(id): UrlPath<String>) -> Response {
+    match state.jobs.lock().get(&id) {
+        Some(job) => Json(job.to_json()).into_response(),
+        None => error_response(StatusCode::NOT_FOUND, format!("unknown job: {id}")),
+    }
+}
+
+async fn job_summary(
+    State(state): State<Arc<ServerState>>,
+    UrlPath(id): UrlPath<String>,
+) -> Response {
+    let job = state.jobs.lock().get(&id).cloned();
+    match job {
+        None => error_response(StatusCode::NOT_FOUND, format!("unknown job: {id}")),
+        Some(Job {
+            status: JobStatus::Succeeded,
+            run_dir: Some(run_dir),
+            ..
+        }) => read_summary(&run_dir),
+        Some(job) => error_response(
+            StatusCode::CONFLICT,
+            format!("job {id} is {}", job.status.as_str()),
+        ),
+    }
+}
+
+async fn run_summary(
+    State(state): State<Arc<ServerState>>,
+    UrlPath(run_id): UrlPath<String>,
+) -> Response {
+    if !is_plain_run_id(&run_id) {
+        return error_response(StatusCode::BAD_REQUEST, format!("invalid run id: {run_id}"));
+    }
+    read_summary(&state.out_dir.join(run_id))
+}
+
+fn execute_job(
+    request: &JobRequest,
+    control: &dyn RunControl,
+    progress: &mut dyn ProgressSink,
+) -> Result<PathBuf, String> {
+    let config = &request.config;
+    let market_data = crate::headless::build_market_data_repo(config)?;
+    let sentiment_repo = crate::headless::build_sentiment_repo(config)?;
+    let artifacts = FilesystemArtifactWriter::new();
+    let remote_agent = crate::headless::build_remote_agent(config)?;
+
+    let run_dir = match request.kind {
+        JobKind::Backtest => kairos_application::backtesting::run_backtest_streaming_control(
+            config,
+            &request.config_toml,
+            None,
+            market_data.as_ref(),
+            sentiment_repo.as_ref(),
+            &artifacts,
+            remote_agent,
+            control,
+            progress,
+        )
+        .map_err(|err| err.to_string())?,
+        JobKind::Paper => kairos_application::paper_trading::run_paper_streaming_control(
+            config,
+            &request.config_toml,
+            None,
+            market_data.as_ref(),
+            sentiment_repo.as_ref(),
+            &artifacts,
+            remote_agent,
+            None,
+            ReplayPacing::from_config(config),
+            control,
+            progress,
+        )
+        .map_err(|err| err.to_string())?,
+    };
+    crate::headless::record_in_registry(Path::new(&config.paths.out_dir), &run_dir);
+    Ok(run_dir)
+}
+
+fn read_summary(run_dir: &Path) -> Response {
+    let path = run_dir.join("summary.json");
+    let raw = match std::fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return error_response(
+                StatusCode::NOT_FOUND,
+                format!("summary not found: {}", path.display()),
+            )
+        }
+        Err(err) => {
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("failed to read {}: {err}", path.display()),
+            )
+        }
+    };
+    match serde_json::from_str::<serde_json::Value>(&raw) {
+        Ok(summary) => Json(summary).into_response(),
+        Err(err) => error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("invalid {}: {err}", path.display()),
+        ),
+    }
+}
+
+// A single directory name under `out_dir` (no separators, no `..`).
+fn is_plain_run_id(run_id: &str) -> bool {
+    !run_id.is_empty() && run_id != "." && run_id != ".." && !run_id.contains(['/', '\\'])
+}
+
+fn error_response(status: StatusCode, message: String) -> Response {
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
+
+fn now() -> String {
+    chrono::Utc::now().to_rfc3339()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        is_plain_run_id, parse_job_request, tokens_match, JobKind, JobRequest, JobStatus, JobStore,
+        ServeOpts,
+    };
+    use std::io::{Read, Write};
+    use std::path::{Path, PathBuf};
+
+    const SAMPLE: &str = include_str!("../../../platform/ops/configs/sample.toml");
+    const AGENT_URL: &str = "http://127.0.0.1:8000";
+
+    fn parse(body: serde_json::Value) -> Result<JobRequest, String> {
+        parse_job_request(&body, Path::new("/srv/runs"), AGENT_URL)
+    }
+
+    #[test]
+    fn job_request_accepts_toml_text_with_overrides() {
+        let request = parse(serde_json::json!({
+            "kind": "Paper",
+            "config": SAMPLE,
+            "set": ["run.run_id=api_run", "costs.fee_bps=2.5"],
+        }))
+        .expect("job request");
+        assert_eq!(request.kind, JobKind::Paper);
+        assert_eq!(request.config.run.run_id, "api_run");
+        assert_eq!(request.config.costs.fee_bps, 2.5);
+        assert!(request.config_toml.contains("api_run"));
+
+        let object: toml::Value = toml::from_str(SAMPLE).unwrap();
+        let request = parse(serde_json::json!({
+            "kind": "backtest",
+            "config": serde_json::to_value(&object).unwrap(),
+        }))
+        .expect("object config");
+        assert_eq!(request.kind, JobKind::Backtest);
+
+        let err = parse(serde_json::json!({"kind": "sweep", "config": SAMPLE}))
+            .err()
+            .expect("bad kind");
+        assert!(err.contains("backtest|paper"), "{err}");
+        let err = parse(serde_json::json!({"kind": "backtest"}))
+            .err()
+            .expect("missing config");
+        assert_eq!(err, "missing field 'config'");
+    }
+
+    #[test]
+    fn job_request_cannot_choose_server_paths_or_endpoints() {
+        let request = parse(serde_json::json!({
+            "kind": "backtest",
+            "config": SAMPLE.replace("http://127.0.0.1:8000", "http://10.0.0.5:9000"),
+            "set": ["paths.out_dir=/etc"],
+        }))
+        .expect("pinned keys are overwritten");
+        assert_eq!(request.config.paths.out_dir, "/srv/runs");
+        assert_eq!(request.config.agent.url, AGENT_URL);
+        assert!(!request.config_toml.contains("/etc"));
+        assert!(!request.config_toml.contains("10.0.0.5"));
+
+        for set in [
+            "db.url=postgres://evil:5432/x",
+            "db.cache_dir=/tmp/cache",
+            "paths.sentiment_path=/etc/passwd",
+        ] {
+            let key = set.split('=').next().unwrap();
+            let err = parse(serde_json::json!({
+                "kind": "backtest",
+                "config": SAMPLE,
+                "set": [set],
+            }))
+            .err()
+            .expect("server key rejected");
+            assert_eq!(err, format!("{key} cannot be set through the API"));
+        }
+
+        let with_file_source = format!(
+            "{SAMPLE}\n[[paths.sentiment_sources]]\nname = \"x\"\npath = \"/etc/shadow\"\n"
+        );
+        let err = parse(serde_json::json!({"kind": "backtest", "config": with_file_source}))
+            .err()
+            .expect("file sentiment source rejected");
+        assert!(err.contains("paths.sentiment_sources[].path"), "{err}");
+
+        let err = parse(serde_json::json!({
+            "kind": "backtest",
+            "config": SAMPLE,
+            "set": ["run.run_id=../../tmp/x"],
+        }))
+        .err()
+        .expect("run id escapes out_dir");
+        assert!(
+            err.starts_with("run.run_id must be a plain directory name"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn serve_refuses_public_addresses_without_a_token() {
+        let err = super::serve(ServeOpts {
+            addr: "0.0.0.0:0".parse().unwrap(),
+            workers: 1,
+            out_dir: PathBuf::from("runs"),
+            agent_url: AGENT_URL.to_string(),
+            token: None,
+        })
+        .unwrap_err();
+        assert_eq!(err.kind, crate::output::ErrorKind::Usage);
+        assert!(err.message.starts_with("--token"), "{err}");
+    }
+
+    #[test]
+    fn tokens_match_only_when_identical() {
+        assert!(tokens_match("s3cret", "s3cret"));
+        assert!(!tokens_match("s3cre", "s3cret"));
+        assert!(!tokens_match("s3creT", "s3cret"));
+    }
+
+    #[test]
+    fn job_store_tracks_lifecycle() {
+        let mut store = JobStore::default();
+        let job = store.submit(JobKind::Backtest, "run_a");
+        assert_eq!(job.id, "job-000001");
+        assert_eq!(job.status, JobStatus::Queued);
+
+        store.start(&job.id);
+        assert_eq!(store.get(&job.id).unwrap().status, JobStatus::Running);
+        store.finish(&job.id, Ok(PathBuf::from("runs/run_a")));
+        let done = store.get(&job.id).unwrap().to_json();
+        assert_eq!(done["status"], "succeeded");
+        assert_eq!(done["run_dir"], "runs/run_a");
+
+        let failed = store.submit(JobKind::Paper, "run_b");
+        store.finish(&failed.id, Err("boom".to_string()));
+        assert_eq!(store.get(&failed.id).unwrap().to_json()["error"], "boom");
+        assert_eq!(store.list().count(), 2);
+    }
+
+    #[test]
+    fn job_store_cancels_queued_and_running_jobs() {
+        let mut store = JobStore::default();
+        let queued = store.submit(JobKind::Backtest, "run_q");
+        let cancelled = store.cancel(&queued.id).unwrap().unwrap();
+        assert_eq!(cancelled.status, JobStatus::Cancelled);
+        assert!(!store.start(&queued.id));
+
+        let running = store.submit(JobKind::Backtest, "run_r");
+        assert!(store.start(&running.id));
+        let job = store.cancel(&running.id).unwrap().unwrap();
+        assert_eq!(job.status, JobStatus::Running);
+        assert!(running.cancel.is_cancelled());
+        store.finish(&running.id, Err("backtest cancelled".to_string()));
+        assert_eq!(
+            store.get(&running.id).unwrap().to_json()["status"],
+            "cancelled"
+        );
+
+        assert_eq!(
+            store.cancel(&running.id).unwrap().unwrap_err(),
+            JobStatus::Cancelled
+        );
+        assert!(store.cancel("job-999999").is_none());
+    }
+
+    #[test]
+    fn run_ids_cannot_escape_out_dir() {
+        assert!(is_plain_run_id("sma_btc_1m"));
+        assert!(!is_plain_run_id(".."));
+        assert!(!is_plain_run_id("../etc"));
+        assert!(!is_plain_run_id("a\\b"));
+    }
+
+    fn http(addr: std::net::SocketAddr, request: &str) -> String {
+        let mut stream = std::net::TcpStream::connect(addr).expect("connect");
+        stream.write_all(request.as_bytes()).expect("write");
+        let mut response = String::new();
+        stream.read_to_string(&mut response).expect("read");
+        response
+    }
+
+    #[test]
+    fn serves_health_summaries_and_rejects_bad_jobs() {
+        let out_dir = std::env::temp_dir().join(format!("kairos_api_test_{}", std::process::id()));
+        std::fs::create_dir_all(out_dir.join("run_1")).unwrap();
+        std::fs::write(
+            out_dir.join("run_1").join("summary.json"),
+            r#"{"trades":3}"#,
+        )
+        .unwrap();
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .unwrap();
+        let Ok(listener) = runtime.block_on(tokio::net::TcpListener::bind("127.0.0.1:0")) else {
+            eprintln!("skipping: cannot bind local test server");
+            return;
+        };
+        let addr = listener.local_addr().unwrap();
+        let app = super::router(&ServeOpts {
+            addr,
+            workers: 1,
+            out_dir: out_dir.clone(),
+            agent_url: AGENT_URL.to_string(),
+            token: Some("s3cret".to_string()),
+        });
+        runtime.spawn(async move { axum::serve(listener, app).await });
+
+        let health = http(
+            addr,
+            "GET /healthz HTTP/1.1\r\nHost: x\r\nConnection: close\r\n\r\n",
+        );
+        assert!(health.starts_with("HTTP/1.1 200"), "{health}");
+
+        let unauthorized = http(
+            addr,
+            "GET /v1/runs/run_1/summary HTTP/1.1\r\nHost: x\r\nConnection: close\r\n\r\n",
+        );
+        assert!(unauthorized.starts_with("HTTP/1.1 401"), "{unauthorized}");
+
+        let summary = http(
+            addr,
+            "GET /v1/runs/run_1/summary HTTP/1.1\r\nHost: x\r\nAuthorization: Bearer s3cret\r\n\
+             Connection: close\r\n\r\n",
+        );
+        assert!(summary.starts_with("HTTP/1.1 200"), "{summary}");
+        assert!(summary.ends_with(r#"{"trades":3}"#), "{summary}");
+
+        let body = r#"{"kind":"backtest"}"#;
+        let rejected = http(
+            addr,
+            &format!(
+                "POST /v1/jobs HTTP/1.1\r\nHost: x\r\nAuthorization: Bearer s3cret\r\n\
+                 Content-Type: application/json\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            ),
+        );
+        assert!(rejected.starts_with("HTTP/1.1 400"), "{rejected}");
+        assert!(rejected.contains("missing field 'config'"), "{rejected}");
+
+        let missing = http(
+            addr,
+            "GET /v1/jobs/job-000042 HTTP/1.1\r\nHost: x\r\nAuthorization: Bearer s3cret\r\n\
+             Connection: close\r\n\r\n",
+        );
+        assert!(missing.starts_with("HTTP/1.1 404"), "{missing}");
+
+        let _ = std::fs::remove_dir_all(&out_dir);
+    }
+}
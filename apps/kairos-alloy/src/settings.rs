@@ -0,0 +1,72 @@
+use crate::notify::NotifySettings;
+use crate::theme::ThemeName;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+// TUI preferences kept across sessions in `settings.json`, next to `recent_configs.json`.
+// Missing keys fall back to their defaults, so older files keep loading.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TuiSettings {
+    pub theme: ThemeName,
+    pub notify: NotifySettings,
+}
+
+pub fn load_settings_from(path: &Path) -> Result<TuiSettings, String> {
+    if !path.exists() {
+        return Ok(TuiSettings::default());
+    }
+    let contents = std::fs::read_to_string(path)
+        .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+    serde_json::from_str(&contents)
+        .map_err(|err| format!("failed to parse {}: {err}", path.display()))
+}
+
+pub fn store_settings_to(path: &Path, settings: &TuiSettings) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("failed to create {}: {err}", parent.display()))?;
+    }
+    let json = serde_json::to_string_pretty(settings)
+        .map_err(|err| format!("failed to serialize settings: {err}"))?;
+    std::fs::write(path, json).map_err(|err| format!("failed to write {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_settings_from, store_settings_to, TuiSettings};
+    use crate::theme::ThemeName;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn settings_roundtrip_and_default_missing_keys() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let path = std::env::temp_dir().join(format!("kairos_settings_{unique}.json"));
+        assert_eq!(
+            load_settings_from(&path).expect("missing"),
+            TuiSettings::default()
+        );
+
+        let settings = TuiSettings {
+            theme: ThemeName::HighContrast,
+            ..TuiSettings::default()
+        };
+        store_settings_to(&path, &settings).expect("store");
+        assert!(std::fs::read_to_string(&path)
+            .expect("read")
+            .contains("\"high_contrast\""));
+        assert_eq!(load_settings_from(&path).expect("load"), settings);
+
+        std::fs::write(&path, "{}").expect("write");
+        assert_eq!(
+            load_settings_from(&path).expect("empty").theme,
+            ThemeName::Dark
+        );
+        std::fs::write(&path, r#"{"theme":"solarized"}"#).expect("write");
+        assert!(load_settings_from(&path).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+}
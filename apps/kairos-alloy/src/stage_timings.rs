@@ -0,0 +1,103 @@
+use kairos_infrastructure::reporting::{artifact_exists, open_artifact};
+use std::io::BufRead;
+use std::path::Path;
+
+// One pipeline stage of a run (load_ohlcv, resample_ohlcv, load_sentiment, align_sentiment,
+// run_engine) and its wall time, from the `timing` audit events of the run's logs.jsonl.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageTiming {
+    pub stage: String,
+    pub duration_ms: u64,
+}
+
+// Stages in the order they first ran, repeated stages summed; a run without logs.jsonl has none.
+// Compressed logs (`report.compression`) are read like plain ones.
+pub fn load_stage_timings(run_dir: &Path) -> Result<Vec<StageTiming>, String> {
+    let path = run_dir.join("logs.jsonl");
+    if !artifact_exists(&path) {
+        return Ok(Vec::new());
+    }
+    let mut timings: Vec<StageTiming> = Vec::new();
+    for line in std::io::BufReader::new(open_artifact(&path)?).lines() {
+        let line = line.map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        // Agent runs log an event per bar; only the few timing lines are worth parsing.
+        if !line.contains("\"timing\"") {
+            continue;
+        }
+        let Ok(event) = serde_json::from_str::<serde_json::Value>(&line) else {
+            continue;
+        };
+        if event["stage"] != "timing" {
+            continue;
+        }
+        let (Some(stage), Some(duration_ms)) = (
+            event["action"].as_str(),
+            event["details"]["duration_ms"].as_u64(),
+        ) else {
+            continue;
+        };
+        match timings.iter_mut().find(|t| t.stage == stage) {
+            Some(timing) => timing.duration_ms += duration_ms,
+            None => timings.push(StageTiming {
+                stage: stage.to_string(),
+                duration_ms,
+            }),
+        }
+    }
+    Ok(timings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_stage_timings, StageTiming};
+    use kairos_domain::repositories::artifacts::ArtifactCompression;
+    use kairos_infrastructure::reporting::compress_artifact;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn timings_come_from_the_timing_audit_events() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let dir = std::env::temp_dir().join(format!("kairos_tui_timings_{unique}"));
+        std::fs::create_dir_all(&dir).expect("mkdir");
+        assert!(load_stage_timings(&dir).expect("no logs").is_empty());
+
+        let lines = [
+            r#"{"run_id":"r","timestamp":0,"stage":"timing","action":"load_ohlcv","details":{"duration_ms":120,"details":{"rows":10}}}"#,
+            r#"{"run_id":"r","timestamp":60,"stage":"agent","action":"timing","details":{}}"#,
+            r#"{"run_id":"r","timestamp":0,"stage":"timing","action":"run_engine","details":{"duration_ms":30,"details":{}}}"#,
+            "not json \"timing\"",
+            r#"{"run_id":"r","timestamp":0,"stage":"timing","action":"load_ohlcv","details":{"duration_ms":5,"details":{}}}"#,
+        ];
+        std::fs::write(dir.join("logs.jsonl"), lines.join("\n")).expect("write");
+
+        let timings = load_stage_timings(&dir).expect("timings");
+        assert_eq!(
+            timings,
+            vec![
+                StageTiming {
+                    stage: "load_ohlcv".to_string(),
+                    duration_ms: 125
+                },
+                StageTiming {
+                    stage: "run_engine".to_string(),
+                    duration_ms: 30
+                },
+            ]
+        );
+
+        // `report.compression` leaves only logs.jsonl.gz / logs.jsonl.zst behind.
+        for compression in [ArtifactCompression::Gzip, ArtifactCompression::Zstd] {
+            std::fs::write(dir.join("logs.jsonl"), lines.join("\n")).expect("write");
+            compress_artifact(&dir.join("logs.jsonl"), compression).expect("compress");
+            assert!(!dir.join("logs.jsonl").exists());
+            assert_eq!(
+                load_stage_timings(&dir).expect("compressed timings"),
+                timings
+            );
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,480 @@
+use crate::app::TextInput;
+use crate::sweep_leaderboard::{metric, next_sort_key, rank_value};
+use kairos_application::experiments::sweep::TrialOutcome;
+use kairos_domain::repositories::run_registry::RunSortKey;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+// A sweep written earlier: `<out_dir>/sweeps/<id>/manifest.json` (only written once it finished).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PastSweep {
+    pub id: String,
+    pub dir: PathBuf,
+    pub modified: SystemTime,
+}
+
+// Newest first.
+pub fn list_sweeps(out_dir: &Path) -> Vec<PastSweep> {
+    let mut sweeps: Vec<PastSweep> = std::fs::read_dir(out_dir.join("sweeps"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let dir = entry.path();
+            let modified = std::fs::metadata(dir.join("manifest.json"))
+                .and_then(|meta| meta.modified())
+                .ok()?;
+            Some(PastSweep {
+                id: entry.file_name().to_string_lossy().into_owned(),
+                dir,
+                modified,
+            })
+        })
+        .collect();
+    sweeps.sort_by(|a, b| b.modified.cmp(&a.modified).then(a.id.cmp(&b.id)));
+    sweeps
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    runs: Vec<ManifestRun>,
+}
+
+#[derive(Deserialize)]
+struct ManifestRun {
+    run_id: String,
+    split_id: String,
+    #[serde(default)]
+    params: BTreeMap<String, serde_json::Value>,
+    status: String,
+    metrics: Option<ManifestMetrics>,
+}
+
+#[derive(Deserialize)]
+struct ManifestMetrics {
+    net_profit: f64,
+    sharpe: f64,
+    max_drawdown: f64,
+}
+
+// The manifest's trials, shaped like the live leaderboard's; trial run dirs sit next to `sweeps/`.
+pub fn load_trials(sweep_dir: &Path) -> Result<Vec<TrialOutcome>, String> {
+    let path = sweep_dir.join("manifest.json");
+    let raw = std::fs::read_to_string(&path)
+        .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+    let manifest: Manifest = serde_json::from_str(&raw)
+        .map_err(|err| format!("failed to parse {}: {err}", path.display()))?;
+    let out_dir = sweep_dir
+        .parent()
+        .and_then(Path::parent)
+        .unwrap_or(Path::new("."));
+    Ok(manifest
+        .runs
+        .into_iter()
+        .map(|run| TrialOutcome {
+            run_dir: out_dir.join(&run.run_id).display().to_string(),
+            run_id: run.run_id,
+            split_id: run.split_id,
+            params: run
+                .params
+                .into_iter()
+                .map(|(path, value)| {
+                    let value = match value {
+                        serde_json::Value::String(raw) => raw,
+                        other => other.to_string(),
+                    };
+                    (path, value)
+                })
+                .collect(),
+            status: run.status,
+            net_profit: run.metrics.as_ref().map(|m| m.net_profit),
+            sharpe: run.metrics.as_ref().map(|m| m.sharpe),
+            max_drawdown: run.metrics.as_ref().map(|m| m.max_drawdown),
+        })
+        .collect())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FilterOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+// Two-character operators first, so `>=` is not read as `>`.
+const FILTER_OPS: [(&str, FilterOp); 6] = [
+    ("<=", FilterOp::Le),
+    (">=", FilterOp::Ge),
+    ("!=", FilterOp::Ne),
+    ("=", FilterOp::Eq),
+    ("<", FilterOp::Lt),
+    (">", FilterOp::Gt),
+];
+
+// `param op value`, e.g. `sma_fast>=10`; the param is its full path or its last segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamFilter {
+    param: String,
+    op: FilterOp,
+    value: String,
+}
+
+impl ParamFilter {
+    fn matches(&self, trial: &TrialOutcome) -> bool {
+        let Some((_, value)) = trial.params.iter().find(|(path, _)| {
+            *path == self.param
+                || path
+                    .rsplit_once('.')
+                    .is_some_and(|(_, last)| last == self.param)
+        }) else {
+            return false;
+        };
+        // Numbers compare as numbers, anything else only by (in)equality.
+        match (value.parse::<f64>(), self.value.parse::<f64>()) {
+            (Ok(actual), Ok(wanted)) => match self.op {
+                FilterOp::Eq => actual == wanted,
+                FilterOp::Ne => actual != wanted,
+                FilterOp::Lt => actual < wanted,
+                FilterOp::Le => actual <= wanted,
+                FilterOp::Gt => actual > wanted,
+                FilterOp::Ge => actual >= wanted,
+            },
+            _ => match self.op {
+                FilterOp::Eq => *value == self.value,
+                FilterOp::Ne => *value != self.value,
+                _ => false,
+            },
+        }
+    }
+}
+
+// Comma-separated filters, all of which must hold.
+pub fn parse_filters(raw: &str) -> Result<Vec<ParamFilter>, String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let (param, op, value) = FILTER_OPS
+                .iter()
+                .find_map(|(token, op)| {
+                    part.split_once(token)
+                        .map(|(param, value)| (param, *op, value))
+                })
+                .ok_or_else(|| {
+                    format!("filter '{part}': expected param op value (op: = != < <= > >=)")
+                })?;
+            let (param, value) = (param.trim(), value.trim());
+            if param.is_empty() || value.is_empty() {
+                return Err(format!("filter '{part}': missing param or value"));
+            }
+            Ok(ParamFilter {
+                param: param.to_string(),
+                op,
+                value: value.to_string(),
+            })
+        })
+        .collect()
+}
+
+// Points of the metric against one parameter. Non-numeric parameters are spread over their sorted
+// distinct values (x is the index into `categories`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scatter {
+    pub points: Vec<(f64, f64)>,
+    pub selected: Option<(f64, f64)>,
+    pub categories: Vec<String>,
+    pub x_bounds: [f64; 2],
+    pub y_bounds: [f64; 2],
+}
+
+// The TUI sweep history: past sweeps of the out dir, and the trials of the one opened, filtered by
+// parameter and ranked by the chosen metric.
+pub struct SweepHistory {
+    pub sweeps: Vec<PastSweep>,
+    pub selected_sweep: usize,
+    // Id of the sweep whose trials are shown; None while picking one.
+    pub opened: Option<String>,
+    pub trials: Vec<TrialOutcome>,
+    // Indices into `trials` that pass the filters, best first.
+    pub visible: Vec<usize>,
+    pub selected: usize,
+    pub sort: RunSortKey,
+    // Index into `param_names()` of the scatter's x axis.
+    pub param: usize,
+    pub filter_input: TextInput,
+    pub editing_filter: bool,
+    filters: Vec<ParamFilter>,
+}
+
+impl SweepHistory {
+    pub fn new() -> Self {
+        Self {
+            sweeps: Vec::new(),
+            selected_sweep: 0,
+            opened: None,
+            trials: Vec::new(),
+            visible: Vec::new(),
+            selected: 0,
+            sort: RunSortKey::Sharpe,
+            param: 0,
+            filter_input: TextInput::new(String::new()),
+            editing_filter: false,
+            filters: Vec::new(),
+        }
+    }
+
+    pub fn refresh(&mut self, out_dir: &Path) {
+        self.sweeps = list_sweeps(out_dir);
+        self.selected_sweep = self.selected_sweep.min(self.sweeps.len().saturating_sub(1));
+    }
+
+    pub fn open_selected(&mut self) -> Result<(), String> {
+        let Some(sweep) = self.sweeps.get(self.selected_sweep) else {
+            return Err("no finished sweeps in this out dir".to_string());
+        };
+        self.trials = load_trials(&sweep.dir)?;
+        self.opened = Some(sweep.id.clone());
+        self.selected = 0;
+        self.param = 0;
+        self.apply();
+        Ok(())
+    }
+
+    pub fn close(&mut self) {
+        self.opened = None;
+        self.trials.clear();
+        self.visible.clear();
+    }
+
+    pub fn select_prev(&mut self) {
+        match self.opened {
+            Some(_) => self.selected = self.selected.saturating_sub(1),
+            None => self.selected_sweep = self.selected_sweep.saturating_sub(1),
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        match self.opened {
+            Some(_) => {
+                self.selected = (self.selected + 1).min(self.visible.len().saturating_sub(1))
+            }
+            None => {
+                self.selected_sweep =
+                    (self.selected_sweep + 1).min(self.sweeps.len().saturating_sub(1))
+            }
+        }
+    }
+
+    pub fn param_names(&self) -> Vec<String> {
+        self.trials
+            .iter()
+            .flat_map(|trial| trial.params.iter().map(|(path, _)| path.clone()))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    pub fn current_param(&self) -> Option<String> {
+        self.param_names().get(self.param).cloned()
+    }
+
+    pub fn cycle_param(&mut self, forward: bool) {
+        let count = self.param_names().len();
+        if count == 0 {
+            return;
+        }
+        self.param = if forward {
+            (self.param + 1) % count
+        } else {
+            (self.param + count - 1) % count
+        };
+    }
+
+    pub fn cycle_sort(&mut self) {
+        self.sort = next_sort_key(self.sort);
+        self.apply();
+    }
+
+    // Applies the filter being typed; on error the previous filters stay.
+    pub fn apply_filter_input(&mut self) -> Result<(), String> {
+        self.filters = parse_filters(&self.filter_input.value)?;
+        self.selected = 0;
+        self.apply();
+        Ok(())
+    }
+
+    fn apply(&mut self) {
+        let keep = self.selected_trial().map(|t| t.run_id.clone());
+        let mut visible: Vec<usize> = (0..self.trials.len())
+            .filter(|&idx| self.filters.iter().all(|f| f.matches(&self.trials[idx])))
+            .collect();
+        let key = self.sort;
+        visible.sort_by(|&a, &b| {
+            rank_value(key, &self.trials[b]).total_cmp(&rank_value(key, &self.trials[a]))
+        });
+        self.visible = visible;
+        self.selected = keep
+            .and_then(|run_id| {
+                self.visible
+                    .iter()
+                    .position(|&idx| self.trials[idx].run_id == run_id)
+            })
+            .unwrap_or(self.selected)
+            .min(self.visible.len().saturating_sub(1));
+    }
+
+    pub fn visible_trials(&self) -> impl Iterator<Item = &TrialOutcome> {
+        self.visible.iter().map(|&idx| &self.trials[idx])
+    }
+
+    pub fn selected_trial(&self) -> Option<&TrialOutcome> {
+        self.visible
+            .get(self.selected)
+            .map(|&idx| &self.trials[idx])
+    }
+
+    pub fn scatter(&self) -> Option<Scatter> {
+        let param = self.current_param()?;
+        let rows: Vec<(&str, f64, bool)> = self
+            .visible
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, &idx)| {
+                let trial = &self.trials[idx];
+                let value = trial.params.iter().find(|(p, _)| *p == param)?.1.as_str();
+                let y = metric(self.sort, trial).filter(|y| y.is_finite())?;
+                Some((value, y, pos == self.selected))
+            })
+            .collect();
+        if rows.is_empty() {
+            return None;
+        }
+        let numeric = rows.iter().all(|(value, ..)| value.parse::<f64>().is_ok());
+        let categories: Vec<String> = if numeric {
+            Vec::new()
+        } else {
+            rows.iter()
+                .map(|(value, ..)| value.to_string())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect()
+        };
+        let x_of = |value: &str| match value.parse::<f64>() {
+            Ok(x) if numeric => x,
+            _ => categories.iter().position(|c| c == value).unwrap_or(0) as f64,
+        };
+        let points: Vec<(f64, f64)> = rows.iter().map(|(value, y, _)| (x_of(value), *y)).collect();
+        let selected = rows
+            .iter()
+            .find(|(.., selected)| *selected)
+            .map(|(value, y, _)| (x_of(value), *y));
+        Some(Scatter {
+            x_bounds: padded_bounds(points.iter().map(|p| p.0)),
+            y_bounds: padded_bounds(points.iter().map(|p| p.1)),
+            points,
+            selected,
+            categories,
+        })
+    }
+}
+
+// Min/max with a 5% margin, so points are not drawn on the border.
+fn padded_bounds(values: impl Iterator<Item = f64>) -> [f64; 2] {
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+        (lo.min(v), hi.max(v))
+    });
+    let pad = if max > min { (max - min) * 0.05 } else { 0.5 };
+    [min - pad, max + pad]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{list_sweeps, parse_filters, SweepHistory};
+    use crate::app::TextInput;
+    use kairos_domain::repositories::run_registry::RunSortKey;
+    use serde_json::json;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn past_sweeps_load_filter_and_plot_by_parameter() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let out_dir = std::env::temp_dir().join(format!("kairos_tui_sweep_history_{unique}"));
+        let sweep_dir = out_dir.join("sweeps").join("sma_grid");
+        std::fs::create_dir_all(&sweep_dir).expect("mkdir");
+        std::fs::create_dir_all(out_dir.join("sweeps").join("unfinished")).expect("mkdir");
+        let run = |id: &str, fast: i64, mode: &str, sharpe: Option<f64>| {
+            json!({
+                "run_id": id,
+                "split_id": "full",
+                "params": {"strategy.sma_fast": fast, "orders.size_mode": mode},
+                "status": if sharpe.is_some() { "ok" } else { "error" },
+                "error": null,
+                "metrics": sharpe.map(|s| json!({
+                    "bars_processed": 10, "trades": 2, "win_rate": 0.5,
+                    "net_profit": fast as f64, "sharpe": s, "max_drawdown": 0.1
+                })),
+            })
+        };
+        let manifest = json!({
+            "sweep_id": "sma_grid",
+            "runs": [
+                run("t1", 5, "qty", Some(0.5)),
+                run("t2", 10, "qty", Some(1.5)),
+                run("t3", 20, "pct_equity", Some(1.0)),
+                run("t4", 40, "qty", None),
+            ],
+        });
+        std::fs::write(sweep_dir.join("manifest.json"), manifest.to_string()).expect("write");
+
+        let sweeps = list_sweeps(&out_dir);
+        assert_eq!(sweeps.len(), 1);
+        let mut history = SweepHistory::new();
+        history.refresh(&out_dir);
+        history.open_selected().expect("open");
+        let order: Vec<&str> = history
+            .visible_trials()
+            .map(|t| t.run_id.as_str())
+            .collect();
+        assert_eq!(order, ["t2", "t3", "t1", "t4"]);
+        assert_eq!(
+            history.selected_trial().expect("best").run_dir,
+            out_dir.join("t2").display().to_string()
+        );
+
+        assert_eq!(
+            history.param_names(),
+            ["orders.size_mode", "strategy.sma_fast"]
+        );
+        history.cycle_param(true);
+        let scatter = history.scatter().expect("scatter");
+        assert!(scatter.categories.is_empty());
+        assert_eq!(scatter.points, [(10.0, 1.5), (20.0, 1.0), (5.0, 0.5)]);
+        assert_eq!(scatter.selected, Some((10.0, 1.5)));
+        history.cycle_param(true);
+        let scatter = history.scatter().expect("categorical");
+        assert_eq!(scatter.categories, ["pct_equity", "qty"]);
+        assert_eq!(scatter.points[0], (1.0, 1.5));
+
+        history.filter_input = TextInput::new("sma_fast>=10, size_mode = qty".to_string());
+        history.apply_filter_input().expect("filters");
+        let order: Vec<&str> = history
+            .visible_trials()
+            .map(|t| t.run_id.as_str())
+            .collect();
+        assert_eq!(order, ["t2", "t4"]);
+        history.cycle_sort();
+        assert_eq!(history.sort, RunSortKey::NetProfit);
+        assert!(parse_filters("sma_fast").unwrap_err().contains("expected"));
+        assert!(parse_filters(">=3").unwrap_err().contains("missing"));
+
+        let _ = std::fs::remove_dir_all(&out_dir);
+    }
+}
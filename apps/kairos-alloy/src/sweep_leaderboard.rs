@@ -0,0 +1,141 @@
+use kairos_application::experiments::sweep::TrialOutcome;
+use kairos_domain::repositories::run_registry::RunSortKey;
+
+// Sort orders the leaderboard cycles through with `s`, best first.
+const SORT_KEYS: [RunSortKey; 3] = [
+    RunSortKey::Sharpe,
+    RunSortKey::NetProfit,
+    RunSortKey::MaxDrawdown,
+];
+
+// The TUI sweep leaderboard: every trial the running sweep reported so far (from its
+// `TrialCompleted` events), kept sorted best first with the selection following its trial.
+pub struct SweepLeaderboard {
+    pub trials: Vec<TrialOutcome>,
+    pub sort: RunSortKey,
+    pub selected: usize,
+}
+
+impl SweepLeaderboard {
+    pub fn new() -> Self {
+        Self {
+            trials: Vec::new(),
+            sort: RunSortKey::Sharpe,
+            selected: 0,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.trials.clear();
+        self.selected = 0;
+    }
+
+    // A trial reported again (resumed sweeps) replaces its earlier row.
+    pub fn record(&mut self, trial: TrialOutcome) {
+        let selected = self.selected_trial().map(|t| t.run_id.clone());
+        match self.trials.iter_mut().find(|t| t.run_id == trial.run_id) {
+            Some(existing) => *existing = trial,
+            None => self.trials.push(trial),
+        }
+        self.sort_trials(selected.as_deref());
+    }
+
+    pub fn cycle_sort(&mut self) {
+        self.sort = next_sort_key(self.sort);
+        let selected = self.selected_trial().map(|t| t.run_id.clone());
+        self.sort_trials(selected.as_deref());
+    }
+
+    fn sort_trials(&mut self, keep: Option<&str>) {
+        let key = self.sort;
+        self.trials
+            .sort_by(|a, b| rank_value(key, b).total_cmp(&rank_value(key, a)));
+        self.selected = keep
+            .and_then(|run_id| self.trials.iter().position(|t| t.run_id == run_id))
+            .unwrap_or(self.selected)
+            .min(self.trials.len().saturating_sub(1));
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1).min(self.trials.len().saturating_sub(1));
+    }
+
+    pub fn select_best(&mut self) {
+        self.selected = 0;
+    }
+
+    pub fn selected_trial(&self) -> Option<&TrialOutcome> {
+        self.trials.get(self.selected)
+    }
+}
+
+pub fn next_sort_key(key: RunSortKey) -> RunSortKey {
+    let idx = SORT_KEYS.iter().position(|k| *k == key);
+    SORT_KEYS[idx.map_or(0, |idx| (idx + 1) % SORT_KEYS.len())]
+}
+
+pub fn metric(key: RunSortKey, trial: &TrialOutcome) -> Option<f64> {
+    match key {
+        RunSortKey::NetProfit => trial.net_profit,
+        RunSortKey::MaxDrawdown => trial.max_drawdown,
+        _ => trial.sharpe,
+    }
+}
+
+// Higher is better after this mapping; trials without the metric (failed, pruned) rank last.
+pub fn rank_value(key: RunSortKey, trial: &TrialOutcome) -> f64 {
+    match metric(key, trial).filter(|value| value.is_finite()) {
+        Some(value) if key.higher_is_better() => value,
+        Some(value) => -value,
+        None => f64::NEG_INFINITY,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SweepLeaderboard;
+    use kairos_application::experiments::sweep::TrialOutcome;
+    use kairos_domain::repositories::run_registry::RunSortKey;
+
+    fn trial(run_id: &str, sharpe: Option<f64>, net_profit: f64, drawdown: f64) -> TrialOutcome {
+        TrialOutcome {
+            run_id: run_id.to_string(),
+            split_id: "full".to_string(),
+            run_dir: format!("runs/{run_id}"),
+            params: vec![("strategy.sma_fast".to_string(), "5".to_string())],
+            status: if sharpe.is_some() { "ok" } else { "error" }.to_string(),
+            net_profit: sharpe.map(|_| net_profit),
+            sharpe,
+            max_drawdown: sharpe.map(|_| drawdown),
+        }
+    }
+
+    #[test]
+    fn trials_stay_sorted_as_they_arrive_and_keep_the_selection() {
+        let mut board = SweepLeaderboard::new();
+        board.record(trial("a", Some(0.5), 30.0, 0.2));
+        board.record(trial("b", None, 0.0, 0.0));
+        board.record(trial("c", Some(1.5), 10.0, 0.1));
+        let order: Vec<&str> = board.trials.iter().map(|t| t.run_id.as_str()).collect();
+        assert_eq!(order, ["c", "a", "b"]);
+        assert_eq!(board.selected_trial().expect("selected").run_id, "a");
+
+        board.record(trial("d", Some(2.0), 5.0, 0.3));
+        assert_eq!(board.selected_trial().expect("selected").run_id, "a");
+
+        board.cycle_sort();
+        assert_eq!(board.sort, RunSortKey::NetProfit);
+        assert_eq!(board.trials[0].run_id, "a");
+        board.cycle_sort();
+        assert_eq!(board.sort, RunSortKey::MaxDrawdown);
+        let order: Vec<&str> = board.trials.iter().map(|t| t.run_id.as_str()).collect();
+        assert_eq!(order, ["c", "a", "d", "b"]);
+
+        board.select_best();
+        assert_eq!(board.selected_trial().expect("best").run_id, "c");
+    }
+}
@@ -1,26 +1,61 @@
+use crate::agent_stats::AgentCallSample;
+use crate::data_quality::DataQualityView;
+use crate::jobs::JobKind;
+use kairos_application::paper_trading::clock::{ReplayPacing, ReplaySpeed};
+use kairos_application::paper_trading::notifications::PaperNotifications;
+use kairos_application::reporting::chart::RunChart;
 use kairos_domain::repositories::agent::AgentClient as AgentPort;
-use kairos_domain::repositories::market_data::MarketDataRepository;
 use kairos_domain::repositories::sentiment::SentimentRepository;
-use kairos_infrastructure::agents::AgentClient as InfraAgentClient;
-use kairos_infrastructure::artifacts::FilesystemArtifactWriter;
-use kairos_infrastructure::persistence::postgres_ohlcv::PostgresMarketDataRepository;
+use kairos_domain::services::engine::backtest::CancellationToken;
+use kairos_domain::services::progress::ProgressEvent;
+use kairos_domain::services::strategy::hot_swap::{StrategyChange, StrategyChanges};
+use kairos_infrastructure::agents::{
+    AgentCallInfo, AgentCallObserver, AgentClient as InfraAgentClient,
+};
+use kairos_infrastructure::artifacts::{FilesystemArtifactReader, FilesystemArtifactWriter};
+use kairos_infrastructure::market_stream::simulated::{SimulatedMarket, SimulatorSpec};
+use kairos_infrastructure::notifications::telegram::TelegramNotifier;
+use kairos_infrastructure::persistence::sqlite_run_registry::SqliteRunRegistry;
 use kairos_infrastructure::sentiment::FilesystemSentimentRepository;
 use parking_lot::{Condvar, Mutex};
-use std::env;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 const STREAM_EVERY_N_BARS: u64 = 10;
+const JOB_LOG_EVERY_N_BARS: u64 = 5_000;
+const MIN_REPLAY_SCALE: f64 = 0.25;
+const MAX_REPLAY_SCALE: f64 = 1_000_000.0;
+const FOLLOW_POLL: std::time::Duration = std::time::Duration::from_millis(500);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TaskKind {
     Validate { strict: bool },
     Backtest,
     Paper,
-    PaperRealtime,
+    // `simulated` streams from the `[paper.simulator]` random walk instead of the exchange.
+    PaperRealtime { simulated: bool },
     Sweep,
+    Ingest,
+    // Tailing the live.jsonl of a run started elsewhere (a headless paper/live session).
+    Follow,
+}
+
+impl TaskKind {
+    // Tasks driven bar by bar through a `RunControl`, which is what `p` pauses.
+    pub fn pausable(self) -> bool {
+        matches!(
+            self,
+            Self::Backtest | Self::Paper | Self::PaperRealtime { .. }
+        )
+    }
+
+    // Paused tasks that `n` can advance one bar (a realtime session waits on the exchange instead).
+    pub fn steppable(self) -> bool {
+        matches!(self, Self::Backtest | Self::Paper)
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -30,7 +65,7 @@ pub struct AgentLlmRuntime {
     pub api_key: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TradeSample {
     pub bar_index: u64,
     pub timestamp: i64,
@@ -44,9 +79,42 @@ pub struct BarProgressSample {
     pub x: f64,
     pub price: f64,
     pub equity: f64,
+    // None when the stream mixes several sleeves or does not report the position (followed runs).
+    pub position_qty: Option<f64>,
     pub trades_in_bar: Vec<TradeSample>,
 }
 
+// The position and working orders of one paper sleeve after a bar, sent for every bar.
+#[derive(Debug, Clone)]
+pub struct PositionSample {
+    pub symbol: String,
+    pub timestamp: i64,
+    pub price: f64,
+    pub quantity: f64,
+    pub avg_price: f64,
+    pub cash: f64,
+    pub equity: f64,
+    pub open_orders: Vec<kairos_domain::services::engine::backtest::OpenOrder>,
+}
+
+impl PositionSample {
+    fn from_progress(
+        symbol: &str,
+        p: &kairos_domain::services::engine::backtest::BarProgress,
+    ) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            timestamp: p.timestamp,
+            price: p.close,
+            quantity: p.position_qty,
+            avg_price: p.avg_price,
+            cash: p.cash,
+            equity: p.equity,
+            open_orders: p.open_orders.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct SweepProgressSample {
     pub total_runs: usize,
@@ -54,16 +122,57 @@ pub struct SweepProgressSample {
     pub ok_runs: usize,
     pub skipped_runs: usize,
     pub error_runs: usize,
+    pub pruned_runs: usize,
     pub last_run_id: Option<String>,
     pub last_error: Option<String>,
+    pub workers: usize,
+    pub elapsed_ms: u64,
+    pub eta_ms: Option<u64>,
+    pub last_trial: Option<kairos_application::experiments::sweep::TrialOutcome>,
+}
+
+// One `RowsWritten`/`RequestRetried` event of a running ingest.
+#[derive(Debug, Clone)]
+pub enum IngestProgressSample {
+    Window {
+        first_ts: i64,
+        last_ts: i64,
+        rows: u64,
+        total_rows: u64,
+    },
+    Retry {
+        attempt: u32,
+        delay_ms: u64,
+        reason: String,
+    },
 }
 
 pub enum TaskEvent {
     Input(crossterm::event::Event),
     Progress(BarProgressSample),
     SweepProgress(SweepProgressSample),
+    IngestProgress(IngestProgressSample),
     StreamStatus(StreamStatusSample),
+    Position(PositionSample),
+    AgentCall(AgentCallSample),
+    // The validate JSON report, sent before `TaskFinished` (also when strict validation fails).
+    DataQuality(serde_json::Value),
     TaskFinished(Result<String, String>),
+    ChartLoaded(Result<RunChart, String>),
+    // The outcome of a database step of the first-run onboarding.
+    OnboardingStep {
+        step: crate::onboarding::Step,
+        result: Result<String, String>,
+    },
+    // A line for the log of a queued job, and its result (keyed by the job queue id).
+    JobLog {
+        id: usize,
+        line: String,
+    },
+    JobFinished {
+        id: usize,
+        result: Result<String, String>,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -74,6 +183,7 @@ pub struct StreamStatusSample {
     pub last_event_timestamp: Option<i64>,
     pub out_of_order_events: u64,
     pub invalid_events: u64,
+    pub symbols: Vec<kairos_application::paper_trading::SymbolStreamStatus>,
 }
 
 #[derive(Clone)]
@@ -84,12 +194,18 @@ pub struct TaskRunner {
 struct TaskRunnerInner {
     tx: tokio::sync::mpsc::UnboundedSender<TaskEvent>,
     control: Mutex<Option<TaskControl>>,
+    // Queued jobs run outside the `control` slot, each with its own cancel token.
+    jobs: Mutex<BTreeMap<usize, TaskControl>>,
 }
 
 #[derive(Clone)]
 struct TaskControl {
-    cancel: Arc<AtomicBool>,
+    cancel: CancellationToken,
     pause: Arc<(Mutex<PauseState>, Condvar)>,
+    // Set by paper replay tasks so the UI can change the replay speed while they run.
+    replay_speed: Arc<Mutex<Option<ReplaySpeed>>>,
+    // Created when a paper session asks for it, so only those accept strategy changes.
+    strategy_changes: Arc<Mutex<Option<StrategyChanges>>>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -101,7 +217,7 @@ struct PauseState {
 impl TaskControl {
     fn new() -> Self {
         Self {
-            cancel: Arc::new(AtomicBool::new(false)),
+            cancel: CancellationToken::new(),
             pause: Arc::new((
                 Mutex::new(PauseState {
                     paused: false,
@@ -109,11 +225,38 @@ impl TaskControl {
                 }),
                 Condvar::new(),
             )),
+            replay_speed: Arc::new(Mutex::new(None)),
+            strategy_changes: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn attach_replay_speed(&self, speed: ReplaySpeed) {
+        *self.replay_speed.lock() = Some(speed);
+    }
+
+    // Multiplies the replay speed by `factor`; `None` when the task has no paced replay. An
+    // unthrottled replay (`replay_scale = 0`) stays unthrottled.
+    fn scale_replay_speed(&self, factor: f64) -> Option<f64> {
+        let slot = self.replay_speed.lock();
+        let speed = slot.as_ref()?;
+        if speed.scale() > 0.0 {
+            speed.set_scale((speed.scale() * factor).clamp(MIN_REPLAY_SCALE, MAX_REPLAY_SCALE));
         }
+        Some(speed.scale())
+    }
+
+    // False when the task is not a paper session.
+    fn queue_strategy_change(&self, change: StrategyChange) -> bool {
+        let slot = self.strategy_changes.lock();
+        let Some(changes) = slot.as_ref() else {
+            return false;
+        };
+        changes.push(change);
+        true
     }
 
     fn cancel(&self) {
-        self.cancel.store(true, Ordering::Relaxed);
+        self.cancel.cancel();
         let (_, cvar) = &*self.pause;
         cvar.notify_all();
     }
@@ -146,13 +289,13 @@ impl TaskControl {
     }
 
     fn is_cancelled(&self) -> bool {
-        self.cancel.load(Ordering::Relaxed)
+        self.cancel.is_cancelled()
     }
 }
 
 impl kairos_domain::services::engine::backtest::RunControl for TaskControl {
     fn should_cancel(&self) -> bool {
-        self.cancel.load(Ordering::Relaxed)
+        self.cancel.is_cancelled()
     }
 
     fn wait_if_paused(&self) -> bool {
@@ -169,6 +312,15 @@ impl kairos_domain::services::engine::backtest::RunControl for TaskControl {
         }
         !self.should_cancel()
     }
+
+    fn strategy_changes(&self) -> Option<StrategyChanges> {
+        Some(
+            self.strategy_changes
+                .lock()
+                .get_or_insert_with(StrategyChanges::default)
+                .clone(),
+        )
+    }
 }
 
 impl TaskRunner {
@@ -177,6 +329,7 @@ impl TaskRunner {
             inner: Arc::new(TaskRunnerInner {
                 tx,
                 control: Mutex::new(None),
+                jobs: Mutex::new(BTreeMap::new()),
             }),
         }
     }
@@ -187,17 +340,12 @@ impl TaskRunner {
         config: Arc<kairos_application::config::Config>,
         config_toml: String,
         agent_llm: Option<AgentLlmRuntime>,
+        resume_session: bool,
     ) {
         let inner = self.inner.clone();
         let tx = inner.tx.clone();
         tokio::task::spawn_blocking(move || {
-            let control = match kind {
-                TaskKind::Backtest
-                | TaskKind::Paper
-                | TaskKind::PaperRealtime
-                | TaskKind::Sweep => Some(TaskControl::new()),
-                _ => None,
-            };
+            let control = Some(TaskControl::new());
             {
                 let mut slot = inner.control.lock();
                 *slot = control.clone();
@@ -210,6 +358,7 @@ impl TaskRunner {
                 &tx,
                 control.as_ref(),
                 agent_llm.as_ref(),
+                resume_session,
             );
             {
                 let mut slot = inner.control.lock();
@@ -249,6 +398,99 @@ impl TaskRunner {
         });
     }
 
+    pub fn start_ingest(
+        &self,
+        args: crate::ingest::KucoinIngestArgs,
+        config: Option<Arc<kairos_application::config::Config>>,
+    ) {
+        let inner = self.inner.clone();
+        let tx = inner.tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let control = Some(TaskControl::new());
+            {
+                let mut slot = inner.control.lock();
+                *slot = control.clone();
+            }
+
+            let result = run_ingest_task(&args, config.as_deref(), &tx, control.as_ref());
+            {
+                let mut slot = inner.control.lock();
+                *slot = None;
+            }
+            let _ = tx.send(TaskEvent::TaskFinished(result));
+        });
+    }
+
+    pub fn start_follow(&self, run_dir: PathBuf) {
+        let inner = self.inner.clone();
+        let tx = inner.tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let control = TaskControl::new();
+            {
+                let mut slot = inner.control.lock();
+                *slot = Some(control.clone());
+            }
+
+            let result = run_follow_task(&run_dir, &tx, &control);
+            {
+                let mut slot = inner.control.lock();
+                *slot = None;
+            }
+            let _ = tx.send(TaskEvent::TaskFinished(result));
+        });
+    }
+
+    pub fn start_job(
+        &self,
+        id: usize,
+        kind: JobKind,
+        config_path: PathBuf,
+        ingest: Option<crate::ingest::KucoinIngestArgs>,
+    ) {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || {
+            let control = TaskControl::new();
+            inner.jobs.lock().insert(id, control.clone());
+            let log = |line: String| {
+                let _ = inner.tx.send(TaskEvent::JobLog { id, line });
+            };
+            let result = run_queued_job(kind, &config_path, ingest.as_ref(), &control, &log);
+            inner.jobs.lock().remove(&id);
+            let _ = inner.tx.send(TaskEvent::JobFinished { id, result });
+        });
+    }
+
+    pub fn cancel_job(&self, id: usize) -> bool {
+        let control = { self.inner.jobs.lock().get(&id).cloned() };
+        control.map(|c| c.cancel()).is_some()
+    }
+
+    // Loads the candlestick chart of a finished run off the UI thread. Not a task: it takes no
+    // control slot, so it can run next to a backtest.
+    pub fn load_chart(&self, config: Arc<kairos_application::config::Config>, run_dir: PathBuf) {
+        let tx = self.inner.tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let result =
+                crate::headless::build_market_data_repo(config.as_ref()).and_then(|market_data| {
+                    kairos_application::reporting::chart::load_run_chart(
+                        &run_dir,
+                        config.as_ref(),
+                        market_data.as_ref(),
+                        &FilesystemArtifactReader::new(),
+                    )
+                });
+            let _ = tx.send(TaskEvent::ChartLoaded(result));
+        });
+    }
+
+    pub fn run_onboarding_step(&self, step: crate::onboarding::Step, db_url: String) {
+        let tx = self.inner.tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let result = crate::onboarding::run_db_step(step, &db_url);
+            let _ = tx.send(TaskEvent::OnboardingStep { step, result });
+        });
+    }
+
     pub fn cancel_current(&self) {
         let control = { self.inner.control.lock().clone() };
         if let Some(control) = control {
@@ -265,6 +507,19 @@ impl TaskRunner {
         let control = { self.inner.control.lock().clone() };
         control.map(|c| c.step_once()).unwrap_or(false)
     }
+
+    pub fn scale_replay_speed(&self, factor: f64) -> Option<f64> {
+        let control = { self.inner.control.lock().clone() };
+        control.and_then(|c| c.scale_replay_speed(factor))
+    }
+
+    // Applied by the running paper session at its next bar; false when none is running.
+    pub fn queue_strategy_change(&self, change: StrategyChange) -> bool {
+        let control = { self.inner.control.lock().clone() };
+        control
+            .map(|c| c.queue_strategy_change(change))
+            .unwrap_or(false)
+    }
 }
 
 fn run_task(
@@ -274,16 +529,169 @@ fn run_task(
     tx: &tokio::sync::mpsc::UnboundedSender<TaskEvent>,
     control: Option<&TaskControl>,
     agent_llm: Option<&AgentLlmRuntime>,
+    resume_session: bool,
 ) -> Result<String, String> {
     match kind {
-        TaskKind::Validate { strict } => run_validate(config, strict),
+        TaskKind::Validate { strict } => run_validate(config, strict, tx, control),
         TaskKind::Backtest => run_backtest(config, config_toml, tx, control, agent_llm),
-        TaskKind::Paper => run_paper(config, config_toml, tx, control, agent_llm),
-        TaskKind::PaperRealtime => run_paper_realtime(config, config_toml, tx, control, agent_llm),
+        TaskKind::Paper => run_paper(config, config_toml, tx, control, agent_llm, resume_session),
+        TaskKind::PaperRealtime { simulated } => run_paper_realtime(
+            config,
+            config_toml,
+            tx,
+            control,
+            agent_llm,
+            resume_session,
+            simulated,
+        ),
         TaskKind::Sweep => Err("internal error: use start_sweep for TaskKind::Sweep".to_string()),
+        TaskKind::Ingest => {
+            Err("internal error: use start_ingest for TaskKind::Ingest".to_string())
+        }
+        TaskKind::Follow => {
+            Err("internal error: use start_follow for TaskKind::Follow".to_string())
+        }
     }
 }
 
+// Stage lines plus a bar/equity line every `JOB_LOG_EVERY_N_BARS` bars make up a job's log.
+fn run_queued_job(
+    kind: JobKind,
+    config_path: &Path,
+    ingest: Option<&crate::ingest::KucoinIngestArgs>,
+    control: &TaskControl,
+    log: &dyn Fn(String),
+) -> Result<String, String> {
+    let (config, config_toml) = kairos_application::config::load_config_with_source(config_path)
+        .map_err(|err| err.to_string())?;
+    log(format!(
+        "{} {} (run_id={} symbol={} timeframe={})",
+        kind.as_str(),
+        config_path.display(),
+        config.run.run_id,
+        config.run.symbol,
+        config.run.timeframe
+    ));
+    let mut progress = |event: ProgressEvent<'_>| match event {
+        ProgressEvent::StageStarted { stage } => log(format!("{stage} started")),
+        ProgressEvent::StageFinished { stage, duration_ms } => {
+            log(format!("{stage} finished in {duration_ms}ms"))
+        }
+        ProgressEvent::BarProcessed(p)
+            if (p.bar_index + 1).is_multiple_of(JOB_LOG_EVERY_N_BARS) =>
+        {
+            log(format!("bar {} equity={:.2}", p.bar_index + 1, p.equity))
+        }
+        _ => {}
+    };
+    if let Some(args) = ingest {
+        log(format!(
+            "ingest {} since {}",
+            args.symbol.as_deref().unwrap_or_default(),
+            args.start
+        ));
+        let mut on_progress = |event: ProgressEvent<'_>| {
+            if let ProgressEvent::RowsWritten { total_rows, .. } = event {
+                log(format!("ingest: {total_rows} rows written"));
+            }
+        };
+        let should_cancel = || control.is_cancelled();
+        let value = crate::ingest::run_kucoin_ingest_with_cancel(
+            args,
+            Some(&config),
+            &mut on_progress,
+            &should_cancel,
+        )
+        .map_err(|err| err.message)?;
+        log(format!(
+            "ingest ok: {}",
+            value["symbol"].as_str().unwrap_or_default()
+        ));
+    }
+    let market_data = crate::headless::build_market_data_repo(&config)?;
+    let sentiment_repo = crate::headless::build_sentiment_repo(&config)?;
+    match kind {
+        JobKind::Validate => {
+            kairos_application::validation::validate_with_control(
+                &config,
+                false,
+                market_data.as_ref(),
+                sentiment_repo.as_ref(),
+                control,
+                &mut progress,
+            )
+            .map_err(|err| err.to_string())?;
+            Ok(format!("validate ok: {}", config.run.run_id))
+        }
+        JobKind::Backtest => {
+            let run_dir = kairos_application::backtesting::run_backtest_streaming_control(
+                &config,
+                &config_toml,
+                None,
+                market_data.as_ref(),
+                sentiment_repo.as_ref(),
+                &FilesystemArtifactWriter::new(),
+                build_remote_agent(&config, None, None)?,
+                control as &dyn kairos_domain::services::engine::backtest::RunControl,
+                &mut progress,
+            )
+            .map_err(|err| err.to_string())?;
+            record_in_registry(Path::new(&config.paths.out_dir), &run_dir);
+            Ok(format!("backtest complete: {}", run_dir.display()))
+        }
+        JobKind::Sweep => Err("sweeps run from Experiments, not the job queue".to_string()),
+    }
+}
+
+fn run_ingest_task(
+    args: &crate::ingest::KucoinIngestArgs,
+    config: Option<&kairos_application::config::Config>,
+    tx: &tokio::sync::mpsc::UnboundedSender<TaskEvent>,
+    control: Option<&TaskControl>,
+) -> Result<String, String> {
+    let mut on_progress = |event: ProgressEvent<'_>| {
+        let sample = match event {
+            ProgressEvent::RowsWritten {
+                first_ts,
+                last_ts,
+                rows,
+                total_rows,
+            } => IngestProgressSample::Window {
+                first_ts,
+                last_ts,
+                rows,
+                total_rows,
+            },
+            ProgressEvent::RequestRetried {
+                attempt,
+                delay_ms,
+                reason,
+            } => IngestProgressSample::Retry {
+                attempt,
+                delay_ms,
+                reason: reason.to_string(),
+            },
+            _ => return,
+        };
+        let _ = tx.send(TaskEvent::IngestProgress(sample));
+    };
+    let should_cancel = || control.map(|c| c.is_cancelled()).unwrap_or(false);
+    let value = crate::ingest::run_kucoin_ingest_with_cancel(
+        args,
+        config,
+        &mut on_progress,
+        &should_cancel,
+    )
+    .map_err(|err| err.message)?;
+    Ok(format!(
+        "ingest ok: {} {} ({}/{})",
+        value["symbol"].as_str().unwrap_or_default(),
+        value["timeframe"].as_str().unwrap_or_default(),
+        value["exchange"].as_str().unwrap_or_default(),
+        value["market"].as_str().unwrap_or_default()
+    ))
+}
+
 fn run_sweep_task(
     sweep_config: &Path,
     parallelism_override: Option<usize>,
@@ -318,34 +726,44 @@ fn run_sweep_task(
     sweep_file.sweep.resume = Some(resume);
 
     let resolved_base = resolve_base_config_path(sweep_config, sweep_file.base.config.as_str());
-    let (base_config, _) = kairos_application::config::load_config_with_source(&resolved_base)?;
+    let (base_config, _) = kairos_application::config::load_config_with_source(&resolved_base)
+        .map_err(|err| err.to_string())?;
     sweep_file.base.config = resolved_base.display().to_string();
 
     let runtime_sweep_path = write_runtime_sweep_file(&sweep_file)?;
-    let market_data = build_market_data_repo(&base_config)?;
+    let market_data = crate::headless::build_market_data_repo(&base_config)?;
     let sentiment_repo =
         Box::new(FilesystemSentimentRepository) as Box<dyn SentimentRepository + Sync>;
     let artifacts = FilesystemArtifactWriter::new();
     let agent_factory =
         |cfg: &kairos_application::config::Config| -> Result<Option<Box<dyn AgentPort>>, String> {
-            build_remote_agent(cfg, None)
+            build_remote_agent(cfg, None, None)
         };
 
-    let mut on_progress = |progress: kairos_application::experiments::sweep::SweepProgress| {
+    let mut on_progress = |event: ProgressEvent<'_>| {
+        let ProgressEvent::TrialCompleted(progress) = event else {
+            return;
+        };
         let _ = tx.send(TaskEvent::SweepProgress(SweepProgressSample {
             total_runs: progress.total_runs,
             completed_runs: progress.completed_runs,
             ok_runs: progress.ok_runs,
             skipped_runs: progress.skipped_runs,
             error_runs: progress.error_runs,
-            last_run_id: progress.last_run_id,
-            last_error: progress.last_error,
+            pruned_runs: progress.pruned_runs,
+            last_run_id: progress.last_run_id.clone(),
+            last_error: progress.last_error.clone(),
+            workers: progress.workers,
+            elapsed_ms: progress.elapsed_ms,
+            eta_ms: progress.eta_ms,
+            last_trial: progress.last_trial.clone(),
         }));
     };
     let should_cancel = || control.map(|c| c.is_cancelled()).unwrap_or(false);
 
     let result = kairos_application::experiments::sweep::run_sweep_with_hooks(
         runtime_sweep_path.as_path(),
+        &kairos_application::experiments::sweep::SweepOverrides::default(),
         &agent_factory,
         market_data.as_ref(),
         sentiment_repo.as_ref(),
@@ -357,6 +775,14 @@ fn run_sweep_task(
     let _ = std::fs::remove_file(&runtime_sweep_path);
 
     let result = result?;
+    let out_dir = PathBuf::from(&base_config.paths.out_dir);
+    for entry in result
+        .runs
+        .iter()
+        .filter(|entry| matches!(entry.status.as_str(), "ok" | "skipped"))
+    {
+        record_in_registry(&out_dir, &out_dir.join(&entry.run_id));
+    }
     Ok(format!(
         "sweep complete: {} (runs={})",
         result.sweep_dir.display(),
@@ -396,33 +822,37 @@ fn write_runtime_sweep_file(
     Ok(path)
 }
 
-fn resolve_db_url(config: &kairos_application::config::Config) -> Result<String, String> {
-    match config.db.url.as_deref() {
-        Some(url) if !url.trim().is_empty() => Ok(url.to_string()),
-        _ => env::var("KAIROS_DB_URL")
-            .map_err(|_| "missing db.url in config and env KAIROS_DB_URL is not set".to_string()),
-    }
-}
-
-fn build_market_data_repo(
+fn load_resume_session(
     config: &kairos_application::config::Config,
-) -> Result<Box<dyn MarketDataRepository>, String> {
-    let db_url = resolve_db_url(config)?;
-    let pool_max_size = config.db.pool_max_size.unwrap_or(8);
-    Ok(Box::new(PostgresMarketDataRepository::new(
-        db_url,
-        config.db.ohlcv_table.to_string(),
-        pool_max_size,
-    )?))
+    resume_session: bool,
+) -> Result<Option<kairos_application::paper_trading::session::PaperSession>, String> {
+    if !resume_session {
+        return Ok(None);
+    }
+    kairos_application::paper_trading::session::load_paper_session(
+        config,
+        None,
+        &FilesystemArtifactReader::new(),
+    )
 }
 
-fn build_sentiment_repo() -> Box<dyn SentimentRepository> {
-    Box::new(FilesystemSentimentRepository)
+// Streams every remote-agent call of the running task to the TUI diagnostics panel.
+fn agent_observer(tx: &tokio::sync::mpsc::UnboundedSender<TaskEvent>) -> AgentCallObserver {
+    let tx = tx.clone();
+    Arc::new(move |info: &AgentCallInfo| {
+        let _ = tx.send(TaskEvent::AgentCall(AgentCallSample {
+            attempts: info.attempts,
+            duration_ms: info.duration_ms,
+            status: info.status,
+            error: info.error.clone(),
+        }));
+    })
 }
 
 fn build_remote_agent(
     config: &kairos_application::config::Config,
     agent_llm: Option<&AgentLlmRuntime>,
+    observer: Option<AgentCallObserver>,
 ) -> Result<Option<Box<dyn AgentPort>>, String> {
     match config.agent.mode {
         kairos_application::config::AgentMode::Remote => {
@@ -474,25 +904,49 @@ fn build_remote_agent(
                     config.agent.url
                 )
             })?;
+            let agent = match observer {
+                Some(observer) => agent.with_observer(observer),
+                None => agent,
+            };
             Ok(Some(Box::new(agent)))
         }
         _ => Ok(None),
     }
 }
 
+// Validates non-strictly and applies `strict` here, so a failing report still reaches the
+// data-quality dashboard.
 fn run_validate(
     config: &kairos_application::config::Config,
     strict: bool,
+    tx: &tokio::sync::mpsc::UnboundedSender<TaskEvent>,
+    control: Option<&TaskControl>,
 ) -> Result<String, String> {
-    let market_data = build_market_data_repo(config)?;
-    let sentiment_repo = build_sentiment_repo();
+    let market_data = crate::headless::build_market_data_repo(config)?;
+    let sentiment_repo = crate::headless::build_sentiment_repo(config)?;
 
-    let report = kairos_application::validation::validate(
-        config,
-        strict,
-        market_data.as_ref(),
-        sentiment_repo.as_ref(),
-    )?;
+    let report = match control {
+        Some(control) => kairos_application::validation::validate_with_control(
+            config,
+            false,
+            market_data.as_ref(),
+            sentiment_repo.as_ref(),
+            control,
+            &mut |_event: ProgressEvent<'_>| {},
+        )
+        .map_err(|err| err.to_string())?,
+        None => kairos_application::validation::validate(
+            config,
+            false,
+            market_data.as_ref(),
+            sentiment_repo.as_ref(),
+        )
+        .map_err(|err| err.to_string())?,
+    };
+    let _ = tx.send(TaskEvent::DataQuality(report.clone()));
+    if strict && !DataQualityView::from_validate_report(&report).passed() {
+        return Err("strict validation failed: data quality limits exceeded".to_string());
+    }
     serde_json::to_string_pretty(&report)
         .map_err(|err| format!("failed to serialize validate report: {err}"))
 }
@@ -504,19 +958,20 @@ fn run_backtest(
     control: Option<&TaskControl>,
     agent_llm: Option<&AgentLlmRuntime>,
 ) -> Result<String, String> {
-    use kairos_domain::services::engine::backtest::BarProgress;
-
-    let market_data = build_market_data_repo(config)?;
-    let sentiment_repo = build_sentiment_repo();
+    let market_data = crate::headless::build_market_data_repo(config)?;
+    let sentiment_repo = crate::headless::build_sentiment_repo(config)?;
     let artifacts = FilesystemArtifactWriter::new();
-    let remote_agent = build_remote_agent(config, agent_llm)?;
+    let remote_agent = build_remote_agent(config, agent_llm, Some(agent_observer(tx)))?;
 
-    let mut last: Option<(f64, f64, f64)> = None;
+    let mut last: Option<(f64, f64, f64, f64)> = None;
     let mut last_sent_x: Option<f64> = None;
-    let mut progress = |p: BarProgress| {
+    let mut progress = |event: ProgressEvent<'_>| {
+        let ProgressEvent::BarProcessed(p) = event else {
+            return;
+        };
         let bar_index = p.bar_index;
         let x = bar_index as f64;
-        last = Some((x, p.close, p.equity));
+        last = Some((x, p.close, p.equity, p.position_qty));
 
         let has_trades = !p.trades_in_bar.is_empty();
         let stream_every = if control.map(|c| c.is_paused()).unwrap_or(false) {
@@ -527,7 +982,7 @@ fn run_backtest(
         if bar_index.is_multiple_of(stream_every) || has_trades {
             let trades_in_bar = if has_trades {
                 p.trades_in_bar
-                    .into_iter()
+                    .iter()
                     .map(|t| TradeSample {
                         bar_index,
                         timestamp: t.timestamp,
@@ -543,6 +998,7 @@ fn run_backtest(
                 x,
                 price: p.close,
                 equity: p.equity,
+                position_qty: Some(p.position_qty),
                 trades_in_bar,
             };
             let _ = tx.send(TaskEvent::Progress(sample));
@@ -561,7 +1017,8 @@ fn run_backtest(
             remote_agent,
             control as &dyn kairos_domain::services::engine::backtest::RunControl,
             &mut progress,
-        )?
+        )
+        .map_err(|err| err.to_string())?
     } else {
         kairos_application::backtesting::run_backtest_streaming(
             config,
@@ -572,18 +1029,21 @@ fn run_backtest(
             &artifacts,
             remote_agent,
             &mut progress,
-        )?
+        )
+        .map_err(|err| err.to_string())?
     };
-    if let Some((x, price, equity)) = last {
+    if let Some((x, price, equity, position_qty)) = last {
         if last_sent_x != Some(x) {
             let _ = tx.send(TaskEvent::Progress(BarProgressSample {
                 x,
                 price,
                 equity,
+                position_qty: Some(position_qty),
                 trades_in_bar: Vec::new(),
             }));
         }
     }
+    record_in_registry(Path::new(&config.paths.out_dir), &run_dir);
     Ok(format!("backtest complete: {}", run_dir.display()))
 }
 
@@ -593,26 +1053,55 @@ fn run_paper(
     tx: &tokio::sync::mpsc::UnboundedSender<TaskEvent>,
     control: Option<&TaskControl>,
     agent_llm: Option<&AgentLlmRuntime>,
+    resume_session: bool,
 ) -> Result<String, String> {
-    use kairos_domain::services::engine::backtest::BarProgress;
-
-    let market_data = build_market_data_repo(config)?;
-    let sentiment_repo = build_sentiment_repo();
+    let resume = load_resume_session(config, resume_session)?;
+    let market_data = crate::headless::build_market_data_repo(config)?;
+    let sentiment_repo = crate::headless::build_sentiment_repo(config)?;
     let artifacts = FilesystemArtifactWriter::new();
-    let remote_agent = build_remote_agent(config, agent_llm)?;
+    let remote_agent = build_remote_agent(config, agent_llm, Some(agent_observer(tx)))?;
+    let telegram = TelegramNotifier::from_env()?;
+    let notifications = telegram.as_ref().map(|notifier| {
+        RefCell::new(PaperNotifications::new(
+            notifier,
+            &config.run.run_id,
+            &config.run.symbol,
+        ))
+    });
+    if let Some(notifications) = &notifications {
+        notifications.borrow_mut().on_start("replay");
+    }
+    let pacing = ReplayPacing::from_config(config);
+    if let Some(control) = control {
+        control.attach_replay_speed(pacing.speed.clone());
+    }
 
-    let mut last: Option<(f64, f64, f64)> = None;
+    let mut last: Option<(f64, f64, f64, f64)> = None;
     let mut last_sent_x: Option<f64> = None;
-    let mut progress = |p: BarProgress| {
+    let mut progress = |event: ProgressEvent<'_>| {
+        let ProgressEvent::BarProcessed(p) = event else {
+            return;
+        };
+        if let Some(notifications) = &notifications {
+            notifications.borrow_mut().on_progress(p);
+        }
         let bar_index = p.bar_index;
         let x = bar_index as f64;
-        last = Some((x, p.close, p.equity));
+        last = Some((x, p.close, p.equity, p.position_qty));
 
         let has_trades = !p.trades_in_bar.is_empty();
+        // An unthrottled replay runs through bars far faster than the TUI redraws.
+        if bar_index.is_multiple_of(STREAM_EVERY_N_BARS) || has_trades || !p.open_orders.is_empty()
+        {
+            let _ = tx.send(TaskEvent::Position(PositionSample::from_progress(
+                &config.run.symbol,
+                p,
+            )));
+        }
         if bar_index.is_multiple_of(STREAM_EVERY_N_BARS) || has_trades {
             let trades_in_bar = if has_trades {
                 p.trades_in_bar
-                    .into_iter()
+                    .iter()
                     .map(|t| TradeSample {
                         bar_index,
                         timestamp: t.timestamp,
@@ -628,6 +1117,7 @@ fn run_paper(
                 x,
                 price: p.close,
                 equity: p.equity,
+                position_qty: Some(p.position_qty),
                 trades_in_bar,
             };
             let _ = tx.send(TaskEvent::Progress(sample));
@@ -635,7 +1125,7 @@ fn run_paper(
         }
     };
 
-    let run_dir = if let Some(control) = control {
+    let result = if let Some(control) = control {
         kairos_application::paper_trading::run_paper_streaming_control(
             config,
             config_toml,
@@ -644,11 +1134,13 @@ fn run_paper(
             sentiment_repo.as_ref(),
             &artifacts,
             remote_agent,
+            resume,
+            pacing,
             control as &dyn kairos_domain::services::engine::backtest::RunControl,
             &mut progress,
-        )?
+        )
     } else {
-        kairos_application::paper_trading::run_paper_streaming(
+        kairos_application::paper_trading::run_paper_streaming_control(
             config,
             config_toml,
             None,
@@ -656,19 +1148,28 @@ fn run_paper(
             sentiment_repo.as_ref(),
             &artifacts,
             remote_agent,
+            resume,
+            pacing,
+            &kairos_domain::services::engine::backtest::NoopControl,
             &mut progress,
-        )?
+        )
     };
-    if let Some((x, price, equity)) = last {
+    if let Some(notifications) = &notifications {
+        notifications.borrow_mut().on_finish(&result);
+    }
+    let run_dir = result.map_err(|err| err.to_string())?;
+    if let Some((x, price, equity, position_qty)) = last {
         if last_sent_x != Some(x) {
             let _ = tx.send(TaskEvent::Progress(BarProgressSample {
                 x,
                 price,
                 equity,
+                position_qty: Some(position_qty),
                 trades_in_bar: Vec::new(),
             }));
         }
     }
+    record_in_registry(Path::new(&config.paths.out_dir), &run_dir);
     Ok(format!("paper run complete: {}", run_dir.display()))
 }
 
@@ -678,21 +1179,61 @@ fn run_paper_realtime(
     tx: &tokio::sync::mpsc::UnboundedSender<TaskEvent>,
     control: Option<&TaskControl>,
     agent_llm: Option<&AgentLlmRuntime>,
+    resume_session: bool,
+    simulated: bool,
 ) -> Result<String, String> {
     use kairos_domain::repositories::market_stream::MarketStream;
 
-    if config.db.exchange.to_lowercase() != "kucoin" || config.db.market.to_lowercase() != "spot" {
+    let simulator = simulated.then(|| simulated_market(config)).transpose()?;
+    if simulator.is_none()
+        && (config.db.exchange.to_lowercase() != "kucoin"
+            || config.db.market.to_lowercase() != "spot")
+    {
         return Err(
             "paper realtime currently supports only db.exchange=kucoin and db.market=spot"
                 .to_string(),
         );
     }
+    if let Some(symbols) = kairos_application::paper_trading::multi_symbol::paper_symbols(config)
+        .map_err(|err| err.to_string())?
+    {
+        return run_paper_realtime_multi(
+            config,
+            config_toml,
+            tx,
+            control,
+            agent_llm,
+            resume_session,
+            symbols,
+            simulator,
+        );
+    }
 
-    let sentiment_repo = build_sentiment_repo();
+    let resume = load_resume_session(config, resume_session)?;
+    let sentiment_repo = crate::headless::build_sentiment_repo(config)?;
     let artifacts = FilesystemArtifactWriter::new();
-    let remote_agent = build_remote_agent(config, agent_llm)?;
+    let remote_agent = build_remote_agent(config, agent_llm, Some(agent_observer(tx)))?;
+    let telegram = TelegramNotifier::from_env()?;
+    let notifications = telegram.as_ref().map(|notifier| {
+        RefCell::new(PaperNotifications::new(
+            notifier,
+            &config.run.run_id,
+            &config.run.symbol,
+        ))
+    });
+    let mode = if simulator.is_some() {
+        "simulated"
+    } else {
+        "realtime"
+    };
+    if let Some(notifications) = &notifications {
+        notifications.borrow_mut().on_start(mode);
+    }
 
     let mut connect_stream = || -> Result<Box<dyn MarketStream>, String> {
+        if let Some(market) = &simulator {
+            return Ok(Box::new(market.connect(config.run.symbol.clone())?));
+        }
         #[cfg(feature = "realtime-kucoin")]
         {
             let stream =
@@ -707,7 +1248,17 @@ fn run_paper_realtime(
         }
     };
 
-    let mut progress = |p: kairos_domain::services::engine::backtest::BarProgress| {
+    let mut progress = |event: ProgressEvent<'_>| {
+        let ProgressEvent::BarProcessed(p) = event else {
+            return;
+        };
+        if let Some(notifications) = &notifications {
+            notifications.borrow_mut().on_progress(p);
+        }
+        let _ = tx.send(TaskEvent::Position(PositionSample::from_progress(
+            &config.run.symbol,
+            p,
+        )));
         let bar_index = p.bar_index;
         let x = bar_index as f64;
         let has_trades = !p.trades_in_bar.is_empty();
@@ -715,7 +1266,7 @@ fn run_paper_realtime(
         if bar_index.is_multiple_of(STREAM_EVERY_N_BARS) || has_trades {
             let trades_in_bar = if has_trades {
                 p.trades_in_bar
-                    .into_iter()
+                    .iter()
                     .map(|t| TradeSample {
                         bar_index,
                         timestamp: t.timestamp,
@@ -731,23 +1282,20 @@ fn run_paper_realtime(
                 x,
                 price: p.close,
                 equity: p.equity,
+                position_qty: Some(p.position_qty),
                 trades_in_bar,
             }));
         }
     };
 
     let mut on_status = |s: kairos_application::paper_trading::RealtimeStreamStatus| {
-        let _ = tx.send(TaskEvent::StreamStatus(StreamStatusSample {
-            connected: s.connected,
-            reconnects: s.reconnects,
-            last_error: s.last_error,
-            last_event_timestamp: s.last_event_timestamp,
-            out_of_order_events: s.out_of_order_events,
-            invalid_events: s.invalid_events,
-        }));
+        if let Some(notifications) = &notifications {
+            notifications.borrow_mut().on_status(&s);
+        }
+        let _ = tx.send(TaskEvent::StreamStatus(stream_status_sample(s)));
     };
 
-    let run_dir = if let Some(control) = control {
+    let result = if let Some(control) = control {
         kairos_application::paper_trading::run_paper_realtime_streaming_control(
             config,
             config_toml,
@@ -756,17 +1304,296 @@ fn run_paper_realtime(
             sentiment_repo.as_ref(),
             &artifacts,
             remote_agent,
+            resume,
             control as &dyn kairos_domain::services::engine::backtest::RunControl,
             &mut progress,
             &mut on_status,
-        )?
+        )
     } else {
         return Err("paper realtime requires a RunControl (pause/stop)".to_string());
     };
+    if let Some(notifications) = &notifications {
+        notifications.borrow_mut().on_finish(&result);
+    }
+    let run_dir = result.map_err(|err| err.to_string())?;
+
+    record_in_registry(Path::new(&config.paths.out_dir), &run_dir);
+    Ok(run_dir.display().to_string())
+}
+
+// The `[paper.simulator]` market, its clock starting now. Without a seed every session walks
+// differently.
+fn simulated_market(
+    config: &kairos_application::config::Config,
+) -> Result<SimulatedMarket, String> {
+    let settings = config
+        .paper
+        .as_ref()
+        .and_then(|paper| paper.simulator.clone())
+        .unwrap_or_default();
+    let defaults = SimulatorSpec::default();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    SimulatedMarket::new(SimulatorSpec {
+        start_price: settings.start_price.unwrap_or(defaults.start_price),
+        volatility: settings.volatility.unwrap_or(defaults.volatility),
+        tick_interval: settings
+            .tick_ms
+            .map(std::time::Duration::from_millis)
+            .unwrap_or(defaults.tick_interval),
+        tick_seconds: settings.tick_seconds.unwrap_or(defaults.tick_seconds),
+        disconnect_rate: settings.disconnect_rate.unwrap_or(defaults.disconnect_rate),
+        seed: settings.seed.unwrap_or(now.as_nanos() as u64),
+        start_ts: now.as_secs() as i64,
+    })
+}
+
+// One session per symbol in `paper.symbols`; the chart follows the first symbol's price and the
+// combined equity of all sleeves.
+#[allow(clippy::too_many_arguments)]
+fn run_paper_realtime_multi(
+    config: &kairos_application::config::Config,
+    config_toml: &str,
+    tx: &tokio::sync::mpsc::UnboundedSender<TaskEvent>,
+    control: Option<&TaskControl>,
+    agent_llm: Option<&AgentLlmRuntime>,
+    resume_session: bool,
+    symbols: Vec<String>,
+    simulator: Option<SimulatedMarket>,
+) -> Result<String, String> {
+    use kairos_application::paper_trading::multi_symbol;
+    use kairos_domain::repositories::market_stream::MultiMarketStream;
+
+    let Some(control) = control else {
+        return Err("paper realtime requires a RunControl (pause/stop)".to_string());
+    };
+    let resume = if resume_session {
+        multi_symbol::load_multi_paper_sessions(config, None, &FilesystemArtifactReader::new())?
+    } else {
+        Vec::new()
+    };
+    let sentiment_repo = crate::headless::build_sentiment_repo(config)?;
+    let artifacts = FilesystemArtifactWriter::new();
+    let telegram = TelegramNotifier::from_env()?;
+    let notifications = telegram.as_ref().map(|notifier| {
+        RefCell::new(PaperNotifications::new(
+            notifier,
+            &config.run.run_id,
+            &symbols.join(","),
+        ))
+    });
+    let mode = if simulator.is_some() {
+        "simulated"
+    } else {
+        "realtime"
+    };
+    if let Some(notifications) = &notifications {
+        notifications.borrow_mut().on_start(mode);
+    }
+
+    let mut connect_stream = || -> Result<Box<dyn MultiMarketStream>, String> {
+        if let Some(market) = &simulator {
+            return Ok(Box::new(market.connect_many(symbols.clone())?));
+        }
+        #[cfg(feature = "realtime-kucoin")]
+        {
+            let stream =
+                kairos_infrastructure::market_stream::kucoin::KucoinPublicTickerStream::connect_many(
+                    symbols.clone(),
+                )?;
+            Ok(Box::new(stream))
+        }
+        #[cfg(not(feature = "realtime-kucoin"))]
+        {
+            Err("kairos-alloy was built without feature realtime-kucoin".to_string())
+        }
+    };
+    let mut remote_agent = || build_remote_agent(config, agent_llm, Some(agent_observer(tx)));
+
+    let mut equities: Vec<f64> =
+        vec![config.run.initial_capital / symbols.len() as f64; symbols.len()];
+    let mut last_price = f64::NAN;
+    let mut bars = 0u64;
+    let mut progress = |symbol: &str, p: kairos_domain::services::engine::backtest::BarProgress| {
+        let Some(idx) = symbols.iter().position(|s| s == symbol) else {
+            return;
+        };
+        equities[idx] = p.equity;
+        let _ = tx.send(TaskEvent::Position(PositionSample::from_progress(
+            symbol, &p,
+        )));
+        if idx == 0 {
+            last_price = p.close;
+        }
+        bars += 1;
+        let has_trades = !p.trades_in_bar.is_empty();
+        if bars.is_multiple_of(STREAM_EVERY_N_BARS) || has_trades {
+            let trades_in_bar = p
+                .trades_in_bar
+                .into_iter()
+                .filter(|_| idx == 0)
+                .map(|t| TradeSample {
+                    bar_index: bars,
+                    timestamp: t.timestamp,
+                    side: t.side,
+                    quantity: t.quantity,
+                    price: t.price,
+                })
+                .collect::<Vec<_>>();
+            let _ = tx.send(TaskEvent::Progress(BarProgressSample {
+                x: bars as f64,
+                price: last_price,
+                equity: equities.iter().sum(),
+                position_qty: None,
+                trades_in_bar,
+            }));
+        }
+    };
+
+    let mut on_status = |s: kairos_application::paper_trading::RealtimeStreamStatus| {
+        if let Some(notifications) = &notifications {
+            notifications.borrow_mut().on_status(&s);
+        }
+        let _ = tx.send(TaskEvent::StreamStatus(stream_status_sample(s)));
+    };
 
+    let result = multi_symbol::run_paper_realtime_multi_control(
+        config,
+        config_toml,
+        None,
+        &mut connect_stream,
+        sentiment_repo.as_ref(),
+        &artifacts,
+        &mut remote_agent,
+        resume,
+        control as &dyn kairos_domain::services::engine::backtest::RunControl,
+        &mut progress,
+        &mut on_status,
+    );
+    if let Some(notifications) = &notifications {
+        notifications.borrow_mut().on_finish(&result);
+    }
+    let run_dir = result.map_err(|err| err.to_string())?;
+
+    for entry in std::fs::read_dir(&run_dir)
+        .map_err(|err| format!("failed to list {}: {err}", run_dir.display()))?
+        .flatten()
+    {
+        if entry.path().is_dir() {
+            record_in_registry(Path::new(&config.paths.out_dir), &entry.path());
+        }
+    }
     Ok(run_dir.display().to_string())
 }
 
+// Streams the run's journal into the Monitor until the run records its `end` (or the follow is
+// cancelled); the run itself is untouched either way.
+fn run_follow_task(
+    run_dir: &Path,
+    tx: &tokio::sync::mpsc::UnboundedSender<TaskEvent>,
+    control: &TaskControl,
+) -> Result<String, String> {
+    use crate::follow::{latest_session, FollowUpdate, JournalFollower};
+    use kairos_domain::repositories::artifacts::ArtifactReader;
+
+    let journal = run_dir.join(kairos_application::paper_trading::live::LIVE_JOURNAL_FILE);
+    let reader = FilesystemArtifactReader::new();
+    if !reader.exists(&journal) {
+        return Err(format!("{} not found", journal.display()));
+    }
+    let mut follower = JournalFollower::default();
+    let mut offset = 0;
+    let mut first_read = true;
+    let mut trades_in_bar = Vec::new();
+    loop {
+        if control.is_cancelled() {
+            return Err("follow cancelled".to_string());
+        }
+        let (records, next_offset) = reader.read_jsonl_from(&journal, offset)?;
+        offset = next_offset;
+        let records = if first_read {
+            first_read = false;
+            latest_session(&records)
+        } else {
+            &records[..]
+        };
+        for (idx, record) in records.iter().enumerate() {
+            match follower.apply(record) {
+                FollowUpdate::Started {
+                    run_id,
+                    symbol,
+                    mode,
+                } => {
+                    tracing::info!(run_dir = %run_dir.display(), %run_id, %symbol, %mode, "following run")
+                }
+                FollowUpdate::Fill(trade) => {
+                    tracing::info!(
+                        side = ?trade.side,
+                        qty = trade.quantity,
+                        price = trade.price,
+                        "followed run filled"
+                    );
+                    trades_in_bar.push(trade);
+                }
+                FollowUpdate::Equity {
+                    bar_index,
+                    close,
+                    equity,
+                } => {
+                    // Catching up on a long journal sends every Nth bar, like a running task.
+                    let last = idx + 1 == records.len();
+                    if bar_index.is_multiple_of(STREAM_EVERY_N_BARS)
+                        || !trades_in_bar.is_empty()
+                        || last
+                    {
+                        let _ = tx.send(TaskEvent::Progress(BarProgressSample {
+                            x: bar_index as f64,
+                            price: close,
+                            equity,
+                            position_qty: None,
+                            trades_in_bar: std::mem::take(&mut trades_in_bar),
+                        }));
+                    }
+                }
+                FollowUpdate::Ended(status) => {
+                    return Ok(format!("{} ended: {status}", run_dir.display()));
+                }
+                FollowUpdate::Other => {}
+            }
+        }
+        std::thread::sleep(FOLLOW_POLL);
+    }
+}
+
+fn stream_status_sample(
+    s: kairos_application::paper_trading::RealtimeStreamStatus,
+) -> StreamStatusSample {
+    StreamStatusSample {
+        connected: s.connected,
+        reconnects: s.reconnects,
+        last_error: s.last_error,
+        last_event_timestamp: s.last_event_timestamp,
+        out_of_order_events: s.out_of_order_events,
+        invalid_events: s.invalid_events,
+        symbols: s.symbols,
+    }
+}
+
+fn record_in_registry(out_dir: &Path, run_dir: &Path) {
+    let registry_path = kairos_application::registry::registry_path(out_dir);
+    let result = SqliteRunRegistry::open(&registry_path)
+        .and_then(|registry| kairos_application::registry::record_run(run_dir, &registry));
+    if let Err(err) = result {
+        tracing::warn!(
+            run_dir = %run_dir.display(),
+            registry = %registry_path.display(),
+            error = %err,
+            "failed to record run in registry"
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::TaskControl;
@@ -0,0 +1,149 @@
+// OTLP trace export (feature `otel`): every `tracing` span (backtest stages, agent calls, OHLCV/
+// sentiment loads, sweep trials, ...) is also sent to an OpenTelemetry collector. It is driven by
+// the standard env vars: export is on when `OTEL_EXPORTER_OTLP_ENDPOINT` or
+// `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT` is set (or `OTEL_TRACES_EXPORTER=otlp`), and off with
+// `OTEL_TRACES_EXPORTER=none` or `OTEL_SDK_DISABLED=true`. Only OTLP/HTTP protobuf is built in.
+
+#[cfg(feature = "otel")]
+use std::sync::OnceLock;
+
+#[cfg(feature = "otel")]
+static PROVIDER: OnceLock<opentelemetry_sdk::trace::SdkTracerProvider> = OnceLock::new();
+
+#[cfg(feature = "otel")]
+const DEFAULT_SERVICE_NAME: &str = "kairos-alloy";
+
+// Whether the `OTEL_*` variables ask for trace export; `env` is `std::env::var` in practice.
+fn otlp_enabled(env: &dyn Fn(&str) -> Option<String>) -> Result<bool, String> {
+    let var = |name: &str| {
+        env(name)
+            .map(|v| v.trim().to_lowercase())
+            .filter(|v| !v.is_empty())
+    };
+    if var("OTEL_SDK_DISABLED").as_deref() == Some("true") {
+        return Ok(false);
+    }
+    let enabled = match var("OTEL_TRACES_EXPORTER").as_deref() {
+        Some("none") => return Ok(false),
+        Some("otlp") => true,
+        Some(other) => {
+            return Err(format!(
+            "environment variable OTEL_TRACES_EXPORTER={other} is not supported (use otlp or none)"
+        ))
+        }
+        None => {
+            var("OTEL_EXPORTER_OTLP_ENDPOINT").is_some()
+                || var("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT").is_some()
+        }
+    };
+    if enabled {
+        let protocol = var("OTEL_EXPORTER_OTLP_TRACES_PROTOCOL")
+            .or_else(|| var("OTEL_EXPORTER_OTLP_PROTOCOL"));
+        if let Some(protocol) = protocol.filter(|p| p != "http/protobuf") {
+            return Err(format!(
+                "environment variable OTEL_EXPORTER_OTLP_PROTOCOL={protocol} is not supported (only http/protobuf)"
+            ));
+        }
+    }
+    Ok(enabled)
+}
+
+// The `tracing` layer that exports spans, or `None` when the env does not enable it.
+#[cfg(feature = "otel")]
+pub fn otel_layer<S>() -> Result<
+    Option<tracing_opentelemetry::OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>>,
+    String,
+>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::WithExportConfig;
+
+    if !otlp_enabled(&|name| std::env::var(name).ok())? {
+        return Ok(None);
+    }
+    // Endpoint, headers, timeout and compression come from the `OTEL_EXPORTER_OTLP_*` env vars.
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_protocol(opentelemetry_otlp::Protocol::HttpBinary)
+        .build()
+        .map_err(|err| format!("failed to build OTLP span exporter: {err}"))?;
+    let mut resource = opentelemetry_sdk::Resource::builder().with_attribute(
+        opentelemetry::KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+    );
+    if std::env::var("OTEL_SERVICE_NAME").is_err() {
+        resource = resource.with_service_name(DEFAULT_SERVICE_NAME);
+    }
+    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource.build())
+        .build();
+    let tracer = provider.tracer(DEFAULT_SERVICE_NAME);
+    let _ = PROVIDER.set(provider);
+    Ok(Some(tracing_opentelemetry::layer().with_tracer(tracer)))
+}
+
+#[cfg(not(feature = "otel"))]
+pub fn otel_layer() -> Result<Option<tracing_subscriber::layer::Identity>, String> {
+    if otlp_enabled(&|name| std::env::var(name).ok())? {
+        tracing::warn!("OTEL_* trace export requested but this build has no `otel` feature");
+    }
+    Ok(None)
+}
+
+// Flushes the spans still queued in the batch exporter; call before `std::process::exit`.
+pub fn shutdown() {
+    #[cfg(feature = "otel")]
+    if let Some(provider) = PROVIDER.get() {
+        if let Err(err) = provider.shutdown() {
+            eprintln!("warning: failed to flush OTLP spans: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::otlp_enabled;
+
+    fn enabled(vars: &[(&str, &str)]) -> Result<bool, String> {
+        otlp_enabled(&|name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn export_follows_the_standard_otel_env_vars() {
+        assert_eq!(enabled(&[]), Ok(false));
+        assert_eq!(
+            enabled(&[("OTEL_EXPORTER_OTLP_ENDPOINT", "http://tempo:4318")]),
+            Ok(true)
+        );
+        assert_eq!(enabled(&[("OTEL_TRACES_EXPORTER", "otlp")]), Ok(true));
+        assert_eq!(
+            enabled(&[
+                (
+                    "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+                    "http://jaeger:4318/v1/traces"
+                ),
+                ("OTEL_TRACES_EXPORTER", "none"),
+            ]),
+            Ok(false)
+        );
+        assert_eq!(
+            enabled(&[
+                ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://tempo:4318"),
+                ("OTEL_SDK_DISABLED", "true"),
+            ]),
+            Ok(false)
+        );
+        assert!(enabled(&[
+            ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://tempo:4317"),
+            ("OTEL_EXPORTER_OTLP_PROTOCOL", "grpc"),
+        ])
+        .is_err());
+        assert!(enabled(&[("OTEL_TRACES_EXPORTER", "zipkin")]).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+    Colorblind,
+}
+
+impl ThemeName {
+    pub const ALL: [ThemeName; 4] = [
+        ThemeName::Dark,
+        ThemeName::Light,
+        ThemeName::HighContrast,
+        ThemeName::Colorblind,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ThemeName::Dark => "dark",
+            ThemeName::Light => "light",
+            ThemeName::HighContrast => "high_contrast",
+            ThemeName::Colorblind => "colorblind",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|name| *name == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+// The TUI palette by role, so views never pick raw colors: `ok`/`error` also color gains/losses
+// and buys/sells, `warn` marks selections and pending states, `accent`/`accent_alt` the charts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub name: ThemeName,
+    pub text: Color,
+    pub muted: Color,
+    pub ok: Color,
+    pub error: Color,
+    pub warn: Color,
+    pub accent: Color,
+    pub accent_alt: Color,
+}
+
+impl Theme {
+    pub fn from_name(name: ThemeName) -> Self {
+        match name {
+            // The original palette.
+            ThemeName::Dark => Self {
+                name,
+                text: Color::Reset,
+                muted: Color::DarkGray,
+                ok: Color::Green,
+                error: Color::Red,
+                warn: Color::Yellow,
+                accent: Color::Cyan,
+                accent_alt: Color::Magenta,
+            },
+            // Dark shades from the 256-color cube: yellow and cyan vanish on a white background.
+            ThemeName::Light => Self {
+                name,
+                text: Color::Reset,
+                muted: Color::Indexed(244),
+                ok: Color::Indexed(28),
+                error: Color::Indexed(124),
+                warn: Color::Indexed(130),
+                accent: Color::Indexed(25),
+                accent_alt: Color::Indexed(90),
+            },
+            ThemeName::HighContrast => Self {
+                name,
+                text: Color::White,
+                muted: Color::Gray,
+                ok: Color::LightGreen,
+                error: Color::LightRed,
+                warn: Color::LightYellow,
+                accent: Color::LightCyan,
+                accent_alt: Color::LightMagenta,
+            },
+            // Blue/orange instead of green/red (Okabe-Ito), which red-green colorblind users can
+            // tell apart.
+            ThemeName::Colorblind => Self {
+                name,
+                text: Color::Reset,
+                muted: Color::Indexed(245),
+                ok: Color::Indexed(32),
+                error: Color::Indexed(208),
+                warn: Color::Indexed(220),
+                accent: Color::Indexed(117),
+                accent_alt: Color::Indexed(166),
+            },
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::from_name(ThemeName::Dark)
+    }
+}
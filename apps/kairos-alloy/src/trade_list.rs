@@ -0,0 +1,343 @@
+use crate::app::{TextInput, ViewId};
+use chrono::{NaiveDate, TimeZone, Timelike, Utc};
+use kairos_domain::repositories::artifacts::ArtifactReader;
+use kairos_domain::services::montecarlo::realized_pnl_by_trade;
+use kairos_domain::value_objects::side::Side;
+use kairos_domain::value_objects::trade::Trade;
+use kairos_infrastructure::artifacts::FilesystemArtifactReader;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    None,
+    // Hour of the day (UTC) the trade was filled in.
+    Hour,
+    Reason,
+    Strategy,
+}
+
+impl GroupBy {
+    pub fn next(self) -> Self {
+        match self {
+            Self::None => Self::Hour,
+            Self::Hour => Self::Reason,
+            Self::Reason => Self::Strategy,
+            Self::Strategy => Self::None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Hour => "hour (UTC)",
+            Self::Reason => "reason",
+            Self::Strategy => "strategy_id",
+        }
+    }
+}
+
+// `side=buy|sell`, `from=`/`to=` (epoch seconds, RFC3339 or YYYY-MM-DD, inclusive) and
+// `min_pnl=` (only sells with at least that realized PnL pass).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TradeFilter {
+    pub side: Option<Side>,
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    pub min_pnl: Option<f64>,
+}
+
+impl TradeFilter {
+    // Comma-separated `key=value` pairs; an empty string clears every filter.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let mut filter = Self::default();
+        for part in raw
+            .split(',')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+        {
+            let Some((key, value)) = part.split_once('=') else {
+                return Err(format!("filter '{part}': expected key=value"));
+            };
+            let value = value.trim();
+            match key.trim().to_lowercase().as_str() {
+                "side" => {
+                    filter.side = Some(match value.to_lowercase().as_str() {
+                        "buy" => Side::Buy,
+                        "sell" => Side::Sell,
+                        other => return Err(format!("filter side: '{other}' is not buy|sell")),
+                    })
+                }
+                "from" => filter.from = Some(parse_time(value)?),
+                "to" => filter.to = Some(parse_time(value)?),
+                "min_pnl" => {
+                    filter.min_pnl = Some(
+                        value
+                            .parse::<f64>()
+                            .map_err(|_| format!("filter min_pnl: invalid number '{value}'"))?,
+                    )
+                }
+                other => {
+                    return Err(format!(
+                        "unknown filter '{other}' (side, from, to, min_pnl)"
+                    ))
+                }
+            }
+        }
+        Ok(filter)
+    }
+
+    fn matches(&self, trade: &Trade, pnl: Option<f64>) -> bool {
+        self.side.is_none_or(|side| trade.side == side)
+            && self.from.is_none_or(|from| trade.timestamp >= from)
+            && self.to.is_none_or(|to| trade.timestamp <= to)
+            && self
+                .min_pnl
+                .is_none_or(|min| pnl.is_some_and(|pnl| pnl >= min))
+    }
+}
+
+// A bare date is its midnight (UTC).
+fn parse_time(raw: &str) -> Result<i64, String> {
+    if let Ok(seconds) = raw.parse::<i64>() {
+        return Ok(seconds);
+    }
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(raw) {
+        return Ok(dt.timestamp());
+    }
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|dt| Utc.from_utc_datetime(&dt).timestamp())
+        .ok_or_else(|| format!("invalid time '{raw}' (epoch seconds, RFC3339 or YYYY-MM-DD)"))
+}
+
+// The visible trades of one group key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeGroup {
+    pub key: String,
+    pub trades: usize,
+    pub quantity: f64,
+    pub fees: f64,
+    // Realized PnL of the group's sells.
+    pub pnl: f64,
+    pub wins: usize,
+    pub losses: usize,
+}
+
+// The trades of a run from trades.csv, each with the realized PnL of its fill (sells against the
+// average cost of the position), filtered and optionally grouped.
+pub struct TradeList {
+    pub run_id: String,
+    pub trades: Vec<Trade>,
+    pub pnls: Vec<Option<f64>>,
+    // Indices into `trades` that pass the filter, in fill order.
+    pub visible: Vec<usize>,
+    pub selected: usize,
+    pub group_by: GroupBy,
+    pub filter_input: TextInput,
+    pub editing_filter: bool,
+    filter: TradeFilter,
+    // Where Esc goes back to.
+    pub back_to: ViewId,
+}
+
+impl TradeList {
+    pub fn load(run_dir: &Path, back_to: ViewId) -> Result<Self, String> {
+        let reader = FilesystemArtifactReader::new();
+        let path = run_dir.join("trades.csv");
+        if !reader.exists(&path) {
+            return Err(format!("no trades.csv in {}", run_dir.display()));
+        }
+        let trades = reader.read_trades_csv(&path)?;
+        let run_id = run_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| run_dir.display().to_string());
+        let mut list = Self {
+            run_id,
+            pnls: realized_pnl_by_trade(&trades),
+            visible: Vec::new(),
+            trades,
+            selected: 0,
+            group_by: GroupBy::None,
+            filter_input: TextInput::new(String::new()),
+            editing_filter: false,
+            filter: TradeFilter::default(),
+            back_to,
+        };
+        list.apply();
+        Ok(list)
+    }
+
+    // Applies the filter being typed; on error the previous filter stays.
+    pub fn apply_filter_input(&mut self) -> Result<(), String> {
+        self.filter = TradeFilter::parse(&self.filter_input.value)?;
+        self.apply();
+        Ok(())
+    }
+
+    fn apply(&mut self) {
+        self.visible = (0..self.trades.len())
+            .filter(|&idx| self.filter.matches(&self.trades[idx], self.pnls[idx]))
+            .collect();
+        self.selected = self.selected.min(self.visible.len().saturating_sub(1));
+    }
+
+    pub fn cycle_group_by(&mut self) {
+        self.group_by = self.group_by.next();
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(self.visible.len().saturating_sub(1));
+    }
+
+    pub fn select_last(&mut self) {
+        self.selected = self.visible.len().saturating_sub(1);
+    }
+
+    pub fn visible_trades(&self) -> impl Iterator<Item = (&Trade, Option<f64>)> {
+        self.visible
+            .iter()
+            .map(|&idx| (&self.trades[idx], self.pnls[idx]))
+    }
+
+    // Realized PnL of the visible trades.
+    pub fn visible_pnl(&self) -> f64 {
+        self.visible_trades().filter_map(|(_, pnl)| pnl).sum()
+    }
+
+    // The visible trades aggregated by `group_by`, ordered by key; empty when not grouping.
+    pub fn groups(&self) -> Vec<TradeGroup> {
+        let mut groups: BTreeMap<String, TradeGroup> = BTreeMap::new();
+        for (trade, pnl) in self.visible_trades() {
+            let key = match self.group_by {
+                GroupBy::None => return Vec::new(),
+                GroupBy::Hour => Utc
+                    .timestamp_opt(trade.timestamp, 0)
+                    .single()
+                    .map(|dt| format!("{:02}:00", dt.hour()))
+                    .unwrap_or_else(|| "-".to_string()),
+                GroupBy::Reason => trade.reason.clone(),
+                GroupBy::Strategy => trade.strategy_id.clone(),
+            };
+            let group = groups.entry(key.clone()).or_insert_with(|| TradeGroup {
+                key,
+                trades: 0,
+                quantity: 0.0,
+                fees: 0.0,
+                pnl: 0.0,
+                wins: 0,
+                losses: 0,
+            });
+            group.trades += 1;
+            group.quantity += trade.quantity;
+            group.fees += trade.fee;
+            if let Some(pnl) = pnl {
+                group.pnl += pnl;
+                if pnl > 0.0 {
+                    group.wins += 1;
+                } else if pnl < 0.0 {
+                    group.losses += 1;
+                }
+            }
+        }
+        groups.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GroupBy, TradeFilter, TradeList};
+    use crate::app::{TextInput, ViewId};
+    use kairos_domain::repositories::artifacts::ArtifactWriter;
+    use kairos_domain::value_objects::side::Side;
+    use kairos_domain::value_objects::trade::Trade;
+    use kairos_infrastructure::artifacts::FilesystemArtifactWriter;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn trade(timestamp: i64, side: Side, price: f64, reason: &str) -> Trade {
+        Trade {
+            timestamp,
+            symbol: "BTCUSD".into(),
+            side,
+            quantity: 1.0,
+            price,
+            fee: 0.0,
+            slippage: 0.0,
+            strategy_id: "simple_sma".to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    #[test]
+    fn trades_filter_and_group_with_their_realized_pnl() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let dir = std::env::temp_dir().join(format!("kairos_tui_trades_{unique}"));
+        std::fs::create_dir_all(&dir).expect("mkdir");
+        assert!(TradeList::load(&dir, ViewId::Runs)
+            .err()
+            .expect("missing")
+            .contains("no trades.csv"));
+
+        // 2024-01-01 at 09:00, 10:00, 09:30 and 15:00 UTC.
+        let trades = [
+            trade(1_704_099_600, Side::Buy, 100.0, "signal"),
+            trade(1_704_103_200, Side::Sell, 110.0, "signal"),
+            trade(1_704_101_400 + 86_400, Side::Buy, 100.0, "signal"),
+            trade(1_704_121_200 + 86_400, Side::Sell, 95.0, "stop_loss"),
+        ];
+        FilesystemArtifactWriter::new()
+            .write_trades_csv(&dir.join("trades.csv"), &trades)
+            .expect("write");
+
+        let mut list = TradeList::load(&dir, ViewId::Runs).expect("load");
+        assert_eq!(list.pnls, vec![None, Some(10.0), None, Some(-5.0)]);
+        assert_eq!(list.visible_pnl(), 5.0);
+        assert!(list.groups().is_empty());
+
+        list.cycle_group_by();
+        assert_eq!(list.group_by, GroupBy::Hour);
+        let hours: Vec<_> = list
+            .groups()
+            .into_iter()
+            .map(|g| (g.key, g.trades))
+            .collect();
+        assert_eq!(
+            hours,
+            [
+                ("09:00".to_string(), 2),
+                ("10:00".to_string(), 1),
+                ("15:00".to_string(), 1)
+            ]
+        );
+        list.cycle_group_by();
+        let reasons = list.groups();
+        assert_eq!(reasons[1].key, "stop_loss");
+        assert_eq!((reasons[1].pnl, reasons[1].losses), (-5.0, 1));
+
+        list.filter_input = TextInput::new("side=sell, from=2024-01-02".to_string());
+        list.apply_filter_input().expect("filter");
+        assert_eq!(list.visible, vec![3]);
+        list.filter_input = TextInput::new("min_pnl=0".to_string());
+        list.apply_filter_input().expect("filter");
+        assert_eq!(list.visible, vec![1]);
+
+        list.filter_input = TextInput::new("side=long".to_string());
+        assert!(list.apply_filter_input().unwrap_err().contains("buy|sell"));
+        assert_eq!(list.visible, vec![1]);
+        assert!(TradeFilter::parse("pnl>3")
+            .unwrap_err()
+            .contains("key=value"));
+        assert!(TradeFilter::parse("to=yesterday")
+            .unwrap_err()
+            .contains("invalid time"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::app::ViewId;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+// Where the last TUI session left off, saved to `tui_state.json` on quit and restored on the next
+// launch. Missing keys fall back to their defaults, so older files keep loading.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TuiState {
+    pub view: Option<ViewId>,
+    pub config_path: Option<PathBuf>,
+    // The run browser selection, by run dir, and the Reports selection, by run id.
+    pub selected_run_dir: Option<String>,
+    pub reports_run_id: Option<String>,
+    pub log_scroll: usize,
+    pub trade_scroll: usize,
+    pub metrics_scroll: usize,
+}
+
+pub fn load_state_from(path: &Path) -> Result<TuiState, String> {
+    if !path.exists() {
+        return Ok(TuiState::default());
+    }
+    let contents = std::fs::read_to_string(path)
+        .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+    serde_json::from_str(&contents)
+        .map_err(|err| format!("failed to parse {}: {err}", path.display()))
+}
+
+pub fn store_state_to(path: &Path, state: &TuiState) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("failed to create {}: {err}", parent.display()))?;
+    }
+    let json = serde_json::to_string_pretty(state)
+        .map_err(|err| format!("failed to serialize TUI state: {err}"))?;
+    std::fs::write(path, json).map_err(|err| format!("failed to write {}: {err}", path.display()))
+}
+
+// Views that need data from the session that produced them reopen on the view they came from.
+pub fn restorable_view(view: ViewId) -> ViewId {
+    match view {
+        ViewId::ConfigEditor => ViewId::Setup,
+        ViewId::Chart | ViewId::Equity | ViewId::Trades | ViewId::ConfigDiff => ViewId::Runs,
+        ViewId::Leaderboard | ViewId::SweepHistory => ViewId::Experiments,
+        ViewId::DataQuality => ViewId::Backtest,
+        ViewId::Onboarding => ViewId::MainMenu,
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_state_from, restorable_view, store_state_to, TuiState};
+    use crate::app::ViewId;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn state_roundtrips_and_transient_views_fall_back() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let path = std::env::temp_dir().join(format!("kairos_tui_state_{unique}.json"));
+        assert_eq!(
+            load_state_from(&path).expect("missing"),
+            TuiState::default()
+        );
+
+        let state = TuiState {
+            view: Some(ViewId::Runs),
+            config_path: Some(PathBuf::from("platform/ops/configs/sample.toml")),
+            selected_run_dir: Some("runs/btc_1".to_string()),
+            log_scroll: 12,
+            ..TuiState::default()
+        };
+        store_state_to(&path, &state).expect("store");
+        assert!(std::fs::read_to_string(&path)
+            .expect("read")
+            .contains("\"view\": \"runs\""));
+        assert_eq!(load_state_from(&path).expect("load"), state);
+
+        std::fs::write(&path, r#"{"view":"data_quality"}"#).expect("write");
+        let state = load_state_from(&path).expect("partial");
+        assert_eq!(state.log_scroll, 0);
+        assert_eq!(state.view.map(restorable_view), Some(ViewId::Backtest));
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,12 +1,21 @@
 use crate::app::{
-    App, BacktestTab, ExperimentsFocus, QuickEditField, ReportsMode, SetupFocus, ViewId,
+    App, BacktestTab, ExperimentsFocus, QuickEditField, ReportsMode, SetupFocus, TaskState, ViewId,
 };
+use crate::config_form::FormFocus;
+use crate::job_queue::JobState;
+use crate::keymap::Action;
+use crate::metrics_view::MetricKind;
+use crate::risk_status::{risk_gauges, Level as RiskLevel, RiskGauge};
+use crate::stage_timings::StageTiming;
 use kairos_domain::value_objects::side::Side;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
+use ratatui::symbols::Marker;
 use ratatui::text::{Line, Span};
+use ratatui::widgets::canvas::{Canvas, Line as CanvasLine, Points, Rectangle};
 use ratatui::widgets::{
-    Axis, Block, Borders, Chart, Dataset, GraphType, List, ListItem, Paragraph, Tabs, Wrap,
+    Axis, Bar, BarChart, BarGroup, Block, Borders, Chart, Clear, Dataset, Gauge, GraphType,
+    LineGauge, List, ListItem, Paragraph, Sparkline, Tabs, Wrap,
 };
 use ratatui::Frame;
 use std::path::PathBuf;
@@ -34,20 +43,110 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
     draw_sidebar(frame, body[0], app);
     draw_main(frame, body[1], app);
     draw_bottom(frame, outer[2], app);
+    if let Some(toast) = &app.toast {
+        draw_toast(frame, size, app, toast);
+    }
+    if app.show_help {
+        draw_help_overlay(frame, size, app);
+    }
+}
+
+// The finished-task toast, in the top-right corner above the view.
+fn draw_toast(frame: &mut Frame, area: Rect, app: &App, toast: &crate::notify::Toast) {
+    let theme = app.theme;
+    let color = if toast.failed { theme.error } else { theme.ok };
+    let width = area.width.min(60);
+    let inner_width = width.saturating_sub(2).max(1) as usize;
+    let lines = toast.text.chars().count().div_ceil(inner_width).clamp(1, 4) as u16;
+    let popup = Rect {
+        x: area.x + area.width - width,
+        y: area.y + 1,
+        width,
+        height: (lines + 2).min(area.height.saturating_sub(1)),
+    };
+    frame.render_widget(Clear, popup);
+    frame.render_widget(
+        Paragraph::new(toast.text.clone())
+            .style(Style::default().fg(color))
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .title(if toast.failed { "Failed" } else { "Finished" })
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(color)),
+            ),
+        popup,
+    );
+}
+
+fn draw_help_overlay(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = app.theme;
+    let help = crate::help::view_help(app.active_view);
+    let rows = crate::help::help_rows(&app.keymap, app.active_view);
+    let width = area.width.min(80);
+    let height = (rows.len() as u16 + 8).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+
+    let mut lines: Vec<Line> = vec![
+        Line::from(Span::styled(help.about, Style::default().fg(theme.muted))),
+        Line::from(""),
+    ];
+    lines.extend(rows.into_iter().map(|(keys, help)| {
+        Line::from(vec![
+            Span::styled(
+                format!("{keys:>14}  "),
+                Style::default().fg(theme.warn).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(help),
+        ])
+    }));
+    lines.push(Line::from(""));
+    let source = match &app.keymap_path {
+        Some(path) if path.exists() => format!("keymap: {}", path.display()),
+        Some(path) => format!("keymap: defaults ({} not found)", path.display()),
+        None => "keymap: defaults".to_string(),
+    };
+    lines.push(Line::from(Span::styled(
+        source,
+        Style::default().fg(theme.muted),
+    )));
+    lines.push(Line::from(Span::styled(
+        "Esc closes",
+        Style::default().fg(theme.muted),
+    )));
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(format!("Keys: {}", help.title))
+                    .borders(Borders::ALL),
+            )
+            .wrap(Wrap { trim: false }),
+        popup,
+    );
 }
 
 fn draw_top_banner(frame: &mut Frame, area: Rect, app: &App) {
-    let (text, style) = if app.cancel_requested {
+    let theme = app.theme;
+    let state = app.task_state();
+    let (text, style) = if state == TaskState::Cancelling {
         (
-            "CANCELLED",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            "CANCELLING...",
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
         )
-    } else if app.paused && app.pause_blink {
+    } else if state == TaskState::Paused && app.pause_blink {
         (
             "PAUSED",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.warn).add_modifier(Modifier::BOLD),
         )
     } else {
         ("", Style::default())
@@ -60,12 +159,18 @@ fn draw_top_banner(frame: &mut Frame, area: Rect, app: &App) {
 }
 
 fn draw_sidebar(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = app.theme;
     let items = [
         "Setup",
         "Backtest",
         "Monitor",
         "Reports",
+        "Runs",
         "Experiments",
+        "Ingest",
+        "Jobs",
+        "Schedule",
+        "Metrics",
         "Quit",
     ];
     let list_items: Vec<ListItem> = items
@@ -74,7 +179,7 @@ fn draw_sidebar(frame: &mut Frame, area: Rect, app: &App) {
         .map(|(idx, label)| {
             let mut style = Style::default();
             if app.active_view == ViewId::MainMenu && idx == app.menu_index {
-                style = style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
+                style = style.fg(theme.warn).add_modifier(Modifier::BOLD);
             }
             ListItem::new(Line::from(Span::styled(*label, style)))
         })
@@ -87,16 +192,30 @@ fn draw_sidebar(frame: &mut Frame, area: Rect, app: &App) {
 
 fn draw_main(frame: &mut Frame, area: Rect, app: &mut App) {
     match app.active_view {
-        ViewId::MainMenu => draw_main_menu(frame, area),
+        ViewId::MainMenu => draw_main_menu(frame, area, app),
         ViewId::Setup => draw_setup(frame, area, app),
+        ViewId::ConfigEditor => draw_config_editor(frame, area, app),
         ViewId::Backtest => draw_backtest(frame, area, app),
         ViewId::Monitor => draw_monitor(frame, area, app),
         ViewId::Reports => draw_reports(frame, area, app),
+        ViewId::Runs => draw_runs(frame, area, app),
+        ViewId::Chart => draw_chart(frame, area, app),
+        ViewId::Equity => draw_equity(frame, area, app),
+        ViewId::Trades => draw_trade_list(frame, area, app),
+        ViewId::ConfigDiff => draw_config_diff(frame, area, app),
         ViewId::Experiments => draw_experiments(frame, area, app),
+        ViewId::Leaderboard => draw_leaderboard(frame, area, app),
+        ViewId::SweepHistory => draw_sweep_history(frame, area, app),
+        ViewId::Ingest => draw_ingest(frame, area, app),
+        ViewId::Jobs => draw_jobs(frame, area, app),
+        ViewId::Schedule => draw_schedule(frame, area, app),
+        ViewId::DataQuality => draw_data_quality(frame, area, app),
+        ViewId::Metrics => draw_metrics(frame, area, app),
+        ViewId::Onboarding => draw_onboarding(frame, area, app),
     }
 }
 
-fn draw_main_menu(frame: &mut Frame, area: Rect) {
+fn draw_main_menu(frame: &mut Frame, area: Rect, app: &App) {
     let version = env!("CARGO_PKG_VERSION");
     let git_sha = option_env!("KAIROS_GIT_SHA").unwrap_or("unknown");
     let target = option_env!("KAIROS_TARGET").unwrap_or("unknown");
@@ -107,7 +226,22 @@ fn draw_main_menu(frame: &mut Frame, area: Rect) {
         )),
         Line::from(""),
         Line::from("Use ↑/↓ + Enter to navigate."),
-        Line::from("Esc returns to menu. Ctrl-C or q quits."),
+        Line::from(format!(
+            "Esc returns to menu. Ctrl-C or {} quits. {} shows the key bindings.",
+            app.keymap.label(Action::Quit),
+            app.keymap.label(Action::Help)
+        )),
+        Line::from(format!(
+            "{} cycles the color theme (now: {}, saved for next sessions).",
+            app.keymap.label(Action::Theme),
+            app.theme.name.as_str()
+        )),
+        Line::from(format!(
+            "Finished tasks/jobs of {}s or more pop a toast; b toggles the bell (now: {}), o the desktop notification (now: {}).",
+            app.settings.notify.min_secs,
+            if app.settings.notify.bell { "on" } else { "off" },
+            app.settings.notify.desktop.as_str()
+        )),
     ];
     frame.render_widget(
         Paragraph::new(lines).block(Block::default().title("Main").borders(Borders::ALL)),
@@ -116,6 +250,7 @@ fn draw_main_menu(frame: &mut Frame, area: Rect) {
 }
 
 fn draw_setup(frame: &mut Frame, area: Rect, app: &mut App) {
+    let theme = app.theme;
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints(
@@ -136,13 +271,13 @@ fn draw_setup(frame: &mut Frame, area: Rect, app: &mut App) {
     if let Some(err) = &app.last_error {
         header.push(Line::from(Span::styled(
             format!("error: {err}"),
-            Style::default().fg(Color::Red),
+            Style::default().fg(theme.error),
         )));
     }
     if let Some(info) = &app.info_message {
         header.push(Line::from(Span::styled(
             format!("info: {info}"),
-            Style::default().fg(Color::Green),
+            Style::default().fg(theme.ok),
         )));
     }
     if let Some(cfg) = &app.config {
@@ -254,9 +389,9 @@ fn draw_setup(frame: &mut Frame, area: Rect, app: &mut App) {
             };
             let mut style = Style::default();
             if field == app.quick_edit.selected {
-                style = style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
+                style = style.fg(theme.warn).add_modifier(Modifier::BOLD);
                 if app.setup_focus != SetupFocus::QuickEdit {
-                    style = style.fg(Color::DarkGray).add_modifier(Modifier::BOLD);
+                    style = style.fg(theme.muted).add_modifier(Modifier::BOLD);
                 }
             }
             lines.push(Line::from(Span::styled(
@@ -290,9 +425,9 @@ fn draw_setup(frame: &mut Frame, area: Rect, app: &mut App) {
 
             let mut style = Style::default();
             if idx == app.selected_config {
-                style = style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
+                style = style.fg(theme.warn).add_modifier(Modifier::BOLD);
                 if app.setup_focus != SetupFocus::List {
-                    style = style.fg(Color::DarkGray).add_modifier(Modifier::BOLD);
+                    style = style.fg(theme.muted).add_modifier(Modifier::BOLD);
                 }
             }
             items.push(ListItem::new(Line::from(Span::styled(
@@ -317,6 +452,8 @@ fn draw_setup(frame: &mut Frame, area: Rect, app: &mut App) {
         Line::from("  ↑/↓: select (list/edit)"),
         Line::from("  i/l/e: focus input/list/edit"),
         Line::from("  g/F5: refresh list"),
+        Line::from("  F2: edit every config field (form, save as)"),
+        Line::from("  j/J: queue a backtest/validate of the selected config (Jobs)"),
         Line::from("  Note: llm_* fields are runtime-only (not saved to config_snapshot)"),
         Line::from("  Note: llm_managed_agent=on spawns python agent (dev checkout only)"),
         Line::from("  Esc: back to menu"),
@@ -328,6 +465,7 @@ fn draw_setup(frame: &mut Frame, area: Rect, app: &mut App) {
 }
 
 fn draw_backtest(frame: &mut Frame, area: Rect, app: &mut App) {
+    let theme = app.theme;
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(3), Constraint::Min(3)].as_ref())
@@ -345,42 +483,57 @@ fn draw_backtest(frame: &mut Frame, area: Rect, app: &mut App) {
     let tabs = Tabs::new(tab_titles)
         .select(tab_index)
         .block(Block::default().title("Mode").borders(Borders::ALL))
-        .highlight_style(
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        );
+        .highlight_style(Style::default().fg(theme.warn).add_modifier(Modifier::BOLD));
     frame.render_widget(tabs, chunks[0]);
 
     let mut lines: Vec<Line> = Vec::new();
     if app.status.running {
         let kind = app.status.kind.map(task_kind_label).unwrap_or("task");
-        let state = if app.cancel_requested {
-            "CANCELLED"
-        } else if app.paused {
-            "PAUSED"
-        } else {
-            "running"
-        };
         lines.push(Line::from(Span::styled(
-            format!("{kind} {state} {}", app.spinner_char()),
-            Style::default().fg(Color::Yellow),
+            format!("{kind} {} {}", app.task_state().label(), app.spinner_char()),
+            Style::default().fg(theme.warn),
         )));
     } else {
-        lines.push(Line::from("idle"));
+        let state = app.task_state();
+        let color = match state {
+            TaskState::Succeeded => theme.ok,
+            TaskState::Failed | TaskState::Cancelled => theme.error,
+            _ => theme.text,
+        };
+        let text = match app.status.kind.filter(|_| state != TaskState::Idle) {
+            Some(kind) => format!("idle (last {}: {})", task_kind_label(kind), state.label()),
+            None => "idle".to_string(),
+        };
+        lines.push(Line::from(Span::styled(text, Style::default().fg(color))));
     }
 
     if app.backtest_tab == BacktestTab::Validate {
         lines.push(Line::from(format!(
-            "strict: {} (toggle: s)",
+            "strict: {} (toggle: s) | data-quality dashboard: d",
             if app.validate_strict { "on" } else { "off" }
         )));
     }
     if app.backtest_tab == BacktestTab::Paper {
         lines.push(Line::from(format!(
-            "paper realtime: {} (toggle: t)",
-            if app.paper_realtime { "on" } else { "off" }
+            "paper realtime: {} (toggle: t) | stream: {} (toggle: s)",
+            if app.paper_realtime { "on" } else { "off" },
+            if app.paper_simulated {
+                "simulator"
+            } else {
+                "exchange"
+            }
+        )));
+        lines.push(Line::from(format!(
+            "resume session: {} (toggle: u)",
+            if app.paper_resume { "on" } else { "off" }
         )));
+        lines.push(Line::from(
+            "replay speed: +/- doubles/halves it while a replay runs",
+        ));
+        lines.push(Line::from(
+            "strategy: h changes it at the next bar while a session runs",
+        ));
+        lines.extend(strategy_prompt_line(app));
     }
     lines.push(Line::from(format!(
         "require validate: {} (toggle: v)",
@@ -392,7 +545,7 @@ fn draw_backtest(frame: &mut Frame, area: Rect, app: &mut App) {
     )));
 
     if let (Some(kind), Some(status)) = (app.status.kind, app.stream_status.as_ref()) {
-        if kind == crate::tasks::TaskKind::PaperRealtime {
+        if matches!(kind, crate::tasks::TaskKind::PaperRealtime { .. }) {
             let conn = if status.connected {
                 "connected"
             } else {
@@ -406,19 +559,36 @@ fn draw_backtest(frame: &mut Frame, area: Rect, app: &mut App) {
                 "ws quality: out_of_order={} invalid={}",
                 status.out_of_order_events, status.invalid_events
             )));
+            if status.symbols.len() > 1 {
+                for symbol in &status.symbols {
+                    lines.push(Line::from(format!(
+                        "  {}: last_ts={:?} last_bar={:?} out_of_order={} invalid={}",
+                        symbol.symbol,
+                        symbol.last_event_timestamp,
+                        symbol.last_bar_timestamp,
+                        symbol.out_of_order_events,
+                        symbol.invalid_events
+                    )));
+                }
+            }
             if let Some(err) = &status.last_error {
                 lines.push(Line::from(Span::styled(
                     format!("ws last_error: {err}"),
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(theme.error),
                 )));
             }
         }
     }
 
     lines.push(Line::from(""));
-    lines.push(Line::from(
-        "keys: r run | p pause/resume | n step | x stop | v gate | t paper mode | Esc menu | ←/→ switch tab",
-    ));
+    let keys = &app.keymap;
+    lines.push(Line::from(format!(
+        "keys: {} run | {} pause/resume | {} step | {} cancel | v gate | t paper mode | Esc menu | ←/→ switch tab",
+        keys.label(Action::Run),
+        keys.label(Action::Pause),
+        keys.label(Action::Step),
+        keys.label(Action::Cancel)
+    )));
 
     if let Some(last) = &app.status.last_result {
         lines.push(Line::from(""));
@@ -426,14 +596,14 @@ fn draw_backtest(frame: &mut Frame, area: Rect, app: &mut App) {
             Ok(msg) => {
                 lines.push(Line::from(Span::styled(
                     "last result: OK",
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(theme.ok),
                 )));
                 lines.extend(msg.lines().take(12).map(Line::from));
             }
             Err(err) => {
                 lines.push(Line::from(Span::styled(
                     "last result: ERR",
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(theme.error),
                 )));
                 lines.extend(err.lines().take(12).map(Line::from));
             }
@@ -449,6 +619,7 @@ fn draw_backtest(frame: &mut Frame, area: Rect, app: &mut App) {
 }
 
 fn draw_experiments(frame: &mut Frame, area: Rect, app: &mut App) {
+    let theme = app.theme;
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(10), Constraint::Min(4)].as_ref())
@@ -458,7 +629,7 @@ fn draw_experiments(frame: &mut Frame, area: Rect, app: &mut App) {
     if app.status.running && app.status.kind == Some(crate::tasks::TaskKind::Sweep) {
         lines.push(Line::from(Span::styled(
             format!("sweep running {}", app.spinner_char()),
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme.warn),
         )));
     } else {
         lines.push(Line::from("sweep idle"));
@@ -497,12 +668,22 @@ fn draw_experiments(frame: &mut Frame, area: Rect, app: &mut App) {
     if let Some(progress) = &app.experiments_progress {
         lines.push(Line::from(""));
         lines.push(Line::from(format!(
-            "progress: {}/{} | ok={} skipped={} error={}",
+            "progress: {}/{} | ok={} skipped={} error={} pruned={}",
             progress.completed_runs,
             progress.total_runs,
             progress.ok_runs,
             progress.skipped_runs,
-            progress.error_runs
+            progress.error_runs,
+            progress.pruned_runs
+        )));
+        lines.push(Line::from(format!(
+            "workers: {} | elapsed: {:.1}s | eta: {}",
+            progress.workers,
+            progress.elapsed_ms as f64 / 1000.0,
+            progress
+                .eta_ms
+                .map(|ms| format!("{:.1}s", ms as f64 / 1000.0))
+                .unwrap_or_else(|| "-".to_string())
         )));
         if let Some(run_id) = &progress.last_run_id {
             lines.push(Line::from(format!("last run: {run_id}")));
@@ -510,7 +691,7 @@ fn draw_experiments(frame: &mut Frame, area: Rect, app: &mut App) {
         if let Some(err) = &progress.last_error {
             lines.push(Line::from(Span::styled(
                 format!("last error: {err}"),
-                Style::default().fg(Color::Red),
+                Style::default().fg(theme.error),
             )));
         }
     }
@@ -518,13 +699,13 @@ fn draw_experiments(frame: &mut Frame, area: Rect, app: &mut App) {
     if let Some(err) = &app.last_error {
         lines.push(Line::from(Span::styled(
             format!("error: {err}"),
-            Style::default().fg(Color::Red),
+            Style::default().fg(theme.error),
         )));
     }
     if let Some(info) = &app.info_message {
         lines.push(Line::from(Span::styled(
             format!("info: {info}"),
-            Style::default().fg(Color::Green),
+            Style::default().fg(theme.ok),
         )));
     }
 
@@ -543,23 +724,25 @@ fn draw_experiments(frame: &mut Frame, area: Rect, app: &mut App) {
     footer.push(Line::from(
         "keys: Tab/↑/↓ focus | type to edit | Backspace/Delete/←/→ cursor",
     ));
-    footer.push(Line::from(
-        "keys: r or Enter run | v toggle resume | x cancel | Esc menu",
-    ));
+    footer.push(Line::from(format!(
+        "keys: {} or Enter run | v toggle resume | {} cancel | F2 leaderboard | F3 past sweeps | Esc menu",
+        app.keymap.label(Action::Run),
+        app.keymap.label(Action::Cancel)
+    )));
     if let Some(last) = &app.status.last_result {
         footer.push(Line::from(""));
         match last {
             Ok(msg) => {
                 footer.push(Line::from(Span::styled(
                     "last result: OK",
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(theme.ok),
                 )));
                 footer.extend(msg.lines().take(8).map(Line::from));
             }
             Err(err) => {
                 footer.push(Line::from(Span::styled(
                     "last result: ERR",
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(theme.error),
                 )));
                 footer.extend(err.lines().take(8).map(Line::from));
             }
@@ -574,147 +757,1567 @@ fn draw_experiments(frame: &mut Frame, area: Rect, app: &mut App) {
     );
 }
 
-fn draw_monitor(frame: &mut Frame, area: Rect, app: &mut App) {
-    if app.price_series.is_empty() || app.equity_series.is_empty() {
-        let lines = vec![
-            Line::from("Monitor"),
-            Line::from(""),
-            Line::from("Waiting for progress stream..."),
-            Line::from("Run Backtest/Paper to see charts update in real time."),
-            Line::from(
-                "Keys: p pause/resume, n step (paused, backtest), x stop, ↑/↓ scroll trades, PgUp/PgDn scroll logs.",
-            ),
-        ];
-        frame.render_widget(
-            Paragraph::new(lines)
-                .block(Block::default().title("Monitor").borders(Borders::ALL))
-                .wrap(Wrap { trim: false }),
-            area,
-        );
+fn draw_config_editor(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = app.theme;
+    let Some(form) = app.config_form.as_ref() else {
         return;
-    }
-
+    };
     let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)].as_ref())
-        .split(area);
-    let charts = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
-        .split(chunks[0]);
-
-    let price_data: Vec<(f64, f64)> = app.price_series.iter().copied().collect();
-    let equity_data: Vec<(f64, f64)> = app.equity_series.iter().copied().collect();
-
-    let (x_min, x_max) = x_bounds(&price_data);
-
-    let (p_min, p_max) = y_bounds(&price_data);
-    let (e_min, e_max) = y_bounds(&equity_data);
+        .constraints(
+            [
+                Constraint::Length(6),
+                Constraint::Min(6),
+                Constraint::Length(7),
+            ]
+            .as_ref(),
+        )
+        .split(area);
 
-    let price = Chart::new(vec![Dataset::default()
-        .name("price")
-        .graph_type(GraphType::Line)
-        .style(Style::default().fg(Color::Cyan))
-        .data(&price_data)])
-    .block(
-        Block::default()
-            .title("Price (close)")
-            .borders(Borders::ALL),
-    )
-    .x_axis(
-        Axis::default()
-            .bounds([x_min, x_max])
-            .labels(axis_labels(x_min, x_max)),
-    )
-    .y_axis(
-        Axis::default()
-            .bounds([p_min, p_max])
-            .labels(axis_labels(p_min, p_max)),
+    let mut header = vec![Line::from(match form.focus {
+        FormFocus::Fields => {
+            "keys: ↑/↓ select | PgUp/PgDn section | Enter edit | a apply | w save as | Esc back"
+        }
+        FormFocus::Editing => "editing: Enter set (empty removes optional) | Esc cancel",
+        FormFocus::SavePath => "save as: type a path + Enter to write and load it | Esc cancel",
+    })];
+    let save_style = if form.focus == FormFocus::SavePath {
+        Style::default().fg(theme.warn)
+    } else {
+        Style::default()
+    };
+    header.push(Line::from(Span::styled(
+        format!("save path: {}", form.save_path.value),
+        save_style,
+    )));
+    let (state, state_style) = if form.is_valid() {
+        (
+            if form.modified {
+                "valid (unapplied changes)"
+            } else {
+                "valid"
+            },
+            Style::default().fg(theme.ok),
+        )
+    } else {
+        ("has issues", Style::default().fg(theme.error))
+    };
+    header.push(Line::from(Span::styled(
+        format!("config: {state}"),
+        state_style,
+    )));
+    if let Some(err) = &app.last_error {
+        header.push(Line::from(Span::styled(
+            format!("error: {err}"),
+            Style::default().fg(theme.error),
+        )));
+    } else if let Some(info) = &app.info_message {
+        header.push(Line::from(Span::styled(
+            format!("info: {info}"),
+            Style::default().fg(theme.ok),
+        )));
+    }
+    frame.render_widget(
+        Paragraph::new(header)
+            .block(
+                Block::default()
+                    .title("Config editor")
+                    .borders(Borders::ALL),
+            )
+            .wrap(Wrap { trim: false }),
+        chunks[0],
     );
 
-    let equity = Chart::new(vec![Dataset::default()
-        .name("equity")
-        .graph_type(GraphType::Line)
-        .style(Style::default().fg(Color::Green))
-        .data(&equity_data)])
-    .block(Block::default().title("Equity").borders(Borders::ALL))
-    .x_axis(
-        Axis::default()
-            .bounds([x_min, x_max])
-            .labels(axis_labels(x_min, x_max)),
-    )
-    .y_axis(
-        Axis::default()
-            .bounds([e_min, e_max])
-            .labels(axis_labels(e_min, e_max)),
+    let mut lines: Vec<Line> = Vec::new();
+    let mut selected_line = 0;
+    let mut group = "";
+    for (idx, field) in form.fields.iter().enumerate() {
+        if field.group != group {
+            group = &field.group;
+            lines.push(Line::from(Span::styled(
+                format!("[{group}]"),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+        }
+        let selected = idx == form.selected;
+        if selected {
+            selected_line = lines.len();
+        }
+        let key = field
+            .path
+            .strip_prefix(&format!("{group}."))
+            .unwrap_or(&field.path);
+        let value = if selected && form.focus == FormFocus::Editing {
+            format!("{}_", form.input.value)
+        } else {
+            form.display_value(&field.path)
+        };
+        let style = if selected {
+            Style::default().fg(theme.warn).add_modifier(Modifier::BOLD)
+        } else if form.errors.contains_key(&field.path) {
+            Style::default().fg(theme.error)
+        } else {
+            Style::default()
+        };
+        let mut spans = vec![Span::styled(
+            format!(
+                "{}{key}{} = {value}",
+                if selected { "> " } else { "  " },
+                if field.required { "*" } else { "" }
+            ),
+            style,
+        )];
+        if let Some(err) = form.errors.get(&field.path) {
+            spans.push(Span::styled(
+                format!("  ! {err}"),
+                Style::default().fg(theme.error),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    let height = chunks[1].height.saturating_sub(2) as usize;
+    let scroll = (selected_line + 1).saturating_sub(height);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title("Fields (* required)")
+                    .borders(Borders::ALL),
+            )
+            .scroll((scroll as u16, 0)),
+        chunks[1],
     );
 
-    frame.render_widget(price, charts[0]);
-    frame.render_widget(equity, charts[1]);
+    let mut details: Vec<Line> = Vec::new();
+    if let Some(field) = form.selected_field() {
+        details.push(Line::from(format!("{} ({})", field.path, field.kind)));
+        if let Some(hint) = &field.hint {
+            details.push(Line::from(format!("hint: {hint}")));
+        }
+    }
+    for err in &form.general_errors {
+        details.push(Line::from(Span::styled(
+            format!("! {err}"),
+            Style::default().fg(theme.error),
+        )));
+    }
+    frame.render_widget(
+        Paragraph::new(details)
+            .block(Block::default().title("Details").borders(Borders::ALL))
+            .wrap(Wrap { trim: false }),
+        chunks[2],
+    );
+}
 
-    let max_lines = chunks[1].height.saturating_sub(2) as usize;
-    let mut lines: Vec<Line> = Vec::new();
-    if app.trades.is_empty() {
-        lines.push(Line::from("no trades yet"));
-    } else {
-        for trade in app
-            .trades
-            .iter()
-            .rev()
-            .skip(app.trade_scroll)
-            .take(max_lines)
-        {
-            let side_style = match trade.side {
-                Side::Buy => Style::default().fg(Color::Green),
-                Side::Sell => Style::default().fg(Color::Red),
-            };
-            lines.push(Line::from(vec![
-                Span::raw(format!("[#{}] ", trade.bar_index)),
+fn draw_metrics(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = app.theme;
+    let panel = &app.metrics;
+    let mut lines: Vec<Line> = vec![Line::from(format!(
+        "process metrics (what the exporter serves), sampled every second; sparklines: last 60 samples | ↑/↓ PgUp/PgDn scroll | x start/stop exporter | a address | {} menu",
+        app.keymap.label(Action::Back)
+    ))];
+    lines.push(
+        match (&app.metrics_addr_input, crate::metrics_exporter::address()) {
+            (Some(input), _) => Line::from(vec![
+                Span::raw("exporter address: "),
+                Span::styled(format!("{}_", input.value), Style::default().fg(theme.warn)),
                 Span::styled(
-                    format!("{:?}", trade.side),
-                    side_style.add_modifier(Modifier::BOLD),
+                    "  (Enter start/move, Esc cancel)",
+                    Style::default().fg(theme.muted),
+                ),
+            ]),
+            (None, Some(addr)) => Line::from(vec![
+                Span::raw("exporter: "),
+                Span::styled(
+                    format!("serving http://{addr}/metrics"),
+                    Style::default().fg(theme.ok),
                 ),
+            ]),
+            (None, None) => Line::from(vec![
+                Span::raw("exporter: "),
+                Span::styled(
+                    format!("stopped (x starts it on {})", app.metrics_addr),
+                    Style::default().fg(theme.muted),
+                ),
+            ]),
+        },
+    );
+    if !panel.available {
+        lines.push(Line::from(Span::styled(
+            "no metrics recorder in this build (enable the `prometheus` feature)",
+            Style::default().fg(theme.muted),
+        )));
+    } else if panel.series.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "nothing recorded yet (run a validate, backtest or paper task)",
+            Style::default().fg(theme.muted),
+        )));
+    }
+    let header = |text: String| {
+        Line::from(Span::styled(
+            text,
+            Style::default().add_modifier(Modifier::BOLD),
+        ))
+    };
+    let spark = |name: &str| Span::styled(panel.sparkline(name), Style::default().fg(theme.accent));
+    let of_kind = |kind: MetricKind| panel.series.iter().filter(move |s| s.kind == kind);
+
+    for (kind, title) in [
+        (MetricKind::Counter, "COUNTER"),
+        (MetricKind::Gauge, "GAUGE"),
+    ] {
+        if of_kind(kind).next().is_none() {
+            continue;
+        }
+        lines.push(Line::from(""));
+        let trend = if kind == MetricKind::Counter {
+            "INCREASE/S"
+        } else {
+            "VALUE"
+        };
+        lines.push(header(format!("{title:<64} {:>14}  {trend}", "VALUE")));
+        for series in of_kind(kind) {
+            lines.push(Line::from(vec![
                 Span::raw(format!(
-                    " qty={:.4} @ {:.2} ts={}",
-                    trade.quantity, trade.price, trade.timestamp
+                    "{:<64} {:>14}  ",
+                    series.name,
+                    format_metric(series.value)
                 )),
+                spark(&series.name),
             ]));
         }
     }
-
-    let mut title = "Trades".to_string();
-    if app.cancel_requested {
-        title.push_str(" (CANCELLED)");
-    } else if app.paused {
-        title.push_str(" (PAUSED)");
-    }
-    if let (Some(kind), Some(status)) = (app.status.kind, app.stream_status.as_ref()) {
-        if kind == crate::tasks::TaskKind::PaperRealtime {
-            title.push_str(if status.connected {
-                " (WS: connected)"
-            } else {
-                " (WS: reconnecting)"
-            });
+    if of_kind(MetricKind::Summary).next().is_some() {
+        lines.push(Line::from(""));
+        lines.push(header(format!(
+            "{:<64} {:>8} {:>10} {:>10} {:>10} {:>10} {:>10}  COUNT/S",
+            "HISTOGRAM", "COUNT", "MEAN", "P50", "P95", "P99", "MAX"
+        )));
+        let value = |v: Option<f64>| v.map_or("-".to_string(), format_metric);
+        for series in of_kind(MetricKind::Summary) {
+            lines.push(Line::from(vec![
+                Span::raw(format!(
+                    "{:<64} {:>8} {:>10} {:>10} {:>10} {:>10} {:>10}  ",
+                    series.name,
+                    format_metric(series.value),
+                    value(series.mean()),
+                    value(series.quantile(0.5)),
+                    value(series.quantile(0.95)),
+                    value(series.quantile(0.99)),
+                    value(series.quantile(1.0)),
+                )),
+                spark(&series.name),
+            ]));
         }
     }
+
+    let max_scroll = lines.len().saturating_sub(1);
     frame.render_widget(
         Paragraph::new(lines)
-            .block(Block::default().title(title).borders(Borders::ALL))
-            .wrap(Wrap { trim: false }),
-        chunks[1],
+            .block(Block::default().title("Metrics").borders(Borders::ALL))
+            .scroll((panel.scroll.min(max_scroll) as u16, 0)),
+        area,
     );
 }
 
-fn x_bounds(points: &[(f64, f64)]) -> (f64, f64) {
-    let x_min = points.first().map(|p| p.0).unwrap_or(0.0);
-    let mut x_max = points.last().map(|p| p.0).unwrap_or(x_min + 1.0);
-    if x_max <= x_min {
-        x_max = x_min + 1.0;
+fn format_metric(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.3}")
     }
-    (x_min, x_max)
 }
 
-fn y_bounds(points: &[(f64, f64)]) -> (f64, f64) {
+fn draw_data_quality(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = app.theme;
+    let Some(report) = app.data_quality.as_ref() else {
+        frame.render_widget(
+            Paragraph::new("no data-quality report yet (run validate from Backtest)")
+                .block(Block::default().title("Data quality").borders(Borders::ALL)),
+            area,
+        );
+        return;
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(9), Constraint::Min(4)].as_ref())
+        .split(area);
+    let ts = |ts: Option<i64>| ts.map_or("-".to_string(), crate::ingest_form::format_ts);
+
+    let failed = report.failed_checks();
+    let (verdict, verdict_color) = if failed == 0 {
+        ("PASS".to_string(), theme.ok)
+    } else {
+        (
+            format!("FAIL ({failed} of {} limits exceeded)", report.checks.len()),
+            theme.error,
+        )
+    };
+    let mut lines: Vec<Line> = vec![
+        Line::from(vec![
+            Span::raw("limits: "),
+            Span::styled(
+                verdict,
+                Style::default()
+                    .fg(verdict_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(format!(
+            "ohlcv: {} bars {} .. {} | gaps={} missing_bars={} max_gap={} | duplicates={} out_of_order={} invalid_close={}",
+            report.rows,
+            ts(report.first_timestamp),
+            ts(report.last_timestamp),
+            report.gaps,
+            report.missing_bars,
+            report
+                .max_gap_seconds
+                .map_or("-".to_string(), |secs| format!("{secs}s")),
+            report.duplicates,
+            report.out_of_order,
+            report.invalid_close
+        )),
+        Line::from(format!(
+            "sentiment: duplicates={} missing={} invalid={} dropped={}",
+            report.sentiment_duplicates,
+            report.sentiment_missing,
+            report.sentiment_invalid,
+            report.sentiment_dropped
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "gaps over the data range:",
+            Style::default().fg(theme.muted),
+        )),
+    ];
+    let width = chunks[0].width.saturating_sub(2) as usize;
+    let timeline: Vec<Span> = report
+        .timeline(width)
+        .into_iter()
+        .map(|column| match column {
+            Some(idx) if idx == report.selected_gap => {
+                Span::styled("█", Style::default().fg(theme.warn))
+            }
+            Some(_) => Span::styled("█", Style::default().fg(theme.error)),
+            None => Span::styled("─", Style::default().fg(theme.muted)),
+        })
+        .collect();
+    lines.push(Line::from(timeline));
+    lines.push(Line::from(format!(
+        "keys: ↑/↓ select gap | b backfill the selected gap | a backfill all gaps | {} back",
+        app.keymap.label(Action::Back)
+    )));
+    if let Some(err) = &app.last_error {
+        lines.push(Line::from(Span::styled(
+            format!("error: {err}"),
+            Style::default().fg(theme.error),
+        )));
+    }
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().title("Data quality").borders(Borders::ALL)),
+        chunks[0],
+    );
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+        .split(chunks[1]);
+    let checks: Vec<Line> = report
+        .checks
+        .iter()
+        .map(|check| {
+            let (badge, color) = match check.limit {
+                None => (" -- ", theme.muted),
+                Some(_) if check.passed() => ("PASS", theme.ok),
+                Some(_) => ("FAIL", theme.error),
+            };
+            let limit = check
+                .limit
+                .map_or("no limit".to_string(), |limit| format!("max {limit}"));
+            Line::from(vec![
+                Span::styled(
+                    format!(" {badge} "),
+                    Style::default()
+                        .fg(color)
+                        .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+                ),
+                Span::raw(format!(" {:<24} {:>8}  ({limit})", check.name, check.value)),
+            ])
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(checks).block(Block::default().title("Limits").borders(Borders::ALL)),
+        columns[0],
+    );
+
+    let height = columns[1].height.saturating_sub(2) as usize;
+    let skip = (report.selected_gap + 1).saturating_sub(height);
+    let gaps: Vec<Line> = if report.gap_ranges.is_empty() {
+        vec![Line::from("no gaps")]
+    } else {
+        report
+            .gap_ranges
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(height)
+            .map(|(idx, (start, end))| {
+                let text = format!(
+                    "{}{:>4} {} .. {} ({}s)",
+                    if idx == report.selected_gap {
+                        "> "
+                    } else {
+                        "  "
+                    },
+                    idx + 1,
+                    crate::ingest_form::format_ts(*start),
+                    crate::ingest_form::format_ts(*end),
+                    end - start
+                );
+                if idx == report.selected_gap {
+                    Line::from(Span::styled(
+                        text,
+                        Style::default().fg(theme.warn).add_modifier(Modifier::BOLD),
+                    ))
+                } else {
+                    Line::from(text)
+                }
+            })
+            .collect()
+    };
+    let title = if report.gap_ranges.len() < report.gaps {
+        format!(
+            "Gaps (first {} of {})",
+            report.gap_ranges.len(),
+            report.gaps
+        )
+    } else {
+        format!("Gaps ({})", report.gaps)
+    };
+    frame.render_widget(
+        Paragraph::new(gaps).block(Block::default().title(title).borders(Borders::ALL)),
+        columns[1],
+    );
+}
+
+fn draw_schedule(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = app.theme;
+    let scheduler = &app.scheduler;
+    let now = chrono::Utc::now();
+    let mut lines: Vec<Line> = vec![
+        Line::from(format!(
+            "daily runs queued as jobs while the TUI is open (times in UTC, now {}) | file: {}",
+            now.format("%H:%M"),
+            scheduler
+                .path
+                .as_ref()
+                .map_or("not saved".to_string(), |p| p.display().to_string())
+        )),
+        Line::from(
+            "keys: a add the loaded config | ←/→ time -/+15min | v validate/backtest | i ingest first | space on/off | r run now | d delete | g reload | Esc menu",
+        ),
+    ];
+    if let Some(err) = &app.last_error {
+        lines.push(Line::from(Span::styled(
+            format!("error: {err}"),
+            Style::default().fg(theme.error),
+        )));
+    } else if let Some(info) = &app.info_message {
+        lines.push(Line::from(Span::styled(
+            format!("info: {info}"),
+            Style::default().fg(theme.ok),
+        )));
+    }
+    lines.push(Line::from(""));
+    if scheduler.entries.is_empty() {
+        lines.push(Line::from(
+            "nothing scheduled (load a config in Setup, then press a)",
+        ));
+    } else {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  {:<3} {:<5} {:<9} {:<12} {:<17} {:<40} {}",
+                "ON", "AT", "KIND", "INGEST", "NEXT", "CONFIG", "LAST RESULT"
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        for (idx, entry) in scheduler.entries.iter().enumerate() {
+            let selected = idx == scheduler.selected;
+            let next = match (entry.job_id, entry.enabled) {
+                (Some(id), _) => format!("running (job #{id})"),
+                (None, false) => "-".to_string(),
+                (None, true) => entry.next_run(now).map_or("-".to_string(), |at| {
+                    at.format("%Y-%m-%d %H:%M").to_string()
+                }),
+            };
+            let color = if entry.job_id.is_some() {
+                theme.warn
+            } else if !entry.enabled {
+                theme.muted
+            } else {
+                theme.text
+            };
+            let mut style = Style::default().fg(color);
+            if selected {
+                style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+            }
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{}{:<3} {:<5} {:<9} {:<12} {:<17} {:<40} {}",
+                    if selected { "> " } else { "  " },
+                    if entry.enabled { "on" } else { "off" },
+                    entry.at,
+                    entry.kind.as_str(),
+                    entry.ingest_symbol.as_deref().unwrap_or("-"),
+                    next,
+                    entry.config.display(),
+                    entry.last_result.as_deref().unwrap_or("-")
+                ),
+                style,
+            )));
+        }
+    }
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().title("Schedule").borders(Borders::ALL))
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+fn draw_jobs(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = app.theme;
+    let queue = &app.job_queue;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Min(4)].as_ref())
+        .split(area);
+
+    let mut lines: Vec<Line> = vec![
+        Line::from(format!(
+            "concurrency: {} | queued={} running={} done={} failed={} cancelled={}",
+            queue.concurrency,
+            queue.count(JobState::Queued),
+            queue.count(JobState::Running),
+            queue.count(JobState::Done),
+            queue.count(JobState::Failed),
+            queue.count(JobState::Cancelled)
+        )),
+        Line::from(format!(
+            "keys: b/v queue backtest/validate of the loaded config | +/- concurrency | {} cancel | r retry | c clear finished | Esc menu",
+            app.keymap.label(Action::Cancel)
+        )),
+    ];
+    if let Some(err) = &app.last_error {
+        lines.push(Line::from(Span::styled(
+            format!("error: {err}"),
+            Style::default().fg(theme.error),
+        )));
+    } else if let Some(info) = &app.info_message {
+        lines.push(Line::from(Span::styled(
+            format!("info: {info}"),
+            Style::default().fg(theme.ok),
+        )));
+    }
+    lines.push(Line::from(""));
+    if queue.jobs.is_empty() {
+        lines.push(Line::from(
+            "no jobs (queue from here or from the Setup config list with j/J)",
+        ));
+    } else {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  {:>4} {:<9} {:<10} {:>9}  {}",
+                "#", "KIND", "STATE", "ELAPSED", "CONFIG"
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        for (idx, job) in queue.jobs.iter().enumerate() {
+            let selected = idx == queue.selected;
+            let color = match job.state {
+                JobState::Queued => theme.text,
+                JobState::Running => theme.warn,
+                JobState::Done => theme.ok,
+                JobState::Failed => theme.error,
+                JobState::Cancelled => theme.muted,
+            };
+            let mut style = Style::default().fg(color);
+            if selected {
+                style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+            }
+            let elapsed_ms = job
+                .elapsed_ms
+                .or_else(|| job.started_at.map(|at| at.elapsed().as_millis() as u64));
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{}{:>4} {:<9} {:<10} {:>9}  {}",
+                    if selected { "> " } else { "  " },
+                    job.id,
+                    job.kind.as_str(),
+                    job.state.as_str(),
+                    elapsed_ms
+                        .map(|ms| format!("{:.1}s", ms as f64 / 1000.0))
+                        .unwrap_or_else(|| "-".to_string()),
+                    job.config_path.display()
+                ),
+                style,
+            )));
+        }
+    }
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().title("Jobs").borders(Borders::ALL))
+            .wrap(Wrap { trim: false }),
+        chunks[0],
+    );
+
+    let (title, log) = match queue.selected_job() {
+        Some(job) => {
+            let height = chunks[1].height.saturating_sub(2) as usize;
+            let log: Vec<Line> = job
+                .log
+                .iter()
+                .skip(job.log.len().saturating_sub(height))
+                .map(|line| Line::from(line.clone()))
+                .collect();
+            (format!("Job #{} log", job.id), log)
+        }
+        None => ("Job log".to_string(), Vec::new()),
+    };
+    frame.render_widget(
+        Paragraph::new(log)
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .wrap(Wrap { trim: false }),
+        chunks[1],
+    );
+}
+
+fn draw_onboarding(frame: &mut Frame, area: Rect, app: &App) {
+    use crate::onboarding::{StepState, FIELD_LABELS, STEPS};
+
+    let theme = app.theme;
+    let form = &app.onboarding;
+    let mut lines: Vec<Line> = vec![
+        Line::from("No config and no runs yet: set up the database and a starter config."),
+        Line::from(format!(
+            "keys: ↑/↓ select | Enter edit | c/m/s/w run a step | {} cancel the ingest | Esc skip to the menu",
+            app.keymap.label(Action::Cancel)
+        )),
+        Line::from(""),
+    ];
+    for (idx, label) in FIELD_LABELS.iter().enumerate() {
+        let selected = idx == form.selected;
+        let value = &form.inputs[idx].value;
+        let value = if selected && form.editing {
+            format!("{value}_")
+        } else if value.is_empty() {
+            "<env KAIROS_DB_URL>".to_string()
+        } else {
+            value.clone()
+        };
+        let style = if selected {
+            Style::default().fg(theme.warn).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(Span::styled(
+            format!("{}{label}: {value}", if selected { "> " } else { "  " }),
+            style,
+        )));
+    }
+    lines.push(Line::from(""));
+    let next = form.next_step();
+    for step in STEPS {
+        let (mark, detail, color) = match form.state(step) {
+            StepState::Pending => ("[ ]", String::new(), theme.text),
+            StepState::Running => ("[~]", app.spinner_char().to_string(), theme.warn),
+            StepState::Done(msg) => ("[x]", msg.clone(), theme.ok),
+            StepState::Failed(err) => ("[!]", err.clone(), theme.error),
+        };
+        let mut style = Style::default().fg(color);
+        if next == Some(step) {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        let detail = if detail.is_empty() {
+            String::new()
+        } else {
+            format!(" - {detail}")
+        };
+        lines.push(Line::from(Span::styled(
+            format!("{mark} {} {}{detail}", step.key(), step.label()),
+            style,
+        )));
+    }
+    if form.is_running(crate::onboarding::Step::SampleIngest) {
+        let progress = &app.ingest_form.progress;
+        lines.push(Line::from(format!(
+            "    {:.1}% | windows: {} | total upserts: {} | retries: {}",
+            progress.fraction() * 100.0,
+            progress.windows,
+            progress.total_rows,
+            progress.retries
+        )));
+    }
+    lines.push(Line::from(""));
+    if let Some(err) = &app.last_error {
+        lines.push(Line::from(Span::styled(
+            format!("error: {err}"),
+            Style::default().fg(theme.error),
+        )));
+    } else if let Some(info) = &app.info_message {
+        lines.push(Line::from(Span::styled(
+            format!("info: {info}"),
+            Style::default().fg(theme.ok),
+        )));
+    }
+    lines.push(Line::from(Span::styled(
+        "postgres not running? `docker compose up -d db` from the repo root (see .env.example)",
+        Style::default().fg(theme.muted),
+    )));
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().title("Welcome").borders(Borders::ALL))
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+fn draw_ingest(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = app.theme;
+    let form = &app.ingest_form;
+    let progress = &form.progress;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(14),
+                Constraint::Length(3),
+                Constraint::Min(4),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+
+    let running = app.status.running && app.status.kind == Some(crate::tasks::TaskKind::Ingest);
+    let mut lines: Vec<Line> = Vec::new();
+    if running {
+        lines.push(Line::from(Span::styled(
+            format!("ingest running {}", app.spinner_char()),
+            Style::default().fg(theme.warn),
+        )));
+    } else {
+        lines.push(Line::from(
+            "ingest idle (KuCoin -> db.url of the loaded config)",
+        ));
+    }
+    lines.push(Line::from(""));
+    for (idx, label) in crate::ingest_form::FIELD_LABELS.iter().enumerate() {
+        let selected = idx == form.selected;
+        let value = &form.inputs[idx].value;
+        let value = if selected && form.editing {
+            format!("{value}_")
+        } else if value.is_empty() {
+            "<from config>".to_string()
+        } else {
+            value.clone()
+        };
+        let style = if selected {
+            Style::default().fg(theme.warn).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(Span::styled(
+            format!("{}{label}: {value}", if selected { "> " } else { "  " }),
+            style,
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(format!(
+        "windows: {} | total upserts: {} | retries: {}",
+        progress.windows, progress.total_rows, progress.retries
+    )));
+    if let Some((first_ts, last_ts, rows)) = progress.last_window {
+        lines.push(Line::from(format!(
+            "last window: {} .. {} ({rows} candles)",
+            crate::ingest_form::format_ts(first_ts),
+            crate::ingest_form::format_ts(last_ts)
+        )));
+    }
+    if let Some(err) = &app.last_error {
+        lines.push(Line::from(Span::styled(
+            format!("error: {err}"),
+            Style::default().fg(theme.error),
+        )));
+    } else if let Some(info) = &app.info_message {
+        lines.push(Line::from(Span::styled(
+            format!("info: {info}"),
+            Style::default().fg(theme.ok),
+        )));
+    } else if let (false, Some(last)) = (running, &app.status.last_result) {
+        if app.status.kind == Some(crate::tasks::TaskKind::Ingest) {
+            let (text, color) = match last {
+                Ok(msg) => (msg.clone(), theme.ok),
+                Err(err) => (format!("failed: {err}"), theme.error),
+            };
+            lines.push(Line::from(Span::styled(text, Style::default().fg(color))));
+        }
+    }
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().title("Ingest").borders(Borders::ALL))
+            .wrap(Wrap { trim: false }),
+        chunks[0],
+    );
+
+    let fraction = progress.fraction();
+    frame.render_widget(
+        Gauge::default()
+            .block(
+                Block::default()
+                    .title(format!(
+                        "keys: ↑/↓ select | Enter edit | c fill from config | {} run | {} cancel | Esc menu",
+                        app.keymap.label(Action::Run),
+                        app.keymap.label(Action::Cancel)
+                    ))
+                    .borders(Borders::ALL),
+            )
+            .gauge_style(Style::default().fg(theme.accent))
+            .ratio(fraction)
+            .label(format!("{:.1}%", fraction * 100.0)),
+        chunks[1],
+    );
+
+    let height = chunks[2].height.saturating_sub(2) as usize;
+    let log: Vec<Line> = progress
+        .log
+        .iter()
+        .skip(progress.log.len().saturating_sub(height))
+        .map(|line| {
+            if line.starts_with("retry") {
+                Line::from(Span::styled(line.clone(), Style::default().fg(theme.warn)))
+            } else {
+                Line::from(line.clone())
+            }
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(log).block(Block::default().title("Windows").borders(Borders::ALL)),
+        chunks[2],
+    );
+}
+
+fn draw_sweep_history(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = app.theme;
+    let history = &app.sweep_history;
+    let mut header: Vec<Line> = Vec::new();
+    let Some(sweep_id) = history.opened.as_ref() else {
+        header.push(Line::from(
+            "finished sweeps under <out_dir>/sweeps (newest first)",
+        ));
+        header.push(Line::from(
+            "keys: ↑/↓ select | Enter open | g refresh | Esc back",
+        ));
+        if let Some(err) = &app.last_error {
+            header.push(Line::from(Span::styled(
+                format!("error: {err}"),
+                Style::default().fg(theme.error),
+            )));
+        }
+        header.push(Line::from(""));
+        if history.sweeps.is_empty() {
+            header.push(Line::from(
+                "no finished sweeps yet (run one in Experiments)",
+            ));
+        }
+        for (idx, sweep) in history.sweeps.iter().enumerate() {
+            let selected = idx == history.selected_sweep;
+            let modified = chrono::DateTime::<chrono::Utc>::from(sweep.modified)
+                .format("%Y-%m-%d %H:%M")
+                .to_string();
+            let style = if selected {
+                Style::default().fg(theme.warn).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            header.push(Line::from(Span::styled(
+                format!(
+                    "{}{modified}  {:<32} {}",
+                    if selected { "> " } else { "  " },
+                    sweep.id,
+                    sweep.dir.display()
+                ),
+                style,
+            )));
+        }
+        frame.render_widget(
+            Paragraph::new(header)
+                .block(Block::default().title("Past sweeps").borders(Borders::ALL))
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+        return;
+    };
+
+    let param = history.current_param().unwrap_or_else(|| "-".to_string());
+    let metric_label = crate::run_browser::sort_label(history.sort);
+    header.push(Line::from(format!(
+        "{sweep_id}: {} of {} trials | x: {param} | y/sort: {metric_label}",
+        history.visible.len(),
+        history.trials.len()
+    )));
+    let filter = if history.editing_filter {
+        format!("{}_", history.filter_input.value)
+    } else if history.filter_input.value.trim().is_empty() {
+        "<none>".to_string()
+    } else {
+        history.filter_input.value.clone()
+    };
+    header.push(Line::from(format!(
+        "filter (e.g. sma_fast>=10, size_mode=qty): {filter}"
+    )));
+    header.push(Line::from(
+        "keys: ↑/↓ select | ←/→ x param | s metric | / filter | Enter open run | Esc sweeps",
+    ));
+    if let Some(err) = &app.last_error {
+        header.push(Line::from(Span::styled(
+            format!("error: {err}"),
+            Style::default().fg(theme.error),
+        )));
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(5), Constraint::Min(6)].as_ref())
+        .split(area);
+    frame.render_widget(Paragraph::new(header), chunks[0]);
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)].as_ref())
+        .split(chunks[1]);
+
+    let height = body[0].height.saturating_sub(3) as usize;
+    let skip = history.selected.saturating_sub(height.saturating_sub(1));
+    let value_of = |value: Option<f64>, scale: f64, precision: usize| {
+        value
+            .map(|v| format!("{:.precision$}", v * scale))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    let mut rows: Vec<Line> = vec![Line::from(Span::styled(
+        format!(
+            "  {:>8} {:>12} {:>8}  {:<8} {}",
+            "SHARPE", "NET_PROFIT", "MAX_DD", "STATUS", "PARAMS"
+        ),
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+    for (idx, trial) in history.visible_trials().enumerate().skip(skip).take(height) {
+        let selected = idx == history.selected;
+        let style = if selected {
+            Style::default().fg(theme.warn)
+        } else if trial.status == "error" {
+            Style::default().fg(theme.error)
+        } else {
+            Style::default()
+        };
+        let params = trial
+            .params
+            .iter()
+            .map(|(path, value)| {
+                let name = path
+                    .rsplit_once('.')
+                    .map_or(path.as_str(), |(_, last)| last);
+                format!("{name}={value}")
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        rows.push(Line::from(Span::styled(
+            format!(
+                "{}{:>8} {:>12} {:>7}%  {:<8} {}",
+                if selected { "> " } else { "  " },
+                value_of(trial.sharpe, 1.0, 3),
+                value_of(trial.net_profit, 1.0, 2),
+                value_of(trial.max_drawdown, 100.0, 2),
+                trial.status,
+                params
+            ),
+            style,
+        )));
+    }
+    frame.render_widget(
+        Paragraph::new(rows).block(Block::default().title("Trials").borders(Borders::ALL)),
+        body[0],
+    );
+
+    let Some(scatter) = history.scatter() else {
+        frame.render_widget(
+            Paragraph::new("no trial with both the parameter and the metric")
+                .block(Block::default().title("Scatter").borders(Borders::ALL)),
+            body[1],
+        );
+        return;
+    };
+    let x_range = if scatter.categories.is_empty() {
+        format!(
+            "{:.2} .. {:.2}",
+            scatter.x_bounds[0] + (scatter.x_bounds[1] - scatter.x_bounds[0]) / 22.0,
+            scatter.x_bounds[1] - (scatter.x_bounds[1] - scatter.x_bounds[0]) / 22.0
+        )
+    } else {
+        scatter.categories.join(" | ")
+    };
+    let title = format!(
+        "{metric_label} vs {param} (x: {x_range}; y: {:.2} .. {:.2})",
+        scatter.y_bounds[0], scatter.y_bounds[1]
+    );
+    let canvas = Canvas::default()
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .marker(Marker::Braille)
+        .x_bounds(scatter.x_bounds)
+        .y_bounds(scatter.y_bounds)
+        .paint(|ctx| {
+            ctx.draw(&Points {
+                coords: &scatter.points,
+                color: theme.accent,
+            });
+            if let Some(point) = scatter.selected {
+                ctx.layer();
+                ctx.draw(&Points {
+                    coords: &[point],
+                    color: theme.warn,
+                });
+            }
+        });
+    frame.render_widget(canvas, body[1]);
+}
+
+fn draw_leaderboard(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = app.theme;
+    let board = &app.sweep_leaderboard;
+    let mut lines: Vec<Line> = Vec::new();
+    let state = if app.status.running && app.status.kind == Some(crate::tasks::TaskKind::Sweep) {
+        format!("sweep running {}", app.spinner_char())
+    } else {
+        "sweep idle".to_string()
+    };
+    let progress = app
+        .experiments_progress
+        .as_ref()
+        .map(|p| format!(" | {}/{} trials", p.completed_runs, p.total_runs))
+        .unwrap_or_default();
+    lines.push(Line::from(format!(
+        "{state}{progress} | sort: {}",
+        crate::run_browser::sort_label(board.sort)
+    )));
+    lines.push(Line::from(format!(
+        "keys: ↑/↓ select | s sort | Enter open run | b open best | {}/{} export csv/json | {} cancel | Esc back",
+        app.keymap.label(Action::ExportCsv),
+        app.keymap.label(Action::ExportJson),
+        app.keymap.label(Action::Cancel)
+    )));
+    if let Some(err) = &app.last_error {
+        lines.push(Line::from(Span::styled(
+            format!("error: {err}"),
+            Style::default().fg(theme.error),
+        )));
+    }
+    lines.push(Line::from(""));
+
+    if board.trials.is_empty() {
+        lines.push(Line::from("no trials yet (start a sweep in Experiments)"));
+    } else {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  {:>4} {:>8} {:>12} {:>8}  {:<8} {:<10} {}",
+                "#", "SHARPE", "NET_PROFIT", "MAX_DD", "STATUS", "SPLIT", "PARAMS"
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        let metric = |value: Option<f64>, scale: f64, precision: usize| {
+            value
+                .map(|v| format!("{:.precision$}", v * scale))
+                .unwrap_or_else(|| "-".to_string())
+        };
+        for (idx, trial) in board.trials.iter().enumerate() {
+            let selected = idx == board.selected;
+            let style = if selected {
+                Style::default().fg(theme.warn)
+            } else if trial.status == "error" {
+                Style::default().fg(theme.error)
+            } else {
+                Style::default()
+            };
+            let params = trial
+                .params
+                .iter()
+                .map(|(path, value)| format!("{path}={value}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{}{:>4} {:>8} {:>12} {:>7}%  {:<8} {:<10} {}",
+                    if selected { "> " } else { "  " },
+                    idx + 1,
+                    metric(trial.sharpe, 1.0, 3),
+                    metric(trial.net_profit, 1.0, 2),
+                    metric(trial.max_drawdown, 100.0, 2),
+                    trial.status,
+                    trial.split_id,
+                    params
+                ),
+                style,
+            )));
+        }
+        if let Some(trial) = board.selected_trial() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(format!("run dir: {}", trial.run_dir)));
+        }
+    }
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title("Sweep leaderboard")
+                    .borders(Borders::ALL),
+            )
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+fn draw_monitor(frame: &mut Frame, area: Rect, app: &mut App) {
+    let theme = app.theme;
+    if app.price_series.is_empty() || app.equity_series.is_empty() {
+        let lines = vec![
+            Line::from("Monitor"),
+            Line::from(""),
+            Line::from("Waiting for progress stream..."),
+            Line::from("Run Backtest/Paper to see charts update in real time."),
+            Line::from(format!(
+                "Keys: {} pause/resume, {} step (paused backtest/replay), {} cancel, ↑/↓ scroll trades, {}/{} export trades to csv/json, PgUp/PgDn scroll logs.",
+                app.keymap.label(Action::Pause),
+                app.keymap.label(Action::Step),
+                app.keymap.label(Action::Cancel),
+                app.keymap.label(Action::ExportCsv),
+                app.keymap.label(Action::ExportJson)
+            )),
+        ];
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(Block::default().title("Monitor").borders(Borders::ALL))
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)].as_ref())
+        .split(area);
+    let charts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Percentage(45),
+                Constraint::Min(5),
+                Constraint::Length(5),
+            ]
+            .as_ref(),
+        )
+        .split(chunks[0]);
+
+    let price_data: Vec<(f64, f64)> = app.price_series.iter().copied().collect();
+    let equity_data: Vec<(f64, f64)> = app.equity_series.iter().copied().collect();
+
+    let (x_min, x_max) = x_bounds(&price_data);
+
+    let (p_min, p_max) = y_bounds(&price_data);
+    let (e_min, e_max) = y_bounds(&equity_data);
+
+    let price = Chart::new(vec![Dataset::default()
+        .name("price")
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(theme.accent))
+        .data(&price_data)])
+    .block(
+        Block::default()
+            .title("Price (close)")
+            .borders(Borders::ALL),
+    )
+    .x_axis(
+        Axis::default()
+            .bounds([x_min, x_max])
+            .labels(axis_labels(x_min, x_max)),
+    )
+    .y_axis(
+        Axis::default()
+            .bounds([p_min, p_max])
+            .labels(axis_labels(p_min, p_max)),
+    );
+
+    let equity = Chart::new(vec![Dataset::default()
+        .name("equity")
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(theme.ok))
+        .data(&equity_data)])
+    .block(
+        Block::default()
+            .title(equity_title(&app.live_equity))
+            .borders(Borders::ALL),
+    )
+    .x_axis(
+        Axis::default()
+            .bounds([x_min, x_max])
+            .labels(axis_labels(x_min, x_max)),
+    )
+    .y_axis(
+        Axis::default()
+            .bounds([e_min, e_max])
+            .labels(axis_labels(e_min, e_max)),
+    );
+
+    frame.render_widget(price, charts[0]);
+    frame.render_widget(equity, charts[1]);
+
+    let live = &app.live_equity;
+    let drawdowns = live.drawdown_bars(charts[2].width.saturating_sub(2) as usize);
+    let drawdown = Sparkline::default()
+        .block(
+            Block::default()
+                .title(format!(
+                    "Drawdown (whole run): now {:.2}% | max {:.2}%",
+                    live.drawdown() * 100.0,
+                    live.max_drawdown * 100.0
+                ))
+                .borders(Borders::ALL),
+        )
+        .style(Style::default().fg(theme.error))
+        .max(drawdowns.iter().copied().max().unwrap_or(0).max(1))
+        .data(&drawdowns);
+    frame.render_widget(drawdown, charts[2]);
+
+    let risk = app
+        .config
+        .as_ref()
+        .map(|cfg| risk_gauges(&cfg.risk, &app.live_equity, app.risk_sample))
+        .unwrap_or_default();
+    let side_column = if risk.is_empty() {
+        chunks[1]
+    } else {
+        let right = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Length(risk.len() as u16 + 2),
+                    Constraint::Min(3),
+                ]
+                .as_ref(),
+            )
+            .split(chunks[1]);
+        draw_risk_gauges(frame, right[0], app, &risk);
+        right[1]
+    };
+
+    let position_lines = position_lines(app);
+    let right_column = if position_lines.is_empty() {
+        side_column
+    } else {
+        let height = (position_lines.len() as u16 + 2).min(side_column.height / 2);
+        let right = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(height), Constraint::Min(3)].as_ref())
+            .split(side_column);
+        frame.render_widget(
+            Paragraph::new(position_lines)
+                .block(Block::default().title("Position").borders(Borders::ALL))
+                .wrap(Wrap { trim: false }),
+            right[0],
+        );
+        right[1]
+    };
+    let trades_area = if app.agent_stats.calls > 0 {
+        let right = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(8)].as_ref())
+            .split(right_column);
+        draw_agent_stats(frame, right[1], app);
+        right[0]
+    } else {
+        right_column
+    };
+
+    let mut lines: Vec<Line> = strategy_prompt_line(app).into_iter().collect();
+    let max_lines = (trades_area.height.saturating_sub(2) as usize).saturating_sub(lines.len());
+    if app.trades.is_empty() {
+        lines.push(Line::from("no trades yet"));
+    } else {
+        for trade in app
+            .trades
+            .iter()
+            .rev()
+            .skip(app.trade_scroll)
+            .take(max_lines)
+        {
+            let side_style = match trade.side {
+                Side::Buy => Style::default().fg(theme.ok),
+                Side::Sell => Style::default().fg(theme.error),
+            };
+            lines.push(Line::from(vec![
+                Span::raw(format!("[#{}] ", trade.bar_index)),
+                Span::styled(
+                    format!("{:?}", trade.side),
+                    side_style.add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(
+                    " qty={:.4} @ {:.2} ts={}",
+                    trade.quantity, trade.price, trade.timestamp
+                )),
+            ]));
+        }
+    }
+
+    let mut title = "Trades".to_string();
+    match app.task_state() {
+        TaskState::Cancelling => title.push_str(" (CANCELLING)"),
+        TaskState::Paused => title.push_str(" (PAUSED)"),
+        TaskState::Cancelled => title.push_str(" (CANCELLED)"),
+        _ => {}
+    }
+    if let (Some(kind), Some(status)) = (app.status.kind, app.stream_status.as_ref()) {
+        if matches!(kind, crate::tasks::TaskKind::PaperRealtime { .. }) {
+            title.push_str(if status.connected {
+                " (WS: connected)"
+            } else {
+                " (WS: reconnecting)"
+            });
+        }
+    }
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .wrap(Wrap { trim: false }),
+        trades_area,
+    );
+}
+
+// One line gauge per enabled `[risk]` limit, colored as it nears the point where the engine starts
+// rejecting orders (or halts, for the drawdown).
+fn draw_risk_gauges(frame: &mut Frame, area: Rect, app: &App, gauges: &[RiskGauge]) {
+    let theme = app.theme;
+    let block = Block::default().title("Risk limits").borders(Borders::ALL);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(1); gauges.len()])
+        .split(inner);
+    for (gauge, row) in gauges.iter().zip(rows.iter()) {
+        let color = match gauge.level() {
+            RiskLevel::Ok => theme.ok,
+            RiskLevel::Warn => theme.warn,
+            RiskLevel::Alert => theme.error,
+        };
+        frame.render_widget(
+            LineGauge::default()
+                .filled_style(Style::default().fg(color))
+                .label(Span::styled(gauge.text(), Style::default().fg(color)))
+                .ratio(gauge.utilization().min(1.0)),
+            *row,
+        );
+    }
+}
+
+fn strategy_prompt_line(app: &App) -> Option<Line<'static>> {
+    let input = app.strategy_prompt.as_ref()?;
+    Some(Line::from(Span::styled(
+        format!(
+            "strategy change (sma <short> <long> | buy_and_hold | hold | fallback <buy|sell|hold>): {}_",
+            input.value
+        ),
+        Style::default().fg(app.theme.accent),
+    )))
+}
+
+// The paper session's position, working orders and (realtime) stream health; empty outside paper
+// sessions.
+fn position_lines(app: &App) -> Vec<Line<'static>> {
+    let theme = app.theme;
+    let paper = matches!(
+        app.status.kind,
+        Some(crate::tasks::TaskKind::Paper | crate::tasks::TaskKind::PaperRealtime { .. })
+    );
+    let positions = &app.positions;
+    if !paper || positions.is_empty() {
+        return Vec::new();
+    }
+    let pnl_style = |pnl: f64| {
+        if pnl > 0.0 {
+            Style::default().fg(theme.ok)
+        } else if pnl < 0.0 {
+            Style::default().fg(theme.error)
+        } else {
+            Style::default()
+        }
+    };
+
+    let mut lines = Vec::new();
+    for sample in &positions.symbols {
+        let pnl = crate::positions::unrealized_pnl(sample);
+        let pct = crate::positions::unrealized_return(sample)
+            .map(|ret| format!(" ({:+.2}%)", ret * 100.0))
+            .unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(
+                sample.symbol.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(
+                " qty {:.4} @ avg {:.2} | last {:.2} ({})",
+                sample.quantity,
+                sample.avg_price,
+                sample.price,
+                crate::ingest_form::format_ts(sample.timestamp)
+            )),
+        ]));
+        lines.push(Line::from(vec![
+            Span::raw("  uPnL "),
+            Span::styled(format!("{pnl:+.2}{pct}"), pnl_style(pnl)),
+            Span::raw(format!(
+                " | cash {:.2} | equity {:.2}",
+                sample.cash, sample.equity
+            )),
+        ]));
+    }
+    if positions.symbols.len() > 1 {
+        let pnl = positions.unrealized_pnl();
+        lines.push(Line::from(vec![
+            Span::raw("total uPnL "),
+            Span::styled(format!("{pnl:+.2}"), pnl_style(pnl)),
+        ]));
+    }
+
+    let orders: Vec<_> = positions.open_orders().collect();
+    lines.push(Line::from(format!("open orders: {}", orders.len())));
+    for (symbol, order) in orders {
+        let side_style = match order.side {
+            Side::Buy => Style::default().fg(theme.ok),
+            Side::Sell => Style::default().fg(theme.error),
+        };
+        let price = match (order.limit_price, order.stop_price) {
+            (Some(limit), _) => format!(" @ {limit:.2}"),
+            (None, Some(stop)) => format!(" stop {stop:.2}"),
+            (None, None) => String::new(),
+        };
+        let symbol = if positions.symbols.len() > 1 {
+            format!("{symbol} ")
+        } else {
+            String::new()
+        };
+        lines.push(Line::from(vec![
+            Span::raw(format!("  #{} {symbol}", order.id)),
+            Span::styled(format!("{:?}", order.side), side_style),
+            Span::raw(format!(
+                " {:?} {:.4}{price} (bar {})",
+                order.kind, order.remaining_qty, order.submitted_bar_index
+            )),
+        ]));
+    }
+
+    if let Some(status) = app.stream_status.as_ref() {
+        let (conn, style) = if status.connected {
+            ("connected", Style::default().fg(theme.ok))
+        } else {
+            ("reconnecting", Style::default().fg(theme.warn))
+        };
+        let last_event = status.last_event_timestamp.map_or_else(
+            || "-".to_string(),
+            |ts| {
+                let age = (chrono::Utc::now().timestamp() - ts).max(0);
+                format!("{} ({age}s ago)", crate::ingest_form::format_ts(ts))
+            },
+        );
+        lines.push(Line::from(vec![
+            Span::raw("stream "),
+            Span::styled(conn, style),
+            Span::raw(format!(
+                " | reconnects {} | last event {last_event}",
+                status.reconnects
+            )),
+        ]));
+    }
+    lines
+}
+
+fn draw_agent_stats(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = app.theme;
+    let stats = &app.agent_stats;
+    let ms = |value: Option<u64>| value.map_or_else(|| "-".to_string(), |v| format!("{v}ms"));
+    let fallback_style = if stats.fallbacks == 0 {
+        Style::default()
+    } else if stats.fallback_rate() < 0.05 {
+        Style::default().fg(theme.warn)
+    } else {
+        Style::default().fg(theme.error)
+    };
+    let mut lines = vec![
+        Line::from(format!(
+            "calls {} | retries {} | status {}",
+            stats.calls,
+            stats.retries,
+            stats
+                .last_status
+                .map_or_else(|| "-".to_string(), |s| s.to_string())
+        )),
+        Line::from(Span::styled(
+            format!(
+                "fallbacks {} ({:.1}%)",
+                stats.fallbacks,
+                stats.fallback_rate() * 100.0
+            ),
+            fallback_style,
+        )),
+        Line::from(format!(
+            "latency p50 {} | p95 {}",
+            ms(stats.latency_percentile(50.0)),
+            ms(stats.latency_percentile(95.0))
+        )),
+    ];
+    if let Some((call, error)) = &stats.last_error {
+        lines.push(Line::from(Span::styled(
+            format!("last error (call {call}): {error}"),
+            Style::default().fg(theme.error),
+        )));
+    }
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().title("Agent").borders(Borders::ALL))
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+fn equity_title(live: &crate::live_equity::LiveEquity) -> String {
+    match live.total_return() {
+        Some(ret) => format!(
+            "Equity {:.2} ({:+.2}%) | peak {:.2}",
+            live.last,
+            ret * 100.0,
+            live.peak
+        ),
+        None => "Equity".to_string(),
+    }
+}
+
+fn x_bounds(points: &[(f64, f64)]) -> (f64, f64) {
+    let x_min = points.first().map(|p| p.0).unwrap_or(0.0);
+    let mut x_max = points.last().map(|p| p.0).unwrap_or(x_min + 1.0);
+    if x_max <= x_min {
+        x_max = x_min + 1.0;
+    }
+    (x_min, x_max)
+}
+
+fn y_bounds(points: &[(f64, f64)]) -> (f64, f64) {
     let mut min = f64::INFINITY;
     let mut max = f64::NEG_INFINITY;
     for (_, y) in points {
@@ -741,6 +2344,7 @@ fn axis_labels(min: f64, max: f64) -> Vec<Line<'static>> {
 }
 
 fn draw_reports(frame: &mut Frame, area: Rect, app: &mut App) {
+    let theme = app.theme;
     let out_dir = app
         .config
         .as_ref()
@@ -773,13 +2377,13 @@ fn draw_reports(frame: &mut Frame, area: Rect, app: &mut App) {
             if let Some(err) = &app.last_error {
                 header.push(Line::from(Span::styled(
                     format!("error: {err}"),
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(theme.error),
                 )));
             }
             if let Some(info) = &app.info_message {
                 header.push(Line::from(Span::styled(
                     format!("info: {info}"),
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(theme.ok),
                 )));
             }
 
@@ -808,13 +2412,13 @@ fn draw_reports(frame: &mut Frame, area: Rect, app: &mut App) {
             if let Some(err) = &app.last_error {
                 lines.push(Line::from(Span::styled(
                     format!("error: {err}"),
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(theme.error),
                 )));
             }
             if let Some(info) = &app.info_message {
                 lines.push(Line::from(Span::styled(
                     format!("info: {info}"),
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(theme.ok),
                 )));
             }
             lines.push(Line::from(""));
@@ -835,6 +2439,12 @@ fn draw_reports(frame: &mut Frame, area: Rect, app: &mut App) {
                                 "  "
                             };
                             lines.push(Line::from(format!("{prefix}{}", run.line)));
+                            for note in &run.notes {
+                                lines.push(Line::from(Span::styled(
+                                    format!("      note: {note}"),
+                                    Style::default().fg(theme.muted),
+                                )));
+                            }
                         }
                     }
                     "Reports (runs)"
@@ -876,6 +2486,556 @@ fn draw_reports(frame: &mut Frame, area: Rect, app: &mut App) {
     }
 }
 
+fn draw_runs(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = app.theme;
+    let browser = &app.run_browser;
+    let panes = if browser.detail {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)].as_ref())
+            .split(area)
+    } else {
+        Layout::default()
+            .constraints([Constraint::Min(3)].as_ref())
+            .split(area)
+    };
+
+    let mut lines = vec![
+        Line::from(format!("Runs directory: {}", browser.out_dir.display())),
+        Line::from(format!(
+            "sort: {} | keys: ↑/↓ select | Enter details | c chart | u equity | t trades | m mark / v diff config | f follow live run | s sort | r regenerate report | d delete | g refresh | {}/{} export csv/json | Esc back",
+            crate::run_browser::sort_label(browser.sort),
+            app.keymap.label(Action::ExportCsv),
+            app.keymap.label(Action::ExportJson)
+        )),
+    ];
+    if let Some(err) = &app.last_error {
+        lines.push(Line::from(Span::styled(
+            format!("error: {err}"),
+            Style::default().fg(theme.error),
+        )));
+    }
+    if let Some(info) = &app.info_message {
+        let color = if browser.confirm_delete {
+            theme.warn
+        } else {
+            theme.ok
+        };
+        lines.push(Line::from(Span::styled(
+            format!("info: {info}"),
+            Style::default().fg(color),
+        )));
+    }
+    lines.push(Line::from(""));
+    if browser.runs.is_empty() {
+        lines.push(Line::from("no runs found (press g to refresh)"));
+    } else {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  {:<28} {:>12} {:>8} {:>8} {:>7}  {}",
+                "RUN_ID", "NET_PROFIT", "SHARPE", "MAX_DD", "TRADES", "RECORDED_AT"
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        for (idx, run) in browser.runs.iter().enumerate() {
+            let selected = idx == browser.selected;
+            let style = if selected {
+                Style::default().fg(theme.warn)
+            } else {
+                Style::default()
+            };
+            let marker = match (selected, browser.diff_base.as_deref() == Some(&run.run_dir)) {
+                (true, _) => "> ",
+                (false, true) => "* ",
+                (false, false) => "  ",
+            };
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{}{:<28} {:>12.2} {:>8.3} {:>7.2}% {:>7}  {}",
+                    marker,
+                    run.run_id,
+                    run.net_profit,
+                    run.sharpe,
+                    run.max_drawdown * 100.0,
+                    run.trades,
+                    crate::runs::format_timestamp(run.recorded_at)
+                ),
+                style,
+            )));
+        }
+    }
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().title("Runs").borders(Borders::ALL))
+            .wrap(Wrap { trim: false }),
+        panes[0],
+    );
+
+    if browser.detail {
+        let text = browser
+            .detail_text()
+            .unwrap_or_else(|| "(no run selected)".to_string());
+        frame.render_widget(
+            Paragraph::new(text)
+                .block(Block::default().title("Run detail").borders(Borders::ALL))
+                .wrap(Wrap { trim: false }),
+            panes[1],
+        );
+    }
+}
+
+fn draw_chart(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = app.theme;
+    let Some(view) = &app.candle_chart else {
+        frame.render_widget(
+            Paragraph::new("no chart loaded (press c on a run in Runs)")
+                .block(Block::default().title("Chart").borders(Borders::ALL)),
+            area,
+        );
+        return;
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(5)].as_ref())
+        .split(area);
+
+    let bars = view.visible_bars();
+    let range = match (bars.first(), bars.last()) {
+        (Some(first), Some(last)) => format!(
+            "{} .. {}",
+            crate::runs::format_timestamp(first.timestamp),
+            crate::runs::format_timestamp(last.timestamp)
+        ),
+        _ => "-".to_string(),
+    };
+    let header = vec![
+        Line::from(format!(
+            "{} {} {} | bars {}-{} of {} | {range}",
+            view.chart.run_id,
+            view.chart.symbol,
+            view.chart.timeframe,
+            view.offset + 1,
+            view.offset + view.width,
+            view.chart.bars.len()
+        )),
+        Line::from("keys: +/- zoom | ←/→ pan | PgUp/PgDn jump | Home/End | Esc back"),
+    ];
+    frame.render_widget(Paragraph::new(header), chunks[0]);
+
+    let (min, max) = view.price_bounds();
+    let marker_gap = (max - min) * 0.03;
+    let trades = view.visible_trades();
+    let canvas = Canvas::default()
+        .block(
+            Block::default()
+                .title(format!("Candles ({min:.2} .. {max:.2})"))
+                .borders(Borders::ALL),
+        )
+        .x_bounds([-0.5, view.width as f64 - 0.5])
+        .y_bounds([min, max])
+        .paint(|ctx| {
+            for (idx, bar) in bars.iter().enumerate() {
+                let x = idx as f64;
+                let color = if bar.close >= bar.open {
+                    theme.ok
+                } else {
+                    theme.error
+                };
+                ctx.draw(&CanvasLine {
+                    x1: x,
+                    y1: bar.low,
+                    x2: x,
+                    y2: bar.high,
+                    color,
+                });
+                ctx.draw(&Rectangle {
+                    x: x - 0.3,
+                    y: bar.open.min(bar.close),
+                    width: 0.6,
+                    height: (bar.close - bar.open).abs(),
+                    color,
+                });
+            }
+            for (idx, trade) in &trades {
+                let bar = &bars[*idx];
+                let (y, mark, color) = match trade.side {
+                    Side::Buy => (bar.low - marker_gap, "▲", theme.accent),
+                    Side::Sell => (bar.high + marker_gap, "▼", theme.accent_alt),
+                };
+                ctx.print(
+                    *idx as f64,
+                    y,
+                    Span::styled(mark, Style::default().fg(color)),
+                );
+            }
+        });
+    frame.render_widget(canvas, chunks[1]);
+}
+
+fn draw_equity(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = app.theme;
+    let Some(view) = &app.equity_view else {
+        frame.render_widget(
+            Paragraph::new("no equity loaded (press u on a run in Runs)")
+                .block(Block::default().title("Equity").borders(Borders::ALL)),
+            area,
+        );
+        return;
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(10)].as_ref())
+        .split(area);
+    let charts_area = if view.timings.is_empty() {
+        chunks[1]
+    } else {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(70), Constraint::Percentage(30)].as_ref())
+            .split(chunks[1]);
+        draw_stage_timings(frame, columns[1], app, &view.timings);
+        columns[0]
+    };
+    let charts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(60), Constraint::Min(5)].as_ref())
+        .split(charts_area);
+
+    let (ts, equity, drawdown) = view.selected();
+    let header = vec![
+        Line::from(format!(
+            "{} | point {}/{} | {} | equity {:.2} | drawdown {:.2}% (max {:.2}%)",
+            view.run_id,
+            view.cursor + 1,
+            view.points.len(),
+            crate::runs::format_timestamp(ts),
+            equity,
+            drawdown * 100.0,
+            view.max_drawdown() * 100.0
+        )),
+        Line::from("keys: ←/→ move the crosshair | PgUp/PgDn jump | Home/End | Esc back"),
+    ];
+    frame.render_widget(Paragraph::new(header), chunks[0]);
+
+    let equity_data = view.equity_curve();
+    let underwater_data = view.underwater_curve();
+    let (x_min, x_max) = x_bounds(&equity_data);
+    let (e_min, e_max) = y_bounds(&equity_data);
+    let (u_min, _) = y_bounds(&underwater_data);
+    let u_bounds = (u_min.min(-0.01), 0.0);
+    let x = ts as f64;
+    let equity_cross = [(x, e_min), (x, e_max)];
+    let underwater_cross = [(x, u_bounds.0), (x, u_bounds.1)];
+    let cross_style = Style::default().fg(theme.accent_alt);
+
+    let equity_chart = Chart::new(vec![
+        Dataset::default()
+            .name("equity")
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.ok))
+            .data(&equity_data),
+        Dataset::default()
+            .graph_type(GraphType::Line)
+            .style(cross_style)
+            .data(&equity_cross),
+    ])
+    .block(Block::default().title("Equity").borders(Borders::ALL))
+    .x_axis(
+        Axis::default()
+            .bounds([x_min, x_max])
+            .labels(time_axis_labels(x_min, x_max)),
+    )
+    .y_axis(
+        Axis::default()
+            .bounds([e_min, e_max])
+            .labels(axis_labels(e_min, e_max)),
+    );
+    let underwater_chart = Chart::new(vec![
+        Dataset::default()
+            .name("drawdown %")
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.error))
+            .data(&underwater_data),
+        Dataset::default()
+            .graph_type(GraphType::Line)
+            .style(cross_style)
+            .data(&underwater_cross),
+    ])
+    .block(
+        Block::default()
+            .title("Underwater (drawdown from peak, %)")
+            .borders(Borders::ALL),
+    )
+    .x_axis(
+        Axis::default()
+            .bounds([x_min, x_max])
+            .labels(time_axis_labels(x_min, x_max)),
+    )
+    .y_axis(
+        Axis::default()
+            .bounds([u_bounds.0, u_bounds.1])
+            .labels(axis_labels(u_bounds.0, u_bounds.1)),
+    );
+    frame.render_widget(equity_chart, charts[0]);
+    frame.render_widget(underwater_chart, charts[1]);
+}
+
+// Wall time per pipeline stage, in run order, with each stage's share of the total.
+fn draw_stage_timings(frame: &mut Frame, area: Rect, app: &App, timings: &[StageTiming]) {
+    let theme = app.theme;
+    let total: u64 = timings.iter().map(|t| t.duration_ms).sum();
+    let bars: Vec<Bar> = timings
+        .iter()
+        .map(|timing| {
+            let share = if total > 0 {
+                timing.duration_ms as f64 / total as f64 * 100.0
+            } else {
+                0.0
+            };
+            Bar::default()
+                .label(Line::from(timing.stage.clone()))
+                .value(timing.duration_ms)
+                .text_value(format!("{}ms ({share:.0}%)", timing.duration_ms))
+                .style(Style::default().fg(theme.accent))
+        })
+        .collect();
+    frame.render_widget(
+        BarChart::default()
+            .block(
+                Block::default()
+                    .title(format!("Stage timings (total {total}ms)"))
+                    .borders(Borders::ALL),
+            )
+            .direction(Direction::Horizontal)
+            .bar_width(1)
+            .bar_gap(1)
+            .value_style(Style::default().fg(theme.text))
+            .data(BarGroup::default().bars(&bars)),
+        area,
+    );
+}
+
+fn draw_trade_list(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = app.theme;
+    let Some(list) = &app.trade_list else {
+        frame.render_widget(
+            Paragraph::new("no trades loaded (press t on a run in Runs)")
+                .block(Block::default().title("Trades").borders(Borders::ALL)),
+            area,
+        );
+        return;
+    };
+    let filter = if list.editing_filter {
+        format!("{}_", list.filter_input.value)
+    } else if list.filter_input.value.trim().is_empty() {
+        "<none>".to_string()
+    } else {
+        list.filter_input.value.clone()
+    };
+    let mut header = vec![
+        Line::from(format!(
+            "{}: {} of {} trades | realized PnL {:.2} | group by: {}",
+            list.run_id,
+            list.visible.len(),
+            list.trades.len(),
+            list.visible_pnl(),
+            list.group_by.label()
+        )),
+        Line::from(format!(
+            "filter (e.g. side=sell, from=2024-01-01, to=2024-02-01, min_pnl=0): {filter}"
+        )),
+        Line::from("keys: ↑/↓ PgUp/PgDn select | / filter | a group by | Esc back"),
+    ];
+    if let Some(err) = &app.last_error {
+        header.push(Line::from(Span::styled(
+            format!("error: {err}"),
+            Style::default().fg(theme.error),
+        )));
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(5), Constraint::Min(6)].as_ref())
+        .split(area);
+    frame.render_widget(Paragraph::new(header), chunks[0]);
+
+    let groups = list.groups();
+    let table_area = if groups.is_empty() {
+        chunks[1]
+    } else {
+        let body = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Min(6),
+                    Constraint::Length((groups.len() as u16 + 3).min(chunks[1].height / 2)),
+                ]
+                .as_ref(),
+            )
+            .split(chunks[1]);
+        let mut lines = vec![Line::from(Span::styled(
+            format!(
+                "{:<20} {:>7} {:>12} {:>10} {:>12} {:>6}",
+                "KEY", "TRADES", "QTY", "FEES", "PNL", "WIN%"
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        ))];
+        for group in &groups {
+            let closed = group.wins + group.losses;
+            let win_rate = if closed > 0 {
+                format!("{:.0}", group.wins as f64 / closed as f64 * 100.0)
+            } else {
+                "-".to_string()
+            };
+            let style = if group.pnl < 0.0 {
+                Style::default().fg(theme.error)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{:<20} {:>7} {:>12.4} {:>10.4} {:>12.2} {:>6}",
+                    group.key, group.trades, group.quantity, group.fees, group.pnl, win_rate
+                ),
+                style,
+            )));
+        }
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title(format!("By {}", list.group_by.label()))
+                    .borders(Borders::ALL),
+            ),
+            body[1],
+        );
+        body[0]
+    };
+
+    let height = table_area.height.saturating_sub(3) as usize;
+    let skip = list.selected.saturating_sub(height.saturating_sub(1));
+    let mut rows = vec![Line::from(Span::styled(
+        format!(
+            "  {:<16} {:<10} {:<4} {:>12} {:>12} {:>9} {:>11}  {:<14} {}",
+            "TIME", "SYMBOL", "SIDE", "QTY", "PRICE", "FEE", "PNL", "STRATEGY", "REASON"
+        ),
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+    for (idx, (trade, pnl)) in list.visible_trades().enumerate().skip(skip).take(height) {
+        let selected = idx == list.selected;
+        let style = if selected {
+            Style::default().fg(theme.warn)
+        } else {
+            match pnl {
+                Some(pnl) if pnl < 0.0 => Style::default().fg(theme.error),
+                Some(pnl) if pnl > 0.0 => Style::default().fg(theme.ok),
+                _ => Style::default(),
+            }
+        };
+        rows.push(Line::from(Span::styled(
+            format!(
+                "{}{:<16} {:<10} {:<4} {:>12.4} {:>12.4} {:>9.4} {:>11}  {:<14} {}",
+                if selected { "> " } else { "  " },
+                crate::runs::format_timestamp(trade.timestamp),
+                trade.symbol.as_str(),
+                format!("{:?}", trade.side).to_uppercase(),
+                trade.quantity,
+                trade.price,
+                trade.fee,
+                pnl.map(|pnl| format!("{pnl:.2}"))
+                    .unwrap_or_else(|| "-".to_string()),
+                trade.strategy_id,
+                trade.reason
+            ),
+            style,
+        )));
+    }
+    if list.visible.is_empty() {
+        rows.push(Line::from("no trades match the filter"));
+    }
+    frame.render_widget(
+        Paragraph::new(rows).block(Block::default().title("Trades").borders(Borders::ALL)),
+        table_area,
+    );
+}
+
+fn draw_config_diff(frame: &mut Frame, area: Rect, app: &App) {
+    use crate::config_diff::DiffLine;
+    let theme = app.theme;
+    let Some(view) = &app.config_diff else {
+        frame.render_widget(
+            Paragraph::new("no config diff (press v on a run in Runs)")
+                .block(Block::default().title("Config diff").borders(Borders::ALL)),
+            area,
+        );
+        return;
+    };
+    let mut header = vec![
+        Line::from(vec![
+            Span::styled(format!("- {}", view.left), Style::default().fg(theme.error)),
+            Span::raw("  vs  "),
+            Span::styled(format!("+ {}", view.right), Style::default().fg(theme.ok)),
+        ]),
+        Line::from(format!(
+            "{} line(s) removed, {} added | keys: ↑/↓ PgUp/PgDn scroll | n/p next/prev change | Esc back",
+            view.removed, view.added
+        )),
+    ];
+    if view.added + view.removed == 0 {
+        header.push(Line::from(Span::styled(
+            "the configs are identical",
+            Style::default().fg(theme.ok),
+        )));
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3)].as_ref())
+        .split(area);
+    frame.render_widget(Paragraph::new(header), chunks[0]);
+
+    let height = chunks[1].height.saturating_sub(2) as usize;
+    let lines: Vec<Line> = view
+        .lines
+        .iter()
+        .skip(view.scroll)
+        .take(height)
+        .map(|line| match line {
+            DiffLine::Same(text) => Line::from(Span::styled(
+                format!("  {text}"),
+                Style::default().fg(theme.muted),
+            )),
+            DiffLine::Removed(text) => Line::from(Span::styled(
+                format!("- {text}"),
+                Style::default().fg(theme.error),
+            )),
+            DiffLine::Added(text) => Line::from(Span::styled(
+                format!("+ {text}"),
+                Style::default().fg(theme.ok),
+            )),
+            DiffLine::Skipped { lines, table } => Line::from(Span::styled(
+                match table {
+                    Some(table) => format!("@@ {lines} unchanged line(s) @@ {table}"),
+                    None => format!("@@ {lines} unchanged line(s) @@"),
+                },
+                Style::default().fg(theme.accent),
+            )),
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .title("config_snapshot.toml")
+                .borders(Borders::ALL),
+        ),
+        chunks[1],
+    );
+}
+
+fn time_axis_labels(min: f64, max: f64) -> Vec<Line<'static>> {
+    vec![
+        Line::from(crate::runs::format_timestamp(min as i64)),
+        Line::from(crate::runs::format_timestamp(max as i64)),
+    ]
+}
+
 fn draw_bottom(frame: &mut Frame, area: Rect, app: &App) {
     let logs = app.logs.lock().snapshot();
     let max_lines = area.height.saturating_sub(2) as usize;
@@ -898,13 +3058,16 @@ fn draw_bottom(frame: &mut Frame, area: Rect, app: &App) {
     );
 }
 
-fn task_kind_label(kind: crate::tasks::TaskKind) -> &'static str {
+pub(crate) fn task_kind_label(kind: crate::tasks::TaskKind) -> &'static str {
     match kind {
         crate::tasks::TaskKind::Validate { .. } => "validate",
         crate::tasks::TaskKind::Backtest => "backtest",
         crate::tasks::TaskKind::Paper => "paper",
-        crate::tasks::TaskKind::PaperRealtime => "paper(realtime)",
+        crate::tasks::TaskKind::PaperRealtime { simulated: false } => "paper(realtime)",
+        crate::tasks::TaskKind::PaperRealtime { simulated: true } => "paper(simulated)",
         crate::tasks::TaskKind::Sweep => "sweep",
+        crate::tasks::TaskKind::Ingest => "ingest",
+        crate::tasks::TaskKind::Follow => "follow",
     }
 }
 
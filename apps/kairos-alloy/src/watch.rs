@@ -0,0 +1,188 @@
+use crate::output::CliError;
+use kairos_application::registry::read_run_record;
+use kairos_domain::repositories::run_registry::RunRecord;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+pub struct WatchBacktestArgs {
+    pub config_path: PathBuf,
+    pub config_sets: Vec<String>,
+    pub interval: Duration,
+    // One JSON object per run (`--json`) instead of the metric-diff line.
+    pub json: bool,
+}
+
+type Fingerprint = Vec<Option<(SystemTime, u64)>>;
+
+// `kairos-alloy backtest --watch`: reruns the backtest whenever the config (or the sentiment file
+// it points at) changes and prints one line per run with the metric deltas versus the previous
+// successful run (one JSON object per run with `json`). Load/run errors are printed and the
+// watch keeps going.
+pub fn watch_backtest(
+    args: &WatchBacktestArgs,
+    should_stop: &dyn Fn() -> bool,
+    out: &mut dyn Write,
+) -> Result<(), String> {
+    let mut previous: Option<RunRecord> = None;
+    let mut iteration = 0_u64;
+    loop {
+        iteration += 1;
+        let watched = watched_paths(args);
+        let stamp = fingerprint(&watched);
+
+        let line = match run_once(args) {
+            Ok(record) => {
+                let line = if args.json {
+                    serde_json::json!({
+                        "status": "ok",
+                        "mode": "backtest_watch",
+                        "iteration": iteration,
+                        "run": record,
+                    })
+                    .to_string()
+                } else {
+                    format!(
+                        "[run {iteration}] {}",
+                        format_metric_diff(previous.as_ref(), &record)
+                    )
+                };
+                previous = Some(record);
+                line
+            }
+            Err(err) if args.json => {
+                let mut value = crate::output::error_json(err.kind, &err.message);
+                value["mode"] = "backtest_watch".into();
+                value["iteration"] = iteration.into();
+                value.to_string()
+            }
+            Err(err) => format!("[run {iteration}] error: {err}"),
+        };
+        writeln!(out, "{line}")
+            .and_then(|_| out.flush())
+            .map_err(|err| format!("failed to write output: {err}"))?;
+        metrics::counter!("kairos.backtest.watch_runs_total").increment(1);
+
+        loop {
+            if should_stop() {
+                return Ok(());
+            }
+            std::thread::sleep(args.interval);
+            if fingerprint(&watched) != stamp {
+                break;
+            }
+        }
+    }
+}
+
+fn run_once(args: &WatchBacktestArgs) -> Result<RunRecord, CliError> {
+    let (config, config_toml) = kairos_application::config::load_config_with_overrides(
+        &args.config_path,
+        &args.config_sets,
+    )?;
+    let run_dir = crate::headless::execute_backtest(&config, &config_toml)?;
+    read_run_record(&run_dir).map_err(CliError::from)
+}
+
+// The config itself plus the sentiment files (`paths.sentiment_path`, `paths.sentiment_sources`)
+// when the config currently parses.
+fn watched_paths(args: &WatchBacktestArgs) -> Vec<PathBuf> {
+    let mut paths = vec![args.config_path.clone()];
+    if let Ok((config, _)) =
+        kairos_application::config::load_config_with_overrides(&args.config_path, &args.config_sets)
+    {
+        if let Some(sentiment) = config.paths.sentiment_path {
+            paths.push(PathBuf::from(sentiment));
+        }
+        paths.extend(
+            config
+                .paths
+                .sentiment_sources
+                .into_iter()
+                .flatten()
+                .filter_map(|source| source.path.map(PathBuf::from)),
+        );
+    }
+    paths
+}
+
+fn fingerprint(paths: &[PathBuf]) -> Fingerprint {
+    paths.iter().map(|path| file_stamp(path)).collect()
+}
+
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+pub fn format_metric_diff(previous: Option<&RunRecord>, current: &RunRecord) -> String {
+    let delta = |cur: f64, prev: Option<f64>, precision: usize| match prev {
+        Some(prev) if (cur - prev).abs() > f64::EPSILON => {
+            format!(" ({:+.precision$})", cur - prev)
+        }
+        Some(_) => " (=)".to_string(),
+        None => String::new(),
+    };
+    format!(
+        "trades {}{}  net_profit {:.2}{}  sharpe {:.3}{}  max_dd {:.2}%{}  win_rate {:.1}%{}",
+        current.trades,
+        delta(current.trades as f64, previous.map(|p| p.trades as f64), 0),
+        current.net_profit,
+        delta(current.net_profit, previous.map(|p| p.net_profit), 2),
+        current.sharpe,
+        delta(current.sharpe, previous.map(|p| p.sharpe), 3),
+        current.max_drawdown * 100.0,
+        delta(
+            current.max_drawdown * 100.0,
+            previous.map(|p| p.max_drawdown * 100.0),
+            2
+        ),
+        current.win_rate * 100.0,
+        delta(
+            current.win_rate * 100.0,
+            previous.map(|p| p.win_rate * 100.0),
+            1
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_metric_diff;
+    use kairos_domain::repositories::run_registry::RunRecord;
+
+    fn record(trades: u64, net_profit: f64, sharpe: f64) -> RunRecord {
+        RunRecord {
+            run_id: "watch".to_string(),
+            run_dir: "runs/watch".to_string(),
+            config_hash: None,
+            symbol: None,
+            timeframe: None,
+            start: None,
+            end: None,
+            bars_processed: 10,
+            trades,
+            win_rate: 0.5,
+            net_profit,
+            sharpe,
+            max_drawdown: 0.1,
+            recorded_at: 0,
+            notes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn diff_shows_signed_deltas_against_previous_run() {
+        let first = record(4, 10.0, 1.0);
+        assert_eq!(
+            format_metric_diff(None, &first),
+            "trades 4  net_profit 10.00  sharpe 1.000  max_dd 10.00%  win_rate 50.0%"
+        );
+
+        let second = record(6, 7.5, 1.25);
+        assert_eq!(
+            format_metric_diff(Some(&first), &second),
+            "trades 6 (+2)  net_profit 7.50 (-2.50)  sharpe 1.250 (+0.250)  max_dd 10.00% (=)  win_rate 50.0% (=)"
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use clap::Parser;
-use std::fs;
+use kairos_application::benchmarking::{BenchMemory, BenchMode, BenchOptions, MemoryProbe};
+use kairos_infrastructure::profiling::{self, HeapStats};
 use std::net::SocketAddr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "kairos-bench")]
@@ -15,10 +16,14 @@ struct Args {
     #[arg(long, default_value_t = 60)]
     step_seconds: i64,
 
-    /// Benchmark mode: engine (baseline strategy) or features (feature pipeline + HOLD).
+    /// Benchmark mode: engine (baseline strategy), features (feature pipeline + HOLD), agent (feature pipeline + in-process mock agent) or symbols (symbol intern/clone on bar load and trade creation).
     #[arg(long, default_value = "features")]
     mode: String,
 
+    /// Simulated round trip of each agent call in `--mode agent`, in milliseconds (default: 0).
+    #[arg(long, default_value_t = 0.0)]
+    agent_latency_ms: f64,
+
     /// Print a single JSON line instead of human output.
     #[arg(long, default_value_t = false)]
     json: bool,
@@ -30,6 +35,41 @@ struct Args {
     /// Write a CPU profile as an SVG flamegraph to this path (requires feature `pprof`).
     #[arg(long)]
     profile_svg: Option<PathBuf>,
+
+    /// Compare throughput against a baseline (a previous `--json` output) and fail on regression.
+    #[arg(long)]
+    compare: Option<PathBuf>,
+
+    /// Maximum bars/sec drop or memory growth vs the baseline, in percent, before `--compare` fails (default: 10).
+    #[arg(long, default_value_t = 10.0)]
+    max_regression_pct: f64,
+}
+
+#[cfg(feature = "heap-profile")]
+#[global_allocator]
+static ALLOCATOR: kairos_infrastructure::profiling::TrackingAllocator =
+    kairos_infrastructure::profiling::TrackingAllocator;
+
+// Peak RSS of the engine stage, plus allocation counts when built with `heap-profile`.
+struct ProcessMemoryProbe {
+    heap_start: Option<HeapStats>,
+}
+
+impl MemoryProbe for ProcessMemoryProbe {
+    fn start(&mut self) {
+        profiling::reset_peak_rss();
+        self.heap_start = cfg!(feature = "heap-profile").then(HeapStats::start_window);
+    }
+
+    fn finish(&mut self) -> BenchMemory {
+        let heap = self.heap_start.map(|start| (start, HeapStats::snapshot()));
+        BenchMemory {
+            peak_rss_bytes: profiling::peak_rss_bytes(),
+            allocations: heap.map(|(start, end)| end.allocations - start.allocations),
+            allocated_bytes: heap.map(|(start, end)| end.allocated_bytes - start.allocated_bytes),
+            peak_live_bytes: heap.map(|(_, end)| end.peak_live_bytes),
+        }
+    }
 }
 
 fn main() {
@@ -44,13 +84,7 @@ fn main() {
         std::process::exit(1);
     }
 
-    if let Err(err) = run_bench(
-        args.bars,
-        args.step_seconds,
-        args.mode,
-        args.json,
-        args.profile_svg,
-    ) {
+    if let Err(err) = run_bench(args) {
         eprintln!("error: {err}");
         std::process::exit(1);
     }
@@ -92,39 +126,70 @@ fn init_metrics(metrics_addr: Option<&str>) -> Result<Option<SocketAddr>, String
     Ok(None)
 }
 
-fn run_bench(
-    bars: usize,
-    step_seconds: i64,
-    mode: String,
-    json: bool,
-    profile_svg: Option<PathBuf>,
-) -> Result<(), String> {
+fn run_bench(args: Args) -> Result<(), String> {
+    let Args {
+        bars,
+        step_seconds,
+        mode,
+        agent_latency_ms,
+        json,
+        profile_svg,
+        compare,
+        max_regression_pct,
+        ..
+    } = args;
     let mode_label = mode.trim().to_lowercase();
+    if mode_label == "symbols" {
+        if compare.is_some() {
+            return Err("--compare is not supported with --mode symbols".to_string());
+        }
+        return run_symbol_bench(bars, step_seconds, json);
+    }
+    let baseline = compare.as_deref().map(read_baseline).transpose()?;
 
     #[cfg(feature = "pprof")]
-    let guard = if let Some(path) = &profile_svg {
-        let _ = fs::create_dir_all(
-            path.parent()
-                .ok_or_else(|| "profile_svg path has no parent".to_string())?,
-        );
-        Some(
-            pprof::ProfilerGuard::new(100)
-                .map_err(|err| format!("failed to start profiler: {err}"))?,
-        )
-    } else {
-        None
-    };
+    let profiler = profile_svg
+        .as_ref()
+        .map(|_| kairos_infrastructure::profiling::CpuProfiler::start(100))
+        .transpose()?;
 
     #[cfg(not(feature = "pprof"))]
     if profile_svg.is_some() {
         return Err("profiling requires kairos-bench feature `pprof`".to_string());
     }
 
-    let bench = kairos_application::benchmarking::run_bench(bars, step_seconds, &mode_label)?;
+    let mut memory_probe = ProcessMemoryProbe { heap_start: None };
+    let bench = kairos_application::benchmarking::run_bench_with(
+        bars,
+        step_seconds,
+        &mode_label,
+        BenchOptions {
+            agent_latency_ms,
+            memory_probe: Some(&mut memory_probe),
+        },
+    )?;
+    let memory = bench.memory.unwrap_or_default();
+    let comparison = baseline
+        .as_ref()
+        .map(|baseline| {
+            kairos_application::benchmarking::compare_to_baseline(
+                &bench,
+                baseline,
+                max_regression_pct,
+            )
+        })
+        .transpose()?;
     let elapsed_ms = bench.elapsed_ms;
     let bars_processed = bench.bars_processed;
     let bars_per_sec = bench.bars_per_sec;
     let results = bench.results;
+    // Wall-clock cost per bar, to extrapolate agent runs to the size of a real backtest.
+    let ms_per_bar = if bars_per_sec > 0.0 {
+        1_000.0 / bars_per_sec
+    } else {
+        0.0
+    };
+    let agent_mode = bench.mode == BenchMode::Agent;
 
     metrics::histogram!("kairos.bench.elapsed_ms", "mode" => mode_label.clone())
         .record(elapsed_ms as f64);
@@ -133,39 +198,58 @@ fn run_bench(
         .set(bars_processed as f64);
 
     #[cfg(feature = "pprof")]
-    if let (Some(guard), Some(path)) = (guard, &profile_svg) {
-        let report = guard
-            .report()
-            .build()
-            .map_err(|err| format!("failed to build profile report: {err}"))?;
-        let file = std::fs::File::create(path)
-            .map_err(|err| format!("failed to create {}: {err}", path.display()))?;
-        report
-            .flamegraph(file)
-            .map_err(|err| format!("failed to write flamegraph: {err}"))?;
+    if let (Some(profiler), Some(path)) = (profiler, &profile_svg) {
+        profiler.write_flamegraph(path)?;
         tracing::info!(profile_svg = %path.display(), "wrote cpu profile flamegraph");
     }
 
     if json {
-        let line = serde_json::json!({
-            "mode": match bench.mode {
-                kairos_application::benchmarking::BenchMode::Engine => "engine",
-                kairos_application::benchmarking::BenchMode::Features => "features",
-            },
+        let mut line = serde_json::json!({
+            "mode": bench.mode.as_str(),
             "bars_requested": bench.bars_requested,
             "bars_processed": bars_processed,
             "elapsed_ms": elapsed_ms,
             "bars_per_sec": bars_per_sec,
             "size_mode": "qty",
         });
+        let measured: serde_json::Map<String, serde_json::Value> = memory
+            .fields()
+            .into_iter()
+            .filter_map(|(field, value)| Some((field.to_string(), value?.into())))
+            .collect();
+        line["memory"] = measured.into();
+        if agent_mode {
+            line["agent"] = serde_json::json!({
+                "latency_ms": agent_latency_ms,
+                "ms_per_bar": ms_per_bar,
+            });
+        }
+        if let Some(comparison) = &comparison {
+            let memory: serde_json::Map<String, serde_json::Value> = comparison
+                .memory
+                .iter()
+                .map(|field| {
+                    let value = serde_json::json!({
+                        "baseline": field.baseline,
+                        "change_pct": field.change_pct,
+                        "regressed": field.regressed,
+                    });
+                    (field.field.to_string(), value)
+                })
+                .collect();
+            line["baseline"] = serde_json::json!({
+                "bars_per_sec": comparison.baseline_bars_per_sec,
+                "change_pct": comparison.change_pct,
+                "memory": memory,
+                "max_regression_pct": comparison.max_regression_pct,
+                "regressed": comparison.regressed,
+            });
+        }
         println!("{}", line);
     } else {
         println!(
             "bench: mode={} bars={} elapsed_ms={} bars_per_sec={:.2}",
-            match bench.mode {
-                kairos_application::benchmarking::BenchMode::Engine => "engine",
-                kairos_application::benchmarking::BenchMode::Features => "features",
-            },
+            bench.mode.as_str(),
             bars_processed,
             elapsed_ms,
             bars_per_sec
@@ -177,7 +261,88 @@ fn run_bench(
             results.summary.sharpe,
             results.summary.max_drawdown
         );
+        if agent_mode {
+            println!(
+                "bench: agent_latency_ms={} ms_per_bar={:.4}",
+                agent_latency_ms, ms_per_bar
+            );
+        }
+        let measured: Vec<String> = memory
+            .fields()
+            .into_iter()
+            .filter_map(|(field, value)| Some(format!("{field}={}", value?)))
+            .collect();
+        if !measured.is_empty() {
+            println!("bench: {}", measured.join(" "));
+        }
+        if let Some(comparison) = &comparison {
+            println!(
+                "bench: baseline_bars_per_sec={:.2} change_pct={:+.2} max_regression_pct={:.2}",
+                comparison.baseline_bars_per_sec,
+                comparison.change_pct,
+                comparison.max_regression_pct
+            );
+            for field in &comparison.memory {
+                println!(
+                    "bench: baseline_{}={} change_pct={:+.2}",
+                    field.field, field.baseline, field.change_pct
+                );
+            }
+        }
     }
 
+    if let Some(comparison) = comparison.filter(|comparison| comparison.regressed) {
+        let mut regressions = Vec::new();
+        if -comparison.change_pct > comparison.max_regression_pct {
+            regressions.push(format!("bars_per_sec {:+.2}%", comparison.change_pct));
+        }
+        for field in comparison.memory.iter().filter(|field| field.regressed) {
+            regressions.push(format!("{} {:+.2}%", field.field, field.change_pct));
+        }
+        return Err(format!(
+            "regressed vs baseline (limit {:.2}%): {}",
+            comparison.max_regression_pct,
+            regressions.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+fn run_symbol_bench(bars: usize, step_seconds: i64, json: bool) -> Result<(), String> {
+    let bench = kairos_application::benchmarking::run_symbol_bench(bars, step_seconds)?;
+    metrics::gauge!("kairos.bench.trades_per_sec", "mode" => "symbols").set(bench.trades_per_sec);
+    if json {
+        let line = serde_json::json!({
+            "mode": "symbols",
+            "bars": bench.bars,
+            "bar_load": {
+                "intern_per_bar_ns": bench.intern_per_bar_ns,
+                "clone_per_bar_ns": bench.clone_per_bar_ns,
+            },
+            "trade_creation": {
+                "trades": bench.trades,
+                "elapsed_ms": bench.trade_creation_ms,
+                "trades_per_sec": bench.trades_per_sec,
+            },
+        });
+        println!("{}", line);
+    } else {
+        println!(
+            "bench: mode=symbols bars={} intern_per_bar_ns={:.2} clone_per_bar_ns={:.2}",
+            bench.bars, bench.intern_per_bar_ns, bench.clone_per_bar_ns
+        );
+        println!(
+            "bench: trades={} elapsed_ms={} trades_per_sec={:.2}",
+            bench.trades, bench.trade_creation_ms, bench.trades_per_sec
+        );
+    }
     Ok(())
 }
+
+fn read_baseline(path: &Path) -> Result<serde_json::Value, String> {
+    let raw = std::fs::read_to_string(path)
+        .map_err(|err| format!("failed to read baseline {}: {err}", path.display()))?;
+    serde_json::from_str(raw.trim())
+        .map_err(|err| format!("invalid baseline {}: {err}", path.display()))
+}
@@ -0,0 +1,289 @@
+use super::connector::{
+    lookup_interval, run_ingest, send_with_retry, ExchangeConnector, IngestJob, Window,
+};
+use super::{parse_f64, Candle, IngestError, InsertMode, Market};
+use chrono::{TimeZone, Utc};
+use kairos_domain::services::progress::ProgressSink;
+use kairos_domain::value_objects::timeframe::Timeframe;
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+const BINANCE_SPOT_BASE: &str = "https://api.binance.com";
+const BINANCE_KLINES_LIMIT: i64 = 1000;
+// Binance allows 6000 request weight per minute and IP; past this much the next window waits for
+// the minute to roll over instead of risking a 429 (and then a 418 ban).
+const BINANCE_WEIGHT_BUDGET: u64 = 5000;
+const USED_WEIGHT_HEADER: &str = "x-mbx-used-weight-1m";
+const BINANCE_INTERVALS: &[(&str, &str)] = &[
+    ("1min", "1m"),
+    ("3min", "3m"),
+    ("5min", "5m"),
+    ("15min", "15m"),
+    ("30min", "30m"),
+    ("1hour", "1h"),
+    ("2hour", "2h"),
+    ("4hour", "4h"),
+    ("6hour", "6h"),
+    ("8hour", "8h"),
+    ("12hour", "12h"),
+    ("1day", "1d"),
+    ("1week", "1w"),
+];
+
+#[derive(Debug, Deserialize)]
+struct BinanceError {
+    code: i64,
+    msg: String,
+}
+
+// Binance spot klines (`/api/v3/klines`).
+pub(crate) struct BinanceConnector {
+    base_url: String,
+    // Request weight used this minute, from the last response.
+    used_weight: Option<u64>,
+}
+
+impl BinanceConnector {
+    pub fn new(base_url: Option<&str>) -> Self {
+        Self {
+            base_url: base_url
+                .unwrap_or(BINANCE_SPOT_BASE)
+                .trim_end_matches('/')
+                .to_string(),
+            used_weight: None,
+        }
+    }
+}
+
+impl ExchangeConnector for BinanceConnector {
+    fn name(&self) -> &'static str {
+        "binance"
+    }
+
+    fn max_candles(&self) -> i64 {
+        BINANCE_KLINES_LIMIT
+    }
+
+    // `BTC-USDT`, `btc/usdt` and `BTCUSDT` all name the Binance pair `BTCUSDT`.
+    fn api_symbol(&self, symbol: &str) -> String {
+        compact_symbol(symbol)
+    }
+
+    fn interval(&self, timeframe: &Timeframe) -> Result<String, IngestError> {
+        lookup_interval("Binance", timeframe, BINANCE_INTERVALS)
+    }
+
+    async fn fetch_candles(
+        &mut self,
+        client: &Client,
+        window: &Window<'_>,
+        progress: &mut dyn ProgressSink,
+    ) -> Result<Vec<Candle>, IngestError> {
+        let url = format!("{}/api/v3/klines", self.base_url);
+        let start = (window.start * 1000).to_string();
+        let end = (window.end * 1000 + 999).to_string();
+        let limit = BINANCE_KLINES_LIMIT.to_string();
+        // A 418 means the IP is already banned for ignoring 429s; it is not retried.
+        let response = send_with_retry(
+            self.name(),
+            || {
+                client.get(&url).query(&[
+                    ("symbol", window.symbol),
+                    ("interval", window.interval),
+                    ("startTime", &start),
+                    ("endTime", &end),
+                    ("limit", &limit),
+                ])
+            },
+            progress,
+        )
+        .await?;
+
+        self.used_weight = response
+            .headers()
+            .get(USED_WEIGHT_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok());
+        let status = response.status();
+        if !status.is_success() {
+            // Rejected requests (bad symbol or interval) carry `{"code":-1121,"msg":"Invalid symbol."}`.
+            return Err(match response.json::<BinanceError>().await {
+                Ok(err) => IngestError::Api {
+                    market: "binance",
+                    code: format!("{} {}", err.code, err.msg),
+                },
+                Err(_) => IngestError::Status {
+                    market: "binance",
+                    status,
+                },
+            });
+        }
+
+        let rows: Vec<Vec<serde_json::Value>> =
+            response
+                .json()
+                .await
+                .map_err(|source| IngestError::Decode {
+                    market: "binance",
+                    source,
+                })?;
+        parse_binance_rows(&rows)
+    }
+
+    fn pause(&self, sleep: Duration) -> Duration {
+        if self
+            .used_weight
+            .is_some_and(|weight| weight >= BINANCE_WEIGHT_BUDGET)
+        {
+            Duration::from_secs((60 - Utc::now().timestamp().rem_euclid(60)) as u64)
+        } else {
+            sleep
+        }
+    }
+}
+
+// Pulls Binance spot klines window by window (at most `BINANCE_KLINES_LIMIT` bars per request) and
+// upserts them into `ohlcv_candles` like the KuCoin ingestion. `symbol` is stored as given, so
+// `BTC-USDT` sits next to the KuCoin rows; the request uses the Binance form (`BTCUSDT`).
+#[allow(clippy::too_many_arguments)]
+pub async fn ingest_binance(
+    db_url: &str,
+    symbol: &str,
+    timeframe: &str,
+    start: &str,
+    end: Option<&str>,
+    exchange: &str,
+    source: &str,
+    sleep_ms: u64,
+    batch_size: usize,
+    insert_mode: InsertMode,
+    base_url: Option<&str>,
+    resume: bool,
+    progress: &mut dyn ProgressSink,
+) -> Result<(), IngestError> {
+    let mut connector = BinanceConnector::new(base_url);
+    let job = IngestJob {
+        db_url,
+        symbol,
+        market: Market::Spot,
+        timeframe,
+        start,
+        end,
+        exchange,
+        source,
+        sleep_ms,
+        batch_size,
+        insert_mode,
+        resume,
+        limiter: None,
+    };
+    run_ingest(&mut connector, job, progress).await
+}
+
+// The pair without separators, uppercased (Binance and Bybit naming).
+pub(crate) fn compact_symbol(symbol: &str) -> String {
+    symbol
+        .chars()
+        .filter(|ch| ch.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_uppercase()
+}
+
+// Rows are `[open_time_ms, "open", "high", "low", "close", "volume", close_time_ms, "quote_volume",
+// trades, ...]`; the quote volume is kept as the turnover, like KuCoin's.
+fn parse_binance_rows(rows: &[Vec<serde_json::Value>]) -> Result<Vec<Candle>, IngestError> {
+    let text = |row: &[serde_json::Value], idx: usize, field: &str| {
+        row[idx]
+            .as_str()
+            .ok_or_else(|| format!("invalid {field}: {}", row[idx]))
+            .and_then(|value| parse_f64(value, field))
+    };
+    let mut candles = Vec::with_capacity(rows.len());
+    for row in rows {
+        if row.len() < 8 {
+            return Err("unexpected kline row length".into());
+        }
+        let open_time = row[0]
+            .as_i64()
+            .ok_or_else(|| format!("invalid open time: {}", row[0]))?;
+        let timestamp = Utc
+            .timestamp_opt(open_time / 1000, 0)
+            .single()
+            .ok_or("invalid timestamp")?;
+        candles.push(Candle {
+            timestamp,
+            open: text(row, 1, "open")?,
+            high: text(row, 2, "high")?,
+            low: text(row, 3, "low")?,
+            close: text(row, 4, "close")?,
+            volume: text(row, 5, "volume")?,
+            turnover: Some(text(row, 7, "quote volume")?),
+        });
+    }
+    candles.sort_by_key(|c| c.timestamp);
+    Ok(candles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compact_symbol, parse_binance_rows, BinanceConnector};
+    use crate::connector::ExchangeConnector;
+    use kairos_domain::value_objects::timeframe::Timeframe;
+    use serde_json::json;
+
+    #[test]
+    fn binance_klines_map_to_candles() {
+        let connector = BinanceConnector {
+            base_url: String::new(),
+            used_weight: None,
+        };
+        assert_eq!(compact_symbol("btc-usdt"), "BTCUSDT");
+        assert_eq!(
+            connector
+                .interval(&Timeframe::parse("4h").unwrap())
+                .unwrap(),
+            "4h"
+        );
+        assert!(connector
+            .interval(&Timeframe::parse("1month").unwrap())
+            .unwrap_err()
+            .to_string()
+            .contains("no Binance kline interval"));
+
+        let rows = vec![
+            vec![
+                json!(1_700_000_060_000i64),
+                json!("101.0"),
+                json!("103.0"),
+                json!("100.5"),
+                json!("102.0"),
+                json!("2.5"),
+                json!(1_700_000_119_999i64),
+                json!("255.0"),
+                json!(42),
+            ],
+            vec![
+                json!(1_700_000_000_000i64),
+                json!("100.0"),
+                json!("101.5"),
+                json!("99.0"),
+                json!("101.0"),
+                json!("1.0"),
+                json!(1_700_000_059_999i64),
+                json!("100.0"),
+                json!(7),
+            ],
+        ];
+        let candles = parse_binance_rows(&rows).unwrap();
+        assert_eq!(candles[0].timestamp.timestamp(), 1_700_000_000);
+        assert_eq!(candles[1].close, 102.0);
+        assert_eq!(candles[1].turnover, Some(255.0));
+
+        let bad = vec![vec![json!(1), json!(100.0)]];
+        assert!(parse_binance_rows(&bad)
+            .unwrap_err()
+            .to_string()
+            .contains("row length"));
+    }
+}
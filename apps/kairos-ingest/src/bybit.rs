@@ -0,0 +1,219 @@
+use super::binance::compact_symbol;
+use super::connector::{
+    decode_json, lookup_interval, run_ingest, send_with_retry, ExchangeConnector, IngestJob, Window,
+};
+use super::{parse_epoch_value, parse_f64, Candle, IngestError, InsertMode, Market};
+use chrono::{TimeZone, Utc};
+use kairos_domain::services::progress::ProgressSink;
+use kairos_domain::value_objects::timeframe::Timeframe;
+use reqwest::Client;
+use serde::Deserialize;
+
+const BYBIT_BASE: &str = "https://api.bybit.com";
+const BYBIT_KLINES_LIMIT: i64 = 1000;
+const BYBIT_INTERVALS: &[(&str, &str)] = &[
+    ("1min", "1"),
+    ("3min", "3"),
+    ("5min", "5"),
+    ("15min", "15"),
+    ("30min", "30"),
+    ("1hour", "60"),
+    ("2hour", "120"),
+    ("4hour", "240"),
+    ("6hour", "360"),
+    ("12hour", "720"),
+    ("1day", "D"),
+    ("1week", "W"),
+    ("1month", "M"),
+];
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BybitResponse {
+    ret_code: i64,
+    ret_msg: String,
+    result: Option<BybitKlines>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BybitKlines {
+    list: Vec<Vec<String>>,
+}
+
+// Bybit v5 klines (`/v5/market/kline`): category `spot`, or `linear` (USDT perpetuals) for futures.
+pub(crate) struct BybitConnector {
+    market: Market,
+    base_url: String,
+}
+
+impl BybitConnector {
+    pub fn new(market: Market, base_url: Option<&str>) -> Self {
+        Self {
+            market,
+            base_url: base_url
+                .unwrap_or(BYBIT_BASE)
+                .trim_end_matches('/')
+                .to_string(),
+        }
+    }
+}
+
+impl ExchangeConnector for BybitConnector {
+    fn name(&self) -> &'static str {
+        match self.market {
+            Market::Spot => "bybit spot",
+            Market::Futures => "bybit futures",
+        }
+    }
+
+    fn max_candles(&self) -> i64 {
+        BYBIT_KLINES_LIMIT
+    }
+
+    fn api_symbol(&self, symbol: &str) -> String {
+        compact_symbol(symbol)
+    }
+
+    fn interval(&self, timeframe: &Timeframe) -> Result<String, IngestError> {
+        lookup_interval("Bybit", timeframe, BYBIT_INTERVALS)
+    }
+
+    async fn fetch_candles(
+        &mut self,
+        client: &Client,
+        window: &Window<'_>,
+        progress: &mut dyn ProgressSink,
+    ) -> Result<Vec<Candle>, IngestError> {
+        let name = self.name();
+        let url = format!("{}/v5/market/kline", self.base_url);
+        let category = match self.market {
+            Market::Spot => "spot",
+            Market::Futures => "linear",
+        };
+        let start = (window.start * 1000).to_string();
+        let end = (window.end * 1000).to_string();
+        let limit = BYBIT_KLINES_LIMIT.to_string();
+        let response = send_with_retry(
+            name,
+            || {
+                client.get(&url).query(&[
+                    ("category", category),
+                    ("symbol", window.symbol),
+                    ("interval", window.interval),
+                    ("start", &start),
+                    ("end", &end),
+                    ("limit", &limit),
+                ])
+            },
+            progress,
+        )
+        .await?;
+
+        // Bybit reports bad symbols and its own rate limit (10006) in `retCode` with HTTP 200.
+        let payload: BybitResponse = decode_json(name, response).await?;
+        if payload.ret_code != 0 {
+            return Err(IngestError::Api {
+                market: name,
+                code: format!("{} {}", payload.ret_code, payload.ret_msg),
+            });
+        }
+        parse_bybit_rows(&payload.result.map(|r| r.list).unwrap_or_default())
+    }
+}
+
+// Pulls Bybit klines (spot, or USDT perpetuals with `Market::Futures`) into `ohlcv_candles`; like
+// Binance the symbol is stored as given and requested without separators.
+#[allow(clippy::too_many_arguments)]
+pub async fn ingest_bybit(
+    db_url: &str,
+    symbol: &str,
+    market: Market,
+    timeframe: &str,
+    start: &str,
+    end: Option<&str>,
+    exchange: &str,
+    source: &str,
+    sleep_ms: u64,
+    batch_size: usize,
+    insert_mode: InsertMode,
+    base_url: Option<&str>,
+    resume: bool,
+    progress: &mut dyn ProgressSink,
+) -> Result<(), IngestError> {
+    let mut connector = BybitConnector::new(market.clone(), base_url);
+    let job = IngestJob {
+        db_url,
+        symbol,
+        market,
+        timeframe,
+        start,
+        end,
+        exchange,
+        source,
+        sleep_ms,
+        batch_size,
+        insert_mode,
+        resume,
+        limiter: None,
+    };
+    run_ingest(&mut connector, job, progress).await
+}
+
+// Rows are `["start_ms", "open", "high", "low", "close", "volume", "turnover"]`, newest first.
+fn parse_bybit_rows(rows: &[Vec<String>]) -> Result<Vec<Candle>, IngestError> {
+    let mut candles = Vec::with_capacity(rows.len());
+    for row in rows {
+        if row.len() < 7 {
+            return Err("unexpected kline row length".into());
+        }
+        let timestamp = Utc
+            .timestamp_opt(parse_epoch_value(&row[0])?, 0)
+            .single()
+            .ok_or("invalid timestamp")?;
+        candles.push(Candle {
+            timestamp,
+            open: parse_f64(&row[1], "open")?,
+            high: parse_f64(&row[2], "high")?,
+            low: parse_f64(&row[3], "low")?,
+            close: parse_f64(&row[4], "close")?,
+            volume: parse_f64(&row[5], "volume")?,
+            turnover: Some(parse_f64(&row[6], "turnover")?),
+        });
+    }
+    candles.sort_by_key(|c| c.timestamp);
+    Ok(candles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_bybit_rows, BybitConnector};
+    use crate::connector::ExchangeConnector;
+    use crate::Market;
+    use kairos_domain::value_objects::timeframe::Timeframe;
+
+    #[test]
+    fn bybit_klines_map_to_candles() {
+        let connector = BybitConnector {
+            market: Market::Futures,
+            base_url: String::new(),
+        };
+        assert_eq!(connector.name(), "bybit futures");
+        assert_eq!(connector.api_symbol("BTC/USDT"), "BTCUSDT");
+        let interval = |raw| connector.interval(&Timeframe::parse(raw).unwrap());
+        assert_eq!(interval("1h").unwrap(), "60");
+        assert_eq!(interval("1d").unwrap(), "D");
+        assert!(interval("8h").is_err());
+
+        let row = |ts: &str, close: &str| {
+            [ts, "100", "105", "99", close, "2", "200"]
+                .map(String::from)
+                .to_vec()
+        };
+        let candles =
+            parse_bybit_rows(&[row("1700000060000", "104"), row("1700000000000", "101")]).unwrap();
+        assert_eq!(candles[0].timestamp.timestamp(), 1_700_000_000);
+        assert_eq!(candles[1].close, 104.0);
+        assert_eq!(candles[1].turnover, Some(200.0));
+        assert!(parse_bybit_rows(&[vec!["1".to_string()]]).is_err());
+    }
+}
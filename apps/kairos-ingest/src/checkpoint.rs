@@ -0,0 +1,87 @@
+use super::IngestError;
+use chrono::{DateTime, TimeZone, Utc};
+use tokio_postgres::Client as PgClient;
+
+// Also applied by every ingest, so databases migrated before the table existed keep ingesting.
+const CHECKPOINTS_DDL: &str =
+    include_str!("../../../platform/ops/migrations/0003_create_ingest_checkpoints.sql");
+
+// The `ohlcv_candles` series an ingest writes, as keyed in `ingest_checkpoints`.
+pub(crate) struct SeriesKey<'a> {
+    pub exchange: &'a str,
+    pub market: &'a str,
+    pub symbol: &'a str,
+    pub timeframe: &'a str,
+}
+
+pub(crate) async fn ensure_table(client: &PgClient) -> Result<(), IngestError> {
+    client
+        .batch_execute(CHECKPOINTS_DDL)
+        .await
+        .map_err(IngestError::db("failed to ensure ingest_checkpoints"))
+}
+
+// The end (epoch seconds) of the last window upserted for the series, if any.
+pub(crate) async fn load(
+    client: &PgClient,
+    key: &SeriesKey<'_>,
+) -> Result<Option<i64>, IngestError> {
+    let row = client
+        .query_opt(
+            "SELECT last_window_end FROM ingest_checkpoints
+             WHERE exchange = $1 AND market = $2 AND symbol = $3 AND timeframe = $4",
+            &[&key.exchange, &key.market, &key.symbol, &key.timeframe],
+        )
+        .await
+        .map_err(IngestError::db("failed to read ingest checkpoint"))?;
+    Ok(row.map(|row| row.get::<_, DateTime<Utc>>(0).timestamp()))
+}
+
+// Written after the window's candles are committed: a crash in between only repeats that window,
+// which the upsert makes harmless.
+pub(crate) async fn store(
+    client: &PgClient,
+    key: &SeriesKey<'_>,
+    window_end: i64,
+) -> Result<(), IngestError> {
+    let window_end = Utc
+        .timestamp_opt(window_end, 0)
+        .single()
+        .ok_or("invalid checkpoint timestamp")?;
+    client
+        .execute(
+            "INSERT INTO ingest_checkpoints (exchange, market, symbol, timeframe, last_window_end)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (exchange, market, symbol, timeframe)
+             DO UPDATE SET last_window_end = EXCLUDED.last_window_end, updated_at = NOW()",
+            &[
+                &key.exchange,
+                &key.market,
+                &key.symbol,
+                &key.timeframe,
+                &window_end,
+            ],
+        )
+        .await
+        .map_err(IngestError::db("failed to write ingest checkpoint"))?;
+    Ok(())
+}
+
+// Where a resumed ingest starts: the bar after the checkpoint, unless `start` is already past it.
+pub(crate) fn resume_start(start: i64, checkpoint: Option<i64>, step_seconds: i64) -> i64 {
+    checkpoint.map_or(start, |last| start.max(last + step_seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::resume_start;
+
+    #[test]
+    fn resume_continues_after_the_checkpoint() {
+        let start = 1_700_000_000;
+        assert_eq!(resume_start(start, None, 60), start);
+        assert_eq!(resume_start(start, Some(start + 3_000), 60), start + 3_060);
+        // A checkpoint from an older range does not move the start back.
+        assert_eq!(resume_start(start, Some(start - 600), 60), start);
+    }
+}
@@ -0,0 +1,360 @@
+use super::checkpoint::{self, SeriesKey};
+use super::multi::RateLimiter;
+use super::{parse_time_input, upsert_candles, Candle, IngestError, InsertMode, Market};
+use chrono::Utc;
+use kairos_domain::services::progress::{ProgressEvent, ProgressSink};
+use kairos_domain::value_objects::timeframe::Timeframe;
+use reqwest::{Client, RequestBuilder, Response};
+use std::time::Duration;
+use tokio_postgres::{Client as PgClient, NoTls};
+
+// Retries of a rate-limited (HTTP 429) request before its status becomes an error.
+const MAX_RATE_LIMIT_RETRIES: u32 = 5;
+
+// One request's worth of candles: `symbol` and `interval` in the exchange's own naming, `start` and
+// `end` (inclusive) in epoch seconds.
+pub(crate) struct Window<'a> {
+    pub symbol: &'a str,
+    pub interval: &'a str,
+    pub start: i64,
+    pub end: i64,
+}
+
+// What an exchange has to provide for `run_ingest` to page through its OHLCV history: its request
+// limits, its timeframe and symbol naming, and how to fetch and decode one window.
+pub(crate) trait ExchangeConnector {
+    // The exchange (and market) in request errors, e.g. `binance` or `okx futures`.
+    fn name(&self) -> &'static str;
+
+    // Most candles one request returns; each window spans that many bars.
+    fn max_candles(&self) -> i64;
+
+    // How the exchange names `symbol`; the stored symbol stays as given.
+    fn api_symbol(&self, symbol: &str) -> String {
+        symbol.to_string()
+    }
+
+    fn parse_timeframe(&self, raw: &str) -> Result<Timeframe, IngestError> {
+        Ok(Timeframe::parse(raw)?)
+    }
+
+    // The exchange's interval code for `timeframe`; timeframes it has no klines for are an error.
+    fn interval(&self, timeframe: &Timeframe) -> Result<String, IngestError>;
+
+    // The candles opened within the window, oldest first.
+    async fn fetch_candles(
+        &mut self,
+        client: &Client,
+        window: &Window<'_>,
+        progress: &mut dyn ProgressSink,
+    ) -> Result<Vec<Candle>, IngestError>;
+
+    // The pause before the next window; exchanges that report their rate-limit usage stretch it.
+    fn pause(&self, sleep: Duration) -> Duration {
+        sleep
+    }
+}
+
+// The arguments every `ingest_*` entry point shares.
+pub(crate) struct IngestJob<'a> {
+    pub db_url: &'a str,
+    pub symbol: &'a str,
+    pub market: Market,
+    pub timeframe: &'a str,
+    pub start: &'a str,
+    pub end: Option<&'a str>,
+    pub exchange: &'a str,
+    pub source: &'a str,
+    pub sleep_ms: u64,
+    pub batch_size: usize,
+    pub insert_mode: InsertMode,
+    // Start after the series' checkpoint instead of at `start` when it is further along.
+    pub resume: bool,
+    // Shared by the jobs of a multi-symbol ingest; paces every request instead of `sleep_ms`
+    // between this job's own windows.
+    pub limiter: Option<&'a RateLimiter>,
+}
+
+// Walks [start, end] window by window, upserting each window's candles into `ohlcv_candles`,
+// checkpointing the window in `ingest_checkpoints` and reporting `RowsWritten` after it.
+pub(crate) async fn run_ingest(
+    connector: &mut impl ExchangeConnector,
+    job: IngestJob<'_>,
+    progress: &mut dyn ProgressSink,
+) -> Result<(), IngestError> {
+    let target = Target::resolve(connector, &job)?;
+    let start_seconds = parse_time_input(job.start)?.timestamp();
+    let end_seconds = match job.end {
+        Some(end) => parse_time_input(end)?,
+        None => Utc::now(),
+    }
+    .timestamp();
+    let (http_client, mut client) = connect(job.db_url).await?;
+
+    let key = SeriesKey {
+        exchange: job.exchange,
+        market: job.market.as_str(),
+        symbol: job.symbol,
+        timeframe: &target.timeframe.label,
+    };
+    checkpoint::ensure_table(&client).await?;
+    let mut window_start = start_seconds;
+    if job.resume {
+        let last = checkpoint::load(&client, &key).await?;
+        window_start = checkpoint::resume_start(start_seconds, last, target.timeframe.step_seconds);
+        if window_start > start_seconds {
+            eprintln!(
+                "ingest resume: exchange={} symbol={} market={:?} timeframe={} from={}",
+                job.exchange, job.symbol, job.market, target.timeframe.label, window_start
+            );
+        }
+    }
+
+    let mut total = 0u64;
+    ingest_range(
+        connector,
+        &http_client,
+        &mut client,
+        &job,
+        &target,
+        (window_start, end_seconds),
+        Some(&key),
+        &mut total,
+        progress,
+    )
+    .await?;
+
+    eprintln!(
+        "ingest complete: exchange={} symbol={} market={:?} timeframe={} total_upserts={}",
+        job.exchange, job.symbol, job.market, target.timeframe.label, total
+    );
+    Ok(())
+}
+
+// Re-fetches only the given [first, last] ranges (missing bars found by `gaps`), without touching
+// the series' checkpoint. Returns the rows upserted.
+pub(crate) async fn run_backfill(
+    connector: &mut impl ExchangeConnector,
+    job: IngestJob<'_>,
+    ranges: &[(i64, i64)],
+    progress: &mut dyn ProgressSink,
+) -> Result<u64, IngestError> {
+    let target = Target::resolve(connector, &job)?;
+    let (http_client, mut client) = connect(job.db_url).await?;
+    let mut total = 0u64;
+    for (idx, &range) in ranges.iter().enumerate() {
+        if idx > 0 {
+            tokio::time::sleep(connector.pause(Duration::from_millis(job.sleep_ms))).await;
+        }
+        ingest_range(
+            connector,
+            &http_client,
+            &mut client,
+            &job,
+            &target,
+            range,
+            None,
+            &mut total,
+            progress,
+        )
+        .await?;
+    }
+    eprintln!(
+        "backfill complete: exchange={} symbol={} market={:?} timeframe={} ranges={} total_upserts={}",
+        job.exchange,
+        job.symbol,
+        job.market,
+        target.timeframe.label,
+        ranges.len(),
+        total
+    );
+    Ok(total)
+}
+
+// The job's timeframe, interval and symbol in the connector's naming.
+struct Target {
+    timeframe: Timeframe,
+    interval: String,
+    api_symbol: String,
+}
+
+impl Target {
+    fn resolve(
+        connector: &impl ExchangeConnector,
+        job: &IngestJob<'_>,
+    ) -> Result<Self, IngestError> {
+        let timeframe = connector.parse_timeframe(job.timeframe)?;
+        Ok(Self {
+            interval: connector.interval(&timeframe)?,
+            api_symbol: connector.api_symbol(job.symbol),
+            timeframe,
+        })
+    }
+}
+
+async fn connect(db_url: &str) -> Result<(Client, PgClient), IngestError> {
+    let http_client = Client::builder()
+        .timeout(Duration::from_secs(30))
+        .pool_idle_timeout(Duration::from_secs(90))
+        .build()
+        .map_err(IngestError::HttpClient)?;
+
+    let (client, connection) = tokio_postgres::connect(db_url, NoTls)
+        .await
+        .map_err(IngestError::Connect)?;
+    tokio::spawn(async move {
+        if let Err(err) = connection.await {
+            eprintln!("postgres connection error: {err}");
+        }
+    });
+    Ok((http_client, client))
+}
+
+// The window loop over [start, end] (epoch seconds, inclusive), adding the upserted rows to `total`.
+#[allow(clippy::too_many_arguments)]
+async fn ingest_range(
+    connector: &mut impl ExchangeConnector,
+    http_client: &Client,
+    client: &mut PgClient,
+    job: &IngestJob<'_>,
+    target: &Target,
+    (start, end): (i64, i64),
+    checkpoint: Option<&SeriesKey<'_>>,
+    total: &mut u64,
+    progress: &mut dyn ProgressSink,
+) -> Result<(), IngestError> {
+    let step = target.timeframe.step_seconds;
+    let window_span = step * (connector.max_candles() - 1);
+    let mut window_start = start;
+    while window_start <= end {
+        let window_end = (window_start + window_span).min(end);
+        let window = Window {
+            symbol: &target.api_symbol,
+            interval: &target.interval,
+            start: window_start,
+            end: window_end,
+        };
+        if let Some(limiter) = job.limiter {
+            limiter
+                .acquire(connector.pause(Duration::from_millis(job.sleep_ms)))
+                .await;
+        }
+        let candles = connector
+            .fetch_candles(http_client, &window, progress)
+            .await?;
+
+        if !candles.is_empty() {
+            *total += upsert_candles(
+                client,
+                job.exchange,
+                &job.market,
+                job.symbol,
+                &target.timeframe.label,
+                job.source,
+                &candles,
+                job.batch_size,
+                job.insert_mode,
+            )
+            .await?;
+        }
+        if let Some(key) = checkpoint {
+            checkpoint::store(client, key, window_end).await?;
+        }
+
+        progress.emit(ProgressEvent::RowsWritten {
+            first_ts: window_start,
+            last_ts: window_end,
+            rows: candles.len() as u64,
+            total_rows: *total,
+        });
+
+        if window_end >= end {
+            break;
+        }
+        window_start = window_end + step;
+        if job.limiter.is_none() {
+            tokio::time::sleep(connector.pause(Duration::from_millis(job.sleep_ms))).await;
+        }
+    }
+    Ok(())
+}
+
+// Sends the request built by `request`, backing off on HTTP 429 (the `Retry-After` header when the
+// exchange sends one, else 500ms more per attempt). Any other status is returned to the connector.
+pub(crate) async fn send_with_retry(
+    name: &'static str,
+    request: impl Fn() -> RequestBuilder,
+    progress: &mut dyn ProgressSink,
+) -> Result<Response, IngestError> {
+    let mut attempts = 0u32;
+    loop {
+        attempts += 1;
+        let response = request()
+            .send()
+            .await
+            .map_err(|source| IngestError::Request {
+                market: name,
+                source,
+            })?;
+        if response.status().as_u16() == 429 && attempts <= MAX_RATE_LIMIT_RETRIES {
+            let backoff = retry_after_ms(&response).unwrap_or(500 * attempts as u64);
+            progress.emit(ProgressEvent::RequestRetried {
+                attempt: attempts,
+                delay_ms: backoff,
+                reason: "rate limited (HTTP 429)",
+            });
+            tokio::time::sleep(Duration::from_millis(backoff)).await;
+            continue;
+        }
+        return Ok(response);
+    }
+}
+
+fn retry_after_ms(response: &Response) -> Option<u64> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(|seconds| seconds * 1000)
+}
+
+// The decoded body of a successful response, or its status as an error.
+pub(crate) async fn decode_json<T: serde::de::DeserializeOwned>(
+    name: &'static str,
+    response: Response,
+) -> Result<T, IngestError> {
+    let status = response.status();
+    if !status.is_success() {
+        return Err(IngestError::Status {
+            market: name,
+            status,
+        });
+    }
+    response.json().await.map_err(|source| IngestError::Decode {
+        market: name,
+        source,
+    })
+}
+
+// Maps a canonical timeframe label to the exchange's interval code through `table`.
+pub(crate) fn lookup_interval(
+    exchange: &str,
+    timeframe: &Timeframe,
+    table: &[(&str, &str)],
+) -> Result<String, IngestError> {
+    table
+        .iter()
+        .find(|(label, _)| *label == timeframe.label)
+        .map(|(_, code)| code.to_string())
+        .ok_or_else(|| {
+            format!(
+                "timeframe {} has no {exchange} kline interval",
+                timeframe.label
+            )
+            .into()
+        })
+}
@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+// Failures of migrations and exchange ingestion. The underlying Postgres/HTTP/IO error is kept as
+// the source; the message is what the CLI prints.
+#[derive(Debug, thiserror::Error)]
+pub enum IngestError {
+    #[error("failed to connect to postgres: {0}")]
+    Connect(#[source] tokio_postgres::Error),
+    #[error("{context}: {source}")]
+    Database {
+        context: String,
+        #[source]
+        source: tokio_postgres::Error,
+    },
+    #[error("{context}: {source}")]
+    Io {
+        context: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("migrations path does not exist: {}", .0.display())]
+    MissingMigrations(PathBuf),
+    #[error("failed to build http client: {0}")]
+    HttpClient(#[source] reqwest::Error),
+    #[error("{market} request failed: {source}")]
+    Request {
+        market: &'static str,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("{market} request failed with status {status}")]
+    Status {
+        market: &'static str,
+        status: reqwest::StatusCode,
+    },
+    #[error("{market} response parse failed: {source}")]
+    Decode {
+        market: &'static str,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("{market} response error code: {code}")]
+    Api { market: &'static str, code: String },
+    // Bad arguments or exchange rows that cannot be read as candles.
+    #[error("{0}")]
+    InvalidInput(String),
+}
+
+impl IngestError {
+    pub(crate) fn db(context: impl Into<String>) -> impl FnOnce(tokio_postgres::Error) -> Self {
+        let context = context.into();
+        move |source| IngestError::Database { context, source }
+    }
+
+    pub(crate) fn io(context: impl Into<String>) -> impl FnOnce(std::io::Error) -> Self {
+        let context = context.into();
+        move |source| IngestError::Io { context, source }
+    }
+}
+
+impl From<String> for IngestError {
+    fn from(message: String) -> Self {
+        IngestError::InvalidInput(message)
+    }
+}
+
+impl From<&str> for IngestError {
+    fn from(message: &str) -> Self {
+        IngestError::InvalidInput(message.to_string())
+    }
+}
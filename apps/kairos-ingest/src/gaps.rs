@@ -0,0 +1,260 @@
+use super::binance::BinanceConnector;
+use super::bybit::BybitConnector;
+use super::connector::{run_backfill, IngestJob};
+use super::kucoin::KucoinConnector;
+use super::okx::OkxConnector;
+use super::{parse_time_input, IngestError, InsertMode, Market};
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use kairos_domain::services::progress::ProgressSink;
+use kairos_domain::value_objects::timeframe::Timeframe;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::NoTls;
+
+// The exchange API a backfill re-fetches from; `ohlcv_candles.exchange` is free text, so it is only
+// inferred when the stored name is one of these.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Venue {
+    Kucoin,
+    Binance,
+    Bybit,
+    Okx,
+}
+
+impl Venue {
+    pub fn from_exchange(exchange: &str) -> Option<Self> {
+        Self::from_str(exchange.trim(), true).ok()
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Venue::Kucoin => "kucoin",
+            Venue::Binance => "binance",
+            Venue::Bybit => "bybit",
+            Venue::Okx => "okx",
+        }
+    }
+}
+
+// A run of missing bars: the first and last missing open times (epoch seconds) and how many bars
+// that is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gap {
+    pub first_missing: i64,
+    pub last_missing: i64,
+    pub missing_bars: i64,
+}
+
+// Scans one `ohlcv_candles` series for missing bars, oldest first. Without `start`/`end` only the
+// holes between the first and last stored bar count; with them, missing bars at either edge too.
+pub async fn find_gaps(
+    db_url: &str,
+    exchange: &str,
+    market: &Market,
+    symbol: &str,
+    timeframe: &str,
+    start: Option<&str>,
+    end: Option<&str>,
+) -> Result<Vec<Gap>, IngestError> {
+    let timeframe = Timeframe::parse_or_seconds(timeframe)?;
+    let start = start.map(parse_time_input).transpose()?;
+    let end = end.map(parse_time_input).transpose()?;
+    let (client, connection) = tokio_postgres::connect(db_url, NoTls)
+        .await
+        .map_err(IngestError::Connect)?;
+    tokio::spawn(async move {
+        if let Err(err) = connection.await {
+            eprintln!("postgres connection error: {err}");
+        }
+    });
+
+    let market_value = market.as_str();
+    let step = timeframe.step_seconds as f64;
+    let params: [&(dyn ToSql + Sync); 7] = [
+        &exchange,
+        &market_value,
+        &symbol,
+        &timeframe.label,
+        &start,
+        &end,
+        &step,
+    ];
+    let bounds = client
+        .query_one(
+            "SELECT MIN(timestamp_utc), MAX(timestamp_utc) FROM ohlcv_candles
+             WHERE exchange = $1 AND market = $2 AND symbol = $3 AND timeframe = $4
+               AND ($5::timestamptz IS NULL OR timestamp_utc >= $5)
+               AND ($6::timestamptz IS NULL OR timestamp_utc <= $6)",
+            &params[..6],
+        )
+        .await
+        .map_err(IngestError::db("failed to read the series bounds"))?;
+    let first: Option<DateTime<Utc>> = bounds.get(0);
+    let last: Option<DateTime<Utc>> = bounds.get(1);
+    let stored = first
+        .zip(last)
+        .map(|(first, last)| (first.timestamp(), last.timestamp()));
+
+    // Consecutive stored bars further apart than one step, found in the database.
+    let rows = client
+        .query(
+            "SELECT prev_ts, timestamp_utc FROM (
+                 SELECT timestamp_utc,
+                        LAG(timestamp_utc) OVER (ORDER BY timestamp_utc) AS prev_ts
+                 FROM ohlcv_candles
+                 WHERE exchange = $1 AND market = $2 AND symbol = $3 AND timeframe = $4
+                   AND ($5::timestamptz IS NULL OR timestamp_utc >= $5)
+                   AND ($6::timestamptz IS NULL OR timestamp_utc <= $6)
+             ) bars
+             WHERE timestamp_utc - prev_ts > make_interval(secs => $7)
+             ORDER BY timestamp_utc",
+            &params,
+        )
+        .await
+        .map_err(IngestError::db("failed to scan ohlcv_candles for gaps"))?;
+    let holes: Vec<(i64, i64)> = rows
+        .iter()
+        .map(|row| {
+            let prev: DateTime<Utc> = row.get(0);
+            let next: DateTime<Utc> = row.get(1);
+            (prev.timestamp(), next.timestamp())
+        })
+        .collect();
+
+    let range = (start.map(|ts| ts.timestamp()), end.map(|ts| ts.timestamp()));
+    Ok(collect_gaps(range, stored, &holes, timeframe.step_seconds))
+}
+
+// Fetches just the bars of `gaps` from `venue` and upserts them like an ingest; returns the rows
+// written. Bars the exchange has no candle for stay missing.
+#[allow(clippy::too_many_arguments)]
+pub async fn backfill_gaps(
+    db_url: &str,
+    venue: Venue,
+    symbol: &str,
+    market: Market,
+    timeframe: &str,
+    gaps: &[Gap],
+    exchange: &str,
+    source: &str,
+    sleep_ms: u64,
+    batch_size: usize,
+    insert_mode: InsertMode,
+    base_url: Option<&str>,
+    progress: &mut dyn ProgressSink,
+) -> Result<u64, IngestError> {
+    let ranges: Vec<(i64, i64)> = gaps
+        .iter()
+        .map(|gap| (gap.first_missing, gap.last_missing))
+        .collect();
+    let job = IngestJob {
+        db_url,
+        symbol,
+        market: market.clone(),
+        timeframe,
+        start: "",
+        end: None,
+        exchange,
+        source,
+        sleep_ms,
+        batch_size,
+        insert_mode,
+        resume: false,
+        limiter: None,
+    };
+    match venue {
+        Venue::Kucoin => {
+            let mut connector = KucoinConnector::new(market, base_url);
+            run_backfill(&mut connector, job, &ranges, progress).await
+        }
+        Venue::Binance => {
+            if matches!(market, Market::Futures) {
+                return Err("binance backfill only covers spot klines".into());
+            }
+            let mut connector = BinanceConnector::new(base_url);
+            run_backfill(&mut connector, job, &ranges, progress).await
+        }
+        Venue::Bybit => {
+            let mut connector = BybitConnector::new(market, base_url);
+            run_backfill(&mut connector, job, &ranges, progress).await
+        }
+        Venue::Okx => {
+            let mut connector = OkxConnector::new(market, base_url);
+            run_backfill(&mut connector, job, &ranges, progress).await
+        }
+    }
+}
+
+// Turns the (prev, next) pairs around each hole, plus the edges of the requested range that lie
+// before the first or after the last stored bar, into gaps. An empty series with a full range is
+// one gap covering it.
+fn collect_gaps(
+    (start, end): (Option<i64>, Option<i64>),
+    stored: Option<(i64, i64)>,
+    holes: &[(i64, i64)],
+    step: i64,
+) -> Vec<Gap> {
+    let gap = |first: i64, last: i64| Gap {
+        first_missing: first,
+        last_missing: last,
+        missing_bars: (last - first) / step + 1,
+    };
+    // The requested edges, aligned to the bar grid the stored bars sit on.
+    let align_up = |ts: i64| ts + (step - ts.rem_euclid(step)) % step;
+    let align_down = |ts: i64| ts - ts.rem_euclid(step);
+
+    let Some((first, last)) = stored else {
+        return match (start, end) {
+            (Some(start), Some(end)) if align_up(start) <= align_down(end) => {
+                vec![gap(align_up(start), align_down(end))]
+            }
+            _ => Vec::new(),
+        };
+    };
+    let mut gaps = Vec::with_capacity(holes.len() + 2);
+    if let Some(start) = start.map(align_up).filter(|&start| start < first) {
+        gaps.push(gap(start, first - step));
+    }
+    gaps.extend(
+        holes
+            .iter()
+            .map(|&(prev, next)| gap(prev + step, next - step)),
+    );
+    if let Some(end) = end.map(align_down).filter(|&end| end > last) {
+        gaps.push(gap(last + step, end));
+    }
+    gaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collect_gaps, Gap, Venue};
+
+    #[test]
+    fn gaps_cover_holes_and_the_requested_edges() {
+        let gap = |first_missing, last_missing, missing_bars| Gap {
+            first_missing,
+            last_missing,
+            missing_bars,
+        };
+        // Bars at 600..=1200 every 60s, missing 780..=840 and 1020.
+        let holes = [(720, 900), (960, 1080)];
+        assert_eq!(
+            collect_gaps((None, None), Some((600, 1200)), &holes, 60),
+            vec![gap(780, 840, 2), gap(1020, 1020, 1)]
+        );
+        // A range wider than the stored bars adds the missing edges, aligned to the bar grid.
+        assert_eq!(
+            collect_gaps((Some(450), Some(1330)), Some((600, 1200)), &[], 60),
+            vec![gap(480, 540, 2), gap(1260, 1320, 2)]
+        );
+        assert_eq!(
+            collect_gaps((Some(0), Some(119)), None, &[], 60),
+            vec![gap(0, 60, 2)]
+        );
+        assert!(collect_gaps((None, None), None, &[], 60).is_empty());
+
+        assert_eq!(Venue::from_exchange("KuCoin"), Some(Venue::Kucoin));
+        assert_eq!(Venue::from_exchange("mock"), None);
+    }
+}
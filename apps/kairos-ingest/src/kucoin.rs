@@ -0,0 +1,190 @@
+use super::connector::{decode_json, send_with_retry, ExchangeConnector, Window};
+use super::{parse_epoch_value, parse_f64, Candle, IngestError, Market};
+use chrono::{TimeZone, Utc};
+use kairos_domain::services::progress::ProgressSink;
+use kairos_domain::value_objects::timeframe::Timeframe;
+use reqwest::Client;
+use serde::Deserialize;
+
+const KUCOIN_SPOT_BASE: &str = "https://api.kucoin.com";
+const KUCOIN_FUTURES_BASE: &str = "https://api-futures.kucoin.com";
+const KUCOIN_SPOT_LIMIT: i64 = 1500;
+const KUCOIN_FUTURES_LIMIT: i64 = 500;
+
+#[derive(Debug, Deserialize)]
+struct KucoinResponse {
+    code: String,
+    data: Vec<Vec<String>>,
+}
+
+// KuCoin spot (`/api/v1/market/candles`, timeframe labels as interval types) or futures
+// (`/api/v1/kline/query`, granularity in seconds).
+pub(crate) struct KucoinConnector {
+    market: Market,
+    base_url: String,
+}
+
+impl KucoinConnector {
+    pub fn new(market: Market, base_url: Option<&str>) -> Self {
+        let default_base = match market {
+            Market::Spot => KUCOIN_SPOT_BASE,
+            Market::Futures => KUCOIN_FUTURES_BASE,
+        };
+        Self {
+            market,
+            base_url: base_url
+                .unwrap_or(default_base)
+                .trim_end_matches('/')
+                .to_string(),
+        }
+    }
+}
+
+impl ExchangeConnector for KucoinConnector {
+    fn name(&self) -> &'static str {
+        match self.market {
+            Market::Spot => "spot",
+            Market::Futures => "futures",
+        }
+    }
+
+    fn max_candles(&self) -> i64 {
+        match self.market {
+            Market::Spot => KUCOIN_SPOT_LIMIT,
+            Market::Futures => KUCOIN_FUTURES_LIMIT,
+        }
+    }
+
+    // Futures also take a granularity in seconds (`60`, `900`).
+    fn parse_timeframe(&self, raw: &str) -> Result<Timeframe, IngestError> {
+        Ok(match self.market {
+            Market::Spot => Timeframe::parse(raw)?,
+            Market::Futures => Timeframe::parse_or_seconds(raw)?,
+        })
+    }
+
+    fn interval(&self, timeframe: &Timeframe) -> Result<String, IngestError> {
+        Ok(match self.market {
+            Market::Spot => timeframe.label.clone(),
+            Market::Futures => timeframe.step_seconds.to_string(),
+        })
+    }
+
+    async fn fetch_candles(
+        &mut self,
+        client: &Client,
+        window: &Window<'_>,
+        progress: &mut dyn ProgressSink,
+    ) -> Result<Vec<Candle>, IngestError> {
+        let name = self.name();
+        let response = match self.market {
+            Market::Spot => {
+                let url = format!("{}/api/v1/market/candles", self.base_url);
+                let (start, end) = (window.start.to_string(), window.end.to_string());
+                send_with_retry(
+                    name,
+                    || {
+                        client.get(&url).query(&[
+                            ("symbol", window.symbol),
+                            ("type", window.interval),
+                            ("startAt", &start),
+                            ("endAt", &end),
+                        ])
+                    },
+                    progress,
+                )
+                .await?
+            }
+            Market::Futures => {
+                let url = format!("{}/api/v1/kline/query", self.base_url);
+                let (from, to) = (
+                    (window.start * 1000).to_string(),
+                    (window.end * 1000).to_string(),
+                );
+                send_with_retry(
+                    name,
+                    || {
+                        client.get(&url).query(&[
+                            ("symbol", window.symbol),
+                            ("granularity", window.interval),
+                            ("from", &from),
+                            ("to", &to),
+                        ])
+                    },
+                    progress,
+                )
+                .await?
+            }
+        };
+
+        let payload: KucoinResponse = decode_json(name, response).await?;
+        if payload.code != "200000" {
+            return Err(IngestError::Api {
+                market: name,
+                code: payload.code,
+            });
+        }
+        parse_kucoin_rows(&payload.data)
+    }
+}
+
+fn parse_kucoin_rows(rows: &[Vec<String>]) -> Result<Vec<Candle>, IngestError> {
+    let mut candles = Vec::with_capacity(rows.len());
+    for row in rows {
+        if row.len() < 6 {
+            return Err("unexpected candle row length".into());
+        }
+
+        let ts = parse_epoch_value(&row[0])?;
+        let timestamp = Utc
+            .timestamp_opt(ts, 0)
+            .single()
+            .ok_or("invalid timestamp")?;
+
+        let open = parse_f64(&row[1], "open")?;
+        let close = parse_f64(&row[2], "close")?;
+        let high = parse_f64(&row[3], "high")?;
+        let low = parse_f64(&row[4], "low")?;
+        let volume = parse_f64(&row[5], "volume")?;
+        let turnover = if row.len() > 6 {
+            Some(parse_f64(&row[6], "turnover")?)
+        } else {
+            None
+        };
+
+        candles.push(Candle {
+            timestamp,
+            open,
+            high,
+            low,
+            close,
+            volume,
+            turnover,
+        });
+    }
+
+    candles.sort_by_key(|c| c.timestamp);
+    Ok(candles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KucoinConnector;
+    use crate::connector::ExchangeConnector;
+    use crate::Market;
+
+    #[test]
+    fn kucoin_timeframes_map_for_spot_and_futures() {
+        let spot = KucoinConnector::new(Market::Spot, None);
+        let tf = spot.parse_timeframe("1m").unwrap();
+        assert_eq!(tf.label, "1min");
+        assert_eq!(spot.interval(&tf).unwrap(), "1min");
+        assert!(spot.parse_timeframe("60").is_err());
+
+        let futures = KucoinConnector::new(Market::Futures, Some("http://localhost:9/"));
+        let tf = futures.parse_timeframe("60").unwrap();
+        assert_eq!(tf.step_seconds, 60);
+        assert_eq!(futures.interval(&tf).unwrap(), "60");
+        assert_eq!(futures.max_candles(), 500);
+    }
+}
@@ -1,17 +1,35 @@
 use chrono::{DateTime, TimeZone, Utc};
 use clap::ValueEnum;
+use kairos_domain::services::progress::{ProgressEvent, ProgressSink};
+use kairos_domain::value_objects::bar::Bar;
 use kairos_domain::value_objects::timeframe::Timeframe;
-use reqwest::Client;
-use serde::Deserialize;
 use std::collections::HashSet;
 use std::path::Path;
-use std::time::Duration;
 use tokio_postgres::{Client as PgClient, NoTls};
 
-const KUCOIN_SPOT_BASE: &str = "https://api.kucoin.com";
-const KUCOIN_FUTURES_BASE: &str = "https://api-futures.kucoin.com";
-const KUCOIN_SPOT_LIMIT: i64 = 1500;
-const KUCOIN_FUTURES_LIMIT: i64 = 500;
+mod binance;
+mod bybit;
+mod checkpoint;
+mod connector;
+mod error;
+mod gaps;
+mod kucoin;
+mod multi;
+mod okx;
+mod upsert;
+
+pub use binance::ingest_binance;
+pub use bybit::ingest_bybit;
+pub use error::IngestError;
+pub use gaps::{backfill_gaps, find_gaps, Gap, Venue};
+pub use multi::{collect_symbols, ingest_kucoin_symbols, stderr_symbol_progress, SymbolOutcome};
+pub use okx::ingest_okx;
+pub use upsert::InsertMode;
+
+use connector::{run_ingest, IngestJob};
+use kucoin::KucoinConnector;
+use upsert::upsert_candles;
+
 const MIGRATION_LOCK_ID: i64 = 891_507_011;
 
 #[derive(ValueEnum, Clone, Debug)]
@@ -20,6 +38,16 @@ pub enum Market {
     Futures,
 }
 
+impl Market {
+    // The `market` column value.
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Market::Spot => "spot",
+            Market::Futures => "futures",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct Candle {
     timestamp: DateTime<Utc>,
@@ -31,23 +59,10 @@ struct Candle {
     turnover: Option<f64>,
 }
 
-#[derive(Debug, Clone)]
-struct TimeframeInfo {
-    api: String,
-    canonical: String,
-    seconds: i64,
-}
-
-#[derive(Debug, Deserialize)]
-struct KucoinResponse {
-    code: String,
-    data: Vec<Vec<String>>,
-}
-
-pub async fn migrate_db(db_url: &str, migrations_path: &Path) -> Result<(), String> {
+pub async fn migrate_db(db_url: &str, migrations_path: &Path) -> Result<(), IngestError> {
     let (mut client, connection) = tokio_postgres::connect(db_url, NoTls)
         .await
-        .map_err(|err| format!("failed to connect to postgres: {err}"))?;
+        .map_err(IngestError::Connect)?;
     tokio::spawn(async move {
         if let Err(err) = connection.await {
             eprintln!("postgres connection error: {err}");
@@ -57,37 +72,33 @@ pub async fn migrate_db(db_url: &str, migrations_path: &Path) -> Result<(), Stri
     acquire_migration_lock(&client).await?;
     let result = if migrations_path.is_dir() {
         migrate_dir(&mut client, migrations_path).await?;
-        println!("migrate complete: {}", migrations_path.display());
+        eprintln!("migrate complete: {}", migrations_path.display());
         Ok(())
     } else if !migrations_path.is_file() {
-        Err(format!(
-            "migrations path does not exist: {}",
-            migrations_path.display()
+        Err(IngestError::MissingMigrations(
+            migrations_path.to_path_buf(),
         ))
     } else {
-        let sql = std::fs::read_to_string(migrations_path).map_err(|err| {
-            format!(
-                "failed to read migrations file {}: {}",
-                migrations_path.display(),
-                err
-            )
-        })?;
+        let sql = std::fs::read_to_string(migrations_path).map_err(IngestError::io(format!(
+            "failed to read migrations file {}",
+            migrations_path.display()
+        )))?;
         client
             .batch_execute(&sql)
             .await
-            .map_err(|err| format!("failed to apply migrations: {err}"))?;
-        println!("migrate complete (legacy): {}", migrations_path.display());
+            .map_err(IngestError::db("failed to apply migrations"))?;
+        eprintln!("migrate complete (legacy): {}", migrations_path.display());
         Ok(())
     };
     release_migration_lock(&client).await;
     result
 }
 
-async fn acquire_migration_lock(client: &PgClient) -> Result<(), String> {
+async fn acquire_migration_lock(client: &PgClient) -> Result<(), IngestError> {
     client
         .execute("SELECT pg_advisory_lock($1)", &[&MIGRATION_LOCK_ID])
         .await
-        .map_err(|err| format!("failed to acquire migration lock: {err}"))?;
+        .map_err(IngestError::db("failed to acquire migration lock"))?;
     Ok(())
 }
 
@@ -97,7 +108,7 @@ async fn release_migration_lock(client: &PgClient) {
         .await;
 }
 
-async fn migrate_dir(client: &mut PgClient, migrations_dir: &Path) -> Result<(), String> {
+async fn migrate_dir(client: &mut PgClient, migrations_dir: &Path) -> Result<(), IngestError> {
     client
         .batch_execute(
             "CREATE TABLE IF NOT EXISTS schema_migrations (\
@@ -106,12 +117,12 @@ async fn migrate_dir(client: &mut PgClient, migrations_dir: &Path) -> Result<(),
              );",
         )
         .await
-        .map_err(|err| format!("failed to ensure schema_migrations: {err}"))?;
+        .map_err(IngestError::db("failed to ensure schema_migrations"))?;
 
     let rows = client
         .query("SELECT version FROM schema_migrations", &[])
         .await
-        .map_err(|err| format!("failed to query schema_migrations: {err}"))?;
+        .map_err(IngestError::db("failed to query schema_migrations"))?;
     let mut applied: HashSet<String> = HashSet::with_capacity(rows.len());
     for row in rows {
         let version: String = row.get(0);
@@ -119,13 +130,10 @@ async fn migrate_dir(client: &mut PgClient, migrations_dir: &Path) -> Result<(),
     }
 
     let mut entries: Vec<_> = std::fs::read_dir(migrations_dir)
-        .map_err(|err| {
-            format!(
-                "failed to list migrations dir {}: {}",
-                migrations_dir.display(),
-                err
-            )
-        })?
+        .map_err(IngestError::io(format!(
+            "failed to list migrations dir {}",
+            migrations_dir.display()
+        )))?
         .filter_map(|e| e.ok())
         .collect();
     entries.sort_by_key(|e| e.file_name());
@@ -141,27 +149,33 @@ async fn migrate_dir(client: &mut PgClient, migrations_dir: &Path) -> Result<(),
             continue;
         }
 
-        let sql = std::fs::read_to_string(&path)
-            .map_err(|err| format!("failed to read migration {}: {}", path.display(), err))?;
+        let sql = std::fs::read_to_string(&path).map_err(IngestError::io(format!(
+            "failed to read migration {}",
+            path.display()
+        )))?;
 
         let tx = client
             .transaction()
             .await
-            .map_err(|err| format!("failed to start migration transaction: {err}"))?;
+            .map_err(IngestError::db("failed to start migration transaction"))?;
         tx.batch_execute(&sql)
             .await
-            .map_err(|err| format!("failed to apply migration {version}: {err}"))?;
+            .map_err(IngestError::db(format!(
+                "failed to apply migration {version}"
+            )))?;
         tx.execute(
             "INSERT INTO schema_migrations (version) VALUES ($1)",
             &[&version],
         )
         .await
-        .map_err(|err| format!("failed to record migration {version}: {err}"))?;
-        tx.commit()
-            .await
-            .map_err(|err| format!("failed to commit migration {version}: {err}"))?;
-
-        println!("applied migration: {}", version);
+        .map_err(IngestError::db(format!(
+            "failed to record migration {version}"
+        )))?;
+        tx.commit().await.map_err(IngestError::db(format!(
+            "failed to commit migration {version}"
+        )))?;
+
+        eprintln!("applied migration: {}", version);
     }
 
     Ok(())
@@ -179,33 +193,80 @@ pub async fn ingest_kucoin(
     source: &str,
     sleep_ms: u64,
     batch_size: usize,
+    insert_mode: InsertMode,
     base_url: Option<&str>,
-) -> Result<(), String> {
-    let timeframe_info = normalize_timeframe(&market, timeframe)?;
-    let start = parse_time_input(start)?;
-    let end = match end {
-        Some(end) => parse_time_input(end)?,
-        None => Utc::now(),
-    };
-
-    let start_seconds = start.timestamp();
-    let end_seconds = end.timestamp();
-
-    let mut window_start = start_seconds;
-    let window_span = match market {
-        Market::Spot => timeframe_info.seconds * (KUCOIN_SPOT_LIMIT - 1),
-        Market::Futures => timeframe_info.seconds * (KUCOIN_FUTURES_LIMIT - 1),
+    resume: bool,
+    progress: &mut dyn ProgressSink,
+) -> Result<(), IngestError> {
+    let mut connector = KucoinConnector::new(market.clone(), base_url);
+    let job = IngestJob {
+        db_url,
+        symbol,
+        market,
+        timeframe,
+        start,
+        end,
+        exchange,
+        source,
+        sleep_ms,
+        batch_size,
+        insert_mode,
+        resume,
+        limiter: None,
     };
+    run_ingest(&mut connector, job, progress).await
+}
 
-    let http_client = Client::builder()
-        .timeout(Duration::from_secs(30))
-        .pool_idle_timeout(Duration::from_secs(90))
-        .build()
-        .map_err(|err| format!("failed to build http client: {err}"))?;
+// The `ingest window=...` stderr lines of the CLI, one per fetched window, plus `ingest retry=...`
+// lines when the exchange rate-limits a request.
+pub fn stderr_progress() -> impl FnMut(ProgressEvent<'_>) {
+    let mut window_index = 0u64;
+    move |event: ProgressEvent<'_>| match event {
+        ProgressEvent::RowsWritten {
+            first_ts,
+            last_ts,
+            rows,
+            total_rows,
+        } => {
+            window_index += 1;
+            eprintln!(
+                "ingest window={} start={} end={} candles={} total_upserts={}",
+                window_index, first_ts, last_ts, rows, total_rows
+            );
+        }
+        ProgressEvent::RequestRetried {
+            attempt,
+            delay_ms,
+            reason,
+        } => eprintln!(
+            "ingest retry={} window={} backoff_ms={} reason={}",
+            attempt,
+            window_index + 1,
+            delay_ms,
+            reason
+        ),
+        _ => {}
+    }
+}
 
+// Writes already-built bars (e.g. synthetic data) through the same upsert as KuCoin ingestion, one
+// transaction per `batch_size` bars. Returns the number of rows written (duplicates included).
+#[allow(clippy::too_many_arguments)]
+pub async fn upsert_bars(
+    db_url: &str,
+    exchange: &str,
+    market: Market,
+    symbol: &str,
+    timeframe: &str,
+    source: &str,
+    bars: &[Bar],
+    batch_size: usize,
+    insert_mode: InsertMode,
+) -> Result<u64, IngestError> {
+    let timeframe = Timeframe::parse(timeframe)?.label;
     let (mut client, connection) = tokio_postgres::connect(db_url, NoTls)
         .await
-        .map_err(|err| format!("failed to connect to postgres: {err}"))?;
+        .map_err(IngestError::Connect)?;
     tokio::spawn(async move {
         if let Err(err) = connection.await {
             eprintln!("postgres connection error: {err}");
@@ -213,72 +274,44 @@ pub async fn ingest_kucoin(
     });
 
     let mut total = 0u64;
-    let mut window_index = 0u64;
-
-    while window_start <= end_seconds {
-        let window_end = (window_start + window_span).min(end_seconds);
-        let candles = match market {
-            Market::Spot => {
-                fetch_kucoin_spot(
-                    &http_client,
-                    base_url.unwrap_or(KUCOIN_SPOT_BASE),
-                    symbol,
-                    &timeframe_info.api,
-                    window_start,
-                    window_end,
-                )
-                .await?
-            }
-            Market::Futures => {
-                fetch_kucoin_futures(
-                    &http_client,
-                    base_url.unwrap_or(KUCOIN_FUTURES_BASE),
-                    symbol,
-                    timeframe_info.seconds,
-                    window_start,
-                    window_end,
-                )
-                .await?
-            }
-        };
-
-        if !candles.is_empty() {
-            let inserted = upsert_candles(
-                &mut client,
-                exchange,
-                &market,
-                symbol,
-                &timeframe_info.canonical,
-                source,
-                &candles,
-                batch_size,
-            )
-            .await?;
-            total += inserted;
-        }
-
-        window_index += 1;
-        println!(
-            "ingest window={} start={} end={} candles={} total_upserts={}",
-            window_index,
-            window_start,
-            window_end,
+    for chunk in bars.chunks(batch_size.max(1)) {
+        let candles: Vec<Candle> = chunk
+            .iter()
+            .map(|bar| {
+                Ok(Candle {
+                    timestamp: Utc
+                        .timestamp_opt(bar.timestamp, 0)
+                        .single()
+                        .ok_or_else(|| format!("invalid timestamp: {}", bar.timestamp))?,
+                    open: bar.open,
+                    high: bar.high,
+                    low: bar.low,
+                    close: bar.close,
+                    volume: bar.volume,
+                    turnover: None,
+                })
+            })
+            .collect::<Result<_, IngestError>>()?;
+        total += upsert_candles(
+            &mut client,
+            exchange,
+            &market,
+            symbol,
+            &timeframe,
+            source,
+            &candles,
+            candles.len(),
+            insert_mode,
+        )
+        .await?;
+        eprintln!(
+            "upsert rows={} total={}/{}",
             candles.len(),
-            total
+            total,
+            bars.len()
         );
-
-        if window_end >= end_seconds {
-            break;
-        }
-        window_start = window_end + timeframe_info.seconds;
-        tokio::time::sleep(Duration::from_millis(sleep_ms)).await;
     }
-
-    println!(
-        "ingest complete: symbol={} market={:?} timeframe={} total_upserts={}",
-        symbol, market, timeframe_info.canonical, total
-    );
-    Ok(())
+    Ok(total)
 }
 
 #[cfg(test)]
@@ -331,250 +364,6 @@ mod migrations_tests {
     }
 }
 
-fn normalize_timeframe(market: &Market, value: &str) -> Result<TimeframeInfo, String> {
-    let tf = match market {
-        Market::Spot => Timeframe::parse(value)?,
-        Market::Futures => Timeframe::parse_or_seconds(value)?,
-    };
-
-    Ok(TimeframeInfo {
-        api: tf.label.clone(),
-        canonical: tf.label,
-        seconds: tf.step_seconds,
-    })
-}
-
-async fn fetch_kucoin_spot(
-    client: &Client,
-    base_url: &str,
-    symbol: &str,
-    timeframe: &str,
-    start: i64,
-    end: i64,
-) -> Result<Vec<Candle>, String> {
-    let url = format!("{}/api/v1/market/candles", base_url.trim_end_matches('/'));
-    let mut attempts = 0u32;
-    loop {
-        attempts += 1;
-        let response = client
-            .get(&url)
-            .query(&[
-                ("symbol", symbol),
-                ("type", timeframe),
-                ("startAt", &start.to_string()),
-                ("endAt", &end.to_string()),
-            ])
-            .send()
-            .await
-            .map_err(|err| format!("spot request failed: {err}"))?;
-
-        if response.status().as_u16() == 429 && attempts <= 5 {
-            let backoff = 500u64 * attempts as u64;
-            tokio::time::sleep(Duration::from_millis(backoff)).await;
-            continue;
-        }
-
-        if !response.status().is_success() {
-            return Err(format!(
-                "spot request failed with status {}",
-                response.status()
-            ));
-        }
-
-        let payload: KucoinResponse = response
-            .json()
-            .await
-            .map_err(|err| format!("spot response parse failed: {err}"))?;
-        if payload.code != "200000" {
-            return Err(format!("spot response error code: {}", payload.code));
-        }
-
-        return parse_kucoin_rows(&payload.data);
-    }
-}
-
-async fn fetch_kucoin_futures(
-    client: &Client,
-    base_url: &str,
-    symbol: &str,
-    granularity_seconds: i64,
-    start: i64,
-    end: i64,
-) -> Result<Vec<Candle>, String> {
-    let url = format!("{}/api/v1/kline/query", base_url.trim_end_matches('/'));
-    let mut attempts = 0u32;
-    let start_ms = start * 1000;
-    let end_ms = end * 1000;
-
-    loop {
-        attempts += 1;
-        let response = client
-            .get(&url)
-            .query(&[
-                ("symbol", symbol),
-                ("granularity", &granularity_seconds.to_string()),
-                ("from", &start_ms.to_string()),
-                ("to", &end_ms.to_string()),
-            ])
-            .send()
-            .await
-            .map_err(|err| format!("futures request failed: {err}"))?;
-
-        if response.status().as_u16() == 429 && attempts <= 5 {
-            let backoff = 500u64 * attempts as u64;
-            tokio::time::sleep(Duration::from_millis(backoff)).await;
-            continue;
-        }
-
-        if !response.status().is_success() {
-            return Err(format!(
-                "futures request failed with status {}",
-                response.status()
-            ));
-        }
-
-        let payload: KucoinResponse = response
-            .json()
-            .await
-            .map_err(|err| format!("futures response parse failed: {err}"))?;
-        if payload.code != "200000" {
-            return Err(format!("futures response error code: {}", payload.code));
-        }
-
-        return parse_kucoin_rows(&payload.data);
-    }
-}
-
-fn parse_kucoin_rows(rows: &[Vec<String>]) -> Result<Vec<Candle>, String> {
-    let mut candles = Vec::with_capacity(rows.len());
-    for row in rows {
-        if row.len() < 6 {
-            return Err("unexpected candle row length".to_string());
-        }
-
-        let ts = parse_epoch_value(&row[0])?;
-        let timestamp = Utc
-            .timestamp_opt(ts, 0)
-            .single()
-            .ok_or("invalid timestamp")?;
-
-        let open = parse_f64(&row[1], "open")?;
-        let close = parse_f64(&row[2], "close")?;
-        let high = parse_f64(&row[3], "high")?;
-        let low = parse_f64(&row[4], "low")?;
-        let volume = parse_f64(&row[5], "volume")?;
-        let turnover = if row.len() > 6 {
-            Some(parse_f64(&row[6], "turnover")?)
-        } else {
-            None
-        };
-
-        candles.push(Candle {
-            timestamp,
-            open,
-            high,
-            low,
-            close,
-            volume,
-            turnover,
-        });
-    }
-
-    candles.sort_by_key(|c| c.timestamp);
-    Ok(candles)
-}
-
-#[allow(clippy::too_many_arguments)]
-async fn upsert_candles(
-    client: &mut PgClient,
-    exchange: &str,
-    market: &Market,
-    symbol: &str,
-    timeframe: &str,
-    source: &str,
-    candles: &[Candle],
-    batch_size: usize,
-) -> Result<u64, String> {
-    if candles.is_empty() {
-        return Ok(0);
-    }
-
-    let market_value = match market {
-        Market::Spot => "spot",
-        Market::Futures => "futures",
-    };
-
-    let statement = client
-        .prepare(
-            "INSERT INTO ohlcv_candles (
-                exchange,
-                market,
-                symbol,
-                timeframe,
-                timestamp_utc,
-                open,
-                high,
-                low,
-                close,
-                volume,
-                turnover,
-                source
-            ) VALUES (
-                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12
-            )
-            ON CONFLICT (exchange, market, symbol, timeframe, timestamp_utc)
-            DO UPDATE SET
-                open = EXCLUDED.open,
-                high = EXCLUDED.high,
-                low = EXCLUDED.low,
-                close = EXCLUDED.close,
-                volume = EXCLUDED.volume,
-                turnover = EXCLUDED.turnover,
-                source = EXCLUDED.source,
-                ingested_at = NOW()",
-        )
-        .await
-        .map_err(|err| format!("failed to prepare upsert: {err}"))?;
-
-    let mut total = 0u64;
-    let transaction = client
-        .transaction()
-        .await
-        .map_err(|err| format!("failed to start transaction: {err}"))?;
-
-    for chunk in candles.chunks(batch_size.max(1)) {
-        for candle in chunk {
-            transaction
-                .execute(
-                    &statement,
-                    &[
-                        &exchange,
-                        &market_value,
-                        &symbol,
-                        &timeframe,
-                        &candle.timestamp,
-                        &candle.open,
-                        &candle.high,
-                        &candle.low,
-                        &candle.close,
-                        &candle.volume,
-                        &candle.turnover,
-                        &source,
-                    ],
-                )
-                .await
-                .map_err(|err| format!("upsert failed: {err}"))?;
-            total += 1;
-        }
-    }
-
-    transaction
-        .commit()
-        .await
-        .map_err(|err| format!("failed to commit: {err}"))?;
-    Ok(total)
-}
-
 fn parse_time_input(value: &str) -> Result<DateTime<Utc>, String> {
     if let Ok(ts) = value.parse::<i64>() {
         let seconds = if ts > 1_000_000_000_000 {
@@ -612,14 +401,17 @@ fn parse_f64(value: &str, field: &str) -> Result<f64, String> {
 
 #[cfg(test)]
 mod tests {
-    use super::normalize_timeframe;
-    use super::Market;
-
-    #[test]
-    fn normalize_timeframe_maps_for_spot_and_futures() {
-        let spot = normalize_timeframe(&Market::Spot, "1m").unwrap();
-        assert_eq!(spot.canonical, "1min");
-        let futures = normalize_timeframe(&Market::Futures, "60").unwrap();
-        assert_eq!(futures.seconds, 60);
+    use super::{migrate_db, IngestError};
+    use std::path::Path;
+
+    #[tokio::test]
+    async fn migrate_db_reports_connect_errors_by_variant() {
+        let err = migrate_db("not a postgres url", Path::new("migrations"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, IngestError::Connect(_)));
+        assert!(err
+            .to_string()
+            .starts_with("failed to connect to postgres: "));
     }
 }
@@ -1,10 +1,17 @@
 use clap::{Parser, Subcommand};
-use kairos_ingest::{ingest_kucoin, migrate_db, Market};
+use kairos_ingest::{
+    backfill_gaps, collect_symbols, find_gaps, ingest_binance, ingest_bybit, ingest_kucoin,
+    ingest_kucoin_symbols, ingest_okx, migrate_db, stderr_progress, stderr_symbol_progress, Gap,
+    IngestError, InsertMode, Market, SymbolOutcome, Venue,
+};
 use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "kairos-ingest")]
-#[command(about = "KuCoin OHLCV ingestion into PostgreSQL.", version)]
+#[command(
+    about = "KuCoin, Binance, Bybit and OKX OHLCV ingestion into PostgreSQL.",
+    version
+)]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
@@ -27,8 +34,17 @@ enum Commands {
     IngestKucoin {
         #[arg(long)]
         db_url: String,
+        #[arg(long, required_unless_present_any = ["symbols", "symbols_file"])]
+        symbol: Option<String>,
+        /// Ingest several symbols concurrently (comma separated); combines with --symbol.
+        #[arg(long, value_delimiter = ',')]
+        symbols: Vec<String>,
+        /// File with one symbol per line (`#` starts a comment).
         #[arg(long)]
-        symbol: String,
+        symbols_file: Option<PathBuf>,
+        /// Symbols ingested at once when several are given; they share the --sleep-ms pacing.
+        #[arg(long, default_value_t = 4)]
+        workers: usize,
         #[arg(long, default_value = "spot")]
         market: Market,
         #[arg(long, default_value = "1min")]
@@ -45,9 +61,151 @@ enum Commands {
         sleep_ms: u64,
         #[arg(long, default_value_t = 500)]
         batch_size: usize,
+        /// copy (binary COPY plus one merge per window) or rows (one INSERT per candle).
+        #[arg(long, value_enum, default_value = "copy")]
+        insert_mode: InsertMode,
         /// Override KuCoin base URL (useful for tests; defaults to real KuCoin endpoints).
         #[arg(long)]
         base_url: Option<String>,
+        /// Continue after the last window a previous ingest of the series completed.
+        #[arg(long)]
+        resume: bool,
+    },
+    /// Ingest Binance spot klines into PostgreSQL.
+    IngestBinance {
+        #[arg(long)]
+        db_url: String,
+        /// Stored as given (e.g. BTC-USDT); requested from Binance without separators (BTCUSDT).
+        #[arg(long)]
+        symbol: String,
+        #[arg(long, default_value = "1min")]
+        timeframe: String,
+        #[arg(long)]
+        start: String,
+        #[arg(long)]
+        end: Option<String>,
+        #[arg(long, default_value = "binance")]
+        exchange: String,
+        #[arg(long, default_value = "binance")]
+        source: String,
+        #[arg(long, default_value_t = 250)]
+        sleep_ms: u64,
+        #[arg(long, default_value_t = 500)]
+        batch_size: usize,
+        /// copy (binary COPY plus one merge per window) or rows (one INSERT per candle).
+        #[arg(long, value_enum, default_value = "copy")]
+        insert_mode: InsertMode,
+        /// Override Binance base URL (useful for tests; defaults to https://api.binance.com).
+        #[arg(long)]
+        base_url: Option<String>,
+        /// Continue after the last window a previous ingest of the series completed.
+        #[arg(long)]
+        resume: bool,
+    },
+    /// Ingest Bybit spot (or USDT perpetual) klines into PostgreSQL.
+    IngestBybit {
+        #[arg(long)]
+        db_url: String,
+        /// Stored as given (e.g. BTC-USDT); requested without separators (BTCUSDT).
+        #[arg(long)]
+        symbol: String,
+        #[arg(long, default_value = "spot")]
+        market: Market,
+        #[arg(long, default_value = "1min")]
+        timeframe: String,
+        #[arg(long)]
+        start: String,
+        #[arg(long)]
+        end: Option<String>,
+        #[arg(long, default_value = "bybit")]
+        exchange: String,
+        #[arg(long, default_value = "bybit")]
+        source: String,
+        #[arg(long, default_value_t = 200)]
+        sleep_ms: u64,
+        #[arg(long, default_value_t = 500)]
+        batch_size: usize,
+        /// copy (binary COPY plus one merge per window) or rows (one INSERT per candle).
+        #[arg(long, value_enum, default_value = "copy")]
+        insert_mode: InsertMode,
+        /// Override the Bybit base URL (useful for tests).
+        #[arg(long)]
+        base_url: Option<String>,
+        /// Continue after the last window a previous ingest of the series completed.
+        #[arg(long)]
+        resume: bool,
+    },
+    /// Ingest OKX spot (or USDT perpetual swap) candles into PostgreSQL.
+    IngestOkx {
+        #[arg(long)]
+        db_url: String,
+        /// Stored as given (e.g. BTC-USDT); futures request the BTC-USDT-SWAP instrument.
+        #[arg(long)]
+        symbol: String,
+        #[arg(long, default_value = "spot")]
+        market: Market,
+        #[arg(long, default_value = "1min")]
+        timeframe: String,
+        #[arg(long)]
+        start: String,
+        #[arg(long)]
+        end: Option<String>,
+        #[arg(long, default_value = "okx")]
+        exchange: String,
+        #[arg(long, default_value = "okx")]
+        source: String,
+        #[arg(long, default_value_t = 150)]
+        sleep_ms: u64,
+        #[arg(long, default_value_t = 500)]
+        batch_size: usize,
+        /// copy (binary COPY plus one merge per window) or rows (one INSERT per candle).
+        #[arg(long, value_enum, default_value = "copy")]
+        insert_mode: InsertMode,
+        /// Override the OKX base URL (useful for tests).
+        #[arg(long)]
+        base_url: Option<String>,
+        /// Continue after the last window a previous ingest of the series completed.
+        #[arg(long)]
+        resume: bool,
+    },
+    /// List missing bars of a stored series; with --backfill, fetch just those from the exchange.
+    Gaps {
+        #[arg(long)]
+        db_url: String,
+        #[arg(long)]
+        symbol: String,
+        #[arg(long, default_value = "spot")]
+        market: Market,
+        #[arg(long, default_value = "1min")]
+        timeframe: String,
+        /// Stored exchange name of the series.
+        #[arg(long, default_value = "kucoin")]
+        exchange: String,
+        /// Also report bars missing from here up to the first stored one.
+        #[arg(long)]
+        start: Option<String>,
+        /// Also report bars missing after the last stored one up to here.
+        #[arg(long)]
+        end: Option<String>,
+        /// Re-fetch only the missing windows and upsert them.
+        #[arg(long)]
+        backfill: bool,
+        /// Exchange API to backfill from; defaults to --exchange when it names one.
+        #[arg(long)]
+        venue: Option<Venue>,
+        /// Source recorded on backfilled rows; defaults to the venue.
+        #[arg(long)]
+        source: Option<String>,
+        #[arg(long, default_value_t = 350)]
+        sleep_ms: u64,
+        #[arg(long, default_value_t = 500)]
+        batch_size: usize,
+        /// copy (binary COPY plus one merge per window) or rows (one INSERT per candle).
+        #[arg(long, value_enum, default_value = "copy")]
+        insert_mode: InsertMode,
+        /// Override the venue's base URL (useful for tests).
+        #[arg(long)]
+        base_url: Option<String>,
     },
 }
 
@@ -59,7 +217,7 @@ async fn main() {
     }
 }
 
-async fn run() -> Result<(), String> {
+async fn run() -> Result<(), IngestError> {
     let cli = Cli::parse();
     match cli.command {
         Commands::Migrate {
@@ -69,6 +227,9 @@ async fn run() -> Result<(), String> {
         Commands::IngestKucoin {
             db_url,
             symbol,
+            symbols,
+            symbols_file,
+            workers,
             market,
             timeframe,
             start,
@@ -77,8 +238,36 @@ async fn run() -> Result<(), String> {
             source,
             sleep_ms,
             batch_size,
+            insert_mode,
             base_url,
+            resume,
         } => {
+            let symbol = match symbol {
+                Some(symbol) if symbols.is_empty() && symbols_file.is_none() => symbol,
+                symbol => {
+                    let listed: Vec<String> = symbol.into_iter().chain(symbols).collect();
+                    let symbols = collect_symbols(&listed, symbols_file.as_deref())?;
+                    let outcomes = ingest_kucoin_symbols(
+                        &db_url,
+                        &symbols,
+                        market,
+                        &timeframe,
+                        &start,
+                        end.as_deref(),
+                        &exchange,
+                        &source,
+                        sleep_ms,
+                        batch_size,
+                        insert_mode,
+                        base_url.as_deref(),
+                        resume,
+                        workers,
+                        &mut stderr_symbol_progress(),
+                    )
+                    .await;
+                    return summarize_symbols(&outcomes);
+                }
+            };
             ingest_kucoin(
                 &db_url,
                 &symbol,
@@ -90,9 +279,208 @@ async fn run() -> Result<(), String> {
                 &source,
                 sleep_ms,
                 batch_size,
+                insert_mode,
+                base_url.as_deref(),
+                resume,
+                &mut stderr_progress(),
+            )
+            .await
+        }
+        Commands::IngestBinance {
+            db_url,
+            symbol,
+            timeframe,
+            start,
+            end,
+            exchange,
+            source,
+            sleep_ms,
+            batch_size,
+            insert_mode,
+            base_url,
+            resume,
+        } => {
+            ingest_binance(
+                &db_url,
+                &symbol,
+                &timeframe,
+                &start,
+                end.as_deref(),
+                &exchange,
+                &source,
+                sleep_ms,
+                batch_size,
+                insert_mode,
+                base_url.as_deref(),
+                resume,
+                &mut stderr_progress(),
+            )
+            .await
+        }
+        Commands::IngestBybit {
+            db_url,
+            symbol,
+            market,
+            timeframe,
+            start,
+            end,
+            exchange,
+            source,
+            sleep_ms,
+            batch_size,
+            insert_mode,
+            base_url,
+            resume,
+        } => {
+            ingest_bybit(
+                &db_url,
+                &symbol,
+                market,
+                &timeframe,
+                &start,
+                end.as_deref(),
+                &exchange,
+                &source,
+                sleep_ms,
+                batch_size,
+                insert_mode,
+                base_url.as_deref(),
+                resume,
+                &mut stderr_progress(),
+            )
+            .await
+        }
+        Commands::IngestOkx {
+            db_url,
+            symbol,
+            market,
+            timeframe,
+            start,
+            end,
+            exchange,
+            source,
+            sleep_ms,
+            batch_size,
+            insert_mode,
+            base_url,
+            resume,
+        } => {
+            ingest_okx(
+                &db_url,
+                &symbol,
+                market,
+                &timeframe,
+                &start,
+                end.as_deref(),
+                &exchange,
+                &source,
+                sleep_ms,
+                batch_size,
+                insert_mode,
                 base_url.as_deref(),
+                resume,
+                &mut stderr_progress(),
             )
             .await
         }
+        Commands::Gaps {
+            db_url,
+            symbol,
+            market,
+            timeframe,
+            exchange,
+            start,
+            end,
+            backfill,
+            venue,
+            source,
+            sleep_ms,
+            batch_size,
+            insert_mode,
+            base_url,
+        } => {
+            let scan = |market| {
+                find_gaps(
+                    &db_url,
+                    &exchange,
+                    market,
+                    &symbol,
+                    &timeframe,
+                    start.as_deref(),
+                    end.as_deref(),
+                )
+            };
+            let gaps = scan(&market).await?;
+            print_gaps(&gaps);
+            if !backfill || gaps.is_empty() {
+                return Ok(());
+            }
+            let venue = venue
+                .or_else(|| Venue::from_exchange(&exchange))
+                .ok_or_else(|| {
+                    format!("--exchange {exchange} is not a known exchange API; pass --venue")
+                })?;
+            let source = source.unwrap_or_else(|| venue.as_str().to_string());
+            backfill_gaps(
+                &db_url,
+                venue,
+                &symbol,
+                market.clone(),
+                &timeframe,
+                &gaps,
+                &exchange,
+                &source,
+                sleep_ms,
+                batch_size,
+                insert_mode,
+                base_url.as_deref(),
+                &mut stderr_progress(),
+            )
+            .await?;
+            // Bars the exchange has no candles for (halts, delistings) stay missing.
+            let remaining = scan(&market).await?;
+            eprintln!(
+                "gaps after backfill: gaps={} missing_bars={}",
+                remaining.len(),
+                remaining.iter().map(|gap| gap.missing_bars).sum::<i64>()
+            );
+            Ok(())
+        }
+    }
+}
+
+// Failed symbols were already tried to the end; report each one, then fail the command if any did.
+fn summarize_symbols(outcomes: &[SymbolOutcome]) -> Result<(), IngestError> {
+    let mut failed = 0;
+    for outcome in outcomes {
+        if let Err(err) = &outcome.result {
+            failed += 1;
+            eprintln!("ingest symbol={} failed: {err}", outcome.symbol);
+        }
+    }
+    eprintln!(
+        "ingest symbols complete: symbols={} ok={} failed={}",
+        outcomes.len(),
+        outcomes.len() - failed,
+        failed
+    );
+    if failed > 0 {
+        return Err(format!("{failed} of {} symbols failed", outcomes.len()).into());
+    }
+    Ok(())
+}
+
+// One line per gap on stdout, then the totals on stderr like the other commands' summaries.
+fn print_gaps(gaps: &[Gap]) {
+    for gap in gaps {
+        println!(
+            "gap first_missing={} last_missing={} missing_bars={}",
+            gap.first_missing, gap.last_missing, gap.missing_bars
+        );
     }
+    eprintln!(
+        "gaps complete: gaps={} missing_bars={}",
+        gaps.len(),
+        gaps.iter().map(|gap| gap.missing_bars).sum::<i64>()
+    );
 }
@@ -0,0 +1,203 @@
+use super::connector::{run_ingest, IngestJob};
+use super::kucoin::KucoinConnector;
+use super::{IngestError, InsertMode, Market};
+use futures_util::future::join_all;
+use kairos_domain::services::progress::ProgressEvent;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+use tokio::time::Instant;
+
+// Spaces the requests of every worker of a multi-symbol ingest: each request takes the next free
+// slot, `spacing` after the one before it. The workers are futures on one task, so a `Cell` is
+// all the sharing it needs.
+#[derive(Debug, Default)]
+pub(crate) struct RateLimiter {
+    next: Cell<Option<Instant>>,
+}
+
+impl RateLimiter {
+    pub(crate) async fn acquire(&self, spacing: Duration) {
+        let now = Instant::now();
+        let slot = self.next.get().map_or(now, |next| next.max(now));
+        self.next.set(Some(slot + spacing));
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+// How one symbol of a multi-symbol ingest ended.
+#[derive(Debug)]
+pub struct SymbolOutcome {
+    pub symbol: String,
+    pub result: Result<(), IngestError>,
+}
+
+// `--symbols` entries plus the lines of `--symbols-file` (blank lines and `#` comments skipped), in
+// order and without repeats.
+pub fn collect_symbols(listed: &[String], file: Option<&Path>) -> Result<Vec<String>, IngestError> {
+    let from_file = match file {
+        Some(path) => std::fs::read_to_string(path).map_err(IngestError::io(format!(
+            "failed to read symbols file {}",
+            path.display()
+        )))?,
+        None => String::new(),
+    };
+    let mut symbols: Vec<String> = Vec::new();
+    for symbol in listed
+        .iter()
+        .map(String::as_str)
+        .chain(
+            from_file
+                .lines()
+                .map(|line| line.split('#').next().unwrap_or("")),
+        )
+        .map(str::trim)
+        .filter(|symbol| !symbol.is_empty())
+    {
+        if !symbols.iter().any(|seen| seen == symbol) {
+            symbols.push(symbol.to_string());
+        }
+    }
+    if symbols.is_empty() {
+        return Err("no symbols to ingest".into());
+    }
+    Ok(symbols)
+}
+
+// Ingests each symbol like `ingest_kucoin`, `workers` of them at a time, all pacing their requests
+// through one limiter at `sleep_ms`. A failed symbol does not stop the others; the outcomes come
+// back in `symbols` order. `progress` gets every event tagged with its symbol.
+#[allow(clippy::too_many_arguments)]
+pub async fn ingest_kucoin_symbols(
+    db_url: &str,
+    symbols: &[String],
+    market: Market,
+    timeframe: &str,
+    start: &str,
+    end: Option<&str>,
+    exchange: &str,
+    source: &str,
+    sleep_ms: u64,
+    batch_size: usize,
+    insert_mode: InsertMode,
+    base_url: Option<&str>,
+    resume: bool,
+    workers: usize,
+    progress: &mut dyn FnMut(&str, ProgressEvent<'_>),
+) -> Vec<SymbolOutcome> {
+    let limiter = RateLimiter::default();
+    let next = Cell::new(0usize);
+    let progress = RefCell::new(progress);
+    let (limiter, next, progress, market) = (&limiter, &next, &progress, &market);
+
+    let worker = move || async move {
+        let mut outcomes = Vec::new();
+        loop {
+            let idx = next.get();
+            let Some(symbol) = symbols.get(idx) else {
+                break;
+            };
+            next.set(idx + 1);
+
+            let mut connector = KucoinConnector::new(market.clone(), base_url);
+            let job = IngestJob {
+                db_url,
+                symbol,
+                market: market.clone(),
+                timeframe,
+                start,
+                end,
+                exchange,
+                source,
+                sleep_ms,
+                batch_size,
+                insert_mode,
+                resume,
+                limiter: Some(limiter),
+            };
+            let mut sink = |event: ProgressEvent<'_>| (*progress.borrow_mut())(symbol, event);
+            let result = run_ingest(&mut connector, job, &mut sink).await;
+            outcomes.push((
+                idx,
+                SymbolOutcome {
+                    symbol: symbol.clone(),
+                    result,
+                },
+            ));
+        }
+        outcomes
+    };
+
+    let workers = workers.clamp(1, symbols.len().max(1));
+    let mut outcomes: Vec<_> = join_all((0..workers).map(|_| worker()))
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+    outcomes.sort_by_key(|(idx, _)| *idx);
+    outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+}
+
+// `stderr_progress` for multi-symbol ingests: the same lines, prefixed with `symbol=` and counting
+// windows per symbol.
+pub fn stderr_symbol_progress() -> impl FnMut(&str, ProgressEvent<'_>) {
+    let mut windows: HashMap<String, u64> = HashMap::new();
+    move |symbol: &str, event: ProgressEvent<'_>| match event {
+        ProgressEvent::RowsWritten {
+            first_ts,
+            last_ts,
+            rows,
+            total_rows,
+        } => {
+            let window_index = windows.entry(symbol.to_string()).or_default();
+            *window_index += 1;
+            eprintln!(
+                "ingest symbol={} window={} start={} end={} candles={} total_upserts={}",
+                symbol, window_index, first_ts, last_ts, rows, total_rows
+            );
+        }
+        ProgressEvent::RequestRetried {
+            attempt,
+            delay_ms,
+            reason,
+        } => eprintln!(
+            "ingest symbol={} retry={} window={} backoff_ms={} reason={}",
+            symbol,
+            attempt,
+            windows.get(symbol).copied().unwrap_or(0) + 1,
+            delay_ms,
+            reason
+        ),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collect_symbols, RateLimiter};
+    use std::time::Duration;
+    use tokio::time::Instant;
+
+    #[tokio::test]
+    async fn symbols_merge_in_order_and_the_limiter_spaces_requests() {
+        let path =
+            std::env::temp_dir().join(format!("kairos_ingest_symbols_{}.txt", std::process::id()));
+        std::fs::write(&path, "# majors\nETH-USDT\n\nSOL-USDT  # alt\nBTC-USDT\n").unwrap();
+        let listed = vec!["BTC-USDT".to_string(), " XRP-USDT ".to_string()];
+        let symbols = collect_symbols(&listed, Some(path.as_path())).expect("symbols");
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(symbols, ["BTC-USDT", "XRP-USDT", "ETH-USDT", "SOL-USDT"]);
+        assert!(collect_symbols(&[" ".to_string()], None).is_err());
+
+        let limiter = RateLimiter::default();
+        let started = Instant::now();
+        let spacing = Duration::from_millis(20);
+        tokio::join!(
+            limiter.acquire(spacing),
+            limiter.acquire(spacing),
+            limiter.acquire(spacing)
+        );
+        assert!(started.elapsed() >= Duration::from_millis(40));
+    }
+}
@@ -0,0 +1,252 @@
+use super::connector::{
+    decode_json, lookup_interval, run_ingest, send_with_retry, ExchangeConnector, IngestJob, Window,
+};
+use super::{parse_epoch_value, parse_f64, Candle, IngestError, InsertMode, Market};
+use chrono::{TimeZone, Utc};
+use kairos_domain::services::progress::ProgressSink;
+use kairos_domain::value_objects::timeframe::Timeframe;
+use reqwest::Client;
+use serde::Deserialize;
+
+const OKX_BASE: &str = "https://www.okx.com";
+// `history-candles` returns at most 100 rows per request.
+const OKX_CANDLES_LIMIT: i64 = 100;
+// Bars of 6 hours and longer use the UTC-aligned variants, like the rest of the database.
+const OKX_BARS: &[(&str, &str)] = &[
+    ("1min", "1m"),
+    ("3min", "3m"),
+    ("5min", "5m"),
+    ("15min", "15m"),
+    ("30min", "30m"),
+    ("1hour", "1H"),
+    ("2hour", "2H"),
+    ("4hour", "4H"),
+    ("6hour", "6Hutc"),
+    ("12hour", "12Hutc"),
+    ("1day", "1Dutc"),
+    ("1week", "1Wutc"),
+    ("1month", "1Mutc"),
+];
+
+#[derive(Debug, Deserialize)]
+struct OkxResponse {
+    code: String,
+    msg: String,
+    data: Vec<Vec<String>>,
+}
+
+// OKX v5 candles (`/api/v5/market/history-candles`): spot instruments, or the USDT perpetual swap
+// (`BTC-USDT-SWAP`) for futures.
+pub(crate) struct OkxConnector {
+    market: Market,
+    base_url: String,
+}
+
+impl OkxConnector {
+    pub fn new(market: Market, base_url: Option<&str>) -> Self {
+        Self {
+            market,
+            base_url: base_url
+                .unwrap_or(OKX_BASE)
+                .trim_end_matches('/')
+                .to_string(),
+        }
+    }
+}
+
+impl ExchangeConnector for OkxConnector {
+    fn name(&self) -> &'static str {
+        match self.market {
+            Market::Spot => "okx spot",
+            Market::Futures => "okx futures",
+        }
+    }
+
+    fn max_candles(&self) -> i64 {
+        OKX_CANDLES_LIMIT
+    }
+
+    // `btc/usdt` -> `BTC-USDT` (spot) or `BTC-USDT-SWAP` (futures).
+    fn api_symbol(&self, symbol: &str) -> String {
+        let inst_id = symbol.trim().to_uppercase().replace(['/', '_'], "-");
+        match self.market {
+            Market::Futures if !inst_id.ends_with("-SWAP") => format!("{inst_id}-SWAP"),
+            _ => inst_id,
+        }
+    }
+
+    fn interval(&self, timeframe: &Timeframe) -> Result<String, IngestError> {
+        lookup_interval("OKX", timeframe, OKX_BARS)
+    }
+
+    async fn fetch_candles(
+        &mut self,
+        client: &Client,
+        window: &Window<'_>,
+        progress: &mut dyn ProgressSink,
+    ) -> Result<Vec<Candle>, IngestError> {
+        let name = self.name();
+        let url = format!("{}/api/v5/market/history-candles", self.base_url);
+        // `after`/`before` are exclusive bounds: rows older than `after` and newer than `before`.
+        let after = (window.end * 1000 + 1).to_string();
+        let before = (window.start * 1000 - 1).to_string();
+        let limit = OKX_CANDLES_LIMIT.to_string();
+        let response = send_with_retry(
+            name,
+            || {
+                client.get(&url).query(&[
+                    ("instId", window.symbol),
+                    ("bar", window.interval),
+                    ("after", &after),
+                    ("before", &before),
+                    ("limit", &limit),
+                ])
+            },
+            progress,
+        )
+        .await?;
+
+        let payload: OkxResponse = decode_json(name, response).await?;
+        if payload.code != "0" {
+            return Err(IngestError::Api {
+                market: name,
+                code: format!("{} {}", payload.code, payload.msg),
+            });
+        }
+        parse_okx_rows(&payload.data, &self.market)
+    }
+}
+
+// Pulls OKX candles (spot, or the USDT perpetual swap with `Market::Futures`) into
+// `ohlcv_candles`, storing the symbol as given.
+#[allow(clippy::too_many_arguments)]
+pub async fn ingest_okx(
+    db_url: &str,
+    symbol: &str,
+    market: Market,
+    timeframe: &str,
+    start: &str,
+    end: Option<&str>,
+    exchange: &str,
+    source: &str,
+    sleep_ms: u64,
+    batch_size: usize,
+    insert_mode: InsertMode,
+    base_url: Option<&str>,
+    resume: bool,
+    progress: &mut dyn ProgressSink,
+) -> Result<(), IngestError> {
+    let mut connector = OkxConnector::new(market.clone(), base_url);
+    let job = IngestJob {
+        db_url,
+        symbol,
+        market,
+        timeframe,
+        start,
+        end,
+        exchange,
+        source,
+        sleep_ms,
+        batch_size,
+        insert_mode,
+        resume,
+        limiter: None,
+    };
+    run_ingest(&mut connector, job, progress).await
+}
+
+// Rows are `["ts_ms", "open", "high", "low", "close", "vol", "volCcy", "volCcyQuote", "confirm"]`,
+// newest first. Swap `vol` counts contracts, so futures take the base-currency `volCcy` instead.
+fn parse_okx_rows(rows: &[Vec<String>], market: &Market) -> Result<Vec<Candle>, IngestError> {
+    let volume_idx = match market {
+        Market::Spot => 5,
+        Market::Futures => 6,
+    };
+    let mut candles = Vec::with_capacity(rows.len());
+    for row in rows {
+        if row.len() < 8 {
+            return Err("unexpected candle row length".into());
+        }
+        let timestamp = Utc
+            .timestamp_opt(parse_epoch_value(&row[0])?, 0)
+            .single()
+            .ok_or("invalid timestamp")?;
+        candles.push(Candle {
+            timestamp,
+            open: parse_f64(&row[1], "open")?,
+            high: parse_f64(&row[2], "high")?,
+            low: parse_f64(&row[3], "low")?,
+            close: parse_f64(&row[4], "close")?,
+            volume: parse_f64(&row[volume_idx], "volume")?,
+            turnover: Some(parse_f64(&row[7], "quote volume")?),
+        });
+    }
+    candles.sort_by_key(|c| c.timestamp);
+    Ok(candles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_okx_rows, OkxConnector};
+    use crate::connector::ExchangeConnector;
+    use crate::Market;
+    use kairos_domain::value_objects::timeframe::Timeframe;
+
+    #[test]
+    fn okx_candles_map_to_candles() {
+        let spot = OkxConnector {
+            market: Market::Spot,
+            base_url: String::new(),
+        };
+        let futures = OkxConnector {
+            market: Market::Futures,
+            base_url: String::new(),
+        };
+        assert_eq!(spot.api_symbol("btc/usdt"), "BTC-USDT");
+        assert_eq!(futures.api_symbol("BTC-USDT"), "BTC-USDT-SWAP");
+        assert_eq!(futures.api_symbol("BTC-USDT-SWAP"), "BTC-USDT-SWAP");
+        let interval = |raw| spot.interval(&Timeframe::parse(raw).unwrap());
+        assert_eq!(interval("4h").unwrap(), "4H");
+        assert_eq!(interval("1d").unwrap(), "1Dutc");
+        assert!(interval("8h")
+            .unwrap_err()
+            .to_string()
+            .contains("no OKX kline interval"));
+
+        let rows: Vec<Vec<String>> = [
+            [
+                "1700000060000",
+                "101",
+                "103",
+                "100",
+                "102",
+                "30",
+                "3",
+                "306",
+                "1",
+            ],
+            [
+                "1700000000000",
+                "100",
+                "102",
+                "99",
+                "101",
+                "10",
+                "1",
+                "101",
+                "1",
+            ],
+        ]
+        .iter()
+        .map(|row| row.map(String::from).to_vec())
+        .collect();
+        let candles = parse_okx_rows(&rows, &Market::Spot).unwrap();
+        assert_eq!(candles[0].timestamp.timestamp(), 1_700_000_000);
+        assert_eq!(
+            (candles[1].volume, candles[1].turnover),
+            (30.0, Some(306.0))
+        );
+        let swap = parse_okx_rows(&rows, &Market::Futures).unwrap();
+        assert_eq!(swap[1].volume, 3.0);
+    }
+}
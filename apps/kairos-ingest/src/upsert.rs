@@ -0,0 +1,320 @@
+use super::{Candle, IngestError, Market};
+use clap::ValueEnum;
+use tokio_postgres::binary_copy::BinaryCopyInWriter;
+use tokio_postgres::types::Type;
+use tokio_postgres::Client as PgClient;
+
+const ON_CONFLICT_UPDATE: &str = "ON CONFLICT (exchange, market, symbol, timeframe, timestamp_utc)
+    DO UPDATE SET
+        open = EXCLUDED.open,
+        high = EXCLUDED.high,
+        low = EXCLUDED.low,
+        close = EXCLUDED.close,
+        volume = EXCLUDED.volume,
+        turnover = EXCLUDED.turnover,
+        source = EXCLUDED.source,
+        ingested_at = NOW()";
+
+// How candles reach `ohlcv_candles`. Both upsert on the table's key and write the same rows.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InsertMode {
+    // Binary COPY into a temp table, then one `INSERT ... ON CONFLICT` merge per call.
+    #[default]
+    Copy,
+    // One prepared INSERT per candle; for servers or poolers that do not allow COPY.
+    Rows,
+}
+
+// Upserts the candles of one series in a single transaction. Returns the rows written, duplicates
+// included; a timestamp given twice keeps its last candle in either mode.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn upsert_candles(
+    client: &mut PgClient,
+    exchange: &str,
+    market: &Market,
+    symbol: &str,
+    timeframe: &str,
+    source: &str,
+    candles: &[Candle],
+    batch_size: usize,
+    insert_mode: InsertMode,
+) -> Result<u64, IngestError> {
+    if candles.is_empty() {
+        return Ok(0);
+    }
+    let series = [exchange, market.as_str(), symbol, timeframe, source];
+    match insert_mode {
+        InsertMode::Copy => copy_candles(client, series, candles).await,
+        InsertMode::Rows => insert_rows(client, series, candles, batch_size).await,
+    }
+}
+
+async fn copy_candles(
+    client: &mut PgClient,
+    [exchange, market, symbol, timeframe, source]: [&str; 5],
+    candles: &[Candle],
+) -> Result<u64, IngestError> {
+    let transaction = client
+        .transaction()
+        .await
+        .map_err(IngestError::db("failed to start transaction"))?;
+    transaction
+        .batch_execute(
+            "CREATE TEMP TABLE ohlcv_candles_stage (
+                seq BIGINT NOT NULL,
+                timestamp_utc TIMESTAMPTZ NOT NULL,
+                open DOUBLE PRECISION NOT NULL,
+                high DOUBLE PRECISION NOT NULL,
+                low DOUBLE PRECISION NOT NULL,
+                close DOUBLE PRECISION NOT NULL,
+                volume DOUBLE PRECISION NOT NULL,
+                turnover DOUBLE PRECISION
+            ) ON COMMIT DROP",
+        )
+        .await
+        .map_err(IngestError::db("failed to create staging table"))?;
+
+    let sink = transaction
+        .copy_in(
+            "COPY ohlcv_candles_stage (seq, timestamp_utc, open, high, low, close, volume, turnover)
+             FROM STDIN BINARY",
+        )
+        .await
+        .map_err(IngestError::db("failed to start copy"))?;
+    let writer = BinaryCopyInWriter::new(
+        sink,
+        &[
+            Type::INT8,
+            Type::TIMESTAMPTZ,
+            Type::FLOAT8,
+            Type::FLOAT8,
+            Type::FLOAT8,
+            Type::FLOAT8,
+            Type::FLOAT8,
+            Type::FLOAT8,
+        ],
+    );
+    let mut writer = std::pin::pin!(writer);
+    for (seq, candle) in candles.iter().enumerate() {
+        writer
+            .as_mut()
+            .write(&[
+                &(seq as i64),
+                &candle.timestamp,
+                &candle.open,
+                &candle.high,
+                &candle.low,
+                &candle.close,
+                &candle.volume,
+                &candle.turnover,
+            ])
+            .await
+            .map_err(IngestError::db("copy failed"))?;
+    }
+    let copied = writer
+        .finish()
+        .await
+        .map_err(IngestError::db("failed to finish copy"))?;
+
+    // ON CONFLICT cannot touch a row twice in one statement, so repeated timestamps are reduced
+    // to their last candle first, matching what row-by-row upserts leave behind.
+    transaction
+        .execute(
+            &format!(
+                "INSERT INTO ohlcv_candles (
+                    exchange,
+                    market,
+                    symbol,
+                    timeframe,
+                    timestamp_utc,
+                    open,
+                    high,
+                    low,
+                    close,
+                    volume,
+                    turnover,
+                    source
+                )
+                SELECT DISTINCT ON (timestamp_utc)
+                    $1::TEXT, $2::TEXT, $3::TEXT, $4::TEXT,
+                    timestamp_utc, open, high, low, close, volume, turnover,
+                    $5::TEXT
+                FROM ohlcv_candles_stage
+                ORDER BY timestamp_utc, seq DESC
+                {ON_CONFLICT_UPDATE}"
+            ),
+            &[&exchange, &market, &symbol, &timeframe, &source],
+        )
+        .await
+        .map_err(IngestError::db("merge from staging table failed"))?;
+
+    transaction
+        .commit()
+        .await
+        .map_err(IngestError::db("failed to commit"))?;
+    Ok(copied)
+}
+
+async fn insert_rows(
+    client: &mut PgClient,
+    [exchange, market, symbol, timeframe, source]: [&str; 5],
+    candles: &[Candle],
+    batch_size: usize,
+) -> Result<u64, IngestError> {
+    let statement = client
+        .prepare(&format!(
+            "INSERT INTO ohlcv_candles (
+                exchange,
+                market,
+                symbol,
+                timeframe,
+                timestamp_utc,
+                open,
+                high,
+                low,
+                close,
+                volume,
+                turnover,
+                source
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12
+            )
+            {ON_CONFLICT_UPDATE}"
+        ))
+        .await
+        .map_err(IngestError::db("failed to prepare upsert"))?;
+
+    let mut total = 0u64;
+    let transaction = client
+        .transaction()
+        .await
+        .map_err(IngestError::db("failed to start transaction"))?;
+
+    for chunk in candles.chunks(batch_size.max(1)) {
+        for candle in chunk {
+            transaction
+                .execute(
+                    &statement,
+                    &[
+                        &exchange,
+                        &market,
+                        &symbol,
+                        &timeframe,
+                        &candle.timestamp,
+                        &candle.open,
+                        &candle.high,
+                        &candle.low,
+                        &candle.close,
+                        &candle.volume,
+                        &candle.turnover,
+                        &source,
+                    ],
+                )
+                .await
+                .map_err(IngestError::db("upsert failed"))?;
+            total += 1;
+        }
+    }
+
+    transaction
+        .commit()
+        .await
+        .map_err(IngestError::db("failed to commit"))?;
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{upsert_candles, InsertMode};
+    use crate::{migrate_db, Candle, Market};
+    use chrono::{TimeZone, Utc};
+    use std::path::Path;
+    use tokio_postgres::NoTls;
+
+    #[tokio::test]
+    async fn copy_and_row_upserts_leave_the_same_rows() {
+        if std::env::var("KAIROS_DB_RUN_TESTS").ok().as_deref() != Some("1") {
+            return;
+        }
+        let Ok(db_url) = std::env::var("KAIROS_DB_URL") else {
+            return;
+        };
+        let migrations =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../../platform/ops/migrations");
+        migrate_db(&db_url, &migrations).await.expect("migrate");
+        let (mut client, connection) = tokio_postgres::connect(&db_url, NoTls)
+            .await
+            .expect("connect");
+        tokio::spawn(connection);
+
+        let candle = |ts: i64, close: f64| Candle {
+            timestamp: Utc.timestamp_opt(ts, 0).single().unwrap(),
+            open: 1.0,
+            high: 2.0,
+            low: 0.5,
+            close,
+            volume: 10.0,
+            turnover: (ts % 120 == 0).then_some(close * 10.0),
+        };
+        // 60 is given twice; the later candle wins. The second call rewrites 120.
+        let first = [
+            candle(0, 1.0),
+            candle(60, 1.5),
+            candle(60, 1.6),
+            candle(120, 1.7),
+        ];
+        let second = [candle(120, 1.8), candle(180, 1.9)];
+
+        let mut stored = Vec::new();
+        for mode in [InsertMode::Copy, InsertMode::Rows] {
+            let symbol = format!("UPSERT-{mode:?}-{}", std::process::id());
+            for candles in [&first[..], &second[..]] {
+                let written = upsert_candles(
+                    &mut client,
+                    "test",
+                    &Market::Spot,
+                    &symbol,
+                    "1min",
+                    "test",
+                    candles,
+                    2,
+                    mode,
+                )
+                .await
+                .expect("upsert");
+                assert_eq!(written, candles.len() as u64);
+            }
+            let rows = client
+                .query(
+                    "SELECT EXTRACT(EPOCH FROM timestamp_utc)::BIGINT, close, turnover
+                     FROM ohlcv_candles WHERE exchange = 'test' AND symbol = $1
+                     ORDER BY timestamp_utc",
+                    &[&symbol],
+                )
+                .await
+                .expect("query");
+            client
+                .execute(
+                    "DELETE FROM ohlcv_candles WHERE exchange = 'test' AND symbol = $1",
+                    &[&symbol],
+                )
+                .await
+                .expect("cleanup");
+            stored.push(
+                rows.iter()
+                    .map(|row| (row.get(0), row.get(1), row.get(2)))
+                    .collect::<Vec<(i64, f64, Option<f64>)>>(),
+            );
+        }
+        assert_eq!(
+            stored[0],
+            vec![
+                (0, 1.0, Some(10.0)),
+                (60, 1.6, None),
+                (120, 1.8, Some(18.0)),
+                (180, 1.9, None)
+            ]
+        );
+        assert_eq!(stored[0], stored[1]);
+    }
+}
@@ -0,0 +1,22 @@
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    // Refresh the embedded SHA when HEAD moves (checkout/commit), not only on build.rs edits.
+    let git_head = Path::new("../../.git/HEAD");
+    if git_head.exists() {
+        println!("cargo:rerun-if-changed=../../.git/HEAD");
+        println!("cargo:rerun-if-changed=../../.git/refs/heads");
+    }
+
+    let git_sha = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=KAIROS_GIT_SHA={git_sha}");
+}
@@ -0,0 +1,56 @@
+use kairos_domain::entities::run_note::{RunNote, RUN_NOTES_FILE_NAME};
+use kairos_domain::repositories::artifacts::{ArtifactReader, ArtifactWriter};
+use std::path::Path;
+use tracing::info_span;
+
+pub fn notes_path(run_dir: &Path) -> std::path::PathBuf {
+    run_dir.join(RUN_NOTES_FILE_NAME)
+}
+
+// Appends a note to `<run_dir>/notes.jsonl`. Notes are never rewritten, so earlier annotations
+// stay in the order they were made.
+pub fn annotate_run(
+    run_dir: &Path,
+    note: &str,
+    reader: &dyn ArtifactReader,
+    writer: &dyn ArtifactWriter,
+) -> Result<RunNote, String> {
+    let _span = info_span!("annotate_run", run_dir = %run_dir.display()).entered();
+
+    let note = note.trim();
+    if note.is_empty() {
+        return Err("note must not be empty".to_string());
+    }
+    if !reader.exists(run_dir) {
+        return Err(format!("run dir not found: {}", run_dir.display()));
+    }
+
+    let record = RunNote {
+        created_at: chrono::Utc::now().to_rfc3339(),
+        note: note.to_string(),
+    };
+    let value =
+        serde_json::to_value(&record).map_err(|err| format!("failed to serialize note: {err}"))?;
+    writer.append_jsonl(&notes_path(run_dir), &[value])?;
+    metrics::counter!("kairos.annotations.notes_total").increment(1);
+    Ok(record)
+}
+
+pub fn read_notes(run_dir: &Path, reader: &dyn ArtifactReader) -> Result<Vec<RunNote>, String> {
+    let path = notes_path(run_dir);
+    if !reader.exists(&path) {
+        return Ok(Vec::new());
+    }
+    let (records, _) = reader.read_jsonl_from(&path, 0)?;
+    Ok(records
+        .into_iter()
+        .filter_map(|record| serde_json::from_value(record).ok())
+        .collect())
+}
+
+// For callers that scan run dirs directly (registry, TUI run browser); a missing file means no notes.
+pub fn load_notes(run_dir: &Path) -> Vec<RunNote> {
+    std::fs::read_to_string(notes_path(run_dir))
+        .map(|raw| RunNote::parse_jsonl(&raw))
+        .unwrap_or_default()
+}
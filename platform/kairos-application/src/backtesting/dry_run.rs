@@ -0,0 +1,172 @@
+use crate::config::{build_ohlcv_query, AgentMode, Config, ConfigError};
+use crate::diagnostics::CheckStatus;
+use crate::error::RunError;
+use crate::shared::{
+    build_feature_config, build_sentiment_inputs, load_sentiment, normalize_timeframe_label,
+    parse_duration_like, resolve_execution_config,
+};
+use kairos_domain::repositories::agent::AgentClient as AgentPort;
+use kairos_domain::repositories::market_data::{MarketDataRepository, OhlcvQuery};
+use kairos_domain::repositories::sentiment::SentimentRepository;
+use kairos_domain::services::features::FeatureBuilder;
+use kairos_domain::services::ohlcv::resample_bars;
+use kairos_domain::services::sentiment;
+use tracing::info_span;
+
+pub const DEFAULT_DRY_RUN_SAMPLE_BARS: u64 = 500;
+
+// What `backtest --dry-run` found without running the engine: the scope of the real run, a
+// sample of it pushed through resampling and the feature builder, and the strategy it would use.
+#[derive(Debug, Clone)]
+pub struct DryRunReport {
+    pub run_id: String,
+    pub symbol: String,
+    pub timeframe: String,
+    pub source_timeframe: String,
+    // Source bars the full run would load (after `run.max_bars`), and their time range.
+    pub rows: u64,
+    pub first_ts: Option<i64>,
+    pub last_ts: Option<i64>,
+    // Bars of the sample at the run timeframe.
+    pub sample_rows: usize,
+    pub feature_count: usize,
+    pub sentiment_points: Option<usize>,
+    pub strategy: &'static str,
+    pub agent: CheckStatus,
+    pub agent_detail: String,
+}
+
+// `agent` is pinged when given; without it remote configs report the agent as skipped.
+pub fn dry_run_backtest(
+    config: &Config,
+    market_data: &dyn MarketDataRepository,
+    sentiment_repo: &dyn SentimentRepository,
+    agent: Option<&dyn AgentPort>,
+    sample_bars: u64,
+) -> Result<DryRunReport, RunError> {
+    let _span = info_span!("dry_run", run_id = %config.run.run_id).entered();
+
+    let expected_step = parse_duration_like(&config.run.timeframe).map_err(ConfigError::Invalid)?;
+    let timeframe_label =
+        normalize_timeframe_label(&config.run.timeframe).map_err(ConfigError::Invalid)?;
+    let source_timeframe_label = normalize_timeframe_label(
+        config
+            .db
+            .source_timeframe
+            .as_deref()
+            .unwrap_or(&timeframe_label),
+    )
+    .map_err(ConfigError::Invalid)?;
+    let source_step = parse_duration_like(&source_timeframe_label).map_err(ConfigError::Invalid)?;
+    if source_step > expected_step {
+        return Err(ConfigError::Invalid(format!(
+            "cannot resample OHLCV: source timeframe ({}) is larger than run timeframe ({})",
+            source_timeframe_label, timeframe_label
+        ))
+        .into());
+    }
+    resolve_execution_config(config).map_err(ConfigError::Invalid)?;
+
+    let query = build_ohlcv_query(config, &source_timeframe_label, source_step)?;
+    let coverage = market_data.ohlcv_coverage(&query).map_err(RunError::Data)?;
+    let rows = query
+        .limit
+        .map_or(coverage.rows, |limit| coverage.rows.min(limit));
+    if rows == 0 {
+        return Err(RunError::Data(format!(
+            "no OHLCV rows for {} {} ({}/{}) in the configured range",
+            query.symbol, source_timeframe_label, query.exchange, query.market
+        )));
+    }
+
+    // Enough source bars to fill `sample_bars` bars at the run timeframe.
+    let per_bar = (expected_step / source_step).max(1) as u64;
+    let sample_query = OhlcvQuery {
+        limit: Some(sample_bars.max(1).saturating_mul(per_bar).min(rows)),
+        ..query
+    };
+    let (source_sample, _) = market_data
+        .load_ohlcv(&sample_query)
+        .map_err(RunError::Data)?;
+    let sample = if source_step != expected_step {
+        resample_bars(&source_sample, expected_step)?
+    } else {
+        source_sample
+    };
+
+    let sentiment_points = match build_sentiment_inputs(config).map_err(ConfigError::Invalid)? {
+        Some(inputs) => Some(load_sentiment(config, &inputs, sentiment_repo)?.points),
+        None => None,
+    };
+
+    let sentiment_lag =
+        parse_duration_like(&config.features.sentiment_lag).map_err(ConfigError::Invalid)?;
+    let timestamps: Vec<i64> = sample.iter().map(|bar| bar.timestamp).collect();
+    let aligned = sentiment_points
+        .as_ref()
+        .map(|points| sentiment::align_with_bars(&timestamps, points, sentiment_lag))
+        .unwrap_or_else(|| vec![None; sample.len()]);
+    let mut builder = FeatureBuilder::new(build_feature_config(config));
+    let feature_count = sample
+        .iter()
+        .zip(&aligned)
+        .map(|(bar, point)| {
+            builder
+                .update(bar, point.as_ref().map(|point| point.values.as_slice()))
+                .values
+                .len()
+        })
+        .last()
+        .unwrap_or(0);
+
+    let (agent, agent_detail) = match (config.agent.mode, agent) {
+        (AgentMode::Remote, Some(agent)) => match agent.health_check() {
+            Ok(()) => (
+                CheckStatus::Pass,
+                format!("{} is healthy", config.agent.url),
+            ),
+            Err(err) => (CheckStatus::Fail, err.to_string()),
+        },
+        (AgentMode::Remote, None) => (CheckStatus::Skip, "not pinged".to_string()),
+        (mode, _) => (
+            CheckStatus::Skip,
+            format!("agent.mode = {mode:?}").to_lowercase(),
+        ),
+    };
+
+    Ok(DryRunReport {
+        run_id: config.run.run_id.clone(),
+        symbol: config.run.symbol.clone(),
+        timeframe: timeframe_label,
+        source_timeframe: source_timeframe_label,
+        rows,
+        first_ts: coverage.first_ts,
+        last_ts: coverage.last_ts,
+        sample_rows: sample.len(),
+        feature_count,
+        sentiment_points: sentiment_points.as_ref().map(Vec::len),
+        strategy: strategy_name(config),
+        agent,
+        agent_detail,
+    })
+}
+
+// Name the backtest's strategy reports (`Strategy::name`), resolved from the config alone.
+fn strategy_name(config: &Config) -> &'static str {
+    match config.agent.mode {
+        AgentMode::Remote => "agent_remote",
+        AgentMode::Hold => "hold",
+        AgentMode::Baseline => {
+            let baseline = config
+                .strategy
+                .as_ref()
+                .map(|strategy| strategy.baseline.as_str())
+                .unwrap_or("buy_and_hold");
+            if baseline == "sma" {
+                "simple_sma"
+            } else {
+                "buy_and_hold"
+            }
+        }
+    }
+}
@@ -1,32 +1,38 @@
-use crate::config::{AgentMode, Config};
+use crate::config::{build_ohlcv_query, AgentMode, Config, ConfigError};
+use crate::error::RunError;
+use crate::reproducibility::{build_reproducibility, fingerprint_bars};
 use crate::shared::{
-    build_metrics_config, config_snapshot_json, normalize_timeframe_label, parse_duration_like,
-    resolve_execution_config, resolve_sentiment_missing_policy, resolve_size_mode,
-    resolve_sma_windows, summary_meta_json_from_equity,
+    build_feature_config, build_metrics_config, build_sentiment_inputs, config_snapshot_json,
+    ensure_not_cancelled, finalize_run_dir, load_ohlcv_with_control, load_sentiment,
+    normalize_timeframe_label, parse_duration_like, resolve_artifact_compression,
+    resolve_execution_config, resolve_size_mode, resolve_sma_windows, sentiment_sources_json,
+    summary_meta_json_from_equity,
 };
 use kairos_domain::entities::risk::RiskLimits;
+use kairos_domain::entities::run_summary::DataFingerprint;
 use kairos_domain::repositories::agent::AgentClient as AgentPort;
-use kairos_domain::repositories::artifacts::ArtifactWriter;
-use kairos_domain::repositories::market_data::{MarketDataRepository, OhlcvQuery};
-use kairos_domain::repositories::sentiment::{
-    SentimentFormat, SentimentQuery, SentimentRepository,
-};
+use kairos_domain::repositories::artifacts::{ArtifactCompression, ArtifactWriter};
+use kairos_domain::repositories::market_data::MarketDataRepository;
+use kairos_domain::repositories::sentiment::SentimentRepository;
 use kairos_domain::services::analyzers::{built_in_analyzers, AnalyzerInput};
 use kairos_domain::services::audit::AuditEvent;
 use kairos_domain::services::engine::backtest::{
-    BacktestResults, BacktestRunError, BacktestRunner, BarProgress, NoopControl, RunControl,
+    BacktestResults, BacktestRunner, BarProgress, NoopControl, RunControl,
 };
 use kairos_domain::services::features;
 use kairos_domain::services::market_data_source::VecBarSource;
 use kairos_domain::services::ohlcv::{data_quality_from_bars, resample_bars};
+use kairos_domain::services::progress::{stage, ProgressEvent, ProgressSink};
 use kairos_domain::services::sentiment;
 use kairos_domain::services::strategy::{
-    AgentStrategy, BuyAndHold, HoldStrategy, SimpleSma, StrategyKind,
+    AgentBudget, AgentStrategy, BuyAndHold, HoldStrategy, SimpleSma, StrategyKind,
 };
 use std::path::PathBuf;
 use std::time::Instant;
 use tracing::info_span;
 
+pub mod dry_run;
+
 pub fn run_backtest(
     config: &Config,
     config_toml: &str,
@@ -35,7 +41,7 @@ pub fn run_backtest(
     sentiment_repo: &dyn SentimentRepository,
     artifacts: &dyn ArtifactWriter,
     remote_agent: Option<Box<dyn AgentPort>>,
-) -> Result<PathBuf, String> {
+) -> Result<PathBuf, RunError> {
     run_backtest_streaming(
         config,
         config_toml,
@@ -44,7 +50,7 @@ pub fn run_backtest(
         sentiment_repo,
         artifacts,
         remote_agent,
-        &mut |_progress: BarProgress| {},
+        &mut |_event: ProgressEvent<'_>| {},
     )
 }
 
@@ -57,8 +63,8 @@ pub fn run_backtest_streaming(
     sentiment_repo: &dyn SentimentRepository,
     artifacts: &dyn ArtifactWriter,
     remote_agent: Option<Box<dyn AgentPort>>,
-    progress: &mut dyn FnMut(BarProgress),
-) -> Result<PathBuf, String> {
+    progress: &mut dyn ProgressSink,
+) -> Result<PathBuf, RunError> {
     let control = NoopControl;
     run_backtest_streaming_control(
         config,
@@ -83,8 +89,36 @@ pub fn run_backtest_streaming_control(
     artifacts: &dyn ArtifactWriter,
     remote_agent: Option<Box<dyn AgentPort>>,
     control: &dyn RunControl,
-    progress: &mut dyn FnMut(BarProgress),
-) -> Result<PathBuf, String> {
+    progress: &mut dyn ProgressSink,
+) -> Result<PathBuf, RunError> {
+    run_backtest_seeded(
+        config,
+        config_toml,
+        out,
+        market_data,
+        sentiment_repo,
+        artifacts,
+        remote_agent,
+        control,
+        progress,
+        None,
+    )
+}
+
+// `seed` is recorded in summary.json when a seeded search picked this run (sweep trials).
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_backtest_seeded(
+    config: &Config,
+    config_toml: &str,
+    out: Option<PathBuf>,
+    market_data: &dyn MarketDataRepository,
+    sentiment_repo: &dyn SentimentRepository,
+    artifacts: &dyn ArtifactWriter,
+    remote_agent: Option<Box<dyn AgentPort>>,
+    control: &dyn RunControl,
+    progress: &mut dyn ProgressSink,
+    seed: Option<u64>,
+) -> Result<PathBuf, RunError> {
     let _span = info_span!(
         "run_backtest",
         run_id = %config.run.run_id,
@@ -95,39 +129,44 @@ pub fn run_backtest_streaming_control(
 
     let mut audit_extras: Vec<AuditEvent> = Vec::new();
 
-    let expected_step = parse_duration_like(&config.run.timeframe)?;
-    let timeframe_label = normalize_timeframe_label(&config.run.timeframe)?;
+    let expected_step = parse_duration_like(&config.run.timeframe).map_err(ConfigError::Invalid)?;
+    let timeframe_label =
+        normalize_timeframe_label(&config.run.timeframe).map_err(ConfigError::Invalid)?;
     let source_timeframe_label = normalize_timeframe_label(
         config
             .db
             .source_timeframe
             .as_deref()
             .unwrap_or(&timeframe_label),
-    )?;
-    let source_step = parse_duration_like(&source_timeframe_label)?;
+    )
+    .map_err(ConfigError::Invalid)?;
+    let source_step = parse_duration_like(&source_timeframe_label).map_err(ConfigError::Invalid)?;
 
+    let query = build_ohlcv_query(config, &source_timeframe_label, source_step)?;
     let stage_start = Instant::now();
-    let (source_bars, source_report) = market_data.load_ohlcv(&OhlcvQuery {
-        exchange: config.db.exchange.to_lowercase(),
-        market: config.db.market.to_lowercase(),
-        symbol: config.run.symbol.clone(),
-        timeframe: source_timeframe_label.clone(),
-        expected_step_seconds: Some(source_step),
+    let (source_bars, source_report) = stage(progress, "load_ohlcv", || {
+        load_ohlcv_with_control(market_data, &query, control, "backtest")
     })?;
     metrics::histogram!("kairos.backtest.load_ohlcv_ms")
         .record(stage_start.elapsed().as_millis() as f64);
 
     let (bars, data_report, resampled) = if source_timeframe_label != timeframe_label {
         if source_step > expected_step {
-            return Err(format!(
+            return Err(ConfigError::Invalid(format!(
                 "cannot resample OHLCV: source timeframe ({}) is larger than run timeframe ({})",
                 source_timeframe_label, timeframe_label
-            ));
+            ))
+            .into());
         }
 
         let resample_start = Instant::now();
-        let resampled_bars = resample_bars(&source_bars, expected_step)?;
-        let report = data_quality_from_bars(&resampled_bars, Some(expected_step));
+        let resampled_bars = stage(progress, "resample_ohlcv", || {
+            resample_bars(&source_bars, expected_step)
+        })?;
+        ensure_not_cancelled(control, "backtest")?;
+        let mut report = data_quality_from_bars(&resampled_bars, Some(expected_step));
+        report.turnover_mismatches = source_report.turnover_mismatches;
+        report.first_turnover_mismatch = source_report.first_turnover_mismatch;
         metrics::histogram!("kairos.backtest.resample_ms")
             .record(resample_start.elapsed().as_millis() as f64);
         audit_extras.push(timing_event(
@@ -162,64 +201,67 @@ pub fn run_backtest_streaming_control(
             "gaps": data_report.gaps,
             "out_of_order": data_report.out_of_order,
             "invalid_close": data_report.invalid_close,
+            "zero_volume_moves": data_report.zero_volume_moves,
+            "turnover_mismatches": data_report.turnover_mismatches,
             "resampled": resampled,
         }),
     ));
+    if data_report.zero_volume_moves > 0 || data_report.turnover_mismatches > 0 {
+        tracing::warn!(
+            zero_volume_moves = data_report.zero_volume_moves,
+            turnover_mismatches = data_report.turnover_mismatches,
+            "OHLCV volume looks inconsistent; volume-based execution models may misbehave"
+        );
+    }
 
-    let sentiment_points = if let Some(path) = &config.paths.sentiment_path {
-        let stage_start = Instant::now();
-        let path_buf = PathBuf::from(path);
-        let ext = path_buf
-            .extension()
-            .and_then(|s| s.to_str())
-            .unwrap_or("")
-            .to_lowercase();
-        let format = if ext == "json" {
-            SentimentFormat::Json
-        } else {
-            SentimentFormat::Csv
-        };
-        let missing_policy = resolve_sentiment_missing_policy(config);
-        let (points, report) = sentiment_repo.load_sentiment(&SentimentQuery {
-            path: path_buf,
-            format,
-            missing_policy,
-        })?;
-        metrics::histogram!("kairos.backtest.load_sentiment_ms")
-            .record(stage_start.elapsed().as_millis() as f64);
+    let sentiment_points =
+        if let Some(inputs) = build_sentiment_inputs(config).map_err(ConfigError::Invalid)? {
+            let stage_start = Instant::now();
+            let loaded = stage(progress, "load_sentiment", || {
+                load_sentiment(config, &inputs, sentiment_repo)
+            })?;
+            let (points, report) = (loaded.points, loaded.report);
+            metrics::histogram!("kairos.backtest.load_sentiment_ms")
+                .record(stage_start.elapsed().as_millis() as f64);
 
-        audit_extras.push(timing_event(
-            &config.run.run_id,
-            0,
-            "timing",
-            Some(&config.run.symbol),
-            "load_sentiment",
-            stage_start.elapsed().as_millis() as u64,
-            serde_json::json!({
-                "rows": points.len(),
-                "duplicates": report.duplicates,
-                "out_of_order": report.out_of_order,
-                "missing_values": report.missing_values,
-                "invalid_values": report.invalid_values,
-                "dropped_rows": report.dropped_rows,
-                "schema": report.schema,
-            }),
-        ));
+            audit_extras.push(timing_event(
+                &config.run.run_id,
+                0,
+                "timing",
+                Some(&config.run.symbol),
+                "load_sentiment",
+                stage_start.elapsed().as_millis() as u64,
+                serde_json::json!({
+                    "rows": points.len(),
+                    "duplicates": report.duplicates,
+                    "out_of_order": report.out_of_order,
+                    "missing_values": report.missing_values,
+                    "invalid_values": report.invalid_values,
+                    "dropped_rows": report.dropped_rows,
+                    "schema": report.schema,
+                    "sources": sentiment_sources_json(&loaded.sources),
+                }),
+            ));
 
-        Some(points)
-    } else {
-        None
-    };
+            Some(points)
+        } else {
+            None
+        };
+    ensure_not_cancelled(control, "backtest")?;
 
-    let sentiment_lag = parse_duration_like(&config.features.sentiment_lag)?;
+    let sentiment_lag =
+        parse_duration_like(&config.features.sentiment_lag).map_err(ConfigError::Invalid)?;
     let bar_timestamps: Vec<i64> = bars.iter().map(|bar| bar.timestamp).collect();
     let stage_start = Instant::now();
-    let aligned_sentiment = sentiment_points
-        .as_ref()
-        .map(|points| sentiment::align_with_bars(&bar_timestamps, points, sentiment_lag))
-        .unwrap_or_else(|| vec![None; bars.len()]);
+    let aligned_sentiment = stage(progress, "align_sentiment", || {
+        sentiment_points
+            .as_ref()
+            .map(|points| sentiment::align_with_bars(&bar_timestamps, points, sentiment_lag))
+            .unwrap_or_else(|| vec![None; bars.len()])
+    });
     metrics::histogram!("kairos.backtest.align_sentiment_ms")
         .record(stage_start.elapsed().as_millis() as f64);
+    ensure_not_cancelled(control, "backtest")?;
     audit_extras.push(timing_event(
         &config.run.run_id,
         0,
@@ -232,23 +274,7 @@ pub fn run_backtest_streaming_control(
         }),
     ));
 
-    let feature_config = features::FeatureConfig {
-        return_mode: config.features.return_mode,
-        sma_windows: config
-            .features
-            .sma_windows
-            .iter()
-            .map(|w| *w as usize)
-            .collect(),
-        volatility_windows: config
-            .features
-            .volatility_windows
-            .as_ref()
-            .map(|windows| windows.iter().map(|w| *w as usize).collect())
-            .unwrap_or_default(),
-        rsi_enabled: config.features.rsi_enabled,
-    };
-    let builder = features::FeatureBuilder::new(feature_config);
+    let builder = features::FeatureBuilder::new(build_feature_config(config));
 
     let risk_limits = RiskLimits {
         max_position_qty: config.risk.max_position_qty,
@@ -261,22 +287,30 @@ pub fn run_backtest_streaming_control(
     let strategy = match config.agent.mode {
         AgentMode::Remote => {
             let Some(agent) = remote_agent else {
-                return Err("agent.mode=remote requires a remote_agent client".to_string());
+                return Err(RunError::Runtime(
+                    "agent.mode=remote requires a remote_agent client".to_string(),
+                ));
             };
             let fallback_action = config.agent.fallback_action;
             let agent_url = config.agent.url.clone();
-            StrategyKind::Agent(AgentStrategy::new(
-                config.run.run_id.clone(),
-                config.run.symbol.clone(),
-                config.run.timeframe.clone(),
-                config.agent.api_version.clone(),
-                config.agent.feature_version.clone(),
-                agent_url,
-                fallback_action,
-                agent,
-                builder,
-                aligned_sentiment,
-            ))
+            StrategyKind::Agent(
+                AgentStrategy::new(
+                    config.run.run_id.clone(),
+                    config.run.symbol.clone(),
+                    config.run.timeframe.clone(),
+                    config.agent.api_version.clone(),
+                    config.agent.feature_version.clone(),
+                    agent_url,
+                    fallback_action,
+                    agent,
+                    builder,
+                    aligned_sentiment,
+                )
+                .with_budget(AgentBudget {
+                    max_calls: config.agent.max_calls,
+                    max_total_ms: config.agent.max_total_ms,
+                }),
+            )
         }
         AgentMode::Baseline => {
             let baseline = config
@@ -296,8 +330,10 @@ pub fn run_backtest_streaming_control(
     };
 
     let metrics_config = build_metrics_config(config);
-    let execution = resolve_execution_config(config)?;
+    let execution = resolve_execution_config(config).map_err(ConfigError::Invalid)?;
+    let compression = resolve_artifact_compression(config).map_err(ConfigError::Invalid)?;
 
+    let data_fingerprint = fingerprint_bars(&bars);
     let data = VecBarSource::new(bars);
     let stage_start = Instant::now();
     let mut runner = BacktestRunner::new_with_execution(
@@ -312,11 +348,20 @@ pub fn run_backtest_streaming_control(
         size_mode,
         execution.clone(),
     );
-    let results = runner
-        .run_with_progress_control(progress, control)
-        .map_err(|err| match err {
-            BacktestRunError::Cancelled => "backtest cancelled".to_string(),
-        })?;
+    progress.emit(ProgressEvent::StageStarted {
+        stage: "run_engine",
+    });
+    let results = info_span!("run_engine", bars = bar_timestamps.len()).in_scope(|| {
+        runner.run_with_progress_control(
+            &mut |bar: BarProgress| progress.emit(ProgressEvent::BarProcessed(&bar)),
+            control,
+        )
+    });
+    progress.emit(ProgressEvent::StageFinished {
+        stage: "run_engine",
+        duration_ms: stage_start.elapsed().as_millis() as u64,
+    });
+    let results = results.map_err(RunError::engine("backtest"))?;
     let engine_ms = stage_start.elapsed().as_millis() as f64;
     metrics::histogram!("kairos.backtest.engine_ms").record(engine_ms);
     metrics::gauge!("kairos.backtest.bars_processed").set(results.summary.bars_processed as f64);
@@ -336,15 +381,21 @@ pub fn run_backtest_streaming_control(
         serde_json::json!({}),
     ));
 
-    write_outputs(
-        config,
-        config_toml,
-        out,
-        results,
-        &execution,
-        artifacts,
-        audit_extras,
-    )
+    stage(progress, "write_outputs", || {
+        write_outputs(
+            config,
+            config_toml,
+            out,
+            results,
+            &execution,
+            compression,
+            artifacts,
+            audit_extras,
+            data_fingerprint,
+            seed,
+        )
+    })
+    .map_err(RunError::Artifacts)
 }
 
 fn timing_event(
@@ -370,28 +421,46 @@ fn timing_event(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn write_outputs(
     config: &Config,
     config_toml: &str,
     out: Option<PathBuf>,
     results: BacktestResults,
     execution: &kairos_domain::services::engine::execution::ExecutionConfig,
+    compression: Option<ArtifactCompression>,
     artifacts: &dyn ArtifactWriter,
     mut audit_extras: Vec<AuditEvent>,
+    data_fingerprint: DataFingerprint,
+    seed: Option<u64>,
 ) -> Result<PathBuf, String> {
+    let _span = info_span!("write_outputs", run_id = %config.run.run_id).entered();
     let base_dir = out.unwrap_or_else(|| PathBuf::from(&config.paths.out_dir));
     let run_dir = base_dir.join(&config.run.run_id);
     artifacts.ensure_dir(&run_dir)?;
 
     artifacts.write_trades_csv(run_dir.join("trades.csv").as_path(), &results.trades)?;
     artifacts.write_equity_csv(run_dir.join("equity.csv").as_path(), &results.equity)?;
+    if config
+        .report
+        .as_ref()
+        .and_then(|report| report.parquet)
+        .unwrap_or(false)
+    {
+        artifacts
+            .write_trades_parquet(run_dir.join("trades.parquet").as_path(), &results.trades)?;
+        artifacts
+            .write_equity_parquet(run_dir.join("equity.parquet").as_path(), &results.equity)?;
+    }
     let meta = summary_meta_json_from_equity(config, &results.equity);
     let config_snapshot = config_snapshot_json(config, execution);
+    let reproducibility = build_reproducibility(config, config_toml, data_fingerprint, seed);
     artifacts.write_summary_json(
         run_dir.join("summary.json").as_path(),
         &results.summary,
         meta.as_ref(),
         Some(&config_snapshot),
+        Some(&reproducibility),
     )?;
 
     let analyzers_dir = run_dir.join("analyzers");
@@ -470,6 +539,7 @@ fn write_outputs(
 
     artifacts
         .write_config_snapshot_toml(run_dir.join("config_snapshot.toml").as_path(), config_toml)?;
+    finalize_run_dir(&run_dir, compression, artifacts)?;
 
     Ok(run_dir)
 }
@@ -1,16 +1,29 @@
 use kairos_domain::entities::metrics::MetricsConfig;
 use kairos_domain::entities::risk::RiskLimits;
+use kairos_domain::errors::AgentError;
+use kairos_domain::repositories::agent::AgentClient;
+use kairos_domain::services::agent::{
+    ActionBatchRequest, ActionBatchResponse, ActionRequest, ActionResponse,
+};
 use kairos_domain::services::engine::backtest::{BacktestResults, BacktestRunner, OrderSizeMode};
 use kairos_domain::services::features;
 use kairos_domain::services::market_data_source::VecBarSource;
-use kairos_domain::services::strategy::BuyAndHold;
-use std::time::Instant;
+use kairos_domain::services::strategy::{AgentStrategy, BuyAndHold, Strategy};
+use kairos_domain::value_objects::action::Action;
+use kairos_domain::value_objects::action_type::ActionType;
+use kairos_domain::value_objects::bar::Bar;
+use kairos_domain::value_objects::bar_columns::BarColumns;
+use kairos_domain::value_objects::symbol::Symbol;
+use std::time::{Duration, Instant};
 use tracing::info_span;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BenchMode {
     Engine,
     Features,
+    // Feature pipeline plus an in-process mock agent answering every bar after a simulated
+    // round trip, to estimate how long a remote-agent backtest will take.
+    Agent,
 }
 
 pub struct BenchSummary {
@@ -19,10 +32,201 @@ pub struct BenchSummary {
     pub bars_processed: u64,
     pub elapsed_ms: u64,
     pub bars_per_sec: f64,
+    // Memory use of the engine stage; `None` unless the run was given a `MemoryProbe`.
+    pub memory: Option<BenchMemory>,
     pub results: BacktestResults,
 }
 
+// Each figure is `None` when the probe cannot measure it (e.g. allocation counts without a
+// counting allocator, peak RSS off Linux).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BenchMemory {
+    pub peak_rss_bytes: Option<u64>,
+    pub allocations: Option<u64>,
+    pub allocated_bytes: Option<u64>,
+    pub peak_live_bytes: Option<u64>,
+}
+
+impl BenchMemory {
+    // Field names as they appear under `memory` in the bench JSON.
+    pub fn fields(&self) -> [(&'static str, Option<u64>); 4] {
+        [
+            ("peak_rss_bytes", self.peak_rss_bytes),
+            ("allocations", self.allocations),
+            ("allocated_bytes", self.allocated_bytes),
+            ("peak_live_bytes", self.peak_live_bytes),
+        ]
+    }
+}
+
+// Measures memory around the engine stage: `start` once the synthetic bars are built, `finish`
+// when the run ends.
+pub trait MemoryProbe {
+    fn start(&mut self);
+    fn finish(&mut self) -> BenchMemory;
+}
+
+impl BenchMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BenchMode::Engine => "engine",
+            BenchMode::Features => "features",
+            BenchMode::Agent => "agent",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct BenchOptions<'a> {
+    // Simulated round trip of each agent call in `agent` mode.
+    pub agent_latency_ms: f64,
+    pub memory_probe: Option<&'a mut dyn MemoryProbe>,
+}
+
+// Stands in for a remote agent: waits `latency` per request, then buys on up-moves and sells on
+// down-moves of the bar's return (the first observation value).
+struct SimulatedAgent {
+    latency: Duration,
+}
+
+impl SimulatedAgent {
+    fn respond(&self, observation: &[f64]) -> ActionResponse {
+        let ret = observation.first().copied().unwrap_or(0.0);
+        let action_type = if ret > 0.0 {
+            "BUY"
+        } else if ret < 0.0 {
+            "SELL"
+        } else {
+            "HOLD"
+        };
+        ActionResponse {
+            action_type: action_type.to_string(),
+            size: 1.0,
+            confidence: None,
+            model_version: Some("bench".to_string()),
+            latency_ms: Some(self.latency.as_millis() as u64),
+            reason: None,
+        }
+    }
+}
+
+impl AgentClient for SimulatedAgent {
+    fn act(&self, request: &ActionRequest) -> Result<ActionResponse, AgentError> {
+        if !self.latency.is_zero() {
+            std::thread::sleep(self.latency);
+        }
+        Ok(self.respond(&request.observation))
+    }
+
+    fn act_batch(&self, request: &ActionBatchRequest) -> Result<ActionBatchResponse, AgentError> {
+        if !self.latency.is_zero() {
+            std::thread::sleep(self.latency);
+        }
+        Ok(ActionBatchResponse {
+            items: request
+                .items
+                .iter()
+                .map(|item| self.respond(&item.observation))
+                .collect(),
+        })
+    }
+}
+
+// Throughput and memory of a run against a baseline: the JSON line a previous
+// `kairos-bench --json` run printed for the same mode. Memory figures are compared only when
+// both runs measured them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchComparison {
+    pub baseline_bars_per_sec: f64,
+    pub bars_per_sec: f64,
+    // Negative when slower than the baseline.
+    pub change_pct: f64,
+    pub memory: Vec<MemoryComparison>,
+    pub max_regression_pct: f64,
+    pub regressed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoryComparison {
+    pub field: &'static str,
+    pub baseline: u64,
+    pub current: u64,
+    // Positive when the run used more than the baseline.
+    pub change_pct: f64,
+    pub regressed: bool,
+}
+
+pub fn compare_to_baseline(
+    summary: &BenchSummary,
+    baseline: &serde_json::Value,
+    max_regression_pct: f64,
+) -> Result<BenchComparison, String> {
+    if !max_regression_pct.is_finite() || max_regression_pct < 0.0 {
+        return Err("--max-regression-pct must be >= 0".to_string());
+    }
+    let mode = summary.mode.as_str();
+    if let Some(baseline_mode) = baseline.get("mode").and_then(|value| value.as_str()) {
+        if baseline_mode != mode {
+            return Err(format!(
+                "baseline was recorded in mode {baseline_mode}, this run is {mode}"
+            ));
+        }
+    }
+    let baseline_bars_per_sec = baseline
+        .get("bars_per_sec")
+        .and_then(|value| value.as_f64())
+        .filter(|value| *value > 0.0)
+        .ok_or("baseline has no positive bars_per_sec")?;
+
+    let change_pct = (summary.bars_per_sec / baseline_bars_per_sec - 1.0) * 100.0;
+    let mut regressed = -change_pct > max_regression_pct;
+
+    let mut memory = Vec::new();
+    for (field, current) in summary.memory.unwrap_or_default().fields() {
+        let Some(current) = current else {
+            continue;
+        };
+        let Some(baseline) = baseline["memory"][field].as_u64() else {
+            continue;
+        };
+        let change_pct = if baseline > 0 {
+            (current as f64 / baseline as f64 - 1.0) * 100.0
+        } else if current > 0 {
+            f64::INFINITY
+        } else {
+            0.0
+        };
+        let field_regressed = change_pct > max_regression_pct;
+        regressed |= field_regressed;
+        memory.push(MemoryComparison {
+            field,
+            baseline,
+            current,
+            change_pct,
+            regressed: field_regressed,
+        });
+    }
+
+    Ok(BenchComparison {
+        baseline_bars_per_sec,
+        bars_per_sec: summary.bars_per_sec,
+        change_pct,
+        memory,
+        max_regression_pct,
+        regressed,
+    })
+}
+
 pub fn run_bench(bars: usize, step_seconds: i64, mode: &str) -> Result<BenchSummary, String> {
+    run_bench_with(bars, step_seconds, mode, BenchOptions::default())
+}
+
+pub fn run_bench_with(
+    bars: usize,
+    step_seconds: i64,
+    mode: &str,
+    options: BenchOptions<'_>,
+) -> Result<BenchSummary, String> {
     let _span = info_span!(
         "run_bench",
         bars = bars,
@@ -37,57 +241,28 @@ pub fn run_bench(bars: usize, step_seconds: i64, mode: &str) -> Result<BenchSumm
     if step_seconds <= 0 {
         return Err("step_seconds must be > 0".to_string());
     }
+    if !options.agent_latency_ms.is_finite() || options.agent_latency_ms < 0.0 {
+        return Err("agent_latency_ms must be >= 0".to_string());
+    }
+    let mut memory_probe = options.memory_probe;
 
     let bench_mode = match mode.trim().to_lowercase().as_str() {
         "engine" => BenchMode::Engine,
         "features" => BenchMode::Features,
-        _ => return Err("unsupported mode (use: engine | features)".to_string()),
+        "agent" => BenchMode::Agent,
+        _ => return Err("unsupported mode (use: engine | features | agent)".to_string()),
     };
 
-    let start_ts = 1_700_000_000i64;
-    let symbol = "BENCH";
-
-    let mut synthetic = Vec::with_capacity(bars);
-    let mut price = 100.0f64;
-    for i in 0..bars {
-        let t = start_ts + (i as i64) * step_seconds;
-        let drift = ((i as f64) * 0.000001).sin() * 0.05;
-        let shock = ((i as f64) * 0.001).cos() * 0.01;
-        let ret = drift + shock;
-        let open = price;
-        let close = (price * (1.0 + ret)).max(0.01);
-        let high = open.max(close) * 1.001;
-        let low = open.min(close) * 0.999;
-        let volume = 1000.0 + ((i as f64) * 0.01).sin().abs() * 100.0;
-        synthetic.push(kairos_domain::value_objects::bar::Bar {
-            symbol: symbol.to_string(),
-            timestamp: t,
-            open,
-            high,
-            low,
-            close,
-            volume,
-        });
-        price = close;
-    }
-
-    let data = VecBarSource::new(synthetic);
+    let symbol = BENCH_SYMBOL;
+    let data = VecBarSource::from_columns(synthetic_columns(bars, step_seconds));
     let metrics_config = MetricsConfig::default();
-    let risk_limits = RiskLimits {
-        max_position_qty: 0.0,
-        max_drawdown_pct: 1.0,
-        max_exposure_pct: 1.0,
-    };
-    let run_id = format!(
-        "bench_{}_{}",
-        match bench_mode {
-            BenchMode::Engine => "engine",
-            BenchMode::Features => "features",
-        },
-        bars
-    );
+    let risk_limits = bench_risk_limits();
+    let run_id = format!("bench_{}_{}", bench_mode.as_str(), bars);
     let size_mode = OrderSizeMode::Quantity;
 
+    if let Some(probe) = memory_probe.as_deref_mut() {
+        probe.start();
+    }
     let start = Instant::now();
     let results = match bench_mode {
         BenchMode::Engine => {
@@ -148,9 +323,46 @@ pub fn run_bench(bars: usize, step_seconds: i64, mode: &str) -> Result<BenchSumm
             );
             runner.run()
         }
+        BenchMode::Agent => {
+            let agent = SimulatedAgent {
+                latency: Duration::from_secs_f64(options.agent_latency_ms / 1_000.0),
+            };
+            let builder = features::FeatureBuilder::new(features::FeatureConfig {
+                return_mode: features::ReturnMode::Log,
+                sma_windows: vec![10, 50],
+                volatility_windows: vec![10],
+                rsi_enabled: false,
+            });
+            let strategy = AgentStrategy::new(
+                run_id.clone(),
+                symbol.to_string(),
+                format!("{step_seconds}s"),
+                "v1".to_string(),
+                "v1".to_string(),
+                "in-process".to_string(),
+                ActionType::Hold,
+                Box::new(agent),
+                builder,
+                Vec::new(),
+            );
+            let mut runner = BacktestRunner::new(
+                run_id.clone(),
+                strategy,
+                data,
+                risk_limits,
+                10_000.0,
+                metrics_config,
+                0.0,
+                0.0,
+                symbol.to_string(),
+                size_mode,
+            );
+            runner.run()
+        }
     };
 
     let elapsed = start.elapsed();
+    let memory = memory_probe.map(|probe| probe.finish());
     let elapsed_ms = elapsed.as_millis() as u64;
     let bars_processed = results.summary.bars_processed as u64;
     let bars_per_sec = if elapsed.as_secs_f64() > 0.0 {
@@ -169,6 +381,145 @@ pub fn run_bench(bars: usize, step_seconds: i64, mode: &str) -> Result<BenchSumm
         bars_processed,
         elapsed_ms,
         bars_per_sec,
+        memory,
         results,
     })
 }
+
+const BENCH_SYMBOL: &str = "BENCH";
+const BENCH_START_TS: i64 = 1_700_000_000;
+
+fn synthetic_bar(i: usize, step_seconds: i64, price: f64, symbol: Symbol) -> Bar {
+    let drift = ((i as f64) * 0.000001).sin() * 0.05;
+    let shock = ((i as f64) * 0.001).cos() * 0.01;
+    let open = price;
+    let close = (price * (1.0 + drift + shock)).max(0.01);
+    Bar {
+        symbol,
+        timestamp: BENCH_START_TS + (i as i64) * step_seconds,
+        open,
+        high: open.max(close) * 1.001,
+        low: open.min(close) * 0.999,
+        close,
+        volume: 1000.0 + ((i as f64) * 0.01).sin().abs() * 100.0,
+    }
+}
+
+fn synthetic_columns(bars: usize, step_seconds: i64) -> BarColumns {
+    let symbol = Symbol::new(BENCH_SYMBOL);
+    let mut columns = BarColumns::with_capacity(bars);
+    let mut price = 100.0f64;
+    for i in 0..bars {
+        let bar = synthetic_bar(i, step_seconds, price, symbol.clone());
+        price = bar.close;
+        columns.push(&bar);
+    }
+    columns
+}
+
+fn bench_risk_limits() -> RiskLimits {
+    RiskLimits {
+        max_position_qty: 0.0,
+        max_drawdown_pct: 1.0,
+        max_exposure_pct: 1.0,
+    }
+}
+
+// `kairos-bench --mode symbols`: the two places a run copies its market symbol in bulk. Bar load
+// builds the bars of a load twice, interning the name on every row (what the OHLCV loaders used
+// to do) and cloning one interned symbol (what they do now); trade creation runs the engine on a
+// strategy that flips its position every bar, so every fill records a trade.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolBenchSummary {
+    pub bars: usize,
+    pub intern_per_bar_ns: f64,
+    pub clone_per_bar_ns: f64,
+    pub trades: usize,
+    pub trade_creation_ms: u64,
+    pub trades_per_sec: f64,
+}
+
+struct FlipStrategy {
+    long: bool,
+}
+
+impl Strategy for FlipStrategy {
+    fn name(&self) -> &str {
+        "symbol_bench_flip"
+    }
+
+    fn on_bar(
+        &mut self,
+        _bar: &Bar,
+        _portfolio: &kairos_domain::entities::portfolio::Portfolio,
+    ) -> Action {
+        self.long = !self.long;
+        Action {
+            action_type: if self.long {
+                ActionType::Buy
+            } else {
+                ActionType::Sell
+            },
+            size: 1.0,
+            reason: None,
+        }
+    }
+}
+
+pub fn run_symbol_bench(bars: usize, step_seconds: i64) -> Result<SymbolBenchSummary, String> {
+    let _span = info_span!("run_symbol_bench", bars = bars).entered();
+    if bars == 0 {
+        return Err("bars must be > 0".to_string());
+    }
+    if step_seconds <= 0 {
+        return Err("step_seconds must be > 0".to_string());
+    }
+
+    let load = |symbol_for_row: &dyn Fn() -> Symbol| {
+        let start = Instant::now();
+        let mut loaded = Vec::with_capacity(bars);
+        let mut price = 100.0f64;
+        for i in 0..bars {
+            let bar = synthetic_bar(i, step_seconds, price, symbol_for_row());
+            price = bar.close;
+            loaded.push(bar);
+        }
+        let elapsed = start.elapsed();
+        drop(std::hint::black_box(loaded));
+        elapsed.as_nanos() as f64 / bars as f64
+    };
+    let intern_per_bar_ns = load(&|| Symbol::new(BENCH_SYMBOL));
+    let interned = Symbol::new(BENCH_SYMBOL);
+    let clone_per_bar_ns = load(&|| interned.clone());
+
+    let data = VecBarSource::from_columns(synthetic_columns(bars, step_seconds));
+    let start = Instant::now();
+    let results = BacktestRunner::new(
+        format!("bench_symbols_{bars}"),
+        FlipStrategy { long: false },
+        data,
+        bench_risk_limits(),
+        10_000.0,
+        MetricsConfig::default(),
+        0.0,
+        0.0,
+        BENCH_SYMBOL.to_string(),
+        OrderSizeMode::Quantity,
+    )
+    .run();
+    let elapsed = start.elapsed();
+    let trades = results.trades.len();
+
+    Ok(SymbolBenchSummary {
+        bars,
+        intern_per_bar_ns,
+        clone_per_bar_ns,
+        trades,
+        trade_creation_ms: elapsed.as_millis() as u64,
+        trades_per_sec: if elapsed.as_secs_f64() > 0.0 {
+            trades as f64 / elapsed.as_secs_f64()
+        } else {
+            0.0
+        },
+    })
+}
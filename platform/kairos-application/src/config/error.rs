@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+// Why a config could not be loaded or turned into a query. Messages start with the offending key
+// where there is one (e.g. `run.start: ...`).
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("failed to read config {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to parse TOML {}: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: Box<ConfigError>,
+    },
+    #[error("{0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("failed to serialize {what}: {source}")]
+    Serialize {
+        what: &'static str,
+        #[source]
+        source: toml::ser::Error,
+    },
+    // `${VAR}` interpolation failed at `key`.
+    #[error("{key}: {reason}")]
+    Interpolation { key: String, reason: String },
+    #[error("{0}")]
+    InvalidOverride(String),
+    #[error("{key}: {reason}")]
+    InvalidTimestamp { key: &'static str, reason: String },
+    #[error("{key} {reason}")]
+    InvalidValue {
+        key: &'static str,
+        reason: &'static str,
+    },
+    // A setting that parses but cannot be used (unknown timeframe, execution model, schedule, ...).
+    #[error("{0}")]
+    Invalid(String),
+}
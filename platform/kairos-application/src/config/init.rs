@@ -0,0 +1,228 @@
+use super::AgentMode;
+use crate::shared::normalize_timeframe_label;
+
+// Answers collected by `kairos-alloy init`; everything else in the generated config uses the
+// same defaults as platform/ops/configs/sample.toml.
+#[derive(Debug, Clone)]
+pub struct InitAnswers {
+    pub symbol: String,
+    pub timeframe: String,
+    pub db_url: Option<String>,
+    pub agent_mode: AgentMode,
+    pub agent_url: String,
+}
+
+impl Default for InitAnswers {
+    fn default() -> Self {
+        Self {
+            symbol: "BTC-USDT".to_string(),
+            timeframe: "1min".to_string(),
+            db_url: None,
+            agent_mode: AgentMode::Baseline,
+            agent_url: "http://127.0.0.1:8000".to_string(),
+        }
+    }
+}
+
+pub fn parse_agent_mode(raw: &str) -> Result<AgentMode, String> {
+    match raw.trim().to_lowercase().as_str() {
+        "baseline" => Ok(AgentMode::Baseline),
+        "remote" => Ok(AgentMode::Remote),
+        "hold" => Ok(AgentMode::Hold),
+        other => Err(format!(
+            "unsupported agent mode '{other}' (expected baseline|remote|hold)"
+        )),
+    }
+}
+
+fn agent_mode_label(mode: AgentMode) -> &'static str {
+    match mode {
+        AgentMode::Baseline => "baseline",
+        AgentMode::Remote => "remote",
+        AgentMode::Hold => "hold",
+    }
+}
+
+// TOML basic string with escaping (symbols/URLs come straight from user input).
+fn quoted(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+pub fn render_config(answers: &InitAnswers) -> Result<String, String> {
+    let symbol = answers.symbol.trim().to_uppercase();
+    if symbol.is_empty() {
+        return Err("symbol must not be empty".to_string());
+    }
+    let timeframe = normalize_timeframe_label(&answers.timeframe)?;
+    let run_id = format!("{}_{}", symbol.to_lowercase().replace('-', "_"), timeframe);
+
+    let db_url = match answers
+        .db_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+    {
+        Some(url) => format!("url = {}", quoted(url)),
+        None => "# url = \"postgres://kairos:CHANGE_ME@db:5432/kairos\"".to_string(),
+    };
+
+    Ok(format!(
+        r#"# Generated by `kairos-alloy init`. Every key is documented in platform/ops/configs/README.md.
+
+[run]
+# Used as the run directory name under `paths.out_dir`.
+run_id = {run_id}
+symbol = {symbol}
+# Bar size the engine runs at (1min|5min|15min|1hour|4hour|1day|...).
+timeframe = {timeframe}
+initial_capital = 10000.0
+
+[db]
+# Set this explicitly OR leave it commented and export KAIROS_DB_URL.
+{db_url}
+ohlcv_table = "ohlcv_candles"
+exchange = "kucoin"
+# "spot" | "futures"
+market = "spot"
+# Optional: postgres connection pool size (default: 8).
+# pool_max_size = 8
+# Optional: timeframe stored in the DB when it is smaller than `run.timeframe`;
+# bars are resampled to `run.timeframe` on load.
+# source_timeframe = "1min"
+
+[paths]
+out_dir = "runs/"
+# Optional sentiment CSV/JSON aligned to the OHLCV bars.
+# sentiment_path = "data/sentiment.csv"
+
+[costs]
+fee_bps = 10.0
+slippage_bps = 5.0
+
+[risk]
+max_position_qty = 1.0
+max_drawdown_pct = 0.30
+max_exposure_pct = 1.00
+
+[orders]
+# "qty" | "pct_equity"
+size_mode = "qty"
+
+[execution]
+# "simple" | "complete"
+model = "complete"
+latency_bars = 1
+buy_kind = "market"
+sell_kind = "market"
+price_reference = "close"
+limit_offset_bps = 10.0
+stop_offset_bps = 10.0
+spread_bps = 0.0
+max_fill_pct_of_volume = 0.25
+# "gtc" | "ioc" | "fok"
+tif = "gtc"
+# expire_after_bars = 3
+
+[features]
+# "log" | "pct"
+return_mode = "log"
+sma_windows = [10, 50]
+volatility_windows = [10]
+rsi_enabled = false
+sentiment_lag = "5m"
+# "error" | "zero_fill" | "forward_fill" | "drop_row"
+sentiment_missing = "error"
+
+[strategy]
+# Used when `agent.mode = "baseline"`: "buy_and_hold" | "sma"
+baseline = "buy_and_hold"
+sma_short = 10
+sma_long = 50
+
+[metrics]
+risk_free_rate = 0.0
+annualization_factor = 365.0
+
+[agent]
+# "baseline" (built-in strategy) | "remote" (HTTP agent at `url`) | "hold" (never trades)
+mode = {agent_mode}
+url = {agent_url}
+timeout_ms = 200
+retries = 1
+# Action used when the agent fails or its budget runs out: "BUY" | "SELL" | "HOLD"
+fallback_action = "HOLD"
+api_version = "v1"
+feature_version = "v1"
+# Optional per-run budget.
+# max_calls = 100000
+# max_total_ms = 600000
+
+[data_quality]
+max_gaps = 0
+max_missing_bars = 0
+max_duplicates = 0
+max_out_of_order = 0
+max_invalid_close = 0
+
+[paper]
+# Replay speed multiplier for paper trading over historical bars.
+replay_scale = 60
+
+[report]
+html = true
+# Also write trades.parquet/equity.parquet next to the CSVs.
+# parquet = false
+# Compress trades.csv/equity.csv/logs.jsonl: "none" | "gzip" | "zstd".
+# compression = "none"
+"#,
+        run_id = quoted(&run_id),
+        symbol = quoted(&symbol),
+        timeframe = quoted(&timeframe),
+        agent_mode = quoted(agent_mode_label(answers.agent_mode)),
+        agent_url = quoted(answers.agent_url.trim()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_agent_mode, render_config, InitAnswers};
+    use crate::config::{AgentMode, Config};
+
+    #[test]
+    fn rendered_config_parses_with_answers_applied() {
+        let answers = InitAnswers {
+            symbol: "eth-usdt".to_string(),
+            timeframe: "5m".to_string(),
+            db_url: Some("postgres://u:p\"w@db/kairos".to_string()),
+            agent_mode: parse_agent_mode("Remote").unwrap(),
+            agent_url: "http://agent:9000".to_string(),
+        };
+        let rendered = render_config(&answers).unwrap();
+        assert_eq!(
+            crate::config::schema::check_config_source(&rendered),
+            Vec::new()
+        );
+        let config: Config = toml::from_str(&rendered).expect("generated config parses");
+        assert_eq!(config.run.run_id, "eth_usdt_5min");
+        assert_eq!(config.run.symbol, "ETH-USDT");
+        assert_eq!(config.run.timeframe, "5min");
+        assert_eq!(
+            config.db.url.as_deref(),
+            Some("postgres://u:p\"w@db/kairos")
+        );
+        assert!(matches!(config.agent.mode, AgentMode::Remote));
+        assert_eq!(config.agent.url, "http://agent:9000");
+
+        let defaults: Config = toml::from_str(&render_config(&InitAnswers::default()).unwrap())
+            .expect("default config parses");
+        assert!(defaults.db.url.is_none());
+        assert!(matches!(defaults.agent.mode, AgentMode::Baseline));
+
+        let bad = InitAnswers {
+            timeframe: "7min".to_string(),
+            ..InitAnswers::default()
+        };
+        assert!(render_config(&bad).is_err());
+        assert!(parse_agent_mode("llm").is_err());
+    }
+}
@@ -1,3 +1,13 @@
+mod error;
+pub mod init;
+pub mod overrides;
+pub mod schema;
+
+pub use error::ConfigError;
+
+use crate::shared::parse_timestamp_seconds;
+use kairos_domain::repositories::market_data::OhlcvQuery;
+use kairos_domain::services::ohlcv::OutlierConfig;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
@@ -10,6 +20,14 @@ pub enum AgentMode {
     Hold,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SentimentSourceKind {
+    #[default]
+    File,
+    Db,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct Config {
@@ -26,6 +44,7 @@ pub struct Config {
     pub metrics: Option<MetricsConfig>,
     pub data_quality: Option<DataQualityConfig>,
     pub paper: Option<PaperConfig>,
+    pub live: Option<LiveConfig>,
     pub report: Option<ReportConfig>,
 }
 
@@ -36,6 +55,9 @@ pub struct RunConfig {
     pub symbol: String,
     pub timeframe: String,
     pub initial_capital: f64,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub max_bars: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -47,13 +69,31 @@ pub struct DbConfig {
     pub market: String,
     pub source_timeframe: Option<String>,
     pub pool_max_size: Option<u32>,
+    pub cache_dir: Option<String>,
+    pub sentiment_table: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct PathsConfig {
     pub sentiment_path: Option<String>,
+    pub sentiment_source: Option<SentimentSourceKind>,
     pub out_dir: String,
+    pub sentiment_sources: Option<Vec<SentimentSourceConfig>>,
+}
+
+// One of several sentiment inputs blended into the run's sentiment (`[[paths.sentiment_sources]]`).
+// `source`/`path`/`table` mean what `paths.sentiment_source`/`paths.sentiment_path`/
+// `db.sentiment_table` mean for a single input; `lag` defaults to `features.sentiment_lag`.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SentimentSourceConfig {
+    pub name: String,
+    pub source: Option<SentimentSourceKind>,
+    pub path: Option<String>,
+    pub table: Option<String>,
+    pub weight: Option<f64>,
+    pub lag: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -114,6 +154,8 @@ pub struct AgentConfig {
     pub fallback_action: kairos_domain::value_objects::action_type::ActionType,
     pub api_version: String,
     pub feature_version: String,
+    pub max_calls: Option<u64>,
+    pub max_total_ms: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -142,41 +184,218 @@ pub struct DataQualityConfig {
     pub max_sentiment_missing: Option<usize>,
     pub max_sentiment_invalid: Option<usize>,
     pub max_sentiment_dropped: Option<usize>,
+    // Outlier checks: thresholds always apply to the report; the `max_*` limits are only enforced
+    // by `validate --strict` when set.
+    pub outlier_sigma: Option<f64>,
+    pub zero_volume_streak_bars: Option<usize>,
+    pub flat_price_streak_bars: Option<usize>,
+    pub max_return_outliers: Option<usize>,
+    pub max_zero_volume_streaks: Option<usize>,
+    pub max_flat_price_streaks: Option<usize>,
+    // Volume checks: `turnover_tolerance` is a fraction of the bar range; the limits work like the
+    // outlier ones.
+    pub turnover_tolerance: Option<f64>,
+    pub max_zero_volume_moves: Option<usize>,
+    pub max_turnover_mismatches: Option<usize>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct PaperConfig {
     pub replay_scale: Option<u64>,
+    pub checkpoint_every_bars: Option<u64>,
+    pub symbols: Option<Vec<String>>,
+    pub session_start: Option<String>,
+    pub session_end: Option<String>,
+    pub max_session_duration: Option<String>,
+    pub flatten_on_session_end: Option<bool>,
+    pub simulator: Option<StreamSimulatorConfig>,
+}
+
+// The synthetic stream the TUI's realtime paper can run on instead of the exchange.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamSimulatorConfig {
+    pub start_price: Option<f64>,
+    pub volatility: Option<f64>,
+    pub tick_ms: Option<u64>,
+    pub tick_seconds: Option<i64>,
+    pub disconnect_rate: Option<f64>,
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct LiveConfig {
+    pub venue: String,
+    pub max_order_notional: f64,
+    pub max_orders: Option<u64>,
+    pub max_bars: Option<u64>,
+    pub fill_timeout_ms: Option<u64>,
+    pub poll_interval_ms: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct ReportConfig {
     pub html: Option<bool>,
+    pub parquet: Option<bool>,
+    pub compression: Option<String>,
 }
 
-pub fn load_config(path: &Path) -> Result<Config, String> {
+pub fn load_config(path: &Path) -> Result<Config, ConfigError> {
     let (config, _source) = load_config_with_source(path)?;
     Ok(config)
 }
 
-pub fn load_config_with_source(path: &Path) -> Result<(Config, String), String> {
-    let contents = fs::read_to_string(path)
-        .map_err(|err| format!("failed to read config {}: {}", path.display(), err))?;
-    let config = toml::from_str(&contents)
-        .map_err(|err| format!("failed to parse TOML {}: {}", path.display(), err))?;
-    Ok((config, contents))
+pub fn load_config_with_source(path: &Path) -> Result<(Config, String), ConfigError> {
+    load_config_with_overrides(path, &[])
 }
 
-pub fn to_toml_pretty(config: &Config) -> Result<String, String> {
-    toml::to_string_pretty(config)
-        .map_err(|err| format!("failed to serialize config as TOML: {err}"))
+// Applies `--set key=value` overrides, then `${ENV}` interpolation. The returned source is what
+// gets written to config_snapshot.toml: the file verbatim when there are no overrides, otherwise
+// the merged TOML. Env placeholders are kept in the snapshot so secrets (db.url) stay out of
+// run dirs; loading the snapshot resolves them again.
+pub fn load_config_with_overrides(
+    path: &Path,
+    sets: &[String],
+) -> Result<(Config, String), ConfigError> {
+    let parse_error = |source: ConfigError| ConfigError::Parse {
+        path: path.to_path_buf(),
+        source: Box::new(source),
+    };
+    let contents = fs::read_to_string(path).map_err(|source| ConfigError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let mut merged: toml::Value =
+        toml::from_str(&contents).map_err(|err| parse_error(ConfigError::Toml(err)))?;
+    for assignment in sets {
+        overrides::apply_set(&mut merged, assignment)?;
+    }
+    let source = if sets.is_empty() {
+        contents
+    } else {
+        toml::to_string_pretty(&merged).map_err(|source| ConfigError::Serialize {
+            what: "merged config",
+            source,
+        })?
+    };
+    let config = load_config_from_source(&source).map_err(parse_error)?;
+    Ok((config, source))
+}
+
+// Parses config TOML with `${ENV}` interpolation. Without placeholders the text is parsed
+// directly so errors keep their line numbers.
+pub fn load_config_from_source(source: &str) -> Result<Config, ConfigError> {
+    let mut value: toml::Value = toml::from_str(source)?;
+    let raw = value.clone();
+    overrides::interpolate_env(&mut value, &|name| std::env::var(name).ok())?;
+    if value == raw {
+        Ok(toml::from_str(source)?)
+    } else {
+        Ok(value.try_into()?)
+    }
+}
+
+// The stored candles a run reads: `db.*` and `run.symbol` at `timeframe`, scoped to
+// `run.start`/`run.end` and capped at `run.max_bars`.
+pub fn build_ohlcv_query(
+    config: &Config,
+    timeframe: &str,
+    step_seconds: i64,
+) -> Result<OhlcvQuery, ConfigError> {
+    let bound = |key: &'static str, raw: Option<&String>| {
+        raw.map(|raw| {
+            parse_timestamp_seconds(raw)
+                .map_err(|reason| ConfigError::InvalidTimestamp { key, reason })
+        })
+        .transpose()
+    };
+    let start = bound("run.start", config.run.start.as_ref())?;
+    let end = bound("run.end", config.run.end.as_ref())?;
+    if let (Some(start), Some(end)) = (start, end) {
+        if end < start {
+            return Err(ConfigError::InvalidValue {
+                key: "run.end",
+                reason: "must not be before run.start",
+            });
+        }
+    }
+    let turnover_tolerance = config
+        .data_quality
+        .as_ref()
+        .and_then(|dq| dq.turnover_tolerance);
+    if turnover_tolerance.is_some_and(|tolerance| !(tolerance.is_finite() && tolerance >= 0.0)) {
+        return Err(ConfigError::InvalidValue {
+            key: "data_quality.turnover_tolerance",
+            reason: "must be >= 0",
+        });
+    }
+    if config.run.max_bars == Some(0) {
+        return Err(ConfigError::InvalidValue {
+            key: "run.max_bars",
+            reason: "must be > 0",
+        });
+    }
+    Ok(OhlcvQuery {
+        exchange: config.db.exchange.to_lowercase(),
+        market: config.db.market.to_lowercase(),
+        symbol: config.run.symbol.clone(),
+        timeframe: timeframe.to_string(),
+        expected_step_seconds: Some(step_seconds),
+        start,
+        end,
+        limit: config.run.max_bars,
+        turnover_tolerance,
+    })
+}
+
+// `data_quality.outlier_sigma` and the streak lengths, falling back to the domain defaults.
+pub fn build_outlier_config(config: &Config) -> Result<OutlierConfig, ConfigError> {
+    let defaults = OutlierConfig::default();
+    let dq = config.data_quality.as_ref();
+    let return_sigma = dq
+        .and_then(|dq| dq.outlier_sigma)
+        .unwrap_or(defaults.return_sigma);
+    if !(return_sigma.is_finite() && return_sigma > 0.0) {
+        return Err(ConfigError::InvalidValue {
+            key: "data_quality.outlier_sigma",
+            reason: "must be > 0",
+        });
+    }
+    let streak = |key: &'static str, value: Option<usize>, default: usize| match value {
+        Some(0) => Err(ConfigError::InvalidValue {
+            key,
+            reason: "must be > 0",
+        }),
+        value => Ok(value.unwrap_or(default)),
+    };
+    Ok(OutlierConfig {
+        return_sigma,
+        zero_volume_streak_bars: streak(
+            "data_quality.zero_volume_streak_bars",
+            dq.and_then(|dq| dq.zero_volume_streak_bars),
+            defaults.zero_volume_streak_bars,
+        )?,
+        flat_price_streak_bars: streak(
+            "data_quality.flat_price_streak_bars",
+            dq.and_then(|dq| dq.flat_price_streak_bars),
+            defaults.flat_price_streak_bars,
+        )?,
+    })
+}
+
+pub fn to_toml_pretty(config: &Config) -> Result<String, ConfigError> {
+    toml::to_string_pretty(config).map_err(|source| ConfigError::Serialize {
+        what: "config as TOML",
+        source,
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Config;
+    use super::{load_config_with_overrides, Config};
 
     fn parse_config(toml_str: &str) -> Config {
         toml::from_str(toml_str).expect("config should parse")
@@ -387,4 +606,46 @@ feature_version = "v1"
         let config = parse_config(toml_str);
         assert_eq!(config.db.pool_max_size, Some(4));
     }
+
+    #[test]
+    fn overrides_and_env_apply_but_snapshot_keeps_placeholders() {
+        let dir = std::env::temp_dir().join(format!("kairos_cfg_overrides_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        let source = include_str!("../../../ops/configs/sample.toml").replace(
+            "# url = \"postgres://kairos:CHANGE_ME@db:5432/kairos\"",
+            "url = \"postgres://kairos:${KAIROS_TEST_OVERRIDES_PASS}@db:5432/kairos\"",
+        );
+        std::fs::write(&path, &source).unwrap();
+
+        let err = load_config_with_overrides(&path, &[])
+            .expect_err("unset env var")
+            .to_string();
+        assert!(err.contains("KAIROS_TEST_OVERRIDES_PASS"), "{err}");
+
+        std::env::set_var("KAIROS_TEST_OVERRIDES_PASS", "s3cret");
+        let (config, snapshot) = load_config_with_overrides(&path, &[]).unwrap();
+        assert_eq!(
+            config.db.url.as_deref(),
+            Some("postgres://kairos:s3cret@db:5432/kairos")
+        );
+        assert_eq!(snapshot, source);
+
+        let sets = vec![
+            "features.rsi_enabled=true".to_string(),
+            "run.symbol=ETH-USDT".to_string(),
+        ];
+        let (config, snapshot) = load_config_with_overrides(&path, &sets).unwrap();
+        assert!(config.features.rsi_enabled);
+        assert_eq!(config.run.symbol, "ETH-USDT");
+        assert!(snapshot.contains("rsi_enabled = true"));
+        assert!(snapshot.contains("${KAIROS_TEST_OVERRIDES_PASS}"));
+        assert!(!snapshot.contains("s3cret"));
+
+        let bad = vec!["features.rsi_enabled=maybe".to_string()];
+        assert!(load_config_with_overrides(&path, &bad).is_err());
+
+        std::env::remove_var("KAIROS_TEST_OVERRIDES_PASS");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -0,0 +1,178 @@
+use super::ConfigError;
+use toml::Value;
+
+// `${VAR}` / `${VAR:-default}` inside string values; `$${` is a literal `${`. Only strings are
+// interpolated, so typed keys (numbers, booleans) stay typed.
+pub fn interpolate_env(
+    value: &mut Value,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<(), ConfigError> {
+    interpolate_at(value, "", lookup)
+}
+
+fn interpolate_at(
+    value: &mut Value,
+    path: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<(), ConfigError> {
+    match value {
+        Value::String(raw) => {
+            if raw.contains('$') {
+                *raw =
+                    interpolate_str(raw, lookup).map_err(|reason| ConfigError::Interpolation {
+                        key: path.to_string(),
+                        reason,
+                    })?;
+            }
+        }
+        Value::Table(table) => {
+            for (key, child) in table.iter_mut() {
+                let child_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                interpolate_at(child, &child_path, lookup)?;
+            }
+        }
+        Value::Array(items) => {
+            for (idx, child) in items.iter_mut().enumerate() {
+                interpolate_at(child, &format!("{path}[{idx}]"), lookup)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+pub fn interpolate_str(
+    raw: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut out = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if let Some(after) = tail.strip_prefix("$${") {
+            out.push_str("${");
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix("${") {
+            let end = after
+                .find('}')
+                .ok_or_else(|| format!("unterminated '${{' in \"{raw}\""))?;
+            let expr = &after[..end];
+            let (name, default) = match expr.split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (expr, None),
+            };
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(format!("empty variable name in \"{raw}\""));
+            }
+            let resolved = lookup(name)
+                .filter(|v| !v.is_empty())
+                .or_else(|| default.map(str::to_string))
+                .ok_or_else(|| format!("environment variable {name} is not set"))?;
+            out.push_str(&resolved);
+            rest = &after[end + 1..];
+        } else {
+            out.push('$');
+            rest = &tail[1..];
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+// `--set section.key=value`: the value is parsed as a TOML literal (`true`, `10`, `[5, 20]`,
+// `"x"`), falling back to a plain string so `--set run.symbol=ETH-USDT` works unquoted.
+// Missing intermediate tables are created (e.g. `--set report.html=true` without a [report]).
+pub fn apply_set(root: &mut Value, assignment: &str) -> Result<(), ConfigError> {
+    let invalid = ConfigError::InvalidOverride;
+    let (path, raw) = assignment.split_once('=').ok_or_else(|| {
+        invalid(format!(
+            "invalid --set '{assignment}' (expected key.path=value)"
+        ))
+    })?;
+    let parts: Vec<&str> = path.split('.').map(str::trim).collect();
+    if parts.iter().any(|part| part.is_empty()) {
+        return Err(invalid(format!("invalid --set key '{path}'")));
+    }
+    let value = parse_literal(raw.trim());
+
+    let mut cur = root;
+    for key in &parts[..parts.len() - 1] {
+        let table = cur
+            .as_table_mut()
+            .ok_or_else(|| invalid(format!("--set {path}: '{key}' is not inside a table")))?;
+        cur = table
+            .entry(key.to_string())
+            .or_insert_with(|| Value::Table(toml::Table::new()));
+    }
+    let table = cur
+        .as_table_mut()
+        .ok_or_else(|| invalid(format!("--set {path}: parent is not a table")))?;
+    table.insert(parts[parts.len() - 1].to_string(), value);
+    Ok(())
+}
+
+fn parse_literal(raw: &str) -> Value {
+    toml::from_str::<toml::Table>(&format!("v = {raw}"))
+        .ok()
+        .and_then(|mut table| table.remove("v"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_set, interpolate_env, interpolate_str};
+    use toml::Value;
+
+    fn env(name: &str) -> Option<String> {
+        match name {
+            "DB_PASS" => Some("s3cret".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn interpolates_vars_defaults_and_escapes() {
+        assert_eq!(
+            interpolate_str("postgres://u:${DB_PASS}@db/${DB_NAME:-kairos}", &env).unwrap(),
+            "postgres://u:s3cret@db/kairos"
+        );
+        assert_eq!(interpolate_str("${EMPTY:-x}", &env).unwrap(), "x");
+        assert_eq!(
+            interpolate_str("cost $5 $${LITERAL}", &env).unwrap(),
+            "cost $5 ${LITERAL}"
+        );
+
+        let mut value: Value =
+            toml::from_str("[db]\nurl = \"${MISSING}\"\n[run]\ninitial_capital = 10.0\n").unwrap();
+        let err = interpolate_env(&mut value, &env).unwrap_err().to_string();
+        assert!(err.contains("db.url") && err.contains("MISSING"), "{err}");
+    }
+
+    #[test]
+    fn set_parses_literals_and_creates_tables() {
+        let mut value: Value = toml::from_str("[features]\nrsi_enabled = false\n").unwrap();
+        apply_set(&mut value, "features.rsi_enabled=true").unwrap();
+        apply_set(&mut value, "features.sma_windows=[5, 20]").unwrap();
+        apply_set(&mut value, "run.symbol=ETH-USDT").unwrap();
+        apply_set(&mut value, "report.html = true").unwrap();
+
+        assert_eq!(value["features"]["rsi_enabled"].as_bool(), Some(true));
+        assert_eq!(
+            value["features"]["sma_windows"].as_array().map(Vec::len),
+            Some(2)
+        );
+        assert_eq!(value["run"]["symbol"].as_str(), Some("ETH-USDT"));
+        assert_eq!(value["report"]["html"].as_bool(), Some(true));
+
+        assert!(apply_set(&mut value, "features.rsi_enabled").is_err());
+        assert!(apply_set(&mut value, "features.rsi_enabled.x=1").is_err());
+        assert!(apply_set(&mut value, ".x=1").is_err());
+    }
+}
@@ -0,0 +1,638 @@
+use super::Config;
+use serde_json::{json, Map, Value};
+use std::ops::Range;
+use toml::de::{DeTable, DeValue};
+use toml::Spanned;
+
+// JSON Schema (draft 2020-12) mirroring `Config`; a test keeps the property sets in sync with the
+// structs. Enums are only listed where serde rejects other values; free-form strings that are
+// interpreted at runtime (size_mode, tif, ...) list their accepted values in the description.
+pub fn config_schema() -> Value {
+    let mut schema = object(
+        &["run", "db", "paths", "costs", "risk", "features", "agent"],
+        vec![
+            (
+                "run",
+                object(
+                    &["run_id", "symbol", "timeframe", "initial_capital"],
+                    vec![
+                        ("run_id", string("Run directory name under paths.out_dir.")),
+                        ("symbol", string("Market symbol, e.g. BTC-USDT.")),
+                        (
+                            "timeframe",
+                            string("Bar size: 1min|3min|5min|15min|30min|1hour|...|1day|1week|1month."),
+                        ),
+                        ("initial_capital", number_min(0.0, false)),
+                        (
+                            "start",
+                            string("First bar to load: epoch seconds or RFC3339 (inclusive)."),
+                        ),
+                        (
+                            "end",
+                            string("Last bar to load: epoch seconds or RFC3339 (inclusive)."),
+                        ),
+                        ("max_bars", integer_min(1)),
+                    ],
+                ),
+            ),
+            (
+                "db",
+                object(
+                    &["ohlcv_table", "exchange", "market"],
+                    vec![
+                        ("url", string("Postgres URL; falls back to env KAIROS_DB_URL.")),
+                        ("ohlcv_table", string("OHLCV table, optionally schema-qualified.")),
+                        ("exchange", string("Exchange name stored with the candles.")),
+                        ("market", string("spot|futures")),
+                        (
+                            "source_timeframe",
+                            string("Stored timeframe to resample from when smaller than run.timeframe."),
+                        ),
+                        ("pool_max_size", integer_min(1)),
+                        (
+                            "cache_dir",
+                            string("Directory for Parquet copies of OHLCV query results."),
+                        ),
+                        (
+                            "sentiment_table",
+                            string("Sentiment table for paths.sentiment_source = \"db\" (default sentiment_scores)."),
+                        ),
+                    ],
+                ),
+            ),
+            (
+                "paths",
+                object(
+                    &["out_dir"],
+                    vec![
+                        ("sentiment_path", string("Optional sentiment CSV/JSON file.")),
+                        ("sentiment_source", string_enum(&["file", "db"])),
+                        ("out_dir", string("Directory for run artifacts.")),
+                        (
+                            "sentiment_sources",
+                            array(object(
+                                &["name"],
+                                vec![
+                                    ("name", string("Label used in reports.")),
+                                    ("source", string_enum(&["file", "db"])),
+                                    ("path", string("Sentiment CSV/JSON file (source = \"file\").")),
+                                    (
+                                        "table",
+                                        string("Sentiment table (source = \"db\"); default db.sentiment_table."),
+                                    ),
+                                    ("weight", number_min(0.0, false)),
+                                    ("lag", string("Alignment lag; default features.sentiment_lag.")),
+                                ],
+                            )),
+                        ),
+                    ],
+                ),
+            ),
+            (
+                "costs",
+                object(
+                    &["fee_bps", "slippage_bps"],
+                    vec![
+                        ("fee_bps", number_min(0.0, true)),
+                        ("slippage_bps", number_min(0.0, true)),
+                    ],
+                ),
+            ),
+            (
+                "risk",
+                object(
+                    &["max_position_qty", "max_drawdown_pct", "max_exposure_pct"],
+                    vec![
+                        ("max_position_qty", number_min(0.0, true)),
+                        ("max_drawdown_pct", number_min(0.0, true)),
+                        ("max_exposure_pct", number_min(0.0, true)),
+                    ],
+                ),
+            ),
+            (
+                "orders",
+                object(&[], vec![("size_mode", string("qty|pct_equity"))]),
+            ),
+            (
+                "execution",
+                object(
+                    &[],
+                    vec![
+                        ("model", string("simple|complete")),
+                        ("latency_bars", integer_min(0)),
+                        ("buy_kind", string("market|limit|stop")),
+                        ("sell_kind", string("market|limit|stop")),
+                        ("price_reference", string("close|open")),
+                        ("limit_offset_bps", number_min(0.0, true)),
+                        ("stop_offset_bps", number_min(0.0, true)),
+                        ("spread_bps", number_min(0.0, true)),
+                        ("max_fill_pct_of_volume", number_min(0.0, true)),
+                        ("tif", string("gtc|ioc|fok")),
+                        ("expire_after_bars", integer_min(0)),
+                    ],
+                ),
+            ),
+            (
+                "features",
+                object(
+                    &["return_mode", "sma_windows", "rsi_enabled", "sentiment_lag"],
+                    vec![
+                        ("return_mode", string_enum(&["log", "pct"])),
+                        ("sma_windows", array(integer_min(0))),
+                        ("volatility_windows", array(integer_min(0))),
+                        ("rsi_enabled", json!({"type": "boolean"})),
+                        ("sentiment_lag", string("Duration such as 0s, 5m, 1h.")),
+                        (
+                            "sentiment_missing",
+                            string("error|zero_fill|forward_fill|drop_row"),
+                        ),
+                    ],
+                ),
+            ),
+            (
+                "agent",
+                object(
+                    &[
+                        "mode",
+                        "url",
+                        "timeout_ms",
+                        "retries",
+                        "fallback_action",
+                        "api_version",
+                        "feature_version",
+                    ],
+                    vec![
+                        ("mode", string_enum(&["remote", "baseline", "hold"])),
+                        ("url", string("Agent base URL (used by mode = remote).")),
+                        ("timeout_ms", integer_min(0)),
+                        ("retries", integer_min(0)),
+                        ("fallback_action", string_enum(&["BUY", "SELL", "HOLD"])),
+                        ("api_version", string("Agent API version, e.g. v1.")),
+                        ("feature_version", string("Feature vector version, e.g. v1.")),
+                        ("max_calls", integer_min(0)),
+                        ("max_total_ms", integer_min(0)),
+                    ],
+                ),
+            ),
+            (
+                "strategy",
+                object(
+                    &["baseline"],
+                    vec![
+                        ("baseline", string("buy_and_hold|sma")),
+                        ("sma_short", integer_min(0)),
+                        ("sma_long", integer_min(0)),
+                    ],
+                ),
+            ),
+            (
+                "metrics",
+                object(
+                    &[],
+                    vec![
+                        ("risk_free_rate", json!({"type": "number"})),
+                        ("annualization_factor", number_min(0.0, false)),
+                    ],
+                ),
+            ),
+            (
+                "data_quality",
+                object(
+                    &[],
+                    [
+                        "max_gaps",
+                        "max_missing_bars",
+                        "max_duplicates",
+                        "max_out_of_order",
+                        "max_invalid_close",
+                        "max_sentiment_missing",
+                        "max_sentiment_invalid",
+                        "max_sentiment_dropped",
+                        "max_return_outliers",
+                        "max_zero_volume_streaks",
+                        "max_flat_price_streaks",
+                        "max_zero_volume_moves",
+                        "max_turnover_mismatches",
+                    ]
+                    .into_iter()
+                    .map(|key| (key, integer_min(0)))
+                    .chain([
+                        ("outlier_sigma", number_min(0.0, false)),
+                        ("zero_volume_streak_bars", integer_min(1)),
+                        ("flat_price_streak_bars", integer_min(1)),
+                        ("turnover_tolerance", number_min(0.0, true)),
+                    ])
+                    .collect(),
+                ),
+            ),
+            (
+                "paper",
+                object(
+                    &[],
+                    vec![
+                        ("replay_scale", integer_min(0)),
+                        ("checkpoint_every_bars", integer_min(0)),
+                        (
+                            "symbols",
+                            array(string("Realtime symbol, e.g. ETH-USDT.")),
+                        ),
+                        (
+                            "session_start",
+                            string("Daily trading window start, HH:MM UTC."),
+                        ),
+                        (
+                            "session_end",
+                            string("Daily trading window end, HH:MM UTC."),
+                        ),
+                        (
+                            "max_session_duration",
+                            string("Maximum run length, e.g. 8h."),
+                        ),
+                        ("flatten_on_session_end", json!({"type": "boolean"})),
+                        (
+                            "simulator",
+                            object(
+                                &[],
+                                vec![
+                                    ("start_price", number_min(0.0, false)),
+                                    ("volatility", number_min(0.0, true)),
+                                    ("tick_ms", integer_min(0)),
+                                    ("tick_seconds", integer_min(1)),
+                                    ("disconnect_rate", number_min(0.0, true)),
+                                    ("seed", integer_min(0)),
+                                ],
+                            ),
+                        ),
+                    ],
+                ),
+            ),
+            (
+                "live",
+                object(
+                    &["venue", "max_order_notional"],
+                    vec![
+                        ("venue", string_enum(&["kucoin_sandbox", "kucoin"])),
+                        ("max_order_notional", number_min(0.0, false)),
+                        ("max_orders", integer_min(1)),
+                        ("max_bars", integer_min(1)),
+                        ("fill_timeout_ms", integer_min(1)),
+                        ("poll_interval_ms", integer_min(1)),
+                    ],
+                ),
+            ),
+            (
+                "report",
+                object(
+                    &[],
+                    vec![
+                        ("html", json!({"type": "boolean"})),
+                        ("parquet", json!({"type": "boolean"})),
+                        ("compression", string_enum(&["none", "gzip", "zstd"])),
+                    ],
+                ),
+            ),
+        ],
+    );
+    let root = schema.as_object_mut().expect("root schema is an object");
+    root.insert(
+        "$schema".to_string(),
+        json!("https://json-schema.org/draft/2020-12/schema"),
+    );
+    root.insert("title".to_string(), json!("Kairos Alloy config"));
+    schema
+}
+
+fn object(required: &[&str], properties: Vec<(&str, Value)>) -> Value {
+    let properties: Map<String, Value> = properties
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect();
+    json!({
+        "type": "object",
+        "additionalProperties": false,
+        "required": required,
+        "properties": properties,
+    })
+}
+
+fn string(description: &str) -> Value {
+    json!({"type": "string", "description": description})
+}
+
+fn string_enum(values: &[&str]) -> Value {
+    json!({"type": "string", "enum": values})
+}
+
+fn integer_min(min: i64) -> Value {
+    json!({"type": "integer", "minimum": min})
+}
+
+fn number_min(min: f64, inclusive: bool) -> Value {
+    if inclusive {
+        json!({"type": "number", "minimum": min})
+    } else {
+        json!({"type": "number", "exclusiveMinimum": min})
+    }
+}
+
+fn array(items: Value) -> Value {
+    json!({"type": "array", "items": items})
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    // Dotted path, e.g. `agent.mode` or `features.sma_windows[1]`; empty for document-level errors.
+    pub field: String,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.field.is_empty() {
+            write!(f, "{}:{}: {}", self.line, self.column, self.message)
+        } else {
+            write!(
+                f,
+                "{}:{}: {}: {}",
+                self.line, self.column, self.field, self.message
+            )
+        }
+    }
+}
+
+// Validates TOML source against `config_schema()`, reporting every issue with its line/column,
+// then runs the real deserializer so nothing the schema cannot express slips through.
+pub fn check_config_source(source: &str) -> Vec<ConfigIssue> {
+    let document = match DeTable::parse(source) {
+        Ok(document) => document,
+        Err(err) => {
+            let start = err.span().map(|span| span.start).unwrap_or(0);
+            return vec![issue(source, "", start, err.message().to_string())];
+        }
+    };
+
+    let schema = config_schema();
+    let mut issues = Vec::new();
+    check_table(
+        source,
+        "",
+        document.get_ref(),
+        document.span(),
+        &schema,
+        &mut issues,
+    );
+
+    if issues.is_empty() {
+        if let Err(err) = toml::from_str::<Config>(source) {
+            let start = err.span().map(|span| span.start).unwrap_or(0);
+            issues.push(issue(source, "", start, err.message().to_string()));
+        }
+    }
+    issues
+}
+
+fn check_table(
+    source: &str,
+    path: &str,
+    table: &DeTable<'_>,
+    span: Range<usize>,
+    schema: &Value,
+    issues: &mut Vec<ConfigIssue>,
+) {
+    let properties = schema.get("properties").and_then(Value::as_object);
+    if let Some(required) = schema.get("required").and_then(Value::as_array) {
+        for key in required.iter().filter_map(Value::as_str) {
+            if !table.keys().any(|k| k.get_ref() == key) {
+                let what = if path.is_empty() {
+                    format!("missing required section [{key}]")
+                } else {
+                    format!("missing required key '{key}'")
+                };
+                issues.push(issue(source, path, span.start, what));
+            }
+        }
+    }
+
+    for (key, value) in table.iter() {
+        let field = join(path, key.get_ref());
+        match properties.and_then(|props| props.get(key.get_ref().as_ref())) {
+            Some(property) => check_value(source, &field, value, property, issues),
+            None => {
+                let expected = properties
+                    .map(|props| props.keys().cloned().collect::<Vec<_>>().join(", "))
+                    .unwrap_or_default();
+                issues.push(issue(
+                    source,
+                    &field,
+                    key.span().start,
+                    format!("unknown field (expected one of: {expected})"),
+                ));
+            }
+        }
+    }
+}
+
+fn check_value(
+    source: &str,
+    field: &str,
+    value: &Spanned<DeValue<'_>>,
+    schema: &Value,
+    issues: &mut Vec<ConfigIssue>,
+) {
+    let start = value.span().start;
+    let expected = schema.get("type").and_then(Value::as_str).unwrap_or("any");
+    let mut messages = Vec::new();
+    let mut report = |message: String| messages.push(message);
+
+    match (expected, value.get_ref()) {
+        ("object", DeValue::Table(table)) => {
+            check_table(source, field, table, value.span(), schema, issues)
+        }
+        ("array", DeValue::Array(items)) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (idx, item) in items.iter().enumerate() {
+                    check_value(
+                        source,
+                        &format!("{field}[{idx}]"),
+                        item,
+                        item_schema,
+                        issues,
+                    );
+                }
+            }
+        }
+        ("string", DeValue::String(text)) => {
+            if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+                if !allowed.iter().any(|v| v.as_str() == Some(text.as_ref())) {
+                    let allowed: Vec<&str> = allowed.iter().filter_map(Value::as_str).collect();
+                    report(format!(
+                        "invalid value \"{text}\" (expected one of: {})",
+                        allowed.join(", ")
+                    ));
+                }
+            }
+        }
+        ("boolean", DeValue::Boolean(_)) => {}
+        ("integer", DeValue::Integer(raw)) => {
+            match i64::from_str_radix(&raw.as_str().replace('_', ""), raw.radix()) {
+                Ok(number) => check_minimum(number as f64, schema, &mut report),
+                Err(_) => report(format!("invalid integer {}", raw.as_str())),
+            }
+        }
+        ("number", DeValue::Integer(raw)) => {
+            if let Ok(number) = i64::from_str_radix(&raw.as_str().replace('_', ""), raw.radix()) {
+                check_minimum(number as f64, schema, &mut report);
+            }
+        }
+        ("number", DeValue::Float(raw)) => match raw.as_str().replace('_', "").parse::<f64>() {
+            Ok(number) if number.is_finite() => check_minimum(number, schema, &mut report),
+            _ => report(format!("expected a finite number, found {}", raw.as_str())),
+        },
+        (expected, found) => report(format!(
+            "expected {}, found {}",
+            expected_label(expected),
+            found.type_str()
+        )),
+    }
+    issues.extend(
+        messages
+            .into_iter()
+            .map(|message| issue(source, field, start, message)),
+    );
+}
+
+fn check_minimum(number: f64, schema: &Value, report: &mut dyn FnMut(String)) {
+    if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+        if number < min {
+            report(format!("must be >= {min}, found {number}"));
+        }
+    }
+    if let Some(min) = schema.get("exclusiveMinimum").and_then(Value::as_f64) {
+        if number <= min {
+            report(format!("must be > {min}, found {number}"));
+        }
+    }
+}
+
+fn expected_label(schema_type: &str) -> &'static str {
+    match schema_type {
+        "object" => "a table",
+        "array" => "an array",
+        "string" => "a string",
+        "boolean" => "a boolean",
+        "integer" => "an integer",
+        "number" => "a number",
+        _ => "a value",
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+fn issue(source: &str, field: &str, offset: usize, message: String) -> ConfigIssue {
+    let (line, column) = line_column(source, offset);
+    ConfigIssue {
+        field: field.to_string(),
+        line,
+        column,
+        message,
+    }
+}
+
+// 1-based line/column for a byte offset.
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map(|tail| tail.chars().count() + 1)
+        .unwrap_or(1);
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_config_source, config_schema};
+    use crate::config::Config;
+    use serde_json::Value;
+
+    const SAMPLE: &str = include_str!("../../../ops/configs/sample.toml");
+
+    fn assert_same_keys(path: &str, value: &Value, schema: &Value) {
+        let Some(object) = value.as_object() else {
+            return;
+        };
+        let properties = schema["properties"].as_object().expect("object schema");
+        let mut fields: Vec<&String> = object.keys().collect();
+        let mut props: Vec<&String> = properties.keys().collect();
+        fields.sort();
+        props.sort();
+        assert_eq!(fields, props, "schema properties out of sync at '{path}'");
+        for (key, child) in object {
+            if child.is_object() {
+                assert_same_keys(key, child, &properties[key]);
+            }
+        }
+    }
+
+    #[test]
+    fn schema_properties_match_config_structs() {
+        // Serializing through serde_json keeps `None` fields as nulls, so every struct field shows up.
+        let config: Config = toml::from_str(SAMPLE).expect("sample parses");
+        let value = serde_json::to_value(&config).unwrap();
+        assert_same_keys("", &value, &config_schema());
+        for source in [
+            SAMPLE,
+            include_str!("../../../ops/configs/quickstart.toml"),
+            include_str!("../../../ops/configs/llm_gemini.toml"),
+        ] {
+            assert_eq!(check_config_source(source), Vec::new());
+        }
+    }
+
+    #[test]
+    fn check_reports_field_and_line_for_each_issue() {
+        let source = SAMPLE
+            .replace("mode = \"baseline\"", "mode = \"llm\"")
+            .replace("fee_bps = 10.0", "fee_bps = \"ten\"")
+            .replace("[paper]", "[paper]\nspeed = 2");
+        let issues = check_config_source(&source);
+        let line_of = |needle: &str| {
+            source
+                .lines()
+                .position(|line| line.starts_with(needle))
+                .unwrap()
+                + 1
+        };
+
+        assert_eq!(issues.len(), 3, "{issues:?}");
+        let by_field = |field: &str| issues.iter().find(|i| i.field == field).unwrap();
+        assert_eq!(by_field("agent.mode").line, line_of("mode = \"llm\""));
+        assert!(by_field("agent.mode").message.contains("baseline"));
+        assert_eq!(by_field("costs.fee_bps").line, line_of("fee_bps"));
+        assert!(by_field("costs.fee_bps")
+            .message
+            .contains("expected a number, found string"));
+        assert_eq!(by_field("paper.speed").line, line_of("speed"));
+
+        let missing = check_config_source("[run]\nrun_id = \"x\"\n");
+        assert!(missing
+            .iter()
+            .any(|i| i.field == "run" && i.message.contains("'symbol'")));
+        assert!(missing
+            .iter()
+            .any(|i| i.message.contains("missing required section [agent]")));
+
+        let broken = check_config_source("[run\nrun_id = 1");
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].line, 1);
+    }
+}
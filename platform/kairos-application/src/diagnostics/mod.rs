@@ -0,0 +1,193 @@
+use crate::config::{build_ohlcv_query, AgentMode, Config};
+use crate::shared::{
+    build_sentiment_inputs, load_sentiment, normalize_timeframe_label, parse_duration_like,
+};
+use kairos_domain::repositories::agent::AgentClient;
+use kairos_domain::repositories::market_data::MarketDataRepository;
+use kairos_domain::repositories::sentiment::SentimentRepository;
+use std::path::Path;
+use tracing::info_span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    // Not applicable for this config (e.g. no sentiment file, non-remote agent).
+    Skip,
+}
+
+impl CheckStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "pass",
+            CheckStatus::Fail => "fail",
+            CheckStatus::Skip => "skip",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DoctorCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    pub fn passed(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|check| check.status != CheckStatus::Fail)
+    }
+}
+
+// Adapter construction can fail on its own (missing db.url, bad agent url); those errors are
+// reported as failed checks instead of aborting the whole diagnosis.
+pub fn run_doctor(
+    config: &Config,
+    market_data: Result<&dyn MarketDataRepository, String>,
+    agent: Result<Option<&dyn AgentClient>, String>,
+    sentiment_repo: Result<&dyn SentimentRepository, String>,
+) -> DoctorReport {
+    let _span = info_span!("doctor", run_id = %config.run.run_id).entered();
+
+    let checks = vec![
+        check("db", check_db(config, market_data)),
+        check_agent(config, agent),
+        check("out_dir", check_out_dir(Path::new(&config.paths.out_dir))),
+        check_sentiment(config, sentiment_repo),
+    ];
+    for entry in &checks {
+        metrics::counter!(
+            "kairos.doctor.checks_total",
+            "check" => entry.name,
+            "status" => entry.status.as_str()
+        )
+        .increment(1);
+    }
+    DoctorReport { checks }
+}
+
+fn check(name: &'static str, result: Result<String, String>) -> DoctorCheck {
+    match result {
+        Ok(detail) => DoctorCheck {
+            name,
+            status: CheckStatus::Pass,
+            detail,
+        },
+        Err(detail) => DoctorCheck {
+            name,
+            status: CheckStatus::Fail,
+            detail,
+        },
+    }
+}
+
+fn skip(name: &'static str, detail: String) -> DoctorCheck {
+    DoctorCheck {
+        name,
+        status: CheckStatus::Skip,
+        detail,
+    }
+}
+
+fn check_db(
+    config: &Config,
+    market_data: Result<&dyn MarketDataRepository, String>,
+) -> Result<String, String> {
+    let market_data = market_data?;
+    let timeframe = normalize_timeframe_label(
+        config
+            .db
+            .source_timeframe
+            .as_deref()
+            .unwrap_or(&config.run.timeframe),
+    )?;
+    let query = build_ohlcv_query(config, &timeframe, parse_duration_like(&timeframe)?)
+        .map_err(|err| err.to_string())?;
+    let coverage = market_data.ohlcv_coverage(&query)?;
+    let scope = format!(
+        "{} {} ({}/{}) in {}",
+        query.symbol, timeframe, query.exchange, query.market, config.db.ohlcv_table
+    );
+    if coverage.rows == 0 {
+        return Err(format!("connected, but no rows for {scope}"));
+    }
+    Ok(format!(
+        "{} rows for {scope}, {} .. {}",
+        coverage.rows,
+        format_ts(coverage.first_ts),
+        format_ts(coverage.last_ts)
+    ))
+}
+
+fn check_agent(config: &Config, agent: Result<Option<&dyn AgentClient>, String>) -> DoctorCheck {
+    if !matches!(config.agent.mode, AgentMode::Remote) {
+        return skip(
+            "agent",
+            format!("agent.mode = {:?}", config.agent.mode).to_lowercase(),
+        );
+    }
+    let result = agent.and_then(|agent| match agent {
+        Some(agent) => agent
+            .health_check()
+            .map(|_| format!("{} is healthy", config.agent.url))
+            .map_err(|err| err.to_string()),
+        None => Err("remote agent client not configured".to_string()),
+    });
+    check("agent", result)
+}
+
+fn check_out_dir(out_dir: &Path) -> Result<String, String> {
+    std::fs::create_dir_all(out_dir)
+        .map_err(|err| format!("cannot create {}: {err}", out_dir.display()))?;
+    let probe = out_dir.join(format!(".kairos_doctor_{}", std::process::id()));
+    std::fs::write(&probe, b"ok")
+        .map_err(|err| format!("{} is not writable: {err}", out_dir.display()))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(format!("{} is writable", out_dir.display()))
+}
+
+fn check_sentiment(
+    config: &Config,
+    sentiment_repo: Result<&dyn SentimentRepository, String>,
+) -> DoctorCheck {
+    let inputs = match build_sentiment_inputs(config) {
+        Ok(Some(inputs)) => inputs,
+        Ok(None) => return skip("sentiment", "paths.sentiment_path not set".to_string()),
+        Err(err) => return check("sentiment", Err(err)),
+    };
+    let source = match inputs.as_slice() {
+        [input] => input.query.source.describe(),
+        _ => {
+            let names: Vec<&str> = inputs.iter().map(|input| input.name.as_str()).collect();
+            format!("{} sources ({})", inputs.len(), names.join(", "))
+        }
+    };
+    let result = sentiment_repo
+        .and_then(|repo| load_sentiment(config, &inputs, repo).map_err(|err| err.to_string()))
+        .map(|loaded| {
+            format!(
+                "{}: {} points, columns [{}], {} missing, {} invalid, {} dropped",
+                source,
+                loaded.points.len(),
+                loaded.report.schema.join(", "),
+                loaded.report.missing_values,
+                loaded.report.invalid_values,
+                loaded.report.dropped_rows
+            )
+        })
+        .map_err(|err| format!("{source}: {err}"));
+    check("sentiment", result)
+}
+
+fn format_ts(ts: Option<i64>) -> String {
+    ts.and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_else(|| "?".to_string())
+}
@@ -0,0 +1,41 @@
+use crate::config::ConfigError;
+use kairos_domain::errors::{AgentError, EngineError};
+
+// Why a validate, backtest, paper or live run, or a report over a finished run, failed. Callers
+// match on the variant (the CLI picks its exit code from it); the message is what the CLI prints.
+#[derive(Debug, thiserror::Error)]
+pub enum RunError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error(transparent)]
+    Engine(#[from] EngineError),
+    #[error(transparent)]
+    Agent(#[from] AgentError),
+    // Market data or sentiment could not be loaded, or cannot feed the run.
+    #[error("{0}")]
+    Data(String),
+    #[error("strict validation failed: {0}")]
+    StrictValidation(String),
+    // Writing the run's artifacts or paper session failed.
+    #[error("{0}")]
+    Artifacts(String),
+    // A caller-supplied argument (output path, run dir, ...) that cannot be used.
+    #[error("{0}")]
+    Usage(String),
+    // Stopped through its `RunControl`; holds what was stopped (`backtest`, `paper run`, ...).
+    #[error("{0} cancelled")]
+    Cancelled(&'static str),
+    // Exchange connections and anything else outside the run's inputs and outputs.
+    #[error("{0}")]
+    Runtime(String),
+}
+
+impl RunError {
+    // For engine results: `EngineError::Cancelled` is reported as the cancelled `what`.
+    pub(crate) fn engine(what: &'static str) -> impl FnOnce(EngineError) -> Self {
+        move |err| match err {
+            EngineError::Cancelled => RunError::Cancelled(what),
+            err => RunError::Engine(err),
+        }
+    }
+}
@@ -272,7 +272,7 @@ mod tests {
     fn bars(n: usize) -> Vec<Bar> {
         (0..n)
             .map(|i| Bar {
-                symbol: "BTCUSDT".to_string(),
+                symbol: "BTCUSDT".into(),
                 timestamp: i as i64 * 60,
                 open: 1.0,
                 high: 1.0,
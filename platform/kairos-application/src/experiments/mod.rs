@@ -1,2 +1,7 @@
 pub mod cpcv;
+pub mod pruning;
+pub mod ranking;
+pub mod rules;
+pub mod search;
 pub mod sweep;
+pub mod walkforward;
@@ -0,0 +1,315 @@
+use kairos_domain::services::engine::backtest::{BarProgress, RunControl};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PruneRule {
+    // Stop a trial that is below the median of the trials that reached the same checkpoint.
+    #[default]
+    Median,
+    // Asynchronous successive halving: only the top `1/eta` at each checkpoint keep running.
+    Halving,
+}
+
+// `[sweep.pruning]`: backtest trials are checked at each checkpoint (share of the split's time
+// range) and stopped early when they fall behind the trials already seen there.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PruningConfig {
+    pub rule: Option<PruneRule>,
+    // Defaults to [0.25, 0.5, 0.75].
+    pub checkpoints: Option<Vec<f64>>,
+    // Trials that must reach a checkpoint before anything is pruned there (default 4).
+    pub min_trials: Option<usize>,
+    // Halving rate (default 3).
+    pub eta: Option<f64>,
+    // sharpe | net_profit | max_drawdown, measured on the equity so far. Defaults to the sweep
+    // objective when it is one of these, otherwise sharpe.
+    pub metric: Option<String>,
+}
+
+const DEFAULT_CHECKPOINTS: [f64; 3] = [0.25, 0.5, 0.75];
+const DEFAULT_MIN_TRIALS: usize = 4;
+const DEFAULT_ETA: f64 = 3.0;
+pub(crate) const PRUNING_METRICS: &[&str] = &["sharpe", "net_profit", "max_drawdown"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunningMetric {
+    Sharpe,
+    NetProfit,
+    MaxDrawdown,
+}
+
+// Shared by the workers of one split; holds the scores every trial reported at each checkpoint.
+#[derive(Debug)]
+pub(crate) struct Pruner {
+    checkpoints: Vec<f64>,
+    keep_fraction: f64,
+    min_trials: usize,
+    metric: RunningMetric,
+    metric_label: String,
+    maximize: bool,
+    start_ts: i64,
+    end_ts: i64,
+    rungs: Mutex<Vec<Vec<f64>>>,
+}
+
+impl Pruner {
+    // `objective`/`maximize` are the sweep's; `start_ts..=end_ts` is the split's bar range.
+    pub(crate) fn new(
+        config: &PruningConfig,
+        objective: &str,
+        maximize: bool,
+        start_ts: i64,
+        end_ts: i64,
+    ) -> Result<Self, String> {
+        let mut checkpoints = config
+            .checkpoints
+            .clone()
+            .unwrap_or_else(|| DEFAULT_CHECKPOINTS.to_vec());
+        if checkpoints.is_empty() || checkpoints.iter().any(|c| !(*c > 0.0 && *c < 1.0)) {
+            return Err("sweep.pruning.checkpoints must be a non-empty list in (0, 1)".to_string());
+        }
+        checkpoints.sort_by(|a, b| a.total_cmp(b));
+        checkpoints.dedup();
+
+        let keep_fraction = match config.rule.unwrap_or_default() {
+            PruneRule::Median => 0.5,
+            PruneRule::Halving => {
+                let eta = config.eta.unwrap_or(DEFAULT_ETA);
+                if !(eta.is_finite() && eta > 1.0) {
+                    return Err("sweep.pruning.eta must be > 1".to_string());
+                }
+                1.0 / eta
+            }
+        };
+
+        let explicit = config.metric.as_deref().map(|m| m.trim().to_lowercase());
+        let label = match &explicit {
+            Some(metric) => metric.clone(),
+            None if PRUNING_METRICS.contains(&objective) => objective.to_string(),
+            None => "sharpe".to_string(),
+        };
+        let metric = match label.as_str() {
+            "sharpe" => RunningMetric::Sharpe,
+            "net_profit" => RunningMetric::NetProfit,
+            "max_drawdown" => RunningMetric::MaxDrawdown,
+            _ => {
+                return Err(format!(
+                    "sweep.pruning.metric must be one of: {}",
+                    PRUNING_METRICS.join(", ")
+                ))
+            }
+        };
+        // Only follow the sweep direction when pruning on the sweep objective itself.
+        let maximize = if explicit.is_none() && label == objective {
+            maximize
+        } else {
+            metric != RunningMetric::MaxDrawdown
+        };
+
+        Ok(Self {
+            rungs: Mutex::new(vec![Vec::new(); checkpoints.len()]),
+            checkpoints,
+            keep_fraction,
+            min_trials: config.min_trials.unwrap_or(DEFAULT_MIN_TRIALS).max(1),
+            metric,
+            metric_label: label,
+            maximize,
+            start_ts,
+            end_ts,
+        })
+    }
+
+    // Records `value` at checkpoint `rung` and returns whether the trial should stop there.
+    fn report(&self, rung: usize, value: f64) -> bool {
+        let score = if self.maximize { value } else { -value };
+        let Ok(mut rungs) = self.rungs.lock() else {
+            return false;
+        };
+        let seen = &mut rungs[rung];
+        seen.push(score);
+        if seen.len() < self.min_trials {
+            return false;
+        }
+        let mut ranked = seen.clone();
+        ranked.sort_by(|a, b| b.total_cmp(a));
+        let keep = ((ranked.len() as f64 * self.keep_fraction).ceil() as usize).max(1);
+        score < ranked[keep - 1]
+    }
+
+    fn checkpoint_ts(&self, rung: usize) -> i64 {
+        let span = (self.end_ts - self.start_ts) as f64;
+        self.start_ts + (span * self.checkpoints[rung]).round() as i64
+    }
+}
+
+// Cancels the trial's backtest once `TrialPruning` decides to stop it.
+#[derive(Debug, Default)]
+pub(crate) struct PruneSignal {
+    pruned: Cell<bool>,
+}
+
+impl RunControl for PruneSignal {
+    fn should_cancel(&self) -> bool {
+        self.pruned.get()
+    }
+}
+
+// Per-trial state: tracks the running equity and reports it to the pruner at each checkpoint.
+pub(crate) struct TrialPruning<'a> {
+    pruner: &'a Pruner,
+    signal: &'a PruneSignal,
+    next_rung: usize,
+    initial_equity: Option<f64>,
+    prev_equity: f64,
+    peak_equity: f64,
+    max_drawdown: f64,
+    returns: usize,
+    sum_returns: f64,
+    sum_sq_returns: f64,
+    reason: Option<String>,
+}
+
+impl<'a> TrialPruning<'a> {
+    pub(crate) fn new(pruner: &'a Pruner, signal: &'a PruneSignal) -> Self {
+        Self {
+            pruner,
+            signal,
+            next_rung: 0,
+            initial_equity: None,
+            prev_equity: 0.0,
+            peak_equity: 0.0,
+            max_drawdown: 0.0,
+            returns: 0,
+            sum_returns: 0.0,
+            sum_sq_returns: 0.0,
+            reason: None,
+        }
+    }
+
+    pub(crate) fn on_bar(&mut self, bar: &BarProgress) {
+        let equity = bar.equity;
+        match self.initial_equity {
+            None => {
+                self.initial_equity = Some(equity);
+                self.peak_equity = equity;
+            }
+            Some(_) => {
+                if self.prev_equity > 0.0 {
+                    let r = equity / self.prev_equity - 1.0;
+                    self.returns += 1;
+                    self.sum_returns += r;
+                    self.sum_sq_returns += r * r;
+                }
+                self.peak_equity = self.peak_equity.max(equity);
+            }
+        }
+        if self.peak_equity > 0.0 {
+            self.max_drawdown = self
+                .max_drawdown
+                .max((self.peak_equity - equity) / self.peak_equity);
+        }
+        self.prev_equity = equity;
+
+        while self.reason.is_none()
+            && self.next_rung < self.pruner.checkpoints.len()
+            && bar.timestamp >= self.pruner.checkpoint_ts(self.next_rung)
+        {
+            let rung = self.next_rung;
+            self.next_rung += 1;
+            let value = self.current_value(equity);
+            if self.pruner.report(rung, value) {
+                let checkpoint = self.pruner.checkpoints[rung];
+                self.signal.pruned.set(true);
+                self.reason = Some(format!(
+                    "pruned at {:.0}% of bars ({} {value:.4})",
+                    checkpoint * 100.0,
+                    self.pruner.metric_label
+                ));
+            }
+        }
+    }
+
+    fn current_value(&self, equity: f64) -> f64 {
+        match self.pruner.metric {
+            RunningMetric::NetProfit => equity - self.initial_equity.unwrap_or(equity),
+            RunningMetric::MaxDrawdown => self.max_drawdown,
+            RunningMetric::Sharpe => {
+                if self.returns < 2 {
+                    return 0.0;
+                }
+                let n = self.returns as f64;
+                let mean = self.sum_returns / n;
+                let var = (self.sum_sq_returns / n - mean * mean).max(0.0);
+                if var > 0.0 {
+                    mean / var.sqrt() * n.sqrt()
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    pub(crate) fn into_reason(self) -> Option<String> {
+        self.reason
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PruneRule, Pruner, PruningConfig};
+
+    fn config(rule: PruneRule) -> PruningConfig {
+        PruningConfig {
+            rule: Some(rule),
+            checkpoints: Some(vec![0.5]),
+            min_trials: Some(3),
+            eta: Some(3.0),
+            metric: None,
+        }
+    }
+
+    #[test]
+    fn median_rule_prunes_below_median_after_min_trials() {
+        let pruner =
+            Pruner::new(&config(PruneRule::Median), "sharpe", true, 0, 100).expect("pruner");
+        assert_eq!(pruner.checkpoint_ts(0), 50);
+        assert!(!pruner.report(0, 1.0));
+        assert!(!pruner.report(0, 2.0));
+        assert!(pruner.report(0, 0.5));
+        assert!(!pruner.report(0, 3.0));
+    }
+
+    #[test]
+    fn halving_keeps_top_third_and_drawdown_is_minimized() {
+        let pruner =
+            Pruner::new(&config(PruneRule::Halving), "sharpe", true, 0, 100).expect("pruner");
+        for value in [3.0, 2.0] {
+            pruner.report(0, value);
+        }
+        // Third of three is outside the top ceil(3/3) = 1.
+        assert!(pruner.report(0, 2.5));
+
+        let mut dd = config(PruneRule::Median);
+        dd.metric = Some("max_drawdown".to_string());
+        let pruner = Pruner::new(&dd, "sharpe", true, 0, 100).expect("pruner");
+        pruner.report(0, 0.05);
+        pruner.report(0, 0.10);
+        assert!(pruner.report(0, 0.30));
+
+        assert!(Pruner::new(
+            &PruningConfig {
+                checkpoints: Some(vec![1.5]),
+                ..config(PruneRule::Median)
+            },
+            "sharpe",
+            true,
+            0,
+            100
+        )
+        .is_err());
+    }
+}
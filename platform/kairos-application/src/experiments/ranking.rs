@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RankMode {
+    // Single metric: `leaderboard.sort_by` / `leaderboard.descending`.
+    #[default]
+    Metric,
+    // Weighted sum of the `leaderboard.weights` metrics, each min-max normalized over the runs.
+    Weighted,
+    // Pareto fronts over the `leaderboard.weights` metrics (the sign is the direction), ties broken
+    // by the weighted score.
+    Pareto,
+}
+
+impl RankMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RankMode::Metric => "metric",
+            RankMode::Weighted => "weighted",
+            RankMode::Pareto => "pareto",
+        }
+    }
+}
+
+// `values[row][k]` is metric `k` of a run and `weights[k]` its weight. Metrics are flipped so that
+// higher is better, then min-max normalized over the rows so metrics on different scales can be
+// summed. A metric that is equal across every run contributes nothing; a non-finite one scores 0.
+pub fn weighted_scores(values: &[Vec<f64>], weights: &[f64]) -> Vec<f64> {
+    let mut scores = vec![0.0; values.len()];
+    for (k, weight) in weights.iter().enumerate() {
+        let column: Vec<f64> = values.iter().map(|row| row[k] * weight.signum()).collect();
+        let finite = column.iter().copied().filter(|v| v.is_finite());
+        let (lo, hi) = finite.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+            (lo.min(v), hi.max(v))
+        });
+        if hi <= lo {
+            continue;
+        }
+        for (score, v) in scores.iter_mut().zip(&column) {
+            if v.is_finite() {
+                *score += weight.abs() * (v - lo) / (hi - lo);
+            }
+        }
+    }
+    scores
+}
+
+// Non-dominated sorting over the same layout as `weighted_scores`: returns the front of each row,
+// 1 being the Pareto front (no other run is at least as good on every metric and better on one).
+pub fn pareto_fronts(values: &[Vec<f64>], weights: &[f64]) -> Vec<usize> {
+    let oriented: Vec<Vec<f64>> = values
+        .iter()
+        .map(|row| {
+            weights
+                .iter()
+                .enumerate()
+                .map(|(k, weight)| {
+                    let v = row[k] * weight.signum();
+                    if v.is_nan() {
+                        f64::NEG_INFINITY
+                    } else {
+                        v
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    let dominates = |a: &[f64], b: &[f64]| {
+        a.iter().zip(b).all(|(x, y)| x >= y) && a.iter().zip(b).any(|(x, y)| x > y)
+    };
+
+    let mut fronts = vec![0; values.len()];
+    let mut front = 0;
+    let mut remaining: Vec<usize> = (0..values.len()).collect();
+    while !remaining.is_empty() {
+        front += 1;
+        let current: Vec<usize> = remaining
+            .iter()
+            .copied()
+            .filter(|&i| {
+                !remaining
+                    .iter()
+                    .any(|&j| j != i && dominates(&oriented[j], &oriented[i]))
+            })
+            .collect();
+        for &i in &current {
+            fronts[i] = front;
+        }
+        remaining.retain(|i| !current.contains(i));
+    }
+    fronts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pareto_fronts, weighted_scores};
+
+    #[test]
+    fn weighted_scores_normalize_and_respect_direction() {
+        // sharpe (maximize, weight 1) and max_drawdown (minimize, weight -1).
+        let values = vec![vec![2.0, 0.30], vec![1.0, 0.10], vec![1.5, 0.20]];
+        let scores = weighted_scores(&values, &[1.0, -1.0]);
+        assert_eq!(scores, vec![1.0, 1.0, 1.0]);
+
+        let scores = weighted_scores(&values, &[1.0, -3.0]);
+        assert!(scores[1] > scores[2] && scores[2] > scores[0], "{scores:?}");
+
+        // A constant metric adds nothing; NaN scores as the worst.
+        let values = vec![vec![1.0, 5.0], vec![2.0, 5.0], vec![f64::NAN, 5.0]];
+        assert_eq!(weighted_scores(&values, &[1.0, 1.0]), vec![0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn pareto_fronts_peel_non_dominated_runs() {
+        let values = vec![
+            vec![2.0, 0.30],
+            vec![1.0, 0.10],
+            vec![1.0, 0.20],
+            vec![0.5, 0.40],
+            vec![2.0, 0.30],
+        ];
+        let fronts = pareto_fronts(&values, &[1.0, -1.0]);
+        assert_eq!(fronts, vec![1, 1, 2, 3, 1]);
+    }
+}
@@ -0,0 +1,502 @@
+use super::search::Assignment;
+use serde::{Deserialize, Serialize};
+
+// `[[derived]]`: a param computed from others, e.g. `strategy.sma_long = strategy.sma_short * 4`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SweepDerived {
+    pub path: String,
+    pub expr: String,
+}
+
+// `[[constraints]]`: assignments where `expr` is false are dropped before any trial runs.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SweepConstraint {
+    pub expr: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Num(f64),
+    Bool(bool),
+    Str(String),
+}
+
+impl Value {
+    fn from_toml(value: &toml::Value) -> Result<Self, String> {
+        match value {
+            toml::Value::Integer(v) => Ok(Value::Num(*v as f64)),
+            toml::Value::Float(v) => Ok(Value::Num(*v)),
+            toml::Value::Boolean(v) => Ok(Value::Bool(*v)),
+            toml::Value::String(v) => Ok(Value::Str(v.clone())),
+            other => Err(format!("unsupported value in expression: {other}")),
+        }
+    }
+
+    fn num(&self) -> Result<f64, String> {
+        match self {
+            Value::Num(v) => Ok(*v),
+            other => Err(format!("expected a number, got {other:?}")),
+        }
+    }
+
+    fn bool(&self) -> Result<bool, String> {
+        match self {
+            Value::Bool(v) => Ok(*v),
+            other => Err(format!("expected a boolean, got {other:?}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(f64),
+    Str(String),
+    Ident(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+    Comma,
+}
+
+const OPERATORS: [&str; 17] = [
+    "<=", ">=", "==", "!=", "&&", "||", "<", ">", "+", "-", "*", "/", "%", "!", "=", "&", "|",
+];
+
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit()))
+        {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_ascii_digit() || chars[i] == '.' || chars[i] == '_')
+            {
+                i += 1;
+            }
+            let raw: String = chars[start..i].iter().filter(|c| **c != '_').collect();
+            let value = raw
+                .parse::<f64>()
+                .map_err(|_| format!("invalid number '{raw}'"))?;
+            tokens.push(Token::Num(value));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+            {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else if c == '"' || c == '\'' {
+            let end = chars[i + 1..]
+                .iter()
+                .position(|n| *n == c)
+                .ok_or_else(|| "unterminated string".to_string())?;
+            tokens.push(Token::Str(chars[i + 1..i + 1 + end].iter().collect()));
+            i += end + 2;
+        } else if c == '(' {
+            tokens.push(Token::LParen);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::RParen);
+            i += 1;
+        } else if c == ',' {
+            tokens.push(Token::Comma);
+            i += 1;
+        } else {
+            let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+            let op = OPERATORS
+                .iter()
+                .find(|op| rest.starts_with(**op))
+                .ok_or_else(|| format!("unexpected character '{c}'"))?;
+            if matches!(*op, "=" | "&" | "|") {
+                return Err(format!("unexpected '{op}' (did you mean '{op}{op}'?)"));
+            }
+            tokens.push(Token::Op(op));
+            i += op.len();
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Lit(Value),
+    Param(String),
+    Neg(Box<Node>),
+    Not(Box<Node>),
+    Binary(&'static str, Box<Node>, Box<Node>),
+    Call(String, Vec<Node>),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat_op(&mut self, ops: &[&str]) -> Option<&'static str> {
+        let op = match self.peek() {
+            Some(Token::Op(op)) if ops.contains(op) => *op,
+            Some(Token::Ident(word)) if word == "and" && ops.contains(&"&&") => "&&",
+            Some(Token::Ident(word)) if word == "or" && ops.contains(&"||") => "||",
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(op)
+    }
+
+    fn binary(
+        &mut self,
+        ops: &[&str],
+        operand: fn(&mut Self) -> Result<Node, String>,
+    ) -> Result<Node, String> {
+        let mut left = operand(self)?;
+        while let Some(op) = self.eat_op(ops) {
+            left = Node::Binary(op, Box::new(left), Box::new(operand(self)?));
+        }
+        Ok(left)
+    }
+
+    fn or(&mut self) -> Result<Node, String> {
+        self.binary(&["||"], Self::and)
+    }
+
+    fn and(&mut self) -> Result<Node, String> {
+        self.binary(&["&&"], Self::not)
+    }
+
+    fn not(&mut self) -> Result<Node, String> {
+        let negated = match self.peek() {
+            Some(Token::Op("!")) => true,
+            Some(Token::Ident(word)) => word == "not",
+            _ => false,
+        };
+        if negated {
+            self.pos += 1;
+            return Ok(Node::Not(Box::new(self.not()?)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Node, String> {
+        let left = self.additive()?;
+        match self.eat_op(&["<", "<=", ">", ">=", "==", "!="]) {
+            Some(op) => Ok(Node::Binary(op, Box::new(left), Box::new(self.additive()?))),
+            None => Ok(left),
+        }
+    }
+
+    fn additive(&mut self) -> Result<Node, String> {
+        self.binary(&["+", "-"], Self::multiplicative)
+    }
+
+    fn multiplicative(&mut self) -> Result<Node, String> {
+        self.binary(&["*", "/", "%"], Self::unary)
+    }
+
+    fn unary(&mut self) -> Result<Node, String> {
+        if self.eat_op(&["-"]).is_some() {
+            return Ok(Node::Neg(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Node, String> {
+        match self.next() {
+            Some(Token::Num(v)) => Ok(Node::Lit(Value::Num(v))),
+            Some(Token::Str(v)) => Ok(Node::Lit(Value::Str(v))),
+            Some(Token::LParen) => {
+                let inner = self.or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(inner),
+                    _ => Err("expected ')'".to_string()),
+                }
+            }
+            Some(Token::Ident(name)) => match name.as_str() {
+                "true" => Ok(Node::Lit(Value::Bool(true))),
+                "false" => Ok(Node::Lit(Value::Bool(false))),
+                _ if self.peek() == Some(&Token::LParen) => {
+                    self.pos += 1;
+                    let mut args = Vec::new();
+                    if self.peek() == Some(&Token::RParen) {
+                        self.pos += 1;
+                    } else {
+                        loop {
+                            args.push(self.or()?);
+                            match self.next() {
+                                Some(Token::Comma) => continue,
+                                Some(Token::RParen) => break,
+                                _ => return Err(format!("expected ',' or ')' in {name}(...)")),
+                            }
+                        }
+                    }
+                    check_call(&name, args.len())?;
+                    Ok(Node::Call(name, args))
+                }
+                _ => Ok(Node::Param(name)),
+            },
+            Some(token) => Err(format!("unexpected {token:?}")),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
+
+fn check_call(name: &str, args: usize) -> Result<(), String> {
+    let ok = match name {
+        "min" | "max" => args >= 1,
+        "abs" | "round" | "floor" | "ceil" | "sqrt" => args == 1,
+        _ => return Err(format!("unknown function '{name}'")),
+    };
+    if ok {
+        Ok(())
+    } else {
+        Err(format!("wrong number of arguments for {name}()"))
+    }
+}
+
+// Arithmetic (`+ - * / %`), comparisons, `&&`/`and`, `||`/`or`, `!`/`not`, numbers, quoted
+// strings, `true`/`false`, `min max abs round floor ceil sqrt`, and dotted config paths
+// (`strategy.sma_short`) that resolve to the trial's param or the base config value.
+#[derive(Debug, Clone)]
+pub struct Expr {
+    source: String,
+    node: Node,
+}
+
+impl Expr {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let wrap = |err: String| format!("invalid expression '{source}': {err}");
+        let tokens = tokenize(source).map_err(wrap)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let node = parser.or().map_err(wrap)?;
+        if let Some(token) = parser.peek() {
+            return Err(wrap(format!("unexpected {token:?}")));
+        }
+        Ok(Self {
+            source: source.to_string(),
+            node,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn eval(&self, lookup: &dyn Fn(&str) -> Option<toml::Value>) -> Result<Value, String> {
+        eval(&self.node, lookup).map_err(|err| format!("expression '{}': {err}", self.source))
+    }
+}
+
+fn eval(node: &Node, lookup: &dyn Fn(&str) -> Option<toml::Value>) -> Result<Value, String> {
+    match node {
+        Node::Lit(value) => Ok(value.clone()),
+        Node::Param(path) => {
+            let value = lookup(path).ok_or_else(|| format!("unknown param '{path}'"))?;
+            Value::from_toml(&value)
+        }
+        Node::Neg(inner) => Ok(Value::Num(-eval(inner, lookup)?.num()?)),
+        Node::Not(inner) => Ok(Value::Bool(!eval(inner, lookup)?.bool()?)),
+        Node::Binary(op, left, right) => {
+            let left = eval(left, lookup)?;
+            // Short-circuit so `a > 0 && b / a > 2` does not evaluate the right side needlessly.
+            match *op {
+                "&&" if !left.bool()? => return Ok(Value::Bool(false)),
+                "||" if left.bool()? => return Ok(Value::Bool(true)),
+                "&&" | "||" => return Ok(Value::Bool(eval(right, lookup)?.bool()?)),
+                _ => {}
+            }
+            let right = eval(right, lookup)?;
+            match *op {
+                "==" => return Ok(Value::Bool(left == right)),
+                "!=" => return Ok(Value::Bool(left != right)),
+                _ => {}
+            }
+            let (a, b) = (left.num()?, right.num()?);
+            Ok(match *op {
+                "+" => Value::Num(a + b),
+                "-" => Value::Num(a - b),
+                "*" => Value::Num(a * b),
+                "/" => Value::Num(a / b),
+                "%" => Value::Num(a % b),
+                "<" => Value::Bool(a < b),
+                "<=" => Value::Bool(a <= b),
+                ">" => Value::Bool(a > b),
+                ">=" => Value::Bool(a >= b),
+                other => return Err(format!("unknown operator '{other}'")),
+            })
+        }
+        Node::Call(name, args) => {
+            let args = args
+                .iter()
+                .map(|arg| eval(arg, lookup)?.num())
+                .collect::<Result<Vec<f64>, String>>()?;
+            Ok(Value::Num(match name.as_str() {
+                "min" => args.iter().copied().fold(f64::INFINITY, f64::min),
+                "max" => args.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+                "abs" => args[0].abs(),
+                "round" => args[0].round(),
+                "floor" => args[0].floor(),
+                "ceil" => args[0].ceil(),
+                "sqrt" => args[0].sqrt(),
+                other => return Err(format!("unknown function '{other}'")),
+            }))
+        }
+    }
+}
+
+// The derived params and constraints of a sweep, applied to each raw assignment.
+#[derive(Debug, Clone, Default)]
+pub struct ParamRules {
+    derived: Vec<(String, Expr)>,
+    constraints: Vec<Expr>,
+}
+
+impl ParamRules {
+    pub fn new(derived: &[SweepDerived], constraints: &[SweepConstraint]) -> Result<Self, String> {
+        Ok(Self {
+            derived: derived
+                .iter()
+                .map(|d| Ok((d.path.clone(), Expr::parse(&d.expr)?)))
+                .collect::<Result<_, String>>()?,
+            constraints: constraints
+                .iter()
+                .map(|c| Expr::parse(&c.expr))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.derived.is_empty() && self.constraints.is_empty()
+    }
+
+    // Adds the derived params (in declaration order, so later ones may use earlier ones) and
+    // returns `None` when a constraint rejects the result. Paths missing from the assignment
+    // resolve against `base`; a derived value is an integer when the base value at its path is.
+    pub fn apply(
+        &self,
+        mut assignment: Assignment,
+        base: &toml::Value,
+    ) -> Result<Option<Assignment>, String> {
+        for (path, expr) in &self.derived {
+            let base_value = lookup_path(base, path);
+            let value = {
+                let lookup = |p: &str| assignment.get(p).cloned().or_else(|| lookup_path(base, p));
+                expr.eval(&lookup)?
+            };
+            let value = match (value, base_value) {
+                (Value::Num(v), Some(toml::Value::Integer(_))) => {
+                    toml::Value::Integer(v.round() as i64)
+                }
+                (Value::Num(v), _) => toml::Value::Float(v),
+                (Value::Bool(v), _) => toml::Value::Boolean(v),
+                (Value::Str(v), _) => toml::Value::String(v),
+            };
+            assignment.insert(path.clone(), value);
+        }
+        let lookup = |p: &str| assignment.get(p).cloned().or_else(|| lookup_path(base, p));
+        for constraint in &self.constraints {
+            let keep = constraint.eval(&lookup)?.bool().map_err(|err| {
+                format!(
+                    "constraint '{}' must be a comparison: {err}",
+                    constraint.source()
+                )
+            })?;
+            if !keep {
+                return Ok(None);
+            }
+        }
+        Ok(Some(assignment))
+    }
+}
+
+fn lookup_path(root: &toml::Value, path: &str) -> Option<toml::Value> {
+    path.split('.')
+        .try_fold(root, |node, key| node.get(key))
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Expr, ParamRules, SweepConstraint, SweepDerived, Value};
+    use crate::experiments::search::Assignment;
+
+    fn eval(src: &str) -> Value {
+        let lookup = |path: &str| match path {
+            "strategy.sma_short" => Some(toml::Value::Integer(10)),
+            "strategy.baseline" => Some(toml::Value::String("sma".to_string())),
+            _ => None,
+        };
+        Expr::parse(src)
+            .expect("parse")
+            .eval(&lookup)
+            .expect("eval")
+    }
+
+    #[test]
+    fn expressions_follow_precedence_and_functions() {
+        assert_eq!(eval("1 + 2 * 3 - 4 / 2"), Value::Num(5.0));
+        assert_eq!(eval("-(1 + 2) % 2"), Value::Num(-1.0));
+        assert_eq!(eval("max(strategy.sma_short * 3, 25)"), Value::Num(30.0));
+        assert_eq!(
+            eval("strategy.sma_short < 20 and strategy.baseline == 'sma'"),
+            Value::Bool(true)
+        );
+        assert_eq!(eval("not (1 < 2) || false"), Value::Bool(false));
+        assert!(Expr::parse("a = b").is_err());
+        assert!(Expr::parse("nope(1)").is_err());
+        assert!(Expr::parse("(1 + 2").is_err());
+        assert!(Expr::parse("1 < 2 < 3").is_err());
+    }
+
+    #[test]
+    fn rules_derive_params_and_reject_invalid_assignments() {
+        let base: toml::Value =
+            toml::from_str("[strategy]\nsma_short = 5\nsma_long = 20\n").expect("base");
+        let rules = ParamRules::new(
+            &[SweepDerived {
+                path: "strategy.sma_long".to_string(),
+                expr: "strategy.sma_short * 2.6".to_string(),
+            }],
+            &[SweepConstraint {
+                expr: "strategy.sma_long <= 40".to_string(),
+            }],
+        )
+        .expect("rules");
+
+        let mut small = Assignment::new();
+        small.insert("strategy.sma_short".to_string(), toml::Value::Integer(10));
+        let applied = rules.apply(small, &base).expect("apply").expect("kept");
+        assert_eq!(applied["strategy.sma_long"], toml::Value::Integer(26));
+
+        let mut big = Assignment::new();
+        big.insert("strategy.sma_short".to_string(), toml::Value::Integer(20));
+        assert!(rules.apply(big, &base).expect("apply").is_none());
+
+        let bad = ParamRules::new(
+            &[],
+            &[SweepConstraint {
+                expr: "strategy.sma_short + 1".to_string(),
+            }],
+        )
+        .expect("rules");
+        assert!(bad.apply(Assignment::new(), &base).is_err());
+    }
+}
@@ -0,0 +1,424 @@
+use super::sweep::SweepParam;
+use kairos_domain::services::rng::SplitMix64;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub type Assignment = BTreeMap<String, toml::Value>;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchStrategy {
+    // Every combination of `values`.
+    #[default]
+    Grid,
+    // `trials` independent draws from `values`/`low..high`.
+    Random,
+    // Tree-structured Parzen estimator: after `init_random` random trials, each suggestion favours
+    // regions where the best quarter of finished trials scored well on the objective.
+    Bayesian,
+}
+
+impl SearchStrategy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SearchStrategy::Grid => "grid",
+            SearchStrategy::Random => "random",
+            SearchStrategy::Bayesian => "bayesian",
+        }
+    }
+}
+
+// Share of finished trials treated as "good" by the Parzen estimator.
+const TPE_GAMMA: f64 = 0.25;
+// Values drawn from the "good" density per parameter; the one with the best good/bad ratio wins.
+const TPE_CANDIDATES: usize = 24;
+// Draws spent looking for an assignment that was not tried yet before giving up.
+const MAX_DRAW_ATTEMPTS: usize = 100;
+
+#[derive(Debug, Clone)]
+enum Domain {
+    Choice(Vec<toml::Value>),
+    Int { low: i64, high: i64, log: bool },
+    Float { low: f64, high: f64, log: bool },
+}
+
+impl Domain {
+    fn from_param(p: &SweepParam) -> Result<Self, String> {
+        let log = p.log.unwrap_or(false);
+        match (&p.low, &p.high) {
+            (None, None) => {
+                if p.log.is_some() {
+                    return Err(format!("sweep param {}: `log` needs `low`/`high`", p.path));
+                }
+                if p.values.is_empty() {
+                    return Err(format!("sweep param has no values: {}", p.path));
+                }
+                Ok(Domain::Choice(p.values.clone()))
+            }
+            (Some(low), Some(high)) => {
+                if !p.values.is_empty() {
+                    return Err(format!(
+                        "sweep param {}: set either `values` or `low`/`high`, not both",
+                        p.path
+                    ));
+                }
+                let domain = match (low, high) {
+                    (toml::Value::Integer(low), toml::Value::Integer(high)) => Domain::Int {
+                        low: *low,
+                        high: *high,
+                        log,
+                    },
+                    _ => Domain::Float {
+                        low: as_f64(low).ok_or_else(|| {
+                            format!("sweep param {}: `low` must be a number", p.path)
+                        })?,
+                        high: as_f64(high).ok_or_else(|| {
+                            format!("sweep param {}: `high` must be a number", p.path)
+                        })?,
+                        log,
+                    },
+                };
+                let (low, high) = domain.bounds();
+                if !(low.is_finite() && high.is_finite() && low < high) {
+                    return Err(format!(
+                        "sweep param {}: `low` must be < `high` (finite)",
+                        p.path
+                    ));
+                }
+                if log && low <= 0.0 {
+                    return Err(format!(
+                        "sweep param {}: `log = true` needs `low` > 0",
+                        p.path
+                    ));
+                }
+                Ok(domain)
+            }
+            _ => Err(format!(
+                "sweep param {}: `low` and `high` must be set together",
+                p.path
+            )),
+        }
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        match self {
+            Domain::Choice(values) => (0.0, values.len() as f64),
+            Domain::Int { low, high, .. } => (*low as f64, *high as f64),
+            Domain::Float { low, high, .. } => (*low, *high),
+        }
+    }
+
+    fn log(&self) -> bool {
+        match self {
+            Domain::Choice(_) => false,
+            Domain::Int { log, .. } | Domain::Float { log, .. } => *log,
+        }
+    }
+
+    fn size(&self) -> Option<u64> {
+        match self {
+            Domain::Choice(values) => Some(values.len() as u64),
+            Domain::Int { low, high, .. } => Some(high.abs_diff(*low).saturating_add(1)),
+            Domain::Float { .. } => None,
+        }
+    }
+
+    // Numeric value mapped to [0, 1] (in log space when `log = true`).
+    fn unit_of(&self, value: &toml::Value) -> Option<f64> {
+        let x = as_f64(value)?;
+        let (low, high) = self.bounds();
+        let u = if self.log() {
+            (x.ln() - low.ln()) / (high.ln() - low.ln())
+        } else {
+            (x - low) / (high - low)
+        };
+        u.is_finite().then_some(u.clamp(0.0, 1.0))
+    }
+
+    fn value_at(&self, u: f64) -> toml::Value {
+        let u = u.clamp(0.0, 1.0);
+        match self {
+            Domain::Choice(values) => {
+                let idx = ((u * values.len() as f64) as usize).min(values.len() - 1);
+                values[idx].clone()
+            }
+            Domain::Int { low, high, log } => {
+                let value = if *log {
+                    let (l, h) = ((*low as f64).ln(), (*high as f64).ln());
+                    (l + u * (h - l)).exp().round() as i64
+                } else {
+                    // Each integer gets an equal slice of [0, 1].
+                    let span = (high - low + 1) as f64;
+                    low + ((u * span) as i64).min(high - low)
+                };
+                toml::Value::Integer(value.clamp(*low, *high))
+            }
+            Domain::Float { low, high, log } => {
+                let value = if *log {
+                    (low.ln() + u * (high.ln() - low.ln())).exp()
+                } else {
+                    low + u * (high - low)
+                };
+                toml::Value::Float(value.clamp(*low, *high))
+            }
+        }
+    }
+
+    fn sample(&self, rng: &mut SplitMix64) -> toml::Value {
+        match self {
+            Domain::Choice(values) => values[rng.next_below(values.len() as u64) as usize].clone(),
+            _ => self.value_at(rng.next_f64()),
+        }
+    }
+
+    fn suggest_tpe(
+        &self,
+        rng: &mut SplitMix64,
+        good: &[&toml::Value],
+        bad: &[&toml::Value],
+    ) -> toml::Value {
+        match self {
+            Domain::Choice(values) => {
+                // Laplace-smoothed category frequencies among good and bad trials.
+                let weights = |seen: &[&toml::Value]| -> Vec<f64> {
+                    values
+                        .iter()
+                        .map(|v| {
+                            (seen.iter().filter(|s| **s == v).count() as f64 + 1.0)
+                                / (seen.len() + values.len()) as f64
+                        })
+                        .collect()
+                };
+                let (l, g) = (weights(good), weights(bad));
+                let total: f64 = l.iter().sum();
+                let mut best = (0, f64::NEG_INFINITY);
+                for _ in 0..TPE_CANDIDATES {
+                    let mut target = rng.next_f64() * total;
+                    let mut idx = values.len() - 1;
+                    for (i, w) in l.iter().enumerate() {
+                        if target < *w {
+                            idx = i;
+                            break;
+                        }
+                        target -= w;
+                    }
+                    let score = l[idx] / g[idx];
+                    if score > best.1 {
+                        best = (idx, score);
+                    }
+                }
+                values[best.0].clone()
+            }
+            _ => {
+                let good: Vec<f64> = good.iter().filter_map(|v| self.unit_of(v)).collect();
+                let bad: Vec<f64> = bad.iter().filter_map(|v| self.unit_of(v)).collect();
+                let (bw_good, bw_bad) = (bandwidth(good.len()), bandwidth(bad.len()));
+                let mut best = (rng.next_f64(), f64::NEG_INFINITY);
+                for _ in 0..TPE_CANDIDATES {
+                    // The good density mixes a uniform prior with one Gaussian per good trial.
+                    let u = if good.is_empty()
+                        || rng.next_below(good.len() as u64 + 1) == good.len() as u64
+                    {
+                        rng.next_f64()
+                    } else {
+                        let center = good[rng.next_below(good.len() as u64) as usize];
+                        (center + bw_good * rng.next_normal()).clamp(0.0, 1.0)
+                    };
+                    let score = parzen(u, &good, bw_good) / parzen(u, &bad, bw_bad);
+                    if score > best.1 {
+                        best = (u, score);
+                    }
+                }
+                self.value_at(best.0)
+            }
+        }
+    }
+}
+
+fn as_f64(value: &toml::Value) -> Option<f64> {
+    match value {
+        toml::Value::Integer(v) => Some(*v as f64),
+        toml::Value::Float(v) => Some(*v),
+        _ => None,
+    }
+}
+
+fn bandwidth(points: usize) -> f64 {
+    (0.3 * (points as f64 + 1.0).powf(-0.2)).max(0.05)
+}
+
+// Density on [0, 1]: uniform prior plus Gaussian kernels, equally weighted.
+fn parzen(u: f64, points: &[f64], bw: f64) -> f64 {
+    let kernels: f64 = points
+        .iter()
+        .map(|p| {
+            let z = (u - p) / bw;
+            (-0.5 * z * z).exp() / (bw * (2.0 * std::f64::consts::PI).sqrt())
+        })
+        .sum();
+    (1.0 + kernels) / (points.len() as f64 + 1.0)
+}
+
+fn assignment_key(assignment: &Assignment) -> String {
+    serde_json::to_string(assignment).unwrap_or_default()
+}
+
+// The `[[params]]` of a sweep as a search space. Grid search only accepts `values` lists.
+#[derive(Debug, Clone)]
+pub struct ParamSpace {
+    params: Vec<(String, Domain)>,
+}
+
+impl ParamSpace {
+    pub fn new(strategy: SearchStrategy, params: &[SweepParam]) -> Result<Self, String> {
+        let mut out = Vec::with_capacity(params.len());
+        for p in params {
+            let domain = Domain::from_param(p)?;
+            if strategy == SearchStrategy::Grid && !matches!(domain, Domain::Choice(_)) {
+                return Err(format!(
+                    "sweep param {}: `low`/`high` ranges need sweep.strategy = \"random\" or \"bayesian\"",
+                    p.path
+                ));
+            }
+            out.push((p.path.clone(), domain));
+        }
+        Ok(Self { params: out })
+    }
+
+    // Number of distinct assignments, `None` when a float range makes it unbounded.
+    pub fn size(&self) -> Option<u64> {
+        self.params.iter().try_fold(1_u64, |acc, (_, domain)| {
+            domain.size().map(|size| acc.saturating_mul(size))
+        })
+    }
+
+    pub fn sample(&self, rng: &mut SplitMix64) -> Assignment {
+        self.params
+            .iter()
+            .map(|(path, domain)| (path.clone(), domain.sample(rng)))
+            .collect()
+    }
+
+    // `history` holds finished trials with their objective, higher is better.
+    pub fn suggest(&self, rng: &mut SplitMix64, history: &[(Assignment, f64)]) -> Assignment {
+        if history.is_empty() {
+            return self.sample(rng);
+        }
+        let mut ranked: Vec<&(Assignment, f64)> = history.iter().collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        let n_good = ((ranked.len() as f64 * TPE_GAMMA).ceil() as usize).max(1);
+        let (good, bad) = ranked.split_at(n_good);
+        self.params
+            .iter()
+            .map(|(path, domain)| {
+                let good: Vec<&toml::Value> =
+                    good.iter().filter_map(|(a, _)| a.get(path)).collect();
+                let bad: Vec<&toml::Value> = bad.iter().filter_map(|(a, _)| a.get(path)).collect();
+                (path.clone(), domain.suggest_tpe(rng, &good, &bad))
+            })
+            .collect()
+    }
+}
+
+// Tracks tried assignments so random and bayesian search never run the same trial twice.
+#[derive(Debug, Default)]
+pub struct TriedAssignments {
+    keys: BTreeSet<String>,
+}
+
+impl TriedAssignments {
+    // Calls `draw` until it yields an untried assignment; `None` once the attempts run out.
+    pub fn next_unique(&mut self, mut draw: impl FnMut() -> Assignment) -> Option<Assignment> {
+        (0..MAX_DRAW_ATTEMPTS)
+            .map(|_| draw())
+            .find(|assignment| self.keys.insert(assignment_key(assignment)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParamSpace, SearchStrategy, TriedAssignments};
+    use crate::experiments::sweep::SweepParam;
+    use kairos_domain::services::rng::SplitMix64;
+
+    fn range(path: &str, low: toml::Value, high: toml::Value) -> SweepParam {
+        SweepParam {
+            path: path.to_string(),
+            low: Some(low),
+            high: Some(high),
+            ..SweepParam::default()
+        }
+    }
+
+    #[test]
+    fn grid_rejects_ranges_and_random_samples_within_bounds() {
+        let params = vec![
+            range(
+                "strategy.sma_short",
+                toml::Value::Integer(2),
+                toml::Value::Integer(9),
+            ),
+            range(
+                "costs.slippage_bps",
+                toml::Value::Float(0.5),
+                toml::Value::Float(50.0),
+            ),
+        ];
+        assert!(ParamSpace::new(SearchStrategy::Grid, &params).is_err());
+
+        let space = ParamSpace::new(SearchStrategy::Random, &params).expect("space");
+        assert_eq!(space.size(), None);
+        let mut rng = SplitMix64::new(1);
+        for _ in 0..200 {
+            let a = space.sample(&mut rng);
+            let short = a["strategy.sma_short"].as_integer().expect("int");
+            let bps = a["costs.slippage_bps"].as_float().expect("float");
+            assert!((2..=9).contains(&short));
+            assert!((0.5..=50.0).contains(&bps));
+        }
+    }
+
+    #[test]
+    fn tried_assignments_stop_when_space_is_exhausted() {
+        let params = vec![SweepParam {
+            path: "strategy.sma_short".to_string(),
+            values: vec![toml::Value::Integer(5), toml::Value::Integer(10)],
+            ..SweepParam::default()
+        }];
+        let space = ParamSpace::new(SearchStrategy::Random, &params).expect("space");
+        assert_eq!(space.size(), Some(2));
+        let mut rng = SplitMix64::new(3);
+        let mut tried = TriedAssignments::default();
+        assert!(tried.next_unique(|| space.sample(&mut rng)).is_some());
+        assert!(tried.next_unique(|| space.sample(&mut rng)).is_some());
+        assert!(tried.next_unique(|| space.sample(&mut rng)).is_none());
+    }
+
+    #[test]
+    fn tpe_suggestions_concentrate_near_the_best_trials() {
+        let params = vec![range(
+            "risk.max_position_qty",
+            toml::Value::Float(0.0),
+            toml::Value::Float(10.0),
+        )];
+        let space = ParamSpace::new(SearchStrategy::Bayesian, &params).expect("space");
+        let mut rng = SplitMix64::new(11);
+        // Objective peaks at 7.0.
+        let history: Vec<_> = (0..20)
+            .map(|i| {
+                let x = i as f64 * 0.5;
+                let mut a = super::Assignment::new();
+                a.insert("risk.max_position_qty".to_string(), toml::Value::Float(x));
+                (a, -(x - 7.0).powi(2))
+            })
+            .collect();
+        let near = (0..50)
+            .filter(|_| {
+                let a = space.suggest(&mut rng, &history);
+                let x = a["risk.max_position_qty"].as_float().expect("float");
+                (5.0..=9.0).contains(&x)
+            })
+            .count();
+        assert!(near >= 35, "{near}/50 suggestions near the optimum");
+    }
+}
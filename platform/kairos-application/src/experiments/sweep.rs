@@ -1,17 +1,30 @@
-use crate::config::Config;
-use crate::shared::{normalize_timeframe_label, parse_duration_like};
+use super::pruning::{PruneSignal, Pruner, PruningConfig, TrialPruning};
+use super::ranking::{pareto_fronts, weighted_scores, RankMode};
+use super::rules::{ParamRules, SweepConstraint, SweepDerived};
+use super::search::{Assignment, ParamSpace, SearchStrategy, TriedAssignments};
+use crate::config::{build_ohlcv_query, Config};
+use crate::shared::{normalize_timeframe_label, parse_duration_like, parse_timestamp_seconds};
+use kairos_domain::entities::run_summary::RunSummary;
 use kairos_domain::repositories::agent::AgentClient as AgentPort;
 use kairos_domain::repositories::artifacts::ArtifactWriter;
 use kairos_domain::repositories::market_data::{MarketDataRepository, OhlcvQuery};
-use kairos_domain::repositories::sentiment::SentimentRepository;
+use kairos_domain::repositories::sentiment::{SentimentQuery, SentimentRepository};
+use kairos_domain::services::engine::backtest::RunControl;
 use kairos_domain::services::ohlcv::data_quality_from_bars;
+use kairos_domain::services::progress::{on_bars, stage, ProgressEvent, ProgressSink};
+pub use kairos_domain::services::progress::{SweepProgress, TrialOutcome};
+use kairos_domain::services::rng::SplitMix64;
+use kairos_domain::services::sentiment::{SentimentPoint, SentimentReport};
 use kairos_domain::value_objects::bar::Bar;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::BTreeMap;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::mpsc;
+use std::sync::Mutex;
+use std::time::Instant;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
@@ -29,6 +42,10 @@ pub struct SweepFile {
     pub params: Vec<SweepParam>,
     pub leaderboard: Option<LeaderboardConfig>,
     pub splits: Option<Vec<SweepSplit>>,
+    #[serde(default)]
+    pub derived: Vec<SweepDerived>,
+    #[serde(default)]
+    pub constraints: Vec<SweepConstraint>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -42,15 +59,36 @@ pub struct SweepBase {
 pub struct SweepMeta {
     pub id: String,
     pub mode: SweepMode,
+    // Worker threads running trials; `workers` is accepted as an alias.
+    #[serde(alias = "workers")]
     pub parallelism: Option<usize>,
     pub resume: Option<bool>,
+    // grid (default) | random | bayesian.
+    pub strategy: Option<SearchStrategy>,
+    // Trials per split for random/bayesian search.
+    pub trials: Option<usize>,
+    pub seed: Option<u64>,
+    // Metric bayesian search optimizes; defaults to `leaderboard.sort_by` (then sharpe).
+    pub objective: Option<String>,
+    // Defaults to `leaderboard.descending` (then true).
+    pub maximize: Option<bool>,
+    // Random trials per split before bayesian suggestions start.
+    pub init_random: Option<usize>,
+    // `[sweep.pruning]`: stop losing backtest trials early.
+    pub pruning: Option<PruningConfig>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct SweepParam {
     pub path: String,
+    #[serde(default)]
     pub values: Vec<toml::Value>,
+    // Range for random/bayesian search, instead of `values`: integer bounds sample integers.
+    pub low: Option<toml::Value>,
+    pub high: Option<toml::Value>,
+    // Sample the range uniformly in log space.
+    pub log: Option<bool>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -58,6 +96,9 @@ pub struct SweepParam {
 pub struct LeaderboardConfig {
     pub sort_by: Option<String>,
     pub descending: Option<bool>,
+    pub rank: Option<RankMode>,
+    // Metric -> weight for `weighted`/`pareto` ranking; a negative weight minimizes the metric.
+    pub weights: Option<BTreeMap<String, f64>>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -93,19 +134,39 @@ pub struct SweepResult {
     pub sweep_id: String,
     pub sweep_dir: PathBuf,
     pub mode: SweepMode,
+    pub strategy: SearchStrategy,
+    // Assignments dropped by `[[constraints]]` instead of being run.
+    pub rejected_by_constraints: usize,
+    pub ranking: RankMode,
+    // Run ids on the first Pareto front of the leaderboard metrics (`weighted`/`pareto` only).
+    pub pareto_front: Vec<String>,
     pub base_config: String,
     pub runs: Vec<SweepRunEntry>,
 }
 
-#[derive(Debug, Clone)]
-pub struct SweepProgress {
-    pub total_runs: usize,
-    pub completed_runs: usize,
-    pub ok_runs: usize,
-    pub skipped_runs: usize,
-    pub error_runs: usize,
-    pub last_run_id: Option<String>,
-    pub last_error: Option<String>,
+// Command-line overrides applied on top of the sweep file and its base config.
+#[derive(Debug, Clone, Default)]
+pub struct SweepOverrides {
+    // Replaces the base config's `paths.out_dir` (trial run dirs and `sweeps/<id>/`).
+    pub out_dir: Option<PathBuf>,
+    // Replaces `[sweep].parallelism` (`--workers`).
+    pub parallelism: Option<usize>,
+    // `key=value` assignments applied to the base config before the grid (see `config::overrides`).
+    pub config_sets: Vec<String>,
+    // Forces `[sweep].resume`: completed trials are skipped and failed ones re-run.
+    pub resume: bool,
+}
+
+// One JSON line per finished trial, appended as the sweep progresses so an interrupted sweep can
+// be resumed from where it stopped (the manifest/CSVs are only written at the end).
+pub const TRIAL_STATUS_FILE_NAME: &str = "trials.jsonl";
+
+#[derive(Debug, Deserialize)]
+struct TrialStatusLine {
+    run_id: String,
+    status: String,
+    #[serde(default)]
+    error: Option<String>,
 }
 
 pub type AgentFactoryResult = Result<Option<Box<dyn AgentPort>>, String>;
@@ -120,6 +181,7 @@ pub fn run_sweep(
 ) -> Result<SweepResult, String> {
     run_sweep_with_hooks(
         sweep_path,
+        &SweepOverrides::default(),
         agent_factory,
         market_data,
         sentiment_repo,
@@ -129,13 +191,15 @@ pub fn run_sweep(
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn run_sweep_with_hooks(
     sweep_path: &Path,
+    overrides: &SweepOverrides,
     agent_factory: &AgentFactory<'_>,
     market_data: &dyn MarketDataRepository,
     sentiment_repo: &(dyn SentimentRepository + Sync),
     artifacts: &(dyn ArtifactWriter + Sync),
-    mut on_progress: Option<&mut dyn FnMut(SweepProgress)>,
+    mut on_progress: Option<&mut dyn ProgressSink>,
     should_cancel: Option<&(dyn Fn() -> bool + Sync)>,
 ) -> Result<SweepResult, String> {
     let raw = std::fs::read_to_string(sweep_path).map_err(|err| {
@@ -147,20 +211,60 @@ pub fn run_sweep_with_hooks(
     let sweep: SweepFile = toml::from_str(&raw)
         .map_err(|err| format!("failed to parse sweep TOML {}: {err}", sweep_path.display()))?;
 
-    validate_param_paths(&sweep.params)?;
+    for p in &sweep.params {
+        validate_param_path(&p.path)?;
+    }
+    for d in &sweep.derived {
+        validate_param_path(&d.path)?;
+        if sweep.params.iter().any(|p| p.path == d.path) {
+            return Err(format!(
+                "invalid sweep config: derived param {} is also a [[params]] entry",
+                d.path
+            ));
+        }
+    }
+    let rules = ParamRules::new(&sweep.derived, &sweep.constraints)
+        .map_err(|err| format!("invalid sweep config: {err}"))?;
 
     let base_config_path = resolve_base_config_path(sweep_path, &sweep.base.config);
-    let (base_config, base_toml_str) =
-        crate::config::load_config_with_source(base_config_path.as_path())?;
-    let base_toml_value: toml::Value = toml::from_str(&base_toml_str)
+    let (base_config, base_toml_str) = crate::config::load_config_with_overrides(
+        base_config_path.as_path(),
+        &overrides.config_sets,
+    )
+    .map_err(|err| err.to_string())?;
+    let mut base_toml_value: toml::Value = toml::from_str(&base_toml_str)
         .map_err(|err| format!("failed to parse base config TOML as value: {err}"))?;
 
-    let out_dir = PathBuf::from(&base_config.paths.out_dir);
+    let out_dir = match &overrides.out_dir {
+        Some(out_dir) => {
+            set_path_value(
+                &mut base_toml_value,
+                "paths.out_dir",
+                toml::Value::String(out_dir.display().to_string()),
+            )?;
+            out_dir.clone()
+        }
+        None => PathBuf::from(&base_config.paths.out_dir),
+    };
     let sweep_dir = out_dir.join("sweeps").join(&sweep.sweep.id);
     std::fs::create_dir_all(&sweep_dir)
         .map_err(|err| format!("failed to create sweep dir {}: {err}", sweep_dir.display()))?;
 
-    let resume = sweep.sweep.resume.unwrap_or(false);
+    let resume = overrides.resume || sweep.sweep.resume.unwrap_or(false);
+    let status_path = sweep_dir.join(TRIAL_STATUS_FILE_NAME);
+    let previous_status = if resume {
+        read_trial_status(&status_path)?
+    } else {
+        BTreeMap::new()
+    };
+    let mut status_file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resume)
+        .truncate(!resume)
+        .open(&status_path)
+        .map_err(|err| format!("failed to open {}: {err}", status_path.display()))?;
+    let mut status_error: Option<String> = None;
     let splits = sweep.splits.clone().unwrap_or_else(|| {
         vec![SweepSplit {
             id: "full".to_string(),
@@ -179,45 +283,143 @@ pub fn run_sweep_with_hooks(
     )?;
     let source_step = parse_duration_like(&source_timeframe_label)?;
 
-    let (source_bars, _source_report) = market_data.load_ohlcv(&OhlcvQuery {
-        exchange: base_config.db.exchange.to_lowercase(),
-        market: base_config.db.market.to_lowercase(),
-        symbol: base_config.run.symbol.clone(),
-        timeframe: source_timeframe_label.clone(),
-        expected_step_seconds: Some(source_step),
-    })?;
+    let load_control = SweepControl {
+        should_cancel,
+        pruned: None,
+    };
+    let source_query = build_ohlcv_query(&base_config, &source_timeframe_label, source_step)
+        .map_err(|err| err.to_string())?;
+    let load = || {
+        market_data
+            .load_ohlcv_with_control(&source_query, &load_control)
+            .map_err(|err| {
+                if should_cancelled(should_cancel) {
+                    "cancelled".to_string()
+                } else {
+                    err
+                }
+            })
+    };
+    let (source_bars, _source_report) = match on_progress.as_mut() {
+        Some(sink) => stage(&mut **sink, "load_ohlcv", load),
+        None => load(),
+    }?;
+
+    // Trials share the bars loaded above and the sentiment series (its path cannot be swept).
+    let shared_sentiment = CachedSentimentRepository::new(sentiment_repo);
+
+    let strategy = sweep.sweep.strategy.unwrap_or_default();
+    let space = ParamSpace::new(strategy, &sweep.params)?;
+    let seed = sweep.sweep.seed.unwrap_or(DEFAULT_SEARCH_SEED);
+    // Grid trials do not depend on the seed; random/bayesian ones were drawn with it.
+    let trial_seed = (!matches!(strategy, SearchStrategy::Grid)).then_some(seed);
+    let objective = search_objective(&sweep)?;
+    let ranking = leaderboard_ranking(sweep.leaderboard.as_ref())?;
+    if sweep.sweep.pruning.is_some() && sweep.sweep.mode != SweepMode::Backtest {
+        return Err("sweep.pruning is only supported with sweep.mode = \"backtest\"".to_string());
+    }
+    let trials_per_split = match strategy {
+        SearchStrategy::Grid => None,
+        SearchStrategy::Random | SearchStrategy::Bayesian => {
+            let trials = sweep.sweep.trials.filter(|n| *n > 0).ok_or_else(|| {
+                format!(
+                    "sweep.trials (> 0) is required for sweep.strategy = \"{}\"",
+                    strategy.as_str()
+                )
+            })?;
+            let space_size = space.size().map_or(usize::MAX, |size| {
+                usize::try_from(size).unwrap_or(usize::MAX)
+            });
+            Some(trials.min(space_size))
+        }
+    };
+    // Grid and random search run the same assignments on every split; bayesian search picks
+    // them per split from that split's results.
+    // Derived params are filled in and constraint violations dropped before anything runs.
+    let apply_rules = |raw: Assignment| {
+        rules
+            .apply(raw, &base_toml_value)
+            .map_err(|err| format!("invalid sweep config: {err}"))
+    };
+    let mut rejected_by_constraints = 0;
+    let fixed_assignments: Vec<Assignment> = match strategy {
+        SearchStrategy::Grid => {
+            let mut kept = Vec::new();
+            for raw in expand_grid(&sweep.params) {
+                match apply_rules(raw)? {
+                    Some(assignment) => kept.push(assignment),
+                    None => rejected_by_constraints += 1,
+                }
+            }
+            kept
+        }
+        SearchStrategy::Random => {
+            let mut rng = SplitMix64::new(seed);
+            let mut tried = TriedAssignments::default();
+            let budget = trials_per_split.unwrap_or(0);
+            let mut kept = Vec::new();
+            let mut rejected_in_row = 0;
+            while kept.len() < budget && rejected_in_row < MAX_REJECTED_DRAWS {
+                let Some(raw) = tried.next_unique(|| space.sample(&mut rng)) else {
+                    break;
+                };
+                match apply_rules(raw)? {
+                    Some(assignment) => {
+                        kept.push(assignment);
+                        rejected_in_row = 0;
+                    }
+                    None => {
+                        rejected_by_constraints += 1;
+                        rejected_in_row += 1;
+                    }
+                }
+            }
+            kept
+        }
+        SearchStrategy::Bayesian => Vec::new(),
+    };
 
     let mut runs: Vec<SweepRunEntry> = Vec::new();
-    let grid = expand_grid(&sweep.params);
-    let requested_parallelism = normalize_parallelism(sweep.sweep.parallelism);
-    let total_runs = grid.len().saturating_mul(splits.len());
+    let requested_parallelism =
+        normalize_parallelism(overrides.parallelism.or(sweep.sweep.parallelism));
+    let runs_per_split = match strategy {
+        SearchStrategy::Bayesian => trials_per_split.unwrap_or(0),
+        SearchStrategy::Grid | SearchStrategy::Random => fixed_assignments.len(),
+    };
+    let total_runs = runs_per_split.saturating_mul(splits.len());
     let mut progress = SweepProgress {
         total_runs,
         completed_runs: 0,
         ok_runs: 0,
         skipped_runs: 0,
         error_runs: 0,
+        pruned_runs: 0,
         last_run_id: None,
+        last_status: None,
         last_error: None,
+        workers: requested_parallelism,
+        last_trial_ms: None,
+        elapsed_ms: 0,
+        eta_ms: None,
+        last_trial: None,
+    };
+    let mut clock = SweepClock {
+        started: Instant::now(),
+        executed_runs: 0,
     };
     emit_progress(&mut on_progress, &progress);
 
-    for split in &splits {
-        if should_cancelled(should_cancel) {
-            return Err("cancelled".to_string());
-        }
-
-        let (bars_for_split, report_for_split) =
-            filter_bars_for_split(&source_bars, source_step, split)?;
-        let in_memory_market = InMemoryMarketDataRepository {
-            bars: bars_for_split,
-            report: report_for_split,
-        };
-
-        let mut split_entries: Vec<Option<SweepRunEntry>> = vec![None; grid.len()];
+    // Runs one batch of assignments on a split (skipping trials a resumed sweep already finished)
+    // and returns their entries in assignment order.
+    let mut run_assignments = |split: &SweepSplit,
+                               market: &InMemoryMarketDataRepository,
+                               pruner: Option<&Pruner>,
+                               assignments: &[Assignment]|
+     -> Result<Vec<SweepRunEntry>, String> {
+        let mut split_entries: Vec<Option<SweepRunEntry>> = vec![None; assignments.len()];
         let mut plans: Vec<SweepRunPlan> = Vec::new();
 
-        for (order_idx, assignment) in grid.iter().enumerate() {
+        for (order_idx, assignment) in assignments.iter().enumerate() {
             let mut toml_value = base_toml_value.clone();
             apply_assignment(&mut toml_value, assignment)?;
 
@@ -227,12 +429,38 @@ pub fn run_sweep_with_hooks(
 
             let config_toml = toml::to_string_pretty(&toml_value)
                 .map_err(|err| format!("failed to serialize sweep config TOML: {err}"))?;
-            let config: Config = toml::from_str(&config_toml)
+            let config: Config = crate::config::load_config_from_source(&config_toml)
                 .map_err(|err| format!("failed to parse generated config TOML: {err}"))?;
 
             let run_dir = out_dir.join(&run_id);
             let summary_path = run_dir.join("summary.json");
-            if resume && summary_path.exists() {
+            // Trials recorded as failed are retried; trials without a status line (older sweeps or
+            // an interruption mid-trial) are skipped only when their summary made it to disk.
+            // Pruned trials stay pruned.
+            let previous = previous_status.get(&run_id);
+            let completed = match previous.map(|line| line.status.as_str()) {
+                Some("error") => false,
+                Some("pruned") => {
+                    if resume {
+                        let entry = SweepRunEntry {
+                            run_id,
+                            split_id: split.id.clone(),
+                            params: assignment.clone(),
+                            status: "pruned".to_string(),
+                            error: previous.and_then(|line| line.error.clone()),
+                            metrics: None,
+                        };
+                        update_progress(&mut progress, &mut clock, &out_dir, &entry, None);
+                        emit_progress(&mut on_progress, &progress);
+                        append_trial_status(&mut status_file, &entry, &mut status_error);
+                        split_entries[order_idx] = Some(entry);
+                        continue;
+                    }
+                    false
+                }
+                _ => summary_path.exists(),
+            };
+            if resume && completed {
                 let entry = SweepRunEntry {
                     run_id,
                     split_id: split.id.clone(),
@@ -241,8 +469,9 @@ pub fn run_sweep_with_hooks(
                     error: None,
                     metrics: read_metrics_from_summary(&summary_path).ok(),
                 };
-                update_progress(&mut progress, &entry);
+                update_progress(&mut progress, &mut clock, &out_dir, &entry, None);
                 emit_progress(&mut on_progress, &progress);
+                append_trial_status(&mut status_file, &entry, &mut status_error);
                 split_entries[order_idx] = Some(entry);
                 continue;
             }
@@ -255,20 +484,23 @@ pub fn run_sweep_with_hooks(
                 config,
                 config_toml,
                 summary_path,
+                seed: trial_seed,
             });
         }
 
-        let mut on_entry = |entry: &SweepRunEntry| {
-            update_progress(&mut progress, entry);
+        let mut on_entry = |entry: &SweepRunEntry, trial_ms: u64| {
+            update_progress(&mut progress, &mut clock, &out_dir, entry, Some(trial_ms));
             emit_progress(&mut on_progress, &progress);
+            append_trial_status(&mut status_file, entry, &mut status_error);
         };
 
         let mut executed = if requested_parallelism <= 1 || plans.len() <= 1 {
             execute_plans_serial(
                 &plans,
                 sweep.sweep.mode,
-                &in_memory_market,
-                sentiment_repo,
+                pruner,
+                market,
+                &shared_sentiment,
                 artifacts,
                 agent_factory,
                 should_cancel,
@@ -279,8 +511,9 @@ pub fn run_sweep_with_hooks(
                 &plans,
                 requested_parallelism,
                 sweep.sweep.mode,
-                &in_memory_market,
-                sentiment_repo,
+                pruner,
+                market,
+                &shared_sentiment,
                 artifacts,
                 agent_factory,
                 should_cancel,
@@ -288,32 +521,142 @@ pub fn run_sweep_with_hooks(
             )?
         };
 
+        if let Some(err) = status_error.take() {
+            return Err(err);
+        }
+
         executed.sort_by_key(|(order_idx, _)| *order_idx);
         for (order_idx, entry) in executed {
             split_entries[order_idx] = Some(entry);
         }
 
-        for entry in split_entries {
-            runs.push(entry.ok_or_else(|| {
-                format!(
-                    "internal sweep error: missing run entry for split '{}' (sweep '{}')",
-                    split.id, sweep.sweep.id
-                )
-            })?);
+        split_entries
+            .into_iter()
+            .map(|entry| {
+                entry.ok_or_else(|| {
+                    format!(
+                        "internal sweep error: missing run entry for split '{}' (sweep '{}')",
+                        split.id, sweep.sweep.id
+                    )
+                })
+            })
+            .collect()
+    };
+
+    for split in &splits {
+        if should_cancelled(should_cancel) {
+            return Err("cancelled".to_string());
+        }
+
+        let (bars_for_split, report_for_split) =
+            filter_bars_for_split(&source_bars, source_step, split)?;
+        let in_memory_market = InMemoryMarketDataRepository {
+            bars: bars_for_split,
+            report: report_for_split,
+        };
+        let pruner = match (&sweep.sweep.pruning, in_memory_market.bars.as_slice()) {
+            (Some(cfg), [first, .., last]) => Some(Pruner::new(
+                cfg,
+                &objective.metric,
+                objective.maximize,
+                first.timestamp,
+                last.timestamp,
+            )?),
+            _ => None,
+        };
+
+        if strategy != SearchStrategy::Bayesian {
+            runs.extend(run_assignments(
+                split,
+                &in_memory_market,
+                pruner.as_ref(),
+                &fixed_assignments,
+            )?);
+            continue;
+        }
+
+        // One round per `workers` suggestions, so every round learns from all finished trials.
+        let budget = trials_per_split.unwrap_or(0);
+        let init_random = sweep
+            .sweep
+            .init_random
+            .unwrap_or((budget / 5).max(DEFAULT_INIT_RANDOM));
+        let mut rng = SplitMix64::new(seed);
+        let mut tried = TriedAssignments::default();
+        let mut history: Vec<(Assignment, f64)> = Vec::new();
+        let mut suggested = 0;
+        let mut rejected_in_row = 0;
+        while suggested < budget {
+            let mut round = Vec::new();
+            while round.len() < requested_parallelism
+                && suggested < budget
+                && rejected_in_row < MAX_REJECTED_DRAWS
+            {
+                let use_model = suggested >= init_random && !history.is_empty();
+                let mut first_draw = true;
+                let next = tried.next_unique(|| {
+                    let model_draw = use_model && std::mem::take(&mut first_draw);
+                    if model_draw {
+                        space.suggest(&mut rng, &history)
+                    } else {
+                        space.sample(&mut rng)
+                    }
+                });
+                let Some(raw) = next else {
+                    break;
+                };
+                let Some(assignment) = apply_rules(raw)? else {
+                    rejected_by_constraints += 1;
+                    rejected_in_row += 1;
+                    continue;
+                };
+                rejected_in_row = 0;
+                round.push(assignment);
+                suggested += 1;
+            }
+            if round.is_empty() {
+                break;
+            }
+            for entry in run_assignments(split, &in_memory_market, pruner.as_ref(), &round)? {
+                // Pruned trials count as the worst seen so the model steers away from them.
+                let score = match (entry.status.as_str(), entry.metrics) {
+                    ("pruned", _) => Some(f64::NEG_INFINITY),
+                    ("error", _) | (_, None) => None,
+                    (_, Some(metrics)) => Some(objective.score(metrics)),
+                };
+                if let Some(score) = score {
+                    history.push((entry.params.clone(), score));
+                }
+                runs.push(entry);
+            }
         }
     }
 
+    let leaderboard = rank_leaderboard(&runs, &ranking);
+    let pareto_front = leaderboard
+        .iter()
+        .filter(|row| row.pareto_front == Some(1))
+        .map(|row| runs[row.index].run_id.clone())
+        .collect();
     let result = SweepResult {
         sweep_id: sweep.sweep.id.clone(),
         sweep_dir: sweep_dir.clone(),
         mode: sweep.sweep.mode,
+        strategy,
+        rejected_by_constraints,
+        ranking: ranking.mode,
+        pareto_front,
         base_config: base_config_path.display().to_string(),
         runs,
     };
 
     write_manifest(&sweep_dir, &result)?;
     write_results_csv(&sweep_dir, &result)?;
-    write_leaderboard_csv(&sweep_dir, &result, sweep.leaderboard.as_ref())?;
+    write_leaderboard_csv(&sweep_dir, &result, &leaderboard)?;
+    artifacts.write_sweep_index_html(
+        &sweep_dir.join("index.html"),
+        &sweep_index_json(&result, &sweep.params, &sweep.derived),
+    )?;
 
     Ok(result)
 }
@@ -327,12 +670,14 @@ struct SweepRunPlan {
     config: Config,
     config_toml: String,
     summary_path: PathBuf,
+    seed: Option<u64>,
 }
 
 enum WorkerMessage {
     Entry {
         order_idx: usize,
         entry: SweepRunEntry,
+        trial_ms: u64,
     },
     Fatal(String),
 }
@@ -341,45 +686,52 @@ fn normalize_parallelism(value: Option<usize>) -> usize {
     value.unwrap_or(1).max(1)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn execute_plans_serial(
     plans: &[SweepRunPlan],
     mode: SweepMode,
+    pruner: Option<&Pruner>,
     market_data: &(dyn MarketDataRepository + Sync),
     sentiment_repo: &(dyn SentimentRepository + Sync),
     artifacts: &(dyn ArtifactWriter + Sync),
     agent_factory: &AgentFactory<'_>,
     should_cancel: Option<&(dyn Fn() -> bool + Sync)>,
-    on_entry: &mut dyn FnMut(&SweepRunEntry),
+    on_entry: &mut dyn FnMut(&SweepRunEntry, u64),
 ) -> Result<Vec<(usize, SweepRunEntry)>, String> {
     let mut out = Vec::with_capacity(plans.len());
     for plan in plans {
         if should_cancelled(should_cancel) {
             return Err("cancelled".to_string());
         }
+        let started = Instant::now();
         let entry = execute_run_plan(
             plan,
             mode,
+            pruner,
             market_data,
             sentiment_repo,
             artifacts,
             agent_factory,
+            should_cancel,
         )?;
-        on_entry(&entry);
+        on_entry(&entry, started.elapsed().as_millis() as u64);
         out.push((plan.order_idx, entry));
     }
     Ok(out)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn execute_plans_parallel(
     plans: &[SweepRunPlan],
     parallelism: usize,
     mode: SweepMode,
+    pruner: Option<&Pruner>,
     market_data: &(dyn MarketDataRepository + Sync),
     sentiment_repo: &(dyn SentimentRepository + Sync),
     artifacts: &(dyn ArtifactWriter + Sync),
     agent_factory: &AgentFactory<'_>,
     should_cancel: Option<&(dyn Fn() -> bool + Sync)>,
-    on_entry: &mut dyn FnMut(&SweepRunEntry),
+    on_entry: &mut dyn FnMut(&SweepRunEntry, u64),
 ) -> Result<Vec<(usize, SweepRunEntry)>, String> {
     let worker_count = parallelism.max(1).min(plans.len());
     let next_index = AtomicUsize::new(0);
@@ -403,19 +755,23 @@ fn execute_plans_parallel(
                     break;
                 }
 
+                let started = Instant::now();
                 match execute_run_plan(
                     &plans[plan_idx],
                     mode,
+                    pruner,
                     market_data,
                     sentiment_repo,
                     artifacts,
                     agent_factory,
+                    should_cancel,
                 ) {
                     Ok(entry) => {
                         if tx
                             .send(WorkerMessage::Entry {
                                 order_idx: plans[plan_idx].order_idx,
                                 entry,
+                                trial_ms: started.elapsed().as_millis() as u64,
                             })
                             .is_err()
                         {
@@ -437,9 +793,13 @@ fn execute_plans_parallel(
         let mut fatal_error: Option<String> = None;
         while let Ok(message) = rx.recv() {
             match message {
-                WorkerMessage::Entry { order_idx, entry } => {
+                WorkerMessage::Entry {
+                    order_idx,
+                    entry,
+                    trial_ms,
+                } => {
                     if fatal_error.is_none() {
-                        on_entry(&entry);
+                        on_entry(&entry, trial_ms);
                         entries.push((order_idx, entry));
                     }
                 }
@@ -466,17 +826,56 @@ fn execute_plans_parallel(
     })
 }
 
+// Stops a trial's run when the sweep is cancelled or the pruner gives up on it.
+struct SweepControl<'a> {
+    should_cancel: Option<&'a (dyn Fn() -> bool + Sync)>,
+    pruned: Option<&'a PruneSignal>,
+}
+
+impl RunControl for SweepControl<'_> {
+    fn should_cancel(&self) -> bool {
+        should_cancelled(self.should_cancel)
+            || self.pruned.is_some_and(|signal| signal.should_cancel())
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn execute_run_plan(
     plan: &SweepRunPlan,
     mode: SweepMode,
+    pruner: Option<&Pruner>,
     market_data: &(dyn MarketDataRepository + Sync),
     sentiment_repo: &(dyn SentimentRepository + Sync),
     artifacts: &(dyn ArtifactWriter + Sync),
     agent_factory: &AgentFactory<'_>,
+    should_cancel: Option<&(dyn Fn() -> bool + Sync)>,
 ) -> Result<SweepRunEntry, String> {
     let remote_agent = agent_factory(&plan.config)?;
-    let result = match mode {
-        SweepMode::Backtest => crate::backtesting::run_backtest(
+    let mut pruned: Option<String> = None;
+    let result = match (mode, pruner) {
+        (SweepMode::Backtest, Some(pruner)) => {
+            let signal = PruneSignal::default();
+            let control = SweepControl {
+                should_cancel,
+                pruned: Some(&signal),
+            };
+            let mut trial = TrialPruning::new(pruner, &signal);
+            let result = crate::backtesting::run_backtest_seeded(
+                &plan.config,
+                &plan.config_toml,
+                None,
+                market_data,
+                sentiment_repo,
+                artifacts,
+                remote_agent,
+                &control,
+                &mut on_bars(|bar| trial.on_bar(bar)),
+                plan.seed,
+            );
+            pruned = trial.into_reason();
+            result.map(|_| ())
+        }
+        (SweepMode::Backtest, None) => crate::backtesting::run_backtest_seeded(
             &plan.config,
             &plan.config_toml,
             None,
@@ -484,9 +883,15 @@ fn execute_run_plan(
             sentiment_repo,
             artifacts,
             remote_agent,
+            &SweepControl {
+                should_cancel,
+                pruned: None,
+            },
+            &mut |_event: ProgressEvent<'_>| {},
+            plan.seed,
         )
         .map(|_| ()),
-        SweepMode::Paper => crate::paper_trading::run_paper(
+        (SweepMode::Paper, _) => crate::paper_trading::run_paper_seeded(
             &plan.config,
             &plan.config_toml,
             None,
@@ -494,12 +899,32 @@ fn execute_run_plan(
             sentiment_repo,
             artifacts,
             remote_agent,
+            None,
+            crate::paper_trading::clock::ReplayPacing::from_config(&plan.config),
+            &SweepControl {
+                should_cancel,
+                pruned: None,
+            },
+            &mut |_event: ProgressEvent<'_>| {},
+            plan.seed,
         )
         .map(|_| ()),
     };
+    // A trial interrupted by the sweep's cancellation is not a result; the sweep as a whole fails.
+    if should_cancelled(should_cancel) {
+        return Err("cancelled".to_string());
+    }
 
-    let entry = match result {
-        Ok(()) => SweepRunEntry {
+    let entry = match (result, pruned) {
+        (_, Some(reason)) => SweepRunEntry {
+            run_id: plan.run_id.clone(),
+            split_id: plan.split_id.clone(),
+            params: plan.params.clone(),
+            status: "pruned".to_string(),
+            error: Some(reason),
+            metrics: None,
+        },
+        (Ok(()), None) => SweepRunEntry {
             run_id: plan.run_id.clone(),
             split_id: plan.split_id.clone(),
             params: plan.params.clone(),
@@ -507,12 +932,12 @@ fn execute_run_plan(
             error: None,
             metrics: read_metrics_from_summary(&plan.summary_path).ok(),
         },
-        Err(err) => SweepRunEntry {
+        (Err(err), None) => SweepRunEntry {
             run_id: plan.run_id.clone(),
             split_id: plan.split_id.clone(),
             params: plan.params.clone(),
             status: "error".to_string(),
-            error: Some(err),
+            error: Some(err.to_string()),
             metrics: None,
         },
     };
@@ -524,24 +949,74 @@ fn should_cancelled(should_cancel: Option<&(dyn Fn() -> bool + Sync)>) -> bool {
     should_cancel.map(|f| f()).unwrap_or(false)
 }
 
-fn update_progress(progress: &mut SweepProgress, entry: &SweepRunEntry) {
+struct SweepClock {
+    started: Instant,
+    executed_runs: usize,
+}
+
+fn update_progress(
+    progress: &mut SweepProgress,
+    clock: &mut SweepClock,
+    out_dir: &Path,
+    entry: &SweepRunEntry,
+    trial_ms: Option<u64>,
+) {
     progress.completed_runs = progress.completed_runs.saturating_add(1);
     progress.last_run_id = Some(entry.run_id.clone());
-    progress.last_error = entry.error.clone();
+    progress.last_status = Some(entry.status.clone());
+    progress.last_error = entry.error.clone().filter(|_| entry.status == "error");
     match entry.status.as_str() {
         "ok" => progress.ok_runs = progress.ok_runs.saturating_add(1),
         "skipped" => progress.skipped_runs = progress.skipped_runs.saturating_add(1),
         "error" => progress.error_runs = progress.error_runs.saturating_add(1),
+        "pruned" => progress.pruned_runs = progress.pruned_runs.saturating_add(1),
         _ => {}
     }
+    progress.last_trial_ms = trial_ms;
+    progress.elapsed_ms = clock.started.elapsed().as_millis() as u64;
+    if trial_ms.is_some() {
+        clock.executed_runs += 1;
+    }
+    progress.eta_ms = estimate_eta_ms(
+        progress.elapsed_ms,
+        clock.executed_runs,
+        progress.total_runs.saturating_sub(progress.completed_runs),
+    );
+    progress.last_trial = Some(TrialOutcome {
+        run_id: entry.run_id.clone(),
+        split_id: entry.split_id.clone(),
+        run_dir: out_dir.join(&entry.run_id).display().to_string(),
+        params: entry
+            .params
+            .iter()
+            .map(|(path, value)| {
+                let value = match value {
+                    toml::Value::String(raw) => raw.clone(),
+                    other => other.to_string(),
+                };
+                (path.clone(), value)
+            })
+            .collect(),
+        status: entry.status.clone(),
+        net_profit: entry.metrics.map(|m| m.net_profit),
+        sharpe: entry.metrics.map(|m| m.sharpe),
+        max_drawdown: entry.metrics.map(|m| m.max_drawdown),
+    });
+}
+
+fn estimate_eta_ms(elapsed_ms: u64, executed_runs: usize, remaining_runs: usize) -> Option<u64> {
+    if remaining_runs == 0 {
+        return Some(0);
+    }
+    if executed_runs == 0 {
+        return None;
+    }
+    Some(elapsed_ms.saturating_mul(remaining_runs as u64) / executed_runs as u64)
 }
 
-fn emit_progress(
-    on_progress: &mut Option<&mut dyn FnMut(SweepProgress)>,
-    progress: &SweepProgress,
-) {
-    if let Some(callback) = on_progress.as_mut() {
-        (callback)(progress.clone());
+fn emit_progress(on_progress: &mut Option<&mut dyn ProgressSink>, progress: &SweepProgress) {
+    if let Some(sink) = on_progress.as_mut() {
+        sink.emit(ProgressEvent::TrialCompleted(progress));
     }
 }
 
@@ -557,33 +1032,99 @@ fn resolve_base_config_path(sweep_path: &Path, base: &str) -> PathBuf {
     }
 }
 
-fn validate_param_paths(params: &[SweepParam]) -> Result<(), String> {
+// Grid-only params (walk-forward re-optimization): every param needs a `values` list.
+pub(crate) fn validate_param_paths(params: &[SweepParam]) -> Result<(), String> {
     for p in params {
-        let path = p.path.trim();
-        if path.is_empty() {
-            return Err("sweep param path cannot be empty".to_string());
-        }
-        let forbidden_prefixes = [
-            "run.symbol",
-            "run.timeframe",
-            "db.exchange",
-            "db.market",
-            "db.ohlcv_table",
-            "db.source_timeframe",
-            "paths.out_dir",
-            "paths.sentiment_path",
-        ];
-        if forbidden_prefixes.iter().any(|pre| path.starts_with(pre)) {
-            return Err(format!("sweep param path not allowed: {}", p.path));
-        }
-        if p.values.is_empty() {
-            return Err(format!("sweep param has no values: {}", p.path));
-        }
+        validate_param_path(&p.path)?;
+    }
+    ParamSpace::new(SearchStrategy::Grid, params).map(|_| ())
+}
+
+fn validate_param_path(raw: &str) -> Result<(), String> {
+    let path = raw.trim();
+    if path.is_empty() {
+        return Err("sweep param path cannot be empty".to_string());
+    }
+    let forbidden_prefixes = [
+        "run.symbol",
+        "run.timeframe",
+        "db.exchange",
+        "db.market",
+        "db.ohlcv_table",
+        "db.source_timeframe",
+        "db.sentiment_table",
+        "paths.out_dir",
+        "paths.sentiment_path",
+        "paths.sentiment_source",
+    ];
+    if forbidden_prefixes.iter().any(|pre| path.starts_with(pre)) {
+        return Err(format!("sweep param path not allowed: {raw}"));
     }
     Ok(())
 }
 
-fn expand_grid(params: &[SweepParam]) -> Vec<BTreeMap<String, toml::Value>> {
+const DEFAULT_SEARCH_SEED: u64 = 42;
+// Consecutive constraint rejections after which random/bayesian search stops drawing.
+const MAX_REJECTED_DRAWS: usize = 1_000;
+const DEFAULT_INIT_RANDOM: usize = 5;
+const OBJECTIVE_METRICS: &[&str] = &[
+    "sharpe",
+    "net_profit",
+    "max_drawdown",
+    "max_dd",
+    "max_drawdown_pct",
+    "win_rate",
+    "trades",
+    "bars_processed",
+    "turnover",
+];
+
+struct SearchObjective {
+    metric: String,
+    maximize: bool,
+}
+
+impl SearchObjective {
+    // Higher is better regardless of direction.
+    fn score(&self, metrics: RunMetrics) -> f64 {
+        let value = metric_value(metrics, &self.metric);
+        if self.maximize {
+            value
+        } else {
+            -value
+        }
+    }
+}
+
+fn search_objective(sweep: &SweepFile) -> Result<SearchObjective, String> {
+    let leaderboard = sweep.leaderboard.as_ref();
+    let metric = sweep
+        .sweep
+        .objective
+        .as_deref()
+        .or_else(|| leaderboard.and_then(|l| l.sort_by.as_deref()))
+        .unwrap_or("sharpe")
+        .trim()
+        .to_lowercase();
+    let metric = match metric.as_str() {
+        "max_dd" | "max_drawdown_pct" => "max_drawdown".to_string(),
+        _ => metric,
+    };
+    if !OBJECTIVE_METRICS.contains(&metric.as_str()) {
+        return Err(format!(
+            "sweep.objective must be one of: {}",
+            OBJECTIVE_METRICS.join(", ")
+        ));
+    }
+    let maximize = sweep
+        .sweep
+        .maximize
+        .or_else(|| leaderboard.and_then(|l| l.descending))
+        .unwrap_or(true);
+    Ok(SearchObjective { metric, maximize })
+}
+
+pub(crate) fn expand_grid(params: &[SweepParam]) -> Vec<BTreeMap<String, toml::Value>> {
     let mut out: Vec<BTreeMap<String, toml::Value>> = vec![BTreeMap::new()];
     for p in params {
         let mut next: Vec<BTreeMap<String, toml::Value>> = Vec::new();
@@ -599,7 +1140,10 @@ fn expand_grid(params: &[SweepParam]) -> Vec<BTreeMap<String, toml::Value>> {
     out
 }
 
-fn assignment_hash(split_id: &str, assignment: &BTreeMap<String, toml::Value>) -> String {
+pub(crate) fn assignment_hash(
+    split_id: &str,
+    assignment: &BTreeMap<String, toml::Value>,
+) -> String {
     let canonical = serde_json::to_string(assignment)
         .unwrap_or_else(|_| "{\"error\":\"assignment\"}".to_string());
     let mut hasher = Sha256::new();
@@ -626,11 +1170,11 @@ fn to_hex_short(bytes: &[u8], chars: usize) -> String {
     out
 }
 
-fn set_run_id(root: &mut toml::Value, run_id: &str) -> Result<(), String> {
+pub(crate) fn set_run_id(root: &mut toml::Value, run_id: &str) -> Result<(), String> {
     set_path_value(root, "run.run_id", toml::Value::String(run_id.to_string()))
 }
 
-fn apply_assignment(
+pub(crate) fn apply_assignment(
     root: &mut toml::Value,
     assignment: &BTreeMap<String, toml::Value>,
 ) -> Result<(), String> {
@@ -695,50 +1239,57 @@ fn filter_bars_for_split(
     Ok((bars, report))
 }
 
-fn parse_timestamp_seconds(raw: &str) -> Result<i64, String> {
-    let trimmed = raw.trim();
-    if trimmed.is_empty() {
-        return Err("timestamp cannot be empty".to_string());
-    }
-    if let Ok(v) = trimmed.parse::<i64>() {
-        return Ok(v);
-    }
-    let dt = chrono::DateTime::parse_from_rfc3339(trimmed)
-        .map_err(|err| format!("invalid timestamp (expected epoch seconds or RFC3339): {err}"))?;
-    Ok(dt.timestamp())
-}
-
-fn read_metrics_from_summary(path: &Path) -> Result<RunMetrics, String> {
+pub(crate) fn read_metrics_from_summary(path: &Path) -> Result<RunMetrics, String> {
     let raw = std::fs::read_to_string(path)
         .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
-    let value: serde_json::Value = serde_json::from_str(&raw)
+    let summary = RunSummary::from_json_str(&raw)
         .map_err(|err| format!("failed to parse {}: {err}", path.display()))?;
-    let summary = value.get("summary").unwrap_or(&value);
     Ok(RunMetrics {
-        bars_processed: summary
-            .get("bars_processed")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0),
-        trades: summary.get("trades").and_then(|v| v.as_u64()).unwrap_or(0),
-        win_rate: summary
-            .get("win_rate")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0),
-        net_profit: summary
-            .get("net_profit")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0),
-        sharpe: summary
-            .get("sharpe")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0),
-        max_drawdown: summary
-            .get("max_drawdown")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0),
+        bars_processed: summary.bars_processed,
+        trades: summary.trades,
+        win_rate: summary.win_rate,
+        net_profit: summary.net_profit,
+        sharpe: summary.sharpe,
+        max_drawdown: summary.max_drawdown,
     })
 }
 
+fn read_trial_status(path: &Path) -> Result<BTreeMap<String, TrialStatusLine>, String> {
+    let raw = match std::fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(err) => return Err(format!("failed to read {}: {err}", path.display())),
+    };
+    let mut status = BTreeMap::new();
+    for line in raw.lines().filter(|line| !line.trim().is_empty()) {
+        // A line cut short by the interruption is ignored; that trial just runs again.
+        if let Ok(entry) = serde_json::from_str::<TrialStatusLine>(line) {
+            status.insert(entry.run_id.clone(), entry);
+        }
+    }
+    Ok(status)
+}
+
+fn append_trial_status(
+    file: &mut std::fs::File,
+    entry: &SweepRunEntry,
+    error: &mut Option<String>,
+) {
+    if error.is_some() {
+        return;
+    }
+    let result = serde_json::to_string(entry)
+        .map_err(|err| format!("failed to serialize trial status: {err}"))
+        .and_then(|line| {
+            writeln!(file, "{line}")
+                .and_then(|_| file.flush())
+                .map_err(|err| format!("failed to write {TRIAL_STATUS_FILE_NAME}: {err}"))
+        });
+    if let Err(err) = result {
+        *error = Some(err);
+    }
+}
+
 fn write_manifest(dir: &Path, result: &SweepResult) -> Result<(), String> {
     let path = dir.join("manifest.json");
     let json = serde_json::to_string_pretty(result)
@@ -788,38 +1339,141 @@ fn write_results_csv(dir: &Path, result: &SweepResult) -> Result<(), String> {
     Ok(())
 }
 
-fn write_leaderboard_csv(
-    dir: &Path,
-    result: &SweepResult,
-    cfg: Option<&LeaderboardConfig>,
-) -> Result<(), String> {
-    let sort_by = cfg
-        .and_then(|c| c.sort_by.as_deref())
-        .unwrap_or("sharpe")
-        .trim()
-        .to_lowercase();
-    let descending = cfg.and_then(|c| c.descending).unwrap_or(true);
+struct LeaderboardRanking {
+    mode: RankMode,
+    sort_by: String,
+    descending: bool,
+    metrics: Vec<String>,
+    weights: Vec<f64>,
+}
 
-    let mut rows: Vec<&SweepRunEntry> = result
-        .runs
+struct LeaderboardRow {
+    index: usize,
+    score: Option<f64>,
+    pareto_front: Option<usize>,
+}
+
+fn leaderboard_ranking(cfg: Option<&LeaderboardConfig>) -> Result<LeaderboardRanking, String> {
+    let mode = cfg.and_then(|c| c.rank).unwrap_or_default();
+    let weights = cfg.and_then(|c| c.weights.clone()).unwrap_or_default();
+    match (mode, weights.is_empty()) {
+        (RankMode::Metric, false) => {
+            return Err(
+                "leaderboard.weights needs leaderboard.rank = \"weighted\" or \"pareto\""
+                    .to_string(),
+            )
+        }
+        (RankMode::Weighted | RankMode::Pareto, true) => {
+            return Err(format!(
+                "leaderboard.weights is required with leaderboard.rank = \"{}\"",
+                mode.as_str()
+            ))
+        }
+        _ => {}
+    }
+    for (metric, weight) in &weights {
+        if !OBJECTIVE_METRICS.contains(&metric.as_str()) {
+            return Err(format!(
+                "leaderboard.weights.{metric}: metric must be one of: {}",
+                OBJECTIVE_METRICS.join(", ")
+            ));
+        }
+        if !weight.is_finite() || *weight == 0.0 {
+            return Err(format!(
+                "leaderboard.weights.{metric} must be finite and non-zero"
+            ));
+        }
+    }
+    Ok(LeaderboardRanking {
+        mode,
+        sort_by: cfg
+            .and_then(|c| c.sort_by.as_deref())
+            .unwrap_or("sharpe")
+            .trim()
+            .to_lowercase(),
+        descending: cfg.and_then(|c| c.descending).unwrap_or(true),
+        metrics: weights.keys().cloned().collect(),
+        weights: weights.into_values().collect(),
+    })
+}
+
+// Orders the `ok` runs for `leaderboard.csv`.
+fn rank_leaderboard(runs: &[SweepRunEntry], ranking: &LeaderboardRanking) -> Vec<LeaderboardRow> {
+    let ranked: Vec<(usize, RunMetrics)> = runs
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.status == "ok")
+        .filter_map(|(index, r)| r.metrics.map(|m| (index, m)))
+        .collect();
+
+    if ranking.mode == RankMode::Metric {
+        let mut rows: Vec<(usize, f64)> = ranked
+            .iter()
+            .map(|(index, m)| (*index, metric_value(*m, &ranking.sort_by)))
+            .collect();
+        rows.sort_by(|(_, a), (_, b)| {
+            let ord = b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal);
+            if ranking.descending {
+                ord
+            } else {
+                ord.reverse()
+            }
+        });
+        return rows
+            .into_iter()
+            .map(|(index, _)| LeaderboardRow {
+                index,
+                score: None,
+                pareto_front: None,
+            })
+            .collect();
+    }
+
+    let values: Vec<Vec<f64>> = ranked
+        .iter()
+        .map(|(_, m)| {
+            ranking
+                .metrics
+                .iter()
+                .map(|metric| metric_value(*m, metric))
+                .collect()
+        })
+        .collect();
+    let scores = weighted_scores(&values, &ranking.weights);
+    let fronts = pareto_fronts(&values, &ranking.weights);
+    let mut rows: Vec<LeaderboardRow> = ranked
         .iter()
-        .filter(|r| r.status == "ok" && r.metrics.is_some())
+        .enumerate()
+        .map(|(k, (index, _))| LeaderboardRow {
+            index: *index,
+            score: Some(scores[k]),
+            pareto_front: Some(fronts[k]),
+        })
         .collect();
     rows.sort_by(|a, b| {
-        let av = metric_value(a.metrics.unwrap(), &sort_by);
-        let bv = metric_value(b.metrics.unwrap(), &sort_by);
-        let ord = bv.partial_cmp(&av).unwrap_or(std::cmp::Ordering::Equal);
-        if descending {
-            ord
+        let by_score = b
+            .score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal);
+        if ranking.mode == RankMode::Pareto {
+            a.pareto_front.cmp(&b.pareto_front).then(by_score)
         } else {
-            ord.reverse()
+            by_score
         }
     });
+    rows
+}
 
+fn write_leaderboard_csv(
+    dir: &Path,
+    result: &SweepResult,
+    rows: &[LeaderboardRow],
+) -> Result<(), String> {
+    let multi_objective = result.ranking != RankMode::Metric;
     let path = dir.join("leaderboard.csv");
     let mut wtr = csv::Writer::from_path(&path)
         .map_err(|err| format!("failed to create {}: {err}", path.display()))?;
-    wtr.write_record([
+    let mut header = vec![
         "rank",
         "run_id",
         "split_id",
@@ -829,12 +1483,19 @@ fn write_leaderboard_csv(
         "net_profit",
         "sharpe",
         "max_drawdown",
-    ])
-    .map_err(|err| format!("failed to write leaderboard header: {err}"))?;
+    ];
+    if multi_objective {
+        header.extend(["turnover", "score", "pareto_front"]);
+    }
+    wtr.write_record(header)
+        .map_err(|err| format!("failed to write leaderboard header: {err}"))?;
 
-    for (idx, r) in rows.iter().enumerate() {
-        let m = r.metrics.unwrap();
-        let record = vec![
+    for (idx, row) in rows.iter().enumerate() {
+        let r = &result.runs[row.index];
+        let Some(m) = r.metrics else {
+            continue;
+        };
+        let mut record = vec![
             (idx + 1).to_string(),
             r.run_id.clone(),
             r.split_id.clone(),
@@ -845,6 +1506,11 @@ fn write_leaderboard_csv(
             format!("{}", m.sharpe),
             format!("{}", m.max_drawdown),
         ];
+        if multi_objective {
+            record.push(format!("{}", metric_value(m, "turnover")));
+            record.push(row.score.map(|s| format!("{s:.6}")).unwrap_or_default());
+            record.push(row.pareto_front.map(|f| f.to_string()).unwrap_or_default());
+        }
         wtr.write_record(record)
             .map_err(|err| format!("failed to write leaderboard row: {err}"))?;
     }
@@ -853,21 +1519,93 @@ fn write_leaderboard_csv(
     Ok(())
 }
 
+// Run dirs live in `<out_dir>/<run_id>` and the index in `<out_dir>/sweeps/<sweep_id>/`, so links are
+// relative and keep working when the whole out dir is moved or archived.
+fn sweep_index_json(
+    result: &SweepResult,
+    params: &[SweepParam],
+    derived: &[SweepDerived],
+) -> serde_json::Value {
+    let runs: Vec<serde_json::Value> = result
+        .runs
+        .iter()
+        .map(|r| {
+            serde_json::json!({
+                "run_id": r.run_id,
+                "split_id": r.split_id,
+                "status": r.status,
+                "error": r.error,
+                "params": r.params,
+                "metrics": r.metrics,
+                "href": format!("../../{}/", r.run_id),
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "sweep_id": result.sweep_id,
+        "mode": result.mode,
+        "base_config": result.base_config,
+        "param_paths": params
+            .iter()
+            .map(|p| p.path.as_str())
+            .chain(derived.iter().map(|d| d.path.as_str()))
+            .collect::<Vec<_>>(),
+        "runs": runs,
+    })
+}
+
 fn metric_value(m: RunMetrics, key: &str) -> f64 {
     match key {
         "net_profit" => m.net_profit,
         "max_drawdown" | "max_dd" | "max_drawdown_pct" => m.max_drawdown,
         "trades" => m.trades as f64,
         "bars_processed" => m.bars_processed as f64,
+        // Trades per bar.
+        "turnover" if m.bars_processed > 0 => m.trades as f64 / m.bars_processed as f64,
+        "turnover" => 0.0,
         "win_rate" => m.win_rate,
         _ => m.sharpe,
     }
 }
 
 #[derive(Default)]
-struct InMemoryMarketDataRepository {
-    bars: Vec<Bar>,
-    report: kairos_domain::services::ohlcv::DataQualityReport,
+pub(crate) struct InMemoryMarketDataRepository {
+    pub(crate) bars: Vec<Bar>,
+    pub(crate) report: kairos_domain::services::ohlcv::DataQualityReport,
+}
+
+// Loads each distinct sentiment query once and hands every trial a copy.
+struct CachedSentimentRepository<'a> {
+    inner: &'a (dyn SentimentRepository + Sync),
+    loaded: Mutex<BTreeMap<String, (Vec<SentimentPoint>, SentimentReport)>>,
+}
+
+impl<'a> CachedSentimentRepository<'a> {
+    fn new(inner: &'a (dyn SentimentRepository + Sync)) -> Self {
+        Self {
+            inner,
+            loaded: Mutex::new(BTreeMap::new()),
+        }
+    }
+}
+
+impl SentimentRepository for CachedSentimentRepository<'_> {
+    fn load_sentiment(
+        &self,
+        query: &SentimentQuery,
+    ) -> Result<(Vec<SentimentPoint>, SentimentReport), String> {
+        let key = format!("{query:?}");
+        let mut loaded = self
+            .loaded
+            .lock()
+            .map_err(|_| "sentiment cache lock poisoned".to_string())?;
+        if let Some(cached) = loaded.get(&key) {
+            return Ok(cached.clone());
+        }
+        let fresh = self.inner.load_sentiment(query)?;
+        loaded.insert(key, fresh.clone());
+        Ok(fresh)
+    }
 }
 
 impl MarketDataRepository for InMemoryMarketDataRepository {
@@ -894,10 +1632,12 @@ mod tests {
             SweepParam {
                 path: "strategy.sma_short".to_string(),
                 values: vec![toml::Value::Integer(1), toml::Value::Integer(2)],
+                ..SweepParam::default()
             },
             SweepParam {
                 path: "strategy.sma_long".to_string(),
                 values: vec![toml::Value::Integer(10), toml::Value::Integer(20)],
+                ..SweepParam::default()
             },
         ];
         let grid = expand_grid(&params);
@@ -934,6 +1674,17 @@ mod tests {
         assert_eq!(normalize_parallelism(Some(4)), 4);
     }
 
+    #[test]
+    fn workers_alias_and_eta_estimate() {
+        let meta: SweepMeta =
+            toml::from_str("id = \"w\"\nmode = \"backtest\"\nworkers = 3\n").expect("meta");
+        assert_eq!(meta.parallelism, Some(3));
+
+        assert_eq!(estimate_eta_ms(0, 0, 10), None);
+        assert_eq!(estimate_eta_ms(4_000, 4, 6), Some(6_000));
+        assert_eq!(estimate_eta_ms(4_000, 4, 0), Some(0));
+    }
+
     struct EmptySentimentRepo;
 
     impl SentimentRepository for EmptySentimentRepo {
@@ -964,7 +1715,7 @@ mod tests {
                 let ts = 60_i64 * (index as i64 + 1);
                 let close = 100.0 + index as f64;
                 Bar {
-                    symbol: symbol.to_string(),
+                    symbol: symbol.into(),
                     timestamp: ts,
                     open: close,
                     high: close + 1.0,
@@ -976,13 +1727,8 @@ mod tests {
             .collect()
     }
 
-    #[test]
-    fn run_sweep_parallelism_executes_concurrently_and_keeps_order() {
-        let temp_dir = test_temp_dir("kairos_sweep_parallel");
-        std::fs::create_dir_all(&temp_dir).expect("temp dir");
-
-        let out_dir = temp_dir.join("runs_out");
-        let base_config = format!(
+    fn base_config_toml(out_dir: &Path) -> String {
+        format!(
             r#"
 [run]
 run_id = "base_run"
@@ -1023,7 +1769,16 @@ api_version = "v1"
 feature_version = "v1"
 "#,
             out_dir.display()
-        );
+        )
+    }
+
+    #[test]
+    fn run_sweep_parallelism_executes_concurrently_and_keeps_order() {
+        let temp_dir = test_temp_dir("kairos_sweep_parallel");
+        std::fs::create_dir_all(&temp_dir).expect("temp dir");
+
+        let out_dir = temp_dir.join("runs_out");
+        let base_config = base_config_toml(&out_dir);
         let base_path = temp_dir.join("base.toml");
         std::fs::write(&base_path, base_config).expect("write base config");
 
@@ -1088,6 +1843,7 @@ values = [0.0, 1.0, 2.0]
                 toml::Value::Float(1.0),
                 toml::Value::Float(2.0),
             ],
+            ..SweepParam::default()
         }]);
         let expected_run_ids: Vec<String> = expected_assignments
             .iter()
@@ -1102,6 +1858,518 @@ values = [0.0, 1.0, 2.0]
             result.runs.iter().map(|run| run.run_id.clone()).collect();
         assert_eq!(actual_run_ids, expected_run_ids);
 
+        let index_html =
+            std::fs::read_to_string(result.sweep_dir.join("index.html")).expect("index.html");
+        assert!(index_html.contains("costs.slippage_bps"));
+        for run_id in &expected_run_ids {
+            assert!(index_html.contains(&format!("\"href\":\"../../{run_id}/\"")));
+        }
+
+        let override_dir = temp_dir.join("override_out");
+        let overridden = run_sweep_with_hooks(
+            &sweep_path,
+            &SweepOverrides {
+                out_dir: Some(override_dir.clone()),
+                parallelism: Some(1),
+                config_sets: vec!["risk.max_position_qty=2.5".to_string()],
+                resume: false,
+            },
+            &agent_factory,
+            &source_market,
+            &sentiment,
+            &artifacts,
+            None,
+            None,
+        )
+        .expect("run sweep with overrides");
+        assert_eq!(
+            overridden.sweep_dir,
+            override_dir.join("sweeps").join("parallel_demo")
+        );
+        for run_id in &expected_run_ids {
+            assert!(override_dir.join(run_id).join("summary.json").is_file());
+            let snapshot =
+                std::fs::read_to_string(override_dir.join(run_id).join("config_snapshot.toml"))
+                    .expect("config snapshot");
+            assert!(snapshot.contains("override_out"));
+            assert!(snapshot.contains("max_position_qty = 2.5"));
+        }
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn random_and_bayesian_strategies_run_the_trial_budget() {
+        let temp_dir = test_temp_dir("kairos_sweep_search");
+        std::fs::create_dir_all(&temp_dir).expect("temp dir");
+        let out_dir = temp_dir.join("runs_out");
+        std::fs::write(temp_dir.join("base.toml"), base_config_toml(&out_dir))
+            .expect("write base config");
+
+        let bars = sample_bars("BTCUSDT", 128);
+        let source_market = InMemoryMarketDataRepository {
+            bars: bars.clone(),
+            report: data_quality_from_bars(&bars, Some(60)),
+        };
+        let sentiment = EmptySentimentRepo;
+        let artifacts = FilesystemArtifactWriter::new();
+        let agent_factory = |_: &Config| -> AgentFactoryResult { Ok(None) };
+
+        for strategy in ["random", "bayesian"] {
+            let sweep_path = temp_dir.join(format!("{strategy}.toml"));
+            std::fs::write(
+                &sweep_path,
+                format!(
+                    r#"
+[base]
+config = "base.toml"
+
+[sweep]
+id = "{strategy}_demo"
+mode = "backtest"
+strategy = "{strategy}"
+trials = 6
+init_random = 3
+workers = 2
+objective = "net_profit"
+
+[[params]]
+path = "costs.slippage_bps"
+low = 0.0
+high = 20.0
+
+[[params]]
+path = "risk.max_position_qty"
+values = [1.0, 2.0]
+"#
+                ),
+            )
+            .expect("write sweep config");
+
+            let result = run_sweep(
+                &sweep_path,
+                &agent_factory,
+                &source_market,
+                &sentiment,
+                &artifacts,
+            )
+            .expect("run sweep");
+            assert_eq!(result.runs.len(), 6, "{strategy}");
+            let unique: std::collections::BTreeSet<&str> =
+                result.runs.iter().map(|run| run.run_id.as_str()).collect();
+            assert_eq!(unique.len(), 6, "{strategy}");
+            for run in &result.runs {
+                let bps = run.params["costs.slippage_bps"].as_float().expect("float");
+                assert!((0.0..=20.0).contains(&bps));
+            }
+            let manifest =
+                std::fs::read_to_string(result.sweep_dir.join("manifest.json")).expect("manifest");
+            assert!(manifest.contains(&format!("\"strategy\": \"{strategy}\"")));
+            let summary_path = out_dir.join(&result.runs[0].run_id).join("summary.json");
+            let raw = std::fs::read_to_string(&summary_path)
+                .map_err(|err| format!("failed to read {}: {err}", summary_path.display()))
+                .expect("trial summary");
+            let summary: serde_json::Value = serde_json::from_str(&raw).expect("summary json");
+            assert_eq!(
+                summary["reproducibility"]["seed"],
+                serde_json::json!(DEFAULT_SEARCH_SEED),
+                "{strategy}"
+            );
+        }
+
+        let grid_with_range = temp_dir.join("grid.toml");
+        std::fs::write(
+            &grid_with_range,
+            r#"
+[base]
+config = "base.toml"
+
+[sweep]
+id = "grid_range"
+mode = "backtest"
+
+[[params]]
+path = "costs.slippage_bps"
+low = 0.0
+high = 20.0
+"#,
+        )
+        .expect("write sweep config");
+        let err = run_sweep(
+            &grid_with_range,
+            &agent_factory,
+            &source_market,
+            &sentiment,
+            &artifacts,
+        )
+        .unwrap_err();
+        assert!(err.contains("sweep.strategy"), "{err}");
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn pruning_stops_trials_that_trail_at_a_checkpoint() {
+        let temp_dir = test_temp_dir("kairos_sweep_pruning");
+        std::fs::create_dir_all(&temp_dir).expect("temp dir");
+        let out_dir = temp_dir.join("runs_out");
+        std::fs::write(temp_dir.join("base.toml"), base_config_toml(&out_dir))
+            .expect("write base config");
+        let sweep_path = temp_dir.join("sweep.toml");
+        // Buy-and-hold on rising bars: a higher fee earns less, so later trials trail.
+        std::fs::write(
+            &sweep_path,
+            r#"
+[base]
+config = "base.toml"
+
+[sweep]
+id = "pruning_demo"
+mode = "backtest"
+objective = "net_profit"
+
+[sweep.pruning]
+rule = "median"
+checkpoints = [0.5]
+min_trials = 2
+
+[[params]]
+path = "costs.fee_bps"
+values = [0.0, 10.0, 20.0, 30.0]
+"#,
+        )
+        .expect("write sweep config");
+
+        let bars = sample_bars("BTCUSDT", 128);
+        let source_market = InMemoryMarketDataRepository {
+            bars: bars.clone(),
+            report: data_quality_from_bars(&bars, Some(60)),
+        };
+        let agent_factory = |_: &Config| -> AgentFactoryResult { Ok(None) };
+        let result = run_sweep(
+            &sweep_path,
+            &agent_factory,
+            &source_market,
+            &EmptySentimentRepo,
+            &FilesystemArtifactWriter::new(),
+        )
+        .expect("run sweep");
+
+        let statuses: Vec<&str> = result.runs.iter().map(|r| r.status.as_str()).collect();
+        assert_eq!(statuses, vec!["ok", "pruned", "pruned", "pruned"]);
+        let pruned = &result.runs[1];
+        assert!(pruned
+            .error
+            .as_deref()
+            .is_some_and(|reason| reason.starts_with("pruned at 50% of bars (net_profit")));
+        assert!(!out_dir.join(&pruned.run_id).join("summary.json").exists());
+        let leaderboard =
+            std::fs::read_to_string(result.sweep_dir.join("leaderboard.csv")).expect("leaderboard");
+        assert_eq!(leaderboard.lines().count(), 2);
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn constraints_skip_invalid_assignments_and_derived_params_are_set() {
+        let temp_dir = test_temp_dir("kairos_sweep_rules");
+        std::fs::create_dir_all(&temp_dir).expect("temp dir");
+        let out_dir = temp_dir.join("runs_out");
+        std::fs::write(temp_dir.join("base.toml"), base_config_toml(&out_dir))
+            .expect("write base config");
+        let sweep_path = temp_dir.join("sweep.toml");
+        std::fs::write(
+            &sweep_path,
+            r#"
+[base]
+config = "base.toml"
+
+[sweep]
+id = "rules_demo"
+mode = "backtest"
+
+[[params]]
+path = "costs.fee_bps"
+values = [0.0, 10.0, 20.0]
+
+[[params]]
+path = "costs.slippage_bps"
+values = [0.0, 10.0, 20.0]
+
+[[derived]]
+path = "risk.max_position_qty"
+expr = "costs.fee_bps / 10 + 1"
+
+[[constraints]]
+expr = "costs.slippage_bps <= costs.fee_bps"
+"#,
+        )
+        .expect("write sweep config");
+
+        let bars = sample_bars("BTCUSDT", 64);
+        let source_market = InMemoryMarketDataRepository {
+            bars: bars.clone(),
+            report: data_quality_from_bars(&bars, Some(60)),
+        };
+        let agent_factory = |_: &Config| -> AgentFactoryResult { Ok(None) };
+        let result = run_sweep(
+            &sweep_path,
+            &agent_factory,
+            &source_market,
+            &EmptySentimentRepo,
+            &FilesystemArtifactWriter::new(),
+        )
+        .expect("run sweep");
+
+        assert_eq!(result.runs.len(), 6);
+        assert_eq!(result.rejected_by_constraints, 3);
+        for run in &result.runs {
+            let fee = run.params["costs.fee_bps"].as_float().expect("fee");
+            let slippage = run.params["costs.slippage_bps"]
+                .as_float()
+                .expect("slippage");
+            assert!(slippage <= fee);
+            let qty = run.params["risk.max_position_qty"]
+                .as_float()
+                .expect("derived");
+            assert_eq!(qty, fee / 10.0 + 1.0);
+            let snapshot =
+                std::fs::read_to_string(out_dir.join(&run.run_id).join("config_snapshot.toml"))
+                    .expect("config snapshot");
+            assert!(snapshot.contains(&format!("max_position_qty = {qty:?}")));
+        }
+        let manifest =
+            std::fs::read_to_string(result.sweep_dir.join("manifest.json")).expect("manifest");
+        assert!(manifest.contains("\"rejected_by_constraints\": 3"));
+
+        std::fs::write(
+            &sweep_path,
+            r#"
+[base]
+config = "base.toml"
+
+[sweep]
+id = "rules_bad"
+mode = "backtest"
+
+[[params]]
+path = "costs.fee_bps"
+values = [0.0]
+
+[[constraints]]
+expr = "costs.fee_bps = 0"
+"#,
+        )
+        .expect("write sweep config");
+        let err = run_sweep(
+            &sweep_path,
+            &agent_factory,
+            &source_market,
+            &EmptySentimentRepo,
+            &FilesystemArtifactWriter::new(),
+        )
+        .unwrap_err();
+        assert!(err.contains("did you mean '=='"), "{err}");
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn pareto_ranking_orders_the_leaderboard_by_front() {
+        let temp_dir = test_temp_dir("kairos_sweep_ranking");
+        std::fs::create_dir_all(&temp_dir).expect("temp dir");
+        let out_dir = temp_dir.join("runs_out");
+        std::fs::write(temp_dir.join("base.toml"), base_config_toml(&out_dir))
+            .expect("write base config");
+        let sweep_path = temp_dir.join("sweep.toml");
+        let write_sweep = |leaderboard: &str| {
+            std::fs::write(
+                &sweep_path,
+                format!(
+                    r#"
+[base]
+config = "base.toml"
+
+[sweep]
+id = "ranking_demo"
+mode = "backtest"
+
+[[params]]
+path = "costs.fee_bps"
+values = [20.0, 0.0, 10.0]
+
+[leaderboard]
+{leaderboard}
+"#
+                ),
+            )
+            .expect("write sweep config");
+        };
+
+        let bars = sample_bars("BTCUSDT", 64);
+        let source_market = InMemoryMarketDataRepository {
+            bars: bars.clone(),
+            report: data_quality_from_bars(&bars, Some(60)),
+        };
+        let agent_factory = |_: &Config| -> AgentFactoryResult { Ok(None) };
+        let run = || {
+            run_sweep(
+                &sweep_path,
+                &agent_factory,
+                &source_market,
+                &EmptySentimentRepo,
+                &FilesystemArtifactWriter::new(),
+            )
+        };
+
+        write_sweep("rank = \"pareto\"\nweights = { net_profit = 1.0, turnover = -0.5 }");
+        let result = run().expect("run sweep");
+        assert_eq!(result.ranking, RankMode::Pareto);
+        // Same trades everywhere (turnover adds nothing), so a lower fee dominates.
+        assert_eq!(result.pareto_front, vec![result.runs[1].run_id.clone()]);
+        let leaderboard =
+            std::fs::read_to_string(result.sweep_dir.join("leaderboard.csv")).expect("leaderboard");
+        let lines: Vec<&str> = leaderboard.lines().collect();
+        assert!(lines[0].ends_with(",turnover,score,pareto_front"));
+        let order: Vec<&str> = lines[1..]
+            .iter()
+            .map(|line| line.split(',').nth(1).expect("run_id"))
+            .collect();
+        let expected: Vec<&str> = [1, 2, 0]
+            .iter()
+            .map(|i| result.runs[*i].run_id.as_str())
+            .collect();
+        assert_eq!(order, expected);
+        assert!(lines[1].ends_with(",1.000000,1"), "{}", lines[1]);
+        assert!(lines[3].ends_with(",3"), "{}", lines[3]);
+
+        write_sweep("weights = { sharpe = 1.0 }");
+        let err = run().unwrap_err();
+        assert!(err.starts_with("leaderboard.weights"), "{err}");
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn resume_skips_completed_trials_and_retries_failed_ones() {
+        let temp_dir = test_temp_dir("kairos_sweep_resume");
+        std::fs::create_dir_all(&temp_dir).expect("temp dir");
+        let out_dir = temp_dir.join("runs_out");
+        std::fs::write(temp_dir.join("base.toml"), base_config_toml(&out_dir))
+            .expect("write base config");
+        let sweep_path = temp_dir.join("sweep.toml");
+        std::fs::write(
+            &sweep_path,
+            r#"
+[base]
+config = "base.toml"
+
+[sweep]
+id = "resume_demo"
+mode = "backtest"
+
+[[params]]
+path = "costs.slippage_bps"
+values = [0.0, 1.0, 2.0]
+"#,
+        )
+        .expect("write sweep config");
+
+        let bars = sample_bars("BTCUSDT", 128);
+        let source_market = InMemoryMarketDataRepository {
+            bars: bars.clone(),
+            report: data_quality_from_bars(&bars, Some(60)),
+        };
+        let sentiment = EmptySentimentRepo;
+        let artifacts = FilesystemArtifactWriter::new();
+        let calls = AtomicUsize::new(0);
+        let agent_factory = |_: &Config| -> AgentFactoryResult {
+            calls.fetch_add(1, Ordering::Relaxed);
+            Ok(None)
+        };
+
+        // Interrupted as the third trial starts; it is aborted and not recorded.
+        let interrupt = || calls.load(Ordering::Relaxed) >= 3;
+        let err = run_sweep_with_hooks(
+            &sweep_path,
+            &SweepOverrides::default(),
+            &agent_factory,
+            &source_market,
+            &sentiment,
+            &artifacts,
+            None,
+            Some(&interrupt),
+        )
+        .unwrap_err();
+        assert_eq!(err, "cancelled");
+
+        let status_path = out_dir
+            .join("sweeps")
+            .join("resume_demo")
+            .join(TRIAL_STATUS_FILE_NAME);
+        let status = std::fs::read_to_string(&status_path).expect("trial status");
+        let lines: Vec<&str> = status.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let failed: serde_json::Value = serde_json::from_str(lines[1]).expect("status line");
+        let failed_run_id = failed["run_id"].as_str().expect("run_id").to_string();
+        // Mark the second trial as failed.
+        std::fs::write(
+            &status_path,
+            format!(
+                "{}\n{}\n",
+                lines[0],
+                lines[1].replace("\"status\":\"ok\"", "\"status\":\"error\"")
+            ),
+        )
+        .expect("rewrite trial status");
+
+        calls.store(0, Ordering::Relaxed);
+        let mut trials: Vec<TrialOutcome> = Vec::new();
+        let mut on_progress = |event: ProgressEvent<'_>| {
+            if let ProgressEvent::TrialCompleted(progress) = event {
+                trials.extend(progress.last_trial.clone());
+            }
+        };
+        let result = run_sweep_with_hooks(
+            &sweep_path,
+            &SweepOverrides {
+                resume: true,
+                ..SweepOverrides::default()
+            },
+            &agent_factory,
+            &source_market,
+            &sentiment,
+            &artifacts,
+            Some(&mut on_progress),
+            None,
+        )
+        .expect("resume sweep");
+
+        // Each trial event carries the trial itself, for live leaderboards.
+        let mut reported: Vec<(String, &str)> = trials
+            .iter()
+            .map(|t| (t.run_id.clone(), t.status.as_str()))
+            .collect();
+        reported.sort();
+        let mut expected: Vec<(String, &str)> = result
+            .runs
+            .iter()
+            .map(|run| (run.run_id.clone(), run.status.as_str()))
+            .collect();
+        expected.sort();
+        assert_eq!(reported, expected);
+        assert!(trials.iter().all(|t| t.sharpe.is_some()
+            && t.params[0].0 == "costs.slippage_bps"
+            && t.run_dir == out_dir.join(&t.run_id).display().to_string()));
+
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+        let statuses: Vec<&str> = result.runs.iter().map(|run| run.status.as_str()).collect();
+        assert_eq!(statuses, vec!["skipped", "ok", "ok"]);
+        assert_eq!(result.runs[1].run_id, failed_run_id);
+        assert!(result.runs[0].metrics.is_some());
+        let status = std::fs::read_to_string(&status_path).expect("trial status");
+        assert_eq!(status.lines().count(), 5);
+
         let _ = std::fs::remove_dir_all(&temp_dir);
     }
 }
@@ -0,0 +1,627 @@
+use super::sweep::{
+    apply_assignment, assignment_hash, expand_grid, read_metrics_from_summary, set_run_id,
+    validate_param_paths, AgentFactory, InMemoryMarketDataRepository, RunMetrics, SweepParam,
+};
+use crate::config::{build_ohlcv_query, Config};
+use crate::shared::{
+    ensure_not_cancelled, load_ohlcv_with_control, normalize_timeframe_label, parse_duration_like,
+    summary_meta_json_from_equity,
+};
+use kairos_domain::entities::metrics::{recompute_summary, MetricsSummary};
+use kairos_domain::repositories::artifacts::{ArtifactReader, ArtifactWriter};
+use kairos_domain::repositories::market_data::MarketDataRepository;
+use kairos_domain::repositories::run_registry::RunSortKey;
+use kairos_domain::repositories::sentiment::SentimentRepository;
+use kairos_domain::services::engine::backtest::{NoopControl, RunControl};
+use kairos_domain::services::ohlcv::{data_quality_from_bars, resample_bars};
+use kairos_domain::services::progress::ProgressEvent;
+use kairos_domain::value_objects::bar::Bar;
+use kairos_domain::value_objects::equity_point::EquityPoint;
+use kairos_domain::value_objects::trade::Trade;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::info_span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalkForwardConfig {
+    pub train_bars: usize,
+    pub test_bars: usize,
+    pub step_bars: usize,
+}
+
+// Inclusive bar range of the run timeframe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct BarWindow {
+    pub start_idx: usize,
+    pub end_idx: usize,
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct WalkForwardWindow {
+    pub window_id: usize,
+    pub train: BarWindow,
+    pub test: BarWindow,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WalkForwardWindowResult {
+    pub window_id: usize,
+    pub train: BarWindow,
+    pub test: BarWindow,
+    // Grid assignment picked on the train window (empty without a grid).
+    pub params: BTreeMap<String, toml::Value>,
+    pub candidates: usize,
+    pub in_sample_run_id: String,
+    pub in_sample: Option<RunMetrics>,
+    pub oos_run_id: String,
+    pub oos: Option<RunMetrics>,
+}
+
+#[derive(Debug)]
+pub struct WalkForwardResult {
+    pub wf_dir: PathBuf,
+    pub windows: Vec<WalkForwardWindowResult>,
+    pub oos_summary: MetricsSummary,
+}
+
+pub struct WalkForwardRequest<'a> {
+    pub config: &'a Config,
+    pub config_toml: &'a str,
+    pub wf: WalkForwardConfig,
+    // Optional grid re-optimized on every train window (same `[[params]]` as sweeps).
+    pub params: &'a [SweepParam],
+    pub select_by: RunSortKey,
+}
+
+// Rolling windows: train `[s, s+train)`, test `[s+train, s+train+test)`, `s += step`. Only
+// windows whose test range fits entirely in `bars` are produced.
+pub fn plan_windows(
+    bars: &[Bar],
+    cfg: WalkForwardConfig,
+) -> Result<Vec<WalkForwardWindow>, String> {
+    if cfg.train_bars == 0 || cfg.test_bars == 0 || cfg.step_bars == 0 {
+        return Err("walkforward train/test/step bars must be > 0".to_string());
+    }
+    if cfg.step_bars < cfg.test_bars {
+        return Err(format!(
+            "walkforward step ({}) must be >= test bars ({}) so OOS windows don't overlap",
+            cfg.step_bars, cfg.test_bars
+        ));
+    }
+    let window_len = cfg.train_bars + cfg.test_bars;
+    if bars.len() < window_len {
+        return Err(format!(
+            "not enough bars for walkforward: bars={} train+test={window_len}",
+            bars.len()
+        ));
+    }
+
+    let range = |start: usize, len: usize| BarWindow {
+        start_idx: start,
+        end_idx: start + len - 1,
+        start_ts: bars[start].timestamp,
+        end_ts: bars[start + len - 1].timestamp,
+    };
+    let mut windows = Vec::new();
+    let mut start = 0;
+    while start + window_len <= bars.len() {
+        windows.push(WalkForwardWindow {
+            window_id: windows.len(),
+            train: range(start, cfg.train_bars),
+            test: range(start + cfg.train_bars, cfg.test_bars),
+        });
+        start += cfg.step_bars;
+    }
+    Ok(windows)
+}
+
+#[derive(Deserialize)]
+struct GridFile {
+    #[serde(default)]
+    params: Vec<SweepParam>,
+}
+
+// Reads the `[[params]]` of a TOML file; other tables are ignored so a sweep config can be reused.
+pub fn load_grid_params(path: &Path) -> Result<Vec<SweepParam>, String> {
+    let raw = std::fs::read_to_string(path)
+        .map_err(|err| format!("failed to read grid {}: {err}", path.display()))?;
+    let grid: GridFile = toml::from_str(&raw)
+        .map_err(|err| format!("failed to parse grid {}: {err}", path.display()))?;
+    Ok(grid.params)
+}
+
+pub fn run_walkforward(
+    request: &WalkForwardRequest<'_>,
+    agent_factory: &AgentFactory<'_>,
+    market_data: &dyn MarketDataRepository,
+    sentiment_repo: &dyn SentimentRepository,
+    reader: &dyn ArtifactReader,
+    writer: &dyn ArtifactWriter,
+) -> Result<WalkForwardResult, String> {
+    run_walkforward_with_control(
+        request,
+        agent_factory,
+        market_data,
+        sentiment_repo,
+        reader,
+        writer,
+        &NoopControl,
+    )
+}
+
+pub fn run_walkforward_with_control(
+    request: &WalkForwardRequest<'_>,
+    agent_factory: &AgentFactory<'_>,
+    market_data: &dyn MarketDataRepository,
+    sentiment_repo: &dyn SentimentRepository,
+    reader: &dyn ArtifactReader,
+    writer: &dyn ArtifactWriter,
+    control: &dyn RunControl,
+) -> Result<WalkForwardResult, String> {
+    let config = request.config;
+    let _span = info_span!("run_walkforward", run_id = %config.run.run_id).entered();
+    if request.select_by == RunSortKey::RecordedAt {
+        return Err(
+            "walkforward --select must be net_profit|sharpe|max_drawdown|win_rate".to_string(),
+        );
+    }
+    validate_param_paths(request.params)?;
+
+    let timeframe_label = normalize_timeframe_label(&config.run.timeframe)?;
+    let step_seconds = parse_duration_like(&timeframe_label)?;
+    let source_timeframe_label = normalize_timeframe_label(
+        config
+            .db
+            .source_timeframe
+            .as_deref()
+            .unwrap_or(&timeframe_label),
+    )?;
+    let source_step = parse_duration_like(&source_timeframe_label)?;
+    let (mut source_bars, _source_report) = load_ohlcv_with_control(
+        market_data,
+        &build_ohlcv_query(config, &source_timeframe_label, source_step)
+            .map_err(|err| err.to_string())?,
+        control,
+        "walkforward",
+    )
+    .map_err(|err| err.to_string())?;
+    source_bars.sort_by_key(|b| b.timestamp);
+    source_bars.dedup_by_key(|b| b.timestamp);
+
+    // Windows are counted in run-timeframe bars; each backtest gets the matching source bars
+    // and resamples them itself.
+    let run_bars = if source_timeframe_label != timeframe_label {
+        resample_bars(&source_bars, step_seconds).map_err(|err| err.to_string())?
+    } else {
+        source_bars.clone()
+    };
+    let windows = plan_windows(&run_bars, request.wf)?;
+
+    let base_toml: toml::Value = toml::from_str(request.config_toml)
+        .map_err(|err| format!("failed to parse config TOML as value: {err}"))?;
+    let grid = expand_grid(request.params);
+    let base_run_id = config.run.run_id.clone();
+    let window_market = |range: &BarWindow| {
+        let bars: Vec<Bar> = source_bars
+            .iter()
+            .filter(|b| b.timestamp >= range.start_ts && b.timestamp < range.end_ts + step_seconds)
+            .cloned()
+            .collect();
+        let report = data_quality_from_bars(&bars, Some(source_step));
+        InMemoryMarketDataRepository { bars, report }
+    };
+    let run_trial = |run_id: &str,
+                     assignment: &BTreeMap<String, toml::Value>,
+                     market: &InMemoryMarketDataRepository|
+     -> Result<(PathBuf, Option<RunMetrics>), String> {
+        let mut toml_value = base_toml.clone();
+        apply_assignment(&mut toml_value, assignment)?;
+        set_run_id(&mut toml_value, run_id)?;
+        let config_toml = toml::to_string_pretty(&toml_value)
+            .map_err(|err| format!("failed to serialize walkforward config TOML: {err}"))?;
+        let trial_config =
+            crate::config::load_config_from_source(&config_toml).map_err(|err| err.to_string())?;
+        let run_dir = crate::backtesting::run_backtest_streaming_control(
+            &trial_config,
+            &config_toml,
+            None,
+            market,
+            sentiment_repo,
+            writer,
+            agent_factory(&trial_config)?,
+            control,
+            &mut |_event: ProgressEvent<'_>| {},
+        )
+        .map_err(|err| {
+            if control.should_cancel() {
+                "walkforward cancelled".to_string()
+            } else {
+                format!("{run_id}: {err}")
+            }
+        })?;
+        let metrics = read_metrics_from_summary(&run_dir.join("summary.json")).ok();
+        Ok((run_dir, metrics))
+    };
+
+    let mut results = Vec::with_capacity(windows.len());
+    let mut stitched_trades: Vec<Trade> = Vec::new();
+    let mut stitched_equity: Vec<EquityPoint> = Vec::new();
+    let mut equity_offset = 0.0;
+    let mut realized_offset = 0.0;
+    for window in &windows {
+        ensure_not_cancelled(control, "walkforward").map_err(|err| err.to_string())?;
+        let train_market = window_market(&window.train);
+        let mut best: Option<(usize, String, Option<RunMetrics>)> = None;
+        for (idx, assignment) in grid.iter().enumerate() {
+            let run_id = if assignment.is_empty() {
+                format!("{base_run_id}__wf{:02}__is", window.window_id)
+            } else {
+                let hash = assignment_hash(&format!("wf{:02}", window.window_id), assignment);
+                format!("{base_run_id}__wf{:02}__is__{hash}", window.window_id)
+            };
+            let (_, metrics) = run_trial(&run_id, assignment, &train_market)?;
+            let better = match (&best, metrics) {
+                (None, _) => true,
+                (Some((_, _, Some(prev))), Some(cur)) => {
+                    let (cur, prev) = (
+                        select_value(cur, request.select_by),
+                        select_value(*prev, request.select_by),
+                    );
+                    if request.select_by.higher_is_better() {
+                        cur > prev
+                    } else {
+                        cur < prev
+                    }
+                }
+                (Some((_, _, None)), Some(_)) => true,
+                (Some(_), None) => false,
+            };
+            if better {
+                best = Some((idx, run_id, metrics));
+            }
+        }
+        let (best_idx, in_sample_run_id, in_sample) =
+            best.ok_or_else(|| "walkforward grid is empty".to_string())?;
+        let params = grid[best_idx].clone();
+
+        let oos_run_id = format!("{base_run_id}__wf{:02}__oos", window.window_id);
+        let (oos_dir, oos) = run_trial(&oos_run_id, &params, &window_market(&window.test))?;
+
+        let trades = reader.read_trades_csv(&oos_dir.join("trades.csv"))?;
+        let equity = reader.read_equity_csv(&oos_dir.join("equity.csv"))?;
+        // Every OOS run starts from `initial_capital`; chaining adds each window's PnL to the
+        // running total instead of compounding it.
+        stitched_trades.extend(trades);
+        stitched_equity.extend(equity.iter().map(|p| EquityPoint {
+            timestamp: p.timestamp,
+            equity: p.equity + equity_offset,
+            cash: p.cash + equity_offset,
+            position_qty: p.position_qty,
+            unrealized_pnl: p.unrealized_pnl,
+            realized_pnl: p.realized_pnl + realized_offset,
+        }));
+        if let Some(last) = equity.last() {
+            equity_offset += last.equity - config.run.initial_capital;
+            realized_offset += last.realized_pnl;
+        }
+
+        tracing::info!(
+            window = window.window_id,
+            in_sample_run_id = %in_sample_run_id,
+            oos_run_id = %oos_run_id,
+            "walkforward window completed"
+        );
+        metrics::counter!("kairos.walkforward.windows_total").increment(1);
+        results.push(WalkForwardWindowResult {
+            window_id: window.window_id,
+            train: window.train,
+            test: window.test,
+            params,
+            candidates: grid.len(),
+            in_sample_run_id,
+            in_sample,
+            oos_run_id,
+            oos,
+        });
+    }
+
+    let oos_summary = recompute_summary(&stitched_trades, &stitched_equity);
+    let wf_dir = PathBuf::from(&config.paths.out_dir)
+        .join("walkforward")
+        .join(&base_run_id);
+    writer.ensure_dir(&wf_dir)?;
+    write_windows_csv(&wf_dir.join("windows.csv"), &results)?;
+    writer.write_trades_csv(&wf_dir.join("trades.csv"), &stitched_trades)?;
+    writer.write_equity_csv(&wf_dir.join("equity.csv"), &stitched_equity)?;
+    let meta = summary_meta_json_from_equity(config, &stitched_equity);
+    writer.write_summary_json(
+        &wf_dir.join("summary.json"),
+        &oos_summary,
+        meta.as_ref(),
+        None,
+        None,
+    )?;
+    let document = serde_json::json!({
+        "run_id": base_run_id,
+        "train_bars": request.wf.train_bars,
+        "test_bars": request.wf.test_bars,
+        "step_bars": request.wf.step_bars,
+        "param_paths": request.params.iter().map(|p| p.path.as_str()).collect::<Vec<_>>(),
+        "windows": results,
+    });
+    let json = serde_json::to_string_pretty(&document)
+        .map_err(|err| format!("failed to serialize walkforward.json: {err}"))?;
+    let path = wf_dir.join("walkforward.json");
+    std::fs::write(&path, json)
+        .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
+    writer.write_manifest(&wf_dir)?;
+
+    Ok(WalkForwardResult {
+        wf_dir,
+        windows: results,
+        oos_summary,
+    })
+}
+
+fn select_value(metrics: RunMetrics, key: RunSortKey) -> f64 {
+    let value = match key {
+        RunSortKey::NetProfit => metrics.net_profit,
+        RunSortKey::MaxDrawdown => metrics.max_drawdown,
+        RunSortKey::WinRate => metrics.win_rate,
+        RunSortKey::Sharpe | RunSortKey::RecordedAt => metrics.sharpe,
+    };
+    // NaN/inf (e.g. sharpe with no variance) never wins a selection.
+    if value.is_finite() {
+        value
+    } else if key.higher_is_better() {
+        f64::NEG_INFINITY
+    } else {
+        f64::INFINITY
+    }
+}
+
+fn write_windows_csv(path: &Path, results: &[WalkForwardWindowResult]) -> Result<(), String> {
+    let mut wtr = csv::Writer::from_path(path)
+        .map_err(|err| format!("failed to create {}: {err}", path.display()))?;
+    wtr.write_record([
+        "window_id",
+        "train_start_ts",
+        "train_end_ts",
+        "test_start_ts",
+        "test_end_ts",
+        "params",
+        "in_sample_net_profit",
+        "in_sample_sharpe",
+        "oos_run_id",
+        "oos_trades",
+        "oos_net_profit",
+        "oos_sharpe",
+        "oos_max_drawdown",
+        "oos_win_rate",
+    ])
+    .map_err(|err| format!("failed to write windows header: {err}"))?;
+    for r in results {
+        let params = serde_json::to_string(&r.params).unwrap_or_default();
+        let is = r.in_sample;
+        let oos = r.oos;
+        wtr.write_record([
+            r.window_id.to_string(),
+            r.train.start_ts.to_string(),
+            r.train.end_ts.to_string(),
+            r.test.start_ts.to_string(),
+            r.test.end_ts.to_string(),
+            params,
+            is.map(|m| m.net_profit.to_string()).unwrap_or_default(),
+            is.map(|m| m.sharpe.to_string()).unwrap_or_default(),
+            r.oos_run_id.clone(),
+            oos.map(|m| m.trades.to_string()).unwrap_or_default(),
+            oos.map(|m| m.net_profit.to_string()).unwrap_or_default(),
+            oos.map(|m| m.sharpe.to_string()).unwrap_or_default(),
+            oos.map(|m| m.max_drawdown.to_string()).unwrap_or_default(),
+            oos.map(|m| m.win_rate.to_string()).unwrap_or_default(),
+        ])
+        .map_err(|err| format!("failed to write windows row: {err}"))?;
+    }
+    wtr.flush()
+        .map_err(|err| format!("failed to flush {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::experiments::sweep::AgentFactoryResult;
+    use kairos_domain::repositories::market_data::OhlcvQuery;
+    use kairos_domain::repositories::sentiment::SentimentQuery;
+    use kairos_domain::services::ohlcv::DataQualityReport;
+    use kairos_domain::services::sentiment::{SentimentPoint, SentimentReport};
+    use kairos_infrastructure::artifacts::{FilesystemArtifactReader, FilesystemArtifactWriter};
+
+    struct EmptySentimentRepo;
+
+    impl SentimentRepository for EmptySentimentRepo {
+        fn load_sentiment(
+            &self,
+            _query: &SentimentQuery,
+        ) -> Result<(Vec<SentimentPoint>, SentimentReport), String> {
+            Ok((Vec::new(), SentimentReport::default()))
+        }
+    }
+
+    struct FixedMarket(Vec<Bar>);
+
+    impl MarketDataRepository for FixedMarket {
+        fn load_ohlcv(&self, _query: &OhlcvQuery) -> Result<(Vec<Bar>, DataQualityReport), String> {
+            Ok((self.0.clone(), data_quality_from_bars(&self.0, Some(60))))
+        }
+    }
+
+    fn bars(count: usize) -> Vec<Bar> {
+        (0..count)
+            .map(|index| {
+                let close = 100.0 + (index as f64 / 3.0).sin() * 5.0;
+                Bar {
+                    symbol: "BTCUSDT".into(),
+                    timestamp: 60 * (index as i64 + 1),
+                    open: close,
+                    high: close + 1.0,
+                    low: close - 1.0,
+                    close,
+                    volume: 1.0,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn plan_windows_rolls_train_and_test_ranges() {
+        let cfg = WalkForwardConfig {
+            train_bars: 40,
+            test_bars: 20,
+            step_bars: 20,
+        };
+        let windows = plan_windows(&bars(105), cfg).unwrap();
+        assert_eq!(windows.len(), 3);
+        assert_eq!(
+            (windows[1].train.start_idx, windows[1].train.end_idx),
+            (20, 59)
+        );
+        assert_eq!(
+            (windows[1].test.start_idx, windows[1].test.end_idx),
+            (60, 79)
+        );
+        assert_eq!(windows[2].test.end_ts, 60 * 100);
+
+        assert!(plan_windows(&bars(50), cfg).is_err());
+        assert!(plan_windows(
+            &bars(105),
+            WalkForwardConfig {
+                step_bars: 10,
+                ..cfg
+            }
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn grid_reuses_sweep_config_params() {
+        let grid: GridFile =
+            toml::from_str(include_str!("../../../ops/configs/sweeps/sma_grid.toml")).unwrap();
+        let paths: Vec<&str> = grid.params.iter().map(|p| p.path.as_str()).collect();
+        assert_eq!(paths, vec!["strategy.sma_short", "strategy.sma_long"]);
+    }
+
+    #[test]
+    fn run_walkforward_reoptimizes_per_window_and_stitches_oos() {
+        let temp_dir = std::env::temp_dir().join(format!(
+            "kairos_walkforward_{}_{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("clock before UNIX_EPOCH")
+                .as_nanos()
+        ));
+        let config_toml = format!(
+            r#"
+[run]
+run_id = "wf_demo"
+symbol = "BTCUSDT"
+timeframe = "1min"
+initial_capital = 1000.0
+
+[db]
+ohlcv_table = "ohlcv_candles"
+exchange = "kucoin"
+market = "spot"
+
+[paths]
+out_dir = "{}"
+
+[costs]
+fee_bps = 0.0
+slippage_bps = 0.0
+
+[risk]
+max_position_qty = 1.0
+max_drawdown_pct = 1.0
+max_exposure_pct = 1.0
+
+[features]
+return_mode = "pct"
+sma_windows = [2, 4]
+rsi_enabled = false
+sentiment_lag = "0s"
+
+[agent]
+mode = "baseline"
+url = "http://127.0.0.1:8000"
+timeout_ms = 100
+retries = 0
+fallback_action = "HOLD"
+api_version = "v1"
+feature_version = "v1"
+"#,
+            temp_dir.display()
+        );
+        let config = crate::config::load_config_from_source(&config_toml).expect("config");
+        let params = vec![SweepParam {
+            path: "costs.fee_bps".to_string(),
+            values: vec![toml::Value::Float(0.0), toml::Value::Float(50.0)],
+            ..SweepParam::default()
+        }];
+        let request = WalkForwardRequest {
+            config: &config,
+            config_toml: &config_toml,
+            wf: WalkForwardConfig {
+                train_bars: 40,
+                test_bars: 20,
+                step_bars: 20,
+            },
+            params: &params,
+            select_by: RunSortKey::NetProfit,
+        };
+        let agent_factory = |_: &Config| -> AgentFactoryResult { Ok(None) };
+
+        let result = run_walkforward(
+            &request,
+            &agent_factory,
+            &FixedMarket(bars(100)),
+            &EmptySentimentRepo,
+            &FilesystemArtifactReader::new(),
+            &FilesystemArtifactWriter::new(),
+        )
+        .expect("run walkforward");
+
+        assert_eq!(result.windows.len(), 3);
+        for window in &result.windows {
+            assert_eq!(window.candidates, 2);
+            assert!(window.params.contains_key("costs.fee_bps"));
+            assert_eq!(window.oos.map(|m| m.bars_processed), Some(20));
+            assert!(temp_dir
+                .join(&window.oos_run_id)
+                .join("summary.json")
+                .is_file());
+        }
+        assert_eq!(result.windows[2].oos_run_id, "wf_demo__wf02__oos");
+        assert_eq!(result.oos_summary.bars_processed, 60);
+        let oos_net: f64 = result
+            .windows
+            .iter()
+            .filter_map(|w| w.oos.map(|m| m.net_profit))
+            .sum();
+        assert!((result.oos_summary.net_profit - oos_net).abs() < 1e-6);
+        for file in [
+            "windows.csv",
+            "walkforward.json",
+            "summary.json",
+            "equity.csv",
+            "manifest.json",
+        ] {
+            assert!(result.wf_dir.join(file).is_file(), "{file}");
+        }
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+}
@@ -1,9 +1,15 @@
+pub mod annotations;
 pub mod backtesting;
 pub mod benchmarking;
 pub mod config;
+pub mod diagnostics;
+pub mod error;
 pub mod experiments;
+pub mod live_trading;
 pub mod meta;
 pub mod paper_trading;
+pub mod registry;
 pub mod reporting;
+pub mod reproducibility;
 mod shared;
 pub mod validation;
@@ -0,0 +1,602 @@
+use crate::config::{Config, ConfigError};
+use crate::error::RunError;
+use crate::paper_trading::live::LiveJournal;
+use crate::paper_trading::{
+    realtime_strategy, resolve_run_dir, write_outputs, RealtimeStreamStatus, StreamBarSource,
+};
+use crate::reproducibility::DataFingerprinter;
+use crate::shared::{
+    build_metrics_config, resolve_artifact_compression, resolve_execution_config, resolve_size_mode,
+};
+use kairos_domain::entities::metrics::MetricsState;
+use kairos_domain::entities::portfolio::Portfolio;
+use kairos_domain::entities::risk::RiskLimits;
+use kairos_domain::repositories::agent::AgentClient as AgentPort;
+use kairos_domain::repositories::artifacts::ArtifactWriter;
+use kairos_domain::repositories::execution_venue::{
+    ExecutionVenue, VenueError, VenueOrderRequest, VenueOrderState,
+};
+use kairos_domain::repositories::market_stream::MarketStream;
+use kairos_domain::repositories::sentiment::SentimentRepository;
+use kairos_domain::services::audit::AuditEvent;
+use kairos_domain::services::engine::backtest::{
+    BacktestResults, BarProgress, OrderSizeMode, RunControl, TradeInBar,
+};
+use kairos_domain::services::market_data_source::MarketDataSource;
+use kairos_domain::services::progress::{stage, ProgressEvent, ProgressSink};
+use kairos_domain::services::strategy::Strategy;
+use kairos_domain::value_objects::action::Action;
+use kairos_domain::value_objects::action_type::ActionType;
+use kairos_domain::value_objects::bar::Bar;
+use kairos_domain::value_objects::equity_point::EquityPoint;
+use kairos_domain::value_objects::side::Side;
+use kairos_domain::value_objects::trade::Trade;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::info_span;
+
+pub const LIVE_VENUES: [&str; 2] = ["kucoin_sandbox", "kucoin"];
+
+const DEFAULT_FILL_TIMEOUT_MS: u64 = 10_000;
+const DEFAULT_POLL_INTERVAL_MS: u64 = 500;
+// Extra tries for a venue call whose outcome was unknown, `live.poll_interval_ms` apart.
+const TRANSPORT_RETRIES: u32 = 3;
+
+// The `[live]` section, checked. Live runs refuse to start without it.
+#[derive(Debug, Clone)]
+pub struct LiveSettings {
+    pub venue: String,
+    pub max_order_notional: f64,
+    pub max_orders: Option<u64>,
+    pub max_bars: Option<u64>,
+    pub fill_timeout: Duration,
+    pub poll_interval: Duration,
+}
+
+pub fn resolve_live_settings(config: &Config) -> Result<LiveSettings, ConfigError> {
+    let Some(live) = config.live.as_ref() else {
+        return Err(ConfigError::Invalid(
+            "live.venue and live.max_order_notional are required (add a [live] section)"
+                .to_string(),
+        ));
+    };
+    let venue = live.venue.trim().to_lowercase();
+    if !LIVE_VENUES.contains(&venue.as_str()) {
+        return Err(ConfigError::Invalid(format!(
+            "live.venue must be: {}",
+            LIVE_VENUES.join(" | ")
+        )));
+    }
+    if !live.max_order_notional.is_finite() || live.max_order_notional <= 0.0 {
+        return Err(ConfigError::Invalid(
+            "live.max_order_notional must be finite and > 0".to_string(),
+        ));
+    }
+    for (key, value) in [
+        ("live.max_orders", live.max_orders),
+        ("live.max_bars", live.max_bars),
+        ("live.fill_timeout_ms", live.fill_timeout_ms),
+        ("live.poll_interval_ms", live.poll_interval_ms),
+    ] {
+        if value == Some(0) {
+            return Err(ConfigError::Invalid(format!("{key} must be > 0")));
+        }
+    }
+    Ok(LiveSettings {
+        venue,
+        max_order_notional: live.max_order_notional,
+        max_orders: live.max_orders,
+        max_bars: live.max_bars,
+        fill_timeout: Duration::from_millis(
+            live.fill_timeout_ms.unwrap_or(DEFAULT_FILL_TIMEOUT_MS),
+        ),
+        poll_interval: Duration::from_millis(
+            live.poll_interval_ms.unwrap_or(DEFAULT_POLL_INTERVAL_MS),
+        ),
+    })
+}
+
+// Runs the strategy on bars closed from the market stream (as in realtime paper) but sends its
+// orders to `venue` as market orders and books the venue's fills. The portfolio starts flat with
+// `run.initial_capital` of quote currency, which the venue account must cover. The session ends
+// after `live.max_bars` bars, when `control` stops it or when a placed order can no longer be
+// tracked; in every case the artifacts are written, since the fills are real.
+#[allow(clippy::too_many_arguments)]
+pub fn run_live_streaming_control(
+    config: &Config,
+    config_toml: &str,
+    out: Option<PathBuf>,
+    connect_stream: &mut dyn FnMut() -> Result<Box<dyn MarketStream>, String>,
+    sentiment_repo: &dyn SentimentRepository,
+    artifacts: &dyn ArtifactWriter,
+    remote_agent: Option<Box<dyn AgentPort>>,
+    venue: &dyn ExecutionVenue,
+    control: &dyn RunControl,
+    progress: &mut dyn ProgressSink,
+    on_status: &mut dyn FnMut(RealtimeStreamStatus),
+) -> Result<PathBuf, RunError> {
+    let _span = info_span!(
+        "run_live",
+        run_id = %config.run.run_id,
+        symbol = %config.run.symbol,
+        venue = %venue.name()
+    )
+    .entered();
+
+    let settings = resolve_live_settings(config)?;
+    let balances = venue
+        .balances(&config.run.symbol)
+        .map_err(RunError::Runtime)?;
+    if balances.quote < config.run.initial_capital {
+        return Err(RunError::Runtime(format!(
+            "{} account has {} quote currency available for {}, less than run.initial_capital {}",
+            venue.name(),
+            balances.quote,
+            config.run.symbol,
+            config.run.initial_capital
+        )));
+    }
+
+    let mut strategy = realtime_strategy(config, sentiment_repo, remote_agent)?;
+    let execution = resolve_execution_config(config).map_err(ConfigError::Invalid)?;
+    let compression = resolve_artifact_compression(config).map_err(ConfigError::Invalid)?;
+    let mut session = LiveSession {
+        run_id: config.run.run_id.clone(),
+        symbol: config.run.symbol.clone(),
+        venue,
+        settings,
+        risk_limits: RiskLimits {
+            max_position_qty: config.risk.max_position_qty,
+            max_drawdown_pct: config.risk.max_drawdown_pct,
+            max_exposure_pct: config.risk.max_exposure_pct,
+        },
+        size_mode: resolve_size_mode(config),
+        fee_bps: config.costs.fee_bps,
+        portfolio: Portfolio::new_with_cash(config.run.initial_capital),
+        metrics: MetricsState::new(build_metrics_config(config)),
+        orders_sent: 0,
+        session_nonce: session_nonce(),
+        order_attempts: 0,
+        halt_trading: false,
+        audit_events: vec![AuditEvent {
+            run_id: config.run.run_id.clone(),
+            timestamp: 0,
+            stage: "live".to_string(),
+            symbol: Some(config.run.symbol.clone()),
+            action: "start".to_string(),
+            error: None,
+            details: json!({
+                "venue": venue.name(),
+                "strategy": strategy.name(),
+                "venue_base_available": balances.base,
+                "venue_quote_available": balances.quote,
+            }),
+        }],
+    };
+
+    let mut data = StreamBarSource::connect(config, connect_stream, on_status)?;
+    let seed = data.seed();
+    let run_dir = resolve_run_dir(config, out);
+    let mut live = LiveJournal::open(
+        artifacts,
+        &run_dir,
+        &config.run.run_id,
+        &config.run.symbol,
+        &config.run.timeframe,
+        "live",
+    )
+    .map_err(RunError::Artifacts)?;
+
+    let mut fingerprinter = DataFingerprinter::new();
+    let mut bar_index: u64 = 0;
+    let mut stopped = false;
+    let mut failure = None;
+    while session.settings.max_bars.is_none_or(|max| bar_index < max) {
+        if control.should_cancel() || !control.wait_if_paused() {
+            stopped = true;
+            break;
+        }
+        let Some(bar) = data.next_bar() else {
+            break;
+        };
+        bar_index += 1;
+
+        let mut trades_in_bar = Vec::new();
+        if !session.halt_trading {
+            let action = strategy.on_bar(&bar, &session.portfolio);
+            match session.execute(&bar, action) {
+                Ok(fill) => trades_in_bar.extend(fill),
+                Err(err) => {
+                    session.halt_trading = true;
+                    session.audit(
+                        &bar,
+                        "order_lost",
+                        Some(err.clone()),
+                        json!({ "venue": session.venue.name() }),
+                    );
+                    failure = Some(err);
+                }
+            }
+        }
+        session.record_equity(&bar);
+
+        let bar_progress = BarProgress {
+            bar_index,
+            timestamp: bar.timestamp,
+            close: bar.close,
+            equity: session.portfolio.equity(&bar.symbol, bar.close),
+            cash: session.portfolio.cash(),
+            position_qty: session.portfolio.position_qty(&bar.symbol),
+            avg_price: session.portfolio.position_avg_price(&bar.symbol),
+            trades_in_bar,
+            // Live orders fill (or fail) at the venue before the bar is reported.
+            open_orders: Vec::new(),
+            trading_halted: session.halt_trading,
+        };
+        fingerprinter.push(bar_progress.timestamp, bar_progress.close);
+        live.record(&bar_progress);
+        progress.emit(ProgressEvent::BarProcessed(&bar_progress));
+        if failure.is_some() {
+            break;
+        }
+    }
+
+    session
+        .audit_events
+        .append(&mut strategy.drain_audit_events());
+    let (equity, trades, summary) = session.metrics.into_parts();
+    session.audit_events.push(AuditEvent {
+        run_id: config.run.run_id.clone(),
+        timestamp: equity.last().map(|p| p.timestamp).unwrap_or(0),
+        stage: "live".to_string(),
+        symbol: Some(config.run.symbol.clone()),
+        action: if failure.is_some() {
+            "error"
+        } else if stopped {
+            "stopped"
+        } else {
+            "complete"
+        }
+        .to_string(),
+        error: failure.clone(),
+        details: json!({
+            "bars_processed": summary.bars_processed,
+            "orders_sent": session.orders_sent,
+            "trades": summary.trades,
+            "net_profit": summary.net_profit,
+            "halt_trading": session.halt_trading,
+        }),
+    });
+    metrics::gauge!("kairos.live.bars_processed").set(summary.bars_processed as f64);
+    metrics::gauge!("kairos.live.trades").set(summary.trades as f64);
+
+    let results = BacktestResults {
+        summary,
+        trades,
+        equity,
+        audit_events: session.audit_events,
+    };
+    if failure.is_some() {
+        live.finish("error");
+    }
+    let written = stage(progress, "write_outputs", || {
+        write_outputs(
+            config,
+            config_toml,
+            &run_dir,
+            results,
+            &execution,
+            compression,
+            artifacts,
+            Vec::new(),
+            fingerprinter.finish(),
+            seed,
+            &mut live,
+            None,
+        )
+    });
+    if written.is_err() {
+        live.finish("error");
+    }
+    written.map_err(RunError::Artifacts)?;
+    if let Some(err) = failure {
+        return Err(RunError::Runtime(err));
+    }
+
+    Ok(run_dir)
+}
+
+// The session start time in ms, moved past the previous session's value so that two sessions
+// started within the same millisecond still get distinct client order ids.
+fn session_nonce() -> u64 {
+    static LAST: AtomicU64 = AtomicU64::new(0);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0);
+    let next = |prev: u64| now.max(prev + 1);
+    let prev = LAST
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |prev| {
+            Some(next(prev))
+        })
+        .unwrap_or_else(|prev| prev);
+    next(prev)
+}
+
+struct LiveSession<'a> {
+    run_id: String,
+    symbol: String,
+    venue: &'a dyn ExecutionVenue,
+    settings: LiveSettings,
+    risk_limits: RiskLimits,
+    size_mode: OrderSizeMode,
+    fee_bps: f64,
+    portfolio: Portfolio,
+    metrics: MetricsState,
+    orders_sent: u64,
+    // Client order ids are `{run_id}-{session_nonce}-{attempt}`: the nonce keeps a rerun of the
+    // same config from looking up (and booking) an earlier session's order, and every placement
+    // attempt takes a new id, accepted or not.
+    session_nonce: u64,
+    order_attempts: u64,
+    halt_trading: bool,
+    audit_events: Vec<AuditEvent>,
+}
+
+impl LiveSession<'_> {
+    // Sizes the action against the local portfolio and the live guards, then places it and waits
+    // for the venue to settle it. Rejections are audited and skipped. A placement whose outcome is
+    // unknown is looked up by client order id; losing track of an order is an error, because the
+    // local portfolio could no longer match the account.
+    fn execute(&mut self, bar: &Bar, action: Action) -> Result<Option<TradeInBar>, String> {
+        let side = match action.action_type {
+            ActionType::Buy => Side::Buy,
+            ActionType::Sell => Side::Sell,
+            ActionType::Hold => return Ok(None),
+        };
+        let quantity = match self.order_quantity(bar, side, action.size) {
+            Ok(quantity) => quantity,
+            Err(reason) => {
+                self.reject(bar, side, action.size, &reason);
+                return Ok(None);
+            }
+        };
+
+        self.order_attempts += 1;
+        let order = VenueOrderRequest {
+            client_order_id: format!(
+                "{}-{}-{}",
+                self.run_id, self.session_nonce, self.order_attempts
+            ),
+            symbol: self.symbol.clone(),
+            side,
+            quantity,
+        };
+        let order_id = match self.venue.place_market_order(&order) {
+            Ok(order_id) => order_id,
+            Err(VenueError::Rejected(reason)) => {
+                self.reject(bar, side, quantity, &reason);
+                return Ok(None);
+            }
+            Err(VenueError::Transport(err)) => {
+                match self.with_retries(|| self.venue.order_by_client_id(&order.client_order_id)) {
+                    Ok(Some(state)) => state.order_id,
+                    Ok(None) => {
+                        self.reject(bar, side, quantity, &err);
+                        return Ok(None);
+                    }
+                    Err(lookup) => {
+                        return Err(format!(
+                            "live order {} has an unknown outcome ({err}) and could not be looked up: {lookup}",
+                            order.client_order_id
+                        ))
+                    }
+                }
+            }
+        };
+        self.orders_sent += 1;
+        metrics::counter!("kairos.live.orders_total").increment(1);
+        let state = self
+            .await_settled(&order_id)
+            .map_err(|err| format!("lost track of live order {order_id}: {err}"))?;
+
+        let details = json!({
+            "venue": self.venue.name(),
+            "order_id": order_id,
+            "client_order_id": order.client_order_id,
+            "side": side,
+            "requested_qty": quantity,
+            "filled_qty": state.filled_qty,
+            "filled_funds": state.filled_funds,
+            "fee": state.fee,
+            "bar_close": bar.close,
+        });
+        let Some(price) = state.avg_price() else {
+            self.audit(bar, "order_unfilled", None, details);
+            return Ok(None);
+        };
+        self.audit(bar, "order_filled", None, details);
+
+        self.portfolio
+            .apply_fill(&self.symbol, side, state.filled_qty, price, state.fee);
+        let slippage = match side {
+            Side::Buy => (price - bar.close) * state.filled_qty,
+            Side::Sell => (bar.close - price) * state.filled_qty,
+        };
+        self.metrics.record_trade(Trade {
+            timestamp: bar.timestamp,
+            symbol: self.symbol.as_str().into(),
+            side,
+            quantity: state.filled_qty,
+            price,
+            fee: state.fee,
+            slippage,
+            strategy_id: order.client_order_id,
+            reason: action.reason.unwrap_or_else(|| "strategy".to_string()),
+        });
+        Ok(Some(TradeInBar {
+            timestamp: bar.timestamp,
+            side,
+            quantity: state.filled_qty,
+            price,
+            fee: state.fee,
+            slippage,
+        }))
+    }
+
+    fn order_quantity(&self, bar: &Bar, side: Side, size: f64) -> Result<f64, String> {
+        if self
+            .settings
+            .max_orders
+            .is_some_and(|max| self.orders_sent >= max)
+        {
+            return Err("max_orders_reached".to_string());
+        }
+        if !size.is_finite() || size <= 0.0 {
+            return Err("size_not_positive".to_string());
+        }
+        if !bar.close.is_finite() || bar.close <= 0.0 {
+            return Err("price_not_positive".to_string());
+        }
+        let position = self.portfolio.position_qty(&self.symbol);
+        let equity = self.portfolio.equity(&self.symbol, bar.close);
+        let requested = match self.size_mode {
+            OrderSizeMode::Quantity => size,
+            OrderSizeMode::PctEquity => {
+                if size > 1.0 {
+                    return Err("pct_out_of_range".to_string());
+                }
+                match side {
+                    Side::Buy => equity * size / bar.close,
+                    Side::Sell => position * size,
+                }
+            }
+        };
+        let notional_cap = self.settings.max_order_notional / bar.close;
+        let quantity = match side {
+            Side::Buy => {
+                let affordable =
+                    self.portfolio.cash() / (bar.close * (1.0 + self.fee_bps / 10_000.0));
+                let quantity = requested.min(notional_cap).min(affordable);
+                if !self.risk_limits.allows_position(position, quantity) {
+                    return Err("risk_position_limit".to_string());
+                }
+                if !self
+                    .risk_limits
+                    .allows_exposure(equity, (position + quantity) * bar.close)
+                {
+                    return Err("risk_exposure_limit".to_string());
+                }
+                quantity
+            }
+            Side::Sell => requested.min(position).min(notional_cap),
+        };
+        if quantity <= 0.0 {
+            return Err(match side {
+                Side::Buy => "insufficient_cash",
+                Side::Sell => "no_position",
+            }
+            .to_string());
+        }
+        Ok(quantity)
+    }
+
+    // Polls until the order is no longer active; a market order still working after
+    // `live.fill_timeout_ms` is cancelled and its partial fill (if any) is kept.
+    fn await_settled(&self, order_id: &str) -> Result<VenueOrderState, VenueError> {
+        let deadline = Instant::now() + self.settings.fill_timeout;
+        let poll = || self.with_retries(|| self.venue.order_state(order_id));
+        let mut state = poll()?;
+        while state.active && Instant::now() < deadline {
+            thread::sleep(self.settings.poll_interval);
+            state = poll()?;
+        }
+        if state.active {
+            // A failed cancel only matters if the order is still working afterwards.
+            let cancelled = self.with_retries(|| self.venue.cancel_order(order_id));
+            state = poll()?;
+            if state.active {
+                cancelled?;
+            }
+        }
+        Ok(state)
+    }
+
+    // Retries `call` while its outcome is unknown; the venue's rejections are returned at once.
+    fn with_retries<T>(
+        &self,
+        mut call: impl FnMut() -> Result<T, VenueError>,
+    ) -> Result<T, VenueError> {
+        let mut retries = 0;
+        loop {
+            match call() {
+                Err(VenueError::Transport(err)) if retries < TRANSPORT_RETRIES => {
+                    retries += 1;
+                    tracing::warn!(venue = %self.venue.name(), retries, error = %err, "live venue call failed, retrying");
+                    thread::sleep(self.settings.poll_interval);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn record_equity(&mut self, bar: &Bar) {
+        self.metrics.record_equity(EquityPoint {
+            timestamp: bar.timestamp,
+            equity: self.portfolio.equity(&bar.symbol, bar.close),
+            cash: self.portfolio.cash(),
+            position_qty: self.portfolio.position_qty(&bar.symbol),
+            unrealized_pnl: self.portfolio.unrealized_pnl(&bar.symbol, bar.close),
+            realized_pnl: self.portfolio.realized_pnl(),
+        });
+        let drawdown = self.metrics.max_drawdown();
+        if !self.halt_trading && !self.risk_limits.allows_drawdown(drawdown) {
+            self.halt_trading = true;
+            self.audit(
+                bar,
+                "halt_drawdown",
+                None,
+                json!({
+                    "drawdown_pct": drawdown,
+                    "max_drawdown_pct": self.risk_limits.max_drawdown_pct,
+                }),
+            );
+        }
+    }
+
+    fn reject(&mut self, bar: &Bar, side: Side, quantity: f64, reason: &str) {
+        metrics::counter!("kairos.live.order_rejects_total").increment(1);
+        self.audit(
+            bar,
+            "order_rejected",
+            Some(reason.to_string()),
+            json!({
+                "venue": self.venue.name(),
+                "side": side,
+                "quantity": quantity,
+                "bar_close": bar.close,
+            }),
+        );
+    }
+
+    fn audit(
+        &mut self,
+        bar: &Bar,
+        action: &str,
+        error: Option<String>,
+        details: serde_json::Value,
+    ) {
+        self.audit_events.push(AuditEvent {
+            run_id: self.run_id.clone(),
+            timestamp: bar.timestamp,
+            stage: "live".to_string(),
+            symbol: Some(self.symbol.clone()),
+            action: action.to_string(),
+            error,
+            details,
+        });
+    }
+}
@@ -0,0 +1,133 @@
+use crate::config::Config;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_REPLAY_SCALE: u64 = 60;
+
+// Longest single wait, so a speed change applies to the bar being waited on.
+const MAX_WAIT_SLICE: Duration = Duration::from_millis(200);
+
+// Time source for paper replay pacing. `now` is monotonic from an arbitrary origin.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Duration;
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock {
+    origin: Instant,
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+// Virtual time: `sleep` advances it instantly, so tests fast-forward a paced replay without
+// waiting and can read back how long it would have taken.
+#[derive(Default)]
+pub struct SimulatedClock {
+    now_ns: AtomicU64,
+}
+
+impl SimulatedClock {
+    pub fn advance(&self, duration: Duration) {
+        self.now_ns
+            .fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+    }
+}
+
+impl Clock for SimulatedClock {
+    fn now(&self) -> Duration {
+        Duration::from_nanos(self.now_ns.load(Ordering::Relaxed))
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
+
+// Replay speed as a multiple of real time (`60` = one minute of bars per second); `0` replays as
+// fast as possible. Clones share the value, so a UI can change it while the run is going.
+#[derive(Clone)]
+pub struct ReplaySpeed(Arc<AtomicU64>);
+
+impl ReplaySpeed {
+    pub fn new(scale: f64) -> Self {
+        let speed = Self(Arc::new(AtomicU64::new(0)));
+        speed.set_scale(scale);
+        speed
+    }
+
+    pub fn scale(&self) -> f64 {
+        f64::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    pub fn set_scale(&self, scale: f64) {
+        let scale = if scale.is_finite() {
+            scale.max(0.0)
+        } else {
+            0.0
+        };
+        self.0.store(scale.to_bits(), Ordering::Relaxed);
+    }
+}
+
+#[derive(Clone)]
+pub struct ReplayPacing {
+    pub clock: Arc<dyn Clock>,
+    pub speed: ReplaySpeed,
+}
+
+impl ReplayPacing {
+    // Wall-clock pacing at `paper.replay_scale` (default 60).
+    pub fn from_config(config: &Config) -> Self {
+        let scale = config
+            .paper
+            .as_ref()
+            .and_then(|paper| paper.replay_scale)
+            .unwrap_or(DEFAULT_REPLAY_SCALE);
+        Self {
+            clock: Arc::new(SystemClock::default()),
+            speed: ReplaySpeed::new(scale as f64),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    // Blocks until a bar of `bar_seconds` has played out since `last` at the current speed, and
+    // returns the new reference instant.
+    pub(crate) fn wait_for_bar(&self, last: Option<Duration>, bar_seconds: i64) -> Duration {
+        let last = last.unwrap_or_else(|| self.clock.now());
+        loop {
+            let scale = self.speed.scale();
+            if scale <= 0.0 || bar_seconds <= 0 {
+                break;
+            }
+            let target = Duration::from_secs_f64(bar_seconds as f64 / scale);
+            let elapsed = self.clock.now().saturating_sub(last);
+            if elapsed >= target {
+                break;
+            }
+            self.clock.sleep((target - elapsed).min(MAX_WAIT_SLICE));
+        }
+        self.clock.now()
+    }
+}
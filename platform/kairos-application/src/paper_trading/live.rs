@@ -0,0 +1,102 @@
+use kairos_domain::repositories::artifacts::ArtifactWriter;
+use kairos_domain::services::engine::backtest::BarProgress;
+use kairos_domain::value_objects::side::Side;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+
+pub const LIVE_JOURNAL_FILE: &str = "live.jsonl";
+
+// Appends fills and equity points to `<run_dir>/live.jsonl` as bars close, so dashboards can tail
+// the run and a crash keeps everything up to the last bar. Restarts append a new `start` record
+// instead of truncating previous history.
+pub struct LiveJournal<'a> {
+    artifacts: &'a dyn ArtifactWriter,
+    path: PathBuf,
+    run_id: String,
+    failed: bool,
+    finished: bool,
+}
+
+impl<'a> LiveJournal<'a> {
+    pub fn open(
+        artifacts: &'a dyn ArtifactWriter,
+        run_dir: &Path,
+        run_id: &str,
+        symbol: &str,
+        timeframe: &str,
+        mode: &str,
+    ) -> Result<Self, String> {
+        artifacts.ensure_dir(run_dir)?;
+        let path = run_dir.join(LIVE_JOURNAL_FILE);
+        artifacts.append_jsonl(
+            &path,
+            &[json!({
+                "type": "start",
+                "run_id": run_id,
+                "symbol": symbol,
+                "timeframe": timeframe,
+                "mode": mode,
+                "started_at": chrono::Utc::now().to_rfc3339(),
+            })],
+        )?;
+        Ok(Self {
+            artifacts,
+            path,
+            run_id: run_id.to_string(),
+            failed: false,
+            finished: false,
+        })
+    }
+
+    pub fn record(&mut self, progress: &BarProgress) {
+        let mut records = Vec::with_capacity(progress.trades_in_bar.len() + 1);
+        for fill in &progress.trades_in_bar {
+            let side = match fill.side {
+                Side::Buy => "BUY",
+                Side::Sell => "SELL",
+            };
+            records.push(json!({
+                "type": "fill",
+                "timestamp_utc": fill.timestamp,
+                "side": side,
+                "qty": fill.quantity,
+                "price": fill.price,
+                "fee": fill.fee,
+                "slippage": fill.slippage,
+            }));
+        }
+        records.push(json!({
+            "type": "equity",
+            "timestamp_utc": progress.timestamp,
+            "bar_index": progress.bar_index,
+            "close": progress.close,
+            "equity": progress.equity,
+            "cash": progress.cash,
+            "position_qty": progress.position_qty,
+            "trading_halted": progress.trading_halted,
+        }));
+        self.append(&records);
+    }
+
+    // Only the first call is recorded.
+    pub fn finish(&mut self, status: &str) {
+        if self.finished {
+            return;
+        }
+        self.finished = true;
+        self.append(&[json!({ "type": "end", "status": status })]);
+    }
+
+    fn append(&mut self, records: &[serde_json::Value]) {
+        match self.artifacts.append_jsonl(&self.path, records) {
+            Ok(()) => self.failed = false,
+            Err(err) => {
+                // Warn once per failure streak; a stuck disk must not flood the log every bar.
+                if !self.failed {
+                    tracing::warn!(run_id = %self.run_id, error = %err, "live journal append failed");
+                }
+                self.failed = true;
+            }
+        }
+    }
+}
@@ -1,35 +1,52 @@
-use crate::config::{AgentMode, Config};
+use crate::config::{build_ohlcv_query, AgentMode, Config, ConfigError};
+use crate::error::RunError;
+use crate::paper_trading::clock::ReplayPacing;
+use crate::paper_trading::live::LiveJournal;
+use crate::paper_trading::session::{PaperSession, SessionCheckpoints};
+use crate::reproducibility::{build_reproducibility, fingerprint_bars, DataFingerprinter};
 use crate::shared::{
-    build_metrics_config, config_snapshot_json, normalize_timeframe_label, parse_duration_like,
-    resolve_execution_config, resolve_sentiment_missing_policy, resolve_size_mode,
-    resolve_sma_windows, summary_meta_json_from_equity,
+    build_feature_config, build_metrics_config, build_sentiment_inputs, config_snapshot_json,
+    ensure_not_cancelled, finalize_run_dir, load_ohlcv_with_control, load_sentiment,
+    normalize_timeframe_label, parse_duration_like, resolve_artifact_compression,
+    resolve_execution_config, resolve_size_mode, resolve_sma_windows, sentiment_sources_json,
+    summary_meta_json_from_equity,
 };
 use kairos_domain::entities::risk::RiskLimits;
+use kairos_domain::entities::run_summary::DataFingerprint;
 use kairos_domain::repositories::agent::AgentClient as AgentPort;
-use kairos_domain::repositories::artifacts::ArtifactWriter;
-use kairos_domain::repositories::market_data::{MarketDataRepository, OhlcvQuery};
-use kairos_domain::repositories::market_stream::MarketStream;
-use kairos_domain::repositories::sentiment::{
-    SentimentFormat, SentimentQuery, SentimentRepository,
+use kairos_domain::repositories::artifacts::{ArtifactCompression, ArtifactWriter};
+use kairos_domain::repositories::market_data::MarketDataRepository;
+use kairos_domain::repositories::market_stream::{
+    MarketEvent, MarketStream, MultiMarketStream, StreamError,
 };
+use kairos_domain::repositories::sentiment::SentimentRepository;
 use kairos_domain::services::analyzers::{built_in_analyzers, AnalyzerInput};
 use kairos_domain::services::audit::AuditEvent;
 use kairos_domain::services::engine::backtest::{
-    BacktestResults, BacktestRunError, BacktestRunner, BarProgress, NoopControl, RunControl,
+    BacktestResults, BacktestRunner, BarProgress, NoopControl, RunControl,
 };
+use kairos_domain::services::engine::schedule::TradingSchedule;
 use kairos_domain::services::features;
 use kairos_domain::services::market_data_source::MarketDataSource;
 use kairos_domain::services::ohlcv::{data_quality_from_bars, resample_bars};
+use kairos_domain::services::progress::{stage, ProgressEvent, ProgressSink};
 use kairos_domain::services::realtime_bar::BarAggregator;
-use kairos_domain::services::sentiment;
+use kairos_domain::services::sentiment::{self, SentimentAligner};
+use kairos_domain::services::strategy::hot_swap::HotSwapStrategy;
 use kairos_domain::services::strategy::{
-    AgentStrategy, BuyAndHold, HoldStrategy, SimpleSma, StrategyKind,
+    AgentBudget, AgentStrategy, BuyAndHold, HoldStrategy, SimpleSma, StrategyKind,
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::{Duration, Instant};
 use tracing::info_span;
 
+pub mod clock;
+pub mod live;
+pub mod multi_symbol;
+pub mod notifications;
+pub mod session;
+
 #[derive(Debug, Clone)]
 pub struct RealtimeStreamStatus {
     pub connected: bool,
@@ -38,6 +55,17 @@ pub struct RealtimeStreamStatus {
     pub last_event_timestamp: Option<i64>,
     pub out_of_order_events: u64,
     pub invalid_events: u64,
+    // One entry per subscribed symbol; the fields above aggregate them.
+    pub symbols: Vec<SymbolStreamStatus>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SymbolStreamStatus {
+    pub symbol: String,
+    pub last_event_timestamp: Option<i64>,
+    pub last_bar_timestamp: Option<i64>,
+    pub out_of_order_events: u64,
+    pub invalid_events: u64,
 }
 
 pub fn run_paper(
@@ -48,7 +76,7 @@ pub fn run_paper(
     sentiment_repo: &dyn SentimentRepository,
     artifacts: &dyn ArtifactWriter,
     remote_agent: Option<Box<dyn AgentPort>>,
-) -> Result<PathBuf, String> {
+) -> Result<PathBuf, RunError> {
     run_paper_streaming(
         config,
         config_toml,
@@ -57,7 +85,7 @@ pub fn run_paper(
         sentiment_repo,
         artifacts,
         remote_agent,
-        &mut |_progress: BarProgress| {},
+        &mut |_event: ProgressEvent<'_>| {},
     )
 }
 
@@ -70,8 +98,8 @@ pub fn run_paper_streaming(
     sentiment_repo: &dyn SentimentRepository,
     artifacts: &dyn ArtifactWriter,
     remote_agent: Option<Box<dyn AgentPort>>,
-    progress: &mut dyn FnMut(BarProgress),
-) -> Result<PathBuf, String> {
+    progress: &mut dyn ProgressSink,
+) -> Result<PathBuf, RunError> {
     let control = NoopControl;
     run_paper_streaming_control(
         config,
@@ -81,6 +109,8 @@ pub fn run_paper_streaming(
         sentiment_repo,
         artifacts,
         remote_agent,
+        None,
+        ReplayPacing::from_config(config),
         &control,
         progress,
     )
@@ -95,9 +125,43 @@ pub fn run_paper_streaming_control(
     sentiment_repo: &dyn SentimentRepository,
     artifacts: &dyn ArtifactWriter,
     remote_agent: Option<Box<dyn AgentPort>>,
+    resume: Option<PaperSession>,
+    pacing: ReplayPacing,
     control: &dyn RunControl,
-    progress: &mut dyn FnMut(BarProgress),
-) -> Result<PathBuf, String> {
+    progress: &mut dyn ProgressSink,
+) -> Result<PathBuf, RunError> {
+    run_paper_seeded(
+        config,
+        config_toml,
+        out,
+        market_data,
+        sentiment_repo,
+        artifacts,
+        remote_agent,
+        resume,
+        pacing,
+        control,
+        progress,
+        None,
+    )
+}
+
+// `seed` is recorded in summary.json when a seeded search picked this run (sweep trials).
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_paper_seeded(
+    config: &Config,
+    config_toml: &str,
+    out: Option<PathBuf>,
+    market_data: &dyn MarketDataRepository,
+    sentiment_repo: &dyn SentimentRepository,
+    artifacts: &dyn ArtifactWriter,
+    remote_agent: Option<Box<dyn AgentPort>>,
+    resume: Option<PaperSession>,
+    pacing: ReplayPacing,
+    control: &dyn RunControl,
+    progress: &mut dyn ProgressSink,
+    seed: Option<u64>,
+) -> Result<PathBuf, RunError> {
     let _span = info_span!(
         "run_paper",
         run_id = %config.run.run_id,
@@ -108,39 +172,44 @@ pub fn run_paper_streaming_control(
 
     let mut audit_extras: Vec<AuditEvent> = Vec::new();
 
-    let expected_step = parse_duration_like(&config.run.timeframe)?;
-    let timeframe_label = normalize_timeframe_label(&config.run.timeframe)?;
+    let expected_step = parse_duration_like(&config.run.timeframe).map_err(ConfigError::Invalid)?;
+    let timeframe_label =
+        normalize_timeframe_label(&config.run.timeframe).map_err(ConfigError::Invalid)?;
     let source_timeframe_label = normalize_timeframe_label(
         config
             .db
             .source_timeframe
             .as_deref()
             .unwrap_or(&timeframe_label),
-    )?;
-    let source_step = parse_duration_like(&source_timeframe_label)?;
+    )
+    .map_err(ConfigError::Invalid)?;
+    let source_step = parse_duration_like(&source_timeframe_label).map_err(ConfigError::Invalid)?;
 
+    let query = build_ohlcv_query(config, &source_timeframe_label, source_step)?;
     let stage_start = Instant::now();
-    let (source_bars, source_report) = market_data.load_ohlcv(&OhlcvQuery {
-        exchange: config.db.exchange.to_lowercase(),
-        market: config.db.market.to_lowercase(),
-        symbol: config.run.symbol.clone(),
-        timeframe: source_timeframe_label.clone(),
-        expected_step_seconds: Some(source_step),
+    let (source_bars, source_report) = stage(progress, "load_ohlcv", || {
+        load_ohlcv_with_control(market_data, &query, control, "paper run")
     })?;
     metrics::histogram!("kairos.paper.load_ohlcv_ms")
         .record(stage_start.elapsed().as_millis() as f64);
 
-    let (bars, data_report, resampled) = if source_timeframe_label != timeframe_label {
+    let (mut bars, data_report, resampled) = if source_timeframe_label != timeframe_label {
         if source_step > expected_step {
-            return Err(format!(
+            return Err(ConfigError::Invalid(format!(
                 "cannot resample OHLCV: source timeframe ({}) is larger than run timeframe ({})",
                 source_timeframe_label, timeframe_label
-            ));
+            ))
+            .into());
         }
 
         let resample_start = Instant::now();
-        let resampled_bars = resample_bars(&source_bars, expected_step)?;
-        let report = data_quality_from_bars(&resampled_bars, Some(expected_step));
+        let resampled_bars = stage(progress, "resample_ohlcv", || {
+            resample_bars(&source_bars, expected_step)
+        })?;
+        ensure_not_cancelled(control, "paper run")?;
+        let mut report = data_quality_from_bars(&resampled_bars, Some(expected_step));
+        report.turnover_mismatches = source_report.turnover_mismatches;
+        report.first_turnover_mismatch = source_report.first_turnover_mismatch;
         metrics::histogram!("kairos.paper.resample_ms")
             .record(resample_start.elapsed().as_millis() as f64);
         audit_extras.push(timing_event(
@@ -175,64 +244,60 @@ pub fn run_paper_streaming_control(
             "gaps": data_report.gaps,
             "out_of_order": data_report.out_of_order,
             "invalid_close": data_report.invalid_close,
+            "zero_volume_moves": data_report.zero_volume_moves,
+            "turnover_mismatches": data_report.turnover_mismatches,
             "resampled": resampled,
         }),
     ));
 
-    let sentiment_points = if let Some(path) = &config.paths.sentiment_path {
-        let stage_start = Instant::now();
-        let path_buf = PathBuf::from(path);
-        let ext = path_buf
-            .extension()
-            .and_then(|s| s.to_str())
-            .unwrap_or("")
-            .to_lowercase();
-        let format = if ext == "json" {
-            SentimentFormat::Json
-        } else {
-            SentimentFormat::Csv
-        };
-        let missing_policy = resolve_sentiment_missing_policy(config);
-        let (points, report) = sentiment_repo.load_sentiment(&SentimentQuery {
-            path: path_buf,
-            format,
-            missing_policy,
-        })?;
-        metrics::histogram!("kairos.paper.load_sentiment_ms")
-            .record(stage_start.elapsed().as_millis() as f64);
+    let sentiment_points =
+        if let Some(inputs) = build_sentiment_inputs(config).map_err(ConfigError::Invalid)? {
+            let stage_start = Instant::now();
+            let loaded = stage(progress, "load_sentiment", || {
+                load_sentiment(config, &inputs, sentiment_repo)
+            })?;
+            let (points, report) = (loaded.points, loaded.report);
+            metrics::histogram!("kairos.paper.load_sentiment_ms")
+                .record(stage_start.elapsed().as_millis() as f64);
 
-        audit_extras.push(timing_event(
-            &config.run.run_id,
-            0,
-            "timing",
-            Some(&config.run.symbol),
-            "load_sentiment",
-            stage_start.elapsed().as_millis() as u64,
-            serde_json::json!({
-                "rows": points.len(),
-                "duplicates": report.duplicates,
-                "out_of_order": report.out_of_order,
-                "missing_values": report.missing_values,
-                "invalid_values": report.invalid_values,
-                "dropped_rows": report.dropped_rows,
-                "schema": report.schema,
-            }),
-        ));
+            audit_extras.push(timing_event(
+                &config.run.run_id,
+                0,
+                "timing",
+                Some(&config.run.symbol),
+                "load_sentiment",
+                stage_start.elapsed().as_millis() as u64,
+                serde_json::json!({
+                    "rows": points.len(),
+                    "duplicates": report.duplicates,
+                    "out_of_order": report.out_of_order,
+                    "missing_values": report.missing_values,
+                    "invalid_values": report.invalid_values,
+                    "dropped_rows": report.dropped_rows,
+                    "schema": report.schema,
+                    "sources": sentiment_sources_json(&loaded.sources),
+                }),
+            ));
 
-        Some(points)
-    } else {
-        None
-    };
+            Some(points)
+        } else {
+            None
+        };
+    ensure_not_cancelled(control, "paper run")?;
 
-    let sentiment_lag = parse_duration_like(&config.features.sentiment_lag)?;
+    let sentiment_lag =
+        parse_duration_like(&config.features.sentiment_lag).map_err(ConfigError::Invalid)?;
     let bar_timestamps: Vec<i64> = bars.iter().map(|bar| bar.timestamp).collect();
     let stage_start = Instant::now();
-    let aligned_sentiment = sentiment_points
-        .as_ref()
-        .map(|points| sentiment::align_with_bars(&bar_timestamps, points, sentiment_lag))
-        .unwrap_or_else(|| vec![None; bars.len()]);
+    let mut aligned_sentiment = stage(progress, "align_sentiment", || {
+        sentiment_points
+            .as_ref()
+            .map(|points| sentiment::align_with_bars(&bar_timestamps, points, sentiment_lag))
+            .unwrap_or_else(|| vec![None; bars.len()])
+    });
     metrics::histogram!("kairos.paper.align_sentiment_ms")
         .record(stage_start.elapsed().as_millis() as f64);
+    ensure_not_cancelled(control, "paper run")?;
     audit_extras.push(timing_event(
         &config.run.run_id,
         0,
@@ -245,23 +310,16 @@ pub fn run_paper_streaming_control(
         }),
     ));
 
-    let feature_config = features::FeatureConfig {
-        return_mode: config.features.return_mode,
-        sma_windows: config
-            .features
-            .sma_windows
-            .iter()
-            .map(|w| *w as usize)
-            .collect(),
-        volatility_windows: config
-            .features
-            .volatility_windows
-            .as_ref()
-            .map(|windows| windows.iter().map(|w| *w as usize).collect())
-            .unwrap_or_default(),
-        rsi_enabled: config.features.rsi_enabled,
-    };
-    let builder = features::FeatureBuilder::new(feature_config);
+    // A resumed session continues after the last bar it processed; strategies warm up again from
+    // there, with sentiment still lined up by bar.
+    let resume_at = resume
+        .as_ref()
+        .and_then(|session| session.engine.last_timestamp())
+        .map(|last| bars.partition_point(|bar| bar.timestamp <= last))
+        .unwrap_or(0);
+    aligned_sentiment.drain(..resume_at.min(aligned_sentiment.len()));
+
+    let builder = features::FeatureBuilder::new(build_feature_config(config));
 
     let risk_limits = RiskLimits {
         max_position_qty: config.risk.max_position_qty,
@@ -274,22 +332,11 @@ pub fn run_paper_streaming_control(
     let strategy = match config.agent.mode {
         AgentMode::Remote => {
             let Some(agent) = remote_agent else {
-                return Err("agent.mode=remote requires a remote_agent client".to_string());
+                return Err(RunError::Runtime(
+                    "agent.mode=remote requires a remote_agent client".to_string(),
+                ));
             };
-            let fallback_action = config.agent.fallback_action;
-            let agent_url = config.agent.url.clone();
-            StrategyKind::Agent(AgentStrategy::new(
-                config.run.run_id.clone(),
-                config.run.symbol.clone(),
-                config.run.timeframe.clone(),
-                config.agent.api_version.clone(),
-                config.agent.feature_version.clone(),
-                agent_url,
-                fallback_action,
-                agent,
-                builder,
-                aligned_sentiment,
-            ))
+            StrategyKind::Agent(agent_strategy(config, agent, builder, aligned_sentiment))
         }
         AgentMode::Baseline => {
             let baseline = config
@@ -307,17 +354,34 @@ pub fn run_paper_streaming_control(
         }
         AgentMode::Hold => StrategyKind::Hold(HoldStrategy),
     };
+    let strategy = HotSwapStrategy::new(
+        config.run.run_id.clone(),
+        strategy,
+        control.strategy_changes().unwrap_or_default(),
+    );
 
     let metrics_config = build_metrics_config(config);
-    let execution = resolve_execution_config(config)?;
+    let execution = resolve_execution_config(config).map_err(ConfigError::Invalid)?;
+    let compression = resolve_artifact_compression(config).map_err(ConfigError::Invalid)?;
+    let schedule = resolve_trading_schedule(config)?;
 
-    let timeframe_seconds = parse_duration_like(&config.run.timeframe)?;
-    let replay_scale = config
-        .paper
-        .as_ref()
-        .and_then(|paper| paper.replay_scale)
-        .unwrap_or(60);
-    let data = RealtimeBarSource::new(bars, timeframe_seconds, replay_scale);
+    let timeframe_seconds = expected_step;
+    let data_fingerprint = fingerprint_bars(&bars);
+    let data = RealtimeBarSource::new(bars.split_off(resume_at), timeframe_seconds, pacing);
+    let run_dir = resolve_run_dir(config, out);
+    let mut checkpoints = SessionCheckpoints::new(artifacts, &run_dir, config, "replay");
+    if let Some(session) = &resume {
+        checkpoints.resume(session).map_err(RunError::Runtime)?;
+    }
+    let mut live = LiveJournal::open(
+        artifacts,
+        &run_dir,
+        &config.run.run_id,
+        &config.run.symbol,
+        &config.run.timeframe,
+        "replay",
+    )
+    .map_err(RunError::Artifacts)?;
     let stage_start = Instant::now();
     let mut runner = BacktestRunner::new_with_execution(
         config.run.run_id.clone(),
@@ -331,11 +395,33 @@ pub fn run_paper_streaming_control(
         size_mode,
         execution.clone(),
     );
-    let results = runner
-        .run_with_progress_control(progress, control)
-        .map_err(|err| match err {
-            BacktestRunError::Cancelled => "paper run cancelled".to_string(),
-        })?;
+    if let Some(schedule) = schedule {
+        runner = runner.with_schedule(schedule);
+    }
+    if let Some(session) = resume {
+        runner.restore(session.engine, session.equity, session.trades);
+    }
+    let every_bars = checkpoints.every_bars();
+    progress.emit(ProgressEvent::StageStarted {
+        stage: "run_engine",
+    });
+    let results = runner.run_with_checkpoints(
+        &mut |bar_progress: BarProgress| {
+            live.record(&bar_progress);
+            progress.emit(ProgressEvent::BarProcessed(&bar_progress));
+        },
+        control,
+        every_bars,
+        &mut |snapshot, history, _data| checkpoints.save(snapshot, history, None),
+    );
+    progress.emit(ProgressEvent::StageFinished {
+        stage: "run_engine",
+        duration_ms: stage_start.elapsed().as_millis() as u64,
+    });
+    let results = results.map_err(|err| {
+        live.finish("cancelled");
+        RunError::engine("paper run")(err)
+    })?;
     let engine_ms = stage_start.elapsed().as_millis() as f64;
     metrics::histogram!("kairos.paper.engine_ms").record(engine_ms);
     metrics::gauge!("kairos.paper.bars_processed").set(results.summary.bars_processed as f64);
@@ -350,15 +436,26 @@ pub fn run_paper_streaming_control(
         serde_json::json!({}),
     ));
 
-    let run_dir = write_outputs(
-        config,
-        config_toml,
-        out,
-        results,
-        &execution,
-        artifacts,
-        audit_extras,
-    )?;
+    let written = stage(progress, "write_outputs", || {
+        write_outputs(
+            config,
+            config_toml,
+            &run_dir,
+            results,
+            &execution,
+            compression,
+            artifacts,
+            audit_extras,
+            data_fingerprint,
+            seed,
+            &mut live,
+            Some(&mut checkpoints),
+        )
+    });
+    if written.is_err() {
+        live.finish("error");
+    }
+    written.map_err(RunError::Artifacts)?;
 
     Ok(run_dir)
 }
@@ -371,11 +468,12 @@ pub fn run_paper_realtime_streaming_control(
     connect_stream: &mut dyn FnMut() -> Result<Box<dyn MarketStream>, String>,
     sentiment_repo: &dyn SentimentRepository,
     artifacts: &dyn ArtifactWriter,
-    _remote_agent: Option<Box<dyn AgentPort>>,
+    remote_agent: Option<Box<dyn AgentPort>>,
+    resume: Option<PaperSession>,
     control: &dyn RunControl,
-    progress: &mut dyn FnMut(BarProgress),
+    progress: &mut dyn ProgressSink,
     on_status: &mut dyn FnMut(RealtimeStreamStatus),
-) -> Result<PathBuf, String> {
+) -> Result<PathBuf, RunError> {
     let _span = info_span!(
         "run_paper_realtime",
         run_id = %config.run.run_id,
@@ -384,149 +482,145 @@ pub fn run_paper_realtime_streaming_control(
     )
     .entered();
 
-    if matches!(config.agent.mode, AgentMode::Remote) {
-        return Err(
-            "paper realtime mode does not support agent.mode=remote yet (requires online feature pipeline)"
-                .to_string(),
-        );
-    }
-
-    // Optional: we still validate/load sentiment to keep operator feedback consistent, but baseline
-    // strategies do not consume it. Remote agent mode is blocked above.
-    let _sentiment_points = if let Some(path) = &config.paths.sentiment_path {
-        let path_buf = PathBuf::from(path);
-        let ext = path_buf
-            .extension()
-            .and_then(|s| s.to_str())
-            .unwrap_or("")
-            .to_lowercase();
-        let format = if ext == "json" {
-            SentimentFormat::Json
-        } else {
-            SentimentFormat::Csv
-        };
-        let missing_policy = resolve_sentiment_missing_policy(config);
-        let (_points, _report) = sentiment_repo.load_sentiment(&SentimentQuery {
-            path: path_buf,
-            format,
-            missing_policy,
-        })?;
-        true
-    } else {
-        false
+    let strategy = HotSwapStrategy::new(
+        config.run.run_id.clone(),
+        realtime_strategy(config, sentiment_repo, remote_agent)?,
+        control.strategy_changes().unwrap_or_default(),
+    );
+    let risk_limits = RiskLimits {
+        max_position_qty: config.risk.max_position_qty,
+        max_drawdown_pct: config.risk.max_drawdown_pct,
+        max_exposure_pct: config.risk.max_exposure_pct,
     };
+    let size_mode = resolve_size_mode(config);
+    let metrics_config = build_metrics_config(config);
+    let execution = resolve_execution_config(config).map_err(ConfigError::Invalid)?;
+    let compression = resolve_artifact_compression(config).map_err(ConfigError::Invalid)?;
+    let schedule = resolve_trading_schedule(config)?;
 
-    let timeframe_seconds = parse_duration_like(&config.run.timeframe)?;
-    let mut aggregator = BarAggregator::new(config.run.symbol.clone(), timeframe_seconds)?;
-
-    let stream = connect_stream()?;
-    on_status(RealtimeStreamStatus {
-        connected: true,
-        reconnects: 0,
-        last_error: None,
-        last_event_timestamp: None,
-        out_of_order_events: 0,
-        invalid_events: 0,
-    });
+    let run_dir = resolve_run_dir(config, out);
+    let mut checkpoints = SessionCheckpoints::new(artifacts, &run_dir, config, "realtime");
+    if let Some(session) = &resume {
+        checkpoints.resume(session).map_err(RunError::Runtime)?;
+    }
 
-    let mut reconnects: u64 = 0;
-    let mut backoff_ms: u64 = 250;
-    let mut last_status_emit = Instant::now();
+    let mut data = StreamBarSource::connect(config, connect_stream, on_status)?;
+    let seed = data.seed();
+    if let Some(aggregator) = resume
+        .as_ref()
+        .and_then(|session| session.aggregator.clone())
+    {
+        data.resume_aggregator(aggregator)?;
+    }
 
-    struct StreamBarSource<'a> {
-        connect: &'a mut dyn FnMut() -> Result<Box<dyn MarketStream>, String>,
-        stream: Box<dyn MarketStream>,
-        aggregator: &'a mut BarAggregator,
-        reconnects: &'a mut u64,
-        backoff_ms: &'a mut u64,
-        last_status_emit: &'a mut Instant,
-        on_status: &'a mut dyn FnMut(RealtimeStreamStatus),
+    let mut live = LiveJournal::open(
+        artifacts,
+        &run_dir,
+        &config.run.run_id,
+        &config.run.symbol,
+        &config.run.timeframe,
+        "realtime",
+    )
+    .map_err(RunError::Artifacts)?;
+    let stage_start = Instant::now();
+    let mut runner = BacktestRunner::new_with_execution(
+        config.run.run_id.clone(),
+        strategy,
+        data,
+        risk_limits,
+        config.run.initial_capital,
+        metrics_config,
+        config.costs.fee_bps,
+        config.run.symbol.clone(),
+        size_mode,
+        execution.clone(),
+    );
+    if let Some(schedule) = schedule {
+        runner = runner.with_schedule(schedule);
     }
 
-    impl MarketDataSource for StreamBarSource<'_> {
-        fn next_bar(&mut self) -> Option<kairos_domain::value_objects::bar::Bar> {
-            loop {
-                match self.stream.next_event() {
-                    Ok(ev) => {
-                        if let Some(bar) = self.aggregator.ingest(ev) {
-                            let report = self.aggregator.report().clone();
-                            (self.on_status)(RealtimeStreamStatus {
-                                connected: true,
-                                reconnects: *self.reconnects,
-                                last_error: None,
-                                last_event_timestamp: report.last_event_timestamp,
-                                out_of_order_events: report.out_of_order_events,
-                                invalid_events: report.invalid_events,
-                            });
-                            return Some(bar);
-                        }
+    if let Some(session) = resume {
+        runner.restore(session.engine, session.equity, session.trades);
+    }
 
-                        // Throttle status updates when we're getting high-frequency ticks.
-                        if self.last_status_emit.elapsed() >= Duration::from_secs(5) {
-                            *self.last_status_emit = Instant::now();
-                            let report = self.aggregator.report().clone();
-                            (self.on_status)(RealtimeStreamStatus {
-                                connected: true,
-                                reconnects: *self.reconnects,
-                                last_error: None,
-                                last_event_timestamp: report.last_event_timestamp,
-                                out_of_order_events: report.out_of_order_events,
-                                invalid_events: report.invalid_events,
-                            });
-                        }
-                    }
-                    Err(err) => {
-                        *self.reconnects = (*self.reconnects).saturating_add(1);
-                        let report = self.aggregator.report().clone();
-                        (self.on_status)(RealtimeStreamStatus {
-                            connected: false,
-                            reconnects: *self.reconnects,
-                            last_error: Some(err.to_string()),
-                            last_event_timestamp: report.last_event_timestamp,
-                            out_of_order_events: report.out_of_order_events,
-                            invalid_events: report.invalid_events,
-                        });
-
-                        let sleep_for = Duration::from_millis((*self.backoff_ms).min(10_000));
-                        thread::sleep(sleep_for);
-                        *self.backoff_ms = (*self.backoff_ms).saturating_mul(2).min(10_000);
-
-                        match (self.connect)() {
-                            Ok(new_stream) => {
-                                self.stream = new_stream;
-                                *self.backoff_ms = 250;
-                                (self.on_status)(RealtimeStreamStatus {
-                                    connected: true,
-                                    reconnects: *self.reconnects,
-                                    last_error: None,
-                                    last_event_timestamp: report.last_event_timestamp,
-                                    out_of_order_events: report.out_of_order_events,
-                                    invalid_events: report.invalid_events,
-                                });
-                            }
-                            Err(connect_err) => {
-                                (self.on_status)(RealtimeStreamStatus {
-                                    connected: false,
-                                    reconnects: *self.reconnects,
-                                    last_error: Some(connect_err),
-                                    last_event_timestamp: report.last_event_timestamp,
-                                    out_of_order_events: report.out_of_order_events,
-                                    invalid_events: report.invalid_events,
-                                });
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    let mut fingerprinter = DataFingerprinter::new();
+    let every_bars = checkpoints.every_bars();
+    progress.emit(ProgressEvent::StageStarted {
+        stage: "run_engine",
+    });
+    let results = runner.run_with_checkpoints(
+        &mut |bar_progress: BarProgress| {
+            fingerprinter.push(bar_progress.timestamp, bar_progress.close);
+            live.record(&bar_progress);
+            progress.emit(ProgressEvent::BarProcessed(&bar_progress));
+        },
+        control,
+        every_bars,
+        &mut |snapshot, history, data: &StreamBarSource| {
+            checkpoints.save(
+                snapshot,
+                history,
+                data.aggregator(&config.run.symbol).cloned(),
+            )
+        },
+    );
+    progress.emit(ProgressEvent::StageFinished {
+        stage: "run_engine",
+        duration_ms: stage_start.elapsed().as_millis() as u64,
+    });
+    let results = results.map_err(|err| {
+        live.finish("cancelled");
+        RunError::engine("paper realtime run")(err)
+    })?;
+
+    let engine_ms = stage_start.elapsed().as_millis() as f64;
+    metrics::histogram!("kairos.paper_realtime.engine_ms").record(engine_ms);
+    metrics::gauge!("kairos.paper_realtime.bars_processed")
+        .set(results.summary.bars_processed as f64);
+    metrics::gauge!("kairos.paper_realtime.trades").set(results.summary.trades as f64);
+
+    // Only write outputs if the run completes (cancelled runs keep just `live.jsonl` and
+    // `session.json`).
+    let written = stage(progress, "write_outputs", || {
+        write_outputs(
+            config,
+            config_toml,
+            &run_dir,
+            results,
+            &execution,
+            compression,
+            artifacts,
+            Vec::new(),
+            fingerprinter.finish(),
+            seed,
+            &mut live,
+            Some(&mut checkpoints),
+        )
+    });
+    if written.is_err() {
+        live.finish("error");
     }
+    written.map_err(RunError::Artifacts)?;
 
-    let risk_limits = RiskLimits {
-        max_position_qty: config.risk.max_position_qty,
-        max_drawdown_pct: config.risk.max_drawdown_pct,
-        max_exposure_pct: config.risk.max_exposure_pct,
-    };
-    let size_mode = resolve_size_mode(config);
+    Ok(run_dir)
+}
+
+// Builds the strategy for stream-driven runs (realtime paper, live). Bars only exist once the
+// aggregator closes them, so sentiment is aligned online (by bar timestamp) and features are built
+// bar by bar by the agent strategy.
+pub(crate) fn realtime_strategy(
+    config: &Config,
+    sentiment_repo: &dyn SentimentRepository,
+    remote_agent: Option<Box<dyn AgentPort>>,
+) -> Result<StrategyKind, RunError> {
+    let sentiment_points =
+        if let Some(inputs) = build_sentiment_inputs(config).map_err(ConfigError::Invalid)? {
+            load_sentiment(config, &inputs, sentiment_repo)?.points
+        } else {
+            Vec::new()
+        };
+    let sentiment_lag =
+        parse_duration_like(&config.features.sentiment_lag).map_err(ConfigError::Invalid)?;
 
     let strategy = match config.agent.mode {
         AgentMode::Baseline => {
@@ -544,60 +638,222 @@ pub fn run_paper_realtime_streaming_control(
             }
         }
         AgentMode::Hold => StrategyKind::Hold(HoldStrategy),
-        AgentMode::Remote => unreachable!("checked above"),
+        AgentMode::Remote => {
+            let Some(agent) = remote_agent else {
+                return Err(RunError::Runtime(
+                    "agent.mode=remote requires a remote_agent client".to_string(),
+                ));
+            };
+            let builder = features::FeatureBuilder::new(build_feature_config(config));
+            StrategyKind::Agent(
+                agent_strategy(config, agent, builder, Vec::new())
+                    .with_online_sentiment(SentimentAligner::new(&sentiment_points, sentiment_lag)),
+            )
+        }
     };
+    Ok(strategy)
+}
 
-    let metrics_config = build_metrics_config(config);
-    let execution = resolve_execution_config(config)?;
-
-    let data = StreamBarSource {
-        connect: connect_stream,
-        stream,
-        aggregator: &mut aggregator,
-        reconnects: &mut reconnects,
-        backoff_ms: &mut backoff_ms,
-        last_status_emit: &mut last_status_emit,
-        on_status,
-    };
+// Turns a tick stream into closed bars, one aggregator per subscribed symbol, reconnecting with
+// exponential backoff when the stream drops. It never runs out of bars; stream-driven runs end
+// through their `RunControl`.
+pub(crate) struct StreamBarSource<'a> {
+    connect: Box<dyn FnMut() -> Result<Box<dyn MultiMarketStream>, String> + 'a>,
+    stream: Box<dyn MultiMarketStream>,
+    aggregators: Vec<BarAggregator>,
+    reconnects: u64,
+    backoff_ms: u64,
+    last_status_emit: Instant,
+    on_status: &'a mut dyn FnMut(RealtimeStreamStatus),
+}
 
-    let stage_start = Instant::now();
-    let mut runner = BacktestRunner::new_with_execution(
-        config.run.run_id.clone(),
-        strategy,
-        data,
-        risk_limits,
-        config.run.initial_capital,
-        metrics_config,
-        config.costs.fee_bps,
-        config.run.symbol.clone(),
-        size_mode,
-        execution.clone(),
-    );
+impl<'a> StreamBarSource<'a> {
+    // Single-symbol source for `run.symbol`.
+    pub(crate) fn connect(
+        config: &Config,
+        connect: &'a mut dyn FnMut() -> Result<Box<dyn MarketStream>, String>,
+        on_status: &'a mut dyn FnMut(RealtimeStreamStatus),
+    ) -> Result<Self, RunError> {
+        let symbol = config.run.symbol.clone();
+        let symbols = vec![symbol.clone()];
+        let connect = move || {
+            connect().map(|stream| {
+                Box::new(SingleSymbolStream {
+                    symbol: symbol.clone(),
+                    stream,
+                }) as Box<dyn MultiMarketStream>
+            })
+        };
+        Self::connect_symbols(config, &symbols, Box::new(connect), on_status)
+    }
 
-    let results = runner
-        .run_with_progress_control(progress, control)
-        .map_err(|err| match err {
-            BacktestRunError::Cancelled => "paper realtime run cancelled".to_string(),
-        })?;
+    pub(crate) fn connect_symbols(
+        config: &Config,
+        symbols: &[String],
+        mut connect: Box<dyn FnMut() -> Result<Box<dyn MultiMarketStream>, String> + 'a>,
+        on_status: &'a mut dyn FnMut(RealtimeStreamStatus),
+    ) -> Result<Self, RunError> {
+        let timeframe_seconds =
+            parse_duration_like(&config.run.timeframe).map_err(ConfigError::Invalid)?;
+        let aggregators = symbols
+            .iter()
+            .map(|symbol| BarAggregator::new(symbol.clone(), timeframe_seconds))
+            .collect::<Result<Vec<_>, _>>()?;
+        let stream = connect().map_err(RunError::Runtime)?;
+        let mut source = Self {
+            connect,
+            stream,
+            aggregators,
+            reconnects: 0,
+            backoff_ms: 250,
+            last_status_emit: Instant::now(),
+            on_status,
+        };
+        source.emit_status(true, None);
+        Ok(source)
+    }
 
-    let engine_ms = stage_start.elapsed().as_millis() as f64;
-    metrics::histogram!("kairos.paper_realtime.engine_ms").record(engine_ms);
-    metrics::gauge!("kairos.paper_realtime.bars_processed")
-        .set(results.summary.bars_processed as f64);
-    metrics::gauge!("kairos.paper_realtime.trades").set(results.summary.trades as f64);
+    pub(crate) fn aggregator(&self, symbol: &str) -> Option<&BarAggregator> {
+        self.aggregators
+            .iter()
+            .find(|aggregator| aggregator.symbol() == symbol)
+    }
 
-    // Only write outputs if the run completes (cancelled runs intentionally do not write artifacts).
-    let run_dir = write_outputs(
-        config,
-        config_toml,
-        out,
-        results,
-        &execution,
-        artifacts,
-        Vec::new(),
-    )?;
+    // Picks up the bar a saved session was building for its symbol.
+    pub(crate) fn resume_aggregator(&mut self, saved: BarAggregator) -> Result<(), RunError> {
+        let Some(aggregator) = self
+            .aggregators
+            .iter_mut()
+            .find(|aggregator| aggregator.symbol() == saved.symbol())
+        else {
+            return Err(RunError::Runtime(format!(
+                "saved bar aggregator is for {}, which this stream does not subscribe to",
+                saved.symbol()
+            )));
+        };
+        Ok(aggregator.resume_from(saved)?)
+    }
 
-    Ok(run_dir)
+    pub(crate) fn seed(&self) -> Option<u64> {
+        self.stream.seed()
+    }
+
+    fn emit_status(&mut self, connected: bool, last_error: Option<String>) {
+        let status = self.status(connected, last_error);
+        (self.on_status)(status);
+    }
+
+    fn status(&self, connected: bool, last_error: Option<String>) -> RealtimeStreamStatus {
+        let symbols: Vec<SymbolStreamStatus> = self
+            .aggregators
+            .iter()
+            .map(|aggregator| {
+                let report = aggregator.report();
+                SymbolStreamStatus {
+                    symbol: aggregator.symbol().to_string(),
+                    last_event_timestamp: report.last_event_timestamp,
+                    last_bar_timestamp: report.last_bar_timestamp,
+                    out_of_order_events: report.out_of_order_events,
+                    invalid_events: report.invalid_events,
+                }
+            })
+            .collect();
+        RealtimeStreamStatus {
+            connected,
+            reconnects: self.reconnects,
+            last_error,
+            last_event_timestamp: symbols.iter().filter_map(|s| s.last_event_timestamp).max(),
+            out_of_order_events: symbols.iter().map(|s| s.out_of_order_events).sum(),
+            invalid_events: symbols.iter().map(|s| s.invalid_events).sum(),
+            symbols,
+        }
+    }
+}
+
+impl MarketDataSource for StreamBarSource<'_> {
+    fn next_bar(&mut self) -> Option<kairos_domain::value_objects::bar::Bar> {
+        loop {
+            match self.stream.next_symbol_event() {
+                Ok((symbol, ev)) => {
+                    let bar = self
+                        .aggregators
+                        .iter_mut()
+                        .find(|aggregator| aggregator.symbol() == symbol)
+                        .and_then(|aggregator| aggregator.ingest(ev));
+                    if let Some(bar) = bar {
+                        self.emit_status(true, None);
+                        return Some(bar);
+                    }
+
+                    // Throttle status updates when we're getting high-frequency ticks.
+                    if self.last_status_emit.elapsed() >= Duration::from_secs(5) {
+                        self.last_status_emit = Instant::now();
+                        self.emit_status(true, None);
+                    }
+                }
+                Err(err) => {
+                    self.reconnects = self.reconnects.saturating_add(1);
+                    self.emit_status(false, Some(err.to_string()));
+
+                    let sleep_for = Duration::from_millis(self.backoff_ms.min(10_000));
+                    thread::sleep(sleep_for);
+                    self.backoff_ms = self.backoff_ms.saturating_mul(2).min(10_000);
+
+                    match (self.connect)() {
+                        Ok(new_stream) => {
+                            self.stream = new_stream;
+                            self.backoff_ms = 250;
+                            self.emit_status(true, None);
+                        }
+                        Err(connect_err) => {
+                            self.emit_status(false, Some(connect_err));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Tags a single-symbol stream's events with that symbol.
+struct SingleSymbolStream {
+    symbol: String,
+    stream: Box<dyn MarketStream>,
+}
+
+impl MultiMarketStream for SingleSymbolStream {
+    fn next_symbol_event(&mut self) -> Result<(String, MarketEvent), StreamError> {
+        let event = self.stream.next_event()?;
+        Ok((self.symbol.clone(), event))
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.stream.seed()
+    }
+}
+
+fn agent_strategy(
+    config: &Config,
+    agent: Box<dyn AgentPort>,
+    builder: features::FeatureBuilder,
+    aligned_sentiment: Vec<Option<sentiment::SentimentPoint>>,
+) -> AgentStrategy {
+    AgentStrategy::new(
+        config.run.run_id.clone(),
+        config.run.symbol.clone(),
+        config.run.timeframe.clone(),
+        config.agent.api_version.clone(),
+        config.agent.feature_version.clone(),
+        config.agent.url.clone(),
+        config.agent.fallback_action,
+        agent,
+        builder,
+        aligned_sentiment,
+    )
+    .with_budget(AgentBudget {
+        max_calls: config.agent.max_calls,
+        max_total_ms: config.agent.max_total_ms,
+    })
 }
 
 fn timing_event(
@@ -623,28 +879,90 @@ fn timing_event(
     }
 }
 
-fn write_outputs(
+// `paper.session_start`/`session_end` (HH:MM UTC) and `paper.max_session_duration`; `None` when
+// the run is not scheduled.
+pub(crate) fn resolve_trading_schedule(
+    config: &Config,
+) -> Result<Option<TradingSchedule>, ConfigError> {
+    let Some(paper) = config.paper.as_ref() else {
+        return Ok(None);
+    };
+    let window = match (&paper.session_start, &paper.session_end) {
+        (Some(start), Some(end)) => Some((
+            TradingSchedule::parse_time_of_day(start)
+                .map_err(|err| ConfigError::Invalid(format!("paper.session_start: {err}")))?,
+            TradingSchedule::parse_time_of_day(end)
+                .map_err(|err| ConfigError::Invalid(format!("paper.session_end: {err}")))?,
+        )),
+        (None, None) => None,
+        _ => {
+            return Err(ConfigError::Invalid(
+                "paper.session_start and paper.session_end must be set together".to_string(),
+            ))
+        }
+    };
+    let max_duration = paper
+        .max_session_duration
+        .as_deref()
+        .map(parse_duration_like)
+        .transpose()
+        .map_err(|err| ConfigError::Invalid(format!("paper.max_session_duration: {err}")))?;
+    if window.is_none() && max_duration.is_none() {
+        return Ok(None);
+    }
+    TradingSchedule::new(
+        window,
+        max_duration,
+        paper.flatten_on_session_end.unwrap_or(true),
+    )
+    .map(Some)
+    .map_err(|err| ConfigError::Invalid(format!("paper.session: {err}")))
+}
+
+pub(crate) fn resolve_run_dir(config: &Config, out: Option<PathBuf>) -> PathBuf {
+    out.unwrap_or_else(|| PathBuf::from(&config.paths.out_dir))
+        .join(&config.run.run_id)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn write_outputs(
     config: &Config,
     config_toml: &str,
-    out: Option<PathBuf>,
+    run_dir: &Path,
     results: BacktestResults,
     execution: &kairos_domain::services::engine::execution::ExecutionConfig,
+    compression: Option<ArtifactCompression>,
     artifacts: &dyn ArtifactWriter,
     mut audit_extras: Vec<AuditEvent>,
-) -> Result<PathBuf, String> {
-    let base_dir = out.unwrap_or_else(|| PathBuf::from(&config.paths.out_dir));
-    let run_dir = base_dir.join(&config.run.run_id);
-    artifacts.ensure_dir(&run_dir)?;
+    data_fingerprint: DataFingerprint,
+    seed: Option<u64>,
+    live: &mut LiveJournal,
+    session: Option<&mut SessionCheckpoints>,
+) -> Result<(), String> {
+    artifacts.ensure_dir(run_dir)?;
 
     artifacts.write_trades_csv(run_dir.join("trades.csv").as_path(), &results.trades)?;
     artifacts.write_equity_csv(run_dir.join("equity.csv").as_path(), &results.equity)?;
+    if config
+        .report
+        .as_ref()
+        .and_then(|report| report.parquet)
+        .unwrap_or(false)
+    {
+        artifacts
+            .write_trades_parquet(run_dir.join("trades.parquet").as_path(), &results.trades)?;
+        artifacts
+            .write_equity_parquet(run_dir.join("equity.parquet").as_path(), &results.equity)?;
+    }
     let meta = summary_meta_json_from_equity(config, &results.equity);
     let config_snapshot = config_snapshot_json(config, execution);
+    let reproducibility = build_reproducibility(config, config_toml, data_fingerprint, seed);
     artifacts.write_summary_json(
         run_dir.join("summary.json").as_path(),
         &results.summary,
         meta.as_ref(),
         Some(&config_snapshot),
+        Some(&reproducibility),
     )?;
 
     let analyzers_dir = run_dir.join("analyzers");
@@ -723,32 +1041,36 @@ fn write_outputs(
 
     artifacts
         .write_config_snapshot_toml(run_dir.join("config_snapshot.toml").as_path(), config_toml)?;
+    // `completed` is recorded once the final artifacts exist (so watchers can switch to them) but
+    // before the manifest, which must cover the finished live.jsonl and session.json.
+    live.finish("completed");
+    if let Some(session) = session {
+        session.complete();
+    }
+    finalize_run_dir(run_dir, compression, artifacts)?;
 
-    Ok(run_dir)
+    Ok(())
 }
 
 struct RealtimeBarSource {
     bars: Vec<kairos_domain::value_objects::bar::Bar>,
     index: usize,
-    sleep_seconds: i64,
-    last_tick: Option<Instant>,
+    bar_seconds: i64,
+    pacing: ReplayPacing,
+    last_tick: Option<Duration>,
 }
 
 impl RealtimeBarSource {
     fn new(
         bars: Vec<kairos_domain::value_objects::bar::Bar>,
-        sleep_seconds: i64,
-        replay_scale: u64,
+        bar_seconds: i64,
+        pacing: ReplayPacing,
     ) -> Self {
-        let scaled = if replay_scale == 0 {
-            0
-        } else {
-            sleep_seconds / replay_scale as i64
-        };
         Self {
             bars,
             index: 0,
-            sleep_seconds: scaled.max(0),
+            bar_seconds,
+            pacing,
             last_tick: None,
         }
     }
@@ -759,19 +1081,7 @@ impl MarketDataSource for RealtimeBarSource {
         if self.index >= self.bars.len() {
             return None;
         }
-        if self.sleep_seconds > 0 {
-            let now = Instant::now();
-            if let Some(last) = self.last_tick {
-                let elapsed = now.saturating_duration_since(last);
-                let target = Duration::from_secs(self.sleep_seconds as u64);
-                if elapsed < target {
-                    thread::sleep(target - elapsed);
-                }
-            } else {
-                thread::sleep(Duration::from_secs(self.sleep_seconds as u64));
-            }
-            self.last_tick = Some(Instant::now());
-        }
+        self.last_tick = Some(self.pacing.wait_for_bar(self.last_tick, self.bar_seconds));
         let bar = self.bars[self.index].clone();
         self.index += 1;
         Some(bar)
@@ -0,0 +1,291 @@
+use crate::config::{Config, ConfigError};
+use crate::error::RunError;
+use crate::paper_trading::live::LiveJournal;
+use crate::paper_trading::session::{load_paper_session_in, PaperSession, SessionCheckpoints};
+use crate::paper_trading::{
+    realtime_strategy, resolve_run_dir, resolve_trading_schedule, write_outputs,
+    RealtimeStreamStatus, StreamBarSource,
+};
+use crate::reproducibility::DataFingerprinter;
+use crate::shared::{
+    build_metrics_config, resolve_artifact_compression, resolve_execution_config, resolve_size_mode,
+};
+use kairos_domain::entities::risk::RiskLimits;
+use kairos_domain::repositories::agent::AgentClient as AgentPort;
+use kairos_domain::repositories::artifacts::{ArtifactReader, ArtifactWriter};
+use kairos_domain::repositories::market_stream::MultiMarketStream;
+use kairos_domain::repositories::sentiment::SentimentRepository;
+use kairos_domain::services::engine::backtest::{BacktestRunner, BarProgress, RunControl};
+use kairos_domain::services::market_data_source::{MarketDataSource, VecBarSource};
+use kairos_domain::services::strategy::StrategyKind;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing::info_span;
+
+// `paper.symbols`, validated; `None` when realtime paper runs on `run.symbol` alone.
+pub fn paper_symbols(config: &Config) -> Result<Option<Vec<String>>, ConfigError> {
+    let Some(symbols) = config
+        .paper
+        .as_ref()
+        .and_then(|paper| paper.symbols.clone())
+    else {
+        return Ok(None);
+    };
+    if symbols.is_empty() {
+        return Err(ConfigError::Invalid(
+            "paper.symbols must list at least one symbol".to_string(),
+        ));
+    }
+    let mut seen = BTreeSet::new();
+    for symbol in &symbols {
+        if symbol.trim().is_empty() {
+            return Err(ConfigError::Invalid(
+                "paper.symbols must not contain empty symbols".to_string(),
+            ));
+        }
+        if !seen.insert(symbol.as_str()) {
+            return Err(ConfigError::Invalid(format!(
+                "paper.symbols lists {symbol} more than once"
+            )));
+        }
+    }
+    Ok(Some(symbols))
+}
+
+// Each symbol runs as its own sleeve under `<run_dir>/<symbol>/`, with an equal share of
+// `run.initial_capital`.
+fn sleeve_config(config: &Config, symbol: &str, capital: f64) -> Config {
+    let mut sleeve = config.clone();
+    sleeve.run.symbol = symbol.to_string();
+    sleeve.run.initial_capital = capital;
+    sleeve
+}
+
+fn sleeve_dir(run_dir: &Path, symbol: &str) -> PathBuf {
+    let name: String = symbol
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    run_dir.join(name)
+}
+
+// The saved session of every sleeve that has one.
+pub fn load_multi_paper_sessions(
+    config: &Config,
+    out: Option<PathBuf>,
+    reader: &dyn ArtifactReader,
+) -> Result<Vec<PaperSession>, String> {
+    let symbols = paper_symbols(config)
+        .map_err(|err| err.to_string())?
+        .ok_or("paper.symbols is not set")?;
+    let run_dir = resolve_run_dir(config, out);
+    let capital = config.run.initial_capital / symbols.len() as f64;
+    let mut sessions = Vec::new();
+    for symbol in &symbols {
+        let sleeve = sleeve_config(config, symbol, capital);
+        if let Some(session) =
+            load_paper_session_in(&sleeve, &sleeve_dir(&run_dir, symbol), reader)?
+        {
+            sessions.push(session);
+        }
+    }
+    Ok(sessions)
+}
+
+struct Sleeve<'a> {
+    config: Config,
+    run_dir: PathBuf,
+    runner: BacktestRunner<StrategyKind, VecBarSource>,
+    live: LiveJournal<'a>,
+    checkpoints: SessionCheckpoints<'a>,
+    fingerprinter: DataFingerprinter,
+}
+
+// Realtime paper trading over every symbol in `paper.symbols`, fed by one multi-symbol stream.
+// Closed bars are routed to their symbol's runner; `progress` gets the symbol with each bar.
+#[allow(clippy::too_many_arguments)]
+pub fn run_paper_realtime_multi_control(
+    config: &Config,
+    config_toml: &str,
+    out: Option<PathBuf>,
+    connect_stream: &mut dyn FnMut() -> Result<Box<dyn MultiMarketStream>, String>,
+    sentiment_repo: &dyn SentimentRepository,
+    artifacts: &dyn ArtifactWriter,
+    remote_agent: &mut dyn FnMut() -> Result<Option<Box<dyn AgentPort>>, String>,
+    resume: Vec<PaperSession>,
+    control: &dyn RunControl,
+    progress: &mut dyn FnMut(&str, BarProgress),
+    on_status: &mut dyn FnMut(RealtimeStreamStatus),
+) -> Result<PathBuf, RunError> {
+    let symbols = paper_symbols(config)?
+        .ok_or_else(|| ConfigError::Invalid("paper.symbols is not set".to_string()))?;
+    let _span = info_span!(
+        "run_paper_realtime_multi",
+        run_id = %config.run.run_id,
+        symbols = %symbols.join(","),
+        timeframe = %config.run.timeframe
+    )
+    .entered();
+
+    let risk_limits = RiskLimits {
+        max_position_qty: config.risk.max_position_qty,
+        max_drawdown_pct: config.risk.max_drawdown_pct,
+        max_exposure_pct: config.risk.max_exposure_pct,
+    };
+    let size_mode = resolve_size_mode(config);
+    let metrics_config = build_metrics_config(config);
+    let execution = resolve_execution_config(config).map_err(ConfigError::Invalid)?;
+    let compression = resolve_artifact_compression(config).map_err(ConfigError::Invalid)?;
+    let run_dir = resolve_run_dir(config, out);
+    let capital = config.run.initial_capital / symbols.len() as f64;
+    let schedule = resolve_trading_schedule(config)?;
+
+    let mut sleeves = Vec::with_capacity(symbols.len());
+    for symbol in &symbols {
+        let sleeve_config = sleeve_config(config, symbol, capital);
+        let sleeve_dir = sleeve_dir(&run_dir, symbol);
+        let agent = remote_agent().map_err(RunError::Runtime)?;
+        let strategy = realtime_strategy(&sleeve_config, sentiment_repo, agent)?;
+        let checkpoints =
+            SessionCheckpoints::new(artifacts, &sleeve_dir, &sleeve_config, "realtime");
+        let live = LiveJournal::open(
+            artifacts,
+            &sleeve_dir,
+            &config.run.run_id,
+            symbol,
+            &config.run.timeframe,
+            "realtime",
+        )
+        .map_err(RunError::Artifacts)?;
+        let mut runner = BacktestRunner::new_with_execution(
+            config.run.run_id.clone(),
+            strategy,
+            VecBarSource::new(Vec::new()),
+            risk_limits,
+            capital,
+            metrics_config,
+            config.costs.fee_bps,
+            symbol.clone(),
+            size_mode,
+            execution.clone(),
+        );
+        if let Some(schedule) = schedule {
+            runner = runner.with_schedule(schedule);
+        }
+        sleeves.push(Sleeve {
+            config: sleeve_config,
+            run_dir: sleeve_dir,
+            runner,
+            live,
+            checkpoints,
+            fingerprinter: DataFingerprinter::new(),
+        });
+    }
+
+    let mut data =
+        StreamBarSource::connect_symbols(config, &symbols, Box::new(connect_stream), on_status)?;
+    for session in resume {
+        let Some(sleeve) = sleeves
+            .iter_mut()
+            .find(|sleeve| sleeve.config.run.symbol == session.symbol)
+        else {
+            return Err(RunError::Runtime(format!(
+                "paper session for {} does not match paper.symbols",
+                session.symbol
+            )));
+        };
+        sleeve
+            .checkpoints
+            .resume(&session)
+            .map_err(RunError::Runtime)?;
+        if let Some(aggregator) = session.aggregator {
+            data.resume_aggregator(aggregator)?;
+        }
+        sleeve
+            .runner
+            .restore(session.engine, session.equity, session.trades);
+    }
+    for sleeve in &mut sleeves {
+        sleeve.runner.start();
+    }
+
+    let stage_start = Instant::now();
+    loop {
+        if control.should_cancel() || !control.wait_if_paused() {
+            for sleeve in &mut sleeves {
+                let aggregator = data.aggregator(&sleeve.config.run.symbol).cloned();
+                sleeve.checkpoints.save(
+                    sleeve.runner.snapshot(),
+                    sleeve.runner.history(),
+                    aggregator,
+                );
+                sleeve.live.finish("cancelled");
+            }
+            return Err(RunError::Cancelled("paper realtime run"));
+        }
+
+        let Some(bar) = data.next_bar() else {
+            break;
+        };
+        let Some(sleeve) = sleeves
+            .iter_mut()
+            .find(|sleeve| sleeve.config.run.symbol == bar.symbol)
+        else {
+            continue;
+        };
+        let bar_progress = sleeve.runner.step(&bar);
+        sleeve.fingerprinter.push(bar.timestamp, bar.close);
+        sleeve.live.record(&bar_progress);
+        let every_bars = sleeve.checkpoints.every_bars();
+        if every_bars > 0 && bar_progress.bar_index.is_multiple_of(every_bars) {
+            sleeve.checkpoints.save(
+                sleeve.runner.snapshot(),
+                sleeve.runner.history(),
+                data.aggregator(&bar.symbol).cloned(),
+            );
+        }
+        progress(&bar.symbol, bar_progress);
+        if sleeves.iter().all(|sleeve| sleeve.runner.session_expired()) {
+            break;
+        }
+    }
+
+    metrics::histogram!("kairos.paper_realtime.engine_ms")
+        .record(stage_start.elapsed().as_millis() as f64);
+    for mut sleeve in sleeves {
+        let aggregator = data.aggregator(&sleeve.config.run.symbol).cloned();
+        sleeve.checkpoints.save(
+            sleeve.runner.snapshot(),
+            sleeve.runner.history(),
+            aggregator,
+        );
+        let results = sleeve.runner.finish();
+        let written = write_outputs(
+            &sleeve.config,
+            config_toml,
+            &sleeve.run_dir,
+            results,
+            &execution,
+            compression,
+            artifacts,
+            Vec::new(),
+            sleeve.fingerprinter.finish(),
+            data.seed(),
+            &mut sleeve.live,
+            Some(&mut sleeve.checkpoints),
+        );
+        if written.is_err() {
+            sleeve.live.finish("error");
+        }
+        written.map_err(RunError::Artifacts)?;
+    }
+
+    Ok(run_dir)
+}
@@ -0,0 +1,321 @@
+use super::RealtimeStreamStatus;
+use crate::error::RunError;
+use chrono::{DateTime, Utc};
+use kairos_domain::repositories::notifier::Notifier;
+use kairos_domain::services::engine::backtest::BarProgress;
+use kairos_domain::value_objects::side::Side;
+use std::cell::Cell;
+use std::path::Path;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+struct DayStats {
+    day: i64,
+    open_equity: f64,
+    close_equity: f64,
+    fills: u64,
+}
+
+// Modes whose bars arrive at market pace, so a message per fill stays readable. Other modes
+// (replay, simulated streams) can fill thousands of times in a few seconds; their fills are only
+// counted in the daily summaries.
+const PER_FILL_MODES: [&str; 2] = ["realtime", "live"];
+
+// Turns paper-trading progress/status callbacks into operator notifications: fills, the first
+// risk halt, stream disconnects/reconnects, a summary per UTC day and the final run outcome.
+// Delivery failures are logged and never interrupt the run.
+pub struct PaperNotifications<'a> {
+    notifier: &'a dyn Notifier,
+    run_id: String,
+    symbol: String,
+    per_fill: bool,
+    halted: bool,
+    connected: bool,
+    failed: Cell<bool>,
+    day: Option<DayStats>,
+}
+
+impl<'a> PaperNotifications<'a> {
+    pub fn new(notifier: &'a dyn Notifier, run_id: &str, symbol: &str) -> Self {
+        Self {
+            notifier,
+            run_id: run_id.to_string(),
+            symbol: symbol.to_string(),
+            per_fill: false,
+            halted: false,
+            connected: true,
+            failed: Cell::new(false),
+            day: None,
+        }
+    }
+
+    pub fn on_start(&mut self, mode: &str) {
+        self.per_fill = PER_FILL_MODES.contains(&mode);
+        self.send(&format!(
+            "[{}] {} paper run started ({mode})",
+            self.run_id, self.symbol
+        ));
+    }
+
+    pub fn on_progress(&mut self, progress: &BarProgress) {
+        let day = progress.timestamp.div_euclid(SECONDS_PER_DAY);
+        if self.day.as_ref().is_some_and(|stats| stats.day != day) {
+            if let Some(stats) = self.day.take() {
+                self.send_day_summary(&stats);
+            }
+        }
+        let stats = self.day.get_or_insert(DayStats {
+            day,
+            open_equity: progress.equity,
+            close_equity: progress.equity,
+            fills: 0,
+        });
+        stats.close_equity = progress.equity;
+        stats.fills += progress.trades_in_bar.len() as u64;
+
+        let fills = if self.per_fill {
+            progress.trades_in_bar.as_slice()
+        } else {
+            &[]
+        };
+        for fill in fills {
+            let side = match fill.side {
+                Side::Buy => "BUY",
+                Side::Sell => "SELL",
+            };
+            self.send(&format!(
+                "[{}] fill {side} {} {} @ {} (fee {}) at {} | equity {:.2}",
+                self.run_id,
+                fill.quantity,
+                self.symbol,
+                fill.price,
+                fill.fee,
+                format_timestamp(fill.timestamp),
+                progress.equity
+            ));
+        }
+
+        if progress.trading_halted && !self.halted {
+            self.halted = true;
+            self.send(&format!(
+                "[{}] RISK HALT: trading halted at {} (equity {:.2}, position {})",
+                self.run_id,
+                format_timestamp(progress.timestamp),
+                progress.equity,
+                progress.position_qty
+            ));
+        }
+    }
+
+    pub fn on_status(&mut self, status: &RealtimeStreamStatus) {
+        if status.connected == self.connected {
+            return;
+        }
+        self.connected = status.connected;
+        let message = if status.connected {
+            format!(
+                "[{}] stream reconnected (reconnects={})",
+                self.run_id, status.reconnects
+            )
+        } else {
+            format!(
+                "[{}] stream DISCONNECTED (reconnects={}): {}",
+                self.run_id,
+                status.reconnects,
+                status.last_error.as_deref().unwrap_or("unknown error")
+            )
+        };
+        self.send(&message);
+    }
+
+    pub fn on_finish(&mut self, result: &Result<impl AsRef<Path>, RunError>) {
+        if let Some(stats) = self.day.take() {
+            self.send_day_summary(&stats);
+        }
+        match result {
+            Ok(run_dir) => self.send(&format!(
+                "[{}] paper run finished: {}",
+                self.run_id,
+                run_dir.as_ref().display()
+            )),
+            Err(err) => self.send(&format!("[{}] paper run FAILED: {err}", self.run_id)),
+        }
+    }
+
+    fn send_day_summary(&self, stats: &DayStats) {
+        let pnl = stats.close_equity - stats.open_equity;
+        let pnl_pct = if stats.open_equity != 0.0 {
+            pnl / stats.open_equity * 100.0
+        } else {
+            0.0
+        };
+        let date = DateTime::<Utc>::from_timestamp(stats.day * SECONDS_PER_DAY, 0)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| stats.day.to_string());
+        self.send(&format!(
+            "[{}] daily summary {date} {}: equity {:.2} -> {:.2} (pnl {:+.2}, {:+.2}%), fills {}",
+            self.run_id,
+            self.symbol,
+            stats.open_equity,
+            stats.close_equity,
+            pnl,
+            pnl_pct,
+            stats.fills
+        ));
+    }
+
+    fn send(&self, message: &str) {
+        let result = self.notifier.notify(message);
+        let result_label = if result.is_ok() { "ok" } else { "err" };
+        metrics::counter!("kairos.paper.notifications_total", "result" => result_label)
+            .increment(1);
+        match result {
+            Ok(()) => self.failed.set(false),
+            Err(err) => {
+                // Warn once per failure streak; a full queue must not flood the log every fill.
+                if !self.failed.replace(true) {
+                    tracing::warn!(run_id = %self.run_id, error = %err, "paper notification failed");
+                }
+            }
+        }
+    }
+}
+
+fn format_timestamp(timestamp: i64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%SZ").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PaperNotifications;
+    use crate::error::RunError;
+    use crate::paper_trading::RealtimeStreamStatus;
+    use kairos_domain::repositories::notifier::Notifier;
+    use kairos_domain::services::engine::backtest::{BarProgress, TradeInBar};
+    use kairos_domain::value_objects::side::Side;
+    use std::cell::RefCell;
+    use std::path::PathBuf;
+
+    #[derive(Default)]
+    struct RecordingNotifier {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl Notifier for RecordingNotifier {
+        fn notify(&self, message: &str) -> Result<(), String> {
+            self.messages.borrow_mut().push(message.to_string());
+            Ok(())
+        }
+    }
+
+    fn progress(timestamp: i64, equity: f64, fills: usize, halted: bool) -> BarProgress {
+        BarProgress {
+            bar_index: 0,
+            timestamp,
+            close: 100.0,
+            equity,
+            cash: equity,
+            position_qty: 0.0,
+            avg_price: 0.0,
+            trades_in_bar: (0..fills)
+                .map(|_| TradeInBar {
+                    timestamp,
+                    side: Side::Buy,
+                    quantity: 1.0,
+                    price: 100.0,
+                    fee: 0.1,
+                    slippage: 0.0,
+                })
+                .collect(),
+            open_orders: Vec::new(),
+            trading_halted: halted,
+        }
+    }
+
+    fn status(connected: bool) -> RealtimeStreamStatus {
+        RealtimeStreamStatus {
+            connected,
+            reconnects: 1,
+            last_error: (!connected).then(|| "socket closed".to_string()),
+            last_event_timestamp: None,
+            out_of_order_events: 0,
+            invalid_events: 0,
+            symbols: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn emits_fills_halt_disconnects_and_daily_summaries() {
+        let notifier = RecordingNotifier::default();
+        let mut notifications = PaperNotifications::new(&notifier, "paper_1", "BTC-USDT");
+        notifications.on_start("realtime");
+
+        notifications.on_progress(&progress(0, 1000.0, 1, false));
+        notifications.on_progress(&progress(60, 1010.0, 0, true));
+        notifications.on_progress(&progress(120, 1005.0, 0, true));
+        notifications.on_status(&status(true));
+        notifications.on_status(&status(false));
+        notifications.on_status(&status(false));
+        notifications.on_status(&status(true));
+        notifications.on_progress(&progress(86_400, 990.0, 0, true));
+        notifications.on_finish(&Ok::<PathBuf, RunError>(PathBuf::from("runs/paper_1")));
+
+        let messages = notifier.messages.borrow();
+        let kinds: Vec<&str> = messages
+            .iter()
+            .map(|m| {
+                if m.contains(" fill BUY") {
+                    "fill"
+                } else if m.contains("RISK HALT") {
+                    "halt"
+                } else if m.contains("DISCONNECTED") {
+                    "disconnected"
+                } else if m.contains("reconnected") {
+                    "reconnected"
+                } else if m.contains("daily summary") {
+                    "daily"
+                } else if m.contains("finished") {
+                    "finished"
+                } else {
+                    "other"
+                }
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "other",
+                "fill",
+                "halt",
+                "disconnected",
+                "reconnected",
+                "daily",
+                "daily",
+                "finished"
+            ]
+        );
+        assert!(messages[5].contains("1970-01-01"));
+        assert!(messages[5].contains("1000.00 -> 1005.00"));
+        assert!(messages[5].contains("fills 1"));
+        assert!(messages[6].contains("1970-01-02"));
+    }
+
+    #[test]
+    fn replay_counts_fills_in_the_daily_summary_only() {
+        let notifier = RecordingNotifier::default();
+        let mut notifications = PaperNotifications::new(&notifier, "paper_1", "BTC-USDT");
+        notifications.on_start("replay");
+
+        for minute in 0..100 {
+            notifications.on_progress(&progress(minute * 60, 1000.0, 2, false));
+        }
+        notifications.on_finish(&Ok::<PathBuf, RunError>(PathBuf::from("runs/paper_1")));
+
+        let messages = notifier.messages.borrow();
+        assert_eq!(messages.len(), 3, "{messages:?}");
+        assert!(messages.iter().all(|m| !m.contains(" fill ")));
+        assert!(messages[1].contains("fills 200"), "{}", messages[1]);
+    }
+}
@@ -0,0 +1,306 @@
+use crate::config::Config;
+use crate::paper_trading::resolve_run_dir;
+use kairos_domain::repositories::artifacts::{ArtifactReader, ArtifactWriter};
+use kairos_domain::services::engine::backtest::{EngineHistory, EngineSnapshot};
+use kairos_domain::services::realtime_bar::BarAggregator;
+use kairos_domain::value_objects::equity_point::EquityPoint;
+use kairos_domain::value_objects::trade::Trade;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+pub const SESSION_FILE: &str = "session.json";
+pub const SESSION_HISTORY_FILE: &str = "session_history.jsonl";
+pub const DEFAULT_CHECKPOINT_EVERY_BARS: u64 = 10;
+
+const SESSION_VERSION: u32 = 2;
+
+// What a paper run saves to `<run_dir>/session.json` so a crash or restart can pick it up with
+// `--resume-session` instead of starting flat.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaperSession {
+    pub version: u32,
+    pub run_id: String,
+    pub symbol: String,
+    pub timeframe: String,
+    // replay | realtime.
+    pub mode: String,
+    // Set once the run has written its final artifacts; there is nothing left to resume.
+    pub completed: bool,
+    pub saved_at: String,
+    pub engine: EngineSnapshot,
+    // Realtime only: the bar being built from ticks when the session was saved.
+    pub aggregator: Option<BarAggregator>,
+    // The equity curve and trades up to `engine`, read from `session_history.jsonl` on load.
+    #[serde(skip)]
+    pub equity: Vec<EquityPoint>,
+    #[serde(skip)]
+    pub trades: Vec<Trade>,
+}
+
+// One line of `session_history.jsonl`. Checkpoints append only the entries recorded since the
+// previous one; `index` is the entry's position, so entries appended after the last saved
+// `session.json` (a crash between the two writes, then a resume) are overwritten on load.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum HistoryRecord {
+    Equity { index: usize, point: EquityPoint },
+    Trade { index: usize, trade: Trade },
+}
+
+// `Ok(None)` when the run dir has no session yet, so a supervisor can always pass
+// `--resume-session`.
+pub fn load_paper_session(
+    config: &Config,
+    out: Option<PathBuf>,
+    reader: &dyn ArtifactReader,
+) -> Result<Option<PaperSession>, String> {
+    load_paper_session_in(config, &resolve_run_dir(config, out), reader)
+}
+
+pub(crate) fn load_paper_session_in(
+    config: &Config,
+    run_dir: &Path,
+    reader: &dyn ArtifactReader,
+) -> Result<Option<PaperSession>, String> {
+    let path = run_dir.join(SESSION_FILE);
+    let Some(value) = reader.read_checkpoint_json(&path)? else {
+        return Ok(None);
+    };
+    let mut session: PaperSession = serde_json::from_value(value)
+        .map_err(|err| format!("invalid paper session {}: {err}", path.display()))?;
+    if session.version != SESSION_VERSION {
+        return Err(format!(
+            "unsupported paper session version {} in {} (expected {SESSION_VERSION})",
+            session.version,
+            path.display()
+        ));
+    }
+    if session.run_id != config.run.run_id
+        || session.symbol != config.run.symbol
+        || session.timeframe != config.run.timeframe
+    {
+        return Err(format!(
+            "paper session {} belongs to run {} ({} {}), not to this config",
+            path.display(),
+            session.run_id,
+            session.symbol,
+            session.timeframe
+        ));
+    }
+    if session.completed {
+        return Err(format!(
+            "paper session {} already completed; set a new run.run_id to start another run",
+            path.display()
+        ));
+    }
+    load_history(&mut session, &run_dir.join(SESSION_HISTORY_FILE), reader)?;
+    Ok(Some(session))
+}
+
+fn load_history(
+    session: &mut PaperSession,
+    path: &Path,
+    reader: &dyn ArtifactReader,
+) -> Result<(), String> {
+    let (equity_points, trade_count) =
+        (session.engine.equity_points(), session.engine.trade_count());
+    let mut equity = Vec::with_capacity(equity_points);
+    let mut trades = Vec::with_capacity(trade_count);
+    if equity_points + trade_count > 0 {
+        let (records, _) = reader.read_jsonl_from(path, 0)?;
+        for value in records {
+            let record: HistoryRecord = serde_json::from_value(value).map_err(|err| {
+                format!("invalid paper session history {}: {err}", path.display())
+            })?;
+            let (index, len) = match &record {
+                HistoryRecord::Equity { index, .. } => (*index, equity.len()),
+                HistoryRecord::Trade { index, .. } => (*index, trades.len()),
+            };
+            if index > len {
+                return Err(format!(
+                    "paper session history {} skips from entry {len} to {index}",
+                    path.display()
+                ));
+            }
+            match record {
+                HistoryRecord::Equity { index, point } => {
+                    equity.truncate(index);
+                    equity.push(point);
+                }
+                HistoryRecord::Trade { index, trade } => {
+                    trades.truncate(index);
+                    trades.push(trade);
+                }
+            }
+        }
+    }
+    if equity.len() < equity_points || trades.len() < trade_count {
+        return Err(format!(
+            "paper session history {} holds {} equity points and {} trades, the session needs {equity_points} and {trade_count}",
+            path.display(),
+            equity.len(),
+            trades.len()
+        ));
+    }
+    equity.truncate(equity_points);
+    trades.truncate(trade_count);
+    session.equity = equity;
+    session.trades = trades;
+    Ok(())
+}
+
+// Rewrites `<run_dir>/session.json` as the engine hands out snapshots, after appending the equity
+// points and trades recorded since the previous checkpoint to `session_history.jsonl`, so each
+// checkpoint costs what happened since the last one. A failed write is logged and retried at the
+// next checkpoint; it never stops the run.
+pub(crate) struct SessionCheckpoints<'a> {
+    artifacts: &'a dyn ArtifactWriter,
+    path: PathBuf,
+    history_path: PathBuf,
+    // History entries already in `session_history.jsonl`.
+    saved_equity: usize,
+    saved_trades: usize,
+    every_bars: u64,
+    last: Option<PaperSession>,
+    run_id: String,
+    symbol: String,
+    timeframe: String,
+    mode: &'static str,
+    failed: bool,
+}
+
+impl<'a> SessionCheckpoints<'a> {
+    pub(crate) fn new(
+        artifacts: &'a dyn ArtifactWriter,
+        run_dir: &Path,
+        config: &Config,
+        mode: &'static str,
+    ) -> Self {
+        Self {
+            artifacts,
+            path: run_dir.join(SESSION_FILE),
+            history_path: run_dir.join(SESSION_HISTORY_FILE),
+            saved_equity: 0,
+            saved_trades: 0,
+            every_bars: config
+                .paper
+                .as_ref()
+                .and_then(|paper| paper.checkpoint_every_bars)
+                .unwrap_or(DEFAULT_CHECKPOINT_EVERY_BARS),
+            last: None,
+            run_id: config.run.run_id.clone(),
+            symbol: config.run.symbol.clone(),
+            timeframe: config.run.timeframe.clone(),
+            mode,
+            failed: false,
+        }
+    }
+
+    pub(crate) fn every_bars(&self) -> u64 {
+        self.every_bars
+    }
+
+    // Continues the saved history instead of appending it again. Fails when the saved session was
+    // written by the other paper mode.
+    pub(crate) fn resume(&mut self, session: &PaperSession) -> Result<(), String> {
+        if session.mode != self.mode {
+            return Err(format!(
+                "paper session {} was saved by a {} run and cannot resume a {} run",
+                self.path.display(),
+                session.mode,
+                self.mode
+            ));
+        }
+        self.saved_equity = session.engine.equity_points();
+        self.saved_trades = session.engine.trade_count();
+        Ok(())
+    }
+
+    pub(crate) fn save(
+        &mut self,
+        engine: EngineSnapshot,
+        history: EngineHistory<'_>,
+        aggregator: Option<BarAggregator>,
+    ) {
+        if let Err(err) = self.append_history(history) {
+            // `session.json` must not point past the history on disk; keep the previous one.
+            self.warn(&err);
+            return;
+        }
+        let session = PaperSession {
+            version: SESSION_VERSION,
+            run_id: self.run_id.clone(),
+            symbol: self.symbol.clone(),
+            timeframe: self.timeframe.clone(),
+            mode: self.mode.to_string(),
+            completed: false,
+            saved_at: chrono::Utc::now().to_rfc3339(),
+            engine,
+            aggregator,
+            equity: Vec::new(),
+            trades: Vec::new(),
+        };
+        self.write(&session);
+        self.last = Some(session);
+    }
+
+    fn append_history(&mut self, history: EngineHistory<'_>) -> Result<(), String> {
+        let new_equity = history.equity.get(self.saved_equity..).unwrap_or_default();
+        let new_trades = history.trades.get(self.saved_trades..).unwrap_or_default();
+        if new_equity.is_empty() && new_trades.is_empty() {
+            return Ok(());
+        }
+        let records = new_equity
+            .iter()
+            .zip(self.saved_equity..)
+            .map(|(point, index)| HistoryRecord::Equity {
+                index,
+                point: point.clone(),
+            })
+            .chain(
+                new_trades
+                    .iter()
+                    .zip(self.saved_trades..)
+                    .map(|(trade, index)| HistoryRecord::Trade {
+                        index,
+                        trade: trade.clone(),
+                    }),
+            )
+            .map(|record| {
+                serde_json::to_value(record)
+                    .map_err(|err| format!("failed to serialize paper session history: {err}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.artifacts.append_jsonl(&self.history_path, &records)?;
+        self.saved_equity = history.equity.len();
+        self.saved_trades = history.trades.len();
+        Ok(())
+    }
+
+    // Marks the last saved session as done, so a later `--resume-session` does not replay it.
+    pub(crate) fn complete(&mut self) {
+        let Some(mut session) = self.last.take() else {
+            return;
+        };
+        session.completed = true;
+        session.saved_at = chrono::Utc::now().to_rfc3339();
+        self.write(&session);
+    }
+
+    fn write(&mut self, session: &PaperSession) {
+        let result = serde_json::to_value(session)
+            .map_err(|err| format!("failed to serialize paper session: {err}"))
+            .and_then(|value| self.artifacts.write_checkpoint_json(&self.path, &value));
+        match result {
+            Ok(()) => self.failed = false,
+            Err(err) => self.warn(&err),
+        }
+    }
+
+    fn warn(&mut self, err: &str) {
+        if !self.failed {
+            tracing::warn!(run_id = %self.run_id, error = %err, "paper session checkpoint failed");
+        }
+        self.failed = true;
+    }
+}
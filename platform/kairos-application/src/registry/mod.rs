@@ -0,0 +1,374 @@
+use kairos_domain::entities::run_summary::RunSummary;
+use kairos_domain::repositories::run_registry::{RunQuery, RunRecord, RunRegistry, RunSortKey};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tracing::info_span;
+
+pub const REGISTRY_FILE_NAME: &str = "registry.sqlite";
+
+pub fn registry_path(out_dir: &Path) -> PathBuf {
+    out_dir.join(REGISTRY_FILE_NAME)
+}
+
+pub fn record_run(run_dir: &Path, registry: &dyn RunRegistry) -> Result<RunRecord, String> {
+    let record = read_run_record(run_dir)?;
+    registry.upsert_run(&record)?;
+    metrics::counter!("kairos.registry.recorded_total").increment(1);
+    Ok(record)
+}
+
+// Scans `out_dir` for run directories (any direct child with a summary.json) and records each of
+// them, so runs produced before the registry existed (or by sweeps) become queryable.
+pub fn reindex(out_dir: &Path, registry: &dyn RunRegistry) -> Result<usize, String> {
+    let _span = info_span!("registry_reindex", out_dir = %out_dir.display()).entered();
+    let run_dirs = run_dirs(out_dir)?;
+    for run_dir in &run_dirs {
+        record_run(run_dir, registry)?;
+    }
+    Ok(run_dirs.len())
+}
+
+// Same discovery as `reindex`, but read straight from disk (no registry needed).
+pub fn scan_runs(out_dir: &Path) -> Result<Vec<RunRecord>, String> {
+    run_dirs(out_dir)?
+        .iter()
+        .map(|run_dir| read_run_record(run_dir))
+        .collect()
+}
+
+// Resolves `id` as a path first, then as a directory name under `out_dir`, then as a run_id.
+pub fn find_run(out_dir: &Path, id: &str) -> Result<RunRecord, String> {
+    let direct = Path::new(id);
+    if direct.join("summary.json").is_file() {
+        return read_run_record(direct);
+    }
+    let nested = out_dir.join(id);
+    if nested.join("summary.json").is_file() {
+        return read_run_record(&nested);
+    }
+    scan_runs(out_dir)?
+        .into_iter()
+        .find(|record| record.run_id == id)
+        .ok_or_else(|| format!("run '{id}' not found under {}", out_dir.display()))
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CleanPolicy {
+    pub older_than_secs: Option<i64>,
+    pub keep_best: Option<(RunSortKey, usize)>,
+}
+
+impl CleanPolicy {
+    // `<key>:<n>`, e.g. `sharpe:10`.
+    pub fn parse_keep_best(raw: &str) -> Result<(RunSortKey, usize), String> {
+        let (key, count) = raw
+            .split_once(':')
+            .ok_or_else(|| format!("invalid keep-best '{raw}' (expected <key>:<n>)"))?;
+        let count = count
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| format!("invalid keep-best count in '{raw}'"))?;
+        Ok((RunSortKey::parse(key)?, count))
+    }
+}
+
+// Runs selected for deletion: older than the cutoff (every run when no age is given) and not among
+// the best `n` by the keep-best key. At least one criterion is required.
+pub fn plan_clean(
+    records: &[RunRecord],
+    policy: &CleanPolicy,
+    now: i64,
+) -> Result<Vec<RunRecord>, String> {
+    if policy.older_than_secs.is_none() && policy.keep_best.is_none() {
+        return Err("clean requires --older-than and/or --keep-best".to_string());
+    }
+
+    let mut keep: Vec<&str> = Vec::new();
+    if let Some((key, count)) = policy.keep_best {
+        let mut ranked: Vec<&RunRecord> = records.iter().collect();
+        ranked.sort_by(|a, b| rank_value(key, b).total_cmp(&rank_value(key, a)));
+        keep.extend(ranked.iter().take(count).map(|r| r.run_dir.as_str()));
+    }
+
+    Ok(records
+        .iter()
+        .filter(|record| match policy.older_than_secs {
+            Some(age) => record.recorded_at < now - age,
+            None => true,
+        })
+        .filter(|record| !keep.contains(&record.run_dir.as_str()))
+        .cloned()
+        .collect())
+}
+
+// Best first: newest for recorded_at, lowest for max_drawdown, highest otherwise.
+pub fn sort_best_first(records: &mut [RunRecord], key: RunSortKey) {
+    records.sort_by(|a, b| rank_value(key, b).total_cmp(&rank_value(key, a)));
+}
+
+// Higher is better after this mapping; non-finite metrics rank last.
+fn rank_value(key: RunSortKey, record: &RunRecord) -> f64 {
+    let value = key.value(record);
+    if !value.is_finite() {
+        return f64::NEG_INFINITY;
+    }
+    if key.higher_is_better() {
+        value
+    } else {
+        -value
+    }
+}
+
+pub fn clean_runs(
+    out_dir: &Path,
+    policy: &CleanPolicy,
+    now: i64,
+    dry_run: bool,
+    registry: Option<&dyn RunRegistry>,
+) -> Result<Vec<RunRecord>, String> {
+    let _span = info_span!("registry_clean", out_dir = %out_dir.display(), dry_run).entered();
+    let removed = plan_clean(&scan_runs(out_dir)?, policy, now)?;
+    if dry_run {
+        return Ok(removed);
+    }
+    for record in &removed {
+        std::fs::remove_dir_all(&record.run_dir)
+            .map_err(|err| format!("failed to remove {}: {err}", record.run_dir))?;
+        if let Some(registry) = registry {
+            registry.remove_run(&record.run_dir)?;
+        }
+        metrics::counter!("kairos.registry.cleaned_total").increment(1);
+    }
+    Ok(removed)
+}
+
+fn run_dirs(out_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    if !out_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let entries = std::fs::read_dir(out_dir)
+        .map_err(|err| format!("failed to read runs dir {}: {err}", out_dir.display()))?;
+    let mut run_dirs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.join("summary.json").is_file())
+        .collect();
+    run_dirs.sort();
+    Ok(run_dirs)
+}
+
+pub fn list_runs(registry: &dyn RunRegistry, query: &RunQuery) -> Result<Vec<RunRecord>, String> {
+    registry.query_runs(query)
+}
+
+pub fn read_run_record(run_dir: &Path) -> Result<RunRecord, String> {
+    let summary_path = run_dir.join("summary.json");
+    let raw = std::fs::read_to_string(&summary_path)
+        .map_err(|err| format!("failed to read {}: {err}", summary_path.display()))?;
+    let summary = RunSummary::from_json_str(&raw)
+        .map_err(|err| format!("failed to parse {}: {err}", summary_path.display()))?;
+    let meta = summary.meta.as_ref();
+
+    let run_id = meta
+        .and_then(|m| m.run_id.clone())
+        .or_else(|| {
+            run_dir
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let config_hash = match std::fs::read(run_dir.join("config_snapshot.toml")) {
+        Ok(bytes) => Some(to_hex(&Sha256::digest(&bytes))),
+        Err(_) => None,
+    };
+
+    let recorded_at = std::fs::metadata(&summary_path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+
+    Ok(RunRecord {
+        run_id,
+        run_dir: run_dir.display().to_string(),
+        config_hash,
+        symbol: meta.and_then(|m| m.symbol.clone()),
+        timeframe: meta.and_then(|m| m.timeframe.clone()),
+        start: meta.and_then(|m| m.start),
+        end: meta.and_then(|m| m.end),
+        bars_processed: summary.bars_processed,
+        trades: summary.trades,
+        win_rate: summary.win_rate,
+        net_profit: summary.net_profit,
+        sharpe: summary.sharpe,
+        max_drawdown: summary.max_drawdown,
+        recorded_at,
+        notes: crate::annotations::load_notes(run_dir)
+            .into_iter()
+            .map(|note| note.note)
+            .collect(),
+    })
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push(HEX[(b >> 4) as usize] as char);
+        out.push(HEX[(b & 0x0f) as usize] as char);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{plan_clean, read_run_record, reindex, CleanPolicy};
+    use kairos_domain::repositories::run_registry::{RunQuery, RunRecord, RunRegistry, RunSortKey};
+    use std::cell::RefCell;
+    use std::path::PathBuf;
+
+    #[derive(Default)]
+    struct MemoryRegistry {
+        records: RefCell<Vec<RunRecord>>,
+    }
+
+    impl RunRegistry for MemoryRegistry {
+        fn upsert_run(&self, record: &RunRecord) -> Result<(), String> {
+            let mut records = self.records.borrow_mut();
+            records.retain(|r| r.run_dir != record.run_dir);
+            records.push(record.clone());
+            Ok(())
+        }
+
+        fn query_runs(&self, _query: &RunQuery) -> Result<Vec<RunRecord>, String> {
+            Ok(self.records.borrow().clone())
+        }
+
+        fn remove_run(&self, run_dir: &str) -> Result<(), String> {
+            self.records.borrow_mut().retain(|r| r.run_dir != run_dir);
+            Ok(())
+        }
+    }
+
+    fn temp_dir() -> PathBuf {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        std::env::temp_dir().join(format!("kairos_registry_{}_{}", std::process::id(), nanos))
+    }
+
+    #[test]
+    fn reindex_records_runs_with_summary_and_skips_other_dirs() {
+        let out_dir = temp_dir();
+        let run_dir = out_dir.join("run_a");
+        std::fs::create_dir_all(&run_dir).unwrap();
+        std::fs::create_dir_all(out_dir.join("sweeps")).unwrap();
+        std::fs::write(
+            run_dir.join("summary.json"),
+            r#"{
+  "meta": {"run_id": "run_a", "symbol": "BTC-USDT", "timeframe": "1min", "start": 60, "end": 180},
+  "config_snapshot": null,
+  "bars_processed": 3,
+  "trades": 2,
+  "win_rate": 0.5,
+  "net_profit": 12.5,
+  "sharpe": 1.25,
+  "max_drawdown": 0.1
+}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            run_dir.join("config_snapshot.toml"),
+            "[run]\nrun_id = \"run_a\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            run_dir.join("notes.jsonl"),
+            "{\"created_at\":\"2026-01-01T00:00:00Z\",\"note\":\"first look\"}\n",
+        )
+        .unwrap();
+
+        let registry = MemoryRegistry::default();
+        assert_eq!(reindex(&out_dir, &registry).unwrap(), 1);
+
+        let records = registry.records.borrow();
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.run_id, "run_a");
+        assert_eq!(record.symbol.as_deref(), Some("BTC-USDT"));
+        assert_eq!(record.start, Some(60));
+        assert_eq!(record.end, Some(180));
+        assert_eq!(record.trades, 2);
+        assert_eq!(record.net_profit, 12.5);
+        assert_eq!(record.config_hash.as_ref().map(|h| h.len()), Some(64));
+        assert!(record.recorded_at > 0);
+        assert_eq!(record.notes, vec!["first look".to_string()]);
+
+        let again = read_run_record(&run_dir).unwrap();
+        assert_eq!(again.config_hash, record.config_hash);
+
+        let _ = std::fs::remove_dir_all(&out_dir);
+    }
+
+    fn record(run_dir: &str, recorded_at: i64, sharpe: f64, max_drawdown: f64) -> RunRecord {
+        RunRecord {
+            run_id: run_dir.to_string(),
+            run_dir: run_dir.to_string(),
+            config_hash: None,
+            symbol: None,
+            timeframe: None,
+            start: None,
+            end: None,
+            bars_processed: 0,
+            trades: 0,
+            win_rate: 0.0,
+            net_profit: 0.0,
+            sharpe,
+            max_drawdown,
+            recorded_at,
+            notes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn plan_clean_keeps_recent_and_best_runs() {
+        let day = 86_400;
+        let now = 100 * day;
+        let records = vec![
+            record("old_best", now - 60 * day, 2.0, 0.3),
+            record("old_mid", now - 60 * day, 1.0, 0.1),
+            record("old_nan", now - 60 * day, f64::NAN, 0.2),
+            record("recent", now - day, -1.0, 0.5),
+        ];
+
+        let policy = CleanPolicy {
+            older_than_secs: Some(30 * day),
+            keep_best: Some(CleanPolicy::parse_keep_best("sharpe:1").unwrap()),
+        };
+        let removed: Vec<String> = plan_clean(&records, &policy, now)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.run_dir)
+            .collect();
+        assert_eq!(removed, vec!["old_mid", "old_nan"]);
+
+        let policy = CleanPolicy {
+            older_than_secs: None,
+            keep_best: Some((RunSortKey::MaxDrawdown, 2)),
+        };
+        let removed: Vec<String> = plan_clean(&records, &policy, now)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.run_dir)
+            .collect();
+        assert_eq!(removed, vec!["old_best", "recent"]);
+
+        assert!(plan_clean(&records, &CleanPolicy::default(), now).is_err());
+        assert!(CleanPolicy::parse_keep_best("sharpe").is_err());
+        assert!(CleanPolicy::parse_keep_best("alpha:3").is_err());
+    }
+}
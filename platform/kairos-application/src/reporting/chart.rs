@@ -0,0 +1,97 @@
+use crate::config::{build_ohlcv_query, Config};
+use crate::registry::read_run_record;
+use crate::shared::{normalize_timeframe_label, parse_duration_like};
+use kairos_domain::repositories::artifacts::ArtifactReader;
+use kairos_domain::repositories::market_data::MarketDataRepository;
+use kairos_domain::services::ohlcv::resample_bars;
+use kairos_domain::value_objects::bar::Bar;
+use kairos_domain::value_objects::trade::Trade;
+use std::path::Path;
+use tracing::info_span;
+
+// What the TUI chart draws for a finished run: its bars at the run timeframe over the range the
+// run covered, and the fills from trades.csv.
+#[derive(Debug, Clone)]
+pub struct RunChart {
+    pub run_id: String,
+    pub symbol: String,
+    pub timeframe: String,
+    pub bars: Vec<Bar>,
+    pub trades: Vec<Trade>,
+}
+
+// Run dirs keep no OHLCV, so the bars are reloaded from `market_data`. The exchange, market and
+// source timeframe come from the run's config_snapshot.toml when it parses, else from `config`;
+// symbol, timeframe and range come from its summary.json.
+pub fn load_run_chart(
+    run_dir: &Path,
+    config: &Config,
+    market_data: &dyn MarketDataRepository,
+    reader: &dyn ArtifactReader,
+) -> Result<RunChart, String> {
+    let _span = info_span!("load_run_chart", run_dir = %run_dir.display()).entered();
+
+    let record = read_run_record(run_dir)?;
+    let (Some(start), Some(end)) = (record.start, record.end) else {
+        return Err(format!(
+            "{} has no run range in summary.json",
+            run_dir.display()
+        ));
+    };
+
+    let snapshot = reader
+        .read_config_snapshot_toml(&run_dir.join("config_snapshot.toml"))?
+        .and_then(|raw| toml::from_str::<Config>(&raw).ok());
+    let mut config = snapshot.unwrap_or_else(|| config.clone());
+    if let Some(symbol) = record.symbol.clone() {
+        config.run.symbol = symbol;
+    }
+    if let Some(timeframe) = record.timeframe.clone() {
+        config.run.timeframe = timeframe;
+    }
+    config.run.start = None;
+    config.run.end = None;
+    config.run.max_bars = None;
+
+    let step = parse_duration_like(&config.run.timeframe)?;
+    let timeframe_label = normalize_timeframe_label(&config.run.timeframe)?;
+    let source_timeframe_label = normalize_timeframe_label(
+        config
+            .db
+            .source_timeframe
+            .as_deref()
+            .unwrap_or(&timeframe_label),
+    )?;
+    let source_step = parse_duration_like(&source_timeframe_label)?;
+    let mut query = build_ohlcv_query(&config, &source_timeframe_label, source_step)
+        .map_err(|err| err.to_string())?;
+    query.start = Some(start);
+    query.end = Some(end);
+    let (source_bars, _) = market_data.load_ohlcv(&query)?;
+    let bars = if source_step != step {
+        resample_bars(&source_bars, step).map_err(|err| err.to_string())?
+    } else {
+        source_bars
+    };
+    if bars.is_empty() {
+        return Err(format!(
+            "no OHLCV rows for {} {} ({}/{}) in the run range",
+            query.symbol, source_timeframe_label, query.exchange, query.market
+        ));
+    }
+
+    let trades_path = run_dir.join("trades.csv");
+    let trades = if reader.exists(&trades_path) {
+        reader.read_trades_csv(&trades_path)?
+    } else {
+        Vec::new()
+    };
+
+    Ok(RunChart {
+        run_id: record.run_id,
+        symbol: config.run.symbol,
+        timeframe: timeframe_label,
+        bars,
+        trades,
+    })
+}
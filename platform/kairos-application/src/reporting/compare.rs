@@ -0,0 +1,297 @@
+use crate::error::RunError;
+use kairos_domain::entities::metrics::{recompute_summary, MetricsSummary};
+use kairos_domain::entities::run_note::RunNote;
+use kairos_domain::repositories::artifacts::{ArtifactReader, ArtifactWriter};
+use kairos_domain::services::downsample::{downsample_equity, CHART_MAX_POINTS};
+use kairos_domain::value_objects::equity_point::EquityPoint;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::info_span;
+
+pub struct CompareRunsResult {
+    pub out_dir: PathBuf,
+    pub run_a: String,
+    pub run_b: String,
+    pub metrics: Vec<MetricDelta>,
+    pub config_diff: Vec<ConfigDiffEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricDelta {
+    pub name: &'static str,
+    pub a: f64,
+    pub b: f64,
+}
+
+impl MetricDelta {
+    pub fn delta(&self) -> f64 {
+        self.b - self.a
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigDiffEntry {
+    pub key: String,
+    pub a: Option<String>,
+    pub b: Option<String>,
+}
+
+struct LoadedRun {
+    dir: PathBuf,
+    run_id: String,
+    summary: MetricsSummary,
+    equity: Vec<EquityPoint>,
+    config: BTreeMap<String, String>,
+    notes: Vec<RunNote>,
+}
+
+pub fn compare_runs(
+    run_a: &Path,
+    run_b: &Path,
+    out_dir: &Path,
+    reader: &dyn ArtifactReader,
+    writer: &dyn ArtifactWriter,
+) -> Result<CompareRunsResult, RunError> {
+    let _span = info_span!(
+        "compare_runs",
+        run_a = %run_a.display(),
+        run_b = %run_b.display()
+    )
+    .entered();
+
+    let a = load_run(run_a, reader).map_err(RunError::Data)?;
+    let b = load_run(run_b, reader).map_err(RunError::Data)?;
+
+    let metrics = metric_deltas(&a.summary, &b.summary);
+    let config_diff = diff_configs(&a.config, &b.config);
+    let report = comparison_json(&a, &b, &metrics, &config_diff);
+
+    let write = || -> Result<(), String> {
+        writer.ensure_dir(out_dir)?;
+        writer.write_analyzer_json(out_dir.join("compare.json").as_path(), &report)?;
+        writer.write_compare_html(out_dir.join("compare.html").as_path(), &report)
+    };
+    write().map_err(RunError::Artifacts)?;
+
+    Ok(CompareRunsResult {
+        out_dir: out_dir.to_path_buf(),
+        run_a: a.run_id,
+        run_b: b.run_id,
+        metrics,
+        config_diff,
+    })
+}
+
+fn load_run(dir: &Path, reader: &dyn ArtifactReader) -> Result<LoadedRun, String> {
+    let trades_path = dir.join("trades.csv");
+    let equity_path = dir.join("equity.csv");
+    if !reader.exists(&trades_path) || !reader.exists(&equity_path) {
+        return Err(format!(
+            "missing trades.csv or equity.csv in {}",
+            dir.display()
+        ));
+    }
+
+    let trades = reader.read_trades_csv(&trades_path)?;
+    let equity = reader.read_equity_csv(&equity_path)?;
+    let summary = recompute_summary(&trades, &equity);
+
+    let config = match reader.read_config_snapshot_toml(&dir.join("config_snapshot.toml"))? {
+        Some(raw) => {
+            let value: toml::Value = toml::from_str(&raw).map_err(|err| {
+                format!(
+                    "failed to parse config snapshot in {}: {err}",
+                    dir.display()
+                )
+            })?;
+            let mut flat = BTreeMap::new();
+            flatten_toml("", &value, &mut flat);
+            flat
+        }
+        None => BTreeMap::new(),
+    };
+
+    let run_id = config
+        .get("run.run_id")
+        .map(|v| v.trim_matches('"').to_string())
+        .or_else(|| {
+            dir.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+
+    Ok(LoadedRun {
+        dir: dir.to_path_buf(),
+        run_id,
+        summary,
+        equity,
+        config,
+        notes: crate::annotations::read_notes(dir, reader)?,
+    })
+}
+
+fn flatten_toml(prefix: &str, value: &toml::Value, out: &mut BTreeMap<String, String>) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, child) in table {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten_toml(&path, child, out);
+            }
+        }
+        other => {
+            out.insert(prefix.to_string(), other.to_string());
+        }
+    }
+}
+
+fn diff_configs(
+    a: &BTreeMap<String, String>,
+    b: &BTreeMap<String, String>,
+) -> Vec<ConfigDiffEntry> {
+    let mut keys: Vec<&String> = a.keys().chain(b.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .filter(|key| a.get(*key) != b.get(*key))
+        .map(|key| ConfigDiffEntry {
+            key: key.clone(),
+            a: a.get(key).cloned(),
+            b: b.get(key).cloned(),
+        })
+        .collect()
+}
+
+fn metric_deltas(a: &MetricsSummary, b: &MetricsSummary) -> Vec<MetricDelta> {
+    vec![
+        MetricDelta {
+            name: "bars_processed",
+            a: a.bars_processed as f64,
+            b: b.bars_processed as f64,
+        },
+        MetricDelta {
+            name: "trades",
+            a: a.trades as f64,
+            b: b.trades as f64,
+        },
+        MetricDelta {
+            name: "win_rate",
+            a: a.win_rate,
+            b: b.win_rate,
+        },
+        MetricDelta {
+            name: "net_profit",
+            a: a.net_profit,
+            b: b.net_profit,
+        },
+        MetricDelta {
+            name: "sharpe",
+            a: a.sharpe,
+            b: b.sharpe,
+        },
+        MetricDelta {
+            name: "max_drawdown",
+            a: a.max_drawdown,
+            b: b.max_drawdown,
+        },
+    ]
+}
+
+fn comparison_json(
+    a: &LoadedRun,
+    b: &LoadedRun,
+    metrics: &[MetricDelta],
+    config_diff: &[ConfigDiffEntry],
+) -> serde_json::Value {
+    let run_json = |run: &LoadedRun| {
+        serde_json::json!({
+            "run_id": run.run_id,
+            "dir": run.dir.display().to_string(),
+            "notes": run.notes,
+        })
+    };
+    let equity_json = |run: &LoadedRun| {
+        downsample_equity(&run.equity, CHART_MAX_POINTS)
+            .iter()
+            .map(|p| serde_json::json!([p.timestamp, p.equity]))
+            .collect::<Vec<_>>()
+    };
+
+    serde_json::json!({
+        "a": run_json(a),
+        "b": run_json(b),
+        "metrics": metrics
+            .iter()
+            .map(|m| serde_json::json!({
+                "name": m.name,
+                "a": m.a,
+                "b": m.b,
+                "delta": m.delta(),
+            }))
+            .collect::<Vec<_>>(),
+        "config_diff": config_diff
+            .iter()
+            .map(|entry| serde_json::json!({
+                "key": entry.key,
+                "a": entry.a,
+                "b": entry.b,
+            }))
+            .collect::<Vec<_>>(),
+        "equity": {
+            "a": equity_json(a),
+            "b": equity_json(b),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff_configs, flatten_toml};
+    use std::collections::BTreeMap;
+
+    fn flat(raw: &str) -> BTreeMap<String, String> {
+        let value: toml::Value = toml::from_str(raw).expect("toml");
+        let mut out = BTreeMap::new();
+        flatten_toml("", &value, &mut out);
+        out
+    }
+
+    #[test]
+    fn diff_configs_reports_changed_added_and_removed_keys() {
+        let a = flat(
+            r#"
+[run]
+run_id = "a"
+[costs]
+fee_bps = 10.0
+[strategy]
+sma_short = 10
+"#,
+        );
+        let b = flat(
+            r#"
+[run]
+run_id = "b"
+[costs]
+fee_bps = 10.0
+[report]
+html = true
+"#,
+        );
+
+        let diff = diff_configs(&a, &b);
+        let keys: Vec<&str> = diff.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec!["report.html", "run.run_id", "strategy.sma_short"]
+        );
+        assert_eq!(diff[0].a, None);
+        assert_eq!(diff[0].b.as_deref(), Some("true"));
+        assert_eq!(diff[2].a.as_deref(), Some("10"));
+        assert_eq!(diff[2].b, None);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::error::RunError;
+use kairos_domain::repositories::artifacts::{ArtifactReader, ArtifactWriter, BundleFormat};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub struct ExportResult {
+    pub input_dir: PathBuf,
+    pub archive: PathBuf,
+    pub format: BundleFormat,
+    pub entries: Vec<String>,
+}
+
+pub fn default_archive_path(input_dir: &Path, format: BundleFormat) -> PathBuf {
+    let name = input_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "run".to_string());
+    let file_name = match format {
+        BundleFormat::Zip => format!("{name}.zip"),
+        BundleFormat::Parquet => format!("{name}.parquet.zip"),
+    };
+    input_dir
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(file_name)
+}
+
+pub fn export_run(
+    input_dir: &Path,
+    out_path: Option<&Path>,
+    format: BundleFormat,
+    reader: &dyn ArtifactReader,
+    writer: &dyn ArtifactWriter,
+) -> Result<ExportResult, RunError> {
+    if !reader.exists(&input_dir.join("summary.json")) {
+        return Err(RunError::Data(format!(
+            "not a run dir (missing summary.json): {}",
+            input_dir.display()
+        )));
+    }
+    let archive = out_path
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| default_archive_path(input_dir, format));
+    if archive.starts_with(input_dir) {
+        return Err(RunError::Usage(format!(
+            "--out must be outside the run dir: {}",
+            archive.display()
+        )));
+    }
+
+    let entries = writer
+        .write_bundle(input_dir, &archive, format)
+        .map_err(RunError::Artifacts)?;
+    metrics::counter!("kairos.export.bundles_total", "format" => format.as_str()).increment(1);
+    Ok(ExportResult {
+        input_dir: input_dir.to_path_buf(),
+        archive,
+        format,
+        entries,
+    })
+}
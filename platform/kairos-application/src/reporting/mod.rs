@@ -1,8 +1,11 @@
-use crate::config::Config;
+use crate::config::{Config, ConfigError};
+use crate::error::RunError;
 use crate::shared::{
-    config_snapshot_json, resolve_execution_config, summary_meta_json_from_equity,
+    config_snapshot_json, finalize_run_dir, resolve_artifact_compression, resolve_execution_config,
+    summary_meta_json_from_equity,
 };
 use kairos_domain::entities::metrics::{recompute_summary, MetricsSummary};
+use kairos_domain::entities::run_summary::{Reproducibility, RunSummary};
 use kairos_domain::repositories::artifacts::{ArtifactReader, ArtifactWriter};
 use kairos_domain::services::audit::AuditEvent;
 use kairos_domain::value_objects::equity_point::EquityPoint;
@@ -11,18 +14,160 @@ use std::path::{Path, PathBuf};
 use std::time::Instant;
 use tracing::info_span;
 
+pub mod chart;
+pub mod compare;
+pub mod export;
+pub mod montecarlo;
+pub mod watch;
+
 pub struct GenerateReportResult {
     pub input_dir: PathBuf,
+    // Where the artifacts went: `input_dir` unless `ReportOptions::out_dir` moved them.
+    pub out_dir: PathBuf,
     pub run_id: String,
     pub summary: MetricsSummary,
     pub wrote_html: bool,
+    pub outputs: Vec<ReportOutput>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportOutput {
+    // summary.json
+    Summary,
+    // summary.html
+    Html,
+    // dashboard.html
+    Dashboard,
+    // summary.md
+    Markdown,
+    // trades.parquet + equity.parquet
+    Parquet,
+}
+
+impl ReportOutput {
+    pub const ALL: [ReportOutput; 5] = [
+        ReportOutput::Summary,
+        ReportOutput::Html,
+        ReportOutput::Dashboard,
+        ReportOutput::Markdown,
+        ReportOutput::Parquet,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReportOutput::Summary => "summary",
+            ReportOutput::Html => "html",
+            ReportOutput::Dashboard => "dashboard",
+            ReportOutput::Markdown => "markdown",
+            ReportOutput::Parquet => "parquet",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReportOptions {
+    // `None` keeps the run's own choice: summary.json, plus summary.html and dashboard.html with
+    // `report.html`.
+    pub outputs: Option<Vec<ReportOutput>>,
+    // Writes the artifacts (and logs.jsonl/manifest.json) there; the run dir is only read.
+    pub out_dir: Option<PathBuf>,
+}
+
+impl ReportOptions {
+    // The requested outputs in `ReportOutput::ALL` order, or `defaults` when none were requested.
+    pub(crate) fn selected(&self, defaults: &[ReportOutput]) -> Vec<ReportOutput> {
+        let wanted = self.outputs.as_deref().unwrap_or(defaults);
+        ReportOutput::ALL
+            .into_iter()
+            .filter(|output| wanted.contains(output))
+            .collect()
+    }
+
+    pub(crate) fn report_dir<'a>(
+        &'a self,
+        input_dir: &'a Path,
+        writer: &dyn ArtifactWriter,
+    ) -> Result<&'a Path, String> {
+        match self.out_dir.as_deref() {
+            Some(out_dir) if out_dir != input_dir => {
+                writer.ensure_dir(out_dir)?;
+                Ok(out_dir)
+            }
+            _ => Ok(input_dir),
+        }
+    }
+}
+
+pub(crate) struct ReportArtifacts<'a> {
+    pub summary: &'a MetricsSummary,
+    pub meta: Option<&'a serde_json::Value>,
+    pub config_snapshot: Option<&'a serde_json::Value>,
+    pub reproducibility: Option<&'a Reproducibility>,
+    pub trades: &'a [Trade],
+    pub equity: &'a [EquityPoint],
+}
+
+pub(crate) fn write_outputs(
+    writer: &dyn ArtifactWriter,
+    out_dir: &Path,
+    outputs: &[ReportOutput],
+    artifacts: &ReportArtifacts<'_>,
+) -> Result<(), String> {
+    for output in outputs {
+        match output {
+            ReportOutput::Summary => writer.write_summary_json(
+                out_dir.join("summary.json").as_path(),
+                artifacts.summary,
+                artifacts.meta,
+                artifacts.config_snapshot,
+                artifacts.reproducibility,
+            )?,
+            ReportOutput::Html => writer.write_summary_html(
+                out_dir.join("summary.html").as_path(),
+                artifacts.summary,
+                artifacts.meta,
+            )?,
+            ReportOutput::Dashboard => writer.write_dashboard_html(
+                out_dir.join("dashboard.html").as_path(),
+                artifacts.summary,
+                artifacts.meta,
+                artifacts.trades,
+                artifacts.equity,
+            )?,
+            ReportOutput::Markdown => writer.write_summary_markdown(
+                out_dir.join("summary.md").as_path(),
+                artifacts.summary,
+                artifacts.meta,
+            )?,
+            ReportOutput::Parquet => {
+                writer.write_trades_parquet(
+                    out_dir.join("trades.parquet").as_path(),
+                    artifacts.trades,
+                )?;
+                writer.write_equity_parquet(
+                    out_dir.join("equity.parquet").as_path(),
+                    artifacts.equity,
+                )?;
+            }
+        }
+    }
+    Ok(())
 }
 
 pub fn generate_report(
     input_dir: &Path,
     reader: &dyn ArtifactReader,
     writer: &dyn ArtifactWriter,
-) -> Result<GenerateReportResult, String> {
+) -> Result<GenerateReportResult, RunError> {
+    generate_report_with(input_dir, &ReportOptions::default(), reader, writer)
+}
+
+pub fn generate_report_with(
+    input_dir: &Path,
+    options: &ReportOptions,
+    reader: &dyn ArtifactReader,
+    writer: &dyn ArtifactWriter,
+) -> Result<GenerateReportResult, RunError> {
     let _span = info_span!("generate_report", input_dir = %input_dir.display()).entered();
 
     let stage_start = Instant::now();
@@ -31,28 +176,34 @@ pub fn generate_report(
     let config_path = input_dir.join("config_snapshot.toml");
 
     if !reader.exists(&trades_path) || !reader.exists(&equity_path) {
-        return Err(format!(
+        return Err(RunError::Data(format!(
             "missing trades.csv or equity.csv in {}",
             input_dir.display()
-        ));
+        )));
     }
 
-    let trades = reader.read_trades_csv(&trades_path)?;
-    let equity = reader.read_equity_csv(&equity_path)?;
+    let trades = reader
+        .read_trades_csv(&trades_path)
+        .map_err(RunError::Data)?;
+    let equity = reader
+        .read_equity_csv(&equity_path)
+        .map_err(RunError::Data)?;
     let summary = recompute_summary(&trades, &equity);
     metrics::histogram!("kairos.report.generate_ms")
         .record(stage_start.elapsed().as_millis() as f64);
     metrics::gauge!("kairos.report.trades").set(trades.len() as f64);
     metrics::gauge!("kairos.report.bars_processed").set(summary.bars_processed as f64);
 
-    let config_toml = reader.read_config_snapshot_toml(&config_path)?;
-    let (run_id, meta, config_snapshot, wrote_html) = match config_toml
+    let config_toml = reader
+        .read_config_snapshot_toml(&config_path)
+        .map_err(RunError::Data)?;
+    let (run_id, meta, config_snapshot, html, compression) = match config_toml
         .as_deref()
         .and_then(|raw| load_config_from_str(raw).ok())
     {
         Some(config) => {
             let meta = summary_meta_json_from_equity(&config, &equity);
-            let execution = resolve_execution_config(&config)?;
+            let execution = resolve_execution_config(&config).map_err(ConfigError::Invalid)?;
             let snapshot = config_snapshot_json(&config, &execution);
             let run_id = meta
                 .as_ref()
@@ -67,32 +218,45 @@ pub fn generate_report(
                 .as_ref()
                 .and_then(|report| report.html)
                 .unwrap_or(false);
-            (run_id, meta, Some(snapshot), html)
+            let compression =
+                resolve_artifact_compression(&config).map_err(ConfigError::Invalid)?;
+            (run_id, meta, Some(snapshot), html, compression)
         }
-        None => ("unknown".to_string(), None, None, false),
+        None => ("unknown".to_string(), None, None, false, None),
     };
 
-    writer.write_summary_json(
-        input_dir.join("summary.json").as_path(),
-        &summary,
-        meta.as_ref(),
-        config_snapshot.as_ref(),
-    )?;
-
-    if wrote_html {
-        writer.write_summary_html(
-            input_dir.join("summary.html").as_path(),
-            &summary,
-            meta.as_ref(),
-        )?;
-        writer.write_dashboard_html(
-            input_dir.join("dashboard.html").as_path(),
-            &summary,
-            meta.as_ref(),
-            &trades,
-            &equity,
-        )?;
-    }
+    // Recomputing metrics from the CSVs does not change the run's inputs, so keep the original
+    // reproducibility block when the previous summary.json has one.
+    let reproducibility = std::fs::read_to_string(input_dir.join("summary.json"))
+        .ok()
+        .and_then(|raw| RunSummary::from_json_str(&raw).ok())
+        .and_then(|previous| previous.reproducibility);
+    let outputs = options.selected(if html {
+        &[
+            ReportOutput::Summary,
+            ReportOutput::Html,
+            ReportOutput::Dashboard,
+        ]
+    } else {
+        &[ReportOutput::Summary]
+    });
+    let out_dir = options
+        .report_dir(input_dir, writer)
+        .map_err(RunError::Artifacts)?;
+    write_outputs(
+        writer,
+        out_dir,
+        &outputs,
+        &ReportArtifacts {
+            summary: &summary,
+            meta: meta.as_ref(),
+            config_snapshot: config_snapshot.as_ref(),
+            reproducibility: reproducibility.as_ref(),
+            trades: &trades,
+            equity: &equity,
+        },
+    )
+    .map_err(RunError::Artifacts)?;
 
     let events = build_report_events(
         &run_id,
@@ -102,13 +266,18 @@ pub fn generate_report(
         meta.as_ref(),
         input_dir,
     );
-    writer.write_audit_jsonl(input_dir.join("logs.jsonl").as_path(), &events)?;
+    writer
+        .write_audit_jsonl(out_dir.join("logs.jsonl").as_path(), &events)
+        .map_err(RunError::Artifacts)?;
+    finalize_run_dir(out_dir, compression, writer).map_err(RunError::Artifacts)?;
 
     Ok(GenerateReportResult {
         input_dir: input_dir.to_path_buf(),
+        out_dir: out_dir.to_path_buf(),
         run_id,
         summary,
-        wrote_html,
+        wrote_html: outputs.contains(&ReportOutput::Html),
+        outputs,
     })
 }
 
@@ -132,7 +301,7 @@ fn build_report_events(
             run_id: run_id.to_string(),
             timestamp: trade.timestamp,
             stage: "trade".to_string(),
-            symbol: Some(trade.symbol.clone()),
+            symbol: Some(trade.symbol.to_string()),
             action: format!("{:?}", trade.side),
             error: None,
             details: serde_json::json!({
@@ -0,0 +1,152 @@
+use crate::error::RunError;
+use kairos_domain::repositories::artifacts::{ArtifactReader, ArtifactWriter};
+use kairos_domain::services::montecarlo::{
+    block_bootstrap, equity_returns, realized_trade_pnls, Compounding, Distribution,
+    MonteCarloConfig, ResamplingResult, BAND_PERCENTILES,
+};
+use std::path::{Path, PathBuf};
+use tracing::info_span;
+
+pub const MONTECARLO_FILE_NAME: &str = "montecarlo.json";
+
+#[derive(Debug)]
+pub struct MonteCarloResult {
+    pub input_dir: PathBuf,
+    pub output: PathBuf,
+    pub config: MonteCarloConfig,
+    // Block bootstrap of the bar-to-bar equity returns.
+    pub returns: ResamplingResult,
+    // Block bootstrap of realized trade PnLs; `None` when the run closed no trades.
+    pub trades: Option<ResamplingResult>,
+}
+
+pub fn default_output_path(input_dir: &Path) -> PathBuf {
+    input_dir.join("analyzers").join(MONTECARLO_FILE_NAME)
+}
+
+pub fn run_montecarlo(
+    input_dir: &Path,
+    out_path: Option<&Path>,
+    config: MonteCarloConfig,
+    reader: &dyn ArtifactReader,
+    writer: &dyn ArtifactWriter,
+) -> Result<MonteCarloResult, RunError> {
+    let _span = info_span!("run_montecarlo", input_dir = %input_dir.display()).entered();
+
+    config
+        .validate()
+        .map_err(|err| RunError::Usage(format!("--{err}")))?;
+    let trades_path = input_dir.join("trades.csv");
+    let equity_path = input_dir.join("equity.csv");
+    if !reader.exists(&trades_path) || !reader.exists(&equity_path) {
+        return Err(RunError::Data(format!(
+            "missing trades.csv or equity.csv in {}",
+            input_dir.display()
+        )));
+    }
+    let trades = reader
+        .read_trades_csv(&trades_path)
+        .map_err(RunError::Data)?;
+    let equity = reader
+        .read_equity_csv(&equity_path)
+        .map_err(RunError::Data)?;
+    let initial_equity = equity
+        .first()
+        .map(|point| point.equity)
+        .ok_or_else(|| RunError::Data(format!("empty equity.csv in {}", input_dir.display())))?;
+
+    let returns = block_bootstrap(
+        initial_equity,
+        &equity_returns(&equity),
+        Compounding::Multiplicative,
+        &config,
+    )
+    .map_err(|err| RunError::Data(format!("equity returns: {err}")))?;
+    let pnls = realized_trade_pnls(&trades);
+    let trades = if pnls.is_empty() {
+        None
+    } else {
+        Some(
+            block_bootstrap(initial_equity, &pnls, Compounding::Additive, &config)
+                .map_err(|err| RunError::Data(format!("trade pnls: {err}")))?,
+        )
+    };
+
+    let output = out_path
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| default_output_path(input_dir));
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        writer.ensure_dir(parent).map_err(RunError::Artifacts)?;
+    }
+    let report = serde_json::json!({
+        "name": "montecarlo",
+        "input_dir": input_dir.display().to_string(),
+        "samples": config.samples,
+        "block": config.block,
+        "seed": config.seed,
+        "initial_equity": initial_equity,
+        "percentiles": BAND_PERCENTILES,
+        "returns": resampling_json(&returns),
+        "trades": trades.as_ref().map(resampling_json),
+    });
+    writer
+        .write_analyzer_json(&output, &report)
+        .map_err(RunError::Artifacts)?;
+    // Keep the run's manifest in sync when the report lands inside the run dir.
+    if output.starts_with(input_dir) {
+        writer
+            .write_manifest(input_dir)
+            .map_err(RunError::Artifacts)?;
+    }
+    metrics::counter!("kairos.montecarlo.runs_total").increment(1);
+
+    Ok(MonteCarloResult {
+        input_dir: input_dir.to_path_buf(),
+        output,
+        config,
+        returns,
+        trades,
+    })
+}
+
+pub fn distribution_json(dist: &Distribution) -> serde_json::Value {
+    let mut out = serde_json::json!({
+        "mean": dist.mean,
+        "std": dist.std,
+        "min": dist.min,
+        "max": dist.max,
+    });
+    for (pct, value) in BAND_PERCENTILES.iter().zip(dist.percentiles) {
+        out[format!("p{pct}")] = serde_json::json!(value);
+    }
+    out
+}
+
+fn resampling_json(result: &ResamplingResult) -> serde_json::Value {
+    let mut bands = serde_json::json!({ "step": result.bands.steps });
+    for (i, pct) in BAND_PERCENTILES.iter().enumerate() {
+        bands[format!("p{pct}")] = result
+            .bands
+            .values
+            .iter()
+            .map(|row| row[i])
+            .collect::<Vec<_>>()
+            .into();
+    }
+    serde_json::json!({
+        "observations": result.observations,
+        "block": result.block,
+        "observed": {
+            "final_equity": result.observed.final_equity,
+            "total_return": result.observed.total_return,
+            "max_drawdown": result.observed.max_drawdown,
+            "sharpe": result.observed.sharpe,
+        },
+        "prob_loss": result.prob_loss,
+        "final_equity": distribution_json(&result.final_equity),
+        "total_return": distribution_json(&result.total_return),
+        "max_drawdown": distribution_json(&result.max_drawdown),
+        "sharpe": distribution_json(&result.sharpe),
+        "bands": bands,
+    })
+}
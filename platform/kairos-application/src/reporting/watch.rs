@@ -0,0 +1,194 @@
+use super::{
+    generate_report_with, write_outputs, GenerateReportResult, ReportArtifacts, ReportOptions,
+    ReportOutput,
+};
+use crate::error::RunError;
+use crate::paper_trading::live::LIVE_JOURNAL_FILE;
+use kairos_domain::entities::metrics::{recompute_summary, MetricsSummary};
+use kairos_domain::repositories::artifacts::{ArtifactReader, ArtifactWriter};
+use kairos_domain::value_objects::equity_point::EquityPoint;
+use kairos_domain::value_objects::side::Side;
+use kairos_domain::value_objects::trade::Trade;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+use tracing::info_span;
+
+#[derive(Debug)]
+pub struct WatchTick<'a> {
+    pub tick: u64,
+    pub new_records: usize,
+    pub bars: usize,
+    pub trades: usize,
+    pub summary: &'a MetricsSummary,
+}
+
+#[derive(Default)]
+struct LiveState {
+    offset: u64,
+    run_id: Option<String>,
+    symbol: Option<String>,
+    timeframe: Option<String>,
+    trades: Vec<Trade>,
+    equity: Vec<EquityPoint>,
+    end_status: Option<String>,
+}
+
+impl LiveState {
+    fn apply(&mut self, record: &serde_json::Value) {
+        let str_field = |key: &str| record.get(key).and_then(|v| v.as_str());
+        let f64_field = |key: &str| record.get(key).and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let timestamp = record
+            .get("timestamp_utc")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(0);
+        match str_field("type") {
+            // A restart appends a new session; only the latest one is reported.
+            Some("start") => {
+                self.run_id = str_field("run_id").map(str::to_string);
+                self.symbol = str_field("symbol").map(str::to_string);
+                self.timeframe = str_field("timeframe").map(str::to_string);
+                self.trades.clear();
+                self.equity.clear();
+                self.end_status = None;
+            }
+            Some("fill") => self.trades.push(Trade {
+                timestamp,
+                symbol: self.symbol.clone().unwrap_or_default().into(),
+                side: if str_field("side") == Some("SELL") {
+                    Side::Sell
+                } else {
+                    Side::Buy
+                },
+                quantity: f64_field("qty"),
+                price: f64_field("price"),
+                fee: f64_field("fee"),
+                slippage: f64_field("slippage"),
+                strategy_id: "live".to_string(),
+                reason: "live".to_string(),
+            }),
+            // live.jsonl carries no PnL split, so the unrealized/realized columns stay at zero.
+            Some("equity") => self.equity.push(EquityPoint {
+                timestamp,
+                equity: f64_field("equity"),
+                cash: f64_field("cash"),
+                position_qty: f64_field("position_qty"),
+                unrealized_pnl: 0.0,
+                realized_pnl: 0.0,
+            }),
+            Some("end") => self.end_status = str_field("status").map(str::to_string),
+            _ => {}
+        }
+    }
+
+    fn meta(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "run_id": self.run_id.as_ref()?,
+            "symbol": self.symbol.as_ref()?,
+            "timeframe": self.timeframe.as_ref()?,
+            "start": self.equity.first()?.timestamp,
+            "end": self.equity.last()?.timestamp,
+        }))
+    }
+}
+
+// Follows `<input_dir>/live.jsonl` while a paper run is in progress, rewriting the selected outputs
+// (by default summary.json, summary.html and dashboard.html) every `interval` from the records
+// appended since the last tick. Once the run records `end: completed`, the final CSV artifacts
+// exist and a regular `generate_report` pass finishes the watch. Dirs without live.jsonl are
+// reported once.
+pub fn watch_report(
+    input_dir: &Path,
+    options: &ReportOptions,
+    reader: &dyn ArtifactReader,
+    writer: &dyn ArtifactWriter,
+    interval: Duration,
+    should_stop: &dyn Fn() -> bool,
+    on_tick: &mut dyn FnMut(&WatchTick<'_>),
+) -> Result<GenerateReportResult, RunError> {
+    let _span = info_span!("watch_report", input_dir = %input_dir.display()).entered();
+
+    let live_path = input_dir.join(LIVE_JOURNAL_FILE);
+    if !reader.exists(&live_path) {
+        return generate_report_with(input_dir, options, reader, writer);
+    }
+
+    let outputs = options.selected(&[
+        ReportOutput::Summary,
+        ReportOutput::Html,
+        ReportOutput::Dashboard,
+    ]);
+    let out_dir = options
+        .report_dir(input_dir, writer)
+        .map_err(RunError::Artifacts)?;
+    let mut state = LiveState::default();
+    let mut tick = 0u64;
+    let mut last = None;
+    loop {
+        let (records, offset) = reader
+            .read_jsonl_from(&live_path, state.offset)
+            .map_err(RunError::Data)?;
+        state.offset = offset;
+        for record in &records {
+            state.apply(record);
+        }
+
+        if state.end_status.as_deref() == Some("completed") {
+            // Give the paper run a moment to finish compressing/writing the manifest.
+            thread::sleep(interval);
+            return generate_report_with(input_dir, options, reader, writer);
+        }
+
+        if !records.is_empty() || last.is_none() {
+            tick += 1;
+            let summary = recompute_summary(&state.trades, &state.equity);
+            write_live_report(out_dir, &outputs, writer, &state, &summary)
+                .map_err(RunError::Artifacts)?;
+            metrics::counter!("kairos.report.watch_ticks_total").increment(1);
+            on_tick(&WatchTick {
+                tick,
+                new_records: records.len(),
+                bars: state.equity.len(),
+                trades: state.trades.len(),
+                summary: &summary,
+            });
+            last = Some(summary);
+        }
+
+        if state.end_status.is_some() || should_stop() {
+            let summary = last.unwrap_or_else(|| recompute_summary(&[], &[]));
+            return Ok(GenerateReportResult {
+                input_dir: input_dir.to_path_buf(),
+                out_dir: out_dir.to_path_buf(),
+                run_id: state.run_id.unwrap_or_else(|| "unknown".to_string()),
+                summary,
+                wrote_html: outputs.contains(&ReportOutput::Html),
+                outputs,
+            });
+        }
+        thread::sleep(interval);
+    }
+}
+
+fn write_live_report(
+    out_dir: &Path,
+    outputs: &[ReportOutput],
+    writer: &dyn ArtifactWriter,
+    state: &LiveState,
+    summary: &MetricsSummary,
+) -> Result<(), String> {
+    let meta = state.meta();
+    write_outputs(
+        writer,
+        out_dir,
+        outputs,
+        &ReportArtifacts {
+            summary,
+            meta: meta.as_ref(),
+            config_snapshot: None,
+            reproducibility: None,
+            trades: &state.trades,
+            equity: &state.equity,
+        },
+    )
+}
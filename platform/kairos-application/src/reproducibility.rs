@@ -0,0 +1,143 @@
+use crate::config::Config;
+use kairos_domain::entities::run_summary::{DataFingerprint, Reproducibility};
+use kairos_domain::value_objects::bar::Bar;
+use sha2::{Digest, Sha256};
+
+pub fn git_sha() -> &'static str {
+    option_env!("KAIROS_GIT_SHA").unwrap_or("unknown")
+}
+
+pub fn crate_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+// Incremental so streaming (realtime) runs can fingerprint bars as they close.
+pub struct DataFingerprinter {
+    rows: u64,
+    first_timestamp: Option<i64>,
+    last_timestamp: Option<i64>,
+    closes: Sha256,
+}
+
+impl DataFingerprinter {
+    pub fn new() -> Self {
+        Self {
+            rows: 0,
+            first_timestamp: None,
+            last_timestamp: None,
+            closes: Sha256::new(),
+        }
+    }
+
+    pub fn push(&mut self, timestamp: i64, close: f64) {
+        self.rows += 1;
+        self.first_timestamp.get_or_insert(timestamp);
+        self.last_timestamp = Some(timestamp);
+        self.closes.update(close.to_le_bytes());
+    }
+
+    pub fn finish(self) -> DataFingerprint {
+        DataFingerprint {
+            rows: self.rows,
+            first_timestamp: self.first_timestamp,
+            last_timestamp: self.last_timestamp,
+            close_sha256: to_hex(&self.closes.finalize()),
+        }
+    }
+}
+
+impl Default for DataFingerprinter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn fingerprint_bars(bars: &[Bar]) -> DataFingerprint {
+    let mut fingerprinter = DataFingerprinter::new();
+    for bar in bars {
+        fingerprinter.push(bar.timestamp, bar.close);
+    }
+    fingerprinter.finish()
+}
+
+// `config_sha256` hashes the exact TOML written to config_snapshot.toml, so it matches the
+// registry's `config_hash` for the same run. `seed` is the RNG seed the run depended on: the
+// synthetic stream's for simulated paper, the search seed for random/bayesian sweep trials.
+pub fn build_reproducibility(
+    config: &Config,
+    config_toml: &str,
+    data: DataFingerprint,
+    seed: Option<u64>,
+) -> Reproducibility {
+    Reproducibility {
+        git_sha: git_sha().to_string(),
+        crate_version: crate_version().to_string(),
+        config_sha256: to_hex(&Sha256::digest(config_toml.as_bytes())),
+        data,
+        seed,
+        feature_schema_sha256: feature_schema_sha256(config),
+    }
+}
+
+// Covers every setting that changes the observation vector an agent sees.
+fn feature_schema_sha256(config: &Config) -> String {
+    let schema = serde_json::json!({
+        "feature_version": config.agent.feature_version,
+        "return_mode": config.features.return_mode,
+        "sma_windows": config.features.sma_windows,
+        "volatility_windows": config.features.volatility_windows.clone().unwrap_or_default(),
+        "rsi_enabled": config.features.rsi_enabled,
+        "sentiment_lag": config.features.sentiment_lag,
+        "sentiment_missing": config.features.sentiment_missing,
+    });
+    to_hex(&Sha256::digest(schema.to_string().as_bytes()))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fingerprint_bars, DataFingerprinter};
+    use kairos_domain::value_objects::bar::Bar;
+
+    fn bar(timestamp: i64, close: f64) -> Bar {
+        Bar {
+            symbol: "BTCUSD".into(),
+            timestamp,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 1.0,
+        }
+    }
+
+    #[test]
+    fn fingerprint_tracks_range_and_close_changes() {
+        let bars = vec![bar(60, 100.0), bar(120, 101.0), bar(180, 99.5)];
+        let fingerprint = fingerprint_bars(&bars);
+        assert_eq!(fingerprint.rows, 3);
+        assert_eq!(fingerprint.first_timestamp, Some(60));
+        assert_eq!(fingerprint.last_timestamp, Some(180));
+        assert_eq!(fingerprint.close_sha256.len(), 64);
+
+        let mut streamed = DataFingerprinter::new();
+        for b in &bars {
+            streamed.push(b.timestamp, b.close);
+        }
+        assert_eq!(streamed.finish(), fingerprint);
+
+        let mut changed = bars.clone();
+        changed[1].close = 101.5;
+        assert_ne!(
+            fingerprint_bars(&changed).close_sha256,
+            fingerprint.close_sha256
+        );
+
+        let empty = DataFingerprinter::default().finish();
+        assert_eq!(empty.rows, 0);
+        assert_eq!(empty.first_timestamp, None);
+    }
+}
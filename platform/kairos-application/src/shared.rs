@@ -1,9 +1,23 @@
-use crate::config::Config;
+use crate::config::{Config, ConfigError, SentimentSourceKind};
+use crate::error::RunError;
 use kairos_domain::entities::metrics::MetricsConfig;
-use kairos_domain::services::engine::backtest::OrderSizeMode;
+use kairos_domain::repositories::artifacts::{ArtifactCompression, ArtifactWriter};
+use kairos_domain::repositories::market_data::{MarketDataRepository, OhlcvQuery};
+use kairos_domain::repositories::sentiment::{
+    SentimentFormat, SentimentQuery, SentimentRepository, SentimentSource,
+};
+use kairos_domain::services::engine::backtest::{OrderSizeMode, RunControl};
 use kairos_domain::services::engine::execution as core_exec;
-use kairos_domain::services::sentiment::MissingValuePolicy;
+use kairos_domain::services::features::FeatureConfig;
+use kairos_domain::services::ohlcv::DataQualityReport;
+use kairos_domain::services::sentiment::{
+    merge_weighted, MissingValuePolicy, SentimentPoint, SentimentReport, WeightedSentiment,
+};
+use kairos_domain::value_objects::bar::Bar;
 use kairos_domain::value_objects::equity_point::EquityPoint;
+use std::path::{Path, PathBuf};
+
+const COMPRESSIBLE_ARTIFACTS: [&str; 3] = ["trades.csv", "equity.csv", "logs.jsonl"];
 
 pub fn parse_duration_like(value: &str) -> Result<i64, String> {
     kairos_domain::value_objects::timeframe::parse_duration_like_seconds(value)
@@ -13,6 +27,65 @@ pub fn normalize_timeframe_label(value: &str) -> Result<String, String> {
     kairos_domain::value_objects::timeframe::Timeframe::parse(value).map(|tf| tf.label)
 }
 
+pub fn parse_timestamp_seconds(raw: &str) -> Result<i64, String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Err("timestamp cannot be empty".to_string());
+    }
+    if let Ok(v) = trimmed.parse::<i64>() {
+        return Ok(v);
+    }
+    let dt = chrono::DateTime::parse_from_rfc3339(trimmed)
+        .map_err(|err| format!("invalid timestamp (expected epoch seconds or RFC3339): {err}"))?;
+    Ok(dt.timestamp())
+}
+
+// Checked between pipeline stages; `what` names the run in the error (`"backtest cancelled"`).
+pub fn ensure_not_cancelled(control: &dyn RunControl, what: &'static str) -> Result<(), RunError> {
+    if control.should_cancel() {
+        return Err(RunError::Cancelled(what));
+    }
+    Ok(())
+}
+
+// A load interrupted by `control` fails like the run it belongs to, not like a data error.
+pub fn load_ohlcv_with_control(
+    market_data: &dyn MarketDataRepository,
+    query: &OhlcvQuery,
+    control: &dyn RunControl,
+    what: &'static str,
+) -> Result<(Vec<Bar>, DataQualityReport), RunError> {
+    ensure_not_cancelled(control, what)?;
+    market_data
+        .load_ohlcv_with_control(query, control)
+        .map_err(|err| {
+            if control.should_cancel() {
+                RunError::Cancelled(what)
+            } else {
+                RunError::Data(err)
+            }
+        })
+}
+
+pub fn build_feature_config(config: &Config) -> FeatureConfig {
+    FeatureConfig {
+        return_mode: config.features.return_mode,
+        sma_windows: config
+            .features
+            .sma_windows
+            .iter()
+            .map(|w| *w as usize)
+            .collect(),
+        volatility_windows: config
+            .features
+            .volatility_windows
+            .as_ref()
+            .map(|windows| windows.iter().map(|w| *w as usize).collect())
+            .unwrap_or_default(),
+        rsi_enabled: config.features.rsi_enabled,
+    }
+}
+
 pub fn resolve_size_mode(config: &Config) -> OrderSizeMode {
     match config
         .orders
@@ -148,6 +221,257 @@ pub fn resolve_sma_windows(config: &Config) -> (usize, usize) {
     (10, 50)
 }
 
+pub const DEFAULT_SENTIMENT_TABLE: &str = "sentiment_scores";
+
+// The sentiment a run reads, if any: `paths.sentiment_path` (JSON by extension, CSV otherwise) or,
+// with `paths.sentiment_source = "db"`, the `db.sentiment_table` rows for `run.symbol` within
+// `run.start`/`run.end`, the start stepped back by `features.sentiment_lag`.
+pub fn build_sentiment_query(config: &Config) -> Result<Option<SentimentQuery>, String> {
+    let lag = parse_duration_like(&config.features.sentiment_lag)?;
+    sentiment_query(
+        config,
+        config.paths.sentiment_source.unwrap_or_default(),
+        config.paths.sentiment_path.as_deref(),
+        config.db.sentiment_table.as_deref(),
+        lag,
+    )
+}
+
+fn sentiment_query(
+    config: &Config,
+    kind: SentimentSourceKind,
+    path: Option<&str>,
+    table: Option<&str>,
+    lag_seconds: i64,
+) -> Result<Option<SentimentQuery>, String> {
+    let source = match kind {
+        SentimentSourceKind::File => {
+            let Some(path) = path else {
+                return Ok(None);
+            };
+            let path = PathBuf::from(path);
+            let format = match path.extension().and_then(|ext| ext.to_str()) {
+                Some(ext) if ext.eq_ignore_ascii_case("json") => SentimentFormat::Json,
+                _ => SentimentFormat::Csv,
+            };
+            SentimentSource::File { path, format }
+        }
+        SentimentSourceKind::Db => {
+            let bound = |key: &str, raw: Option<&String>| {
+                raw.map(|raw| {
+                    parse_timestamp_seconds(raw).map_err(|reason| format!("{key}: {reason}"))
+                })
+                .transpose()
+            };
+            SentimentSource::Db {
+                table: table.unwrap_or(DEFAULT_SENTIMENT_TABLE).to_string(),
+                symbol: config.run.symbol.clone(),
+                start: bound("run.start", config.run.start.as_ref())?
+                    .map(|start| start.saturating_sub(lag_seconds)),
+                end: bound("run.end", config.run.end.as_ref())?,
+            }
+        }
+    };
+    Ok(Some(SentimentQuery {
+        source,
+        missing_policy: resolve_sentiment_missing_policy(config),
+    }))
+}
+
+// One sentiment input of a run, ready to load.
+#[derive(Debug, Clone)]
+pub struct SentimentInput {
+    pub name: String,
+    pub query: SentimentQuery,
+    pub weight: f64,
+    pub lag_seconds: i64,
+}
+
+// The run's sentiment inputs, if any: the `paths.sentiment_sources` entries or, without them, the
+// single input of `build_sentiment_query` (named "sentiment", weight 1, `features.sentiment_lag`).
+pub fn build_sentiment_inputs(config: &Config) -> Result<Option<Vec<SentimentInput>>, String> {
+    let sources = match config.paths.sentiment_sources.as_deref() {
+        Some(sources) if !sources.is_empty() => sources,
+        _ => {
+            let lag_seconds = parse_duration_like(&config.features.sentiment_lag)?;
+            return Ok(build_sentiment_query(config)?.map(|query| {
+                vec![SentimentInput {
+                    name: "sentiment".to_string(),
+                    query,
+                    weight: 1.0,
+                    lag_seconds,
+                }]
+            }));
+        }
+    };
+    if config.paths.sentiment_path.is_some() || config.paths.sentiment_source.is_some() {
+        return Err(
+            "paths.sentiment_sources cannot be combined with paths.sentiment_path/sentiment_source"
+                .to_string(),
+        );
+    }
+    let mut inputs: Vec<SentimentInput> = Vec::with_capacity(sources.len());
+    for (idx, source) in sources.iter().enumerate() {
+        let key = format!("paths.sentiment_sources[{idx}]");
+        let name = source.name.trim();
+        if name.is_empty() {
+            return Err(format!("{key}.name must not be empty"));
+        }
+        if inputs.iter().any(|input| input.name == name) {
+            return Err(format!("{key}.name {name:?} is used twice"));
+        }
+        let weight = source.weight.unwrap_or(1.0);
+        if !weight.is_finite() || weight <= 0.0 {
+            return Err(format!("{key}.weight must be > 0"));
+        }
+        let lag_seconds = parse_duration_like(
+            source
+                .lag
+                .as_deref()
+                .unwrap_or(&config.features.sentiment_lag),
+        )
+        .map_err(|err| format!("{key}.lag: {err}"))?;
+        let kind = source.source.unwrap_or_default();
+        let query = sentiment_query(
+            config,
+            kind,
+            source.path.as_deref(),
+            source
+                .table
+                .as_deref()
+                .or(config.db.sentiment_table.as_deref()),
+            lag_seconds,
+        )?
+        .ok_or_else(|| format!("{key}.path is required for source = \"file\""))?;
+        inputs.push(SentimentInput {
+            name: name.to_string(),
+            query,
+            weight,
+            lag_seconds,
+        });
+    }
+    Ok(Some(inputs))
+}
+
+// A run's sentiment once every input is loaded: the points the run aligns with
+// `features.sentiment_lag`, a report with the quality counts summed over the inputs (and the
+// merged schema and time range), and each input's own report.
+#[derive(Debug, Clone)]
+pub struct LoadedSentiment {
+    pub points: Vec<SentimentPoint>,
+    pub report: SentimentReport,
+    pub sources: Vec<LoadedSentimentSource>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LoadedSentimentSource {
+    pub name: String,
+    pub source: String,
+    pub weight: f64,
+    pub lag_seconds: i64,
+    pub rows: usize,
+    pub report: SentimentReport,
+}
+
+// Loads every input and merges them with `merge_weighted`. A single input on the base lag is
+// used as loaded.
+pub fn load_sentiment(
+    config: &Config,
+    inputs: &[SentimentInput],
+    sentiment_repo: &dyn SentimentRepository,
+) -> Result<LoadedSentiment, RunError> {
+    let base_lag =
+        parse_duration_like(&config.features.sentiment_lag).map_err(ConfigError::Invalid)?;
+    let mut loaded = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        let (points, report) =
+            sentiment_repo
+                .load_sentiment(&input.query)
+                .map_err(|err| match inputs.len() {
+                    1 => RunError::Data(err),
+                    _ => RunError::Data(format!("sentiment source {}: {err}", input.name)),
+                })?;
+        loaded.push((points, report));
+    }
+
+    let (points, report) = match loaded.as_slice() {
+        [(points, report)] if inputs[0].lag_seconds == base_lag => (points.clone(), report.clone()),
+        _ => {
+            let weighted: Vec<WeightedSentiment<'_>> = inputs
+                .iter()
+                .zip(&loaded)
+                .map(|(input, (points, report))| WeightedSentiment {
+                    points,
+                    schema: &report.schema,
+                    weight: input.weight,
+                    lag_seconds: input.lag_seconds,
+                })
+                .collect();
+            let (points, schema) = merge_weighted(&weighted, base_lag);
+            let reports = || loaded.iter().map(|(_, report)| report);
+            let report = SentimentReport {
+                duplicates: reports().map(|report| report.duplicates).sum(),
+                out_of_order: reports().map(|report| report.out_of_order).sum(),
+                missing_values: reports().map(|report| report.missing_values).sum(),
+                invalid_values: reports().map(|report| report.invalid_values).sum(),
+                dropped_rows: reports().map(|report| report.dropped_rows).sum(),
+                first_timestamp: points.first().map(|point| point.timestamp),
+                last_timestamp: points.last().map(|point| point.timestamp),
+                first_duplicate: reports().filter_map(|report| report.first_duplicate).min(),
+                first_out_of_order: reports()
+                    .filter_map(|report| report.first_out_of_order)
+                    .min(),
+                schema,
+            };
+            (points, report)
+        }
+    };
+
+    let sources = inputs
+        .iter()
+        .zip(loaded)
+        .map(|(input, (points, report))| LoadedSentimentSource {
+            name: input.name.clone(),
+            source: input.query.source.describe(),
+            weight: input.weight,
+            lag_seconds: input.lag_seconds,
+            rows: points.len(),
+            report,
+        })
+        .collect();
+    Ok(LoadedSentiment {
+        points,
+        report,
+        sources,
+    })
+}
+
+// Per-input quality stats, for reports and timing events.
+pub fn sentiment_sources_json(sources: &[LoadedSentimentSource]) -> serde_json::Value {
+    serde_json::Value::Array(
+        sources
+            .iter()
+            .map(|source| {
+                serde_json::json!({
+                    "name": source.name,
+                    "source": source.source,
+                    "weight": source.weight,
+                    "lag_seconds": source.lag_seconds,
+                    "rows": source.rows,
+                    "duplicates": source.report.duplicates,
+                    "out_of_order": source.report.out_of_order,
+                    "missing_values": source.report.missing_values,
+                    "invalid_values": source.report.invalid_values,
+                    "dropped_rows": source.report.dropped_rows,
+                    "first_timestamp": source.report.first_timestamp,
+                    "last_timestamp": source.report.last_timestamp,
+                    "schema": source.report.schema,
+                })
+            })
+            .collect(),
+    )
+}
+
 pub fn resolve_sentiment_missing_policy(config: &Config) -> MissingValuePolicy {
     match config
         .features
@@ -165,6 +489,40 @@ pub fn resolve_sentiment_missing_policy(config: &Config) -> MissingValuePolicy {
     }
 }
 
+pub fn resolve_artifact_compression(
+    config: &Config,
+) -> Result<Option<ArtifactCompression>, String> {
+    match config
+        .report
+        .as_ref()
+        .and_then(|report| report.compression.as_deref())
+        .map(|s| s.trim().to_lowercase())
+        .as_deref()
+    {
+        None | Some("") | Some("none") => Ok(None),
+        Some("gzip") | Some("gz") => Ok(Some(ArtifactCompression::Gzip)),
+        Some("zstd") | Some("zst") => Ok(Some(ArtifactCompression::Zstd)),
+        Some(other) => Err(format!(
+            "invalid report.compression '{other}' (expected none|gzip|zstd)"
+        )),
+    }
+}
+
+// Last step of every run/report: compress the large artifacts (when enabled) and write
+// manifest.json, so the manifest always reflects the final on-disk files.
+pub fn finalize_run_dir(
+    run_dir: &Path,
+    compression: Option<ArtifactCompression>,
+    artifacts: &dyn ArtifactWriter,
+) -> Result<(), String> {
+    if let Some(compression) = compression {
+        for name in COMPRESSIBLE_ARTIFACTS {
+            artifacts.compress_artifact(run_dir.join(name).as_path(), compression)?;
+        }
+    }
+    artifacts.write_manifest(run_dir)
+}
+
 pub fn summary_meta_json_from_equity(
     config: &Config,
     equity: &[EquityPoint],
@@ -237,6 +595,8 @@ pub fn config_snapshot_json(
             "fallback_action": config.agent.fallback_action,
             "api_version": config.agent.api_version,
             "feature_version": config.agent.feature_version,
+            "max_calls": config.agent.max_calls,
+            "max_total_ms": config.agent.max_total_ms,
         },
         "data_quality": config.data_quality.as_ref().map(|dq| serde_json::json!({
             "max_gaps": dq.max_gaps,
@@ -247,14 +607,24 @@ pub fn config_snapshot_json(
             "max_sentiment_missing": dq.max_sentiment_missing,
             "max_sentiment_invalid": dq.max_sentiment_invalid,
             "max_sentiment_dropped": dq.max_sentiment_dropped,
+            "outlier_sigma": dq.outlier_sigma,
+            "zero_volume_streak_bars": dq.zero_volume_streak_bars,
+            "flat_price_streak_bars": dq.flat_price_streak_bars,
+            "max_return_outliers": dq.max_return_outliers,
+            "max_zero_volume_streaks": dq.max_zero_volume_streaks,
+            "max_flat_price_streaks": dq.max_flat_price_streaks,
+            "turnover_tolerance": dq.turnover_tolerance,
+            "max_zero_volume_moves": dq.max_zero_volume_moves,
+            "max_turnover_mismatches": dq.max_turnover_mismatches,
         })),
     })
 }
 
 #[cfg(test)]
 mod tests {
-    use super::resolve_execution_config;
-    use crate::config::{AgentMode, Config};
+    use super::{build_sentiment_query, resolve_execution_config};
+    use crate::config::{AgentMode, Config, SentimentSourceKind};
+    use kairos_domain::repositories::sentiment::SentimentSource;
 
     fn minimal_config_with_tif(tif: &str) -> Config {
         let toml_str = format!(
@@ -327,4 +697,36 @@ feature_version = "v1"
             kairos_domain::services::engine::execution::TimeInForce::Fok
         ));
     }
+
+    #[test]
+    fn build_sentiment_query_reads_db_table_from_lagged_start() {
+        let mut cfg = minimal_config_with_tif("gtc");
+        assert!(build_sentiment_query(&cfg).expect("query").is_none());
+
+        cfg.paths.sentiment_source = Some(SentimentSourceKind::Db);
+        cfg.run.start = Some("2024-01-01T00:00:00Z".to_string());
+        cfg.run.end = Some("1704153600".to_string());
+        cfg.features.sentiment_lag = "5m".to_string();
+        let query = build_sentiment_query(&cfg)
+            .expect("query")
+            .expect("db source");
+        match query.source {
+            SentimentSource::Db {
+                table,
+                symbol,
+                start,
+                end,
+            } => {
+                assert_eq!(table, "sentiment_scores");
+                assert_eq!(symbol, "BTC-USDT");
+                assert_eq!(start, Some(1_704_067_200 - 300));
+                assert_eq!(end, Some(1_704_153_600));
+            }
+            other => panic!("unexpected source {other:?}"),
+        }
+
+        cfg.run.end = Some("yesterday".to_string());
+        let err = build_sentiment_query(&cfg).expect_err("bad end");
+        assert!(err.starts_with("run.end: "), "{err}");
+    }
 }
@@ -1,22 +1,46 @@
-use crate::config::Config;
+use crate::config::{build_ohlcv_query, build_outlier_config, Config, ConfigError};
+use crate::error::RunError;
 use crate::shared::{
-    normalize_timeframe_label, parse_duration_like, resolve_sentiment_missing_policy,
+    build_sentiment_inputs, ensure_not_cancelled, load_ohlcv_with_control, load_sentiment,
+    normalize_timeframe_label, parse_duration_like, sentiment_sources_json,
 };
-use kairos_domain::repositories::market_data::{MarketDataRepository, OhlcvQuery};
-use kairos_domain::repositories::sentiment::{
-    SentimentFormat, SentimentQuery, SentimentRepository,
+use kairos_domain::repositories::market_data::MarketDataRepository;
+use kairos_domain::repositories::sentiment::SentimentRepository;
+use kairos_domain::services::engine::backtest::{NoopControl, RunControl};
+use kairos_domain::services::ohlcv::{
+    data_quality_from_bars, outliers_from_bars, resample_bars, DataQualityReport, OutlierConfig,
+    OutlierReport,
 };
-use kairos_domain::services::ohlcv::{data_quality_from_bars, resample_bars, DataQualityReport};
-use std::path::PathBuf;
+use kairos_domain::services::progress::{stage, ProgressEvent, ProgressSink};
 use std::time::Instant;
 use tracing::info_span;
 
+pub mod reconcile;
+
 pub fn validate(
     config: &Config,
     strict: bool,
     market_data: &dyn MarketDataRepository,
     sentiment_repo: &dyn SentimentRepository,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, RunError> {
+    validate_with_control(
+        config,
+        strict,
+        market_data,
+        sentiment_repo,
+        &NoopControl,
+        &mut |_event: ProgressEvent<'_>| {},
+    )
+}
+
+pub fn validate_with_control(
+    config: &Config,
+    strict: bool,
+    market_data: &dyn MarketDataRepository,
+    sentiment_repo: &dyn SentimentRepository,
+    control: &dyn RunControl,
+    progress: &mut dyn ProgressSink,
+) -> Result<serde_json::Value, RunError> {
     let _span = info_span!(
         "validate",
         strict = strict,
@@ -27,67 +51,64 @@ pub fn validate(
     .entered();
 
     let stage_start = Instant::now();
-    let expected_step = parse_duration_like(&config.run.timeframe)?;
-    let timeframe_label = normalize_timeframe_label(&config.run.timeframe)?;
+    let expected_step = parse_duration_like(&config.run.timeframe).map_err(ConfigError::Invalid)?;
+    let timeframe_label =
+        normalize_timeframe_label(&config.run.timeframe).map_err(ConfigError::Invalid)?;
     let source_timeframe_label = normalize_timeframe_label(
         config
             .db
             .source_timeframe
             .as_deref()
             .unwrap_or(&timeframe_label),
-    )?;
-    let source_step = parse_duration_like(&source_timeframe_label)?;
+    )
+    .map_err(ConfigError::Invalid)?;
+    let source_step = parse_duration_like(&source_timeframe_label).map_err(ConfigError::Invalid)?;
+    let outlier_config = build_outlier_config(config)?;
 
-    let (source_bars, source_report) = market_data.load_ohlcv(&OhlcvQuery {
-        exchange: config.db.exchange.to_lowercase(),
-        market: config.db.market.to_lowercase(),
-        symbol: config.run.symbol.clone(),
-        timeframe: source_timeframe_label.clone(),
-        expected_step_seconds: Some(source_step),
+    let query = build_ohlcv_query(config, &source_timeframe_label, source_step)?;
+    let (source_bars, source_report) = stage(progress, "load_ohlcv", || {
+        load_ohlcv_with_control(market_data, &query, control, "validate")
     })?;
     let source_rows = source_bars.len();
     metrics::histogram!("kairos.validate.load_ohlcv_ms")
         .record(stage_start.elapsed().as_millis() as f64);
 
-    let (ohlcv_report, ohlcv_source_report_json, effective_rows, resampled) =
+    let (ohlcv_report, outliers, ohlcv_source_report_json, effective_rows, resampled) =
         if source_timeframe_label != timeframe_label {
             if source_step > expected_step {
-                return Err(format!(
+                return Err(ConfigError::Invalid(format!(
                     "cannot resample OHLCV: source timeframe ({}) is larger than run timeframe ({})",
                     source_timeframe_label, timeframe_label
-                ));
+                ))
+                .into());
             }
-            let resampled_bars = resample_bars(&source_bars, expected_step)?;
-            let report = data_quality_from_bars(&resampled_bars, Some(expected_step));
+            let resampled_bars = stage(progress, "resample_ohlcv", || {
+                resample_bars(&source_bars, expected_step)
+            })?;
+            ensure_not_cancelled(control, "validate")?;
+            // Turnover is only known for the stored bars, so the resampled report keeps their count.
+            let mut report = data_quality_from_bars(&resampled_bars, Some(expected_step));
+            report.turnover_mismatches = source_report.turnover_mismatches;
+            report.first_turnover_mismatch = source_report.first_turnover_mismatch;
+            let outliers = outliers_from_bars(&resampled_bars, &outlier_config);
             (
                 report,
+                outliers,
                 Some(data_quality_json(&source_report, source_rows)),
                 resampled_bars.len(),
                 true,
             )
         } else {
-            (source_report, None, source_rows, false)
+            let outliers = outliers_from_bars(&source_bars, &outlier_config);
+            (source_report, outliers, None, source_rows, false)
         };
 
-    let (s_duplicates, s_out_of_order, s_missing, s_invalid, s_dropped, sentiment_schema) =
-        if let Some(path) = &config.paths.sentiment_path {
-            let path_buf = PathBuf::from(path);
-            let ext = path_buf
-                .extension()
-                .and_then(|s| s.to_str())
-                .unwrap_or("")
-                .to_lowercase();
-            let format = if ext == "json" {
-                SentimentFormat::Json
-            } else {
-                SentimentFormat::Csv
-            };
-            let missing_policy = resolve_sentiment_missing_policy(config);
-            let (_points, report) = sentiment_repo.load_sentiment(&SentimentQuery {
-                path: path_buf,
-                format,
-                missing_policy,
+    let (s_duplicates, s_out_of_order, s_missing, s_invalid, s_dropped, sentiment_schema, sources) =
+        if let Some(inputs) = build_sentiment_inputs(config).map_err(ConfigError::Invalid)? {
+            let loaded = stage(progress, "load_sentiment", || {
+                load_sentiment(config, &inputs, sentiment_repo)
             })?;
+            let report = loaded.report;
             (
                 report.duplicates,
                 report.out_of_order,
@@ -95,10 +116,12 @@ pub fn validate(
                 report.invalid_values,
                 report.dropped_rows,
                 report.schema,
+                sentiment_sources_json(&loaded.sources),
             )
         } else {
-            (0, 0, 0, 0, 0, Vec::new())
+            (0, 0, 0, 0, 0, Vec::new(), serde_json::json!([]))
         };
+    ensure_not_cancelled(control, "validate")?;
 
     let limits = config.data_quality.as_ref();
     let max_gaps = limits.and_then(|l| l.max_gaps).unwrap_or(0);
@@ -109,9 +132,15 @@ pub fn validate(
     let max_sentiment_missing = limits.and_then(|l| l.max_sentiment_missing).unwrap_or(0);
     let max_sentiment_invalid = limits.and_then(|l| l.max_sentiment_invalid).unwrap_or(0);
     let max_sentiment_dropped = limits.and_then(|l| l.max_sentiment_dropped).unwrap_or(0);
+    let max_return_outliers = limits.and_then(|l| l.max_return_outliers);
+    let max_zero_volume_streaks = limits.and_then(|l| l.max_zero_volume_streaks);
+    let max_flat_price_streaks = limits.and_then(|l| l.max_flat_price_streaks);
+    let max_zero_volume_moves = limits.and_then(|l| l.max_zero_volume_moves);
+    let max_turnover_mismatches = limits.and_then(|l| l.max_turnover_mismatches);
+    let over = |count: usize, limit: Option<usize>| limit.is_some_and(|limit| count > limit);
 
-    if strict
-        && (ohlcv_report.gaps > max_gaps
+    let exceeded = stage(progress, "check_limits", || {
+        ohlcv_report.gaps > max_gaps
             || ohlcv_report.gap_count > max_missing_bars
             || ohlcv_report.duplicates > max_duplicates
             || ohlcv_report.out_of_order > max_out_of_order
@@ -120,15 +149,32 @@ pub fn validate(
             || s_out_of_order > max_out_of_order
             || s_missing > max_sentiment_missing
             || s_invalid > max_sentiment_invalid
-            || s_dropped > max_sentiment_dropped)
-    {
-        return Err("strict validation failed: data quality limits exceeded".to_string());
+            || s_dropped > max_sentiment_dropped
+            || over(outliers.return_outliers, max_return_outliers)
+            || over(outliers.zero_volume_streaks, max_zero_volume_streaks)
+            || over(outliers.flat_price_streaks, max_flat_price_streaks)
+            || over(ohlcv_report.zero_volume_moves, max_zero_volume_moves)
+            || over(ohlcv_report.turnover_mismatches, max_turnover_mismatches)
+    });
+    if strict && exceeded {
+        return Err(RunError::StrictValidation(
+            "data quality limits exceeded".to_string(),
+        ));
     }
 
     metrics::gauge!("kairos.validate.ohlcv.gaps").set(ohlcv_report.gaps as f64);
     metrics::gauge!("kairos.validate.ohlcv.duplicates").set(ohlcv_report.duplicates as f64);
     metrics::gauge!("kairos.validate.ohlcv.out_of_order").set(ohlcv_report.out_of_order as f64);
     metrics::gauge!("kairos.validate.ohlcv.invalid_close").set(ohlcv_report.invalid_close as f64);
+    metrics::gauge!("kairos.validate.ohlcv.zero_volume_moves")
+        .set(ohlcv_report.zero_volume_moves as f64);
+    metrics::gauge!("kairos.validate.ohlcv.turnover_mismatches")
+        .set(ohlcv_report.turnover_mismatches as f64);
+    metrics::gauge!("kairos.validate.ohlcv.return_outliers").set(outliers.return_outliers as f64);
+    metrics::gauge!("kairos.validate.ohlcv.zero_volume_streaks")
+        .set(outliers.zero_volume_streaks as f64);
+    metrics::gauge!("kairos.validate.ohlcv.flat_price_streaks")
+        .set(outliers.flat_price_streaks as f64);
     metrics::gauge!("kairos.validate.sentiment.missing").set(s_missing as f64);
     metrics::gauge!("kairos.validate.sentiment.invalid").set(s_invalid as f64);
     metrics::gauge!("kairos.validate.sentiment.dropped").set(s_dropped as f64);
@@ -144,6 +190,7 @@ pub fn validate(
         }) } else { serde_json::Value::Null },
         "ohlcv_source": ohlcv_source_report_json,
         "ohlcv": data_quality_json(&ohlcv_report, effective_rows),
+        "ohlcv_outliers": outliers_json(&outliers, &outlier_config),
         "sentiment": {
             "duplicates": s_duplicates,
             "out_of_order": s_out_of_order,
@@ -151,6 +198,7 @@ pub fn validate(
             "invalid_values": s_invalid,
             "dropped_rows": s_dropped,
             "schema": sentiment_schema,
+            "sources": sources,
         },
         "limits": {
             "max_gaps": max_gaps,
@@ -161,6 +209,11 @@ pub fn validate(
             "max_sentiment_missing": max_sentiment_missing,
             "max_sentiment_invalid": max_sentiment_invalid,
             "max_sentiment_dropped": max_sentiment_dropped,
+            "max_return_outliers": max_return_outliers,
+            "max_zero_volume_streaks": max_zero_volume_streaks,
+            "max_flat_price_streaks": max_flat_price_streaks,
+            "max_zero_volume_moves": max_zero_volume_moves,
+            "max_turnover_mismatches": max_turnover_mismatches,
         },
         "strict": strict
     }))
@@ -182,5 +235,27 @@ fn data_quality_json(report: &DataQualityReport, rows: usize) -> serde_json::Val
         "first_invalid_close": report.first_invalid_close,
         "max_gap_seconds": report.max_gap_seconds,
         "gap_count": report.gap_count,
+        "zero_volume_moves": report.zero_volume_moves,
+        "first_zero_volume_move": report.first_zero_volume_move,
+        "turnover_mismatches": report.turnover_mismatches,
+        "first_turnover_mismatch": report.first_turnover_mismatch,
+        "gap_ranges": report.gap_ranges,
+    })
+}
+
+fn outliers_json(report: &OutlierReport, config: &OutlierConfig) -> serde_json::Value {
+    serde_json::json!({
+        "return_sigma": config.return_sigma,
+        "return_outliers": report.return_outliers,
+        "first_return_outlier": report.first_return_outlier,
+        "max_return_sigma": report.max_return_sigma,
+        "zero_volume_streak_bars": config.zero_volume_streak_bars,
+        "zero_volume_streaks": report.zero_volume_streaks,
+        "first_zero_volume_streak": report.first_zero_volume_streak,
+        "longest_zero_volume_streak": report.longest_zero_volume_streak,
+        "flat_price_streak_bars": config.flat_price_streak_bars,
+        "flat_price_streaks": report.flat_price_streaks,
+        "first_flat_price_streak": report.first_flat_price_streak,
+        "longest_flat_price_streak": report.longest_flat_price_streak,
     })
 }
@@ -0,0 +1,107 @@
+use crate::config::{build_ohlcv_query, Config, ConfigError};
+use crate::error::RunError;
+use crate::shared::{
+    ensure_not_cancelled, load_ohlcv_with_control, normalize_timeframe_label, parse_duration_like,
+};
+use kairos_domain::repositories::market_data::MarketDataRepository;
+use kairos_domain::services::engine::backtest::RunControl;
+use kairos_domain::services::ohlcv::{reconcile_bars, resample_bars, ReconciliationReport};
+use kairos_domain::services::progress::{stage, ProgressSink};
+use kairos_domain::value_objects::bar::Bar;
+use tracing::info_span;
+
+// `validate --reconcile-*`: loads `run.symbol` at the run timeframe from the configured source and
+// from `reference` (the same config pointed at another exchange, market or table) and compares
+// them bar by bar.
+pub fn reconcile_sources(
+    config: &Config,
+    reference: &Config,
+    market_data: &dyn MarketDataRepository,
+    reference_data: &dyn MarketDataRepository,
+    control: &dyn RunControl,
+    progress: &mut dyn ProgressSink,
+) -> Result<serde_json::Value, RunError> {
+    let _span = info_span!(
+        "reconcile",
+        symbol = %config.run.symbol,
+        exchange = %config.db.exchange,
+        reference_exchange = %reference.db.exchange
+    )
+    .entered();
+
+    let primary = stage(progress, "load_ohlcv", || {
+        load_run_bars(config, market_data, control)
+    })?;
+    let reference_bars = stage(progress, "load_reference_ohlcv", || {
+        load_run_bars(reference, reference_data, control)
+    })?;
+    ensure_not_cancelled(control, "validate")?;
+    let report = stage(progress, "reconcile", || {
+        reconcile_bars(&primary, &reference_bars)
+    });
+
+    Ok(serde_json::json!({
+        "primary": source_json(config),
+        "reference": source_json(reference),
+        "report": reconciliation_json(&report),
+    }))
+}
+
+fn load_run_bars(
+    config: &Config,
+    market_data: &dyn MarketDataRepository,
+    control: &dyn RunControl,
+) -> Result<Vec<Bar>, RunError> {
+    let expected_step = parse_duration_like(&config.run.timeframe).map_err(ConfigError::Invalid)?;
+    let timeframe_label =
+        normalize_timeframe_label(&config.run.timeframe).map_err(ConfigError::Invalid)?;
+    let source_timeframe_label = normalize_timeframe_label(
+        config
+            .db
+            .source_timeframe
+            .as_deref()
+            .unwrap_or(&timeframe_label),
+    )
+    .map_err(ConfigError::Invalid)?;
+    let source_step = parse_duration_like(&source_timeframe_label).map_err(ConfigError::Invalid)?;
+    if source_step > expected_step {
+        return Err(ConfigError::Invalid(format!(
+            "cannot resample OHLCV: source timeframe ({}) is larger than run timeframe ({})",
+            source_timeframe_label, timeframe_label
+        ))
+        .into());
+    }
+    let query = build_ohlcv_query(config, &source_timeframe_label, source_step)?;
+    let (bars, _) = load_ohlcv_with_control(market_data, &query, control, "validate")?;
+    if source_step == expected_step {
+        return Ok(bars);
+    }
+    Ok(resample_bars(&bars, expected_step)?)
+}
+
+fn source_json(config: &Config) -> serde_json::Value {
+    serde_json::json!({
+        "exchange": config.db.exchange,
+        "market": config.db.market,
+        "table": config.db.ohlcv_table,
+    })
+}
+
+fn reconciliation_json(report: &ReconciliationReport) -> serde_json::Value {
+    serde_json::json!({
+        "primary_rows": report.primary_rows,
+        "reference_rows": report.reference_rows,
+        "overlap_start": report.overlap_start,
+        "overlap_end": report.overlap_end,
+        "matched_bars": report.matched_bars,
+        "missing_in_primary": report.missing_in_primary,
+        "first_missing_in_primary": report.first_missing_in_primary,
+        "missing_in_reference": report.missing_in_reference,
+        "first_missing_in_reference": report.first_missing_in_reference,
+        "mean_divergence_bps": report.mean_divergence_bps,
+        "mean_abs_divergence_bps": report.mean_abs_divergence_bps,
+        "p95_abs_divergence_bps": report.p95_abs_divergence_bps,
+        "max_abs_divergence_bps": report.max_abs_divergence_bps,
+        "max_divergence_at": report.max_divergence_at,
+    })
+}
@@ -1,4 +1,7 @@
-use kairos_application::benchmarking::{run_bench, BenchMode};
+use kairos_application::benchmarking::{
+    compare_to_baseline, run_bench, run_bench_with, run_symbol_bench, BenchMemory, BenchMode,
+    BenchOptions, MemoryProbe,
+};
 
 #[test]
 fn run_bench_rejects_invalid_args() {
@@ -25,3 +28,106 @@ fn run_bench_features_does_not_trade_but_processes_bars() {
     assert_eq!(out.results.summary.trades, 0);
     assert_eq!(out.results.summary.bars_processed, 50);
 }
+
+#[test]
+fn run_symbol_bench_times_both_loads_and_trades_every_flip() {
+    assert!(run_symbol_bench(0, 60).is_err());
+    let out = run_symbol_bench(200, 60).expect("bench symbols");
+    assert_eq!(out.bars, 200);
+    assert!(out.intern_per_bar_ns > 0.0);
+    assert!(out.clone_per_bar_ns > 0.0);
+    assert!(out.trades >= 50, "{out:?}");
+}
+
+#[test]
+fn run_bench_agent_waits_for_every_simulated_call() {
+    let options = BenchOptions {
+        agent_latency_ms: 2.0,
+        ..BenchOptions::default()
+    };
+    let out = run_bench_with(20, 60, "agent", options).expect("bench agent");
+    assert_eq!(out.mode, BenchMode::Agent);
+    assert_eq!(out.bars_processed, 20);
+    assert!(out.elapsed_ms >= 40, "elapsed_ms={}", out.elapsed_ms);
+    assert!(out.results.summary.trades >= 1);
+
+    let negative = BenchOptions {
+        agent_latency_ms: -1.0,
+        ..BenchOptions::default()
+    };
+    assert!(run_bench_with(20, 60, "agent", negative).is_err());
+}
+
+#[test]
+fn compare_to_baseline_flags_drops_beyond_the_threshold() {
+    let out = run_bench(50, 60, "engine").expect("bench engine");
+    let faster = serde_json::json!({
+        "mode": "engine",
+        "bars_per_sec": out.bars_per_sec / 2.0,
+    });
+    let comparison = compare_to_baseline(&out, &faster, 10.0).expect("compare");
+    assert!(!comparison.regressed);
+    assert!((comparison.change_pct - 100.0).abs() < 1e-6);
+
+    let slower = serde_json::json!({ "mode": "engine", "bars_per_sec": out.bars_per_sec * 2.0 });
+    let comparison = compare_to_baseline(&out, &slower, 10.0).expect("compare");
+    assert!(comparison.regressed);
+    assert!((comparison.change_pct + 50.0).abs() < 1e-6);
+    assert!(
+        !compare_to_baseline(&out, &slower, 60.0)
+            .expect("compare")
+            .regressed
+    );
+
+    let other_mode = serde_json::json!({ "mode": "features", "bars_per_sec": 1.0 });
+    assert!(compare_to_baseline(&out, &other_mode, 10.0).is_err());
+    assert!(compare_to_baseline(&out, &serde_json::json!({}), 10.0).is_err());
+    assert!(compare_to_baseline(&out, &faster, -1.0).is_err());
+}
+
+struct FixedProbe {
+    started: bool,
+}
+
+impl MemoryProbe for FixedProbe {
+    fn start(&mut self) {
+        self.started = true;
+    }
+
+    fn finish(&mut self) -> BenchMemory {
+        BenchMemory {
+            peak_rss_bytes: Some(1_000),
+            allocations: Some(200),
+            ..BenchMemory::default()
+        }
+    }
+}
+
+#[test]
+fn compare_to_baseline_flags_memory_growth() {
+    let mut probe = FixedProbe { started: false };
+    let options = BenchOptions {
+        memory_probe: Some(&mut probe),
+        ..BenchOptions::default()
+    };
+    let out = run_bench_with(50, 60, "engine", options).expect("bench engine");
+    assert!(probe.started);
+    assert_eq!(out.memory.and_then(|memory| memory.allocations), Some(200));
+
+    let baseline = serde_json::json!({
+        "mode": "engine",
+        "bars_per_sec": out.bars_per_sec / 2.0,
+        "memory": { "peak_rss_bytes": 950, "allocations": 100, "peak_live_bytes": 10 },
+    });
+    let comparison = compare_to_baseline(&out, &baseline, 10.0).expect("compare");
+    assert!(comparison.regressed);
+    let fields: Vec<_> = comparison
+        .memory
+        .iter()
+        .map(|field| (field.field, field.regressed))
+        .collect();
+    assert_eq!(
+        fields,
+        vec![("peak_rss_bytes", false), ("allocations", true)]
+    );
+}
@@ -0,0 +1,394 @@
+use kairos_application::config::Config;
+use kairos_application::error::RunError;
+use kairos_application::live_trading::{resolve_live_settings, run_live_streaming_control};
+use kairos_domain::repositories::execution_venue::{
+    ExecutionVenue, VenueBalances, VenueError, VenueOrderRequest, VenueOrderState,
+};
+use kairos_domain::repositories::market_stream::{MarketEvent, MarketStream, StreamError};
+use kairos_domain::repositories::sentiment::{SentimentQuery, SentimentRepository};
+use kairos_domain::services::engine::backtest::NoopControl;
+use kairos_domain::services::progress::{on_bars, ProgressEvent};
+use kairos_domain::services::sentiment::{SentimentPoint, SentimentReport};
+use kairos_domain::value_objects::side::Side;
+use kairos_infrastructure::artifacts::FilesystemArtifactWriter;
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Reports a seed like the synthetic stream does, so it should reach summary.json.
+struct FakeStream {
+    events: Vec<MarketEvent>,
+    idx: usize,
+}
+
+const STREAM_SEED: u64 = 7;
+
+impl MarketStream for FakeStream {
+    fn next_event(&mut self) -> Result<MarketEvent, StreamError> {
+        let ev = self
+            .events
+            .get(self.idx)
+            .cloned()
+            .ok_or_else(|| StreamError::Disconnected("eof".to_string()))?;
+        self.idx += 1;
+        Ok(ev)
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(STREAM_SEED)
+    }
+}
+
+struct NoSentiment;
+
+impl SentimentRepository for NoSentiment {
+    fn load_sentiment(
+        &self,
+        _query: &SentimentQuery,
+    ) -> Result<(Vec<SentimentPoint>, SentimentReport), String> {
+        Ok((Vec::new(), SentimentReport::default()))
+    }
+}
+
+// Fills every market order at `fill_price` on the second status poll. `placement_timeouts`
+// accepted orders answer with a transport error, `placement_losses` placements fail the same way
+// without reaching the venue, and `status_outages` status polls fail too.
+struct FakeVenue {
+    quote: f64,
+    fill_price: f64,
+    orders: RefCell<Vec<VenueOrderRequest>>,
+    polls: Cell<u32>,
+    placement_timeouts: Cell<u32>,
+    placement_losses: Cell<u32>,
+    status_outages: Cell<u32>,
+}
+
+impl FakeVenue {
+    fn new(quote: f64, fill_price: f64) -> Self {
+        Self {
+            quote,
+            fill_price,
+            orders: RefCell::new(Vec::new()),
+            polls: Cell::new(0),
+            placement_timeouts: Cell::new(0),
+            placement_losses: Cell::new(0),
+            status_outages: Cell::new(0),
+        }
+    }
+}
+
+impl ExecutionVenue for FakeVenue {
+    fn name(&self) -> &str {
+        "fake"
+    }
+
+    fn balances(&self, _symbol: &str) -> Result<VenueBalances, String> {
+        Ok(VenueBalances {
+            base: 0.0,
+            quote: self.quote,
+        })
+    }
+
+    fn place_market_order(&self, order: &VenueOrderRequest) -> Result<String, VenueError> {
+        if self.placement_losses.get() > 0 {
+            self.placement_losses.set(self.placement_losses.get() - 1);
+            return Err(VenueError::Transport("connection refused".to_string()));
+        }
+        self.orders.borrow_mut().push(order.clone());
+        if self.placement_timeouts.get() > 0 {
+            self.placement_timeouts
+                .set(self.placement_timeouts.get() - 1);
+            return Err(VenueError::Transport("read timed out".to_string()));
+        }
+        Ok(format!("order-{}", self.orders.borrow().len()))
+    }
+
+    fn order_state(&self, order_id: &str) -> Result<VenueOrderState, VenueError> {
+        if self.status_outages.get() > 0 {
+            self.status_outages.set(self.status_outages.get() - 1);
+            return Err(VenueError::Transport("connection reset".to_string()));
+        }
+        self.polls.set(self.polls.get() + 1);
+        let filled = self.polls.get() >= 2;
+        let qty = self
+            .orders
+            .borrow()
+            .last()
+            .map(|o| o.quantity)
+            .unwrap_or(0.0);
+        Ok(VenueOrderState {
+            order_id: order_id.to_string(),
+            active: !filled,
+            filled_qty: if filled { qty } else { 0.0 },
+            filled_funds: if filled { qty * self.fill_price } else { 0.0 },
+            fee: if filled { 0.01 } else { 0.0 },
+        })
+    }
+
+    fn order_by_client_id(
+        &self,
+        client_order_id: &str,
+    ) -> Result<Option<VenueOrderState>, VenueError> {
+        let orders = self.orders.borrow();
+        let Some(idx) = orders
+            .iter()
+            .position(|o| o.client_order_id == client_order_id)
+        else {
+            return Ok(None);
+        };
+        Ok(Some(VenueOrderState {
+            order_id: format!("order-{}", idx + 1),
+            active: true,
+            filled_qty: 0.0,
+            filled_funds: 0.0,
+            fee: 0.0,
+        }))
+    }
+
+    fn cancel_order(&self, _order_id: &str) -> Result<(), VenueError> {
+        Err(VenueError::Rejected("nothing to cancel".to_string()))
+    }
+}
+
+const CONFIG_TEMPLATE: &str = r#"
+[run]
+run_id = "live_test"
+symbol = "BTC-USDT"
+timeframe = "60s"
+initial_capital = 1000.0
+
+[db]
+ohlcv_table = "ohlcv_candles"
+exchange = "kucoin"
+market = "spot"
+
+[paths]
+out_dir = "{out_dir}"
+
+[costs]
+fee_bps = 0.0
+slippage_bps = 0.0
+
+[risk]
+max_position_qty = 1.0
+max_drawdown_pct = 0.50
+max_exposure_pct = 1.0
+
+[orders]
+size_mode = "qty"
+
+[execution]
+model = "simple"
+latency_bars = 0
+buy_kind = "market"
+sell_kind = "market"
+price_reference = "close"
+limit_offset_bps = 0.0
+stop_offset_bps = 0.0
+spread_bps = 0.0
+max_fill_pct_of_volume = 1.0
+tif = "gtc"
+
+[features]
+return_mode = "log"
+sma_windows = [10]
+volatility_windows = [10]
+rsi_enabled = false
+sentiment_lag = "0s"
+sentiment_missing = "error"
+
+[strategy]
+baseline = "buy_and_hold"
+
+[metrics]
+risk_free_rate = 0.0
+annualization_factor = 365.0
+
+[agent]
+mode = "baseline"
+url = "http://127.0.0.1:8000"
+timeout_ms = 200
+retries = 0
+fallback_action = "HOLD"
+api_version = "v1"
+feature_version = "v1"
+[live]
+venue = "kucoin_sandbox"
+max_order_notional = 5.0
+max_bars = 3
+fill_timeout_ms = 1000
+poll_interval_ms = 1
+"#;
+
+fn config_for(out_dir: &str) -> (Config, String) {
+    let toml_str = CONFIG_TEMPLATE.replace("{out_dir}", out_dir);
+    (toml::from_str(&toml_str).expect("config parses"), toml_str)
+}
+
+fn temp_out_dir() -> std::path::PathBuf {
+    let suffix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    std::env::temp_dir().join(format!("kairos_live_{suffix}"))
+}
+
+// Bars close at 60, 120 and 180; `live.max_bars = 3` ends the session there. Returns the run and
+// the number of bars reported.
+fn run_three_bars(venue: &FakeVenue, out_dir: &Path) -> (Result<PathBuf, RunError>, usize) {
+    let (config, toml_str) = config_for(&out_dir.display().to_string());
+    let events: Vec<MarketEvent> = [(0, 10.0), (60, 11.0), (120, 12.0), (180, 13.0)]
+        .into_iter()
+        .map(|(timestamp, price)| MarketEvent::Tick { timestamp, price })
+        .collect();
+    let mut connect_stream = || {
+        Ok(Box::new(FakeStream {
+            events: events.clone(),
+            idx: 0,
+        }) as Box<dyn MarketStream>)
+    };
+    let mut bars = 0;
+
+    let run = run_live_streaming_control(
+        &config,
+        &toml_str,
+        None,
+        &mut connect_stream,
+        &NoSentiment,
+        &FilesystemArtifactWriter::new(),
+        None,
+        venue,
+        &NoopControl,
+        &mut on_bars(|_progress| bars += 1),
+        &mut |_status| {},
+    );
+    (run, bars)
+}
+
+#[test]
+fn live_session_sends_capped_orders_and_books_venue_fills() {
+    let out_dir = temp_out_dir();
+    let venue = FakeVenue::new(1000.0, 10.1);
+    let (run, bars) = run_three_bars(&venue, &out_dir);
+    let run_dir = run.expect("live run");
+    assert_eq!(bars, 3);
+
+    // Buy-and-hold asks for 1 BTC; `live.max_order_notional = 5` caps it at 0.5 @ 10.
+    let orders = venue.orders.borrow();
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].side, Side::Buy);
+    assert_eq!(orders[0].quantity, 0.5);
+    assert!(orders[0].client_order_id.starts_with("live_test-"));
+    assert!(orders[0].client_order_id.ends_with("-1"));
+
+    let trades = std::fs::read_to_string(run_dir.join("trades.csv")).expect("trades.csv");
+    assert_eq!(trades.lines().count(), 2, "{trades}");
+    assert!(trades.contains("10.1"), "{trades}");
+    let logs = std::fs::read_to_string(run_dir.join("logs.jsonl")).expect("logs.jsonl");
+    assert!(logs.contains("order_filled"));
+    let summary: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(run_dir.join("summary.json")).expect("summary.json"),
+    )
+    .expect("summary json");
+    assert_eq!(summary["reproducibility"]["seed"], STREAM_SEED);
+    let _ = std::fs::remove_dir_all(&out_dir);
+}
+
+#[test]
+fn live_session_books_an_order_accepted_before_a_transport_failure() {
+    let out_dir = temp_out_dir();
+    let venue = FakeVenue::new(1000.0, 10.1);
+    venue.placement_timeouts.set(1);
+    venue.status_outages.set(2);
+    let (run, _bars) = run_three_bars(&venue, &out_dir);
+    let run_dir = run.expect("live run");
+
+    // The timed-out placement is found by client order id and polled through two outages.
+    assert_eq!(venue.orders.borrow().len(), 1);
+    let trades = std::fs::read_to_string(run_dir.join("trades.csv")).expect("trades.csv");
+    assert_eq!(trades.lines().count(), 2, "{trades}");
+    let logs = std::fs::read_to_string(run_dir.join("logs.jsonl")).expect("logs.jsonl");
+    assert!(logs.contains("order_filled"), "{logs}");
+    assert!(!logs.contains("order_rejected"), "{logs}");
+    let _ = std::fs::remove_dir_all(&out_dir);
+}
+
+#[test]
+fn live_session_does_not_adopt_an_earlier_sessions_order() {
+    let venue = FakeVenue::new(1000.0, 10.1);
+    let first_out = temp_out_dir();
+    let (run, _bars) = run_three_bars(&venue, &first_out);
+    run.expect("first run");
+    assert_eq!(venue.orders.borrow().len(), 1);
+
+    // Same run_id, and the first placement never reaches the venue: the lookup must not find the
+    // first session's order under a reused client order id.
+    let second_out = temp_out_dir();
+    venue.placement_losses.set(u32::MAX);
+    let (run, _bars) = run_three_bars(&venue, &second_out);
+    let run_dir = run.expect("second run");
+
+    assert_eq!(venue.orders.borrow().len(), 1);
+    let trades = std::fs::read_to_string(run_dir.join("trades.csv")).expect("trades.csv");
+    assert_eq!(trades.lines().count(), 1, "{trades}");
+    let logs = std::fs::read_to_string(run_dir.join("logs.jsonl")).expect("logs.jsonl");
+    assert!(logs.contains("order_rejected"), "{logs}");
+    assert!(!logs.contains("order_filled"), "{logs}");
+    let _ = std::fs::remove_dir_all(&first_out);
+    let _ = std::fs::remove_dir_all(&second_out);
+}
+
+#[test]
+fn live_session_writes_artifacts_when_it_loses_track_of_an_order() {
+    let out_dir = temp_out_dir();
+    let venue = FakeVenue::new(1000.0, 10.1);
+    venue.status_outages.set(u32::MAX);
+    let (run, bars) = run_three_bars(&venue, &out_dir);
+    let err = run.expect_err("order lost");
+    assert!(matches!(err, RunError::Runtime(_)), "{err}");
+    assert!(
+        err.to_string().contains("lost track of live order"),
+        "{err}"
+    );
+    assert_eq!(bars, 1);
+
+    let run_dir = out_dir.join("live_test");
+    assert!(run_dir.join("trades.csv").exists());
+    assert!(run_dir.join("summary.json").exists());
+    let logs = std::fs::read_to_string(run_dir.join("logs.jsonl")).expect("logs.jsonl");
+    assert!(logs.contains("order_lost"), "{logs}");
+    let _ = std::fs::remove_dir_all(&out_dir);
+}
+
+#[test]
+fn live_session_is_guarded_by_config_and_account_balance() {
+    let (mut config, toml_str) = config_for("runs/");
+    let venue = FakeVenue::new(100.0, 10.0);
+    let mut connect_stream = || Err::<Box<dyn MarketStream>, String>("offline".to_string());
+    let err = run_live_streaming_control(
+        &config,
+        &toml_str,
+        None,
+        &mut connect_stream,
+        &NoSentiment,
+        &FilesystemArtifactWriter::new(),
+        None,
+        &venue,
+        &NoopControl,
+        &mut |_event: ProgressEvent<'_>| {},
+        &mut |_status| {},
+    )
+    .expect_err("initial_capital exceeds the account");
+    assert!(matches!(err, RunError::Runtime(_)), "{err}");
+    assert!(
+        err.to_string().contains("less than run.initial_capital"),
+        "{err}"
+    );
+
+    config.live.as_mut().unwrap().venue = "binance".to_string();
+    let err = resolve_live_settings(&config).expect_err("unknown venue");
+    assert!(err.to_string().starts_with("live.venue must be"), "{err}");
+    config.live = None;
+    let err = resolve_live_settings(&config).expect_err("no [live] section");
+    assert!(err.to_string().contains("[live]"), "{err}");
+}
@@ -1,7 +1,7 @@
 use kairos_infrastructure::artifacts::FilesystemArtifactWriter;
 use kairos_infrastructure::persistence::postgres_ohlcv::PostgresMarketDataRepository;
 use kairos_infrastructure::sentiment::FilesystemSentimentRepository;
-use kairos_ingest::{ingest_kucoin, migrate_db, Market};
+use kairos_ingest::{ingest_kucoin, migrate_db, stderr_progress, InsertMode, Market};
 use std::fs;
 use std::io::{Read, Write};
 use std::net::{TcpListener, TcpStream};
@@ -273,7 +273,10 @@ fn prd20_e2e_ingest_then_backtest_csv_sentiment() {
             "mock",
             0,
             100,
+            InsertMode::default(),
             Some(&server.base_url),
+            false,
+            &mut stderr_progress(),
         )
         .await
         .expect("ingest");
@@ -358,7 +361,10 @@ fn prd20_smoke_paper_json_sentiment() {
             "mock",
             0,
             100,
+            InsertMode::default(),
             Some(&server.base_url),
+            false,
+            &mut stderr_progress(),
         )
         .await
         .expect("ingest");
@@ -1,12 +1,21 @@
 use kairos_application::config::Config;
+use kairos_application::error::RunError;
+use kairos_domain::errors::AgentError;
+use kairos_domain::repositories::agent::AgentClient;
 use kairos_domain::repositories::artifacts::ArtifactWriter;
-use kairos_domain::repositories::market_stream::{MarketEvent, MarketStream, StreamError};
+use kairos_domain::repositories::market_stream::{
+    MarketEvent, MarketStream, MultiMarketStream, StreamError,
+};
 use kairos_domain::repositories::sentiment::{SentimentQuery, SentimentRepository};
+use kairos_domain::services::agent::{
+    ActionBatchRequest, ActionBatchResponse, ActionRequest, ActionResponse,
+};
 use kairos_domain::services::engine::backtest::RunControl;
+use kairos_domain::services::progress::on_bars;
 use kairos_domain::services::sentiment::{MissingValuePolicy, SentimentPoint, SentimentReport};
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 struct FakeStream {
     events: Vec<MarketEvent>,
@@ -30,14 +39,31 @@ impl MarketStream for FakeStream {
     }
 }
 
+struct FakeMultiStream {
+    events: Vec<(String, MarketEvent)>,
+    idx: usize,
+}
+
+impl MultiMarketStream for FakeMultiStream {
+    fn next_symbol_event(&mut self) -> Result<(String, MarketEvent), StreamError> {
+        let Some(ev) = self.events.get(self.idx).cloned() else {
+            return Err(StreamError::Disconnected("eof".to_string()));
+        };
+        self.idx += 1;
+        Ok(ev)
+    }
+}
+
 #[derive(Default)]
 struct NoopArtifacts {
     calls: AtomicU64,
+    live_records: AtomicU64,
+    checkpoints: AtomicU64,
 }
 
 impl ArtifactWriter for NoopArtifacts {
+    // The live journal creates the run dir up front, so directories are not counted as artifacts.
     fn ensure_dir(&self, _path: &Path) -> Result<(), String> {
-        self.calls.fetch_add(1, Ordering::Relaxed);
         Ok(())
     }
     fn write_trades_csv(
@@ -56,12 +82,29 @@ impl ArtifactWriter for NoopArtifacts {
         self.calls.fetch_add(1, Ordering::Relaxed);
         Ok(())
     }
+    fn write_trades_parquet(
+        &self,
+        _path: &Path,
+        _trades: &[kairos_domain::value_objects::trade::Trade],
+    ) -> Result<(), String> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+    fn write_equity_parquet(
+        &self,
+        _path: &Path,
+        _points: &[kairos_domain::value_objects::equity_point::EquityPoint],
+    ) -> Result<(), String> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
     fn write_summary_json(
         &self,
         _path: &Path,
         _summary: &kairos_domain::entities::metrics::MetricsSummary,
         _meta: Option<&serde_json::Value>,
         _config_snapshot: Option<&serde_json::Value>,
+        _reproducibility: Option<&kairos_domain::entities::run_summary::Reproducibility>,
     ) -> Result<(), String> {
         self.calls.fetch_add(1, Ordering::Relaxed);
         Ok(())
@@ -79,6 +122,15 @@ impl ArtifactWriter for NoopArtifacts {
         self.calls.fetch_add(1, Ordering::Relaxed);
         Ok(())
     }
+    fn write_summary_markdown(
+        &self,
+        _path: &Path,
+        _summary: &kairos_domain::entities::metrics::MetricsSummary,
+        _meta: Option<&serde_json::Value>,
+    ) -> Result<(), String> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
     fn write_dashboard_html(
         &self,
         _path: &Path,
@@ -90,6 +142,22 @@ impl ArtifactWriter for NoopArtifacts {
         self.calls.fetch_add(1, Ordering::Relaxed);
         Ok(())
     }
+    fn write_compare_html(
+        &self,
+        _path: &Path,
+        _comparison: &serde_json::Value,
+    ) -> Result<(), String> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+    fn write_sweep_index_html(
+        &self,
+        _path: &Path,
+        _index: &serde_json::Value,
+    ) -> Result<(), String> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
     fn write_audit_jsonl(
         &self,
         _path: &Path,
@@ -102,6 +170,39 @@ impl ArtifactWriter for NoopArtifacts {
         self.calls.fetch_add(1, Ordering::Relaxed);
         Ok(())
     }
+    fn compress_artifact(
+        &self,
+        _path: &Path,
+        _compression: kairos_domain::repositories::artifacts::ArtifactCompression,
+    ) -> Result<(), String> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+    fn write_manifest(&self, _run_dir: &Path) -> Result<(), String> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+    fn append_jsonl(&self, _path: &Path, records: &[serde_json::Value]) -> Result<(), String> {
+        self.live_records
+            .fetch_add(records.len() as u64, Ordering::Relaxed);
+        Ok(())
+    }
+    fn write_checkpoint_json(
+        &self,
+        _path: &Path,
+        _value: &serde_json::Value,
+    ) -> Result<(), String> {
+        self.checkpoints.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+    fn write_bundle(
+        &self,
+        _run_dir: &Path,
+        _out_path: &Path,
+        _format: kairos_domain::repositories::artifacts::BundleFormat,
+    ) -> Result<Vec<String>, String> {
+        Ok(Vec::new())
+    }
 }
 
 struct FakeSentimentRepo;
@@ -130,9 +231,7 @@ impl RunControl for CancelAfter {
     }
 }
 
-#[test]
-fn paper_realtime_can_cancel_without_writing_artifacts() {
-    let toml_str = r#"
+const CONFIG_TOML: &str = r#"
 [run]
 run_id = "rt_test"
 symbol = "BTC-USDT"
@@ -196,6 +295,10 @@ api_version = "v1"
 feature_version = "v1"
 "#;
 
+#[test]
+fn paper_realtime_can_cancel_without_writing_artifacts() {
+    let toml_str = CONFIG_TOML;
+
     let config: Config = toml::from_str(toml_str).expect("config parses");
     let artifacts = NoopArtifacts::default();
     let sentiment = FakeSentimentRepo;
@@ -235,7 +338,7 @@ feature_version = "v1"
     };
 
     let mut bars_seen = 0u64;
-    let mut progress = |p: kairos_domain::services::engine::backtest::BarProgress| {
+    let mut progress = on_bars(|p| {
         bars_seen += 1;
         if bars_seen >= 2 {
             cancel.store(true, Ordering::Relaxed);
@@ -243,7 +346,7 @@ feature_version = "v1"
         // Ensure progress is sane.
         assert!(p.close.is_finite());
         assert!(p.equity.is_finite());
-    };
+    });
 
     let mut status_calls = 0u64;
     let mut on_status = |_s: kairos_application::paper_trading::RealtimeStreamStatus| {
@@ -258,6 +361,7 @@ feature_version = "v1"
         &sentiment,
         &artifacts,
         None,
+        None,
         &control,
         &mut progress,
         &mut on_status,
@@ -265,14 +369,201 @@ feature_version = "v1"
     .expect_err("cancel should return error");
 
     assert!(
-        err.to_lowercase().contains("cancel"),
+        matches!(err, RunError::Cancelled(_)),
         "unexpected error: {err}"
     );
     assert!(bars_seen >= 2);
     assert!(status_calls > 0);
     assert_eq!(connect_calls, 1);
     assert_eq!(artifacts.calls.load(Ordering::Relaxed), 0);
+    // start + one equity point per processed bar + end(cancelled).
+    assert!(artifacts.live_records.load(Ordering::Relaxed) >= bars_seen + 2);
+    // The cancelled session stays resumable.
+    assert!(artifacts.checkpoints.load(Ordering::Relaxed) >= 1);
 
     // Keep compiler from warning about unused policy in this file on some configurations.
     let _ = MissingValuePolicy::Error;
 }
+
+struct PointsSentimentRepo(Vec<SentimentPoint>);
+
+impl SentimentRepository for PointsSentimentRepo {
+    fn load_sentiment(
+        &self,
+        _query: &SentimentQuery,
+    ) -> Result<(Vec<SentimentPoint>, SentimentReport), String> {
+        Ok((self.0.clone(), SentimentReport::default()))
+    }
+}
+
+struct RecordingAgent {
+    observations: Arc<Mutex<Vec<Vec<f64>>>>,
+}
+
+impl AgentClient for RecordingAgent {
+    fn act(&self, request: &ActionRequest) -> Result<ActionResponse, AgentError> {
+        self.observations
+            .lock()
+            .expect("observations")
+            .push(request.observation.clone());
+        Ok(ActionResponse {
+            action_type: "HOLD".to_string(),
+            size: 0.0,
+            confidence: None,
+            model_version: None,
+            latency_ms: None,
+            reason: None,
+        })
+    }
+
+    fn act_batch(&self, _request: &ActionBatchRequest) -> Result<ActionBatchResponse, AgentError> {
+        Ok(ActionBatchResponse { items: Vec::new() })
+    }
+}
+
+#[test]
+fn paper_realtime_feeds_remote_agent_online_features_and_sentiment() {
+    let toml_str = CONFIG_TOML
+        .replace("mode = \"baseline\"", "mode = \"remote\"")
+        .replace(
+            "out_dir = \"runs/\"",
+            "out_dir = \"runs/\"\nsentiment_path = \"sentiment.csv\"",
+        );
+    let config: Config = toml::from_str(&toml_str).expect("config parses");
+    let artifacts = NoopArtifacts::default();
+    let sentiment = PointsSentimentRepo(vec![SentimentPoint {
+        timestamp: 30,
+        values: vec![0.7],
+    }]);
+    let observations = Arc::new(Mutex::new(Vec::new()));
+    let agent = RecordingAgent {
+        observations: observations.clone(),
+    };
+
+    // Bars close at 70 (bar 0) and 130 (bar 60).
+    let events: Vec<MarketEvent> = [(0, 10.0), (10, 11.0), (70, 12.0), (80, 13.0), (130, 14.0)]
+        .into_iter()
+        .map(|(timestamp, price)| MarketEvent::Tick { timestamp, price })
+        .collect();
+    let mut connect_stream =
+        || Ok(Box::new(FakeStream::new(events.clone())) as Box<dyn MarketStream>);
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    let control = CancelAfter {
+        cancel: cancel.clone(),
+    };
+    let mut bars_seen = 0u64;
+    let mut progress = on_bars(|_p| {
+        bars_seen += 1;
+        if bars_seen >= 2 {
+            cancel.store(true, Ordering::Relaxed);
+        }
+    });
+
+    let err = kairos_application::paper_trading::run_paper_realtime_streaming_control(
+        &config,
+        &toml_str,
+        None,
+        &mut connect_stream,
+        &sentiment,
+        &artifacts,
+        Some(Box::new(agent)),
+        None,
+        &control,
+        &mut progress,
+        &mut |_s: kairos_application::paper_trading::RealtimeStreamStatus| {},
+    )
+    .expect_err("cancel should return error");
+    assert!(
+        matches!(err, RunError::Cancelled(_)),
+        "unexpected error: {err}"
+    );
+
+    // return + sma(10) + vol(10), plus the sentiment value once the point is past the bar.
+    let observations = observations.lock().expect("observations");
+    assert_eq!(observations.len(), 2);
+    assert_eq!(observations[0].len(), 3);
+    assert_eq!(observations[1].len(), 4);
+    assert_eq!(observations[1][3], 0.7);
+}
+
+#[test]
+fn paper_realtime_multi_symbol_routes_bars_and_reports_each_symbol() {
+    let toml_str = format!("{CONFIG_TOML}\n[paper]\nsymbols = [\"BTC-USDT\", \"ETH-USDT\"]\n");
+    let config: Config = toml::from_str(&toml_str).expect("config parses");
+    let artifacts = NoopArtifacts::default();
+
+    // Each symbol closes bars at 60 and 120; the unsubscribed symbol is ignored.
+    let events: Vec<(String, MarketEvent)> = [
+        ("BTC-USDT", 0, 100.0),
+        ("ETH-USDT", 5, 10.0),
+        ("XRP-USDT", 6, 1.0),
+        ("BTC-USDT", 60, 101.0),
+        ("ETH-USDT", 65, 11.0),
+        ("BTC-USDT", 120, 102.0),
+        ("ETH-USDT", 125, 12.0),
+    ]
+    .into_iter()
+    .map(|(symbol, timestamp, price)| (symbol.to_string(), MarketEvent::Tick { timestamp, price }))
+    .collect();
+    let mut connect_stream = || {
+        Ok(Box::new(FakeMultiStream {
+            events: events.clone(),
+            idx: 0,
+        }) as Box<dyn MultiMarketStream>)
+    };
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    let control = CancelAfter {
+        cancel: cancel.clone(),
+    };
+    let mut seen: Vec<(String, f64)> = Vec::new();
+    let mut progress = |symbol: &str, p: kairos_domain::services::engine::backtest::BarProgress| {
+        seen.push((symbol.to_string(), p.equity));
+        if seen.len() >= 4 {
+            cancel.store(true, Ordering::Relaxed);
+        }
+    };
+    let mut last_status = None;
+    let mut on_status = |s: kairos_application::paper_trading::RealtimeStreamStatus| {
+        last_status = Some(s);
+    };
+
+    let err = kairos_application::paper_trading::multi_symbol::run_paper_realtime_multi_control(
+        &config,
+        &toml_str,
+        None,
+        &mut connect_stream,
+        &FakeSentimentRepo,
+        &artifacts,
+        &mut || Ok(None),
+        Vec::new(),
+        &control,
+        &mut progress,
+        &mut on_status,
+    )
+    .expect_err("cancel should return error");
+    assert!(
+        matches!(err, RunError::Cancelled(_)),
+        "unexpected error: {err}"
+    );
+
+    let symbols: Vec<&str> = seen.iter().map(|(symbol, _)| symbol.as_str()).collect();
+    assert_eq!(symbols, ["BTC-USDT", "ETH-USDT", "BTC-USDT", "ETH-USDT"]);
+    // Each sleeve starts with half of run.initial_capital.
+    assert!(seen
+        .iter()
+        .all(|(_, equity)| (*equity - 500.0).abs() < 100.0));
+
+    let status = last_status.expect("status");
+    let per_symbol: Vec<(&str, Option<i64>)> = status
+        .symbols
+        .iter()
+        .map(|s| (s.symbol.as_str(), s.last_bar_timestamp))
+        .collect();
+    assert_eq!(per_symbol, [("BTC-USDT", Some(60)), ("ETH-USDT", Some(60))]);
+    assert_eq!(status.last_event_timestamp, Some(125));
+    assert_eq!(artifacts.calls.load(Ordering::Relaxed), 0);
+    // One session checkpoint per sleeve on cancel.
+    assert!(artifacts.checkpoints.load(Ordering::Relaxed) >= 2);
+}
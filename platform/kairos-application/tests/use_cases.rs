@@ -1,16 +1,24 @@
-use kairos_application::config::{AgentMode, Config};
-use kairos_domain::repositories::artifacts::{ArtifactReader, ArtifactWriter};
+use kairos_application::config::{AgentMode, Config, SentimentSourceConfig};
+use kairos_application::error::RunError;
+use kairos_application::paper_trading::clock::{Clock, ReplayPacing, SimulatedClock};
+use kairos_domain::repositories::artifacts::{
+    ArtifactCompression, ArtifactReader, ArtifactWriter, BundleFormat,
+};
 use kairos_domain::repositories::market_data::{MarketDataRepository, OhlcvQuery};
 use kairos_domain::repositories::sentiment::{SentimentQuery, SentimentRepository};
 use kairos_domain::services::audit::AuditEvent;
+use kairos_domain::services::engine::backtest::{CancellationToken, NoopControl, RunControl};
 use kairos_domain::services::ohlcv::DataQualityReport;
+use kairos_domain::services::progress::{on_bars, ProgressEvent};
 use kairos_domain::services::sentiment::{SentimentPoint, SentimentReport};
 use kairos_domain::value_objects::bar::Bar;
 use kairos_domain::value_objects::equity_point::EquityPoint;
 use kairos_domain::value_objects::side::Side;
 use kairos_domain::value_objects::trade::Trade;
-use std::cell::RefCell;
+use kairos_infrastructure::sentiment::FilesystemSentimentRepository;
+use std::cell::{Cell, RefCell};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 #[derive(Default)]
 struct FakeMarketDataRepo {
@@ -19,8 +27,15 @@ struct FakeMarketDataRepo {
 }
 
 impl MarketDataRepository for FakeMarketDataRepo {
-    fn load_ohlcv(&self, _query: &OhlcvQuery) -> Result<(Vec<Bar>, DataQualityReport), String> {
-        Ok((self.bars.clone(), self.report.clone()))
+    fn load_ohlcv(&self, query: &OhlcvQuery) -> Result<(Vec<Bar>, DataQualityReport), String> {
+        let bars = self
+            .bars
+            .iter()
+            .filter(|bar| query.contains(bar.timestamp))
+            .take(query.limit.map_or(usize::MAX, |limit| limit as usize))
+            .cloned()
+            .collect();
+        Ok((bars, self.report.clone()))
     }
 }
 
@@ -41,11 +56,20 @@ struct RecordingWriter {
     ensured_dirs: RefCell<Vec<PathBuf>>,
     trades_written: RefCell<Option<usize>>,
     equity_written: RefCell<Option<usize>>,
+    trades_parquet_written: RefCell<Option<usize>>,
+    equity_parquet_written: RefCell<Option<usize>>,
     summary_written: RefCell<Option<serde_json::Value>>,
     summary_html_written: RefCell<bool>,
+    summary_markdown_written: RefCell<bool>,
     dashboard_html_written: RefCell<bool>,
+    compare_html_written: RefCell<bool>,
     audit_written: RefCell<Option<usize>>,
     config_snapshot: RefCell<Option<String>>,
+    compressed: RefCell<Vec<(String, ArtifactCompression)>>,
+    manifest_written: RefCell<bool>,
+    appended_jsonl: RefCell<Vec<(PathBuf, serde_json::Value)>>,
+    checkpoints: RefCell<Vec<(PathBuf, serde_json::Value)>>,
+    bundles: RefCell<Vec<(PathBuf, PathBuf, BundleFormat)>>,
 }
 
 impl ArtifactWriter for RecordingWriter {
@@ -64,12 +88,23 @@ impl ArtifactWriter for RecordingWriter {
         Ok(())
     }
 
+    fn write_trades_parquet(&self, _path: &Path, trades: &[Trade]) -> Result<(), String> {
+        *self.trades_parquet_written.borrow_mut() = Some(trades.len());
+        Ok(())
+    }
+
+    fn write_equity_parquet(&self, _path: &Path, points: &[EquityPoint]) -> Result<(), String> {
+        *self.equity_parquet_written.borrow_mut() = Some(points.len());
+        Ok(())
+    }
+
     fn write_summary_json(
         &self,
         _path: &Path,
         summary: &kairos_domain::entities::metrics::MetricsSummary,
         meta: Option<&serde_json::Value>,
         config_snapshot: Option<&serde_json::Value>,
+        reproducibility: Option<&kairos_domain::entities::run_summary::Reproducibility>,
     ) -> Result<(), String> {
         let json = serde_json::json!({
             "summary": {
@@ -82,6 +117,7 @@ impl ArtifactWriter for RecordingWriter {
             },
             "meta": meta,
             "config_snapshot": config_snapshot,
+            "reproducibility": reproducibility,
         });
         *self.summary_written.borrow_mut() = Some(json);
         Ok(())
@@ -101,6 +137,16 @@ impl ArtifactWriter for RecordingWriter {
         Ok(())
     }
 
+    fn write_summary_markdown(
+        &self,
+        _path: &Path,
+        _summary: &kairos_domain::entities::metrics::MetricsSummary,
+        _meta: Option<&serde_json::Value>,
+    ) -> Result<(), String> {
+        *self.summary_markdown_written.borrow_mut() = true;
+        Ok(())
+    }
+
     fn write_dashboard_html(
         &self,
         _path: &Path,
@@ -113,6 +159,23 @@ impl ArtifactWriter for RecordingWriter {
         Ok(())
     }
 
+    fn write_sweep_index_html(
+        &self,
+        _path: &Path,
+        _index: &serde_json::Value,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn write_compare_html(
+        &self,
+        _path: &Path,
+        _comparison: &serde_json::Value,
+    ) -> Result<(), String> {
+        *self.compare_html_written.borrow_mut() = true;
+        Ok(())
+    }
+
     fn write_audit_jsonl(&self, _path: &Path, events: &[AuditEvent]) -> Result<(), String> {
         *self.audit_written.borrow_mut() = Some(events.len());
         Ok(())
@@ -122,6 +185,56 @@ impl ArtifactWriter for RecordingWriter {
         *self.config_snapshot.borrow_mut() = Some(contents.to_string());
         Ok(())
     }
+
+    fn compress_artifact(
+        &self,
+        path: &Path,
+        compression: ArtifactCompression,
+    ) -> Result<(), String> {
+        assert!(
+            !*self.manifest_written.borrow(),
+            "compression must happen before the manifest"
+        );
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        self.compressed.borrow_mut().push((name, compression));
+        Ok(())
+    }
+
+    fn write_manifest(&self, _run_dir: &Path) -> Result<(), String> {
+        *self.manifest_written.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn append_jsonl(&self, path: &Path, records: &[serde_json::Value]) -> Result<(), String> {
+        self.appended_jsonl.borrow_mut().extend(
+            records
+                .iter()
+                .map(|record| (path.to_path_buf(), record.clone())),
+        );
+        Ok(())
+    }
+
+    fn write_checkpoint_json(&self, path: &Path, value: &serde_json::Value) -> Result<(), String> {
+        self.checkpoints
+            .borrow_mut()
+            .push((path.to_path_buf(), value.clone()));
+        Ok(())
+    }
+
+    fn write_bundle(
+        &self,
+        run_dir: &Path,
+        out_path: &Path,
+        format: BundleFormat,
+    ) -> Result<Vec<String>, String> {
+        self.bundles
+            .borrow_mut()
+            .push((run_dir.to_path_buf(), out_path.to_path_buf(), format));
+        Ok(vec!["run/summary.json".to_string()])
+    }
 }
 
 #[derive(Default)]
@@ -129,6 +242,8 @@ struct FakeReader {
     trades: Vec<Trade>,
     equity: Vec<EquityPoint>,
     config_toml: Option<String>,
+    live_records: Vec<serde_json::Value>,
+    session: Option<serde_json::Value>,
 }
 
 impl ArtifactReader for FakeReader {
@@ -144,6 +259,24 @@ impl ArtifactReader for FakeReader {
         Ok(self.config_toml.clone())
     }
 
+    fn read_jsonl_from(
+        &self,
+        _path: &Path,
+        offset: u64,
+    ) -> Result<(Vec<serde_json::Value>, u64), String> {
+        // Offsets are record indices here; the real reader uses byte offsets.
+        let records = self
+            .live_records
+            .get(offset as usize..)
+            .unwrap_or_default()
+            .to_vec();
+        Ok((records, self.live_records.len() as u64))
+    }
+
+    fn read_checkpoint_json(&self, _path: &Path) -> Result<Option<serde_json::Value>, String> {
+        Ok(self.session.clone())
+    }
+
     fn exists(&self, _path: &Path) -> bool {
         true
     }
@@ -156,6 +289,9 @@ fn minimal_config() -> Config {
             symbol: "BTCUSD".to_string(),
             timeframe: "1m".to_string(),
             initial_capital: 1000.0,
+            start: None,
+            end: None,
+            max_bars: None,
         },
         db: kairos_application::config::DbConfig {
             url: None,
@@ -164,10 +300,14 @@ fn minimal_config() -> Config {
             market: "spot".to_string(),
             source_timeframe: None,
             pool_max_size: None,
+            cache_dir: None,
+            sentiment_table: None,
         },
         paths: kairos_application::config::PathsConfig {
             sentiment_path: None,
+            sentiment_source: None,
             out_dir: "runs/".to_string(),
+            sentiment_sources: None,
         },
         costs: kairos_application::config::CostsConfig {
             fee_bps: 0.0,
@@ -198,6 +338,8 @@ fn minimal_config() -> Config {
             fallback_action: kairos_domain::value_objects::action_type::ActionType::Hold,
             api_version: "v1".to_string(),
             feature_version: "v1".to_string(),
+            max_calls: None,
+            max_total_ms: None,
         },
         strategy: Some(kairos_application::config::StrategyConfig {
             baseline: "buy_and_hold".to_string(),
@@ -214,22 +356,47 @@ fn minimal_config() -> Config {
             max_sentiment_missing: Some(0),
             max_sentiment_invalid: Some(0),
             max_sentiment_dropped: Some(0),
+            outlier_sigma: None,
+            zero_volume_streak_bars: None,
+            flat_price_streak_bars: None,
+            max_return_outliers: None,
+            max_zero_volume_streaks: None,
+            max_flat_price_streaks: None,
+            turnover_tolerance: None,
+            max_zero_volume_moves: None,
+            max_turnover_mismatches: None,
         }),
         paper: Some(kairos_application::config::PaperConfig {
             replay_scale: Some(0),
+            checkpoint_every_bars: None,
+            symbols: None,
+            session_start: None,
+            session_end: None,
+            max_session_duration: None,
+            flatten_on_session_end: None,
+            simulator: None,
+        }),
+        live: None,
+        report: Some(kairos_application::config::ReportConfig {
+            html: Some(false),
+            parquet: None,
+            compression: None,
         }),
-        report: Some(kairos_application::config::ReportConfig { html: Some(false) }),
     }
 }
 
 #[test]
 fn run_backtest_writes_summary_and_snapshot() {
     let mut config = minimal_config();
-    config.report = Some(kairos_application::config::ReportConfig { html: Some(false) });
+    config.report = Some(kairos_application::config::ReportConfig {
+        html: Some(false),
+        parquet: None,
+        compression: None,
+    });
 
     let bars = vec![
         Bar {
-            symbol: "BTCUSD".to_string(),
+            symbol: "BTCUSD".into(),
             timestamp: 1,
             open: 10.0,
             high: 10.0,
@@ -238,7 +405,7 @@ fn run_backtest_writes_summary_and_snapshot() {
             volume: 10.0,
         },
         Bar {
-            symbol: "BTCUSD".to_string(),
+            symbol: "BTCUSD".into(),
             timestamp: 2,
             open: 10.0,
             high: 10.0,
@@ -247,7 +414,7 @@ fn run_backtest_writes_summary_and_snapshot() {
             volume: 10.0,
         },
         Bar {
-            symbol: "BTCUSD".to_string(),
+            symbol: "BTCUSD".into(),
             timestamp: 3,
             open: 10.0,
             high: 10.0,
@@ -285,6 +452,125 @@ fn run_backtest_writes_summary_and_snapshot() {
     let json = summary_json.as_ref().expect("summary json written");
     assert_eq!(json["summary"]["bars_processed"], 3);
     assert_eq!(json["meta"]["run_id"], "test_run");
+    let reproducibility = &json["reproducibility"];
+    assert_eq!(reproducibility["data"]["rows"], 3);
+    assert_eq!(reproducibility["data"]["first_timestamp"], 1);
+    assert_eq!(reproducibility["data"]["last_timestamp"], 3);
+    assert_eq!(
+        reproducibility["config_sha256"],
+        "f83185988e6470952b4268867f4ae19e1ce10da29b0aed16bc35e431fe093278"
+    );
+    assert!(reproducibility["git_sha"].is_string());
+    assert!(reproducibility["crate_version"].is_string());
+    assert!(reproducibility["seed"].is_null());
+    assert_eq!(*writer.trades_parquet_written.borrow(), None);
+    assert_eq!(*writer.equity_parquet_written.borrow(), None);
+}
+
+#[test]
+fn run_backtest_writes_parquet_when_enabled() {
+    let mut config = minimal_config();
+    config.report = Some(kairos_application::config::ReportConfig {
+        html: Some(false),
+        parquet: Some(true),
+        compression: None,
+    });
+
+    let bars = (1..=3)
+        .map(|ts| Bar {
+            symbol: "BTCUSD".into(),
+            timestamp: ts,
+            open: 10.0,
+            high: 10.0,
+            low: 10.0,
+            close: 10.0,
+            volume: 10.0,
+        })
+        .collect();
+    let market = FakeMarketDataRepo {
+        bars,
+        report: DataQualityReport::default(),
+    };
+    let sentiment = FakeSentimentRepo;
+    let writer = RecordingWriter::default();
+
+    kairos_application::backtesting::run_backtest(
+        &config,
+        "",
+        Some(std::env::temp_dir().join("kairos_app_tests")),
+        &market,
+        &sentiment,
+        &writer,
+        None,
+    )
+    .expect("run_backtest");
+
+    assert_eq!(
+        *writer.trades_parquet_written.borrow(),
+        *writer.trades_written.borrow()
+    );
+    assert_eq!(*writer.equity_parquet_written.borrow(), Some(3));
+}
+
+#[test]
+fn run_backtest_compresses_artifacts_and_always_writes_manifest() {
+    let bars: Vec<Bar> = (1..=3)
+        .map(|ts| Bar {
+            symbol: "BTCUSD".into(),
+            timestamp: ts,
+            open: 10.0,
+            high: 10.0,
+            low: 10.0,
+            close: 10.0,
+            volume: 10.0,
+        })
+        .collect();
+    let run = |compression: Option<&str>| {
+        let mut config = minimal_config();
+        config.report = Some(kairos_application::config::ReportConfig {
+            html: Some(false),
+            parquet: None,
+            compression: compression.map(|c| c.to_string()),
+        });
+        let market = FakeMarketDataRepo {
+            bars: bars.clone(),
+            report: DataQualityReport::default(),
+        };
+        let writer = RecordingWriter::default();
+        let result = kairos_application::backtesting::run_backtest(
+            &config,
+            "",
+            Some(std::env::temp_dir().join("kairos_app_tests")),
+            &market,
+            &FakeSentimentRepo,
+            &writer,
+            None,
+        );
+        (result, writer)
+    };
+
+    let (result, writer) = run(None);
+    result.expect("run_backtest");
+    assert!(writer.compressed.borrow().is_empty());
+    assert!(*writer.manifest_written.borrow());
+
+    let (result, writer) = run(Some("zstd"));
+    result.expect("run_backtest");
+    assert_eq!(
+        *writer.compressed.borrow(),
+        vec![
+            ("trades.csv".to_string(), ArtifactCompression::Zstd),
+            ("equity.csv".to_string(), ArtifactCompression::Zstd),
+            ("logs.jsonl".to_string(), ArtifactCompression::Zstd),
+        ]
+    );
+    assert!(*writer.manifest_written.borrow());
+
+    let (result, writer) = run(Some("lz4"));
+    let err = result.expect_err("invalid compression");
+    assert!(matches!(err, RunError::Config(_)), "{err}");
+    assert!(err.to_string().contains("report.compression"));
+    assert_eq!(*writer.trades_written.borrow(), None);
 }
 
 #[test]
@@ -294,7 +580,7 @@ fn run_backtest_rejects_negative_slippage() {
 
     let market = FakeMarketDataRepo {
         bars: vec![Bar {
-            symbol: "BTCUSD".to_string(),
+            symbol: "BTCUSD".into(),
             timestamp: 1,
             open: 10.0,
             high: 10.0,
@@ -317,7 +603,64 @@ fn run_backtest_rejects_negative_slippage() {
         None,
     )
     .expect_err("should fail");
-    assert!(err.contains("slippage_bps"));
+    assert!(matches!(err, RunError::Config(_)), "{err}");
+    assert!(err.to_string().contains("slippage_bps"));
+}
+
+#[test]
+fn run_backtest_loads_only_the_configured_time_range() {
+    let mut config = minimal_config();
+    config.run.start = Some("1970-01-01T00:02:00Z".to_string());
+    config.run.end = Some("600".to_string());
+    config.run.max_bars = Some(4);
+
+    let bars = (0..20)
+        .map(|i| Bar {
+            symbol: "BTCUSD".into(),
+            timestamp: i * 60,
+            open: 10.0,
+            high: 10.0,
+            low: 10.0,
+            close: 10.0,
+            volume: 10.0,
+        })
+        .collect();
+    let market = FakeMarketDataRepo {
+        bars,
+        report: DataQualityReport::default(),
+    };
+    let writer = RecordingWriter::default();
+
+    kairos_application::backtesting::run_backtest(
+        &config,
+        "",
+        Some(std::env::temp_dir().join("kairos_app_tests_range")),
+        &market,
+        &FakeSentimentRepo,
+        &writer,
+        None,
+    )
+    .expect("run_backtest");
+
+    let summary_json = writer.summary_written.borrow();
+    let json = summary_json.as_ref().expect("summary json written");
+    assert_eq!(json["summary"]["bars_processed"], 4);
+    assert_eq!(json["reproducibility"]["data"]["first_timestamp"], 120);
+    assert_eq!(json["reproducibility"]["data"]["last_timestamp"], 300);
+
+    config.run.end = Some("60".to_string());
+    let err = kairos_application::backtesting::run_backtest(
+        &config,
+        "",
+        Some(std::env::temp_dir().join("kairos_app_tests_range")),
+        &market,
+        &FakeSentimentRepo,
+        &writer,
+        None,
+    )
+    .expect_err("end before start");
+    assert!(matches!(err, RunError::Config(_)), "{err}");
+    assert!(err.to_string().starts_with("run.end"));
 }
 
 #[test]
@@ -334,7 +677,7 @@ fn validate_strict_fails_when_limits_exceeded() {
 
     let err = kairos_application::validation::validate(&config, true, &market, &sentiment)
         .expect_err("strict should fail");
-    assert!(err.contains("strict validation failed"));
+    assert!(matches!(err, RunError::StrictValidation(_)), "{err}");
 }
 
 #[test]
@@ -351,14 +694,227 @@ fn validate_strict_fails_when_missing_bars_exceeded() {
 
     let err = kairos_application::validation::validate(&config, true, &market, &sentiment)
         .expect_err("strict should fail");
-    assert!(err.contains("strict validation failed"));
+    assert!(matches!(err, RunError::StrictValidation(_)), "{err}");
+}
+
+#[test]
+fn validate_reports_outliers_and_enforces_their_limits_only_when_set() {
+    let mut config = minimal_config();
+    let bars = (0..50)
+        .map(|i| {
+            let close = match i {
+                25 => 20.0,
+                _ if i % 2 == 0 => 10.1,
+                _ => 9.9,
+            };
+            Bar {
+                symbol: "BTCUSD".into(),
+                timestamp: i * 60,
+                open: close,
+                high: close,
+                low: close,
+                close,
+                volume: 10.0,
+            }
+        })
+        .collect();
+    let market = FakeMarketDataRepo {
+        bars,
+        report: DataQualityReport::default(),
+    };
+
+    let report =
+        kairos_application::validation::validate(&config, true, &market, &FakeSentimentRepo)
+            .expect("outlier limits are opt-in");
+    assert_eq!(report["ohlcv_outliers"]["return_outliers"], 2);
+    assert_eq!(report["ohlcv_outliers"]["first_return_outlier"], 25 * 60);
+    assert_eq!(report["ohlcv_outliers"]["zero_volume_streaks"], 0);
+    assert!(report["limits"]["max_return_outliers"].is_null());
+
+    let dq = config.data_quality.as_mut().expect("data_quality");
+    dq.outlier_sigma = Some(3.0);
+    dq.max_return_outliers = Some(1);
+    let err = kairos_application::validation::validate(&config, true, &market, &FakeSentimentRepo)
+        .expect_err("strict should fail");
+    assert!(matches!(err, RunError::StrictValidation(_)), "{err}");
+
+    config
+        .data_quality
+        .as_mut()
+        .expect("data_quality")
+        .outlier_sigma = Some(0.0);
+    let err = kairos_application::validation::validate(&config, false, &market, &FakeSentimentRepo)
+        .expect_err("sigma must be positive");
+    assert!(matches!(err, RunError::Config(_)), "{err}");
+    assert!(err.to_string().starts_with("data_quality.outlier_sigma"));
+}
+
+#[test]
+fn validate_reports_volume_anomalies_and_enforces_their_limits_only_when_set() {
+    let mut config = minimal_config();
+    let market = FakeMarketDataRepo {
+        bars: Vec::new(),
+        report: DataQualityReport {
+            zero_volume_moves: 2,
+            first_zero_volume_move: Some(120),
+            turnover_mismatches: 1,
+            first_turnover_mismatch: Some(60),
+            ..DataQualityReport::default()
+        },
+    };
+
+    let report =
+        kairos_application::validation::validate(&config, true, &market, &FakeSentimentRepo)
+            .expect("volume limits are opt-in");
+    assert_eq!(report["ohlcv"]["zero_volume_moves"], 2);
+    assert_eq!(report["ohlcv"]["first_zero_volume_move"], 120);
+    assert_eq!(report["ohlcv"]["turnover_mismatches"], 1);
+
+    let dq = config.data_quality.as_mut().expect("data_quality");
+    dq.max_turnover_mismatches = Some(0);
+    let err = kairos_application::validation::validate(&config, true, &market, &FakeSentimentRepo)
+        .expect_err("strict should fail");
+    assert!(matches!(err, RunError::StrictValidation(_)), "{err}");
+
+    config
+        .data_quality
+        .as_mut()
+        .expect("data_quality")
+        .turnover_tolerance = Some(-0.1);
+    let err = kairos_application::validation::validate(&config, false, &market, &FakeSentimentRepo)
+        .expect_err("tolerance must not be negative");
+    assert!(matches!(err, RunError::Config(_)), "{err}");
+    assert!(err
+        .to_string()
+        .starts_with("data_quality.turnover_tolerance"));
+}
+
+#[test]
+fn validate_merges_weighted_sentiment_sources_and_reports_each_one() {
+    let dir = std::env::temp_dir().join("kairos_sentiment_sources_test");
+    std::fs::create_dir_all(&dir).expect("create dir");
+    let news = dir.join("news.csv");
+    let social = dir.join("social.csv");
+    std::fs::write(
+        &news,
+        "timestamp_utc,score\n2024-01-01T00:00:00Z,1.0\n2024-01-01T00:02:00Z,\n",
+    )
+    .expect("write news");
+    std::fs::write(
+        &social,
+        "timestamp_utc,score,volume\n2024-01-01T00:00:00Z,0.0,5\n2024-01-01T00:01:00Z,0.5,7\n",
+    )
+    .expect("write social");
+
+    let mut config = minimal_config();
+    config.features.sentiment_missing = Some("zero_fill".to_string());
+    let source = |name: &str, path: &Path, weight: f64, lag: &str| SentimentSourceConfig {
+        name: name.to_string(),
+        source: None,
+        path: Some(path.display().to_string()),
+        table: None,
+        weight: Some(weight),
+        lag: Some(lag.to_string()),
+    };
+    config.paths.sentiment_sources = Some(vec![
+        source("news", &news, 3.0, "1m"),
+        source("social", &social, 1.0, "0s"),
+    ]);
+    let sentiment = FilesystemSentimentRepository;
+    let market = FakeMarketDataRepo::default();
+
+    let report = kairos_application::validation::validate(&config, false, &market, &sentiment)
+        .expect("validate");
+    assert_eq!(
+        report["sentiment"]["schema"],
+        serde_json::json!(["score", "volume"])
+    );
+    assert_eq!(report["sentiment"]["missing_values"], 1);
+    let sources = report["sentiment"]["sources"].as_array().expect("sources");
+    assert_eq!(sources.len(), 2);
+    assert_eq!(sources[0]["name"], "news");
+    assert_eq!(sources[0]["weight"], 3.0);
+    assert_eq!(sources[0]["lag_seconds"], 60);
+    assert_eq!(sources[0]["rows"], 2);
+    assert_eq!(sources[0]["missing_values"], 1);
+    assert_eq!(sources[1]["name"], "social");
+    assert_eq!(sources[1]["missing_values"], 0);
+    assert_eq!(sources[1]["schema"], serde_json::json!(["score", "volume"]));
+
+    config.paths.sentiment_sources.as_mut().expect("sources")[1].weight = Some(0.0);
+    let err = kairos_application::validation::validate(&config, false, &market, &sentiment)
+        .expect_err("weight must be positive");
+    assert!(matches!(err, RunError::Config(_)), "{err}");
+    assert_eq!(
+        err.to_string(),
+        "paths.sentiment_sources[1].weight must be > 0"
+    );
+
+    config.paths.sentiment_sources.as_mut().expect("sources")[1].weight = None;
+    config.paths.sentiment_path = Some(news.display().to_string());
+    let err = kairos_application::validation::validate(&config, false, &market, &sentiment)
+        .expect_err("one way to configure sentiment");
+    assert!(
+        err.to_string()
+            .starts_with("paths.sentiment_sources cannot be combined"),
+        "{err}"
+    );
+}
+
+#[test]
+fn reconcile_sources_reports_divergence_and_missing_bars_between_exchanges() {
+    let config = minimal_config();
+    let mut reference = config.clone();
+    reference.db.exchange = "binance".to_string();
+    let bars = |timestamps: &[i64], close: f64| -> Vec<Bar> {
+        timestamps
+            .iter()
+            .map(|&timestamp| Bar {
+                symbol: "BTCUSD".into(),
+                timestamp,
+                open: close,
+                high: close,
+                low: close,
+                close,
+                volume: 1.0,
+            })
+            .collect()
+    };
+    let primary = FakeMarketDataRepo {
+        bars: bars(&[0, 60, 120, 180], 100.0),
+        report: DataQualityReport::default(),
+    };
+    let other = FakeMarketDataRepo {
+        bars: bars(&[0, 120, 180, 240], 100.5),
+        report: DataQualityReport::default(),
+    };
+
+    let json = kairos_application::validation::reconcile::reconcile_sources(
+        &config,
+        &reference,
+        &primary,
+        &other,
+        &NoopControl,
+        &mut |_event: ProgressEvent<'_>| {},
+    )
+    .expect("reconcile");
+    assert_eq!(json["reference"]["exchange"], "binance");
+    assert_eq!(json["report"]["matched_bars"], 3);
+    assert_eq!(json["report"]["missing_in_reference"], 1);
+    assert_eq!(json["report"]["first_missing_in_reference"], 60);
+    assert_eq!(json["report"]["missing_in_primary"], 0);
+    assert_eq!(json["report"]["overlap_end"], 180);
+    let mean = json["report"]["mean_divergence_bps"]
+        .as_f64()
+        .expect("mean");
+    assert!((mean + 49.751).abs() < 1e-3);
 }
 
 #[test]
 fn generate_report_writes_html_when_enabled() {
     let trades = vec![Trade {
         timestamp: 1,
-        symbol: "BTCUSD".to_string(),
+        symbol: "BTCUSD".into(),
         side: Side::Buy,
         quantity: 1.0,
         price: 100.0,
@@ -435,6 +991,8 @@ html = true
         trades,
         equity,
         config_toml: Some(config_toml.trim().to_string()),
+        live_records: Vec::new(),
+        session: None,
     };
     let writer = RecordingWriter::default();
 
@@ -447,6 +1005,157 @@ html = true
     assert!(result.wrote_html);
     assert!(*writer.summary_html_written.borrow());
     assert!(writer.audit_written.borrow().unwrap_or(0) >= 2);
+
+    // Selected outputs only, written next to the run instead of over it.
+    use kairos_application::reporting::{ReportOptions, ReportOutput};
+    let writer = RecordingWriter::default();
+    let out_dir = std::env::temp_dir().join("kairos_report_test_out");
+    let options = ReportOptions {
+        outputs: Some(vec![ReportOutput::Parquet, ReportOutput::Markdown]),
+        out_dir: Some(out_dir.clone()),
+    };
+    let result = kairos_application::reporting::generate_report_with(
+        tmp_dir.as_path(),
+        &options,
+        &reader,
+        &writer,
+    )
+    .expect("generate selected outputs");
+
+    assert_eq!(
+        result.outputs,
+        vec![ReportOutput::Markdown, ReportOutput::Parquet]
+    );
+    assert_eq!(result.out_dir, out_dir);
+    assert!(!result.wrote_html);
+    assert!(*writer.summary_markdown_written.borrow());
+    assert!(writer.trades_parquet_written.borrow().is_some());
+    assert!(writer.summary_written.borrow().is_none());
+    assert!(!*writer.summary_html_written.borrow());
+    assert_eq!(*writer.ensured_dirs.borrow(), vec![out_dir]);
+}
+
+#[test]
+fn watch_report_rebuilds_summary_from_live_journal() {
+    let live_records = vec![
+        serde_json::json!({"type": "start", "run_id": "live1", "symbol": "BTCUSD", "timeframe": "1m", "mode": "paper"}),
+        serde_json::json!({"type": "equity", "timestamp_utc": 60, "equity": 1000.0, "cash": 1000.0, "position_qty": 0.0}),
+        serde_json::json!({"type": "fill", "timestamp_utc": 120, "side": "BUY", "qty": 1.0, "price": 100.0, "fee": 0.1, "slippage": 0.0}),
+        serde_json::json!({"type": "equity", "timestamp_utc": 120, "equity": 999.9, "cash": 899.9, "position_qty": 1.0}),
+        serde_json::json!({"type": "end", "status": "cancelled"}),
+    ];
+    let reader = FakeReader {
+        live_records,
+        ..FakeReader::default()
+    };
+    let writer = RecordingWriter::default();
+
+    let mut ticks = Vec::new();
+    let result = kairos_application::reporting::watch::watch_report(
+        std::env::temp_dir().join("kairos_watch_test").as_path(),
+        &kairos_application::reporting::ReportOptions::default(),
+        &reader,
+        &writer,
+        std::time::Duration::from_millis(1),
+        &|| false,
+        &mut |tick| ticks.push((tick.tick, tick.new_records, tick.bars, tick.trades)),
+    )
+    .expect("watch report");
+
+    assert_eq!(result.run_id, "live1");
+    assert_eq!(result.summary.trades, 1);
+    assert_eq!(result.summary.bars_processed, 2);
+    assert_eq!(ticks, vec![(1, 5, 2, 1)]);
+    let summary = writer.summary_written.borrow().clone().expect("summary");
+    assert_eq!(summary["meta"]["run_id"], "live1");
+    assert_eq!(summary["meta"]["end"], 120);
+    assert!(*writer.dashboard_html_written.borrow());
+}
+
+#[test]
+fn annotate_run_appends_note_and_reads_it_back() {
+    let run_dir = Path::new("runs/ann1");
+    let writer = RecordingWriter::default();
+    let empty = FakeReader::default();
+
+    let err = kairos_application::annotations::annotate_run(run_dir, "   ", &empty, &writer)
+        .expect_err("blank note");
+    assert!(err.contains("must not be empty"));
+
+    let note = kairos_application::annotations::annotate_run(
+        run_dir,
+        "  widened stops after review ",
+        &empty,
+        &writer,
+    )
+    .expect("annotate");
+    assert_eq!(note.note, "widened stops after review");
+
+    let appended = writer.appended_jsonl.borrow();
+    assert_eq!(appended.len(), 1);
+    assert_eq!(appended[0].0, run_dir.join("notes.jsonl"));
+    assert_eq!(appended[0].1["note"], "widened stops after review");
+
+    let reader = FakeReader {
+        live_records: vec![appended[0].1.clone(), serde_json::json!({"unrelated": 1})],
+        ..FakeReader::default()
+    };
+    let notes = kairos_application::annotations::read_notes(run_dir, &reader).expect("notes");
+    assert_eq!(notes, vec![note]);
+}
+
+#[test]
+fn compare_runs_reports_zero_deltas_for_identical_runs() {
+    let equity = vec![
+        EquityPoint {
+            timestamp: 1,
+            equity: 100.0,
+            cash: 100.0,
+            position_qty: 0.0,
+            unrealized_pnl: 0.0,
+            realized_pnl: 0.0,
+        },
+        EquityPoint {
+            timestamp: 2,
+            equity: 90.0,
+            cash: 90.0,
+            position_qty: 0.0,
+            unrealized_pnl: 0.0,
+            realized_pnl: 0.0,
+        },
+    ];
+    let reader = FakeReader {
+        trades: Vec::new(),
+        equity,
+        config_toml: Some("[run]\nrun_id = \"cmp1\"\n".to_string()),
+        live_records: Vec::new(),
+        session: None,
+    };
+    let writer = RecordingWriter::default();
+
+    let out_dir = std::env::temp_dir().join("kairos_compare_test");
+    let result = kairos_application::reporting::compare::compare_runs(
+        Path::new("runs/a"),
+        Path::new("runs/b"),
+        &out_dir,
+        &reader,
+        &writer,
+    )
+    .expect("compare runs");
+
+    assert_eq!(result.run_a, "cmp1");
+    assert_eq!(result.run_b, "cmp1");
+    assert!(result.config_diff.is_empty());
+    assert_eq!(result.metrics.len(), 6);
+    assert!(result.metrics.iter().all(|m| m.delta() == 0.0));
+    let drawdown = result
+        .metrics
+        .iter()
+        .find(|m| m.name == "max_drawdown")
+        .expect("max_drawdown metric");
+    assert!(drawdown.a > 0.0);
+    assert!(*writer.compare_html_written.borrow());
+    assert_eq!(writer.ensured_dirs.borrow().as_slice(), &[out_dir]);
 }
 
 #[test]
@@ -454,13 +1163,24 @@ fn run_paper_writes_summary_and_snapshot_without_sleep() {
     let mut config = minimal_config();
     config.paper = Some(kairos_application::config::PaperConfig {
         replay_scale: Some(0),
+        checkpoint_every_bars: None,
+        symbols: None,
+        session_start: None,
+        session_end: None,
+        max_session_duration: None,
+        flatten_on_session_end: None,
+        simulator: None,
     });
     config.agent.mode = AgentMode::Baseline;
-    config.report = Some(kairos_application::config::ReportConfig { html: Some(false) });
+    config.report = Some(kairos_application::config::ReportConfig {
+        html: Some(false),
+        parquet: None,
+        compression: None,
+    });
 
     let bars = vec![
         Bar {
-            symbol: "BTCUSD".to_string(),
+            symbol: "BTCUSD".into(),
             timestamp: 1,
             open: 10.0,
             high: 10.0,
@@ -469,7 +1189,7 @@ fn run_paper_writes_summary_and_snapshot_without_sleep() {
             volume: 10.0,
         },
         Bar {
-            symbol: "BTCUSD".to_string(),
+            symbol: "BTCUSD".into(),
             timestamp: 2,
             open: 10.0,
             high: 10.0,
@@ -478,7 +1198,7 @@ fn run_paper_writes_summary_and_snapshot_without_sleep() {
             volume: 10.0,
         },
         Bar {
-            symbol: "BTCUSD".to_string(),
+            symbol: "BTCUSD".into(),
             timestamp: 3,
             open: 10.0,
             high: 10.0,
@@ -517,4 +1237,699 @@ fn run_paper_writes_summary_and_snapshot_without_sleep() {
     let json = summary_json.as_ref().expect("summary json written");
     assert_eq!(json["summary"]["bars_processed"], 3);
     assert_eq!(json["meta"]["run_id"], "test_run");
+
+    let appended = writer.appended_jsonl.borrow();
+    let live: Vec<_> = appended
+        .iter()
+        .filter(|(path, _)| path == &run_dir.join("live.jsonl"))
+        .collect();
+    assert!(appended.iter().all(|(path, _)| {
+        path == &run_dir.join("live.jsonl") || path == &run_dir.join("session_history.jsonl")
+    }));
+    let kinds: Vec<&str> = live
+        .iter()
+        .map(|(_, record)| record["type"].as_str().unwrap_or(""))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec!["start", "equity", "fill", "equity", "equity", "end"]
+    );
+    assert_eq!(live[0].1["mode"], "replay");
+    assert_eq!(live[2].1["side"], "BUY");
+    assert_eq!(live[4].1["timestamp_utc"], 3);
+    assert_eq!(live[5].1["status"], "completed");
+}
+
+struct StopFlag<'a>(&'a Cell<bool>);
+
+impl RunControl for StopFlag<'_> {
+    fn should_cancel(&self) -> bool {
+        self.0.get()
+    }
+}
+
+// Cancels its token while "loading", as if the user aborted during a slow query.
+struct CancelOnLoad(CancellationToken);
+
+impl MarketDataRepository for CancelOnLoad {
+    fn load_ohlcv(&self, _query: &OhlcvQuery) -> Result<(Vec<Bar>, DataQualityReport), String> {
+        self.0.cancel();
+        Ok((Vec::new(), DataQualityReport::default()))
+    }
+}
+
+#[test]
+fn use_cases_stop_when_cancelled_during_the_data_load() {
+    let config = minimal_config();
+    let token = CancellationToken::new();
+    let writer = RecordingWriter::default();
+    let err = kairos_application::backtesting::run_backtest_streaming_control(
+        &config,
+        "",
+        None,
+        &CancelOnLoad(token.clone()),
+        &FakeSentimentRepo,
+        &writer,
+        None,
+        &token,
+        &mut |_event: ProgressEvent<'_>| {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, RunError::Cancelled("backtest")), "{err}");
+    assert!(writer.summary_written.borrow().is_none());
+
+    let token = CancellationToken::new();
+    let err = kairos_application::validation::validate_with_control(
+        &config,
+        false,
+        &CancelOnLoad(token.clone()),
+        &FakeSentimentRepo,
+        &token,
+        &mut |_event: ProgressEvent<'_>| {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, RunError::Cancelled("validate")), "{err}");
+}
+
+#[test]
+fn dry_run_reports_the_scope_and_samples_without_writing_anything() {
+    let mut config = minimal_config();
+    config.run.timeframe = "5m".to_string();
+    config.db.source_timeframe = Some("1m".to_string());
+    let market = FakeMarketDataRepo {
+        bars: (0..20)
+            .map(|i| Bar {
+                symbol: "BTCUSD".into(),
+                timestamp: 1_700_000_100 + i * 60,
+                open: 10.0,
+                high: 10.0,
+                low: 10.0,
+                close: 10.0 + i as f64,
+                volume: 1.0,
+            })
+            .collect(),
+        report: DataQualityReport::default(),
+    };
+    let report = kairos_application::backtesting::dry_run::dry_run_backtest(
+        &config,
+        &market,
+        &FakeSentimentRepo,
+        None,
+        2,
+    )
+    .expect("dry run");
+    assert_eq!(report.rows, 20);
+    assert_eq!(report.first_ts, Some(1_700_000_100));
+    assert_eq!(report.last_ts, Some(1_700_001_240));
+    assert_eq!(report.source_timeframe, "1min");
+    // 2 bars at 5min come from the first 10 source bars.
+    assert_eq!(report.sample_rows, 2);
+    assert!(report.feature_count > 0);
+    assert_eq!(report.strategy, "buy_and_hold");
+    assert_eq!(
+        report.agent,
+        kairos_application::diagnostics::CheckStatus::Skip
+    );
+
+    let empty = FakeMarketDataRepo {
+        bars: Vec::new(),
+        report: DataQualityReport::default(),
+    };
+    let err = kairos_application::backtesting::dry_run::dry_run_backtest(
+        &config,
+        &empty,
+        &FakeSentimentRepo,
+        None,
+        2,
+    )
+    .unwrap_err();
+    assert!(matches!(err, RunError::Data(_)), "{err:?}");
+    assert!(
+        err.to_string().starts_with("no OHLCV rows for BTCUSD"),
+        "{err}"
+    );
+}
+
+#[test]
+fn run_backtest_reports_stages_and_bars_to_the_progress_sink() {
+    let config = minimal_config();
+    let bars = (1..=3)
+        .map(|timestamp| Bar {
+            symbol: "BTCUSD".into(),
+            timestamp,
+            open: 10.0,
+            high: 10.0,
+            low: 10.0,
+            close: 10.0,
+            volume: 10.0,
+        })
+        .collect();
+    let market = FakeMarketDataRepo {
+        bars,
+        report: DataQualityReport::default(),
+    };
+    let mut events = Vec::new();
+    kairos_application::backtesting::run_backtest_streaming(
+        &config,
+        "",
+        Some(std::env::temp_dir().join("kairos_app_tests")),
+        &market,
+        &FakeSentimentRepo,
+        &RecordingWriter::default(),
+        None,
+        &mut |event: ProgressEvent<'_>| {
+            events.push(match event {
+                ProgressEvent::StageStarted { stage } => format!("+{stage}"),
+                ProgressEvent::StageFinished { stage, .. } => format!("-{stage}"),
+                ProgressEvent::BarProcessed(bar) => format!("bar {}", bar.timestamp),
+                other => format!("{other:?}"),
+            })
+        },
+    )
+    .expect("run_backtest");
+    assert_eq!(
+        events,
+        vec![
+            "+load_ohlcv",
+            "-load_ohlcv",
+            "+align_sentiment",
+            "-align_sentiment",
+            "+run_engine",
+            "bar 1",
+            "bar 2",
+            "bar 3",
+            "-run_engine",
+            "+write_outputs",
+            "-write_outputs",
+        ]
+    );
+}
+
+#[test]
+fn run_paper_replay_paces_bars_on_the_clock_and_follows_speed_changes() {
+    let mut config = minimal_config();
+    config.paper = Some(kairos_application::config::PaperConfig {
+        replay_scale: Some(60),
+        checkpoint_every_bars: None,
+        symbols: None,
+        session_start: None,
+        session_end: None,
+        max_session_duration: None,
+        flatten_on_session_end: None,
+        simulator: None,
+    });
+    config.agent.mode = AgentMode::Hold;
+    let bars: Vec<Bar> = (1..=4)
+        .map(|i| Bar {
+            symbol: "BTCUSD".into(),
+            timestamp: i * 60,
+            open: 10.0,
+            high: 10.0,
+            low: 10.0,
+            close: 10.0,
+            volume: 10.0,
+        })
+        .collect();
+    let market = FakeMarketDataRepo {
+        bars,
+        report: DataQualityReport::default(),
+    };
+    let clock = Arc::new(SimulatedClock::default());
+    let pacing = ReplayPacing::from_config(&config).with_clock(clock.clone());
+    let speed = pacing.speed.clone();
+
+    // 1m bars at 60x take a simulated second each; after bar 2 the replay is sped up to 120x.
+    let mut played_at = Vec::new();
+    kairos_application::paper_trading::run_paper_streaming_control(
+        &config,
+        "[run]\nrun_id=\"test_run\"\n",
+        Some(std::env::temp_dir().join("kairos_app_paper_clock_tests")),
+        &market,
+        &FakeSentimentRepo,
+        &RecordingWriter::default(),
+        None,
+        None,
+        pacing,
+        &NoopControl,
+        &mut on_bars(|progress| {
+            played_at.push(clock.now().as_millis());
+            if progress.bar_index == 2 {
+                speed.set_scale(120.0);
+            }
+        }),
+    )
+    .expect("paced run");
+    assert_eq!(played_at, vec![1_000, 2_000, 2_500, 3_000]);
+}
+
+#[test]
+fn run_paper_stops_at_max_session_duration_and_flattens() {
+    let mut config = minimal_config();
+    config.paper = Some(kairos_application::config::PaperConfig {
+        replay_scale: Some(0),
+        checkpoint_every_bars: None,
+        symbols: None,
+        session_start: None,
+        session_end: None,
+        max_session_duration: Some("2m".to_string()),
+        flatten_on_session_end: None,
+        simulator: None,
+    });
+    config.agent.mode = AgentMode::Baseline;
+    let bars: Vec<Bar> = (1..=6)
+        .map(|i| Bar {
+            symbol: "BTCUSD".into(),
+            timestamp: i * 60,
+            open: 10.0,
+            high: 10.0,
+            low: 10.0,
+            close: 10.0,
+            volume: 10.0,
+        })
+        .collect();
+    let market = FakeMarketDataRepo {
+        bars,
+        report: DataQualityReport::default(),
+    };
+    let writer = RecordingWriter::default();
+    kairos_application::paper_trading::run_paper(
+        &config,
+        "[run]\nrun_id=\"test_run\"\n",
+        Some(std::env::temp_dir().join("kairos_app_paper_schedule_tests")),
+        &market,
+        &FakeSentimentRepo,
+        &writer,
+        None,
+    )
+    .expect("scheduled run");
+    let summary = writer.summary_written.borrow();
+    let summary = summary.as_ref().expect("summary json written");
+    assert_eq!(summary["summary"]["bars_processed"], 3);
+    // Bought on the first bar, filled on the second and flattened when the session expired.
+    assert_eq!(summary["summary"]["trades"], 2);
+
+    if let Some(paper) = config.paper.as_mut() {
+        paper.session_start = Some("09:00".to_string());
+    }
+    let err = kairos_application::paper_trading::run_paper(
+        &config,
+        "",
+        None,
+        &market,
+        &FakeSentimentRepo,
+        &writer,
+        None,
+    )
+    .expect_err("half a window");
+    assert!(
+        matches!(err, RunError::Config(_)) && err.to_string().starts_with("paper.session_start"),
+        "unexpected error: {err}"
+    );
+}
+
+#[test]
+fn run_paper_resumes_saved_session_instead_of_starting_flat() {
+    let mut config = minimal_config();
+    config.paper = Some(kairos_application::config::PaperConfig {
+        replay_scale: Some(0),
+        checkpoint_every_bars: Some(1),
+        symbols: None,
+        session_start: None,
+        session_end: None,
+        max_session_duration: None,
+        flatten_on_session_end: None,
+        simulator: None,
+    });
+    config.agent.mode = AgentMode::Baseline;
+    let bars: Vec<Bar> = (1..=4)
+        .map(|i| Bar {
+            symbol: "BTCUSD".into(),
+            timestamp: i,
+            open: 10.0 + i as f64,
+            high: 10.0 + i as f64,
+            low: 10.0 + i as f64,
+            close: 10.0 + i as f64,
+            volume: 10.0,
+        })
+        .collect();
+    let market = FakeMarketDataRepo {
+        bars,
+        report: DataQualityReport::default(),
+    };
+    let config_toml = "[run]\nrun_id=\"test_run\"\n";
+    let out_dir = std::env::temp_dir().join("kairos_app_paper_resume_tests");
+
+    // First process: stopped after two bars, as a crash or Ctrl-C would.
+    let crashed = RecordingWriter::default();
+    let stop = Cell::new(false);
+    let mut bars_seen = 0;
+    let err = kairos_application::paper_trading::run_paper_streaming_control(
+        &config,
+        config_toml,
+        Some(out_dir.clone()),
+        &market,
+        &FakeSentimentRepo,
+        &crashed,
+        None,
+        None,
+        ReplayPacing::from_config(&config),
+        &StopFlag(&stop),
+        &mut on_bars(|_progress| {
+            bars_seen += 1;
+            stop.set(bars_seen == 2);
+        }),
+    )
+    .expect_err("stopped run");
+    assert!(
+        matches!(err, RunError::Cancelled(_)),
+        "unexpected error: {err}"
+    );
+    assert!(crashed.summary_written.borrow().is_none());
+    let (path, saved) = crashed
+        .checkpoints
+        .borrow()
+        .last()
+        .cloned()
+        .expect("checkpoint");
+    assert_eq!(path, out_dir.join("test_run").join("session.json"));
+    assert_eq!(saved["mode"], "replay");
+    assert_eq!(saved["completed"], false);
+    assert!(saved["engine"].get("equity").is_none());
+
+    // Each checkpoint appended only what was new, so no history entry was written twice.
+    let history_path = out_dir.join("test_run").join("session_history.jsonl");
+    let mut history: Vec<serde_json::Value> = crashed
+        .appended_jsonl
+        .borrow()
+        .iter()
+        .filter(|(path, _)| path == &history_path)
+        .map(|(_, record)| record.clone())
+        .collect();
+    let equity_indices: Vec<_> = history
+        .iter()
+        .filter(|record| record["type"] == "equity")
+        .map(|record| record["index"].as_u64().unwrap())
+        .collect();
+    assert_eq!(equity_indices, vec![0, 1]);
+    // An entry appended after the last session.json (a crash between the two writes) is
+    // overwritten or dropped on load.
+    let mut stale = history[0].clone();
+    stale["index"] = serde_json::json!(2);
+    history.push(stale);
+
+    let reader = FakeReader {
+        session: Some(saved),
+        live_records: history,
+        ..FakeReader::default()
+    };
+    let session = kairos_application::paper_trading::session::load_paper_session(
+        &config,
+        Some(out_dir.clone()),
+        &reader,
+    )
+    .expect("load session")
+    .expect("session to resume");
+    assert_eq!(session.engine.bar_index(), 2);
+    assert_eq!(session.equity.len(), 2);
+    assert_eq!(session.trades.len(), 1);
+
+    // Second process: continues at bar 3 holding the position bought at bar 2.
+    let resumed = RecordingWriter::default();
+    let mut resumed_bars = Vec::new();
+    kairos_application::paper_trading::run_paper_streaming_control(
+        &config,
+        config_toml,
+        Some(out_dir.clone()),
+        &market,
+        &FakeSentimentRepo,
+        &resumed,
+        None,
+        Some(session),
+        ReplayPacing::from_config(&config),
+        &NoopControl,
+        &mut on_bars(|progress| resumed_bars.push((progress.bar_index, progress.timestamp))),
+    )
+    .expect("resumed run");
+    assert_eq!(resumed_bars, vec![(3, 3), (4, 4)]);
+    let summary = resumed.summary_written.borrow();
+    let summary = summary.as_ref().expect("summary json written");
+    assert_eq!(summary["summary"]["bars_processed"], 4);
+    assert_eq!(summary["summary"]["trades"], 1);
+
+    let (_, last) = resumed
+        .checkpoints
+        .borrow()
+        .last()
+        .cloned()
+        .expect("checkpoint");
+    assert_eq!(last["completed"], true);
+    let reader = FakeReader {
+        session: Some(last),
+        ..FakeReader::default()
+    };
+    let err = kairos_application::paper_trading::session::load_paper_session(
+        &config,
+        Some(out_dir),
+        &reader,
+    )
+    .expect_err("completed session");
+    assert!(err.contains("already completed"), "unexpected error: {err}");
+}
+
+#[test]
+fn export_run_defaults_archive_next_to_run_dir() {
+    let reader = FakeReader::default();
+    let writer = RecordingWriter::default();
+    let run_dir = Path::new("runs").join("demo_run");
+
+    let result = kairos_application::reporting::export::export_run(
+        &run_dir,
+        None,
+        BundleFormat::Parquet,
+        &reader,
+        &writer,
+    )
+    .expect("export_run");
+    assert_eq!(
+        result.archive,
+        Path::new("runs").join("demo_run.parquet.zip")
+    );
+    assert_eq!(result.entries, vec!["run/summary.json".to_string()]);
+    assert_eq!(
+        writer.bundles.borrow().as_slice(),
+        &[(
+            run_dir.clone(),
+            result.archive.clone(),
+            BundleFormat::Parquet
+        )]
+    );
+
+    let err = kairos_application::reporting::export::export_run(
+        &run_dir,
+        Some(&run_dir.join("bundle.zip")),
+        BundleFormat::Zip,
+        &reader,
+        &writer,
+    )
+    .unwrap_err();
+    assert!(matches!(err, RunError::Usage(_)), "{err:?}");
+    assert!(err.to_string().contains("outside the run dir"), "{err}");
+}
+
+#[test]
+fn montecarlo_writes_report_inside_run_dir_and_refreshes_manifest() {
+    let point = |timestamp: i64, equity: f64| EquityPoint {
+        timestamp,
+        equity,
+        cash: equity,
+        position_qty: 0.0,
+        unrealized_pnl: 0.0,
+        realized_pnl: 0.0,
+    };
+    let trade = |timestamp: i64, side: Side, price: f64| Trade {
+        timestamp,
+        symbol: "BTCUSD".into(),
+        side,
+        quantity: 1.0,
+        price,
+        fee: 0.0,
+        slippage: 0.0,
+        strategy_id: "test".to_string(),
+        reason: "test".to_string(),
+    };
+    let reader = FakeReader {
+        trades: vec![
+            trade(60, Side::Buy, 100.0),
+            trade(120, Side::Sell, 110.0),
+            trade(180, Side::Buy, 110.0),
+            trade(240, Side::Sell, 105.0),
+        ],
+        equity: vec![
+            point(60, 1000.0),
+            point(120, 1010.0),
+            point(180, 1010.0),
+            point(240, 1005.0),
+        ],
+        ..FakeReader::default()
+    };
+    let writer = RecordingWriter::default();
+    let run_dir = Path::new("runs").join("mc_run");
+    let config = kairos_domain::services::montecarlo::MonteCarloConfig {
+        samples: 200,
+        block: 2,
+        seed: 1,
+    };
+
+    let result = kairos_application::reporting::montecarlo::run_montecarlo(
+        &run_dir, None, config, &reader, &writer,
+    )
+    .expect("run_montecarlo");
+    assert_eq!(
+        result.output,
+        run_dir.join("analyzers").join("montecarlo.json")
+    );
+    assert_eq!(result.returns.observations, 3);
+    assert_eq!(result.returns.bands.steps, vec![0, 1, 2, 3]);
+    let trades = result.trades.expect("trade pnls");
+    assert_eq!(trades.observations, 2);
+    assert!((trades.observed.final_equity - 1005.0).abs() < 1e-9);
+    assert!(*writer.manifest_written.borrow());
+
+    let err = kairos_application::reporting::montecarlo::run_montecarlo(
+        &run_dir,
+        None,
+        kairos_domain::services::montecarlo::MonteCarloConfig {
+            samples: 0,
+            ..config
+        },
+        &reader,
+        &writer,
+    )
+    .unwrap_err();
+    assert!(matches!(err, RunError::Usage(_)), "{err:?}");
+    assert!(err.to_string().contains("--samples"), "{err}");
+}
+
+#[test]
+fn doctor_reports_db_coverage_and_skips_unconfigured_checks() {
+    use kairos_application::diagnostics::{run_doctor, CheckStatus};
+
+    let mut config = minimal_config();
+    config.paths.out_dir = std::env::temp_dir()
+        .join("kairos_doctor_test")
+        .display()
+        .to_string();
+    let bar = |timestamp: i64| Bar {
+        symbol: "BTCUSD".into(),
+        timestamp,
+        open: 10.0,
+        high: 10.0,
+        low: 10.0,
+        close: 10.0,
+        volume: 10.0,
+    };
+    let market = FakeMarketDataRepo {
+        bars: vec![bar(60), bar(120)],
+        report: DataQualityReport::default(),
+    };
+
+    let report = run_doctor(&config, Ok(&market), Ok(None), Ok(&FakeSentimentRepo));
+    let statuses: Vec<_> = report
+        .checks
+        .iter()
+        .map(|check| (check.name, check.status))
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            ("db", CheckStatus::Pass),
+            ("agent", CheckStatus::Skip),
+            ("out_dir", CheckStatus::Pass),
+            ("sentiment", CheckStatus::Skip),
+        ]
+    );
+    assert!(
+        report.checks[0].detail.starts_with("2 rows for BTCUSD 1m"),
+        "{}",
+        report.checks[0].detail
+    );
+    assert!(report.passed());
+
+    let empty = FakeMarketDataRepo::default();
+    let report = run_doctor(
+        &config,
+        Ok(&empty),
+        Err("unused".to_string()),
+        Ok(&FakeSentimentRepo),
+    );
+    assert_eq!(report.checks[0].status, CheckStatus::Fail);
+    assert!(report.checks[0].detail.contains("no rows"));
+    let report = run_doctor(
+        &config,
+        Err("missing db.url".to_string()),
+        Ok(None),
+        Ok(&FakeSentimentRepo),
+    );
+    assert_eq!(report.checks[0].detail, "missing db.url");
+    assert!(!report.passed());
+}
+
+#[test]
+fn run_chart_reloads_the_run_range_at_the_run_timeframe_with_its_trades() {
+    let run_dir = std::env::temp_dir().join("kairos_app_tests_chart");
+    let _ = std::fs::remove_dir_all(&run_dir);
+    std::fs::create_dir_all(&run_dir).expect("run dir");
+    std::fs::write(
+        run_dir.join("summary.json"),
+        r#"{"meta": {"run_id": "chart_run", "symbol": "BTCUSD", "timeframe": "5m", "start": 300, "end": 900},
+            "summary": {"net_profit": 1.0}}"#,
+    )
+    .expect("summary");
+    kairos_infrastructure::reporting::write_trades_csv(
+        &run_dir.join("trades.csv"),
+        &[Trade {
+            timestamp: 360,
+            symbol: "BTCUSD".into(),
+            side: Side::Buy,
+            quantity: 1.0,
+            price: 10.0,
+            fee: 0.0,
+            slippage: 0.0,
+            strategy_id: "sma".to_string(),
+            reason: "signal".to_string(),
+        }],
+    )
+    .expect("trades");
+
+    let mut config = minimal_config();
+    config.db.source_timeframe = Some("1m".to_string());
+    let market = FakeMarketDataRepo {
+        bars: (0..20)
+            .map(|i| Bar {
+                symbol: "BTCUSD".into(),
+                timestamp: i * 60,
+                open: 10.0 + i as f64,
+                high: 11.0 + i as f64,
+                low: 9.0 + i as f64,
+                close: 10.5 + i as f64,
+                volume: 1.0,
+            })
+            .collect(),
+        report: DataQualityReport::default(),
+    };
+    let chart = kairos_application::reporting::chart::load_run_chart(
+        &run_dir,
+        &config,
+        &market,
+        &kairos_infrastructure::artifacts::FilesystemArtifactReader::new(),
+    )
+    .expect("chart");
+
+    assert_eq!(chart.run_id, "chart_run");
+    assert_eq!(chart.timeframe, "5min");
+    let timestamps: Vec<i64> = chart.bars.iter().map(|bar| bar.timestamp).collect();
+    assert_eq!(timestamps, [300, 600, 900]);
+    assert_eq!(chart.bars[0].high, 20.0);
+    assert_eq!(chart.trades.len(), 1);
+    let _ = std::fs::remove_dir_all(&run_dir);
 }
@@ -0,0 +1,105 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use kairos_domain::entities::metrics::MetricsConfig;
+use kairos_domain::entities::risk::RiskLimits;
+use kairos_domain::services::engine::backtest::{BacktestRunner, OrderSizeMode};
+use kairos_domain::services::features::{FeatureBuilder, FeatureConfig, ReturnMode};
+use kairos_domain::services::market_data_source::VecBarSource;
+use kairos_domain::services::ohlcv::resample_bars;
+use kairos_domain::services::strategy::BuyAndHold;
+use kairos_domain::value_objects::bar::Bar;
+
+const BARS: usize = 10_000;
+
+fn synthetic_bars(count: usize) -> Vec<Bar> {
+    let mut price = 100.0f64;
+    (0..count)
+        .map(|i| {
+            let open = price;
+            let close = (price * (1.0 + ((i as f64) * 0.001).cos() * 0.01)).max(0.01);
+            price = close;
+            Bar {
+                symbol: "BENCH".into(),
+                timestamp: 1_700_000_000 + (i as i64) * 60,
+                open,
+                high: open.max(close) * 1.001,
+                low: open.min(close) * 0.999,
+                close,
+                volume: 1000.0,
+            }
+        })
+        .collect()
+}
+
+fn feature_builder_update(c: &mut Criterion) {
+    let bars = synthetic_bars(BARS);
+    let mut group = c.benchmark_group("feature_builder_update");
+    group.throughput(Throughput::Elements(bars.len() as u64));
+    group.bench_function("sma_vol_rsi", |b| {
+        b.iter_batched(
+            || {
+                FeatureBuilder::new(FeatureConfig {
+                    return_mode: ReturnMode::Log,
+                    sma_windows: vec![10, 50],
+                    volatility_windows: vec![10],
+                    rsi_enabled: true,
+                })
+            },
+            |mut builder| {
+                for bar in &bars {
+                    black_box(builder.update(bar, None));
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn backtest_runner_loop(c: &mut Criterion) {
+    let bars = synthetic_bars(BARS);
+    let mut group = c.benchmark_group("backtest_runner");
+    group.throughput(Throughput::Elements(bars.len() as u64));
+    group.bench_function("buy_and_hold", |b| {
+        b.iter_batched(
+            || {
+                BacktestRunner::new(
+                    "bench".to_string(),
+                    BuyAndHold::new(1.0),
+                    VecBarSource::new(bars.clone()),
+                    RiskLimits {
+                        max_position_qty: 0.0,
+                        max_drawdown_pct: 1.0,
+                        max_exposure_pct: 1.0,
+                    },
+                    10_000.0,
+                    MetricsConfig::default(),
+                    0.0,
+                    0.0,
+                    "BENCH".to_string(),
+                    OrderSizeMode::Quantity,
+                )
+            },
+            |mut runner| black_box(runner.run()),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn resample(c: &mut Criterion) {
+    let bars = synthetic_bars(BARS);
+    let mut group = c.benchmark_group("resample_bars");
+    group.throughput(Throughput::Elements(bars.len() as u64));
+    group.bench_function("1min_to_5min", |b| {
+        b.iter(|| resample_bars(black_box(&bars), 300))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    feature_builder_update,
+    backtest_runner_loop,
+    resample
+);
+criterion_main!(benches);
@@ -262,7 +262,7 @@ mod tests {
         let mut metrics = MetricsState::new(MetricsConfig::default());
         metrics.record_trade(Trade {
             timestamp: 1,
-            symbol: "BTCUSD".to_string(),
+            symbol: "BTCUSD".into(),
             side: Side::Buy,
             quantity: 1.0,
             price: 100.0,
@@ -273,7 +273,7 @@ mod tests {
         });
         metrics.record_trade(Trade {
             timestamp: 2,
-            symbol: "BTCUSD".to_string(),
+            symbol: "BTCUSD".into(),
             side: Side::Sell,
             quantity: 1.0,
             price: 120.0,
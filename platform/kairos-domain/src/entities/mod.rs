@@ -1,3 +1,5 @@
 pub mod metrics;
 pub mod portfolio;
 pub mod risk;
+pub mod run_note;
+pub mod run_summary;
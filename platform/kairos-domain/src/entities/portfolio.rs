@@ -21,6 +21,15 @@ impl Portfolio {
         Self::new_with_cash(0.0)
     }
 
+    // Rebuilds a portfolio from a saved session (see `EngineSnapshot`).
+    pub fn restore(cash: f64, realized_pnl: f64, positions: Vec<Position>) -> Self {
+        Self {
+            positions,
+            cash,
+            realized_pnl,
+        }
+    }
+
     pub fn positions(&self) -> &[Position] {
         &self.positions
     }
@@ -75,7 +84,7 @@ impl Portfolio {
                     }
                     None => {
                         self.positions.push(Position {
-                            symbol: symbol.to_string(),
+                            symbol: symbol.into(),
                             quantity,
                             avg_price: cost / quantity,
                         });
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+pub const RUN_NOTES_FILE_NAME: &str = "notes.jsonl";
+
+// Free-form annotation attached to a finished (or running) run, one JSON object per line in
+// `<run_dir>/notes.jsonl`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunNote {
+    pub created_at: String,
+    pub note: String,
+}
+
+impl RunNote {
+    // Lines that are blank or not a note are skipped, so a hand-edited file never hides the rest.
+    pub fn parse_jsonl(raw: &str) -> Vec<Self> {
+        raw.lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+}
@@ -0,0 +1,227 @@
+use crate::entities::metrics::MetricsSummary;
+use serde::{Deserialize, Deserializer, Serialize};
+
+// Bump when a field is removed/renamed or its meaning changes. Adding optional fields does not
+// require a bump. Files written before versioning existed read back as version 0.
+pub const SUMMARY_SCHEMA_VERSION: u32 = 1;
+
+// Every field is optional so that a summary written by an older build (or hand-edited) keeps the
+// fields it does have instead of losing the whole block to one missing or mistyped entry.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SummaryMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeframe: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<i64>,
+}
+
+impl SummaryMeta {
+    // `None` only when `value` is not an object; fields that are absent or of the wrong type are
+    // left unset individually.
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let object = value.as_object()?;
+        let text = |key: &str| {
+            object
+                .get(key)
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string)
+        };
+        let integer = |key: &str| object.get(key).and_then(serde_json::Value::as_i64);
+        Some(Self {
+            run_id: text("run_id"),
+            symbol: text("symbol"),
+            timeframe: text("timeframe"),
+            start: integer("start"),
+            end: integer("end"),
+        })
+    }
+}
+
+// Identifies the market data a run consumed: bar count, time range and a SHA-256 over the close
+// prices (little-endian f64 bytes, in bar order).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataFingerprint {
+    pub rows: u64,
+    pub first_timestamp: Option<i64>,
+    pub last_timestamp: Option<i64>,
+    pub close_sha256: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reproducibility {
+    pub git_sha: String,
+    pub crate_version: String,
+    pub config_sha256: String,
+    pub data: DataFingerprint,
+    // RNG seed the run depended on (simulated stream, seeded sweep search); `None` when nothing
+    // random fed it.
+    pub seed: Option<u64>,
+    pub feature_schema_sha256: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunSummary {
+    #[serde(default)]
+    pub schema_version: u32,
+    #[serde(default, deserialize_with = "lenient_meta")]
+    pub meta: Option<SummaryMeta>,
+    #[serde(default)]
+    pub config_snapshot: Option<serde_json::Value>,
+    #[serde(default)]
+    pub bars_processed: u64,
+    #[serde(default)]
+    pub trades: u64,
+    #[serde(default)]
+    pub win_rate: f64,
+    #[serde(default)]
+    pub net_profit: f64,
+    #[serde(default)]
+    pub sharpe: f64,
+    #[serde(default)]
+    pub max_drawdown: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reproducibility: Option<Reproducibility>,
+}
+
+impl RunSummary {
+    pub fn new(
+        summary: &MetricsSummary,
+        meta: Option<SummaryMeta>,
+        config_snapshot: Option<serde_json::Value>,
+        reproducibility: Option<Reproducibility>,
+    ) -> Self {
+        Self {
+            schema_version: SUMMARY_SCHEMA_VERSION,
+            meta,
+            config_snapshot,
+            bars_processed: summary.bars_processed as u64,
+            trades: summary.trades as u64,
+            win_rate: summary.win_rate,
+            net_profit: summary.net_profit,
+            sharpe: summary.sharpe,
+            max_drawdown: summary.max_drawdown,
+            reproducibility,
+        }
+    }
+
+    pub fn from_json_str(raw: &str) -> Result<Self, String> {
+        let value: serde_json::Value =
+            serde_json::from_str(raw).map_err(|err| format!("invalid summary json: {err}"))?;
+        Self::from_json(value)
+    }
+
+    // Accepts every summary.json layout ever written: the current versioned document, unversioned
+    // files (schema_version = 0) and the early layout that nested the metrics under "summary".
+    pub fn from_json(mut value: serde_json::Value) -> Result<Self, String> {
+        let object = value
+            .as_object_mut()
+            .ok_or_else(|| "summary json must be an object".to_string())?;
+        if let Some(serde_json::Value::Object(nested)) = object.remove("summary") {
+            for (key, val) in nested {
+                object.entry(key).or_insert(val);
+            }
+        }
+        let summary: Self =
+            serde_json::from_value(value).map_err(|err| format!("invalid summary json: {err}"))?;
+        if summary.schema_version > SUMMARY_SCHEMA_VERSION {
+            return Err(format!(
+                "unsupported summary schema_version {} (max supported: {})",
+                summary.schema_version, SUMMARY_SCHEMA_VERSION
+            ));
+        }
+        Ok(summary)
+    }
+}
+
+fn lenient_meta<'de, D>(deserializer: D) -> Result<Option<SummaryMeta>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(value.as_ref().and_then(SummaryMeta::from_json))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DataFingerprint, Reproducibility, RunSummary, SummaryMeta, SUMMARY_SCHEMA_VERSION,
+    };
+    use crate::entities::metrics::MetricsSummary;
+
+    #[test]
+    fn round_trips_current_schema() {
+        let metrics = MetricsSummary {
+            bars_processed: 10,
+            trades: 4,
+            win_rate: 0.5,
+            net_profit: 12.5,
+            sharpe: 1.1,
+            max_drawdown: 0.2,
+        };
+        let meta = SummaryMeta {
+            run_id: Some("run_a".to_string()),
+            symbol: Some("BTC-USDT".to_string()),
+            timeframe: Some("1min".to_string()),
+            start: Some(60),
+            end: Some(600),
+        };
+        let reproducibility = Reproducibility {
+            git_sha: "abc123".to_string(),
+            crate_version: "0.1.0".to_string(),
+            config_sha256: "c0ffee".to_string(),
+            data: DataFingerprint {
+                rows: 10,
+                first_timestamp: Some(60),
+                last_timestamp: Some(600),
+                close_sha256: "beef".to_string(),
+            },
+            seed: None,
+            feature_schema_sha256: "f00d".to_string(),
+        };
+        let summary = RunSummary::new(&metrics, Some(meta), None, Some(reproducibility));
+        let raw = serde_json::to_string(&summary).expect("serialize");
+        let parsed = RunSummary::from_json_str(&raw).expect("parse");
+        assert_eq!(parsed, summary);
+        assert_eq!(parsed.schema_version, SUMMARY_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn reads_legacy_layouts() {
+        let unversioned = RunSummary::from_json_str(
+            r#"{"meta": null, "config_snapshot": null, "bars_processed": 3, "trades": 1,
+                "win_rate": 1.0, "net_profit": 2.0, "sharpe": 0.5, "max_drawdown": 0.1}"#,
+        )
+        .expect("unversioned");
+        assert_eq!(unversioned.schema_version, 0);
+        assert_eq!(unversioned.trades, 1);
+        assert!(unversioned.meta.is_none());
+
+        let nested = RunSummary::from_json_str(
+            r#"{"meta": {"run_id": "x"}, "summary": {"net_profit": 7.0, "sharpe": 1.5}}"#,
+        )
+        .expect("nested");
+        assert_eq!(nested.net_profit, 7.0);
+        assert_eq!(nested.sharpe, 1.5);
+        let meta = nested.meta.expect("partial meta is kept");
+        assert_eq!(meta.run_id.as_deref(), Some("x"));
+        assert!(meta.timeframe.is_none());
+
+        let mistyped = RunSummary::from_json_str(
+            r#"{"meta": {"run_id": "y", "symbol": "ETH-USDT", "start": "60", "end": 600}}"#,
+        )
+        .expect("mistyped");
+        let meta = mistyped.meta.expect("mistyped field does not drop meta");
+        assert_eq!(meta.symbol.as_deref(), Some("ETH-USDT"));
+        assert_eq!(meta.start, None);
+        assert_eq!(meta.end, Some(600));
+
+        let future = format!(r#"{{"schema_version": {}}}"#, SUMMARY_SCHEMA_VERSION + 1);
+        assert!(RunSummary::from_json_str(&future).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use std::error::Error as StdError;
+
+// Errors raised by the engine and its building blocks (schedules, bar aggregation, resampling).
+// The messages are what the CLI prints.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum EngineError {
+    // The run's `RunControl` asked it to stop before the data ran out.
+    #[error("run cancelled")]
+    Cancelled,
+    #[error("{field} must be > 0")]
+    NonPositiveStep { field: &'static str },
+    #[error("{0}")]
+    InvalidSchedule(&'static str),
+    #[error("invalid time of day {0:?} (expected HH:MM)")]
+    InvalidTimeOfDay(String),
+    #[error(
+        "saved bar aggregator is for {saved_symbol} every {saved_step_seconds}s, not {symbol} every {step_seconds}s"
+    )]
+    AggregatorMismatch {
+        saved_symbol: String,
+        saved_step_seconds: i64,
+        symbol: String,
+        step_seconds: i64,
+    },
+}
+
+// Why an agent call produced no usable action. The strategy falls back on any of them; `kind` is
+// the label used in metrics and audit events.
+#[derive(Debug, thiserror::Error)]
+pub enum AgentError {
+    #[error("agent request failed: {0}")]
+    Transport(#[source] Box<dyn StdError + Send + Sync>),
+    #[error("agent http error: status {0}")]
+    HttpStatus(u16),
+    #[error("failed to parse agent {what}: {source}")]
+    Decode {
+        what: &'static str,
+        #[source]
+        source: Box<dyn StdError + Send + Sync>,
+    },
+    #[error("{0}")]
+    InvalidResponse(String),
+    #[error("agent batch size mismatch: expected {expected} items, got {actual}")]
+    BatchSizeMismatch { expected: usize, actual: usize },
+    #[error("agent unreachable at {endpoint}: {source}")]
+    Unreachable {
+        endpoint: String,
+        #[source]
+        source: Box<dyn StdError + Send + Sync>,
+    },
+    #[error("{endpoint} returned HTTP {status}")]
+    Unhealthy { endpoint: String, status: String },
+    #[error("agent request failed after retries")]
+    RetriesExhausted,
+    // Agents outside this crate's adapters (in-process models, test doubles).
+    #[error("{0}")]
+    Other(String),
+}
+
+impl AgentError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AgentError::Transport(_) | AgentError::Unreachable { .. } => "transport",
+            AgentError::HttpStatus(_) | AgentError::Unhealthy { .. } => "http_status",
+            AgentError::Decode { .. } => "decode",
+            AgentError::InvalidResponse(_) => "validate",
+            AgentError::BatchSizeMismatch { .. } => "batch_size_mismatch",
+            AgentError::RetriesExhausted | AgentError::Other(_) => "unknown",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AgentError, EngineError};
+    use std::error::Error;
+
+    #[test]
+    fn errors_keep_cli_messages_and_sources() {
+        let err = EngineError::NonPositiveStep {
+            field: "step_seconds",
+        };
+        assert_eq!(err.to_string(), "step_seconds must be > 0");
+
+        let io = std::io::Error::other("connection reset");
+        let err = AgentError::Transport(Box::new(io));
+        assert_eq!(err.to_string(), "agent request failed: connection reset");
+        assert_eq!(err.kind(), "transport");
+        assert_eq!(
+            err.source().map(|source| source.to_string()).as_deref(),
+            Some("connection reset")
+        );
+    }
+}
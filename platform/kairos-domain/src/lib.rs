@@ -1,5 +1,6 @@
 pub mod aggregates;
 pub mod entities;
+pub mod errors;
 pub mod events;
 pub mod repositories;
 pub mod services;
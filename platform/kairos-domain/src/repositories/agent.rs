@@ -1,9 +1,15 @@
+use crate::errors::AgentError;
 use crate::services::agent::{
     ActionBatchRequest, ActionBatchResponse, ActionRequest, ActionResponse,
 };
 
 pub trait AgentClient {
-    fn act(&self, request: &ActionRequest) -> Result<ActionResponse, String>;
+    fn act(&self, request: &ActionRequest) -> Result<ActionResponse, AgentError>;
 
-    fn act_batch(&self, request: &ActionBatchRequest) -> Result<ActionBatchResponse, String>;
+    fn act_batch(&self, request: &ActionBatchRequest) -> Result<ActionBatchResponse, AgentError>;
+
+    // Liveness probe; in-process agents are always reachable.
+    fn health_check(&self) -> Result<(), AgentError> {
+        Ok(())
+    }
 }
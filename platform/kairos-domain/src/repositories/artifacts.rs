@@ -1,19 +1,65 @@
 use crate::entities::metrics::MetricsSummary;
+use crate::entities::run_summary::Reproducibility;
 use crate::services::audit::AuditEvent;
 use crate::value_objects::equity_point::EquityPoint;
 use crate::value_objects::trade::Trade;
 use std::path::Path;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactCompression {
+    Gzip,
+    Zstd,
+}
+
+impl ArtifactCompression {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ArtifactCompression::Gzip => "gz",
+            ArtifactCompression::Zstd => "zst",
+        }
+    }
+}
+
+// Archive layout for `export`: `Zip` bundles the run dir as-is; `Parquet` also replaces
+// `trades.csv`/`equity.csv` (plain or compressed) with their Parquet equivalents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleFormat {
+    Zip,
+    Parquet,
+}
+
+impl BundleFormat {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        match raw.trim().to_lowercase().as_str() {
+            "zip" => Ok(BundleFormat::Zip),
+            "parquet" => Ok(BundleFormat::Parquet),
+            other => Err(format!(
+                "unsupported export format '{other}' (expected zip|parquet)"
+            )),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BundleFormat::Zip => "zip",
+            BundleFormat::Parquet => "parquet",
+        }
+    }
+}
+
 pub trait ArtifactWriter {
     fn ensure_dir(&self, path: &Path) -> Result<(), String>;
     fn write_trades_csv(&self, path: &Path, trades: &[Trade]) -> Result<(), String>;
     fn write_equity_csv(&self, path: &Path, points: &[EquityPoint]) -> Result<(), String>;
+    fn write_trades_parquet(&self, path: &Path, trades: &[Trade]) -> Result<(), String>;
+    fn write_equity_parquet(&self, path: &Path, points: &[EquityPoint]) -> Result<(), String>;
     fn write_summary_json(
         &self,
         path: &Path,
         summary: &MetricsSummary,
         meta: Option<&serde_json::Value>,
         config_snapshot: Option<&serde_json::Value>,
+        reproducibility: Option<&Reproducibility>,
     ) -> Result<(), String>;
     fn write_analyzer_json(&self, path: &Path, value: &serde_json::Value) -> Result<(), String>;
     fn write_summary_html(
@@ -22,6 +68,12 @@ pub trait ArtifactWriter {
         summary: &MetricsSummary,
         meta: Option<&serde_json::Value>,
     ) -> Result<(), String>;
+    fn write_summary_markdown(
+        &self,
+        path: &Path,
+        summary: &MetricsSummary,
+        meta: Option<&serde_json::Value>,
+    ) -> Result<(), String>;
     fn write_dashboard_html(
         &self,
         path: &Path,
@@ -30,13 +82,45 @@ pub trait ArtifactWriter {
         trades: &[Trade],
         equity: &[EquityPoint],
     ) -> Result<(), String>;
+    fn write_compare_html(&self, path: &Path, comparison: &serde_json::Value)
+        -> Result<(), String>;
+    fn write_sweep_index_html(&self, path: &Path, index: &serde_json::Value) -> Result<(), String>;
     fn write_audit_jsonl(&self, path: &Path, events: &[AuditEvent]) -> Result<(), String>;
     fn write_config_snapshot_toml(&self, path: &Path, contents: &str) -> Result<(), String>;
+    // Appends one JSON object per line to `path` (created if missing) and flushes before returning.
+    fn append_jsonl(&self, path: &Path, records: &[serde_json::Value]) -> Result<(), String>;
+    // Replaces `path` in one step (temp file + rename), so a crash never leaves it half written.
+    fn write_checkpoint_json(&self, path: &Path, value: &serde_json::Value) -> Result<(), String>;
+    // Replaces `path` with `<path>.<ext>`; a missing `path` is a no-op.
+    fn compress_artifact(
+        &self,
+        path: &Path,
+        compression: ArtifactCompression,
+    ) -> Result<(), String>;
+    // Writes `<run_dir>/manifest.json` listing every file in the run dir with size and SHA-256.
+    fn write_manifest(&self, run_dir: &Path) -> Result<(), String>;
+    // Writes a zip archive of `run_dir` (under a `<run_dir name>/` prefix, `manifest.json` included)
+    // and returns the archived entry names.
+    fn write_bundle(
+        &self,
+        run_dir: &Path,
+        out_path: &Path,
+        format: BundleFormat,
+    ) -> Result<Vec<String>, String>;
 }
 
 pub trait ArtifactReader {
     fn read_trades_csv(&self, path: &Path) -> Result<Vec<Trade>, String>;
     fn read_equity_csv(&self, path: &Path) -> Result<Vec<EquityPoint>, String>;
     fn read_config_snapshot_toml(&self, path: &Path) -> Result<Option<String>, String>;
+    // Reads the complete JSONL lines starting at byte `offset` and returns them with the offset just
+    // past the last complete line; a partially written trailing line is left for the next call.
+    fn read_jsonl_from(
+        &self,
+        path: &Path,
+        offset: u64,
+    ) -> Result<(Vec<serde_json::Value>, u64), String>;
+    // `Ok(None)` when `path` does not exist.
+    fn read_checkpoint_json(&self, path: &Path) -> Result<Option<serde_json::Value>, String>;
     fn exists(&self, path: &Path) -> bool;
 }
@@ -0,0 +1,75 @@
+use crate::value_objects::side::Side;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VenueOrderRequest {
+    // Idempotency key; a retried submission with the same id is not placed twice.
+    pub client_order_id: String,
+    pub symbol: String,
+    pub side: Side,
+    // Base-asset quantity of a market order.
+    pub quantity: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VenueOrderState {
+    pub order_id: String,
+    // Still working on the venue (not filled, cancelled or expired yet).
+    pub active: bool,
+    pub filled_qty: f64,
+    // Quote amount of the filled part, so the average price is `filled_funds / filled_qty`.
+    pub filled_funds: f64,
+    // Fee charged so far, in the quote currency.
+    pub fee: f64,
+}
+
+impl VenueOrderState {
+    pub fn avg_price(&self) -> Option<f64> {
+        (self.filled_qty > 0.0).then(|| self.filled_funds / self.filled_qty)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct VenueBalances {
+    // Available (not on hold) amounts in the symbol's base and quote currencies.
+    pub base: f64,
+    pub quote: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VenueError {
+    // The venue answered and refused the request; nothing was placed or changed.
+    Rejected(String),
+    // The request may or may not have reached the venue (timeout, dropped connection, garbled
+    // response), so its outcome is unknown.
+    Transport(String),
+}
+
+impl std::fmt::Display for VenueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VenueError::Rejected(msg) => write!(f, "rejected: {msg}"),
+            VenueError::Transport(msg) => write!(f, "transport: {msg}"),
+        }
+    }
+}
+
+// Where live orders go. Adapters are blocking; the live runner polls `order_state` until the
+// order is no longer active.
+pub trait ExecutionVenue {
+    fn name(&self) -> &str;
+
+    fn balances(&self, symbol: &str) -> Result<VenueBalances, String>;
+
+    // Places a market order and returns the venue order id.
+    fn place_market_order(&self, order: &VenueOrderRequest) -> Result<String, VenueError>;
+
+    fn order_state(&self, order_id: &str) -> Result<VenueOrderState, VenueError>;
+
+    // Looks an order up by its client order id; `None` when the venue never accepted it.
+    fn order_by_client_id(
+        &self,
+        client_order_id: &str,
+    ) -> Result<Option<VenueOrderState>, VenueError>;
+
+    fn cancel_order(&self, order_id: &str) -> Result<(), VenueError>;
+}
@@ -1,3 +1,4 @@
+use crate::services::engine::backtest::RunControl;
 use crate::services::ohlcv::DataQualityReport;
 use crate::value_objects::bar::Bar;
 
@@ -8,8 +9,64 @@ pub struct OhlcvQuery {
     pub symbol: String,
     pub timeframe: String,
     pub expected_step_seconds: Option<i64>,
+    // Inclusive bounds on bar timestamps (epoch seconds) and a cap on the number of bars, counted
+    // from the earliest one; `None` loads the full history.
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+    pub limit: Option<u64>,
+    // Tolerance for the turnover check in the quality report (see `DataQualityReport::check_volume`);
+    // `None` uses the default.
+    pub turnover_tolerance: Option<f64>,
 }
 
+impl OhlcvQuery {
+    pub fn contains(&self, timestamp: i64) -> bool {
+        self.start.is_none_or(|start| timestamp >= start)
+            && self.end.is_none_or(|end| timestamp <= end)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OhlcvCoverage {
+    pub rows: u64,
+    pub first_ts: Option<i64>,
+    pub last_ts: Option<i64>,
+    // Latest write to any row in scope (epoch microseconds), when the store tracks it; lets
+    // caches notice upserts that leave the row count and time range unchanged.
+    pub last_modified: Option<i64>,
+}
+
+pub const OHLCV_LOAD_CANCELLED: &str = "ohlcv load cancelled";
+
 pub trait MarketDataRepository {
     fn load_ohlcv(&self, query: &OhlcvQuery) -> Result<(Vec<Bar>, DataQualityReport), String>;
+
+    // `load_ohlcv` that gives up once `control` asks to cancel. The default only checks before and
+    // after the load; adapters that stream rows should check while reading.
+    fn load_ohlcv_with_control(
+        &self,
+        query: &OhlcvQuery,
+        control: &dyn RunControl,
+    ) -> Result<(Vec<Bar>, DataQualityReport), String> {
+        if control.should_cancel() {
+            return Err(OHLCV_LOAD_CANCELLED.to_string());
+        }
+        let loaded = self.load_ohlcv(query)?;
+        if control.should_cancel() {
+            return Err(OHLCV_LOAD_CANCELLED.to_string());
+        }
+        Ok(loaded)
+    }
+
+    // Row count and time range without materializing bars; adapters should override this with
+    // a cheaper query.
+    fn ohlcv_coverage(&self, query: &OhlcvQuery) -> Result<OhlcvCoverage, String> {
+        let (bars, _) = self.load_ohlcv(query)?;
+        Ok(OhlcvCoverage {
+            rows: bars.len() as u64,
+            first_ts: bars.first().map(|bar| bar.timestamp),
+            last_ts: bars.last().map(|bar| bar.timestamp),
+            last_modified: None,
+        })
+    }
 }
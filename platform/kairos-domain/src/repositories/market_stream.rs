@@ -30,4 +30,18 @@ impl std::fmt::Display for StreamError {
 
 pub trait MarketStream {
     fn next_event(&mut self) -> Result<MarketEvent, StreamError>;
+
+    // RNG seed of a synthetic stream, recorded so the run can be reproduced; None for a market.
+    fn seed(&self) -> Option<u64> {
+        None
+    }
+}
+
+// A stream subscribed to several symbols at once; every event carries the symbol it belongs to.
+pub trait MultiMarketStream {
+    fn next_symbol_event(&mut self) -> Result<(String, MarketEvent), StreamError>;
+
+    fn seed(&self) -> Option<u64> {
+        None
+    }
 }
@@ -1,5 +1,8 @@
 pub mod agent;
 pub mod artifacts;
+pub mod execution_venue;
 pub mod market_data;
 pub mod market_stream;
+pub mod notifier;
+pub mod run_registry;
 pub mod sentiment;
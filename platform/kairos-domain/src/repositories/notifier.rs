@@ -0,0 +1,4 @@
+pub trait Notifier {
+    // Delivers a plain-text message. Implementations may queue and send asynchronously.
+    fn notify(&self, message: &str) -> Result<(), String>;
+}
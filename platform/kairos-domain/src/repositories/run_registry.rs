@@ -0,0 +1,96 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunRecord {
+    pub run_id: String,
+    pub run_dir: String,
+    pub config_hash: Option<String>,
+    pub symbol: Option<String>,
+    pub timeframe: Option<String>,
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+    pub bars_processed: u64,
+    pub trades: u64,
+    pub win_rate: f64,
+    pub net_profit: f64,
+    pub sharpe: f64,
+    pub max_drawdown: f64,
+    pub recorded_at: i64,
+    // Texts from the run's notes.jsonl, oldest first.
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunSortKey {
+    RecordedAt,
+    NetProfit,
+    Sharpe,
+    MaxDrawdown,
+    WinRate,
+}
+
+impl RunSortKey {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        match raw.trim().to_lowercase().as_str() {
+            "recorded_at" => Ok(Self::RecordedAt),
+            "net_profit" => Ok(Self::NetProfit),
+            "sharpe" => Ok(Self::Sharpe),
+            "max_drawdown" => Ok(Self::MaxDrawdown),
+            "win_rate" => Ok(Self::WinRate),
+            other => Err(format!(
+                "unsupported sort key '{other}' (expected recorded_at|net_profit|sharpe|max_drawdown|win_rate)"
+            )),
+        }
+    }
+
+    pub fn value(self, record: &RunRecord) -> f64 {
+        match self {
+            Self::RecordedAt => record.recorded_at as f64,
+            Self::NetProfit => record.net_profit,
+            Self::Sharpe => record.sharpe,
+            Self::MaxDrawdown => record.max_drawdown,
+            Self::WinRate => record.win_rate,
+        }
+    }
+
+    // Drawdown is the only key where smaller is better.
+    pub fn higher_is_better(self) -> bool {
+        !matches!(self, Self::MaxDrawdown)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RunQuery {
+    pub symbol: Option<String>,
+    pub timeframe: Option<String>,
+    pub config_hash: Option<String>,
+    // Inclusive bounds on the run's data range (epoch seconds).
+    pub start_from: Option<i64>,
+    pub end_to: Option<i64>,
+    pub sort_by: RunSortKey,
+    pub descending: bool,
+    pub limit: Option<usize>,
+}
+
+impl Default for RunQuery {
+    fn default() -> Self {
+        Self {
+            symbol: None,
+            timeframe: None,
+            config_hash: None,
+            start_from: None,
+            end_to: None,
+            sort_by: RunSortKey::RecordedAt,
+            descending: true,
+            limit: None,
+        }
+    }
+}
+
+pub trait RunRegistry {
+    // Inserts or replaces the record keyed by `run_dir`.
+    fn upsert_run(&self, record: &RunRecord) -> Result<(), String>;
+    fn query_runs(&self, query: &RunQuery) -> Result<Vec<RunRecord>, String>;
+    // Deleting a run_dir that is not recorded is not an error.
+    fn remove_run(&self, run_dir: &str) -> Result<(), String>;
+}
@@ -7,10 +7,35 @@ pub enum SentimentFormat {
     Json,
 }
 
+#[derive(Debug, Clone)]
+pub enum SentimentSource {
+    File {
+        path: PathBuf,
+        format: SentimentFormat,
+    },
+    // Rows of `table` for `symbol`, with inclusive bounds on their timestamps (epoch seconds).
+    // The latest row at or before `start` is loaded too, so the first bars still align.
+    Db {
+        table: String,
+        symbol: String,
+        start: Option<i64>,
+        end: Option<i64>,
+    },
+}
+
+impl SentimentSource {
+    // Where the points come from, for messages.
+    pub fn describe(&self) -> String {
+        match self {
+            SentimentSource::File { path, .. } => path.display().to_string(),
+            SentimentSource::Db { table, symbol, .. } => format!("{table} ({symbol})"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SentimentQuery {
-    pub path: PathBuf,
-    pub format: SentimentFormat,
+    pub source: SentimentSource,
     pub missing_policy: MissingValuePolicy,
 }
 
@@ -0,0 +1,84 @@
+use crate::value_objects::equity_point::EquityPoint;
+
+// Upper bound for equity points embedded in HTML reports; full resolution stays in equity.csv/parquet.
+pub const CHART_MAX_POINTS: usize = 5_000;
+
+// Largest-Triangle-Three-Buckets: keeps the first and last points and, for each bucket in between,
+// the point forming the largest triangle with the previously kept point and the next bucket's
+// average. Unlike striding, this preserves visual extremes such as drawdown troughs.
+pub fn lttb_indices(points: &[(f64, f64)], threshold: usize) -> Vec<usize> {
+    let n = points.len();
+    if threshold >= n || threshold < 3 {
+        return (0..n).collect();
+    }
+
+    let every = (n - 2) as f64 / (threshold - 2) as f64;
+    let mut selected = Vec::with_capacity(threshold);
+    let mut a = 0usize;
+    selected.push(a);
+
+    for i in 0..threshold - 2 {
+        let avg_start = ((i + 1) as f64 * every) as usize + 1;
+        let avg_end = (((i + 2) as f64 * every) as usize + 1).min(n);
+        let (avg_x, avg_y) = if avg_end > avg_start {
+            let len = (avg_end - avg_start) as f64;
+            let (sum_x, sum_y) = points[avg_start..avg_end]
+                .iter()
+                .fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x, sy + y));
+            (sum_x / len, sum_y / len)
+        } else {
+            points[n - 1]
+        };
+
+        let range_start = (i as f64 * every) as usize + 1;
+        let range_end = ((i + 1) as f64 * every) as usize + 1;
+        let (ax, ay) = points[a];
+        let mut best = range_start;
+        let mut best_area = -1.0;
+        for (offset, (x, y)) in points[range_start..range_end].iter().enumerate() {
+            let area = ((ax - avg_x) * (y - ay) - (ax - x) * (avg_y - ay)).abs();
+            if area > best_area {
+                best_area = area;
+                best = range_start + offset;
+            }
+        }
+        selected.push(best);
+        a = best;
+    }
+
+    selected.push(n - 1);
+    selected
+}
+
+pub fn downsample_equity(points: &[EquityPoint], threshold: usize) -> Vec<EquityPoint> {
+    let xy: Vec<(f64, f64)> = points
+        .iter()
+        .map(|p| (p.timestamp as f64, p.equity))
+        .collect();
+    lttb_indices(&xy, threshold)
+        .into_iter()
+        .map(|idx| points[idx].clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lttb_indices;
+
+    #[test]
+    fn lttb_keeps_endpoints_and_extremes() {
+        let mut points: Vec<(f64, f64)> = (0..1_000).map(|i| (i as f64, 100.0)).collect();
+        points[420].1 = 40.0;
+        points[777].1 = 180.0;
+
+        let indices = lttb_indices(&points, 50);
+        assert_eq!(indices.len(), 50);
+        assert_eq!(indices.first(), Some(&0));
+        assert_eq!(indices.last(), Some(&999));
+        assert!(indices.windows(2).all(|w| w[0] < w[1]));
+        assert!(indices.contains(&420));
+        assert!(indices.contains(&777));
+
+        assert_eq!(lttb_indices(&points[..10], 50).len(), 10);
+    }
+}
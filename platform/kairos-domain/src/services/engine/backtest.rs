@@ -18,20 +18,28 @@
 //! - No complex order types (OCO, iceberg, post-only, reduce-only, etc.).
 //! - Price references are bar-based (e.g., next bar open / within-bar touch), not tick-accurate.
 use super::execution::{ExecutionConfig, ExecutionModel, OrderKind, PriceReference, TimeInForce};
+use super::schedule::{SessionState, TradingSchedule};
 use crate::entities::metrics::{MetricsConfig, MetricsState, MetricsSummary};
 use crate::entities::portfolio::Portfolio;
 use crate::entities::risk::RiskLimits;
+use crate::errors::EngineError;
 use crate::services::audit::AuditEvent;
 use crate::services::market_data_source::MarketDataSource;
+use crate::services::strategy::hot_swap::StrategyChanges;
 use crate::services::strategy::Strategy;
 use crate::value_objects::action::Action;
 use crate::value_objects::action_type::ActionType;
 use crate::value_objects::bar::Bar;
 use crate::value_objects::equity_point::EquityPoint;
+use crate::value_objects::position::Position;
 use crate::value_objects::side::Side;
+use crate::value_objects::symbol::Symbol;
 use crate::value_objects::trade::Trade;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy)]
 pub enum OrderSizeMode {
@@ -49,7 +57,7 @@ pub struct TradeInBar {
     pub slippage: f64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct SimOrder {
     id: u64,
     side: Side,
@@ -66,6 +74,57 @@ struct SimOrder {
     tif: TimeInForce,
 }
 
+// Engine state needed to continue a paper session after a restart: portfolio, working orders,
+// counters and the trading session state. The equity curve and trades are not copied; the
+// snapshot only records how long they were, and `EngineHistory` hands them out for the saver to
+// persist incrementally. Strategy state is not included; strategies warm up again after a resume.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineSnapshot {
+    bar_index: u64,
+    next_order_id: u64,
+    halt_trading: bool,
+    cash: f64,
+    realized_pnl: f64,
+    positions: Vec<Position>,
+    open_orders: Vec<SimOrder>,
+    equity_points: usize,
+    trade_count: usize,
+    last_timestamp: Option<i64>,
+    // Checkpoints saved before this field existed resume as open.
+    #[serde(default)]
+    session_state: SessionState,
+}
+
+impl EngineSnapshot {
+    pub fn bar_index(&self) -> u64 {
+        self.bar_index
+    }
+
+    pub fn last_timestamp(&self) -> Option<i64> {
+        self.last_timestamp
+    }
+
+    // Length of the equity curve when the snapshot was taken.
+    pub fn equity_points(&self) -> usize {
+        self.equity_points
+    }
+
+    pub fn trade_count(&self) -> usize {
+        self.trade_count
+    }
+
+    pub fn open_orders(&self) -> usize {
+        self.open_orders.len()
+    }
+}
+
+// The equity curve and trades a runner has recorded so far, borrowed at a checkpoint.
+#[derive(Debug, Clone, Copy)]
+pub struct EngineHistory<'a> {
+    pub equity: &'a [EquityPoint],
+    pub trades: &'a [Trade],
+}
+
 #[derive(Debug)]
 pub struct BacktestRunner<S, D>
 where
@@ -83,10 +142,13 @@ where
     open_orders: VecDeque<SimOrder>,
     next_order_id: u64,
     fee_bps: f64,
-    symbol: String,
+    symbol: Symbol,
     halt_trading: bool,
     size_mode: OrderSizeMode,
     audit_events: Vec<AuditEvent>,
+    schedule: Option<TradingSchedule>,
+    session_state: SessionState,
+    session_started_at: Option<i64>,
 }
 
 pub struct BacktestResults {
@@ -104,12 +166,23 @@ pub struct BarProgress {
     pub equity: f64,
     pub cash: f64,
     pub position_qty: f64,
+    pub avg_price: f64,
     pub trades_in_bar: Vec<TradeInBar>,
+    // Orders still working after this bar: resting limit/stop orders, and market orders waiting
+    // out their latency or a volume cap.
+    pub open_orders: Vec<OpenOrder>,
+    pub trading_halted: bool,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum BacktestRunError {
-    Cancelled,
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenOrder {
+    pub id: u64,
+    pub side: Side,
+    pub kind: OrderKind,
+    pub remaining_qty: f64,
+    pub limit_price: Option<f64>,
+    pub stop_price: Option<f64>,
+    pub submitted_bar_index: u64,
 }
 
 pub trait RunControl {
@@ -120,12 +193,42 @@ pub trait RunControl {
     fn wait_if_paused(&self) -> bool {
         true
     }
+
+    // Strategy changes a UI queues for a paper session; None when nothing can change it.
+    fn strategy_changes(&self) -> Option<StrategyChanges> {
+        None
+    }
 }
 
 pub struct NoopControl;
 
 impl RunControl for NoopControl {}
 
+// Shared cancel flag for callers that stop a run from another thread (TUI tasks, API jobs).
+// Clones observe the same flag; cancelling is one-way.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl RunControl for CancellationToken {
+    fn should_cancel(&self) -> bool {
+        self.is_cancelled()
+    }
+}
+
 impl<S, D> BacktestRunner<S, D>
 where
     S: Strategy,
@@ -156,10 +259,13 @@ where
             open_orders: VecDeque::new(),
             next_order_id: 1,
             fee_bps,
-            symbol,
+            symbol: symbol.into(),
             halt_trading: false,
             size_mode,
             audit_events: Vec::new(),
+            schedule: None,
+            session_state: SessionState::Open,
+            session_started_at: None,
         }
     }
 
@@ -188,13 +294,26 @@ where
             open_orders: VecDeque::new(),
             next_order_id: 1,
             fee_bps,
-            symbol,
+            symbol: symbol.into(),
             halt_trading: false,
             size_mode,
             audit_events: Vec::new(),
+            schedule: None,
+            session_state: SessionState::Open,
+            session_started_at: None,
         }
     }
 
+    pub fn with_schedule(mut self, schedule: TradingSchedule) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    // Set once the schedule's maximum run length has passed; the `run_*` loops stop there.
+    pub fn session_expired(&self) -> bool {
+        self.session_state == SessionState::Expired
+    }
+
     pub fn run(&mut self) -> BacktestResults {
         self.run_with_progress(|_progress| {})
     }
@@ -211,12 +330,142 @@ where
         &mut self,
         on_progress: &mut dyn FnMut(BarProgress),
         control: &dyn RunControl,
-    ) -> Result<BacktestResults, BacktestRunError> {
+    ) -> Result<BacktestResults, EngineError> {
+        self.run_with_checkpoints(
+            on_progress,
+            control,
+            0,
+            &mut |_snapshot, _history, _data| {},
+        )
+    }
+
+    pub fn snapshot(&self) -> EngineSnapshot {
+        let equity = self.metrics.equity_curve();
+        EngineSnapshot {
+            bar_index: self.bar_index,
+            next_order_id: self.next_order_id,
+            halt_trading: self.halt_trading,
+            cash: self.portfolio.cash(),
+            realized_pnl: self.portfolio.realized_pnl(),
+            positions: self.portfolio.positions().to_vec(),
+            open_orders: self.open_orders.iter().cloned().collect(),
+            equity_points: equity.len(),
+            trade_count: self.metrics.trades().len(),
+            last_timestamp: equity.last().map(|point| point.timestamp),
+            session_state: self.session_state,
+        }
+    }
+
+    pub fn history(&self) -> EngineHistory<'_> {
+        EngineHistory {
+            equity: self.metrics.equity_curve(),
+            trades: self.metrics.trades(),
+        }
+    }
+
+    // Continues from a saved session instead of starting flat; call before running. `equity` and
+    // `trades` are the history saved up to the snapshot (its first `equity_points`/`trade_count`
+    // entries), replayed so peak equity and drawdown pick up where they were.
+    pub fn restore(
+        &mut self,
+        snapshot: EngineSnapshot,
+        equity: Vec<EquityPoint>,
+        trades: Vec<Trade>,
+    ) {
+        let position_qty = snapshot
+            .positions
+            .iter()
+            .find(|pos| pos.symbol == self.symbol)
+            .map(|pos| pos.quantity)
+            .unwrap_or(0.0);
+        self.audit_events.push(AuditEvent {
+            run_id: self.run_id.clone(),
+            timestamp: snapshot.last_timestamp().unwrap_or(0),
+            stage: "engine".to_string(),
+            symbol: Some(self.symbol.to_string()),
+            action: "resume".to_string(),
+            error: None,
+            details: json!({
+                "bar_index": snapshot.bar_index,
+                "cash": snapshot.cash,
+                "position_qty": position_qty,
+                "open_orders": snapshot.open_orders.len(),
+                "trades": trades.len(),
+                "halt_trading": snapshot.halt_trading,
+            }),
+        });
+        self.bar_index = snapshot.bar_index;
+        self.next_order_id = snapshot.next_order_id;
+        self.halt_trading = snapshot.halt_trading;
+        self.session_state = snapshot.session_state;
+        self.portfolio =
+            Portfolio::restore(snapshot.cash, snapshot.realized_pnl, snapshot.positions);
+        self.open_orders = snapshot.open_orders.into_iter().collect();
+        for point in equity {
+            self.metrics.record_equity(point);
+        }
+        for trade in trades {
+            self.metrics.record_trade(trade);
+        }
+    }
+
+    // `run_with_progress_control` that also hands `on_checkpoint` a snapshot, the history recorded so
+    // far and the data source (whose state may need saving too) every `every_bars` bars, at the end
+    // of the data and when the run is cancelled. `every_bars = 0` disables checkpoints.
+    pub fn run_with_checkpoints(
+        &mut self,
+        on_progress: &mut dyn FnMut(BarProgress),
+        control: &dyn RunControl,
+        every_bars: u64,
+        on_checkpoint: &mut dyn FnMut(EngineSnapshot, EngineHistory<'_>, &D),
+    ) -> Result<BacktestResults, EngineError> {
+        self.start();
+        // Refilled in place each bar, so a columnar source replays without allocating.
+        let mut bar = Bar {
+            symbol: self.symbol.clone(),
+            timestamp: 0,
+            open: 0.0,
+            high: 0.0,
+            low: 0.0,
+            close: 0.0,
+            volume: 0.0,
+        };
+        loop {
+            if control.should_cancel() || !control.wait_if_paused() {
+                if every_bars > 0 {
+                    on_checkpoint(self.snapshot(), self.history(), &self.data);
+                }
+                return Err(EngineError::Cancelled);
+            }
+
+            if !self.data.next_bar_into(&mut bar) {
+                break;
+            }
+
+            on_progress(self.step(&bar));
+            if every_bars > 0 && self.bar_index.is_multiple_of(every_bars) {
+                on_checkpoint(self.snapshot(), self.history(), &self.data);
+            }
+            if self.session_expired() {
+                break;
+            }
+        }
+        if every_bars > 0 {
+            on_checkpoint(self.snapshot(), self.history(), &self.data);
+        }
+
+        Ok(self.finish())
+    }
+
+    // Push-driven alternative to the `run_*` loops for callers that own the bar feed (e.g. one
+    // runner per symbol fed from a shared stream): `start` once, `step` every closed bar, then
+    // `finish`. The runner's own data source is not read.
+    pub fn start(&mut self) {
         self.audit_events.push(AuditEvent {
             run_id: self.run_id.clone(),
             timestamp: 0,
             stage: "engine".to_string(),
-            symbol: Some(self.symbol.clone()),
+            symbol: Some(self.symbol.to_string()),
             action: "start".to_string(),
             error: None,
             details: json!({
@@ -240,43 +489,47 @@ where
                 },
             }),
         });
+    }
 
+    pub fn step(&mut self, bar: &Bar) -> BarProgress {
         let mut trades_in_bar: Vec<TradeInBar> = Vec::new();
-        loop {
-            if control.should_cancel() {
-                return Err(BacktestRunError::Cancelled);
-            }
-            if !control.wait_if_paused() {
-                return Err(BacktestRunError::Cancelled);
-            }
-
-            let Some(bar) = self.data.next_bar() else {
-                break;
-            };
+        self.bar_index = self.bar_index.saturating_add(1);
+        self.process_open_orders(bar, &mut trades_in_bar);
+        let session = self.update_session(bar, &mut trades_in_bar);
 
-            self.bar_index = self.bar_index.saturating_add(1);
-            self.process_open_orders(&bar, &mut trades_in_bar);
-
-            if !self.halt_trading {
-                let action = self.strategy.on_bar(&bar, &self.portfolio);
-                self.schedule_order(&bar, action);
-            }
-
-            self.record_equity(&bar);
-            let emitted_trades = std::mem::take(&mut trades_in_bar);
-            on_progress(BarProgress {
-                bar_index: self.bar_index,
-                timestamp: bar.timestamp,
-                close: bar.close,
-                equity: self.portfolio.equity(&bar.symbol, bar.close),
-                cash: self.portfolio.cash(),
-                position_qty: self.portfolio.position_qty(&bar.symbol),
-                trades_in_bar: emitted_trades,
-            });
+        if !self.halt_trading && session == SessionState::Open {
+            let action = self.strategy.on_bar(bar, &self.portfolio);
+            self.schedule_order(bar, action);
+        }
 
-            // Placeholder: extend with full risk/metrics/reporting.
+        self.record_equity(bar);
+        BarProgress {
+            bar_index: self.bar_index,
+            timestamp: bar.timestamp,
+            close: bar.close,
+            equity: self.portfolio.equity(&bar.symbol, bar.close),
+            cash: self.portfolio.cash(),
+            position_qty: self.portfolio.position_qty(&bar.symbol),
+            avg_price: self.portfolio.position_avg_price(&bar.symbol),
+            trades_in_bar,
+            open_orders: self
+                .open_orders
+                .iter()
+                .map(|order| OpenOrder {
+                    id: order.id,
+                    side: order.side,
+                    kind: order.kind,
+                    remaining_qty: order.remaining_qty,
+                    limit_price: order.limit_price,
+                    stop_price: order.stop_price,
+                    submitted_bar_index: order.submitted_bar_index,
+                })
+                .collect(),
+            trading_halted: self.halt_trading,
         }
+    }
 
+    pub fn finish(&mut self) -> BacktestResults {
         let mut strategy_events = self.strategy.drain_audit_events();
         self.audit_events.append(&mut strategy_events);
 
@@ -285,7 +538,7 @@ where
             run_id: self.run_id.clone(),
             timestamp: 0,
             stage: "engine".to_string(),
-            symbol: Some(self.symbol.clone()),
+            symbol: Some(self.symbol.to_string()),
             action: "complete".to_string(),
             error: None,
             details: json!({
@@ -305,12 +558,129 @@ where
                 .then_with(|| a.action.cmp(&b.action))
         });
 
-        Ok(BacktestResults {
+        BacktestResults {
             summary,
             trades,
             equity,
             audit_events: std::mem::take(&mut self.audit_events),
-        })
+        }
+    }
+
+    fn update_session(&mut self, bar: &Bar, trades_in_bar: &mut Vec<TradeInBar>) -> SessionState {
+        let Some(schedule) = self.schedule else {
+            return SessionState::Open;
+        };
+        // A resumed run keeps measuring its length from the first bar it ever processed.
+        let started_at = *self.session_started_at.get_or_insert_with(|| {
+            self.metrics
+                .equity_curve()
+                .first()
+                .map(|point| point.timestamp)
+                .unwrap_or(bar.timestamp)
+        });
+        let state = schedule.state(started_at, bar.timestamp);
+        if state == self.session_state {
+            return state;
+        }
+        let (action, details) = match state {
+            SessionState::Open => ("session_open", json!({})),
+            SessionState::Closed | SessionState::Expired => {
+                let flattened_qty = if schedule.flatten() {
+                    self.flatten_position(bar, trades_in_bar)
+                } else {
+                    0.0
+                };
+                let reason = if state == SessionState::Expired {
+                    "max_duration"
+                } else {
+                    "window"
+                };
+                (
+                    "session_close",
+                    json!({ "reason": reason, "flattened_qty": flattened_qty }),
+                )
+            }
+        };
+        self.audit_events.push(AuditEvent {
+            run_id: self.run_id.clone(),
+            timestamp: bar.timestamp,
+            stage: "session".to_string(),
+            symbol: Some(self.symbol.to_string()),
+            action: action.to_string(),
+            error: None,
+            details,
+        });
+        self.session_state = state;
+        state
+    }
+
+    // End of session: drops working orders and sells the whole position at the bar close, with the
+    // usual spread/slippage and fees. Returns the quantity sold.
+    fn flatten_position(&mut self, bar: &Bar, trades_in_bar: &mut Vec<TradeInBar>) -> f64 {
+        for order in std::mem::take(&mut self.open_orders) {
+            self.audit_events.push(AuditEvent {
+                run_id: self.run_id.clone(),
+                timestamp: bar.timestamp,
+                stage: "order".to_string(),
+                symbol: Some(self.symbol.to_string()),
+                action: "cancel".to_string(),
+                error: Some("session_closed".to_string()),
+                details: json!({
+                    "order_id": order.id,
+                    "side": format!("{:?}", order.side),
+                    "kind": format!("{:?}", order.kind).to_lowercase(),
+                    "remaining_qty": order.remaining_qty,
+                }),
+            });
+        }
+
+        let qty = self.portfolio.position_qty(&self.symbol);
+        let impact_bps = (self.execution.spread_bps / 2.0) + self.execution.slippage_bps;
+        let price = bar.close * (1.0 - impact_bps / 10_000.0);
+        if qty <= 0.0 || price <= 0.0 || !price.is_finite() {
+            return 0.0;
+        }
+        let fee = price * qty * self.fee_bps / 10_000.0;
+        let slippage = (bar.close - price) * qty;
+        self.portfolio
+            .apply_fill(&self.symbol, Side::Sell, qty, price, fee);
+        trades_in_bar.push(TradeInBar {
+            timestamp: bar.timestamp,
+            side: Side::Sell,
+            quantity: qty,
+            price,
+            fee,
+            slippage,
+        });
+        self.metrics.record_trade(Trade {
+            timestamp: bar.timestamp,
+            symbol: self.symbol.clone(),
+            side: Side::Sell,
+            quantity: qty,
+            price,
+            fee,
+            slippage,
+            strategy_id: self.strategy.name().to_string(),
+            reason: "session_end".to_string(),
+        });
+        self.audit_events.push(AuditEvent {
+            run_id: self.run_id.clone(),
+            timestamp: bar.timestamp,
+            stage: "trade".to_string(),
+            symbol: Some(self.symbol.to_string()),
+            action: format!("{:?}", Side::Sell),
+            error: None,
+            details: json!({
+                "qty": qty,
+                "price": price,
+                "fee": fee,
+                "slippage": slippage,
+                "raw_price": bar.close,
+                "reason": "session_end",
+                "strategy_id": self.strategy.name(),
+            }),
+        });
+        qty
     }
 
     fn process_open_orders(&mut self, bar: &Bar, trades_in_bar: &mut Vec<TradeInBar>) {
@@ -326,7 +696,7 @@ where
                         run_id: self.run_id.clone(),
                         timestamp: bar.timestamp,
                         stage: "order".to_string(),
-                        symbol: Some(self.symbol.clone()),
+                        symbol: Some(self.symbol.to_string()),
                         action: "cancel".to_string(),
                         error: Some("expired".to_string()),
                         details: json!({
@@ -359,7 +729,7 @@ where
                             run_id: self.run_id.clone(),
                             timestamp: bar.timestamp,
                             stage: "order".to_string(),
-                            symbol: Some(self.symbol.clone()),
+                            symbol: Some(self.symbol.to_string()),
                             action: "cancel".to_string(),
                             error: Some(
                                 match order.tif {
@@ -389,7 +759,7 @@ where
                     run_id: self.run_id.clone(),
                     timestamp: bar.timestamp,
                     stage: "order".to_string(),
-                    symbol: Some(self.symbol.clone()),
+                    symbol: Some(self.symbol.to_string()),
                     action: "cancel".to_string(),
                     error: Some("invalid_price".to_string()),
                     details: json!({
@@ -409,7 +779,7 @@ where
                     run_id: self.run_id.clone(),
                     timestamp: bar.timestamp,
                     stage: "order".to_string(),
-                    symbol: Some(self.symbol.clone()),
+                    symbol: Some(self.symbol.to_string()),
                     action: "cancel".to_string(),
                     error: Some("invalid_volume".to_string()),
                     details: json!({
@@ -433,7 +803,7 @@ where
                     run_id: self.run_id.clone(),
                     timestamp: bar.timestamp,
                     stage: "order".to_string(),
-                    symbol: Some(self.symbol.clone()),
+                    symbol: Some(self.symbol.to_string()),
                     action: "cancel".to_string(),
                     error: Some("invalid_exec_price".to_string()),
                     details: json!({
@@ -482,7 +852,7 @@ where
                     run_id: self.run_id.clone(),
                     timestamp: bar.timestamp,
                     stage: "order".to_string(),
-                    symbol: Some(self.symbol.clone()),
+                    symbol: Some(self.symbol.to_string()),
                     action: "cancel".to_string(),
                     error: Some("fok_unfillable".to_string()),
                     details: json!({
@@ -509,7 +879,7 @@ where
                         run_id: self.run_id.clone(),
                         timestamp: bar.timestamp,
                         stage: "order".to_string(),
-                        symbol: Some(self.symbol.clone()),
+                        symbol: Some(self.symbol.to_string()),
                         action: "cancel".to_string(),
                         error: Some("ioc_unfilled".to_string()),
                         details: json!({
@@ -565,7 +935,7 @@ where
                 run_id: self.run_id.clone(),
                 timestamp: bar.timestamp,
                 stage: "trade".to_string(),
-                symbol: Some(self.symbol.clone()),
+                symbol: Some(self.symbol.to_string()),
                 action: format!("{:?}", order.side),
                 error: None,
                 details: json!({
@@ -591,7 +961,7 @@ where
                         run_id: self.run_id.clone(),
                         timestamp: bar.timestamp,
                         stage: "order".to_string(),
-                        symbol: Some(self.symbol.clone()),
+                        symbol: Some(self.symbol.to_string()),
                         action: "cancel".to_string(),
                         error: Some("ioc_partial_cancel".to_string()),
                         details: json!({
@@ -610,7 +980,7 @@ where
                     run_id: self.run_id.clone(),
                     timestamp: bar.timestamp,
                     stage: "order".to_string(),
-                    symbol: Some(self.symbol.clone()),
+                    symbol: Some(self.symbol.to_string()),
                     action: "partial_fill".to_string(),
                     error: None,
                     details: json!({
@@ -874,7 +1244,7 @@ where
                     run_id: self.run_id.clone(),
                     timestamp: bar.timestamp,
                     stage: "order".to_string(),
-                    symbol: Some(self.symbol.clone()),
+                    symbol: Some(self.symbol.to_string()),
                     action: "submit".to_string(),
                     error: None,
                     details: json!({
@@ -1054,7 +1424,7 @@ where
                     run_id: self.run_id.clone(),
                     timestamp: bar.timestamp,
                     stage: "order".to_string(),
-                    symbol: Some(self.symbol.clone()),
+                    symbol: Some(self.symbol.to_string()),
                     action: "submit".to_string(),
                     error: None,
                     details: json!({
@@ -1105,7 +1475,7 @@ where
                     run_id: self.run_id.clone(),
                     timestamp: bar.timestamp,
                     stage: "risk".to_string(),
-                    symbol: Some(self.symbol.clone()),
+                    symbol: Some(self.symbol.to_string()),
                     action: "halt_drawdown".to_string(),
                     error: None,
                     details: json!({
@@ -1190,13 +1560,15 @@ mod tests {
     use super::super::execution::{
         ExecutionConfig, ExecutionModel, OrderKind, PriceReference, TimeInForce,
     };
+    use super::super::schedule::TradingSchedule;
     use super::BacktestRunner;
     use super::OrderSizeMode;
+    use super::{EngineSnapshot, NoopControl};
     use crate::entities::metrics::MetricsConfig;
     use crate::entities::portfolio::Portfolio;
     use crate::entities::risk::RiskLimits;
     use crate::services::market_data_source::MarketDataSource;
-    use crate::services::strategy::Strategy;
+    use crate::services::strategy::{BuyAndHold, Strategy};
     use crate::value_objects::action::Action;
     use crate::value_objects::action_type::ActionType;
     use crate::value_objects::bar::Bar;
@@ -1230,7 +1602,7 @@ mod tests {
     fn run_with_progress_emits_one_event_per_bar() {
         let bars = vec![
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 1,
                 open: 10.0,
                 high: 10.0,
@@ -1239,7 +1611,7 @@ mod tests {
                 volume: 10.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 2,
                 open: 11.0,
                 high: 11.0,
@@ -1296,7 +1668,7 @@ mod tests {
     fn run_with_progress_includes_trades_for_the_filled_bar() {
         let bars = vec![
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 1,
                 open: 10.0,
                 high: 10.0,
@@ -1305,7 +1677,7 @@ mod tests {
                 volume: 10.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 2,
                 open: 11.0,
                 high: 11.0,
@@ -1372,7 +1744,7 @@ mod tests {
     fn run_with_progress_control_can_cancel_before_next_bar() {
         let bars = vec![
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 1,
                 open: 10.0,
                 high: 10.0,
@@ -1381,7 +1753,7 @@ mod tests {
                 volume: 10.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 2,
                 open: 11.0,
                 high: 11.0,
@@ -1449,7 +1821,7 @@ mod tests {
             },
             &cancel,
         );
-        assert!(matches!(res, Err(super::BacktestRunError::Cancelled)));
+        assert!(matches!(res, Err(crate::errors::EngineError::Cancelled)));
         assert_eq!(progress_calls, 1);
     }
 
@@ -1494,7 +1866,7 @@ mod tests {
     fn run_counts_processed_bars() {
         let bars = vec![
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 1,
                 open: 1.0,
                 high: 1.0,
@@ -1503,7 +1875,7 @@ mod tests {
                 volume: 1.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 2,
                 open: 1.0,
                 high: 1.0,
@@ -1536,7 +1908,7 @@ mod tests {
     fn buy_qty_never_makes_cash_negative() {
         let bars = vec![
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 1,
                 open: 10.0,
                 high: 10.0,
@@ -1545,7 +1917,7 @@ mod tests {
                 volume: 1.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 2,
                 open: 10.0,
                 high: 10.0,
@@ -1579,7 +1951,7 @@ mod tests {
     fn buy_qty_with_zero_cash_is_rejected() {
         let bars = vec![
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 1,
                 open: 10.0,
                 high: 10.0,
@@ -1588,7 +1960,7 @@ mod tests {
                 volume: 1.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 2,
                 open: 10.0,
                 high: 10.0,
@@ -1657,7 +2029,7 @@ mod tests {
     fn complete_limit_buy_fills_on_touch_low() {
         let bars = vec![
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 1,
                 open: 100.0,
                 high: 100.0,
@@ -1666,7 +2038,7 @@ mod tests {
                 volume: 10_000.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 2,
                 open: 100.0,
                 high: 100.0,
@@ -1717,7 +2089,7 @@ mod tests {
     fn complete_limit_buy_does_not_fill_when_not_touched() {
         let bars = vec![
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 1,
                 open: 100.0,
                 high: 100.0,
@@ -1726,7 +2098,7 @@ mod tests {
                 volume: 10_000.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 2,
                 open: 100.0,
                 high: 100.0,
@@ -1765,15 +2137,21 @@ mod tests {
             OrderSizeMode::Quantity,
             execution,
         );
-        let result = runner.run();
+        let mut last_progress = None;
+        let result = runner.run_with_progress(|p| last_progress = Some(p));
         assert!(result.trades.is_empty());
+        let open_orders = last_progress.expect("progress").open_orders;
+        assert_eq!(open_orders.len(), 1);
+        assert_eq!(open_orders[0].kind, OrderKind::Limit);
+        assert_eq!(open_orders[0].remaining_qty, 1.0);
+        assert!((open_orders[0].limit_price.expect("limit") - 99.0).abs() < 1e-9);
     }
 
     #[test]
     fn complete_stop_sell_triggers_on_touch_low() {
         let bars = vec![
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 1,
                 open: 100.0,
                 high: 100.0,
@@ -1782,7 +2160,7 @@ mod tests {
                 volume: 10_000.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 2,
                 open: 100.0,
                 high: 100.0,
@@ -1791,7 +2169,7 @@ mod tests {
                 volume: 10_000.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 3,
                 open: 100.0,
                 high: 101.0,
@@ -1855,7 +2233,7 @@ mod tests {
     fn complete_latency_delays_activation() {
         let bars = vec![
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 1,
                 open: 10.0,
                 high: 10.0,
@@ -1864,7 +2242,7 @@ mod tests {
                 volume: 10_000.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 2,
                 open: 10.0,
                 high: 10.0,
@@ -1873,7 +2251,7 @@ mod tests {
                 volume: 10_000.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 3,
                 open: 10.0,
                 high: 10.0,
@@ -1918,11 +2296,208 @@ mod tests {
         assert_eq!(result.trades[0].timestamp, 3);
     }
 
+    #[test]
+    fn restored_snapshot_keeps_pending_orders_and_history() {
+        let bar = |timestamp: i64| Bar {
+            symbol: "BTCUSD".into(),
+            timestamp,
+            open: 10.0,
+            high: 10.0,
+            low: 10.0,
+            close: 10.0,
+            volume: 10_000.0,
+        };
+        let execution = ExecutionConfig {
+            model: ExecutionModel::Complete,
+            latency_bars: 2,
+            buy_kind: OrderKind::Market,
+            sell_kind: OrderKind::Market,
+            price_reference: PriceReference::Close,
+            limit_offset_bps: 0.0,
+            stop_offset_bps: 0.0,
+            spread_bps: 0.0,
+            slippage_bps: 0.0,
+            max_fill_pct_of_volume: 1.0,
+            tif: TimeInForce::Gtc,
+            expire_after_bars: None,
+        };
+        // The session stops after the first bar with the buy still waiting for its latency.
+        let mut first = BacktestRunner::new_with_execution(
+            "resume".to_string(),
+            BuyOnceStrategy::new(1.0),
+            DummyDataSource::new(vec![bar(1)]),
+            RiskLimits::default(),
+            10_000.0,
+            MetricsConfig::default(),
+            0.0,
+            "BTCUSD".to_string(),
+            OrderSizeMode::Quantity,
+            execution.clone(),
+        );
+        let mut saved = None;
+        first
+            .run_with_checkpoints(
+                &mut |_p| {},
+                &NoopControl,
+                1,
+                &mut |snapshot, history, _data| {
+                    saved = Some((snapshot, history.equity.to_vec(), history.trades.to_vec()))
+                },
+            )
+            .expect("run");
+        let (snapshot, equity, trades) = saved.expect("checkpoint");
+        let json = serde_json::to_string(&snapshot).expect("serialize");
+        let snapshot: EngineSnapshot = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(snapshot.bar_index(), 1);
+        assert_eq!(snapshot.open_orders(), 1);
+        assert_eq!(snapshot.equity_points(), equity.len());
+        assert_eq!(snapshot.last_timestamp(), Some(1));
+        // The history is not part of the snapshot itself.
+        assert!(!json.contains("\"equity\":"), "{json}");
+
+        // The resumed session only holds, so its one trade is the restored pending buy.
+        let mut second = BacktestRunner::new_with_execution(
+            "resume".to_string(),
+            DummyStrategy,
+            DummyDataSource::new(vec![bar(2), bar(3)]),
+            RiskLimits::default(),
+            10_000.0,
+            MetricsConfig::default(),
+            0.0,
+            "BTCUSD".to_string(),
+            OrderSizeMode::Quantity,
+            execution,
+        );
+        second.restore(snapshot, equity, trades);
+        let result = second.run();
+
+        assert_eq!(result.summary.bars_processed, 3);
+        assert_eq!(result.equity.len(), 3);
+        assert_eq!(result.trades.len(), 1);
+        assert_eq!(result.trades[0].timestamp, 3);
+        assert!(result.audit_events.iter().any(|e| e.action == "resume"));
+    }
+
+    #[test]
+    fn schedule_flattens_outside_the_window_and_stops_at_max_duration() {
+        let hour = 3_600;
+        let bars: Vec<Bar> = (0..10)
+            .map(|h| Bar {
+                symbol: "BTCUSD".into(),
+                timestamp: h * hour,
+                open: 10.0,
+                high: 10.0,
+                low: 10.0,
+                close: 10.0,
+                volume: 10_000.0,
+            })
+            .collect();
+        let schedule =
+            TradingSchedule::new(Some((hour, 4 * hour)), Some(6 * hour), true).expect("schedule");
+        let mut runner = BacktestRunner::new(
+            "schedule".to_string(),
+            BuyAndHold::new(1.0),
+            DummyDataSource::new(bars),
+            RiskLimits::default(),
+            10_000.0,
+            MetricsConfig::default(),
+            0.0,
+            0.0,
+            "BTCUSD".to_string(),
+            OrderSizeMode::Quantity,
+        )
+        .with_schedule(schedule);
+        let result = runner.run();
+
+        // Bought inside 01:00-04:00, sold at 04:00, never re-bought while closed, and the run
+        // ends at the 6h mark instead of reading the remaining bars.
+        assert_eq!(result.summary.bars_processed, 7);
+        assert_eq!(result.trades.len(), 2);
+        assert_eq!(result.trades[0].side, Side::Buy);
+        assert_eq!(result.trades[1].side, Side::Sell);
+        assert_eq!(result.trades[1].timestamp, 4 * hour);
+        assert_eq!(result.trades[1].reason, "session_end");
+        let closes: Vec<&str> = result
+            .audit_events
+            .iter()
+            .filter(|e| e.action == "session_close")
+            .map(|e| e.details["reason"].as_str().unwrap_or_default())
+            .collect();
+        assert_eq!(closes, vec!["window", "window", "max_duration"]);
+    }
+
+    #[test]
+    fn resumed_session_keeps_a_closed_window_closed() {
+        let hour = 3_600;
+        let bars = |hours: std::ops::Range<i64>| -> Vec<Bar> {
+            hours
+                .map(|h| Bar {
+                    symbol: "BTCUSD".into(),
+                    timestamp: h * hour,
+                    open: 10.0,
+                    high: 10.0,
+                    low: 10.0,
+                    close: 10.0,
+                    volume: 10_000.0,
+                })
+                .collect()
+        };
+        let schedule = TradingSchedule::new(Some((0, 2 * hour)), None, true).expect("schedule");
+        let runner = |data: Vec<Bar>| {
+            BacktestRunner::new(
+                "session_resume".to_string(),
+                BuyAndHold::new(1.0),
+                DummyDataSource::new(data),
+                RiskLimits::default(),
+                10_000.0,
+                MetricsConfig::default(),
+                0.0,
+                0.0,
+                "BTCUSD".to_string(),
+                OrderSizeMode::Quantity,
+            )
+            .with_schedule(schedule)
+        };
+
+        // Buys at 00:00 and is flattened when the window closes at 02:00.
+        let mut first = runner(bars(0..3));
+        let mut saved = None;
+        let result = first
+            .run_with_checkpoints(
+                &mut |_p| {},
+                &NoopControl,
+                1,
+                &mut |snapshot, history, _data| {
+                    saved = Some((snapshot, history.equity.to_vec(), history.trades.to_vec()))
+                },
+            )
+            .expect("run");
+        assert_eq!(result.trades.len(), 2);
+        let (snapshot, equity, trades) = saved.expect("checkpoint");
+        let json = serde_json::to_string(&snapshot).expect("serialize");
+        assert!(json.contains("\"session_state\":\"closed\""), "{json}");
+        let snapshot: EngineSnapshot = serde_json::from_str(&json).expect("deserialize");
+
+        // Still outside the window: no second close and nothing to flatten again.
+        let mut second = runner(bars(3..5));
+        second.restore(snapshot, equity, trades);
+        let result = second.run();
+        assert_eq!(result.trades.len(), 2);
+        assert!(
+            !result
+                .audit_events
+                .iter()
+                .any(|e| e.action == "session_close"),
+            "{:?}",
+            result.audit_events
+        );
+    }
+
     #[test]
     fn complete_volume_cap_partial_fill_across_bars() {
         let bars = vec![
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 1,
                 open: 10.0,
                 high: 10.0,
@@ -1931,7 +2506,7 @@ mod tests {
                 volume: 10.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 2,
                 open: 10.0,
                 high: 10.0,
@@ -1940,7 +2515,7 @@ mod tests {
                 volume: 10.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 3,
                 open: 10.0,
                 high: 10.0,
@@ -1949,7 +2524,7 @@ mod tests {
                 volume: 10.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 4,
                 open: 10.0,
                 high: 10.0,
@@ -1999,7 +2574,7 @@ mod tests {
     fn complete_fok_cancels_if_volume_insufficient() {
         let bars = vec![
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 1,
                 open: 10.0,
                 high: 10.0,
@@ -2008,7 +2583,7 @@ mod tests {
                 volume: 10.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 2,
                 open: 10.0,
                 high: 10.0,
@@ -2061,7 +2636,7 @@ mod tests {
     fn complete_ioc_cancels_if_not_touched_on_first_active_bar() {
         let bars = vec![
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 1,
                 open: 10.0,
                 high: 10.0,
@@ -2071,7 +2646,7 @@ mod tests {
             },
             // Limit price is 10.0; low stays above => not touched => IOC cancel.
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 2,
                 open: 11.0,
                 high: 12.0,
@@ -2124,7 +2699,7 @@ mod tests {
     fn complete_gtc_expires_after_bars() {
         let bars = vec![
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 1,
                 open: 10.0,
                 high: 10.0,
@@ -2134,7 +2709,7 @@ mod tests {
             },
             // Order becomes active here (ready_bar_index=2) but never touched.
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 2,
                 open: 11.0,
                 high: 12.0,
@@ -2144,7 +2719,7 @@ mod tests {
             },
             // Expiration triggers at bar_index=3 (> expires_bar_index=2).
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 3,
                 open: 11.0,
                 high: 12.0,
@@ -2196,7 +2771,7 @@ mod tests {
     #[test]
     fn risk_position_limit_rejects_buy() {
         let bars = vec![Bar {
-            symbol: "BTCUSD".to_string(),
+            symbol: "BTCUSD".into(),
             timestamp: 1,
             open: 10.0,
             high: 10.0,
@@ -2238,7 +2813,7 @@ mod tests {
     #[test]
     fn risk_exposure_limit_rejects_buy() {
         let bars = vec![Bar {
-            symbol: "BTCUSD".to_string(),
+            symbol: "BTCUSD".into(),
             timestamp: 1,
             open: 100.0,
             high: 100.0,
@@ -2281,7 +2856,7 @@ mod tests {
     fn halts_trading_on_drawdown() {
         let bars = vec![
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 1,
                 open: 100.0,
                 high: 100.0,
@@ -2290,7 +2865,7 @@ mod tests {
                 volume: 100.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 2,
                 open: 100.0,
                 high: 100.0,
@@ -2299,7 +2874,7 @@ mod tests {
                 volume: 100.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 3,
                 open: 50.0,
                 high: 50.0,
@@ -1,2 +1,3 @@
 pub mod backtest;
 pub mod execution;
+pub mod schedule;
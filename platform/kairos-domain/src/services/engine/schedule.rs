@@ -0,0 +1,124 @@
+use crate::errors::EngineError;
+use serde::{Deserialize, Serialize};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+// When an unattended run may trade, judged on bar timestamps (UTC): a daily window and/or a
+// maximum run length. Outside the window the strategy is not consulted; past the maximum length
+// the run ends. With `flatten`, the position is closed at the first bar that leaves the session.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TradingSchedule {
+    // Seconds since midnight UTC; `end < start` spans midnight.
+    window: Option<(i64, i64)>,
+    max_duration_seconds: Option<i64>,
+    flatten: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionState {
+    #[default]
+    Open,
+    // Outside the daily window; trading resumes when it opens again.
+    Closed,
+    // Past the maximum run length; the run should stop.
+    Expired,
+}
+
+impl TradingSchedule {
+    pub fn new(
+        window: Option<(i64, i64)>,
+        max_duration_seconds: Option<i64>,
+        flatten: bool,
+    ) -> Result<Self, EngineError> {
+        if let Some((start, end)) = window {
+            if !(0..SECONDS_PER_DAY).contains(&start) || !(0..SECONDS_PER_DAY).contains(&end) {
+                return Err(EngineError::InvalidSchedule(
+                    "session window must be within a day",
+                ));
+            }
+            if start == end {
+                return Err(EngineError::InvalidSchedule(
+                    "session window start and end must differ",
+                ));
+            }
+        }
+        if max_duration_seconds.is_some_and(|max| max <= 0) {
+            return Err(EngineError::InvalidSchedule(
+                "max session duration must be > 0",
+            ));
+        }
+        Ok(Self {
+            window,
+            max_duration_seconds,
+            flatten,
+        })
+    }
+
+    // `HH:MM` or `HH:MM:SS`, as seconds since midnight.
+    pub fn parse_time_of_day(value: &str) -> Result<i64, EngineError> {
+        let parts: Vec<&str> = value.trim().split(':').collect();
+        if !(2..=3).contains(&parts.len()) {
+            return Err(EngineError::InvalidTimeOfDay(value.to_string()));
+        }
+        let mut seconds = 0;
+        for (part, (max, unit)) in parts.iter().zip([(23, 3_600), (59, 60), (59, 1)]) {
+            let n: i64 = part
+                .parse()
+                .ok()
+                .filter(|n| (0..=max).contains(n) && part.len() == 2)
+                .ok_or_else(|| EngineError::InvalidTimeOfDay(value.to_string()))?;
+            seconds += n * unit;
+        }
+        Ok(seconds)
+    }
+
+    pub fn flatten(&self) -> bool {
+        self.flatten
+    }
+
+    pub fn state(&self, started_at: i64, timestamp: i64) -> SessionState {
+        if self
+            .max_duration_seconds
+            .is_some_and(|max| timestamp.saturating_sub(started_at) >= max)
+        {
+            return SessionState::Expired;
+        }
+        let Some((start, end)) = self.window else {
+            return SessionState::Open;
+        };
+        let time = timestamp.rem_euclid(SECONDS_PER_DAY);
+        let open = if start < end {
+            (start..end).contains(&time)
+        } else {
+            time >= start || time < end
+        };
+        if open {
+            SessionState::Open
+        } else {
+            SessionState::Closed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SessionState, TradingSchedule};
+
+    #[test]
+    fn window_and_max_duration_drive_the_session_state() {
+        let hour = 3_600;
+        let overnight = TradingSchedule::new(Some((22 * hour, 6 * hour)), Some(30 * hour), true)
+            .expect("schedule");
+        assert_eq!(overnight.state(0, 23 * hour), SessionState::Open);
+        assert_eq!(overnight.state(0, 24 * hour + 5 * hour), SessionState::Open);
+        assert_eq!(overnight.state(0, 12 * hour), SessionState::Closed);
+        assert_eq!(overnight.state(0, 30 * hour), SessionState::Expired);
+
+        assert_eq!(TradingSchedule::parse_time_of_day("09:30"), Ok(34_200));
+        assert_eq!(TradingSchedule::parse_time_of_day("23:59:59"), Ok(86_399));
+        assert!(TradingSchedule::parse_time_of_day("24:00").is_err());
+        assert!(TradingSchedule::parse_time_of_day("9:30").is_err());
+        assert!(TradingSchedule::new(Some((hour, hour)), None, true).is_err());
+    }
+}
@@ -108,7 +108,7 @@ mod tests {
 
     fn bar(price: f64) -> Bar {
         Bar {
-            symbol: "BTCUSD".to_string(),
+            symbol: "BTCUSD".into(),
             timestamp: 0,
             open: price,
             high: price,
@@ -1,27 +1,49 @@
 use crate::value_objects::bar::Bar;
+use crate::value_objects::bar_columns::BarColumns;
 
 pub trait MarketDataSource {
     fn next_bar(&mut self) -> Option<Bar>;
+
+    // Overwrites `bar` with the next bar; `false` when the source is exhausted. Sources that can
+    // fill the caller's buffer in place override this so hot loops do not allocate per bar.
+    fn next_bar_into(&mut self, bar: &mut Bar) -> bool {
+        match self.next_bar() {
+            Some(next) => {
+                *bar = next;
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 pub struct VecBarSource {
-    bars: Vec<Bar>,
+    bars: BarColumns,
     index: usize,
 }
 
 impl VecBarSource {
     pub fn new(bars: Vec<Bar>) -> Self {
+        Self::from_columns(bars.into_iter().collect())
+    }
+
+    pub fn from_columns(bars: BarColumns) -> Self {
         Self { bars, index: 0 }
     }
 }
 
 impl MarketDataSource for VecBarSource {
     fn next_bar(&mut self) -> Option<Bar> {
-        if self.index >= self.bars.len() {
-            return None;
-        }
-        let bar = self.bars[self.index].clone();
+        let bar = self.bars.bar(self.index)?;
         self.index += 1;
         Some(bar)
     }
+
+    fn next_bar_into(&mut self, bar: &mut Bar) -> bool {
+        if !self.bars.read_into(self.index, bar) {
+            return false;
+        }
+        self.index += 1;
+        true
+    }
 }
@@ -1,10 +1,15 @@
 pub mod agent;
 pub mod analyzers;
 pub mod audit;
+pub mod downsample;
 pub mod engine;
 pub mod features;
 pub mod market_data_source;
+pub mod montecarlo;
 pub mod ohlcv;
+pub mod progress;
 pub mod realtime_bar;
+pub mod rng;
 pub mod sentiment;
 pub mod strategy;
+pub mod synthetic;
@@ -0,0 +1,368 @@
+use super::rng::SplitMix64;
+use crate::value_objects::equity_point::EquityPoint;
+use crate::value_objects::side::Side;
+use crate::value_objects::trade::Trade;
+use std::collections::HashMap;
+
+pub const BAND_PERCENTILES: [f64; 5] = [5.0, 25.0, 50.0, 75.0, 95.0];
+
+// Upper bound for band steps kept per path; longer paths are sampled at evenly spaced steps.
+pub const MAX_BAND_STEPS: usize = 2_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonteCarloConfig {
+    pub samples: usize,
+    pub block: usize,
+    pub seed: u64,
+}
+
+impl MonteCarloConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.samples == 0 {
+            return Err("samples must be > 0".to_string());
+        }
+        if self.block == 0 {
+            return Err("block must be > 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution {
+    pub mean: f64,
+    pub std: f64,
+    pub min: f64,
+    pub max: f64,
+    // Same order as `BAND_PERCENTILES`.
+    pub percentiles: [f64; 5],
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathStats {
+    pub final_equity: f64,
+    pub total_return: f64,
+    pub max_drawdown: f64,
+    pub sharpe: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquityBands {
+    pub steps: Vec<usize>,
+    // One row per entry in `steps`, columns in `BAND_PERCENTILES` order.
+    pub values: Vec<[f64; 5]>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResamplingResult {
+    pub observations: usize,
+    pub block: usize,
+    pub observed: PathStats,
+    pub final_equity: Distribution,
+    pub total_return: Distribution,
+    pub max_drawdown: Distribution,
+    pub sharpe: Distribution,
+    // Share of resampled paths ending below the starting equity.
+    pub prob_loss: f64,
+    pub bands: EquityBands,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compounding {
+    // Steps are simple returns: equity *= 1 + r.
+    Multiplicative,
+    // Steps are PnL amounts: equity += pnl.
+    Additive,
+}
+
+// Bar-to-bar simple returns of the equity curve (steps from a non-positive equity are skipped).
+pub fn equity_returns(equity: &[EquityPoint]) -> Vec<f64> {
+    equity
+        .windows(2)
+        .filter(|pair| pair[0].equity > 0.0)
+        .map(|pair| pair[1].equity / pair[0].equity - 1.0)
+        .filter(|ret| ret.is_finite())
+        .collect()
+}
+
+// Realized PnL of every sell against the average cost of the open position, in fill order.
+pub fn realized_trade_pnls(trades: &[Trade]) -> Vec<f64> {
+    realized_pnl_by_trade(trades)
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+// The realized PnL of each trade, aligned with `trades`: sells against the average cost of their
+// symbol's open position, None for buys (and for sells without a position or with invalid fills).
+pub fn realized_pnl_by_trade(trades: &[Trade]) -> Vec<Option<f64>> {
+    // symbol -> (position_qty, avg_cost)
+    let mut positions: HashMap<&str, (f64, f64)> = HashMap::new();
+
+    trades
+        .iter()
+        .map(|trade| {
+            if !trade.quantity.is_finite() || trade.quantity <= 0.0 || !trade.price.is_finite() {
+                return None;
+            }
+            let (position_qty, avg_cost) =
+                positions.entry(trade.symbol.as_str()).or_insert((0.0, 0.0));
+            match trade.side {
+                Side::Buy => {
+                    let new_qty = *position_qty + trade.quantity;
+                    *avg_cost =
+                        (*avg_cost * *position_qty + trade.quantity * trade.price + trade.fee)
+                            / new_qty;
+                    *position_qty = new_qty;
+                    None
+                }
+                Side::Sell => {
+                    if *position_qty <= 0.0 {
+                        return None;
+                    }
+                    let qty = trade.quantity.min(*position_qty);
+                    let pnl = qty * trade.price - trade.fee - qty * *avg_cost;
+                    *position_qty -= qty;
+                    if *position_qty <= 0.0 {
+                        *position_qty = 0.0;
+                        *avg_cost = 0.0;
+                    }
+                    Some(pnl)
+                }
+            }
+        })
+        .collect()
+}
+
+// Moving-block bootstrap: every sample is a path of the same length as `steps`, built from
+// blocks of `block` consecutive observations starting at uniformly drawn offsets, so short-range
+// autocorrelation inside a block survives the resampling. `block = 1` is a plain i.i.d. bootstrap.
+pub fn block_bootstrap(
+    initial_equity: f64,
+    steps: &[f64],
+    compounding: Compounding,
+    config: &MonteCarloConfig,
+) -> Result<ResamplingResult, String> {
+    config.validate()?;
+    if steps.is_empty() {
+        return Err("nothing to resample (no observations)".to_string());
+    }
+    if !initial_equity.is_finite() || initial_equity <= 0.0 {
+        return Err(format!("invalid starting equity: {initial_equity}"));
+    }
+
+    let n = steps.len();
+    let block = config.block.min(n);
+    let band_steps = band_steps(n);
+    let mut band_samples = vec![Vec::with_capacity(config.samples); band_steps.len()];
+    let mut finals = Vec::with_capacity(config.samples);
+    let mut returns = Vec::with_capacity(config.samples);
+    let mut drawdowns = Vec::with_capacity(config.samples);
+    let mut sharpes = Vec::with_capacity(config.samples);
+
+    let mut rng = SplitMix64::new(config.seed);
+    let mut path = Vec::with_capacity(n);
+    for _ in 0..config.samples {
+        path.clear();
+        while path.len() < n {
+            let start = rng.next_below((n - block + 1) as u64) as usize;
+            let take = block.min(n - path.len());
+            path.extend_from_slice(&steps[start..start + take]);
+        }
+
+        let mut band_idx = 0;
+        let stats = walk_path(initial_equity, &path, compounding, |step, equity| {
+            if band_steps.get(band_idx) == Some(&step) {
+                band_samples[band_idx].push(equity);
+                band_idx += 1;
+            }
+        });
+        finals.push(stats.final_equity);
+        returns.push(stats.total_return);
+        drawdowns.push(stats.max_drawdown);
+        sharpes.push(stats.sharpe);
+    }
+
+    let losses = finals.iter().filter(|v| **v < initial_equity).count();
+    let bands = EquityBands {
+        values: band_samples
+            .iter_mut()
+            .map(|values| distribution(values).percentiles)
+            .collect(),
+        steps: band_steps,
+    };
+
+    Ok(ResamplingResult {
+        observations: n,
+        block,
+        observed: walk_path(initial_equity, steps, compounding, |_, _| {}),
+        final_equity: distribution(&mut finals),
+        total_return: distribution(&mut returns),
+        max_drawdown: distribution(&mut drawdowns),
+        sharpe: distribution(&mut sharpes),
+        prob_loss: losses as f64 / config.samples as f64,
+        bands,
+    })
+}
+
+// Step 0 is the starting equity and step `n` the final one.
+fn band_steps(n: usize) -> Vec<usize> {
+    if n < MAX_BAND_STEPS {
+        return (0..=n).collect();
+    }
+    let mut steps: Vec<usize> = (0..MAX_BAND_STEPS)
+        .map(|i| i * n / (MAX_BAND_STEPS - 1))
+        .collect();
+    steps.dedup();
+    steps
+}
+
+fn walk_path(
+    initial_equity: f64,
+    steps: &[f64],
+    compounding: Compounding,
+    mut on_step: impl FnMut(usize, f64),
+) -> PathStats {
+    let mut equity = initial_equity;
+    let mut peak = initial_equity;
+    let mut max_drawdown = 0.0f64;
+    let mut period_returns = Vec::with_capacity(steps.len());
+    on_step(0, equity);
+
+    for (i, step) in steps.iter().enumerate() {
+        let prev = equity;
+        equity = match compounding {
+            Compounding::Multiplicative => equity * (1.0 + step),
+            Compounding::Additive => equity + step,
+        };
+        if prev > 0.0 {
+            period_returns.push(equity / prev - 1.0);
+        }
+        peak = peak.max(equity);
+        if peak > 0.0 {
+            max_drawdown = max_drawdown.max((peak - equity) / peak);
+        }
+        on_step(i + 1, equity);
+    }
+
+    PathStats {
+        final_equity: equity,
+        total_return: equity / initial_equity - 1.0,
+        max_drawdown,
+        sharpe: sharpe(&period_returns),
+    }
+}
+
+// Same convention as `MetricsState` with the default config: zero risk-free rate, scaled by the
+// square root of the number of periods.
+fn sharpe(returns: &[f64]) -> f64 {
+    if returns.len() < 2 {
+        return 0.0;
+    }
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    let var = returns.iter().map(|r| (r - mean) * (r - mean)).sum::<f64>() / (n - 1.0);
+    let std = var.sqrt();
+    if std == 0.0 || !std.is_finite() {
+        0.0
+    } else {
+        mean / std * n.sqrt()
+    }
+}
+
+fn distribution(values: &mut [f64]) -> Distribution {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let std = if values.len() > 1 {
+        (values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / (n - 1.0)).sqrt()
+    } else {
+        0.0
+    };
+    Distribution {
+        mean,
+        std,
+        min: values[0],
+        max: values[values.len() - 1],
+        percentiles: BAND_PERCENTILES.map(|p| percentile_sorted(values, p)),
+    }
+}
+
+// Linear interpolation between closest ranks.
+fn percentile_sorted(sorted: &[f64], pct: f64) -> f64 {
+    let rank = pct / 100.0 * (sorted.len() - 1) as f64;
+    let lo = rank.floor() as usize;
+    let hi = rank.ceil() as usize;
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        block_bootstrap, percentile_sorted, realized_pnl_by_trade, realized_trade_pnls,
+        Compounding, MonteCarloConfig,
+    };
+    use crate::value_objects::side::Side;
+    use crate::value_objects::trade::Trade;
+
+    fn config(block: usize) -> MonteCarloConfig {
+        MonteCarloConfig {
+            samples: 500,
+            block,
+            seed: 7,
+        }
+    }
+
+    #[test]
+    fn bootstrap_is_seeded_and_keeps_path_length() {
+        let steps = [0.01, -0.02, 0.015, 0.0, 0.005, -0.01, 0.02, 0.01];
+        let a = block_bootstrap(1000.0, &steps, Compounding::Multiplicative, &config(3)).unwrap();
+        let b = block_bootstrap(1000.0, &steps, Compounding::Multiplicative, &config(3)).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.observations, steps.len());
+        assert_eq!(a.bands.steps, (0..=steps.len()).collect::<Vec<_>>());
+        assert_eq!(a.bands.values[0], [1000.0; 5]);
+        let last = a.bands.values[steps.len()];
+        assert!(last.windows(2).all(|w| w[0] <= w[1]), "{last:?}");
+        assert!(a.final_equity.min <= a.observed.final_equity);
+        assert!(a.final_equity.max >= a.observed.final_equity);
+    }
+
+    #[test]
+    fn full_length_block_reproduces_observed_path() {
+        let steps = [10.0, -5.0, 20.0, -30.0];
+        let result = block_bootstrap(100.0, &steps, Compounding::Additive, &config(50)).unwrap();
+        assert_eq!(result.block, steps.len());
+        assert_eq!(result.final_equity.min, 95.0);
+        assert_eq!(result.final_equity.max, 95.0);
+        assert!((result.max_drawdown.mean - 30.0 / 125.0).abs() < 1e-12);
+        assert_eq!(result.prob_loss, 1.0);
+    }
+
+    #[test]
+    fn realized_pnls_use_average_cost() {
+        let trade = |side, quantity, price| Trade {
+            timestamp: 0,
+            symbol: "BTCUSD".into(),
+            side,
+            quantity,
+            price,
+            fee: 0.0,
+            slippage: 0.0,
+            strategy_id: "test".to_string(),
+            reason: "test".to_string(),
+        };
+        let trades = [
+            trade(Side::Buy, 1.0, 100.0),
+            trade(Side::Buy, 1.0, 120.0),
+            trade(Side::Sell, 1.0, 130.0),
+            trade(Side::Sell, 1.0, 100.0),
+        ];
+        assert_eq!(realized_trade_pnls(&trades), vec![20.0, -10.0]);
+        assert_eq!(
+            realized_pnl_by_trade(&trades),
+            vec![None, None, Some(20.0), Some(-10.0)]
+        );
+        assert_eq!(percentile_sorted(&[1.0, 2.0, 3.0, 4.0, 5.0], 25.0), 2.0);
+    }
+}
@@ -1,7 +1,9 @@
+use crate::errors::EngineError;
 use crate::value_objects::bar::Bar;
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct DataQualityReport {
     pub duplicates: usize,
     pub gaps: usize,
@@ -15,6 +17,65 @@ pub struct DataQualityReport {
     pub first_invalid_close: Option<i64>,
     pub max_gap_seconds: Option<i64>,
     pub gap_count: usize,
+    // Bars that moved (range or open/close) on zero volume, and bars whose turnover does not match
+    // price x volume; turnover is only checked by loaders whose source stores it.
+    #[serde(default)]
+    pub zero_volume_moves: usize,
+    #[serde(default)]
+    pub first_zero_volume_move: Option<i64>,
+    #[serde(default)]
+    pub turnover_mismatches: usize,
+    #[serde(default)]
+    pub first_turnover_mismatch: Option<i64>,
+    // Where the gaps are: (last bar before, first bar after), in time order, for the first
+    // `MAX_GAP_RANGES` gaps (`gaps` keeps the full count).
+    #[serde(default)]
+    pub gap_ranges: Vec<(i64, i64)>,
+}
+
+pub const DEFAULT_TURNOVER_TOLERANCE: f64 = 0.01;
+pub const MAX_GAP_RANGES: usize = 1_000;
+
+impl DataQualityReport {
+    // Records the gap between two consecutive unique timestamps, if they are more than `step`
+    // seconds apart.
+    pub fn check_gap(&mut self, prev: i64, ts: i64, step: i64) {
+        let diff = ts - prev;
+        if diff <= step {
+            return;
+        }
+        self.gaps += 1;
+        self.gap_count += ((diff - 1) / step) as usize;
+        self.first_gap.get_or_insert(ts);
+        self.max_gap_seconds = Some(self.max_gap_seconds.map_or(diff, |max| max.max(diff)));
+        if self.gap_ranges.len() < MAX_GAP_RANGES {
+            self.gap_ranges.push((prev, ts));
+        }
+    }
+
+    // Volume checks for one loaded bar. `turnover` (quote volume) is consistent when the average
+    // price it implies (`turnover / volume`) lies within the bar's low..high range, widened by
+    // `tolerance` (a fraction) on each side; zero volume must come with zero turnover.
+    pub fn check_volume(&mut self, bar: &Bar, turnover: Option<f64>, tolerance: f64) {
+        if bar.volume == 0.0 && (bar.high != bar.low || bar.open != bar.close) {
+            self.zero_volume_moves += 1;
+            self.first_zero_volume_move.get_or_insert(bar.timestamp);
+        }
+        let Some(turnover) = turnover.filter(|turnover| turnover.is_finite()) else {
+            return;
+        };
+        let consistent = if bar.volume > 0.0 {
+            let average_price = turnover / bar.volume;
+            average_price >= bar.low * (1.0 - tolerance)
+                && average_price <= bar.high * (1.0 + tolerance)
+        } else {
+            turnover == 0.0
+        };
+        if !consistent {
+            self.turnover_mismatches += 1;
+            self.first_turnover_mismatch.get_or_insert(bar.timestamp);
+        }
+    }
 }
 
 pub fn data_quality_from_bars(
@@ -48,6 +109,7 @@ pub fn data_quality_from_bars(
                 report.first_invalid_close = Some(ts);
             }
         }
+        report.check_volume(bar, None, DEFAULT_TURNOVER_TOLERANCE);
 
         if let Some(prev) = last_ts_in_input {
             if ts < prev {
@@ -77,27 +139,244 @@ pub fn data_quality_from_bars(
     }
 
     unique_ts.sort();
-    let mut max_gap: Option<i64> = None;
-    let mut prev = unique_ts[0];
-    for &ts in unique_ts.iter().skip(1) {
-        let diff = ts - prev;
-        if diff > step {
-            report.gaps += 1;
-            report.gap_count += ((diff - 1) / step) as usize;
-            if report.first_gap.is_none() {
-                report.first_gap = Some(ts);
+    for pair in unique_ts.windows(2) {
+        report.check_gap(pair[0], pair[1], step);
+    }
+    report
+}
+
+// Thresholds for `outliers_from_bars`. Streak lengths are in bars; a streak is counted once it
+// reaches that length.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutlierConfig {
+    pub return_sigma: f64,
+    pub zero_volume_streak_bars: usize,
+    pub flat_price_streak_bars: usize,
+}
+
+impl Default for OutlierConfig {
+    fn default() -> Self {
+        Self {
+            return_sigma: 6.0,
+            zero_volume_streak_bars: 10,
+            flat_price_streak_bars: 10,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutlierReport {
+    pub return_outliers: usize,
+    pub first_return_outlier: Option<i64>,
+    pub max_return_sigma: Option<f64>,
+    pub zero_volume_streaks: usize,
+    pub first_zero_volume_streak: Option<i64>,
+    pub longest_zero_volume_streak: usize,
+    pub flat_price_streaks: usize,
+    pub first_flat_price_streak: Option<i64>,
+    pub longest_flat_price_streak: usize,
+}
+
+// Price spikes and stale data. Bars are taken in timestamp order and those with an invalid close
+// are skipped (`data_quality_from_bars` already counts them). A return outlier is a bar whose log
+// return from the previous bar is more than `return_sigma` robust standard deviations from the
+// median; a flat bar has no range (`high == low`) and closes where the previous bar closed.
+// Streaks are reported by the timestamp of their first bar.
+pub fn outliers_from_bars(bars: &[Bar], config: &OutlierConfig) -> OutlierReport {
+    let mut report = OutlierReport::default();
+    let mut sorted: Vec<&Bar> = bars
+        .iter()
+        .filter(|bar| bar.close.is_finite() && bar.close > 0.0)
+        .collect();
+    sorted.sort_by_key(|bar| bar.timestamp);
+
+    let returns: Vec<(i64, f64)> = sorted
+        .windows(2)
+        .map(|pair| (pair[1].timestamp, (pair[1].close / pair[0].close).ln()))
+        .collect();
+    if returns.len() >= 2 {
+        let center = median(returns.iter().map(|(_, r)| *r).collect());
+        let scale = robust_std_dev(&returns, center);
+        if scale > 0.0 {
+            for &(ts, r) in &returns {
+                let sigma = (r - center).abs() / scale;
+                report.max_return_sigma =
+                    Some(report.max_return_sigma.map_or(sigma, |max| max.max(sigma)));
+                if sigma > config.return_sigma {
+                    report.return_outliers += 1;
+                    if report.first_return_outlier.is_none() {
+                        report.first_return_outlier = Some(ts);
+                    }
+                }
             }
-            max_gap = Some(max_gap.map_or(diff, |current| current.max(diff)));
         }
-        prev = ts;
     }
-    report.max_gap_seconds = max_gap;
+
+    let mut zero_volume = Streak::default();
+    let mut flat_price = Streak::default();
+    let mut prev_close: Option<f64> = None;
+    for bar in sorted {
+        let zero = bar.volume == 0.0;
+        let flat = bar.high == bar.low && prev_close == Some(bar.close);
+        if zero_volume.step(zero, bar.timestamp, config.zero_volume_streak_bars) {
+            report.zero_volume_streaks += 1;
+            report.first_zero_volume_streak = report.first_zero_volume_streak.or(zero_volume.start);
+        }
+        if flat_price.step(flat, bar.timestamp, config.flat_price_streak_bars) {
+            report.flat_price_streaks += 1;
+            report.first_flat_price_streak = report.first_flat_price_streak.or(flat_price.start);
+        }
+        prev_close = Some(bar.close);
+    }
+    report.longest_zero_volume_streak = zero_volume.longest;
+    report.longest_flat_price_streak = flat_price.longest;
     report
 }
 
-pub fn resample_bars(bars: &[Bar], target_step_seconds: i64) -> Result<Vec<Bar>, String> {
+// The median absolute deviation scaled to a standard deviation, so a few spikes do not inflate the
+// threshold they are measured against. Falls back to the plain standard deviation when more than
+// half of the returns are identical (e.g. mostly unchanged closes).
+fn robust_std_dev(returns: &[(i64, f64)], center: f64) -> f64 {
+    let mad = median(returns.iter().map(|(_, r)| (r - center).abs()).collect());
+    if mad > 0.0 {
+        return mad * 1.4826;
+    }
+    let n = returns.len() as f64;
+    let mean = returns.iter().map(|(_, r)| r).sum::<f64>() / n;
+    (returns.iter().map(|(_, r)| (r - mean).powi(2)).sum::<f64>() / n).sqrt()
+}
+
+fn median(mut values: Vec<f64>) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+#[derive(Default)]
+struct Streak {
+    start: Option<i64>,
+    len: usize,
+    longest: usize,
+}
+
+impl Streak {
+    // Returns true when the current streak reaches `min_len`.
+    fn step(&mut self, hit: bool, ts: i64, min_len: usize) -> bool {
+        if !hit {
+            self.start = None;
+            self.len = 0;
+            return false;
+        }
+        if self.len == 0 {
+            self.start = Some(ts);
+        }
+        self.len += 1;
+        self.longest = self.longest.max(self.len);
+        self.len == min_len.max(1)
+    }
+}
+
+// How a primary OHLCV source compares with a reference one (another exchange or table) for the
+// same symbol and timeframe. Only the span both sources cover is compared; divergences are the
+// primary close minus the reference close, in basis points of the reference close.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    pub primary_rows: usize,
+    pub reference_rows: usize,
+    pub overlap_start: Option<i64>,
+    pub overlap_end: Option<i64>,
+    pub matched_bars: usize,
+    pub missing_in_primary: usize,
+    pub first_missing_in_primary: Option<i64>,
+    pub missing_in_reference: usize,
+    pub first_missing_in_reference: Option<i64>,
+    pub mean_divergence_bps: Option<f64>,
+    pub mean_abs_divergence_bps: Option<f64>,
+    pub p95_abs_divergence_bps: Option<f64>,
+    pub max_abs_divergence_bps: Option<f64>,
+    pub max_divergence_at: Option<i64>,
+}
+
+pub fn reconcile_bars(primary: &[Bar], reference: &[Bar]) -> ReconciliationReport {
+    let closes = |bars: &[Bar]| -> BTreeMap<i64, f64> {
+        bars.iter()
+            .filter(|bar| bar.close.is_finite() && bar.close > 0.0)
+            .map(|bar| (bar.timestamp, bar.close))
+            .collect()
+    };
+    let primary = closes(primary);
+    let reference = closes(reference);
+    let mut report = ReconciliationReport {
+        primary_rows: primary.len(),
+        reference_rows: reference.len(),
+        ..ReconciliationReport::default()
+    };
+    let (Some((&p_first, _)), Some((&r_first, _))) =
+        (primary.first_key_value(), reference.first_key_value())
+    else {
+        return report;
+    };
+    let (Some((&p_last, _)), Some((&r_last, _))) =
+        (primary.last_key_value(), reference.last_key_value())
+    else {
+        return report;
+    };
+    let (start, end) = (p_first.max(r_first), p_last.min(r_last));
+    if start > end {
+        return report;
+    }
+    report.overlap_start = Some(start);
+    report.overlap_end = Some(end);
+
+    let mut divergences = Vec::new();
+    for (&ts, &close) in primary.range(start..=end) {
+        match reference.get(&ts) {
+            Some(&reference_close) => {
+                let bps = (close - reference_close) / reference_close * 10_000.0;
+                if report
+                    .max_abs_divergence_bps
+                    .is_none_or(|max| bps.abs() > max)
+                {
+                    report.max_abs_divergence_bps = Some(bps.abs());
+                    report.max_divergence_at = Some(ts);
+                }
+                divergences.push(bps);
+            }
+            None => {
+                report.missing_in_reference += 1;
+                report.first_missing_in_reference.get_or_insert(ts);
+            }
+        }
+    }
+    for &ts in reference.range(start..=end).map(|(ts, _)| ts) {
+        if !primary.contains_key(&ts) {
+            report.missing_in_primary += 1;
+            report.first_missing_in_primary.get_or_insert(ts);
+        }
+    }
+
+    report.matched_bars = divergences.len();
+    if !divergences.is_empty() {
+        let n = divergences.len() as f64;
+        report.mean_divergence_bps = Some(divergences.iter().sum::<f64>() / n);
+        let mut abs: Vec<f64> = divergences.iter().map(|bps| bps.abs()).collect();
+        report.mean_abs_divergence_bps = Some(abs.iter().sum::<f64>() / n);
+        abs.sort_by(f64::total_cmp);
+        let rank = ((abs.len() as f64) * 0.95).ceil() as usize;
+        report.p95_abs_divergence_bps = Some(abs[rank.clamp(1, abs.len()) - 1]);
+    }
+    report
+}
+
+pub fn resample_bars(bars: &[Bar], target_step_seconds: i64) -> Result<Vec<Bar>, EngineError> {
     if target_step_seconds <= 0 {
-        return Err("target_step_seconds must be > 0".to_string());
+        return Err(EngineError::NonPositiveStep {
+            field: "target_step_seconds",
+        });
     }
     if bars.is_empty() {
         return Ok(Vec::new());
@@ -160,12 +439,15 @@ pub fn resample_bars(bars: &[Bar], target_step_seconds: i64) -> Result<Vec<Bar>,
 
 #[cfg(test)]
 mod tests {
-    use super::data_quality_from_bars;
+    use super::{
+        data_quality_from_bars, outliers_from_bars, reconcile_bars, DataQualityReport,
+        OutlierConfig,
+    };
     use crate::value_objects::bar::Bar;
 
     fn bar(ts: i64) -> Bar {
         Bar {
-            symbol: "BTCUSD".to_string(),
+            symbol: "BTCUSD".into(),
             timestamp: ts,
             open: 1.0,
             high: 1.0,
@@ -210,5 +492,119 @@ mod tests {
         assert_eq!(report.gap_count, 13);
         assert_eq!(report.first_gap, Some(10));
         assert_eq!(report.max_gap_seconds, Some(10));
+        assert_eq!(report.gap_ranges, [(5, 10), (10, 20)]);
+    }
+
+    fn priced(ts: i64, close: f64, volume: f64) -> Bar {
+        Bar {
+            high: close * 1.001,
+            low: close * 0.999,
+            close,
+            volume,
+            ..bar(ts)
+        }
+    }
+
+    #[test]
+    fn outliers_flag_return_spikes_beyond_the_sigma_threshold() {
+        let mut bars: Vec<Bar> = (0..100)
+            .map(|i| priced(i * 60, 100.0 + if i % 2 == 0 { 0.1 } else { -0.1 }, 1.0))
+            .collect();
+        bars[50].close = 150.0;
+        let config = OutlierConfig {
+            return_sigma: 5.0,
+            ..OutlierConfig::default()
+        };
+        let report = outliers_from_bars(&bars, &config);
+        assert_eq!(report.return_outliers, 2);
+        assert_eq!(report.first_return_outlier, Some(50 * 60));
+        assert!(report.max_return_sigma.unwrap() > 5.0);
+        assert_eq!(report.zero_volume_streaks, 0);
+        assert_eq!(report.flat_price_streaks, 0);
+    }
+
+    #[test]
+    fn outliers_count_zero_volume_and_flat_price_streaks_once_they_reach_the_minimum() {
+        let mut bars: Vec<Bar> = (0..20)
+            .map(|i| priced(i * 60, 100.0 + i as f64, 1.0))
+            .collect();
+        for bar in &mut bars[2..6] {
+            bar.volume = 0.0;
+        }
+        bars[10].volume = 0.0;
+        for bar in &mut bars[12..17] {
+            bar.close = 111.0;
+            bar.high = 111.0;
+            bar.low = 111.0;
+        }
+        let config = OutlierConfig {
+            return_sigma: 100.0,
+            zero_volume_streak_bars: 3,
+            flat_price_streak_bars: 3,
+        };
+        let report = outliers_from_bars(&bars, &config);
+        assert_eq!(report.zero_volume_streaks, 1);
+        assert_eq!(report.first_zero_volume_streak, Some(2 * 60));
+        assert_eq!(report.longest_zero_volume_streak, 4);
+        assert_eq!(report.flat_price_streaks, 1);
+        assert_eq!(report.first_flat_price_streak, Some(12 * 60));
+        assert_eq!(report.longest_flat_price_streak, 5);
+    }
+
+    #[test]
+    fn volume_checks_flag_moves_on_zero_volume_and_turnover_off_the_price_range() {
+        let mut report = DataQualityReport::default();
+        let moved = Bar {
+            volume: 0.0,
+            ..priced(60, 100.0, 1.0)
+        };
+        report.check_volume(&moved, None, 0.01);
+        report.check_volume(&bar(120), Some(1.0), 0.01);
+        report.check_volume(&priced(180, 100.0, 2.0), Some(200.5), 0.01);
+        report.check_volume(&priced(240, 100.0, 2.0), Some(260.0), 0.01);
+        report.check_volume(
+            &Bar {
+                volume: 0.0,
+                ..bar(300)
+            },
+            Some(5.0),
+            0.01,
+        );
+        assert_eq!(report.zero_volume_moves, 1);
+        assert_eq!(report.first_zero_volume_move, Some(60));
+        assert_eq!(report.turnover_mismatches, 2);
+        assert_eq!(report.first_turnover_mismatch, Some(240));
+
+        let bars = vec![bar(0), moved];
+        assert_eq!(data_quality_from_bars(&bars, Some(60)).zero_volume_moves, 1);
+    }
+
+    #[test]
+    fn reconcile_compares_the_common_span_and_reports_missing_bars_on_each_side() {
+        let primary: Vec<Bar> = [0, 60, 120, 240, 300, 360]
+            .into_iter()
+            .map(|ts| priced(ts, 100.0, 1.0))
+            .collect();
+        let mut reference: Vec<Bar> = [60, 120, 180, 240, 300]
+            .into_iter()
+            .map(|ts| priced(ts, 100.0, 1.0))
+            .collect();
+        reference[1].close = 99.0;
+
+        let report = reconcile_bars(&primary, &reference);
+        assert_eq!((report.primary_rows, report.reference_rows), (6, 5));
+        assert_eq!(report.overlap_start, Some(60));
+        assert_eq!(report.overlap_end, Some(300));
+        assert_eq!(report.matched_bars, 4);
+        assert_eq!(report.missing_in_primary, 1);
+        assert_eq!(report.first_missing_in_primary, Some(180));
+        assert_eq!(report.missing_in_reference, 0);
+        assert_eq!(report.max_divergence_at, Some(120));
+        let max = report.max_abs_divergence_bps.expect("max");
+        assert!((max - 101.0101).abs() < 1e-3);
+        assert_eq!(report.p95_abs_divergence_bps, Some(max));
+        assert!((report.mean_divergence_bps.expect("mean") - max / 4.0).abs() < 1e-9);
+
+        assert_eq!(reconcile_bars(&primary, &[]).overlap_start, None);
     }
 }
@@ -0,0 +1,157 @@
+use crate::services::engine::backtest::BarProgress;
+use std::time::Instant;
+
+// What a long-running use case reports while it works, whoever is listening (CLI lines, TUI
+// charts, `--progress ndjson`, API job status). Stage names match the `timing` audit events:
+// `load_ohlcv`, `resample_ohlcv`, `load_sentiment`, `align_sentiment`, `run_engine`,
+// `write_outputs`, plus `check_limits` (validate). Ingestion reports `RowsWritten` per window and
+// `RequestRetried` when the exchange asks it to back off.
+#[derive(Debug, Clone, Copy)]
+pub enum ProgressEvent<'a> {
+    StageStarted {
+        stage: &'a str,
+    },
+    StageFinished {
+        stage: &'a str,
+        duration_ms: u64,
+    },
+    BarProcessed(&'a BarProgress),
+    TrialCompleted(&'a SweepProgress),
+    // One exchange window upserted by ingestion; `total_rows` counts the whole run so far.
+    RowsWritten {
+        first_ts: i64,
+        last_ts: i64,
+        rows: u64,
+        total_rows: u64,
+    },
+    // An exchange request retried after `delay_ms`; `attempt` is the retry number (1 = first).
+    RequestRetried {
+        attempt: u32,
+        delay_ms: u64,
+        reason: &'a str,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct SweepProgress {
+    pub total_runs: usize,
+    pub completed_runs: usize,
+    pub ok_runs: usize,
+    pub skipped_runs: usize,
+    pub error_runs: usize,
+    pub pruned_runs: usize,
+    pub last_run_id: Option<String>,
+    pub last_status: Option<String>,
+    pub last_error: Option<String>,
+    pub workers: usize,
+    // Wall time of the last finished trial (`None` for skipped trials).
+    pub last_trial_ms: Option<u64>,
+    pub elapsed_ms: u64,
+    // Remaining trials times the observed wall time per executed trial, so it already accounts
+    // for the worker count. `None` until a trial has been executed.
+    pub eta_ms: Option<u64>,
+    // The trial that just finished, for live leaderboards; `None` on the initial event.
+    pub last_trial: Option<TrialOutcome>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrialOutcome {
+    pub run_id: String,
+    pub split_id: String,
+    pub run_dir: String,
+    // Assigned params as `path = value`, in path order.
+    pub params: Vec<(String, String)>,
+    pub status: String,
+    // Read from the trial's summary.json; `None` for failed and pruned trials.
+    pub net_profit: Option<f64>,
+    pub sharpe: Option<f64>,
+    pub max_drawdown: Option<f64>,
+}
+
+pub trait ProgressSink {
+    fn emit(&mut self, event: ProgressEvent<'_>);
+}
+
+// Any closure over events is a sink; `&mut |_event: ProgressEvent<'_>| {}` discards them.
+impl<F> ProgressSink for F
+where
+    F: FnMut(ProgressEvent<'_>),
+{
+    fn emit(&mut self, event: ProgressEvent<'_>) {
+        self(event)
+    }
+}
+
+// Sink for listeners that only follow bars (charts, job counters, pruning).
+pub struct OnBars<F>(F);
+
+pub fn on_bars<F: FnMut(&BarProgress)>(on_bar: F) -> OnBars<F> {
+    OnBars(on_bar)
+}
+
+impl<F: FnMut(&BarProgress)> ProgressSink for OnBars<F> {
+    fn emit(&mut self, event: ProgressEvent<'_>) {
+        if let ProgressEvent::BarProcessed(progress) = event {
+            (self.0)(progress);
+        }
+    }
+}
+
+// Runs `work` between `StageStarted` and `StageFinished`; the stage finishes even when `work`
+// returns an error, so listeners see how far a failed run got.
+pub fn stage<T>(sink: &mut dyn ProgressSink, stage: &str, work: impl FnOnce() -> T) -> T {
+    sink.emit(ProgressEvent::StageStarted { stage });
+    let started = Instant::now();
+    let out = work();
+    sink.emit(ProgressEvent::StageFinished {
+        stage,
+        duration_ms: started.elapsed().as_millis() as u64,
+    });
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{on_bars, stage, ProgressEvent, ProgressSink};
+    use crate::services::engine::backtest::BarProgress;
+
+    #[test]
+    fn closures_are_sinks_and_stages_bracket_their_work() {
+        let mut events = Vec::new();
+        let mut sink = |event: ProgressEvent<'_>| {
+            events.push(match event {
+                ProgressEvent::StageStarted { stage } => format!("start {stage}"),
+                ProgressEvent::StageFinished { stage, .. } => format!("finish {stage}"),
+                ProgressEvent::BarProcessed(bar) => format!("bar {}", bar.bar_index),
+                _ => "other".to_string(),
+            })
+        };
+        let value: Result<u32, String> = stage(&mut sink, "load_ohlcv", || Err("boom".into()));
+        assert!(value.is_err());
+        let bar = BarProgress {
+            bar_index: 7,
+            timestamp: 0,
+            close: 1.0,
+            equity: 1.0,
+            cash: 1.0,
+            position_qty: 0.0,
+            avg_price: 0.0,
+            trades_in_bar: Vec::new(),
+            open_orders: Vec::new(),
+            trading_halted: false,
+        };
+        sink.emit(ProgressEvent::BarProcessed(&bar));
+        assert_eq!(
+            events,
+            vec!["start load_ohlcv", "finish load_ohlcv", "bar 7"]
+        );
+
+        let mut seen = Vec::new();
+        let mut bars_only = on_bars(|bar| seen.push(bar.bar_index));
+        bars_only.emit(ProgressEvent::StageStarted {
+            stage: "run_engine",
+        });
+        bars_only.emit(ProgressEvent::BarProcessed(&bar));
+        assert_eq!(seen, vec![7]);
+    }
+}
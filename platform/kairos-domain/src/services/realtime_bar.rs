@@ -1,7 +1,10 @@
+use crate::errors::EngineError;
 use crate::repositories::market_stream::MarketEvent;
 use crate::value_objects::bar::Bar;
+use crate::value_objects::symbol::Symbol;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct BarAggregationReport {
     pub out_of_order_events: u64,
     pub invalid_events: u64,
@@ -9,9 +12,10 @@ pub struct BarAggregationReport {
     pub last_bar_timestamp: Option<i64>,
 }
 
-#[derive(Debug, Clone)]
+// Serializable so a paper session can save the bar being built and pick it up after a restart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BarAggregator {
-    symbol: String,
+    symbol: Symbol,
     step_seconds: i64,
     current_bucket_start: Option<i64>,
     working: Option<Bar>,
@@ -20,12 +24,14 @@ pub struct BarAggregator {
 }
 
 impl BarAggregator {
-    pub fn new(symbol: String, step_seconds: i64) -> Result<Self, String> {
+    pub fn new(symbol: String, step_seconds: i64) -> Result<Self, EngineError> {
         if step_seconds <= 0 {
-            return Err("step_seconds must be > 0".to_string());
+            return Err(EngineError::NonPositiveStep {
+                field: "step_seconds",
+            });
         }
         Ok(Self {
-            symbol,
+            symbol: symbol.into(),
             step_seconds,
             current_bucket_start: None,
             working: None,
@@ -34,6 +40,25 @@ impl BarAggregator {
         })
     }
 
+    // Takes over the working bar and event clock of a saved aggregator; ticks older than its last
+    // event are then dropped as out of order.
+    pub fn resume_from(&mut self, saved: BarAggregator) -> Result<(), EngineError> {
+        if saved.symbol != self.symbol || saved.step_seconds != self.step_seconds {
+            return Err(EngineError::AggregatorMismatch {
+                saved_symbol: saved.symbol.to_string(),
+                saved_step_seconds: saved.step_seconds,
+                symbol: self.symbol.to_string(),
+                step_seconds: self.step_seconds,
+            });
+        }
+        *self = saved;
+        Ok(())
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
     pub fn report(&self) -> &BarAggregationReport {
         &self.report
     }
@@ -176,4 +201,39 @@ mod tests {
         assert!(out.is_none());
         assert_eq!(agg.report().out_of_order_events, 1);
     }
+
+    #[test]
+    fn resumed_aggregator_continues_the_working_bar() {
+        let mut agg = BarAggregator::new("BTC-USDT".to_string(), 60).unwrap();
+        agg.ingest(MarketEvent::Tick {
+            timestamp: 0,
+            price: 10.0,
+        });
+        agg.ingest(MarketEvent::Tick {
+            timestamp: 20,
+            price: 12.0,
+        });
+        let saved: BarAggregator =
+            serde_json::from_str(&serde_json::to_string(&agg).unwrap()).unwrap();
+
+        let mut resumed = BarAggregator::new("BTC-USDT".to_string(), 60).unwrap();
+        resumed.resume_from(saved).expect("same symbol and step");
+        resumed.ingest(MarketEvent::Tick {
+            timestamp: 30,
+            price: 9.0,
+        });
+        let bar = resumed
+            .ingest(MarketEvent::Tick {
+                timestamp: 60,
+                price: 11.0,
+            })
+            .expect("finalize resumed bar");
+        assert_eq!(
+            (bar.open, bar.high, bar.low, bar.close),
+            (10.0, 12.0, 9.0, 9.0)
+        );
+
+        let mut other = BarAggregator::new("ETH-USDT".to_string(), 60).unwrap();
+        assert!(other.resume_from(resumed).is_err());
+    }
 }
@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 #[derive(Debug, Clone, Copy)]
 pub enum MissingValuePolicy {
     Error,
@@ -12,7 +14,7 @@ pub struct SentimentPoint {
     pub values: Vec<f64>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct SentimentReport {
     pub duplicates: usize,
     pub out_of_order: usize,
@@ -31,20 +33,191 @@ pub fn align_with_bars(
     sentiment: &[SentimentPoint],
     sentiment_lag_seconds: i64,
 ) -> Vec<Option<SentimentPoint>> {
-    use std::collections::BTreeMap;
+    let aligner = SentimentAligner::new(sentiment, sentiment_lag_seconds);
+    bar_timestamps
+        .iter()
+        .map(|ts| aligner.at(*ts).cloned())
+        .collect()
+}
+
+// Online form of `align_with_bars`, for bars that only exist once they close (realtime streams):
+// each bar sees the latest point at or before `bar timestamp - lag`.
+#[derive(Debug, Clone, Default)]
+pub struct SentimentAligner {
+    points: BTreeMap<i64, SentimentPoint>,
+    lag_seconds: i64,
+}
 
-    let mut map: BTreeMap<i64, SentimentPoint> = BTreeMap::new();
-    for point in sentiment {
-        map.insert(point.timestamp, point.clone());
+impl SentimentAligner {
+    pub fn new(sentiment: &[SentimentPoint], sentiment_lag_seconds: i64) -> Self {
+        Self {
+            points: sentiment
+                .iter()
+                .map(|point| (point.timestamp, point.clone()))
+                .collect(),
+            lag_seconds: sentiment_lag_seconds,
+        }
     }
 
-    bar_timestamps
+    pub fn at(&self, bar_timestamp: i64) -> Option<&SentimentPoint> {
+        let cutoff = bar_timestamp.saturating_sub(self.lag_seconds);
+        self.points
+            .range(..=cutoff)
+            .next_back()
+            .map(|(_, point)| point)
+    }
+}
+
+// One input of `merge_weighted`: its points, column names, weight and alignment lag.
+#[derive(Debug, Clone, Copy)]
+pub struct WeightedSentiment<'a> {
+    pub points: &'a [SentimentPoint],
+    pub schema: &'a [String],
+    pub weight: f64,
+    pub lag_seconds: i64,
+}
+
+// Blends several sentiment series into one that, aligned with `base_lag_seconds`, gives each bar
+// the weighted mean of what every input would give it under its own lag. Input timestamps are
+// shifted by `lag - base_lag`; at each of them a column is the weighted mean of the inputs that
+// have that column and a point so far. Timestamps before every column has an input are skipped.
+// Returns the points and the merged schema (columns in order of first appearance).
+pub fn merge_weighted(
+    inputs: &[WeightedSentiment<'_>],
+    base_lag_seconds: i64,
+) -> (Vec<SentimentPoint>, Vec<String>) {
+    let mut schema: Vec<String> = Vec::new();
+    let columns: Vec<Vec<usize>> = inputs
         .iter()
-        .map(|ts| {
-            let cutoff = ts.saturating_sub(sentiment_lag_seconds);
-            map.range(..=cutoff)
-                .next_back()
-                .map(|(_, point)| point.clone())
+        .map(|input| {
+            input
+                .schema
+                .iter()
+                .map(|name| match schema.iter().position(|known| known == name) {
+                    Some(idx) => idx,
+                    None => {
+                        schema.push(name.clone());
+                        schema.len() - 1
+                    }
+                })
+                .collect()
         })
-        .collect()
+        .collect();
+    let aligners: Vec<SentimentAligner> = inputs
+        .iter()
+        .map(|input| SentimentAligner::new(input.points, input.lag_seconds - base_lag_seconds))
+        .collect();
+    let timestamps: BTreeSet<i64> = inputs
+        .iter()
+        .flat_map(|input| {
+            let shift = input.lag_seconds - base_lag_seconds;
+            input
+                .points
+                .iter()
+                .map(move |point| point.timestamp + shift)
+        })
+        .collect();
+
+    let mut points = Vec::with_capacity(timestamps.len());
+    for timestamp in timestamps {
+        let mut sums = vec![0.0; schema.len()];
+        let mut weights = vec![0.0; schema.len()];
+        // A column fed by a single input keeps that value as is.
+        let mut only: Vec<Option<f64>> = vec![None; schema.len()];
+        let mut contributors = vec![0usize; schema.len()];
+        for ((input, aligner), columns) in inputs.iter().zip(&aligners).zip(&columns) {
+            let Some(point) = aligner.at(timestamp) else {
+                continue;
+            };
+            for (value, &column) in point.values.iter().zip(columns) {
+                sums[column] += input.weight * value;
+                weights[column] += input.weight;
+                only[column] = Some(*value);
+                contributors[column] += 1;
+            }
+        }
+        if contributors.contains(&0) {
+            continue;
+        }
+        let values = (0..schema.len())
+            .map(|column| match (contributors[column], only[column]) {
+                (1, Some(value)) => value,
+                _ => sums[column] / weights[column],
+            })
+            .collect();
+        points.push(SentimentPoint { timestamp, values });
+    }
+    (points, schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{align_with_bars, merge_weighted, SentimentPoint, WeightedSentiment};
+
+    fn point(timestamp: i64, values: &[f64]) -> SentimentPoint {
+        SentimentPoint {
+            timestamp,
+            values: values.to_vec(),
+        }
+    }
+
+    #[test]
+    fn merge_weighted_blends_shared_columns_under_each_source_lag() {
+        let news = vec![point(0, &[1.0]), point(120, &[-1.0])];
+        let social = vec![point(0, &[0.0, 5.0]), point(60, &[0.5, 7.0])];
+        let news_schema = vec!["score".to_string()];
+        let social_schema = vec!["score".to_string(), "volume".to_string()];
+        let (points, schema) = merge_weighted(
+            &[
+                WeightedSentiment {
+                    points: &news,
+                    schema: &news_schema,
+                    weight: 3.0,
+                    lag_seconds: 60,
+                },
+                WeightedSentiment {
+                    points: &social,
+                    schema: &social_schema,
+                    weight: 1.0,
+                    lag_seconds: 0,
+                },
+            ],
+            0,
+        );
+        assert_eq!(schema, vec!["score", "volume"]);
+
+        let bars = [0, 60, 120, 180];
+        let aligned = align_with_bars(&bars, &points, 0);
+        // News only counts from t=60 (its lag); before that only social has a point.
+        assert_eq!(aligned[0], Some(point(0, &[0.0, 5.0])));
+        assert_eq!(aligned[1], Some(point(60, &[(3.0 + 0.5) / 4.0, 7.0])));
+        assert_eq!(aligned[2], Some(point(60, &[(3.0 + 0.5) / 4.0, 7.0])));
+        assert_eq!(aligned[3], Some(point(180, &[(-3.0 + 0.5) / 4.0, 7.0])));
+    }
+
+    #[test]
+    fn merge_weighted_skips_points_until_every_column_has_a_source() {
+        let a = vec![point(0, &[1.0])];
+        let b = vec![point(30, &[2.0])];
+        let a_schema = vec!["a".to_string()];
+        let b_schema = vec!["b".to_string()];
+        let (points, _) = merge_weighted(
+            &[
+                WeightedSentiment {
+                    points: &a,
+                    schema: &a_schema,
+                    weight: 1.0,
+                    lag_seconds: 10,
+                },
+                WeightedSentiment {
+                    points: &b,
+                    schema: &b_schema,
+                    weight: 1.0,
+                    lag_seconds: 10,
+                },
+            ],
+            10,
+        );
+        assert_eq!(points, vec![point(30, &[1.0, 2.0])]);
+    }
 }
@@ -0,0 +1,261 @@
+use super::{BuyAndHold, HoldStrategy, SimpleSma, Strategy, StrategyKind};
+use crate::entities::portfolio::Portfolio;
+use crate::services::audit::AuditEvent;
+use crate::value_objects::action::Action;
+use crate::value_objects::action_type::ActionType;
+use crate::value_objects::bar::Bar;
+use crate::value_objects::tick::Tick;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+// A change to the strategy of a running paper session, applied before the next bar.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StrategyChange {
+    // Switches to the SMA baseline (or retunes it, keeping its price history).
+    SmaWindows { short: usize, long: usize },
+    BuyAndHold,
+    Hold,
+    // The action a remote agent strategy answers with when the agent fails or runs out of budget.
+    FallbackAction(ActionType),
+}
+
+impl StrategyChange {
+    // `sma <short> <long>`, `buy_and_hold`, `hold` or `fallback <buy|sell|hold>`.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let words: Vec<String> = raw.split_whitespace().map(str::to_lowercase).collect();
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        match words.as_slice() {
+            ["sma", short, long] => {
+                let window = |raw: &str| {
+                    raw.parse::<usize>()
+                        .ok()
+                        .filter(|w| *w > 0)
+                        .ok_or_else(|| format!("invalid SMA window '{raw}'"))
+                };
+                let (short, long) = (window(short)?, window(long)?);
+                if short >= long {
+                    return Err(format!(
+                        "the short window ({short}) must be below the long one ({long})"
+                    ));
+                }
+                Ok(Self::SmaWindows { short, long })
+            }
+            ["buy_and_hold"] => Ok(Self::BuyAndHold),
+            ["hold"] => Ok(Self::Hold),
+            ["fallback", action] => match *action {
+                "buy" => Ok(Self::FallbackAction(ActionType::Buy)),
+                "sell" => Ok(Self::FallbackAction(ActionType::Sell)),
+                "hold" => Ok(Self::FallbackAction(ActionType::Hold)),
+                other => Err(format!("unknown fallback action '{other}' (buy|sell|hold)")),
+            },
+            _ => Err(format!(
+                "unknown strategy change '{}' (sma <short> <long> | buy_and_hold | hold | fallback <buy|sell|hold>)",
+                raw.trim()
+            )),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::SmaWindows { short, long } => format!("sma {short} {long}"),
+            Self::BuyAndHold => "buy_and_hold".to_string(),
+            Self::Hold => "hold".to_string(),
+            Self::FallbackAction(action) => format!("fallback {action:?}").to_lowercase(),
+        }
+    }
+}
+
+// Changes queued by a UI for a running session. Clones share the queue.
+#[derive(Debug, Clone, Default)]
+pub struct StrategyChanges(Arc<Mutex<Vec<StrategyChange>>>);
+
+impl StrategyChanges {
+    pub fn push(&self, change: StrategyChange) {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(change);
+    }
+
+    pub fn take(&self) -> Vec<StrategyChange> {
+        std::mem::take(
+            &mut *self
+                .0
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        )
+    }
+}
+
+// Wraps the session's strategy so queued changes take effect at the next bar boundary, each one
+// recorded as a `strategy`/`hot_swap` audit event (`hot_swap_rejected` when it cannot apply).
+pub struct HotSwapStrategy {
+    inner: StrategyKind,
+    changes: StrategyChanges,
+    run_id: String,
+    audit_events: Vec<AuditEvent>,
+}
+
+impl HotSwapStrategy {
+    pub fn new(run_id: String, inner: StrategyKind, changes: StrategyChanges) -> Self {
+        Self {
+            inner,
+            changes,
+            run_id,
+            audit_events: Vec::new(),
+        }
+    }
+
+    fn apply(&mut self, change: StrategyChange, bar: &Bar) {
+        let from = self.inner.name().to_string();
+        let result = match (&change, &mut self.inner) {
+            (StrategyChange::SmaWindows { short, long }, StrategyKind::SimpleSma(sma)) => {
+                sma.set_windows(*short, *long);
+                Ok(())
+            }
+            (StrategyChange::SmaWindows { short, long }, _) => {
+                self.replace(StrategyKind::SimpleSma(SimpleSma::new(*short, *long)));
+                Ok(())
+            }
+            (StrategyChange::BuyAndHold, _) => {
+                self.replace(StrategyKind::BuyAndHold(BuyAndHold::new(1.0)));
+                Ok(())
+            }
+            (StrategyChange::Hold, _) => {
+                self.replace(StrategyKind::Hold(HoldStrategy));
+                Ok(())
+            }
+            (StrategyChange::FallbackAction(action), StrategyKind::Agent(agent)) => {
+                agent.fallback_action = *action;
+                Ok(())
+            }
+            (StrategyChange::FallbackAction(_), _) => Err(format!(
+                "{from} has no fallback action (agent strategies only)"
+            )),
+        };
+        self.audit_events.push(AuditEvent {
+            run_id: self.run_id.clone(),
+            timestamp: bar.timestamp,
+            stage: "strategy".to_string(),
+            symbol: Some(bar.symbol.to_string()),
+            action: if result.is_ok() {
+                "hot_swap".to_string()
+            } else {
+                "hot_swap_rejected".to_string()
+            },
+            error: result.err(),
+            details: json!({
+                "change": change.describe(),
+                "from": from,
+                "to": self.inner.name(),
+            }),
+        });
+    }
+
+    // Keeps the audit events the outgoing strategy recorded so far.
+    fn replace(&mut self, next: StrategyKind) {
+        let mut pending = self.inner.drain_audit_events();
+        self.audit_events.append(&mut pending);
+        self.inner = next;
+    }
+}
+
+impl Strategy for HotSwapStrategy {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn on_bar(&mut self, bar: &Bar, portfolio: &Portfolio) -> Action {
+        for change in self.changes.take() {
+            self.apply(change, bar);
+        }
+        self.inner.on_bar(bar, portfolio)
+    }
+
+    fn on_tick(&mut self, tick: &Tick) {
+        self.inner.on_tick(tick);
+    }
+
+    fn drain_audit_events(&mut self) -> Vec<AuditEvent> {
+        let mut events = std::mem::take(&mut self.audit_events);
+        events.append(&mut self.inner.drain_audit_events());
+        events.sort_by_key(|event| event.timestamp);
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HotSwapStrategy, StrategyChange, StrategyChanges};
+    use crate::entities::portfolio::Portfolio;
+    use crate::services::strategy::{BuyAndHold, Strategy, StrategyKind};
+    use crate::value_objects::action_type::ActionType;
+    use crate::value_objects::bar::Bar;
+
+    fn bar(ts: i64, close: f64) -> Bar {
+        Bar {
+            symbol: "BTCUSD".into(),
+            timestamp: ts,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 1.0,
+        }
+    }
+
+    #[test]
+    fn queued_changes_apply_at_the_next_bar_and_are_audited() {
+        assert_eq!(
+            StrategyChange::parse(" SMA 2 3 "),
+            Ok(StrategyChange::SmaWindows { short: 2, long: 3 })
+        );
+        assert_eq!(
+            StrategyChange::parse("fallback sell"),
+            Ok(StrategyChange::FallbackAction(ActionType::Sell))
+        );
+        assert!(StrategyChange::parse("sma 5 2")
+            .unwrap_err()
+            .contains("below the long"));
+        assert!(StrategyChange::parse("martingale")
+            .unwrap_err()
+            .contains("unknown strategy change"));
+
+        let changes = StrategyChanges::default();
+        let mut strategy = HotSwapStrategy::new(
+            "run".to_string(),
+            StrategyKind::BuyAndHold(BuyAndHold::new(1.0)),
+            changes.clone(),
+        );
+        let portfolio = Portfolio::new_with_cash(1_000.0);
+        assert_eq!(
+            strategy.on_bar(&bar(1, 10.0), &portfolio).action_type,
+            ActionType::Buy
+        );
+
+        changes.push(StrategyChange::SmaWindows { short: 1, long: 2 });
+        changes.push(StrategyChange::FallbackAction(ActionType::Hold));
+        assert_eq!(strategy.name(), "buy_and_hold");
+        // Warming up the new SMA: nothing to do on its first bar.
+        assert_eq!(
+            strategy.on_bar(&bar(2, 10.0), &portfolio).action_type,
+            ActionType::Hold
+        );
+        assert_eq!(strategy.name(), "simple_sma");
+        assert_eq!(
+            strategy.on_bar(&bar(3, 12.0), &portfolio).action_type,
+            ActionType::Buy
+        );
+
+        let events = strategy.drain_audit_events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            (events[0].action.as_str(), events[0].timestamp),
+            ("hot_swap", 2)
+        );
+        assert_eq!(events[0].details["from"], "buy_and_hold");
+        assert_eq!(events[0].details["to"], "simple_sma");
+        assert_eq!(events[1].action, "hot_swap_rejected");
+        assert!(events[1].error.as_deref().unwrap().contains("agent"));
+    }
+}
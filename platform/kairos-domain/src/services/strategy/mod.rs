@@ -3,7 +3,7 @@ use crate::repositories::agent as agent_port;
 use crate::services::agent::{ActionRequest, ActionResponse, PortfolioState};
 use crate::services::audit::AuditEvent;
 use crate::services::features::{FeatureBuilder, Observation};
-use crate::services::sentiment::SentimentPoint;
+use crate::services::sentiment::{SentimentAligner, SentimentPoint};
 use crate::value_objects::action::Action;
 use crate::value_objects::action_type::ActionType;
 use crate::value_objects::bar::Bar;
@@ -11,6 +11,9 @@ use crate::value_objects::tick::Tick;
 use chrono::{DateTime, TimeZone, Utc};
 use chrono::{NaiveDate, NaiveDateTime};
 use serde_json::json;
+use std::time::Instant;
+
+pub mod hot_swap;
 
 pub trait Strategy {
     fn name(&self) -> &str;
@@ -45,8 +48,9 @@ impl Strategy for BuyAndHold {
         "buy_and_hold"
     }
 
-    fn on_bar(&mut self, _bar: &Bar, _portfolio: &Portfolio) -> Action {
-        if self.has_bought {
+    fn on_bar(&mut self, bar: &Bar, portfolio: &Portfolio) -> Action {
+        // A resumed paper session starts with a fresh strategy but may already hold the position.
+        if self.has_bought || portfolio.position_qty(&bar.symbol) > 0.0 {
             return Action::hold();
         }
         self.has_bought = true;
@@ -73,6 +77,12 @@ impl SimpleSma {
         }
     }
 
+    // Retunes a running strategy; the prices seen so far still count toward the new windows.
+    pub fn set_windows(&mut self, short_window: usize, long_window: usize) {
+        self.short_window = short_window;
+        self.long_window = long_window;
+    }
+
     fn sma(&self, window: usize) -> Option<f64> {
         if self.prices.len() < window || window == 0 {
             return None;
@@ -126,6 +136,14 @@ impl Strategy for HoldStrategy {
     }
 }
 
+// Per-run limits on remote agent usage. Once a limit is hit, the strategy stops calling the
+// agent and answers every remaining bar with `fallback_action`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AgentBudget {
+    pub max_calls: Option<u64>,
+    pub max_total_ms: Option<u64>,
+}
+
 pub struct AgentStrategy {
     pub run_id: String,
     pub symbol: String,
@@ -137,6 +155,12 @@ pub struct AgentStrategy {
     pub agent: Box<dyn agent_port::AgentClient>,
     pub features: FeatureBuilder,
     pub sentiment: Vec<Option<SentimentPoint>>,
+    // Realtime runs: sentiment looked up by bar timestamp instead of the pre-aligned `sentiment`.
+    online_sentiment: Option<SentimentAligner>,
+    budget: AgentBudget,
+    calls: u64,
+    total_ms: u64,
+    budget_exceeded: bool,
     index: usize,
     audit_events: Vec<AuditEvent>,
 }
@@ -166,11 +190,65 @@ impl AgentStrategy {
             agent,
             features,
             sentiment,
+            online_sentiment: None,
+            budget: AgentBudget::default(),
+            calls: 0,
+            total_ms: 0,
+            budget_exceeded: false,
             index: 0,
             audit_events: Vec::new(),
         }
     }
 
+    pub fn with_budget(mut self, budget: AgentBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    // For bars that are not known up front (realtime streams); `sentiment` is then ignored.
+    pub fn with_online_sentiment(mut self, aligner: SentimentAligner) -> Self {
+        self.online_sentiment = Some(aligner);
+        self
+    }
+
+    pub fn budget_exceeded(&self) -> bool {
+        self.budget_exceeded
+    }
+
+    fn budget_breach(&self) -> Option<&'static str> {
+        if let Some(max_calls) = self.budget.max_calls {
+            if self.calls >= max_calls {
+                return Some("max_calls");
+            }
+        }
+        if let Some(max_total_ms) = self.budget.max_total_ms {
+            if self.total_ms >= max_total_ms {
+                return Some("max_total_ms");
+            }
+        }
+        None
+    }
+
+    fn record_budget_breach(&mut self, timestamp: i64, reason: &str) {
+        self.budget_exceeded = true;
+        self.audit_events.push(AuditEvent {
+            run_id: self.run_id.clone(),
+            timestamp,
+            stage: "agent".to_string(),
+            symbol: Some(self.symbol.clone()),
+            action: "budget_exceeded".to_string(),
+            error: None,
+            details: json!({
+                "url": self.agent_url.clone(),
+                "reason": reason,
+                "calls": self.calls,
+                "total_ms": self.total_ms,
+                "max_calls": self.budget.max_calls,
+                "max_total_ms": self.budget.max_total_ms,
+            }),
+        });
+    }
+
     fn build_request(
         &self,
         bar: &Bar,
@@ -242,27 +320,48 @@ impl Strategy for AgentStrategy {
     }
 
     fn on_bar(&mut self, bar: &Bar, portfolio: &Portfolio) -> Action {
-        let sentiment_values = self
-            .sentiment
-            .get(self.index)
-            .and_then(|point| point.as_ref())
-            .map(|point| point.values.as_slice());
+        let sentiment_point = match &self.online_sentiment {
+            Some(aligner) => aligner.at(bar.timestamp),
+            None => self
+                .sentiment
+                .get(self.index)
+                .and_then(|point| point.as_ref()),
+        };
+        let sentiment_values = sentiment_point.map(|point| point.values.as_slice());
         let observation = self.features.update(bar, sentiment_values);
         let request = self.build_request(bar, &observation, portfolio);
 
-        let result = self.agent.act(&request);
+        if !self.budget_exceeded {
+            if let Some(reason) = self.budget_breach() {
+                self.record_budget_breach(bar.timestamp, reason);
+            }
+        }
+
+        let result = if self.budget_exceeded {
+            Err(None)
+        } else {
+            let start = Instant::now();
+            let result = self.agent.act(&request).map_err(Some);
+            self.calls += 1;
+            self.total_ms = self
+                .total_ms
+                .saturating_add(start.elapsed().as_millis() as u64);
+            result
+        };
         let (response, used_fallback) = match result {
             Ok(response) => (response, false),
-            Err(err) => {
+            Err(None) => (self.fallback_response(), true),
+            Err(Some(err)) => {
                 self.audit_events.push(AuditEvent {
                     run_id: self.run_id.clone(),
                     timestamp: bar.timestamp,
                     stage: "agent".to_string(),
                     symbol: Some(self.symbol.clone()),
                     action: "error".to_string(),
-                    error: Some(err),
+                    error: Some(err.to_string()),
                     details: json!({
                         "url": self.agent_url.clone(),
+                        "kind": err.kind(),
                     }),
                 });
                 (self.fallback_response(), true)
@@ -283,6 +382,7 @@ impl Strategy for AgentStrategy {
             details: json!({
                 "url": self.agent_url.clone(),
                 "used_fallback": used_fallback,
+                "budget_exceeded": self.budget_exceeded,
                 "response_action_type": response.action_type,
                 "response_size": response.size,
                 "response_reason": response.reason,
@@ -344,14 +444,15 @@ impl Strategy for StrategyKind {
 
 #[cfg(test)]
 mod tests {
-    use super::{AgentStrategy, BuyAndHold, SimpleSma, Strategy};
+    use super::{AgentBudget, AgentStrategy, BuyAndHold, SimpleSma, Strategy};
     use crate::entities::portfolio::Portfolio;
+    use crate::errors::AgentError;
     use crate::repositories::agent::AgentClient as AgentPort;
     use crate::services::agent::{
         ActionBatchRequest, ActionBatchResponse, ActionRequest, ActionResponse,
     };
     use crate::services::features::{FeatureBuilder, FeatureConfig, ReturnMode};
-    use crate::services::sentiment::SentimentPoint;
+    use crate::services::sentiment::{SentimentAligner, SentimentPoint};
     use crate::value_objects::action_type::ActionType;
     use crate::value_objects::bar::Bar;
     use crate::value_objects::side::Side;
@@ -360,7 +461,7 @@ mod tests {
 
     fn bar(ts: i64, close: f64) -> Bar {
         Bar {
-            symbol: "BTCUSD".to_string(),
+            symbol: "BTCUSD".into(),
             timestamp: ts,
             open: close,
             high: close,
@@ -419,7 +520,7 @@ mod tests {
     }
 
     impl AgentPort for MockAgent {
-        fn act(&self, request: &ActionRequest) -> Result<ActionResponse, String> {
+        fn act(&self, request: &ActionRequest) -> Result<ActionResponse, AgentError> {
             let n = self.calls.get() + 1;
             self.calls.set(n);
             *self.last_observation_len.borrow_mut() = Some(request.observation.len());
@@ -434,11 +535,14 @@ mod tests {
                     reason: None,
                 })
             } else {
-                Err("agent_down".to_string())
+                Err(AgentError::Other("agent_down".to_string()))
             }
         }
 
-        fn act_batch(&self, _request: &ActionBatchRequest) -> Result<ActionBatchResponse, String> {
+        fn act_batch(
+            &self,
+            _request: &ActionBatchRequest,
+        ) -> Result<ActionBatchResponse, AgentError> {
             Ok(ActionBatchResponse { items: Vec::new() })
         }
     }
@@ -506,4 +610,106 @@ mod tests {
             .unwrap_or(0);
         assert_eq!(obs_len, 5);
     }
+
+    #[test]
+    fn agent_strategy_looks_up_online_sentiment_by_bar_timestamp() {
+        let builder = FeatureBuilder::new(FeatureConfig {
+            return_mode: ReturnMode::Pct,
+            sma_windows: vec![2],
+            volatility_windows: vec![],
+            rsi_enabled: false,
+        });
+        let points = [SentimentPoint {
+            timestamp: 60,
+            values: vec![0.5, 0.6],
+        }];
+        let mut strategy = AgentStrategy::new(
+            "run1".to_string(),
+            "BTCUSD".to_string(),
+            "1min".to_string(),
+            "v1".to_string(),
+            "v1".to_string(),
+            "http://agent".to_string(),
+            ActionType::Hold,
+            Box::new(MockAgent::default()),
+            builder,
+            Vec::new(),
+        )
+        .with_online_sentiment(SentimentAligner::new(&points, 30));
+
+        let portfolio = Portfolio::new_with_cash(1000.0);
+        // 60 - 30s lag is before the first point; 120 - 30 sees it.
+        for ts in [60, 120] {
+            strategy.on_bar(&bar(ts, 10.0), &portfolio);
+        }
+        let observation_lens: Vec<u64> = strategy
+            .drain_audit_events()
+            .iter()
+            .filter(|e| e.stage == "agent" && (e.action == "call" || e.action == "fallback"))
+            .filter_map(|e| e.details.get("observation_len").and_then(|v| v.as_u64()))
+            .collect();
+        assert_eq!(observation_lens, vec![2, 4]);
+    }
+
+    #[test]
+    fn agent_strategy_switches_to_fallback_when_call_budget_is_exhausted() {
+        let agent = Box::new(MockAgent::default());
+        let builder = FeatureBuilder::new(FeatureConfig {
+            return_mode: ReturnMode::Pct,
+            sma_windows: vec![2],
+            volatility_windows: vec![2],
+            rsi_enabled: false,
+        });
+
+        let mut strategy = AgentStrategy::new(
+            "run1".to_string(),
+            "BTCUSD".to_string(),
+            "1min".to_string(),
+            "v1".to_string(),
+            "v1".to_string(),
+            "http://agent".to_string(),
+            ActionType::Hold,
+            agent,
+            builder,
+            Vec::new(),
+        )
+        .with_budget(AgentBudget {
+            max_calls: Some(1),
+            max_total_ms: None,
+        });
+
+        let portfolio = Portfolio::new_with_cash(1000.0);
+        let a1 = strategy.on_bar(&bar(1, 10.0), &portfolio);
+        assert_eq!(a1.action_type, ActionType::Buy);
+        assert!(!strategy.budget_exceeded());
+
+        for ts in 2..5 {
+            let action = strategy.on_bar(&bar(ts, 10.0), &portfolio);
+            assert_eq!(action.action_type, ActionType::Hold);
+        }
+        assert!(strategy.budget_exceeded());
+
+        let events = strategy.drain_audit_events();
+        let breaches: Vec<_> = events
+            .iter()
+            .filter(|e| e.stage == "agent" && e.action == "budget_exceeded")
+            .collect();
+        assert_eq!(breaches.len(), 1);
+        assert_eq!(breaches[0].timestamp, 2);
+        assert_eq!(
+            breaches[0].details.get("reason").and_then(|v| v.as_str()),
+            Some("max_calls")
+        );
+        // The mock errors after its first call; no error events means it was never called again.
+        assert!(!events
+            .iter()
+            .any(|e| e.stage == "agent" && e.action == "error"));
+        assert_eq!(
+            events
+                .iter()
+                .filter(|e| e.stage == "agent" && e.action == "fallback")
+                .count(),
+            3
+        );
+    }
 }
@@ -0,0 +1,218 @@
+use super::rng::SplitMix64;
+use crate::value_objects::bar::Bar;
+use crate::value_objects::symbol::Symbol;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Regime {
+    // Persistent drift that flips direction every few thousand bars.
+    Trending,
+    // Mean-reverting around the starting price, no net drift.
+    Choppy,
+    // Trending prices with missing bars and occasional price jumps between bars.
+    Gappy,
+}
+
+impl Regime {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        match raw.trim().to_lowercase().as_str() {
+            "trending" => Ok(Regime::Trending),
+            "choppy" => Ok(Regime::Choppy),
+            "gappy" => Ok(Regime::Gappy),
+            other => Err(format!(
+                "unsupported regime '{other}' (expected trending|choppy|gappy)"
+            )),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Regime::Trending => "trending",
+            Regime::Choppy => "choppy",
+            Regime::Gappy => "gappy",
+        }
+    }
+
+    // Share of bars dropped when no explicit gap rate is given.
+    pub fn default_gap_rate(self) -> f64 {
+        match self {
+            Regime::Gappy => 0.01,
+            Regime::Trending | Regime::Choppy => 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntheticSpec {
+    pub symbol: String,
+    pub bars: usize,
+    pub start_ts: i64,
+    pub step_seconds: i64,
+    pub start_price: f64,
+    pub regime: Regime,
+    pub seed: u64,
+    // Probability that a bar is left out of the series.
+    pub gap_rate: f64,
+    // Probability that a bar is emitted twice (the copy has a slightly different close).
+    pub duplicate_rate: f64,
+}
+
+impl SyntheticSpec {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.bars == 0 {
+            return Err("bars must be > 0".to_string());
+        }
+        if self.step_seconds <= 0 {
+            return Err("step_seconds must be > 0".to_string());
+        }
+        if !(self.start_price.is_finite() && self.start_price > 0.0) {
+            return Err("start_price must be finite and > 0".to_string());
+        }
+        for (name, rate) in [
+            ("gap_rate", self.gap_rate),
+            ("duplicate_rate", self.duplicate_rate),
+        ] {
+            if !(0.0..1.0).contains(&rate) {
+                return Err(format!("{name} must be in [0, 1)"));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntheticSeries {
+    // Ascending by timestamp; an injected duplicate directly follows its original.
+    pub bars: Vec<Bar>,
+    pub gaps: usize,
+    pub duplicates: usize,
+}
+
+// Deterministic for a given spec: the same seed always yields the same bars.
+pub fn generate_bars(spec: &SyntheticSpec) -> Result<SyntheticSeries, String> {
+    spec.validate()?;
+    let mut rng = SplitMix64::new(spec.seed);
+    let mut bars = Vec::with_capacity(spec.bars);
+    let (mut gaps, mut duplicates) = (0, 0);
+    let mut price = spec.start_price;
+    let symbol = Symbol::new(&spec.symbol);
+    let mut drift = 0.0002;
+
+    for i in 0..spec.bars {
+        let ret = match spec.regime {
+            Regime::Trending | Regime::Gappy => {
+                if i > 0 && i % 5_000 == 0 && rng.next_f64() < 0.5 {
+                    drift = -drift;
+                }
+                drift + 0.002 * rng.next_normal()
+            }
+            Regime::Choppy => 0.0025 * (spec.start_price / price).ln() + 0.003 * rng.next_normal(),
+        };
+        let jump = if spec.regime == Regime::Gappy && rng.next_f64() < 0.002 {
+            0.03 * rng.next_normal()
+        } else {
+            0.0
+        };
+        let open = (price * (1.0 + jump)).max(0.01);
+        let close = (open * (1.0 + ret)).max(0.01);
+        let wick = open.max(close) * 0.001 * rng.next_f64();
+        let bar = Bar {
+            symbol: symbol.clone(),
+            timestamp: spec.start_ts + i as i64 * spec.step_seconds,
+            open,
+            high: open.max(close) + wick,
+            low: (open.min(close) - wick).max(0.0),
+            close,
+            volume: 1_000.0 * (1.0 + rng.next_f64()) * (1.0 + 20.0 * ret.abs()),
+        };
+        price = close;
+
+        // Draws happen every bar so toggling one rate does not reshuffle the other.
+        let skip = rng.next_f64() < spec.gap_rate;
+        let duplicate = rng.next_f64() < spec.duplicate_rate;
+        if skip {
+            gaps += 1;
+            continue;
+        }
+        if duplicate {
+            let mut copy = bar.clone();
+            copy.close *= 1.0005;
+            copy.high = copy.high.max(copy.close);
+            bars.push(bar);
+            bars.push(copy);
+            duplicates += 1;
+        } else {
+            bars.push(bar);
+        }
+    }
+
+    Ok(SyntheticSeries {
+        bars,
+        gaps,
+        duplicates,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate_bars, Regime, SyntheticSpec};
+
+    fn spec(regime: Regime) -> SyntheticSpec {
+        SyntheticSpec {
+            symbol: "TEST".to_string(),
+            bars: 20_000,
+            start_ts: 1_700_000_000,
+            step_seconds: 60,
+            start_price: 100.0,
+            regime,
+            seed: 7,
+            gap_rate: regime.default_gap_rate(),
+            duplicate_rate: 0.0,
+        }
+    }
+
+    #[test]
+    fn same_seed_same_bars_and_valid_ohlc() {
+        let a = generate_bars(&spec(Regime::Trending)).expect("bars");
+        let b = generate_bars(&spec(Regime::Trending)).expect("bars");
+        assert_eq!(a, b);
+        assert_eq!(a.bars.len(), 20_000);
+        assert!(a
+            .bars
+            .windows(2)
+            .all(|w| w[1].timestamp - w[0].timestamp == 60));
+        assert!(a
+            .bars
+            .iter()
+            .all(|bar| bar.low <= bar.open.min(bar.close) && bar.high >= bar.open.max(bar.close)));
+    }
+
+    #[test]
+    fn choppy_stays_near_start_price() {
+        let series = generate_bars(&spec(Regime::Choppy)).expect("bars");
+        let last = series.bars.last().expect("last").close;
+        assert!((50.0..200.0).contains(&last), "{last}");
+    }
+
+    #[test]
+    fn gappy_and_duplicates_are_injected_and_counted() {
+        let mut spec = spec(Regime::Gappy);
+        spec.duplicate_rate = 0.01;
+        let series = generate_bars(&spec).expect("bars");
+        assert!(series.gaps > 0 && series.duplicates > 0);
+        assert_eq!(
+            series.bars.len(),
+            spec.bars - series.gaps + series.duplicates
+        );
+        let repeated = series
+            .bars
+            .windows(2)
+            .filter(|w| w[0].timestamp == w[1].timestamp)
+            .count();
+        assert_eq!(repeated, series.duplicates);
+        assert!(generate_bars(&SyntheticSpec {
+            gap_rate: 1.0,
+            ..spec.clone()
+        })
+        .is_err());
+    }
+}
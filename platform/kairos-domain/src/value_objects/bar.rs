@@ -1,6 +1,9 @@
-#[derive(Debug, Clone, PartialEq)]
+use crate::value_objects::symbol::Symbol;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Bar {
-    pub symbol: String,
+    pub symbol: Symbol,
     pub timestamp: i64,
     pub open: f64,
     pub high: f64,
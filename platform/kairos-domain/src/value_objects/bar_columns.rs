@@ -0,0 +1,163 @@
+use crate::value_objects::bar::Bar;
+use crate::value_objects::symbol::Symbol;
+
+// Bars stored column by column: one array per field plus a per-bar index into a small table of
+// distinct symbols, instead of a `Vec<Bar>`. Scans over one field (timestamps, closes) stay
+// contiguous, and replaying into a reused `Bar` does not allocate.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BarColumns {
+    symbols: Vec<Symbol>,
+    symbol_ids: Vec<u32>,
+    timestamps: Vec<i64>,
+    opens: Vec<f64>,
+    highs: Vec<f64>,
+    lows: Vec<f64>,
+    closes: Vec<f64>,
+    volumes: Vec<f64>,
+}
+
+impl BarColumns {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            symbols: Vec::new(),
+            symbol_ids: Vec::with_capacity(capacity),
+            timestamps: Vec::with_capacity(capacity),
+            opens: Vec::with_capacity(capacity),
+            highs: Vec::with_capacity(capacity),
+            lows: Vec::with_capacity(capacity),
+            closes: Vec::with_capacity(capacity),
+            volumes: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, bar: &Bar) {
+        let id = self.intern(&bar.symbol);
+        self.symbol_ids.push(id);
+        self.timestamps.push(bar.timestamp);
+        self.opens.push(bar.open);
+        self.highs.push(bar.high);
+        self.lows.push(bar.low);
+        self.closes.push(bar.close);
+        self.volumes.push(bar.volume);
+    }
+
+    pub fn len(&self) -> usize {
+        self.timestamps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.timestamps.is_empty()
+    }
+
+    pub fn timestamps(&self) -> &[i64] {
+        &self.timestamps
+    }
+
+    pub fn closes(&self) -> &[f64] {
+        &self.closes
+    }
+
+    pub fn symbol(&self, index: usize) -> Option<&Symbol> {
+        let id = *self.symbol_ids.get(index)?;
+        Some(&self.symbols[id as usize])
+    }
+
+    pub fn bar(&self, index: usize) -> Option<Bar> {
+        let mut bar = Bar {
+            symbol: Symbol::default(),
+            timestamp: 0,
+            open: 0.0,
+            high: 0.0,
+            low: 0.0,
+            close: 0.0,
+            volume: 0.0,
+        };
+        self.read_into(index, &mut bar).then_some(bar)
+    }
+
+    // Overwrites `bar` with the bar at `index`; `false` past the end.
+    pub fn read_into(&self, index: usize, bar: &mut Bar) -> bool {
+        let Some(&id) = self.symbol_ids.get(index) else {
+            return false;
+        };
+        // Same-symbol runs skip the reference-count traffic of a clone.
+        let symbol = &self.symbols[id as usize];
+        if !Symbol::ptr_eq(&bar.symbol, symbol) {
+            bar.symbol = symbol.clone();
+        }
+        bar.timestamp = self.timestamps[index];
+        bar.open = self.opens[index];
+        bar.high = self.highs[index];
+        bar.low = self.lows[index];
+        bar.close = self.closes[index];
+        bar.volume = self.volumes[index];
+        true
+    }
+
+    fn intern(&mut self, symbol: &Symbol) -> u32 {
+        // Runs are almost always single-symbol, so the previous bar's symbol is checked first.
+        if let Some(&last) = self.symbol_ids.last() {
+            if self.symbols[last as usize] == *symbol {
+                return last;
+            }
+        }
+        if let Some(id) = self.symbols.iter().position(|known| known == symbol) {
+            return id as u32;
+        }
+        self.symbols.push(symbol.clone());
+        (self.symbols.len() - 1) as u32
+    }
+}
+
+impl FromIterator<Bar> for BarColumns {
+    fn from_iter<I: IntoIterator<Item = Bar>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut columns = Self::with_capacity(iter.size_hint().0);
+        for bar in iter {
+            columns.push(&bar);
+        }
+        columns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BarColumns;
+    use crate::value_objects::bar::Bar;
+
+    fn bar(symbol: &str, timestamp: i64, close: f64) -> Bar {
+        Bar {
+            symbol: symbol.into(),
+            timestamp,
+            open: close - 1.0,
+            high: close + 1.0,
+            low: close - 2.0,
+            close,
+            volume: 5.0,
+        }
+    }
+
+    #[test]
+    fn round_trips_bars_and_interns_symbols() {
+        let bars = vec![
+            bar("BTC-USDT", 60, 10.0),
+            bar("BTC-USDT", 120, 11.0),
+            bar("ETH-USDT", 120, 2.0),
+            bar("BTC-USDT", 180, 12.0),
+        ];
+        let columns: BarColumns = bars.clone().into_iter().collect();
+        assert_eq!(columns.len(), 4);
+        assert_eq!(columns.symbols.len(), 2);
+        assert_eq!(columns.timestamps(), &[60, 120, 120, 180]);
+        assert_eq!(columns.closes(), &[10.0, 11.0, 2.0, 12.0]);
+
+        let mut reused = bar("", 0, 0.0);
+        for (index, expected) in bars.iter().enumerate() {
+            assert!(columns.read_into(index, &mut reused));
+            assert_eq!(&reused, expected);
+            assert_eq!(columns.bar(index).as_ref(), Some(expected));
+        }
+        assert!(!columns.read_into(4, &mut reused));
+        assert_eq!(columns.bar(4), None);
+    }
+}
@@ -1,11 +1,13 @@
 pub mod action;
 pub mod action_type;
 pub mod bar;
+pub mod bar_columns;
 pub mod equity_point;
 pub mod fill;
 pub mod order;
 pub mod position;
 pub mod side;
+pub mod symbol;
 pub mod tick;
 pub mod timeframe;
 pub mod trade;
@@ -1,6 +1,9 @@
-#[derive(Debug, Clone, PartialEq)]
+use crate::value_objects::symbol::Symbol;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Position {
-    pub symbol: String,
+    pub symbol: Symbol,
     pub quantity: f64,
     pub avg_price: f64,
 }
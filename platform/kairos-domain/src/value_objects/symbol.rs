@@ -0,0 +1,173 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+
+// Market symbol shared by every bar, trade and position that names it. Symbols are interned
+// process-wide, so building one allocates at most once per distinct name and cloning is a
+// reference-count bump; hot loops can copy them per bar without touching the allocator.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(Arc<str>);
+
+fn interned() -> &'static Mutex<HashSet<Arc<str>>> {
+    static SYMBOLS: OnceLock<Mutex<HashSet<Arc<str>>>> = OnceLock::new();
+    SYMBOLS.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+impl Symbol {
+    pub fn new(name: &str) -> Self {
+        let mut symbols = interned()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(existing) = symbols.get(name) {
+            return Self(existing.clone());
+        }
+        let name: Arc<str> = Arc::from(name);
+        symbols.insert(name.clone());
+        Self(name)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    // Cheaper than `==` when both sides come from the interner, which is every symbol built
+    // through `Symbol::new`: equal names are then the same allocation.
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        Arc::ptr_eq(&a.0, &b.0)
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<String> for Symbol {
+    fn eq(&self, other: &String) -> bool {
+        &*self.0 == other.as_str()
+    }
+}
+
+impl PartialEq<Symbol> for str {
+    fn eq(&self, other: &Symbol) -> bool {
+        self == &*other.0
+    }
+}
+
+impl PartialEq<Symbol> for &str {
+    fn eq(&self, other: &Symbol) -> bool {
+        *self == &*other.0
+    }
+}
+
+impl PartialEq<Symbol> for String {
+    fn eq(&self, other: &Symbol) -> bool {
+        self.as_str() == &*other.0
+    }
+}
+
+impl Default for Symbol {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Symbol {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<&String> for Symbol {
+    fn from(name: &String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(name: String) -> Self {
+        Self::new(&name)
+    }
+}
+
+impl From<Symbol> for String {
+    fn from(symbol: Symbol) -> Self {
+        symbol.0.to_string()
+    }
+}
+
+impl Serialize for Symbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        Ok(Self::new(&name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Symbol;
+    use std::sync::Arc;
+
+    #[test]
+    fn equal_names_share_one_allocation() {
+        let a = Symbol::new("BTC-USDT");
+        let b = Symbol::from("BTC-USDT".to_string());
+        assert!(Arc::ptr_eq(&a.0, &b.0));
+        assert_eq!(a, b);
+        assert_eq!(a, "BTC-USDT");
+        assert_ne!(a, Symbol::new("ETH-USDT"));
+
+        let json = serde_json::to_string(&a).expect("serialize");
+        assert_eq!(json, "\"BTC-USDT\"");
+        let back: Symbol = serde_json::from_str(&json).expect("deserialize");
+        assert!(Arc::ptr_eq(&a.0, &back.0));
+    }
+}
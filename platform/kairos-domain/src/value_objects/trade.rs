@@ -1,10 +1,11 @@
 use crate::value_objects::side::Side;
-use serde::Serialize;
+use crate::value_objects::symbol::Symbol;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Trade {
     pub timestamp: i64,
-    pub symbol: String,
+    pub symbol: Symbol,
     pub side: Side,
     pub quantity: f64,
     pub price: f64,
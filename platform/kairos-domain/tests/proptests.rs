@@ -10,7 +10,7 @@ use proptest::prelude::*;
 
 fn bar(ts: i64, close: f64) -> Bar {
     Bar {
-        symbol: "BTCUSD".to_string(),
+        symbol: "BTCUSD".into(),
         timestamp: ts,
         open: close,
         high: close,
@@ -1,5 +1,6 @@
 use kairos_domain::entities::metrics::MetricsConfig;
 use kairos_domain::entities::risk::RiskLimits;
+use kairos_domain::errors::AgentError;
 use kairos_domain::repositories::agent::AgentClient as AgentPort;
 use kairos_domain::services::agent::{
     ActionBatchRequest, ActionBatchResponse, ActionRequest, ActionResponse,
@@ -15,7 +16,7 @@ use std::cell::Cell;
 
 fn make_bar(symbol: &str, ts: i64, close: f64) -> Bar {
     Bar {
-        symbol: symbol.to_string(),
+        symbol: symbol.into(),
         timestamp: ts,
         open: close,
         high: close,
@@ -86,7 +87,7 @@ impl ScriptedAgent {
 }
 
 impl AgentPort for ScriptedAgent {
-    fn act(&self, _request: &ActionRequest) -> Result<ActionResponse, String> {
+    fn act(&self, _request: &ActionRequest) -> Result<ActionResponse, AgentError> {
         let i = self.idx.get();
         self.idx.set(i.saturating_add(1));
         let response = self
@@ -97,8 +98,8 @@ impl AgentPort for ScriptedAgent {
         Ok(response)
     }
 
-    fn act_batch(&self, _request: &ActionBatchRequest) -> Result<ActionBatchResponse, String> {
-        Err("not implemented".to_string())
+    fn act_batch(&self, _request: &ActionBatchRequest) -> Result<ActionBatchResponse, AgentError> {
+        Err(AgentError::Other("not implemented".to_string()))
     }
 }
 
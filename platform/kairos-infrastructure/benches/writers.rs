@@ -0,0 +1,93 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use kairos_domain::entities::metrics::MetricsSummary;
+use kairos_domain::value_objects::equity_point::EquityPoint;
+use kairos_domain::value_objects::side::Side;
+use kairos_domain::value_objects::trade::Trade;
+use kairos_infrastructure::reporting::{
+    write_equity_csv, write_equity_parquet, write_summary_html, write_summary_json,
+    write_trades_csv, write_trades_parquet,
+};
+use std::fs;
+use std::path::PathBuf;
+
+const ROWS: usize = 10_000;
+
+fn trades(count: usize) -> Vec<Trade> {
+    (0..count)
+        .map(|i| Trade {
+            timestamp: 1_700_000_000 + (i as i64) * 60,
+            symbol: "BENCH".into(),
+            side: if i % 2 == 0 { Side::Buy } else { Side::Sell },
+            quantity: 1.5,
+            price: 100.0 + (i as f64) * 0.01,
+            fee: 0.1,
+            slippage: 0.05,
+            strategy_id: "bench".to_string(),
+            reason: "signal".to_string(),
+        })
+        .collect()
+}
+
+fn equity(count: usize) -> Vec<EquityPoint> {
+    (0..count)
+        .map(|i| EquityPoint {
+            timestamp: 1_700_000_000 + (i as i64) * 60,
+            equity: 10_000.0 + (i as f64).sin() * 50.0,
+            cash: 5_000.0,
+            position_qty: 1.5,
+            unrealized_pnl: (i as f64).cos() * 10.0,
+            realized_pnl: (i as f64) * 0.01,
+        })
+        .collect()
+}
+
+fn bench_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("kairos_writer_bench_{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("create bench dir");
+    dir
+}
+
+fn csv_writers(c: &mut Criterion) {
+    let dir = bench_dir();
+    let trades = trades(ROWS);
+    let equity = equity(ROWS);
+    let mut group = c.benchmark_group("csv_writers");
+    group.throughput(Throughput::Elements(ROWS as u64));
+    group.bench_function("trades", |b| {
+        b.iter(|| write_trades_csv(&dir.join("trades.csv"), &trades).expect("trades csv"))
+    });
+    group.bench_function("equity", |b| {
+        b.iter(|| write_equity_csv(&dir.join("equity.csv"), &equity).expect("equity csv"))
+    });
+    group.finish();
+    let _ = fs::remove_dir_all(&dir);
+}
+
+fn report_writers(c: &mut Criterion) {
+    let dir = bench_dir();
+    let trades = trades(ROWS);
+    let equity = equity(ROWS);
+    let summary = MetricsSummary::default();
+    let mut group = c.benchmark_group("report_writers");
+    group.bench_function("summary_json", |b| {
+        b.iter(|| {
+            write_summary_json(&dir.join("summary.json"), &summary, None, None, None)
+                .expect("summary json")
+        })
+    });
+    group.bench_function("summary_html", |b| {
+        b.iter(|| write_summary_html(&dir.join("summary.html"), &summary, None).expect("html"))
+    });
+    group.throughput(Throughput::Elements(ROWS as u64));
+    group.bench_function("trades_parquet", |b| {
+        b.iter(|| write_trades_parquet(&dir.join("trades.parquet"), &trades).expect("parquet"))
+    });
+    group.bench_function("equity_parquet", |b| {
+        b.iter(|| write_equity_parquet(&dir.join("equity.parquet"), &equity).expect("parquet"))
+    });
+    group.finish();
+    let _ = fs::remove_dir_all(&dir);
+}
+
+criterion_group!(benches, csv_writers, report_writers);
+criterion_main!(benches);
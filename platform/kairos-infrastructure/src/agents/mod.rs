@@ -1,3 +1,4 @@
+use kairos_domain::errors::AgentError;
 pub use kairos_domain::services::agent::{
     ActionBatchItem, ActionBatchRequest, ActionBatchResponse, ActionRequest, ActionResponse,
     PortfolioState,
@@ -8,6 +9,7 @@ use reqwest::blocking::Client;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use reqwest::StatusCode;
 use serde::Serialize;
+use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -19,18 +21,26 @@ pub struct AgentCallInfo {
     pub error: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Serialize)]
 pub struct AgentCallResult {
     pub info: AgentCallInfo,
     pub response: Option<ActionResponse>,
+    // Why `response` is missing; `info.error` carries its message.
+    #[serde(skip)]
+    pub failure: Option<AgentError>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Serialize)]
 pub struct AgentBatchCallResult {
     pub info: AgentCallInfo,
     pub responses: Option<Vec<ActionResponse>>,
+    #[serde(skip)]
+    pub failure: Option<AgentError>,
 }
 
+// Sees the outcome of every call (single or batch) as it finishes, e.g. for live diagnostics.
+pub type AgentCallObserver = Arc<dyn Fn(&AgentCallInfo) + Send + Sync>;
+
 pub struct AgentClient {
     pub url: String,
     pub timeout_ms: u64,
@@ -40,6 +50,7 @@ pub struct AgentClient {
     pub fallback_action: ActionType,
     extra_headers: HeaderMap,
     client: Client,
+    observer: Option<AgentCallObserver>,
 }
 
 impl AgentClient {
@@ -94,21 +105,36 @@ impl AgentClient {
             fallback_action,
             extra_headers: headers,
             client,
+            observer: None,
         })
     }
 
-    pub fn act(&self, request: &ActionRequest) -> Result<ActionResponse, String> {
+    pub fn with_observer(mut self, observer: AgentCallObserver) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    fn observe(&self, info: &AgentCallInfo) {
+        if let Some(observer) = &self.observer {
+            observer(info);
+        }
+    }
+
+    pub fn act(&self, request: &ActionRequest) -> Result<ActionResponse, AgentError> {
         let result = self.act_detailed(request);
         match result.response {
             Some(response) => Ok(response),
-            None => Err(result
-                .info
-                .error
-                .unwrap_or_else(|| "agent request failed".to_string())),
+            None => Err(result.failure.unwrap_or(AgentError::RetriesExhausted)),
         }
     }
 
     pub fn act_detailed(&self, request: &ActionRequest) -> AgentCallResult {
+        let result = self.call_act(request);
+        self.observe(&result.info);
+        result
+    }
+
+    fn call_act(&self, request: &ActionRequest) -> AgentCallResult {
         let endpoint = format!("{}/v1/act", self.url.trim_end_matches('/'));
         let span = tracing::info_span!(
             "infra.agent.act",
@@ -124,8 +150,7 @@ impl AgentClient {
         let start = Instant::now();
         let mut attempts = 0u32;
         let mut last_status: Option<u16> = None;
-        let mut last_error: Option<String> = None;
-        let mut last_error_kind: Option<&'static str> = None;
+        let mut last_error: Option<AgentError> = None;
 
         while attempts <= self.retries {
             attempts += 1;
@@ -179,17 +204,19 @@ impl AgentClient {
                                             error: None,
                                         },
                                         response: Some(parsed),
+                                        failure: None,
                                     };
                                 }
                                 Err(err) => {
                                     last_error = Some(err);
-                                    last_error_kind = Some("validate");
                                     break;
                                 }
                             },
                             Err(err) => {
-                                last_error = Some(format!("failed to parse agent response: {err}"));
-                                last_error_kind = Some("decode");
+                                last_error = Some(AgentError::Decode {
+                                    what: "response",
+                                    source: Box::new(err),
+                                });
                                 break;
                             }
                         }
@@ -198,11 +225,7 @@ impl AgentClient {
                     if resp.status().is_server_error() && attempts <= self.retries {
                         continue;
                     }
-                    last_error = Some(format!(
-                        "agent http error: status {}",
-                        resp.status().as_u16()
-                    ));
-                    last_error_kind = Some("http_status");
+                    last_error = Some(AgentError::HttpStatus(resp.status().as_u16()));
                     break;
                 }
                 Err(err) => {
@@ -212,8 +235,7 @@ impl AgentClient {
                         "status" => "err"
                     )
                     .record(attempt_start.elapsed().as_millis() as f64);
-                    last_error = Some(format!("agent request failed: {err}"));
-                    last_error_kind = Some("transport");
+                    last_error = Some(AgentError::Transport(Box::new(err)));
                     if attempts <= self.retries {
                         continue;
                     }
@@ -226,7 +248,8 @@ impl AgentClient {
         let status_label = last_status
             .map(|s| s.to_string())
             .unwrap_or_else(|| "none".to_string());
-        let kind = last_error_kind.unwrap_or("unknown");
+        let error = last_error.unwrap_or(AgentError::RetriesExhausted);
+        let kind = error.kind();
         metrics::counter!(
             "kairos.infra.agent.errors_total",
             "endpoint" => "act",
@@ -247,7 +270,7 @@ impl AgentClient {
         tracing::warn!(
             attempts,
             status = ?last_status,
-            error = last_error.as_deref().unwrap_or("unknown"),
+            error = %error,
             "agent request failed"
         );
 
@@ -256,25 +279,61 @@ impl AgentClient {
                 attempts,
                 duration_ms,
                 status: last_status,
-                error: last_error
-                    .or_else(|| Some("agent request failed after retries".to_string())),
+                error: Some(error.to_string()),
             },
             response: None,
+            failure: Some(error),
+        }
+    }
+
+    // GET `/healthz`, falling back to `/health` (served by the bundled Python agents) on 404.
+    pub fn health_check(&self) -> Result<(), AgentError> {
+        let base = self.url.trim_end_matches('/');
+        let mut last_status = None;
+        for path in ["/healthz", "/health"] {
+            let endpoint = format!("{base}{path}");
+            let mut builder = self.client.get(&endpoint);
+            if !self.extra_headers.is_empty() {
+                builder = builder.headers(self.extra_headers.clone());
+            }
+            let resp = builder.send().map_err(|err| AgentError::Unreachable {
+                endpoint: endpoint.clone(),
+                source: Box::new(err),
+            })?;
+            if resp.status().is_success() {
+                return Ok(());
+            }
+            last_status = Some((endpoint, resp.status()));
+            if resp.status() != StatusCode::NOT_FOUND {
+                break;
+            }
+        }
+        match last_status {
+            Some((endpoint, status)) => Err(AgentError::Unhealthy {
+                endpoint,
+                status: status.to_string(),
+            }),
+            None => Err(AgentError::Other("agent health check failed".to_string())),
         }
     }
 
-    pub fn act_batch(&self, batch: &ActionBatchRequest) -> Result<ActionBatchResponse, String> {
+    pub fn act_batch(&self, batch: &ActionBatchRequest) -> Result<ActionBatchResponse, AgentError> {
         let result = self.act_batch_detailed(batch);
         match result.responses {
             Some(items) => Ok(ActionBatchResponse { items }),
-            None => Err(result
-                .info
-                .error
-                .unwrap_or_else(|| "agent batch request failed".to_string())),
+            None => Err(result.failure.unwrap_or(AgentError::RetriesExhausted)),
         }
     }
 
     pub fn act_batch_detailed(&self, batch: &ActionBatchRequest) -> AgentBatchCallResult {
+        let result = self.call_act_batch(batch);
+        if !batch.items.is_empty() {
+            self.observe(&result.info);
+        }
+        result
+    }
+
+    fn call_act_batch(&self, batch: &ActionBatchRequest) -> AgentBatchCallResult {
         if batch.items.is_empty() {
             return AgentBatchCallResult {
                 info: AgentCallInfo {
@@ -284,6 +343,7 @@ impl AgentClient {
                     error: None,
                 },
                 responses: Some(Vec::new()),
+                failure: None,
             };
         }
 
@@ -303,8 +363,7 @@ impl AgentClient {
         let start = Instant::now();
         let mut attempts = 0u32;
         let mut last_status: Option<u16> = None;
-        let mut last_error: Option<String> = None;
-        let mut last_error_kind: Option<&'static str> = None;
+        let mut last_error: Option<AgentError> = None;
 
         while attempts <= self.retries {
             attempts += 1;
@@ -336,18 +395,15 @@ impl AgentClient {
                         match resp.json::<ActionBatchResponse>() {
                             Ok(parsed) => {
                                 if parsed.items.len() != batch.items.len() {
-                                    last_error = Some(format!(
-                                        "agent batch size mismatch: expected {} items, got {}",
-                                        batch.items.len(),
-                                        parsed.items.len()
-                                    ));
-                                    last_error_kind = Some("batch_size_mismatch");
+                                    last_error = Some(AgentError::BatchSizeMismatch {
+                                        expected: batch.items.len(),
+                                        actual: parsed.items.len(),
+                                    });
                                     break;
                                 }
                                 for item in &parsed.items {
                                     if let Err(err) = validate_action_response(item) {
                                         last_error = Some(err);
-                                        last_error_kind = Some("validate");
                                         break;
                                     }
                                 }
@@ -374,14 +430,16 @@ impl AgentClient {
                                             error: None,
                                         },
                                         responses: Some(parsed.items),
+                                        failure: None,
                                     };
                                 }
                                 break;
                             }
                             Err(err) => {
-                                last_error =
-                                    Some(format!("failed to parse agent batch response: {err}"));
-                                last_error_kind = Some("decode");
+                                last_error = Some(AgentError::Decode {
+                                    what: "batch response",
+                                    source: Box::new(err),
+                                });
                                 break;
                             }
                         }
@@ -390,11 +448,7 @@ impl AgentClient {
                     if resp.status().is_server_error() && attempts <= self.retries {
                         continue;
                     }
-                    last_error = Some(format!(
-                        "agent http error: status {}",
-                        resp.status().as_u16()
-                    ));
-                    last_error_kind = Some("http_status");
+                    last_error = Some(AgentError::HttpStatus(resp.status().as_u16()));
                     break;
                 }
                 Err(err) => {
@@ -405,8 +459,7 @@ impl AgentClient {
                     )
                     .record(attempt_start.elapsed().as_millis() as f64);
 
-                    last_error = Some(format!("agent request failed: {err}"));
-                    last_error_kind = Some("transport");
+                    last_error = Some(AgentError::Transport(Box::new(err)));
                     if attempts <= self.retries {
                         continue;
                     }
@@ -419,7 +472,8 @@ impl AgentClient {
         let status_label = last_status
             .map(|s| s.to_string())
             .unwrap_or_else(|| "none".to_string());
-        let kind = last_error_kind.unwrap_or("unknown");
+        let error = last_error.unwrap_or(AgentError::RetriesExhausted);
+        let kind = error.kind();
         metrics::counter!(
             "kairos.infra.agent.errors_total",
             "endpoint" => "act_batch",
@@ -440,7 +494,7 @@ impl AgentClient {
         tracing::warn!(
             attempts,
             status = ?last_status,
-            error = last_error.as_deref().unwrap_or("unknown"),
+            error = %error,
             "agent batch request failed"
         );
 
@@ -449,10 +503,10 @@ impl AgentClient {
                 attempts,
                 duration_ms,
                 status: last_status,
-                error: last_error
-                    .or_else(|| Some("agent request failed after retries".to_string())),
+                error: Some(error.to_string()),
             },
             responses: None,
+            failure: Some(error),
         }
     }
 
@@ -489,43 +543,62 @@ impl AgentClient {
     }
 }
 
-fn validate_action_response(response: &ActionResponse) -> Result<(), String> {
+fn validate_action_response(response: &ActionResponse) -> Result<(), AgentError> {
     let action_type = response.action_type.to_uppercase();
     if action_type != "BUY" && action_type != "SELL" && action_type != "HOLD" {
-        return Err(format!("invalid action_type: {}", response.action_type));
+        return Err(AgentError::InvalidResponse(format!(
+            "invalid action_type: {}",
+            response.action_type
+        )));
     }
     if !response.size.is_finite() || response.size < 0.0 {
-        return Err(format!("invalid size: {}", response.size));
+        return Err(AgentError::InvalidResponse(format!(
+            "invalid size: {}",
+            response.size
+        )));
     }
     if let Some(confidence) = response.confidence {
         if !confidence.is_finite() || !(0.0..=1.0).contains(&confidence) {
-            return Err(format!("invalid confidence: {}", confidence));
+            return Err(AgentError::InvalidResponse(format!(
+                "invalid confidence: {}",
+                confidence
+            )));
         }
     }
     if let Some(reason) = response.reason.as_deref() {
         if reason.len() > 2000 {
-            return Err("invalid reason: too long".to_string());
+            return Err(AgentError::InvalidResponse(
+                "invalid reason: too long".to_string(),
+            ));
         }
     }
     Ok(())
 }
 
 impl kairos_domain::repositories::agent::AgentClient for AgentClient {
-    fn act(&self, request: &ActionRequest) -> Result<ActionResponse, String> {
+    fn act(&self, request: &ActionRequest) -> Result<ActionResponse, AgentError> {
         AgentClient::act(self, request)
     }
 
-    fn act_batch(&self, request: &ActionBatchRequest) -> Result<ActionBatchResponse, String> {
+    fn act_batch(&self, request: &ActionBatchRequest) -> Result<ActionBatchResponse, AgentError> {
         AgentClient::act_batch(self, request)
     }
+
+    fn health_check(&self) -> Result<(), AgentError> {
+        AgentClient::health_check(self)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{ActionBatchItem, ActionBatchRequest, ActionRequest, AgentClient, PortfolioState};
+    use super::{
+        ActionBatchItem, ActionBatchRequest, ActionRequest, AgentCallInfo, AgentClient,
+        PortfolioState,
+    };
     use kairos_domain::value_objects::action_type::ActionType;
     use std::io::{Read, Write};
     use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
     use std::thread;
 
     fn http_response(status: u16, reason: &str, content_type: &str, body: &str) -> String {
@@ -618,6 +691,32 @@ mod tests {
         assert_eq!(response.action_type, "HOLD");
     }
 
+    #[test]
+    fn health_check_falls_back_to_health_on_404() {
+        let Some(base_url) = try_spawn_server(vec![
+            http_response(404, "Not Found", "text/plain", "nope"),
+            http_response(200, "OK", "text/plain", "OK"),
+            http_response(503, "Service Unavailable", "text/plain", "down"),
+        ]) else {
+            eprintln!("skipping: cannot bind local test server");
+            return;
+        };
+
+        let client = AgentClient::new(
+            base_url,
+            500,
+            "v1".to_string(),
+            "v1".to_string(),
+            0,
+            ActionType::Hold,
+        )
+        .expect("agent client");
+
+        client.health_check().expect("healthy via /health");
+        let err = client.health_check().unwrap_err().to_string();
+        assert!(err.contains("/healthz returned HTTP 503"), "{err}");
+    }
+
     #[test]
     fn act_retries_on_server_error_then_succeeds() {
         let ok_body = r#"{"action_type":"HOLD","size":0.0,"confidence":null,"model_version":null,"latency_ms":null}"#;
@@ -638,11 +737,17 @@ mod tests {
             ActionType::Hold,
         )
         .expect("agent client");
+        let observed = Arc::new(Mutex::new(Vec::new()));
+        let sink = observed.clone();
+        let client = client.with_observer(Arc::new(move |info: &AgentCallInfo| {
+            sink.lock().unwrap().push((info.attempts, info.status));
+        }));
 
         let detailed = client.act_detailed(&sample_request());
         assert_eq!(detailed.info.attempts, 2);
         assert_eq!(detailed.info.status, Some(200));
         assert_eq!(detailed.response.as_ref().unwrap().action_type, "HOLD");
+        assert_eq!(*observed.lock().unwrap(), [(2, Some(200))]);
     }
 
     #[test]
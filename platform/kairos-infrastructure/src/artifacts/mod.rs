@@ -1,6 +1,9 @@
 use crate::reporting;
 use kairos_domain::entities::metrics::MetricsSummary;
-use kairos_domain::repositories::artifacts::{ArtifactReader, ArtifactWriter};
+use kairos_domain::entities::run_summary::{Reproducibility, SummaryMeta};
+use kairos_domain::repositories::artifacts::{
+    ArtifactCompression, ArtifactReader, ArtifactWriter, BundleFormat,
+};
 use kairos_domain::services::audit::AuditEvent;
 use kairos_domain::value_objects::equity_point::EquityPoint;
 use kairos_domain::value_objects::trade::Trade;
@@ -17,17 +20,7 @@ impl FilesystemArtifactWriter {
     }
 }
 
-fn parse_summary_meta(meta: &serde_json::Value) -> Option<reporting::SummaryMeta> {
-    Some(reporting::SummaryMeta {
-        run_id: meta.get("run_id")?.as_str()?.to_string(),
-        symbol: meta.get("symbol")?.as_str()?.to_string(),
-        timeframe: meta.get("timeframe")?.as_str()?.to_string(),
-        start: meta.get("start")?.as_i64()?,
-        end: meta.get("end")?.as_i64()?,
-    })
-}
-
-fn record_write_metrics(kind: &'static str, start: Instant, result: &Result<(), String>) {
+fn record_write_metrics<T>(kind: &'static str, start: Instant, result: &Result<T, String>) {
     let result_label = if result.is_ok() { "ok" } else { "err" };
     metrics::counter!(
         "kairos.infra.artifacts.write.calls_total",
@@ -74,16 +67,37 @@ impl ArtifactWriter for FilesystemArtifactWriter {
         result
     }
 
+    fn write_trades_parquet(&self, path: &Path, trades: &[Trade]) -> Result<(), String> {
+        let start = Instant::now();
+        let result = reporting::write_trades_parquet(path, trades);
+        record_write_metrics("trades_parquet", start, &result);
+        result
+    }
+
+    fn write_equity_parquet(&self, path: &Path, points: &[EquityPoint]) -> Result<(), String> {
+        let start = Instant::now();
+        let result = reporting::write_equity_parquet(path, points);
+        record_write_metrics("equity_parquet", start, &result);
+        result
+    }
+
     fn write_summary_json(
         &self,
         path: &Path,
         summary: &MetricsSummary,
         meta: Option<&serde_json::Value>,
         config_snapshot: Option<&serde_json::Value>,
+        reproducibility: Option<&Reproducibility>,
     ) -> Result<(), String> {
-        let parsed = meta.and_then(parse_summary_meta);
+        let parsed = meta.and_then(SummaryMeta::from_json);
         let start = Instant::now();
-        let result = reporting::write_summary_json(path, summary, parsed.as_ref(), config_snapshot);
+        let result = reporting::write_summary_json(
+            path,
+            summary,
+            parsed.as_ref(),
+            config_snapshot,
+            reproducibility,
+        );
         record_write_metrics("summary_json", start, &result);
         result
     }
@@ -107,13 +121,26 @@ impl ArtifactWriter for FilesystemArtifactWriter {
         summary: &MetricsSummary,
         meta: Option<&serde_json::Value>,
     ) -> Result<(), String> {
-        let parsed = meta.and_then(parse_summary_meta);
+        let parsed = meta.and_then(SummaryMeta::from_json);
         let start = Instant::now();
         let result = reporting::write_summary_html(path, summary, parsed.as_ref());
         record_write_metrics("summary_html", start, &result);
         result
     }
 
+    fn write_summary_markdown(
+        &self,
+        path: &Path,
+        summary: &MetricsSummary,
+        meta: Option<&serde_json::Value>,
+    ) -> Result<(), String> {
+        let parsed = meta.and_then(SummaryMeta::from_json);
+        let start = Instant::now();
+        let result = reporting::write_summary_markdown(path, summary, parsed.as_ref());
+        record_write_metrics("summary_md", start, &result);
+        result
+    }
+
     fn write_dashboard_html(
         &self,
         path: &Path,
@@ -122,7 +149,7 @@ impl ArtifactWriter for FilesystemArtifactWriter {
         trades: &[Trade],
         equity: &[EquityPoint],
     ) -> Result<(), String> {
-        let parsed = meta.and_then(parse_summary_meta);
+        let parsed = meta.and_then(SummaryMeta::from_json);
         let start = Instant::now();
         let result =
             reporting::write_dashboard_html(path, summary, parsed.as_ref(), trades, equity);
@@ -130,6 +157,24 @@ impl ArtifactWriter for FilesystemArtifactWriter {
         result
     }
 
+    fn write_compare_html(
+        &self,
+        path: &Path,
+        comparison: &serde_json::Value,
+    ) -> Result<(), String> {
+        let start = Instant::now();
+        let result = reporting::write_compare_html(path, comparison);
+        record_write_metrics("compare_html", start, &result);
+        result
+    }
+
+    fn write_sweep_index_html(&self, path: &Path, index: &serde_json::Value) -> Result<(), String> {
+        let start = Instant::now();
+        let result = reporting::write_sweep_index_html(path, index);
+        record_write_metrics("sweep_index_html", start, &result);
+        result
+    }
+
     fn write_audit_jsonl(&self, path: &Path, events: &[AuditEvent]) -> Result<(), String> {
         let start = Instant::now();
         let result = reporting::write_audit_jsonl(path, events);
@@ -149,6 +194,50 @@ impl ArtifactWriter for FilesystemArtifactWriter {
         record_write_metrics("config_snapshot_toml", start, &result);
         result
     }
+
+    fn append_jsonl(&self, path: &Path, records: &[serde_json::Value]) -> Result<(), String> {
+        let start = Instant::now();
+        let result = reporting::append_jsonl(path, records);
+        record_write_metrics("append_jsonl", start, &result);
+        result
+    }
+
+    fn write_checkpoint_json(&self, path: &Path, value: &serde_json::Value) -> Result<(), String> {
+        let start = Instant::now();
+        let result = reporting::write_json_atomic(path, value);
+        record_write_metrics("checkpoint_json", start, &result);
+        result
+    }
+
+    fn compress_artifact(
+        &self,
+        path: &Path,
+        compression: ArtifactCompression,
+    ) -> Result<(), String> {
+        let start = Instant::now();
+        let result = reporting::compress_artifact(path, compression);
+        record_write_metrics("compress", start, &result);
+        result
+    }
+
+    fn write_manifest(&self, run_dir: &Path) -> Result<(), String> {
+        let start = Instant::now();
+        let result = reporting::write_manifest(run_dir);
+        record_write_metrics("manifest_json", start, &result);
+        result
+    }
+
+    fn write_bundle(
+        &self,
+        run_dir: &Path,
+        out_path: &Path,
+        format: BundleFormat,
+    ) -> Result<Vec<String>, String> {
+        let start = Instant::now();
+        let result = reporting::write_bundle(run_dir, out_path, format);
+        record_write_metrics("bundle", start, &result);
+        result
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -192,7 +281,25 @@ impl ArtifactReader for FilesystemArtifactReader {
         result
     }
 
+    fn read_jsonl_from(
+        &self,
+        path: &Path,
+        offset: u64,
+    ) -> Result<(Vec<serde_json::Value>, u64), String> {
+        let start = Instant::now();
+        let result = reporting::read_jsonl_from(path, offset);
+        record_read_metrics("jsonl", start, &result);
+        result
+    }
+
+    fn read_checkpoint_json(&self, path: &Path) -> Result<Option<serde_json::Value>, String> {
+        let start = Instant::now();
+        let result = reporting::read_json_if_exists(path);
+        record_read_metrics("checkpoint_json", start, &result);
+        result
+    }
+
     fn exists(&self, path: &Path) -> bool {
-        path.exists()
+        reporting::artifact_exists(path)
     }
 }
@@ -0,0 +1,466 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use hmac::{Hmac, Mac};
+use kairos_domain::repositories::execution_venue::{
+    ExecutionVenue, VenueBalances, VenueError, VenueOrderRequest, VenueOrderState,
+};
+use kairos_domain::value_objects::side::Side;
+use reqwest::blocking::Client;
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const KUCOIN_API_KEY_ENV: &str = "KAIROS_KUCOIN_API_KEY";
+pub const KUCOIN_API_SECRET_ENV: &str = "KAIROS_KUCOIN_API_SECRET";
+pub const KUCOIN_API_PASSPHRASE_ENV: &str = "KAIROS_KUCOIN_API_PASSPHRASE";
+pub const KUCOIN_API_URL_ENV: &str = "KAIROS_KUCOIN_API_URL";
+
+pub const KUCOIN_SANDBOX_API: &str = "https://openapi-sandbox.kucoin.com";
+pub const KUCOIN_PRODUCTION_API: &str = "https://api.kucoin.com";
+
+const REQUEST_TIMEOUT_MS: u64 = 10_000;
+const SUCCESS_CODE: &str = "200000";
+
+#[derive(Clone)]
+pub struct KucoinCredentials {
+    pub api_key: String,
+    pub api_secret: String,
+    pub passphrase: String,
+}
+
+impl KucoinCredentials {
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .ok_or_else(|| format!("{name} is not set (required for live trading)"))
+        };
+        Ok(Self {
+            api_key: var(KUCOIN_API_KEY_ENV)?,
+            api_secret: var(KUCOIN_API_SECRET_ENV)?,
+            passphrase: var(KUCOIN_API_PASSPHRASE_ENV)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SymbolRules {
+    base_increment: String,
+    base_min_size: f64,
+}
+
+// Signed REST adapter for KuCoin spot (API key version 2). Orders are market orders sized in the
+// base asset, rounded down to the symbol's `baseIncrement`; fills are read by polling the order.
+pub struct KucoinVenue {
+    name: String,
+    client: Client,
+    base_url: String,
+    credentials: KucoinCredentials,
+    symbol_rules: Mutex<HashMap<String, SymbolRules>>,
+}
+
+impl KucoinVenue {
+    pub fn sandbox(credentials: KucoinCredentials) -> Result<Self, String> {
+        Self::with_base_url("kucoin_sandbox", KUCOIN_SANDBOX_API, credentials)
+    }
+
+    pub fn production(credentials: KucoinCredentials) -> Result<Self, String> {
+        Self::with_base_url("kucoin", KUCOIN_PRODUCTION_API, credentials)
+    }
+
+    pub fn with_base_url(
+        name: &str,
+        base_url: &str,
+        credentials: KucoinCredentials,
+    ) -> Result<Self, String> {
+        let base_url = base_url.trim().trim_end_matches('/');
+        if !(base_url.starts_with("http://") || base_url.starts_with("https://")) {
+            return Err(format!(
+                "invalid {KUCOIN_API_URL_ENV} {base_url:?}: expected an http(s) URL"
+            ));
+        }
+        let client = Client::builder()
+            .timeout(Duration::from_millis(REQUEST_TIMEOUT_MS))
+            .build()
+            .map_err(|err| format!("failed to build http client: {err}"))?;
+        Ok(Self {
+            name: name.to_string(),
+            client,
+            base_url: base_url.to_string(),
+            credentials,
+            symbol_rules: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<T, VenueError> {
+        self.request_optional(method.clone(), path, body)?
+            .ok_or_else(|| {
+                VenueError::Transport(format!("kucoin {method} {path} response missing data"))
+            })
+    }
+
+    // A non-success `code` is the venue refusing the request; anything short of a parsed envelope
+    // leaves the outcome unknown.
+    fn request_optional<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<Option<T>, VenueError> {
+        let body = body.map(|value| value.to_string()).unwrap_or_default();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0)
+            .to_string();
+        let secret = &self.credentials.api_secret;
+        let mut request = self
+            .client
+            .request(method.clone(), format!("{}{path}", self.base_url))
+            .header("KC-API-KEY", &self.credentials.api_key)
+            .header(
+                "KC-API-SIGN",
+                sign(secret, &format!("{timestamp}{method}{path}{body}")),
+            )
+            .header("KC-API-TIMESTAMP", &timestamp)
+            .header(
+                "KC-API-PASSPHRASE",
+                sign(secret, &self.credentials.passphrase),
+            )
+            .header("KC-API-KEY-VERSION", "2");
+        if !body.is_empty() {
+            request = request
+                .header("Content-Type", "application/json")
+                .body(body);
+        }
+        let response = request.send().map_err(|err| {
+            VenueError::Transport(format!("kucoin {method} {path} failed: {err}"))
+        })?;
+        let status = response.status();
+        let text = response.text().map_err(|err| {
+            VenueError::Transport(format!("kucoin {method} {path} failed: {err}"))
+        })?;
+        let envelope: Envelope<T> = serde_json::from_str(&text).map_err(|_| {
+            VenueError::Transport(format!(
+                "kucoin {method} {path} returned {status}: {}",
+                text.trim()
+            ))
+        })?;
+        if envelope.code != SUCCESS_CODE {
+            return Err(VenueError::Rejected(format!(
+                "kucoin {method} {path} error {}: {}",
+                envelope.code,
+                envelope.msg.unwrap_or_default()
+            )));
+        }
+        Ok(envelope.data)
+    }
+
+    fn rules(&self, symbol: &str) -> Result<SymbolRules, VenueError> {
+        if let Some(rules) = self.symbol_rules.lock().expect("rules lock").get(symbol) {
+            return Ok(rules.clone());
+        }
+        let info: SymbolInfo =
+            self.request(Method::GET, &format!("/api/v2/symbols/{symbol}"), None)?;
+        let rules = SymbolRules {
+            base_min_size: parse_amount("baseMinSize", &info.base_min_size)
+                .map_err(VenueError::Rejected)?,
+            base_increment: info.base_increment,
+        };
+        self.symbol_rules
+            .lock()
+            .expect("rules lock")
+            .insert(symbol.to_string(), rules.clone());
+        Ok(rules)
+    }
+}
+
+impl ExecutionVenue for KucoinVenue {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn balances(&self, symbol: &str) -> Result<VenueBalances, String> {
+        let (base, quote) = symbol
+            .split_once('-')
+            .ok_or_else(|| format!("kucoin symbol must look like BASE-QUOTE (got {symbol})"))?;
+        let accounts: Vec<Account> = self
+            .request(Method::GET, "/api/v1/accounts?type=trade", None)
+            .map_err(|err| err.to_string())?;
+        let mut balances = VenueBalances::default();
+        for account in accounts {
+            if account.currency == base {
+                balances.base += parse_amount("available", &account.available)?;
+            } else if account.currency == quote {
+                balances.quote += parse_amount("available", &account.available)?;
+            }
+        }
+        Ok(balances)
+    }
+
+    fn place_market_order(&self, order: &VenueOrderRequest) -> Result<String, VenueError> {
+        let rules = self.rules(&order.symbol)?;
+        let size = round_down_to_increment(order.quantity, &rules.base_increment)
+            .map_err(VenueError::Rejected)?;
+        if size.parse::<f64>().unwrap_or(0.0) < rules.base_min_size {
+            return Err(VenueError::Rejected(format!(
+                "order size {} {} is below the venue minimum {}",
+                order.quantity, order.symbol, rules.base_min_size
+            )));
+        }
+        let side = match order.side {
+            Side::Buy => "buy",
+            Side::Sell => "sell",
+        };
+        let placed: PlacedOrder = self.request(
+            Method::POST,
+            "/api/v1/orders",
+            Some(serde_json::json!({
+                "clientOid": order.client_order_id,
+                "side": side,
+                "symbol": order.symbol,
+                "type": "market",
+                "size": size,
+            })),
+        )?;
+        metrics::counter!("kairos.infra.kucoin.orders_total", "side" => side).increment(1);
+        Ok(placed.order_id)
+    }
+
+    fn order_state(&self, order_id: &str) -> Result<VenueOrderState, VenueError> {
+        let details: OrderDetails =
+            self.request(Method::GET, &format!("/api/v1/orders/{order_id}"), None)?;
+        details.into_state()
+    }
+
+    fn order_by_client_id(
+        &self,
+        client_order_id: &str,
+    ) -> Result<Option<VenueOrderState>, VenueError> {
+        let details: Option<OrderDetails> = self.request_optional(
+            Method::GET,
+            &format!("/api/v1/order/client-order/{client_order_id}"),
+            None,
+        )?;
+        details.map(OrderDetails::into_state).transpose()
+    }
+
+    fn cancel_order(&self, order_id: &str) -> Result<(), VenueError> {
+        let _: serde_json::Value =
+            self.request(Method::DELETE, &format!("/api/v1/orders/{order_id}"), None)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Envelope<T> {
+    code: String,
+    data: Option<T>,
+    msg: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Account {
+    currency: String,
+    available: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SymbolInfo {
+    base_increment: String,
+    base_min_size: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PlacedOrder {
+    order_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderDetails {
+    id: String,
+    is_active: bool,
+    deal_size: String,
+    deal_funds: String,
+    fee: String,
+}
+
+impl OrderDetails {
+    fn into_state(self) -> Result<VenueOrderState, VenueError> {
+        let amount = |field, value: &str| parse_amount(field, value).map_err(VenueError::Transport);
+        Ok(VenueOrderState {
+            filled_qty: amount("dealSize", &self.deal_size)?,
+            filled_funds: amount("dealFunds", &self.deal_funds)?,
+            fee: amount("fee", &self.fee)?,
+            order_id: self.id,
+            active: self.is_active,
+        })
+    }
+}
+
+// base64(HMAC-SHA256(secret, message)), used for both the request signature and the passphrase.
+fn sign(secret: &str, message: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("hmac accepts any key length");
+    mac.update(message.as_bytes());
+    BASE64.encode(mac.finalize().into_bytes())
+}
+
+fn parse_amount(field: &str, value: &str) -> Result<f64, String> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| format!("kucoin returned an invalid {field}: {value:?}"))
+}
+
+// Formats `quantity` with the increment's decimals, rounded down so the order never exceeds what
+// the caller asked for.
+fn round_down_to_increment(quantity: f64, increment: &str) -> Result<String, String> {
+    let step = parse_amount("baseIncrement", increment)?;
+    if step <= 0.0 {
+        return Err(format!(
+            "kucoin returned an invalid baseIncrement: {increment:?}"
+        ));
+    }
+    let decimals = increment
+        .trim()
+        .split_once('.')
+        .map(|(_, frac)| frac.len())
+        .unwrap_or(0);
+    // The epsilon keeps exact multiples (0.3 / 0.1 = 2.9999...) from losing a step.
+    let steps = (quantity / step + 1e-9).floor().max(0.0);
+    Ok(format!("{:.*}", decimals, steps * step))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{round_down_to_increment, sign, KucoinCredentials, KucoinVenue};
+    use kairos_domain::repositories::execution_venue::{
+        ExecutionVenue, VenueError, VenueOrderRequest,
+    };
+    use kairos_domain::value_objects::side::Side;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn try_spawn_server(bodies: Vec<&'static str>) -> Option<String> {
+        let listener = TcpListener::bind("127.0.0.1:0").ok()?;
+        let addr = listener.local_addr().ok()?;
+        thread::spawn(move || {
+            for body in bodies {
+                let (mut stream, _) = listener.accept().expect("accept");
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf);
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).expect("write");
+            }
+        });
+        Some(format!("http://{addr}"))
+    }
+
+    #[test]
+    fn signs_and_sizes_orders_like_kucoin_expects() {
+        assert_eq!(
+            sign("key", "The quick brown fox jumps over the lazy dog"),
+            "97yD9DBThCSxMpjmqm+xQ+9NWaFJRhdZl0edvC0aPNg="
+        );
+        assert_eq!(
+            round_down_to_increment(0.123456789, "0.0001").unwrap(),
+            "0.1234"
+        );
+        assert_eq!(round_down_to_increment(0.3, "0.1").unwrap(), "0.3");
+        assert_eq!(round_down_to_increment(12.9, "1").unwrap(), "12");
+    }
+
+    #[test]
+    fn places_market_order_and_reads_fill() {
+        let Some(url) = try_spawn_server(vec![
+            r#"{"code":"200000","data":{"baseIncrement":"0.0001","baseMinSize":"0.001"}}"#,
+            r#"{"code":"200000","data":{"orderId":"abc123"}}"#,
+            r#"{"code":"200000","data":{"id":"abc123","isActive":false,"dealSize":"0.0123","dealFunds":"12.3","fee":"0.0123"}}"#,
+            r#"{"code":"400100","msg":"Balance insufficient!"}"#,
+        ]) else {
+            return;
+        };
+        let venue = KucoinVenue::with_base_url(
+            "kucoin_sandbox",
+            &url,
+            KucoinCredentials {
+                api_key: "k".to_string(),
+                api_secret: "s".to_string(),
+                passphrase: "p".to_string(),
+            },
+        )
+        .expect("venue");
+        let mut order = VenueOrderRequest {
+            client_order_id: "run-1".to_string(),
+            symbol: "BTC-USDT".to_string(),
+            side: Side::Buy,
+            quantity: 0.01234,
+        };
+        let id = venue.place_market_order(&order).expect("placed");
+        assert_eq!(id, "abc123");
+        let state = venue.order_state(&id).expect("state");
+        assert!(!state.active);
+        assert_eq!(state.avg_price(), Some(12.3 / 0.0123));
+
+        // Rules are cached, so this goes straight to the (failing) order endpoint.
+        order.client_order_id = "run-2".to_string();
+        let err = venue.place_market_order(&order).expect_err("rejected");
+        assert!(
+            matches!(&err, VenueError::Rejected(msg) if msg.contains("400100") && msg.contains("Balance insufficient")),
+            "{err}"
+        );
+        order.quantity = 0.0005;
+        let err = venue.place_market_order(&order).expect_err("too small");
+        assert!(err.to_string().contains("below the venue minimum"), "{err}");
+    }
+
+    #[test]
+    fn looks_orders_up_by_client_id_and_flags_unknown_outcomes() {
+        let Some(url) = try_spawn_server(vec![
+            r#"{"code":"200000","data":{"id":"abc123","isActive":true,"dealSize":"0","dealFunds":"0","fee":"0"}}"#,
+            r#"{"code":"200000","data":null}"#,
+            "<html>502 Bad Gateway</html>",
+        ]) else {
+            return;
+        };
+        let venue = KucoinVenue::with_base_url(
+            "kucoin_sandbox",
+            &url,
+            KucoinCredentials {
+                api_key: "k".to_string(),
+                api_secret: "s".to_string(),
+                passphrase: "p".to_string(),
+            },
+        )
+        .expect("venue");
+        let found = venue.order_by_client_id("run-1").expect("lookup");
+        assert_eq!(
+            found.map(|state| state.order_id),
+            Some("abc123".to_string())
+        );
+        assert_eq!(venue.order_by_client_id("run-2").expect("lookup"), None);
+        let err = venue.order_state("abc123").expect_err("garbled");
+        assert!(matches!(err, VenueError::Transport(_)), "{err}");
+    }
+}
@@ -1,7 +1,11 @@
 pub mod agents;
 pub mod artifacts;
+pub mod execution;
 pub mod market_data;
 pub mod market_stream;
+pub mod notifications;
 pub mod persistence;
+pub mod profiling;
+pub mod pushgateway;
 pub mod reporting;
 pub mod sentiment;
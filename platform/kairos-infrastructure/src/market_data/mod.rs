@@ -1 +1,2 @@
 pub mod ohlcv;
+pub mod parquet_cache;
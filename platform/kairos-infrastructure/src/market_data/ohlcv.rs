@@ -1,6 +1,7 @@
 use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
-use kairos_domain::services::ohlcv::DataQualityReport;
+use kairos_domain::services::ohlcv::{DataQualityReport, DEFAULT_TURNOVER_TOLERANCE};
 use kairos_domain::value_objects::bar::Bar;
+use kairos_domain::value_objects::symbol::Symbol;
 use serde::Deserialize;
 use std::collections::BTreeMap;
 use std::fs::File;
@@ -31,8 +32,8 @@ pub fn load_csv_with_step(
     let mut bars_by_ts: BTreeMap<i64, Bar> = BTreeMap::new();
     let mut report = DataQualityReport::default();
     let mut last_seen_ts: Option<i64> = None;
-    let mut max_gap: Option<i64> = None;
     let step = expected_step_seconds.unwrap_or(1).max(1);
+    let symbol = Symbol::new("UNKNOWN");
 
     for result in reader.deserialize::<OhlcvRecord>() {
         let record = result.map_err(|err| format!("failed to parse CSV row: {}", err))?;
@@ -56,21 +57,17 @@ pub fn load_csv_with_step(
         }
         last_seen_ts = Some(timestamp);
 
-        if bars_by_ts
-            .insert(
-                timestamp,
-                Bar {
-                    symbol: "UNKNOWN".to_string(),
-                    timestamp,
-                    open: record.open,
-                    high: record.high,
-                    low: record.low,
-                    close: record.close,
-                    volume: record.volume,
-                },
-            )
-            .is_some()
-        {
+        let bar = Bar {
+            symbol: symbol.clone(),
+            timestamp,
+            open: record.open,
+            high: record.high,
+            low: record.low,
+            close: record.close,
+            volume: record.volume,
+        };
+        report.check_volume(&bar, None, DEFAULT_TURNOVER_TOLERANCE);
+        if bars_by_ts.insert(timestamp, bar).is_some() {
             report.duplicates += 1;
             if report.first_duplicate.is_none() {
                 report.first_duplicate = Some(timestamp);
@@ -91,22 +88,13 @@ pub fn load_csv_with_step(
         report.last_timestamp = Some(timestamp);
 
         if let Some(prev) = last_unique_ts {
-            let diff = timestamp - prev;
-            if diff > step {
-                report.gaps += 1;
-                report.gap_count += ((diff - 1) / step) as usize;
-                if report.first_gap.is_none() {
-                    report.first_gap = Some(timestamp);
-                }
-                max_gap = Some(max_gap.map_or(diff, |current| current.max(diff)));
-            }
+            report.check_gap(prev, timestamp, step);
         }
         last_unique_ts = Some(timestamp);
 
         bars.push(bar);
     }
 
-    report.max_gap_seconds = max_gap;
     Ok((bars, report))
 }
 
@@ -188,7 +176,7 @@ mod tests {
     fn resample_bars_aggregates_ohlcv() {
         let bars = vec![
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 0,
                 open: 10.0,
                 high: 11.0,
@@ -197,7 +185,7 @@ mod tests {
                 volume: 1.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 60,
                 open: 10.5,
                 high: 12.0,
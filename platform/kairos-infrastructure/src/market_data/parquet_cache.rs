@@ -0,0 +1,299 @@
+use crate::reporting::parquet::{write_columns, Column};
+use kairos_domain::repositories::market_data::{MarketDataRepository, OhlcvCoverage, OhlcvQuery};
+use kairos_domain::services::engine::backtest::{NoopControl, RunControl};
+use kairos_domain::services::ohlcv::DataQualityReport;
+use kairos_domain::value_objects::bar::Bar;
+use kairos_domain::value_objects::symbol::Symbol;
+use parquet::file::metadata::KeyValue;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::RowAccessor;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+const BARS_SCHEMA: &str = "
+message ohlcv {
+    REQUIRED INT64 timestamp_utc;
+    REQUIRED DOUBLE open;
+    REQUIRED DOUBLE high;
+    REQUIRED DOUBLE low;
+    REQUIRED DOUBLE close;
+    REQUIRED DOUBLE volume;
+}
+";
+
+const REPORT_KEY: &str = "kairos.data_quality";
+
+// Bumped whenever the file layout or the key derivation changes, so old entries are ignored.
+const CACHE_VERSION: u32 = 2;
+
+// Keeps the bars of each OHLCV query in `<dir>/<sha256>.parquet`. The key covers the query and
+// the store's coverage for it (row count, time range, last write), so a cheap coverage query
+// decides whether the full load can be skipped; anything the store changes in scope produces a
+// new key. Cache failures fall back to the inner repository.
+pub struct ParquetOhlcvCache<R> {
+    inner: R,
+    dir: PathBuf,
+    // Identifies the store (e.g. database and table) so caches of different sources can share
+    // a directory.
+    namespace: String,
+}
+
+impl<R: MarketDataRepository> ParquetOhlcvCache<R> {
+    pub fn new(inner: R, dir: PathBuf, namespace: String) -> Self {
+        Self {
+            inner,
+            dir,
+            namespace,
+        }
+    }
+
+    fn entry_path(&self, query: &OhlcvQuery, coverage: &OhlcvCoverage) -> PathBuf {
+        let key = format!(
+            "v{CACHE_VERSION}|{}|{}|{}|{}|{}|{:?}|{:?}|{:?}|{:?}|{:?}|{}|{:?}|{:?}|{:?}",
+            self.namespace,
+            query.exchange,
+            query.market,
+            query.symbol,
+            query.timeframe,
+            query.expected_step_seconds,
+            query.start,
+            query.end,
+            query.limit,
+            query.turnover_tolerance,
+            coverage.rows,
+            coverage.first_ts,
+            coverage.last_ts,
+            coverage.last_modified,
+        );
+        let digest = Sha256::digest(key.as_bytes());
+        let name: String = digest.iter().map(|byte| format!("{byte:02x}")).collect();
+        self.dir.join(format!("{name}.parquet"))
+    }
+}
+
+impl<R: MarketDataRepository> MarketDataRepository for ParquetOhlcvCache<R> {
+    fn load_ohlcv(&self, query: &OhlcvQuery) -> Result<(Vec<Bar>, DataQualityReport), String> {
+        self.load_ohlcv_with_control(query, &NoopControl)
+    }
+
+    fn load_ohlcv_with_control(
+        &self,
+        query: &OhlcvQuery,
+        control: &dyn RunControl,
+    ) -> Result<(Vec<Bar>, DataQualityReport), String> {
+        let coverage = self.inner.ohlcv_coverage(query)?;
+        if coverage.rows == 0 {
+            return self.inner.load_ohlcv_with_control(query, control);
+        }
+        let path = self.entry_path(query, &coverage);
+        if path.exists() {
+            match read_entry(&path, &query.symbol) {
+                Ok(loaded) => {
+                    metrics::counter!("kairos.infra.ohlcv_cache.lookups_total", "result" => "hit")
+                        .increment(1);
+                    tracing::debug!(
+                        path = %path.display(),
+                        bars = loaded.0.len(),
+                        "OHLCV cache hit"
+                    );
+                    return Ok(loaded);
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        path = %path.display(),
+                        error = %err,
+                        "ignoring unreadable OHLCV cache entry"
+                    );
+                }
+            }
+        }
+        metrics::counter!("kairos.infra.ohlcv_cache.lookups_total", "result" => "miss")
+            .increment(1);
+
+        let (bars, report) = self.inner.load_ohlcv_with_control(query, control)?;
+        if let Err(err) = write_entry(&path, &bars, &report) {
+            tracing::warn!(
+                path = %path.display(),
+                error = %err,
+                "failed to write OHLCV cache entry"
+            );
+        }
+        Ok((bars, report))
+    }
+
+    fn ohlcv_coverage(&self, query: &OhlcvQuery) -> Result<OhlcvCoverage, String> {
+        self.inner.ohlcv_coverage(query)
+    }
+}
+
+fn write_entry(path: &Path, bars: &[Bar], report: &DataQualityReport) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("failed to create {}: {err}", parent.display()))?;
+    }
+    let report_json = serde_json::to_string(report)
+        .map_err(|err| format!("failed to serialize data quality report: {err}"))?;
+    let columns = vec![
+        Column::Int64(bars.iter().map(|bar| bar.timestamp).collect()),
+        Column::Double(bars.iter().map(|bar| bar.open).collect()),
+        Column::Double(bars.iter().map(|bar| bar.high).collect()),
+        Column::Double(bars.iter().map(|bar| bar.low).collect()),
+        Column::Double(bars.iter().map(|bar| bar.close).collect()),
+        Column::Double(bars.iter().map(|bar| bar.volume).collect()),
+    ];
+    // Written aside and renamed, so a concurrent reader never sees a partial file.
+    let tmp = path.with_extension("parquet.tmp");
+    let written = write_columns(
+        &tmp,
+        "ohlcv cache",
+        BARS_SCHEMA,
+        columns,
+        vec![KeyValue::new(REPORT_KEY.to_string(), report_json)],
+    )
+    .and_then(|_| {
+        fs::rename(&tmp, path)
+            .map_err(|err| format!("failed to move {} into place: {err}", tmp.display()))
+    });
+    if written.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    written
+}
+
+fn read_entry(path: &Path, symbol: &str) -> Result<(Vec<Bar>, DataQualityReport), String> {
+    let file =
+        File::open(path).map_err(|err| format!("failed to open {}: {err}", path.display()))?;
+    let reader = SerializedFileReader::new(file)
+        .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+    let metadata = reader.metadata().file_metadata();
+    let report_json = metadata
+        .key_value_metadata()
+        .and_then(|entries| entries.iter().find(|entry| entry.key == REPORT_KEY))
+        .and_then(|entry| entry.value.as_deref())
+        .ok_or_else(|| format!("{} has no data quality report", path.display()))?;
+    let report: DataQualityReport = serde_json::from_str(report_json)
+        .map_err(|err| format!("invalid data quality report in {}: {err}", path.display()))?;
+
+    let mut bars = Vec::with_capacity(metadata.num_rows().max(0) as usize);
+    let symbol = Symbol::new(symbol);
+    let rows = reader
+        .get_row_iter(None)
+        .map_err(|err| format!("failed to iterate {}: {err}", path.display()))?;
+    for row in rows {
+        let row = row.map_err(|err| format!("failed to read row of {}: {err}", path.display()))?;
+        let column = |err: parquet::errors::ParquetError| {
+            format!("unexpected column in {}: {err}", path.display())
+        };
+        bars.push(Bar {
+            symbol: symbol.clone(),
+            timestamp: row.get_long(0).map_err(column)?,
+            open: row.get_double(1).map_err(column)?,
+            high: row.get_double(2).map_err(column)?,
+            low: row.get_double(3).map_err(column)?,
+            close: row.get_double(4).map_err(column)?,
+            volume: row.get_double(5).map_err(column)?,
+        });
+    }
+    Ok((bars, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParquetOhlcvCache;
+    use kairos_domain::repositories::market_data::{
+        MarketDataRepository, OhlcvCoverage, OhlcvQuery,
+    };
+    use kairos_domain::services::ohlcv::DataQualityReport;
+    use kairos_domain::value_objects::bar::Bar;
+    use std::cell::{Cell, RefCell};
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    struct CountingRepo {
+        bars: RefCell<Vec<Bar>>,
+        loads: Cell<usize>,
+    }
+
+    impl MarketDataRepository for CountingRepo {
+        fn load_ohlcv(&self, _query: &OhlcvQuery) -> Result<(Vec<Bar>, DataQualityReport), String> {
+            self.loads.set(self.loads.get() + 1);
+            let report = DataQualityReport {
+                duplicates: 2,
+                ..DataQualityReport::default()
+            };
+            Ok((self.bars.borrow().clone(), report))
+        }
+
+        fn ohlcv_coverage(&self, _query: &OhlcvQuery) -> Result<OhlcvCoverage, String> {
+            let bars = self.bars.borrow();
+            Ok(OhlcvCoverage {
+                rows: bars.len() as u64,
+                first_ts: bars.first().map(|bar| bar.timestamp),
+                last_ts: bars.last().map(|bar| bar.timestamp),
+                last_modified: None,
+            })
+        }
+    }
+
+    fn bar(timestamp: i64, close: f64) -> Bar {
+        Bar {
+            symbol: "BTC-USDT".into(),
+            timestamp,
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            volume: 3.0,
+        }
+    }
+
+    #[test]
+    fn reuses_the_entry_until_the_store_changes() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let dir =
+            std::env::temp_dir().join(format!("kairos_ohlcv_cache_{}_{}", std::process::id(), now));
+        let cache = ParquetOhlcvCache::new(
+            CountingRepo {
+                bars: RefCell::new(vec![bar(60, 10.0), bar(120, 11.0)]),
+                loads: Cell::new(0),
+            },
+            dir.clone(),
+            "test".to_string(),
+        );
+        let query = OhlcvQuery {
+            exchange: "kucoin".to_string(),
+            market: "spot".to_string(),
+            symbol: "BTC-USDT".to_string(),
+            timeframe: "1min".to_string(),
+            expected_step_seconds: Some(60),
+            start: None,
+            end: None,
+            limit: None,
+            turnover_tolerance: None,
+        };
+
+        let (first, _) = cache.load_ohlcv(&query).expect("miss");
+        let (second, report) = cache.load_ohlcv(&query).expect("hit");
+        assert_eq!(cache.inner.loads.get(), 1);
+        assert_eq!(first, second);
+        assert_eq!(report.duplicates, 2);
+
+        cache.inner.bars.borrow_mut().push(bar(180, 12.0));
+        let (third, _) = cache.load_ohlcv(&query).expect("stale entry");
+        assert_eq!(cache.inner.loads.get(), 2);
+        assert_eq!(third.len(), 3);
+
+        let scoped = OhlcvQuery {
+            limit: Some(2),
+            ..query
+        };
+        cache.load_ohlcv(&scoped).expect("different query");
+        assert_eq!(cache.inner.loads.get(), 3);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,4 +1,6 @@
-use kairos_domain::repositories::market_stream::{MarketEvent, MarketStream, StreamError};
+use kairos_domain::repositories::market_stream::{
+    MarketEvent, MarketStream, MultiMarketStream, StreamError,
+};
 use rand::RngCore;
 use serde::Deserialize;
 use std::time::{Duration, Instant};
@@ -9,7 +11,7 @@ const KUCOIN_BULLET_PUBLIC: &str = "https://api.kucoin.com/api/v1/bullet-public"
 
 #[derive(Debug)]
 pub struct KucoinPublicTickerStream {
-    symbol: String,
+    symbols: Vec<String>,
     socket: tungstenite::WebSocket<tungstenite::stream::MaybeTlsStream<std::net::TcpStream>>,
     ping_interval: Duration,
     last_ping: Instant,
@@ -17,18 +19,24 @@ pub struct KucoinPublicTickerStream {
 
 impl KucoinPublicTickerStream {
     pub fn connect(symbol: String) -> Result<Self, String> {
-        let (socket, ping_interval) = connect_socket(&symbol)?;
+        Self::connect_many(vec![symbol])
+    }
+
+    // One socket, one subscription: KuCoin takes a comma-separated symbol list in the ticker topic.
+    pub fn connect_many(symbols: Vec<String>) -> Result<Self, String> {
+        if symbols.is_empty() {
+            return Err("ticker stream needs at least one symbol".to_string());
+        }
+        let (socket, ping_interval) = connect_socket(&symbols.join(","))?;
         Ok(Self {
-            symbol,
+            symbols,
             socket,
             ping_interval,
             last_ping: Instant::now(),
         })
     }
-}
 
-impl MarketStream for KucoinPublicTickerStream {
-    fn next_event(&mut self) -> Result<MarketEvent, StreamError> {
+    fn next_ticker(&mut self) -> Result<(String, MarketEvent), StreamError> {
         loop {
             if self.last_ping.elapsed() >= self.ping_interval {
                 let id = format!("ping-{}", random_id());
@@ -47,9 +55,12 @@ impl MarketStream for KucoinPublicTickerStream {
             match msg {
                 Message::Text(text) => {
                     if let Ok(envelope) = serde_json::from_str::<KucoinEnvelope>(&text) {
-                        if envelope.r#type == "message"
-                            && envelope.topic == format!("/market/ticker:{}", self.symbol)
-                        {
+                        let symbol = envelope
+                            .topic
+                            .strip_prefix("/market/ticker:")
+                            .filter(|symbol| self.symbols.iter().any(|s| s == symbol))
+                            .map(str::to_string);
+                        if let Some(symbol) = symbol.filter(|_| envelope.r#type == "message") {
                             let data = envelope.data.ok_or_else(|| {
                                 StreamError::Protocol("ticker message missing data".to_string())
                             })?;
@@ -58,10 +69,13 @@ impl MarketStream for KucoinPublicTickerStream {
                                 .price
                                 .parse::<f64>()
                                 .map_err(|e| StreamError::InvalidData(format!("bad price: {e}")))?;
-                            return Ok(MarketEvent::Tick {
-                                timestamp: ts,
-                                price,
-                            });
+                            return Ok((
+                                symbol,
+                                MarketEvent::Tick {
+                                    timestamp: ts,
+                                    price,
+                                },
+                            ));
                         }
                         // Ignore other messages (welcome/ack/pong).
                         continue;
@@ -83,6 +97,18 @@ impl MarketStream for KucoinPublicTickerStream {
     }
 }
 
+impl MarketStream for KucoinPublicTickerStream {
+    fn next_event(&mut self) -> Result<MarketEvent, StreamError> {
+        self.next_ticker().map(|(_symbol, event)| event)
+    }
+}
+
+impl MultiMarketStream for KucoinPublicTickerStream {
+    fn next_symbol_event(&mut self) -> Result<(String, MarketEvent), StreamError> {
+        self.next_ticker()
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct KucoinBulletResponse {
     code: String,
@@ -119,7 +145,7 @@ struct KucoinTickerData {
 }
 
 fn connect_socket(
-    symbol: &str,
+    symbols: &str,
 ) -> Result<
     (
         tungstenite::WebSocket<tungstenite::stream::MaybeTlsStream<std::net::TcpStream>>,
@@ -159,7 +185,7 @@ fn connect_socket(
         tungstenite::connect(url).map_err(|e| format!("ws connect failed: {e}"))?;
 
     let id = format!("sub-{}", random_id());
-    let topic = format!("/market/ticker:{symbol}");
+    let topic = format!("/market/ticker:{symbols}");
     let subscribe = serde_json::json!({
         "id": id,
         "type": "subscribe",
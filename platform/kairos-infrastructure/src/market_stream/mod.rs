@@ -1,2 +1,3 @@
 #[cfg(feature = "realtime-kucoin")]
 pub mod kucoin;
+pub mod simulated;
@@ -0,0 +1,238 @@
+use kairos_domain::repositories::market_stream::{
+    MarketEvent, MarketStream, MultiMarketStream, StreamError,
+};
+use kairos_domain::services::rng::SplitMix64;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatorSpec {
+    pub start_price: f64,
+    // Standard deviation of the log return between two trades of a symbol.
+    pub volatility: f64,
+    // Wall-clock pause before each trade.
+    pub tick_interval: Duration,
+    // Simulated seconds between rounds of trades, so bars can close faster than real time.
+    pub tick_seconds: i64,
+    // Probability that a trade is replaced by a dropped connection.
+    pub disconnect_rate: f64,
+    pub seed: u64,
+    // Epoch seconds of the first trade.
+    pub start_ts: i64,
+}
+
+impl Default for SimulatorSpec {
+    fn default() -> Self {
+        Self {
+            start_price: 100.0,
+            volatility: 0.001,
+            tick_interval: Duration::from_millis(200),
+            tick_seconds: 5,
+            disconnect_rate: 0.002,
+            seed: 0,
+            start_ts: 0,
+        }
+    }
+}
+
+impl SimulatorSpec {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.start_price.is_finite() && self.start_price > 0.0) {
+            return Err("simulator start_price must be finite and > 0".to_string());
+        }
+        if !(self.volatility.is_finite() && self.volatility >= 0.0) {
+            return Err("simulator volatility must be finite and >= 0".to_string());
+        }
+        if self.tick_seconds <= 0 {
+            return Err("simulator tick_seconds must be > 0".to_string());
+        }
+        if !(0.0..1.0).contains(&self.disconnect_rate) {
+            return Err("simulator disconnect_rate must be in [0, 1)".to_string());
+        }
+        Ok(())
+    }
+}
+
+// A random-walk market that outlives its connections: reconnecting picks the walk and the clock up
+// where the dropped stream left them, like a real exchange would.
+#[derive(Debug, Clone)]
+pub struct SimulatedMarket {
+    spec: SimulatorSpec,
+    state: Arc<Mutex<MarketState>>,
+}
+
+#[derive(Debug)]
+struct MarketState {
+    rng: SplitMix64,
+    clock: i64,
+    prices: Vec<(String, f64)>,
+}
+
+impl SimulatedMarket {
+    pub fn new(spec: SimulatorSpec) -> Result<Self, String> {
+        spec.validate()?;
+        let state = MarketState {
+            rng: SplitMix64::new(spec.seed),
+            clock: spec.start_ts,
+            prices: Vec::new(),
+        };
+        Ok(Self {
+            spec,
+            state: Arc::new(Mutex::new(state)),
+        })
+    }
+
+    pub fn connect(&self, symbol: String) -> Result<SimulatedTickerStream, String> {
+        self.connect_many(vec![symbol])
+    }
+
+    // Each round emits one trade per symbol, in order, then advances the clock by `tick_seconds`.
+    pub fn connect_many(&self, symbols: Vec<String>) -> Result<SimulatedTickerStream, String> {
+        if symbols.is_empty() {
+            return Err("ticker stream needs at least one symbol".to_string());
+        }
+        Ok(SimulatedTickerStream {
+            market: self.clone(),
+            symbols,
+            next: 0,
+            dropped: false,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct SimulatedTickerStream {
+    market: SimulatedMarket,
+    symbols: Vec<String>,
+    next: usize,
+    dropped: bool,
+}
+
+impl SimulatedTickerStream {
+    fn next_trade(&mut self) -> Result<(String, MarketEvent), StreamError> {
+        if self.dropped {
+            return Err(StreamError::Disconnected(
+                "simulated connection is closed".to_string(),
+            ));
+        }
+        let spec = &self.market.spec;
+        if !spec.tick_interval.is_zero() {
+            std::thread::sleep(spec.tick_interval);
+        }
+        let mut state = self
+            .market
+            .state
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        let state = &mut *state;
+        if state.rng.next_f64() < spec.disconnect_rate {
+            self.dropped = true;
+            return Err(StreamError::Disconnected(
+                "simulated disconnect".to_string(),
+            ));
+        }
+
+        let symbol = self.symbols[self.next].clone();
+        let idx = match state.prices.iter().position(|(s, _)| *s == symbol) {
+            Some(idx) => idx,
+            None => {
+                state.prices.push((symbol.clone(), spec.start_price));
+                state.prices.len() - 1
+            }
+        };
+        let price = state.prices[idx].1 * (spec.volatility * state.rng.next_normal()).exp();
+        state.prices[idx].1 = price;
+        let quantity = 0.01 + state.rng.next_f64();
+        let timestamp = state.clock;
+
+        self.next += 1;
+        if self.next == self.symbols.len() {
+            self.next = 0;
+            state.clock += spec.tick_seconds;
+        }
+        Ok((
+            symbol,
+            MarketEvent::Trade {
+                timestamp,
+                price,
+                quantity,
+            },
+        ))
+    }
+}
+
+impl MarketStream for SimulatedTickerStream {
+    fn next_event(&mut self) -> Result<MarketEvent, StreamError> {
+        self.next_trade().map(|(_symbol, event)| event)
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.market.spec.seed)
+    }
+}
+
+impl MultiMarketStream for SimulatedTickerStream {
+    fn next_symbol_event(&mut self) -> Result<(String, MarketEvent), StreamError> {
+        self.next_trade()
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.market.spec.seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SimulatedMarket, SimulatorSpec};
+    use kairos_domain::repositories::market_stream::{MarketEvent, MultiMarketStream, StreamError};
+    use std::time::Duration;
+
+    #[test]
+    fn walk_and_clock_survive_injected_disconnects() {
+        let market = SimulatedMarket::new(SimulatorSpec {
+            tick_interval: Duration::ZERO,
+            tick_seconds: 10,
+            disconnect_rate: 0.05,
+            seed: 7,
+            start_ts: 1_000,
+            ..SimulatorSpec::default()
+        })
+        .expect("market");
+        let symbols = vec!["BTC-USDT".to_string(), "ETH-USDT".to_string()];
+        let mut stream = market.connect_many(symbols.clone()).expect("connect");
+        assert_eq!(stream.seed(), Some(7));
+
+        let (mut trades, mut disconnects) = (Vec::new(), 0);
+        while trades.len() < 400 {
+            match stream.next_symbol_event() {
+                Ok((
+                    symbol,
+                    MarketEvent::Trade {
+                        timestamp, price, ..
+                    },
+                )) => {
+                    assert!(price.is_finite() && price > 0.0);
+                    trades.push((symbol, timestamp));
+                }
+                Ok(other) => panic!("unexpected event {other:?}"),
+                Err(err) => {
+                    assert!(matches!(err, StreamError::Disconnected(_)));
+                    // A dropped stream stays down until it is replaced.
+                    assert!(stream.next_symbol_event().is_err());
+                    disconnects += 1;
+                    stream = market.connect_many(symbols.clone()).expect("reconnect");
+                }
+            }
+        }
+        assert!(disconnects > 0);
+        assert!(trades.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        assert_eq!(trades[0], ("BTC-USDT".to_string(), 1_000));
+        assert!(trades.iter().any(|(symbol, _)| symbol == "ETH-USDT"));
+
+        let bad = SimulatorSpec {
+            disconnect_rate: 1.0,
+            ..SimulatorSpec::default()
+        };
+        assert!(SimulatedMarket::new(bad).is_err());
+    }
+}
@@ -0,0 +1,313 @@
+use kairos_domain::repositories::notifier::Notifier;
+use reqwest::blocking::Client;
+use reqwest::StatusCode;
+use std::sync::{mpsc, Arc, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const TELEGRAM_BOT_TOKEN_ENV: &str = "KAIROS_TELEGRAM_BOT_TOKEN";
+pub const TELEGRAM_CHAT_ID_ENV: &str = "KAIROS_TELEGRAM_CHAT_ID";
+
+const DEFAULT_API_BASE: &str = "https://api.telegram.org";
+const SEND_TIMEOUT_MS: u64 = 10_000;
+const MAX_RETRY_AFTER_SECS: u64 = 30;
+const QUEUE_CAPACITY: usize = 256;
+const FLUSH_TIMEOUT_MS: u64 = 5_000;
+
+// Messages are queued and delivered by a background thread so a slow Telegram API never stalls the
+// trading loop. The queue is bounded: when it is full, `notify` drops the message. Dropping the
+// notifier flushes what it can within `FLUSH_TIMEOUT_MS` and discards the rest.
+pub struct TelegramNotifier {
+    sender: Option<mpsc::SyncSender<String>>,
+    worker: Option<thread::JoinHandle<()>>,
+    flush_deadline: Arc<OnceLock<Instant>>,
+}
+
+impl TelegramNotifier {
+    pub fn new(bot_token: String, chat_id: String) -> Result<Self, String> {
+        Self::with_api_base(DEFAULT_API_BASE, bot_token, chat_id)
+    }
+
+    pub fn with_api_base(
+        api_base: &str,
+        bot_token: String,
+        chat_id: String,
+    ) -> Result<Self, String> {
+        if bot_token.trim().is_empty() {
+            return Err("telegram bot token must not be empty".to_string());
+        }
+        if chat_id.trim().is_empty() {
+            return Err("telegram chat id must not be empty".to_string());
+        }
+        let client = Client::builder()
+            .timeout(Duration::from_millis(SEND_TIMEOUT_MS))
+            .build()
+            .map_err(|err| format!("failed to build http client: {err}"))?;
+        let endpoint = format!(
+            "{}/bot{}/sendMessage",
+            api_base.trim_end_matches('/'),
+            bot_token.trim()
+        );
+        let chat_id = chat_id.trim().to_string();
+
+        let (sender, receiver) = mpsc::sync_channel::<String>(QUEUE_CAPACITY);
+        let flush_deadline = Arc::new(OnceLock::<Instant>::new());
+        let deadline = Arc::clone(&flush_deadline);
+        let worker = thread::Builder::new()
+            .name("kairos-telegram".to_string())
+            .spawn(move || {
+                let mut discarded = 0u64;
+                for message in receiver {
+                    if deadline.get().is_some_and(|d| Instant::now() >= *d) {
+                        discarded += 1;
+                        continue;
+                    }
+                    let start = Instant::now();
+                    let result = send_message(&client, &endpoint, &chat_id, &message);
+                    record_send_metrics(start, &result);
+                    if let Err(err) = result {
+                        tracing::warn!(error = %err, "telegram notification failed");
+                    }
+                }
+                if discarded > 0 {
+                    metrics::counter!("kairos.infra.telegram.discarded_total").increment(discarded);
+                    tracing::warn!(discarded, "telegram notifications discarded at shutdown");
+                }
+            })
+            .map_err(|err| format!("failed to spawn telegram worker: {err}"))?;
+
+        Ok(Self {
+            sender: Some(sender),
+            worker: Some(worker),
+            flush_deadline,
+        })
+    }
+
+    // Returns `Ok(None)` when no bot token is configured, so callers can treat Telegram as opt-in.
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let token = std::env::var(TELEGRAM_BOT_TOKEN_ENV)
+            .ok()
+            .filter(|v| !v.trim().is_empty());
+        let chat_id = std::env::var(TELEGRAM_CHAT_ID_ENV)
+            .ok()
+            .filter(|v| !v.trim().is_empty());
+        match (token, chat_id) {
+            (None, None) => Ok(None),
+            (Some(token), Some(chat_id)) => Self::new(token, chat_id).map(Some),
+            (Some(_), None) => Err(format!(
+                "{TELEGRAM_BOT_TOKEN_ENV} is set but {TELEGRAM_CHAT_ID_ENV} is missing"
+            )),
+            (None, Some(_)) => Err(format!(
+                "{TELEGRAM_CHAT_ID_ENV} is set but {TELEGRAM_BOT_TOKEN_ENV} is missing"
+            )),
+        }
+    }
+}
+
+impl Notifier for TelegramNotifier {
+    fn notify(&self, message: &str) -> Result<(), String> {
+        let sender = self
+            .sender
+            .as_ref()
+            .ok_or_else(|| "telegram notifier is closed".to_string())?;
+        sender
+            .try_send(message.to_string())
+            .map_err(|err| match err {
+                mpsc::TrySendError::Full(_) => {
+                    "telegram queue is full; message dropped".to_string()
+                }
+                mpsc::TrySendError::Disconnected(_) => "telegram worker stopped".to_string(),
+            })
+    }
+}
+
+impl Drop for TelegramNotifier {
+    // Waits for the worker only until the flush deadline; a worker still busy after it (a slow
+    // request or a 429 back-off) is left to finish on its own and discards the queued rest.
+    fn drop(&mut self) {
+        let deadline = Instant::now() + Duration::from_millis(FLUSH_TIMEOUT_MS);
+        let _ = self.flush_deadline.set(deadline);
+        self.sender.take();
+        if let Some(worker) = self.worker.take() {
+            while !worker.is_finished() && Instant::now() < deadline {
+                thread::sleep(Duration::from_millis(10));
+            }
+            if worker.is_finished() {
+                let _ = worker.join();
+            }
+        }
+    }
+}
+
+fn send_message(client: &Client, endpoint: &str, chat_id: &str, text: &str) -> Result<(), String> {
+    let body = serde_json::json!({
+        "chat_id": chat_id,
+        "text": text,
+        "disable_web_page_preview": true,
+    });
+
+    let mut retried = false;
+    loop {
+        // Errors from reqwest include the URL, which embeds the bot token; keep it out of logs.
+        let response = client
+            .post(endpoint)
+            .json(&body)
+            .send()
+            .map_err(|err| format!("telegram request failed: {}", err.without_url()))?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let payload: serde_json::Value = response.json().unwrap_or(serde_json::Value::Null);
+        if status == StatusCode::TOO_MANY_REQUESTS && !retried {
+            let retry_after = payload
+                .get("parameters")
+                .and_then(|p| p.get("retry_after"))
+                .and_then(|v| v.as_u64())
+                .unwrap_or(1)
+                .min(MAX_RETRY_AFTER_SECS);
+            thread::sleep(Duration::from_secs(retry_after));
+            retried = true;
+            continue;
+        }
+
+        let description = payload
+            .get("description")
+            .and_then(|v| v.as_str())
+            .unwrap_or("no description");
+        return Err(format!(
+            "telegram returned HTTP {}: {description}",
+            status.as_u16()
+        ));
+    }
+}
+
+fn record_send_metrics(start: Instant, result: &Result<(), String>) {
+    let result_label = if result.is_ok() { "ok" } else { "err" };
+    metrics::counter!("kairos.infra.telegram.sent_total", "result" => result_label).increment(1);
+    metrics::histogram!("kairos.infra.telegram.send_ms", "result" => result_label)
+        .record(start.elapsed().as_millis() as f64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TelegramNotifier, FLUSH_TIMEOUT_MS, QUEUE_CAPACITY};
+    use kairos_domain::repositories::notifier::Notifier;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    // Reads headers plus a Content-Length body, which reqwest may send in separate writes.
+    fn read_request(stream: &mut impl Read) -> String {
+        let mut raw = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = stream.read(&mut buf).unwrap_or(0);
+            if n == 0 {
+                break;
+            }
+            raw.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&raw).to_string();
+            if let Some(header_end) = text.find("\r\n\r\n") {
+                let content_length = text[..header_end]
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<usize>().ok())?
+                    })
+                    .unwrap_or(0);
+                if raw.len() >= header_end + 4 + content_length {
+                    break;
+                }
+            }
+        }
+        String::from_utf8_lossy(&raw).to_string()
+    }
+
+    fn try_spawn_server(expected: usize) -> Option<(String, mpsc::Receiver<String>)> {
+        let listener = TcpListener::bind("127.0.0.1:0").ok()?;
+        let addr = listener.local_addr().ok()?;
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for _ in 0..expected {
+                let (mut stream, _) = listener.accept().expect("accept");
+                let _ = tx.send(read_request(&mut stream));
+                let body = r#"{"ok":true,"result":{}}"#;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream
+                    .write_all(response.as_bytes())
+                    .expect("write response");
+            }
+        });
+
+        Some((format!("http://{}", addr), rx))
+    }
+
+    #[test]
+    fn notify_posts_send_message_with_chat_id() {
+        let Some((base_url, requests)) = try_spawn_server(1) else {
+            eprintln!("skipping: cannot bind local test server");
+            return;
+        };
+
+        let notifier =
+            TelegramNotifier::with_api_base(&base_url, "123:abc".to_string(), "42".to_string())
+                .expect("notifier");
+        notifier.notify("fill BUY 1 @ 100").expect("notify");
+        drop(notifier);
+
+        let request = requests
+            .recv_timeout(Duration::from_secs(5))
+            .expect("request");
+        assert!(request.starts_with("POST /bot123:abc/sendMessage "));
+        assert!(request.contains(r#""chat_id":"42""#));
+        assert!(request.contains("fill BUY 1 @ 100"));
+    }
+
+    #[test]
+    fn bounded_queue_drops_overflow_and_drop_does_not_wait_for_it() {
+        // Accepts the first request and never answers it, so the worker stays busy.
+        let Ok(listener) = TcpListener::bind("127.0.0.1:0") else {
+            eprintln!("skipping: cannot bind local test server");
+            return;
+        };
+        let addr = listener.local_addr().expect("addr");
+        thread::spawn(move || {
+            let _held = listener.accept();
+            thread::sleep(Duration::from_secs(30));
+        });
+
+        let notifier = TelegramNotifier::with_api_base(
+            &format!("http://{addr}"),
+            "123:abc".to_string(),
+            "42".to_string(),
+        )
+        .expect("notifier");
+        let errors = (0..QUEUE_CAPACITY + 10)
+            .filter(|i| notifier.notify(&format!("fill {i}")).is_err())
+            .count();
+        assert!(errors > 0 && errors <= 10, "{errors}");
+
+        let start = Instant::now();
+        drop(notifier);
+        assert!(
+            start.elapsed() < Duration::from_millis(FLUSH_TIMEOUT_MS + 2_000),
+            "{:?}",
+            start.elapsed()
+        );
+    }
+
+    #[test]
+    fn rejects_empty_credentials() {
+        assert!(TelegramNotifier::new(" ".to_string(), "42".to_string()).is_err());
+        assert!(TelegramNotifier::new("123:abc".to_string(), String::new()).is_err());
+    }
+}
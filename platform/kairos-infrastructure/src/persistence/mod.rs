@@ -1 +1,3 @@
 pub mod postgres_ohlcv;
+pub mod postgres_sentiment;
+pub mod sqlite_run_registry;
@@ -1,6 +1,11 @@
 use chrono::{DateTime, Utc};
-use kairos_domain::services::ohlcv::DataQualityReport;
+use kairos_domain::repositories::market_data::{OhlcvCoverage, OhlcvQuery, OHLCV_LOAD_CANCELLED};
+use kairos_domain::services::engine::backtest::{NoopControl, RunControl};
+use kairos_domain::services::ohlcv::{DataQualityReport, DEFAULT_TURNOVER_TOLERANCE};
 use kairos_domain::value_objects::bar::Bar;
+use kairos_domain::value_objects::symbol::Symbol;
+use postgres::fallible_iterator::FallibleIterator;
+use postgres::types::ToSql;
 use postgres::NoTls;
 use r2d2::Pool;
 use r2d2_postgres::PostgresConnectionManager;
@@ -34,39 +39,141 @@ impl PostgresMarketDataRepository {
 impl kairos_domain::repositories::market_data::MarketDataRepository
     for PostgresMarketDataRepository
 {
-    fn load_ohlcv(
+    fn load_ohlcv(&self, query: &OhlcvQuery) -> Result<(Vec<Bar>, DataQualityReport), String> {
+        load_postgres(&self.pool, &self.ohlcv_table, query)
+    }
+
+    fn load_ohlcv_with_control(
         &self,
-        query: &kairos_domain::repositories::market_data::OhlcvQuery,
+        query: &OhlcvQuery,
+        control: &dyn RunControl,
     ) -> Result<(Vec<Bar>, DataQualityReport), String> {
-        load_postgres(
-            &self.pool,
-            &self.ohlcv_table,
-            &query.exchange,
-            &query.market,
-            &query.symbol,
-            &query.timeframe,
-            query.expected_step_seconds,
-        )
+        load_postgres_with_control(&self.pool, &self.ohlcv_table, query, control)
+    }
+
+    fn ohlcv_coverage(&self, query: &OhlcvQuery) -> Result<OhlcvCoverage, String> {
+        validate_table_name(&self.ohlcv_table)?;
+        let mut client = self
+            .pool
+            .get()
+            .map_err(|err| format!("failed to checkout postgres connection: {err}"))?;
+        let scope = OhlcvScope::new(query)?;
+        let sql = format!(
+            "SELECT COUNT(*), MIN(timestamp_utc), MAX(timestamp_utc), MAX(ingested_at) FROM \
+             (SELECT timestamp_utc, ingested_at FROM {} {}) AS scoped",
+            self.ohlcv_table,
+            scope.clauses()
+        );
+        let row = client
+            .query_one(&sql, &scope.params())
+            .map_err(|err| format!("failed to query OHLCV coverage: {err}"))?;
+        let rows: i64 = row.get(0);
+        let first: Option<DateTime<Utc>> = row.get(1);
+        let last: Option<DateTime<Utc>> = row.get(2);
+        let modified: Option<DateTime<Utc>> = row.get(3);
+        Ok(OhlcvCoverage {
+            rows: rows.max(0) as u64,
+            first_ts: first.map(|ts| ts.timestamp()),
+            last_ts: last.map(|ts| ts.timestamp()),
+            last_modified: modified.map(|ts| ts.timestamp_micros()),
+        })
+    }
+}
+
+// `WHERE ... ORDER BY ... [LIMIT]` for a query, with its bind parameters. Bounds are pushed down
+// so scoped runs only transfer the rows they use.
+struct OhlcvScope<'a> {
+    query: &'a OhlcvQuery,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    limit: Option<i64>,
+}
+
+impl<'a> OhlcvScope<'a> {
+    fn new(query: &'a OhlcvQuery) -> Result<Self, String> {
+        let timestamp = |ts: i64| {
+            DateTime::<Utc>::from_timestamp(ts, 0)
+                .ok_or_else(|| format!("OHLCV query bound out of range: {ts}"))
+        };
+        Ok(Self {
+            query,
+            start: query.start.map(timestamp).transpose()?,
+            end: query.end.map(timestamp).transpose()?,
+            limit: query
+                .limit
+                .map(|limit| i64::try_from(limit).unwrap_or(i64::MAX)),
+        })
+    }
+
+    fn clauses(&self) -> String {
+        let mut sql = "WHERE exchange=$1 AND market=$2 AND symbol=$3 AND timeframe=$4".to_string();
+        let mut next = 5;
+        if self.start.is_some() {
+            sql.push_str(&format!(" AND timestamp_utc >= ${next}"));
+            next += 1;
+        }
+        if self.end.is_some() {
+            sql.push_str(&format!(" AND timestamp_utc <= ${next}"));
+            next += 1;
+        }
+        sql.push_str(" ORDER BY timestamp_utc ASC");
+        if self.limit.is_some() {
+            sql.push_str(&format!(" LIMIT ${next}"));
+        }
+        sql
+    }
+
+    fn params(&self) -> Vec<&(dyn ToSql + Sync)> {
+        let mut params: Vec<&(dyn ToSql + Sync)> = vec![
+            &self.query.exchange,
+            &self.query.market,
+            &self.query.symbol,
+            &self.query.timeframe,
+        ];
+        if let Some(start) = &self.start {
+            params.push(start);
+        }
+        if let Some(end) = &self.end {
+            params.push(end);
+        }
+        if let Some(limit) = &self.limit {
+            params.push(limit);
+        }
+        params
     }
 }
 
+// Rows between cancellation checks while streaming a result set.
+const CANCEL_CHECK_ROWS: usize = 4096;
+
 pub fn load_postgres(
     pool: &Pool<PostgresConnectionManager<NoTls>>,
     table: &str,
-    exchange: &str,
-    market: &str,
-    symbol: &str,
-    timeframe: &str,
-    expected_step_seconds: Option<i64>,
+    query: &OhlcvQuery,
+) -> Result<(Vec<Bar>, DataQualityReport), String> {
+    load_postgres_with_control(pool, table, query, &NoopControl)
+}
+
+// Streams the result set instead of buffering it, so a cancelled run stops mid-transfer; the
+// connection discards the rest of the response before going back to the pool.
+pub fn load_postgres_with_control(
+    pool: &Pool<PostgresConnectionManager<NoTls>>,
+    table: &str,
+    query: &OhlcvQuery,
+    control: &dyn RunControl,
 ) -> Result<(Vec<Bar>, DataQualityReport), String> {
     let overall_start = Instant::now();
+    let symbol = query.symbol.as_str();
     let span = tracing::info_span!(
         "infra.postgres.load_ohlcv",
         table = %table,
-        exchange = %exchange,
-        market = %market,
+        exchange = %query.exchange,
+        market = %query.market,
         symbol = %symbol,
-        timeframe = %timeframe
+        timeframe = %query.timeframe,
+        start = ?query.start,
+        end = ?query.end,
+        limit = ?query.limit
     );
     let _enter = span.enter();
 
@@ -82,6 +189,8 @@ pub fn load_postgres(
         return Err(err);
     }
 
+    let scope = OhlcvScope::new(query)?;
+
     let get_start = Instant::now();
     let mut client = match pool.get() {
         Ok(client) => client,
@@ -102,34 +211,47 @@ pub fn load_postgres(
     metrics::histogram!("kairos.infra.postgres.pool.get_ms")
         .record(get_start.elapsed().as_secs_f64() * 1000.0);
 
-    let query = format!(
-        "SELECT timestamp_utc, open, high, low, close, volume FROM {} \
-         WHERE exchange=$1 AND market=$2 AND symbol=$3 AND timeframe=$4 \
-         ORDER BY timestamp_utc ASC",
-        table
+    let sql = format!(
+        "SELECT timestamp_utc, open, high, low, close, volume, turnover FROM {} {}",
+        table,
+        scope.clauses()
     );
     let query_start = Instant::now();
-    let rows = match client.query(&query, &[&exchange, &market, &symbol, &timeframe]) {
-        Ok(rows) => rows,
-        Err(err) => {
-            metrics::counter!("kairos.infra.postgres.load_ohlcv.calls_total", "result" => "err")
-                .increment(1);
-            metrics::counter!("kairos.infra.postgres.load_ohlcv.errors_total", "stage" => "query")
-                .increment(1);
-            tracing::error!(error = %err, "failed to query OHLCV");
-            return Err(format!("failed to query OHLCV: {err}"));
-        }
+    let query_error = |err: postgres::Error| {
+        metrics::counter!("kairos.infra.postgres.load_ohlcv.calls_total", "result" => "err")
+            .increment(1);
+        metrics::counter!("kairos.infra.postgres.load_ohlcv.errors_total", "stage" => "query")
+            .increment(1);
+        tracing::error!(error = %err, "failed to query OHLCV");
+        format!("failed to query OHLCV: {err}")
     };
+    let mut rows = client
+        .query_raw(&sql, scope.params())
+        .map_err(query_error)?;
     metrics::histogram!("kairos.infra.postgres.query_ms")
         .record(query_start.elapsed().as_secs_f64() * 1000.0);
 
-    let rows_len = rows.len();
-
-    let mut bars_raw = Vec::with_capacity(rows.len());
+    let mut rows_len = 0usize;
+    let mut bars_raw = Vec::with_capacity(rows.size_hint().0);
     let mut report = DataQualityReport::default();
     let mut last_seen_ts: Option<i64> = None;
-
-    for row in rows {
+    let turnover_tolerance = query
+        .turnover_tolerance
+        .unwrap_or(DEFAULT_TURNOVER_TOLERANCE);
+    // Interned once; every bar shares it instead of taking the interner lock per row.
+    let bar_symbol = Symbol::new(symbol);
+
+    while let Some(row) = rows.next().map_err(query_error)? {
+        rows_len += 1;
+        if rows_len.is_multiple_of(CANCEL_CHECK_ROWS) && control.should_cancel() {
+            metrics::counter!(
+                "kairos.infra.postgres.load_ohlcv.calls_total",
+                "result" => "cancelled"
+            )
+            .increment(1);
+            tracing::info!(rows = rows_len, "OHLCV load cancelled");
+            return Err(OHLCV_LOAD_CANCELLED.to_string());
+        }
         let timestamp: DateTime<Utc> = row.get(0);
         let ts = timestamp.timestamp();
         let close: f64 = row.get(4);
@@ -151,15 +273,17 @@ pub fn load_postgres(
         }
 
         last_seen_ts = Some(ts);
-        bars_raw.push(Bar {
-            symbol: symbol.to_string(),
+        let bar = Bar {
+            symbol: bar_symbol.clone(),
             timestamp: ts,
             open: row.get(1),
             high: row.get(2),
             low: row.get(3),
             close,
             volume: row.get(5),
-        });
+        };
+        report.check_volume(&bar, row.get(6), turnover_tolerance);
+        bars_raw.push(bar);
     }
 
     if bars_raw.is_empty() {
@@ -181,6 +305,8 @@ pub fn load_postgres(
             rows = rows_len,
             bars = 0,
             invalid_close = report.invalid_close,
+            zero_volume_moves = report.zero_volume_moves,
+            turnover_mismatches = report.turnover_mismatches,
             duplicates = report.duplicates,
             gaps = report.gaps,
             out_of_order = report.out_of_order,
@@ -189,7 +315,7 @@ pub fn load_postgres(
         return Ok((Vec::new(), report));
     }
 
-    let bars = canonicalize_bars(bars_raw, expected_step_seconds, &mut report);
+    let bars = canonicalize_bars(bars_raw, query.expected_step_seconds, &mut report);
 
     metrics::counter!("kairos.infra.postgres.load_ohlcv.calls_total", "result" => "ok")
         .increment(1);
@@ -231,6 +357,7 @@ fn canonicalize_bars(
     report.first_gap = None;
     report.first_duplicate = None;
     report.max_gap_seconds = None;
+    report.gap_ranges.clear();
 
     bars_raw.sort_by_key(|bar| bar.timestamp);
 
@@ -253,29 +380,14 @@ fn canonicalize_bars(
     report.last_timestamp = bars.last().map(|b| b.timestamp);
 
     let step = expected_step_seconds.unwrap_or(1).max(1);
-    let mut max_gap: Option<i64> = None;
-    let mut last_unique_ts: Option<i64> = None;
-    for bar in &bars {
-        let ts = bar.timestamp;
-        if let Some(prev) = last_unique_ts {
-            let diff = ts - prev;
-            if diff > step {
-                report.gaps += 1;
-                report.gap_count += ((diff - 1) / step) as usize;
-                if report.first_gap.is_none() {
-                    report.first_gap = Some(ts);
-                }
-                max_gap = Some(max_gap.map_or(diff, |current| current.max(diff)));
-            }
-        }
-        last_unique_ts = Some(ts);
+    for pair in bars.windows(2) {
+        report.check_gap(pair[0].timestamp, pair[1].timestamp, step);
     }
-    report.max_gap_seconds = max_gap;
 
     bars
 }
 
-fn validate_table_name(table: &str) -> Result<(), String> {
+pub(crate) fn validate_table_name(table: &str) -> Result<(), String> {
     if table.is_empty() {
         return Err("table name is empty".to_string());
     }
@@ -304,7 +416,8 @@ fn validate_table_name(table: &str) -> Result<(), String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{canonicalize_bars, load_postgres, validate_table_name};
+    use super::{canonicalize_bars, load_postgres, validate_table_name, OhlcvScope};
+    use kairos_domain::repositories::market_data::OhlcvQuery;
     use kairos_domain::services::ohlcv::DataQualityReport;
     use kairos_domain::value_objects::bar::Bar;
     use postgres::NoTls;
@@ -322,11 +435,34 @@ mod tests {
     #[test]
     fn load_postgres_rejects_invalid_table_name_before_connect() {
         let pool = build_pool("postgres://invalid");
-        let err = load_postgres(&pool, "ohlcv;drop", "ex", "spot", "BTCUSD", "1m", None)
+        let err = load_postgres(&pool, "ohlcv;drop", &query(None, None, None))
             .expect_err("invalid table name");
         assert!(err.contains("invalid table name"));
     }
 
+    #[test]
+    fn ohlcv_scope_binds_only_the_bounds_that_are_set() {
+        let unbounded = query(None, None, None);
+        let scope = OhlcvScope::new(&unbounded).expect("scope");
+        assert!(!scope.clauses().contains("timestamp_utc >="));
+        assert!(!scope.clauses().contains("LIMIT"));
+        assert_eq!(scope.params().len(), 4);
+
+        let bounded = query(None, Some(1_700_000_000), Some(500));
+        let scope = OhlcvScope::new(&bounded).expect("scope");
+        assert!(scope
+            .clauses()
+            .ends_with("AND timestamp_utc <= $5 ORDER BY timestamp_utc ASC LIMIT $6"));
+        assert_eq!(scope.params().len(), 6);
+
+        let ranged = query(Some(0), Some(60), None);
+        let scope = OhlcvScope::new(&ranged).expect("scope");
+        assert!(scope
+            .clauses()
+            .contains("AND timestamp_utc >= $5 AND timestamp_utc <= $6"));
+        assert!(OhlcvScope::new(&query(Some(i64::MAX), None, None)).is_err());
+    }
+
     #[test]
     fn load_postgres_errors_on_invalid_db_url() {
         let err = super::PostgresMarketDataRepository::new(
@@ -343,7 +479,7 @@ mod tests {
         let mut report = DataQualityReport::default();
         let raw = vec![
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 0,
                 open: 1.0,
                 high: 1.0,
@@ -352,7 +488,7 @@ mod tests {
                 volume: 1.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 0,
                 open: 2.0,
                 high: 2.0,
@@ -361,7 +497,7 @@ mod tests {
                 volume: 2.0,
             },
             Bar {
-                symbol: "BTCUSD".to_string(),
+                symbol: "BTCUSD".into(),
                 timestamp: 300,
                 open: 3.0,
                 high: 3.0,
@@ -387,4 +523,18 @@ mod tests {
         let manager = PostgresConnectionManager::new(config, NoTls);
         Pool::builder().max_size(1).build_unchecked(manager)
     }
+
+    fn query(start: Option<i64>, end: Option<i64>, limit: Option<u64>) -> OhlcvQuery {
+        OhlcvQuery {
+            exchange: "ex".to_string(),
+            market: "spot".to_string(),
+            symbol: "BTCUSD".to_string(),
+            timeframe: "1m".to_string(),
+            expected_step_seconds: None,
+            start,
+            end,
+            limit,
+            turnover_tolerance: None,
+        }
+    }
 }
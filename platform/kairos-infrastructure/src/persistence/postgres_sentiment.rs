@@ -0,0 +1,244 @@
+use crate::persistence::postgres_ohlcv::validate_table_name;
+use crate::sentiment::{load_instrumented, resolve_rows, FilesystemSentimentRepository};
+use chrono::{DateTime, Utc};
+use kairos_domain::repositories::sentiment::{
+    SentimentQuery, SentimentRepository, SentimentSource,
+};
+use kairos_domain::services::sentiment::{MissingValuePolicy, SentimentPoint, SentimentReport};
+use postgres::types::{ToSql, Type};
+use postgres::NoTls;
+use r2d2::Pool;
+use r2d2_postgres::PostgresConnectionManager;
+use std::collections::BTreeMap;
+
+// Sentiment stored next to the candles: one row per `symbol` and `timestamp_utc` (TIMESTAMPTZ),
+// with every DOUBLE PRECISION or REAL column as a value; other columns are ignored. File sources
+// are delegated to `FilesystemSentimentRepository`.
+#[derive(Debug, Clone)]
+pub struct PostgresSentimentRepository {
+    pool: Pool<PostgresConnectionManager<NoTls>>,
+}
+
+impl PostgresSentimentRepository {
+    pub fn new(db_url: &str, pool_max_size: u32) -> Result<Self, String> {
+        let config = db_url
+            .parse::<postgres::Config>()
+            .map_err(|err| format!("invalid postgres db url: {err}"))?;
+        let manager = PostgresConnectionManager::new(config, NoTls);
+        // Connections are opened on first load, like the file repository this one stands in for.
+        let pool = Pool::builder()
+            .max_size(pool_max_size)
+            .build_unchecked(manager);
+        Ok(Self { pool })
+    }
+}
+
+impl SentimentRepository for PostgresSentimentRepository {
+    fn load_sentiment(
+        &self,
+        query: &SentimentQuery,
+    ) -> Result<(Vec<SentimentPoint>, SentimentReport), String> {
+        let SentimentSource::Db {
+            table,
+            symbol,
+            start,
+            end,
+        } = &query.source
+        else {
+            return FilesystemSentimentRepository.load_sentiment(query);
+        };
+        load_instrumented("db", query.missing_policy, table, || {
+            load_postgres_sentiment(
+                &self.pool,
+                table,
+                symbol,
+                *start,
+                *end,
+                query.missing_policy,
+            )
+        })
+    }
+}
+
+fn load_postgres_sentiment(
+    pool: &Pool<PostgresConnectionManager<NoTls>>,
+    table: &str,
+    symbol: &str,
+    start: Option<i64>,
+    end: Option<i64>,
+    policy: MissingValuePolicy,
+) -> Result<(Vec<SentimentPoint>, SentimentReport), String> {
+    validate_table_name(table)?;
+    let timestamp = |ts: i64| {
+        DateTime::<Utc>::from_timestamp(ts, 0)
+            .ok_or_else(|| format!("sentiment query bound out of range: {ts}"))
+    };
+    let start = start.map(timestamp).transpose()?;
+    let end = end.map(timestamp).transpose()?;
+
+    let mut client = pool
+        .get()
+        .map_err(|err| format!("failed to checkout postgres connection: {err}"))?;
+    let query_error =
+        |err: postgres::Error| format!("failed to query sentiment from {table}: {err}");
+    let statement = client
+        .prepare(&sentiment_sql(table, start.is_some(), end.is_some()))
+        .map_err(query_error)?;
+    let columns: Vec<(&str, &Type)> = statement
+        .columns()
+        .iter()
+        .map(|column| (column.name(), column.type_()))
+        .collect();
+    let ts_idx = columns
+        .iter()
+        .position(|(name, _)| *name == "timestamp_utc")
+        .ok_or_else(|| format!("sentiment table {table} has no timestamp_utc column"))?;
+    let value_columns = value_columns(&columns);
+    if value_columns.is_empty() {
+        return Err(format!(
+            "sentiment table {table} has no DOUBLE PRECISION value columns"
+        ));
+    }
+
+    let mut report = SentimentReport {
+        schema: value_columns
+            .iter()
+            .map(|&idx| columns[idx].0.to_string())
+            .collect(),
+        ..SentimentReport::default()
+    };
+    let mut params: Vec<&(dyn ToSql + Sync)> = vec![&symbol];
+    if let Some(start) = &start {
+        params.push(start);
+    }
+    if let Some(end) = &end {
+        params.push(end);
+    }
+    let rows = client.query(&statement, &params).map_err(query_error)?;
+
+    let mut raw_by_ts: BTreeMap<i64, Vec<Option<f64>>> = BTreeMap::new();
+    for row in &rows {
+        let ts = row
+            .try_get::<_, DateTime<Utc>>(ts_idx)
+            .map_err(|err| format!("invalid timestamp_utc in {table}: {err}"))?
+            .timestamp();
+        report.first_timestamp.get_or_insert(ts);
+        report.last_timestamp = Some(ts);
+
+        let mut values = Vec::with_capacity(value_columns.len());
+        for &idx in &value_columns {
+            let value = match *columns[idx].1 {
+                Type::FLOAT4 => row.try_get::<_, Option<f32>>(idx).map(|v| v.map(f64::from)),
+                _ => row.try_get::<_, Option<f64>>(idx),
+            }
+            .map_err(|err| format!("invalid {} in {table}: {err}", columns[idx].0))?;
+            values.push(match value {
+                None => {
+                    report.missing_values += 1;
+                    None
+                }
+                Some(value) if !value.is_finite() => {
+                    report.invalid_values += 1;
+                    if matches!(policy, MissingValuePolicy::Error) {
+                        return Err(format!(
+                            "invalid sentiment value '{value}' in column {}",
+                            columns[idx].0
+                        ));
+                    }
+                    None
+                }
+                Some(value) => Some(value),
+            });
+        }
+        if raw_by_ts.insert(ts, values).is_some() {
+            report.duplicates += 1;
+            report.first_duplicate.get_or_insert(ts);
+        }
+    }
+
+    let points = resolve_rows(raw_by_ts, report.schema.len(), policy, "db", &mut report)?;
+    Ok((points, report))
+}
+
+// Rows for `$1` (symbol), optionally bounded by `$2`/`$3`. The lower bound steps back to the last
+// row at or before it, which is what the first bar aligns with.
+fn sentiment_sql(table: &str, start: bool, end: bool) -> String {
+    let mut sql = format!("SELECT * FROM {table} WHERE symbol = $1");
+    let mut next = 2;
+    if start {
+        sql.push_str(&format!(
+            " AND timestamp_utc >= COALESCE((SELECT MAX(timestamp_utc) FROM {table} \
+             WHERE symbol = $1 AND timestamp_utc <= ${next}), ${next})"
+        ));
+        next += 1;
+    }
+    if end {
+        sql.push_str(&format!(" AND timestamp_utc <= ${next}"));
+    }
+    sql.push_str(" ORDER BY timestamp_utc ASC");
+    sql
+}
+
+fn value_columns(columns: &[(&str, &Type)]) -> Vec<usize> {
+    columns
+        .iter()
+        .enumerate()
+        .filter(|(_, (name, ty))| {
+            !matches!(*name, "symbol" | "timestamp_utc")
+                && matches!(**ty, Type::FLOAT8 | Type::FLOAT4)
+        })
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sentiment_sql, value_columns, PostgresSentimentRepository};
+    use kairos_domain::repositories::sentiment::{
+        SentimentQuery, SentimentRepository, SentimentSource,
+    };
+    use kairos_domain::services::sentiment::MissingValuePolicy;
+    use postgres::types::Type;
+
+    #[test]
+    fn sentiment_sql_binds_only_the_bounds_that_are_set() {
+        assert_eq!(
+            sentiment_sql("sentiment_scores", false, false),
+            "SELECT * FROM sentiment_scores WHERE symbol = $1 ORDER BY timestamp_utc ASC"
+        );
+        let bounded = sentiment_sql("sentiment_scores", true, true);
+        assert!(bounded.contains("timestamp_utc <= $2), $2)"));
+        assert!(bounded.contains("AND timestamp_utc <= $3 ORDER BY"));
+        assert!(sentiment_sql("s", false, true).contains("AND timestamp_utc <= $2 ORDER BY"));
+    }
+
+    #[test]
+    fn value_columns_are_the_float_columns_besides_the_key() {
+        let columns = [
+            ("symbol", &Type::TEXT),
+            ("timestamp_utc", &Type::TIMESTAMPTZ),
+            ("score", &Type::FLOAT8),
+            ("source", &Type::TEXT),
+            ("volume", &Type::FLOAT4),
+        ];
+        assert_eq!(value_columns(&columns), vec![2, 4]);
+    }
+
+    #[test]
+    fn load_rejects_invalid_table_name_before_connect() {
+        let repo =
+            PostgresSentimentRepository::new("postgres://localhost:1/none", 1).expect("lazy pool");
+        let err = repo
+            .load_sentiment(&SentimentQuery {
+                source: SentimentSource::Db {
+                    table: "bad;table".to_string(),
+                    symbol: "BTC-USDT".to_string(),
+                    start: None,
+                    end: None,
+                },
+                missing_policy: MissingValuePolicy::Error,
+            })
+            .expect_err("invalid table");
+        assert!(err.contains("invalid table name"));
+    }
+}
@@ -0,0 +1,352 @@
+use kairos_domain::repositories::run_registry::{RunQuery, RunRecord, RunRegistry, RunSortKey};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, Row};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+const SCHEMA_SQL: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    run_dir TEXT PRIMARY KEY,
+    run_id TEXT NOT NULL,
+    config_hash TEXT,
+    symbol TEXT,
+    timeframe TEXT,
+    start_ts INTEGER,
+    end_ts INTEGER,
+    bars_processed INTEGER NOT NULL,
+    trades INTEGER NOT NULL,
+    win_rate REAL NOT NULL,
+    net_profit REAL NOT NULL,
+    sharpe REAL NOT NULL,
+    max_drawdown REAL NOT NULL,
+    recorded_at INTEGER NOT NULL,
+    notes TEXT NOT NULL DEFAULT '[]'
+);
+CREATE INDEX IF NOT EXISTS runs_symbol_timeframe_idx ON runs (symbol, timeframe);
+CREATE INDEX IF NOT EXISTS runs_config_hash_idx ON runs (config_hash);
+";
+
+#[derive(Debug)]
+pub struct SqliteRunRegistry {
+    path: PathBuf,
+    conn: Mutex<Connection>,
+}
+
+impl SqliteRunRegistry {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|err| {
+                format!("failed to create registry dir {}: {err}", parent.display())
+            })?;
+        }
+        let conn = Connection::open(path)
+            .map_err(|err| format!("failed to open run registry {}: {err}", path.display()))?;
+        conn.execute_batch(SCHEMA_SQL)
+            .map_err(|err| format!("failed to initialize run registry schema: {err}"))?;
+        migrate_notes_column(&conn)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn with_conn<T>(&self, f: impl FnOnce(&Connection) -> Result<T, String>) -> Result<T, String> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|_| "run registry connection lock poisoned".to_string())?;
+        f(&conn)
+    }
+}
+
+// Registries created before run annotations existed lack the `notes` column.
+fn migrate_notes_column(conn: &Connection) -> Result<(), String> {
+    let has_notes = conn
+        .prepare("SELECT 1 FROM pragma_table_info('runs') WHERE name = 'notes'")
+        .and_then(|mut stmt| stmt.exists([]))
+        .map_err(|err| format!("failed to inspect run registry schema: {err}"))?;
+    if has_notes {
+        return Ok(());
+    }
+    conn.execute_batch("ALTER TABLE runs ADD COLUMN notes TEXT NOT NULL DEFAULT '[]'")
+        .map_err(|err| format!("failed to migrate run registry schema: {err}"))
+}
+
+fn record_registry_metrics<T>(op: &'static str, start: Instant, result: &Result<T, String>) {
+    let result_label = if result.is_ok() { "ok" } else { "err" };
+    metrics::counter!(
+        "kairos.infra.registry.calls_total",
+        "op" => op,
+        "result" => result_label
+    )
+    .increment(1);
+    metrics::histogram!("kairos.infra.registry.op_ms", "op" => op, "result" => result_label)
+        .record(start.elapsed().as_millis() as f64);
+}
+
+fn sort_column(key: RunSortKey) -> &'static str {
+    match key {
+        RunSortKey::RecordedAt => "recorded_at",
+        RunSortKey::NetProfit => "net_profit",
+        RunSortKey::Sharpe => "sharpe",
+        RunSortKey::MaxDrawdown => "max_drawdown",
+        RunSortKey::WinRate => "win_rate",
+    }
+}
+
+fn row_to_record(row: &Row<'_>) -> rusqlite::Result<RunRecord> {
+    Ok(RunRecord {
+        run_dir: row.get("run_dir")?,
+        run_id: row.get("run_id")?,
+        config_hash: row.get("config_hash")?,
+        symbol: row.get("symbol")?,
+        timeframe: row.get("timeframe")?,
+        start: row.get("start_ts")?,
+        end: row.get("end_ts")?,
+        bars_processed: row.get::<_, i64>("bars_processed")?.max(0) as u64,
+        trades: row.get::<_, i64>("trades")?.max(0) as u64,
+        win_rate: row.get("win_rate")?,
+        net_profit: row.get("net_profit")?,
+        sharpe: row.get("sharpe")?,
+        max_drawdown: row.get("max_drawdown")?,
+        recorded_at: row.get("recorded_at")?,
+        // Tolerate hand-edited rows; notes are informational.
+        notes: serde_json::from_str(&row.get::<_, String>("notes")?).unwrap_or_default(),
+    })
+}
+
+impl RunRegistry for SqliteRunRegistry {
+    fn upsert_run(&self, record: &RunRecord) -> Result<(), String> {
+        let start = Instant::now();
+        let notes = serde_json::to_string(&record.notes)
+            .map_err(|err| format!("failed to serialize notes for run {}: {err}", record.run_id))?;
+        let result = self.with_conn(|conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO runs (
+                    run_dir, run_id, config_hash, symbol, timeframe, start_ts, end_ts,
+                    bars_processed, trades, win_rate, net_profit, sharpe, max_drawdown, recorded_at,
+                    notes
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+                params![
+                    record.run_dir,
+                    record.run_id,
+                    record.config_hash,
+                    record.symbol,
+                    record.timeframe,
+                    record.start,
+                    record.end,
+                    record.bars_processed as i64,
+                    record.trades as i64,
+                    record.win_rate,
+                    record.net_profit,
+                    record.sharpe,
+                    record.max_drawdown,
+                    record.recorded_at,
+                    notes,
+                ],
+            )
+            .map(|_| ())
+            .map_err(|err| format!("failed to upsert run {}: {err}", record.run_id))
+        });
+        record_registry_metrics("upsert", start, &result);
+        result
+    }
+
+    fn query_runs(&self, query: &RunQuery) -> Result<Vec<RunRecord>, String> {
+        let start = Instant::now();
+        let mut clauses: Vec<&str> = Vec::new();
+        let mut values: Vec<Value> = Vec::new();
+        if let Some(symbol) = &query.symbol {
+            clauses.push("symbol = ?");
+            values.push(Value::Text(symbol.clone()));
+        }
+        if let Some(timeframe) = &query.timeframe {
+            clauses.push("timeframe = ?");
+            values.push(Value::Text(timeframe.clone()));
+        }
+        if let Some(config_hash) = &query.config_hash {
+            clauses.push("config_hash = ?");
+            values.push(Value::Text(config_hash.clone()));
+        }
+        if let Some(start_from) = query.start_from {
+            clauses.push("start_ts >= ?");
+            values.push(Value::Integer(start_from));
+        }
+        if let Some(end_to) = query.end_to {
+            clauses.push("end_ts <= ?");
+            values.push(Value::Integer(end_to));
+        }
+
+        let mut sql = "SELECT * FROM runs".to_string();
+        if !clauses.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&clauses.join(" AND "));
+        }
+        sql.push_str(&format!(
+            " ORDER BY {} {}, run_id ASC",
+            sort_column(query.sort_by),
+            if query.descending { "DESC" } else { "ASC" }
+        ));
+        if let Some(limit) = query.limit {
+            sql.push_str(" LIMIT ?");
+            values.push(Value::Integer(limit as i64));
+        }
+
+        let result = self.with_conn(|conn| {
+            let mut stmt = conn
+                .prepare(&sql)
+                .map_err(|err| format!("failed to prepare run registry query: {err}"))?;
+            let rows = stmt
+                .query_map(params_from_iter(values.iter()), row_to_record)
+                .map_err(|err| format!("failed to query run registry: {err}"))?;
+            rows.collect::<Result<Vec<_>, _>>()
+                .map_err(|err| format!("failed to read run registry row: {err}"))
+        });
+        record_registry_metrics("query", start, &result);
+        result
+    }
+
+    fn remove_run(&self, run_dir: &str) -> Result<(), String> {
+        let start = Instant::now();
+        let result = self.with_conn(|conn| {
+            conn.execute("DELETE FROM runs WHERE run_dir = ?1", params![run_dir])
+                .map(|_| ())
+                .map_err(|err| format!("failed to remove run {run_dir}: {err}"))
+        });
+        record_registry_metrics("remove", start, &result);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SqliteRunRegistry;
+    use kairos_domain::repositories::run_registry::{RunQuery, RunRecord, RunRegistry, RunSortKey};
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn unique_tmp_dir(prefix: &str) -> PathBuf {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        std::env::temp_dir().join(format!("kairos_{prefix}_{}_{}", std::process::id(), now))
+    }
+
+    fn record(run_id: &str, symbol: &str, sharpe: f64) -> RunRecord {
+        RunRecord {
+            run_id: run_id.to_string(),
+            run_dir: format!("runs/{run_id}"),
+            config_hash: Some("abc".to_string()),
+            symbol: Some(symbol.to_string()),
+            timeframe: Some("1min".to_string()),
+            start: Some(100),
+            end: Some(200),
+            bars_processed: 10,
+            trades: 2,
+            win_rate: 0.5,
+            net_profit: 1.0,
+            sharpe,
+            max_drawdown: 0.1,
+            recorded_at: 1,
+            notes: vec!["baseline".to_string()],
+        }
+    }
+
+    #[test]
+    fn upsert_replaces_and_query_filters_and_sorts() {
+        let dir = unique_tmp_dir("registry_test");
+        let registry = SqliteRunRegistry::open(&dir.join("registry.sqlite")).expect("open");
+
+        registry.upsert_run(&record("a", "BTC-USDT", 0.5)).unwrap();
+        registry.upsert_run(&record("b", "BTC-USDT", 1.5)).unwrap();
+        registry.upsert_run(&record("c", "ETH-USDT", 3.0)).unwrap();
+        // Re-recording the same run_dir replaces the row instead of duplicating it.
+        registry.upsert_run(&record("a", "BTC-USDT", 2.0)).unwrap();
+
+        let all = registry.query_runs(&RunQuery::default()).unwrap();
+        assert_eq!(all.len(), 3);
+
+        let btc = registry
+            .query_runs(&RunQuery {
+                symbol: Some("BTC-USDT".to_string()),
+                sort_by: RunSortKey::Sharpe,
+                descending: true,
+                ..RunQuery::default()
+            })
+            .unwrap();
+        let ids: Vec<&str> = btc.iter().map(|r| r.run_id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+        assert_eq!(btc[0], record("a", "BTC-USDT", 2.0));
+
+        let limited = registry
+            .query_runs(&RunQuery {
+                start_from: Some(100),
+                end_to: Some(200),
+                limit: Some(1),
+                ..RunQuery::default()
+            })
+            .unwrap();
+        assert_eq!(limited.len(), 1);
+
+        let none = registry
+            .query_runs(&RunQuery {
+                end_to: Some(150),
+                ..RunQuery::default()
+            })
+            .unwrap();
+        assert!(none.is_empty());
+
+        registry.remove_run("runs/c").unwrap();
+        registry.remove_run("runs/missing").unwrap();
+        assert_eq!(registry.query_runs(&RunQuery::default()).unwrap().len(), 2);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn open_adds_notes_column_to_existing_registry() {
+        let dir = unique_tmp_dir("registry_migrate");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("registry.sqlite");
+        {
+            let conn = rusqlite::Connection::open(&path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE runs (
+                    run_dir TEXT PRIMARY KEY, run_id TEXT NOT NULL, config_hash TEXT,
+                    symbol TEXT, timeframe TEXT, start_ts INTEGER, end_ts INTEGER,
+                    bars_processed INTEGER NOT NULL, trades INTEGER NOT NULL,
+                    win_rate REAL NOT NULL, net_profit REAL NOT NULL, sharpe REAL NOT NULL,
+                    max_drawdown REAL NOT NULL, recorded_at INTEGER NOT NULL
+                );
+                INSERT INTO runs VALUES ('runs/old', 'old', NULL, NULL, NULL, NULL, NULL,
+                    1, 0, 0.0, 0.0, 0.0, 0.0, 1);",
+            )
+            .unwrap();
+        }
+
+        let registry = SqliteRunRegistry::open(&path).expect("open migrates");
+        let runs = registry.query_runs(&RunQuery::default()).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert!(runs[0].notes.is_empty());
+
+        registry.upsert_run(&record("a", "BTC-USDT", 1.0)).unwrap();
+        let a = registry
+            .query_runs(&RunQuery {
+                symbol: Some("BTC-USDT".to_string()),
+                ..RunQuery::default()
+            })
+            .unwrap();
+        assert_eq!(a[0].notes, vec!["baseline".to_string()]);
+        drop(registry);
+        // Re-opening an already migrated registry is a no-op.
+        SqliteRunRegistry::open(&path).expect("reopen");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
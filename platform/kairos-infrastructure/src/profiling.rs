@@ -0,0 +1,148 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// CPU sampling profiler (pprof) rendered as an SVG flamegraph.
+#[cfg(feature = "pprof")]
+pub struct CpuProfiler {
+    guard: pprof::ProfilerGuard<'static>,
+}
+
+#[cfg(feature = "pprof")]
+impl CpuProfiler {
+    pub fn start(frequency_hz: i32) -> Result<Self, String> {
+        let guard = pprof::ProfilerGuard::new(frequency_hz)
+            .map_err(|err| format!("failed to start profiler: {err}"))?;
+        Ok(Self { guard })
+    }
+
+    pub fn write_flamegraph(self, path: &std::path::Path) -> Result<(), String> {
+        let report = self
+            .guard
+            .report()
+            .build()
+            .map_err(|err| format!("failed to build profile report: {err}"))?;
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|err| format!("failed to create {}: {err}", parent.display()))?;
+        }
+        let file = std::fs::File::create(path)
+            .map_err(|err| format!("failed to create {}: {err}", path.display()))?;
+        report
+            .flamegraph(file)
+            .map_err(|err| format!("failed to write flamegraph: {err}"))
+    }
+}
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+
+// System allocator that counts allocations. Only counts when a binary installs it with
+// `#[global_allocator]`; otherwise `HeapStats::snapshot` stays at zero.
+pub struct TrackingAllocator;
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size() as u64);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE_BYTES.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            LIVE_BYTES.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+            record_alloc(new_size as u64);
+        }
+        new_ptr
+    }
+}
+
+fn record_alloc(size: u64) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+    let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_LIVE_BYTES.fetch_max(live, Ordering::Relaxed);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeapStats {
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+    pub live_bytes: u64,
+    pub peak_live_bytes: u64,
+}
+
+impl HeapStats {
+    pub fn snapshot() -> Self {
+        Self {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+            live_bytes: LIVE_BYTES.load(Ordering::Relaxed),
+            peak_live_bytes: PEAK_LIVE_BYTES.load(Ordering::Relaxed),
+        }
+    }
+
+    // Starts a measurement window: the peak is reset to the bytes live right now.
+    pub fn start_window() -> Self {
+        PEAK_LIVE_BYTES.store(LIVE_BYTES.load(Ordering::Relaxed), Ordering::Relaxed);
+        Self::snapshot()
+    }
+
+    pub fn to_json_since(&self, start: &HeapStats) -> serde_json::Value {
+        serde_json::json!({
+            "allocations": self.allocations.saturating_sub(start.allocations),
+            "allocated_bytes": self.allocated_bytes.saturating_sub(start.allocated_bytes),
+            "live_bytes_start": start.live_bytes,
+            "live_bytes_end": self.live_bytes,
+            "peak_live_bytes": self.peak_live_bytes,
+        })
+    }
+}
+
+// Peak resident set size of this process (`VmHWM`). Linux only; `None` elsewhere.
+pub fn peak_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kib: u64 = line
+        .trim_start_matches("VmHWM:")
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+// Resets the peak RSS to the current RSS, so `peak_rss_bytes` covers only what follows. Returns
+// `false` when the kernel does not support it.
+pub fn reset_peak_rss() -> bool {
+    std::fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{peak_rss_bytes, reset_peak_rss};
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn peak_rss_grows_with_touched_memory() {
+        reset_peak_rss();
+        let before = peak_rss_bytes().expect("VmHWM");
+        let block = vec![1u8; 64 << 20];
+        let after = peak_rss_bytes().expect("VmHWM");
+        assert!(
+            after >= before + (32 << 20),
+            "before={before} after={after}"
+        );
+        drop(block);
+    }
+}
@@ -0,0 +1,255 @@
+use kairos_domain::repositories::run_registry::RunRecord;
+use reqwest::blocking::Client;
+use reqwest::Url;
+use std::time::Duration;
+
+pub const PUSHGATEWAY_URL_ENV: &str = "KAIROS_PUSHGATEWAY_URL";
+pub const PUSHGATEWAY_JOB_ENV: &str = "KAIROS_PUSHGATEWAY_JOB";
+
+const DEFAULT_JOB: &str = "kairos_alloy";
+const PUSH_TIMEOUT_MS: u64 = 5_000;
+
+// Publishes the final metrics of a finished run to a Prometheus Pushgateway, grouped by
+// `job`/`run_id`. The `/metrics` exporter only helps long-lived processes; a headless run exits
+// before Prometheus scrapes it.
+pub struct Pushgateway {
+    client: Client,
+    base_url: Url,
+    job: String,
+}
+
+// What gets pushed for one run, on top of its registry record.
+#[derive(Debug, Clone, Copy)]
+pub struct RunTiming<'a> {
+    // backtest | paper | live.
+    pub mode: &'a str,
+    pub duration_ms: u64,
+}
+
+impl Pushgateway {
+    pub fn new(base_url: &str, job: &str) -> Result<Self, String> {
+        let base_url = Url::parse(base_url.trim())
+            .map_err(|err| format!("invalid {PUSHGATEWAY_URL_ENV} {base_url:?}: {err}"))?;
+        if !matches!(base_url.scheme(), "http" | "https") || base_url.cannot_be_a_base() {
+            return Err(format!(
+                "invalid {PUSHGATEWAY_URL_ENV} {base_url}: expected an http(s) URL"
+            ));
+        }
+        if job.trim().is_empty() {
+            return Err(format!("{PUSHGATEWAY_JOB_ENV} must not be empty"));
+        }
+        let client = Client::builder()
+            .timeout(Duration::from_millis(PUSH_TIMEOUT_MS))
+            .build()
+            .map_err(|err| format!("failed to build http client: {err}"))?;
+        Ok(Self {
+            client,
+            base_url,
+            job: job.trim().to_string(),
+        })
+    }
+
+    // Returns `Ok(None)` when no Pushgateway URL is configured (pushing is opt-in).
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let Some(url) = std::env::var(PUSHGATEWAY_URL_ENV)
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+        else {
+            return Ok(None);
+        };
+        let job = std::env::var(PUSHGATEWAY_JOB_ENV).unwrap_or_else(|_| DEFAULT_JOB.to_string());
+        Self::new(&url, &job).map(Some)
+    }
+
+    // `PUT /metrics/job/<job>/run_id/<run_id>` replaces whatever an earlier push of the same run
+    // left in its group.
+    pub fn push_run(&self, run: &RunRecord, timing: RunTiming<'_>) -> Result<(), String> {
+        let url = self.group_url(&run.run_id)?;
+        let response = self
+            .client
+            .put(url.clone())
+            .header("Content-Type", "text/plain; version=0.0.4")
+            .body(run_exposition(run, timing))
+            .send()
+            .map_err(|err| format!("pushgateway request to {url} failed: {err}"))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().unwrap_or_default();
+            return Err(format!(
+                "pushgateway {url} returned {status}: {}",
+                body.trim()
+            ));
+        }
+        metrics::counter!("kairos.infra.pushgateway.pushes_total").increment(1);
+        Ok(())
+    }
+
+    fn group_url(&self, run_id: &str) -> Result<Url, String> {
+        // The Pushgateway cannot route a `/` inside a label value; those go base64url-encoded.
+        let (run_label, run_value) = if run_id.contains('/') {
+            ("run_id@base64", base64_url(run_id.as_bytes()))
+        } else {
+            ("run_id", run_id.to_string())
+        };
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .map_err(|_| format!("invalid {PUSHGATEWAY_URL_ENV} {}", self.base_url))?
+            .pop_if_empty()
+            .extend(["metrics", "job", &self.job, run_label, &run_value]);
+        Ok(url)
+    }
+}
+
+fn base64_url(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (u32::from(*b) << (16 - 8 * i)));
+        for i in 0..=chunk.len() {
+            out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+        }
+    }
+    out
+}
+
+// Prometheus text exposition of the run's final metrics. `job`/`run_id` come from the group URL.
+fn run_exposition(run: &RunRecord, timing: RunTiming<'_>) -> String {
+    let mut labels = vec![format!("mode=\"{}\"", escape_label(timing.mode))];
+    if let Some(symbol) = &run.symbol {
+        labels.push(format!("symbol=\"{}\"", escape_label(symbol)));
+    }
+    if let Some(timeframe) = &run.timeframe {
+        labels.push(format!("timeframe=\"{}\"", escape_label(timeframe)));
+    }
+    let labels = labels.join(",");
+    let duration_secs = timing.duration_ms as f64 / 1000.0;
+    let bars_per_sec = if duration_secs > 0.0 {
+        run.bars_processed as f64 / duration_secs
+    } else {
+        0.0
+    };
+
+    let gauges: [(&str, &str, f64); 8] = [
+        (
+            "kairos_run_duration_seconds",
+            "Wall-clock duration of the run.",
+            duration_secs,
+        ),
+        (
+            "kairos_run_bars_processed",
+            "Bars processed by the engine.",
+            run.bars_processed as f64,
+        ),
+        (
+            "kairos_run_bars_per_second",
+            "Bars processed per second of wall-clock time.",
+            bars_per_sec,
+        ),
+        ("kairos_run_trades", "Trades executed.", run.trades as f64),
+        (
+            "kairos_run_net_profit",
+            "Final equity minus initial capital.",
+            run.net_profit,
+        ),
+        ("kairos_run_sharpe", "Sharpe ratio.", run.sharpe),
+        (
+            "kairos_run_max_drawdown",
+            "Maximum drawdown (fraction).",
+            run.max_drawdown,
+        ),
+        (
+            "kairos_run_last_success_timestamp_seconds",
+            "Unix time the run finished.",
+            run.recorded_at as f64,
+        ),
+    ];
+    let mut out = String::new();
+    for (name, help, value) in gauges {
+        out.push_str(&format!(
+            "# HELP {name} {help}\n# TYPE {name} gauge\n{name}{{{labels}}} {}\n",
+            prometheus_value(value)
+        ));
+    }
+    out
+}
+
+fn prometheus_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_exposition, Pushgateway, RunTiming};
+    use kairos_domain::repositories::run_registry::RunRecord;
+
+    fn record() -> RunRecord {
+        RunRecord {
+            run_id: "btc/1h run".to_string(),
+            run_dir: "runs/btc".to_string(),
+            config_hash: None,
+            symbol: Some("BTC-USDT".to_string()),
+            timeframe: Some("1h".to_string()),
+            start: None,
+            end: None,
+            bars_processed: 5_000,
+            trades: 12,
+            win_rate: 0.5,
+            net_profit: 250.5,
+            sharpe: f64::NAN,
+            max_drawdown: 0.1,
+            recorded_at: 1_700_000_000,
+            notes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn exposition_has_one_labelled_gauge_per_metric() {
+        let text = run_exposition(
+            &record(),
+            RunTiming {
+                mode: "backtest",
+                duration_ms: 2_000,
+            },
+        );
+        let labels = "{mode=\"backtest\",symbol=\"BTC-USDT\",timeframe=\"1h\"}";
+        assert!(text.contains(&format!("kairos_run_bars_per_second{labels} 2500\n")));
+        assert!(text.contains(&format!("kairos_run_trades{labels} 12\n")));
+        assert!(text.contains(&format!("kairos_run_net_profit{labels} 250.5\n")));
+        assert!(text.contains(&format!("kairos_run_duration_seconds{labels} 2\n")));
+        assert!(text.contains(&format!("kairos_run_sharpe{labels} NaN\n")));
+        assert_eq!(text.matches("# TYPE ").count(), 8);
+    }
+
+    #[test]
+    fn group_url_is_keyed_by_job_and_escaped_run_id() {
+        let gateway = Pushgateway::new("http://pushgateway:9091/", "nightly").expect("gateway");
+        let url = gateway.group_url("sma run").expect("url");
+        assert_eq!(
+            url.as_str(),
+            "http://pushgateway:9091/metrics/job/nightly/run_id/sma%20run"
+        );
+        let url = gateway.group_url("btc/1h").expect("url");
+        assert_eq!(
+            url.as_str(),
+            "http://pushgateway:9091/metrics/job/nightly/run_id@base64/YnRjLzFo"
+        );
+        assert!(Pushgateway::new("pushgateway:9091", "nightly").is_err());
+        assert!(Pushgateway::new("http://pushgateway:9091", " ").is_err());
+    }
+}
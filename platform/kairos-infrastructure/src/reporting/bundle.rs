@@ -0,0 +1,244 @@
+use super::integrity::{artifact_exists, write_manifest};
+use super::parquet::{write_equity_parquet, write_trades_parquet};
+use super::{read_equity_csv, read_trades_csv};
+use kairos_domain::repositories::artifacts::BundleFormat;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+// Replaced by `trades.parquet` / `equity.parquet` in `BundleFormat::Parquet` archives.
+const CONVERTED_CSVS: [&str; 2] = ["trades.csv", "equity.csv"];
+
+pub fn write_bundle(
+    run_dir: &Path,
+    out_path: &Path,
+    format: BundleFormat,
+) -> Result<Vec<String>, String> {
+    if !run_dir.is_dir() {
+        return Err(format!("run dir not found: {}", run_dir.display()));
+    }
+    let prefix = run_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "run".to_string());
+    match format {
+        BundleFormat::Zip => zip_dir(run_dir, &prefix, out_path),
+        BundleFormat::Parquet => {
+            // Converted copy of the run dir with its own manifest, so the archive stays verifiable.
+            let staging = staging_dir(out_path);
+            let result =
+                stage_parquet(run_dir, &staging).and_then(|_| zip_dir(&staging, &prefix, out_path));
+            let _ = fs::remove_dir_all(&staging);
+            result
+        }
+    }
+}
+
+fn staging_dir(out_path: &Path) -> PathBuf {
+    let name = out_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "bundle".to_string());
+    std::env::temp_dir().join(format!(
+        "kairos_export_{}_{}",
+        std::process::id(),
+        name.replace('.', "_")
+    ))
+}
+
+fn stage_parquet(run_dir: &Path, staging: &Path) -> Result<(), String> {
+    if staging.exists() {
+        fs::remove_dir_all(staging)
+            .map_err(|err| format!("failed to clear {}: {err}", staging.display()))?;
+    }
+    for (relative, path) in list_files(run_dir)? {
+        let converted = CONVERTED_CSVS
+            .iter()
+            .any(|csv| relative == *csv || relative.starts_with(&format!("{csv}.")));
+        if converted {
+            continue;
+        }
+        let target = staging.join(&relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| format!("failed to create dir {}: {err}", parent.display()))?;
+        }
+        fs::copy(&path, &target)
+            .map_err(|err| format!("failed to copy {}: {err}", path.display()))?;
+    }
+
+    let trades_csv = run_dir.join("trades.csv");
+    if artifact_exists(&trades_csv) && !staging.join("trades.parquet").exists() {
+        write_trades_parquet(
+            &staging.join("trades.parquet"),
+            &read_trades_csv(&trades_csv)?,
+        )?;
+    }
+    let equity_csv = run_dir.join("equity.csv");
+    if artifact_exists(&equity_csv) && !staging.join("equity.parquet").exists() {
+        write_equity_parquet(
+            &staging.join("equity.parquet"),
+            &read_equity_csv(&equity_csv)?,
+        )?;
+    }
+    write_manifest(staging)
+}
+
+fn zip_dir(dir: &Path, prefix: &str, out_path: &Path) -> Result<Vec<String>, String> {
+    let files = list_files(dir)?;
+    if let Some(parent) = out_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("failed to create dir {}: {err}", parent.display()))?;
+    }
+    let file = File::create(out_path)
+        .map_err(|err| format!("failed to create {}: {err}", out_path.display()))?;
+    let mut zip = ZipWriter::new(BufWriter::new(file));
+    let zip_err =
+        |err: zip::result::ZipError| format!("failed to write {}: {err}", out_path.display());
+    let io_err = |err: io::Error| format!("failed to write {}: {err}", out_path.display());
+
+    let mut entries = Vec::with_capacity(files.len());
+    for (relative, path) in files {
+        let name = format!("{prefix}/{relative}");
+        // gzip/zstd/parquet payloads are already compressed.
+        let method = if [".gz", ".zst", ".parquet"]
+            .iter()
+            .any(|ext| relative.ends_with(ext))
+        {
+            CompressionMethod::Stored
+        } else {
+            CompressionMethod::Deflated
+        };
+        zip.start_file(
+            name.as_str(),
+            SimpleFileOptions::default().compression_method(method),
+        )
+        .map_err(zip_err)?;
+        let mut input = BufReader::new(
+            File::open(&path).map_err(|err| format!("failed to open {}: {err}", path.display()))?,
+        );
+        io::copy(&mut input, &mut zip).map_err(io_err)?;
+        entries.push(name);
+    }
+    zip.finish().map_err(zip_err)?.flush().map_err(io_err)?;
+    Ok(entries)
+}
+
+// Every file under `root` as (`/`-separated relative path, full path), sorted.
+fn list_files(root: &Path) -> Result<Vec<(String, PathBuf)>, String> {
+    let mut out = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = fs::read_dir(&dir)
+            .map_err(|err| format!("failed to read dir {}: {err}", dir.display()))?;
+        for entry in entries {
+            let entry =
+                entry.map_err(|err| format!("failed to read dir {}: {err}", dir.display()))?;
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let relative = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/");
+            out.push((relative, path));
+        }
+    }
+    out.sort();
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_bundle;
+    use crate::reporting::{compress_artifact, write_manifest};
+    use kairos_domain::repositories::artifacts::{ArtifactCompression, BundleFormat};
+    use std::io::Read;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn unique_tmp_dir(prefix: &str) -> PathBuf {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        std::env::temp_dir().join(format!("kairos_{prefix}_{}_{}", std::process::id(), now))
+    }
+
+    #[test]
+    fn bundles_run_dir_and_converts_csvs_to_parquet() {
+        let root = unique_tmp_dir("bundle_test");
+        let run_dir = root.join("demo_run");
+        std::fs::create_dir_all(run_dir.join("analyzers")).unwrap();
+        std::fs::write(
+            run_dir.join("trades.csv"),
+            "timestamp_utc,symbol,side,qty,price,fee,slippage,strategy_id,reason\n\
+             60,BTCUSDT,BUY,1,100,0.1,0,baseline,signal\n",
+        )
+        .unwrap();
+        std::fs::write(
+            run_dir.join("equity.csv"),
+            "timestamp_utc,equity,cash,position_qty,unrealized_pnl,realized_pnl\n\
+             60,1000,900,1,0,0\n120,1010,900,1,10,0\n",
+        )
+        .unwrap();
+        compress_artifact(&run_dir.join("equity.csv"), ArtifactCompression::Gzip).unwrap();
+        std::fs::write(run_dir.join("summary.json"), "{}").unwrap();
+        std::fs::write(run_dir.join("analyzers").join("drawdown.json"), "{}").unwrap();
+        write_manifest(&run_dir).unwrap();
+
+        let zip_path = root.join("demo_run.zip");
+        let entries = write_bundle(&run_dir, &zip_path, BundleFormat::Zip).expect("zip bundle");
+        assert_eq!(
+            entries,
+            vec![
+                "demo_run/analyzers/drawdown.json",
+                "demo_run/equity.csv.gz",
+                "demo_run/manifest.json",
+                "demo_run/summary.json",
+                "demo_run/trades.csv",
+            ]
+        );
+
+        let parquet_path = root.join("demo_run.parquet.zip");
+        let entries =
+            write_bundle(&run_dir, &parquet_path, BundleFormat::Parquet).expect("parquet bundle");
+        assert_eq!(
+            entries,
+            vec![
+                "demo_run/analyzers/drawdown.json",
+                "demo_run/equity.parquet",
+                "demo_run/manifest.json",
+                "demo_run/summary.json",
+                "demo_run/trades.parquet",
+            ]
+        );
+
+        let mut archive =
+            zip::ZipArchive::new(std::fs::File::open(&parquet_path).unwrap()).unwrap();
+        let mut manifest = String::new();
+        archive
+            .by_name("demo_run/manifest.json")
+            .unwrap()
+            .read_to_string(&mut manifest)
+            .unwrap();
+        assert!(manifest.contains("\"equity.parquet\""));
+        assert!(!manifest.contains("equity.csv"));
+        let mut magic = [0u8; 4];
+        archive
+            .by_name("demo_run/trades.parquet")
+            .unwrap()
+            .read_exact(&mut magic)
+            .unwrap();
+        assert_eq!(&magic, b"PAR1");
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,250 @@
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use kairos_domain::entities::run_note::RUN_NOTES_FILE_NAME;
+use kairos_domain::repositories::artifacts::ArtifactCompression;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+const COMPRESSIONS: [ArtifactCompression; 2] =
+    [ArtifactCompression::Gzip, ArtifactCompression::Zstd];
+
+#[derive(Debug, Serialize)]
+struct ManifestEntry {
+    path: String,
+    bytes: u64,
+    sha256: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Manifest {
+    algorithm: &'static str,
+    files: Vec<ManifestEntry>,
+}
+
+fn with_extension_suffix(path: &Path, compression: ArtifactCompression) -> PathBuf {
+    let mut raw = path.as_os_str().to_os_string();
+    raw.push(".");
+    raw.push(compression.extension());
+    PathBuf::from(raw)
+}
+
+pub fn compress_artifact(path: &Path, compression: ArtifactCompression) -> Result<(), String> {
+    if !path.is_file() {
+        return Ok(());
+    }
+    let target = with_extension_suffix(path, compression);
+    let mut input = BufReader::new(
+        File::open(path).map_err(|err| format!("failed to open {}: {err}", path.display()))?,
+    );
+    let output = BufWriter::new(
+        File::create(&target)
+            .map_err(|err| format!("failed to create {}: {err}", target.display()))?,
+    );
+    let copy_err = |err: io::Error| format!("failed to compress {}: {err}", path.display());
+    match compression {
+        ArtifactCompression::Gzip => {
+            let mut encoder = GzEncoder::new(output, flate2::Compression::default());
+            io::copy(&mut input, &mut encoder).map_err(copy_err)?;
+            encoder
+                .finish()
+                .and_then(|mut w| w.flush())
+                .map_err(copy_err)?;
+        }
+        ArtifactCompression::Zstd => {
+            let mut encoder = zstd::stream::write::Encoder::new(output, 0).map_err(copy_err)?;
+            io::copy(&mut input, &mut encoder).map_err(copy_err)?;
+            encoder
+                .finish()
+                .and_then(|mut w| w.flush())
+                .map_err(copy_err)?;
+        }
+    }
+
+    // Drop stale variants (e.g. a run re-compressed with a different codec).
+    for other in COMPRESSIONS.iter().filter(|c| **c != compression) {
+        let stale = with_extension_suffix(path, *other);
+        if stale.exists() {
+            fs::remove_file(&stale)
+                .map_err(|err| format!("failed to remove {}: {err}", stale.display()))?;
+        }
+    }
+    fs::remove_file(path).map_err(|err| format!("failed to remove {}: {err}", path.display()))
+}
+
+// Opens `path`, falling back to its compressed variants (`.gz`, `.zst`) when the plain file is
+// missing, so readers work the same on compressed run directories.
+pub fn open_artifact(path: &Path) -> Result<Box<dyn Read>, String> {
+    if path.is_file() {
+        let file =
+            File::open(path).map_err(|err| format!("failed to open {}: {err}", path.display()))?;
+        return Ok(Box::new(BufReader::new(file)));
+    }
+    for compression in COMPRESSIONS {
+        let candidate = with_extension_suffix(path, compression);
+        if !candidate.is_file() {
+            continue;
+        }
+        let file = File::open(&candidate)
+            .map_err(|err| format!("failed to open {}: {err}", candidate.display()))?;
+        let reader = BufReader::new(file);
+        return match compression {
+            ArtifactCompression::Gzip => Ok(Box::new(GzDecoder::new(reader))),
+            ArtifactCompression::Zstd => zstd::stream::read::Decoder::with_buffer(reader)
+                .map(|decoder| Box::new(decoder) as Box<dyn Read>)
+                .map_err(|err| format!("failed to open {}: {err}", candidate.display())),
+        };
+    }
+    Err(format!("{} not found", path.display()))
+}
+
+pub fn artifact_exists(path: &Path) -> bool {
+    path.exists()
+        || COMPRESSIONS
+            .iter()
+            .any(|c| with_extension_suffix(path, *c).is_file())
+}
+
+pub fn write_manifest(run_dir: &Path) -> Result<(), String> {
+    let mut files = Vec::new();
+    collect_files(run_dir, run_dir, &mut files)?;
+    files.sort();
+
+    let mut entries = Vec::with_capacity(files.len());
+    for (relative, path) in files {
+        let (bytes, sha256) = hash_file(&path)?;
+        entries.push(ManifestEntry {
+            path: relative,
+            bytes,
+            sha256,
+        });
+    }
+
+    let manifest = Manifest {
+        algorithm: "sha256",
+        files: entries,
+    };
+    let json = serde_json::to_string_pretty(&manifest)
+        .map_err(|err| format!("failed to serialize manifest: {err}"))?;
+    let path = run_dir.join(MANIFEST_FILE_NAME);
+    fs::write(&path, json).map_err(|err| format!("failed to write {}: {err}", path.display()))
+}
+
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<(String, PathBuf)>) -> Result<(), String> {
+    let entries =
+        fs::read_dir(dir).map_err(|err| format!("failed to read dir {}: {err}", dir.display()))?;
+    for entry in entries {
+        let entry = entry.map_err(|err| format!("failed to read dir {}: {err}", dir.display()))?;
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(root, &path, out)?;
+            continue;
+        }
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join("/");
+        // Notes are appended after the run by design; they are not part of its integrity record.
+        if relative == MANIFEST_FILE_NAME || relative == RUN_NOTES_FILE_NAME {
+            continue;
+        }
+        out.push((relative, path));
+    }
+    Ok(())
+}
+
+fn hash_file(path: &Path) -> Result<(u64, String), String> {
+    let mut file =
+        File::open(path).map_err(|err| format!("failed to open {}: {err}", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 64 * 1024];
+    let mut bytes = 0u64;
+    loop {
+        let n = file
+            .read(&mut buf)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        if n == 0 {
+            break;
+        }
+        bytes += n as u64;
+        hasher.update(&buf[..n]);
+    }
+    let digest = hasher.finalize();
+    let hex = digest
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+    Ok((bytes, hex))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{artifact_exists, compress_artifact, open_artifact, write_manifest};
+    use kairos_domain::repositories::artifacts::ArtifactCompression;
+    use std::io::Read;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn unique_tmp_dir(prefix: &str) -> PathBuf {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        std::env::temp_dir().join(format!("kairos_{prefix}_{}_{}", std::process::id(), now))
+    }
+
+    #[test]
+    fn compressed_artifacts_read_back_and_manifest_lists_files() {
+        let dir = unique_tmp_dir("integrity_test");
+        std::fs::create_dir_all(dir.join("analyzers")).unwrap();
+        let contents = "timestamp_utc,equity\n1,100\n2,101\n";
+        for (name, compression) in [
+            ("equity.csv", ArtifactCompression::Gzip),
+            ("trades.csv", ArtifactCompression::Zstd),
+        ] {
+            let path = dir.join(name);
+            std::fs::write(&path, contents).unwrap();
+            compress_artifact(&path, compression).expect("compress");
+            assert!(!path.exists());
+            assert!(artifact_exists(&path));
+
+            let mut decoded = String::new();
+            open_artifact(&path)
+                .expect("open")
+                .read_to_string(&mut decoded)
+                .unwrap();
+            assert_eq!(decoded, contents);
+        }
+        std::fs::write(dir.join("analyzers").join("a.json"), "{}").unwrap();
+        std::fs::write(dir.join("notes.jsonl"), "{}\n").unwrap();
+
+        write_manifest(&dir).expect("manifest");
+        let manifest: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.join("manifest.json")).unwrap())
+                .unwrap();
+        let paths: Vec<&str> = manifest["files"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["path"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            paths,
+            vec!["analyzers/a.json", "equity.csv.gz", "trades.csv.zst"]
+        );
+        assert_eq!(
+            manifest["files"][0]["sha256"].as_str(),
+            Some("44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a")
+        );
+        assert_eq!(manifest["files"][0]["bytes"].as_u64(), Some(2));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
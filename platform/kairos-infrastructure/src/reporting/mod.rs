@@ -1,13 +1,25 @@
 use kairos_domain::entities::metrics::MetricsSummary;
+pub use kairos_domain::entities::run_summary::SummaryMeta;
+use kairos_domain::entities::run_summary::{Reproducibility, RunSummary};
 use kairos_domain::services::audit::AuditEvent;
+use kairos_domain::services::downsample::{downsample_equity, CHART_MAX_POINTS};
 use kairos_domain::value_objects::equity_point::EquityPoint;
 use kairos_domain::value_objects::side::Side;
 use kairos_domain::value_objects::trade::Trade;
-use serde::Serialize;
 use std::fs;
-use std::io::Write;
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::Path;
 
+mod bundle;
+mod integrity;
+pub(crate) mod parquet;
+
+pub use bundle::write_bundle;
+pub use integrity::{
+    artifact_exists, compress_artifact, open_artifact, write_manifest, MANIFEST_FILE_NAME,
+};
+pub use parquet::{write_equity_parquet, write_trades_parquet};
+
 pub fn write_audit_jsonl(path: &Path, events: &[AuditEvent]) -> Result<(), String> {
     let mut file =
         fs::File::create(path).map_err(|err| format!("failed to create logs: {}", err))?;
@@ -21,6 +33,75 @@ pub fn write_audit_jsonl(path: &Path, events: &[AuditEvent]) -> Result<(), Strin
     Ok(())
 }
 
+pub fn append_jsonl(path: &Path, records: &[serde_json::Value]) -> Result<(), String> {
+    let mut buf = Vec::new();
+    for record in records {
+        serde_json::to_writer(&mut buf, record)
+            .map_err(|err| format!("failed to serialize jsonl record: {}", err))?;
+        buf.push(b'\n');
+    }
+    // A single write per batch keeps lines whole for readers tailing the file.
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|err| format!("failed to open {}: {}", path.display(), err))?;
+    file.write_all(&buf)
+        .and_then(|_| file.flush())
+        .map_err(|err| format!("failed to append to {}: {}", path.display(), err))
+}
+
+pub fn write_json_atomic(path: &Path, value: &serde_json::Value) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(value)
+        .map_err(|err| format!("failed to serialize {}: {}", path.display(), err))?;
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp = path.with_file_name(tmp_name);
+    let written = fs::File::create(&tmp).and_then(|mut file| {
+        file.write_all(&json)?;
+        file.sync_all()
+    });
+    written
+        .and_then(|_| fs::rename(&tmp, path))
+        .map_err(|err| format!("failed to write {}: {}", path.display(), err))
+}
+
+pub fn read_json_if_exists(path: &Path) -> Result<Option<serde_json::Value>, String> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(format!("failed to read {}: {}", path.display(), err)),
+    };
+    serde_json::from_slice(&bytes)
+        .map(Some)
+        .map_err(|err| format!("invalid json in {}: {}", path.display(), err))
+}
+
+pub fn read_jsonl_from(path: &Path, offset: u64) -> Result<(Vec<serde_json::Value>, u64), String> {
+    let mut file = fs::File::open(path)
+        .map_err(|err| format!("failed to open {}: {}", path.display(), err))?;
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|err| format!("failed to seek {}: {}", path.display(), err))?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)
+        .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+
+    let complete = match buf.iter().rposition(|b| *b == b'\n') {
+        Some(idx) => idx + 1,
+        None => return Ok((Vec::new(), offset)),
+    };
+    let mut records = Vec::new();
+    for line in buf[..complete].split(|b| *b == b'\n') {
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        let record = serde_json::from_slice(line)
+            .map_err(|err| format!("invalid jsonl line in {}: {}", path.display(), err))?;
+        records.push(record);
+    }
+    Ok((records, offset + complete as u64))
+}
+
 pub fn write_trades_csv(path: &Path, trades: &[Trade]) -> Result<(), String> {
     let mut wtr = csv::Writer::from_path(path)
         .map_err(|err| format!("failed to create trades csv {}: {}", path.display(), err))?;
@@ -44,7 +125,7 @@ pub fn write_trades_csv(path: &Path, trades: &[Trade]) -> Result<(), String> {
         };
         wtr.write_record([
             trade.timestamp.to_string(),
-            trade.symbol.clone(),
+            trade.symbol.to_string(),
             side.to_string(),
             trade.quantity.to_string(),
             trade.price.to_string(),
@@ -89,42 +170,20 @@ pub fn write_equity_csv(path: &Path, points: &[EquityPoint]) -> Result<(), Strin
         .map_err(|err| format!("failed to flush equity csv: {}", err))
 }
 
-#[derive(Debug, Serialize)]
-pub struct SummaryMeta {
-    pub run_id: String,
-    pub symbol: String,
-    pub timeframe: String,
-    pub start: i64,
-    pub end: i64,
-}
-
 pub fn write_summary_json(
     path: &Path,
     summary: &MetricsSummary,
     meta: Option<&SummaryMeta>,
     config_snapshot: Option<&serde_json::Value>,
+    reproducibility: Option<&Reproducibility>,
 ) -> Result<(), String> {
-    let meta_json = meta.map(|meta| {
-        serde_json::json!({
-            "run_id": meta.run_id,
-            "symbol": meta.symbol,
-            "timeframe": meta.timeframe,
-            "start": meta.start,
-            "end": meta.end,
-        })
-    });
-
-    let json = serde_json::json!({
-        "meta": meta_json,
-        "config_snapshot": config_snapshot,
-        "bars_processed": summary.bars_processed,
-        "trades": summary.trades,
-        "win_rate": summary.win_rate,
-        "net_profit": summary.net_profit,
-        "sharpe": summary.sharpe,
-        "max_drawdown": summary.max_drawdown,
-    });
-    let json = serde_json::to_string_pretty(&json)
+    let document = RunSummary::new(
+        summary,
+        meta.cloned(),
+        config_snapshot.cloned(),
+        reproducibility.cloned(),
+    );
+    let json = serde_json::to_string_pretty(&document)
         .map_err(|err| format!("failed to serialize summary: {}", err))?;
     let mut file =
         fs::File::create(path).map_err(|err| format!("failed to create summary: {}", err))?;
@@ -132,27 +191,32 @@ pub fn write_summary_json(
         .map_err(|err| format!("failed to write summary: {}", err))
 }
 
+// Display values for the header of the HTML/Markdown reports; any field the meta lacks reads as
+// "unknown".
+fn meta_labels(meta: Option<&SummaryMeta>) -> (&str, &str, &str, String, String) {
+    fn text(field: Option<&String>) -> &str {
+        field.map(String::as_str).unwrap_or("unknown")
+    }
+    let stamp = |field: Option<i64>| {
+        field
+            .map(|value| value.to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    };
+    (
+        text(meta.and_then(|m| m.run_id.as_ref())),
+        text(meta.and_then(|m| m.symbol.as_ref())),
+        text(meta.and_then(|m| m.timeframe.as_ref())),
+        stamp(meta.and_then(|m| m.start)),
+        stamp(meta.and_then(|m| m.end)),
+    )
+}
+
 pub fn write_summary_html(
     path: &Path,
     summary: &MetricsSummary,
     meta: Option<&SummaryMeta>,
 ) -> Result<(), String> {
-    let (run_id, symbol, timeframe, start, end) = match meta {
-        Some(meta) => (
-            meta.run_id.as_str(),
-            meta.symbol.as_str(),
-            meta.timeframe.as_str(),
-            meta.start.to_string(),
-            meta.end.to_string(),
-        ),
-        None => (
-            "unknown",
-            "unknown",
-            "unknown",
-            "unknown".to_string(),
-            "unknown".to_string(),
-        ),
-    };
+    let (run_id, symbol, timeframe, start, end) = meta_labels(meta);
 
     let html = format!(
         r#"<!DOCTYPE html>
@@ -200,6 +264,45 @@ pub fn write_summary_html(
         .map_err(|err| format!("failed to write html: {}", err))
 }
 
+// The summary.html content as a Markdown table, for pasting into notes or pull requests.
+pub fn write_summary_markdown(
+    path: &Path,
+    summary: &MetricsSummary,
+    meta: Option<&SummaryMeta>,
+) -> Result<(), String> {
+    let (run_id, symbol, timeframe, start, end) = meta_labels(meta);
+
+    let markdown = format!(
+        "# Kairos Alloy Summary
+
+- run_id: `{run_id}`
+- symbol: `{symbol}`
+- timeframe: `{timeframe}`
+- start: `{start}`
+- end: `{end}`
+
+## Metrics
+
+| metric | value |
+| --- | ---: |
+| bars_processed | {} |
+| trades | {} |
+| win_rate | {:.4} |
+| net_profit | {:.4} |
+| sharpe | {:.4} |
+| max_drawdown | {:.4} |
+",
+        summary.bars_processed,
+        summary.trades,
+        summary.win_rate,
+        summary.net_profit,
+        summary.sharpe,
+        summary.max_drawdown,
+    );
+
+    fs::write(path, markdown).map_err(|err| format!("failed to write markdown: {err}"))
+}
+
 pub fn write_dashboard_html(
     path: &Path,
     summary: &MetricsSummary,
@@ -207,24 +310,11 @@ pub fn write_dashboard_html(
     trades: &[Trade],
     equity: &[EquityPoint],
 ) -> Result<(), String> {
-    let (run_id, symbol, timeframe, start, end) = match meta {
-        Some(meta) => (
-            meta.run_id.as_str(),
-            meta.symbol.as_str(),
-            meta.timeframe.as_str(),
-            meta.start.to_string(),
-            meta.end.to_string(),
-        ),
-        None => (
-            "unknown",
-            "unknown",
-            "unknown",
-            "unknown".to_string(),
-            "unknown".to_string(),
-        ),
-    };
+    let (run_id, symbol, timeframe, start, end) = meta_labels(meta);
 
-    let equity_json = serde_json::to_string(equity)
+    let chart_equity = downsample_equity(equity, CHART_MAX_POINTS);
+    let chart_points = format!("{} of {} points", chart_equity.len(), equity.len());
+    let equity_json = serde_json::to_string(&chart_equity)
         .map_err(|err| format!("failed to serialize equity: {err}"))?;
     let trades_json = serde_json::to_string(trades)
         .map_err(|err| format!("failed to serialize trades: {err}"))?;
@@ -259,6 +349,7 @@ pub fn write_dashboard_html(
       <h2>Equity</h2>
       <canvas id="equity"></canvas>
       <p class="muted">bars_processed={bars_processed} trades={trades} net_profit={net_profit:.4} sharpe={sharpe:.4} max_drawdown={max_drawdown:.4}</p>
+      <p class="muted">chart: {chart_points} (LTTB; full resolution in equity.csv)</p>
     </div>
     <div class="card">
       <h2>Trades</h2>
@@ -298,11 +389,15 @@ pub fn write_dashboard_html(
       const values = points.map(p => p.equity);
       const minV = Math.min(...values);
       const maxV = Math.max(...values);
+      const minT = points[0].timestamp;
+      const maxT = points[points.length - 1].timestamp;
       const pad = 20 * window.devicePixelRatio;
       const x0 = pad, y0 = pad, x1 = w - pad, y1 = h - pad;
 
-      function x(i) {{
-        return x0 + (i / (points.length - 1)) * (x1 - x0);
+      // Downsampled points are unevenly spaced, so place them by timestamp rather than index.
+      function x(t) {{
+        if (maxT === minT) return (x0 + x1) / 2;
+        return x0 + ((t - minT) / (maxT - minT)) * (x1 - x0);
       }}
       function y(v) {{
         if (maxV === minV) return (y0 + y1) / 2;
@@ -313,9 +408,9 @@ pub fn write_dashboard_html(
       ctx.strokeStyle = '#2b6cb0';
       ctx.lineWidth = 2 * window.devicePixelRatio;
       ctx.beginPath();
-      ctx.moveTo(x(0), y(points[0].equity));
+      ctx.moveTo(x(points[0].timestamp), y(points[0].equity));
       for (let i = 1; i < points.length; i++) {{
-        ctx.lineTo(x(i), y(points[i].equity));
+        ctx.lineTo(x(points[i].timestamp), y(points[i].equity));
       }}
       ctx.stroke();
     }}
@@ -357,6 +452,285 @@ pub fn write_dashboard_html(
         .map_err(|err| format!("failed to write html: {}", err))
 }
 
+pub fn write_compare_html(path: &Path, comparison: &serde_json::Value) -> Result<(), String> {
+    let run_id = |side: &str| {
+        comparison
+            .get(side)
+            .and_then(|run| run.get("run_id"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown")
+            .to_string()
+    };
+    let (run_a, run_b) = (run_id("a"), run_id("b"));
+    // Keep embedded values (e.g. config strings) from closing the script tag.
+    let comparison_json = serde_json::to_string(comparison)
+        .map_err(|err| format!("failed to serialize comparison: {err}"))?
+        .replace("</", "<\\/");
+
+    let html = format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+  <meta charset="utf-8"/>
+  <title>Kairos Alloy Compare</title>
+  <style>
+    body {{ font-family: ui-sans-serif, system-ui; padding: 24px; }}
+    code {{ background: #f2f2f2; padding: 2px 6px; border-radius: 4px; }}
+    .grid {{ display: grid; grid-template-columns: 1fr 1fr; gap: 16px; align-items: start; }}
+    .card {{ border: 1px solid #ddd; border-radius: 10px; padding: 16px; background: #fff; }}
+    canvas {{ width: 100%; height: 320px; border: 1px solid #eee; border-radius: 8px; }}
+    table {{ border-collapse: collapse; width: 100%; }}
+    th, td {{ border: 1px solid #eee; padding: 8px; font-size: 12px; }}
+    th {{ background: #fafafa; text-align: left; }}
+    .muted {{ color: #666; }}
+    .a {{ color: #2b6cb0; }}
+    .b {{ color: #dd6b20; }}
+  </style>
+</head>
+<body>
+  <h1>Kairos Alloy Compare</h1>
+  <p class="muted">
+    A: <code class="a">{run_a}</code> · B: <code class="b">{run_b}</code>
+  </p>
+
+  <div class="card">
+    <h2>Equity (<span class="a">A</span> vs <span class="b">B</span>)</h2>
+    <canvas id="equity"></canvas>
+  </div>
+
+  <div class="grid" style="margin-top: 16px;">
+    <div class="card">
+      <h2>Metrics</h2>
+      <table id="metrics_table">
+        <thead><tr><th>metric</th><th>A</th><th>B</th><th>B - A</th></tr></thead>
+        <tbody></tbody>
+      </table>
+    </div>
+    <div class="card">
+      <h2>Config diff</h2>
+      <table id="config_table">
+        <thead><tr><th>key</th><th>A</th><th>B</th></tr></thead>
+        <tbody></tbody>
+      </table>
+    </div>
+  </div>
+
+  <div class="card" style="margin-top: 16px;">
+    <h2>Notes</h2>
+    <table id="notes_table">
+      <thead><tr><th>run</th><th>created_at</th><th>note</th></tr></thead>
+      <tbody></tbody>
+    </table>
+  </div>
+
+  <script>
+    const comparison = {comparison_json};
+
+    function drawOverlay(canvas, series) {{
+      const ctx = canvas.getContext('2d');
+      const w = canvas.width = canvas.clientWidth * window.devicePixelRatio;
+      const h = canvas.height = canvas.clientHeight * window.devicePixelRatio;
+      ctx.clearRect(0, 0, w, h);
+
+      const all = series.flatMap(s => s.points);
+      if (all.length < 2) {{
+        ctx.fillStyle = '#666';
+        ctx.fillText('no equity data', 10, 20);
+        return;
+      }}
+
+      const minT = Math.min(...all.map(p => p[0]));
+      const maxT = Math.max(...all.map(p => p[0]));
+      const minV = Math.min(...all.map(p => p[1]));
+      const maxV = Math.max(...all.map(p => p[1]));
+      const pad = 20 * window.devicePixelRatio;
+      const x0 = pad, y0 = pad, x1 = w - pad, y1 = h - pad;
+
+      function x(t) {{
+        if (maxT === minT) return (x0 + x1) / 2;
+        return x0 + ((t - minT) / (maxT - minT)) * (x1 - x0);
+      }}
+      function y(v) {{
+        if (maxV === minV) return (y0 + y1) / 2;
+        const t = (v - minV) / (maxV - minV);
+        return y1 - t * (y1 - y0);
+      }}
+
+      for (const s of series) {{
+        if (s.points.length === 0) continue;
+        ctx.strokeStyle = s.color;
+        ctx.lineWidth = 2 * window.devicePixelRatio;
+        ctx.beginPath();
+        ctx.moveTo(x(s.points[0][0]), y(s.points[0][1]));
+        for (let i = 1; i < s.points.length; i++) {{
+          ctx.lineTo(x(s.points[i][0]), y(s.points[i][1]));
+        }}
+        ctx.stroke();
+      }}
+    }}
+
+    function fmt(v) {{
+      if (v === null || v === undefined) return '<missing>';
+      return typeof v === 'number' ? v.toFixed(4) : String(v);
+    }}
+
+    function renderRows(tableId, rows, cells) {{
+      const tbody = document.querySelector(`#${{tableId}} tbody`);
+      tbody.innerHTML = '';
+      for (const row of rows) {{
+        const tr = document.createElement('tr');
+        for (const value of cells(row)) {{
+          const td = document.createElement('td');
+          td.textContent = value;
+          tr.appendChild(td);
+        }}
+        tbody.appendChild(tr);
+      }}
+    }}
+
+    const series = [
+      {{ color: '#2b6cb0', points: comparison.equity.a }},
+      {{ color: '#dd6b20', points: comparison.equity.b }},
+    ];
+    drawOverlay(document.getElementById('equity'), series);
+    renderRows('metrics_table', comparison.metrics, m => [m.name, fmt(m.a), fmt(m.b), fmt(m.delta)]);
+    renderRows('config_table', comparison.config_diff, d => [d.key, fmt(d.a), fmt(d.b)]);
+    const notes = ['a', 'b'].flatMap(side =>
+      (comparison[side].notes || []).map(n => [side.toUpperCase(), n.created_at, n.note]));
+    renderRows('notes_table', notes, n => n);
+    window.addEventListener('resize', () => drawOverlay(document.getElementById('equity'), series));
+  </script>
+</body>
+</html>"#
+    );
+
+    let mut file =
+        fs::File::create(path).map_err(|err| format!("failed to create html: {}", err))?;
+    file.write_all(html.as_bytes())
+        .map_err(|err| format!("failed to write html: {}", err))
+}
+
+pub fn write_sweep_index_html(path: &Path, index: &serde_json::Value) -> Result<(), String> {
+    let sweep_id = index
+        .get("sweep_id")
+        .and_then(|v| v.as_str())
+        .unwrap_or("unknown")
+        .to_string();
+    // Keep embedded values (e.g. error strings) from closing the script tag.
+    let index_json = serde_json::to_string(index)
+        .map_err(|err| format!("failed to serialize sweep index: {err}"))?
+        .replace("</", "<\\/");
+
+    let html = format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+  <meta charset="utf-8"/>
+  <title>Kairos Alloy Sweep {sweep_id}</title>
+  <style>
+    body {{ font-family: ui-sans-serif, system-ui; padding: 24px; }}
+    code {{ background: #f2f2f2; padding: 2px 6px; border-radius: 4px; }}
+    table {{ border-collapse: collapse; width: 100%; }}
+    th, td {{ border: 1px solid #eee; padding: 6px 8px; font-size: 12px; }}
+    th {{ background: #fafafa; text-align: left; cursor: pointer; user-select: none; }}
+    td.num {{ text-align: right; font-variant-numeric: tabular-nums; }}
+    tr.error td {{ color: #c53030; }}
+    .muted {{ color: #666; }}
+  </style>
+</head>
+<body>
+  <h1>Sweep <code>{sweep_id}</code></h1>
+  <p class="muted" id="meta"></p>
+  <p class="muted">Click a column header to sort (click again to reverse).</p>
+  <table id="runs_table">
+    <thead><tr></tr></thead>
+    <tbody></tbody>
+  </table>
+
+  <script>
+    const index = {index_json};
+    const metricKeys = ['net_profit', 'sharpe', 'max_drawdown', 'win_rate', 'trades', 'bars_processed'];
+    const columns = [
+      {{ key: 'run_id', label: 'run', value: r => r.run_id, link: true }},
+      {{ key: 'split_id', label: 'split', value: r => r.split_id }},
+      {{ key: 'status', label: 'status', value: r => r.status }},
+      ...index.param_paths.map(p => ({{ key: 'param:' + p, label: p, value: r => r.params[p] }})),
+      ...metricKeys.map(m => ({{ key: m, label: m, value: r => r.metrics ? r.metrics[m] : null, num: true }})),
+      {{ key: 'error', label: 'error', value: r => r.error }},
+    ];
+
+    document.getElementById('meta').textContent =
+      `mode=${{index.mode}} · base_config=${{index.base_config}} · runs=${{index.runs.length}}`;
+
+    let sortKey = 'sharpe';
+    let descending = true;
+
+    function fmt(v) {{
+      if (v === null || v === undefined) return '';
+      if (typeof v === 'number') return Number.isInteger(v) ? String(v) : v.toFixed(4);
+      return typeof v === 'object' ? JSON.stringify(v) : String(v);
+    }}
+
+    function compare(a, b) {{
+      // Missing values always sort last, regardless of direction.
+      if (a === null || a === undefined) return (b === null || b === undefined) ? 0 : 1;
+      if (b === null || b === undefined) return -1;
+      const ord = (typeof a === 'number' && typeof b === 'number')
+        ? a - b
+        : String(a).localeCompare(String(b), undefined, {{ numeric: true }});
+      return descending ? -ord : ord;
+    }}
+
+    function render() {{
+      const head = document.querySelector('#runs_table thead tr');
+      head.innerHTML = '';
+      for (const col of columns) {{
+        const th = document.createElement('th');
+        th.textContent = col.label + (col.key === sortKey ? (descending ? ' ▼' : ' ▲') : '');
+        th.addEventListener('click', () => {{
+          descending = col.key === sortKey ? !descending : true;
+          sortKey = col.key;
+          render();
+        }});
+        head.appendChild(th);
+      }}
+
+      const active = columns.find(c => c.key === sortKey) || columns[0];
+      const rows = [...index.runs].sort((a, b) => compare(active.value(a), active.value(b)));
+      const tbody = document.querySelector('#runs_table tbody');
+      tbody.innerHTML = '';
+      for (const run of rows) {{
+        const tr = document.createElement('tr');
+        if (run.status === 'error') tr.className = 'error';
+        for (const col of columns) {{
+          const td = document.createElement('td');
+          if (col.num) td.className = 'num';
+          if (col.link) {{
+            const a = document.createElement('a');
+            a.href = run.href;
+            a.textContent = run.run_id;
+            td.appendChild(a);
+          }} else {{
+            td.textContent = fmt(col.value(run));
+          }}
+          tr.appendChild(td);
+        }}
+        tbody.appendChild(tr);
+      }}
+    }}
+
+    render();
+  </script>
+</body>
+</html>"#
+    );
+
+    let mut file =
+        fs::File::create(path).map_err(|err| format!("failed to create html: {}", err))?;
+    file.write_all(html.as_bytes())
+        .map_err(|err| format!("failed to write html: {}", err))
+}
+
 #[derive(Debug, Clone, serde::Deserialize)]
 struct TradeRecord {
     timestamp_utc: i64,
@@ -371,8 +745,7 @@ struct TradeRecord {
 }
 
 pub fn read_trades_csv(path: &Path) -> Result<Vec<Trade>, String> {
-    let mut rdr = csv::Reader::from_path(path)
-        .map_err(|err| format!("failed to open trades csv {}: {}", path.display(), err))?;
+    let mut rdr = csv::Reader::from_reader(open_artifact(path)?);
     let mut trades = Vec::new();
     for result in rdr.deserialize::<TradeRecord>() {
         let record = result.map_err(|err| format!("failed to parse trade record: {}", err))?;
@@ -383,7 +756,7 @@ pub fn read_trades_csv(path: &Path) -> Result<Vec<Trade>, String> {
         };
         trades.push(Trade {
             timestamp: record.timestamp_utc,
-            symbol: record.symbol,
+            symbol: record.symbol.into(),
             side,
             quantity: record.qty,
             price: record.price,
@@ -407,8 +780,7 @@ struct EquityRecord {
 }
 
 pub fn read_equity_csv(path: &Path) -> Result<Vec<EquityPoint>, String> {
-    let mut rdr = csv::Reader::from_path(path)
-        .map_err(|err| format!("failed to open equity csv {}: {}", path.display(), err))?;
+    let mut rdr = csv::Reader::from_reader(open_artifact(path)?);
     let mut points = Vec::new();
     for result in rdr.deserialize::<EquityRecord>() {
         let record = result.map_err(|err| format!("failed to parse equity record: {}", err))?;
@@ -441,7 +813,7 @@ pub fn write_logs_jsonl(
             run_id: run_id.to_string(),
             timestamp: trade.timestamp,
             stage: "trade".to_string(),
-            symbol: Some(trade.symbol.clone()),
+            symbol: Some(trade.symbol.to_string()),
             action: format!("{:?}", trade.side),
             error: None,
             details: serde_json::json!({
@@ -477,13 +849,16 @@ pub fn write_logs_jsonl(
 #[cfg(test)]
 mod tests {
     use super::{
-        read_trades_csv, write_equity_csv, write_logs_jsonl, write_summary_json, write_trades_csv,
+        append_jsonl, read_json_if_exists, read_jsonl_from, read_trades_csv, write_dashboard_html,
+        write_equity_csv, write_json_atomic, write_logs_jsonl, write_summary_json,
+        write_summary_markdown, write_trades_csv,
     };
     use kairos_domain::entities::metrics::MetricsSummary;
     use kairos_domain::value_objects::equity_point::EquityPoint;
     use kairos_domain::value_objects::side::Side;
     use kairos_domain::value_objects::trade::Trade;
     use std::fs;
+    use std::io::Write;
     use std::path::PathBuf;
     use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -495,6 +870,67 @@ mod tests {
         std::env::temp_dir().join(format!("kairos_{prefix}_{}_{}", std::process::id(), now))
     }
 
+    #[test]
+    fn write_json_atomic_replaces_previous_contents() {
+        let dir = unique_tmp_dir("write_json_atomic_test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.json");
+
+        assert_eq!(read_json_if_exists(&path).expect("read"), None);
+        write_json_atomic(&path, &serde_json::json!({"bar_index": 1})).expect("write");
+        write_json_atomic(&path, &serde_json::json!({"bar_index": 2})).expect("write");
+        let value = read_json_if_exists(&path).expect("read").expect("exists");
+        assert_eq!(value["bar_index"], 2);
+        assert!(!dir.join("session.json.tmp").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn append_jsonl_keeps_previous_lines() {
+        let dir = unique_tmp_dir("append_jsonl_test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("live.jsonl");
+
+        append_jsonl(&path, &[serde_json::json!({"type": "start"})]).expect("append");
+        let (first, offset) = read_jsonl_from(&path, 0).expect("read");
+        assert_eq!(first.len(), 1);
+        append_jsonl(
+            &path,
+            &[
+                serde_json::json!({"type": "fill", "qty": 1.0}),
+                serde_json::json!({"type": "equity", "equity": 100.0}),
+            ],
+        )
+        .expect("append");
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let kinds: Vec<String> = contents
+            .lines()
+            .map(|line| {
+                let value: serde_json::Value = serde_json::from_str(line).unwrap();
+                value["type"].as_str().unwrap().to_string()
+            })
+            .collect();
+        assert_eq!(kinds, vec!["start", "fill", "equity"]);
+
+        // Only new complete lines are returned; a partial trailing line waits for the next read.
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"type\": \"eq")
+            .unwrap();
+        let (next, next_offset) = read_jsonl_from(&path, offset).expect("read");
+        assert_eq!(next.len(), 2);
+        assert_eq!(next[1]["type"], "equity");
+        let (none, same_offset) = read_jsonl_from(&path, next_offset).expect("read");
+        assert!(none.is_empty());
+        assert_eq!(same_offset, next_offset);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn writes_report_files() {
         let dir = unique_tmp_dir("report_test");
@@ -502,7 +938,7 @@ mod tests {
 
         let trades = vec![Trade {
             timestamp: 1,
-            symbol: "BTCUSD".to_string(),
+            symbol: "BTCUSD".into(),
             side: Side::Buy,
             quantity: 1.0,
             price: 100.0,
@@ -530,15 +966,58 @@ mod tests {
 
         write_trades_csv(dir.join("trades.csv").as_path(), &trades).expect("trades");
         write_equity_csv(dir.join("equity.csv").as_path(), &equity).expect("equity");
-        write_summary_json(dir.join("summary.json").as_path(), &summary, None, None)
-            .expect("summary");
+        write_summary_json(
+            dir.join("summary.json").as_path(),
+            &summary,
+            None,
+            None,
+            None,
+        )
+        .expect("summary");
         write_logs_jsonl(dir.join("logs.jsonl").as_path(), "run1", &trades, &summary)
             .expect("logs");
+        write_summary_markdown(dir.join("summary.md").as_path(), &summary, None).expect("markdown");
 
         assert!(dir.join("trades.csv").exists());
         assert!(dir.join("equity.csv").exists());
         assert!(dir.join("summary.json").exists());
         assert!(dir.join("logs.jsonl").exists());
+        let markdown = fs::read_to_string(dir.join("summary.md")).expect("read markdown");
+        assert!(markdown.contains("- run_id: `unknown`"));
+        assert!(markdown.contains("| trades | 1 |"));
+    }
+
+    #[test]
+    fn dashboard_embeds_downsampled_equity() {
+        let dir = unique_tmp_dir("report_dashboard_downsample");
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("dashboard.html");
+
+        let equity: Vec<EquityPoint> = (0..20_000)
+            .map(|i| EquityPoint {
+                timestamp: i,
+                equity: 100.0 + (i % 7) as f64,
+                cash: 0.0,
+                position_qty: 0.0,
+                unrealized_pnl: 0.0,
+                realized_pnl: 0.0,
+            })
+            .collect();
+        let summary = MetricsSummary {
+            bars_processed: equity.len(),
+            trades: 0,
+            win_rate: 0.0,
+            net_profit: 0.0,
+            sharpe: 0.0,
+            max_drawdown: 0.0,
+        };
+
+        write_dashboard_html(path.as_path(), &summary, None, &[], &equity).expect("dashboard");
+        let html = fs::read_to_string(&path).unwrap();
+        assert!(html.contains("chart: 5000 of 20000 points"));
+        assert_eq!(html.matches("\"timestamp\":").count(), 5_000);
+
+        let _ = fs::remove_dir_all(&dir);
     }
 
     #[test]
@@ -549,7 +1028,7 @@ mod tests {
 
         let trades = vec![Trade {
             timestamp: 1704067200,
-            symbol: "BTCUSD".to_string(),
+            symbol: "BTCUSD".into(),
             side: Side::Buy,
             quantity: 1.0,
             price: 100.0,
@@ -0,0 +1,236 @@
+use kairos_domain::value_objects::equity_point::EquityPoint;
+use kairos_domain::value_objects::side::Side;
+use kairos_domain::value_objects::trade::Trade;
+use parquet::basic::Compression;
+use parquet::data_type::{ByteArray, ByteArrayType, DoubleType, Int64Type};
+use parquet::file::metadata::KeyValue;
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+const TRADES_SCHEMA: &str = "
+message trades {
+    REQUIRED INT64 timestamp_utc;
+    REQUIRED BYTE_ARRAY symbol (UTF8);
+    REQUIRED BYTE_ARRAY side (UTF8);
+    REQUIRED DOUBLE qty;
+    REQUIRED DOUBLE price;
+    REQUIRED DOUBLE fee;
+    REQUIRED DOUBLE slippage;
+    REQUIRED BYTE_ARRAY strategy_id (UTF8);
+    REQUIRED BYTE_ARRAY reason (UTF8);
+}
+";
+
+const EQUITY_SCHEMA: &str = "
+message equity {
+    REQUIRED INT64 timestamp_utc;
+    REQUIRED DOUBLE equity;
+    REQUIRED DOUBLE cash;
+    REQUIRED DOUBLE position_qty;
+    REQUIRED DOUBLE unrealized_pnl;
+    REQUIRED DOUBLE realized_pnl;
+}
+";
+
+pub(crate) enum Column {
+    Int64(Vec<i64>),
+    Double(Vec<f64>),
+    Utf8(Vec<ByteArray>),
+}
+
+// Column names/order mirror the CSV artifacts so both formats can be used interchangeably.
+pub fn write_trades_parquet(path: &Path, trades: &[Trade]) -> Result<(), String> {
+    let columns = vec![
+        Column::Int64(trades.iter().map(|t| t.timestamp).collect()),
+        Column::Utf8(trades.iter().map(|t| t.symbol.as_str().into()).collect()),
+        Column::Utf8(
+            trades
+                .iter()
+                .map(|t| match t.side {
+                    Side::Buy => "BUY".into(),
+                    Side::Sell => "SELL".into(),
+                })
+                .collect(),
+        ),
+        Column::Double(trades.iter().map(|t| t.quantity).collect()),
+        Column::Double(trades.iter().map(|t| t.price).collect()),
+        Column::Double(trades.iter().map(|t| t.fee).collect()),
+        Column::Double(trades.iter().map(|t| t.slippage).collect()),
+        Column::Utf8(
+            trades
+                .iter()
+                .map(|t| t.strategy_id.as_str().into())
+                .collect(),
+        ),
+        Column::Utf8(trades.iter().map(|t| t.reason.as_str().into()).collect()),
+    ];
+    write_columns(path, "trades", TRADES_SCHEMA, columns, Vec::new())
+}
+
+pub fn write_equity_parquet(path: &Path, points: &[EquityPoint]) -> Result<(), String> {
+    let columns = vec![
+        Column::Int64(points.iter().map(|p| p.timestamp).collect()),
+        Column::Double(points.iter().map(|p| p.equity).collect()),
+        Column::Double(points.iter().map(|p| p.cash).collect()),
+        Column::Double(points.iter().map(|p| p.position_qty).collect()),
+        Column::Double(points.iter().map(|p| p.unrealized_pnl).collect()),
+        Column::Double(points.iter().map(|p| p.realized_pnl).collect()),
+    ];
+    write_columns(path, "equity", EQUITY_SCHEMA, columns, Vec::new())
+}
+
+pub(crate) fn write_columns(
+    path: &Path,
+    kind: &str,
+    schema: &str,
+    columns: Vec<Column>,
+    metadata: Vec<KeyValue>,
+) -> Result<(), String> {
+    let schema = Arc::new(
+        parse_message_type(schema)
+            .map_err(|err| format!("invalid {kind} parquet schema: {err}"))?,
+    );
+    let props = Arc::new(
+        WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .set_key_value_metadata((!metadata.is_empty()).then_some(metadata))
+            .build(),
+    );
+    let file = File::create(path)
+        .map_err(|err| format!("failed to create {kind} parquet {}: {err}", path.display()))?;
+    let mut writer = SerializedFileWriter::new(file, schema, props)
+        .map_err(|err| format!("failed to open {kind} parquet writer: {err}"))?;
+    let mut row_group = writer
+        .next_row_group()
+        .map_err(|err| format!("failed to start {kind} parquet row group: {err}"))?;
+
+    for column in columns {
+        let mut col_writer = row_group
+            .next_column()
+            .map_err(|err| format!("failed to open {kind} parquet column: {err}"))?
+            .ok_or_else(|| format!("{kind} parquet schema has fewer columns than data"))?;
+        let written = match &column {
+            Column::Int64(values) => col_writer
+                .typed::<Int64Type>()
+                .write_batch(values, None, None),
+            Column::Double(values) => col_writer
+                .typed::<DoubleType>()
+                .write_batch(values, None, None),
+            Column::Utf8(values) => col_writer
+                .typed::<ByteArrayType>()
+                .write_batch(values, None, None),
+        };
+        written.map_err(|err| format!("failed to write {kind} parquet column: {err}"))?;
+        col_writer
+            .close()
+            .map_err(|err| format!("failed to close {kind} parquet column: {err}"))?;
+    }
+
+    row_group
+        .close()
+        .map_err(|err| format!("failed to close {kind} parquet row group: {err}"))?;
+    writer.close().map_err(|err| {
+        format!(
+            "failed to finalize {kind} parquet {}: {err}",
+            path.display()
+        )
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_equity_parquet, write_trades_parquet};
+    use kairos_domain::value_objects::equity_point::EquityPoint;
+    use kairos_domain::value_objects::side::Side;
+    use kairos_domain::value_objects::trade::Trade;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use std::fs::{self, File};
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn unique_tmp_dir(prefix: &str) -> PathBuf {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        std::env::temp_dir().join(format!("kairos_{prefix}_{}_{}", std::process::id(), now))
+    }
+
+    #[test]
+    fn writes_trades_and_equity_parquet_with_all_rows() {
+        let dir = unique_tmp_dir("parquet_test");
+        let _ = fs::create_dir_all(&dir);
+        let trades = vec![
+            Trade {
+                timestamp: 1,
+                symbol: "BTCUSD".into(),
+                side: Side::Buy,
+                quantity: 1.0,
+                price: 100.0,
+                fee: 0.1,
+                slippage: 0.0,
+                strategy_id: "buy_and_hold".to_string(),
+                reason: "signal".to_string(),
+            },
+            Trade {
+                timestamp: 2,
+                symbol: "BTCUSD".into(),
+                side: Side::Sell,
+                quantity: 1.0,
+                price: 110.0,
+                fee: 0.1,
+                slippage: 0.0,
+                strategy_id: "buy_and_hold".to_string(),
+                reason: "signal".to_string(),
+            },
+        ];
+        let equity = vec![
+            EquityPoint {
+                timestamp: 1,
+                equity: 1000.0,
+                cash: 900.0,
+                position_qty: 1.0,
+                unrealized_pnl: 0.0,
+                realized_pnl: 0.0,
+            },
+            EquityPoint {
+                timestamp: 2,
+                equity: 1010.0,
+                cash: 1010.0,
+                position_qty: 0.0,
+                unrealized_pnl: 0.0,
+                realized_pnl: 10.0,
+            },
+            EquityPoint {
+                timestamp: 3,
+                equity: 1010.0,
+                cash: 1010.0,
+                position_qty: 0.0,
+                unrealized_pnl: 0.0,
+                realized_pnl: 10.0,
+            },
+        ];
+
+        let trades_path = dir.join("trades.parquet");
+        let equity_path = dir.join("equity.parquet");
+        write_trades_parquet(&trades_path, &trades).expect("write trades parquet");
+        write_equity_parquet(&equity_path, &equity).expect("write equity parquet");
+
+        let reader = SerializedFileReader::new(File::open(&trades_path).unwrap()).unwrap();
+        let meta = reader.metadata().file_metadata();
+        assert_eq!(meta.num_rows(), 2);
+        assert_eq!(meta.schema_descr().num_columns(), 9);
+
+        let reader = SerializedFileReader::new(File::open(&equity_path).unwrap()).unwrap();
+        let meta = reader.metadata().file_metadata();
+        assert_eq!(meta.num_rows(), 3);
+        assert_eq!(meta.schema_descr().column(1).name(), "equity");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
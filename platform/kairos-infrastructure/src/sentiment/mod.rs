@@ -1,4 +1,5 @@
 use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use kairos_domain::repositories::sentiment::{SentimentFormat, SentimentQuery, SentimentSource};
 use kairos_domain::services::sentiment::{MissingValuePolicy, SentimentPoint, SentimentReport};
 use serde::Deserialize;
 use std::collections::BTreeMap;
@@ -21,122 +22,137 @@ fn policy_label(policy: MissingValuePolicy) -> &'static str {
 impl kairos_domain::repositories::sentiment::SentimentRepository for FilesystemSentimentRepository {
     fn load_sentiment(
         &self,
-        query: &kairos_domain::repositories::sentiment::SentimentQuery,
+        query: &SentimentQuery,
     ) -> Result<(Vec<SentimentPoint>, SentimentReport), String> {
-        let format_label = match query.format {
-            kairos_domain::repositories::sentiment::SentimentFormat::Csv => "csv",
-            kairos_domain::repositories::sentiment::SentimentFormat::Json => "json",
+        let SentimentSource::File { path, format } = &query.source else {
+            return Err(format!(
+                "sentiment source {} needs a database-backed sentiment repository",
+                query.source.describe()
+            ));
         };
-        let policy_label = policy_label(query.missing_policy);
-        let path_hint = query
-            .path
+        let format_label = match format {
+            SentimentFormat::Csv => "csv",
+            SentimentFormat::Json => "json",
+        };
+        let path_hint = path
             .file_name()
             .and_then(|name| name.to_str())
             .unwrap_or("<sentiment>");
+        load_instrumented(
+            format_label,
+            query.missing_policy,
+            path_hint,
+            || match format {
+                SentimentFormat::Csv => load_csv_with_policy(path, query.missing_policy),
+                SentimentFormat::Json => load_json_with_policy(path, query.missing_policy),
+            },
+        )
+    }
+}
 
-        let start = Instant::now();
-        let span = tracing::info_span!(
-            "infra.sentiment.load",
-            format = format_label,
-            missing_policy = policy_label,
-            path = path_hint
-        );
-        let _enter = span.enter();
-
-        let result = match query.format {
-            kairos_domain::repositories::sentiment::SentimentFormat::Csv => {
-                load_csv_with_policy(query.path.as_path(), query.missing_policy)
-            }
-            kairos_domain::repositories::sentiment::SentimentFormat::Json => {
-                load_json_with_policy(query.path.as_path(), query.missing_policy)
-            }
-        };
-
-        match &result {
-            Ok((points, report)) => {
-                metrics::counter!("kairos.infra.sentiment.load.calls_total", "result" => "ok")
-                    .increment(1);
-                metrics::counter!(
-                    "kairos.infra.sentiment.points_loaded_total",
-                    "format" => format_label,
-                    "policy" => policy_label
-                )
-                .increment(points.len() as u64);
-                metrics::histogram!(
-                    "kairos.infra.sentiment.load_ms",
-                    "format" => format_label,
-                    "policy" => policy_label,
-                    "result" => "ok"
-                )
-                .record(start.elapsed().as_millis() as f64);
-                metrics::gauge!(
-                    "kairos.infra.sentiment.points_loaded",
-                    "format" => format_label,
-                    "policy" => policy_label
-                )
-                .set(points.len() as f64);
-                metrics::gauge!(
-                    "kairos.infra.sentiment.missing_values",
-                    "format" => format_label,
-                    "policy" => policy_label
-                )
-                .set(report.missing_values as f64);
-                metrics::gauge!(
-                    "kairos.infra.sentiment.invalid_values",
-                    "format" => format_label,
-                    "policy" => policy_label
-                )
-                .set(report.invalid_values as f64);
-                metrics::gauge!(
-                    "kairos.infra.sentiment.duplicates",
-                    "format" => format_label,
-                    "policy" => policy_label
-                )
-                .set(report.duplicates as f64);
-                metrics::gauge!(
-                    "kairos.infra.sentiment.out_of_order",
-                    "format" => format_label,
-                    "policy" => policy_label
-                )
-                .set(report.out_of_order as f64);
-                metrics::gauge!(
-                    "kairos.infra.sentiment.dropped_rows",
-                    "format" => format_label,
-                    "policy" => policy_label
-                )
-                .set(report.dropped_rows as f64);
-                tracing::debug!(
-                    points = points.len(),
-                    missing_values = report.missing_values,
-                    invalid_values = report.invalid_values,
-                    duplicates = report.duplicates,
-                    out_of_order = report.out_of_order,
-                    dropped_rows = report.dropped_rows,
-                    "loaded sentiment"
-                );
-            }
-            Err(err) => {
-                metrics::counter!("kairos.infra.sentiment.load.calls_total", "result" => "err")
-                    .increment(1);
-                metrics::histogram!(
-                    "kairos.infra.sentiment.load_ms",
-                    "format" => format_label,
-                    "policy" => policy_label,
-                    "result" => "err"
-                )
-                .record(start.elapsed().as_millis() as f64);
-                metrics::counter!(
-                    "kairos.infra.sentiment.load.errors_total",
-                    "format" => format_label,
-                    "policy" => policy_label
-                )
+// Span, timing and quality metrics around one sentiment load, labelled by format and policy.
+pub(crate) fn load_instrumented(
+    format_label: &'static str,
+    policy: MissingValuePolicy,
+    path_hint: &str,
+    load: impl FnOnce() -> Result<(Vec<SentimentPoint>, SentimentReport), String>,
+) -> Result<(Vec<SentimentPoint>, SentimentReport), String> {
+    let policy_label = policy_label(policy);
+    let start = Instant::now();
+    let span = tracing::info_span!(
+        "infra.sentiment.load",
+        format = format_label,
+        missing_policy = policy_label,
+        path = path_hint
+    );
+    let _enter = span.enter();
+
+    let result = load();
+
+    match &result {
+        Ok((points, report)) => {
+            metrics::counter!("kairos.infra.sentiment.load.calls_total", "result" => "ok")
                 .increment(1);
-                tracing::warn!(error = %err, "failed to load sentiment");
-            }
+            metrics::counter!(
+                "kairos.infra.sentiment.points_loaded_total",
+                "format" => format_label,
+                "policy" => policy_label
+            )
+            .increment(points.len() as u64);
+            metrics::histogram!(
+                "kairos.infra.sentiment.load_ms",
+                "format" => format_label,
+                "policy" => policy_label,
+                "result" => "ok"
+            )
+            .record(start.elapsed().as_millis() as f64);
+            metrics::gauge!(
+                "kairos.infra.sentiment.points_loaded",
+                "format" => format_label,
+                "policy" => policy_label
+            )
+            .set(points.len() as f64);
+            metrics::gauge!(
+                "kairos.infra.sentiment.missing_values",
+                "format" => format_label,
+                "policy" => policy_label
+            )
+            .set(report.missing_values as f64);
+            metrics::gauge!(
+                "kairos.infra.sentiment.invalid_values",
+                "format" => format_label,
+                "policy" => policy_label
+            )
+            .set(report.invalid_values as f64);
+            metrics::gauge!(
+                "kairos.infra.sentiment.duplicates",
+                "format" => format_label,
+                "policy" => policy_label
+            )
+            .set(report.duplicates as f64);
+            metrics::gauge!(
+                "kairos.infra.sentiment.out_of_order",
+                "format" => format_label,
+                "policy" => policy_label
+            )
+            .set(report.out_of_order as f64);
+            metrics::gauge!(
+                "kairos.infra.sentiment.dropped_rows",
+                "format" => format_label,
+                "policy" => policy_label
+            )
+            .set(report.dropped_rows as f64);
+            tracing::debug!(
+                points = points.len(),
+                missing_values = report.missing_values,
+                invalid_values = report.invalid_values,
+                duplicates = report.duplicates,
+                out_of_order = report.out_of_order,
+                dropped_rows = report.dropped_rows,
+                "loaded sentiment"
+            );
+        }
+        Err(err) => {
+            metrics::counter!("kairos.infra.sentiment.load.calls_total", "result" => "err")
+                .increment(1);
+            metrics::histogram!(
+                "kairos.infra.sentiment.load_ms",
+                "format" => format_label,
+                "policy" => policy_label,
+                "result" => "err"
+            )
+            .record(start.elapsed().as_millis() as f64);
+            metrics::counter!(
+                "kairos.infra.sentiment.load.errors_total",
+                "format" => format_label,
+                "policy" => policy_label
+            )
+            .increment(1);
+            tracing::warn!(error = %err, "failed to load sentiment");
         }
-
-        result
     }
+
+    result
 }
 
 #[derive(Debug, Deserialize)]
@@ -263,6 +279,18 @@ pub fn load_csv_with_policy(
         }
     }
 
+    let points = resolve_rows(raw_by_ts, schema_len, policy, "csv", &mut report)?;
+    Ok((points, report))
+}
+
+// Applies the missing-value policy to rows keyed by timestamp (one slot per schema column).
+pub(crate) fn resolve_rows(
+    raw_by_ts: BTreeMap<i64, Vec<Option<f64>>>,
+    schema_len: usize,
+    policy: MissingValuePolicy,
+    format_label: &'static str,
+    report: &mut SentimentReport,
+) -> Result<Vec<SentimentPoint>, String> {
     let mut points = Vec::with_capacity(raw_by_ts.len());
     let mut last_values: Vec<Option<f64>> = vec![None; schema_len];
     for (timestamp, values) in raw_by_ts {
@@ -281,8 +309,8 @@ pub fn load_csv_with_policy(
                     MissingValuePolicy::Error => {
                         metrics::counter!(
                             "kairos.infra.sentiment.load.errors_total",
-                            "format" => "csv",
-                            "policy" => policy_label_value,
+                            "format" => format_label,
+                            "policy" => policy_label(policy),
                             "stage" => "missing_value"
                         )
                         .increment(1);
@@ -301,7 +329,7 @@ pub fn load_csv_with_policy(
         });
     }
 
-    Ok((points, report))
+    Ok(points)
 }
 
 pub fn load_json(path: &Path) -> Result<(Vec<SentimentPoint>, SentimentReport), String> {
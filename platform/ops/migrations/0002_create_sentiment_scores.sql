@@ -0,0 +1,9 @@
+-- Sentiment read by `paths.sentiment_source = "db"`: every DOUBLE PRECISION column besides the
+-- key is loaded as one sentiment value, so add a column per feature.
+CREATE TABLE IF NOT EXISTS sentiment_scores (
+    symbol TEXT NOT NULL,
+    timestamp_utc TIMESTAMPTZ NOT NULL,
+    score DOUBLE PRECISION,
+    ingested_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (symbol, timestamp_utc)
+);
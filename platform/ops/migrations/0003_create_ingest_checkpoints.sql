@@ -0,0 +1,11 @@
+-- The end of the last window an exchange ingest upserted per series, so `--resume` continues a
+-- broken backfill from there instead of from `--start`.
+CREATE TABLE IF NOT EXISTS ingest_checkpoints (
+    exchange TEXT NOT NULL,
+    market TEXT NOT NULL,
+    symbol TEXT NOT NULL,
+    timeframe TEXT NOT NULL,
+    last_window_end TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (exchange, market, symbol, timeframe)
+);